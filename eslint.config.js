@@ -7,7 +7,12 @@ import prettier from "eslint-config-prettier"
 
 export default tseslint.config(
   {
-    ignores: ["dist", "src-tauri/target", "node_modules"],
+    ignores: [
+      "dist",
+      "src-tauri/target",
+      "node_modules",
+      "src/shared/types/bindings.ts",
+    ],
   },
   {
     files: ["**/*.{ts,tsx}"],
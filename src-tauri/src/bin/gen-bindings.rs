@@ -0,0 +1,37 @@
+//! `gen-bindings` — writes the frontend's generated TypeScript types
+//! (`src/shared/types/bindings.ts`) from the Rust wire types. See
+//! `bindings` in the library for what gets exported.
+//!
+//! Usage:
+//!   pnpm bindings                        # regenerate
+//!   cargo run --bin gen-bindings -- --check   # fail if stale (CI)
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let check = env::args().skip(1).any(|a| a == "--check");
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../src/shared/types/bindings.ts");
+    let generated = quantum_kapital_lib::typescript_bindings();
+
+    if check {
+        let current = fs::read_to_string(&path).unwrap_or_default();
+        if current == generated {
+            return ExitCode::SUCCESS;
+        }
+        eprintln!("{} is stale; run `pnpm bindings`", path.display());
+        return ExitCode::from(1);
+    }
+    match fs::write(&path, generated) {
+        Ok(()) => {
+            println!("wrote {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: write {}: {e}", path.display());
+            ExitCode::from(1)
+        }
+    }
+}
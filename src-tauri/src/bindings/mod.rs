@@ -0,0 +1,92 @@
+//! TypeScript declarations for the frontend, generated from the Rust
+//! wire types through their `JsonSchema` derives. `pnpm bindings` (the
+//! `gen-bindings` binary) writes them to `src/shared/types/bindings.ts`
+//! so the frontend stops mirroring these types by hand.
+//!
+//! Exported today: every `AppEvent` variant, `CommandError` and every
+//! `ibkr::types` type, plus whatever they reference. A command payload
+//! joins by listing its type in [`typescript`]; it needs `JsonSchema`
+//! beside its serde derives, and a hand-written serde impl needs a
+//! hand-written schema to match (see `StrategyTag`).
+//!
+//! Shapes follow serialization: an `Option` field without
+//! `skip_serializing_if` is always present, possibly `null`.
+
+use schemars::generate::SchemaSettings;
+
+use crate::events::AppEvent;
+use crate::ibkr::types::*;
+use crate::middleware::validation::CommandError;
+
+mod ts;
+
+const HEADER: &str = "// Generated by `pnpm bindings` from the Rust types \
+                      (src-tauri/src/bindings). Do not edit.\n";
+
+macro_rules! export {
+    ($generator:ident; $($ty:ty),* $(,)?) => {
+        $( $generator.subschema_for::<$ty>(); )*
+    };
+}
+
+pub fn typescript() -> String {
+    let mut generator = SchemaSettings::draft2020_12()
+        .for_serialize()
+        .into_generator();
+    export!(generator;
+        AppEvent,
+        CommandError,
+        // account
+        AccountSummary, AccountInfo,
+        // combo
+        ComboLeg, ComboOrderRequest, ComboKind,
+        // connection
+        MarketDataType, ConnectionConfig, ConnectionStatus, DataTier,
+        // fundamentals
+        FinancialProjection, CagrMetrics, YearlyProjection, ProjectionResults,
+        ExpectedValue, ProjectionResultsWithFundamentals,
+        ScenarioProjectionsWithFundamentals, ScenarioProjections, ScenarioCagr,
+        HistoricalFinancial, AnalystEstimate, FundamentalData, FxConversion,
+        GrowthAnalysis, YearGrowth, HistoricalCagr, OverriddenField,
+        DataQualityWarning, DataQualityCode, DataQualitySeverity, AnalystEstimates,
+        CurrentMetrics, ShortInterest, ProjectionAssumptions,
+        // historical
+        HistoricalDataRequest, BarSize, WhatToShow, HistoricalBar,
+        // market data
+        MarketDataSnapshot, OptionGreeks, Quote,
+        // news
+        NewsItem, TickerSentiment, NewsTone, NewsVerdict,
+        // options
+        OptionRight, OptionContract, OptionChain,
+        // orders
+        ExecutionSide, IbkrExecution, OrderRequest, AlgoStrategy, AdaptivePriority,
+        AlgoParams, StartAt, SizeRules, OrderAction, OrderType, BracketRequest,
+        BracketReceipt, ModifyStopRequest, SentOrder, OrderAuditEvent, OpenOrder,
+        // positions
+        Position, ContractDetails, ContractRoute, SecurityType,
+        // scanner
+        ScannerSubscription, ScannerData,
+        // tracker
+        TrackerSource, TrackerStatus, TrackedTicker, TickerPrimingStepStatus,
+        TickerPrimingOutcome, SetupStatus, AlertKind, Alert, Setup, StrategyTag,
+    );
+    let defs = generator.take_definitions(true);
+    format!("{HEADER}\n{}", ts::declarations(&defs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports_events_and_ibkr_types() {
+        let out = typescript();
+        assert!(out.starts_with(HEADER));
+        assert!(out.contains("export type AppEvent = "));
+        assert!(out.contains("  type: \"PositionLevelHit\"\n"));
+        assert!(out.contains("export interface Position {\n"));
+        // Referenced from `Setup`, outside `ibkr::types`.
+        assert!(out.contains("export interface Sizing {\n"));
+        assert!(out.contains("export type StrategyTag = string\n"));
+    }
+}
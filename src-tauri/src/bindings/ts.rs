@@ -0,0 +1,241 @@
+//! JSON Schema → TypeScript, for the subset `schemars` emits for serde
+//! types: objects, string enums, tagged unions, `Option`, sequences,
+//! maps, tuples and `$ref`s to `$defs`.
+
+use serde_json::{Map, Value};
+
+/// One `export type` / `export interface` per definition, by name.
+pub fn declarations(defs: &Map<String, Value>) -> String {
+    let mut names: Vec<&String> = defs.keys().collect();
+    names.sort();
+    let mut out = String::new();
+    for name in names {
+        let schema = &defs[name];
+        out.push_str(&doc(schema, ""));
+        match interface_body(schema, "") {
+            Some(body) => out.push_str(&format!("export interface {name} {body}\n\n")),
+            None => out.push_str(&format!("export type {name} = {}\n\n", ty(schema, ""))),
+        }
+    }
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+/// `{ ... }` when the schema is a plain object with named properties.
+fn interface_body(schema: &Value, indent: &str) -> Option<String> {
+    let obj = schema.as_object()?;
+    if obj.contains_key("oneOf") || obj.contains_key("anyOf") || obj.contains_key("allOf") {
+        return None;
+    }
+    if type_names(schema) != ["object"] || !obj.contains_key("properties") {
+        return None;
+    }
+    Some(object(schema, indent))
+}
+
+fn ty(schema: &Value, indent: &str) -> String {
+    let obj = match schema {
+        Value::Bool(true) => return "unknown".to_string(),
+        Value::Bool(false) => return "never".to_string(),
+        Value::Object(obj) => obj,
+        _ => return "unknown".to_string(),
+    };
+    if let Some(r) = obj.get("$ref").and_then(Value::as_str) {
+        return r.rsplit('/').next().unwrap_or(r).to_string();
+    }
+    if let Some(c) = obj.get("const") {
+        return c.to_string();
+    }
+    if let Some(values) = obj.get("enum").and_then(Value::as_array) {
+        return union(values.iter().map(Value::to_string).collect());
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(variants) = obj.get(key).and_then(Value::as_array) {
+            let alts = union(variants.iter().map(|v| ty(v, indent)).collect());
+            // Flattened fields beside a union.
+            if obj.contains_key("properties") {
+                return format!("{} & ({alts})", object(schema, indent));
+            }
+            return alts;
+        }
+    }
+    if let Some(parts) = obj.get("allOf").and_then(Value::as_array) {
+        return parts
+            .iter()
+            .map(|p| ty(p, indent))
+            .collect::<Vec<_>>()
+            .join(" & ");
+    }
+    let names = type_names(schema);
+    if names.is_empty() {
+        return "unknown".to_string();
+    }
+    union(
+        names
+            .iter()
+            .map(|t| match *t {
+                "string" => "string".to_string(),
+                "integer" | "number" => "number".to_string(),
+                "boolean" => "boolean".to_string(),
+                "null" => "null".to_string(),
+                "array" => array(obj, indent),
+                "object" => object(schema, indent),
+                _ => "unknown".to_string(),
+            })
+            .collect(),
+    )
+}
+
+fn type_names(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn array(obj: &Map<String, Value>, indent: &str) -> String {
+    if let Some(items) = obj.get("prefixItems").and_then(Value::as_array) {
+        let items: Vec<String> = items.iter().map(|i| ty(i, indent)).collect();
+        return format!("[{}]", items.join(", "));
+    }
+    match obj.get("items") {
+        Some(items) => {
+            let item = ty(items, indent);
+            if item.contains(' ') {
+                format!("({item})[]")
+            } else {
+                format!("{item}[]")
+            }
+        }
+        None => "unknown[]".to_string(),
+    }
+}
+
+fn object(schema: &Value, indent: &str) -> String {
+    let props = schema.get("properties").and_then(Value::as_object);
+    let extra = schema.get("additionalProperties");
+    let Some(props) = props.filter(|p| !p.is_empty()) else {
+        return match extra {
+            Some(v) if !v.is_boolean() => format!("Record<string, {}>", ty(v, indent)),
+            _ => "Record<string, unknown>".to_string(),
+        };
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let inner = format!("{indent}  ");
+    let mut out = String::from("{\n");
+    for (name, prop) in props {
+        out.push_str(&doc(prop, &inner));
+        // Serde only omits a field it would otherwise write as `null`
+        // (`skip_serializing_if`), so an optional one is never `null`.
+        let (optional, prop_ty) = if required.contains(&name.as_str()) {
+            ("", ty(prop, &inner))
+        } else {
+            ("?", ty(&without_null(prop), &inner))
+        };
+        out.push_str(&format!("{inner}{}{optional}: {prop_ty}\n", key(name)));
+    }
+    out.push_str(indent);
+    out.push('}');
+    out
+}
+
+/// `schema` with `null` dropped from its type list or `anyOf`.
+fn without_null(schema: &Value) -> Value {
+    let mut schema = schema.clone();
+    if let Some(obj) = schema.as_object_mut() {
+        if let Some(Value::Array(types)) = obj.get_mut("type") {
+            types.retain(|t| t != "null");
+        }
+        if let Some(Value::Array(variants)) = obj.get_mut("anyOf") {
+            variants.retain(|v| type_names(v) != ["null"]);
+        }
+    }
+    schema
+}
+
+fn union(mut alts: Vec<String>) -> String {
+    alts.dedup();
+    if alts.is_empty() {
+        return "never".to_string();
+    }
+    alts.join(" | ")
+}
+
+fn key(name: &str) -> String {
+    let ident = name.chars().enumerate().all(|(i, c)| {
+        c == '_' || c == '$' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit())
+    });
+    if ident && !name.is_empty() {
+        name.to_string()
+    } else {
+        Value::String(name.to_string()).to_string()
+    }
+}
+
+fn doc(schema: &Value, indent: &str) -> String {
+    let Some(text) = schema.get("description").and_then(Value::as_str) else {
+        return String::new();
+    };
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.len() == 1 {
+        return format!("{indent}/** {} */\n", lines[0].replace("*/", "*\\/"));
+    }
+    let mut out = format!("{indent}/**\n");
+    for line in lines {
+        let line = line.replace("*/", "*\\/");
+        if line.is_empty() {
+            out.push_str(&format!("{indent} *\n"));
+        } else {
+            out.push_str(&format!("{indent} * {line}\n"));
+        }
+    }
+    out.push_str(&format!("{indent} */\n"));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn objects_unions_and_nullables() {
+        let defs = json!({
+            "Zone": { "description": "Where the price sits.", "enum": ["below", "within"] },
+            "Row": {
+                "type": "object",
+                "properties": {
+                    "zone": { "$ref": "#/$defs/Zone" },
+                    "price": { "type": ["number", "null"] },
+                    "note": { "type": ["string", "null"] },
+                    "prior": { "anyOf": [{ "$ref": "#/$defs/Zone" }, { "type": "null" }] },
+                    "tags": { "type": "array", "items": { "type": "string" } },
+                    "extra": { "type": "object", "additionalProperties": { "type": "integer" } },
+                    "raw": true
+                },
+                "required": ["zone", "price", "tags", "raw"]
+            },
+            "Event": {
+                "oneOf": [
+                    { "type": "object", "properties": { "type": { "const": "Tick" } }, "required": ["type"] },
+                    { "type": "object", "properties": { "type": { "const": "Row" }, "data": { "$ref": "#/$defs/Row" } }, "required": ["type", "data"] }
+                ]
+            }
+        });
+        let out = declarations(defs.as_object().unwrap());
+        assert_eq!(
+            out,
+            "export type Event = {\n  type: \"Tick\"\n} | {\n  data: Row\n  type: \"Row\"\n}\n\n\
+             export interface Row {\n  extra?: Record<string, number>\n  note?: string\n  \
+             price: number | null\n  prior?: Zone\n  raw: unknown\n  tags: string[]\n  zone: Zone\n}\n\n\
+             /** Where the price sits. */\nexport type Zone = \"below\" | \"within\"\n"
+        );
+    }
+}
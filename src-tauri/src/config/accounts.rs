@@ -0,0 +1,55 @@
+//! Account aliases — `DU1234567` → "Roth IRA".
+//!
+//! Display-only: the raw id stays the key everywhere it is stored.
+//! `ibkr_get_accounts` returns each id with its alias, and the event
+//! emitter adds `account_alias` to any event payload whose `account` /
+//! `account_id` has one, so notifications read the same as the UI.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Long enough for "Joint brokerage (taxable)", short enough for a pill.
+pub const MAX_ALIAS_LEN: usize = 40;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountsConfig {
+    /// Account id → alias.
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+}
+
+impl AccountsConfig {
+    pub fn alias(&self, account: &str) -> Option<&str> {
+        self.aliases.get(account).map(String::as_str)
+    }
+
+    /// Set `account`'s alias, or clear it with `None` / a blank alias.
+    pub fn set_alias(&mut self, account: &str, alias: Option<&str>) {
+        let account = account.trim().to_string();
+        match alias.map(str::trim).filter(|a| !a.is_empty()) {
+            Some(alias) => {
+                self.aliases.insert(account, alias.to_string());
+            }
+            None => {
+                self.aliases.remove(&account);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_alias_trims_and_blank_clears() {
+        let mut cfg = AccountsConfig::default();
+        cfg.set_alias(" DU1234567 ", Some("  Roth IRA "));
+        assert_eq!(cfg.alias("DU1234567"), Some("Roth IRA"));
+
+        cfg.set_alias("DU1234567", Some("   "));
+        assert_eq!(cfg.alias("DU1234567"), None);
+        assert!(cfg.aliases.is_empty());
+    }
+}
@@ -1,4 +1,9 @@
+use super::accounts::AccountsConfig;
+use super::secrets::{self, SecretProvider};
 use super::settings::AppConfig;
+use super::validation::{format_errors, FieldError};
+use super::workspaces::{Workspace, WorkspaceError, WorkspacesConfig};
+use crate::middleware::validation::CommandError;
 use std::sync::Arc;
 use tauri::State;
 use tokio::sync::RwLock;
@@ -16,19 +21,25 @@ impl SettingsState {
     }
 }
 
-/// Get all settings
+/// Get all settings, with API keys redacted (see `config/secrets.rs`).
+/// Use [`has_api_key`] to show whether a key is configured.
 #[tauri::command]
 pub async fn get_settings(state: State<'_, SettingsState>) -> Result<AppConfig, String> {
     let config = state.config.read().await;
-    Ok(config.clone())
+    Ok(config.redacted())
 }
 
-/// Update settings
+/// Update settings. Rejected with field errors (see
+/// [`AppConfig::validate`]) rather than persisting a config that would
+/// break the next startup.
 #[tauri::command]
 pub async fn update_settings(
-    settings: AppConfig,
+    mut settings: AppConfig,
     state: State<'_, SettingsState>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
+    // The form only ever sees redacted keys; keys change via `set_api_key`.
+    settings.keep_secrets_from(&*state.config.read().await);
+    settings.validate()?;
     let mut config = state.config.write().await;
     *config = settings.clone();
 
@@ -41,6 +52,51 @@ pub async fn update_settings(
     Ok(())
 }
 
+/// Dry-run validation for the settings form: every field error, empty
+/// when `settings` would be accepted by [`update_settings`].
+#[tauri::command]
+pub async fn validate_settings(settings: AppConfig) -> Result<Vec<FieldError>, String> {
+    Ok(settings.validate().err().unwrap_or_default())
+}
+
+/// Store (or, with an empty `key`, remove) an API key in the OS
+/// keychain and apply it to the live config.
+#[tauri::command]
+pub async fn set_api_key(
+    provider: SecretProvider,
+    key: String,
+    state: State<'_, SettingsState>,
+) -> Result<(), String> {
+    let key = key.trim().to_string();
+    let mut config = state.config.write().await;
+    let mut next = config.clone();
+    if key.is_empty() {
+        next.set_secret(provider, None);
+        tokio::task::spawn_blocking(move || secrets::keychain().delete(provider))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| format!("Failed to remove {} key: {e}", provider.as_str()))?;
+    } else {
+        next.set_secret(provider, Some(key));
+    }
+    next.validate().map_err(|errs| format_errors(&errs))?;
+    next.save()
+        .await
+        .map_err(|e| format!("Failed to save settings: {e}"))?;
+    *config = next;
+    Ok(())
+}
+
+/// Whether a key is configured for `provider` — the only key-related
+/// fact the frontend can read back.
+#[tauri::command]
+pub async fn has_api_key(
+    provider: SecretProvider,
+    state: State<'_, SettingsState>,
+) -> Result<bool, String> {
+    Ok(state.config.read().await.has_secret(provider))
+}
+
 /// Get settings file path (for debugging)
 #[tauri::command]
 pub async fn get_settings_path() -> Result<String, String> {
@@ -48,3 +104,84 @@ pub async fn get_settings_path() -> Result<String, String> {
         .map(|p| p.to_string_lossy().to_string())
         .map_err(|e| format!("Failed to get settings path: {e}"))
 }
+
+/// Apply `f` to a copy of the live config, validate and persist it,
+/// then swap it in. A rejected edit (bad spreadsheet id, bad cron
+/// expression, ...) leaves the live config untouched. Shared by the
+/// commands that edit one settings section in place.
+pub async fn mutate_config<F>(state: &SettingsState, f: F) -> Result<AppConfig, String>
+where
+    F: FnOnce(&mut AppConfig) -> Result<(), String>,
+{
+    let mut config = state.config.write().await;
+    let mut next = config.clone();
+    f(&mut next)?;
+    next.validate().map_err(|errs| format_errors(&errs))?;
+    next.save()
+        .await
+        .map_err(|e| format!("Failed to save settings: {e}"))?;
+    *config = next;
+    Ok(config.clone())
+}
+
+/// [`mutate_config`] over the workspaces section, so each workspace
+/// command stays a one-liner over the pure [`WorkspacesConfig`]
+/// operations.
+async fn mutate_workspaces<F>(state: &SettingsState, f: F) -> Result<WorkspacesConfig, String>
+where
+    F: FnOnce(&mut WorkspacesConfig) -> Result<(), WorkspaceError>,
+{
+    let config = mutate_config(state, |c| f(&mut c.workspaces).map_err(|e| e.to_string())).await?;
+    Ok(config.workspaces)
+}
+
+/// Set an account's alias, or clear it with `alias = None` / blank.
+#[tauri::command]
+pub async fn account_set_alias(
+    account: String,
+    alias: Option<String>,
+    state: State<'_, SettingsState>,
+) -> Result<AccountsConfig, String> {
+    if account.trim().is_empty() {
+        return Err("account id must not be empty".to_string());
+    }
+    let config = mutate_config(&state, |c| {
+        c.accounts.set_alias(&account, alias.as_deref());
+        Ok(())
+    })
+    .await?;
+    Ok(config.accounts)
+}
+
+/// All workspaces plus the active name.
+#[tauri::command]
+pub async fn workspace_list(state: State<'_, SettingsState>) -> Result<WorkspacesConfig, String> {
+    Ok(state.config.read().await.workspaces.clone())
+}
+
+/// Create or replace a workspace (matched by case-insensitive name).
+#[tauri::command]
+pub async fn workspace_save(
+    workspace: Workspace,
+    state: State<'_, SettingsState>,
+) -> Result<WorkspacesConfig, String> {
+    mutate_workspaces(&state, |w| w.upsert(workspace)).await
+}
+
+#[tauri::command]
+pub async fn workspace_delete(
+    name: String,
+    state: State<'_, SettingsState>,
+) -> Result<WorkspacesConfig, String> {
+    mutate_workspaces(&state, |w| w.remove(&name)).await
+}
+
+/// Switch the active workspace. `name = None` returns to the
+/// unfiltered view.
+#[tauri::command]
+pub async fn workspace_switch(
+    name: Option<String>,
+    state: State<'_, SettingsState>,
+) -> Result<WorkspacesConfig, String> {
+    mutate_workspaces(&state, |w| w.switch(name.as_deref())).await
+}
@@ -0,0 +1,26 @@
+//! `LlmService` transport selector, kept out of `settings.rs` (which is
+//! at the file-size cap) and re-exported from there.
+
+use serde::{Deserialize, Serialize};
+
+/// Backend transport for `LlmService`. Default is the historical
+/// API path; opting into `ClaudeCli` flips every Rust LLM call site
+/// onto the user's local subscription via the v2.1+ `claude -p`
+/// flag surface.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LlmBackendKind {
+    #[default]
+    Anthropic,
+    ClaudeCli,
+}
+
+impl LlmBackendKind {
+    pub fn from_env_str(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "anthropic" | "anthropic-api" | "" => Some(Self::Anthropic),
+            "claude_cli" | "claude-cli" => Some(Self::ClaudeCli),
+            _ => None,
+        }
+    }
+}
@@ -1,5 +1,11 @@
+pub mod accounts;
 pub mod commands;
+pub mod llm_backend;
+pub mod persistence;
+pub mod secrets;
 pub mod settings;
+pub mod validation;
+pub mod workspaces;
 
 pub use commands::SettingsState;
 #[allow(unused_imports)]
@@ -0,0 +1,274 @@
+//! Versioned settings.json persistence.
+//!
+//! The file carries a top-level `schema_version` that lives only on disk
+//! (it is not a field of [`AppConfig`]): [`to_versioned_json`] stamps it on
+//! save and [`parse_versioned`] runs the [`MIGRATIONS`] chain on the raw
+//! JSON before deserializing. Files without the key are version 0 — every
+//! settings.json written before versioning existed.
+//!
+//! Saves are backup-first: the previous file is copied to
+//! `settings.json.bak` before being overwritten. A load that had to
+//! migrate also snapshots the original as `settings.v{N}.json.bak` once,
+//! so the pre-migration file survives any number of later saves.
+//!
+//! Files from a *newer* build (version above [`CURRENT_SCHEMA_VERSION`])
+//! are refused rather than parsed: serde would silently drop the fields
+//! this build doesn't know and the next save would erase them.
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use super::secrets::{self, SecretStore};
+use super::settings::{ApiConfig, AppConfig, IbkrConfig, LoggingConfig, UiConfig};
+
+pub const CURRENT_SCHEMA_VERSION: u64 = 1;
+const VERSION_KEY: &str = "schema_version";
+
+type BoxError = Box<dyn Error>;
+type Migration = fn(&mut Map<String, Value>) -> Result<(), BoxError>;
+
+/// `MIGRATIONS[n]` upgrades a version-`n` document to `n + 1`. Append
+/// only; never edit a shipped step.
+const MIGRATIONS: &[Migration] = &[v0_to_v1];
+
+/// v0 → v1: `ibkr` / `logging` / `ui` / `api` predate `#[serde(default)]`
+/// and are required by the struct, so a hand-edited file missing one
+/// failed to load outright. Backfill each missing section with defaults.
+fn v0_to_v1(doc: &mut Map<String, Value>) -> Result<(), BoxError> {
+    let required = [
+        ("ibkr", serde_json::to_value(IbkrConfig::default())?),
+        ("logging", serde_json::to_value(LoggingConfig::default())?),
+        ("ui", serde_json::to_value(UiConfig::default())?),
+        ("api", serde_json::to_value(ApiConfig::default())?),
+    ];
+    for (key, default) in required {
+        doc.entry(key).or_insert(default);
+    }
+    Ok(())
+}
+
+/// Outcome of [`parse_versioned`]. `migrated_from` is `Some(v)` when the
+/// on-disk document was at version `v < CURRENT_SCHEMA_VERSION`.
+pub struct Parsed {
+    pub config: AppConfig,
+    pub migrated_from: Option<u64>,
+}
+
+pub fn parse_versioned(contents: &str) -> Result<Parsed, BoxError> {
+    let mut value: Value = serde_json::from_str(contents)?;
+    let doc = value
+        .as_object_mut()
+        .ok_or("settings.json must be a JSON object")?;
+    let version = doc
+        .remove(VERSION_KEY)
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0);
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(format!(
+            "settings.json schema_version {version} is newer than this build supports \
+             ({CURRENT_SCHEMA_VERSION}); refusing to load"
+        )
+        .into());
+    }
+    for step in &MIGRATIONS[version as usize..CURRENT_SCHEMA_VERSION as usize] {
+        step(doc)?;
+    }
+    Ok(Parsed {
+        config: serde_json::from_value(value)?,
+        migrated_from: (version < CURRENT_SCHEMA_VERSION).then_some(version),
+    })
+}
+
+/// Serialize for disk: secrets moved to `store` are nulled out (see
+/// `config/secrets.rs`) and `schema_version` is stamped.
+pub fn to_versioned_json(config: &AppConfig, store: &dyn SecretStore) -> Result<String, BoxError> {
+    let mut value = serde_json::to_value(config)?;
+    secrets::strip_for_disk(config, &mut value, store);
+    if let Some(doc) = value.as_object_mut() {
+        doc.insert(VERSION_KEY.to_string(), Value::from(CURRENT_SCHEMA_VERSION));
+    }
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
+fn sibling(path: &Path, file_name: String) -> PathBuf {
+    path.with_file_name(file_name)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "settings.json".to_string())
+}
+
+pub fn backup_path(path: &Path) -> PathBuf {
+    sibling(path, format!("{}.bak", file_name(path)))
+}
+
+fn migration_backup_path(path: &Path, from: u64) -> PathBuf {
+    let name = file_name(path);
+    let stem = name.strip_suffix(".json").unwrap_or(&name);
+    sibling(path, format!("{stem}.v{from}.json.bak"))
+}
+
+/// Read + migrate `path` and hydrate secrets from `store`, or defaults
+/// when the file doesn't exist. A file that still held plaintext keys is
+/// rewritten without them once they're safely in the keychain.
+pub fn load_from(path: &Path, store: &dyn SecretStore) -> Result<AppConfig, BoxError> {
+    if !path.exists() {
+        let mut config = AppConfig::default();
+        secrets::hydrate(&mut config, store);
+        return Ok(config);
+    }
+    let contents = std::fs::read_to_string(path)?;
+    let parsed = parse_versioned(&contents)?;
+    if let Some(from) = parsed.migrated_from {
+        let snapshot = migration_backup_path(path, from);
+        if !snapshot.exists() {
+            std::fs::write(&snapshot, &contents)?;
+        }
+        tracing::info!(
+            "settings.json migrated from schema v{from} to v{CURRENT_SCHEMA_VERSION} \
+             (original kept at {})",
+            snapshot.display()
+        );
+    }
+    let mut config = parsed.config;
+    if secrets::hydrate(&mut config, store) {
+        write_with_backup(path, &to_versioned_json(&config, store)?)?;
+        tracing::info!("moved plaintext API keys from settings.json into the OS keychain");
+    }
+    Ok(config)
+}
+
+fn write_with_backup(path: &Path, json: &str) -> Result<(), BoxError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if path.exists() {
+        std::fs::copy(path, backup_path(path))?;
+    }
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+/// Backup the current file (if any), then write `config` to `path`.
+/// Runs on the blocking pool — keychain writes are synchronous IPC.
+pub async fn save_to(
+    config: &AppConfig,
+    path: &Path,
+    store: &'static dyn SecretStore,
+) -> Result<(), BoxError> {
+    let config = config.clone();
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        to_versioned_json(&config, store)
+            .and_then(|json| write_with_backup(&path, &json))
+            .map_err(|e| e.to_string())
+    })
+    .await?
+    .map_err(Into::into)
+}
+
+#[allow(dead_code)]
+impl AppConfig {
+    /// Get the path to the settings file
+    pub fn settings_path() -> Result<PathBuf, BoxError> {
+        let config_dir = dirs::config_dir().ok_or("Could not find config directory")?;
+        let app_dir = config_dir.join("quantum-kapital");
+        Ok(app_dir.join("settings.json"))
+    }
+
+    /// Load settings from disk, or return defaults if file doesn't exist
+    pub async fn load() -> Result<Self, BoxError> {
+        let path = Self::settings_path()?;
+        tokio::task::spawn_blocking(move || {
+            load_from(&path, secrets::keychain()).map_err(|e| e.to_string())
+        })
+        .await?
+        .map_err(Into::into)
+    }
+
+    /// Save settings to disk, keeping the previous file as `.bak`
+    pub async fn save(&self) -> Result<(), BoxError> {
+        // Bind first: a `Box<dyn Error>` temporary held across the await
+        // would make every settings-saving command future `!Send`.
+        let path = Self::settings_path()?;
+        save_to(self, &path, secrets::keychain()).await
+    }
+
+    /// Load synchronously (for initial app setup)
+    pub fn load_sync() -> Result<Self, BoxError> {
+        load_from(&Self::settings_path()?, secrets::keychain())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::secrets::{MemoryStore, SecretProvider};
+    use tempfile::TempDir;
+
+    fn memory_store() -> &'static MemoryStore {
+        Box::leak(Box::new(MemoryStore::default()))
+    }
+
+    #[test]
+    fn unversioned_file_is_migrated_and_missing_sections_backfilled() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("settings.json");
+        std::fs::write(&path, r#"{ "ui": { "theme": "light", "default_refresh_interval_ms": 500, "show_notifications": false, "auto_save_layout": false } }"#).unwrap();
+
+        let cfg = load_from(&path, memory_store()).unwrap();
+        assert_eq!(cfg.ui.theme, "light");
+        assert_eq!(cfg.ibkr.default_port, IbkrConfig::default().default_port);
+        assert!(dir.path().join("settings.v0.json.bak").exists());
+    }
+
+    #[test]
+    fn newer_schema_is_refused() {
+        let json = format!(r#"{{ "{VERSION_KEY}": {} }}"#, CURRENT_SCHEMA_VERSION + 1);
+        assert!(parse_versioned(&json).is_err());
+    }
+
+    #[tokio::test]
+    async fn save_stamps_version_and_backs_up_previous_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("settings.json");
+        std::fs::write(&path, "{\"previous\": true}").unwrap();
+
+        save_to(&AppConfig::default(), &path, memory_store())
+            .await
+            .unwrap();
+
+        let saved: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved[VERSION_KEY], CURRENT_SCHEMA_VERSION);
+        assert_eq!(
+            std::fs::read_to_string(backup_path(&path)).unwrap(),
+            "{\"previous\": true}"
+        );
+        // Round-trip: a current-version file parses without migrating.
+        let parsed = parse_versioned(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(parsed.migrated_from.is_none());
+    }
+
+    #[test]
+    fn plaintext_keys_are_moved_to_the_keychain_on_load() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("settings.json");
+        let mut cfg = AppConfig::default();
+        cfg.api.alpha_vantage_api_key = Some("AV-KEY".into());
+        std::fs::write(&path, serde_json::to_string(&cfg).unwrap()).unwrap();
+
+        let store = memory_store();
+        let loaded = load_from(&path, store).unwrap();
+        assert_eq!(loaded.api.alpha_vantage_api_key.as_deref(), Some("AV-KEY"));
+        assert_eq!(
+            store.get(SecretProvider::AlphaVantage).unwrap().as_deref(),
+            Some("AV-KEY")
+        );
+        let on_disk = std::fs::read_to_string(&path).unwrap();
+        assert!(!on_disk.contains("AV-KEY"), "plaintext key left on disk");
+    }
+}
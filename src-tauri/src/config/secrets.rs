@@ -0,0 +1,320 @@
+//! API keys live in the OS keychain, not in settings.json.
+//!
+//! [`AppConfig`] keeps its `Option<String>` key fields so every existing
+//! consumer (`lib.rs` reading `config.api.anthropic_api_key`, the AV
+//! service, ...) is unchanged — they are simply *hydrated* from the
+//! keychain on load and *stripped* from the JSON on save:
+//!
+//!   - load: a plaintext key still present in the file is moved into the
+//!     keychain (one-time migration for pre-keychain installs); an absent
+//!     key is filled from the keychain.
+//!   - save: each populated key is written to the keychain and removed
+//!     from the serialized JSON. If the keychain is unavailable (headless
+//!     Linux without a Secret Service daemon) the key stays in the file
+//!     and a warning is logged — losing the key is worse than plaintext.
+//!   - `get_settings` returns [`AppConfig::redacted`]; `update_settings`
+//!     carries the live keys over via [`AppConfig::keep_secrets_from`], so
+//!     a round-trip through the settings form never wipes a key. Keys are
+//!     changed only through `set_api_key`.
+
+#[cfg(test)]
+use std::collections::HashMap;
+#[cfg(test)]
+use std::sync::Mutex;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use super::settings::AppConfig;
+
+const KEYRING_SERVICE: &str = "quantum-kapital";
+
+/// Closed set of secrets the app stores. Adding a provider means adding
+/// a variant and its [`SecretProvider::slot`] mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretProvider {
+    AlphaVantage,
+    Anthropic,
+    HttpApi,
+    Notion,
+    RedditClientSecret,
+    SmtpPassword,
+    Telegram,
+}
+
+impl SecretProvider {
+    pub const ALL: [SecretProvider; 7] = [
+        SecretProvider::AlphaVantage,
+        SecretProvider::Anthropic,
+        SecretProvider::HttpApi,
+        SecretProvider::Notion,
+        SecretProvider::RedditClientSecret,
+        SecretProvider::SmtpPassword,
+        SecretProvider::Telegram,
+    ];
+
+    /// Keychain account name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecretProvider::AlphaVantage => "alpha_vantage",
+            SecretProvider::Anthropic => "anthropic",
+            SecretProvider::HttpApi => "http_api",
+            SecretProvider::Notion => "notion",
+            SecretProvider::RedditClientSecret => "reddit_client_secret",
+            SecretProvider::SmtpPassword => "smtp_password",
+            SecretProvider::Telegram => "telegram",
+        }
+    }
+
+    /// `(section, field)` of the JSON key this provider shadows.
+    fn json_path(&self) -> (&'static str, &'static str) {
+        match self {
+            SecretProvider::AlphaVantage => ("api", "alpha_vantage_api_key"),
+            SecretProvider::Anthropic => ("api", "anthropic_api_key"),
+            SecretProvider::HttpApi => ("http_api", "token"),
+            SecretProvider::Notion => ("notion", "token"),
+            SecretProvider::RedditClientSecret => ("social_sentiment", "reddit_client_secret"),
+            SecretProvider::SmtpPassword => ("notifications", "smtp_password"),
+            SecretProvider::Telegram => ("telegram", "token"),
+        }
+    }
+
+    fn value<'a>(&self, cfg: &'a AppConfig) -> &'a Option<String> {
+        match self {
+            SecretProvider::AlphaVantage => &cfg.api.alpha_vantage_api_key,
+            SecretProvider::Anthropic => &cfg.api.anthropic_api_key,
+            SecretProvider::HttpApi => &cfg.http_api.token,
+            SecretProvider::Notion => &cfg.notion.token,
+            SecretProvider::RedditClientSecret => &cfg.social_sentiment.reddit_client_secret,
+            SecretProvider::SmtpPassword => &cfg.notifications.smtp_password,
+            SecretProvider::Telegram => &cfg.telegram.token,
+        }
+    }
+
+    fn slot<'a>(&self, cfg: &'a mut AppConfig) -> &'a mut Option<String> {
+        match self {
+            SecretProvider::AlphaVantage => &mut cfg.api.alpha_vantage_api_key,
+            SecretProvider::Anthropic => &mut cfg.api.anthropic_api_key,
+            SecretProvider::HttpApi => &mut cfg.http_api.token,
+            SecretProvider::Notion => &mut cfg.notion.token,
+            SecretProvider::RedditClientSecret => &mut cfg.social_sentiment.reddit_client_secret,
+            SecretProvider::SmtpPassword => &mut cfg.notifications.smtp_password,
+            SecretProvider::Telegram => &mut cfg.telegram.token,
+        }
+    }
+}
+
+/// Keychain seam. Production uses [`KeyringStore`]; tests use
+/// [`MemoryStore`].
+pub trait SecretStore: Send + Sync {
+    fn get(&self, provider: SecretProvider) -> Result<Option<String>, String>;
+    fn set(&self, provider: SecretProvider, value: &str) -> Result<(), String>;
+    fn delete(&self, provider: SecretProvider) -> Result<(), String>;
+}
+
+pub struct KeyringStore;
+
+impl KeyringStore {
+    fn entry(provider: SecretProvider) -> Result<keyring::Entry, String> {
+        keyring::Entry::new(KEYRING_SERVICE, provider.as_str()).map_err(|e| e.to_string())
+    }
+}
+
+impl SecretStore for KeyringStore {
+    fn get(&self, provider: SecretProvider) -> Result<Option<String>, String> {
+        match Self::entry(provider)?.get_password() {
+            Ok(v) => Ok(Some(v)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    fn set(&self, provider: SecretProvider, value: &str) -> Result<(), String> {
+        Self::entry(provider)?
+            .set_password(value)
+            .map_err(|e| e.to_string())
+    }
+
+    fn delete(&self, provider: SecretProvider) -> Result<(), String> {
+        match Self::entry(provider)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// In-memory store for tests. `unavailable` simulates a missing keychain.
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryStore {
+    values: Mutex<HashMap<SecretProvider, String>>,
+    pub unavailable: bool,
+}
+
+#[cfg(test)]
+impl SecretStore for MemoryStore {
+    fn get(&self, provider: SecretProvider) -> Result<Option<String>, String> {
+        if self.unavailable {
+            return Err("keychain unavailable".into());
+        }
+        Ok(self.values.lock().unwrap().get(&provider).cloned())
+    }
+
+    fn set(&self, provider: SecretProvider, value: &str) -> Result<(), String> {
+        if self.unavailable {
+            return Err("keychain unavailable".into());
+        }
+        self.values
+            .lock()
+            .unwrap()
+            .insert(provider, value.to_string());
+        Ok(())
+    }
+
+    fn delete(&self, provider: SecretProvider) -> Result<(), String> {
+        self.values.lock().unwrap().remove(&provider);
+        Ok(())
+    }
+}
+
+/// Process-wide keychain handle used by `AppConfig::load` / `save`.
+pub fn keychain() -> &'static dyn SecretStore {
+    static STORE: OnceLock<KeyringStore> = OnceLock::new();
+    STORE.get_or_init(|| KeyringStore)
+}
+
+/// Load-time pass. Returns `true` when a plaintext key was moved into
+/// the keychain, i.e. the file should be rewritten without it.
+pub fn hydrate(cfg: &mut AppConfig, store: &dyn SecretStore) -> bool {
+    let mut migrated = false;
+    for provider in SecretProvider::ALL {
+        let slot = provider.slot(cfg);
+        match slot.as_deref().filter(|v| !v.trim().is_empty()) {
+            Some(plaintext) => match store.set(provider, plaintext) {
+                Ok(()) => migrated = true,
+                Err(e) => tracing::warn!(
+                    "keychain unavailable; {} stays in settings.json: {e}",
+                    provider.as_str()
+                ),
+            },
+            None => match store.get(provider) {
+                Ok(stored) => *slot = stored.or(slot.take()),
+                Err(e) => tracing::debug!("keychain read {}: {e}", provider.as_str()),
+            },
+        }
+    }
+    migrated
+}
+
+/// Save-time pass over the serialized JSON: every key the keychain
+/// accepted is nulled out of `doc`.
+pub fn strip_for_disk(cfg: &AppConfig, doc: &mut serde_json::Value, store: &dyn SecretStore) {
+    for provider in SecretProvider::ALL {
+        let Some(value) = provider
+            .value(cfg)
+            .as_deref()
+            .filter(|v| !v.trim().is_empty())
+        else {
+            continue;
+        };
+        match store.set(provider, value) {
+            Ok(()) => {
+                let (section, field) = provider.json_path();
+                if let Some(obj) = doc.get_mut(section).and_then(|s| s.as_object_mut()) {
+                    obj.insert(field.to_string(), serde_json::Value::Null);
+                }
+            }
+            Err(e) => tracing::warn!(
+                "keychain unavailable; writing {} to settings.json: {e}",
+                provider.as_str()
+            ),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Copy with every secret field cleared, for `get_settings`.
+    pub fn redacted(&self) -> AppConfig {
+        let mut out = self.clone();
+        for provider in SecretProvider::ALL {
+            *provider.slot(&mut out) = None;
+        }
+        out
+    }
+
+    /// Carry `live`'s secrets into `self` (an incoming, redacted config
+    /// from the settings form).
+    pub fn keep_secrets_from(&mut self, live: &AppConfig) {
+        for provider in SecretProvider::ALL {
+            *provider.slot(self) = provider.value(live).clone();
+        }
+    }
+
+    pub fn set_secret(&mut self, provider: SecretProvider, value: Option<String>) {
+        *provider.slot(self) = value;
+    }
+
+    pub fn has_secret(&self, provider: SecretProvider) -> bool {
+        provider
+            .value(self)
+            .as_deref()
+            .is_some_and(|v| !v.trim().is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_key() -> AppConfig {
+        let mut cfg = AppConfig::default();
+        cfg.api.alpha_vantage_api_key = Some("AV-KEY".into());
+        cfg.api.anthropic_api_key = None;
+        cfg
+    }
+
+    #[test]
+    fn hydrate_moves_plaintext_into_store_and_fills_missing() {
+        let store = MemoryStore::default();
+        store.set(SecretProvider::Anthropic, "sk-ant").unwrap();
+        let mut cfg = with_key();
+
+        assert!(hydrate(&mut cfg, &store));
+        assert_eq!(
+            store.get(SecretProvider::AlphaVantage).unwrap().as_deref(),
+            Some("AV-KEY")
+        );
+        assert_eq!(cfg.api.anthropic_api_key.as_deref(), Some("sk-ant"));
+    }
+
+    #[test]
+    fn strip_nulls_keys_only_when_store_accepts_them() {
+        let cfg = with_key();
+        let mut doc = serde_json::to_value(&cfg).unwrap();
+        strip_for_disk(&cfg, &mut doc, &MemoryStore::default());
+        assert!(doc["api"]["alpha_vantage_api_key"].is_null());
+
+        let mut doc = serde_json::to_value(&cfg).unwrap();
+        let broken = MemoryStore {
+            unavailable: true,
+            ..Default::default()
+        };
+        strip_for_disk(&cfg, &mut doc, &broken);
+        assert_eq!(doc["api"]["alpha_vantage_api_key"], "AV-KEY");
+    }
+
+    #[test]
+    fn redacted_round_trip_keeps_live_secrets() {
+        let live = with_key();
+        let mut incoming = live.redacted();
+        assert!(incoming.api.alpha_vantage_api_key.is_none());
+        incoming.ui.theme = "light".into();
+        incoming.keep_secrets_from(&live);
+        assert_eq!(
+            incoming.api.alpha_vantage_api_key.as_deref(),
+            Some("AV-KEY")
+        );
+        assert_eq!(incoming.ui.theme, "light");
+    }
+}
@@ -1,10 +1,42 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use tokio::fs;
 
+use super::accounts::AccountsConfig;
+pub use super::llm_backend::LlmBackendKind;
+use super::workspaces::WorkspacesConfig;
+use crate::http_api::HttpApiConfig;
+use crate::middleware::rate_limits::RateLimitsConfig;
+use crate::services::automation::AutomationConfig;
+use crate::services::benchmarks::BenchmarkConfig;
+use crate::services::carry_costs::CarryCostsConfig;
+use crate::services::cash_drag::CashDragConfig;
+use crate::services::drawdown::DrawdownConfig;
+use crate::services::edgar_service::EdgarConfig;
+use crate::services::factor_exposure::FactorExposureConfig;
+use crate::services::fx_service::FxConfig;
+use crate::services::liquidity::LiquidityConfig;
+use crate::services::margin_compare::MarginCompareConfig;
+use crate::services::margin_monitor::MarginConfig;
+use crate::services::margin_of_safety::MarginOfSafetyConfig;
+use crate::services::model_portfolio::ModelPortfoliosConfig;
+use crate::services::notifications::NotificationsConfig;
+use crate::services::notion_export::NotionConfig;
+use crate::services::option_expiry::OptionExpiryConfig;
+use crate::services::option_roll::OptionRollConfig;
+use crate::services::order_guard::OrderGuardConfig;
+use crate::services::paper_trading::PaperTradingConfig;
 use crate::services::portfolio_risk::ConcentrationConfig;
+use crate::services::position_health::PositionHealthConfig;
+use crate::services::position_plans::StopBabysitterConfig;
+use crate::services::projection_refresh::ProjectionRefreshConfig;
+use crate::services::projection_templates::ProjectionTemplatesConfig;
 use crate::services::regime::RegimeConfig;
 use crate::services::risk_engine::RiskConfig;
+use crate::services::scheduler::SchedulerConfig;
+use crate::services::stress_test::StressTestConfig;
+use crate::services::tax_lots::TaxConfig;
+use crate::services::telegram_bot::TelegramConfig;
+use crate::services::valuation::ValuationConfig;
 use crate::strategies::DetectorsConfig;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -43,6 +75,131 @@ pub struct AppConfig {
     /// plan's per-detector defaults table.
     #[serde(default)]
     pub regime: RegimeConfig,
+    /// Optional read-only localhost REST API (`http_api/`). Ships dark;
+    /// refuses to start without a bearer token.
+    #[serde(default)]
+    pub http_api: HttpApiConfig,
+    /// Named workspaces (account filter + watchlist + sheet id +
+    /// projection assumptions). See `config/workspaces.rs`.
+    #[serde(default)]
+    pub workspaces: WorkspacesConfig,
+    /// Per-endpoint IBKR pacing layered on `ibkr.rate_limit_per_second`.
+    /// See `middleware/rate_limits.rs`.
+    #[serde(default)]
+    pub rate_limits: RateLimitsConfig,
+    /// Base currency for fundamentals. See `services/fx_service`.
+    #[serde(default)]
+    pub fx: FxConfig,
+    /// Cron per recurring task. See `services/scheduler`.
+    #[serde(default)]
+    pub scheduler: SchedulerConfig,
+    /// Duplicate-order window. See `services/order_guard`.
+    #[serde(default)]
+    pub order_guard: OrderGuardConfig,
+    /// Account id → alias. See `config/accounts.rs`.
+    #[serde(default)]
+    pub accounts: AccountsConfig,
+    /// Target-allocation templates. See `services/model_portfolio`.
+    #[serde(default)]
+    pub model_portfolios: ModelPortfoliosConfig,
+    /// Cushion alert threshold. See `services/margin_monitor`.
+    #[serde(default)]
+    pub margin: MarginConfig,
+    /// Margin rate tiers and borrow fees. See `services/carry_costs`.
+    #[serde(default)]
+    pub carry_costs: CarryCostsConfig,
+    /// Risk-free rate override and equity risk premium. See
+    /// `services/valuation`.
+    #[serde(default)]
+    pub valuation: ValuationConfig,
+    /// Named assumption sets for projections. See
+    /// `services/projection_templates`.
+    #[serde(default)]
+    pub projection_templates: ProjectionTemplatesConfig,
+    /// Buy-below / sell-above margin. See `services/margin_of_safety`.
+    #[serde(default)]
+    pub margin_of_safety: MarginOfSafetyConfig,
+    /// Re-running projections on a new fiscal year. See
+    /// `services/projection_refresh`.
+    #[serde(default)]
+    pub projection_refresh: ProjectionRefreshConfig,
+    /// Notion database the ticker summaries are mirrored into. See
+    /// `services/notion_export`.
+    #[serde(default)]
+    pub notion: NotionConfig,
+    /// Email / webhook delivery of alerts and finished jobs. See
+    /// `services/notifications`.
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    /// Read-only Telegram bot and its alert chats. See
+    /// `services/telegram_bot`.
+    #[serde(default)]
+    pub telegram: TelegramConfig,
+    /// Slippage, commissions and starting cash of the simulator. See
+    /// `services/paper_trading`.
+    #[serde(default)]
+    pub paper_trading: PaperTradingConfig,
+    /// Run against a simulated account and market data instead of IBKR
+    /// and Alpha Vantage, in a separate database. Read at startup. See
+    /// `ibkr/client/demo.rs`.
+    #[serde(default)]
+    pub demo: bool,
+    /// Risk limits for automation rules' order tickets, and the audit log's
+    /// retention. See `services/automation`.
+    #[serde(default)]
+    pub automation: AutomationConfig,
+    /// Limit-exit pricing of the stop babysitter. See
+    /// `services/position_plans`.
+    #[serde(default)]
+    pub stop_babysitter: StopBabysitterConfig,
+    /// ATR period and scoring bands of the position health score. See
+    /// `services/position_health`.
+    #[serde(default)]
+    pub position_health: PositionHealthConfig,
+    /// Rolling window of the portfolio drawdown stats. See
+    /// `services/drawdown`.
+    #[serde(default)]
+    pub drawdown: DrawdownConfig,
+    /// Default benchmark and return windows of the benchmark-relative
+    /// report. See `services/benchmarks`.
+    #[serde(default)]
+    pub benchmarks: BenchmarkConfig,
+    /// Factor proxies and fitting window of the factor model. See
+    /// `services/factor_exposure`.
+    #[serde(default)]
+    pub factor_exposure: FactorExposureConfig,
+    /// Idle-cash threshold, alert and suggestions. See
+    /// `services/cash_drag`.
+    #[serde(default)]
+    pub cash_drag: CashDragConfig,
+    /// Rates and loss deduction limit of the tax projection. See
+    /// `services/tax_lots`.
+    #[serde(default)]
+    pub tax: TaxConfig,
+    /// Shock scenarios and the rates proxy of the stress test. See
+    /// `services/stress_test`.
+    #[serde(default)]
+    pub stress_test: StressTestConfig,
+    /// Volume window, participation cap and day limit of the liquidity
+    /// check. See `services/liquidity`.
+    #[serde(default)]
+    pub liquidity: LiquidityConfig,
+    /// Alert window and at-the-money band of the option expiry
+    /// monitor. See `services/option_expiry`.
+    #[serde(default)]
+    pub option_expiry: OptionExpiryConfig,
+    /// Which short options get roll suggestions, and how many. See
+    /// `services/option_roll`.
+    #[serde(default)]
+    pub option_roll: OptionRollConfig,
+    /// Price scan and per-contract floor of the portfolio margin
+    /// estimate. See `services/margin_compare`.
+    #[serde(default)]
+    pub margin_compare: MarginCompareConfig,
+    /// `User-Agent` sent to SEC EDGAR for filing lookups. See
+    /// `services/edgar_service`.
+    #[serde(default)]
+    pub edgar: EdgarConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -89,28 +246,6 @@ pub struct ApiConfig {
     pub llm_backend: LlmBackendKind,
 }
 
-/// Backend transport for `LlmService`. Default is the historical
-/// API path; opting into `ClaudeCli` flips every Rust LLM call site
-/// onto the user's local subscription via the v2.1+ `claude -p`
-/// flag surface.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
-#[serde(rename_all = "snake_case")]
-pub enum LlmBackendKind {
-    #[default]
-    Anthropic,
-    ClaudeCli,
-}
-
-impl LlmBackendKind {
-    pub fn from_env_str(value: &str) -> Option<Self> {
-        match value.trim().to_ascii_lowercase().as_str() {
-            "anthropic" | "anthropic-api" | "" => Some(Self::Anthropic),
-            "claude_cli" | "claude-cli" => Some(Self::ClaudeCli),
-            _ => None,
-        }
-    }
-}
-
 pub fn default_daily_llm_budget_usd() -> f64 {
     5.0
 }
@@ -390,147 +525,6 @@ impl Default for TrackerConfig {
     }
 }
 
-#[allow(dead_code)]
-impl AppConfig {
-    /// Get the path to the settings file
-    pub fn settings_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
-        let config_dir = dirs::config_dir().ok_or("Could not find config directory")?;
-        let app_dir = config_dir.join("quantum-kapital");
-        Ok(app_dir.join("settings.json"))
-    }
-
-    /// Load settings from disk, or return defaults if file doesn't exist
-    pub async fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        let settings_path = Self::settings_path()?;
-
-        if settings_path.exists() {
-            let contents = fs::read_to_string(&settings_path).await?;
-            let config: AppConfig = serde_json::from_str(&contents)?;
-            Ok(config)
-        } else {
-            // Return default settings if file doesn't exist
-            Ok(Self::default())
-        }
-    }
-
-    /// Save settings to disk
-    pub async fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let settings_path = Self::settings_path()?;
-
-        // Ensure directory exists
-        if let Some(parent) = settings_path.parent() {
-            fs::create_dir_all(parent).await?;
-        }
-
-        // Serialize settings with pretty formatting
-        let json = serde_json::to_string_pretty(self)?;
-
-        // Write to file
-        fs::write(&settings_path, json).await?;
-
-        Ok(())
-    }
-
-    /// Load synchronously (for initial app setup)
-    pub fn load_sync() -> Result<Self, Box<dyn std::error::Error>> {
-        let settings_path = Self::settings_path()?;
-
-        if settings_path.exists() {
-            let contents = std::fs::read_to_string(&settings_path)?;
-            let config: AppConfig = serde_json::from_str(&contents)?;
-            Ok(config)
-        } else {
-            Ok(Self::default())
-        }
-    }
-}
-
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn auto_scanner_defaults_are_disabled_with_broad_profiles() {
-        let cfg = AutoScannerConfig::default();
-        assert!(!cfg.enabled, "auto-scanner ships dark");
-        assert_eq!(cfg.interval_minutes, 30);
-        assert_eq!(cfg.daily_cap, 10);
-        assert!(cfg.industries.is_empty());
-        let scan_codes: Vec<&str> = cfg.profiles.iter().map(|p| p.scan_code.as_str()).collect();
-        assert_eq!(
-            scan_codes,
-            vec![
-                "TOP_PERC_GAIN",
-                "TOP_PERC_LOSE",
-                "HOT_BY_VOLUME",
-                "MOST_ACTIVE",
-                "HIGH_VS_52W_HL",
-            ]
-        );
-        assert!(cfg.profiles.iter().all(|p| p.industry_filter.is_none()));
-        // Phase 4 default: 0.7 score threshold for auto-promotion.
-        assert!((cfg.auto_promote_threshold - 0.7).abs() < f64::EPSILON);
-    }
-
-    #[test]
-    fn effective_profiles_appends_one_per_industry() {
-        let cfg = AutoScannerConfig {
-            industries: vec!["Semiconductors".to_string(), "Biotechnology".to_string()],
-            ..Default::default()
-        };
-        let effective = cfg.effective_profiles();
-        // 5 broad + 2 industry-filtered.
-        assert_eq!(effective.len(), 7);
-        let industry_filtered: Vec<&Option<String>> = effective
-            .iter()
-            .filter(|p| p.industry_filter.is_some())
-            .map(|p| &p.industry_filter)
-            .collect();
-        assert_eq!(industry_filtered.len(), 2);
-        assert_eq!(industry_filtered[0].as_deref(), Some("Semiconductors"));
-        assert_eq!(industry_filtered[1].as_deref(), Some("Biotechnology"));
-        // Industry-derived profiles all use TOP_PERC_GAIN per the plan.
-        assert!(effective
-            .iter()
-            .filter(|p| p.industry_filter.is_some())
-            .all(|p| p.scan_code == "TOP_PERC_GAIN"));
-    }
-
-    #[test]
-    fn app_config_round_trips_through_json_with_auto_scanner_default() {
-        // Existing settings files predate the field; the `#[serde(default)]`
-        // attribute on `AppConfig.auto_scanner` must keep them parseable.
-        let pre_existing = r#"{
-            "ibkr": {
-                "default_host": "127.0.0.1",
-                "default_port": 4004,
-                "default_client_id": 100,
-                "connection_timeout_ms": 30000,
-                "reconnect_interval_ms": 5000,
-                "max_reconnect_attempts": 3,
-                "rate_limit_per_second": 50
-            },
-            "logging": {
-                "level": "info",
-                "file_path": null,
-                "max_file_size_mb": 10,
-                "max_files": 5,
-                "console_output": true
-            },
-            "ui": {
-                "theme": "dark",
-                "default_refresh_interval_ms": 1000,
-                "show_notifications": true,
-                "auto_save_layout": true
-            },
-            "api": { "alpha_vantage_api_key": null },
-            "tracker": { "intraday_tick_interval_secs": 300 },
-            "detectors": {}
-        }"#;
-        let cfg: AppConfig = serde_json::from_str(pre_existing).unwrap();
-        assert!(!cfg.auto_scanner.enabled);
-        assert_eq!(cfg.auto_scanner.profiles.len(), 5);
-        // The new field round-trips with its default when absent from JSON.
-        assert!((cfg.auto_scanner.auto_promote_threshold - 0.7).abs() < f64::EPSILON);
-    }
-}
+#[path = "settings_tests.rs"]
+mod tests;
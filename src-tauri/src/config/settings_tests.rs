@@ -0,0 +1,86 @@
+use super::*;
+
+#[test]
+fn auto_scanner_defaults_are_disabled_with_broad_profiles() {
+    let cfg = AutoScannerConfig::default();
+    assert!(!cfg.enabled, "auto-scanner ships dark");
+    assert_eq!(cfg.interval_minutes, 30);
+    assert_eq!(cfg.daily_cap, 10);
+    assert!(cfg.industries.is_empty());
+    let scan_codes: Vec<&str> = cfg.profiles.iter().map(|p| p.scan_code.as_str()).collect();
+    assert_eq!(
+        scan_codes,
+        vec![
+            "TOP_PERC_GAIN",
+            "TOP_PERC_LOSE",
+            "HOT_BY_VOLUME",
+            "MOST_ACTIVE",
+            "HIGH_VS_52W_HL",
+        ]
+    );
+    assert!(cfg.profiles.iter().all(|p| p.industry_filter.is_none()));
+    // Phase 4 default: 0.7 score threshold for auto-promotion.
+    assert!((cfg.auto_promote_threshold - 0.7).abs() < f64::EPSILON);
+}
+
+#[test]
+fn effective_profiles_appends_one_per_industry() {
+    let cfg = AutoScannerConfig {
+        industries: vec!["Semiconductors".to_string(), "Biotechnology".to_string()],
+        ..Default::default()
+    };
+    let effective = cfg.effective_profiles();
+    // 5 broad + 2 industry-filtered.
+    assert_eq!(effective.len(), 7);
+    let industry_filtered: Vec<&Option<String>> = effective
+        .iter()
+        .filter(|p| p.industry_filter.is_some())
+        .map(|p| &p.industry_filter)
+        .collect();
+    assert_eq!(industry_filtered.len(), 2);
+    assert_eq!(industry_filtered[0].as_deref(), Some("Semiconductors"));
+    assert_eq!(industry_filtered[1].as_deref(), Some("Biotechnology"));
+    // Industry-derived profiles all use TOP_PERC_GAIN per the plan.
+    assert!(effective
+        .iter()
+        .filter(|p| p.industry_filter.is_some())
+        .all(|p| p.scan_code == "TOP_PERC_GAIN"));
+}
+
+#[test]
+fn app_config_round_trips_through_json_with_auto_scanner_default() {
+    // Existing settings files predate the field; the `#[serde(default)]`
+    // attribute on `AppConfig.auto_scanner` must keep them parseable.
+    let pre_existing = r#"{
+        "ibkr": {
+            "default_host": "127.0.0.1",
+            "default_port": 4004,
+            "default_client_id": 100,
+            "connection_timeout_ms": 30000,
+            "reconnect_interval_ms": 5000,
+            "max_reconnect_attempts": 3,
+            "rate_limit_per_second": 50
+        },
+        "logging": {
+            "level": "info",
+            "file_path": null,
+            "max_file_size_mb": 10,
+            "max_files": 5,
+            "console_output": true
+        },
+        "ui": {
+            "theme": "dark",
+            "default_refresh_interval_ms": 1000,
+            "show_notifications": true,
+            "auto_save_layout": true
+        },
+        "api": { "alpha_vantage_api_key": null },
+        "tracker": { "intraday_tick_interval_secs": 300 },
+        "detectors": {}
+    }"#;
+    let cfg: AppConfig = serde_json::from_str(pre_existing).unwrap();
+    assert!(!cfg.auto_scanner.enabled);
+    assert_eq!(cfg.auto_scanner.profiles.len(), 5);
+    // The new field round-trips with its default when absent from JSON.
+    assert!((cfg.auto_scanner.auto_promote_threshold - 0.7).abs() < f64::EPSILON);
+}
@@ -0,0 +1,394 @@
+//! `AppConfig::validate` — rejects values that would break the next
+//! startup (or silently disable a subsystem) before they reach disk.
+//!
+//! Errors are field-level: `field` is the dotted JSON path the settings
+//! UI binds to (`ibkr.default_port`, `workspaces.items[1].spreadsheetId`),
+//! so the form can highlight the offending input instead of showing one
+//! banner. Only checks that are unambiguous live here; soft "this looks
+//! odd" warnings belong in the UI.
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::accounts::MAX_ALIAS_LEN;
+use super::settings::AppConfig;
+use crate::middleware::rate_limits::ENDPOINTS;
+use crate::services::fx_service::is_currency_code;
+use crate::services::scheduler::cron::CronSchedule;
+
+mod analytics;
+mod integrations;
+mod portfolio;
+mod research;
+mod trading;
+
+/// Google spreadsheet ids are URL-safe base64-ish — `[A-Za-z0-9_-]`,
+/// 40-ish chars. 20 is a conservative floor that still catches a pasted
+/// sheet name or a truncated id.
+const MIN_SPREADSHEET_ID_LEN: usize = 20;
+/// An hour is already far past any double-click or retry.
+const MAX_DUPLICATE_WINDOW_SECS: u64 = 3_600;
+/// Model-portfolio weights may be typed to one or two decimals.
+const WEIGHT_SUM_TOLERANCE: f64 = 0.011;
+/// A band this wide already means "never rebalance".
+const MAX_BAND_PCT: f64 = 50.0;
+/// Projections past this are guesswork compounded.
+const MAX_PROJECTION_YEARS: u32 = 15;
+
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+/// Field errors joined for the `Result<_, String>` command surface:
+/// `invalid settings: a.b: msg; c.d: msg`.
+pub fn format_errors(errors: &[FieldError]) -> String {
+    let joined: Vec<String> = errors
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect();
+    format!("invalid settings: {}", joined.join("; "))
+}
+
+pub fn is_valid_spreadsheet_id(id: &str) -> bool {
+    id.len() >= MIN_SPREADSHEET_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+struct Collector(Vec<FieldError>);
+
+impl Collector {
+    fn check(&mut self, ok: bool, field: impl Into<String>, message: &str) {
+        if !ok {
+            self.0.push(FieldError {
+                field: field.into(),
+                message: message.to_string(),
+            });
+        }
+    }
+}
+
+impl AppConfig {
+    /// Every violated rule, in field order. `Ok(())` when the config is
+    /// safe to persist.
+    pub fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut c = Collector(Vec::new());
+
+        let ibkr = &self.ibkr;
+        c.check(
+            !ibkr.default_host.trim().is_empty(),
+            "ibkr.default_host",
+            "must not be empty",
+        );
+        c.check(
+            ibkr.default_port != 0,
+            "ibkr.default_port",
+            "must be between 1 and 65535",
+        );
+        c.check(
+            ibkr.default_client_id >= 0,
+            "ibkr.default_client_id",
+            "must be non-negative",
+        );
+        c.check(
+            ibkr.connection_timeout_ms > 0,
+            "ibkr.connection_timeout_ms",
+            "must be greater than 0",
+        );
+        c.check(
+            ibkr.reconnect_interval_ms > 0,
+            "ibkr.reconnect_interval_ms",
+            "must be greater than 0",
+        );
+        c.check(
+            ibkr.rate_limit_per_second > 0,
+            "ibkr.rate_limit_per_second",
+            "must be greater than 0 (0 would block every request)",
+        );
+
+        c.check(
+            self.api.daily_llm_budget_usd.is_finite() && self.api.daily_llm_budget_usd >= 0.0,
+            "api.daily_llm_budget_usd",
+            "must be a non-negative number",
+        );
+        c.check(
+            self.tracker.intraday_tick_interval_secs > 0,
+            "tracker.intraday_tick_interval_secs",
+            "must be greater than 0",
+        );
+        c.check(
+            self.auto_scanner.interval_minutes > 0,
+            "auto_scanner.interval_minutes",
+            "must be greater than 0",
+        );
+        c.check(
+            (0.0..=1.0).contains(&self.auto_scanner.auto_promote_threshold),
+            "auto_scanner.auto_promote_threshold",
+            "must be between 0 and 1",
+        );
+
+        if self.http_api.enabled {
+            c.check(
+                self.http_api.port != 0,
+                "http_api.port",
+                "must be between 1 and 65535",
+            );
+            c.check(
+                self.http_api
+                    .token
+                    .as_deref()
+                    .is_some_and(|t| !t.trim().is_empty()),
+                "http_api.token",
+                "required when the HTTP API is enabled",
+            );
+        }
+
+        let limits = &self.rate_limits;
+        c.check(
+            (0.0..=1.0).contains(&limits.warn_below_fraction),
+            "rate_limits.warn_below_fraction",
+            "must be between 0 and 1",
+        );
+        c.check(
+            limits.max_in_flight > 0,
+            "rate_limits.max_in_flight",
+            "must be greater than 0",
+        );
+        for (name, limit) in &limits.endpoints {
+            c.check(
+                ENDPOINTS.contains(&name.as_str()),
+                format!("rate_limits.endpoints.{name}"),
+                &format!(
+                    "unknown endpoint (expected one of {})",
+                    ENDPOINTS.join(", ")
+                ),
+            );
+            c.check(
+                limit.per_second.is_finite() && limit.per_second > 0.0,
+                format!("rate_limits.endpoints.{name}.per_second"),
+                "must be greater than 0",
+            );
+        }
+
+        c.check(
+            is_currency_code(self.fx.base_currency.trim()),
+            "fx.base_currency",
+            "must be a three-letter ISO 4217 code",
+        );
+
+        for (i, job) in self.scheduler.jobs.iter().enumerate() {
+            if let Err(e) = CronSchedule::parse(&job.cron) {
+                c.check(false, format!("scheduler.jobs[{i}].cron"), &e);
+            }
+            c.check(
+                !self.scheduler.jobs[..i]
+                    .iter()
+                    .any(|prev| prev.id == job.id),
+                format!("scheduler.jobs[{i}].id"),
+                "duplicate job id",
+            );
+        }
+
+        c.check(
+            self.order_guard.duplicate_window_secs <= MAX_DUPLICATE_WINDOW_SECS,
+            "order_guard.duplicate_window_secs",
+            &format!("must be at most {MAX_DUPLICATE_WINDOW_SECS}"),
+        );
+
+        let aliases = &self.accounts.aliases;
+        for (i, (id, alias)) in aliases.iter().enumerate() {
+            let field = format!("accounts.aliases.{id}");
+            let alias = alias.trim();
+            c.check(!alias.is_empty(), field.clone(), "must not be empty");
+            c.check(
+                alias.chars().count() <= MAX_ALIAS_LEN,
+                field.clone(),
+                &format!("must be at most {MAX_ALIAS_LEN} characters"),
+            );
+            let duplicate = aliases
+                .values()
+                .take(i)
+                .any(|prev| prev.trim().eq_ignore_ascii_case(alias));
+            c.check(!duplicate, field, "alias already used by another account");
+        }
+
+        portfolio::check(self, &mut c);
+        trading::check(self, &mut c);
+        analytics::check(self, &mut c);
+        research::check(self, &mut c);
+        integrations::check(self, &mut c);
+
+        let ws = &self.workspaces;
+        for (i, item) in ws.items.iter().enumerate() {
+            c.check(
+                !item.name.trim().is_empty(),
+                format!("workspaces.items[{i}].name"),
+                "must not be empty",
+            );
+            let duplicate = ws.items[..i]
+                .iter()
+                .any(|prev| prev.name.trim().eq_ignore_ascii_case(item.name.trim()));
+            c.check(
+                !duplicate,
+                format!("workspaces.items[{i}].name"),
+                "duplicate workspace name",
+            );
+            if let Some(id) = item.spreadsheet_id.as_deref() {
+                c.check(
+                    is_valid_spreadsheet_id(id.trim()),
+                    format!("workspaces.items[{i}].spreadsheetId"),
+                    "not a valid Google spreadsheet id",
+                );
+            }
+        }
+        if let Some(active) = ws.active.as_deref() {
+            c.check(
+                ws.active_workspace().is_some(),
+                "workspaces.active",
+                &format!("no workspace named `{active}`"),
+            );
+        }
+
+        if c.0.is_empty() {
+            Ok(())
+        } else {
+            Err(c.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::workspaces::Workspace;
+    use crate::services::model_portfolio::{AllocationBasis, AllocationTarget, AllocationTemplate};
+    use crate::services::notifications::{WebhookConfig, WebhookKind};
+    use crate::services::scheduler::ScheduleDef;
+    use crate::services::stress_test::Shock;
+
+    #[test]
+    fn defaults_are_valid() {
+        assert_eq!(AppConfig::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn reports_every_broken_field() {
+        let mut cfg = AppConfig::default();
+        cfg.ibkr.default_port = 0;
+        cfg.ibkr.rate_limit_per_second = 0;
+        cfg.api.daily_llm_budget_usd = -1.0;
+        cfg.http_api.enabled = true;
+        cfg.http_api.token = None;
+        cfg.fx.base_currency = "dollars".into();
+        cfg.scheduler.jobs.push(ScheduleDef {
+            id: "fair_value_check".into(),
+            cron: "every day".into(),
+            enabled: true,
+        });
+        cfg.order_guard.duplicate_window_secs = 86_400;
+        cfg.accounts.aliases.insert("DU1".into(), "Roth".into());
+        cfg.accounts.aliases.insert("DU2".into(), " roth ".into());
+        cfg.model_portfolios.templates.push(AllocationTemplate {
+            name: "core".into(),
+            basis: AllocationBasis::Ticker,
+            targets: vec![AllocationTarget {
+                key: "AAPL".into(),
+                weight: 90.0,
+            }],
+            band_pct: 1.0,
+        });
+        cfg.margin.cushion_alert_pct = 120.0;
+        cfg.carry_costs.day_count = 252;
+        cfg.paper_trading.slippage_bps = -1.0;
+        cfg.automation.max_order_notional = 0.0;
+        cfg.stop_babysitter.limit_offset_bps = 600.0;
+        cfg.position_health.red_below = 80.0;
+        cfg.drawdown.window_sessions = 1;
+        cfg.benchmarks.rank_window = 5;
+        cfg.factor_exposure.min_overlap = 3;
+        cfg.cash_drag.threshold_pct = 0.0;
+        cfg.tax.long_term_rate_pct = 120.0;
+        cfg.stress_test.scenarios[2].shocks.push(Shock::Price {
+            symbol: "xlk".into(),
+            move_pct: -10.0,
+        });
+        cfg.liquidity.participation_pct = 0.0;
+        cfg.option_expiry.atm_band_pct = 25.0;
+        cfg.option_roll.candidates = 0;
+        cfg.margin_compare.pm_move_pct = 0.0;
+        cfg.edgar.user_agent = "quantum-kapital".into();
+        cfg.valuation.risk_free_rate_pct = Some(-1.0);
+        cfg.projection_templates.templates[1].name = "Hyper-Growth".into();
+        cfg.margin_of_safety.margin_pct = 100.0;
+        cfg.notion.database_id = Some("https://notion.so/abc".into());
+        cfg.notifications.webhooks.push(WebhookConfig {
+            url: "discord.com/api/webhooks/1".into(),
+            kind: WebhookKind::Discord,
+        });
+        cfg.workspaces.items.push(Workspace {
+            name: "Retirement".into(),
+            accounts: vec![],
+            watchlist: vec![],
+            spreadsheet_id: Some("My Sheet".into()),
+            assumptions: None,
+        });
+        cfg.workspaces.active = Some("Nope".into());
+
+        let fields: Vec<String> = cfg
+            .validate()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                "ibkr.default_port",
+                "ibkr.rate_limit_per_second",
+                "api.daily_llm_budget_usd",
+                "http_api.token",
+                "fx.base_currency",
+                "scheduler.jobs[0].cron",
+                "order_guard.duplicate_window_secs",
+                "accounts.aliases.DU2",
+                "model_portfolios.templates[0].targets",
+                "margin.cushion_alert_pct",
+                "carry_costs.day_count",
+                "paper_trading.slippage_bps",
+                "automation.max_order_notional",
+                "stop_babysitter.limit_offset_bps",
+                "position_health.green_min",
+                "drawdown.window_sessions",
+                "benchmarks.rank_window",
+                "factor_exposure.min_overlap",
+                "cash_drag.threshold_pct",
+                "tax.long_term_rate_pct",
+                "stress_test.scenarios[2].shocks",
+                "liquidity.participation_pct",
+                "option_expiry.atm_band_pct",
+                "option_roll.candidates",
+                "margin_compare.pm_move_pct",
+                "edgar.user_agent",
+                "valuation.risk_free_rate_pct",
+                "projection_templates.templates[1].name",
+                "margin_of_safety.margin_pct",
+                "notion.database_id",
+                "notifications.webhooks[0].url",
+                "workspaces.items[0].spreadsheetId",
+                "workspaces.active",
+            ]
+        );
+    }
+
+    #[test]
+    fn spreadsheet_id_shape() {
+        assert!(is_valid_spreadsheet_id(
+            "1BxiMVs0XRA5nFMdKvBdBZjgmUUqptlbs74OgvE2upms"
+        ));
+        assert!(!is_valid_spreadsheet_id("1BxiMVs0XRA5nFMd/edit"));
+        assert!(!is_valid_spreadsheet_id("short"));
+    }
+}
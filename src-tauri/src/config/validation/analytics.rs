@@ -0,0 +1,187 @@
+//! Benchmarks, factor exposure, cash drag, tax, stress tests,
+//! liquidity, options, margin comparison, EDGAR and valuation.
+
+use super::Collector;
+use crate::config::settings::AppConfig;
+use crate::middleware::validation::validate_symbol;
+use crate::services::liquidity::MIN_SESSIONS;
+use crate::services::stress_test::Shock;
+
+pub(super) fn check(cfg: &AppConfig, c: &mut Collector) {
+    let benchmarks = &cfg.benchmarks;
+    c.check(
+        !benchmarks.windows.is_empty() && benchmarks.windows.iter().all(|w| (1..=2520).contains(w)),
+        "benchmarks.windows",
+        "must be 1 to 2520 sessions each, at least one",
+    );
+    c.check(
+        benchmarks.windows.contains(&benchmarks.rank_window),
+        "benchmarks.rank_window",
+        "must be one of the windows",
+    );
+    if let Some(benchmark) = &benchmarks.default_benchmark {
+        c.check(
+            validate_symbol(benchmark).is_ok(),
+            "benchmarks.default_benchmark",
+            "must be a ticker symbol",
+        );
+    }
+    let factors = &cfg.factor_exposure;
+    c.check(
+        !factors.factors.is_empty()
+            && factors.factors.iter().all(|f| {
+                !f.name.trim().is_empty()
+                    && validate_symbol(&f.long).is_ok()
+                    && f.short
+                        .as_deref()
+                        .is_none_or(|s| validate_symbol(s).is_ok())
+            }),
+        "factor_exposure.factors",
+        "each factor needs a name and ticker symbols, at least one",
+    );
+    c.check(
+        (20..=1260).contains(&factors.lookback_returns),
+        "factor_exposure.lookback_returns",
+        "must be between 20 and 1260",
+    );
+    c.check(
+        factors.min_overlap as usize > factors.factors.len() + 1
+            && factors.min_overlap <= factors.lookback_returns,
+        "factor_exposure.min_overlap",
+        "must be more than the factors plus one, and at most lookback_returns",
+    );
+    c.check(
+        cfg.cash_drag.threshold_pct > 0.0 && cfg.cash_drag.threshold_pct < 100.0,
+        "cash_drag.threshold_pct",
+        "must be between 0 and 100",
+    );
+    c.check(
+        (1..=50).contains(&cfg.cash_drag.max_suggestions),
+        "cash_drag.max_suggestions",
+        "must be between 1 and 50",
+    );
+    c.check(
+        (0.0..=100.0).contains(&cfg.tax.short_term_rate_pct),
+        "tax.short_term_rate_pct",
+        "must be between 0 and 100",
+    );
+    c.check(
+        (0.0..=100.0).contains(&cfg.tax.long_term_rate_pct),
+        "tax.long_term_rate_pct",
+        "must be between 0 and 100",
+    );
+    c.check(
+        cfg.tax.loss_deduction_limit >= 0.0,
+        "tax.loss_deduction_limit",
+        "must not be negative",
+    );
+    let stress = &cfg.stress_test;
+    c.check(
+        validate_symbol(&stress.rates_proxy).is_ok(),
+        "stress_test.rates_proxy",
+        "must be a ticker symbol",
+    );
+    c.check(
+        stress.rates_proxy_duration > 0.0 && stress.rates_proxy_duration <= 40.0,
+        "stress_test.rates_proxy_duration",
+        "must be more than 0 and at most 40",
+    );
+    for (i, scenario) in stress.scenarios.iter().enumerate() {
+        let field = format!("stress_test.scenarios[{i}]");
+        c.check(
+            !scenario.name.trim().is_empty(),
+            format!("{field}.name"),
+            "must not be empty",
+        );
+        c.check(
+            !stress.scenarios[..i]
+                .iter()
+                .any(|prev| prev.name.trim().eq_ignore_ascii_case(scenario.name.trim())),
+            format!("{field}.name"),
+            "duplicate stress scenario name",
+        );
+        let proxies: Vec<String> = scenario
+            .shocks
+            .iter()
+            .map(|s| s.proxy_move(stress).symbol)
+            .collect();
+        c.check(
+            !scenario.shocks.is_empty()
+                && scenario.shocks.iter().all(|s| match s {
+                    Shock::Price { symbol, move_pct } => {
+                        validate_symbol(symbol).is_ok() && *move_pct > -100.0
+                    }
+                    Shock::Rates { bp } => bp.abs() <= 1_000.0,
+                })
+                && !proxies
+                    .iter()
+                    .enumerate()
+                    .any(|(j, p)| proxies[..j].contains(p)),
+            format!("{field}.shocks"),
+            "needs shocks on distinct tickers: prices above -100%, rates within 1000bp",
+        );
+    }
+    c.check(
+        (MIN_SESSIONS as u32..=250).contains(&cfg.liquidity.adv_sessions),
+        "liquidity.adv_sessions",
+        "must be between 5 and 250",
+    );
+    c.check(
+        cfg.liquidity.participation_pct > 0.0 && cfg.liquidity.participation_pct <= 100.0,
+        "liquidity.participation_pct",
+        "must be more than 0 and at most 100",
+    );
+    c.check(
+        cfg.liquidity.max_days > 0.0 && cfg.liquidity.max_days <= 60.0,
+        "liquidity.max_days",
+        "must be more than 0 and at most 60",
+    );
+    c.check(
+        cfg.option_expiry.alert_dte <= 60,
+        "option_expiry.alert_dte",
+        "must be at most 60",
+    );
+    c.check(
+        cfg.option_expiry.atm_band_pct > 0.0 && cfg.option_expiry.atm_band_pct <= 10.0,
+        "option_expiry.atm_band_pct",
+        "must be more than 0 and at most 10",
+    );
+    c.check(
+        cfg.option_roll.within_dte <= 60,
+        "option_roll.within_dte",
+        "must be at most 60",
+    );
+    c.check(
+        (1..=10).contains(&cfg.option_roll.candidates),
+        "option_roll.candidates",
+        "must be between 1 and 10",
+    );
+    c.check(
+        cfg.margin_compare.pm_move_pct > 0.0 && cfg.margin_compare.pm_move_pct <= 50.0,
+        "margin_compare.pm_move_pct",
+        "must be more than 0 and at most 50",
+    );
+    c.check(
+        cfg.margin_compare.pm_option_floor >= 0.0,
+        "margin_compare.pm_option_floor",
+        "must not be negative",
+    );
+    c.check(
+        cfg.edgar.user_agent.trim().is_empty() || cfg.edgar.contact().is_some(),
+        "edgar.user_agent",
+        "must include a contact email",
+    );
+
+    if let Some(rate) = cfg.valuation.risk_free_rate_pct {
+        c.check(
+            (0.0..=20.0).contains(&rate),
+            "valuation.risk_free_rate_pct",
+            "must be between 0 and 20",
+        );
+    }
+    c.check(
+        (0.0..=20.0).contains(&cfg.valuation.equity_risk_premium_pct),
+        "valuation.equity_risk_premium_pct",
+        "must be between 0 and 20",
+    );
+}
@@ -0,0 +1,36 @@
+//! Notion and outbound notifications (email, webhooks).
+
+use super::Collector;
+use crate::config::settings::AppConfig;
+
+pub(super) fn check(cfg: &AppConfig, c: &mut Collector) {
+    if let Some(id) = cfg.notion.database_id.as_deref() {
+        let hex: String = id.chars().filter(|c| *c != '-').collect();
+        c.check(
+            hex.len() == 32 && hex.chars().all(|c| c.is_ascii_hexdigit()),
+            "notion.database_id",
+            "must be the 32-hex-digit id from the database URL",
+        );
+    }
+
+    let notifications = &cfg.notifications;
+    if let Some(email) = &notifications.email {
+        c.check(
+            !email.smtp_host.trim().is_empty(),
+            "notifications.email.smtp_host",
+            "must not be empty",
+        );
+        c.check(
+            !email.to.is_empty(),
+            "notifications.email.to",
+            "needs at least one recipient",
+        );
+    }
+    for (i, hook) in notifications.webhooks.iter().enumerate() {
+        c.check(
+            hook.url.starts_with("https://") || hook.url.starts_with("http://"),
+            format!("notifications.webhooks[{i}].url"),
+            "must be an http(s) URL",
+        );
+    }
+}
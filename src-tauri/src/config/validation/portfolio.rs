@@ -0,0 +1,85 @@
+//! Model portfolios, the margin cushion and carry costs.
+
+use super::{Collector, MAX_BAND_PCT, WEIGHT_SUM_TOLERANCE};
+use crate::config::settings::AppConfig;
+
+pub(super) fn check(cfg: &AppConfig, c: &mut Collector) {
+    let templates = &cfg.model_portfolios.templates;
+    for (i, t) in templates.iter().enumerate() {
+        let field = format!("model_portfolios.templates[{i}]");
+        c.check(
+            !t.name.trim().is_empty(),
+            format!("{field}.name"),
+            "must not be empty",
+        );
+        c.check(
+            !templates[..i]
+                .iter()
+                .any(|prev| prev.name.trim().eq_ignore_ascii_case(t.name.trim())),
+            format!("{field}.name"),
+            "duplicate model portfolio name",
+        );
+        let total: f64 = t.targets.iter().map(|x| x.weight).sum();
+        c.check(
+            !t.targets.is_empty()
+                && t.targets.iter().all(|x| x.weight > 0.0)
+                && (total - 100.0).abs() <= WEIGHT_SUM_TOLERANCE,
+            format!("{field}.targets"),
+            "weights must be positive and sum to 100",
+        );
+        c.check(
+            !t.targets.iter().enumerate().any(|(j, x)| {
+                t.targets[..j]
+                    .iter()
+                    .any(|prev| prev.key.trim().eq_ignore_ascii_case(x.key.trim()))
+            }),
+            format!("{field}.targets"),
+            "duplicate key",
+        );
+        c.check(
+            (0.0..=MAX_BAND_PCT).contains(&t.band_pct),
+            format!("{field}.bandPct"),
+            &format!("must be between 0 and {MAX_BAND_PCT}"),
+        );
+    }
+
+    c.check(
+        (0.0..=100.0).contains(&cfg.margin.cushion_alert_pct),
+        "margin.cushion_alert_pct",
+        "must be between 0 and 100",
+    );
+
+    let carry = &cfg.carry_costs;
+    for (i, tier) in carry.margin_tiers.iter().enumerate() {
+        let field = format!("carry_costs.margin_tiers[{i}]");
+        c.check(
+            (0.0..=100.0).contains(&tier.rate_pct),
+            format!("{field}.rate_pct"),
+            "must be between 0 and 100",
+        );
+        let last = i + 1 == carry.margin_tiers.len();
+        let ascending = match (
+            i.checked_sub(1).map(|p| carry.margin_tiers[p].up_to),
+            tier.up_to,
+        ) {
+            (_, None) => last,
+            (Some(Some(prev)), Some(up_to)) => up_to > prev,
+            (_, Some(up_to)) => up_to > 0.0,
+        };
+        c.check(
+            ascending,
+            format!("{field}.up_to"),
+            "tiers must ascend; only the last may be unbounded",
+        );
+    }
+    c.check(
+        matches!(carry.day_count, 360 | 365),
+        "carry_costs.day_count",
+        "must be 360 or 365",
+    );
+    c.check(
+        carry.default_borrow_fee_pct >= 0.0 && carry.borrow_fees.values().all(|fee| *fee >= 0.0),
+        "carry_costs.borrow_fees",
+        "fees must not be negative",
+    );
+}
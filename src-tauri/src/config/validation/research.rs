@@ -0,0 +1,55 @@
+//! Projection templates, margin-of-safety bands and projection
+//! refresh.
+
+use super::{Collector, MAX_PROJECTION_YEARS};
+use crate::config::settings::AppConfig;
+
+pub(super) fn check(cfg: &AppConfig, c: &mut Collector) {
+    let projection = &cfg.projection_templates.templates;
+    for (i, t) in projection.iter().enumerate() {
+        let field = format!("projection_templates.templates[{i}]");
+        c.check(
+            !t.name.trim().is_empty(),
+            format!("{field}.name"),
+            "must not be empty",
+        );
+        c.check(
+            !projection[..i]
+                .iter()
+                .any(|prev| prev.name.trim().eq_ignore_ascii_case(t.name.trim())),
+            format!("{field}.name"),
+            "duplicate projection template name",
+        );
+        c.check(
+            (1..=MAX_PROJECTION_YEARS).contains(&t.assumptions.years),
+            format!("{field}.assumptions.years"),
+            &format!("must be between 1 and {MAX_PROJECTION_YEARS}"),
+        );
+        let probability = t.assumptions.probability_error();
+        c.check(
+            probability.is_none(),
+            format!("{field}.assumptions"),
+            probability.as_deref().unwrap_or_default(),
+        );
+    }
+
+    let mos = &cfg.margin_of_safety;
+    c.check(
+        (0.0..100.0).contains(&mos.margin_pct),
+        "margin_of_safety.margin_pct",
+        "must be at least 0 and below 100",
+    );
+    for (symbol, pct) in &mos.per_symbol {
+        c.check(
+            (0.0..100.0).contains(pct),
+            format!("margin_of_safety.per_symbol.{symbol}"),
+            "must be at least 0 and below 100",
+        );
+    }
+
+    c.check(
+        cfg.projection_refresh.material_change_pct >= 0.0,
+        "projection_refresh.material_change_pct",
+        "must not be negative",
+    );
+}
@@ -0,0 +1,70 @@
+//! The paper-trading simulator, automation, the stop babysitter,
+//! position health and drawdown.
+
+use super::Collector;
+use crate::config::settings::AppConfig;
+
+pub(super) fn check(cfg: &AppConfig, c: &mut Collector) {
+    let sim = &cfg.paper_trading;
+    c.check(
+        sim.starting_cash.is_finite() && sim.starting_cash > 0.0,
+        "paper_trading.starting_cash",
+        "must be greater than 0",
+    );
+    c.check(
+        (0.0..=1_000.0).contains(&sim.slippage_bps),
+        "paper_trading.slippage_bps",
+        "must be between 0 and 1000",
+    );
+    c.check(
+        sim.commission_per_share >= 0.0 && sim.min_commission >= 0.0,
+        "paper_trading.commission_per_share",
+        "commissions must not be negative",
+    );
+
+    let automation = &cfg.automation;
+    c.check(
+        automation.max_order_notional.is_finite() && automation.max_order_notional > 0.0,
+        "automation.max_order_notional",
+        "must be greater than 0",
+    );
+    c.check(
+        (0.0..=500.0).contains(&automation.live_limit_offset_bps),
+        "automation.live_limit_offset_bps",
+        "must be between 0 and 500",
+    );
+    c.check(
+        (0.0..=500.0).contains(&cfg.stop_babysitter.limit_offset_bps),
+        "stop_babysitter.limit_offset_bps",
+        "must be between 0 and 500",
+    );
+
+    let health = &cfg.position_health;
+    c.check(
+        (2..=100).contains(&health.atr_period),
+        "position_health.atr_period",
+        "must be between 2 and 100",
+    );
+    c.check(
+        health.stop_atr > 0.0 && health.drawdown_atr > 0.0,
+        "position_health.stop_atr",
+        "ATR bands must be greater than 0",
+    );
+    c.check(
+        health.max_weight_pct > 0.0 && health.max_weight_pct <= 100.0,
+        "position_health.max_weight_pct",
+        "must be between 0 and 100",
+    );
+    c.check(
+        0.0 <= health.red_below
+            && health.red_below <= health.green_min
+            && health.green_min <= 100.0,
+        "position_health.green_min",
+        "must be between red_below (at least 0) and 100",
+    );
+    c.check(
+        (2..=2520).contains(&cfg.drawdown.window_sessions),
+        "drawdown.window_sessions",
+        "must be between 2 and 2520",
+    );
+}
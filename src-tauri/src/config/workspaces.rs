@@ -0,0 +1,221 @@
+//! Named workspaces — lets one install keep e.g. "retirement" and
+//! "speculative" portfolios apart without maintaining two settings files.
+//!
+//! A workspace is a saved lens, not a separate data store: it filters
+//! which IBKR / imported accounts a dashboard shows, carries its own
+//! symbol watchlist and spreadsheet id, and optionally overrides the
+//! default projection assumptions. Everything lives in
+//! `AppConfig.workspaces`; `active = None` means "no filter" so existing
+//! installs behave exactly as before.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ibkr::types::ProjectionAssumptions;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspacesConfig {
+    /// Name of the active workspace. `None` = unfiltered legacy view.
+    #[serde(default)]
+    pub active: Option<String>,
+    #[serde(default)]
+    pub items: Vec<Workspace>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    /// Unique, case-insensitive key. Trimmed on save.
+    pub name: String,
+    /// Account ids (IBKR or imported labels) shown in this workspace.
+    /// Empty = every account.
+    #[serde(default)]
+    pub accounts: Vec<String>,
+    /// Upper-cased symbols pinned to this workspace's watchlist.
+    #[serde(default)]
+    pub watchlist: Vec<String>,
+    #[serde(default)]
+    pub spreadsheet_id: Option<String>,
+    /// Projection defaults for this workspace. `None` falls back to
+    /// [`ProjectionAssumptions::default`].
+    #[serde(default)]
+    pub assumptions: Option<ProjectionAssumptions>,
+}
+
+impl Workspace {
+    pub fn includes_account(&self, account: &str) -> bool {
+        self.accounts.is_empty() || self.accounts.iter().any(|a| a == account)
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum WorkspaceError {
+    #[error("workspace name must be non-empty")]
+    EmptyName,
+    #[error("workspace `{0}` not found")]
+    NotFound(String),
+}
+
+impl WorkspacesConfig {
+    pub fn active_workspace(&self) -> Option<&Workspace> {
+        let active = self.active.as_deref()?;
+        self.find(active)
+    }
+
+    fn find(&self, name: &str) -> Option<&Workspace> {
+        self.items
+            .iter()
+            .find(|w| w.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Insert or replace (by case-insensitive name). Normalises the
+    /// name, account ids and watchlist symbols before storing.
+    pub fn upsert(&mut self, mut workspace: Workspace) -> Result<(), WorkspaceError> {
+        workspace.name = workspace.name.trim().to_string();
+        if workspace.name.is_empty() {
+            return Err(WorkspaceError::EmptyName);
+        }
+        workspace.accounts = normalise_list(workspace.accounts, false);
+        workspace.watchlist = normalise_list(workspace.watchlist, true);
+        match self
+            .items
+            .iter_mut()
+            .find(|w| w.name.eq_ignore_ascii_case(&workspace.name))
+        {
+            Some(existing) => *existing = workspace,
+            None => self.items.push(workspace),
+        }
+        Ok(())
+    }
+
+    /// Remove `name`. Clears `active` when it pointed at the removed entry.
+    pub fn remove(&mut self, name: &str) -> Result<(), WorkspaceError> {
+        let before = self.items.len();
+        self.items
+            .retain(|w| !w.name.eq_ignore_ascii_case(name.trim()));
+        if self.items.len() == before {
+            return Err(WorkspaceError::NotFound(name.to_string()));
+        }
+        if self
+            .active
+            .as_deref()
+            .is_some_and(|a| a.eq_ignore_ascii_case(name.trim()))
+        {
+            self.active = None;
+        }
+        Ok(())
+    }
+
+    /// Switch to `name`, or back to the unfiltered view with `None`.
+    pub fn switch(&mut self, name: Option<&str>) -> Result<(), WorkspaceError> {
+        self.active = match name.map(str::trim).filter(|n| !n.is_empty()) {
+            Some(n) => Some(
+                self.find(n)
+                    .ok_or_else(|| WorkspaceError::NotFound(n.to_string()))?
+                    .name
+                    .clone(),
+            ),
+            None => None,
+        };
+        Ok(())
+    }
+
+    /// Replace `from` with `to` in every watchlist, dropping it where
+    /// `to` is already listed. Returns how many workspaces changed.
+    pub fn rename_symbol(&mut self, from: &str, to: &str) -> usize {
+        let mut changed = 0;
+        for w in &mut self.items {
+            let Some(at) = w.watchlist.iter().position(|s| s == from) else {
+                continue;
+            };
+            if w.watchlist.iter().any(|s| s == to) {
+                w.watchlist.remove(at);
+            } else {
+                w.watchlist[at] = to.to_string();
+            }
+            changed += 1;
+        }
+        changed
+    }
+}
+
+fn normalise_list(values: Vec<String>, upper: bool) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for v in values {
+        let v = v.trim();
+        let v = if upper {
+            v.to_uppercase()
+        } else {
+            v.to_string()
+        };
+        if !v.is_empty() && !out.contains(&v) {
+            out.push(v);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ws(name: &str) -> Workspace {
+        Workspace {
+            name: name.to_string(),
+            accounts: vec![" U1 ".into(), "U1".into()],
+            watchlist: vec!["nvda".into(), "NVDA".into(), "".into()],
+            spreadsheet_id: None,
+            assumptions: None,
+        }
+    }
+
+    #[test]
+    fn upsert_normalises_and_replaces_case_insensitively() {
+        let mut cfg = WorkspacesConfig::default();
+        cfg.upsert(ws(" Retirement ")).unwrap();
+        assert_eq!(cfg.items[0].name, "Retirement");
+        assert_eq!(cfg.items[0].accounts, vec!["U1"]);
+        assert_eq!(cfg.items[0].watchlist, vec!["NVDA"]);
+
+        let mut again = ws("retirement");
+        again.accounts = vec![];
+        cfg.upsert(again).unwrap();
+        assert_eq!(cfg.items.len(), 1);
+        assert!(cfg.items[0].includes_account("anything"));
+        assert_eq!(cfg.upsert(ws("  ")), Err(WorkspaceError::EmptyName));
+    }
+
+    #[test]
+    fn switch_and_remove_keep_active_consistent() {
+        let mut cfg = WorkspacesConfig::default();
+        cfg.upsert(ws("Speculative")).unwrap();
+        cfg.switch(Some("speculative")).unwrap();
+        assert_eq!(cfg.active.as_deref(), Some("Speculative"));
+        assert!(cfg.active_workspace().unwrap().includes_account("U1"));
+        assert!(!cfg.active_workspace().unwrap().includes_account("U2"));
+
+        assert!(matches!(
+            cfg.switch(Some("nope")),
+            Err(WorkspaceError::NotFound(_))
+        ));
+        cfg.remove("SPECULATIVE").unwrap();
+        assert_eq!(cfg.active, None);
+        assert!(cfg.remove("Speculative").is_err());
+    }
+
+    #[test]
+    fn rename_symbol_keeps_watchlists_unique() {
+        let mut cfg = WorkspacesConfig::default();
+        cfg.upsert(ws("One")).unwrap();
+        let mut two = ws("Two");
+        two.watchlist = vec!["NVDA".into(), "META".into()];
+        cfg.upsert(two).unwrap();
+        cfg.upsert(ws("Three")).unwrap();
+        cfg.items[2].watchlist = vec!["AAPL".into()];
+
+        assert_eq!(cfg.rename_symbol("NVDA", "META"), 2);
+        assert_eq!(cfg.items[0].watchlist, vec!["META"]);
+        assert_eq!(cfg.items[1].watchlist, vec!["META"]);
+        assert_eq!(cfg.items[2].watchlist, vec!["AAPL"]);
+    }
+}
@@ -0,0 +1,48 @@
+//! Account aliases on outgoing event payloads (see `config/accounts.rs`).
+
+use std::collections::BTreeMap;
+
+use super::AppEvent;
+
+/// `event` as JSON with `data.account_alias` added when its `account` /
+/// `account_id` has an alias in `config.accounts`.
+pub(super) fn with_account_alias(
+    event: &AppEvent,
+    aliases: &BTreeMap<String, String>,
+) -> serde_json::Value {
+    let mut payload = serde_json::to_value(event).unwrap_or_default();
+    if let Some(data) = payload.get_mut("data").and_then(|d| d.as_object_mut()) {
+        let alias = ["account", "account_id"]
+            .iter()
+            .find_map(|k| data.get(*k)?.as_str())
+            .and_then(|id| aliases.get(id));
+        if let Some(alias) = alias {
+            data.insert("account_alias".into(), alias.clone().into());
+        }
+    }
+    payload
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stamps_alias_only_for_named_accounts() {
+        let aliases = BTreeMap::from([("DU1234567".to_string(), "Roth IRA".to_string())]);
+        let pnl = |account: &str| AppEvent::DailyPnLUpdate {
+            account: account.to_string(),
+            daily_pnl: 12.5,
+            unrealized_pnl: None,
+            realized_pnl: None,
+        };
+
+        let named = with_account_alias(&pnl("DU1234567"), &aliases);
+        assert_eq!(named["type"], "DailyPnLUpdate");
+        assert_eq!(named["data"]["account"], "DU1234567");
+        assert_eq!(named["data"]["account_alias"], "Roth IRA");
+
+        let unnamed = with_account_alias(&pnl("DU7654321"), &aliases);
+        assert!(unnamed["data"].get("account_alias").is_none());
+    }
+}
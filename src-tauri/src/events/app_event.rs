@@ -0,0 +1,476 @@
+//! Every event the backend emits, tagged `{ type, data }` on the wire.
+//! The Tauri event name of each variant is in `names.rs`.
+
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::ibkr::types::tracker::{Setup, TickerPrimingOutcome, TrackerStatus};
+use crate::ibkr::types::{DataTier, ScannerData};
+use crate::services::cash_management::CashWarning;
+use crate::services::corporate_actions::{CorporateAction, TableAdjustment};
+use crate::services::fair_value_watch::FairValueZone;
+use crate::services::jobs::JobInfo;
+use crate::services::margin_of_safety::BandZone;
+use crate::services::option_expiry::AssignmentRisk;
+use crate::services::option_greeks::PortfolioGreeks;
+use crate::services::order_ticket::BracketStatus;
+use crate::services::position_plans::PlanLevel;
+use crate::services::projection_refresh::ProjectionUpdate;
+use crate::services::regime::Regime;
+use crate::services::risk_engine::Sizing;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", content = "data")]
+pub enum AppEvent {
+    // Connection events
+    ConnectionStatusChanged {
+        connected: bool,
+        message: String,
+    },
+    ConnectionError {
+        error: String,
+    },
+    /// Emitted by the connection heartbeat when a `server_time` round
+    /// trip spikes well above the rolling median (see
+    /// `services/connection_health`). Fires once per degraded episode.
+    ConnectionDegraded {
+        latency_ms: f64,
+        p50_ms: f64,
+    },
+
+    // Account events
+    AccountUpdate {
+        account_id: String,
+        data: serde_json::Value,
+    },
+    AccountsListChanged {
+        accounts: Vec<String>,
+    },
+    DailyPnLUpdate {
+        account: String,
+        daily_pnl: f64,
+        unrealized_pnl: Option<f64>,
+        realized_pnl: Option<f64>,
+    },
+
+    // Market data events
+    MarketDataUpdate {
+        symbol: String,
+        data: serde_json::Value,
+    },
+    MarketDataSubscribed {
+        symbol: String,
+    },
+    MarketDataUnsubscribed {
+        symbol: String,
+    },
+    /// Emitted by `IbkrClient` after the connect-time probe finishes
+    /// (or on disconnect, where it carries `DataTier::Unknown`). Lets
+    /// the UI banner and any tier-gated consumer react without polling
+    /// `IbkrState`.
+    DataTierDetected {
+        tier: DataTier,
+    },
+
+    // Order events
+    OrderPlaced {
+        order_id: i32,
+        symbol: String,
+    },
+    OrderFilled {
+        order_id: i32,
+        filled_qty: f64,
+    },
+    OrderCancelled {
+        order_id: i32,
+    },
+    OrderError {
+        order_id: Option<i32>,
+        error: String,
+    },
+    /// Phase 3 — emitted after `OrderTicket::with_brackets` persists a
+    /// successful submission. Lets the watchlist + setup card flip to
+    /// "bracket live" without polling `bracket_groups`.
+    BracketPlaced {
+        parent_order_id: i32,
+        setup_id: i64,
+        symbol: String,
+        qty: u32,
+    },
+    /// Phase 3 — emitted on `BracketGroupRecord.last_status` flips
+    /// (cancel-by-trader, future fill-status reconciler). Carries the
+    /// new status so listeners can update their row state.
+    BracketStatusChanged {
+        parent_order_id: i32,
+        setup_id: i64,
+        status: BracketStatus,
+    },
+
+    // Position events
+    PositionUpdate {
+        symbol: String,
+        position: f64,
+    },
+    PositionsRefreshed,
+
+    // Scanner events
+    ScannerUpdate {
+        results: Vec<ScannerData>,
+    },
+
+    // Tracker / scheduling events
+    /// Emitted by `TrackerRunner` after a detector hit is persisted.
+    /// The `thesis` field stays `None` until Phase 17 wires the LLM
+    /// thesis prompt; the frontend treats absence as "no narrative
+    /// yet".
+    SetupDetected {
+        setup: Box<Setup>,
+        thesis: Option<String>,
+    },
+    /// Quant-decisions Phase 1 — emitted by `TrackerRunner` after
+    /// `RiskEngine::size` has run and the sizing has been persisted
+    /// to the row. Fires before `SetupDetected` so the frontend
+    /// always sees a sized payload (or a `skipped_reason`) when it
+    /// surfaces the setup card.
+    SetupSized {
+        setup_id: i64,
+        symbol: String,
+        sizing: Sizing,
+    },
+    /// Emitted by `TrackerStateMachine::mark_invalidated` when a
+    /// persisted setup is flipped to `Invalidated`. Carries the
+    /// reason so the UI can surface it in a toast.
+    SetupInvalidated {
+        setup_id: i64,
+        symbol: String,
+        reason: String,
+    },
+    /// Quant-decisions Phase 5 — emitted by `TrackerRunner` when a
+    /// detector hit was gated before sizing (earnings or FOMC
+    /// blackout). The setup row is persisted with `skipped_reason`
+    /// set so the trader can see the skip in the SkippedSetupsPanel
+    /// and override per-setup with a recorded reason. `kind` is
+    /// the short tag like `"earnings_blackout"`.
+    SetupSkipped {
+        setup_id: i64,
+        symbol: String,
+        strategy: String,
+        kind: String,
+        reason: String,
+    },
+    /// Emitted whenever a tracker row's status changes. Lets the
+    /// frontend update the watchlist row badge without a full
+    /// re-fetch.
+    TickerStatusChanged {
+        symbol: String,
+        from: TrackerStatus,
+        to: TrackerStatus,
+    },
+    /// Emitted by the EOD scheduler after a successful 16:05 ET sweep.
+    /// `date` is the ET trading-day date. `ranked_count` is `0` until
+    /// Phase 20's daily ranker fills it in.
+    MorningPackReady {
+        date: NaiveDate,
+        ranked_count: usize,
+    },
+
+    // Phase 02 — research artifacts written via MCP write tools.
+    /// Emitted after `write_research_note` persists a row. Carries the
+    /// minimal identifiers the UI needs to refresh its query for the
+    /// affected symbol / alert without a full refetch of every note.
+    ResearchNoteWritten {
+        note_id: i64,
+        symbol: String,
+        alert_id: Option<i64>,
+        setup_id: Option<i64>,
+    },
+    /// Emitted after `write_morning_pack` upserts an agent-authored
+    /// pack. The frontend re-queries the pack for `date` to render the
+    /// new ranked ideas.
+    AgentMorningPackWritten {
+        date: NaiveDate,
+        idea_count: usize,
+    },
+    /// Phase 4 (quant-decisions): emitted after `write_trade_review`
+    /// upserts a structured trade review. The frontend re-queries
+    /// `get_trade_review(date)` to refresh the daily card.
+    /// `formula_version` ("v1" / "v2") tells the UI which scoring
+    /// payload it should expect (legacy `grade`/`grade_score` vs new
+    /// `score_v2`/`discipline_v2`).
+    TradeReviewWritten {
+        date: NaiveDate,
+        account: String,
+        prompt_version: i32,
+        formula_version: String,
+    },
+    /// Phase 5 (behavioral assessment) — emitted after `write_playbook`
+    /// inserts a structured pre-market playbook. The frontend re-queries
+    /// `get_today_playbook(date)` to refresh the playbook panel.
+    PlaybookWritten {
+        date: NaiveDate,
+        account: String,
+        generation_id: i32,
+        n_setups: usize,
+        n_skip: usize,
+    },
+    /// Emitted after `ack_alert` records a decision. The UI flips the
+    /// alert's row treatment (e.g. greying acted/passed alerts) without
+    /// reloading the whole feed.
+    AlertDecisionRecorded {
+        alert_id: i64,
+        decision: String,
+        note_id: Option<i64>,
+    },
+    /// Phase 6 — emitted after `mark_alert_enriched` flips an alert
+    /// from "pending dive" to "enriched" (or "dive skipped"). The UI
+    /// flips the alert detail panel from "Enriching..." to "Deep dive
+    /// ready" / "skipped" using `research_note_id`.
+    AlertEnriched {
+        alert_id: i64,
+        research_note_id: Option<i64>,
+    },
+    /// Phase 6 — emitted when the per-alert dive bypassed an alert
+    /// (currently: global LLM budget below the per-alert reserve). Lets
+    /// the UI render a "deep dive skipped (budget)" badge without
+    /// having to inspect the audit row.
+    AlertDiveSkipped {
+        alert_id: i64,
+        reason: String,
+    },
+    /// Phase 4 (AV strip-out) — emitted after the MCP `set_fundamentals`
+    /// tool persists a manual row. The analysis UI re-queries
+    /// `get_fundamentals(symbol)` and re-renders the projection so the
+    /// freshly pasted snapshot is immediately reflected without polling.
+    FundamentalsManualWritten {
+        symbol: String,
+        as_of_date: String,
+        source: String,
+    },
+    /// Ticker-intake Phase 1 — emitted by `TickerPrimerService` after the
+    /// post-add fundamentals → projection → news chain completes (success
+    /// or partial). `outcome` carries per-step status so the workspace
+    /// can decide which panels to refresh without a full re-fetch.
+    TickerPrimingDone {
+        symbol: String,
+        outcome: TickerPrimingOutcome,
+    },
+
+    /// Phase 8 (quant-decisions) — emitted whenever the
+    /// `PortfolioRiskService` recomputes its snapshot (executions tick,
+    /// bracket revision, 60s scheduled refresh). The frontend's
+    /// RiskSnapshot card subscribes to this so the dollar-risk header
+    /// stays live without polling. The payload is a compact summary;
+    /// full exposures live in the `portfolio_snapshots` row identified
+    /// by `snapshot_id`.
+    PortfolioRiskChanged {
+        snapshot_id: i64,
+        account: String,
+        nlv_cents: i64,
+        total_dollar_risk_cents: i64,
+        open_position_count: usize,
+    },
+
+    /// Phase 9 (quant-decisions) — emitted whenever the
+    /// `RegimeService` recomputes its snapshot AND the stable view
+    /// flips on at least one axis. The first snapshot of a session
+    /// also emits (None → Some). Frontend's RegimeIndicator
+    /// subscribes so the pill stays live without polling.
+    RegimeChanged {
+        snapshot_id: i64,
+        regime: Regime,
+        /// One of `daily_close` | `intraday` | `force_recompute`.
+        source: String,
+    },
+
+    /// Phase 11 (quant-decisions) — emitted when the tilt guard
+    /// activates a new pause (cumulative-R floor or two-consecutive
+    /// closed losses). UI banner subscribes so it can render the red
+    /// state without polling. `auto_reset_at` is the next session
+    /// open in UTC; the banner formats it for ET.
+    TiltActivated {
+        episode_id: i64,
+        account: String,
+        trigger_kind: String,
+        cumulative_r: f64,
+        auto_reset_at: chrono::DateTime<chrono::Utc>,
+    },
+    /// Phase 11 — emitted when the tilt guard releases a pause. Two
+    /// release flavors carry through: `auto` (next-session-open
+    /// reset) and `manual_override` (trader dismissed with a reason).
+    /// `session_end` flips don't emit — they're audit-only.
+    TiltReleased {
+        episode_id: i64,
+        account: String,
+        /// One of `auto` | `manual_override`.
+        release_kind: String,
+    },
+
+    /// Progress or terminal transition of a background job (see
+    /// `services::jobs`). `job.result` is always omitted here; fetch it
+    /// via `list_jobs`.
+    JobProgress {
+        job: JobInfo,
+    },
+
+    /// Emitted by `services::scheduler` after each run of a recurring
+    /// task, scheduled or `run_now`. `summary` is the run's summary or
+    /// error.
+    ScheduledJobFinished {
+        id: String,
+        ok: bool,
+        summary: String,
+    },
+
+    /// Emitted by the daily fair-value watch when a ticker's price
+    /// moves below its bear value or above its bull value. Fires on the
+    /// crossing only; the standing reading lives in `fair_value_flags`.
+    FairValueCrossed {
+        symbol: String,
+        zone: FairValueZone,
+        price: f64,
+        bear_value: f64,
+        bull_value: f64,
+    },
+
+    /// Emitted by the margin-of-safety watch when a ticker's price
+    /// moves into a scenario's buy-below or sell-above band. Fires on
+    /// the entry only; the bands live in `margin_of_safety_bands`.
+    MarginOfSafetyEntered {
+        symbol: String,
+        scenario: String,
+        zone: BandZone,
+        price: f64,
+        buy_below: f64,
+        sell_above: f64,
+    },
+
+    /// Emitted by the `position_plan_check` task when an open
+    /// position's price reaches the target or stop of its plan. Fires
+    /// on the crossing only; the standing reading is the plan's `hit`.
+    PositionLevelHit {
+        account: String,
+        contract: String,
+        level: PlanLevel,
+        level_price: f64,
+        price: f64,
+        thesis: Option<String>,
+    },
+
+    /// Emitted by the `stop_babysitter` task when the price breaches a
+    /// plan stop it watches. `action` is the plan's stop action
+    /// (`alert`, `market_exit`, `limit_exit`); `exit_ticket` is set when
+    /// an exit ticket awaits the trader's confirmation. `result` says
+    /// what was done. Fires once until the plan is re-armed.
+    StopBreached {
+        account: String,
+        contract: String,
+        stop_price: f64,
+        price: f64,
+        action: String,
+        exit_ticket: bool,
+        result: String,
+    },
+
+    /// Emitted by `ProjectionRefresher` after a new fiscal year re-ran
+    /// the stored projections of the listed tickers.
+    ProjectionsUpdated {
+        updates: Vec<ProjectionUpdate>,
+    },
+
+    /// Emitted by `CashManagementService::overview` when pending orders
+    /// would take a currency's cash below zero, put the account on
+    /// margin, or exceed buying power. Fires once per condition until
+    /// it clears.
+    CashWarning {
+        #[serde(flatten)]
+        warning: CashWarning,
+    },
+
+    /// Emitted by the `margin_sample` task when an account's `Cushion`
+    /// (a 0..1 fraction) drops below `margin.cushion_alert_pct`. Fires
+    /// on the crossing only.
+    MarginCushionLow {
+        account: String,
+        cushion: f64,
+        threshold_pct: f64,
+        excess_liquidity: f64,
+    },
+
+    /// Emitted by the `cash_drag_sample` task when an account's cash
+    /// rises above `cash_drag.threshold_pct` of NAV. Fires on the
+    /// crossing only. `suggestions` are watchlist symbols below their
+    /// base-case buy band, deepest first.
+    IdleCash {
+        account: String,
+        cash_pct: f64,
+        threshold_pct: f64,
+        idle_cash: f64,
+        suggestions: Vec<String>,
+    },
+
+    /// Emitted by `CorporateActionService` after a split, symbol change
+    /// or spin-off is applied to local data. `source` is `provider`,
+    /// `ibkr` or `manual`; `adjusted` lists the rows changed per table.
+    CorporateActionApplied {
+        action: CorporateAction,
+        source: String,
+        adjusted: Vec<TableAdjustment>,
+    },
+
+    /// Emitted by the `option_expiry_check` task for each short option
+    /// at risk of assignment: `early_exercise` (in the money, with an
+    /// ex-dividend date before expiry worth more than its time value)
+    /// or `at_expiry` (in or at the money near expiry). Dates are ISO.
+    OptionAssignmentRisk {
+        account: String,
+        symbol: String,
+        local_symbol: String,
+        risk: AssignmentRisk,
+        expiry: String,
+        dte: i64,
+        moneyness_pct: f64,
+        ex_dividend_date: Option<String>,
+    },
+
+    /// Emitted by `PaperTrader` when a simulated order fills. `side` is
+    /// `buy` or `sell`.
+    SimOrderFilled {
+        order_id: i64,
+        symbol: String,
+        side: String,
+        quantity: f64,
+        price: f64,
+    },
+
+    /// Emitted by `RuleEngine` when an automation rule's condition
+    /// crosses. `action` is the rule's action kind and `outcome` what
+    /// came of it (`notified`, `dry_run`, `placed`, `blocked`, `failed`).
+    RuleTriggered {
+        rule_id: i64,
+        name: String,
+        symbol: String,
+        action: String,
+        outcome: String,
+        detail: Option<String>,
+    },
+
+    /// Emitted by the `option_greeks` task with per-position and
+    /// aggregate model greeks for the account's option positions.
+    PortfolioGreeksUpdate {
+        #[serde(flatten)]
+        greeks: PortfolioGreeks,
+    },
+
+    // System events
+    /// Emitted by `RateLimits` when an IBKR pacing bucket drops below
+    /// `rate_limits.warn_below_fraction` of capacity. `endpoint` is
+    /// `global` or one of the `rate_limits::ENDPOINTS` keys.
+    RateLimitWarning {
+        endpoint: String,
+        remaining: u32,
+    },
+    SystemError {
+        error: String,
+    },
+}
@@ -1,348 +1,12 @@
-use chrono::NaiveDate;
-use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tauri::{AppHandle, Emitter, Manager};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use tauri::{AppHandle, Emitter, EventTarget, Manager};
 use tokio::sync::RwLock;
 
-use crate::ibkr::types::tracker::{Setup, TickerPrimingOutcome, TrackerStatus};
-use crate::ibkr::types::{DataTier, ScannerData};
-use crate::services::order_ticket::BracketStatus;
-use crate::services::regime::Regime;
-use crate::services::risk_engine::Sizing;
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type", content = "data")]
-pub enum AppEvent {
-    // Connection events
-    ConnectionStatusChanged {
-        connected: bool,
-        message: String,
-    },
-    ConnectionError {
-        error: String,
-    },
-
-    // Account events
-    AccountUpdate {
-        account_id: String,
-        data: serde_json::Value,
-    },
-    AccountsListChanged {
-        accounts: Vec<String>,
-    },
-    DailyPnLUpdate {
-        account: String,
-        daily_pnl: f64,
-        unrealized_pnl: Option<f64>,
-        realized_pnl: Option<f64>,
-    },
-
-    // Market data events
-    MarketDataUpdate {
-        symbol: String,
-        data: serde_json::Value,
-    },
-    MarketDataSubscribed {
-        symbol: String,
-    },
-    MarketDataUnsubscribed {
-        symbol: String,
-    },
-    /// Emitted by `IbkrClient` after the connect-time probe finishes
-    /// (or on disconnect, where it carries `DataTier::Unknown`). Lets
-    /// the UI banner and any tier-gated consumer react without polling
-    /// `IbkrState`.
-    DataTierDetected {
-        tier: DataTier,
-    },
-
-    // Order events
-    OrderPlaced {
-        order_id: i32,
-        symbol: String,
-    },
-    OrderFilled {
-        order_id: i32,
-        filled_qty: f64,
-    },
-    OrderCancelled {
-        order_id: i32,
-    },
-    OrderError {
-        order_id: Option<i32>,
-        error: String,
-    },
-    /// Phase 3 — emitted after `OrderTicket::with_brackets` persists a
-    /// successful submission. Lets the watchlist + setup card flip to
-    /// "bracket live" without polling `bracket_groups`.
-    BracketPlaced {
-        parent_order_id: i32,
-        setup_id: i64,
-        symbol: String,
-        qty: u32,
-    },
-    /// Phase 3 — emitted on `BracketGroupRecord.last_status` flips
-    /// (cancel-by-trader, future fill-status reconciler). Carries the
-    /// new status so listeners can update their row state.
-    BracketStatusChanged {
-        parent_order_id: i32,
-        setup_id: i64,
-        status: BracketStatus,
-    },
-
-    // Position events
-    PositionUpdate {
-        symbol: String,
-        position: f64,
-    },
-    PositionsRefreshed,
-
-    // Scanner events
-    ScannerUpdate {
-        results: Vec<ScannerData>,
-    },
-
-    // Tracker / scheduling events
-    /// Emitted by `TrackerRunner` after a detector hit is persisted.
-    /// The `thesis` field stays `None` until Phase 17 wires the LLM
-    /// thesis prompt; the frontend treats absence as "no narrative
-    /// yet".
-    SetupDetected {
-        setup: Box<Setup>,
-        thesis: Option<String>,
-    },
-    /// Quant-decisions Phase 1 — emitted by `TrackerRunner` after
-    /// `RiskEngine::size` has run and the sizing has been persisted
-    /// to the row. Fires before `SetupDetected` so the frontend
-    /// always sees a sized payload (or a `skipped_reason`) when it
-    /// surfaces the setup card.
-    SetupSized {
-        setup_id: i64,
-        symbol: String,
-        sizing: Sizing,
-    },
-    /// Emitted by `TrackerStateMachine::mark_invalidated` when a
-    /// persisted setup is flipped to `Invalidated`. Carries the
-    /// reason so the UI can surface it in a toast.
-    SetupInvalidated {
-        setup_id: i64,
-        symbol: String,
-        reason: String,
-    },
-    /// Quant-decisions Phase 5 — emitted by `TrackerRunner` when a
-    /// detector hit was gated before sizing (earnings or FOMC
-    /// blackout). The setup row is persisted with `skipped_reason`
-    /// set so the trader can see the skip in the SkippedSetupsPanel
-    /// and override per-setup with a recorded reason. `kind` is
-    /// the short tag like `"earnings_blackout"`.
-    SetupSkipped {
-        setup_id: i64,
-        symbol: String,
-        strategy: String,
-        kind: String,
-        reason: String,
-    },
-    /// Emitted whenever a tracker row's status changes. Lets the
-    /// frontend update the watchlist row badge without a full
-    /// re-fetch.
-    TickerStatusChanged {
-        symbol: String,
-        from: TrackerStatus,
-        to: TrackerStatus,
-    },
-    /// Emitted by the EOD scheduler after a successful 16:05 ET sweep.
-    /// `date` is the ET trading-day date. `ranked_count` is `0` until
-    /// Phase 20's daily ranker fills it in.
-    MorningPackReady {
-        date: NaiveDate,
-        ranked_count: usize,
-    },
-
-    // Phase 02 — research artifacts written via MCP write tools.
-    /// Emitted after `write_research_note` persists a row. Carries the
-    /// minimal identifiers the UI needs to refresh its query for the
-    /// affected symbol / alert without a full refetch of every note.
-    ResearchNoteWritten {
-        note_id: i64,
-        symbol: String,
-        alert_id: Option<i64>,
-        setup_id: Option<i64>,
-    },
-    /// Emitted after `write_morning_pack` upserts an agent-authored
-    /// pack. The frontend re-queries the pack for `date` to render the
-    /// new ranked ideas.
-    AgentMorningPackWritten {
-        date: NaiveDate,
-        idea_count: usize,
-    },
-    /// Phase 4 (quant-decisions): emitted after `write_trade_review`
-    /// upserts a structured trade review. The frontend re-queries
-    /// `get_trade_review(date)` to refresh the daily card.
-    /// `formula_version` ("v1" / "v2") tells the UI which scoring
-    /// payload it should expect (legacy `grade`/`grade_score` vs new
-    /// `score_v2`/`discipline_v2`).
-    TradeReviewWritten {
-        date: NaiveDate,
-        account: String,
-        prompt_version: i32,
-        formula_version: String,
-    },
-    /// Phase 5 (behavioral assessment) — emitted after `write_playbook`
-    /// inserts a structured pre-market playbook. The frontend re-queries
-    /// `get_today_playbook(date)` to refresh the playbook panel.
-    PlaybookWritten {
-        date: NaiveDate,
-        account: String,
-        generation_id: i32,
-        n_setups: usize,
-        n_skip: usize,
-    },
-    /// Emitted after `ack_alert` records a decision. The UI flips the
-    /// alert's row treatment (e.g. greying acted/passed alerts) without
-    /// reloading the whole feed.
-    AlertDecisionRecorded {
-        alert_id: i64,
-        decision: String,
-        note_id: Option<i64>,
-    },
-    /// Phase 6 — emitted after `mark_alert_enriched` flips an alert
-    /// from "pending dive" to "enriched" (or "dive skipped"). The UI
-    /// flips the alert detail panel from "Enriching..." to "Deep dive
-    /// ready" / "skipped" using `research_note_id`.
-    AlertEnriched {
-        alert_id: i64,
-        research_note_id: Option<i64>,
-    },
-    /// Phase 6 — emitted when the per-alert dive bypassed an alert
-    /// (currently: global LLM budget below the per-alert reserve). Lets
-    /// the UI render a "deep dive skipped (budget)" badge without
-    /// having to inspect the audit row.
-    AlertDiveSkipped {
-        alert_id: i64,
-        reason: String,
-    },
-    /// Phase 4 (AV strip-out) — emitted after the MCP `set_fundamentals`
-    /// tool persists a manual row. The analysis UI re-queries
-    /// `get_fundamentals(symbol)` and re-renders the projection so the
-    /// freshly pasted snapshot is immediately reflected without polling.
-    FundamentalsManualWritten {
-        symbol: String,
-        as_of_date: String,
-        source: String,
-    },
-    /// Ticker-intake Phase 1 — emitted by `TickerPrimerService` after the
-    /// post-add fundamentals → projection → news chain completes (success
-    /// or partial). `outcome` carries per-step status so the workspace
-    /// can decide which panels to refresh without a full re-fetch.
-    TickerPrimingDone {
-        symbol: String,
-        outcome: TickerPrimingOutcome,
-    },
-
-    /// Phase 8 (quant-decisions) — emitted whenever the
-    /// `PortfolioRiskService` recomputes its snapshot (executions tick,
-    /// bracket revision, 60s scheduled refresh). The frontend's
-    /// RiskSnapshot card subscribes to this so the dollar-risk header
-    /// stays live without polling. The payload is a compact summary;
-    /// full exposures live in the `portfolio_snapshots` row identified
-    /// by `snapshot_id`.
-    PortfolioRiskChanged {
-        snapshot_id: i64,
-        account: String,
-        nlv_cents: i64,
-        total_dollar_risk_cents: i64,
-        open_position_count: usize,
-    },
-
-    /// Phase 9 (quant-decisions) — emitted whenever the
-    /// `RegimeService` recomputes its snapshot AND the stable view
-    /// flips on at least one axis. The first snapshot of a session
-    /// also emits (None → Some). Frontend's RegimeIndicator
-    /// subscribes so the pill stays live without polling.
-    RegimeChanged {
-        snapshot_id: i64,
-        regime: Regime,
-        /// One of `daily_close` | `intraday` | `force_recompute`.
-        source: String,
-    },
-
-    /// Phase 11 (quant-decisions) — emitted when the tilt guard
-    /// activates a new pause (cumulative-R floor or two-consecutive
-    /// closed losses). UI banner subscribes so it can render the red
-    /// state without polling. `auto_reset_at` is the next session
-    /// open in UTC; the banner formats it for ET.
-    TiltActivated {
-        episode_id: i64,
-        account: String,
-        trigger_kind: String,
-        cumulative_r: f64,
-        auto_reset_at: chrono::DateTime<chrono::Utc>,
-    },
-    /// Phase 11 — emitted when the tilt guard releases a pause. Two
-    /// release flavors carry through: `auto` (next-session-open
-    /// reset) and `manual_override` (trader dismissed with a reason).
-    /// `session_end` flips don't emit — they're audit-only.
-    TiltReleased {
-        episode_id: i64,
-        account: String,
-        /// One of `auto` | `manual_override`.
-        release_kind: String,
-    },
-
-    // System events
-    RateLimitWarning {
-        remaining: u32,
-    },
-    SystemError {
-        error: String,
-    },
-}
-
-impl AppEvent {
-    fn name(&self) -> &'static str {
-        match self {
-            AppEvent::ConnectionStatusChanged { .. } => "connection-status-changed",
-            AppEvent::ConnectionError { .. } => "connection-error",
-            AppEvent::AccountUpdate { .. } => "account-update",
-            AppEvent::AccountsListChanged { .. } => "accounts-list-changed",
-            AppEvent::DailyPnLUpdate { .. } => "daily-pnl-update",
-            AppEvent::MarketDataUpdate { .. } => "market-data-update",
-            AppEvent::MarketDataSubscribed { .. } => "market-data-subscribed",
-            AppEvent::MarketDataUnsubscribed { .. } => "market-data-unsubscribed",
-            AppEvent::DataTierDetected { .. } => "data-tier-detected",
-            AppEvent::OrderPlaced { .. } => "order-placed",
-            AppEvent::OrderFilled { .. } => "order-filled",
-            AppEvent::OrderCancelled { .. } => "order-cancelled",
-            AppEvent::OrderError { .. } => "order-error",
-            AppEvent::BracketPlaced { .. } => "bracket-placed",
-            AppEvent::BracketStatusChanged { .. } => "bracket-status-changed",
-            AppEvent::PositionUpdate { .. } => "position-update",
-            AppEvent::PositionsRefreshed => "positions-refreshed",
-            AppEvent::ScannerUpdate { .. } => "scanner-update",
-            AppEvent::SetupDetected { .. } => "setup-detected",
-            AppEvent::SetupSized { .. } => "setup-sized",
-            AppEvent::SetupInvalidated { .. } => "setup-invalidated",
-            AppEvent::SetupSkipped { .. } => "setup-skipped",
-            AppEvent::TickerStatusChanged { .. } => "ticker-status-changed",
-            AppEvent::MorningPackReady { .. } => "morning-pack-ready",
-            AppEvent::ResearchNoteWritten { .. } => "research-note-written",
-            AppEvent::AgentMorningPackWritten { .. } => "agent-morning-pack-written",
-            AppEvent::TradeReviewWritten { .. } => "trade-review-written",
-            AppEvent::PlaybookWritten { .. } => "playbook-written",
-            AppEvent::AlertDecisionRecorded { .. } => "alert-decision-recorded",
-            AppEvent::AlertEnriched { .. } => "alert-enriched",
-            AppEvent::AlertDiveSkipped { .. } => "alert-dive-skipped",
-            AppEvent::FundamentalsManualWritten { .. } => "fundamentals-manual-written",
-            AppEvent::TickerPrimingDone { .. } => "ticker-priming-done",
-            AppEvent::PortfolioRiskChanged { .. } => "portfolio-risk-changed",
-            AppEvent::RegimeChanged { .. } => "regime-changed",
-            AppEvent::TiltActivated { .. } => "tilt-activated",
-            AppEvent::TiltReleased { .. } => "tilt-released",
-            AppEvent::RateLimitWarning { .. } => "rate-limit-warning",
-            AppEvent::SystemError { .. } => "system-error",
-        }
-    }
-}
+use super::account_alias::with_account_alias;
+use super::windows::WindowRoute;
+use super::AppEvent;
+use crate::config::AppConfig;
 
 pub struct EventEmitter {
     app_handle: Arc<RwLock<Option<AppHandle>>>,
@@ -351,6 +15,19 @@ pub struct EventEmitter {
     /// tests assert on emissions without standing up a full Tauri
     /// runtime. Production code never enables this.
     capture: Arc<RwLock<Option<Vec<AppEvent>>>>,
+    /// Live settings, read for `accounts.aliases` on every emit.
+    settings: OnceLock<Arc<RwLock<AppConfig>>>,
+    /// Backend observer of every emitted event (see [`EventSink`]).
+    sink: OnceLock<Arc<dyn EventSink>>,
+    /// Registered windows by label (see `events::windows`).
+    windows: Arc<RwLock<HashMap<String, WindowRoute>>>,
+}
+
+/// Backend consumer of the event stream, e.g. `services::notifications`
+/// forwarding alerts off-device. `observe` runs inline in `emit`, so it
+/// must not block; slow work belongs in a spawned task.
+pub trait EventSink: Send + Sync {
+    fn observe(&self, event: &AppEvent);
 }
 
 #[allow(dead_code)]
@@ -359,6 +36,9 @@ impl EventEmitter {
         Self {
             app_handle: Arc::new(RwLock::new(None)),
             capture: Arc::new(RwLock::new(None)),
+            settings: OnceLock::new(),
+            sink: OnceLock::new(),
+            windows: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -369,6 +49,9 @@ impl EventEmitter {
         Self {
             app_handle: Arc::new(RwLock::new(None)),
             capture: Arc::new(RwLock::new(Some(Vec::new()))),
+            settings: OnceLock::new(),
+            sink: OnceLock::new(),
+            windows: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -393,7 +76,45 @@ impl EventEmitter {
         *app_handle = Some(handle);
     }
 
+    pub fn attach_settings(&self, config: Arc<RwLock<AppConfig>>) {
+        let _ = self.settings.set(config);
+    }
+
+    pub fn attach_sink(&self, sink: Arc<dyn EventSink>) {
+        let _ = self.sink.set(sink);
+    }
+
+    /// Send `label` only the events `route` accepts, replacing any
+    /// earlier route for it.
+    pub async fn route_window(&self, label: &str, route: WindowRoute) {
+        self.windows
+            .write()
+            .await
+            .insert(label.to_string(), route.normalized());
+    }
+
+    /// Back to every event; also called when the window closes.
+    pub async fn unroute_window(&self, label: &str) -> bool {
+        self.windows.write().await.remove(label).is_some()
+    }
+
+    pub async fn window_routes(&self) -> HashMap<String, WindowRoute> {
+        self.windows.read().await.clone()
+    }
+
+    async fn payload(&self, event: &AppEvent) -> serde_json::Value {
+        match self.settings.get() {
+            Some(config) => with_account_alias(event, &config.read().await.accounts.aliases),
+            None => with_account_alias(event, &Default::default()),
+        }
+    }
+
     pub async fn emit(&self, event: AppEvent) -> Result<(), String> {
+        metrics::counter!(crate::telemetry::EVENTS_EMITTED_TOTAL, "event" => event.name())
+            .increment(1);
+        if let Some(sink) = self.sink.get() {
+            sink.observe(&event);
+        }
         // Record into the capture buffer first (if enabled). We clone
         // the event so the dispatch path below still owns its copy.
         let captured = {
@@ -408,9 +129,25 @@ impl EventEmitter {
 
         let app_handle = self.app_handle.read().await;
         if let Some(handle) = app_handle.as_ref() {
-            handle
-                .emit(event.name(), &event)
-                .map_err(|e| format!("Failed to emit event: {e}"))?;
+            let payload = self.payload(&event).await;
+            let routes = self.windows.read().await;
+            let blocked: Vec<&str> = routes
+                .iter()
+                .filter(|(_, route)| !route.accepts(&event))
+                .map(|(label, _)| label.as_str())
+                .collect();
+            let sent = if blocked.is_empty() {
+                handle.emit(event.name(), payload)
+            } else {
+                handle.emit_filter(event.name(), payload, |target| match target {
+                    EventTarget::AnyLabel { label }
+                    | EventTarget::Window { label }
+                    | EventTarget::Webview { label }
+                    | EventTarget::WebviewWindow { label } => !blocked.contains(&label.as_str()),
+                    _ => true,
+                })
+            };
+            sent.map_err(|e| format!("Failed to emit event: {e}"))?;
             return Ok(());
         }
 
@@ -426,9 +163,12 @@ impl EventEmitter {
         let app_handle = self.app_handle.read().await;
 
         if let Some(handle) = app_handle.as_ref() {
-            if let Some(window) = handle.get_webview_window(window) {
-                window
-                    .emit(event.name(), &event)
+            if handle.get_webview_window(window).is_some() {
+                // `WebviewWindow::emit` reaches every window; target
+                // the label instead.
+                let payload = self.payload(&event).await;
+                handle
+                    .emit_to(EventTarget::webview_window(window), event.name(), payload)
                     .map_err(|e| format!("Failed to emit event to window: {e}"))?;
 
                 Ok(())
@@ -1,3 +1,9 @@
+mod account_alias;
+mod app_event;
 pub mod emitter;
+mod names;
+mod windows;
 
-pub use emitter::{AppEvent, EventEmitter};
+pub use app_event::AppEvent;
+pub use emitter::{EventEmitter, EventSink};
+pub use windows::WindowRoute;
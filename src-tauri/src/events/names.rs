@@ -0,0 +1,66 @@
+//! Tauri event names for each [`AppEvent`] variant; the frontend
+//! subscribes by these strings.
+
+use super::AppEvent;
+
+impl AppEvent {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            AppEvent::ConnectionStatusChanged { .. } => "connection-status-changed",
+            AppEvent::ConnectionError { .. } => "connection-error",
+            AppEvent::ConnectionDegraded { .. } => "connection-degraded",
+            AppEvent::AccountUpdate { .. } => "account-update",
+            AppEvent::AccountsListChanged { .. } => "accounts-list-changed",
+            AppEvent::DailyPnLUpdate { .. } => "daily-pnl-update",
+            AppEvent::MarketDataUpdate { .. } => "market-data-update",
+            AppEvent::MarketDataSubscribed { .. } => "market-data-subscribed",
+            AppEvent::MarketDataUnsubscribed { .. } => "market-data-unsubscribed",
+            AppEvent::DataTierDetected { .. } => "data-tier-detected",
+            AppEvent::OrderPlaced { .. } => "order-placed",
+            AppEvent::OrderFilled { .. } => "order-filled",
+            AppEvent::OrderCancelled { .. } => "order-cancelled",
+            AppEvent::OrderError { .. } => "order-error",
+            AppEvent::BracketPlaced { .. } => "bracket-placed",
+            AppEvent::BracketStatusChanged { .. } => "bracket-status-changed",
+            AppEvent::PositionUpdate { .. } => "position-update",
+            AppEvent::PositionsRefreshed => "positions-refreshed",
+            AppEvent::ScannerUpdate { .. } => "scanner-update",
+            AppEvent::SetupDetected { .. } => "setup-detected",
+            AppEvent::SetupSized { .. } => "setup-sized",
+            AppEvent::SetupInvalidated { .. } => "setup-invalidated",
+            AppEvent::SetupSkipped { .. } => "setup-skipped",
+            AppEvent::TickerStatusChanged { .. } => "ticker-status-changed",
+            AppEvent::MorningPackReady { .. } => "morning-pack-ready",
+            AppEvent::ResearchNoteWritten { .. } => "research-note-written",
+            AppEvent::AgentMorningPackWritten { .. } => "agent-morning-pack-written",
+            AppEvent::TradeReviewWritten { .. } => "trade-review-written",
+            AppEvent::PlaybookWritten { .. } => "playbook-written",
+            AppEvent::AlertDecisionRecorded { .. } => "alert-decision-recorded",
+            AppEvent::AlertEnriched { .. } => "alert-enriched",
+            AppEvent::AlertDiveSkipped { .. } => "alert-dive-skipped",
+            AppEvent::FundamentalsManualWritten { .. } => "fundamentals-manual-written",
+            AppEvent::TickerPrimingDone { .. } => "ticker-priming-done",
+            AppEvent::PortfolioRiskChanged { .. } => "portfolio-risk-changed",
+            AppEvent::RegimeChanged { .. } => "regime-changed",
+            AppEvent::TiltActivated { .. } => "tilt-activated",
+            AppEvent::TiltReleased { .. } => "tilt-released",
+            AppEvent::FairValueCrossed { .. } => "fair-value-crossed",
+            AppEvent::MarginOfSafetyEntered { .. } => "margin-of-safety-entered",
+            AppEvent::PositionLevelHit { .. } => "position-level-hit",
+            AppEvent::StopBreached { .. } => "stop-breached",
+            AppEvent::ProjectionsUpdated { .. } => "projections-updated",
+            AppEvent::CashWarning { .. } => "cash-warning",
+            AppEvent::MarginCushionLow { .. } => "margin-cushion-low",
+            AppEvent::IdleCash { .. } => "idle-cash",
+            AppEvent::CorporateActionApplied { .. } => "corporate-action-applied",
+            AppEvent::OptionAssignmentRisk { .. } => "option-assignment-risk",
+            AppEvent::SimOrderFilled { .. } => "sim-order-filled",
+            AppEvent::RuleTriggered { .. } => "rule-triggered",
+            AppEvent::PortfolioGreeksUpdate { .. } => "portfolio-greeks-update",
+            AppEvent::JobProgress { .. } => "job-progress",
+            AppEvent::ScheduledJobFinished { .. } => "scheduled-job-finished",
+            AppEvent::RateLimitWarning { .. } => "rate-limit-warning",
+            AppEvent::SystemError { .. } => "system-error",
+        }
+    }
+}
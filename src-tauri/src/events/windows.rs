@@ -0,0 +1,133 @@
+//! Per-window event routing. A window that registers a [`WindowRoute`]
+//! (a pop-out ticker chart, say) is only sent the events it asked for;
+//! windows that never register, the main one included, keep getting
+//! everything.
+//!
+//! Routing filters by target, so it applies to listeners a window binds
+//! on itself (`getCurrentWebviewWindow().listen`); Tauri hands events
+//! to app-wide `listen` handlers regardless.
+
+use serde::{Deserialize, Serialize};
+
+use super::AppEvent;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowRoute {
+    /// Event names (`market-data-update`, ...); empty for all.
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Tickers; empty for any. With tickers set, an event without a
+    /// symbol only gets through when `events` names it.
+    #[serde(default)]
+    pub symbols: Vec<String>,
+}
+
+impl WindowRoute {
+    /// Trimmed, de-duplicated and sorted; tickers upper-cased.
+    pub fn normalized(self) -> Self {
+        fn clean(list: Vec<String>, f: fn(&str) -> String) -> Vec<String> {
+            let mut out: Vec<String> = list
+                .iter()
+                .map(|s| f(s.trim()))
+                .filter(|s| !s.is_empty())
+                .collect();
+            out.sort();
+            out.dedup();
+            out
+        }
+        Self {
+            events: clean(self.events, str::to_string),
+            symbols: clean(self.symbols, str::to_uppercase),
+        }
+    }
+
+    pub fn accepts(&self, event: &AppEvent) -> bool {
+        let named = self.events.iter().any(|e| e == event.name());
+        if !self.events.is_empty() && !named {
+            return false;
+        }
+        if self.symbols.is_empty() {
+            return true;
+        }
+        match event.symbol() {
+            Some(symbol) => self.symbols.iter().any(|s| s.eq_ignore_ascii_case(symbol)),
+            None => named,
+        }
+    }
+}
+
+impl AppEvent {
+    /// The ticker an event is about, for events about one.
+    pub(crate) fn symbol(&self) -> Option<&str> {
+        match self {
+            AppEvent::MarketDataUpdate { symbol, .. }
+            | AppEvent::MarketDataSubscribed { symbol }
+            | AppEvent::MarketDataUnsubscribed { symbol }
+            | AppEvent::OrderPlaced { symbol, .. }
+            | AppEvent::BracketPlaced { symbol, .. }
+            | AppEvent::PositionUpdate { symbol, .. }
+            | AppEvent::SetupSized { symbol, .. }
+            | AppEvent::SetupInvalidated { symbol, .. }
+            | AppEvent::SetupSkipped { symbol, .. }
+            | AppEvent::TickerStatusChanged { symbol, .. }
+            | AppEvent::ResearchNoteWritten { symbol, .. }
+            | AppEvent::FundamentalsManualWritten { symbol, .. }
+            | AppEvent::TickerPrimingDone { symbol, .. }
+            | AppEvent::FairValueCrossed { symbol, .. }
+            | AppEvent::MarginOfSafetyEntered { symbol, .. }
+            | AppEvent::OptionAssignmentRisk { symbol, .. } => Some(symbol.as_str()),
+            AppEvent::SetupDetected { setup, .. } => Some(setup.symbol.as_str()),
+            AppEvent::CorporateActionApplied { action, .. } => Some(action.symbol()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(symbol: &str) -> AppEvent {
+        AppEvent::MarketDataUpdate {
+            symbol: symbol.to_string(),
+            data: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn routes_by_event_name_and_symbol() {
+        let chart = WindowRoute {
+            events: vec![
+                "market-data-update".into(),
+                "connection-status-changed".into(),
+            ],
+            symbols: vec![" aapl ".into()],
+        }
+        .normalized();
+        assert_eq!(chart.symbols, vec!["AAPL"]);
+        assert!(chart.accepts(&quote("AAPL")));
+        assert!(!chart.accepts(&quote("MSFT")));
+        assert!(!chart.accepts(&AppEvent::PositionUpdate {
+            symbol: "AAPL".into(),
+            position: 10.0,
+        }));
+        // Named, symbol-less events still arrive.
+        assert!(chart.accepts(&AppEvent::ConnectionStatusChanged {
+            connected: true,
+            message: String::new(),
+        }));
+
+        // Symbols alone: that ticker's events only.
+        let ticker = WindowRoute {
+            events: Vec::new(),
+            symbols: vec!["AAPL".into()],
+        };
+        assert!(ticker.accepts(&AppEvent::PositionUpdate {
+            symbol: "AAPL".into(),
+            position: 10.0,
+        }));
+        assert!(!ticker.accepts(&AppEvent::PositionsRefreshed));
+        assert!(WindowRoute::default().accepts(&AppEvent::PositionsRefreshed));
+    }
+}
@@ -0,0 +1,101 @@
+//! Optional read-only localhost REST API.
+//!
+//! Sibling of `mcp/` for consumers that speak plain HTTP rather than MCP
+//! (Obsidian / Notion dashboards, shell scripts, cron jobs). Same contract
+//! as the MCP surface: read-only, and there are no order routes — ever.
+//!
+//! Ships dark: [`HttpApiConfig::enabled`] defaults to `false`. When enabled
+//! the listener binds `127.0.0.1` only and every request must carry
+//! `Authorization: Bearer <token>`. A missing / empty token refuses to
+//! start rather than serving unauthenticated — see [`server::HttpApiServer`].
+//!
+//! Routes (all `GET`, JSON bodies, camelCase where the wire DTO already is):
+//!   - `/v1/health`                    — liveness probe
+//!   - `/v1/positions?account=`        — live IBKR positions
+//!   - `/v1/fundamentals/{symbol}`     — cached fundamentals, 404 if none
+//!   - `/v1/projections/{symbol}`      — latest stored projection, 404 if none
+//!   - `/v1/cached-tickers`            — symbols with a valid AV cache entry
+//!   - `/v1/position-health?account=`  — per-position health score and light
+//!   - `/v1/metrics`                   — Prometheus text (`telemetry/`)
+//!
+//! Nothing here reaches Alpha Vantage: fundamentals come from the manual
+//! store and the AV file cache (as the screener reads them), projections
+//! from `projection_snapshots`.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::mcp::ibkr_seam::AccountReader;
+use crate::services::cache_service::CacheService;
+use crate::services::position_health::PositionHealthService;
+use crate::services::projection_history::ProjectionHistoryStore;
+use crate::services::screener::CachedFundamentals;
+
+pub mod routes;
+pub mod server;
+
+#[cfg(test)]
+mod tests;
+
+pub use server::HttpApiServer;
+
+/// `AppConfig.http_api` section. `#[serde(default)]` on the parent field
+/// keeps pre-existing settings.json files parseable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpApiConfig {
+    pub enabled: bool,
+    /// Loopback port. The bind address is not configurable on purpose.
+    pub port: u16,
+    /// Shared secret for the `Authorization: Bearer` header. Defaults to
+    /// the `QK_HTTP_API_TOKEN` env var so the token can stay out of
+    /// settings.json.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+impl Default for HttpApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 17890,
+            token: std::env::var("QK_HTTP_API_TOKEN").ok(),
+        }
+    }
+}
+
+/// Shared handler state. Cheap to clone — every field is an `Arc`.
+#[derive(Clone)]
+pub struct HttpApiState {
+    pub(crate) accounts: Arc<dyn AccountReader>,
+    pub(crate) fundamentals: Arc<dyn CachedFundamentals>,
+    pub(crate) projections: Arc<ProjectionHistoryStore>,
+    pub(crate) av_cache: Arc<CacheService>,
+    /// `None` answers `/v1/position-health` with 503.
+    pub(crate) position_health: Option<Arc<PositionHealthService>>,
+    pub(crate) token: Arc<str>,
+}
+
+impl HttpApiState {
+    pub fn new(
+        accounts: Arc<dyn AccountReader>,
+        fundamentals: Arc<dyn CachedFundamentals>,
+        projections: Arc<ProjectionHistoryStore>,
+        av_cache: Arc<CacheService>,
+        token: impl Into<Arc<str>>,
+    ) -> Self {
+        Self {
+            accounts,
+            fundamentals,
+            projections,
+            av_cache,
+            position_health: None,
+            token: token.into(),
+        }
+    }
+
+    pub fn with_position_health(mut self, health: Arc<PositionHealthService>) -> Self {
+        self.position_health = Some(health);
+        self
+    }
+}
@@ -0,0 +1,230 @@
+//! Route table, bearer-token guard and JSON error mapping for the
+//! localhost HTTP API. Handlers are thin: positions go through the same
+//! [`AccountReader`] seam as the MCP tools; fundamentals and projections
+//! are read from what is already stored ([`CachedFundamentals`],
+//! [`ProjectionHistoryStore`]), never fetched.
+//!
+//! [`AccountReader`]: crate::mcp::ibkr_seam::AccountReader
+//! [`CachedFundamentals`]: crate::services::screener::CachedFundamentals
+//! [`ProjectionHistoryStore`]: crate::services::projection_history::ProjectionHistoryStore
+
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::{FundamentalData, Position};
+use crate::mcp::tools::pick_account;
+use crate::services::position_health::PositionHealthReport;
+use crate::services::projection_history::ProjectionSnapshot;
+use crate::services::{fundamentals_growth, fundamentals_quality};
+use crate::utils::symbols;
+
+use super::HttpApiState;
+
+/// Error body is always `{"error": "<code>", "message": "<detail>"}`.
+/// `code` is a stable discriminant (mirrors the Tauri command strings:
+/// `disconnected`, `no_data`, ...) so scripts can switch on it.
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn unauthorized() -> Self {
+        Self::new(
+            StatusCode::UNAUTHORIZED,
+            "unauthorized",
+            "missing or invalid bearer token",
+        )
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = json!({ "error": self.code, "message": self.message });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+impl From<IbkrError> for ApiError {
+    fn from(e: IbkrError) -> Self {
+        match e {
+            IbkrError::NotConnected => Self::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "disconnected",
+                e.to_string(),
+            ),
+            IbkrError::Timeout(_) => {
+                Self::new(StatusCode::GATEWAY_TIMEOUT, "timeout", e.to_string())
+            }
+            other => Self::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "fetch_failed",
+                other.to_string(),
+            ),
+        }
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+/// Build the full router, auth layer included. Public so tests can serve
+/// it on an ephemeral port without going through [`super::HttpApiServer`].
+pub fn router(state: HttpApiState) -> Router {
+    Router::new()
+        .route("/v1/health", get(health))
+        .route("/v1/positions", get(positions))
+        .route("/v1/fundamentals/{symbol}", get(fundamentals))
+        .route("/v1/projections/{symbol}", get(projections))
+        .route("/v1/cached-tickers", get(cached_tickers))
+        .route("/v1/position-health", get(position_health))
+        .route("/v1/metrics", get(metrics_text))
+        .layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state)
+}
+
+async fn require_token(
+    State(state): State<HttpApiState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let presented = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or("");
+    if !constant_time_eq(presented.as_bytes(), state.token.as_bytes()) {
+        return Err(ApiError::unauthorized());
+    }
+    Ok(next.run(req).await)
+}
+
+/// Length leaks (tokens are fixed-length in practice); contents don't.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+async fn health() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+/// Prometheus text format, so a local scraper can point straight at it.
+async fn metrics_text() -> Response {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        crate::telemetry::render(),
+    )
+        .into_response()
+}
+
+#[derive(Debug, Deserialize)]
+struct PositionsQuery {
+    account: Option<String>,
+}
+
+async fn positions(
+    State(state): State<HttpApiState>,
+    Query(q): Query<PositionsQuery>,
+) -> ApiResult<Vec<Position>> {
+    let accounts = state.accounts.list_accounts().await?;
+    let account = pick_account(&accounts, q.account.as_deref())
+        .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, "bad_account", e))?;
+    Ok(Json(state.accounts.get_positions(&account).await?))
+}
+
+async fn position_health(
+    State(state): State<HttpApiState>,
+    Query(q): Query<PositionsQuery>,
+) -> ApiResult<PositionHealthReport> {
+    let health = state.position_health.as_ref().ok_or_else(|| {
+        ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "unavailable",
+            "position health is not wired up",
+        )
+    })?;
+    let report = health.report(q.account).await.map_err(|e| {
+        ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "fetch_failed",
+            e.to_string(),
+        )
+    })?;
+    Ok(Json(report))
+}
+
+async fn fundamentals(
+    State(state): State<HttpApiState>,
+    Path(symbol): Path<String>,
+) -> ApiResult<FundamentalData> {
+    let symbol = symbols::normalize(&symbol);
+    let mut data = state.fundamentals.load(&symbol).await.ok_or_else(|| {
+        ApiError::new(
+            StatusCode::NOT_FOUND,
+            "no_data",
+            format!("no cached fundamentals for {symbol}"),
+        )
+    })?;
+    fundamentals_quality::annotate(&mut data);
+    fundamentals_growth::annotate(&mut data);
+    Ok(Json(data))
+}
+
+/// The symbol's latest projection as the analysis screen stored it,
+/// with the assumptions it ran under.
+async fn projections(
+    State(state): State<HttpApiState>,
+    Path(symbol): Path<String>,
+) -> ApiResult<ProjectionSnapshot> {
+    let symbol = symbols::normalize(&symbol);
+    let snapshot = state
+        .projections
+        .latest(&symbol)
+        .await
+        .map_err(|e| {
+            ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "fetch_failed",
+                e.to_string(),
+            )
+        })?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                "no_data",
+                format!("no stored projection for {symbol}"),
+            )
+        })?;
+    Ok(Json(snapshot))
+}
+
+async fn cached_tickers(State(state): State<HttpApiState>) -> ApiResult<Vec<String>> {
+    let symbols = state.av_cache.list_cached_symbols().map_err(|e| {
+        ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "fetch_failed",
+            e.to_string(),
+        )
+    })?;
+    Ok(Json(symbols))
+}
@@ -0,0 +1,69 @@
+//! Loopback listener for the HTTP API. Lifecycle mirrors
+//! [`crate::mcp::server::McpServer`]: [`HttpApiServer::start`] returns a
+//! [`StreamHandle`] the runtime stores on `IbkrState::http_api_handle`.
+//!
+//! axum's graceful shutdown takes a future, so the shutdown flag is polled
+//! at [`SHUTDOWN_POLL_INTERVAL`] — same polling-with-timeout pattern as the
+//! MCP accept loop. In-flight requests drain before the task exits.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+use crate::ibkr::client::StreamHandle;
+
+use super::{routes, HttpApiState};
+
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct HttpApiServer {
+    state: HttpApiState,
+    port: u16,
+}
+
+impl HttpApiServer {
+    pub fn new(state: HttpApiState, port: u16) -> Self {
+        Self { state, port }
+    }
+
+    /// Bind `127.0.0.1:<port>` and spawn the serve loop. Refuses to start
+    /// with an empty token: an unauthenticated listener is never the
+    /// intended outcome, even on loopback. `port = 0` picks an ephemeral
+    /// port (tests); the bound address is returned alongside the handle.
+    pub async fn start(self) -> std::io::Result<(StreamHandle, SocketAddr)> {
+        if self.state.token.trim().is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "http_api.token is empty; refusing to serve without auth",
+            ));
+        }
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, self.port)).await?;
+        let addr = listener.local_addr()?;
+        info!("HTTP API listening on http://{addr}");
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let shutdown_task = Arc::clone(&shutdown);
+        let app = routes::router(self.state);
+
+        let join = tokio::spawn(async move {
+            let signal = async move {
+                while !shutdown_task.load(Ordering::Relaxed) {
+                    tokio::time::sleep(SHUTDOWN_POLL_INTERVAL).await;
+                }
+            };
+            if let Err(e) = axum::serve(listener, app)
+                .with_graceful_shutdown(signal)
+                .await
+            {
+                warn!("HTTP API server exited with error: {e}");
+            }
+            info!("HTTP API server stopped");
+        });
+
+        Ok((StreamHandle::new("http-api", shutdown, join), addr))
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tempfile::{NamedTempFile, TempDir};
+
+use super::*;
+use crate::ibkr::mocks::MockIbkrClient;
+use crate::ibkr::types::{
+    CurrentMetrics, FundamentalData, HistoricalFinancial, Position, ProjectionAssumptions,
+};
+use crate::services::projection_service::ProjectionService;
+use crate::storage::Db;
+
+const TOKEN: &str = "test-token";
+
+/// Cached fundamentals by symbol; nothing is ever fetched.
+#[derive(Default)]
+struct FakeCache(HashMap<String, FundamentalData>);
+
+#[async_trait]
+impl CachedFundamentals for FakeCache {
+    async fn symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<_> = self.0.keys().cloned().collect();
+        symbols.sort();
+        symbols
+    }
+
+    async fn load(&self, symbol: &str) -> Option<FundamentalData> {
+        self.0.get(symbol).cloned()
+    }
+}
+
+struct Harness {
+    base: String,
+    handle: crate::ibkr::client::StreamHandle,
+    projections: Arc<ProjectionHistoryStore>,
+    _cache_dir: TempDir,
+    _db: NamedTempFile,
+}
+
+async fn start(mock: MockIbkrClient, fundamentals: FakeCache) -> Harness {
+    let cache_dir = TempDir::new().unwrap();
+    let cache = Arc::new(CacheService::new(cache_dir.path()).unwrap());
+    cache.write("NVDA_overview", &"{}").unwrap();
+    let db = NamedTempFile::new().unwrap();
+    let projections = Arc::new(ProjectionHistoryStore::new(Arc::new(
+        Db::open(db.path()).unwrap(),
+    )));
+    let state = HttpApiState::new(
+        Arc::new(mock),
+        Arc::new(fundamentals),
+        Arc::clone(&projections),
+        cache,
+        TOKEN,
+    );
+    let (handle, addr) = HttpApiServer::new(state, 0).start().await.unwrap();
+    Harness {
+        base: format!("http://{addr}"),
+        handle,
+        projections,
+        _cache_dir: cache_dir,
+        _db: db,
+    }
+}
+
+async fn get(h: &Harness, path: &str, token: Option<&str>) -> (u16, serde_json::Value) {
+    let mut req = reqwest::Client::new().get(format!("{}{path}", h.base));
+    if let Some(t) = token {
+        req = req.bearer_auth(t);
+    }
+    let resp = req.send().await.unwrap();
+    let status = resp.status().as_u16();
+    (status, resp.json().await.unwrap())
+}
+
+fn sample_fundamentals(symbol: &str) -> FundamentalData {
+    FundamentalData {
+        symbol: symbol.to_string(),
+        historical: vec![HistoricalFinancial {
+            year: 2024,
+            revenue: 130.0,
+            net_income: 70.0,
+            eps: 2.9,
+            split_adjustment: None,
+        }],
+        analyst_estimates: None,
+        data_quality: Vec::new(),
+        overrides: Vec::new(),
+        growth: None,
+        currency: None,
+        fx_conversion: None,
+        segments: Vec::new(),
+        current_metrics: CurrentMetrics {
+            price: Some(120.0),
+            pe_ratio: 40.0,
+            shares_outstanding: 24.0,
+            name: None,
+            exchange: None,
+            market_cap: None,
+            dividend_yield: None,
+            short_interest: None,
+        },
+    }
+}
+
+#[tokio::test]
+async fn rejects_missing_and_wrong_token() {
+    let h = start(MockIbkrClient::new(), FakeCache::default()).await;
+
+    let (status, body) = get(&h, "/v1/health", None).await;
+    assert_eq!(status, 401);
+    assert_eq!(body["error"], "unauthorized");
+
+    let (status, _) = get(&h, "/v1/health", Some("nope")).await;
+    assert_eq!(status, 401);
+
+    let (status, body) = get(&h, "/v1/health", Some(TOKEN)).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["status"], "ok");
+    h.handle.stop().await;
+}
+
+#[tokio::test]
+async fn positions_resolve_single_account_and_map_disconnected() {
+    let mock = MockIbkrClient::new();
+    mock.set_positions(vec![Position {
+        account: "DU123456".into(),
+        symbol: "AAPL".into(),
+        position: 10.0,
+        ..Default::default()
+    }])
+    .await;
+    let h = start(mock.clone(), FakeCache::default()).await;
+
+    let (status, body) = get(&h, "/v1/positions", Some(TOKEN)).await;
+    assert_eq!(status, 503);
+    assert_eq!(body["error"], "disconnected");
+
+    mock.set_connected(true).await;
+    let (status, body) = get(&h, "/v1/positions", Some(TOKEN)).await;
+    assert_eq!(status, 200);
+    assert_eq!(body[0]["symbol"], "AAPL");
+
+    let (status, body) = get(&h, "/v1/positions?account=DU999", Some(TOKEN)).await;
+    assert_eq!(status, 400);
+    assert_eq!(body["error"], "bad_account");
+
+    // Not wired into this state.
+    let (status, body) = get(&h, "/v1/position-health", Some(TOKEN)).await;
+    assert_eq!(status, 503);
+    assert_eq!(body["error"], "unavailable");
+    h.handle.stop().await;
+}
+
+#[tokio::test]
+async fn fundamentals_projections_and_cached_tickers() {
+    let mut fundamentals = FakeCache::default();
+    fundamentals
+        .0
+        .insert("NVDA".to_string(), sample_fundamentals("NVDA"));
+    let h = start(MockIbkrClient::new(), fundamentals).await;
+
+    // Symbols are normalized before the cache lookup.
+    let (status, body) = get(&h, "/v1/fundamentals/nvda", Some(TOKEN)).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["symbol"], "NVDA");
+
+    let (status, body) = get(&h, "/v1/fundamentals/ZZZZ", Some(TOKEN)).await;
+    assert_eq!(status, 404);
+    assert_eq!(body["error"], "no_data");
+
+    // Cached fundamentals alone don't make a projection: only a stored
+    // one is served.
+    let (status, body) = get(&h, "/v1/projections/NVDA", Some(TOKEN)).await;
+    assert_eq!(status, 404);
+    assert_eq!(body["error"], "no_data");
+
+    let assumptions = ProjectionAssumptions::default();
+    let results =
+        ProjectionService::generate_projection_results(&sample_fundamentals("NVDA"), &assumptions)
+            .unwrap();
+    h.projections
+        .record("NVDA", &assumptions, &results)
+        .await
+        .unwrap();
+    let (status, body) = get(&h, "/v1/projections/nvda", Some(TOKEN)).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["symbol"], "NVDA");
+    assert_eq!(body["results"]["projections"].as_array().unwrap().len(), 5);
+
+    let (status, body) = get(&h, "/v1/cached-tickers", Some(TOKEN)).await;
+    assert_eq!(status, 200);
+    assert_eq!(body, serde_json::json!(["NVDA"]));
+    h.handle.stop().await;
+}
+
+#[tokio::test]
+async fn refuses_to_start_without_token() {
+    let cache_dir = TempDir::new().unwrap();
+    let db = NamedTempFile::new().unwrap();
+    let state = HttpApiState::new(
+        Arc::new(MockIbkrClient::new()),
+        Arc::new(FakeCache::default()),
+        Arc::new(ProjectionHistoryStore::new(Arc::new(
+            Db::open(db.path()).unwrap(),
+        ))),
+        Arc::new(CacheService::new(cache_dir.path()).unwrap()),
+        "  ",
+    );
+    assert!(HttpApiServer::new(state, 0).start().await.is_err());
+}
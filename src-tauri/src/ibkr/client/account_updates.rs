@@ -0,0 +1,178 @@
+//! `get_account_summary` / `get_positions` — both drain ibapi's
+//! `account_updates` stream until `AccountDownloadEnd`.
+//!
+//! The drain is bounded: it polls with a short timeout so it can notice
+//! the overall deadline (the executor's request timeout, i.e.
+//! `ibkr.connection_timeout_ms`) and a cancellation from
+//! `ibkr_cancel_request` (see [`super::requests`]). Either way the ibapi
+//! subscription is cancelled before returning, which releases
+//! `account_updates_lock` and the executor permit instead of leaving a
+//! blocking thread parked on a hung Gateway.
+
+use std::time::{Duration, Instant};
+
+use ibapi::accounts::types::AccountId;
+use ibapi::accounts::AccountUpdate;
+use ibapi::subscriptions::Subscription;
+
+use crate::ibkr::error::{IbkrError, Result};
+use crate::ibkr::types::{AccountSummary, Position};
+use crate::middleware::rate_limits;
+
+use super::requests::RequestTicket;
+use super::IbkrClient;
+
+/// How often the drain wakes to check the deadline / cancel flag.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Feed every update before `End` to `on_update`.
+fn drain(
+    stream: &Subscription<AccountUpdate>,
+    timeout: Duration,
+    ticket: &RequestTicket,
+    mut on_update: impl FnMut(AccountUpdate),
+) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if ticket.is_cancelled() {
+            stream.cancel();
+            return Err(IbkrError::Cancelled);
+        }
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            stream.cancel();
+            return Err(IbkrError::Timeout(timeout.as_millis() as u64));
+        }
+        match stream.next_timeout(left.min(POLL_INTERVAL)) {
+            Some(AccountUpdate::End) => return Ok(()),
+            Some(update) => on_update(update),
+            None => {
+                if let Some(e) = stream.error() {
+                    return Err(IbkrError::from(e));
+                }
+            }
+        }
+    }
+}
+
+impl IbkrClient {
+    pub async fn get_account_summary(&self, account: &str) -> Result<Vec<AccountSummary>> {
+        if let Some(demo) = self.demo() {
+            return demo.account_summary(account);
+        }
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_ACCOUNT).await?;
+
+        let account = account.to_string();
+        let ticket = self.requests.register("get_account_summary", &account);
+        let timeout = self.request_timeout();
+        let updates_guard = self.account_updates_lock.clone().lock_owned().await;
+
+        self.run_blocking(move || {
+            let _updates_guard = updates_guard;
+            let mut summaries = Vec::new();
+
+            // Use account_updates to get all account values
+            let account_id = AccountId(account.clone());
+            let stream = client_clone.account_updates(&account_id)?;
+            drain(&stream, timeout, &ticket, |update| {
+                // Ignore other update types (PortfolioValue, UpdateTime)
+                if let AccountUpdate::AccountValue(value) = update {
+                    tracing::info!(
+                        "Account value: key={}, value={}, currency={}",
+                        value.key,
+                        value.value,
+                        value.currency
+                    );
+                    summaries.push(AccountSummary {
+                        account: account.clone(),
+                        tag: value.key,
+                        value: value.value,
+                        currency: value.currency,
+                    });
+                }
+            })?;
+            tracing::info!("Account updates end reached");
+            Ok(summaries)
+        })
+        .await?
+    }
+
+    pub async fn get_positions(&self, account: &str) -> Result<Vec<Position>> {
+        if let Some(demo) = self.demo() {
+            return demo.positions(account);
+        }
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_ACCOUNT).await?;
+
+        let account = account.to_string();
+        let ticket = self.requests.register("get_positions", &account);
+        let timeout = self.request_timeout();
+        let updates_guard = self.account_updates_lock.clone().lock_owned().await;
+
+        self.run_blocking(move || {
+            let _updates_guard = updates_guard;
+            let mut positions = Vec::new();
+            // Use account_updates to get portfolio values with market data
+            let account_id = AccountId(account.clone());
+            let stream = client_clone.account_updates(&account_id)?;
+            drain(&stream, timeout, &ticket, |update| {
+                if let AccountUpdate::PortfolioValue(portfolio) = update {
+                    positions.push(to_position(portfolio, &account));
+                }
+            })?;
+            tracing::debug!("All portfolio positions received");
+            Ok(positions)
+        })
+        .await?
+    }
+}
+
+fn to_position(portfolio: ibapi::accounts::AccountPortfolioValue, account: &str) -> Position {
+    tracing::debug!(
+        "Portfolio position: symbol={}, position={}, market_price={}, market_value={}, unrealized_pnl={}",
+        portfolio.contract.symbol,
+        portfolio.position,
+        portfolio.market_price,
+        portfolio.market_value,
+        portfolio.unrealized_pnl
+    );
+    let contract_type = portfolio.contract.security_type.clone().to_string();
+    // Stock contracts come back with empty strings / 0.0 in the
+    // option-only fields; surface them as `None` so the JSON omits them
+    // entirely (see `Position` serde `skip_serializing_if`).
+    let is_option_like = matches!(contract_type.as_str(), "OPT" | "FOP" | "FUT" | "WAR");
+    let opt_string = |s: String| {
+        if is_option_like && !s.is_empty() {
+            Some(s)
+        } else {
+            None
+        }
+    };
+    let opt_f64 = |v: f64| {
+        if is_option_like && v != 0.0 {
+            Some(v)
+        } else {
+            None
+        }
+    };
+    Position {
+        account: portfolio
+            .account
+            .clone()
+            .unwrap_or_else(|| account.to_string()),
+        symbol: portfolio.contract.symbol.0.clone(),
+        position: portfolio.position,
+        average_cost: portfolio.average_cost,
+        market_price: portfolio.market_price,
+        market_value: portfolio.market_value,
+        unrealized_pnl: portfolio.unrealized_pnl,
+        realized_pnl: portfolio.realized_pnl,
+        contract_type,
+        currency: portfolio.contract.currency.0.clone(),
+        exchange: portfolio.contract.exchange.0.clone(),
+        local_symbol: portfolio.contract.local_symbol.clone(),
+        expiry: opt_string(portfolio.contract.last_trade_date_or_contract_month.clone()),
+        strike: opt_f64(portfolio.contract.strike),
+        right: opt_string(portfolio.contract.right.clone()),
+        multiplier: opt_string(portfolio.contract.multiplier.clone()),
+    }
+}
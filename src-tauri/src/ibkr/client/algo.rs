@@ -0,0 +1,179 @@
+//! `OrderRequest::algo` → ibapi `algo_strategy` / `algo_params`
+//! (VWAP, TWAP, Adaptive).
+//!
+//! Tag names are IBKR's (TWS API "IB Algos"). Times go out as
+//! `yyyymmdd-hh:mm:ss`, which IBKR reads as UTC, so no timezone name
+//! has to be spelled.
+
+use chrono::{DateTime, Utc};
+use ibapi::contracts::TagValue;
+use ibapi::orders::Order;
+
+use crate::ibkr::error::{IbkrError, Result};
+use crate::ibkr::types::{AdaptivePriority, AlgoParams, AlgoStrategy};
+
+/// IBKR rejects VWAP participation above 50%.
+const MAX_PARTICIPATION: f64 = 0.5;
+
+pub(super) fn apply_algo(order: &mut Order, algo: &AlgoParams) -> Result<()> {
+    if algo.priority.is_some() && algo.strategy != AlgoStrategy::Adaptive {
+        return Err(invalid("priority applies to Adaptive only"));
+    }
+    if algo.max_participation.is_some() && algo.strategy != AlgoStrategy::Vwap {
+        return Err(invalid("max_participation applies to VWAP only"));
+    }
+    let (strategy, params) = match algo.strategy {
+        AlgoStrategy::Vwap => ("Vwap", timed_params(algo, vwap_params(algo)?)?),
+        AlgoStrategy::Twap => (
+            "Twap",
+            timed_params(algo, vec![tag("strategyType", "Marketable")])?,
+        ),
+        AlgoStrategy::Adaptive => ("Adaptive", adaptive_params(order, algo)?),
+    };
+    order.algo_strategy = strategy.to_string();
+    order.algo_params = params;
+    Ok(())
+}
+
+fn vwap_params(algo: &AlgoParams) -> Result<Vec<TagValue>> {
+    let mut params = Vec::new();
+    if let Some(pct) = algo.max_participation {
+        if !(pct > 0.0 && pct <= MAX_PARTICIPATION) {
+            return Err(invalid(&format!(
+                "max_participation must be in (0, {MAX_PARTICIPATION}]"
+            )));
+        }
+        params.push(tag("maxPctVol", pct.to_string()));
+    }
+    params.push(tag("noTakeLiq", "0"));
+    Ok(params)
+}
+
+/// `params` plus the VWAP / TWAP working window.
+fn timed_params(algo: &AlgoParams, mut params: Vec<TagValue>) -> Result<Vec<TagValue>> {
+    if let (Some(start), Some(end)) = (algo.start_time, algo.end_time) {
+        if end <= start {
+            return Err(invalid("end_time must be after start_time"));
+        }
+    }
+    if let Some(start) = algo.start_time {
+        params.push(tag("startTime", algo_time(start)));
+    }
+    if let Some(end) = algo.end_time {
+        params.push(tag("endTime", algo_time(end)));
+    }
+    // Finish the remainder after `end_time` rather than leave it unfilled.
+    params.push(tag("allowPastEndTime", "1"));
+    Ok(params)
+}
+
+/// Adaptive only wraps `MKT` / `LMT` orders and has no time window.
+fn adaptive_params(order: &Order, algo: &AlgoParams) -> Result<Vec<TagValue>> {
+    if !matches!(order.order_type.as_str(), "MKT" | "LMT") {
+        return Err(invalid("Adaptive needs a Market or Limit order"));
+    }
+    if algo.start_time.is_some() || algo.end_time.is_some() {
+        return Err(invalid("Adaptive takes no start_time / end_time"));
+    }
+    let priority = match algo.priority.unwrap_or(AdaptivePriority::Normal) {
+        AdaptivePriority::Urgent => "Urgent",
+        AdaptivePriority::Normal => "Normal",
+        AdaptivePriority::Patient => "Patient",
+    };
+    Ok(vec![tag("adaptivePriority", priority)])
+}
+
+fn algo_time(t: DateTime<Utc>) -> String {
+    t.format("%Y%m%d-%H:%M:%S").to_string()
+}
+
+fn tag(name: &str, value: impl Into<String>) -> TagValue {
+    TagValue {
+        tag: name.to_string(),
+        value: value.into(),
+    }
+}
+
+fn invalid(msg: &str) -> IbkrError {
+    IbkrError::RequestFailed(format!("algo: {msg}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn params(strategy: AlgoStrategy, max_participation: Option<f64>) -> AlgoParams {
+        AlgoParams {
+            strategy,
+            start_time: Some(Utc.with_ymd_and_hms(2025, 2, 6, 15, 0, 0).unwrap()),
+            end_time: Some(Utc.with_ymd_and_hms(2025, 2, 6, 20, 30, 0).unwrap()),
+            max_participation,
+            priority: None,
+        }
+    }
+
+    fn value<'a>(order: &'a Order, name: &str) -> Option<&'a str> {
+        order
+            .algo_params
+            .iter()
+            .find(|t| t.tag == name)
+            .map(|t| t.value.as_str())
+    }
+
+    #[test]
+    fn vwap_maps_window_and_participation() {
+        let mut order = Order::default();
+        apply_algo(&mut order, &params(AlgoStrategy::Vwap, Some(0.1))).unwrap();
+        assert_eq!(order.algo_strategy, "Vwap");
+        assert_eq!(value(&order, "startTime"), Some("20250206-15:00:00"));
+        assert_eq!(value(&order, "endTime"), Some("20250206-20:30:00"));
+        assert_eq!(value(&order, "maxPctVol"), Some("0.1"));
+    }
+
+    #[test]
+    fn rejects_bad_participation_and_inverted_window() {
+        let mut order = Order::default();
+        assert!(apply_algo(&mut order, &params(AlgoStrategy::Vwap, Some(0.8))).is_err());
+        assert!(apply_algo(&mut order, &params(AlgoStrategy::Vwap, Some(0.0))).is_err());
+        assert!(apply_algo(&mut order, &params(AlgoStrategy::Twap, Some(0.1))).is_err());
+        let mut inverted = params(AlgoStrategy::Twap, None);
+        std::mem::swap(&mut inverted.start_time, &mut inverted.end_time);
+        assert!(apply_algo(&mut order, &inverted).is_err());
+
+        apply_algo(&mut order, &params(AlgoStrategy::Twap, None)).unwrap();
+        assert_eq!(order.algo_strategy, "Twap");
+        assert_eq!(value(&order, "strategyType"), Some("Marketable"));
+    }
+
+    #[test]
+    fn adaptive_wraps_market_and_limit_with_priority() {
+        let adaptive = |priority| AlgoParams {
+            strategy: AlgoStrategy::Adaptive,
+            start_time: None,
+            end_time: None,
+            max_participation: None,
+            priority,
+        };
+        let mut order = Order {
+            order_type: "LMT".to_string(),
+            ..Order::default()
+        };
+        apply_algo(&mut order, &adaptive(Some(AdaptivePriority::Patient))).unwrap();
+        assert_eq!(order.algo_strategy, "Adaptive");
+        assert_eq!(value(&order, "adaptivePriority"), Some("Patient"));
+
+        apply_algo(&mut order, &adaptive(None)).unwrap();
+        assert_eq!(value(&order, "adaptivePriority"), Some("Normal"));
+
+        let mut midprice = Order {
+            order_type: "MIDPRICE".to_string(),
+            ..Order::default()
+        };
+        assert!(apply_algo(&mut midprice, &adaptive(None)).is_err());
+        // Priority on a non-Adaptive algo is a caller mistake.
+        let mut vwap = params(AlgoStrategy::Vwap, None);
+        vwap.priority = Some(AdaptivePriority::Urgent);
+        assert!(apply_algo(&mut order, &vwap).is_err());
+    }
+}
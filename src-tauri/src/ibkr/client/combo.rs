@@ -0,0 +1,98 @@
+//! `place_combo_order` — a two-leg option spread as one IBKR `BAG`
+//! order.
+//!
+//! Each leg is resolved to its contract id first (combo legs are sent
+//! by id), then the bag goes out on SMART with the legs' ratios and
+//! sides. The request is validated before anything touches the
+//! gateway; see [`ComboOrderRequest::validate`].
+
+use ibapi::contracts::{ComboLeg as IbComboLeg, Contract, SecurityType};
+use ibapi::orders::{Action, Order};
+
+use crate::ibkr::error::{IbkrError, Result};
+use crate::ibkr::types::{ComboOrderRequest, OrderAction, OrderType};
+use crate::middleware::rate_limits;
+
+use super::contract::option_contract;
+use super::order_audit::submit;
+use super::IbkrClient;
+
+impl IbkrClient {
+    pub async fn place_combo_order(&self, request: ComboOrderRequest) -> Result<i32> {
+        request.validate().map_err(IbkrError::RequestFailed)?;
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_ORDERS).await?;
+        let sink = self.order_sink_snapshot();
+
+        self.run_blocking(move || -> Result<i32> {
+            let symbol = request.symbol.trim().to_uppercase();
+            let mut legs = Vec::with_capacity(request.legs.len());
+            for leg in &request.legs {
+                let contract = option_contract(&leg.option);
+                let contract_id = client_clone
+                    .contract_details(&contract)?
+                    .first()
+                    .map(|d| d.contract.contract_id)
+                    .ok_or_else(|| {
+                        IbkrError::RequestFailed(format!(
+                            "no contract for {} {} {}{}",
+                            symbol,
+                            leg.option.expiry,
+                            leg.option.strike,
+                            leg.option.right.as_str()
+                        ))
+                    })?;
+                legs.push(IbComboLeg {
+                    contract_id,
+                    ratio: leg.ratio as i32,
+                    action: match leg.action {
+                        OrderAction::Buy => "BUY",
+                        OrderAction::Sell => "SELL",
+                    }
+                    .to_string(),
+                    exchange: "SMART".to_string(),
+                    ..Default::default()
+                });
+            }
+            let currency = request.legs[0]
+                .option
+                .currency
+                .clone()
+                .unwrap_or_else(|| "USD".to_string());
+            let contract = Contract {
+                symbol: symbol.as_str().into(),
+                security_type: SecurityType::Spread,
+                exchange: "SMART".into(),
+                currency: currency.as_str().into(),
+                combo_legs: legs,
+                ..Default::default()
+            };
+
+            let (order_type, limit_price) = match request.order_type {
+                OrderType::Limit => ("LMT", request.price),
+                _ => ("MKT", None),
+            };
+            let order = Order {
+                action: match request.action {
+                    OrderAction::Buy => Action::Buy,
+                    OrderAction::Sell => Action::Sell,
+                },
+                total_quantity: request.quantity,
+                order_type: order_type.to_string(),
+                limit_price,
+                ..Default::default()
+            };
+
+            let order_id = client_clone.next_order_id();
+            submit(
+                &client_clone,
+                sink.as_ref(),
+                order_id,
+                &symbol,
+                &contract,
+                &order,
+            )?;
+            Ok(order_id)
+        })
+        .await?
+    }
+}
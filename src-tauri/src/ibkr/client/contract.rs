@@ -0,0 +1,143 @@
+//! Stock and option contract construction shared by the order and
+//! market-data paths, and the per-contract order-size rules that decide
+//! whether a fractional quantity can be sent.
+
+use ibapi::client::blocking::Client;
+use ibapi::contracts::{Contract, SecurityType};
+
+use crate::ibkr::error::Result;
+use crate::ibkr::types::{ContractRoute, OptionContract, SizeRules};
+use crate::middleware::rate_limits;
+use crate::utils::symbols;
+
+use super::IbkrClient;
+
+/// First gateway server version that accepts fractional order sizes
+/// (ibapi's `server_versions::FRACTIONAL_SIZE_SUPPORT`, not exported).
+const FRACTIONAL_SIZE_SUPPORT: i32 = 163;
+
+impl IbkrClient {
+    /// Order-size rules for `symbol` on `route` (default SMART / USD).
+    pub async fn get_size_rules(
+        &self,
+        symbol: &str,
+        route: Option<ContractRoute>,
+    ) -> Result<SizeRules> {
+        let client = self.ibapi_client(rate_limits::ENDPOINT_ORDERS).await?;
+        let contract = stock_contract(symbol, route.as_ref());
+        self.run_blocking(move || size_rules_blocking(&client, &contract))
+            .await?
+    }
+}
+
+/// `Contract::stock(symbol)` (SMART / USD), in IBKR's notation, with
+/// whatever `route` pins down layered on top.
+pub(super) fn stock_contract(symbol: &str, route: Option<&ContractRoute>) -> Contract {
+    let mut builder = Contract::stock(symbols::ibkr(symbol));
+    let Some(route) = route else {
+        return builder.build();
+    };
+    if let Some(exchange) = pinned(&route.exchange) {
+        builder = builder.on_exchange(exchange.to_uppercase());
+    }
+    if let Some(primary) = pinned(&route.primary_exchange) {
+        builder = builder.primary(primary.to_uppercase());
+    }
+    if let Some(currency) = pinned(&route.currency) {
+        builder = builder.in_currency(currency.to_uppercase());
+    }
+    builder.build()
+}
+
+/// `option` on SMART, in its currency (default USD).
+pub(super) fn option_contract(option: &OptionContract) -> Contract {
+    Contract {
+        symbol: symbols::ibkr(&option.symbol).as_str().into(),
+        security_type: SecurityType::Option,
+        exchange: "SMART".into(),
+        currency: pinned(&option.currency)
+            .map_or("USD".to_string(), str::to_uppercase)
+            .as_str()
+            .into(),
+        last_trade_date_or_contract_month: option.expiry.trim().to_string(),
+        strike: option.strike,
+        right: option.right.as_str().to_string(),
+        multiplier: pinned(&option.multiplier).unwrap_or_default().to_string(),
+        ..Default::default()
+    }
+}
+
+fn pinned(field: &Option<String>) -> Option<&str> {
+    field.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// Size rules from the contract's first details row. Gateways too old
+/// for fractional sizes, and contracts that report nothing usable,
+/// trade whole shares. Blocking.
+pub(super) fn size_rules_blocking(client: &Client, contract: &Contract) -> Result<SizeRules> {
+    if client.server_version() < FRACTIONAL_SIZE_SUPPORT {
+        return Ok(SizeRules::WHOLE_SHARES);
+    }
+    let details = client.contract_details(contract)?;
+    Ok(details
+        .first()
+        .map(|d| size_rules(d.min_size, d.size_increment))
+        .unwrap_or(SizeRules::WHOLE_SHARES))
+}
+
+fn size_rules(min_size: f64, size_increment: f64) -> SizeRules {
+    // Unset doubles arrive as 0 or `f64::MAX`.
+    let usable = |v: f64| v.is_finite() && v > 0.0 && v < f64::MAX;
+    if !usable(size_increment) {
+        return SizeRules::WHOLE_SHARES;
+    }
+    SizeRules {
+        min_size: if usable(min_size) {
+            min_size
+        } else {
+            size_increment
+        },
+        size_increment,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_overrides_only_the_fields_it_sets() {
+        let plain = stock_contract("SHOP", None);
+        assert_eq!(plain.exchange.as_str(), "SMART");
+        assert_eq!(stock_contract("brk.b", None).symbol.as_str(), "BRK B");
+        assert_eq!(plain.currency.as_str(), "USD");
+
+        let route = ContractRoute {
+            exchange: None,
+            primary_exchange: Some("tse".to_string()),
+            currency: Some("CAD".to_string()),
+        };
+        let routed = stock_contract("SHOP", Some(&route));
+        assert_eq!(routed.exchange.as_str(), "SMART");
+        assert_eq!(routed.primary_exchange.as_str(), "TSE");
+        assert_eq!(routed.currency.as_str(), "CAD");
+    }
+
+    #[test]
+    fn size_rules_validate_and_round_fractional_quantities() {
+        let whole = size_rules(0.0, f64::MAX);
+        assert_eq!(whole, SizeRules::WHOLE_SHARES);
+        assert!(whole.check(10.0).is_ok());
+        assert!(whole.check(2.5).is_err());
+        assert_eq!(whole.round_down(2.5), 2.0);
+
+        let fractional = size_rules(0.0001, 0.0001);
+        assert!(fractional.fractional());
+        assert!(fractional.check(0.1 + 0.2).is_ok());
+        assert!(fractional.check(1.23456).is_err());
+        assert!(fractional.check(0.00005).is_err());
+        assert!(fractional.check(0.0).is_err());
+        assert_eq!(fractional.round_down(1.23456), 1.2345);
+        assert_eq!(fractional.round_down(0.00005), 0.0);
+    }
+}
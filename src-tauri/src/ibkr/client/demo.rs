@@ -0,0 +1,480 @@
+//! Simulated account and market data for demo mode (`demo` in
+//! settings).
+//!
+//! With a [`DemoFeed`] installed (`IbkrClient::set_demo`), the client
+//! answers the calls the main screens make from memory instead of a
+//! Gateway: connection, accounts, account summary, positions,
+//! snapshots, historical bars, executions, open orders and plain stock
+//! orders. Every other IBKR call fails with [`UNSUPPORTED`].
+//!
+//! Prices follow a deterministic path per symbol (a slow cycle, a daily
+//! cycle and per-minute noise, all seeded from the symbol), so a
+//! snapshot agrees with the bar ending the same minute and a restart
+//! shows the same chart. Orders fill at once at the simulated price and
+//! move the book; nothing rests, so there are never open orders.
+
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::Mutex as StdMutex;
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+
+use crate::ibkr::error::{IbkrError, Result};
+use crate::ibkr::types::historical::{BarSize, HistoricalBar, HistoricalDataRequest};
+use crate::ibkr::types::{
+    AccountSummary, ExecutionSide, IbkrExecution, MarketDataSnapshot, OrderAction, OrderRequest,
+    OrderType, Position,
+};
+use crate::utils::symbols;
+
+mod fixtures;
+
+pub use fixtures::seed;
+use fixtures::{mix, price, round_cents, unit, volume, BOOK, COMMISSION, STARTING_CASH};
+
+/// The one account demo mode trades.
+pub const DEMO_ACCOUNT: &str = "DEMO0001";
+
+/// Error text for IBKR calls demo mode does not simulate.
+pub const UNSUPPORTED: &str = "not available in demo mode";
+
+/// Most bars one historical request returns.
+const MAX_BARS: i64 = 2_000;
+
+struct Holding {
+    symbol: String,
+    shares: f64,
+    average_cost: f64,
+}
+
+struct Book {
+    cash: f64,
+    realized_pnl: f64,
+    holdings: Vec<Holding>,
+    fills: Vec<IbkrExecution>,
+}
+
+pub struct DemoFeed {
+    connected: AtomicBool,
+    next_order_id: AtomicI32,
+    book: StdMutex<Book>,
+}
+
+impl Default for DemoFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DemoFeed {
+    pub fn new() -> Self {
+        let holdings = BOOK
+            .iter()
+            .map(|&(symbol, shares, average_cost)| Holding {
+                symbol: symbol.to_string(),
+                shares,
+                average_cost,
+            })
+            .collect();
+        Self {
+            connected: AtomicBool::new(false),
+            next_order_id: AtomicI32::new(1),
+            book: StdMutex::new(Book {
+                cash: STARTING_CASH,
+                realized_pnl: 0.0,
+                holdings,
+                fills: Vec::new(),
+            }),
+        }
+    }
+
+    pub fn connect(&self) {
+        self.connected.store(true, Ordering::SeqCst);
+    }
+
+    pub fn disconnect(&self) {
+        self.connected.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    fn ensure_connected(&self) -> Result<()> {
+        if self.is_connected() {
+            Ok(())
+        } else {
+            Err(IbkrError::NotConnected)
+        }
+    }
+
+    fn ensure_account(account: &str) -> Result<()> {
+        if account == DEMO_ACCOUNT {
+            Ok(())
+        } else {
+            Err(IbkrError::RequestFailed(format!(
+                "unknown account {account}; demo mode trades {DEMO_ACCOUNT}"
+            )))
+        }
+    }
+
+    pub fn accounts(&self) -> Result<Vec<String>> {
+        self.ensure_connected()?;
+        Ok(vec![DEMO_ACCOUNT.to_string()])
+    }
+
+    pub fn positions(&self, account: &str) -> Result<Vec<Position>> {
+        self.ensure_connected()?;
+        Self::ensure_account(account)?;
+        let now = Utc::now().timestamp();
+        let book = self.book.lock().expect("demo book poisoned");
+        Ok(book
+            .holdings
+            .iter()
+            .map(|h| {
+                let market_price = price(&h.symbol, now);
+                Position {
+                    account: DEMO_ACCOUNT.to_string(),
+                    symbol: h.symbol.clone(),
+                    position: h.shares,
+                    average_cost: h.average_cost,
+                    market_price,
+                    market_value: h.shares * market_price,
+                    unrealized_pnl: (market_price - h.average_cost) * h.shares,
+                    realized_pnl: 0.0,
+                    contract_type: "STK".to_string(),
+                    currency: "USD".to_string(),
+                    exchange: "SMART".to_string(),
+                    local_symbol: h.symbol.clone(),
+                    ..Position::default()
+                }
+            })
+            .collect())
+    }
+
+    pub fn account_summary(&self, account: &str) -> Result<Vec<AccountSummary>> {
+        let gross: f64 = self
+            .positions(account)?
+            .iter()
+            .map(|p| p.market_value.abs())
+            .sum();
+        let (cash, realized) = {
+            let book = self.book.lock().expect("demo book poisoned");
+            (book.cash, book.realized_pnl)
+        };
+        let net_liquidation = cash + gross;
+        let maint_margin = gross * 0.25;
+        let excess = net_liquidation - maint_margin;
+        let row = |tag: &str, value: f64| AccountSummary {
+            account: DEMO_ACCOUNT.to_string(),
+            tag: tag.to_string(),
+            value: format!("{value:.2}"),
+            currency: "USD".to_string(),
+        };
+        Ok(vec![
+            row("NetLiquidation", net_liquidation),
+            row("TotalCashValue", cash),
+            row("GrossPositionValue", gross),
+            row("BuyingPower", excess * 4.0),
+            row("AvailableFunds", excess),
+            row("ExcessLiquidity", excess),
+            row("MaintMarginReq", maint_margin),
+            AccountSummary {
+                value: format!("{:.4}", excess / net_liquidation),
+                ..row("Cushion", 0.0)
+            },
+            row("RealizedPnL", realized),
+        ])
+    }
+
+    pub fn snapshot(&self, symbol: &str) -> Result<MarketDataSnapshot> {
+        self.ensure_connected()?;
+        let symbol = symbols::normalize(symbol);
+        let now = Utc::now();
+        let t = now.timestamp();
+        let day_start = t - t.rem_euclid(86_400);
+        let last = price(&symbol, t);
+        let open = price(&symbol, day_start);
+        let seed = seed(&symbol);
+        Ok(MarketDataSnapshot {
+            symbol: symbol.clone(),
+            bid_price: Some(round_cents(last - 0.01)),
+            bid_size: Some(100 + (seed % 9) as i32 * 100),
+            ask_price: Some(round_cents(last + 0.01)),
+            ask_size: Some(100 + (seed % 7) as i32 * 100),
+            last_price: Some(last),
+            last_size: Some(100),
+            high: Some(last.max(open)),
+            low: Some(last.min(open)),
+            volume: Some(volume(&symbol, day_start, t)),
+            close: Some(price(&symbol, day_start - 1)),
+            open: Some(open),
+            last_rth_price: Some(last),
+            session: None,
+            timestamp: t,
+        })
+    }
+
+    /// Bars ending now, oldest first, over `request.duration`
+    /// (`N S|D|W|M|Y`). Daily bars skip weekends.
+    pub fn bars(&self, request: &HistoricalDataRequest) -> Result<Vec<HistoricalBar>> {
+        self.ensure_connected()?;
+        let symbol = symbols::normalize(&request.symbol);
+        let span = duration_secs(&request.duration).ok_or_else(|| {
+            IbkrError::RequestFailed(format!("invalid duration string '{}'", request.duration))
+        })?;
+        let step = bar_secs(request.bar_size);
+        let now = Utc::now().timestamp();
+        let last_start = now - now.rem_euclid(step);
+        let count = (span / step).clamp(1, MAX_BARS);
+        let mut bars = Vec::new();
+        for k in (0..count).rev() {
+            let start = last_start - k * step;
+            let Some(at) = DateTime::from_timestamp(start, 0) else {
+                continue;
+            };
+            if request.bar_size == BarSize::Day1
+                && matches!(at.weekday(), Weekday::Sat | Weekday::Sun)
+            {
+                continue;
+            }
+            let end = (start + step).min(now);
+            let open = price(&symbol, start);
+            let close = price(&symbol, end);
+            let wiggle = 1.0 + 0.004 * unit(mix(seed(&symbol) ^ start as u64)).abs();
+            let time = if request.bar_size == BarSize::Day1 {
+                at.format("%Y%m%d").to_string()
+            } else {
+                at.format("%Y%m%d %H:%M:%S").to_string()
+            };
+            bars.push(HistoricalBar {
+                time,
+                open,
+                high: round_cents(open.max(close) * wiggle),
+                low: round_cents(open.min(close) / wiggle),
+                close,
+                volume: volume(&symbol, start, end),
+                wap: round_cents((open + close) / 2.0),
+                count: ((end - start) / 6).max(1) as i32,
+            });
+        }
+        Ok(bars)
+    }
+
+    pub fn executions(&self, date: NaiveDate) -> Result<Vec<IbkrExecution>> {
+        self.ensure_connected()?;
+        let book = self.book.lock().expect("demo book poisoned");
+        Ok(book
+            .fills
+            .iter()
+            .filter(|f| f.exec_time.date_naive() == date)
+            .cloned()
+            .collect())
+    }
+
+    /// Fills `order` in full at once: market and midprice orders at
+    /// the simulated price, limits only when marketable. Stops, held
+    /// starts, options and selling more than is held are refused.
+    pub fn place_order(&self, order: &OrderRequest) -> Result<i32> {
+        self.ensure_connected()?;
+        let refuse = |why: &str| Err(IbkrError::RequestFailed(format!("{why} in demo mode")));
+        if order.option.is_some() {
+            return refuse("options can't be traded");
+        }
+        if order.start_at.is_some() {
+            return refuse("orders can't be held for later");
+        }
+        if !(order.quantity.is_finite() && order.quantity > 0.0) {
+            return refuse("quantity must be positive");
+        }
+        let symbol = symbols::normalize(&order.symbol);
+        let now = Utc::now();
+        let market = price(&symbol, now.timestamp());
+        let fill_price = match (&order.order_type, order.price) {
+            (OrderType::Market | OrderType::Midprice, _) => market,
+            (OrderType::Limit, Some(limit)) => match &order.action {
+                OrderAction::Buy if limit >= market => market,
+                OrderAction::Sell if limit <= market => market,
+                _ => return refuse("limit orders that aren't marketable can't rest"),
+            },
+            (OrderType::Limit, None) => return refuse("a limit order needs a price"),
+            (OrderType::Stop | OrderType::StopLimit, _) => {
+                return refuse("stop orders can't be placed")
+            }
+        };
+
+        let mut book = self.book.lock().expect("demo book poisoned");
+        let held = book.holdings.iter().position(|h| h.symbol == symbol);
+        let signed = match &order.action {
+            OrderAction::Buy => order.quantity,
+            OrderAction::Sell => -order.quantity,
+        };
+        let mut realized = None;
+        match (held, &order.action) {
+            (Some(i), OrderAction::Buy) => {
+                let h = &mut book.holdings[i];
+                let cost = h.average_cost * h.shares + fill_price * order.quantity;
+                h.shares += order.quantity;
+                h.average_cost = cost / h.shares;
+            }
+            (None, OrderAction::Buy) => book.holdings.push(Holding {
+                symbol: symbol.clone(),
+                shares: order.quantity,
+                average_cost: fill_price,
+            }),
+            (Some(i), OrderAction::Sell) if book.holdings[i].shares >= order.quantity => {
+                let h = &mut book.holdings[i];
+                let pnl = (fill_price - h.average_cost) * order.quantity - COMMISSION;
+                h.shares -= order.quantity;
+                if h.shares.abs() < 1e-9 {
+                    book.holdings.remove(i);
+                }
+                book.realized_pnl += pnl;
+                realized = Some(pnl);
+            }
+            (_, OrderAction::Sell) => {
+                return refuse("selling more than is held isn't supported");
+            }
+        }
+        book.cash -= signed * fill_price + COMMISSION;
+
+        let order_id = self.next_order_id.fetch_add(1, Ordering::SeqCst);
+        book.fills.push(IbkrExecution {
+            symbol,
+            side: match &order.action {
+                OrderAction::Buy => ExecutionSide::Bought,
+                OrderAction::Sell => ExecutionSide::Sold,
+            },
+            qty: order.quantity,
+            avg_price: fill_price,
+            exec_time: now,
+            order_id,
+            exec_id: format!("demo.{order_id:08}"),
+            account: DEMO_ACCOUNT.to_string(),
+            contract_type: "STK".to_string(),
+            expiry: None,
+            strike: None,
+            right: None,
+            multiplier: None,
+            commission: Some(COMMISSION),
+            realized_pnl: realized,
+            currency: Some("USD".to_string()),
+            commission_currency: Some("USD".to_string()),
+        });
+        Ok(order_id)
+    }
+}
+
+/// Simulated price of `symbol` now: what the feed quotes and fills at.
+pub fn last_price(symbol: &str) -> f64 {
+    price(&symbols::normalize(symbol), Utc::now().timestamp())
+}
+
+fn bar_secs(bar_size: BarSize) -> i64 {
+    match bar_size {
+        BarSize::Sec1 => 1,
+        BarSize::Sec5 => 5,
+        BarSize::Sec15 => 15,
+        BarSize::Sec30 => 30,
+        BarSize::Min1 => 60,
+        BarSize::Min2 => 120,
+        BarSize::Min3 => 180,
+        BarSize::Min5 => 300,
+        BarSize::Min15 => 900,
+        BarSize::Min20 => 1_200,
+        BarSize::Min30 => 1_800,
+        BarSize::Hour1 => 3_600,
+        BarSize::Day1 => 86_400,
+    }
+}
+
+/// IBKR duration string (`"30 D"`, `"1 Y"`) in seconds.
+fn duration_secs(duration: &str) -> Option<i64> {
+    let (n, unit) = duration.trim().split_once(' ')?;
+    let n: i64 = n.parse().ok().filter(|n| *n > 0)?;
+    let unit = match unit.trim() {
+        "S" => 1,
+        "D" => 86_400,
+        "W" => 7 * 86_400,
+        "M" => 30 * 86_400,
+        "Y" => 365 * 86_400,
+        _ => return None,
+    };
+    Some(n * unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ibkr::types::historical::WhatToShow;
+
+    fn order(action: OrderAction, quantity: f64) -> OrderRequest {
+        OrderRequest {
+            symbol: "aapl".to_string(),
+            action,
+            quantity,
+            order_type: OrderType::Market,
+            price: None,
+            start_at: None,
+            algo: None,
+            route: None,
+            option: None,
+        }
+    }
+
+    #[test]
+    fn simulates_the_account_and_fills_orders() {
+        let feed = DemoFeed::new();
+        assert!(matches!(feed.accounts(), Err(IbkrError::NotConnected)));
+        feed.connect();
+        assert_eq!(feed.accounts().unwrap(), vec![DEMO_ACCOUNT]);
+        assert!(feed.positions("U123").is_err());
+
+        let before = feed.positions(DEMO_ACCOUNT).unwrap();
+        assert_eq!(before.len(), BOOK.len());
+        let aapl = before.iter().find(|p| p.symbol == "AAPL").unwrap();
+        assert_eq!(aapl.position, 120.0);
+        assert!(aapl.market_price > 100.0);
+
+        let id = feed.place_order(&order(OrderAction::Sell, 20.0)).unwrap();
+        assert!(feed.place_order(&order(OrderAction::Sell, 500.0)).is_err());
+        let after = feed.positions(DEMO_ACCOUNT).unwrap();
+        assert_eq!(
+            after.iter().find(|p| p.symbol == "AAPL").unwrap().position,
+            100.0
+        );
+        let fills = feed.executions(Utc::now().date_naive()).unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].order_id, id);
+        assert!(fills[0].realized_pnl.is_some());
+
+        let summary = feed.account_summary(DEMO_ACCOUNT).unwrap();
+        let nlv = summary.iter().find(|s| s.tag == "NetLiquidation").unwrap();
+        assert!(nlv.value.parse::<f64>().unwrap() > STARTING_CASH);
+    }
+
+    #[test]
+    fn prices_are_deterministic_and_bars_end_at_the_snapshot() {
+        let feed = DemoFeed::new();
+        feed.connect();
+        assert_eq!(price("MSFT", 1_760_000_000), price("MSFT", 1_760_000_000));
+        assert_ne!(price("MSFT", 1_760_000_000), price("ZZZ", 1_760_000_000));
+
+        let request = HistoricalDataRequest {
+            symbol: "MSFT".to_string(),
+            end_date_time: String::new(),
+            duration: "2 W".to_string(),
+            bar_size: BarSize::Day1,
+            what_to_show: WhatToShow::Trades,
+            use_rth: true,
+        };
+        let bars = feed.bars(&request).unwrap();
+        assert!((9..=11).contains(&bars.len()), "{}", bars.len());
+        assert!(bars.iter().all(|b| b.low <= b.open && b.open <= b.high));
+        let snapshot = feed.snapshot("msft").unwrap();
+        assert_eq!(snapshot.symbol, "MSFT");
+        assert!(snapshot.bid_price < snapshot.ask_price);
+
+        assert_eq!(duration_secs("30 D"), Some(30 * 86_400));
+        assert_eq!(duration_secs("D 30"), None);
+    }
+}
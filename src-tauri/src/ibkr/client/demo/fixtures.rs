@@ -0,0 +1,68 @@
+//! The demo account's starting book and the deterministic price path
+//! every simulated quote, bar and fill reads.
+
+use std::f64::consts::TAU;
+
+pub(super) const STARTING_CASH: f64 = 50_000.0;
+pub(super) const COMMISSION: f64 = 1.0;
+
+/// Starting book: symbol, shares, average cost.
+pub(super) const BOOK: [(&str, f64, f64); 5] = [
+    ("AAPL", 120.0, 172.40),
+    ("MSFT", 60.0, 381.10),
+    ("NVDA", 200.0, 96.25),
+    ("JNJ", 80.0, 152.30),
+    ("SPY", 40.0, 498.70),
+];
+
+/// Price each path oscillates around; other symbols get one derived
+/// from their name.
+const ANCHORS: [(&str, f64); 5] = [
+    ("AAPL", 190.0),
+    ("MSFT", 420.0),
+    ("NVDA", 120.0),
+    ("JNJ", 158.0),
+    ("SPY", 540.0),
+];
+
+/// Simulated price of `symbol` at unix second `t`, to the cent.
+pub(super) fn price(symbol: &str, t: i64) -> f64 {
+    let seed = seed(symbol);
+    let anchor = ANCHORS
+        .iter()
+        .find(|(s, _)| *s == symbol)
+        .map_or(20.0 + (seed % 38_000) as f64 / 100.0, |&(_, p)| p);
+    let phase = (seed % 1_000) as f64 / 1_000.0 * TAU;
+    let days = t as f64 / 86_400.0;
+    let cycle = 0.12 * (TAU * days / 90.0 + phase).sin() + 0.03 * (TAU * days + phase).sin();
+    let noise = 0.004 * unit(mix(seed ^ t.div_euclid(60) as u64));
+    round_cents(anchor * (cycle + noise).exp())
+}
+
+pub(super) fn volume(symbol: &str, from: i64, to: i64) -> i64 {
+    let per_minute = 2_000 + (seed(symbol) % 8_000) as i64;
+    ((to - from) / 60).max(1) * per_minute
+}
+
+/// FNV-1a: stable across builds, unlike `DefaultHasher`.
+pub fn seed(symbol: &str) -> u64 {
+    symbol.bytes().fold(0xcbf2_9ce4_8422_2325, |h, b| {
+        (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// splitmix64 finalizer.
+pub(super) fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// `x` mapped onto `[-1, 1)`.
+pub(super) fn unit(x: u64) -> f64 {
+    (x >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+}
+
+pub(super) fn round_cents(x: f64) -> f64 {
+    (x * 100.0).round() / 100.0
+}
@@ -0,0 +1,138 @@
+//! Bounded executor for blocking ibapi calls.
+//!
+//! Every request path hands its ibapi work to `spawn_blocking`; with
+//! nothing in front of it a slow TWS (or a burst of tracker ticks) piles
+//! up dozens of blocking threads against the one socket. [`BlockingExecutor`]
+//! puts a semaphore in front: at most `max_in_flight` calls run, the rest
+//! queue in FIFO order (tokio's semaphore is fair).
+//!
+//! Each call gets a deadline of `connection_timeout_ms`, covering queue
+//! wait plus execution. On expiry the caller gets `IbkrError::Timeout`;
+//! the blocking thread itself cannot be cancelled and keeps its permit
+//! until ibapi returns, so a wedged socket shrinks capacity instead of
+//! spawning unbounded replacements.
+//!
+//! Long-lived stream loops (`start_daily_pnl_stream`,
+//! `start_scanner_stream`) bypass the executor — they'd hold a permit
+//! for the life of the subscription.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+
+use crate::ibkr::error::{IbkrError, Result};
+
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 8;
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub struct BlockingExecutor {
+    permits: Arc<Semaphore>,
+    timeout: Duration,
+}
+
+impl Default for BlockingExecutor {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_IN_FLIGHT, DEFAULT_TIMEOUT)
+    }
+}
+
+impl BlockingExecutor {
+    /// `max_in_flight` is clamped to at least 1.
+    pub fn new(max_in_flight: usize, timeout: Duration) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max_in_flight.max(1))),
+            timeout,
+        }
+    }
+
+    /// Run `f` on the blocking pool once a permit is free.
+    pub async fn run<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let permits = Arc::clone(&self.permits);
+        let call = async move {
+            let permit = permits
+                .acquire_owned()
+                .await
+                .map_err(|e| IbkrError::Unknown(e.to_string()))?;
+            tokio::task::spawn_blocking(move || {
+                let _permit = permit;
+                f()
+            })
+            .await
+            .map_err(|e| IbkrError::Unknown(e.to_string()))
+        };
+        let started = std::time::Instant::now();
+        let out = tokio::time::timeout(self.timeout, call)
+            .await
+            .map_err(|_| IbkrError::Timeout(self.timeout.as_millis() as u64));
+        metrics::histogram!(crate::telemetry::IBKR_REQUEST_DURATION)
+            .record(started.elapsed().as_secs_f64());
+        out?
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Permits currently free.
+    #[allow(dead_code)]
+    pub fn available(&self) -> usize {
+        self.permits.available_permits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn caps_concurrent_blocking_calls() {
+        let exec = Arc::new(BlockingExecutor::new(2, Duration::from_secs(5)));
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let calls: Vec<_> = (0..6)
+            .map(|_| {
+                let exec = Arc::clone(&exec);
+                let running = Arc::clone(&running);
+                let peak = Arc::clone(&peak);
+                tokio::spawn(async move {
+                    exec.run(move || {
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        std::thread::sleep(Duration::from_millis(30));
+                        running.fetch_sub(1, Ordering::SeqCst);
+                    })
+                    .await
+                })
+            })
+            .collect();
+        for call in calls {
+            call.await.unwrap().unwrap();
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(exec.available(), 2);
+    }
+
+    #[tokio::test]
+    async fn queued_call_times_out_behind_a_wedged_one() {
+        let exec = Arc::new(BlockingExecutor::new(1, Duration::from_millis(50)));
+        let wedged = {
+            let exec = Arc::clone(&exec);
+            tokio::spawn(async move {
+                exec.run(|| std::thread::sleep(Duration::from_millis(200)))
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let queued = exec.run(|| 42).await;
+        assert!(matches!(queued, Err(IbkrError::Timeout(50))));
+        assert!(matches!(wedged.await.unwrap(), Err(IbkrError::Timeout(50))));
+    }
+}
@@ -1,10 +1,11 @@
-use ibapi::contracts::Contract;
-
 use crate::ibkr::error::{IbkrError, Result};
 use crate::ibkr::types::historical::{
     BarSize as OurBarSize, HistoricalBar, HistoricalDataRequest, WhatToShow as OurWhatToShow,
 };
 
+use crate::middleware::rate_limits;
+
+use super::contract::stock_contract;
 use super::IbkrClient;
 
 impl IbkrClient {
@@ -22,84 +23,87 @@ impl IbkrClient {
         };
         use ibapi::market_data::TradingHours;
 
-        let client_clone = self.ibapi_client().await?;
+        if let Some(demo) = self.demo() {
+            return demo.bars(&request);
+        }
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_HISTORICAL).await?;
 
-        let bars = tokio::task::spawn_blocking(move || -> Result<Vec<HistoricalBar>> {
-            let contract = Contract::stock(&request.symbol).build();
-            let ib_bar = match request.bar_size {
-                OurBarSize::Sec1 => IbBarSize::Sec,
-                OurBarSize::Sec5 => IbBarSize::Sec5,
-                OurBarSize::Sec15 => IbBarSize::Sec15,
-                OurBarSize::Sec30 => IbBarSize::Sec30,
-                OurBarSize::Min1 => IbBarSize::Min,
-                OurBarSize::Min2 => IbBarSize::Min2,
-                OurBarSize::Min3 => IbBarSize::Min3,
-                OurBarSize::Min5 => IbBarSize::Min5,
-                OurBarSize::Min15 => IbBarSize::Min15,
-                OurBarSize::Min20 => IbBarSize::Min20,
-                OurBarSize::Min30 => IbBarSize::Min30,
-                OurBarSize::Hour1 => IbBarSize::Hour,
-                OurBarSize::Day1 => IbBarSize::Day,
-            };
-            let ib_what = match request.what_to_show {
-                OurWhatToShow::Trades => IbWhatToShow::Trades,
-                OurWhatToShow::Midpoint => IbWhatToShow::MidPoint,
-                OurWhatToShow::Bid => IbWhatToShow::Bid,
-                OurWhatToShow::Ask => IbWhatToShow::Ask,
-                OurWhatToShow::BidAsk => IbWhatToShow::BidAsk,
-                OurWhatToShow::HistoricalVolatility => IbWhatToShow::HistoricalVolatility,
-                OurWhatToShow::OptionImpliedVolatility => IbWhatToShow::OptionImpliedVolatility,
-            };
+        let bars = self
+            .run_blocking(move || -> Result<Vec<HistoricalBar>> {
+                let contract = stock_contract(&request.symbol, None);
+                let ib_bar = match request.bar_size {
+                    OurBarSize::Sec1 => IbBarSize::Sec,
+                    OurBarSize::Sec5 => IbBarSize::Sec5,
+                    OurBarSize::Sec15 => IbBarSize::Sec15,
+                    OurBarSize::Sec30 => IbBarSize::Sec30,
+                    OurBarSize::Min1 => IbBarSize::Min,
+                    OurBarSize::Min2 => IbBarSize::Min2,
+                    OurBarSize::Min3 => IbBarSize::Min3,
+                    OurBarSize::Min5 => IbBarSize::Min5,
+                    OurBarSize::Min15 => IbBarSize::Min15,
+                    OurBarSize::Min20 => IbBarSize::Min20,
+                    OurBarSize::Min30 => IbBarSize::Min30,
+                    OurBarSize::Hour1 => IbBarSize::Hour,
+                    OurBarSize::Day1 => IbBarSize::Day,
+                };
+                let ib_what = match request.what_to_show {
+                    OurWhatToShow::Trades => IbWhatToShow::Trades,
+                    OurWhatToShow::Midpoint => IbWhatToShow::MidPoint,
+                    OurWhatToShow::Bid => IbWhatToShow::Bid,
+                    OurWhatToShow::Ask => IbWhatToShow::Ask,
+                    OurWhatToShow::BidAsk => IbWhatToShow::BidAsk,
+                    OurWhatToShow::HistoricalVolatility => IbWhatToShow::HistoricalVolatility,
+                    OurWhatToShow::OptionImpliedVolatility => IbWhatToShow::OptionImpliedVolatility,
+                };
 
-            // Parse our "{N} {UNIT}" duration string back into ibapi's Duration.
-            // We only emit "{N} D" from the service so this is the common path.
-            let ib_duration: IbDuration = request.duration.parse().map_err(|e| {
-                IbkrError::RequestFailed(format!(
-                    "invalid duration string '{}': {e}",
-                    request.duration
-                ))
-            })?;
+                // Parse our "{N} {UNIT}" duration string back into ibapi's Duration.
+                // We only emit "{N} D" from the service so this is the common path.
+                let ib_duration: IbDuration = request.duration.parse().map_err(|e| {
+                    IbkrError::RequestFailed(format!(
+                        "invalid duration string '{}': {e}",
+                        request.duration
+                    ))
+                })?;
 
-            let trading_hours = if request.use_rth {
-                TradingHours::Regular
-            } else {
-                TradingHours::Extended
-            };
+                let trading_hours = if request.use_rth {
+                    TradingHours::Regular
+                } else {
+                    TradingHours::Extended
+                };
 
-            // We pass `None` for end_date_time and let IBKR default to "now".
-            // The end_date_time string in our request type is informational
-            // for now; a future revision can route it through OffsetDateTime.
-            let data = client_clone
-                .historical_data(&contract, None, ib_duration, ib_bar, ib_what, trading_hours)
-                .map_err(IbkrError::from)?;
+                // We pass `None` for end_date_time and let IBKR default to "now".
+                // The end_date_time string in our request type is informational
+                // for now; a future revision can route it through OffsetDateTime.
+                let data = client_clone
+                    .historical_data(&contract, None, ib_duration, ib_bar, ib_what, trading_hours)
+                    .map_err(IbkrError::from)?;
 
-            Ok(data
-                .bars
-                .into_iter()
-                .map(|b| {
-                    let ts = b.date.unix_timestamp();
-                    let chrono_dt =
-                        chrono::DateTime::from_timestamp(ts, 0).unwrap_or_else(chrono::Utc::now);
-                    let formatted = if request.bar_size == OurBarSize::Day1 {
-                        chrono_dt.format("%Y%m%d").to_string()
-                    } else {
-                        chrono_dt.format("%Y%m%d %H:%M:%S").to_string()
-                    };
-                    HistoricalBar {
-                        time: formatted,
-                        open: b.open,
-                        high: b.high,
-                        low: b.low,
-                        close: b.close,
-                        volume: b.volume as i64,
-                        wap: b.wap,
-                        count: b.count,
-                    }
-                })
-                .collect())
-        })
-        .await
-        .map_err(|e| IbkrError::Unknown(e.to_string()))??;
+                Ok(data
+                    .bars
+                    .into_iter()
+                    .map(|b| {
+                        let ts = b.date.unix_timestamp();
+                        let chrono_dt = chrono::DateTime::from_timestamp(ts, 0)
+                            .unwrap_or_else(chrono::Utc::now);
+                        let formatted = if request.bar_size == OurBarSize::Day1 {
+                            chrono_dt.format("%Y%m%d").to_string()
+                        } else {
+                            chrono_dt.format("%Y%m%d %H:%M:%S").to_string()
+                        };
+                        HistoricalBar {
+                            time: formatted,
+                            open: b.open,
+                            high: b.high,
+                            low: b.low,
+                            close: b.close,
+                            volume: b.volume as i64,
+                            wap: b.wap,
+                            count: b.count,
+                        }
+                    })
+                    .collect())
+            })
+            .await??;
 
         Ok(bars)
     }
@@ -2,18 +2,29 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use ibapi::client::blocking::Client;
-use ibapi::contracts::tick_types::TickType;
-use ibapi::contracts::Contract;
 use ibapi::market_data::realtime::TickTypes;
 use tracing::{debug, info};
 
 use crate::ibkr::error::{IbkrError, Result};
-use crate::ibkr::types::{DataTier, MarketDataSnapshot, MarketDataType};
+use crate::ibkr::types::{ContractRoute, DataTier, MarketDataSnapshot, MarketDataType};
 
+use crate::middleware::rate_limits;
+use crate::utils::symbols;
+
+use super::contract::stock_contract;
+use super::snapshot_ticks::{
+    apply_price, apply_price_size, apply_size, classify_tick_type, empty_snapshot, finish,
+    snapshot_has_price,
+};
 use super::IbkrClient;
 
 pub(super) const SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Generic tick 318: last regular-session trade (`LastRthTrade`).
+/// `snapshot=true` requests can't carry generic ticks, so only the
+/// streaming paths ask for it.
+const LAST_RTH_TRADE: &str = "318";
+
 /// Quiet-window after the last received tick before `streaming_drain_blocking`
 /// considers the burst complete and exits early. IBKR delivers an initial
 /// burst of ticks (Bid/Ask/Last/Close/Volume…) within ~500–800ms on streaming
@@ -47,52 +58,21 @@ impl SnapshotMode {
     }
 }
 
-/// Classify a single `TickType` into the data tier it implies.
-///
-/// Returns `None` for tick types that don't carry a tier signal (e.g.
-/// `Halted`, `RtVolume`, options-specific ticks). The probe loop keeps
-/// reading until it gets a `Some(_)` or hits its timeout / snapshot
-/// end, so unclassified ticks just don't move the answer.
-fn classify_tick_type(tick_type: TickType) -> Option<DataTier> {
-    match tick_type {
-        TickType::Bid
-        | TickType::Ask
-        | TickType::Last
-        | TickType::High
-        | TickType::Low
-        | TickType::Close
-        | TickType::Open
-        | TickType::Volume
-        | TickType::BidSize
-        | TickType::AskSize
-        | TickType::LastSize => Some(DataTier::RealTime),
-        TickType::DelayedBid
-        | TickType::DelayedAsk
-        | TickType::DelayedLast
-        | TickType::DelayedHigh
-        | TickType::DelayedLow
-        | TickType::DelayedClose
-        | TickType::DelayedOpen
-        | TickType::DelayedVolume
-        | TickType::DelayedBidSize
-        | TickType::DelayedAskSize
-        | TickType::DelayedLastSize => Some(DataTier::Delayed),
-        _ => None,
-    }
-}
-
 impl IbkrClient {
     /// Existing best-effort subscription. Kept because
     /// `ibkr_subscribe_market_data` Tauri command depends on it.
     pub async fn subscribe_market_data(&self, symbol: &str) -> Result<()> {
-        let client_clone = self.ibapi_client().await?;
+        if let Some(demo) = self.demo() {
+            return demo.snapshot(symbol).map(|_| ());
+        }
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_MARKET_DATA).await?;
 
         let symbol = symbol.to_string();
 
-        tokio::task::spawn_blocking(move || {
-            let contract = Contract::stock(&symbol).build();
+        self.run_blocking(move || {
+            let contract = stock_contract(&symbol, None);
             // For now, we'll request basic tick types
-            let tick_types = &["233"]; // RTVolume
+            let tick_types = &["233", LAST_RTH_TRADE]; // RTVolume
             match client_clone
                 .market_data(&contract)
                 .generic_ticks(tick_types)
@@ -105,8 +85,7 @@ impl IbkrClient {
                 Err(e) => Err(IbkrError::from(e)),
             }
         })
-        .await
-        .map_err(|e| IbkrError::Unknown(e.to_string()))?
+        .await?
     }
 
     /// One-shot fetch of level-1 market data for `symbol`.
@@ -126,54 +105,45 @@ impl IbkrClient {
     ///   `SNAPSHOT_TIMEOUT`.
     /// - `IbkrError::ApiError` for any other ibapi error.
     pub async fn get_market_data_snapshot(&self, symbol: &str) -> Result<MarketDataSnapshot> {
+        self.get_market_data_snapshot_on(symbol, None).await
+    }
+
+    /// [`Self::get_market_data_snapshot`] on an explicit venue /
+    /// currency instead of SMART / USD.
+    pub async fn get_market_data_snapshot_on(
+        &self,
+        symbol: &str,
+        route: Option<ContractRoute>,
+    ) -> Result<MarketDataSnapshot> {
         debug!("get_market_data_snapshot: enter symbol={}", symbol);
-        let client_clone = self.ibapi_client().await?;
+        if let Some(demo) = self.demo() {
+            return demo.snapshot(symbol);
+        }
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_MARKET_DATA).await?;
         let market_data_type = self.config.read().await.market_data_type;
         let mode = SnapshotMode::for_market_data_type(market_data_type);
-        let symbol_owned = symbol.to_string();
+        let symbol_owned = symbols::normalize(symbol);
 
-        tokio::task::spawn_blocking(move || -> Result<MarketDataSnapshot> {
+        self.run_blocking(move || -> Result<MarketDataSnapshot> {
             match mode {
-                SnapshotMode::OneShot => snapshot_blocking(client_clone, symbol_owned),
+                SnapshotMode::OneShot => snapshot_blocking(client_clone, symbol_owned, route),
                 SnapshotMode::StreamingDrain => {
-                    streaming_drain_blocking(client_clone, symbol_owned)
+                    streaming_drain_blocking(client_clone, symbol_owned, route)
                 }
             }
         })
-        .await
-        .map_err(|e| IbkrError::Unknown(e.to_string()))?
-    }
-}
-
-fn empty_snapshot(symbol: &str) -> MarketDataSnapshot {
-    MarketDataSnapshot {
-        symbol: symbol.to_string(),
-        bid_price: None,
-        bid_size: None,
-        ask_price: None,
-        ask_size: None,
-        last_price: None,
-        last_size: None,
-        high: None,
-        low: None,
-        volume: None,
-        close: None,
-        open: None,
-        timestamp: chrono::Utc::now().timestamp(),
+        .await?
     }
 }
 
-/// True if `snapshot` carries any usable price (last, close, or open).
-/// Used by the streaming path to decide whether a quiet-window or
-/// channel-close should return `Ok(snapshot)` or `Err(Timeout)`.
-fn snapshot_has_price(snapshot: &MarketDataSnapshot) -> bool {
-    snapshot.last_price.is_some() || snapshot.close.is_some() || snapshot.open.is_some()
-}
-
 /// `reqMktData(snapshot=true)` path. Original behavior — kept verbatim
 /// for accounts on `Live` or `Frozen` market data.
-fn snapshot_blocking(client: Arc<Client>, symbol: String) -> Result<MarketDataSnapshot> {
-    let contract = Contract::stock(&symbol).build();
+fn snapshot_blocking(
+    client: Arc<Client>,
+    symbol: String,
+    route: Option<ContractRoute>,
+) -> Result<MarketDataSnapshot> {
+    let contract = stock_contract(&symbol, route.as_ref());
     let generic_ticks: Vec<&str> = Vec::new();
 
     let subscription = client
@@ -208,7 +178,7 @@ fn snapshot_blocking(client: Arc<Client>, symbol: String) -> Result<MarketDataSn
             Some(TickTypes::Size(tick)) => apply_size(&mut snapshot, &tick),
             Some(TickTypes::PriceSize(tick)) => apply_price_size(&mut snapshot, &tick),
             Some(TickTypes::SnapshotEnd) => {
-                snapshot.timestamp = chrono::Utc::now().timestamp();
+                finish(&mut snapshot, route.as_ref(), chrono::Utc::now());
                 debug!(
                     "get_market_data_snapshot({}): SnapshotEnd last_price={:?} close={:?}",
                     symbol, snapshot.last_price, snapshot.close
@@ -260,9 +230,13 @@ fn snapshot_blocking(client: Arc<Client>, symbol: String) -> Result<MarketDataSn
 ///   `subscription.error()`. Slice timeouts re-enter the loop.
 /// - On exit, the held `Subscription` drops and ibapi sends
 ///   `cancelMktData` automatically.
-fn streaming_drain_blocking(client: Arc<Client>, symbol: String) -> Result<MarketDataSnapshot> {
-    let contract = Contract::stock(&symbol).build();
-    let generic_ticks: Vec<&str> = Vec::new();
+fn streaming_drain_blocking(
+    client: Arc<Client>,
+    symbol: String,
+    route: Option<ContractRoute>,
+) -> Result<MarketDataSnapshot> {
+    let contract = stock_contract(&symbol, route.as_ref());
+    let generic_ticks = [LAST_RTH_TRADE];
 
     let subscription = client
         .market_data(&contract)
@@ -287,7 +261,7 @@ fn streaming_drain_blocking(client: Arc<Client>, symbol: String) -> Result<Marke
         // Hard cap: out of time.
         if now >= hard_deadline {
             if snapshot_has_price(&snapshot) {
-                snapshot.timestamp = chrono::Utc::now().timestamp();
+                finish(&mut snapshot, route.as_ref(), chrono::Utc::now());
                 debug!(
                     "get_market_data_snapshot({}, streaming): -> hard deadline, returning partial last_price={:?} close={:?}",
                     symbol, snapshot.last_price, snapshot.close
@@ -313,7 +287,7 @@ fn streaming_drain_blocking(client: Arc<Client>, symbol: String) -> Result<Marke
         if slice.is_zero() {
             // Quiet-window expired with at least one prior tick → exit.
             if snapshot_has_price(&snapshot) {
-                snapshot.timestamp = chrono::Utc::now().timestamp();
+                finish(&mut snapshot, route.as_ref(), chrono::Utc::now());
                 debug!(
                     "get_market_data_snapshot({}, streaming): -> quiet window, last_price={:?} close={:?}",
                     symbol, snapshot.last_price, snapshot.close
@@ -341,7 +315,7 @@ fn streaming_drain_blocking(client: Arc<Client>, symbol: String) -> Result<Marke
             }
             Some(TickTypes::SnapshotEnd) => {
                 // Some servers still emit SnapshotEnd on streaming reqs — treat as exit signal.
-                snapshot.timestamp = chrono::Utc::now().timestamp();
+                finish(&mut snapshot, route.as_ref(), chrono::Utc::now());
                 debug!(
                     "get_market_data_snapshot({}, streaming): SnapshotEnd last_price={:?} close={:?}",
                     symbol, snapshot.last_price, snapshot.close
@@ -399,7 +373,7 @@ impl IbkrClient {
     /// mirrors the trait method on `IbkrClientTrait`.
     #[allow(dead_code)]
     pub async fn probe_data_tier(&self, symbol: &str) -> Result<DataTier> {
-        let client_clone = self.ibapi_client().await?;
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_MARKET_DATA).await?;
         run_probe(client_clone, symbol).await
     }
 }
@@ -411,7 +385,7 @@ pub(super) async fn run_probe(client: Arc<Client>, symbol: &str) -> Result<DataT
     let symbol_owned = symbol.to_string();
 
     tokio::task::spawn_blocking(move || -> Result<DataTier> {
-        let contract = Contract::stock(&symbol_owned).build();
+        let contract = stock_contract(&symbol_owned, None);
         let generic_ticks: Vec<&str> = Vec::new();
 
         let subscription = client
@@ -470,155 +444,10 @@ pub(super) async fn run_probe(client: Arc<Client>, symbol: &str) -> Result<DataT
     .map_err(|e| IbkrError::Unknown(e.to_string()))?
 }
 
-fn apply_price(snapshot: &mut MarketDataSnapshot, tick: &ibapi::market_data::realtime::TickPrice) {
-    match tick.tick_type {
-        TickType::Bid | TickType::DelayedBid => snapshot.bid_price = Some(tick.price),
-        TickType::Ask | TickType::DelayedAsk => snapshot.ask_price = Some(tick.price),
-        TickType::Last | TickType::DelayedLast => snapshot.last_price = Some(tick.price),
-        TickType::High | TickType::DelayedHigh => snapshot.high = Some(tick.price),
-        TickType::Low | TickType::DelayedLow => snapshot.low = Some(tick.price),
-        TickType::Close | TickType::DelayedClose => snapshot.close = Some(tick.price),
-        TickType::Open | TickType::DelayedOpen => snapshot.open = Some(tick.price),
-        _ => {}
-    }
-}
-
-fn apply_size(snapshot: &mut MarketDataSnapshot, tick: &ibapi::market_data::realtime::TickSize) {
-    match tick.tick_type {
-        TickType::BidSize | TickType::DelayedBidSize => {
-            snapshot.bid_size = Some(tick.size as i32);
-        }
-        TickType::AskSize | TickType::DelayedAskSize => {
-            snapshot.ask_size = Some(tick.size as i32);
-        }
-        TickType::LastSize | TickType::DelayedLastSize => {
-            snapshot.last_size = Some(tick.size as i32);
-        }
-        TickType::Volume | TickType::DelayedVolume => {
-            snapshot.volume = Some(tick.size as i64);
-        }
-        _ => {}
-    }
-}
-
-fn apply_price_size(
-    snapshot: &mut MarketDataSnapshot,
-    tick: &ibapi::market_data::realtime::TickPriceSize,
-) {
-    match tick.price_tick_type {
-        TickType::Bid | TickType::DelayedBid => snapshot.bid_price = Some(tick.price),
-        TickType::Ask | TickType::DelayedAsk => snapshot.ask_price = Some(tick.price),
-        TickType::Last | TickType::DelayedLast => snapshot.last_price = Some(tick.price),
-        _ => {}
-    }
-    match tick.size_tick_type {
-        TickType::BidSize | TickType::DelayedBidSize => snapshot.bid_size = Some(tick.size as i32),
-        TickType::AskSize | TickType::DelayedAskSize => snapshot.ask_size = Some(tick.size as i32),
-        TickType::LastSize | TickType::DelayedLastSize => {
-            snapshot.last_size = Some(tick.size as i32);
-        }
-        _ => {}
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn classify_real_time_price_ticks() {
-        assert_eq!(classify_tick_type(TickType::Last), Some(DataTier::RealTime));
-        assert_eq!(classify_tick_type(TickType::Bid), Some(DataTier::RealTime));
-        assert_eq!(classify_tick_type(TickType::Ask), Some(DataTier::RealTime));
-        assert_eq!(classify_tick_type(TickType::High), Some(DataTier::RealTime));
-        assert_eq!(classify_tick_type(TickType::Low), Some(DataTier::RealTime));
-        assert_eq!(
-            classify_tick_type(TickType::Close),
-            Some(DataTier::RealTime)
-        );
-        assert_eq!(classify_tick_type(TickType::Open), Some(DataTier::RealTime));
-        assert_eq!(
-            classify_tick_type(TickType::Volume),
-            Some(DataTier::RealTime)
-        );
-    }
-
-    #[test]
-    fn classify_real_time_size_ticks() {
-        assert_eq!(
-            classify_tick_type(TickType::BidSize),
-            Some(DataTier::RealTime)
-        );
-        assert_eq!(
-            classify_tick_type(TickType::AskSize),
-            Some(DataTier::RealTime)
-        );
-        assert_eq!(
-            classify_tick_type(TickType::LastSize),
-            Some(DataTier::RealTime)
-        );
-    }
-
-    #[test]
-    fn classify_delayed_price_ticks() {
-        assert_eq!(
-            classify_tick_type(TickType::DelayedLast),
-            Some(DataTier::Delayed)
-        );
-        assert_eq!(
-            classify_tick_type(TickType::DelayedBid),
-            Some(DataTier::Delayed)
-        );
-        assert_eq!(
-            classify_tick_type(TickType::DelayedAsk),
-            Some(DataTier::Delayed)
-        );
-        assert_eq!(
-            classify_tick_type(TickType::DelayedHigh),
-            Some(DataTier::Delayed)
-        );
-        assert_eq!(
-            classify_tick_type(TickType::DelayedLow),
-            Some(DataTier::Delayed)
-        );
-        assert_eq!(
-            classify_tick_type(TickType::DelayedClose),
-            Some(DataTier::Delayed)
-        );
-        assert_eq!(
-            classify_tick_type(TickType::DelayedOpen),
-            Some(DataTier::Delayed)
-        );
-        assert_eq!(
-            classify_tick_type(TickType::DelayedVolume),
-            Some(DataTier::Delayed)
-        );
-    }
-
-    #[test]
-    fn classify_delayed_size_ticks() {
-        assert_eq!(
-            classify_tick_type(TickType::DelayedBidSize),
-            Some(DataTier::Delayed)
-        );
-        assert_eq!(
-            classify_tick_type(TickType::DelayedAskSize),
-            Some(DataTier::Delayed)
-        );
-        assert_eq!(
-            classify_tick_type(TickType::DelayedLastSize),
-            Some(DataTier::Delayed)
-        );
-    }
-
-    #[test]
-    fn classify_unrelated_ticks_returns_none() {
-        assert_eq!(classify_tick_type(TickType::Halted), None);
-        assert_eq!(classify_tick_type(TickType::Unknown), None);
-        assert_eq!(classify_tick_type(TickType::RtVolume), None);
-        assert_eq!(classify_tick_type(TickType::MarkPrice), None);
-    }
-
     #[test]
     fn snapshot_mode_routes_real_time_to_one_shot() {
         assert_eq!(
@@ -645,45 +474,4 @@ mod tests {
             SnapshotMode::StreamingDrain
         );
     }
-
-    #[test]
-    fn snapshot_has_price_detects_any_filled_price_field() {
-        let mut snap = empty_snapshot("AMD");
-        assert!(!snapshot_has_price(&snap));
-
-        snap.bid_price = Some(100.0);
-        snap.ask_price = Some(100.5);
-        assert!(
-            !snapshot_has_price(&snap),
-            "bid/ask alone shouldn't count — UI needs last/close/open"
-        );
-
-        snap.last_price = Some(100.25);
-        assert!(snapshot_has_price(&snap));
-
-        let mut snap = empty_snapshot("AMD");
-        snap.close = Some(99.0);
-        assert!(snapshot_has_price(&snap));
-
-        let mut snap = empty_snapshot("AMD");
-        snap.open = Some(98.5);
-        assert!(snapshot_has_price(&snap));
-    }
-
-    #[test]
-    fn empty_snapshot_carries_symbol_and_clears_optionals() {
-        let snap = empty_snapshot("AMD");
-        assert_eq!(snap.symbol, "AMD");
-        assert!(snap.bid_price.is_none());
-        assert!(snap.ask_price.is_none());
-        assert!(snap.last_price.is_none());
-        assert!(snap.close.is_none());
-        assert!(snap.open.is_none());
-        assert!(snap.high.is_none());
-        assert!(snap.low.is_none());
-        assert!(snap.volume.is_none());
-        assert!(snap.bid_size.is_none());
-        assert!(snap.ask_size.is_none());
-        assert!(snap.last_size.is_none());
-    }
 }
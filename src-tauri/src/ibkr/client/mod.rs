@@ -1,13 +1,26 @@
+mod account_updates;
+mod algo;
+mod combo;
+mod contract;
+pub mod demo;
 mod executions_merge;
+pub(crate) mod executor;
 mod historical;
 mod market_data;
 mod news;
+mod open_orders;
+mod option_chain;
+mod option_greeks;
+mod order_audit;
 mod orders;
+pub mod requests;
+mod snapshot_ticks;
 mod streams;
+mod what_if;
 
+pub use self::executor::BlockingExecutor;
 pub use self::streams::StreamHandle;
 
-use ibapi::accounts::types::AccountId;
 use ibapi::client::blocking::Client;
 use std::sync::{Arc, Mutex as StdMutex};
 use tokio::sync::{Mutex, RwLock};
@@ -15,9 +28,12 @@ use tracing::{error, info};
 
 use crate::events::{AppEvent, EventEmitter};
 use crate::ibkr::error::{IbkrError, Result};
-use crate::ibkr::types::{
-    AccountSummary, ConnectionConfig, ConnectionStatus, DataTier, MarketDataType, Position,
-};
+use crate::ibkr::types::{ConnectionConfig, ConnectionStatus, DataTier, MarketDataType};
+use crate::middleware::rate_limits::{self, RateLimits};
+
+use self::demo::DemoFeed;
+pub(crate) use self::order_audit::OrderSink;
+use self::requests::RequestRegistry;
 
 /// Sink wired in by `IbkrState::new` so the probe-on-connect task and
 /// the disconnect path can publish a tier without `IbkrClient` knowing
@@ -39,16 +55,71 @@ pub struct IbkrClient {
     // before the other has consumed its share. See client.rs:get_positions.
     account_updates_lock: Arc<Mutex<()>>,
     tier_sink: Arc<StdMutex<Option<TierSink>>>,
+    /// Request pacing, wired by `lib.rs::run` from `AppConfig`. `None`
+    /// (tests, early startup) means unpaced.
+    rate_limits: Arc<StdMutex<Option<Arc<RateLimits>>>>,
+    /// Concurrency cap + deadline for one-shot blocking calls. Starts at
+    /// the defaults; `lib.rs::run` swaps in the configured one.
+    executor: Arc<StdMutex<Arc<BlockingExecutor>>>,
+    /// Cancellable stream drains (see `requests.rs`).
+    requests: Arc<RequestRegistry>,
+    /// Audit feed for order submissions (see `order_audit.rs`), wired
+    /// by `lib.rs::run`.
+    order_sink: Arc<StdMutex<Option<OrderSink>>>,
+    /// Simulated account and market data (see `demo.rs`), wired by
+    /// `lib.rs::run` when `AppConfig::demo` is on. Replaces the Gateway.
+    demo: Arc<StdMutex<Option<Arc<DemoFeed>>>>,
 }
 
 impl IbkrClient {
     /// Snapshot the underlying `ibapi::Client` handle. Returns `NotConnected`
     /// when no connection is live; otherwise clones the `Arc` so the
     /// caller can run `spawn_blocking` work without holding the read lock.
-    pub(super) async fn ibapi_client(&self) -> Result<Arc<Client>> {
-        let client = self.client.read().await;
-        let client = client.as_ref().ok_or(IbkrError::NotConnected)?;
-        Ok(Arc::clone(client))
+    ///
+    /// Waits on the global and `endpoint` rate-limit buckets (see
+    /// `middleware/rate_limits.rs`) before returning, so every request
+    /// path is paced without each call site knowing about it.
+    ///
+    /// In demo mode every call that reaches the Gateway fails with
+    /// [`demo::UNSUPPORTED`].
+    pub(super) async fn ibapi_client(&self, endpoint: &'static str) -> Result<Arc<Client>> {
+        if self.demo().is_some() {
+            return Err(IbkrError::RequestFailed(demo::UNSUPPORTED.to_string()));
+        }
+        let client = {
+            let client = self.client.read().await;
+            Arc::clone(client.as_ref().ok_or(IbkrError::NotConnected)?)
+        };
+        let limits = self
+            .rate_limits
+            .lock()
+            .expect("rate_limits poisoned")
+            .clone();
+        if let Some(limits) = limits {
+            limits.acquire(endpoint).await;
+        }
+        Ok(client)
+    }
+
+    /// Run one-shot ibapi work through the bounded [`BlockingExecutor`].
+    /// Join failures and deadline expiry surface as `IbkrError`.
+    pub(super) async fn run_blocking<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let executor = Arc::clone(&self.executor.lock().expect("executor poisoned"));
+        executor.run(f).await
+    }
+
+    /// Deadline applied to each blocking call; stream drains use it as
+    /// their overall timeout.
+    pub(super) fn request_timeout(&self) -> std::time::Duration {
+        self.executor.lock().expect("executor poisoned").timeout()
+    }
+
+    pub fn requests(&self) -> &RequestRegistry {
+        &self.requests
     }
 }
 
@@ -60,6 +131,11 @@ impl IbkrClient {
             config: Arc::new(RwLock::new(config)),
             account_updates_lock: Arc::new(Mutex::new(())),
             tier_sink: Arc::new(StdMutex::new(None)),
+            rate_limits: Arc::new(StdMutex::new(None)),
+            executor: Arc::new(StdMutex::new(Arc::new(BlockingExecutor::default()))),
+            requests: Arc::new(RequestRegistry::default()),
+            order_sink: Arc::new(StdMutex::new(None)),
+            demo: Arc::new(StdMutex::new(None)),
         }
     }
 
@@ -69,6 +145,11 @@ impl IbkrClient {
             config,
             account_updates_lock: Arc::new(Mutex::new(())),
             tier_sink: Arc::new(StdMutex::new(None)),
+            rate_limits: Arc::new(StdMutex::new(None)),
+            executor: Arc::new(StdMutex::new(Arc::new(BlockingExecutor::default()))),
+            requests: Arc::new(RequestRegistry::default()),
+            order_sink: Arc::new(StdMutex::new(None)),
+            demo: Arc::new(StdMutex::new(None)),
         }
     }
 
@@ -83,6 +164,38 @@ impl IbkrClient {
         *sink = Some(TierSink { tier, emitter });
     }
 
+    pub fn set_rate_limits(&self, limits: Arc<RateLimits>) {
+        *self.rate_limits.lock().expect("rate_limits poisoned") = Some(limits);
+    }
+
+    pub fn set_executor(&self, executor: BlockingExecutor) {
+        *self.executor.lock().expect("executor poisoned") = Arc::new(executor);
+    }
+
+    pub fn set_order_sink(&self, sink: OrderSink) {
+        *self.order_sink.lock().expect("order_sink poisoned") = Some(sink);
+    }
+
+    /// Serve from `feed` instead of IBKR from now on.
+    pub fn set_demo(&self, feed: Arc<DemoFeed>) {
+        *self.demo.lock().expect("demo poisoned") = Some(feed);
+    }
+
+    pub(super) fn demo(&self) -> Option<Arc<DemoFeed>> {
+        self.demo.lock().expect("demo poisoned").clone()
+    }
+
+    /// Drop the audit sender, so the ingest loop writes what is still
+    /// queued and ends. Orders placed afterwards go unaudited; only
+    /// called on app exit.
+    pub fn close_order_sink(&self) {
+        self.order_sink.lock().expect("order_sink poisoned").take();
+    }
+
+    pub(super) fn order_sink_snapshot(&self) -> Option<OrderSink> {
+        self.order_sink.lock().expect("order_sink poisoned").clone()
+    }
+
     /// Snapshot of the wired sink (cloned so callers don't hold the
     /// lock across awaits).
     fn tier_sink_snapshot(&self) -> Option<TierSink> {
@@ -98,6 +211,10 @@ impl IbkrClient {
     }
 
     pub async fn connect(&self) -> Result<()> {
+        if let Some(demo) = self.demo() {
+            demo.connect();
+            return Ok(());
+        }
         // Check if already connected and verify the connection is still active
         {
             let client_lock = self.client.read().await;
@@ -228,6 +345,10 @@ impl IbkrClient {
 
     pub async fn disconnect(&self) -> Result<()> {
         info!("🔴 CLIENT DISCONNECT METHOD CALLED");
+        if let Some(demo) = self.demo() {
+            demo.disconnect();
+            return Ok(());
+        }
         // Reset the tier first so consumers see `Unknown` immediately
         // (before the blocking client drop holds the write lock).
         if let Some(sink) = self.tier_sink_snapshot() {
@@ -278,11 +399,22 @@ impl IbkrClient {
 
     #[allow(dead_code)]
     pub async fn is_connected(&self) -> bool {
+        if let Some(demo) = self.demo() {
+            return demo.is_connected();
+        }
         let client = self.client.read().await;
         client.is_some()
     }
 
     pub async fn get_connection_status(&self) -> Result<ConnectionStatus> {
+        if let Some(demo) = self.demo() {
+            let connected = demo.is_connected();
+            return Ok(ConnectionStatus {
+                connected,
+                server_time: connected.then(|| chrono::Utc::now().to_string()),
+                client_id: self.config.read().await.client_id,
+            });
+        }
         let client = self.client.read().await;
         let config = self.config.read().await;
 
@@ -325,11 +457,14 @@ impl IbkrClient {
     }
 
     pub async fn get_accounts(&self) -> Result<Vec<String>> {
-        let client_clone = self.ibapi_client().await?;
+        if let Some(demo) = self.demo() {
+            return demo.accounts();
+        }
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_ACCOUNT).await?;
 
-        let accounts = tokio::task::spawn_blocking(move || client_clone.managed_accounts())
-            .await
-            .map_err(|e| IbkrError::Unknown(e.to_string()))?;
+        let accounts = self
+            .run_blocking(move || client_clone.managed_accounts())
+            .await?;
 
         match accounts {
             Ok(accounts) => {
@@ -339,131 +474,4 @@ impl IbkrClient {
             Err(e) => Err(IbkrError::from(e)),
         }
     }
-
-    pub async fn get_account_summary(&self, account: &str) -> Result<Vec<AccountSummary>> {
-        let client_clone = self.ibapi_client().await?;
-
-        let account = account.to_string();
-        let updates_guard = self.account_updates_lock.clone().lock_owned().await;
-
-        let summaries = tokio::task::spawn_blocking(move || {
-            let _updates_guard = updates_guard;
-            let mut summaries = Vec::new();
-
-            // Use account_updates to get all account values
-            let account_id = AccountId(account.clone());
-            match client_clone.account_updates(&account_id) {
-                Ok(stream) => {
-                    for update in stream {
-                        match update {
-                            ibapi::accounts::AccountUpdate::AccountValue(value) => {
-                                tracing::info!(
-                                    "Account value: key={}, value={}, currency={}",
-                                    value.key,
-                                    value.value,
-                                    value.currency
-                                );
-                                summaries.push(AccountSummary {
-                                    account: account.clone(),
-                                    tag: value.key,
-                                    value: value.value,
-                                    currency: value.currency,
-                                });
-                            }
-                            ibapi::accounts::AccountUpdate::End => {
-                                tracing::info!("Account updates end reached");
-                                break;
-                            }
-                            _ => {} // Ignore other update types (PortfolioValue, UpdateTime)
-                        }
-                    }
-                    Ok(summaries)
-                }
-                Err(e) => Err(IbkrError::from(e)),
-            }
-        })
-        .await
-        .map_err(|e| IbkrError::Unknown(e.to_string()))?;
-
-        summaries
-    }
-
-    pub async fn get_positions(&self, account: &str) -> Result<Vec<Position>> {
-        let client_clone = self.ibapi_client().await?;
-
-        let updates_guard = self.account_updates_lock.clone().lock_owned().await;
-        let account = account.to_string();
-
-        let positions = tokio::task::spawn_blocking(move || {
-            let _updates_guard = updates_guard;
-            let mut positions = Vec::new();
-            // Use account_updates to get portfolio values with market data
-            let account_id = AccountId(account.clone());
-            match client_clone.account_updates(&account_id) {
-                Ok(stream) => {
-                    for update in stream {
-                        match update {
-                            ibapi::accounts::AccountUpdate::PortfolioValue(portfolio) => {
-                                tracing::debug!("Portfolio position: symbol={}, position={}, market_price={}, market_value={}, unrealized_pnl={}",
-                                    portfolio.contract.symbol, portfolio.position, portfolio.market_price,
-                                    portfolio.market_value, portfolio.unrealized_pnl);
-                                let contract_type =
-                                    portfolio.contract.security_type.clone().to_string();
-                                // Stock contracts come back with empty
-                                // strings / 0.0 in the option-only fields;
-                                // surface them as `None` so the JSON omits
-                                // them entirely (see `Position` serde
-                                // `skip_serializing_if`).
-                                let is_option_like =
-                                    matches!(contract_type.as_str(), "OPT" | "FOP" | "FUT" | "WAR");
-                                let opt_string = |s: String| {
-                                    if is_option_like && !s.is_empty() {
-                                        Some(s)
-                                    } else {
-                                        None
-                                    }
-                                };
-                                let opt_f64 = |v: f64| {
-                                    if is_option_like && v != 0.0 {
-                                        Some(v)
-                                    } else {
-                                        None
-                                    }
-                                };
-                                positions.push(Position {
-                                    account: portfolio.account.clone().unwrap_or_else(|| account.clone()),
-                                    symbol: portfolio.contract.symbol.0.clone(),
-                                    position: portfolio.position,
-                                    average_cost: portfolio.average_cost,
-                                    market_price: portfolio.market_price,
-                                    market_value: portfolio.market_value,
-                                    unrealized_pnl: portfolio.unrealized_pnl,
-                                    realized_pnl: portfolio.realized_pnl,
-                                    contract_type,
-                                    currency: portfolio.contract.currency.0.clone(),
-                                    exchange: portfolio.contract.exchange.0.clone(),
-                                    local_symbol: portfolio.contract.local_symbol.clone(),
-                                    expiry: opt_string(
-                                        portfolio.contract.last_trade_date_or_contract_month.clone(),
-                                    ),
-                                    strike: opt_f64(portfolio.contract.strike),
-                                    right: opt_string(portfolio.contract.right.clone()),
-                                    multiplier: opt_string(portfolio.contract.multiplier.clone()),
-                                });
-                            }
-                            ibapi::accounts::AccountUpdate::End => {
-                                tracing::debug!("All portfolio positions received");
-                                break;
-                            }
-                            _ => {} // Ignore other update types
-                        }
-                    }
-                    Ok(positions)
-                }
-                Err(e) => Err(IbkrError::from(e))
-            }
-        }).await.map_err(|e| IbkrError::Unknown(e.to_string()))?;
-
-        positions
-    }
 }
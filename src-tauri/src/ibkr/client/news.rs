@@ -12,24 +12,29 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use chrono::Utc;
-use ibapi::contracts::Contract;
 use time::{Duration as TimeDuration, OffsetDateTime};
 
 use crate::ibkr::error::IbkrError;
+use crate::middleware::rate_limits;
 use crate::services::news_provider::ibkr::client::{
     IbkrHeadline, IbkrNewsClient, IbkrNewsProviderInfo,
 };
 use crate::services::news_provider::NewsError;
 
+use super::contract::stock_contract;
 use super::IbkrClient;
 
 #[async_trait]
 impl IbkrNewsClient for IbkrClient {
     async fn news_providers(&self) -> Result<Vec<IbkrNewsProviderInfo>, NewsError> {
-        let client = self.ibapi_client().await.map_err(ibkr_to_news_error)?;
-        let res = tokio::task::spawn_blocking(move || client.news_providers())
+        let client = self
+            .ibapi_client(rate_limits::ENDPOINT_NEWS)
             .await
-            .map_err(|e| NewsError::Other(format!("join: {e}")))?;
+            .map_err(ibkr_to_news_error)?;
+        let res = self
+            .run_blocking(move || client.news_providers())
+            .await
+            .map_err(ibkr_to_news_error)?;
         match res {
             Ok(rows) => Ok(rows
                 .into_iter()
@@ -55,29 +60,35 @@ impl IbkrNewsClient for IbkrClient {
             });
         }
 
-        let client = self.ibapi_client().await.map_err(ibkr_to_news_error)?;
+        let client = self
+            .ibapi_client(rate_limits::ENDPOINT_NEWS)
+            .await
+            .map_err(ibkr_to_news_error)?;
 
         let symbol_owned = symbol.to_string();
         let codes_owned: Vec<String> = provider_codes.to_vec();
 
         // Resolve conid up-front. `req_historical_news` requires a
-        // numeric contract id; `Contract::stock(symbol).build()`
+        // numeric contract id; `stock_contract(symbol, None)`
         // alone is rejected with code 200 ("No security definition…")
         // because the routing exchange isn't pinned.
         let client_for_resolve = Arc::clone(&client);
         let resolve_symbol = symbol_owned.clone();
-        let conid = tokio::task::spawn_blocking(move || -> Result<i32, ibapi::Error> {
-            let contract = Contract::stock(&resolve_symbol).build();
-            let details = client_for_resolve.contract_details(&contract)?;
-            details
-                .into_iter()
-                .next()
-                .map(|d| d.contract.contract_id)
-                .ok_or_else(|| ibapi::Error::Simple(format!("no contract for {resolve_symbol}")))
-        })
-        .await
-        .map_err(|e| NewsError::Other(format!("conid join: {e}")))?
-        .map_err(|e| ibapi_to_news_error(e, Some(&symbol_owned)))?;
+        let conid = self
+            .run_blocking(move || -> Result<i32, ibapi::Error> {
+                let contract = stock_contract(&resolve_symbol, None);
+                let details = client_for_resolve.contract_details(&contract)?;
+                details
+                    .into_iter()
+                    .next()
+                    .map(|d| d.contract.contract_id)
+                    .ok_or_else(|| {
+                        ibapi::Error::Simple(format!("no contract for {resolve_symbol}"))
+                    })
+            })
+            .await
+            .map_err(ibkr_to_news_error)?
+            .map_err(|e| ibapi_to_news_error(e, Some(&symbol_owned)))?;
 
         // historical_news start/end window. AV's `lookback_hours`
         // semantic is "rows inside the last N hours" — we mirror that
@@ -88,8 +99,8 @@ impl IbkrNewsClient for IbkrClient {
 
         let client_for_news = client;
         let codes_for_news = codes_owned.clone();
-        let news_res =
-            tokio::task::spawn_blocking(move || -> Result<Vec<IbkrHeadline>, ibapi::Error> {
+        let news_res = self
+            .run_blocking(move || -> Result<Vec<IbkrHeadline>, ibapi::Error> {
                 let codes_borrowed: Vec<&str> = codes_for_news.iter().map(String::as_str).collect();
                 let subscription = client_for_news.historical_news(
                     conid,
@@ -114,7 +125,7 @@ impl IbkrNewsClient for IbkrClient {
                 Ok(out)
             })
             .await
-            .map_err(|e| NewsError::Other(format!("historical_news join: {e}")))?;
+            .map_err(ibkr_to_news_error)?;
 
         match news_res {
             Ok(headlines) => Ok(headlines),
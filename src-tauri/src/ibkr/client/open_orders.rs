@@ -0,0 +1,88 @@
+//! `open_orders` — every working order on the Gateway session
+//! (`reqAllOpenOrders`), including ones entered in TWS.
+//!
+//! IBKR answers with an `OpenOrder` row per order and usually an
+//! `OrderStatus` row carrying the live `remaining`; the stream ends at
+//! `OpenOrderEnd`. Orders already done (filled, cancelled, inactive)
+//! are dropped.
+
+use std::collections::HashMap;
+
+use ibapi::orders::{Action, OrderData, Orders};
+
+use crate::ibkr::error::{IbkrError, Result};
+use crate::ibkr::types::{OpenOrder, OrderAction};
+use crate::middleware::rate_limits;
+
+use super::IbkrClient;
+
+const DONE_STATUSES: [&str; 5] = [
+    "Filled",
+    "Cancelled",
+    "ApiCancelled",
+    "PendingCancel",
+    "Inactive",
+];
+
+impl IbkrClient {
+    pub async fn open_orders(&self) -> Result<Vec<OpenOrder>> {
+        // Demo orders fill at once; nothing rests.
+        if self.demo().is_some() {
+            return Ok(Vec::new());
+        }
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_ORDERS).await?;
+
+        self.run_blocking(move || -> Result<Vec<OpenOrder>> {
+            let subscription = client_clone.all_open_orders().map_err(IbkrError::from)?;
+            let mut orders = Vec::new();
+            let mut remaining = HashMap::new();
+            for event in subscription.iter() {
+                match event {
+                    Orders::OrderData(data) => orders.push(data),
+                    Orders::OrderStatus(status) => {
+                        remaining.insert(status.order_id, (status.status, status.remaining));
+                    }
+                    Orders::Notice(notice) => {
+                        tracing::debug!("open orders notice: {}", notice.message)
+                    }
+                }
+            }
+            Ok(orders
+                .into_iter()
+                .map(|data| {
+                    let live = remaining.remove(&data.order_id);
+                    to_open_order(data, live)
+                })
+                .filter(|o| o.remaining > 0.0 && !DONE_STATUSES.contains(&o.status.as_str()))
+                .collect())
+        })
+        .await?
+    }
+}
+
+fn to_open_order(data: OrderData, live: Option<(String, f64)>) -> OpenOrder {
+    let (status, remaining) = live.unwrap_or_else(|| {
+        (
+            data.order_state.status.clone(),
+            data.order.total_quantity - data.order.filled_quantity,
+        )
+    });
+    let action = match data.order.action {
+        Action::Buy => OrderAction::Buy,
+        Action::Sell | Action::SellShort | Action::SellLong => OrderAction::Sell,
+    };
+    OpenOrder {
+        order_id: data.order_id,
+        account: data.order.account,
+        symbol: data.contract.symbol.0,
+        sec_type: data.contract.security_type.to_string(),
+        currency: data.contract.currency.0,
+        action,
+        remaining,
+        order_type: data.order.order_type,
+        limit_price: data.order.limit_price,
+        aux_price: data.order.aux_price,
+        multiplier: data.contract.multiplier.parse().unwrap_or(1.0),
+        status,
+    }
+}
@@ -0,0 +1,64 @@
+//! `option_chain` — listed expirations and strikes for a stock
+//! (`reqSecDefOptParams`).
+//!
+//! IBKR wants the underlying's contract id, so the stock's details are
+//! resolved first. One row comes back per exchange and trading class;
+//! the SMART row for the underlying's own trading class is used when
+//! present, otherwise every row is merged.
+
+use ibapi::contracts::SecurityType;
+
+use crate::ibkr::error::{IbkrError, Result};
+use crate::ibkr::types::OptionChain;
+use crate::middleware::rate_limits;
+
+use super::contract::stock_contract;
+use super::IbkrClient;
+
+impl IbkrClient {
+    pub async fn option_chain(&self, symbol: &str) -> Result<OptionChain> {
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_MARKET_DATA).await?;
+        let symbol = symbol.trim().to_uppercase();
+
+        self.run_blocking(move || -> Result<OptionChain> {
+            let contract = stock_contract(&symbol, None);
+            let con_id = client_clone
+                .contract_details(&contract)?
+                .first()
+                .map(|d| d.contract.contract_id)
+                .ok_or_else(|| IbkrError::RequestFailed(format!("no contract for {symbol}")))?;
+            let rows: Vec<_> = client_clone
+                .option_chain(&symbol, "", SecurityType::Stock, con_id)?
+                .iter()
+                .collect();
+
+            let smart: Vec<_> = rows
+                .iter()
+                .filter(|r| r.exchange == "SMART" && r.trading_class == symbol)
+                .collect();
+            let picked = if smart.is_empty() {
+                rows.iter().collect()
+            } else {
+                smart
+            };
+            let mut chain = OptionChain {
+                symbol: symbol.clone(),
+                multiplier: picked
+                    .first()
+                    .map(|r| r.multiplier.clone())
+                    .unwrap_or_else(|| "100".to_string()),
+                ..Default::default()
+            };
+            for row in picked {
+                chain.expirations.extend(row.expirations.iter().cloned());
+                chain.strikes.extend(row.strikes.iter().copied());
+            }
+            chain.expirations.sort();
+            chain.expirations.dedup();
+            chain.strikes.sort_by(f64::total_cmp);
+            chain.strikes.dedup();
+            Ok(chain)
+        })
+        .await?
+    }
+}
@@ -0,0 +1,78 @@
+//! `option_greeks` — TWS model greeks for one option contract.
+//!
+//! Streams market data for the option contract and returns on the
+//! first model-computation tick (`ModelOption`, or its delayed twin
+//! when the connection is on delayed data); TWS pushes these for
+//! options without any generic tick list. The subscription drops, and
+//! is cancelled, on return.
+
+use ibapi::contracts::tick_types::TickType;
+use ibapi::market_data::realtime::TickTypes;
+use tracing::debug;
+
+use crate::ibkr::error::{IbkrError, Result};
+use crate::ibkr::types::{OptionContract, OptionGreeks};
+use crate::middleware::rate_limits;
+
+use super::contract::option_contract;
+use super::market_data::SNAPSHOT_TIMEOUT;
+use super::IbkrClient;
+
+impl IbkrClient {
+    /// Errors with `IbkrError::Timeout` when no model tick arrives in
+    /// time.
+    pub async fn option_greeks(&self, option: &OptionContract) -> Result<OptionGreeks> {
+        let contract = option_contract(option);
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_MARKET_DATA).await?;
+        let label = format!(
+            "{} {} {}{}",
+            option.symbol,
+            option.expiry,
+            option.strike,
+            option.right.as_str()
+        );
+
+        self.run_blocking(move || -> Result<OptionGreeks> {
+            let subscription = client_clone
+                .market_data(&contract)
+                .subscribe()
+                .map_err(IbkrError::from)?;
+            let deadline = std::time::Instant::now() + SNAPSHOT_TIMEOUT;
+            loop {
+                let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+                if remaining.is_zero() {
+                    return Err(IbkrError::Timeout(SNAPSHOT_TIMEOUT.as_millis() as u64));
+                }
+                match subscription.next_timeout(remaining) {
+                    Some(TickTypes::OptionComputation(c))
+                        if matches!(
+                            c.field,
+                            TickType::ModelOption | TickType::DelayedModelOption
+                        ) =>
+                    {
+                        debug!("option_greeks({label}): delta={:?}", c.delta);
+                        return Ok(OptionGreeks {
+                            implied_volatility: c.implied_volatility,
+                            delta: c.delta,
+                            gamma: c.gamma,
+                            theta: c.theta,
+                            vega: c.vega,
+                            option_price: c.option_price,
+                            underlying_price: c.underlying_price,
+                        });
+                    }
+                    Some(TickTypes::Notice(notice)) if notice.code == 354 => {
+                        return Err(IbkrError::MarketDataPermissionDenied);
+                    }
+                    Some(_) => {}
+                    None => {
+                        if let Some(err) = subscription.error() {
+                            return Err(IbkrError::from(err));
+                        }
+                    }
+                }
+            }
+        })
+        .await?
+    }
+}
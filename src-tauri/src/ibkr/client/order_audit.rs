@@ -0,0 +1,186 @@
+//! Order submission choke point and audit feed.
+//!
+//! Every placement path (`place_order`, `place_bracket`,
+//! `modify_stop_price`) submits through [`submit`], which publishes a
+//! `Sent` / `Rejected` [`OrderAuditEvent`] with the exact fields handed
+//! to ibapi and, on success, keeps draining the order's subscription on
+//! a watcher thread so status transitions, fills and notices reach the
+//! sink too. Without a wired sink (tests, early startup) the
+//! subscription is dropped as before.
+
+use std::time::{Duration, Instant};
+
+use ibapi::client::blocking::Client;
+use ibapi::contracts::Contract;
+use ibapi::orders::{Order, PlaceOrder};
+use ibapi::subscriptions::Subscription;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::debug;
+
+use crate::ibkr::error::{IbkrError, Result};
+use crate::ibkr::types::{OrderAuditEvent, SentOrder};
+
+pub(crate) type OrderSink = UnboundedSender<OrderAuditEvent>;
+
+/// How long a watcher follows one order. Covers a GTC limit resting
+/// through a full session; anything longer is picked up by the
+/// executions ingestor instead.
+const WATCH_LIMIT: Duration = Duration::from_secs(8 * 60 * 60);
+/// Trailing messages (the last fill, commission) can land just after a
+/// terminal status.
+const TERMINAL_GRACE: Duration = Duration::from_secs(5);
+
+pub(super) fn sent_order(order_id: i32, symbol: &str, order: &Order) -> SentOrder {
+    SentOrder {
+        order_id,
+        symbol: symbol.to_string(),
+        action: order.action.to_string(),
+        quantity: order.total_quantity,
+        order_type: order.order_type.clone(),
+        limit_price: order.limit_price,
+        aux_price: order.aux_price,
+        parent_id: order.parent_id,
+        transmit: order.transmit,
+        good_after_time: Some(order.good_after_time.clone()).filter(|s| !s.is_empty()),
+        algo_strategy: Some(order.algo_strategy.clone()).filter(|s| !s.is_empty()),
+        algo_params: order
+            .algo_params
+            .iter()
+            .map(|t| (t.tag.clone(), t.value.clone()))
+            .collect(),
+    }
+}
+
+/// Place `order` and report it to `sink`. Blocking; call from inside
+/// `run_blocking`.
+pub(super) fn submit(
+    client: &Client,
+    sink: Option<&OrderSink>,
+    order_id: i32,
+    symbol: &str,
+    contract: &Contract,
+    order: &Order,
+) -> Result<()> {
+    let sent = sent_order(order_id, symbol, order);
+    match client.place_order(order_id, contract, order) {
+        Ok(subscription) => {
+            if let Some(sink) = sink {
+                let _ = sink.send(OrderAuditEvent::Sent(sent));
+                watch(sink.clone(), subscription, order_id, symbol.to_string());
+            }
+            Ok(())
+        }
+        Err(e) => {
+            let err = IbkrError::from(e);
+            if let Some(sink) = sink {
+                let _ = sink.send(OrderAuditEvent::Rejected {
+                    order: sent,
+                    error: err.to_string(),
+                });
+            }
+            Err(err)
+        }
+    }
+}
+
+fn watch(sink: OrderSink, subscription: Subscription<PlaceOrder>, order_id: i32, symbol: String) {
+    std::thread::spawn(move || {
+        let mut deadline = Instant::now() + WATCH_LIMIT;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            let Some(message) = subscription.next_timeout(remaining) else {
+                break;
+            };
+            let Some(event) = translate(message, order_id, &symbol) else {
+                continue;
+            };
+            if event.is_terminal() {
+                deadline = deadline.min(Instant::now() + TERMINAL_GRACE);
+            }
+            if sink.send(event).is_err() {
+                break;
+            }
+        }
+        debug!("order {order_id} ({symbol}): audit watcher done");
+    });
+}
+
+fn translate(message: PlaceOrder, order_id: i32, symbol: &str) -> Option<OrderAuditEvent> {
+    let symbol = symbol.to_string();
+    match message {
+        PlaceOrder::OrderStatus(s) => Some(OrderAuditEvent::Status {
+            order_id,
+            symbol,
+            status: s.status,
+            filled: s.filled,
+            remaining: s.remaining,
+            avg_fill_price: s.average_fill_price,
+        }),
+        PlaceOrder::ExecutionData(e) => Some(OrderAuditEvent::Fill {
+            order_id,
+            symbol,
+            exec_id: e.execution.execution_id,
+            side: e.execution.side,
+            shares: e.execution.shares,
+            price: e.execution.price,
+            time: e.execution.time,
+        }),
+        PlaceOrder::Message(n) => Some(OrderAuditEvent::Notice {
+            order_id,
+            symbol,
+            code: n.code,
+            message: n.message,
+        }),
+        // Echoes of what we sent / commission detail the executions
+        // ingestor already stores.
+        PlaceOrder::OpenOrder(_) | PlaceOrder::CommissionReport(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ibapi::orders::{Action, OrderStatus};
+
+    #[test]
+    fn sent_order_copies_the_submitted_fields() {
+        let order = Order {
+            action: Action::Sell,
+            total_quantity: 25.0,
+            order_type: "STP".to_string(),
+            aux_price: Some(98.5),
+            parent_id: 41,
+            transmit: false,
+            ..Order::default()
+        };
+        let sent = sent_order(42, "AAPL", &order);
+        assert_eq!(sent.action, "SELL");
+        assert_eq!(sent.order_type, "STP");
+        assert_eq!(sent.aux_price, Some(98.5));
+        assert_eq!(sent.parent_id, 41);
+        assert!(!sent.transmit);
+        assert_eq!(sent.good_after_time, None);
+    }
+
+    #[test]
+    fn filled_and_cancelled_statuses_end_the_watch() {
+        let status = |s: &str| {
+            translate(
+                PlaceOrder::OrderStatus(OrderStatus {
+                    status: s.to_string(),
+                    ..OrderStatus::default()
+                }),
+                7,
+                "MSFT",
+            )
+            .unwrap()
+        };
+        assert!(status("Filled").is_terminal());
+        assert!(status("Cancelled").is_terminal());
+        assert!(!status("Submitted").is_terminal());
+        assert_eq!(status("Submitted").order_id(), 7);
+    }
+}
@@ -1,61 +1,106 @@
-use ibapi::contracts::Contract;
 use ibapi::orders::Order;
 
 use crate::ibkr::error::{IbkrError, Result};
 use crate::ibkr::types::{
-    BracketReceipt, BracketRequest, IbkrExecution, ModifyStopRequest, OrderAction, OrderRequest,
-    OrderType,
+    format_good_after, BracketReceipt, BracketRequest, IbkrExecution, ModifyStopRequest,
+    OrderAction, OrderRequest, OrderType, SizeRules,
 };
 
+use crate::middleware::rate_limits;
+
+use super::algo::apply_algo;
+use super::contract::{option_contract, size_rules_blocking, stock_contract};
 use super::executions_merge::merge_commission_reports;
+use super::order_audit::submit;
 use super::IbkrClient;
 
 impl IbkrClient {
     pub async fn place_order(&self, order_request: OrderRequest) -> Result<i32> {
-        let client_clone = self.ibapi_client().await?;
+        if let Some(demo) = self.demo() {
+            return demo.place_order(&order_request);
+        }
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_ORDERS).await?;
+        let sink = self.order_sink_snapshot();
 
-        let order_id = tokio::task::spawn_blocking(move || {
-            let contract = Contract::stock(&order_request.symbol).build();
-            let order_id = client_clone.next_order_id();
+        let order_id = self
+            .run_blocking(move || {
+                let contract = match &order_request.option {
+                    Some(option) => option_contract(option),
+                    None => stock_contract(&order_request.symbol, order_request.route.as_ref()),
+                };
+                let order_id = client_clone.next_order_id();
 
-            let mut order = Order::default();
+                let mut order = Order::default();
 
-            // Set action and order type using the ibapi types
-            use ibapi::orders::Action;
+                // Set action and order type using the ibapi types
+                use ibapi::orders::Action;
 
-            order.action = match order_request.action {
-                OrderAction::Buy => Action::Buy,
-                OrderAction::Sell => Action::Sell,
-            };
+                order.action = match order_request.action {
+                    OrderAction::Buy => Action::Buy,
+                    OrderAction::Sell => Action::Sell,
+                };
 
-            order.total_quantity = order_request.quantity;
+                // Whole quantities skip the contract-details round trip;
+                // a fractional one has to fit the contract's size rules.
+                // Options trade in whole contracts.
+                let rules = if order_request.option.is_none()
+                    && order_request.quantity.fract().abs() > 1e-9
+                {
+                    size_rules_blocking(&client_clone, &contract)?
+                } else {
+                    SizeRules::WHOLE_SHARES
+                };
+                rules.check(order_request.quantity).map_err(|e| {
+                    IbkrError::RequestFailed(format!("{}: {e}", order_request.symbol))
+                })?;
+                order.total_quantity = order_request.quantity;
 
-            // Set order type - Type is likely a string in ibapi
-            match order_request.order_type {
-                OrderType::Market => {
-                    order.order_type = "MKT".to_string();
-                }
-                OrderType::Limit => {
-                    order.order_type = "LMT".to_string();
-                    order.limit_price = order_request.price;
+                // Set order type - Type is likely a string in ibapi
+                if let Some(start) = &order_request.start_at {
+                    let at = start.resolve(chrono::Utc::now());
+                    if at <= chrono::Utc::now() {
+                        return Err(IbkrError::RequestFailed(format!(
+                            "start time {} is in the past",
+                            at.to_rfc3339()
+                        )));
+                    }
+                    order.good_after_time = format_good_after(at);
                 }
-                _ => {
-                    return Err(IbkrError::RequestFailed(
-                        "Order type not implemented".to_string(),
-                    ))
-                }
-            };
 
-            match client_clone.place_order(order_id, &contract, &order) {
-                Ok(_subscription) => {
-                    // TODO: Handle order status updates
-                    Ok(order_id)
+                match order_request.order_type {
+                    OrderType::Market => {
+                        order.order_type = "MKT".to_string();
+                    }
+                    OrderType::Limit => {
+                        order.order_type = "LMT".to_string();
+                        order.limit_price = order_request.price;
+                    }
+                    OrderType::Midprice => {
+                        order.order_type = "MIDPRICE".to_string();
+                        order.limit_price = order_request.price;
+                    }
+                    _ => {
+                        return Err(IbkrError::RequestFailed(
+                            "Order type not implemented".to_string(),
+                        ))
+                    }
+                };
+
+                if let Some(algo) = &order_request.algo {
+                    apply_algo(&mut order, algo)?;
                 }
-                Err(e) => Err(IbkrError::from(e)),
-            }
-        })
-        .await
-        .map_err(|e| IbkrError::Unknown(e.to_string()))?;
+
+                submit(
+                    &client_clone,
+                    sink.as_ref(),
+                    order_id,
+                    &order_request.symbol,
+                    &contract,
+                    &order,
+                )?;
+                Ok(order_id)
+            })
+            .await?;
 
         order_id
     }
@@ -74,12 +119,13 @@ impl IbkrClient {
     /// reductions are IBKR's responsibility; we don't model them
     /// client-side.
     pub async fn place_bracket(&self, req: BracketRequest) -> Result<BracketReceipt> {
-        let client_clone = self.ibapi_client().await?;
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_ORDERS).await?;
+        let sink = self.order_sink_snapshot();
 
-        tokio::task::spawn_blocking(move || -> Result<BracketReceipt> {
+        self.run_blocking(move || -> Result<BracketReceipt> {
             use ibapi::orders::Action;
 
-            let contract = Contract::stock(&req.symbol).build();
+            let contract = stock_contract(&req.symbol, None);
             let parent_id = client_clone.next_order_id();
             let stop_id = client_clone.next_order_id();
             let target_ids: Vec<i32> = (0..req.target_rungs.len())
@@ -124,12 +170,22 @@ impl IbkrClient {
             // Submit parent + stop with transmit=false. The last
             // target child below carries transmit=true to fire the
             // batch.
-            client_clone
-                .place_order(parent_id, &contract, &parent)
-                .map_err(IbkrError::from)?;
-            client_clone
-                .place_order(stop_id, &contract, &stop)
-                .map_err(IbkrError::from)?;
+            submit(
+                &client_clone,
+                sink.as_ref(),
+                parent_id,
+                &req.symbol,
+                &contract,
+                &parent,
+            )?;
+            submit(
+                &client_clone,
+                sink.as_ref(),
+                stop_id,
+                &req.symbol,
+                &contract,
+                &stop,
+            )?;
 
             for (idx, (price, qty)) in req.target_rungs.iter().enumerate() {
                 let target = Order {
@@ -144,9 +200,14 @@ impl IbkrClient {
                     transmit: idx + 1 == req.target_rungs.len(),
                     ..Order::default()
                 };
-                client_clone
-                    .place_order(target_ids[idx], &contract, &target)
-                    .map_err(IbkrError::from)?;
+                submit(
+                    &client_clone,
+                    sink.as_ref(),
+                    target_ids[idx],
+                    &req.symbol,
+                    &contract,
+                    &target,
+                )?;
             }
 
             Ok(BracketReceipt {
@@ -155,8 +216,7 @@ impl IbkrClient {
                 target_order_ids: target_ids,
             })
         })
-        .await
-        .map_err(|e| IbkrError::Unknown(e.to_string()))?
+        .await?
     }
 
     /// Phase 7 — modify an existing stop child's `aux_price`. Used by
@@ -173,12 +233,13 @@ impl IbkrClient {
     /// will reject with `OrderRejected`; callers must verify the
     /// bracket is still open before modifying.
     pub async fn modify_stop_price(&self, req: ModifyStopRequest) -> Result<()> {
-        let client_clone = self.ibapi_client().await?;
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_ORDERS).await?;
+        let sink = self.order_sink_snapshot();
 
-        tokio::task::spawn_blocking(move || -> Result<()> {
+        self.run_blocking(move || -> Result<()> {
             use ibapi::orders::Action;
 
-            let contract = Contract::stock(&req.symbol).build();
+            let contract = stock_contract(&req.symbol, None);
             let action = match req.action {
                 OrderAction::Buy => Action::Buy,
                 OrderAction::Sell => Action::Sell,
@@ -194,13 +255,16 @@ impl IbkrClient {
                 transmit: true,
                 ..Order::default()
             };
-            client_clone
-                .place_order(req.stop_order_id, &contract, &stop)
-                .map_err(IbkrError::from)?;
-            Ok(())
+            submit(
+                &client_clone,
+                sink.as_ref(),
+                req.stop_order_id,
+                &req.symbol,
+                &contract,
+                &stop,
+            )
         })
-        .await
-        .map_err(|e| IbkrError::Unknown(e.to_string()))?
+        .await?
     }
 
     /// Returns the day's executions filtered to the requested ET trading date.
@@ -214,27 +278,30 @@ impl IbkrClient {
     pub async fn executions(&self, date: chrono::NaiveDate) -> Result<Vec<IbkrExecution>> {
         use ibapi::orders::ExecutionFilter;
 
-        let client_clone = self.ibapi_client().await?;
+        if let Some(demo) = self.demo() {
+            return demo.executions(date);
+        }
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_ORDERS).await?;
 
         let date_yyyymmdd = date.format("%Y%m%d").to_string();
 
-        let executions = tokio::task::spawn_blocking(move || -> Result<Vec<IbkrExecution>> {
-            let filter = ExecutionFilter {
-                specific_dates: vec![date_yyyymmdd],
-                ..ExecutionFilter::default()
-            };
+        let executions = self
+            .run_blocking(move || -> Result<Vec<IbkrExecution>> {
+                let filter = ExecutionFilter {
+                    specific_dates: vec![date_yyyymmdd],
+                    ..ExecutionFilter::default()
+                };
 
-            let subscription = client_clone.executions(filter).map_err(IbkrError::from)?;
+                let subscription = client_clone.executions(filter).map_err(IbkrError::from)?;
 
-            let events: Vec<_> = subscription.iter().collect();
-            // Orphans (CommissionReport without a matching ExecutionData)
-            // are warned about inside the merge; we drop the
-            // `orphan_commission_ids` field on the floor here because the
-            // production drain has no other use for it.
-            Ok(merge_commission_reports(events, date).rows)
-        })
-        .await
-        .map_err(|e| IbkrError::Unknown(e.to_string()))??;
+                let events: Vec<_> = subscription.iter().collect();
+                // Orphans (CommissionReport without a matching ExecutionData)
+                // are warned about inside the merge; we drop the
+                // `orphan_commission_ids` field on the floor here because the
+                // production drain has no other use for it.
+                Ok(merge_commission_reports(events, date).rows)
+            })
+            .await??;
 
         Ok(executions)
     }
@@ -0,0 +1,134 @@
+//! Registry of cancellable in-flight IBKR requests.
+//!
+//! Long stream drains (`account_updates` behind `get_account_summary` /
+//! `get_positions`) register a [`RequestTicket`] for their lifetime.
+//! `ibkr_list_requests` shows what's running and `ibkr_cancel_request`
+//! flips the ticket's token; the drain loop polls it between messages,
+//! cancels the ibapi subscription and returns `IbkrError::Cancelled`.
+//! Dropping the ticket (normal completion, error, timeout) removes the
+//! entry, so the registry only ever lists live work.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InFlightRequest {
+    pub request_id: u64,
+    /// Client method, e.g. `get_positions`.
+    pub kind: &'static str,
+    pub account: String,
+    pub started_at: DateTime<Utc>,
+}
+
+struct Entry {
+    info: InFlightRequest,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+pub struct RequestRegistry {
+    next_id: AtomicU64,
+    entries: Arc<Mutex<HashMap<u64, Entry>>>,
+}
+
+impl RequestRegistry {
+    pub fn register(&self, kind: &'static str, account: &str) -> RequestTicket {
+        let request_id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let info = InFlightRequest {
+            request_id,
+            kind,
+            account: account.to_string(),
+            started_at: Utc::now(),
+        };
+        self.entries.lock().expect("requests poisoned").insert(
+            request_id,
+            Entry {
+                info,
+                cancelled: Arc::clone(&cancelled),
+            },
+        );
+        RequestTicket {
+            request_id,
+            cancelled,
+            entries: Arc::clone(&self.entries),
+        }
+    }
+
+    /// Flag `request_id` for cancellation. `false` when it isn't (or is
+    /// no longer) in flight.
+    pub fn cancel(&self, request_id: u64) -> bool {
+        match self
+            .entries
+            .lock()
+            .expect("requests poisoned")
+            .get(&request_id)
+        {
+            Some(entry) => {
+                entry.cancelled.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Oldest first.
+    pub fn list(&self) -> Vec<InFlightRequest> {
+        let mut out: Vec<InFlightRequest> = self
+            .entries
+            .lock()
+            .expect("requests poisoned")
+            .values()
+            .map(|e| e.info.clone())
+            .collect();
+        out.sort_by_key(|r| r.request_id);
+        out
+    }
+}
+
+/// Held by the blocking drain for the request's lifetime.
+pub struct RequestTicket {
+    pub request_id: u64,
+    cancelled: Arc<AtomicBool>,
+    entries: Arc<Mutex<HashMap<u64, Entry>>>,
+}
+
+impl RequestTicket {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for RequestTicket {
+    fn drop(&mut self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(&self.request_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_flags_ticket_and_drop_unregisters() {
+        let registry = RequestRegistry::default();
+        let ticket = registry.register("get_positions", "DU123456");
+        let other = registry.register("get_account_summary", "DU123456");
+        assert_eq!(registry.list().len(), 2);
+
+        assert!(registry.cancel(ticket.request_id));
+        assert!(ticket.is_cancelled());
+        assert!(!other.is_cancelled());
+
+        let id = ticket.request_id;
+        drop(ticket);
+        assert_eq!(registry.list().len(), 1);
+        assert!(!registry.cancel(id));
+    }
+}
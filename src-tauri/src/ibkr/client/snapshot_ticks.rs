@@ -0,0 +1,142 @@
+//! Pure tick-to-snapshot logic shared by the `reqMktData` paths in
+//! [`super::market_data`]: folding price / size ticks into a
+//! `MarketDataSnapshot`, stamping its trading session, and classifying
+//! ticks into the data tier they imply for the connect-time probe.
+
+use ibapi::contracts::tick_types::TickType;
+use ibapi::market_data::realtime::{TickPrice, TickPriceSize, TickSize};
+
+use crate::ibkr::types::{ContractRoute, DataTier, MarketDataSnapshot};
+use crate::utils::market_calendar::{self, TradingSession};
+
+/// Classify a single `TickType` into the data tier it implies.
+///
+/// Returns `None` for tick types that don't carry a tier signal (e.g.
+/// `Halted`, `RtVolume`, options-specific ticks). The probe loop keeps
+/// reading until it gets a `Some(_)` or hits its timeout / snapshot
+/// end, so unclassified ticks just don't move the answer.
+pub(super) fn classify_tick_type(tick_type: TickType) -> Option<DataTier> {
+    match tick_type {
+        TickType::Bid
+        | TickType::Ask
+        | TickType::Last
+        | TickType::High
+        | TickType::Low
+        | TickType::Close
+        | TickType::Open
+        | TickType::Volume
+        | TickType::BidSize
+        | TickType::AskSize
+        | TickType::LastSize => Some(DataTier::RealTime),
+        TickType::DelayedBid
+        | TickType::DelayedAsk
+        | TickType::DelayedLast
+        | TickType::DelayedHigh
+        | TickType::DelayedLow
+        | TickType::DelayedClose
+        | TickType::DelayedOpen
+        | TickType::DelayedVolume
+        | TickType::DelayedBidSize
+        | TickType::DelayedAskSize
+        | TickType::DelayedLastSize => Some(DataTier::Delayed),
+        _ => None,
+    }
+}
+
+pub(super) fn empty_snapshot(symbol: &str) -> MarketDataSnapshot {
+    MarketDataSnapshot {
+        symbol: symbol.to_string(),
+        bid_price: None,
+        bid_size: None,
+        ask_price: None,
+        ask_size: None,
+        last_price: None,
+        last_size: None,
+        high: None,
+        low: None,
+        volume: None,
+        close: None,
+        open: None,
+        last_rth_price: None,
+        session: None,
+        timestamp: chrono::Utc::now().timestamp(),
+    }
+}
+
+/// Stamp a completed snapshot with its time and the venue's trading
+/// session. During regular hours the last trade is the regular-session
+/// price, so it fills in for a missing tick 318.
+pub(super) fn finish(
+    snapshot: &mut MarketDataSnapshot,
+    route: Option<&ContractRoute>,
+    now: chrono::DateTime<chrono::Utc>,
+) {
+    snapshot.timestamp = now.timestamp();
+    let exchange = route
+        .and_then(ContractRoute::listing_exchange)
+        .unwrap_or("SMART");
+    snapshot.session = market_calendar::trading_session(exchange, now);
+    if snapshot.session == Some(TradingSession::Regular) && snapshot.last_rth_price.is_none() {
+        snapshot.last_rth_price = snapshot.last_price;
+    }
+}
+
+/// True if `snapshot` carries any usable price (last, close, or open).
+/// Used by the streaming path to decide whether a quiet-window or
+/// channel-close should return `Ok(snapshot)` or `Err(Timeout)`.
+pub(super) fn snapshot_has_price(snapshot: &MarketDataSnapshot) -> bool {
+    snapshot.last_price.is_some() || snapshot.close.is_some() || snapshot.open.is_some()
+}
+
+pub(super) fn apply_price(snapshot: &mut MarketDataSnapshot, tick: &TickPrice) {
+    match tick.tick_type {
+        TickType::Bid | TickType::DelayedBid => snapshot.bid_price = Some(tick.price),
+        TickType::Ask | TickType::DelayedAsk => snapshot.ask_price = Some(tick.price),
+        TickType::Last | TickType::DelayedLast => snapshot.last_price = Some(tick.price),
+        TickType::High | TickType::DelayedHigh => snapshot.high = Some(tick.price),
+        TickType::Low | TickType::DelayedLow => snapshot.low = Some(tick.price),
+        TickType::Close | TickType::DelayedClose => snapshot.close = Some(tick.price),
+        TickType::Open | TickType::DelayedOpen => snapshot.open = Some(tick.price),
+        TickType::LastRthTrade => snapshot.last_rth_price = Some(tick.price),
+        _ => {}
+    }
+}
+
+pub(super) fn apply_size(snapshot: &mut MarketDataSnapshot, tick: &TickSize) {
+    match tick.tick_type {
+        TickType::BidSize | TickType::DelayedBidSize => {
+            snapshot.bid_size = Some(tick.size as i32);
+        }
+        TickType::AskSize | TickType::DelayedAskSize => {
+            snapshot.ask_size = Some(tick.size as i32);
+        }
+        TickType::LastSize | TickType::DelayedLastSize => {
+            snapshot.last_size = Some(tick.size as i32);
+        }
+        TickType::Volume | TickType::DelayedVolume => {
+            snapshot.volume = Some(tick.size as i64);
+        }
+        _ => {}
+    }
+}
+
+pub(super) fn apply_price_size(snapshot: &mut MarketDataSnapshot, tick: &TickPriceSize) {
+    match tick.price_tick_type {
+        TickType::Bid | TickType::DelayedBid => snapshot.bid_price = Some(tick.price),
+        TickType::Ask | TickType::DelayedAsk => snapshot.ask_price = Some(tick.price),
+        TickType::Last | TickType::DelayedLast => snapshot.last_price = Some(tick.price),
+        _ => {}
+    }
+    match tick.size_tick_type {
+        TickType::BidSize | TickType::DelayedBidSize => snapshot.bid_size = Some(tick.size as i32),
+        TickType::AskSize | TickType::DelayedAskSize => snapshot.ask_size = Some(tick.size as i32),
+        TickType::LastSize | TickType::DelayedLastSize => {
+            snapshot.last_size = Some(tick.size as i32);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+#[path = "snapshot_ticks_tests.rs"]
+mod tests;
@@ -0,0 +1,166 @@
+use super::*;
+
+#[test]
+fn classify_real_time_price_ticks() {
+    assert_eq!(classify_tick_type(TickType::Last), Some(DataTier::RealTime));
+    assert_eq!(classify_tick_type(TickType::Bid), Some(DataTier::RealTime));
+    assert_eq!(classify_tick_type(TickType::Ask), Some(DataTier::RealTime));
+    assert_eq!(classify_tick_type(TickType::High), Some(DataTier::RealTime));
+    assert_eq!(classify_tick_type(TickType::Low), Some(DataTier::RealTime));
+    assert_eq!(
+        classify_tick_type(TickType::Close),
+        Some(DataTier::RealTime)
+    );
+    assert_eq!(classify_tick_type(TickType::Open), Some(DataTier::RealTime));
+    assert_eq!(
+        classify_tick_type(TickType::Volume),
+        Some(DataTier::RealTime)
+    );
+}
+
+#[test]
+fn classify_real_time_size_ticks() {
+    assert_eq!(
+        classify_tick_type(TickType::BidSize),
+        Some(DataTier::RealTime)
+    );
+    assert_eq!(
+        classify_tick_type(TickType::AskSize),
+        Some(DataTier::RealTime)
+    );
+    assert_eq!(
+        classify_tick_type(TickType::LastSize),
+        Some(DataTier::RealTime)
+    );
+}
+
+#[test]
+fn classify_delayed_price_ticks() {
+    assert_eq!(
+        classify_tick_type(TickType::DelayedLast),
+        Some(DataTier::Delayed)
+    );
+    assert_eq!(
+        classify_tick_type(TickType::DelayedBid),
+        Some(DataTier::Delayed)
+    );
+    assert_eq!(
+        classify_tick_type(TickType::DelayedAsk),
+        Some(DataTier::Delayed)
+    );
+    assert_eq!(
+        classify_tick_type(TickType::DelayedHigh),
+        Some(DataTier::Delayed)
+    );
+    assert_eq!(
+        classify_tick_type(TickType::DelayedLow),
+        Some(DataTier::Delayed)
+    );
+    assert_eq!(
+        classify_tick_type(TickType::DelayedClose),
+        Some(DataTier::Delayed)
+    );
+    assert_eq!(
+        classify_tick_type(TickType::DelayedOpen),
+        Some(DataTier::Delayed)
+    );
+    assert_eq!(
+        classify_tick_type(TickType::DelayedVolume),
+        Some(DataTier::Delayed)
+    );
+}
+
+#[test]
+fn classify_delayed_size_ticks() {
+    assert_eq!(
+        classify_tick_type(TickType::DelayedBidSize),
+        Some(DataTier::Delayed)
+    );
+    assert_eq!(
+        classify_tick_type(TickType::DelayedAskSize),
+        Some(DataTier::Delayed)
+    );
+    assert_eq!(
+        classify_tick_type(TickType::DelayedLastSize),
+        Some(DataTier::Delayed)
+    );
+}
+
+#[test]
+fn classify_unrelated_ticks_returns_none() {
+    assert_eq!(classify_tick_type(TickType::Halted), None);
+    assert_eq!(classify_tick_type(TickType::Unknown), None);
+    assert_eq!(classify_tick_type(TickType::RtVolume), None);
+    assert_eq!(classify_tick_type(TickType::MarkPrice), None);
+}
+
+#[test]
+fn snapshot_has_price_detects_any_filled_price_field() {
+    let mut snap = empty_snapshot("AMD");
+    assert!(!snapshot_has_price(&snap));
+
+    snap.bid_price = Some(100.0);
+    snap.ask_price = Some(100.5);
+    assert!(
+        !snapshot_has_price(&snap),
+        "bid/ask alone shouldn't count — UI needs last/close/open"
+    );
+
+    snap.last_price = Some(100.25);
+    assert!(snapshot_has_price(&snap));
+
+    let mut snap = empty_snapshot("AMD");
+    snap.close = Some(99.0);
+    assert!(snapshot_has_price(&snap));
+
+    let mut snap = empty_snapshot("AMD");
+    snap.open = Some(98.5);
+    assert!(snapshot_has_price(&snap));
+}
+
+#[test]
+fn empty_snapshot_carries_symbol_and_clears_optionals() {
+    let snap = empty_snapshot("AMD");
+    assert_eq!(snap.symbol, "AMD");
+    assert!(snap.bid_price.is_none());
+    assert!(snap.ask_price.is_none());
+    assert!(snap.last_price.is_none());
+    assert!(snap.close.is_none());
+    assert!(snap.open.is_none());
+    assert!(snap.high.is_none());
+    assert!(snap.low.is_none());
+    assert!(snap.volume.is_none());
+    assert!(snap.bid_size.is_none());
+    assert!(snap.ask_size.is_none());
+    assert!(snap.last_size.is_none());
+}
+
+#[test]
+fn finish_tags_session_and_fills_rth_price_in_regular_hours() {
+    use chrono::TimeZone;
+
+    // Tue 2026-04-28 10:00 EDT, then 18:00 EDT.
+    let regular = chrono::Utc.with_ymd_and_hms(2026, 4, 28, 14, 0, 0).unwrap();
+    let post = chrono::Utc.with_ymd_and_hms(2026, 4, 28, 22, 0, 0).unwrap();
+
+    let mut snap = empty_snapshot("AMD");
+    snap.last_price = Some(101.0);
+    finish(&mut snap, None, regular);
+    assert_eq!(snap.session, Some(TradingSession::Regular));
+    assert_eq!(snap.last_rth_price, Some(101.0));
+    assert_eq!(snap.timestamp, regular.timestamp());
+
+    let mut snap = empty_snapshot("AMD");
+    snap.last_price = Some(103.5);
+    finish(&mut snap, None, post);
+    assert_eq!(snap.session, Some(TradingSession::Post));
+    assert_eq!(snap.last_rth_price, None);
+
+    let lse = ContractRoute {
+        primary_exchange: Some("LSE".to_string()),
+        ..Default::default()
+    };
+    let mut snap = empty_snapshot("VOD");
+    finish(&mut snap, Some(&lse), regular);
+    assert_eq!(snap.session, None);
+}
@@ -8,6 +8,8 @@ use crate::events::{AppEvent, EventEmitter};
 use crate::ibkr::error::Result;
 use crate::ibkr::types::{ContractDetails, ScannerData, ScannerSubscription, SecurityType};
 
+use crate::middleware::rate_limits;
+
 use super::IbkrClient;
 
 pub struct StreamHandle {
@@ -41,7 +43,7 @@ impl IbkrClient {
     ) -> Result<StreamHandle> {
         use ibapi::accounts::types::AccountId;
 
-        let client_clone = self.ibapi_client().await?;
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_ACCOUNT).await?;
 
         let account = account.to_string();
         let shutdown = Arc::new(AtomicBool::new(false));
@@ -94,8 +96,8 @@ impl IbkrClient {
         opts: ScannerSubscription,
         timeout: Duration,
     ) -> Result<Vec<ScannerData>> {
-        let client_clone = self.ibapi_client().await?;
-        let task = tokio::task::spawn_blocking(move || -> Result<Vec<ScannerData>> {
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_SCANNER).await?;
+        let task = self.run_blocking(move || -> Result<Vec<ScannerData>> {
             let ib_sub = to_ibapi_scanner_subscription(&opts);
             let filter = scanner_filter_options(&opts);
             let subscription = client_clone
@@ -119,7 +121,7 @@ impl IbkrClient {
         opts: ScannerSubscription,
         emitter: Arc<EventEmitter>,
     ) -> Result<StreamHandle> {
-        let client_clone = self.ibapi_client().await?;
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_SCANNER).await?;
 
         let shutdown = Arc::new(AtomicBool::new(false));
         let shutdown_task = Arc::clone(&shutdown);
@@ -0,0 +1,145 @@
+//! `what_if_margin` — IBKR's margin check for an order it doesn't
+//! place.
+//!
+//! The legs go out as one market order with `what_if` set: a single
+//! leg as itself, several as a `BAG` on SMART with each leg's quantity
+//! as its ratio, so IBKR margins them together (a covered call, a
+//! spread) rather than one at a time. TWS answers with an open-order
+//! message carrying the margin changes and places nothing. Because
+//! nothing is placed this doesn't go through the order audit `submit`.
+
+use std::time::{Duration, Instant};
+
+use ibapi::contracts::{ComboLeg as IbComboLeg, Contract, SecurityType};
+use ibapi::orders::{Action, Order, PlaceOrder};
+
+use crate::ibkr::error::{IbkrError, Result};
+use crate::ibkr::types::{MarginImpact, OrderAction, WhatIfLeg};
+use crate::middleware::rate_limits;
+
+use super::contract::{option_contract, stock_contract};
+use super::IbkrClient;
+
+/// How long to wait for TWS's what-if answer.
+const WHAT_IF_TIMEOUT: Duration = Duration::from_secs(10);
+
+impl IbkrClient {
+    /// Margin impact of `legs` for `account`, as one order. Errors with
+    /// `IbkrError::Timeout` when TWS doesn't answer in time.
+    pub async fn what_if_margin(
+        &self,
+        account: &str,
+        legs: Vec<WhatIfLeg>,
+    ) -> Result<MarginImpact> {
+        if legs.is_empty() {
+            return Err(IbkrError::RequestFailed("what-if needs a leg".into()));
+        }
+        if legs
+            .iter()
+            .any(|l| l.quantity <= 0.0 || l.quantity.fract().abs() > 1e-9)
+        {
+            return Err(IbkrError::RequestFailed(
+                "what-if legs take whole, positive quantities".into(),
+            ));
+        }
+        let client_clone = self.ibapi_client(rate_limits::ENDPOINT_ORDERS).await?;
+        let account = account.to_string();
+
+        self.run_blocking(move || -> Result<MarginImpact> {
+            let contract_of = |leg: &WhatIfLeg| match &leg.option {
+                Some(option) => option_contract(option),
+                None => stock_contract(&leg.symbol, None),
+            };
+            let side = |action: &OrderAction| match action {
+                OrderAction::Buy => Action::Buy,
+                OrderAction::Sell => Action::Sell,
+            };
+            let (contract, action, quantity) = match legs.as_slice() {
+                [leg] => (contract_of(leg), side(&leg.action), leg.quantity),
+                _ => {
+                    let symbol = legs[0].symbol.trim().to_uppercase();
+                    let mut combo_legs = Vec::with_capacity(legs.len());
+                    for leg in &legs {
+                        let contract_id = client_clone
+                            .contract_details(&contract_of(leg))?
+                            .first()
+                            .map(|d| d.contract.contract_id)
+                            .ok_or_else(|| {
+                                IbkrError::RequestFailed(format!(
+                                    "no contract for a {} leg",
+                                    leg.symbol
+                                ))
+                            })?;
+                        combo_legs.push(IbComboLeg {
+                            contract_id,
+                            ratio: leg.quantity as i32,
+                            action: match leg.action {
+                                OrderAction::Buy => "BUY",
+                                OrderAction::Sell => "SELL",
+                            }
+                            .to_string(),
+                            exchange: "SMART".to_string(),
+                            ..Default::default()
+                        });
+                    }
+                    let contract = Contract {
+                        symbol: symbol.as_str().into(),
+                        security_type: SecurityType::Spread,
+                        exchange: "SMART".into(),
+                        currency: "USD".into(),
+                        combo_legs,
+                        ..Default::default()
+                    };
+                    (contract, Action::Buy, 1.0)
+                }
+            };
+            let order = Order {
+                action,
+                total_quantity: quantity,
+                order_type: "MKT".to_string(),
+                account,
+                what_if: true,
+                ..Default::default()
+            };
+
+            let order_id = client_clone.next_order_id();
+            let subscription = client_clone
+                .place_order(order_id, &contract, &order)
+                .map_err(IbkrError::from)?;
+            let deadline = Instant::now() + WHAT_IF_TIMEOUT;
+            loop {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Err(IbkrError::Timeout(WHAT_IF_TIMEOUT.as_millis() as u64));
+                }
+                match subscription.next_timeout(remaining) {
+                    Some(PlaceOrder::OpenOrder(data)) => {
+                        let state = data.order_state;
+                        if let (Some(initial), Some(maintenance)) =
+                            (state.initial_margin_change, state.maintenance_margin_change)
+                        {
+                            return Ok(MarginImpact {
+                                initial_change: initial,
+                                maintenance_change: maintenance,
+                                equity_with_loan_change: state.equity_with_loan_change,
+                            });
+                        }
+                    }
+                    Some(PlaceOrder::Message(notice)) if notice.is_error() => {
+                        return Err(IbkrError::RequestFailed(format!(
+                            "what-if rejected ({}): {}",
+                            notice.code, notice.message
+                        )));
+                    }
+                    Some(_) => {}
+                    None => {
+                        if let Some(err) = subscription.error() {
+                            return Err(IbkrError::from(err));
+                        }
+                    }
+                }
+            }
+        })
+        .await?
+    }
+}
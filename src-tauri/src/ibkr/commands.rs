@@ -5,55 +5,157 @@ pub mod accounts;
 pub mod analysis;
 pub mod assessments;
 pub mod auto_scanner;
+pub mod automation;
 pub mod backtest;
+pub mod benchmarks;
 pub mod candidates;
+pub mod carry_costs;
+pub mod cash;
+pub mod cash_drag;
+pub mod combo;
 pub mod connection;
+pub mod corporate_actions;
+pub mod cost_basis;
+pub mod drawdown;
+pub mod edgar;
 pub mod eval;
 pub mod event_calendar;
+pub mod executions;
 pub mod exits;
+pub mod expected_return;
+pub mod factor_exposure;
+pub mod fair_value;
+pub mod fundamental_timeseries;
+pub mod fundamentals_overrides;
+pub mod hedging;
+pub mod insider_activity;
+pub mod iv_rank;
+pub mod jobs;
+pub mod liquidity;
+pub mod macro_snapshot;
+pub mod margin;
+pub mod margin_compare;
+pub mod margin_of_safety;
 pub mod market_data;
+pub mod market_hours;
+pub mod model_portfolio;
+pub mod morning_briefing;
 pub mod news;
+pub mod notifications;
+pub mod notion_export;
+pub mod option_expiry;
+pub mod option_greeks;
+pub mod option_income;
+pub mod option_roll;
+pub mod order_audit;
 pub mod order_ticket;
+pub mod paper_trading;
 pub mod param_refit;
+pub mod portfolio_analysis;
+pub mod portfolio_diff;
+pub mod portfolio_import;
 pub mod portfolio_risk;
+pub mod position_health;
+pub mod position_plans;
+pub mod projection_history;
+pub mod projection_templates;
+pub mod projections;
 pub mod regime;
+pub mod report;
 pub mod research;
 pub mod risk;
 pub mod scanner;
+pub mod scheduler;
+pub mod screener;
 pub mod sentiment;
 pub mod share;
+pub mod stress_test;
+pub mod tax_lots;
 pub mod tca;
 pub mod tilt_guard;
 pub mod tracker;
+pub mod trade_ideas;
 pub mod trade_review_metrics;
 pub mod trades;
 pub mod trading;
+pub mod windows;
 
 // Re-export all commands at the root level for backward compatibility
 pub use accounts::*;
 pub use analysis::*;
 pub use assessments::*;
 pub use auto_scanner::*;
+pub use automation::*;
 pub use backtest::*;
+pub use benchmarks::*;
 pub use candidates::*;
+pub use carry_costs::*;
+pub use cash::*;
+pub use cash_drag::*;
+pub use combo::*;
 pub use connection::*;
+pub use corporate_actions::*;
+pub use cost_basis::*;
+pub use drawdown::*;
+pub use edgar::*;
 pub use eval::*;
 pub use event_calendar::*;
+pub use executions::*;
 pub use exits::*;
+pub use expected_return::*;
+pub use factor_exposure::*;
+pub use fair_value::*;
+pub use fundamental_timeseries::*;
+pub use fundamentals_overrides::*;
+pub use hedging::*;
+pub use insider_activity::*;
+pub use iv_rank::*;
+pub use jobs::*;
+pub use liquidity::*;
+pub use macro_snapshot::*;
+pub use margin::*;
+pub use margin_compare::*;
+pub use margin_of_safety::*;
 pub use market_data::*;
+pub use market_hours::*;
+pub use model_portfolio::*;
+pub use morning_briefing::*;
 pub use news::*;
+pub use notifications::*;
+pub use notion_export::*;
+pub use option_expiry::*;
+pub use option_greeks::*;
+pub use option_income::*;
+pub use option_roll::*;
+pub use order_audit::*;
 pub use order_ticket::*;
+pub use paper_trading::*;
 pub use param_refit::*;
+pub use portfolio_analysis::*;
+pub use portfolio_diff::*;
+pub use portfolio_import::*;
 pub use portfolio_risk::*;
+pub use position_health::*;
+pub use position_plans::*;
+pub use projection_history::*;
+pub use projection_templates::*;
+pub use projections::*;
 pub use regime::*;
+pub use report::*;
 pub use research::*;
 pub use risk::*;
 pub use scanner::*;
+pub use scheduler::*;
+pub use screener::*;
 pub use sentiment::*;
 pub use share::*;
+pub use stress_test::*;
+pub use tax_lots::*;
 pub use tca::*;
 pub use tilt_guard::*;
 pub use tracker::*;
+pub use trade_ideas::*;
 pub use trade_review_metrics::*;
 pub use trades::*;
 pub use trading::*;
+pub use windows::*;
@@ -1,10 +1,29 @@
+use crate::config::SettingsState;
 use crate::ibkr::state::IbkrState;
-use crate::ibkr::types::{AccountSummary, Position};
+use crate::ibkr::types::{AccountInfo, AccountSummary, Position};
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::utils::symbols;
 use tauri::State;
 
+/// Account ids, each with its alias when one is set.
 #[tauri::command]
-pub async fn ibkr_get_accounts(state: State<'_, IbkrState>) -> Result<Vec<String>, String> {
-    state.client.get_accounts().await.map_err(|e| e.to_string())
+pub async fn ibkr_get_accounts(
+    state: State<'_, IbkrState>,
+    settings: State<'_, SettingsState>,
+) -> Result<Vec<AccountInfo>, String> {
+    let ids = state
+        .client
+        .get_accounts()
+        .await
+        .map_err(|e| e.to_string())?;
+    let config = settings.config.read().await;
+    Ok(ids
+        .into_iter()
+        .map(|id| AccountInfo {
+            alias: config.accounts.alias(&id).map(str::to_string),
+            id,
+        })
+        .collect())
 }
 
 #[tauri::command]
@@ -19,11 +38,22 @@ pub async fn ibkr_get_account_summary(
         .map_err(|e| e.to_string())
 }
 
+/// Positions for `account`, optionally only those in `symbol` (an
+/// option's underlying counts), in IBKR's order and windowed by
+/// `limit` / `offset`. Served from `IbkrState::positions` while the
+/// last snapshot is fresh; see `ibkr::position_cache`.
 #[tauri::command]
 pub async fn ibkr_get_positions(
     state: State<'_, IbkrState>,
     account: Option<String>,
-) -> Result<Vec<Position>, String> {
+    symbol: Option<String>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<Position>, CommandError> {
+    let mut inputs = Inputs::new();
+    let page = inputs.page(limit, offset);
+    inputs.finish()?;
+
     // Resolve which account to query: explicit arg wins, otherwise fall
     // back to the first managed account (preserves the pre-existing UI
     // behaviour where the command took no args).
@@ -41,11 +71,32 @@ pub async fn ibkr_get_positions(
                 .ok_or_else(|| "no IBKR accounts available".to_string())?
         }
     };
-    state
-        .client
-        .get_positions(&account)
+    let positions = state
+        .positions
+        .get_or_refresh(&account, || state.client.get_positions(&account))
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    let positions = filter_by_symbol(positions, symbol.as_deref(), |p| p.symbol.as_str());
+    Ok(page.apply(positions))
+}
+
+/// Rows whose symbol normalizes to `symbol`'s; all of them when it is
+/// blank. Not held to [`validate_symbol`](crate::middleware::validation::validate_symbol):
+/// a filter may name a routed non-US ticker.
+pub(crate) fn filter_by_symbol<T>(
+    rows: Vec<T>,
+    symbol: Option<&str>,
+    symbol_of: impl Fn(&T) -> &str,
+) -> Vec<T> {
+    let Some(wanted) = symbol
+        .filter(|s| !s.trim().is_empty())
+        .map(symbols::normalize)
+    else {
+        return rows;
+    };
+    rows.into_iter()
+        .filter(|row| symbols::normalize(symbol_of(row)) == wanted)
+        .collect()
 }
 
 #[tauri::command]
@@ -61,3 +112,22 @@ pub async fn ibkr_stop_daily_pnl(state: State<'_, IbkrState>) -> Result<(), Stri
     state.stop_daily_pnl().await;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbol_filter_matches_any_notation() {
+        let rows = vec!["BRK B", "AAPL", "brk.b", "7203"];
+        assert_eq!(
+            filter_by_symbol(rows.clone(), Some("BRK-B"), |s| s),
+            vec!["BRK B", "brk.b"]
+        );
+        assert_eq!(
+            filter_by_symbol(rows.clone(), Some("7203"), |s| s),
+            vec!["7203"]
+        );
+        assert_eq!(filter_by_symbol(rows.clone(), Some(" "), |s| s), rows);
+    }
+}
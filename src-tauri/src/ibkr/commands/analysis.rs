@@ -1,25 +1,22 @@
 use crate::ibkr::types::{
-    FundamentalData, ProjectionAssumptions, ProjectionResultsWithFundamentals, Quote,
+    ContractRoute, FundamentalData, ProjectionAssumptions, Quote,
     ScenarioProjectionsWithFundamentals,
 };
 use crate::services::cache_service::CacheService;
 use crate::services::fundamentals_provider::{FundamentalsError, FundamentalsProvider};
 use crate::services::projection_service::ProjectionService;
 use crate::services::quote_service::QuoteService;
-use std::collections::HashSet;
+use crate::services::short_interest::ShortInterestService;
+use crate::services::{fundamentals_growth, fundamentals_quality};
 use std::sync::Arc;
 use std::time::Duration;
 use tauri::State;
 use tokio::time::timeout;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
-/// Cap on how long the projection command will wait for the live-quote
-/// overlay before giving up. The IBKR snapshot path waits up to 5s per
-/// fetch (no ticks ⇒ channel close); on weekends / disconnected TWS
-/// every projection render would otherwise eat that full window. The
-/// overlay is best-effort by design — fail fast and let the UI render
-/// without the price rather than block 5s on a quote that isn't coming.
-const OVERLAY_QUOTE_DEADLINE: Duration = Duration::from_millis(1500);
+/// FINRA is one HTTP round trip; past this the fundamentals go out
+/// without short interest.
+const SHORT_INTEREST_DEADLINE: Duration = Duration::from_secs(3);
 
 /// Stable error-string discriminants returned by the fundamentals
 /// commands. Frontend hooks switch on these to render dedicated empty
@@ -47,29 +44,48 @@ fn map_fundamentals_error(err: &FundamentalsError) -> String {
 
 /// Fetch fundamentals via the trait-shaped provider. Phase 3 wires the
 /// AV adapter directly; Phase 4 swaps in the composite (manual store →
-/// AV cache → AV API) without changing this call site.
-async fn fetch_fundamentals(
+/// AV cache → AV API) without changing this call site. The result is
+/// annotated with `fundamentals_quality` findings and the
+/// `fundamentals_growth` decomposition on the way out.
+pub(super) async fn fetch_fundamentals(
     provider: &Arc<dyn FundamentalsProvider>,
     symbol: &str,
 ) -> Result<FundamentalData, String> {
     info!("Fetching fundamentals for {symbol} via FundamentalsProvider");
-    provider
+    let mut data = provider
         .fetch(symbol)
         .await
-        .map_err(|e| map_fundamentals_error(&e))
+        .map_err(|e| map_fundamentals_error(&e))?;
+    fundamentals_quality::annotate(&mut data);
+    fundamentals_growth::annotate(&mut data);
+    if !data.data_quality.is_empty() {
+        warn!(
+            "fundamentals for {symbol}: {} data-quality finding(s)",
+            data.data_quality.len()
+        );
+    }
+    Ok(data)
 }
 
 /// Get fundamental data for a symbol via the configured provider.
 /// Phase 3 removes the silent mock-data fallback; upstream failures
 /// surface as typed error strings the frontend can switch on
 /// (`rate_limited`, `no_data`, `disconnected`, `parse_error`,
-/// `budget_exhausted`, or the `Other` payload verbatim).
+/// `budget_exhausted`, or the `Other` payload verbatim). Short interest
+/// is attached best-effort; a slow or failed FINRA read leaves it unset.
 #[tauri::command]
 pub async fn ibkr_get_fundamental_data(
     fundamentals: State<'_, Arc<dyn FundamentalsProvider>>,
+    short_interest: State<'_, Arc<ShortInterestService>>,
     symbol: String,
 ) -> Result<FundamentalData, String> {
-    fetch_fundamentals(&fundamentals, &symbol).await
+    let mut data = fetch_fundamentals(&fundamentals, &symbol).await?;
+    match timeout(SHORT_INTEREST_DEADLINE, short_interest.annotate(&mut data)).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => warn!("short interest for {symbol} unavailable: {e}"),
+        Err(_) => debug!("short interest for {symbol} timed out"),
+    }
+    Ok(data)
 }
 
 /// Generate financial projections based on fundamental data and assumptions
@@ -90,80 +106,6 @@ pub async fn ibkr_generate_projections(
     })
 }
 
-/// Generate projection results grouped by year (baseline + forward
-/// projections). Returns the underlying fundamentals alongside so the
-/// frontend can render projection inputs without a second fetch — this
-/// is the dedup half of the AV-quota burn fix.
-#[tauri::command]
-pub async fn ibkr_generate_projection_results(
-    fundamentals: State<'_, Arc<dyn FundamentalsProvider>>,
-    quote_service: State<'_, Arc<QuoteService>>,
-    symbol: String,
-    assumptions: Option<ProjectionAssumptions>,
-) -> Result<ProjectionResultsWithFundamentals, String> {
-    generate_projection_results_with_quote(
-        &fundamentals,
-        &quote_service,
-        &symbol,
-        &assumptions.unwrap_or_default(),
-    )
-    .await
-}
-
-/// Fetch fundamentals + live quote, fold the quote's price into
-/// `current_metrics.price`, and run projections. Quote fetch is
-/// best-effort: if IBKR is disconnected / lacks market-data perms /
-/// times out, projections still render — the baseline row's
-/// price-derived fields just fall back to whatever (if anything) the
-/// fundamentals provider supplied.
-async fn generate_projection_results_with_quote(
-    fundamentals: &Arc<dyn FundamentalsProvider>,
-    quote_service: &Arc<QuoteService>,
-    symbol: &str,
-    assumptions: &ProjectionAssumptions,
-) -> Result<ProjectionResultsWithFundamentals, String> {
-    let mut fundamentals = fetch_fundamentals(fundamentals, symbol).await?;
-    overlay_live_price(&mut fundamentals, quote_service, symbol).await;
-    let results = ProjectionService::generate_projection_results(&fundamentals, assumptions)
-        .map_err(|e| e.to_string())?;
-    Ok(ProjectionResultsWithFundamentals {
-        fundamentals,
-        results,
-    })
-}
-
-/// Best-effort overlay of the IBKR live quote onto
-/// `fundamentals.current_metrics.price`. Prefers `last_price` (which
-/// after-hours holds the prior session's last trade — i.e. the close).
-/// Falls back to `prev_close` if the snapshot didn't include a last
-/// tick. Quote-fetch failures are logged and swallowed so projections
-/// still render with whatever (if anything) the fundamentals provider
-/// supplied.
-async fn overlay_live_price(
-    fundamentals: &mut FundamentalData,
-    quote_service: &Arc<QuoteService>,
-    symbol: &str,
-) {
-    match timeout(OVERLAY_QUOTE_DEADLINE, quote_service.fetch_quote(symbol)).await {
-        Ok(Ok(quote)) => {
-            if let Some(price) = quote.last_price.or(quote.prev_close) {
-                fundamentals.current_metrics.price = Some(price);
-            }
-        }
-        Ok(Err(err)) => {
-            debug!(
-                "overlay_live_price: {symbol} quote fetch failed ({err:?}); leaving fundamentals.price untouched"
-            );
-        }
-        Err(_) => {
-            debug!(
-                "overlay_live_price: {symbol} quote fetch exceeded {}ms deadline; leaving fundamentals.price untouched",
-                OVERLAY_QUOTE_DEADLINE.as_millis()
-            );
-        }
-    }
-}
-
 /// Get list of cached ticker symbols
 /// Returns unique ticker symbols that have cached data
 #[tauri::command]
@@ -171,28 +113,14 @@ pub async fn ibkr_get_cached_tickers() -> Result<Vec<String>, String> {
     // Initialize cache service with the same path used by FinancialDataService
     let cache = CacheService::new("cache/alphavantage").map_err(|e| e.to_string())?;
 
-    // Get all valid cache keys
-    let keys = cache.list_valid_keys().map_err(|e| e.to_string())?;
-
-    // Extract unique ticker symbols from cache keys
-    // Cache keys are like "AAPL_overview", "AAPL_income_statement", "AAPL_earnings"
-    let mut tickers = HashSet::new();
-    for key in keys {
-        // Split by underscore and take the first part (the ticker symbol)
-        if let Some(ticker) = key.split('_').next() {
-            tickers.insert(ticker.to_string());
-        }
-    }
-
-    // Convert to sorted vector
-    let mut ticker_list: Vec<String> = tickers.into_iter().collect();
-    ticker_list.sort();
+    let ticker_list = cache.list_cached_symbols().map_err(|e| e.to_string())?;
 
     info!("Found {} cached tickers", ticker_list.len());
     Ok(ticker_list)
 }
 
-/// Fetches a one-shot live quote from IBKR. Maps typed errors to
+/// Fetches a one-shot live quote from IBKR, on `route` (exchange /
+/// currency) when given. Maps typed errors to
 /// stable string discriminants the frontend can switch on:
 ///   - `"disconnected"`         → IbkrError::NotConnected
 ///   - `"no_permission"`        → IbkrError::MarketDataPermissionDenied
@@ -203,11 +131,16 @@ pub async fn ibkr_get_cached_tickers() -> Result<Vec<String>, String> {
 pub async fn ibkr_get_quote(
     quote_service: tauri::State<'_, Arc<QuoteService>>,
     symbol: String,
+    route: Option<ContractRoute>,
 ) -> Result<Quote, String> {
     use crate::ibkr::error::IbkrError;
 
-    debug!("ibkr_get_quote: enter symbol={}", symbol);
-    match quote_service.fetch_quote(&symbol).await {
+    debug!("ibkr_get_quote: enter symbol={} route={:?}", symbol, route);
+    let fetched = match &route {
+        Some(route) => quote_service.fetch_quote_routed(&symbol, route).await,
+        None => quote_service.fetch_quote(&symbol).await,
+    };
+    match fetched {
         Ok(quote) => {
             debug!(
                 "ibkr_get_quote: {} ok last_price={:?} prev_close={:?}",
@@ -237,113 +170,7 @@ pub async fn ibkr_get_quote(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ibkr::error::IbkrError;
-    use crate::ibkr::types::{
-        CurrentMetrics, FundamentalData, HistoricalFinancial, MarketDataSnapshot,
-    };
     use crate::services::fundamentals_provider::test_support::FakeFundamentalsProvider;
-    use crate::services::quote_service::QuoteFetcher;
-    use async_trait::async_trait;
-    use std::sync::Mutex as StdMutex;
-    use std::time::Duration;
-
-    /// Minimal `QuoteFetcher` that hands back one canned snapshot or
-    /// error then panics on subsequent calls. The production
-    /// `quote_service::tests::StubFetcher` is private to its module, so
-    /// we mirror its single-shot shape here.
-    struct StubQuoteFetcher {
-        result: StdMutex<Option<crate::ibkr::error::Result<MarketDataSnapshot>>>,
-    }
-
-    impl StubQuoteFetcher {
-        fn ok(snapshot: MarketDataSnapshot) -> Arc<Self> {
-            Arc::new(Self {
-                result: StdMutex::new(Some(Ok(snapshot))),
-            })
-        }
-
-        fn err(error: IbkrError) -> Arc<Self> {
-            Arc::new(Self {
-                result: StdMutex::new(Some(Err(error))),
-            })
-        }
-    }
-
-    #[async_trait]
-    impl QuoteFetcher for StubQuoteFetcher {
-        async fn get_market_data_snapshot(
-            &self,
-            _symbol: &str,
-        ) -> crate::ibkr::error::Result<MarketDataSnapshot> {
-            self.result
-                .lock()
-                .unwrap()
-                .take()
-                .expect("StubQuoteFetcher called more than once")
-        }
-    }
-
-    /// `QuoteFetcher` that never resolves. Stand-in for the live IBKR
-    /// snapshot path on weekends / disconnected TWS, where
-    /// `subscription.next()` blocks until the channel closes ~5s
-    /// later — driving the overlay's deadline-skip branch.
-    struct HangingQuoteFetcher;
-
-    #[async_trait]
-    impl QuoteFetcher for HangingQuoteFetcher {
-        async fn get_market_data_snapshot(
-            &self,
-            _symbol: &str,
-        ) -> crate::ibkr::error::Result<MarketDataSnapshot> {
-            std::future::pending().await
-        }
-    }
-
-    fn fundamentals_without_price(symbol: &str) -> FundamentalData {
-        FundamentalData {
-            symbol: symbol.to_string(),
-            historical: vec![HistoricalFinancial {
-                year: 2024,
-                revenue: 400.0,
-                net_income: 100.0,
-                eps: 6.0,
-            }],
-            analyst_estimates: None,
-            current_metrics: CurrentMetrics {
-                price: None,
-                pe_ratio: 0.0,
-                shares_outstanding: 16_000.0,
-                name: None,
-                exchange: None,
-                market_cap: None,
-                dividend_yield: None,
-            },
-        }
-    }
-
-    fn snapshot_with(last_price: Option<f64>, prev_close: Option<f64>) -> MarketDataSnapshot {
-        MarketDataSnapshot {
-            symbol: "AAPL".to_string(),
-            bid_price: None,
-            bid_size: None,
-            ask_price: None,
-            ask_size: None,
-            last_price,
-            last_size: None,
-            high: None,
-            low: None,
-            volume: None,
-            close: prev_close,
-            open: None,
-            timestamp: 0,
-        }
-    }
-
-    fn provider_with(symbol: &str, fund: FundamentalData) -> Arc<dyn FundamentalsProvider> {
-        let fake = FakeFundamentalsProvider::new();
-        fake.insert(symbol, fund);
-        Arc::new(fake)
-    }
 
     /// Locks in the stable error-string discriminants the frontend
     /// switches on. Changing any of these is a contract break — update
@@ -404,90 +231,4 @@ mod tests {
             .expect_err("must surface upstream failure as typed error");
         assert_eq!(err, "no_data");
     }
-
-    #[tokio::test]
-    async fn projection_results_uses_live_quote_last_price_for_baseline() {
-        let provider = provider_with("AAPL", fundamentals_without_price("AAPL"));
-        let quote_service = Arc::new(QuoteService::new(StubQuoteFetcher::ok(snapshot_with(
-            Some(200.0),
-            Some(195.0),
-        ))));
-
-        let bundle = generate_projection_results_with_quote(
-            &provider,
-            &quote_service,
-            "AAPL",
-            &ProjectionAssumptions::default(),
-        )
-        .await
-        .expect("ok");
-
-        assert_eq!(bundle.results.baseline.share_price_low, 200.0);
-        assert_eq!(bundle.results.baseline.share_price_high, 200.0);
-        assert_eq!(bundle.fundamentals.current_metrics.price, Some(200.0));
-    }
-
-    #[tokio::test]
-    async fn projection_results_falls_back_to_prev_close_when_last_price_missing() {
-        let provider = provider_with("AAPL", fundamentals_without_price("AAPL"));
-        let quote_service = Arc::new(QuoteService::new(StubQuoteFetcher::ok(snapshot_with(
-            None,
-            Some(195.0),
-        ))));
-
-        let bundle = generate_projection_results_with_quote(
-            &provider,
-            &quote_service,
-            "AAPL",
-            &ProjectionAssumptions::default(),
-        )
-        .await
-        .expect("ok");
-
-        assert_eq!(bundle.results.baseline.share_price_low, 195.0);
-        assert_eq!(bundle.fundamentals.current_metrics.price, Some(195.0));
-    }
-
-    #[tokio::test]
-    async fn projection_results_continues_when_quote_fetch_fails() {
-        let provider = provider_with("AAPL", fundamentals_without_price("AAPL"));
-        let quote_service = Arc::new(QuoteService::new(StubQuoteFetcher::err(
-            IbkrError::NotConnected,
-        )));
-
-        let bundle = generate_projection_results_with_quote(
-            &provider,
-            &quote_service,
-            "AAPL",
-            &ProjectionAssumptions::default(),
-        )
-        .await
-        .expect("quote failure must not abort projections");
-
-        assert_eq!(bundle.results.baseline.share_price_low, 0.0);
-        assert_eq!(bundle.fundamentals.current_metrics.price, None);
-    }
-
-    /// Locks in the overlay deadline: a hanging quote fetcher must not
-    /// stall the projection command past `OVERLAY_QUOTE_DEADLINE`.
-    /// `start_paused = true` lets tokio auto-advance virtual time to the
-    /// deadline timer, so this completes instantly instead of waiting
-    /// the real 1.5s.
-    #[tokio::test(start_paused = true)]
-    async fn projection_results_skips_overlay_when_quote_fetch_exceeds_deadline() {
-        let provider = provider_with("AAPL", fundamentals_without_price("AAPL"));
-        let quote_service = Arc::new(QuoteService::new(Arc::new(HangingQuoteFetcher)));
-
-        let bundle = generate_projection_results_with_quote(
-            &provider,
-            &quote_service,
-            "AAPL",
-            &ProjectionAssumptions::default(),
-        )
-        .await
-        .expect("overlay deadline must not abort projections");
-
-        assert_eq!(bundle.results.baseline.share_price_low, 0.0);
-        assert_eq!(bundle.fundamentals.current_metrics.price, None);
-    }
 }
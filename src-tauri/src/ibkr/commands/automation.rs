@@ -0,0 +1,210 @@
+//! Tauri commands behind automation rules (see `services::automation`).
+//!
+//! `confirm_rule_ticket` is the only way a rule's live order goes out:
+//! the trader confirms the ticket in the UI and it is sent like a manual
+//! `ibkr_place_order`, order guard and TCA included (Hard Invariant 1).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use super::trading::place_confirmed;
+use crate::config::SettingsState;
+use crate::ibkr::state::IbkrState;
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::services::automation::{
+    AutomationRule, RuleAction, RuleCondition, RuleEngine, RuleEvaluation, RuleInput, RuleRunReport,
+};
+use crate::services::order_guard::OrderGuard;
+use crate::services::tca::TcaService;
+
+/// Field checks shared by create and update; returns the rule with its
+/// name trimmed and symbol normalized. A portfolio drawdown rule has no
+/// symbol and can only notify.
+fn check_rule(rule: RuleInput) -> Result<RuleInput, CommandError> {
+    let mut inputs = Inputs::new();
+    let name = inputs.text("rule.name", &rule.name);
+    let symbol = match rule.condition {
+        RuleCondition::PortfolioDrawdown { .. } => {
+            inputs.check(
+                rule.symbol.trim().is_empty(),
+                "rule.symbol",
+                "must be empty for a portfolio drawdown rule",
+            );
+            inputs.check(
+                rule.action == RuleAction::Notify,
+                "rule.action",
+                "a portfolio drawdown rule can only notify",
+            );
+            String::new()
+        }
+        _ => inputs.symbol("rule.symbol", &rule.symbol),
+    };
+    match rule.condition {
+        RuleCondition::Price { level, .. } => {
+            inputs.positive("rule.condition.level", level);
+        }
+        RuleCondition::Sma { period, .. } => inputs.check(
+            (2..=200).contains(&period),
+            "rule.condition.period",
+            "must be between 2 and 200 days",
+        ),
+        RuleCondition::Rsi { period, level, .. } => {
+            inputs.check(
+                (2..=100).contains(&period),
+                "rule.condition.period",
+                "must be between 2 and 100 days",
+            );
+            inputs.check(
+                level > 0.0 && level < 100.0,
+                "rule.condition.level",
+                "must be between 0 and 100",
+            );
+        }
+        RuleCondition::FairValue { .. } => {}
+        RuleCondition::PortfolioDrawdown { level } => inputs.check(
+            level > 0.0 && level < 100.0,
+            "rule.condition.level",
+            "must be between 0 and 100",
+        ),
+    }
+    if let RuleAction::DryRunOrder { quantity, .. } | RuleAction::LiveOrder { quantity, .. } =
+        rule.action
+    {
+        inputs.positive("rule.action.quantity", quantity);
+    }
+    inputs.finish()?;
+    Ok(RuleInput {
+        name,
+        symbol,
+        ..rule
+    })
+}
+
+/// Every rule, by name.
+#[tauri::command]
+pub async fn list_automation_rules(
+    engine: State<'_, Arc<RuleEngine>>,
+) -> Result<Vec<AutomationRule>, String> {
+    engine.list().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_automation_rule(
+    engine: State<'_, Arc<RuleEngine>>,
+    rule: RuleInput,
+) -> Result<AutomationRule, CommandError> {
+    let rule = check_rule(rule)?;
+    Ok(engine.create(rule).await.map_err(|e| e.to_string())?)
+}
+
+/// Replace a rule; it waits for a fresh crossing.
+#[tauri::command]
+pub async fn update_automation_rule(
+    engine: State<'_, Arc<RuleEngine>>,
+    id: i64,
+    rule: RuleInput,
+) -> Result<AutomationRule, CommandError> {
+    let rule = check_rule(rule)?;
+    Ok(engine.update(id, rule).await.map_err(|e| e.to_string())?)
+}
+
+#[tauri::command]
+pub async fn set_automation_rule_enabled(
+    engine: State<'_, Arc<RuleEngine>>,
+    id: i64,
+    enabled: bool,
+) -> Result<AutomationRule, String> {
+    engine
+        .set_enabled(id, enabled)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// The rule's audit rows are kept.
+#[tauri::command]
+pub async fn delete_automation_rule(
+    engine: State<'_, Arc<RuleEngine>>,
+    id: i64,
+) -> Result<bool, String> {
+    engine.delete(id).await.map_err(|e| e.to_string())
+}
+
+/// The audit log, newest first; `firedOnly` skips evaluations where
+/// nothing crossed.
+#[tauri::command]
+pub async fn list_rule_evaluations(
+    engine: State<'_, Arc<RuleEngine>>,
+    rule_id: Option<i64>,
+    fired_only: Option<bool>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<RuleEvaluation>, CommandError> {
+    let mut inputs = Inputs::new();
+    let page = inputs.page(limit, offset);
+    inputs.finish()?;
+    Ok(engine
+        .evaluations(rule_id, fired_only.unwrap_or(false), page)
+        .await
+        .map_err(|e| e.to_string())?)
+}
+
+/// Evaluate every enabled rule now instead of waiting for the next tick.
+#[tauri::command]
+pub async fn run_automation_rules(
+    engine: State<'_, Arc<RuleEngine>>,
+) -> Result<RuleRunReport, String> {
+    engine.run().await.map_err(|e| e.to_string())
+}
+
+/// Order tickets posted today that await confirmation, newest first.
+#[tauri::command]
+pub async fn list_rule_tickets(
+    engine: State<'_, Arc<RuleEngine>>,
+) -> Result<Vec<RuleEvaluation>, String> {
+    engine.pending_tickets().await.map_err(|e| e.to_string())
+}
+
+/// Send a pending ticket the trader has confirmed. Retrying the same
+/// ticket replays the first order id instead of sending it twice.
+#[tauri::command]
+pub async fn confirm_rule_ticket(
+    engine: State<'_, Arc<RuleEngine>>,
+    state: State<'_, IbkrState>,
+    tca: State<'_, Arc<TcaService>>,
+    guard: State<'_, Arc<OrderGuard>>,
+    settings: State<'_, SettingsState>,
+    evaluation_id: i64,
+) -> Result<RuleEvaluation, CommandError> {
+    let order = engine
+        .ticket(evaluation_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let intended_price = order.price;
+    let order_id = place_confirmed(
+        &state,
+        &tca,
+        &guard,
+        &settings,
+        order,
+        None,
+        intended_price,
+        Some(format!("rule-ticket-{evaluation_id}")),
+    )
+    .await?;
+    Ok(engine
+        .ticket_placed(evaluation_id, order_id)
+        .await
+        .map_err(|e| e.to_string())?)
+}
+
+#[tauri::command]
+pub async fn dismiss_rule_ticket(
+    engine: State<'_, Arc<RuleEngine>>,
+    evaluation_id: i64,
+) -> Result<RuleEvaluation, String> {
+    engine
+        .dismiss_ticket(evaluation_id)
+        .await
+        .map_err(|e| e.to_string())
+}
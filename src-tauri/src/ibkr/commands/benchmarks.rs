@@ -0,0 +1,66 @@
+//! Tauri commands behind benchmark tags and the benchmark-relative
+//! report (see `services::benchmarks`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::services::benchmarks::{BenchmarkReport, BenchmarkService, BenchmarkTag};
+
+/// Every tag, by symbol.
+#[tauri::command]
+pub async fn list_benchmark_tags(
+    benchmarks: State<'_, Arc<BenchmarkService>>,
+) -> Result<Vec<BenchmarkTag>, String> {
+    benchmarks.tags().await.map_err(|e| e.to_string())
+}
+
+/// Measure `symbol` against `benchmark`, replacing any tag it had.
+#[tauri::command]
+pub async fn set_benchmark_tag(
+    benchmarks: State<'_, Arc<BenchmarkService>>,
+    symbol: String,
+    benchmark: String,
+) -> Result<BenchmarkTag, CommandError> {
+    let mut inputs = Inputs::new();
+    let symbol = inputs.symbol("symbol", &symbol);
+    let benchmark = inputs.symbol("benchmark", &benchmark);
+    inputs.check(
+        symbol.is_empty() || symbol != benchmark,
+        "benchmark",
+        "must differ from the symbol",
+    );
+    inputs.finish()?;
+    Ok(benchmarks
+        .set_tag(&symbol, &benchmark)
+        .await
+        .map_err(|e| e.to_string())?)
+}
+
+/// Back to the default benchmark (for a holding) or out of the report
+/// (for a watchlist entry).
+#[tauri::command]
+pub async fn clear_benchmark_tag(
+    benchmarks: State<'_, Arc<BenchmarkService>>,
+    symbol: String,
+) -> Result<bool, CommandError> {
+    let mut inputs = Inputs::new();
+    let symbol = inputs.symbol("symbol", &symbol);
+    inputs.finish()?;
+    Ok(benchmarks
+        .clear_tag(&symbol)
+        .await
+        .map_err(|e| e.to_string())?)
+}
+
+/// Holdings and tagged watchlist entries of `account` (the current
+/// account when omitted) against their benchmarks, worst relative
+/// return first.
+#[tauri::command]
+pub async fn get_benchmark_report(
+    benchmarks: State<'_, Arc<BenchmarkService>>,
+    account: Option<String>,
+) -> Result<BenchmarkReport, String> {
+    benchmarks.report(account).await.map_err(|e| e.to_string())
+}
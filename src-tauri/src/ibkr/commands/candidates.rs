@@ -8,6 +8,7 @@ use std::sync::Arc;
 
 use tauri::State;
 
+use crate::middleware::validation::{CommandError, Inputs};
 use crate::services::candidate_promoter::{CandidatePromoter, PromotionOutcome};
 use crate::services::candidate_scheduler::CandidateScheduler;
 use crate::services::candidate_universe::types::{Candidate, CandidateFilter};
@@ -52,16 +53,13 @@ pub async fn candidates_promote(
     promoter: State<'_, Arc<CandidatePromoter>>,
     symbol: String,
     reason: String,
-) -> Result<bool, String> {
-    let symbol_norm = symbol.trim().to_string();
-    if symbol_norm.is_empty() {
-        return Err("symbol must not be empty".into());
-    }
-    if reason.trim().is_empty() {
-        return Err("reason must not be empty".into());
-    }
+) -> Result<bool, CommandError> {
+    let mut inputs = Inputs::new();
+    let symbol = inputs.symbol("symbol", &symbol);
+    inputs.text("reason", &reason);
+    inputs.finish()?;
     let outcome = promoter
-        .promote_for_agent(&symbol_norm, &reason)
+        .promote_for_agent(&symbol, &reason)
         .await
         .map_err(|e| e.to_string())?;
     Ok(matches!(outcome, PromotionOutcome::Promoted))
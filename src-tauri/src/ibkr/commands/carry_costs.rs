@@ -0,0 +1,24 @@
+//! `ibkr_get_carry_costs` — daily margin interest and short borrow fee
+//! estimate (`services::carry_costs`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::config::SettingsState;
+use crate::services::carry_costs::{CarryCostService, CarryCosts};
+
+/// Carrying costs for `account` (default: the first managed account)
+/// under the `carry_costs` rate table in settings.
+#[tauri::command]
+pub async fn ibkr_get_carry_costs(
+    settings: State<'_, SettingsState>,
+    service: State<'_, Arc<CarryCostService>>,
+    account: Option<String>,
+) -> Result<CarryCosts, String> {
+    let config = settings.config.read().await.carry_costs.clone();
+    service
+        .estimate(&config, account)
+        .await
+        .map_err(|e| e.to_string())
+}
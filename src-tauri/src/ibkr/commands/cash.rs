@@ -0,0 +1,18 @@
+//! Tauri command for the cash management view. See
+//! `services::cash_management`; the overview is read-only and its
+//! sweeps are suggestions for the trader to act on in TWS.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::cash_management::{CashManagementService, CashOverview};
+
+/// Cash per currency across every managed account, projected past the
+/// working orders. Raises `cash-warning` events for new problems.
+#[tauri::command]
+pub async fn cash_overview(
+    service: State<'_, Arc<CashManagementService>>,
+) -> Result<CashOverview, String> {
+    service.overview().await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,31 @@
+//! Tauri command behind the cash drag view (see `services::cash_drag`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::services::cash_drag::{CashDragReport, CashDragService};
+
+/// Cash as a percent of NAV for `account` (the current account when
+/// omitted), with the last `days` days of daily samples (a year when
+/// omitted) and, while cash is above `cash_drag.threshold_pct`, the
+/// watchlist names below their buy band.
+#[tauri::command]
+pub async fn get_cash_drag(
+    cash_drag: State<'_, Arc<CashDragService>>,
+    account: Option<String>,
+    days: Option<u32>,
+) -> Result<CashDragReport, CommandError> {
+    let mut inputs = Inputs::new();
+    inputs.check(
+        days.is_none_or(|d| (1..=3650).contains(&d)),
+        "days",
+        "must be between 1 and 3650",
+    );
+    inputs.finish()?;
+    Ok(cash_drag
+        .report(account, days)
+        .await
+        .map_err(|e| e.to_string())?)
+}
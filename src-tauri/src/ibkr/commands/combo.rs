@@ -0,0 +1,54 @@
+//! `ibkr_place_combo_order` — two-leg option spreads (verticals,
+//! calendars, diagonals) as one IBKR combo order. Manual UI use only,
+//! under the same human-confirmation rule as `ibkr_place_order`
+//! (master Hard Invariant 1), and behind the same `OrderGuard`.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use tauri::State;
+
+use crate::config::SettingsState;
+use crate::ibkr::state::IbkrState;
+use crate::ibkr::types::{ComboKind, ComboOrderRequest};
+use crate::services::order_guard::{Admission, OrderGuard};
+
+#[tauri::command]
+pub async fn ibkr_place_combo_order(
+    state: State<'_, IbkrState>,
+    guard: State<'_, Arc<OrderGuard>>,
+    settings: State<'_, SettingsState>,
+    order: ComboOrderRequest,
+    idempotency_key: Option<String>,
+) -> Result<i32, String> {
+    order.validate()?;
+    let window = settings
+        .config
+        .read()
+        .await
+        .order_guard
+        .duplicate_window_secs;
+    let reservation = match guard.admit(
+        &order,
+        idempotency_key.as_deref(),
+        chrono::Duration::seconds(window as i64),
+        Utc::now(),
+    )? {
+        Admission::Place(r) => r,
+        Admission::Replay(order_id) => return Ok(order_id),
+    };
+    let placed = state
+        .client
+        .place_combo_order(order)
+        .await
+        .map_err(|e| e.to_string());
+    guard.settle(&reservation, placed.as_ref().ok().copied());
+    placed
+}
+
+/// Validate a spread without placing it, so the order form can show
+/// what it is (or what's wrong) before confirmation.
+#[tauri::command]
+pub async fn ibkr_validate_combo_order(order: ComboOrderRequest) -> Result<ComboKind, String> {
+    order.validate()
+}
@@ -1,5 +1,8 @@
+use crate::ibkr::client::requests::InFlightRequest;
 use crate::ibkr::state::IbkrState;
 use crate::ibkr::types::{ConnectionConfig, ConnectionStatus};
+use crate::services::connection_health::{ConnectionHealth, ConnectionMetrics};
+use std::sync::Arc;
 use tauri::State;
 
 #[tauri::command]
@@ -22,6 +25,7 @@ pub async fn ibkr_connect(
         Err(e) => {
             tracing::error!("🟢 CONNECT ERROR: {}", e);
             state.update_connection_status(false).await;
+            state.positions.clear();
             Err(e.to_string())
         }
     }
@@ -41,6 +45,7 @@ pub async fn ibkr_disconnect(state: State<'_, IbkrState>) -> Result<String, Stri
         Ok(_) => {
             tracing::info!("🔴 UPDATING CONNECTION STATUS TO FALSE");
             state.update_connection_status(false).await;
+            state.positions.clear();
 
             // Increment client ID to avoid conflicts on reconnect
             state.increment_client_id().await;
@@ -65,3 +70,31 @@ pub async fn ibkr_get_connection_status(
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Long-running IBKR requests that can be cancelled (account summary /
+/// positions stream drains).
+#[tauri::command]
+pub async fn ibkr_list_requests(
+    state: State<'_, IbkrState>,
+) -> Result<Vec<InFlightRequest>, String> {
+    Ok(state.client.requests().list())
+}
+
+/// Cancel an in-flight request from [`ibkr_list_requests`]. The pending
+/// command fails with "Request cancelled". Returns `false` when the
+/// request already finished.
+#[tauri::command]
+pub async fn ibkr_cancel_request(
+    state: State<'_, IbkrState>,
+    request_id: u64,
+) -> Result<bool, String> {
+    Ok(state.client.requests().cancel(request_id))
+}
+
+/// Heartbeat-derived latency percentiles, disconnect count and uptime.
+#[tauri::command]
+pub async fn get_connection_metrics(
+    health: State<'_, Arc<ConnectionHealth>>,
+) -> Result<ConnectionMetrics, String> {
+    Ok(health.metrics().await)
+}
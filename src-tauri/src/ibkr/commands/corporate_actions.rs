@@ -0,0 +1,82 @@
+//! Tauri commands behind the corporate-action log (see
+//! `services::corporate_actions`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::services::corporate_actions::{
+    ActionSource, AppliedAction, CorporateAction, CorporateActionService,
+};
+
+/// The last `limit` applied actions (50 when omitted), newest first.
+#[tauri::command]
+pub async fn list_corporate_actions(
+    corporate_actions: State<'_, Arc<CorporateActionService>>,
+    limit: Option<u32>,
+) -> Result<Vec<AppliedAction>, CommandError> {
+    let mut inputs = Inputs::new();
+    inputs.check(
+        limit.is_none_or(|l| (1..=1000).contains(&l)),
+        "limit",
+        "must be between 1 and 1000",
+    );
+    inputs.finish()?;
+    Ok(corporate_actions
+        .list(limit.unwrap_or(50))
+        .await
+        .map_err(|e| e.to_string())?)
+}
+
+/// Apply an action the daily check didn't detect, such as a spin-off.
+/// Refused when the same kind, symbol and date is already applied.
+#[tauri::command]
+pub async fn apply_corporate_action(
+    corporate_actions: State<'_, Arc<CorporateActionService>>,
+    action: CorporateAction,
+) -> Result<AppliedAction, CommandError> {
+    let mut inputs = Inputs::new();
+    match &action {
+        CorporateAction::Split { symbol, ratio, .. } => {
+            inputs.symbol("action.symbol", symbol);
+            inputs.positive("action.ratio", *ratio);
+            inputs.check(*ratio != 1.0, "action.ratio", "must not be 1");
+        }
+        CorporateAction::SymbolChange { from, to, .. } => {
+            let from = inputs.symbol("action.from", from);
+            let to = inputs.symbol("action.to", to);
+            inputs.check(
+                from.is_empty() || from != to,
+                "action.to",
+                "must differ from action.from",
+            );
+        }
+        CorporateAction::SpinOff {
+            symbol,
+            new_symbol,
+            ratio,
+            cost_fraction,
+            ..
+        } => {
+            let symbol = inputs.symbol("action.symbol", symbol);
+            let new_symbol = inputs.symbol("action.new_symbol", new_symbol);
+            inputs.check(
+                symbol.is_empty() || symbol != new_symbol,
+                "action.new_symbol",
+                "must differ from action.symbol",
+            );
+            inputs.positive("action.ratio", *ratio);
+            inputs.check(
+                (0.0..=1.0).contains(cost_fraction),
+                "action.cost_fraction",
+                "must be between 0 and 1",
+            );
+        }
+    }
+    inputs.finish()?;
+    Ok(corporate_actions
+        .apply(action, ActionSource::Manual)
+        .await
+        .map_err(|e| e.to_string())?)
+}
@@ -0,0 +1,21 @@
+//! Tauri command behind cost-basis reconciliation (see
+//! `services::cost_basis`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::cost_basis::{CostBasisReconciler, Reconciliation};
+
+/// Compare the locally rebuilt book for `account` (the current account
+/// when omitted) with IBKR's positions.
+#[tauri::command]
+pub async fn reconcile_cost_basis(
+    reconciler: State<'_, Arc<CostBasisReconciler>>,
+    account: Option<String>,
+) -> Result<Reconciliation, String> {
+    reconciler
+        .reconcile(account)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,31 @@
+//! Tauri command behind the portfolio drawdown stats (see
+//! `services::drawdown`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::services::drawdown::{DrawdownService, DrawdownStats};
+
+/// Drawdown of `account` (the current account when omitted) from its
+/// high-water mark, with the rolling max drawdown over `window`
+/// snapshots (`drawdown.window_sessions` when omitted).
+#[tauri::command]
+pub async fn get_drawdown_stats(
+    drawdown: State<'_, Arc<DrawdownService>>,
+    account: Option<String>,
+    window: Option<u32>,
+) -> Result<DrawdownStats, CommandError> {
+    let mut inputs = Inputs::new();
+    inputs.check(
+        window.is_none_or(|w| (2..=2520).contains(&w)),
+        "window",
+        "must be between 2 and 2520",
+    );
+    inputs.finish()?;
+    Ok(drawdown
+        .stats(account, window)
+        .await
+        .map_err(|e| e.to_string())?)
+}
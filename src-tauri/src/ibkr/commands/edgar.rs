@@ -0,0 +1,19 @@
+//! `get_recent_filings` — a symbol's latest 10-K, 10-Q and 8-K filings
+//! with links to SEC EDGAR (`services::edgar_service`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::edgar_service::{EdgarService, RecentFilings};
+
+#[tauri::command]
+pub async fn get_recent_filings(
+    service: State<'_, Arc<EdgarService>>,
+    symbol: String,
+) -> Result<RecentFilings, String> {
+    service
+        .recent_filings(&symbol)
+        .await
+        .map_err(|e| e.to_string())
+}
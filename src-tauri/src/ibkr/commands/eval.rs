@@ -10,6 +10,7 @@ use std::sync::Arc;
 use chrono::Utc;
 use tauri::State;
 
+use crate::middleware::validation::{CommandError, Inputs};
 use crate::services::eval_harness::{
     self, CalibrationStats, CostAttribution, PredictionWithOutcome,
 };
@@ -52,15 +53,15 @@ pub async fn eval_prediction_history(
     db: State<'_, Arc<Db>>,
     symbol: String,
     window_days: Option<i64>,
-) -> Result<Vec<PredictionWithOutcome>, String> {
-    if symbol.trim().is_empty() {
-        return Err("symbol must not be empty".to_string());
-    }
+) -> Result<Vec<PredictionWithOutcome>, CommandError> {
+    let mut inputs = Inputs::new();
+    let symbol = inputs.symbol("symbol", &symbol);
+    inputs.finish()?;
     let window = window_days
         .unwrap_or(HISTORY_DEFAULT_WINDOW_DAYS)
         .clamp(1, MAX_WINDOW_DAYS);
     let since_unix = Utc::now().timestamp() - window * 86_400;
-    eval_harness::prediction_history(&db, &symbol, since_unix)
+    Ok(eval_harness::prediction_history(&db, &symbol, since_unix)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
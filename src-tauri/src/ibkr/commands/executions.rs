@@ -0,0 +1,62 @@
+//! Read-only order-form lookups: the day's executions and a
+//! contract's size rules.
+
+use chrono::NaiveDate;
+use tauri::State;
+
+use super::accounts::filter_by_symbol;
+use crate::ibkr::state::IbkrState;
+use crate::ibkr::types::{ContractRoute, IbkrExecution, SizeRules};
+use crate::middleware::validation::{CommandError, Inputs};
+
+/// Parses a `YYYY-MM-DD` argument, as a field error on `field`.
+/// Extracted so it can be unit-tested without constructing a Tauri
+/// `State`.
+pub(crate) fn parse_date_arg(field: &str, date: &str) -> Result<NaiveDate, CommandError> {
+    let mut inputs = Inputs::new();
+    let parsed = inputs.date(field, date);
+    inputs.finish()?;
+    Ok(parsed.expect("validated"))
+}
+
+/// Executions on `date`, optionally for one account and one symbol,
+/// windowed by `limit` / `offset`.
+#[tauri::command]
+pub async fn ibkr_get_executions(
+    state: State<'_, IbkrState>,
+    date: String,
+    account: Option<String>,
+    symbol: Option<String>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<IbkrExecution>, CommandError> {
+    let mut inputs = Inputs::new();
+    let parsed = inputs.date("date", &date);
+    let page = inputs.page(limit, offset);
+    inputs.finish()?;
+    let mut executions = state
+        .client
+        .executions(parsed.expect("validated"))
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(account) = account.as_deref().map(str::trim).filter(|a| !a.is_empty()) {
+        executions.retain(|e| e.account == account);
+    }
+    let executions = filter_by_symbol(executions, symbol.as_deref(), |e| e.symbol.as_str());
+    Ok(page.apply(executions))
+}
+
+/// Minimum size and size increment for `symbol`, so the order form can
+/// round a fractional quantity before it is sent.
+#[tauri::command]
+pub async fn ibkr_get_size_rules(
+    state: State<'_, IbkrState>,
+    symbol: String,
+    route: Option<ContractRoute>,
+) -> Result<SizeRules, String> {
+    state
+        .client
+        .get_size_rules(&symbol, route)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,22 @@
+//! Tauri command behind the expected portfolio return (see
+//! `services::expected_return`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::expected_return::{ExpectedPortfolioReturn, ExpectedReturnService};
+
+/// Expected annual return of the long stock positions of `account` (the
+/// current account when omitted) from their stored base-case
+/// projections, with each holding's contribution.
+#[tauri::command]
+pub async fn get_expected_portfolio_return(
+    expected_return: State<'_, Arc<ExpectedReturnService>>,
+    account: Option<String>,
+) -> Result<ExpectedPortfolioReturn, String> {
+    expected_return
+        .report(account)
+        .await
+        .map_err(|e| e.to_string())
+}
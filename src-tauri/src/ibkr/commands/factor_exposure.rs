@@ -0,0 +1,19 @@
+//! Tauri command behind the factor exposure section of the risk report
+//! (see `services::factor_exposure`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::factor_exposure::{FactorExposureReport, FactorExposureService};
+
+/// Market, size, value and momentum exposures of the stock positions of
+/// `account` (the current account when omitted), per position and for
+/// the book.
+#[tauri::command]
+pub async fn get_factor_exposure(
+    factors: State<'_, Arc<FactorExposureService>>,
+    account: Option<String>,
+) -> Result<FactorExposureReport, String> {
+    factors.report(account).await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,25 @@
+//! Tauri commands behind the fair-value dashboard flags.
+//!
+//! The daily run is scheduled (see `services::fair_value_watch`);
+//! `fair_value_run_now` lets the operator re-evaluate after refreshing
+//! a projection without waiting for 16:20 ET.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::fair_value_watch::{FairValueFlag, FairValueReport, FairValueWatcher};
+
+#[tauri::command]
+pub async fn fair_value_list_flags(
+    watcher: State<'_, Arc<FairValueWatcher>>,
+) -> Result<Vec<FairValueFlag>, String> {
+    watcher.list().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn fair_value_run_now(
+    watcher: State<'_, Arc<FairValueWatcher>>,
+) -> Result<FairValueReport, String> {
+    watcher.run().await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,29 @@
+//! Tauri command behind the fundamentals charts.
+//!
+//! Revenue, margins, EPS and free cash flow as annual, quarterly and
+//! TTM series from the cached Alpha Vantage statements (see
+//! `services::financial_data_service::timeseries`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::services::financial_data_service::{
+    FinancialDataService, FundamentalMetric, FundamentalTimeseries,
+};
+
+#[tauri::command]
+pub async fn get_fundamental_timeseries(
+    financial: State<'_, Arc<FinancialDataService>>,
+    symbol: String,
+    metric: FundamentalMetric,
+) -> Result<FundamentalTimeseries, CommandError> {
+    let mut inputs = Inputs::new();
+    let symbol = inputs.symbol("symbol", &symbol);
+    inputs.finish()?;
+    Ok(financial
+        .fundamental_timeseries(&symbol, metric)
+        .await
+        .map_err(|e| e.to_string())?)
+}
@@ -0,0 +1,100 @@
+//! Tauri commands behind the manual fundamentals override editor.
+//!
+//! Overrides are per-field corrections merged over provider data by
+//! `OverridingFundamentalsProvider`; the next `ibkr_get_fundamental_data`
+//! / projection call picks a write up immediately. Saving an empty
+//! override clears the symbol's row.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::fundamentals_overrides::{
+    FundamentalsOverride, FundamentalsOverridesStore, OverrideRow,
+};
+
+/// `written_by` for edits made from the UI.
+const INTERACTIVE: &str = "interactive";
+
+#[tauri::command]
+pub async fn fundamentals_get_override(
+    store: State<'_, Arc<FundamentalsOverridesStore>>,
+    symbol: String,
+) -> Result<Option<OverrideRow>, String> {
+    store.get(&symbol).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn fundamentals_list_overrides(
+    store: State<'_, Arc<FundamentalsOverridesStore>>,
+) -> Result<Vec<OverrideRow>, String> {
+    store.list().await.map_err(|e| e.to_string())
+}
+
+/// Replace `symbol`'s override. Returns the stored row, or `None` when
+/// the override was empty and the row was cleared.
+#[tauri::command]
+pub async fn fundamentals_set_override(
+    store: State<'_, Arc<FundamentalsOverridesStore>>,
+    symbol: String,
+    overrides: FundamentalsOverride,
+    notes: Option<String>,
+) -> Result<Option<OverrideRow>, String> {
+    validate(&overrides)?;
+    store
+        .upsert(&symbol, overrides, INTERACTIVE, notes)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn fundamentals_clear_override(
+    store: State<'_, Arc<FundamentalsOverridesStore>>,
+    symbol: String,
+) -> Result<bool, String> {
+    store.clear(&symbol).await.map_err(|e| e.to_string())
+}
+
+/// Reject values that could only be typos: non-finite numbers, a
+/// non-positive share count or price, duplicate years or segments.
+fn validate(ov: &FundamentalsOverride) -> Result<(), String> {
+    let mut seen = std::collections::HashSet::new();
+    for y in &ov.years {
+        if !seen.insert(y.year) {
+            return Err(format!("year {} listed twice", y.year));
+        }
+        for (field, value) in [
+            ("revenue", y.revenue),
+            ("netIncome", y.net_income),
+            ("eps", y.eps),
+        ] {
+            if value.is_some_and(|v| !v.is_finite()) {
+                return Err(format!("{field} for {} must be a finite number", y.year));
+            }
+        }
+    }
+    for (field, value) in [
+        ("sharesOutstanding", ov.shares_outstanding),
+        ("price", ov.price),
+    ] {
+        if value.is_some_and(|v| !v.is_finite() || v <= 0.0) {
+            return Err(format!("{field} must be a positive number"));
+        }
+    }
+    let mut names = std::collections::HashSet::new();
+    for segment in &ov.segments {
+        let name = segment.name.trim();
+        if name.is_empty() {
+            return Err("segments need a name".to_string());
+        }
+        if !names.insert(name.to_lowercase()) {
+            return Err(format!("segment {name} listed twice"));
+        }
+        if segment.revenue.iter().any(|r| !r.revenue.is_finite()) {
+            return Err(format!(
+                "revenue for segment {name} must be a finite number"
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,18 @@
+//! `ibkr_suggest_hedge` — SPY put / short SPY / MES hedges sized to
+//! the beta-weighted exposure (`services::hedging`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::hedging::{HedgePlan, HedgeRequest, HedgingService};
+
+/// Suggestions only; order tickets are for review and go through
+/// `ibkr_place_order`.
+#[tauri::command]
+pub async fn ibkr_suggest_hedge(
+    service: State<'_, Arc<HedgingService>>,
+    request: HedgeRequest,
+) -> Result<HedgePlan, String> {
+    service.suggest(&request).await.map_err(|e| e.to_string())
+}
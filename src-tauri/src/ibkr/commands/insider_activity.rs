@@ -0,0 +1,16 @@
+//! `get_insider_activity` — Form 4 buys and sells with 3/6/12-month net
+//! summaries (`services::insider_activity`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::insider_activity::{InsiderActivity, InsiderActivityService};
+
+#[tauri::command]
+pub async fn get_insider_activity(
+    service: State<'_, Arc<InsiderActivityService>>,
+    symbol: String,
+) -> Result<InsiderActivity, String> {
+    service.activity(&symbol).await.map_err(|e| e.to_string())
+}
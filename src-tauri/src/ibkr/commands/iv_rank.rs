@@ -0,0 +1,15 @@
+//! `get_iv_rank` — 52-week IV rank / percentile (`services::iv_rank`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::iv_rank::{IvRank, IvRankService};
+
+#[tauri::command]
+pub async fn get_iv_rank(
+    service: State<'_, Arc<IvRankService>>,
+    symbol: String,
+) -> Result<IvRank, String> {
+    service.rank(&symbol).await.map_err(|e| e.to_string())
+}
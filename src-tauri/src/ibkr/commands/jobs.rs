@@ -0,0 +1,27 @@
+//! Tauri commands over the background job registry
+//! (`services::jobs`). Jobs are started by the feature commands that
+//! own them, e.g. `start_portfolio_analysis_job`.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::jobs::{JobInfo, JobRegistry};
+
+#[tauri::command]
+pub async fn list_jobs(jobs: State<'_, Arc<JobRegistry>>) -> Result<Vec<JobInfo>, String> {
+    Ok(jobs.list().await)
+}
+
+/// `false` when the job is unknown or has already finished.
+#[tauri::command]
+pub async fn cancel_job(jobs: State<'_, Arc<JobRegistry>>, id: u64) -> Result<bool, String> {
+    Ok(jobs.cancel(id).await)
+}
+
+#[tauri::command]
+pub async fn get_job(jobs: State<'_, Arc<JobRegistry>>, id: u64) -> Result<JobInfo, String> {
+    jobs.get(id)
+        .await
+        .ok_or_else(|| format!("unknown job {id}"))
+}
@@ -0,0 +1,19 @@
+//! Tauri command behind the liquidity section of the risk report (see
+//! `services::liquidity`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::liquidity::{LiquidityReport, LiquidityService};
+
+/// Average daily volume and days to liquidate of the stock positions of
+/// `account` (the current account when omitted), slowest first, with
+/// the illiquid ones flagged.
+#[tauri::command]
+pub async fn get_liquidity(
+    liquidity: State<'_, Arc<LiquidityService>>,
+    account: Option<String>,
+) -> Result<LiquidityReport, String> {
+    liquidity.report(account).await.map_err(|e| e.to_string())
+}
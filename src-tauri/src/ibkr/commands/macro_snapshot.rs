@@ -0,0 +1,15 @@
+//! `get_macro_snapshot` — fed funds, Treasury yields, CPI inflation and
+//! the yield curve (`services::macro_service`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::macro_service::{MacroService, MacroSnapshot};
+
+#[tauri::command]
+pub async fn get_macro_snapshot(
+    service: State<'_, Arc<MacroService>>,
+) -> Result<MacroSnapshot, String> {
+    service.snapshot().await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,39 @@
+//! `get_margin_history` — read side of `services::margin_monitor`.
+
+use std::sync::Arc;
+
+use chrono::Duration;
+use tauri::State;
+
+use super::order_audit::et_midnight_ms;
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::services::margin_monitor::{MarginMonitor, MarginSample};
+
+/// Margin samples between `from` and `to` (inclusive `YYYY-MM-DD` ET
+/// dates; either may be omitted for an open end), oldest first,
+/// optionally for one account. `limit` / `offset` page through them.
+#[tauri::command]
+pub async fn get_margin_history(
+    monitor: State<'_, Arc<MarginMonitor>>,
+    from: Option<String>,
+    to: Option<String>,
+    account: Option<String>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<MarginSample>, CommandError> {
+    let mut inputs = Inputs::new();
+    let (from, to) = inputs.date_range("from", from.as_deref(), "to", to.as_deref());
+    let page = inputs.page(limit, offset);
+    inputs.finish()?;
+    let from_s = from.map_or(0, |d| et_midnight_ms(d) / 1000);
+    let to_s = to.map_or(i64::MAX, |d| et_midnight_ms(d + Duration::days(1)) / 1000);
+    Ok(monitor
+        .history(
+            from_s,
+            to_s,
+            account.as_deref().filter(|a| !a.trim().is_empty()),
+            page,
+        )
+        .await
+        .map_err(|e| e.to_string())?)
+}
@@ -0,0 +1,22 @@
+//! `get_margin_comparison` — Reg-T against portfolio margin for the
+//! current book (`services::margin_compare`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::margin_compare::{MarginCompareService, MarginComparison};
+
+/// Each position bundle's Reg-T requirement (IBKR what-if) next to its
+/// estimated portfolio margin, for `account` (the current account when
+/// omitted).
+#[tauri::command]
+pub async fn get_margin_comparison(
+    margin_compare: State<'_, Arc<MarginCompareService>>,
+    account: Option<String>,
+) -> Result<MarginComparison, String> {
+    margin_compare
+        .compare(account)
+        .await
+        .map_err(|e| e.to_string())
+}
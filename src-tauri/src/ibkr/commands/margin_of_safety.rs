@@ -0,0 +1,30 @@
+//! Tauri commands behind the margin-of-safety bands.
+//!
+//! The watch is scheduled (see `services::margin_of_safety`);
+//! `margin_of_safety_run_now` rebuilds the bands after a projection
+//! refresh or a margin change without waiting for the next tick.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::margin_of_safety::{MarginOfSafetyReport, MarginOfSafetyWatcher, SafetyBand};
+
+/// Stored bands, optionally for one ticker.
+#[tauri::command]
+pub async fn margin_of_safety_list(
+    watcher: State<'_, Arc<MarginOfSafetyWatcher>>,
+    symbol: Option<String>,
+) -> Result<Vec<SafetyBand>, String> {
+    watcher
+        .list(symbol.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn margin_of_safety_run_now(
+    watcher: State<'_, Arc<MarginOfSafetyWatcher>>,
+) -> Result<MarginOfSafetyReport, String> {
+    watcher.run().await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,16 @@
+//! `get_market_status` — session state from `utils::market_calendar`.
+
+use chrono::Utc;
+
+use crate::utils::market_calendar::{self, MarketStatus};
+
+/// Whether `exchange` (default SMART) is in its regular session, with
+/// the next open and close.
+#[tauri::command]
+pub async fn get_market_status(exchange: Option<String>) -> Result<MarketStatus, String> {
+    let exchange = exchange
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| "SMART".to_string());
+    market_calendar::market_status(&exchange, Utc::now())
+        .ok_or_else(|| format!("no trading calendar for exchange `{exchange}`"))
+}
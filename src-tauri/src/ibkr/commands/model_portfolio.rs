@@ -0,0 +1,81 @@
+//! Tauri commands for model-portfolio templates and the rebalancing
+//! view. See `services::model_portfolio`. Evaluation only proposes
+//! trades; placing them goes through the order ticket as usual.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::config::commands::mutate_config;
+use crate::config::SettingsState;
+use crate::services::model_portfolio::{
+    AllocationTemplate, ModelPortfolioError, ModelPortfolioService, ModelPortfoliosConfig,
+    RebalancePlan,
+};
+
+async fn mutate_templates<F>(
+    settings: &SettingsState,
+    f: F,
+) -> Result<Vec<AllocationTemplate>, String>
+where
+    F: FnOnce(&mut ModelPortfoliosConfig) -> Result<(), ModelPortfolioError>,
+{
+    let config = mutate_config(settings, |c| {
+        f(&mut c.model_portfolios).map_err(|e| e.to_string())
+    })
+    .await?;
+    Ok(config.model_portfolios.templates)
+}
+
+#[tauri::command]
+pub async fn model_portfolio_list(
+    settings: State<'_, SettingsState>,
+) -> Result<Vec<AllocationTemplate>, String> {
+    Ok(settings
+        .config
+        .read()
+        .await
+        .model_portfolios
+        .templates
+        .clone())
+}
+
+/// Create or replace a template (matched by case-insensitive name).
+#[tauri::command]
+pub async fn model_portfolio_save(
+    settings: State<'_, SettingsState>,
+    template: AllocationTemplate,
+) -> Result<Vec<AllocationTemplate>, String> {
+    mutate_templates(&settings, |m| m.upsert(template)).await
+}
+
+#[tauri::command]
+pub async fn model_portfolio_delete(
+    settings: State<'_, SettingsState>,
+    name: String,
+) -> Result<Vec<AllocationTemplate>, String> {
+    mutate_templates(&settings, |m| m.remove(&name)).await
+}
+
+/// Drift per bucket and the rebalancing trade list for `account`
+/// (default: the first managed account) against template `name`.
+#[tauri::command]
+pub async fn model_portfolio_evaluate(
+    settings: State<'_, SettingsState>,
+    service: State<'_, Arc<ModelPortfolioService>>,
+    name: String,
+    account: Option<String>,
+) -> Result<RebalancePlan, String> {
+    let template = settings
+        .config
+        .read()
+        .await
+        .model_portfolios
+        .find(&name)
+        .cloned()
+        .ok_or_else(|| ModelPortfolioError::NotFound(name.clone()).to_string())?;
+    service
+        .evaluate(&template, account)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,17 @@
+//! `generate_briefing` — the pre-market summary (see
+//! `services::morning_briefing`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::morning_briefing::{Briefing, MorningBriefingService};
+
+/// Builds the briefing now. `markdown` (default off) also renders it.
+#[tauri::command]
+pub async fn generate_briefing(
+    briefings: State<'_, Arc<MorningBriefingService>>,
+    markdown: Option<bool>,
+) -> Result<Briefing, String> {
+    Ok(briefings.generate(markdown.unwrap_or(false)).await)
+}
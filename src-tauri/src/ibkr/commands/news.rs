@@ -15,6 +15,7 @@ use tauri::State;
 use crate::ibkr::types::news::NewsItem;
 use crate::services::news_cache::read_cache_with_verdict;
 use crate::storage::Db;
+use crate::utils::symbols;
 
 /// Read-only view returned by [`news_get_cached`]. Mirrors the columns
 /// `services::news_cache::CachedNews` exposes, with the verdict
@@ -33,7 +34,7 @@ pub async fn news_get_cached(
     db: State<'_, Arc<Db>>,
     symbol: String,
 ) -> Result<CachedTickerNews, String> {
-    let symbol = symbol.trim().to_uppercase();
+    let symbol = symbols::normalize(&symbol);
     if symbol.is_empty() {
         return Err("symbol must not be empty".to_string());
     }
@@ -88,7 +89,7 @@ mod tests {
     /// Mirrors the body of `news_get_cached` so the bulk of the logic
     /// is testable without Tauri `State` extraction.
     async fn call(db: Arc<Db>, symbol: &str) -> Result<CachedTickerNews, String> {
-        let symbol = symbol.trim().to_uppercase();
+        let symbol = symbols::normalize(symbol);
         if symbol.is_empty() {
             return Err("symbol must not be empty".to_string());
         }
@@ -0,0 +1,15 @@
+//! `notifications_send_test` — push a test message through every
+//! configured channel (see `services::notifications`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::notifications::{DeliveryReport, Notifier};
+
+#[tauri::command]
+pub async fn notifications_send_test(
+    notifier: State<'_, Arc<Notifier>>,
+) -> Result<DeliveryReport, String> {
+    notifier.send_test().await.map_err(|e| e.to_string())
+}
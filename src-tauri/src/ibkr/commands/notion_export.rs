@@ -0,0 +1,14 @@
+//! `notion_export_now` — on-demand run of `services::notion_export`.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::notion_export::{NotionExportReport, NotionExporter};
+
+#[tauri::command]
+pub async fn notion_export_now(
+    exporter: State<'_, Arc<NotionExporter>>,
+) -> Result<NotionExportReport, String> {
+    exporter.export().await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,46 @@
+//! Tauri commands behind the option expiry monitor (see
+//! `services::option_expiry`).
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use tauri::State;
+
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::services::option_expiry::{ExpirySummary, OptionExpiryReport, OptionExpiryService};
+use crate::utils::market_calendar::et_date;
+
+/// Days to expiry, moneyness and assignment risk of every option
+/// position of `account` (the current account when omitted), nearest
+/// expiry first.
+#[tauri::command]
+pub async fn get_option_expiries(
+    option_expiry: State<'_, Arc<OptionExpiryService>>,
+    account: Option<String>,
+) -> Result<OptionExpiryReport, String> {
+    option_expiry
+        .report(account, et_date(Utc::now()))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// What expires over the next `days` calendar days (7 when omitted), by
+/// date, with the shares and cash that change hands on exercise.
+#[tauri::command]
+pub async fn get_expiry_summary(
+    option_expiry: State<'_, Arc<OptionExpiryService>>,
+    account: Option<String>,
+    days: Option<u32>,
+) -> Result<ExpirySummary, CommandError> {
+    let mut inputs = Inputs::new();
+    inputs.check(
+        days.is_none_or(|d| (1..=90).contains(&d)),
+        "days",
+        "must be between 1 and 90",
+    );
+    inputs.finish()?;
+    Ok(option_expiry
+        .summary(account, et_date(Utc::now()), days.unwrap_or(7))
+        .await
+        .map_err(|e| e.to_string())?)
+}
@@ -0,0 +1,22 @@
+//! `ibkr_get_portfolio_greeks` — model greeks for option positions
+//! (`services::option_greeks`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::option_greeks::{OptionGreeksService, PortfolioGreeks};
+
+/// Per-position and aggregate greeks for `account` (default: the
+/// first managed account). The same payload is pushed as
+/// `portfolio-greeks-update` by the `option_greeks` task.
+#[tauri::command]
+pub async fn ibkr_get_portfolio_greeks(
+    service: State<'_, Arc<OptionGreeksService>>,
+    account: Option<String>,
+) -> Result<PortfolioGreeks, String> {
+    service
+        .portfolio_greeks(account)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,18 @@
+//! `ibkr_suggest_option_income` — covered-call / cash-secured-put
+//! strike and expiration suggestions (`services::option_income`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::option_income::{IncomeIdeas, IncomeRequest, OptionIncomeService};
+
+/// Suggestions only; each carries an `OrderRequest` the trader can
+/// review and send through `ibkr_place_order`.
+#[tauri::command]
+pub async fn ibkr_suggest_option_income(
+    service: State<'_, Arc<OptionIncomeService>>,
+    request: IncomeRequest,
+) -> Result<IncomeIdeas, String> {
+    service.suggest(&request).await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,24 @@
+//! `suggest_option_rolls` — roll candidates for expiring short options
+//! (`services::option_roll`).
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use tauri::State;
+
+use crate::services::option_roll::{OptionRollService, RollReport};
+use crate::utils::market_calendar::et_date;
+
+/// Suggestions only; each candidate carries a `ComboOrderRequest` the
+/// trader can review and send through `ibkr_place_combo_order`.
+#[tauri::command]
+pub async fn suggest_option_rolls(
+    option_roll: State<'_, Arc<OptionRollService>>,
+    account: Option<String>,
+    symbol: Option<String>,
+) -> Result<RollReport, String> {
+    option_roll
+        .suggest(account, symbol, et_date(Utc::now()))
+        .await
+        .map_err(|e| e.to_string())
+}
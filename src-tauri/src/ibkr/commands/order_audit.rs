@@ -0,0 +1,55 @@
+//! `get_order_history` / `list_scheduled_orders` — read side of the
+//! order audit trail (`services::order_audit`).
+
+use std::sync::Arc;
+
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use tauri::State;
+
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::services::order_audit::{OrderAuditEntry, OrderAuditStore, ScheduledOrder};
+use crate::utils::market_calendar::et_offset;
+
+/// Audit entries between `from` and `to` (inclusive `YYYY-MM-DD` ET
+/// dates; either may be omitted for an open end), oldest first,
+/// optionally for one symbol. `limit` / `offset` page through them.
+#[tauri::command]
+pub async fn get_order_history(
+    audit: State<'_, Arc<OrderAuditStore>>,
+    from: Option<String>,
+    to: Option<String>,
+    symbol: Option<String>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<OrderAuditEntry>, CommandError> {
+    let mut inputs = Inputs::new();
+    let (from, to) = inputs.date_range("from", from.as_deref(), "to", to.as_deref());
+    let symbol = inputs.optional_symbol("symbol", symbol.as_deref());
+    let page = inputs.page(limit, offset);
+    inputs.finish()?;
+    let from_ms = from.map_or(0, et_midnight_ms);
+    let to_ms = to.map_or(i64::MAX, |d| et_midnight_ms(d + Duration::days(1)));
+    Ok(audit
+        .history(from_ms, to_ms, symbol.as_deref(), page)
+        .await
+        .map_err(|e| e.to_string())?)
+}
+
+/// Orders placed with a `start_at` that IBKR is still holding.
+#[tauri::command]
+pub async fn list_scheduled_orders(
+    audit: State<'_, Arc<OrderAuditStore>>,
+) -> Result<Vec<ScheduledOrder>, String> {
+    audit
+        .pending_scheduled(Utc::now())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+pub(super) fn et_midnight_ms(date: NaiveDate) -> i64 {
+    date.and_time(NaiveTime::MIN)
+        .and_local_timezone(et_offset())
+        .single()
+        .map(|t| t.timestamp_millis())
+        .unwrap_or(0)
+}
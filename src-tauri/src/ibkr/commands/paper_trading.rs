@@ -0,0 +1,139 @@
+//! Tauri commands behind the paper-trading simulator (see
+//! `services::paper_trading`). Nothing here reaches IBKR except the
+//! price reads and, for `sim_replay`, a historical bars request.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::ibkr::state::IbkrState;
+use crate::ibkr::types::historical::{BarSize, HistoricalDataRequest, WhatToShow};
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::services::paper_trading::{
+    PaperTrader, SimFill, SimOrder, SimOrderRequest, SimOrderStatus, SimOrderType, SimPortfolio,
+};
+
+/// Place a simulated order; a market order fills before this returns.
+#[tauri::command]
+pub async fn sim_place_order(
+    trader: State<'_, Arc<PaperTrader>>,
+    order: SimOrderRequest,
+) -> Result<SimOrder, CommandError> {
+    let mut inputs = Inputs::new();
+    let symbol = inputs.symbol("order.symbol", &order.symbol);
+    inputs.positive("order.quantity", order.quantity);
+    if order.order_type != SimOrderType::Market {
+        inputs.check(
+            order.price.is_some_and(|p| p.is_finite() && p > 0.0),
+            "order.price",
+            format!(
+                "a {} order needs a price above 0",
+                order.order_type.as_str()
+            ),
+        );
+    }
+    inputs.finish()?;
+    Ok(trader
+        .place(SimOrderRequest { symbol, ..order })
+        .await
+        .map_err(|e| e.to_string())?)
+}
+
+#[tauri::command]
+pub async fn sim_cancel_order(
+    trader: State<'_, Arc<PaperTrader>>,
+    id: i64,
+) -> Result<SimOrder, String> {
+    trader.cancel(id).await.map_err(|e| e.to_string())
+}
+
+/// Newest first, optionally with one status.
+#[tauri::command]
+pub async fn sim_list_orders(
+    trader: State<'_, Arc<PaperTrader>>,
+    status: Option<SimOrderStatus>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<SimOrder>, CommandError> {
+    let mut inputs = Inputs::new();
+    let page = inputs.page(limit, offset);
+    inputs.finish()?;
+    Ok(trader
+        .orders(status, page)
+        .await
+        .map_err(|e| e.to_string())?)
+}
+
+/// Newest first, optionally for one symbol.
+#[tauri::command]
+pub async fn sim_list_fills(
+    trader: State<'_, Arc<PaperTrader>>,
+    symbol: Option<String>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<SimFill>, CommandError> {
+    let mut inputs = Inputs::new();
+    let symbol = inputs.optional_symbol("symbol", symbol.as_deref());
+    let page = inputs.page(limit, offset);
+    inputs.finish()?;
+    Ok(trader
+        .fills(symbol.as_deref(), page)
+        .await
+        .map_err(|e| e.to_string())?)
+}
+
+#[tauri::command]
+pub async fn sim_get_portfolio(
+    trader: State<'_, Arc<PaperTrader>>,
+) -> Result<SimPortfolio, String> {
+    trader.portfolio().await.map_err(|e| e.to_string())
+}
+
+/// Start a new run: every sim order and fill is dropped.
+#[tauri::command]
+pub async fn sim_reset(
+    trader: State<'_, Arc<PaperTrader>>,
+    starting_cash: Option<f64>,
+) -> Result<SimPortfolio, CommandError> {
+    let mut inputs = Inputs::new();
+    if let Some(cash) = starting_cash {
+        inputs.positive("startingCash", cash);
+    }
+    inputs.finish()?;
+    Ok(trader
+        .reset(starting_cash)
+        .await
+        .map_err(|e| e.to_string())?)
+}
+
+/// Run `symbol`'s working orders against its IBKR bars over
+/// `duration` (`"5 D"`) ending now, oldest bar first. Returns the fills.
+#[tauri::command]
+pub async fn sim_replay(
+    state: State<'_, IbkrState>,
+    trader: State<'_, Arc<PaperTrader>>,
+    symbol: String,
+    duration: String,
+    bar_size: BarSize,
+) -> Result<Vec<SimFill>, CommandError> {
+    let mut inputs = Inputs::new();
+    let symbol = inputs.symbol("symbol", &symbol);
+    let duration = inputs.text("duration", &duration);
+    inputs.finish()?;
+    let bars = state
+        .client
+        .get_historical_data(HistoricalDataRequest {
+            symbol: symbol.clone(),
+            end_date_time: String::new(),
+            duration,
+            bar_size,
+            what_to_show: WhatToShow::Trades,
+            use_rth: true,
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(trader
+        .replay(&symbol, &bars)
+        .await
+        .map_err(|e| e.to_string())?)
+}
@@ -0,0 +1,48 @@
+//! Tauri commands behind the bulk "analyse my positions" view. See
+//! `services::portfolio_analysis`.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::ibkr::types::ProjectionAssumptions;
+use crate::services::jobs::JobRegistry;
+use crate::services::portfolio_analysis::{PortfolioAnalysis, PortfolioAnalyzer};
+
+#[tauri::command]
+pub async fn analyze_portfolio(
+    analyzer: State<'_, Arc<PortfolioAnalyzer>>,
+    account: Option<String>,
+    assumptions: Option<ProjectionAssumptions>,
+) -> Result<PortfolioAnalysis, String> {
+    analyzer
+        .analyze(account, &assumptions.unwrap_or_default(), None)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Same as `analyze_portfolio`, run as a background job. Returns the job
+/// id at once; progress arrives as `job-progress` events and the
+/// `PortfolioAnalysis` lands in the job's `result`.
+#[tauri::command]
+pub async fn start_portfolio_analysis_job(
+    analyzer: State<'_, Arc<PortfolioAnalyzer>>,
+    jobs: State<'_, Arc<JobRegistry>>,
+    account: Option<String>,
+    assumptions: Option<ProjectionAssumptions>,
+) -> Result<u64, String> {
+    let analyzer = Arc::clone(&analyzer);
+    let job = jobs.start("portfolio_analysis", 0).await;
+    let id = job.id();
+    tauri::async_runtime::spawn(async move {
+        let assumptions = assumptions.unwrap_or_default();
+        match analyzer.analyze(account, &assumptions, Some(&job)).await {
+            Ok(analysis) => match serde_json::to_value(&analysis) {
+                Ok(value) => job.finish(value).await,
+                Err(e) => job.fail(e.to_string()).await,
+            },
+            Err(e) => job.fail(e.to_string()).await,
+        }
+    });
+    Ok(id)
+}
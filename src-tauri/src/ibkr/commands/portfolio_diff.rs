@@ -0,0 +1,24 @@
+//! `get_portfolio_diff` — live positions against a stored daily
+//! snapshot (see `services::portfolio_diff`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use super::executions::parse_date_arg;
+use crate::middleware::validation::CommandError;
+use crate::services::portfolio_diff::{PortfolioDiff, PortfolioDiffService};
+
+/// Changes since the snapshot on or before `date` (`YYYY-MM-DD`, ET);
+/// since the last one before today when omitted.
+#[tauri::command]
+pub async fn get_portfolio_diff(
+    diffs: State<'_, Arc<PortfolioDiffService>>,
+    date: Option<String>,
+) -> Result<PortfolioDiff, CommandError> {
+    let since = date
+        .as_deref()
+        .map(|d| parse_date_arg("date", d))
+        .transpose()?;
+    Ok(diffs.diff(since).await.map_err(|e| e.to_string())?)
+}
@@ -0,0 +1,69 @@
+//! Tauri commands for importing broker CSV exports into `imported_lots`
+//! and reading the merged (IBKR + imported) portfolio.
+//!
+//! The import command takes a file path rather than file contents so
+//! the frontend can hand over whatever the dialog plugin returned
+//! without shipping a multi-MB string across IPC.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::config::SettingsState;
+use crate::ibkr::types::Position;
+use crate::mcp::ibkr_seam::AccountReader;
+use crate::services::portfolio_import::{self, BrokerFormat, ImportSummary, ImportedLot};
+use crate::storage::Db;
+
+/// Import a CSV export at `path`. `format = None` auto-detects.
+/// Re-importing the same account replaces its previous snapshot.
+#[tauri::command]
+pub async fn portfolio_import_csv(
+    db: State<'_, Arc<Db>>,
+    path: String,
+    account: String,
+    format: Option<BrokerFormat>,
+) -> Result<ImportSummary, String> {
+    let text = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("read {path}: {e}"))?;
+    portfolio_import::import_csv(&db, &account, &text, format)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn portfolio_list_imported_lots(
+    db: State<'_, Arc<Db>>,
+) -> Result<Vec<ImportedLot>, String> {
+    portfolio_import::list_lots(&db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn portfolio_clear_imported(
+    db: State<'_, Arc<Db>>,
+    account: String,
+) -> Result<usize, String> {
+    portfolio_import::clear_account(&db, &account)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Live IBKR positions (every managed account) plus imported positions,
+/// filtered to the active workspace's accounts when one is selected.
+#[tauri::command]
+pub async fn portfolio_merged_positions(
+    db: State<'_, Arc<Db>>,
+    reader: State<'_, Arc<dyn AccountReader>>,
+    settings: State<'_, SettingsState>,
+) -> Result<Vec<Position>, String> {
+    let mut positions = portfolio_import::merged_positions(&db, reader.inner().as_ref())
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(ws) = settings.config.read().await.workspaces.active_workspace() {
+        positions.retain(|p| ws.includes_account(&p.account));
+    }
+    Ok(positions)
+}
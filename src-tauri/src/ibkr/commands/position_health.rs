@@ -0,0 +1,18 @@
+//! Tauri command behind the position health score (see
+//! `services::position_health`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::position_health::{PositionHealthReport, PositionHealthService};
+
+/// Score the open stock positions of `account` (the current account
+/// when omitted), worst first. Recomputed on every call.
+#[tauri::command]
+pub async fn get_position_health(
+    health: State<'_, Arc<PositionHealthService>>,
+    account: Option<String>,
+) -> Result<PositionHealthReport, String> {
+    health.report(account).await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,129 @@
+//! Tauri commands behind per-position plans (see
+//! `services::position_plans`).
+//!
+//! `confirm_stop_exit` is the only way a babysitter exit goes out: the
+//! trader confirms the ticket and it is sent like a manual
+//! `ibkr_place_order` (Hard Invariant 1).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use super::trading::place_confirmed;
+use crate::config::SettingsState;
+use crate::ibkr::state::IbkrState;
+use crate::middleware::validation::CommandError;
+use crate::services::order_guard::OrderGuard;
+use crate::services::position_plans::{
+    EnrichedPosition, PlanCheckReport, PositionPlan, PositionPlanInput, PositionPlanService,
+    StopBabysitter, StopWatchReport,
+};
+use crate::services::tca::TcaService;
+
+/// Open positions of `account` (the current account when omitted),
+/// each with its plan.
+#[tauri::command]
+pub async fn get_enriched_positions(
+    plans: State<'_, Arc<PositionPlanService>>,
+    account: Option<String>,
+) -> Result<Vec<EnrichedPosition>, String> {
+    plans.enriched(account).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_position_plans(
+    plans: State<'_, Arc<PositionPlanService>>,
+    account: Option<String>,
+) -> Result<Vec<PositionPlan>, String> {
+    plans
+        .list(account.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create or replace a plan; `None` when the input cleared it.
+#[tauri::command]
+pub async fn set_position_plan(
+    plans: State<'_, Arc<PositionPlanService>>,
+    plan: PositionPlanInput,
+) -> Result<Option<PositionPlan>, String> {
+    plans.set(plan).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_position_plan(
+    plans: State<'_, Arc<PositionPlanService>>,
+    account: String,
+    contract: String,
+) -> Result<bool, String> {
+    plans
+        .delete(&account, &contract)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Check the plans now instead of waiting for the next tick.
+#[tauri::command]
+pub async fn position_plan_check_now(
+    plans: State<'_, Arc<PositionPlanService>>,
+) -> Result<PlanCheckReport, String> {
+    plans.check().await.map_err(|e| e.to_string())
+}
+
+/// Check the babysat stops now; posts exit tickets like the task does.
+#[tauri::command]
+pub async fn stop_babysitter_check_now(
+    babysitter: State<'_, Arc<StopBabysitter>>,
+) -> Result<StopWatchReport, String> {
+    babysitter.check().await.map_err(|e| e.to_string())
+}
+
+/// Send a plan's pending exit ticket the trader has confirmed. Retrying
+/// replays the first order id instead of sending it twice.
+#[tauri::command]
+pub async fn confirm_stop_exit(
+    babysitter: State<'_, Arc<StopBabysitter>>,
+    state: State<'_, IbkrState>,
+    tca: State<'_, Arc<TcaService>>,
+    guard: State<'_, Arc<OrderGuard>>,
+    settings: State<'_, SettingsState>,
+    account: String,
+    contract: String,
+) -> Result<PositionPlan, CommandError> {
+    let plan = babysitter
+        .pending_exit(&account, &contract)
+        .await
+        .map_err(|e| e.to_string())?;
+    let order = plan
+        .exit_ticket
+        .ok_or_else(|| format!("no pending exit ticket for {contract}"))?;
+    let triggered_at = plan.stop_triggered_at.unwrap_or_default();
+    let intended_price = order.price;
+    let order_id = place_confirmed(
+        &state,
+        &tca,
+        &guard,
+        &settings,
+        order,
+        None,
+        intended_price,
+        Some(format!("stop-exit-{account}-{contract}-{triggered_at}")),
+    )
+    .await?;
+    Ok(babysitter
+        .exit_placed(&account, &contract, order_id)
+        .await
+        .map_err(|e| e.to_string())?)
+}
+
+#[tauri::command]
+pub async fn dismiss_stop_exit(
+    babysitter: State<'_, Arc<StopBabysitter>>,
+    account: String,
+    contract: String,
+) -> Result<PositionPlan, String> {
+    babysitter
+        .dismiss_exit(&account, &contract)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,27 @@
+//! Tauri command behind the projection history view.
+//!
+//! Snapshots are written by `ibkr_generate_projection_results`; this
+//! reads them back oldest-first, each with a diff against the previous
+//! snapshot (see `services::projection_history`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::services::projection_history::{ProjectionHistoryEntry, ProjectionHistoryStore};
+
+#[tauri::command]
+pub async fn get_projection_history(
+    history: State<'_, Arc<ProjectionHistoryStore>>,
+    symbol: String,
+    limit: Option<u32>,
+) -> Result<Vec<ProjectionHistoryEntry>, CommandError> {
+    let mut inputs = Inputs::new();
+    let symbol = inputs.symbol("symbol", &symbol);
+    inputs.finish()?;
+    Ok(history
+        .history(&symbol, limit)
+        .await
+        .map_err(|e| e.to_string())?)
+}
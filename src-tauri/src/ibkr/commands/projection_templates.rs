@@ -0,0 +1,75 @@
+//! Tauri commands for projection templates and the per-ticker choice.
+//! See `services::projection_templates`.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::config::commands::mutate_config;
+use crate::config::SettingsState;
+use crate::services::projection_templates::{
+    ProjectionTemplate, ProjectionTemplateError, ProjectionTemplatesConfig, TickerTemplateStore,
+};
+
+async fn mutate_templates<F>(
+    settings: &SettingsState,
+    f: F,
+) -> Result<Vec<ProjectionTemplate>, String>
+where
+    F: FnOnce(&mut ProjectionTemplatesConfig) -> Result<(), ProjectionTemplateError>,
+{
+    let config = mutate_config(settings, |c| {
+        f(&mut c.projection_templates).map_err(|e| e.to_string())
+    })
+    .await?;
+    Ok(config.projection_templates.templates)
+}
+
+#[tauri::command]
+pub async fn projection_template_list(
+    settings: State<'_, SettingsState>,
+) -> Result<Vec<ProjectionTemplate>, String> {
+    Ok(settings
+        .config
+        .read()
+        .await
+        .projection_templates
+        .templates
+        .clone())
+}
+
+/// Create or replace a template (matched by case-insensitive name).
+#[tauri::command]
+pub async fn projection_template_save(
+    settings: State<'_, SettingsState>,
+    template: ProjectionTemplate,
+) -> Result<Vec<ProjectionTemplate>, String> {
+    mutate_templates(&settings, |t| t.upsert(template)).await
+}
+
+#[tauri::command]
+pub async fn projection_template_delete(
+    settings: State<'_, SettingsState>,
+    name: String,
+) -> Result<Vec<ProjectionTemplate>, String> {
+    mutate_templates(&settings, |t| t.remove(&name)).await
+}
+
+/// Name of the template remembered for `symbol`, if any.
+#[tauri::command]
+pub async fn get_ticker_projection_template(
+    store: State<'_, Arc<TickerTemplateStore>>,
+    symbol: String,
+) -> Result<Option<String>, String> {
+    store.chosen(&symbol).await.map_err(|e| e.to_string())
+}
+
+/// Forget the template for `symbol`; projections go back to the
+/// defaults.
+#[tauri::command]
+pub async fn clear_ticker_projection_template(
+    store: State<'_, Arc<TickerTemplateStore>>,
+    symbol: String,
+) -> Result<bool, String> {
+    store.clear(&symbol).await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,360 @@
+//! `ibkr_generate_projection_results`: projections for one symbol over
+//! its fundamentals and live quote, recorded in the projection history
+//! and discounted to today.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::State;
+use tokio::time::timeout;
+use tracing::{debug, warn};
+
+use super::analysis::fetch_fundamentals;
+use crate::config::SettingsState;
+use crate::ibkr::types::{
+    FundamentalData, ProjectionAssumptions, ProjectionResultsWithFundamentals,
+};
+use crate::services::fundamentals_provider::FundamentalsProvider;
+use crate::services::macro_service::MacroService;
+use crate::services::projection_history::ProjectionHistoryStore;
+use crate::services::projection_service::ProjectionService;
+use crate::services::projection_templates::TickerTemplateStore;
+use crate::services::quote_service::QuoteService;
+use crate::services::valuation;
+
+/// Cap on how long the projection command will wait for the live-quote
+/// overlay before giving up. The IBKR snapshot path waits up to 5s per
+/// fetch (no ticks ⇒ channel close); on weekends / disconnected TWS
+/// every projection render would otherwise eat that full window. The
+/// overlay is best-effort by design — fail fast and let the UI render
+/// without the price rather than block 5s on a quote that isn't coming.
+const OVERLAY_QUOTE_DEADLINE: Duration = Duration::from_millis(1500);
+
+/// Generate projection results grouped by year (baseline + forward
+/// projections). Returns the underlying fundamentals alongside so the
+/// frontend can render projection inputs without a second fetch — this
+/// is the dedup half of the AV-quota burn fix. Each result is recorded
+/// in the projection history (best-effort), then discounted to today
+/// at the configured discount rate. `template` names a projection
+/// template and remembers it for the ticker; see
+/// `TickerTemplateStore::resolve` for how it combines with
+/// `assumptions`. `include_trace` adds the per-year calculation trace
+/// (`ProjectionService::explain`).
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn ibkr_generate_projection_results(
+    fundamentals: State<'_, Arc<dyn FundamentalsProvider>>,
+    quote_service: State<'_, Arc<QuoteService>>,
+    history: State<'_, Arc<ProjectionHistoryStore>>,
+    settings: State<'_, SettingsState>,
+    macro_service: State<'_, Arc<MacroService>>,
+    templates: State<'_, Arc<TickerTemplateStore>>,
+    symbol: String,
+    assumptions: Option<ProjectionAssumptions>,
+    template: Option<String>,
+    include_trace: Option<bool>,
+) -> Result<ProjectionResultsWithFundamentals, String> {
+    let (valuation_config, template_config) = {
+        let config = settings.config.read().await;
+        (
+            config.valuation.clone(),
+            config.projection_templates.clone(),
+        )
+    };
+    let resolved = templates
+        .resolve(&template_config, &symbol, template.as_deref(), assumptions)
+        .await
+        .map_err(|e| e.to_string())?;
+    let assumptions = resolved.assumptions;
+    let mut bundle = generate_projection_results_with_quote(
+        &fundamentals,
+        &quote_service,
+        &symbol,
+        &assumptions,
+    )
+    .await?;
+    if let Err(e) = history.record(&symbol, &assumptions, &bundle.results).await {
+        warn!("projection history: failed to record {symbol}: {e}");
+    }
+    bundle.template = resolved.template;
+    if include_trace.unwrap_or(false) {
+        bundle.trace = Some(ProjectionService::explain(
+            &bundle.fundamentals,
+            &assumptions,
+            &bundle.results,
+        ));
+    }
+    let rate = valuation::discount_rate(&valuation_config, macro_service.inner().as_ref()).await;
+    bundle.valuation = Some(valuation::discount(
+        &bundle.results,
+        rate,
+        bundle.fundamentals.current_metrics.price,
+    ));
+    Ok(bundle)
+}
+
+/// Fetch fundamentals + live quote, fold the quote's price into
+/// `current_metrics.price`, and run projections. Quote fetch is
+/// best-effort: if IBKR is disconnected / lacks market-data perms /
+/// times out, projections still render — the baseline row's
+/// price-derived fields just fall back to whatever (if anything) the
+/// fundamentals provider supplied.
+async fn generate_projection_results_with_quote(
+    fundamentals: &Arc<dyn FundamentalsProvider>,
+    quote_service: &Arc<QuoteService>,
+    symbol: &str,
+    assumptions: &ProjectionAssumptions,
+) -> Result<ProjectionResultsWithFundamentals, String> {
+    let mut fundamentals = fetch_fundamentals(fundamentals, symbol).await?;
+    overlay_live_price(&mut fundamentals, quote_service, symbol).await;
+    let results = ProjectionService::generate_projection_results(&fundamentals, assumptions)
+        .map_err(|e| e.to_string())?;
+    Ok(ProjectionResultsWithFundamentals {
+        fundamentals,
+        results,
+        valuation: None,
+        template: None,
+        trace: None,
+    })
+}
+
+/// Best-effort overlay of the IBKR live quote onto
+/// `fundamentals.current_metrics.price`. Prefers the regular-session
+/// price (`Quote::regular_price`, so a pre/post-market print doesn't
+/// move the projection). Falls back to `prev_close` if the snapshot didn't include a last
+/// tick. Quote-fetch failures are logged and swallowed so projections
+/// still render with whatever (if anything) the fundamentals provider
+/// supplied.
+async fn overlay_live_price(
+    fundamentals: &mut FundamentalData,
+    quote_service: &Arc<QuoteService>,
+    symbol: &str,
+) {
+    // An operator price override beats the live quote.
+    if fundamentals.overrides.iter().any(|o| o.field == "price") {
+        return;
+    }
+    match timeout(OVERLAY_QUOTE_DEADLINE, quote_service.fetch_quote(symbol)).await {
+        Ok(Ok(quote)) => {
+            if let Some(price) = quote.regular_price().or(quote.prev_close) {
+                fundamentals.current_metrics.price = Some(price);
+            }
+        }
+        Ok(Err(err)) => {
+            debug!(
+                "overlay_live_price: {symbol} quote fetch failed ({err:?}); leaving fundamentals.price untouched"
+            );
+        }
+        Err(_) => {
+            debug!(
+                "overlay_live_price: {symbol} quote fetch exceeded {}ms deadline; leaving fundamentals.price untouched",
+                OVERLAY_QUOTE_DEADLINE.as_millis()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ibkr::error::IbkrError;
+    use crate::ibkr::types::{CurrentMetrics, HistoricalFinancial, MarketDataSnapshot};
+    use crate::services::fundamentals_provider::test_support::FakeFundamentalsProvider;
+    use crate::services::quote_service::QuoteFetcher;
+    use async_trait::async_trait;
+    use std::sync::Mutex as StdMutex;
+
+    /// Minimal `QuoteFetcher` that hands back one canned snapshot or
+    /// error then panics on subsequent calls. The production
+    /// `quote_service::tests::StubFetcher` is private to its module, so
+    /// we mirror its single-shot shape here.
+    struct StubQuoteFetcher {
+        result: StdMutex<Option<crate::ibkr::error::Result<MarketDataSnapshot>>>,
+    }
+
+    impl StubQuoteFetcher {
+        fn ok(snapshot: MarketDataSnapshot) -> Arc<Self> {
+            Arc::new(Self {
+                result: StdMutex::new(Some(Ok(snapshot))),
+            })
+        }
+
+        fn err(error: IbkrError) -> Arc<Self> {
+            Arc::new(Self {
+                result: StdMutex::new(Some(Err(error))),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl QuoteFetcher for StubQuoteFetcher {
+        async fn get_market_data_snapshot(
+            &self,
+            _symbol: &str,
+        ) -> crate::ibkr::error::Result<MarketDataSnapshot> {
+            self.result
+                .lock()
+                .unwrap()
+                .take()
+                .expect("StubQuoteFetcher called more than once")
+        }
+    }
+
+    /// `QuoteFetcher` that never resolves. Stand-in for the live IBKR
+    /// snapshot path on weekends / disconnected TWS, where
+    /// `subscription.next()` blocks until the channel closes ~5s
+    /// later — driving the overlay's deadline-skip branch.
+    struct HangingQuoteFetcher;
+
+    #[async_trait]
+    impl QuoteFetcher for HangingQuoteFetcher {
+        async fn get_market_data_snapshot(
+            &self,
+            _symbol: &str,
+        ) -> crate::ibkr::error::Result<MarketDataSnapshot> {
+            std::future::pending().await
+        }
+    }
+
+    fn fundamentals_without_price(symbol: &str) -> FundamentalData {
+        FundamentalData {
+            symbol: symbol.to_string(),
+            historical: vec![HistoricalFinancial {
+                year: 2024,
+                revenue: 400.0,
+                net_income: 100.0,
+                eps: 6.0,
+                split_adjustment: None,
+            }],
+            analyst_estimates: None,
+            data_quality: Vec::new(),
+            overrides: Vec::new(),
+            growth: None,
+            currency: None,
+            fx_conversion: None,
+            segments: Vec::new(),
+            current_metrics: CurrentMetrics {
+                price: None,
+                pe_ratio: 0.0,
+                shares_outstanding: 16_000.0,
+                name: None,
+                exchange: None,
+                market_cap: None,
+                dividend_yield: None,
+                short_interest: None,
+            },
+        }
+    }
+
+    fn snapshot_with(last_price: Option<f64>, prev_close: Option<f64>) -> MarketDataSnapshot {
+        MarketDataSnapshot {
+            symbol: "AAPL".to_string(),
+            bid_price: None,
+            bid_size: None,
+            ask_price: None,
+            ask_size: None,
+            last_price,
+            last_size: None,
+            high: None,
+            low: None,
+            volume: None,
+            close: prev_close,
+            open: None,
+            last_rth_price: None,
+            session: None,
+            timestamp: 0,
+        }
+    }
+
+    fn provider_with(symbol: &str, fund: FundamentalData) -> Arc<dyn FundamentalsProvider> {
+        let fake = FakeFundamentalsProvider::new();
+        fake.insert(symbol, fund);
+        Arc::new(fake)
+    }
+
+    #[tokio::test]
+    async fn projection_results_uses_live_quote_last_price_for_baseline() {
+        let provider = provider_with("AAPL", fundamentals_without_price("AAPL"));
+        let quote_service = Arc::new(QuoteService::new(StubQuoteFetcher::ok(snapshot_with(
+            Some(200.0),
+            Some(195.0),
+        ))));
+
+        let bundle = generate_projection_results_with_quote(
+            &provider,
+            &quote_service,
+            "AAPL",
+            &ProjectionAssumptions::default(),
+        )
+        .await
+        .expect("ok");
+
+        assert_eq!(bundle.results.baseline.share_price_low, 200.0);
+        assert_eq!(bundle.results.baseline.share_price_high, 200.0);
+        assert_eq!(bundle.fundamentals.current_metrics.price, Some(200.0));
+    }
+
+    #[tokio::test]
+    async fn projection_results_falls_back_to_prev_close_when_last_price_missing() {
+        let provider = provider_with("AAPL", fundamentals_without_price("AAPL"));
+        let quote_service = Arc::new(QuoteService::new(StubQuoteFetcher::ok(snapshot_with(
+            None,
+            Some(195.0),
+        ))));
+
+        let bundle = generate_projection_results_with_quote(
+            &provider,
+            &quote_service,
+            "AAPL",
+            &ProjectionAssumptions::default(),
+        )
+        .await
+        .expect("ok");
+
+        assert_eq!(bundle.results.baseline.share_price_low, 195.0);
+        assert_eq!(bundle.fundamentals.current_metrics.price, Some(195.0));
+    }
+
+    #[tokio::test]
+    async fn projection_results_continues_when_quote_fetch_fails() {
+        let provider = provider_with("AAPL", fundamentals_without_price("AAPL"));
+        let quote_service = Arc::new(QuoteService::new(StubQuoteFetcher::err(
+            IbkrError::NotConnected,
+        )));
+
+        let bundle = generate_projection_results_with_quote(
+            &provider,
+            &quote_service,
+            "AAPL",
+            &ProjectionAssumptions::default(),
+        )
+        .await
+        .expect("quote failure must not abort projections");
+
+        assert_eq!(bundle.results.baseline.share_price_low, 0.0);
+        assert_eq!(bundle.fundamentals.current_metrics.price, None);
+    }
+
+    /// Locks in the overlay deadline: a hanging quote fetcher must not
+    /// stall the projection command past `OVERLAY_QUOTE_DEADLINE`.
+    /// `start_paused = true` lets tokio auto-advance virtual time to the
+    /// deadline timer, so this completes instantly instead of waiting
+    /// the real 1.5s.
+    #[tokio::test(start_paused = true)]
+    async fn projection_results_skips_overlay_when_quote_fetch_exceeds_deadline() {
+        let provider = provider_with("AAPL", fundamentals_without_price("AAPL"));
+        let quote_service = Arc::new(QuoteService::new(Arc::new(HangingQuoteFetcher)));
+
+        let bundle = generate_projection_results_with_quote(
+            &provider,
+            &quote_service,
+            "AAPL",
+            &ProjectionAssumptions::default(),
+        )
+        .await
+        .expect("overlay deadline must not abort projections");
+
+        assert_eq!(bundle.results.baseline.share_price_low, 0.0);
+        assert_eq!(bundle.fundamentals.current_metrics.price, None);
+    }
+}
@@ -0,0 +1,20 @@
+//! `report_generate` — on-demand research report for one ticker (see
+//! `services::report_service`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::report_service::{ReportArtifact, ReportService};
+
+#[tauri::command]
+pub async fn report_generate(
+    reports: State<'_, Arc<ReportService>>,
+    symbol: String,
+    attach_to_journal: Option<bool>,
+) -> Result<ReportArtifact, String> {
+    reports
+        .generate(&symbol, attach_to_journal.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
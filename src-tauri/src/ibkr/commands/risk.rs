@@ -75,7 +75,7 @@ pub async fn risk_recompute_setup(
     };
 
     let (sizing, _snap) = engine
-        .size_for_candidate(&candidate)
+        .size_for_candidate(&setup.symbol, &candidate)
         .await
         .map_err(|e| e.to_string())?;
     let refreshed = state
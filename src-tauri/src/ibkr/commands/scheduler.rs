@@ -0,0 +1,72 @@
+//! Tauri commands over the recurring-task scheduler
+//! (`services::scheduler`). Schedules are settings: `scheduler_set_job`
+//! writes `AppConfig.scheduler` through the same validate-then-persist
+//! path as the settings form.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::config::commands::mutate_config;
+use crate::config::SettingsState;
+use crate::services::scheduler::history::JobRun;
+use crate::services::scheduler::{ScheduledJobView, Scheduler};
+
+/// Default page size for `scheduler_run_history`.
+const DEFAULT_HISTORY_LIMIT: u32 = 20;
+
+#[tauri::command]
+pub async fn scheduler_list_jobs(
+    scheduler: State<'_, Arc<Scheduler>>,
+) -> Result<Vec<ScheduledJobView>, String> {
+    scheduler.list().await.map_err(|e| e.to_string())
+}
+
+/// Enable / disable job `id`, optionally re-timing it with a new cron
+/// expression. Returns the refreshed job list.
+#[tauri::command]
+pub async fn scheduler_set_job(
+    scheduler: State<'_, Arc<Scheduler>>,
+    settings: State<'_, SettingsState>,
+    id: String,
+    enabled: bool,
+    cron: Option<String>,
+) -> Result<Vec<ScheduledJobView>, String> {
+    let task = scheduler
+        .task(&id)
+        .ok_or_else(|| format!("unknown scheduled job `{id}`"))?;
+    mutate_config(&settings, |config| {
+        let mut def = Scheduler::effective(task.as_ref(), &config.scheduler);
+        def.enabled = enabled;
+        if let Some(cron) = cron {
+            def.cron = cron.trim().to_string();
+        }
+        config.scheduler.upsert(def);
+        Ok(())
+    })
+    .await?;
+    scheduler.list().await.map_err(|e| e.to_string())
+}
+
+/// Runs of job `id`, newest first.
+#[tauri::command]
+pub async fn scheduler_run_history(
+    scheduler: State<'_, Arc<Scheduler>>,
+    id: String,
+    limit: Option<u32>,
+) -> Result<Vec<JobRun>, String> {
+    scheduler
+        .history(&id, limit.unwrap_or(DEFAULT_HISTORY_LIMIT))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Run job `id` once now and return its summary. A failing run is an
+/// `Err` here and is also written to the run history.
+#[tauri::command]
+pub async fn scheduler_run_now(
+    scheduler: State<'_, Arc<Scheduler>>,
+    id: String,
+) -> Result<String, String> {
+    scheduler.run_now(&id).await?
+}
@@ -0,0 +1,17 @@
+//! Tauri command behind the fundamentals screener. Reads cached data
+//! only (see `services::screener`); pass IBKR scanner symbols as
+//! `criteria.universe` to screen a scan's output.
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::screener::{Screener, ScreenerCriteria, ScreenerResult};
+
+#[tauri::command]
+pub async fn screener(
+    screener: State<'_, Arc<Screener>>,
+    criteria: ScreenerCriteria,
+) -> Result<ScreenerResult, String> {
+    screener.screen(&criteria).await
+}
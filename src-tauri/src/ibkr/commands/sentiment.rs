@@ -9,6 +9,7 @@ use std::sync::Arc;
 
 use tauri::State;
 
+use crate::middleware::validation::{CommandError, Inputs};
 use crate::services::social_sentiment::repo::{latest_per_source, rows_for_symbol_since};
 use crate::services::social_sentiment::types::SocialSentimentRow;
 use crate::services::social_sentiment::SocialSentimentService;
@@ -22,14 +23,13 @@ use crate::storage::Db;
 pub async fn social_get_latest(
     db: State<'_, Arc<Db>>,
     symbol: String,
-) -> Result<Vec<SocialSentimentRow>, String> {
-    let symbol_norm = symbol.trim().to_string();
-    if symbol_norm.is_empty() {
-        return Err("symbol must not be empty".into());
-    }
-    latest_per_source(Arc::clone(&db), symbol_norm)
+) -> Result<Vec<SocialSentimentRow>, CommandError> {
+    let mut inputs = Inputs::new();
+    let symbol = inputs.symbol("symbol", &symbol);
+    inputs.finish()?;
+    Ok(latest_per_source(Arc::clone(&db), symbol)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?)
 }
 
 /// Time-series rows for `symbol` over a trailing window. `since_unix`
@@ -41,14 +41,16 @@ pub async fn social_list_window(
     symbol: String,
     since_unix: i64,
     sources: Option<Vec<String>>,
-) -> Result<Vec<SocialSentimentRow>, String> {
-    let symbol_norm = symbol.trim().to_string();
-    if symbol_norm.is_empty() {
-        return Err("symbol must not be empty".into());
-    }
-    rows_for_symbol_since(Arc::clone(&db), symbol_norm, since_unix, sources)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<Vec<SocialSentimentRow>, CommandError> {
+    let mut inputs = Inputs::new();
+    let symbol = inputs.symbol("symbol", &symbol);
+    inputs.check(since_unix >= 0, "sinceUnix", "must not be negative");
+    inputs.finish()?;
+    Ok(
+        rows_for_symbol_since(Arc::clone(&db), symbol, since_unix, sources)
+            .await
+            .map_err(|e| e.to_string())?,
+    )
 }
 
 /// Force one scheduler tick. Useful from the UI gear menu when the
@@ -0,0 +1,23 @@
+//! Tauri command behind the stress test section of the risk report (see
+//! `services::stress_test`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::stress_test::{StressTestReport, StressTestService};
+
+/// Projected P&L per position and in total of the stock positions of
+/// `account` (the current account when omitted) under every scenario in
+/// `stress_test.scenarios`, or only the one named `scenario`.
+#[tauri::command]
+pub async fn run_stress_test(
+    stress_test: State<'_, Arc<StressTestService>>,
+    account: Option<String>,
+    scenario: Option<String>,
+) -> Result<StressTestReport, String> {
+    stress_test
+        .run(account, scenario)
+        .await
+        .map_err(|e| e.to_string())
+}
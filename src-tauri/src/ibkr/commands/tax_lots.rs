@@ -0,0 +1,64 @@
+//! Tauri commands behind the tax-lot report and the tax projection (see
+//! `services::tax_lots`).
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use tauri::State;
+
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::services::tax_lots::{PlannedSale, TaxLotReport, TaxLotService, TaxProjection};
+use crate::utils::market_calendar::et_date;
+
+/// Most planned sales one projection takes.
+const MAX_PLANNED_SALES: usize = 200;
+
+/// Lots realized in `year` across every account, with wash sales
+/// flagged and their disallowed losses carried into the replacement
+/// lots' basis.
+#[tauri::command]
+pub async fn get_tax_lot_report(
+    tax_lots: State<'_, Arc<TaxLotService>>,
+    year: i32,
+) -> Result<TaxLotReport, CommandError> {
+    let mut inputs = Inputs::new();
+    inputs.check(
+        (2000..=2100).contains(&year),
+        "year",
+        "must be between 2000 and 2100",
+    );
+    inputs.finish()?;
+    Ok(tax_lots.report(year).await.map_err(|e| e.to_string())?)
+}
+
+/// The gains `sales` would realize, split by term, and the estimated
+/// tax of each year from this one through the last sale's at the `tax`
+/// rates.
+#[tauri::command]
+pub async fn project_tax(
+    tax_lots: State<'_, Arc<TaxLotService>>,
+    sales: Vec<PlannedSale>,
+) -> Result<TaxProjection, CommandError> {
+    let today = et_date(Utc::now());
+    let mut inputs = Inputs::new();
+    inputs.check(
+        !sales.is_empty() && sales.len() <= MAX_PLANNED_SALES,
+        "sales",
+        format!("must list between 1 and {MAX_PLANNED_SALES} sales"),
+    );
+    for (i, sale) in sales.iter().enumerate() {
+        inputs.symbol(&format!("sales[{i}].symbol"), &sale.symbol);
+        inputs.positive(&format!("sales[{i}].quantity"), sale.quantity);
+        inputs.positive(&format!("sales[{i}].price"), sale.price);
+        inputs.check(
+            sale.date >= today,
+            &format!("sales[{i}].date"),
+            "must not be in the past",
+        );
+    }
+    inputs.finish()?;
+    Ok(tax_lots
+        .project(&sales, today)
+        .await
+        .map_err(|e| e.to_string())?)
+}
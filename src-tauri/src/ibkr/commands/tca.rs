@@ -1,7 +1,7 @@
 //! Phase 2 — TCA Tauri commands.
 //!
-//! Read-only attribution + slippage distribution for the
-//! trade-review surface, plus a write rail for retroactive manual
+//! Read-only attribution, slippage distribution and execution quality
+//! (fills vs the arrival quote) for the trade-review surface, plus a write rail for retroactive manual
 //! intents (out-of-band TWS fills the trader wants to attribute).
 //!
 //! `tca_record_manual_intent` is a Tauri command **only** — never
@@ -16,14 +16,18 @@ use chrono::{Duration, NaiveDate, Utc};
 use tauri::State;
 
 use crate::ibkr::state::IbkrState;
+use crate::middleware::validation::{CommandError, Inputs};
 use crate::services::tca::{
-    AttributionRow, IntendedPriceSource, IntentSide, NewOrderIntent, SlippageDistributionRow,
-    TcaService,
+    AttributionRow, ExecutionQualityReport, IntendedPriceSource, IntentSide, NewOrderIntent,
+    SlippageDistributionRow, TcaService,
 };
 
-fn parse_iso_date(date: &str) -> Result<NaiveDate, String> {
-    NaiveDate::parse_from_str(date, "%Y-%m-%d")
-        .map_err(|e| format!("invalid date '{date}', expected YYYY-MM-DD: {e}"))
+/// Both ends required, `dateTo` on or after `dateFrom`.
+fn parse_range(date_from: &str, date_to: &str) -> Result<(NaiveDate, NaiveDate), CommandError> {
+    let mut inputs = Inputs::new();
+    let (from, to) = inputs.date_range("dateFrom", Some(date_from), "dateTo", Some(date_to));
+    inputs.finish()?;
+    Ok(from.zip(to).expect("validated"))
 }
 
 #[tauri::command]
@@ -33,18 +37,14 @@ pub async fn tca_get_attribution(
     date_from: String,
     date_to: String,
     account: Option<String>,
-) -> Result<Vec<AttributionRow>, String> {
-    let from = parse_iso_date(&date_from)?;
-    let to = parse_iso_date(&date_to)?;
-    if to < from {
-        return Err("date_to must be >= date_from".to_string());
-    }
+) -> Result<Vec<AttributionRow>, CommandError> {
+    let (from, to) = parse_range(&date_from, &date_to)?;
     let resolved = resolve_account_arg(&state, account.as_deref()).await?;
     tca.inner()
         .attribution()
         .attribution(from, to, &resolved)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string().into())
 }
 
 #[tauri::command]
@@ -54,18 +54,33 @@ pub async fn tca_get_slippage_distribution(
     date_from: String,
     date_to: String,
     account: Option<String>,
-) -> Result<Vec<SlippageDistributionRow>, String> {
-    let from = parse_iso_date(&date_from)?;
-    let to = parse_iso_date(&date_to)?;
-    if to < from {
-        return Err("date_to must be >= date_from".to_string());
-    }
+) -> Result<Vec<SlippageDistributionRow>, CommandError> {
+    let (from, to) = parse_range(&date_from, &date_to)?;
     let resolved = resolve_account_arg(&state, account.as_deref()).await?;
     tca.inner()
         .attribution()
         .slippage_distribution(from, to, &resolved, None)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string().into())
+}
+
+/// Stock orders placed from the app over the range, per order and
+/// rolled up by symbol and order type, with slippage measured against
+/// the bid / ask mid when the order was placed.
+#[tauri::command]
+pub async fn get_execution_quality_report(
+    tca: State<'_, Arc<TcaService>>,
+    state: State<'_, IbkrState>,
+    date_from: String,
+    date_to: String,
+    account: Option<String>,
+) -> Result<ExecutionQualityReport, CommandError> {
+    let (from, to) = parse_range(&date_from, &date_to)?;
+    let resolved = resolve_account_arg(&state, account.as_deref()).await?;
+    tca.inner()
+        .execution_quality(from, to, &resolved)
+        .await
+        .map_err(|e| e.to_string().into())
 }
 
 /// Wire DTO for `tca_record_manual_intent`. The intent is built
@@ -88,27 +103,31 @@ pub async fn tca_record_manual_intent(
     tca: State<'_, Arc<TcaService>>,
     state: State<'_, IbkrState>,
     args: ManualIntentArgs,
-) -> Result<String, String> {
-    let side = IntentSide::parse(&args.side)
-        .ok_or_else(|| format!("invalid side '{}', expected 'buy' or 'sell'", args.side))?;
-    if !args.qty.is_finite() || args.qty <= 0.0 {
-        return Err("qty must be > 0".to_string());
-    }
-    if !args.intended_price.is_finite() || args.intended_price <= 0.0 {
-        return Err("intended_price must be > 0".to_string());
-    }
+) -> Result<String, CommandError> {
+    let mut inputs = Inputs::new();
+    let symbol = inputs.symbol("args.symbol", &args.symbol);
+    let side = IntentSide::parse(&args.side);
+    inputs.check(
+        side.is_some(),
+        "args.side",
+        format!("`{}` is not `buy` or `sell`", args.side),
+    );
+    inputs.positive("args.qty", args.qty);
+    inputs.positive("args.intended_price", args.intended_price);
+    inputs.finish()?;
+    let side = side.expect("validated");
     let account = resolve_account_arg(&state, args.account.as_deref()).await?;
     let now = Utc::now();
     let intent_id = format!(
         "intent_manual_{}_{}",
         now.timestamp_nanos_opt().unwrap_or(0),
-        args.symbol.replace(' ', "_"),
+        symbol,
     );
     let new_intent = NewOrderIntent {
         intent_id: intent_id.clone(),
         setup_id: args.setup_id,
         account,
-        symbol: args.symbol,
+        symbol,
         side,
         qty: args.qty,
         intended_price_cents: (args.intended_price * 100.0).round() as i64,
@@ -149,9 +168,13 @@ mod tests {
     use super::*;
 
     #[test]
-    fn parse_iso_date_accepts_canonical_format() {
-        assert!(parse_iso_date("2026-05-04").is_ok());
-        assert!(parse_iso_date("2026/05/04").is_err());
-        assert!(parse_iso_date("not a date").is_err());
+    fn parse_range_accepts_canonical_format() {
+        assert!(parse_range("2026-05-04", "2026-05-04").is_ok());
+        assert!(parse_range("2026/05/04", "2026-05-04").is_err());
+        assert!(parse_range("not a date", "2026-05-04").is_err());
+        let Err(CommandError::Invalid { fields }) = parse_range("2026-05-04", "2026-05-01") else {
+            panic!("expected an inverted-range error");
+        };
+        assert_eq!(fields[0].field, "dateTo");
     }
 }
@@ -0,0 +1,77 @@
+//! Tauri commands behind the trade idea pipeline (see
+//! `services::trade_ideas`).
+
+use std::sync::Arc;
+
+use tauri::State;
+
+use crate::services::trade_ideas::{
+    IdeaLinkKind, IdeaStage, TradeIdea, TradeIdeaDetail, TradeIdeaService,
+};
+
+#[tauri::command]
+pub async fn create_trade_idea(
+    ideas: State<'_, Arc<TradeIdeaService>>,
+    symbol: String,
+    note: Option<String>,
+) -> Result<TradeIdea, String> {
+    ideas.create(&symbol, note).await.map_err(|e| e.to_string())
+}
+
+/// Move an idea forward to `stage`; going back is refused.
+#[tauri::command]
+pub async fn move_trade_idea(
+    ideas: State<'_, Arc<TradeIdeaService>>,
+    id: i64,
+    stage: IdeaStage,
+    note: Option<String>,
+) -> Result<TradeIdea, String> {
+    ideas
+        .move_to(id, stage, note)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Link a projection snapshot or journal entry to an idea.
+#[tauri::command]
+pub async fn link_trade_idea(
+    ideas: State<'_, Arc<TradeIdeaService>>,
+    id: i64,
+    kind: IdeaLinkKind,
+    ref_id: i64,
+) -> Result<TradeIdeaDetail, String> {
+    ideas
+        .link(id, kind, ref_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn unlink_trade_idea(
+    ideas: State<'_, Arc<TradeIdeaService>>,
+    id: i64,
+    kind: IdeaLinkKind,
+    ref_id: i64,
+) -> Result<TradeIdeaDetail, String> {
+    ideas
+        .unlink(id, kind, ref_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_trade_ideas(
+    ideas: State<'_, Arc<TradeIdeaService>>,
+    stage: Option<IdeaStage>,
+) -> Result<Vec<TradeIdea>, String> {
+    ideas.list(stage).await.map_err(|e| e.to_string())
+}
+
+/// One idea with its stage history and links.
+#[tauri::command]
+pub async fn get_trade_idea(
+    ideas: State<'_, Arc<TradeIdeaService>>,
+    id: i64,
+) -> Result<TradeIdeaDetail, String> {
+    ideas.get(id).await.map_err(|e| e.to_string())
+}
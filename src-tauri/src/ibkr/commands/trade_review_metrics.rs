@@ -21,6 +21,7 @@ use tauri::State;
 use crate::mcp::ibkr_seam::AccountReader;
 use crate::mcp::tools::executions::ExecutionRow;
 use crate::mcp::tools::resolve_account;
+use crate::middleware::validation::{CommandError, Inputs};
 use crate::services::executions::ExecutionsStore;
 use crate::services::trade_legs::{match_legs, TradeLeg};
 use crate::services::trade_reviews::{
@@ -31,20 +32,18 @@ use crate::storage::Db;
 
 const MAX_RANGE_DAYS: i64 = 365;
 
-fn parse_range(start: &str, end: &str) -> Result<(NaiveDate, NaiveDate), String> {
-    let s = NaiveDate::parse_from_str(start, "%Y-%m-%d")
-        .map_err(|e| format!("invalid start `{start}`, expected YYYY-MM-DD: {e}"))?;
-    let e = NaiveDate::parse_from_str(end, "%Y-%m-%d")
-        .map_err(|e| format!("invalid end `{end}`, expected YYYY-MM-DD: {e}"))?;
-    if e < s {
-        return Err(format!("end < start: {end} < {start}"));
-    }
-    if (e - s).num_days() > MAX_RANGE_DAYS {
-        return Err(format!(
-            "range > {MAX_RANGE_DAYS} days; chunk into smaller queries",
-        ));
+fn parse_range(start: &str, end: &str) -> Result<(NaiveDate, NaiveDate), CommandError> {
+    let mut inputs = Inputs::new();
+    let (s, e) = inputs.date_range("start", Some(start), "end", Some(end));
+    if let (Some(s), Some(e)) = (s, e) {
+        inputs.check(
+            (e - s).num_days() <= MAX_RANGE_DAYS,
+            "end",
+            format!("range > {MAX_RANGE_DAYS} days; chunk into smaller queries"),
+        );
     }
-    Ok((s, e))
+    inputs.finish()?;
+    Ok(s.zip(e).expect("validated"))
 }
 
 async fn fetch_executions_in_range(
@@ -110,7 +109,7 @@ pub async fn trade_review_get_metrics(
     start: String,
     end: String,
     account: Option<String>,
-) -> Result<RiskMetrics, String> {
+) -> Result<RiskMetrics, CommandError> {
     let (s, e) = parse_range(&start, &end)?;
     let resolved = resolve_account(reader.inner().as_ref(), account.as_deref()).await?;
     let fills = fetch_executions_in_range(db.inner(), &resolved, s, e).await?;
@@ -140,7 +139,7 @@ pub async fn trade_review_get_equity_curve(
     end: String,
     account: Option<String>,
     starting_equity: Option<f64>,
-) -> Result<Vec<EquityPoint>, String> {
+) -> Result<Vec<EquityPoint>, CommandError> {
     let (s, e) = parse_range(&start, &end)?;
     let resolved = resolve_account(reader.inner().as_ref(), account.as_deref()).await?;
     let fills = fetch_executions_in_range(db.inner(), &resolved, s, e).await?;
@@ -157,7 +156,7 @@ pub async fn trade_review_get_strategy_rollup(
     start: String,
     end: String,
     account: Option<String>,
-) -> Result<Vec<StrategyRollup>, String> {
+) -> Result<Vec<StrategyRollup>, CommandError> {
     let (s, e) = parse_range(&start, &end)?;
     let resolved = resolve_account(reader.inner().as_ref(), account.as_deref()).await?;
     let fills = fetch_executions_in_range(db.inner(), &resolved, s, e).await?;
@@ -11,22 +11,162 @@
 //! the command resolves the trader's account, generates a unique
 //! `intent_id`, and lands the row through `TcaService::record_intent`
 //! before forwarding to IBKR.
+//!
+//! Stock orders also snapshot the bid / ask on the way out; once IBKR
+//! returns the order id, the quote lands in `order_arrivals` through
+//! `TcaService::record_arrival` and fills are later measured against
+//! its mid (`get_execution_quality_report`). Bracket orders placed
+//! through `OrderTicket` don't record one.
+//!
+//! Before any of that the request passes `OrderGuard`: an optional
+//! `idempotency_key` replays the first placement's order id, and an
+//! identical request inside `order_guard.duplicate_window_secs` is
+//! rejected (double-clicks, frontend retries).
+//!
+//! Malformed input (no symbol, a non-positive quantity, a limit order
+//! without a price) is rejected field by field before any of it.
+//!
+//! Order types that only work during regular trading hours (MIDPRICE,
+//! IBKR algos) are refused while the contract's market is closed,
+//! unless the order is held for a later start. Exchanges without a
+//! known calendar pass through.
 
-use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::Duration;
 
-use chrono::{NaiveDate, Utc};
+use chrono::{DateTime, Utc};
 use tauri::State;
+use tokio::time::timeout;
 
+use crate::config::SettingsState;
 use crate::ibkr::state::IbkrState;
-use crate::ibkr::types::{IbkrExecution, OrderAction, OrderRequest, OrderType};
-use crate::services::tca::{IntendedPriceSource, IntentSide, NewOrderIntent, TcaService};
+use crate::ibkr::types::{ContractRoute, OrderRequest, OrderType};
+use crate::middleware::validation::{CommandError, Inputs};
+use crate::services::order_guard::{Admission, OrderGuard};
+use crate::services::tca::TcaService;
+use crate::utils::market_calendar;
+
+mod records;
+
+use records::{build_arrival, build_intent};
+
+/// How long placement waits on the arrival snapshot.
+const ARRIVAL_QUOTE_DEADLINE: Duration = Duration::from_millis(1500);
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn ibkr_place_order(
     state: State<'_, IbkrState>,
     tca: State<'_, Arc<TcaService>>,
+    guard: State<'_, Arc<OrderGuard>>,
+    settings: State<'_, SettingsState>,
+    order: OrderRequest,
+    setup_id: Option<i64>,
+    intended_price: Option<f64>,
+    idempotency_key: Option<String>,
+) -> Result<i32, CommandError> {
+    place_confirmed(
+        &state,
+        &tca,
+        &guard,
+        &settings,
+        order,
+        setup_id,
+        intended_price,
+        idempotency_key,
+    )
+    .await
+}
+
+/// Everything `ibkr_place_order` does, for other commands that send an
+/// order the trader has just confirmed.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn place_confirmed(
+    state: &IbkrState,
+    tca: &TcaService,
+    guard: &OrderGuard,
+    settings: &SettingsState,
+    order: OrderRequest,
+    setup_id: Option<i64>,
+    intended_price: Option<f64>,
+    idempotency_key: Option<String>,
+) -> Result<i32, CommandError> {
+    check_order(&order, intended_price)?;
+    session_gate(&order, Utc::now())?;
+    let window = settings
+        .config
+        .read()
+        .await
+        .order_guard
+        .duplicate_window_secs;
+    let reservation = match guard.admit(
+        &order,
+        idempotency_key.as_deref(),
+        chrono::Duration::seconds(window as i64),
+        Utc::now(),
+    )? {
+        Admission::Place(r) => r,
+        Admission::Replay(order_id) => return Ok(order_id),
+    };
+    let placed = place_unguarded(state, tca, order, setup_id, intended_price).await;
+    guard.settle(&reservation, placed.as_ref().ok().copied());
+    Ok(placed?)
+}
+
+/// The symbol isn't held to the ticker format: with a `route` it may be
+/// any exchange's, and IBKR resolves the contract.
+fn check_order(order: &OrderRequest, intended_price: Option<f64>) -> Result<(), CommandError> {
+    let mut inputs = Inputs::new();
+    inputs.text("order.symbol", &order.symbol);
+    inputs.positive("order.quantity", order.quantity);
+    match order.price {
+        Some(price) => {
+            inputs.positive("order.price", price);
+        }
+        None => inputs.check(
+            !matches!(order.order_type, OrderType::Limit),
+            "order.price",
+            "a limit order needs a price",
+        ),
+    }
+    if let Some(price) = intended_price {
+        inputs.positive("intendedPrice", price);
+    }
+    inputs.finish()
+}
+
+/// Refuse RTH-only order types while the order's market is closed.
+fn session_gate(order: &OrderRequest, now: DateTime<Utc>) -> Result<(), String> {
+    let rth_only = matches!(order.order_type, OrderType::Midprice) || order.algo.is_some();
+    if !rth_only || order.start_at.is_some() {
+        return Ok(());
+    }
+    let exchange = order
+        .route
+        .as_ref()
+        .filter(|_| order.option.is_none())
+        .and_then(ContractRoute::listing_exchange)
+        .unwrap_or("SMART");
+    if market_calendar::is_market_open(exchange, now) != Some(false) {
+        return Ok(());
+    }
+    let opens = market_calendar::next_open(exchange, now)
+        .map(|t| format!("; it opens {}", t.format("%Y-%m-%d %H:%M UTC")))
+        .unwrap_or_default();
+    Err(format!(
+        "{} orders need regular trading hours and the {exchange} market is closed{opens}. \
+         Use a limit order or hold it for the open.",
+        if order.algo.is_some() {
+            "Algo"
+        } else {
+            "Midprice"
+        }
+    ))
+}
+
+async fn place_unguarded(
+    state: &IbkrState,
+    tca: &TcaService,
     order: OrderRequest,
     setup_id: Option<i64>,
     intended_price: Option<f64>,
@@ -35,107 +175,74 @@ pub async fn ibkr_place_order(
     // setup_id) means we have nothing to compare against and the
     // intent is skipped. The order itself proceeds either way —
     // surveillance-only is preserved.
+    let mut account = None;
     if let Some(price) = intended_price {
-        let account = state
-            .client
-            .get_accounts()
-            .await
-            .map_err(|e| e.to_string())?
-            .into_iter()
-            .next()
-            .ok_or_else(|| "no IBKR account available for intent recording".to_string())?;
-        let intent = build_intent(&account, &order, setup_id, price);
-        if let Err(e) = tca.inner().record_intent(intent).await {
+        let resolved = first_account(state).await?;
+        let intent = build_intent(&resolved, &order, setup_id, price);
+        if let Err(e) = tca.record_intent(intent).await {
             // Log-and-continue: a botched intent shouldn't block the
             // order. This branches cleanly into the "unattributed"
             // bucket; the trader still gets the fill.
             tracing::warn!(error = %e, "place_order: intent record failed; continuing");
         }
+        account = Some(resolved);
     }
-    state
+    // The arrival quote is a stock's bid / ask; option orders go
+    // unmeasured. A slow or failed snapshot records no quote rather
+    // than holding the order back.
+    let arrival = if order.option.is_none() {
+        let snapshot = timeout(
+            ARRIVAL_QUOTE_DEADLINE,
+            state
+                .client
+                .get_market_data_snapshot_on(&order.symbol, order.route.clone()),
+        )
+        .await;
+        let snapshot = match snapshot {
+            Ok(Ok(s)) => Some(s),
+            Ok(Err(e)) => {
+                tracing::debug!(symbol = %order.symbol, error = %e, "place_order: no arrival quote");
+                None
+            }
+            Err(_) => None,
+        };
+        Some(build_arrival(&order, snapshot.as_ref(), Utc::now()))
+    } else {
+        None
+    };
+    let order_id = state
         .client
         .place_order(order)
         .await
-        .map_err(|e| e.to_string())
-}
-
-fn build_intent(
-    account: &str,
-    order: &OrderRequest,
-    setup_id: Option<i64>,
-    intended_price: f64,
-) -> NewOrderIntent {
-    let now = Utc::now();
-    let is_market = matches!(order.order_type, OrderType::Market);
-    let window_minutes = if is_market { 5 } else { 60 };
-    let intended_cents = (intended_price * 100.0).round() as i64;
-    NewOrderIntent {
-        intent_id: gen_intent_id(setup_id),
-        setup_id,
-        account: account.to_string(),
-        symbol: order.symbol.clone(),
-        side: match order.action {
-            OrderAction::Buy => IntentSide::Buy,
-            OrderAction::Sell => IntentSide::Sell,
-        },
-        qty: order.quantity,
-        intended_price_cents: intended_cents.max(0),
-        // The UI passes `intended_price` after sourcing it from the
-        // setup trigger; when no setup, source is best-guess
-        // `LimitPrice` for limit orders, `LiveQuote` otherwise. The
-        // command itself is one layer above the source decision and
-        // can't tell the difference, so default conservatively.
-        intended_price_source: if setup_id.is_some() {
-            IntendedPriceSource::TriggerPrice
-        } else if matches!(order.order_type, OrderType::Limit) {
-            IntendedPriceSource::LimitPrice
-        } else {
-            IntendedPriceSource::LiveQuote
-        },
-        posted_at: now,
-        expires_at: now + chrono::Duration::minutes(window_minutes),
+        .map_err(|e| e.to_string())?;
+    if let Some(mut arrival) = arrival {
+        let resolved = match account {
+            Some(a) => Ok(a),
+            None => first_account(state).await,
+        };
+        match resolved {
+            Ok(a) => {
+                arrival.account = a;
+                arrival.order_id = order_id;
+                if let Err(e) = tca.record_arrival(arrival).await {
+                    tracing::warn!(order_id, error = %e, "place_order: arrival record failed");
+                }
+            }
+            Err(e) => tracing::warn!(order_id, error = %e, "place_order: arrival not recorded"),
+        }
     }
+    Ok(order_id)
 }
 
-/// Process-local monotonic counter so two intents recorded inside
-/// the same nanosecond resolution don't collide. The combination of
-/// `(unix_nanos, counter)` is unique per process; collisions across
-/// processes are impossible because intents are only generated by
-/// the running app, never by external clients.
-static INTENT_COUNTER: AtomicU64 = AtomicU64::new(0);
-
-fn gen_intent_id(setup_id: Option<i64>) -> String {
-    let prefix = match setup_id {
-        Some(id) => format!("intent_s{id}"),
-        None => "intent_manual".to_string(),
-    };
-    let nanos = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_nanos())
-        .unwrap_or(0);
-    let n = INTENT_COUNTER.fetch_add(1, Ordering::Relaxed);
-    format!("{prefix}_{nanos}_{n}")
-}
-
-/// Parses a `YYYY-MM-DD` string into a `NaiveDate`, returning a typed error
-/// for the Tauri command boundary. Extracted so it can be unit-tested without
-/// constructing a Tauri `State`.
-pub(crate) fn parse_date_arg(date: &str) -> Result<NaiveDate, String> {
-    NaiveDate::parse_from_str(date, "%Y-%m-%d")
-        .map_err(|e| format!("invalid date '{date}', expected YYYY-MM-DD: {e}"))
-}
-
-#[tauri::command]
-pub async fn ibkr_get_executions(
-    state: State<'_, IbkrState>,
-    date: String,
-) -> Result<Vec<IbkrExecution>, String> {
-    let parsed = parse_date_arg(&date)?;
+async fn first_account(state: &IbkrState) -> Result<String, String> {
     state
         .client
-        .executions(parsed)
+        .get_accounts()
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "no IBKR account available for intent recording".to_string())
 }
 
 #[cfg(test)]
@@ -150,108 +257,73 @@ mod tests {
             quantity: 100.0,
             order_type: ty,
             price: Some(100.0),
+            start_at: None,
+            algo: None,
+            route: None,
+            option: None,
         }
     }
 
     #[test]
-    fn build_intent_market_order_uses_short_window() {
-        let intent = build_intent(
-            "DU1",
-            &order(OrderAction::Buy, OrderType::Market),
-            None,
-            100.0,
-        );
-        let window = (intent.expires_at - intent.posted_at).num_minutes();
-        assert!(window <= 5);
-    }
+    fn session_gate_blocks_rth_only_orders_while_closed() {
+        use crate::ibkr::types::StartAt;
+        use chrono::TimeZone;
 
-    #[test]
-    fn build_intent_limit_order_uses_long_window() {
-        let intent = build_intent(
-            "DU1",
-            &order(OrderAction::Buy, OrderType::Limit),
-            None,
-            100.0,
-        );
-        let window = (intent.expires_at - intent.posted_at).num_minutes();
-        assert!(window >= 60);
-    }
+        // Saturday; then a Tuesday 10:00 EDT.
+        let closed = Utc.with_ymd_and_hms(2026, 5, 2, 15, 0, 0).unwrap();
+        let open = Utc.with_ymd_and_hms(2026, 4, 28, 14, 0, 0).unwrap();
+        let mid = order(OrderAction::Buy, OrderType::Midprice);
 
-    #[test]
-    fn build_intent_setup_linked_uses_trigger_price_source() {
-        let intent = build_intent(
-            "DU1",
-            &order(OrderAction::Buy, OrderType::Limit),
-            Some(42),
-            100.0,
-        );
-        assert_eq!(
-            intent.intended_price_source,
-            IntendedPriceSource::TriggerPrice
-        );
-        assert_eq!(intent.setup_id, Some(42));
-    }
+        let err = session_gate(&mid, closed).unwrap_err();
+        assert!(err.contains("2026-05-04 13:30 UTC"), "{err}");
+        assert!(session_gate(&mid, open).is_ok());
+        assert!(session_gate(&order(OrderAction::Buy, OrderType::Limit), closed).is_ok());
 
-    #[test]
-    fn build_intent_no_setup_limit_falls_to_limit_price_source() {
-        let intent = build_intent(
-            "DU1",
-            &order(OrderAction::Sell, OrderType::Limit),
-            None,
-            100.0,
-        );
-        assert_eq!(
-            intent.intended_price_source,
-            IntendedPriceSource::LimitPrice
-        );
-        assert_eq!(intent.side, IntentSide::Sell);
-    }
-
-    #[test]
-    fn build_intent_no_setup_market_falls_to_live_quote_source() {
-        let intent = build_intent(
-            "DU1",
-            &order(OrderAction::Buy, OrderType::Market),
-            None,
-            100.0,
-        );
-        assert_eq!(intent.intended_price_source, IntendedPriceSource::LiveQuote);
+        let held = OrderRequest {
+            start_at: Some(StartAt::MarketOpen),
+            ..mid.clone()
+        };
+        assert!(session_gate(&held, closed).is_ok());
+        let foreign = OrderRequest {
+            route: Some(ContractRoute {
+                exchange: Some("SMART".to_string()),
+                primary_exchange: Some("LSE".to_string()),
+                currency: Some("GBP".to_string()),
+            }),
+            ..mid
+        };
+        assert!(session_gate(&foreign, closed).is_ok());
     }
 
     #[test]
-    fn build_intent_rounds_intended_price_to_cents() {
-        let intent = build_intent(
-            "DU1",
-            &order(OrderAction::Buy, OrderType::Limit),
-            None,
-            100.504,
-        );
-        assert_eq!(intent.intended_price_cents, 10_050);
-        let intent = build_intent(
-            "DU1",
-            &order(OrderAction::Buy, OrderType::Limit),
-            None,
-            100.506,
+    fn check_order_reports_each_bad_field() {
+        assert_eq!(
+            check_order(&order(OrderAction::Buy, OrderType::Limit), Some(100.0)),
+            Ok(())
         );
-        assert_eq!(intent.intended_price_cents, 10_051);
-    }
-
-    #[test]
-    fn build_intent_clamps_negative_price_to_zero() {
-        let intent = build_intent(
-            "DU1",
-            &order(OrderAction::Buy, OrderType::Limit),
-            None,
-            -5.0,
+        let bad = OrderRequest {
+            symbol: " ".to_string(),
+            quantity: -5.0,
+            price: None,
+            ..order(OrderAction::Sell, OrderType::Limit)
+        };
+        let Err(CommandError::Invalid { fields }) = check_order(&bad, Some(0.0)) else {
+            panic!("expected field errors");
+        };
+        let names: Vec<_> = fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "order.symbol",
+                "order.quantity",
+                "order.price",
+                "intendedPrice"
+            ]
         );
-        assert_eq!(intent.intended_price_cents, 0);
-    }
-
-    #[test]
-    fn gen_intent_id_carries_setup_prefix() {
-        let with_setup = gen_intent_id(Some(7));
-        assert!(with_setup.starts_with("intent_s7_"));
-        let manual = gen_intent_id(None);
-        assert!(manual.starts_with("intent_manual_"));
+        let market = OrderRequest {
+            price: None,
+            ..order(OrderAction::Buy, OrderType::Market)
+        };
+        assert_eq!(check_order(&market, None), Ok(()));
     }
 }
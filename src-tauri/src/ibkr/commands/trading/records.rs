@@ -0,0 +1,256 @@
+//! The TCA rows an order leaves behind: the intent it answers and the
+//! quote it arrived at.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
+
+use crate::ibkr::types::{MarketDataSnapshot, OrderAction, OrderRequest, OrderType};
+use crate::services::tca::{ArrivalQuote, IntendedPriceSource, IntentSide, NewOrderIntent};
+
+/// The arrival row for `order`, less the account and order id the
+/// caller fills in once IBKR has accepted it.
+pub(super) fn build_arrival(
+    order: &OrderRequest,
+    snapshot: Option<&MarketDataSnapshot>,
+    placed_at: DateTime<Utc>,
+) -> ArrivalQuote {
+    let cents = |p: Option<f64>| {
+        p.filter(|p| p.is_finite() && *p > 0.0)
+            .map(|p| (p * 100.0).round() as i64)
+    };
+    ArrivalQuote {
+        account: String::new(),
+        order_id: 0,
+        symbol: order.symbol.clone(),
+        side: match order.action {
+            OrderAction::Buy => IntentSide::Buy,
+            OrderAction::Sell => IntentSide::Sell,
+        },
+        order_type: match order.order_type {
+            OrderType::Market => "market",
+            OrderType::Limit => "limit",
+            OrderType::Stop => "stop",
+            OrderType::StopLimit => "stop_limit",
+            OrderType::Midprice => "midprice",
+        }
+        .to_string(),
+        qty: order.quantity,
+        limit_price_cents: cents(order.price),
+        bid_cents: cents(snapshot.and_then(|s| s.bid_price)),
+        ask_cents: cents(snapshot.and_then(|s| s.ask_price)),
+        placed_at,
+    }
+}
+
+pub(super) fn build_intent(
+    account: &str,
+    order: &OrderRequest,
+    setup_id: Option<i64>,
+    intended_price: f64,
+) -> NewOrderIntent {
+    let now = Utc::now();
+    let is_market = matches!(order.order_type, OrderType::Market);
+    let window_minutes = if is_market { 5 } else { 60 };
+    let intended_cents = (intended_price * 100.0).round() as i64;
+    NewOrderIntent {
+        intent_id: gen_intent_id(setup_id),
+        setup_id,
+        account: account.to_string(),
+        symbol: order.symbol.clone(),
+        side: match order.action {
+            OrderAction::Buy => IntentSide::Buy,
+            OrderAction::Sell => IntentSide::Sell,
+        },
+        qty: order.quantity,
+        intended_price_cents: intended_cents.max(0),
+        // The UI passes `intended_price` after sourcing it from the
+        // setup trigger; when no setup, source is best-guess
+        // `LimitPrice` for limit orders, `LiveQuote` otherwise. The
+        // command itself is one layer above the source decision and
+        // can't tell the difference, so default conservatively.
+        intended_price_source: if setup_id.is_some() {
+            IntendedPriceSource::TriggerPrice
+        } else if matches!(order.order_type, OrderType::Limit) {
+            IntendedPriceSource::LimitPrice
+        } else {
+            IntendedPriceSource::LiveQuote
+        },
+        posted_at: now,
+        expires_at: now + chrono::Duration::minutes(window_minutes),
+    }
+}
+
+/// Process-local monotonic counter so two intents recorded inside
+/// the same nanosecond resolution don't collide. The combination of
+/// `(unix_nanos, counter)` is unique per process; collisions across
+/// processes are impossible because intents are only generated by
+/// the running app, never by external clients.
+static INTENT_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+pub(super) fn gen_intent_id(setup_id: Option<i64>) -> String {
+    let prefix = match setup_id {
+        Some(id) => format!("intent_s{id}"),
+        None => "intent_manual".to_string(),
+    };
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let n = INTENT_COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{prefix}_{nanos}_{n}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(action: OrderAction, ty: OrderType) -> OrderRequest {
+        OrderRequest {
+            symbol: "AAPL".to_string(),
+            action,
+            quantity: 100.0,
+            order_type: ty,
+            price: Some(100.0),
+            start_at: None,
+            algo: None,
+            route: None,
+            option: None,
+        }
+    }
+
+    #[test]
+    fn build_arrival_keeps_only_a_quoted_side() {
+        let snapshot = MarketDataSnapshot {
+            symbol: "AAPL".to_string(),
+            bid_price: Some(99.996),
+            bid_size: None,
+            ask_price: Some(-1.0),
+            ask_size: None,
+            last_price: None,
+            last_size: None,
+            high: None,
+            low: None,
+            volume: None,
+            close: None,
+            open: None,
+            last_rth_price: None,
+            session: None,
+            timestamp: 0,
+        };
+        let arrival = build_arrival(
+            &order(OrderAction::Sell, OrderType::StopLimit),
+            Some(&snapshot),
+            Utc::now(),
+        );
+        assert_eq!(arrival.side, IntentSide::Sell);
+        assert_eq!(arrival.order_type, "stop_limit");
+        assert_eq!(arrival.limit_price_cents, Some(10_000));
+        assert_eq!(arrival.bid_cents, Some(10_000));
+        assert_eq!(arrival.ask_cents, None);
+        assert_eq!(arrival.mid(), None);
+    }
+
+    #[test]
+    fn build_intent_market_order_uses_short_window() {
+        let intent = build_intent(
+            "DU1",
+            &order(OrderAction::Buy, OrderType::Market),
+            None,
+            100.0,
+        );
+        let window = (intent.expires_at - intent.posted_at).num_minutes();
+        assert!(window <= 5);
+    }
+
+    #[test]
+    fn build_intent_limit_order_uses_long_window() {
+        let intent = build_intent(
+            "DU1",
+            &order(OrderAction::Buy, OrderType::Limit),
+            None,
+            100.0,
+        );
+        let window = (intent.expires_at - intent.posted_at).num_minutes();
+        assert!(window >= 60);
+    }
+
+    #[test]
+    fn build_intent_setup_linked_uses_trigger_price_source() {
+        let intent = build_intent(
+            "DU1",
+            &order(OrderAction::Buy, OrderType::Limit),
+            Some(42),
+            100.0,
+        );
+        assert_eq!(
+            intent.intended_price_source,
+            IntendedPriceSource::TriggerPrice
+        );
+        assert_eq!(intent.setup_id, Some(42));
+    }
+
+    #[test]
+    fn build_intent_no_setup_limit_falls_to_limit_price_source() {
+        let intent = build_intent(
+            "DU1",
+            &order(OrderAction::Sell, OrderType::Limit),
+            None,
+            100.0,
+        );
+        assert_eq!(
+            intent.intended_price_source,
+            IntendedPriceSource::LimitPrice
+        );
+        assert_eq!(intent.side, IntentSide::Sell);
+    }
+
+    #[test]
+    fn build_intent_no_setup_market_falls_to_live_quote_source() {
+        let intent = build_intent(
+            "DU1",
+            &order(OrderAction::Buy, OrderType::Market),
+            None,
+            100.0,
+        );
+        assert_eq!(intent.intended_price_source, IntendedPriceSource::LiveQuote);
+    }
+
+    #[test]
+    fn build_intent_rounds_intended_price_to_cents() {
+        let intent = build_intent(
+            "DU1",
+            &order(OrderAction::Buy, OrderType::Limit),
+            None,
+            100.504,
+        );
+        assert_eq!(intent.intended_price_cents, 10_050);
+        let intent = build_intent(
+            "DU1",
+            &order(OrderAction::Buy, OrderType::Limit),
+            None,
+            100.506,
+        );
+        assert_eq!(intent.intended_price_cents, 10_051);
+    }
+
+    #[test]
+    fn build_intent_clamps_negative_price_to_zero() {
+        let intent = build_intent(
+            "DU1",
+            &order(OrderAction::Buy, OrderType::Limit),
+            None,
+            -5.0,
+        );
+        assert_eq!(intent.intended_price_cents, 0);
+    }
+
+    #[test]
+    fn gen_intent_id_carries_setup_prefix() {
+        let with_setup = gen_intent_id(Some(7));
+        assert!(with_setup.starts_with("intent_s7_"));
+        let manual = gen_intent_id(None);
+        assert!(manual.starts_with("intent_manual_"));
+    }
+}
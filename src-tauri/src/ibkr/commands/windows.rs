@@ -0,0 +1,91 @@
+//! Window registration for per-window event routing (see
+//! `events::windows`) and pop-out ticker windows.
+
+use std::collections::HashMap;
+
+use tauri::{AppHandle, Manager, State, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+use crate::events::WindowRoute;
+use crate::ibkr::state::IbkrState;
+use crate::middleware::validation::{CommandError, Inputs};
+
+/// Route events for the calling window: from now on it's only sent
+/// what `route` accepts.
+#[tauri::command]
+pub async fn register_window(
+    state: State<'_, IbkrState>,
+    window: WebviewWindow,
+    route: WindowRoute,
+) -> Result<(), String> {
+    state
+        .event_emitter
+        .route_window(window.label(), route)
+        .await;
+    Ok(())
+}
+
+/// Send the calling window every event again.
+#[tauri::command]
+pub async fn unregister_window(
+    state: State<'_, IbkrState>,
+    window: WebviewWindow,
+) -> Result<bool, String> {
+    Ok(state.event_emitter.unroute_window(window.label()).await)
+}
+
+/// Routes by window label.
+#[tauri::command]
+pub async fn list_window_routes(
+    state: State<'_, IbkrState>,
+) -> Result<HashMap<String, WindowRoute>, String> {
+    Ok(state.event_emitter.window_routes().await)
+}
+
+/// Open (or focus) a detached window for `symbol`, routed to that
+/// ticker's events. Returns the window label.
+#[tauri::command]
+pub async fn open_ticker_window(
+    app: AppHandle,
+    state: State<'_, IbkrState>,
+    symbol: String,
+) -> Result<String, CommandError> {
+    let mut inputs = Inputs::new();
+    let symbol = inputs.symbol("symbol", &symbol);
+    inputs.finish()?;
+    let label = ticker_window_label(&symbol);
+    if let Some(window) = app.get_webview_window(&label) {
+        window.set_focus().map_err(|e| e.to_string())?;
+        return Ok(label);
+    }
+    // Routed before the page loads so it never sees the full stream.
+    state
+        .event_emitter
+        .route_window(
+            &label,
+            WindowRoute {
+                events: Vec::new(),
+                symbols: vec![symbol.clone()],
+            },
+        )
+        .await;
+    let url = format!("index.html?window=ticker&symbol={symbol}");
+    let built = WebviewWindowBuilder::new(&app, &label, WebviewUrl::App(url.into()))
+        .title(format!("{symbol} — quantum-kapital"))
+        .inner_size(900.0, 600.0)
+        .build();
+    if let Err(e) = built {
+        state.event_emitter.unroute_window(&label).await;
+        return Err(e.to_string().into());
+    }
+    Ok(label)
+}
+
+/// Window labels allow `[A-Za-z0-9-/:_]`, so `BRK.B` becomes
+/// `ticker-BRK_B`.
+fn ticker_window_label(symbol: &str) -> String {
+    let safe: String = symbol
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("ticker-{safe}")
+}
@@ -24,6 +24,10 @@ pub enum IbkrError {
     #[error("Request timed out after {0}ms")]
     Timeout(u64),
 
+    /// Cancelled via `ibkr_cancel_request`.
+    #[error("Request cancelled")]
+    Cancelled,
+
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
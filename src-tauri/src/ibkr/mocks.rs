@@ -232,6 +232,8 @@ impl IbkrClientTrait for MockIbkrClient {
             volume: Some(1234567),
             close: Some(149.80),
             open: Some(149.00),
+            last_rth_price: None,
+            session: None,
             timestamp: chrono::Utc::now().timestamp(),
         })
     }
@@ -440,6 +442,10 @@ pub mod test_fixtures {
             quantity: 100.0,
             order_type: OrderType::Limit,
             price: Some(150.0),
+            start_at: None,
+            algo: None,
+            route: None,
+            option: None,
         }
     }
 
@@ -458,6 +464,8 @@ pub mod test_fixtures {
             volume: Some(1234567),
             close: Some(149.80),
             open: Some(149.00),
+            last_rth_price: None,
+            session: None,
             timestamp: 1704825600, // Fixed timestamp for testing
         }
     }
@@ -1,6 +1,7 @@
 pub mod client;
 pub mod commands;
 pub mod error;
+pub mod position_cache;
 pub mod state;
 pub mod types;
 
@@ -0,0 +1,140 @@
+//! Short-lived per-account cache in front of `IbkrClient::get_positions`.
+//!
+//! Every positions read is a full `account_updates` round-trip that
+//! holds the client's account-updates lock until the stream drains, and
+//! every window that shows the portfolio asks for positions on its own.
+//! [`PositionCache::get_or_refresh`] serves a snapshot younger than
+//! [`POSITION_CACHE_TTL`] as-is; when it is stale, the first caller
+//! refreshes it while concurrent callers for the same account wait on
+//! that refresh instead of starting their own.
+//! Failed refreshes are not cached.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use tokio::sync::Mutex as TokioMutex;
+use tokio::time::Instant;
+
+use crate::ibkr::types::Position;
+
+/// How long a positions snapshot is served before IBKR is asked again.
+pub const POSITION_CACHE_TTL: Duration = Duration::from_secs(5);
+
+struct Snapshot {
+    fetched_at: Instant,
+    positions: Vec<Position>,
+}
+
+type Slot = Arc<TokioMutex<Option<Snapshot>>>;
+
+pub struct PositionCache {
+    ttl: Duration,
+    slots: StdMutex<HashMap<String, Slot>>,
+}
+
+impl Default for PositionCache {
+    fn default() -> Self {
+        Self::new(POSITION_CACHE_TTL)
+    }
+}
+
+impl PositionCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            slots: StdMutex::new(HashMap::new()),
+        }
+    }
+
+    /// Positions for `account`: the cached snapshot when fresh,
+    /// otherwise whatever `fetch` returns, which is then cached.
+    pub async fn get_or_refresh<F, Fut, E>(
+        &self,
+        account: &str,
+        fetch: F,
+    ) -> Result<Vec<Position>, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Vec<Position>, E>>,
+    {
+        let slot = self.slot(account);
+        // Held across the fetch: a concurrent caller queues here and
+        // finds the snapshot this refresh stores.
+        let mut snapshot = slot.lock().await;
+        if let Some(cached) = snapshot.as_ref() {
+            if cached.fetched_at.elapsed() < self.ttl {
+                return Ok(cached.positions.clone());
+            }
+        }
+        let positions = fetch().await?;
+        *snapshot = Some(Snapshot {
+            fetched_at: Instant::now(),
+            positions: positions.clone(),
+        });
+        Ok(positions)
+    }
+
+    /// Drop every snapshot, e.g. on disconnect.
+    pub fn clear(&self) {
+        self.slots.lock().expect("position cache poisoned").clear();
+    }
+
+    fn slot(&self, account: &str) -> Slot {
+        let mut slots = self.slots.lock().expect("position cache poisoned");
+        Arc::clone(slots.entry(account.to_string()).or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn position(symbol: &str) -> Position {
+        Position {
+            account: "DU1".to_string(),
+            symbol: symbol.to_string(),
+            ..Position::default()
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn concurrent_reads_share_one_refresh_until_stale() {
+        let cache = PositionCache::new(Duration::from_secs(5));
+        let fetches = AtomicUsize::new(0);
+        let fetch = || async {
+            fetches.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok::<_, String>(vec![position("AAPL")])
+        };
+
+        let (a, b) = tokio::join!(
+            cache.get_or_refresh("DU1", fetch),
+            cache.get_or_refresh("DU1", fetch)
+        );
+        assert_eq!(a.unwrap()[0].symbol, "AAPL");
+        assert_eq!(b.unwrap().len(), 1);
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        cache.get_or_refresh("DU1", fetch).await.unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        tokio::time::advance(Duration::from_secs(6)).await;
+        cache.get_or_refresh("DU1", fetch).await.unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        let failed = cache
+            .get_or_refresh("DU2", || async { Err("not connected".to_string()) })
+            .await;
+        assert!(failed.is_err());
+        cache.get_or_refresh("DU2", fetch).await.unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 3);
+
+        cache.clear();
+        cache.get_or_refresh("DU1", fetch).await.unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 4);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::events::{AppEvent, EventEmitter};
 use crate::ibkr::client::{IbkrClient, StreamHandle};
+use crate::ibkr::position_cache::PositionCache;
 use crate::ibkr::types::{ConnectionConfig, DataTier, ScannerSubscription};
 use crate::services::auto_scanner::AutoScannerScheduler;
 use crate::services::eod_scheduler::EodScheduler;
@@ -25,6 +26,11 @@ pub struct IbkrState {
     /// the handle here so the accept loop stays alive for the app's
     /// lifetime. Stopped implicitly when `IbkrState` is dropped.
     pub mcp_handle: Arc<RwLock<Option<StreamHandle>>>,
+    /// Optional localhost HTTP API listener (`http_api/`). `None` unless
+    /// `http_api.enabled` is set and the server bound successfully.
+    pub http_api_handle: Arc<RwLock<Option<StreamHandle>>>,
+    /// Connection heartbeat loop (`services/connection_health`).
+    pub heartbeat_handle: Arc<RwLock<Option<StreamHandle>>>,
     /// Quant-decisions Phase 7 — `BracketReviser` poll loop handle.
     /// `lib.rs::run` spawns the reviser once `OrderTicket` is wired
     /// and stores the handle here. Stopped implicitly on drop.
@@ -36,6 +42,9 @@ pub struct IbkrState {
     /// value here (via the wired tier sink) and `disconnect` resets to
     /// `Unknown`.
     pub data_tier: Arc<RwLock<DataTier>>,
+    /// Positions snapshots behind `ibkr_get_positions`, so repeated
+    /// reads inside a few seconds don't each drain `account_updates`.
+    pub positions: Arc<PositionCache>,
 }
 
 impl IbkrState {
@@ -61,10 +70,13 @@ impl IbkrState {
             intraday_handle: Arc::new(RwLock::new(None)),
             auto_scanner_handle: Arc::new(RwLock::new(None)),
             mcp_handle: Arc::new(RwLock::new(None)),
+            http_api_handle: Arc::new(RwLock::new(None)),
+            heartbeat_handle: Arc::new(RwLock::new(None)),
             bracket_reviser_handle: Arc::new(RwLock::new(None)),
             tracker,
             state_machine,
             data_tier,
+            positions: Arc::new(PositionCache::default()),
         }
     }
 
@@ -164,6 +176,28 @@ impl IbkrState {
         }
     }
 
+    /// Stop every stream, scheduler and listener this state owns, in
+    /// field order. Used on app exit.
+    pub async fn stop_all(&self) {
+        let slots = [
+            &self.daily_pnl_handle,
+            &self.scanner_handle,
+            &self.eod_handle,
+            &self.intraday_handle,
+            &self.auto_scanner_handle,
+            &self.mcp_handle,
+            &self.http_api_handle,
+            &self.heartbeat_handle,
+            &self.bracket_reviser_handle,
+        ];
+        for slot in slots {
+            let handle = slot.write().await.take();
+            if let Some(handle) = handle {
+                handle.stop().await;
+            }
+        }
+    }
+
     pub async fn update_connection_status(&self, connected: bool) {
         let _ = self
             .event_emitter
@@ -1,7 +1,8 @@
-use crate::ibkr::commands::trading::parse_date_arg;
+use crate::ibkr::commands::executions::parse_date_arg;
 use crate::ibkr::error::IbkrError;
 use crate::ibkr::mocks::{test_fixtures, IbkrClientTrait, MockIbkrClient};
 use crate::ibkr::types::*;
+use crate::middleware::validation::CommandError;
 use chrono::{NaiveDate, TimeZone, Utc};
 use chrono_tz::America::New_York;
 
@@ -115,6 +116,10 @@ async fn test_command_order_validation() {
         quantity: 100.0,
         order_type: OrderType::Limit,
         price: Some(150.0),
+        start_at: None,
+        algo: None,
+        route: None,
+        option: None,
     };
 
     let result = client.place_order(valid_order).await;
@@ -127,6 +132,10 @@ async fn test_command_order_validation() {
         quantity: 50.0,
         order_type: OrderType::Market,
         price: None,
+        start_at: None,
+        algo: None,
+        route: None,
+        option: None,
     };
 
     let result = client.place_order(market_order).await;
@@ -323,21 +332,19 @@ async fn executions_disconnected_returns_not_connected() {
 
 #[test]
 fn command_parses_correct_date() {
-    let parsed = parse_date_arg("2026-04-29").unwrap();
+    let parsed = parse_date_arg("date", "2026-04-29").unwrap();
     assert_eq!(parsed, NaiveDate::from_ymd_opt(2026, 4, 29).unwrap());
 }
 
 #[test]
 fn command_rejects_malformed_date() {
-    // Wrong separator
-    let err = parse_date_arg("2026/04/29").unwrap_err();
-    assert!(err.contains("invalid date"));
-
-    // Out-of-range month
-    let err = parse_date_arg("2026-13-01").unwrap_err();
-    assert!(err.contains("invalid date"));
-
-    // Empty string
-    let err = parse_date_arg("").unwrap_err();
-    assert!(err.contains("invalid date"));
+    // Wrong separator, out-of-range month, empty string.
+    for bad in ["2026/04/29", "2026-13-01", ""] {
+        let err = parse_date_arg("date", bad).unwrap_err();
+        let CommandError::Invalid { fields } = err else {
+            panic!("expected a field error for {bad:?}");
+        };
+        assert_eq!(fields[0].field, "date");
+        assert!(fields[0].message.contains("YYYY-MM-DD"));
+    }
 }
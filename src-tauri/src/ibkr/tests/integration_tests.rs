@@ -60,6 +60,10 @@ async fn test_full_trading_session_flow() {
         quantity: 10.0,
         order_type: OrderType::Limit,
         price: Some(200.0),
+        start_at: None,
+        algo: None,
+        route: None,
+        option: None,
     };
 
     let order_id = client.place_order(order).await.unwrap();
@@ -177,6 +181,10 @@ async fn test_order_types_handling() {
         quantity: 100.0,
         order_type: OrderType::Market,
         price: None,
+        start_at: None,
+        algo: None,
+        route: None,
+        option: None,
     };
     let result = client.place_order(market_order).await;
     assert!(result.is_ok());
@@ -188,6 +196,10 @@ async fn test_order_types_handling() {
         quantity: 50.0,
         order_type: OrderType::Limit,
         price: Some(155.0),
+        start_at: None,
+        algo: None,
+        route: None,
+        option: None,
     };
     let result = client.place_order(limit_order).await;
     assert!(result.is_ok());
@@ -199,6 +211,10 @@ async fn test_order_types_handling() {
         quantity: 100.0,
         order_type: OrderType::Stop,
         price: Some(145.0),
+        start_at: None,
+        algo: None,
+        route: None,
+        option: None,
     };
     let result = client.place_order(stop_order).await;
     assert!(result.is_ok());
@@ -2,13 +2,16 @@
 // for backward compatibility and convenience
 
 pub mod account;
+pub mod combo;
 pub mod connection;
 pub mod data_tier;
 pub mod fundamentals;
 pub mod market_data;
+pub mod options;
 pub mod orders;
 pub mod positions;
 pub mod quote;
+pub mod what_if;
 
 pub mod historical;
 pub mod news;
@@ -17,13 +20,16 @@ pub mod tracker;
 
 // Re-export all types at the root level for backward compatibility
 pub use account::*;
+pub use combo::*;
 pub use connection::*;
 pub use data_tier::*;
 pub use fundamentals::*;
 pub use market_data::*;
+pub use options::*;
 pub use orders::*;
 pub use positions::*;
 pub use quote::*;
+pub use what_if::*;
 
 #[allow(unused_imports)]
 pub use historical::*;
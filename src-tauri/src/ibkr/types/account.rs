@@ -1,6 +1,7 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AccountSummary {
     pub account: String,
     pub tag: String,
@@ -8,8 +9,15 @@ pub struct AccountSummary {
     pub currency: String,
 }
 
+/// An IBKR account id with its alias from `config.accounts`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AccountInfo {
+    pub id: String,
+    pub alias: Option<String>,
+}
+
 #[cfg(test)]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AccountValue {
     pub key: String,
     pub value: String,
@@ -0,0 +1,241 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{OptionContract, OrderAction, OrderType};
+
+/// One option leg of a combo. `action` is the leg's side when the
+/// combo is bought; selling the combo reverses every leg.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ComboLeg {
+    pub option: OptionContract,
+    pub action: OrderAction,
+    #[serde(default = "default_ratio")]
+    pub ratio: u32,
+}
+
+fn default_ratio() -> u32 {
+    1
+}
+
+/// Two-leg option spread on one underlying, sent to IBKR as a `BAG`
+/// contract so both legs fill together.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ComboOrderRequest {
+    /// Underlying; every leg must be on it.
+    pub symbol: String,
+    pub legs: Vec<ComboLeg>,
+    /// `Buy` pays `price` (net debit) for the legs as given; `Sell`
+    /// collects it (net credit) with every leg reversed.
+    pub action: OrderAction,
+    /// Number of spreads.
+    pub quantity: f64,
+    /// `Market` or `Limit`.
+    pub order_type: OrderType,
+    /// Net price per spread, per share of underlying.
+    pub price: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ComboKind {
+    /// Same expiry, different strikes.
+    Vertical,
+    /// Same strike, different expiries.
+    Calendar,
+    /// Different strike and expiry.
+    Diagonal,
+}
+
+impl ComboOrderRequest {
+    /// Shape check before anything is sent: two legs on `symbol` with
+    /// the same right and ratio, one bought and one sold, on different
+    /// contracts; whole quantity; a positive limit price that, for a
+    /// vertical, stays inside the strike width (a spread can't be
+    /// worth more than that).
+    pub fn validate(&self) -> Result<ComboKind, String> {
+        let symbol = self.symbol.trim();
+        if symbol.is_empty() {
+            return Err("symbol is required".into());
+        }
+        let [a, b] = self.legs.as_slice() else {
+            return Err(format!("a spread has 2 legs, {} given", self.legs.len()));
+        };
+        for leg in [a, b] {
+            if !leg.option.symbol.trim().eq_ignore_ascii_case(symbol) {
+                return Err(format!(
+                    "leg on {} doesn't match underlying {symbol}",
+                    leg.option.symbol
+                ));
+            }
+        }
+        if a.option.right != b.option.right {
+            return Err("legs must both be calls or both be puts".into());
+        }
+        if a.ratio == 0 || a.ratio != b.ratio {
+            return Err("legs must have the same non-zero ratio".into());
+        }
+        if matches!(
+            (&a.action, &b.action),
+            (OrderAction::Buy, OrderAction::Buy) | (OrderAction::Sell, OrderAction::Sell)
+        ) {
+            return Err("one leg must be bought and the other sold".into());
+        }
+        let same_expiry = a.option.expiry.trim() == b.option.expiry.trim();
+        let same_strike = (a.option.strike - b.option.strike).abs() < 1e-9;
+        let kind = match (same_expiry, same_strike) {
+            (true, true) => return Err("legs are the same contract".into()),
+            (true, false) => ComboKind::Vertical,
+            (false, true) => ComboKind::Calendar,
+            (false, false) => ComboKind::Diagonal,
+        };
+
+        if self.quantity <= 0.0 || self.quantity.fract().abs() > 1e-9 {
+            return Err(format!(
+                "quantity must be a whole number of spreads, got {}",
+                self.quantity
+            ));
+        }
+        match self.order_type {
+            OrderType::Market => {}
+            OrderType::Limit => {
+                let price = self
+                    .price
+                    .filter(|p| *p > 0.0)
+                    .ok_or("a limit combo needs a positive net price")?;
+                let width = (a.option.strike - b.option.strike).abs();
+                if kind == ComboKind::Vertical && price > width {
+                    return Err(format!(
+                        "net price {price} exceeds the {width} strike width"
+                    ));
+                }
+            }
+            _ => return Err("combos take Market or Limit orders".into()),
+        }
+        Ok(kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ibkr::types::OptionRight;
+
+    fn leg(expiry: &str, strike: f64, right: OptionRight, action: OrderAction) -> ComboLeg {
+        ComboLeg {
+            option: OptionContract {
+                symbol: "SPY".to_string(),
+                expiry: expiry.to_string(),
+                strike,
+                right,
+                multiplier: None,
+                currency: None,
+            },
+            action,
+            ratio: 1,
+        }
+    }
+
+    fn combo(legs: Vec<ComboLeg>, price: f64) -> ComboOrderRequest {
+        ComboOrderRequest {
+            symbol: "spy".to_string(),
+            legs,
+            action: OrderAction::Buy,
+            quantity: 2.0,
+            order_type: OrderType::Limit,
+            price: Some(price),
+        }
+    }
+
+    #[test]
+    fn classifies_verticals_calendars_and_diagonals() {
+        use OptionRight::Call;
+        let vertical = combo(
+            vec![
+                leg("20261120", 500.0, Call, OrderAction::Buy),
+                leg("20261120", 505.0, Call, OrderAction::Sell),
+            ],
+            2.1,
+        );
+        assert_eq!(vertical.validate(), Ok(ComboKind::Vertical));
+        let calendar = combo(
+            vec![
+                leg("20261120", 500.0, Call, OrderAction::Sell),
+                leg("20261218", 500.0, Call, OrderAction::Buy),
+            ],
+            7.5,
+        );
+        assert_eq!(calendar.validate(), Ok(ComboKind::Calendar));
+        let diagonal = combo(
+            vec![
+                leg("20261120", 510.0, Call, OrderAction::Sell),
+                leg("20261218", 500.0, Call, OrderAction::Buy),
+            ],
+            12.0,
+        );
+        assert_eq!(diagonal.validate(), Ok(ComboKind::Diagonal));
+    }
+
+    #[test]
+    fn rejects_legs_that_dont_net_to_a_spread() {
+        use OptionRight::{Call, Put};
+        let reject = |legs: Vec<ComboLeg>, price: f64, needle: &str| {
+            let err = combo(legs, price).validate().unwrap_err();
+            assert!(err.contains(needle), "{err}");
+        };
+        reject(
+            vec![leg("20261120", 500.0, Call, OrderAction::Buy)],
+            1.0,
+            "2 legs",
+        );
+        reject(
+            vec![
+                leg("20261120", 500.0, Call, OrderAction::Buy),
+                leg("20261120", 505.0, Put, OrderAction::Sell),
+            ],
+            1.0,
+            "both be calls",
+        );
+        reject(
+            vec![
+                leg("20261120", 500.0, Call, OrderAction::Buy),
+                leg("20261120", 505.0, Call, OrderAction::Buy),
+            ],
+            1.0,
+            "one leg must be bought",
+        );
+        reject(
+            vec![
+                leg("20261120", 500.0, Call, OrderAction::Buy),
+                leg("20261120", 500.0, Call, OrderAction::Sell),
+            ],
+            1.0,
+            "same contract",
+        );
+        reject(
+            vec![
+                leg("20261120", 500.0, Call, OrderAction::Buy),
+                leg("20261120", 505.0, Call, OrderAction::Sell),
+            ],
+            5.5,
+            "strike width",
+        );
+
+        let mut other = leg("20261120", 505.0, Call, OrderAction::Sell);
+        other.option.symbol = "QQQ".to_string();
+        reject(
+            vec![leg("20261120", 500.0, Call, OrderAction::Buy), other],
+            1.0,
+            "doesn't match",
+        );
+
+        let mut fractional = combo(
+            vec![
+                leg("20261120", 500.0, Call, OrderAction::Buy),
+                leg("20261120", 505.0, Call, OrderAction::Sell),
+            ],
+            1.0,
+        );
+        fractional.quantity = 1.5;
+        assert!(fractional.validate().unwrap_err().contains("whole number"));
+    }
+}
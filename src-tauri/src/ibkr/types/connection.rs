@@ -1,4 +1,5 @@
 use crate::config::IbkrConfig;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// IBKR market data type. Mirrors `ibapi::market_data::MarketDataType`.
@@ -6,7 +7,7 @@ use serde::{Deserialize, Serialize};
 /// Defaults to `DelayedFrozen` so accounts without real-time subscriptions still
 /// receive ticks during RTH (delayed) and the last frozen quote outside RTH
 /// (weekends, after-hours) instead of silent 5s snapshot timeouts.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum MarketDataType {
     Live,
@@ -27,7 +28,7 @@ impl From<MarketDataType> for ibapi::market_data::MarketDataType {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConnectionConfig {
     pub host: String,
     pub port: u16,
@@ -58,7 +59,7 @@ impl From<IbkrConfig> for ConnectionConfig {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConnectionStatus {
     pub connected: bool,
     pub server_time: Option<String>,
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Empirically detected market-data capability for the active IBKR
@@ -7,7 +8,7 @@ use serde::{Deserialize, Serialize};
 /// Distinct from `MarketDataType` (the *configured* mode the client
 /// requests): a paper account configured as `Live` may still only get
 /// `Delayed` ticks, and that fact only shows up in the data stream.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum DataTier {
     /// Probe has not run, has not finished, or has been reset by a
@@ -2,7 +2,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Financial projection for a single year
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FinancialProjection {
     pub year: u32,
@@ -23,10 +23,24 @@ pub struct FinancialProjection {
     pub ps_high_est: Option<f64>, // Price-to-Sales high (if P/S used)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub analyst_eps_estimate: Option<f64>, // Analyst consensus EPS estimate (if available)
+    /// Segment revenues `revenue` rolled up from, when the projection
+    /// ran by segment.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<ProjectedSegment>,
+}
+
+/// One segment's revenue in a projected year.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectedSegment {
+    pub name: String,
+    pub revenue: f64,
+    /// Percent over the segment's prior year.
+    pub revenue_growth: f64,
 }
 
 /// CAGR (Compound Annual Growth Rate) calculations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CagrMetrics {
     pub revenue: f64,     // percentage
@@ -34,7 +48,7 @@ pub struct CagrMetrics {
 }
 
 /// Projections for a single year with bear/base/bull scenarios
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct YearlyProjection {
     pub year: u32,
@@ -44,27 +58,53 @@ pub struct YearlyProjection {
 }
 
 /// Complete projection results with baseline and forward projections
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectionResults {
     pub baseline: FinancialProjection, // Most recent complete year (actual data)
     pub projections: Vec<YearlyProjection>, // Future years with bear/base/bull scenarios
     pub cagr: ScenarioCagr,            // CAGR for each scenario
+    /// Probability-weighted outcome; `None` with no projected years (and
+    /// on snapshots stored before scenario weights existed).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<ExpectedValue>,
+}
+
+/// Scenario outcomes weighted by `ProjectionAssumptions`'s
+/// `*_probability`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpectedValue {
+    /// Final projected year the target is for.
+    pub year: u32,
+    /// Σ probability × midpoint of the scenario's price range.
+    pub target_price: f64,
+    /// Σ probability × scenario CAGR.
+    pub cagr: CagrMetrics,
 }
 
 /// Bundled response for `ibkr_generate_projection_results`. Returning
 /// fundamentals + projection results in a single Tauri call lets the UI
 /// hook collapse to one request instead of fetching fundamentals twice
 /// (once for display, once internally for the projection inputs).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectionResultsWithFundamentals {
     pub fundamentals: FundamentalData,
     pub results: ProjectionResults,
+    /// Targets discounted to today; see `services::valuation`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valuation: Option<crate::services::valuation::DiscountedValuation>,
+    /// Projection template the assumptions came from, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Per-year calculation trace, when asked for with `include_trace`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<crate::services::projection_service::ProjectionTrace>,
 }
 
 /// Same idea for the deprecated `ibkr_generate_projections` command.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ScenarioProjectionsWithFundamentals {
     pub fundamentals: FundamentalData,
@@ -72,7 +112,7 @@ pub struct ScenarioProjectionsWithFundamentals {
 }
 
 /// Complete scenario projections (Bear/Base/Bull) - DEPRECATED, use ProjectionResults
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScenarioProjections {
     pub bear: Vec<FinancialProjection>,
     pub base: Vec<FinancialProjection>,
@@ -80,7 +120,7 @@ pub struct ScenarioProjections {
     pub cagr: ScenarioCagr,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ScenarioCagr {
     pub bear: CagrMetrics,
@@ -96,6 +136,26 @@ pub struct HistoricalFinancial {
     pub revenue: f64,
     pub net_income: f64,
     pub eps: f64,
+    /// Cumulative split factor `eps` was divided by to put it on today's
+    /// share basis; `None` when the figure is as-reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split_adjustment: Option<f64>,
+}
+
+/// One reported business segment's revenue history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RevenueSegment {
+    pub name: String,
+    pub revenue: Vec<SegmentRevenue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentRevenue {
+    pub year: u32,
+    /// Billions, like `HistoricalFinancial::revenue`.
+    pub revenue: f64,
 }
 
 /// Analyst estimate for a specific metric
@@ -112,8 +172,122 @@ pub struct AnalystEstimate {
 pub struct FundamentalData {
     pub symbol: String,
     pub historical: Vec<HistoricalFinancial>,
+    /// Revenue by reported business segment, from a manual record or
+    /// an override. Empty when none was entered.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<RevenueSegment>,
     pub analyst_estimates: Option<AnalystEstimates>,
     pub current_metrics: CurrentMetrics,
+    /// Anomalies found by `services::fundamentals_quality::validate`.
+    /// Empty (and omitted) when the inputs look sane or were never
+    /// validated.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub data_quality: Vec<DataQualityWarning>,
+    /// Fields replaced by an operator override (see
+    /// `services::fundamentals_overrides`). Empty when the record is
+    /// exactly what the provider returned.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<OverriddenField>,
+    /// Growth decomposition of `historical`, filled by
+    /// `services::fundamentals_growth::annotate`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub growth: Option<GrowthAnalysis>,
+    /// ISO 4217 code of every monetary figure in the record; `None`
+    /// means USD (pre-currency records, manual rows that omit it).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Set when `services::fx_service::convert` moved the record out of
+    /// its reporting currency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fx_conversion: Option<FxConversion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FxConversion {
+    /// Reporting currency the provider returned.
+    pub from: String,
+    /// Units of `FundamentalData::currency` per unit of `from`.
+    pub rate: f64,
+}
+
+/// Year-over-year growth, margin trend and trailing CAGRs derived from
+/// the historical series. All figures are percentages (35.0 = 35%);
+/// `None` where the math is undefined (no prior year, a non-positive
+/// base, a gap in the series).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GrowthAnalysis {
+    /// One row per historical year, ascending.
+    pub years: Vec<YearGrowth>,
+    /// Trailing CAGRs ending at the latest year, one per window.
+    pub cagr: Vec<HistoricalCagr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct YearGrowth {
+    pub year: u32,
+    pub revenue_growth: Option<f64>,
+    pub net_income_growth: Option<f64>,
+    pub eps_growth: Option<f64>,
+    /// Net income over revenue.
+    pub net_margin: Option<f64>,
+    /// Change in `net_margin` from the prior year, in percentage points.
+    pub margin_change: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricalCagr {
+    /// Window length in years (3 = latest year vs. three years before).
+    pub years: u32,
+    pub revenue: Option<f64>,
+    pub net_income: Option<f64>,
+    pub eps: Option<f64>,
+}
+
+/// One field in a [`FundamentalData`] record that an override replaced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OverriddenField {
+    /// `revenue` | `net_income` | `eps` | `shares_outstanding` | `price`.
+    pub field: String,
+    /// Fiscal year for the per-year fields; `None` for current metrics.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<u32>,
+    /// What the provider said, if it said anything.
+    pub provider_value: Option<f64>,
+    pub value: f64,
+}
+
+/// One anomaly in a [`FundamentalData`] record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DataQualityWarning {
+    pub code: DataQualityCode,
+    pub severity: DataQualitySeverity,
+    /// Fiscal year the anomaly points at, when it is year-specific.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<u32>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DataQualityCode {
+    RevenueJump,
+    NegativeSharesOutstanding,
+    MissingYears,
+}
+
+/// `Error` makes projections refuse the record; `Warning` is shown
+/// alongside the numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DataQualitySeverity {
+    Warning,
+    Error,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -143,10 +317,34 @@ pub struct CurrentMetrics {
     pub market_cap: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dividend_yield: Option<f64>,
+    /// Latest exchange-reported short interest, laid over by
+    /// `services::short_interest`; never part of a provider payload.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_interest: Option<ShortInterest>,
+}
+
+/// Short position as of one FINRA settlement date (reported twice a
+/// month, published about a week later).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortInterest {
+    /// `YYYY-MM-DD`.
+    pub settlement_date: String,
+    pub shares_short: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_shares_short: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_daily_volume: Option<f64>,
+    /// Shares short over average daily volume.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_to_cover: Option<f64>,
+    /// Percent of shares outstanding (no float figure is available).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percent_of_shares: Option<f64>,
 }
 
 /// Assumptions for generating projections
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectionAssumptions {
     pub years: u32,               // number of years to project (default 5)
@@ -161,6 +359,60 @@ pub struct ProjectionAssumptions {
     pub ps_low: f64,              // Price-to-Sales low estimate (used when EPS < 0)
     pub ps_high: f64,             // Price-to-Sales high estimate (used when EPS < 0)
     pub shares_growth: f64,       // annual change in shares (negative for buybacks)
+    /// Scenario probabilities in percent; each non-negative, together
+    /// summing to 100.
+    #[serde(default = "default_bear_probability")]
+    pub bear_probability: f64,
+    #[serde(default = "default_base_probability")]
+    pub base_probability: f64,
+    #[serde(default = "default_bull_probability")]
+    pub bull_probability: f64,
+    /// Per-segment revenue growth. When set and the baseline year has
+    /// segment revenue, revenue is projected segment by segment and
+    /// summed; see `services::projection_service::segments`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segment_growth: Vec<SegmentGrowth>,
+}
+
+/// Revenue growth for one segment, in percent per year like the
+/// scenario-wide `*_revenue_growth`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentGrowth {
+    /// Matches `RevenueSegment::name`, ignoring case.
+    pub segment: String,
+    pub bear_revenue_growth: f64,
+    pub base_revenue_growth: f64,
+    pub bull_revenue_growth: f64,
+}
+
+fn default_bear_probability() -> f64 {
+    25.0
+}
+
+fn default_base_probability() -> f64 {
+    50.0
+}
+
+fn default_bull_probability() -> f64 {
+    25.0
+}
+
+impl ProjectionAssumptions {
+    /// Why the scenario probabilities are unusable, if they are.
+    pub fn probability_error(&self) -> Option<String> {
+        let weights = [
+            self.bear_probability,
+            self.base_probability,
+            self.bull_probability,
+        ];
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Some("scenario probabilities must not be negative".to_string());
+        }
+        let total: f64 = weights.iter().sum();
+        ((total - 100.0).abs() > 0.01)
+            .then(|| format!("scenario probabilities must sum to 100% (got {total}%)"))
+    }
 }
 
 impl Default for ProjectionAssumptions {
@@ -178,6 +430,10 @@ impl Default for ProjectionAssumptions {
             ps_low: 3.0,  // Conservative P/S for unprofitable companies
             ps_high: 8.0, // Optimistic P/S for high-growth companies
             shares_growth: 0.0,
+            bear_probability: default_bear_probability(),
+            base_probability: default_base_probability(),
+            bull_probability: default_bull_probability(),
+            segment_growth: Vec::new(),
         }
     }
 }
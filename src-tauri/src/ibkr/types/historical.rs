@@ -1,6 +1,7 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct HistoricalDataRequest {
     pub symbol: String,
     pub end_date_time: String,
@@ -10,7 +11,7 @@ pub struct HistoricalDataRequest {
     pub use_rth: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub enum BarSize {
     Sec1,
     Sec5,
@@ -74,7 +75,7 @@ impl BarSize {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub enum WhatToShow {
     Trades,
     Midpoint,
@@ -85,7 +86,22 @@ pub enum WhatToShow {
     OptionImpliedVolatility,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+impl WhatToShow {
+    /// Stable string used for SQLite storage keys, like [`BarSize::as_str`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WhatToShow::Trades => "trades",
+            WhatToShow::Midpoint => "midpoint",
+            WhatToShow::Bid => "bid",
+            WhatToShow::Ask => "ask",
+            WhatToShow::BidAsk => "bid_ask",
+            WhatToShow::HistoricalVolatility => "historical_volatility",
+            WhatToShow::OptionImpliedVolatility => "option_implied_volatility",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct HistoricalBar {
     pub time: String,
     pub open: f64,
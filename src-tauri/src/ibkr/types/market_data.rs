@@ -1,6 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::utils::market_calendar::TradingSession;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MarketDataSnapshot {
     pub symbol: String,
     pub bid_price: Option<f64>,
@@ -14,5 +17,31 @@ pub struct MarketDataSnapshot {
     pub volume: Option<i64>,
     pub close: Option<f64>,
     pub open: Option<f64>,
+    /// Last trade of the regular session (generic tick 318). Outside
+    /// regular hours `last_price` is an extended-hours trade; this is
+    /// the price alerts and P&L should compare against.
+    #[serde(default)]
+    pub last_rth_price: Option<f64>,
+    /// Trading session of the snapshot's venue when it completed;
+    /// `None` for a venue without a known calendar.
+    #[serde(default)]
+    pub session: Option<TradingSession>,
     pub timestamp: i64,
 }
+
+/// TWS model greeks for one option contract (tick type
+/// `ModelOption` / `DelayedModelOption`). Per contract, per unit of
+/// underlying: multiply by quantity × multiplier for the position.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionGreeks {
+    pub implied_volatility: Option<f64>,
+    pub delta: Option<f64>,
+    pub gamma: Option<f64>,
+    /// Per calendar day.
+    pub theta: Option<f64>,
+    /// Per 1 vol point.
+    pub vega: Option<f64>,
+    pub option_price: Option<f64>,
+    pub underlying_price: Option<f64>,
+}
@@ -1,7 +1,8 @@
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct NewsItem {
     pub time_published: DateTime<Utc>,
     pub title: String,
@@ -13,7 +14,7 @@ pub struct NewsItem {
     pub ticker_sentiment: Vec<TickerSentiment>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct TickerSentiment {
     pub ticker: String,
     pub relevance_score: f64,
@@ -21,7 +22,7 @@ pub struct TickerSentiment {
     pub ticker_sentiment_label: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum NewsTone {
     Bullish,
@@ -52,7 +53,7 @@ impl NewsTone {
 /// LLM-derived per-symbol news classification. Persisted in
 /// `news_cache.news_verdict_json` and consumed by the EP detector to
 /// disambiguate sentiment polarity.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct NewsVerdict {
     pub tone: NewsTone,
     pub ep_worthy: bool,
@@ -0,0 +1,74 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::Position;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum OptionRight {
+    #[serde(rename = "C")]
+    Call,
+    #[serde(rename = "P")]
+    Put,
+}
+
+impl OptionRight {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Call => "C",
+            Self::Put => "P",
+        }
+    }
+}
+
+/// One listed option, enough to resolve the contract on SMART.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionContract {
+    pub symbol: String,
+    /// `YYYYMMDD`.
+    pub expiry: String,
+    pub strike: f64,
+    pub right: OptionRight,
+    /// `None` lets IBKR pick (100 for standard equity options).
+    #[serde(default)]
+    pub multiplier: Option<String>,
+    /// `None` is USD.
+    #[serde(default)]
+    pub currency: Option<String>,
+}
+
+impl OptionContract {
+    /// The contract behind an `OPT` position; `None` for anything else
+    /// or when IBKR left out expiry, strike or right.
+    pub fn from_position(position: &Position) -> Option<Self> {
+        if position.contract_type != "OPT" {
+            return None;
+        }
+        // IBKR sometimes reports `CALL` / `PUT`.
+        let right = match position.right.as_deref()?.chars().next()? {
+            'C' | 'c' => OptionRight::Call,
+            'P' | 'p' => OptionRight::Put,
+            _ => return None,
+        };
+        Some(Self {
+            symbol: position.symbol.clone(),
+            expiry: position.expiry.clone()?,
+            strike: position.strike?,
+            right,
+            multiplier: position.multiplier.clone(),
+            currency: Some(position.currency.clone()).filter(|c| !c.is_empty()),
+        })
+    }
+}
+
+/// Listed expirations and strikes for an underlying (SMART).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionChain {
+    pub symbol: String,
+    pub multiplier: String,
+    /// `YYYYMMDD`, ascending.
+    pub expirations: Vec<String>,
+    /// Ascending.
+    pub strikes: Vec<f64>,
+}
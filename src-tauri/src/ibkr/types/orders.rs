@@ -1,14 +1,17 @@
 use chrono::{DateTime, NaiveDate, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+use super::{ContractRoute, OptionContract};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ExecutionSide {
     Bought,
     Sold,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct IbkrExecution {
     pub symbol: String,
     pub side: ExecutionSide,
@@ -60,27 +63,172 @@ pub struct IbkrExecution {
     pub commission_currency: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct OrderRequest {
     pub symbol: String,
     pub action: OrderAction,
     pub quantity: f64,
     pub order_type: OrderType,
     pub price: Option<f64>,
+    /// Hold the order until this time. Sent to IBKR straight away as a
+    /// Good-After-Time order, so IBKR (not this app) holds it and it
+    /// survives restarts on either side.
+    #[serde(default)]
+    pub start_at: Option<StartAt>,
+    /// Work the order through an IBKR algo instead of sending it to the
+    /// book in one piece.
+    #[serde(default)]
+    pub algo: Option<AlgoParams>,
+    /// Exchange / primary exchange / currency, typically copied from the
+    /// symbol's `ContractDetails`. `None` is SMART / USD.
+    #[serde(default)]
+    pub route: Option<ContractRoute>,
+    /// Trade this option on `symbol` instead of the stock. Quantity is
+    /// in contracts; `route` is ignored.
+    #[serde(default)]
+    pub option: Option<OptionContract>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum AlgoStrategy {
+    Vwap,
+    Twap,
+    /// IBKR Adaptive: works a market or limit order between the bid
+    /// and ask at the chosen [`AdaptivePriority`].
+    Adaptive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum AdaptivePriority {
+    Urgent,
+    Normal,
+    Patient,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AlgoParams {
+    pub strategy: AlgoStrategy,
+    /// Window to work the order in; IBKR defaults to now → close.
+    #[serde(default)]
+    pub start_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub end_time: Option<DateTime<Utc>>,
+    /// VWAP only: cap on the share of market volume, `0.1` = 10%.
+    #[serde(default)]
+    pub max_participation: Option<f64>,
+    /// Adaptive only; IBKR's default is `Normal`.
+    #[serde(default)]
+    pub priority: Option<AdaptivePriority>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StartAt {
+    /// The next regular-session open (09:30 ET).
+    MarketOpen,
+    Time {
+        at: DateTime<Utc>,
+    },
+}
+
+impl StartAt {
+    pub fn resolve(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Self::MarketOpen => crate::utils::market_calendar::next_open_at(now),
+            Self::Time { at } => *at,
+        }
+    }
+}
+
+/// IBKR's `goodAfterTime` format, in UTC.
+const GOOD_AFTER_FORMAT: &str = "%Y%m%d %H:%M:%S";
+
+pub fn format_good_after(at: DateTime<Utc>) -> String {
+    format!("{} UTC", at.format(GOOD_AFTER_FORMAT))
+}
+
+/// Inverse of [`format_good_after`]; `None` for an empty or foreign
+/// string.
+pub fn parse_good_after(s: &str) -> Option<DateTime<Utc>> {
+    let naive =
+        chrono::NaiveDateTime::parse_from_str(s.strip_suffix(" UTC")?, GOOD_AFTER_FORMAT).ok()?;
+    Some(naive.and_utc())
+}
+
+/// Order-size rules for one contract, from IBKR's contract details
+/// (`minSize` / `sizeIncrement`). A fractional-eligible US stock
+/// reports an increment below 1; everything else trades whole shares.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeRules {
+    pub min_size: f64,
+    pub size_increment: f64,
+}
+
+/// Slack for float quantities like `0.1 + 0.2`.
+const SIZE_EPSILON: f64 = 1e-9;
+
+impl SizeRules {
+    pub const WHOLE_SHARES: Self = Self {
+        min_size: 1.0,
+        size_increment: 1.0,
+    };
+
+    pub fn fractional(&self) -> bool {
+        self.size_increment < 1.0
+    }
+
+    /// Largest valid quantity not above `qty`; `0` below `min_size`.
+    pub fn round_down(&self, qty: f64) -> f64 {
+        let steps = (qty / self.size_increment + SIZE_EPSILON).floor();
+        // Trim the float noise `steps * increment` leaves behind.
+        let rounded = (steps * self.size_increment * 1e8).round() / 1e8;
+        if rounded + SIZE_EPSILON < self.min_size {
+            0.0
+        } else {
+            rounded
+        }
+    }
+
+    pub fn check(&self, qty: f64) -> Result<(), String> {
+        if !qty.is_finite() || qty <= 0.0 {
+            return Err(format!("quantity must be positive, got {qty}"));
+        }
+        if qty + SIZE_EPSILON < self.min_size {
+            return Err(format!(
+                "quantity {qty} is below the minimum {}",
+                self.min_size
+            ));
+        }
+        if (self.round_down(qty) - qty).abs() > SIZE_EPSILON {
+            return Err(if self.fractional() {
+                format!(
+                    "quantity {qty} is not a multiple of the size increment {}",
+                    self.size_increment
+                )
+            } else {
+                format!("quantity {qty}: fractional shares are not available for this contract")
+            });
+        }
+        Ok(())
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum OrderAction {
     Buy,
     Sell,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum OrderType {
     Market,
     Limit,
     Stop,
     StopLimit,
+    /// IBKR `MIDPRICE`: pegged to the NBBO midpoint. `price`, when set,
+    /// is the cap (buy) / floor (sell).
+    Midprice,
 }
 
 /// Phase 3 — request shape for `IbkrClient::place_bracket`. Wraps the
@@ -88,7 +236,7 @@ pub enum OrderType {
 /// the IBKR adapter. `entry` is always a LIMIT (the trader picks a
 /// price; setups never fire MKT in the bracket path); `stop` is a
 /// STOP order; `targets` are LIMITs whose qty sums to `qty`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BracketRequest {
     pub symbol: String,
     /// Entry side. Long brackets buy → sell; short brackets sell →
@@ -105,7 +253,7 @@ pub struct BracketRequest {
 /// IBKR ids returned by `place_bracket`. Order of `target_order_ids`
 /// matches the input `target_rungs` 1:1 so the OrderTicket service
 /// can join back to its `TargetSpec` ladder.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct BracketReceipt {
     pub parent_order_id: i32,
     pub stop_order_id: i32,
@@ -116,7 +264,7 @@ pub struct BracketReceipt {
 /// submits an existing stop child with a new `aux_price`. The
 /// `BracketReviser` builds these from a `BracketGroupRecord` plus
 /// the freshly-computed chandelier stop.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ModifyStopRequest {
     pub stop_order_id: i32,
     pub parent_id: i32,
@@ -133,8 +281,132 @@ pub struct ModifyStopRequest {
     pub oca_group: String,
 }
 
+/// The order fields the adapter actually handed to ibapi, as recorded
+/// in the audit trail. Mirrors the subset of `ibapi::orders::Order`
+/// our placement paths set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SentOrder {
+    pub order_id: i32,
+    pub symbol: String,
+    /// `BUY` / `SELL`.
+    pub action: String,
+    pub quantity: f64,
+    /// IBKR order-type code (`MKT`, `LMT`, `STP`, ...).
+    pub order_type: String,
+    pub limit_price: Option<f64>,
+    pub aux_price: Option<f64>,
+    /// `0` for a standalone order.
+    pub parent_id: i32,
+    pub transmit: bool,
+    /// IBKR `goodAfterTime` as sent; `None` for an immediate order.
+    #[serde(default)]
+    pub good_after_time: Option<String>,
+    /// IBKR algo name (`Vwap`, `Twap`, ...) and its tag/value params.
+    #[serde(default)]
+    pub algo_strategy: Option<String>,
+    #[serde(default)]
+    pub algo_params: Vec<(String, String)>,
+}
+
+/// One entry of the order audit trail: what the adapter sent and what
+/// IBKR said back. Published on the sink wired by
+/// `IbkrClient::set_order_sink`; `services::order_audit` persists it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OrderAuditEvent {
+    /// Accepted by the gateway connection (not yet by the exchange).
+    Sent(SentOrder),
+    Rejected {
+        order: SentOrder,
+        error: String,
+    },
+    Status {
+        order_id: i32,
+        symbol: String,
+        status: String,
+        filled: f64,
+        remaining: f64,
+        avg_fill_price: f64,
+    },
+    Fill {
+        order_id: i32,
+        symbol: String,
+        exec_id: String,
+        /// `BOT` / `SLD`, as IBKR reports it.
+        side: String,
+        shares: f64,
+        price: f64,
+        /// IBKR's execution timestamp string.
+        time: String,
+    },
+    Notice {
+        order_id: i32,
+        symbol: String,
+        code: i32,
+        message: String,
+    },
+}
+
+impl OrderAuditEvent {
+    pub fn order_id(&self) -> i32 {
+        match self {
+            Self::Sent(order) | Self::Rejected { order, .. } => order.order_id,
+            Self::Status { order_id, .. }
+            | Self::Fill { order_id, .. }
+            | Self::Notice { order_id, .. } => *order_id,
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        match self {
+            Self::Sent(order) | Self::Rejected { order, .. } => &order.symbol,
+            Self::Status { symbol, .. }
+            | Self::Fill { symbol, .. }
+            | Self::Notice { symbol, .. } => symbol,
+        }
+    }
+
+    /// A status after which IBKR sends nothing more for the order.
+    pub fn is_terminal(&self) -> bool {
+        const TERMINAL: [&str; 4] = ["Filled", "Cancelled", "ApiCancelled", "Inactive"];
+        matches!(self, Self::Status { status, .. } if TERMINAL.contains(&status.as_str()))
+    }
+
+    /// Stable tag stored alongside the payload.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Sent(_) => "sent",
+            Self::Rejected { .. } => "rejected",
+            Self::Status { .. } => "status",
+            Self::Fill { .. } => "fill",
+            Self::Notice { .. } => "notice",
+        }
+    }
+}
+
+/// A working order from IBKR's `reqAllOpenOrders`, so it includes
+/// orders entered in TWS or by other API clients. `remaining` is the
+/// unfilled quantity.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrder {
+    pub order_id: i32,
+    pub account: String,
+    pub symbol: String,
+    pub sec_type: String,
+    pub currency: String,
+    pub action: OrderAction,
+    pub remaining: f64,
+    pub order_type: String,
+    pub limit_price: Option<f64>,
+    pub aux_price: Option<f64>,
+    /// Contract multiplier; 1 for stocks.
+    pub multiplier: f64,
+    pub status: String,
+}
+
 #[cfg(test)]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Execution {
     pub exec_id: String,
     pub time: String,
@@ -1,6 +1,7 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Position {
     pub account: String,
     pub symbol: String,
@@ -32,7 +33,19 @@ pub struct Position {
     pub multiplier: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Position {
+    /// Ticker for stock; IBKR local symbol otherwise, so each option
+    /// series is its own key.
+    pub fn contract_key(&self) -> String {
+        if self.contract_type == "STK" || self.local_symbol.is_empty() {
+            self.symbol.clone()
+        } else {
+            self.local_symbol.clone()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ContractDetails {
     pub symbol: String,
     pub sec_type: SecurityType,
@@ -47,7 +60,41 @@ pub struct ContractDetails {
     pub price_magnifier: i32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Explicit venue for a stock contract. Unset fields keep the defaults
+/// (`SMART` routing, `USD`, no primary exchange), which are wrong for
+/// dual-listed and non-US symbols.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ContractRoute {
+    #[serde(default)]
+    pub exchange: Option<String>,
+    #[serde(default)]
+    pub primary_exchange: Option<String>,
+    #[serde(default)]
+    pub currency: Option<String>,
+}
+
+impl ContractRoute {
+    /// Exchange whose trading calendar applies: the primary listing,
+    /// else the routing exchange.
+    pub fn listing_exchange(&self) -> Option<&str> {
+        self.primary_exchange
+            .as_deref()
+            .or(self.exchange.as_deref())
+    }
+}
+
+impl From<&ContractDetails> for ContractRoute {
+    fn from(d: &ContractDetails) -> Self {
+        let set = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+        Self {
+            exchange: set(&d.exchange),
+            primary_exchange: set(&d.primary_exchange),
+            currency: set(&d.currency),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum SecurityType {
     Stock,
     Option,
@@ -1,10 +1,13 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::utils::market_calendar::TradingSession;
+
 /// A live, never-cached, UI-shaped quote. Sourced from
 /// `MarketDataSnapshot` via `QuoteService`. Distinct from
 /// `MarketDataSnapshot` because the UI only needs four fields and
 /// because future quote sources need not match the snapshot shape.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Quote {
     pub symbol: String,
@@ -17,6 +20,26 @@ pub struct Quote {
     pub prev_close: Option<f64>,
     /// Cumulative session volume.
     pub volume: Option<i64>,
+    /// Last regular-session trade; differs from `last_price` in pre-
+    /// and post-market.
+    #[serde(default)]
+    pub last_rth_price: Option<f64>,
+    /// Trading session when the snapshot completed; `None` for venues
+    /// without a known calendar.
+    #[serde(default)]
+    pub session: Option<TradingSession>,
     /// Unix epoch seconds when the snapshot completed.
     pub timestamp: i64,
 }
+
+impl Quote {
+    /// Price on the regular-session basis alerts and P&L assume: the
+    /// last trade during regular hours, else the last regular-session
+    /// trade when known. Falls back to `last_price`.
+    pub fn regular_price(&self) -> Option<f64> {
+        match self.session {
+            Some(TradingSession::Regular) | None => self.last_price,
+            Some(_) => self.last_rth_price.or(self.last_price),
+        }
+    }
+}
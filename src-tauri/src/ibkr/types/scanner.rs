@@ -1,7 +1,8 @@
 use super::positions::ContractDetails;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScannerSubscription {
     pub number_of_rows: i32,
     pub instrument: String,
@@ -20,7 +21,7 @@ pub struct ScannerSubscription {
     pub industry_filter: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScannerData {
     pub rank: i32,
     pub contract: ContractDetails,
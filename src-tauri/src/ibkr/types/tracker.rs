@@ -5,10 +5,13 @@
 //! enforced here — Phase 04 stores transitions verbatim and Phase 12 will
 //! add the validator on top.
 
+use std::borrow::Cow;
+
 use chrono::{DateTime, Utc};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum TrackerSource {
     Scanner,
@@ -45,7 +48,7 @@ impl TrackerSource {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum TrackerStatus {
     Watching,
@@ -117,7 +120,20 @@ impl<'de> Deserialize<'de> for StrategyTag {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+impl JsonSchema for StrategyTag {
+    fn schema_name() -> Cow<'static, str> {
+        "StrategyTag".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "`breakout`, `episodic_pivot`, `parabolic_short` or a custom label.",
+            "type": "string"
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct TrackedTicker {
     pub symbol: String,
     pub source: TrackerSource,
@@ -146,7 +162,7 @@ pub struct TrackedTicker {
 /// `Skipped` is reserved for the idempotent fast-path (a re-prime within
 /// 24h short-circuits with every step set to `Skipped`); `NoData` means
 /// the upstream was healthy but had nothing for this symbol.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case", tag = "kind", content = "detail")]
 pub enum TickerPrimingStepStatus {
     Ok,
@@ -158,7 +174,7 @@ pub enum TickerPrimingStepStatus {
 /// Outcome payload of `AppEvent::TickerPrimingDone`. Granular per-step
 /// status lets the UI decide which panel to refresh without re-querying
 /// every read command.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct TickerPrimingOutcome {
     pub fundamentals: TickerPrimingStepStatus,
     pub projection: TickerPrimingStepStatus,
@@ -170,7 +186,7 @@ pub struct TickerPrimingOutcome {
 /// `Active` rows; `Invalidated` and `Completed` are reserved for the
 /// status state machine in Phase 12 and the LLM decay-watcher in
 /// Phase 18.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum SetupStatus {
     Active,
@@ -204,7 +220,7 @@ impl SetupStatus {
 /// target hit on a completed setup (`TargetHit`), and a thesis update
 /// from the LLM pipeline (`ThesisChanged`). Storage encodes each as a
 /// snake_case string in the `alerts.kind` column.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AlertKind {
     Detected,
@@ -237,7 +253,7 @@ impl AlertKind {
 /// Persisted alert row mirroring the `alerts` table. `payload` is the
 /// event-specific JSON body; the frontend reads `payload.symbol` to wire
 /// row clicks to the analysis tab.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Alert {
     pub id: i64,
     pub setup_id: i64,
@@ -262,7 +278,7 @@ pub struct Alert {
 /// `direction` and `targets` types are owned by the strategies module
 /// so the persistence layer and the detector framework agree on a
 /// single representation; Phase 10 introduces this shared shape.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Setup {
     pub id: i64,
     pub symbol: String,
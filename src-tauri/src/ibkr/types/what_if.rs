@@ -0,0 +1,26 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{OptionContract, OrderAction};
+
+/// One leg of a what-if order: `quantity` shares of `symbol`, or
+/// contracts of `option` when set.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WhatIfLeg {
+    pub symbol: String,
+    pub option: Option<OptionContract>,
+    pub action: OrderAction,
+    /// Whole shares or contracts.
+    pub quantity: f64,
+}
+
+/// How an order IBKR checked, but didn't place, would move the
+/// account's margin. Under the account's own margin regime.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginImpact {
+    pub initial_change: f64,
+    pub maintenance_change: f64,
+    pub equity_with_loan_change: Option<f64>,
+}
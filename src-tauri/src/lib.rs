@@ -1,17 +1,22 @@
+mod bindings;
 mod config;
 mod events;
+mod http_api;
 mod ibkr;
 pub mod mcp;
 mod middleware;
 mod services;
+mod shutdown;
 mod storage;
 mod strategies;
+mod telemetry;
 mod utils;
 
 // Targeted re-exports for the `flex_backfill` and `qk-backtest`
 // binaries in `bin/`. Keeps `ibkr` / `services` / `storage` private
 // to the lib while letting the one-shot binaries share the live
 // ingestor's types and store.
+pub use bindings::typescript as typescript_bindings;
 pub use ibkr::types::{ExecutionSide, IbkrExecution, StrategyTag};
 pub use services::backtester::{
     BacktestResult, BacktestSpec, Backtester, DbBarsReader, FillModelKind, PositionSizingMode,
@@ -31,45 +36,104 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use config::{settings::LlmBackendKind, AppConfig, SettingsState};
+use ibkr::client::demo::DemoFeed;
+use ibkr::client::BlockingExecutor;
 use ibkr::IbkrState;
 use middleware::{AlphaVantageRateLimiter, HistoricalRateLimiter, IbkrNewsRateLimiter};
 use services::auto_scanner::{AutoScannerScheduler, AutoScannerService, MarketScanner};
+use services::automation::{IbkrRuleData, RuleEngine, TiltGuardPause};
+use services::benchmarks::BenchmarkService;
 use services::bracket_reviser::{BracketReviser, QuoteSource as ReviserQuoteSource};
+use services::carry_costs::CarryCostService;
+use services::cash_drag::CashDragService;
+use services::cash_management::CashManagementService;
+use services::connection_health::{ConnectionHealth, HeartbeatProbe};
+use services::corporate_actions::CorporateActionService;
+use services::cost_basis::CostBasisReconciler;
 use services::daily_ranker::DailyRanker;
 use services::decay_watcher::{DecayWatcher, LlmDecayWatcher};
+use services::drawdown::DrawdownService;
+use services::edgar_service::sec::SecHttp;
+use services::edgar_service::EdgarService;
 use services::eod_scheduler::EodScheduler;
 use services::executions::{ExecutionsIngestor, LiveExecutionsFetcher};
-use services::financial_data_service::FinancialDataService;
+use services::expected_return::ExpectedReturnService;
+use services::factor_exposure::FactorExposureService;
+use services::fair_value_watch::FairValueWatcher;
+use services::financial_data_service::{FinancialDataService, ReqwestAvHttp};
+use services::fundamentals_overrides::FundamentalsOverridesStore;
 use services::fundamentals_provider::alpha_vantage::AlphaVantageFundamentalsProvider;
 use services::fundamentals_provider::av_call_ledger::AvCallLedger;
 use services::fundamentals_provider::composite::{AvGuard, CompositeFundamentalsProvider};
+use services::fundamentals_provider::currency::CurrencyConvertingFundamentalsProvider;
+use services::fundamentals_provider::demo::DemoFundamentalsProvider;
 use services::fundamentals_provider::manual::ManualFundamentalsProvider;
+use services::fundamentals_provider::overrides::OverridingFundamentalsProvider;
+use services::fundamentals_provider::refreshing::RefreshingFundamentalsProvider;
 use services::fundamentals_provider::FundamentalsProvider;
+use services::fx_service::alpha_vantage::AlphaVantageFxProvider;
+use services::fx_service::FxRateProvider;
+use services::hedging::HedgingService;
 use services::historical_data_service::{HistoricalDataFetcher, HistoricalDataService};
+use services::insider_activity::alpha_vantage::AlphaVantageInsiderFeed;
+use services::insider_activity::InsiderActivityService;
 use services::intraday_scheduler::IntradayScheduler;
+use services::iv_rank::IvRankService;
+use services::jobs::JobRegistry;
+use services::liquidity::LiquidityService;
 use services::llm_service::{
     backend::LlmBackend, ApiBackend, ClaudeCliBackend, LlmService, ReqwestAnthropicHttp,
 };
+use services::macro_service::alpha_vantage::AlphaVantageMacroFeed;
+use services::macro_service::MacroService;
 use services::manual_fundamentals_store::ManualFundamentalsStore;
+use services::margin_compare::MarginCompareService;
+use services::margin_monitor::MarginMonitor;
+use services::margin_of_safety::MarginOfSafetyWatcher;
+use services::model_portfolio::ModelPortfolioService;
+use services::morning_briefing::MorningBriefingService;
 use services::news_interpreter::NewsInterpreter;
 use services::news_provider::ibkr::client::IbkrNewsClient;
 use services::news_provider::ibkr::IbkrNewsProvider;
 use services::news_provider::NewsProvider;
+use services::notifications::Notifier;
+use services::notion_export::NotionExporter;
+use services::option_expiry::OptionExpiryService;
+use services::option_greeks::OptionGreeksService;
+use services::option_income::OptionIncomeService;
+use services::option_roll::OptionRollService;
+use services::order_audit::OrderAuditStore;
+use services::order_guard::OrderGuard;
 use services::order_ticket::{
     AccountResolver, BracketGroupStore, BracketModifier, BracketPlacer, OrderTicket,
 };
+use services::paper_trading::PaperTrader;
 use services::param_refit::{MonthlyRefitScheduler, ParamRefitService, ProdBacktesterFactory};
+use services::portfolio_analysis::PortfolioAnalyzer;
+use services::portfolio_diff::{PortfolioDiffEmail, PortfolioDiffService};
 use services::portfolio_risk::{
     FactorBuckets, OpenPositionsSource, PortfolioRiskService, SectorMap,
 };
+use services::position_health::PositionHealthService;
+use services::position_plans::{PositionPlanService, StopBabysitter};
+use services::projection_history::ProjectionHistoryStore;
+use services::projection_refresh::ProjectionRefresher;
+use services::projection_templates::TickerTemplateStore;
 use services::regime::RegimeService;
+use services::report_service::ReportService;
 use services::risk_engine::{EquityFetcher, EquitySnapshotService, RiskEngine};
+use services::scheduler::{ScheduledTask, Scheduler};
+use services::screener::source::LocalFundamentalsSource;
+use services::screener::{CachedFundamentals, Screener};
+use services::short_interest::{finra::FinraShortInterest, ShortInterestService};
 use services::social_sentiment::apewisdom::ApewisdomProvider;
 use services::social_sentiment::provider::{ReqwestHttpFetcher, SentimentProvider};
 use services::social_sentiment::reddit::RedditWsbProvider;
 use services::social_sentiment::stocktwits::StocktwitsProvider;
 use services::social_sentiment::SocialSentimentService;
 use services::social_sentiment_scheduler::SocialSentimentScheduler;
+use services::stress_test::StressTestService;
+use services::tax_lots::TaxLotService;
 use services::tca::TcaService;
 use services::thesis_generator::ThesisGenerator;
 use services::ticker_primer::TickerPrimerService;
@@ -77,6 +141,7 @@ use services::tilt_guard::{
     ClosedTradeSource, DbClosedTradeSource, TiltConfig, TiltEpisodeStore, TiltGuardService,
 };
 use services::tracker_runner::{BarsFetcher, TrackerRunner};
+use services::trade_ideas::TradeIdeaService;
 use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -86,6 +151,7 @@ pub fn run() {
 
     // Initialize tracing
     tracing_subscriber::fmt::init();
+    telemetry::init();
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
@@ -106,7 +172,13 @@ pub fn run() {
                 .map_err(|e| format!("resolve app_local_data_dir: {e}"))?;
             std::fs::create_dir_all(&db_dir)
                 .map_err(|e| format!("create app data dir {db_dir:?}: {e}"))?;
-            let db_path = db_dir.join("tracker.sqlite");
+            // Demo mode keeps its made-up fills and journal out of the
+            // real database.
+            let db_path = db_dir.join(if config.demo {
+                "demo.sqlite"
+            } else {
+                "tracker.sqlite"
+            });
             let db =
                 Db::open(&db_path).map_err(|e| format!("open tracker db at {db_path:?}: {e}"))?;
             let db = Arc::new(db);
@@ -151,10 +223,64 @@ pub fn run() {
 
             // Initialize IBKR state with configuration + shared DB.
             let ibkr_state = IbkrState::new(config.ibkr.clone().into(), Arc::clone(&db));
+            ibkr_state
+                .client
+                .set_rate_limits(Arc::new(middleware::rate_limits::RateLimits::new(
+                    config.ibkr.rate_limit_per_second,
+                    &config.rate_limits,
+                    Some(Arc::clone(&ibkr_state.event_emitter)),
+                )));
+            ibkr_state.client.set_executor(BlockingExecutor::new(
+                config.rate_limits.max_in_flight,
+                std::time::Duration::from_millis(config.ibkr.connection_timeout_ms),
+            ));
+            if config.demo {
+                tracing::info!("demo mode: simulated account and market data");
+                ibkr_state.client.set_demo(Arc::new(DemoFeed::new()));
+            }
+
+            // Every order the adapter submits, and what IBKR says back
+            // about it, lands in `order_audit`; fills also drop the
+            // cached positions.
+            let order_audit = Arc::new(
+                OrderAuditStore::new(Arc::clone(&db))
+                    .with_positions(Arc::clone(&ibkr_state.positions)),
+            );
+            let (order_tx, order_rx) = tokio::sync::mpsc::unbounded_channel();
+            ibkr_state.client.set_order_sink(order_tx);
+            // Awaited on exit (`shutdown::on_exit`) so queued events land.
+            {
+                let store = OrderAuditStore::clone(&order_audit);
+                let ingest = tauri::async_runtime::spawn(async move {
+                    if let Err(e) = store.spawn_ingest(order_rx).await {
+                        tracing::warn!("order audit ingest ended abnormally: {e}");
+                    }
+                });
+                app.manage(shutdown::PendingWrites::new(ingest));
+            }
 
             // Set app handle for event emitter
             let app_handle = app.handle().clone();
             let state_clone = ibkr_state.clone();
+            state_clone
+                .event_emitter
+                .attach_settings(Arc::clone(&settings_state.config));
+            // Email / webhook copies of alerts and finished jobs, so
+            // they reach the trader with the window closed.
+            // The Telegram client is shared with the bot below, which
+            // answers read-only portfolio queries from the same chats.
+            let telegram_api: Arc<dyn services::telegram_bot::TelegramApi> =
+                Arc::new(services::telegram_bot::client::BotApiClient::new());
+            let notifier = Arc::new(
+                Notifier::new(
+                    Arc::new(services::notifications::transport::LiveTransport::new()),
+                    Arc::clone(&settings_state.config),
+                )
+                .with_telegram(Arc::clone(&telegram_api)),
+            );
+            state_clone
+                .event_emitter
+                .attach_sink(Arc::clone(&notifier) as Arc<dyn events::EventSink>);
             tauri::async_runtime::spawn(async move {
                 state_clone.event_emitter.set_app_handle(app_handle).await;
             });
@@ -185,7 +311,12 @@ pub fn run() {
             // AV-news strip-out it serves only the fundamentals
             // fallback inside `CompositeFundamentalsProvider`. News is
             // owned by `IbkrNewsProvider` (constructed below).
-            let api_key = std::env::var("ALPHA_VANTAGE_API_KEY").unwrap_or_default();
+            // No key in demo mode: nothing reaches Alpha Vantage.
+            let api_key = if config.demo {
+                String::new()
+            } else {
+                std::env::var("ALPHA_VANTAGE_API_KEY").unwrap_or_default()
+            };
             let api_key_present = !api_key.trim().is_empty();
             // Phase 19: news interpreter runs after each successful
             // news fetch and lands a structured NewsVerdict in
@@ -203,7 +334,7 @@ pub fn run() {
             // rate-limit payloads.
             let av_rate_limiter = Arc::new(AlphaVantageRateLimiter::per_second());
             let financial_service = Arc::new(
-                FinancialDataService::new(api_key).with_rate_limiter(Arc::clone(&av_rate_limiter)),
+                FinancialDataService::new(api_key.clone()).with_rate_limiter(Arc::clone(&av_rate_limiter)),
             );
 
             // Phase 4 (AV migration): the production fundamentals path
@@ -215,11 +346,14 @@ pub fn run() {
             // first; manual writes invalidate the AV file cache for
             // the same symbol via `set_fundamentals`.
             let manual_fundamentals_store = Arc::new(ManualFundamentalsStore::new(Arc::clone(&db)));
-            let av_fundamentals_provider: Arc<dyn FundamentalsProvider> =
+            let av_fundamentals_provider: Arc<dyn FundamentalsProvider> = if config.demo {
+                Arc::new(DemoFundamentalsProvider::new())
+            } else {
                 Arc::new(AlphaVantageFundamentalsProvider::new(
                     Arc::clone(&financial_service),
                     api_key_present,
-                ));
+                ))
+            };
             let manual_fundamentals_provider = Arc::new(ManualFundamentalsProvider::new(
                 Arc::clone(&manual_fundamentals_store),
             ));
@@ -241,13 +375,106 @@ pub fn run() {
                 Arc::clone(&av_call_ledger),
                 Arc::clone(&av_cache_for_guard),
             ));
-            let fundamentals_provider: Arc<dyn FundamentalsProvider> = Arc::new(
-                CompositeFundamentalsProvider::new(
-                    Arc::clone(&manual_fundamentals_provider),
-                    Arc::clone(&av_fundamentals_provider),
+            let composite = CompositeFundamentalsProvider::new(
+                Arc::clone(&manual_fundamentals_provider),
+                Arc::clone(&av_fundamentals_provider),
+            );
+            // The demo provider has no quota to guard, and the AV file
+            // cache would serve real records.
+            let composite_fundamentals: Arc<dyn FundamentalsProvider> = if config.demo {
+                Arc::new(composite)
+            } else {
+                Arc::new(composite.with_av_guard(Arc::clone(&av_guard)))
+            };
+            // Per-field operator corrections wrap the whole chain so
+            // manual rows and AV data are corrected alike.
+            let fundamentals_overrides_store =
+                Arc::new(FundamentalsOverridesStore::new(Arc::clone(&db)));
+            // Conversion into the base currency runs before overrides
+            // so operator corrections are entered in the currency the
+            // UI shows.
+            let insider_activity = Arc::new(InsiderActivityService::new(Arc::new(
+                AlphaVantageInsiderFeed::new(Arc::new(ReqwestAvHttp::new()), api_key.clone())
+                    .with_cache(
+                        services::cache_service::CacheService::with_ttl(
+                            "cache/insider_activity",
+                            services::insider_activity::alpha_vantage::CACHE_TTL,
+                        )
+                        .expect("insider cache directory init"),
+                    )
+                    .with_rate_limiter(Arc::clone(&av_rate_limiter)),
+            )));
+            // SEC filing index; no API key, only the configured
+            // User-Agent, read per lookup.
+            let edgar_service = Arc::new(
+                EdgarService::new(Arc::new(SecHttp::new()), Arc::clone(&settings_state.config))
+                    .with_cache(
+                        services::cache_service::CacheService::with_ttl(
+                            "cache/edgar",
+                            services::edgar_service::CACHE_TTL,
+                        )
+                        .expect("edgar cache directory init"),
+                    ),
+            );
+            let macro_service = Arc::new(MacroService::new(Arc::new(
+                AlphaVantageMacroFeed::new(Arc::new(ReqwestAvHttp::new()), api_key.clone())
+                    .with_cache(
+                        services::cache_service::CacheService::with_ttl(
+                            "cache/macro",
+                            services::macro_service::alpha_vantage::CACHE_TTL,
+                        )
+                        .expect("macro cache directory init"),
+                    )
+                    .with_rate_limiter(Arc::clone(&av_rate_limiter)),
+            )));
+            let fx_provider = Arc::new(
+                AlphaVantageFxProvider::new(
+                    Arc::new(ReqwestAvHttp::new()),
+                    api_key,
                 )
-                .with_av_guard(Arc::clone(&av_guard)),
+                .with_rate_limiter(Arc::clone(&av_rate_limiter)),
             );
+            let converted_fundamentals: Arc<dyn FundamentalsProvider> =
+                Arc::new(CurrencyConvertingFundamentalsProvider::new(
+                    composite_fundamentals,
+                    Arc::clone(&fx_provider) as Arc<dyn FxRateProvider>,
+                    &config.fx.base_currency,
+                ));
+            let overridden_fundamentals: Arc<dyn FundamentalsProvider> =
+                Arc::new(OverridingFundamentalsProvider::new(
+                    converted_fundamentals,
+                    Arc::clone(&fundamentals_overrides_store),
+                ));
+
+            let projection_history_store =
+                Arc::new(ProjectionHistoryStore::new(Arc::clone(&db)));
+            // A fetch that brings a new fiscal year re-runs the ticker's
+            // stored projection; emits `ProjectionsUpdated`.
+            let fundamentals_provider: Arc<dyn FundamentalsProvider> =
+                Arc::new(RefreshingFundamentalsProvider::new(
+                    overridden_fundamentals,
+                    Arc::new(ProjectionRefresher::new(
+                        Arc::clone(&projection_history_store),
+                        Arc::clone(&settings_state.config),
+                        Arc::clone(&ibkr_state.event_emitter),
+                    )),
+                ));
+            let ticker_templates = Arc::new(TickerTemplateStore::new(Arc::clone(&db)));
+
+            // Screener reads the same AV cache + manual store the
+            // provider chain does, but never fetches.
+            let short_interest = Arc::new(ShortInterestService::new(
+                Arc::clone(&db),
+                Arc::new(FinraShortInterest::new()),
+            ));
+            let local_fundamentals: Arc<dyn CachedFundamentals> =
+                Arc::new(LocalFundamentalsSource::new(
+                    Arc::clone(&av_cache_for_guard),
+                    Arc::clone(&manual_fundamentals_store),
+                    Arc::clone(&fundamentals_overrides_store),
+                    Arc::clone(&short_interest),
+                ));
+            let screener = Arc::new(Screener::new(Arc::clone(&local_fundamentals)));
 
             let bars: Arc<dyn BarsFetcher> = Arc::clone(&hist_service) as Arc<dyn BarsFetcher>;
             let decay_bars: Arc<dyn BarsFetcher> =
@@ -329,13 +556,23 @@ pub fn run() {
                 TiltConfig::default(),
             ));
 
+            // Days to liquidate per holding behind `get_liquidity`; the
+            // same participation cap clips the risk engine's sizings.
+            let liquidity = Arc::new(LiquidityService::new(
+                Arc::clone(&account_source),
+                Arc::clone(&ibkr_state.client) as Arc<dyn OpenPositionsSource>,
+                Arc::clone(&hist_service) as Arc<dyn services::historical_data_service::DailyBars>,
+                Arc::clone(&settings_state.config),
+            ));
+
             let risk_engine = Arc::new(
                 RiskEngine::new(
                     Arc::clone(&equity_snapshot_svc),
                     account_source,
                     config.risk_engine.clone(),
                 )
-                .with_tilt_guard(Arc::clone(&tilt_guard)),
+                .with_tilt_guard(Arc::clone(&tilt_guard))
+                .with_liquidity(Arc::clone(&liquidity)),
             );
 
             // Quant-decisions Phase 5 — event-blackout gate. Composite
@@ -377,10 +614,78 @@ pub fn run() {
                 Arc::clone(&ibkr_state.client) as Arc<dyn OpenPositionsSource>;
             let portfolio_account_source: Arc<dyn services::risk_engine::AccountSource> =
                 Arc::clone(&ibkr_state.client) as Arc<dyn services::risk_engine::AccountSource>;
+            // Telegram bot: long-polls, so it needs no open port. Idles
+            // until `telegram.enabled` and a token are set.
+            {
+                let bot = Arc::new(services::telegram_bot::TelegramBot::new(
+                    Arc::clone(&telegram_api),
+                    Arc::clone(&positions_source),
+                    Arc::clone(&portfolio_account_source),
+                    Arc::clone(&quote_service),
+                    Arc::clone(&settings_state.config),
+                ));
+                tauri::async_runtime::spawn(async move {
+                    let _handle = bot.spawn();
+                });
+            }
+            let job_registry = Arc::new(JobRegistry::new(Arc::clone(&ibkr_state.event_emitter)));
+            // Bulk "analyse every position" — same positions seam and
+            // provider chain as the single-ticker analysis view.
+            let portfolio_analyzer = Arc::new(PortfolioAnalyzer::new(
+                Arc::clone(&positions_source),
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&fundamentals_provider),
+                Arc::clone(&projection_history_store),
+            ));
+            // Daily position snapshots (`position_snapshot` task below)
+            // behind `get_portfolio_diff` and the morning summary.
+            let portfolio_diff = Arc::new(PortfolioDiffService::new(
+                Arc::clone(&db),
+                Arc::clone(&positions_source),
+                Arc::clone(&portfolio_account_source),
+            ));
+            let model_portfolios = Arc::new(ModelPortfolioService::new(
+                Arc::clone(&positions_source),
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::quote_service::QuoteFetcher>,
+            ));
+            let iv_rank = Arc::new(IvRankService::new(
+                Arc::clone(&hist_service) as Arc<dyn services::iv_rank::IvHistory>,
+            ));
+            let option_income = Arc::new(OptionIncomeService::new(
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::option_income::OptionMarket>,
+                Arc::clone(&positions_source),
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&iv_rank),
+            ));
+            // Roll candidates for expiring short options, each with a
+            // combo ticket for `ibkr_place_combo_order`.
+            let option_roll = Arc::new(OptionRollService::new(
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&positions_source),
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::option_income::OptionMarket>,
+                Arc::clone(&settings_state.config),
+            ));
+            let hedging = Arc::new(HedgingService::new(
+                Arc::clone(&hist_service) as Arc<dyn services::historical_data_service::DailyBars>,
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::option_income::OptionMarket>,
+                Arc::clone(&positions_source),
+                Arc::clone(&portfolio_account_source),
+            ));
+            let carry_costs = Arc::new(CarryCostService::new(
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::carry_costs::AccountValuesSource>,
+                Arc::clone(&positions_source),
+                Arc::clone(&portfolio_account_source),
+            ));
+            let cash_management = Arc::new(CashManagementService::new(
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::cash_management::CashSource>,
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::quote_service::QuoteFetcher>,
+                Arc::clone(&ibkr_state.event_emitter),
+            ));
             let portfolio_risk = Arc::new(PortfolioRiskService::new(
                 Arc::clone(&db),
-                positions_source,
-                portfolio_account_source,
+                Arc::clone(&positions_source),
+                Arc::clone(&portfolio_account_source),
                 Arc::clone(&equity_snapshot_svc),
                 Arc::clone(&ibkr_state.event_emitter),
                 SectorMap::arc(),
@@ -484,6 +789,257 @@ pub fn run() {
                     let _handle = scheduler.spawn();
                 });
             }
+            // Fair value vs market price: re-evaluates every projected
+            // ticker (16:20 ET by default, via the scheduler below) and
+            // emits `FairValueCrossed` when one breaks out of its
+            // bear..bull band.
+            let fair_value_watcher = Arc::new(FairValueWatcher::new(
+                Arc::clone(&db),
+                Arc::clone(&projection_history_store),
+                Arc::clone(&quote_service) as Arc<dyn services::fair_value_watch::PriceSource>,
+                Arc::clone(&ibkr_state.event_emitter),
+            ));
+            // Buy-below / sell-above bands around each scenario's fair
+            // value (`margin_of_safety_check` task below); emits
+            // `MarginOfSafetyEntered`.
+            let margin_of_safety = Arc::new(MarginOfSafetyWatcher::new(
+                Arc::clone(&db),
+                Arc::clone(&projection_history_store),
+                Arc::clone(&quote_service) as Arc<dyn services::fair_value_watch::PriceSource>,
+                Arc::clone(&settings_state.config),
+                Arc::clone(&ibkr_state.event_emitter),
+            ));
+            // Margin requirement / excess liquidity / cushion samples
+            // (`margin_sample` task below); emits `MarginCushionLow`.
+            let margin_monitor = Arc::new(MarginMonitor::new(
+                Arc::clone(&db),
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::margin_monitor::MarginSource>,
+                Arc::clone(&settings_state.config),
+                Arc::clone(&ibkr_state.event_emitter),
+            ));
+            // Daily cash-to-NAV samples (`cash_drag_sample` task
+            // below) and the idle-cash suggestions behind
+            // `get_cash_drag`; emits `IdleCash`.
+            let cash_drag = Arc::new(CashDragService::new(
+                Arc::clone(&db),
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::cash_drag::CashDragSource>,
+                Arc::clone(&ibkr_state.tracker),
+                Arc::clone(&margin_of_safety),
+                Arc::clone(&settings_state.config),
+                Arc::clone(&ibkr_state.event_emitter),
+            ));
+            // Local fill engine and simulated portfolio behind the
+            // `sim_*` commands; the `sim_fill` task below fills working
+            // orders. Emits `SimOrderFilled`.
+            let paper_trader = Arc::new(PaperTrader::new(
+                Arc::clone(&db),
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::paper_trading::SimPriceSource>,
+                Arc::clone(&settings_state.config),
+                Arc::clone(&ibkr_state.event_emitter),
+            ));
+            // Drawdown from the high-water mark over the daily
+            // `equity_snapshots`, today at the live NLV. Behind
+            // `get_drawdown_stats` and the `portfolio_drawdown` rule
+            // condition.
+            let drawdown = Arc::new(DrawdownService::new(
+                Arc::clone(&db),
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&ibkr_state.client) as Arc<dyn EquityFetcher>,
+                Arc::clone(&settings_state.config),
+            ));
+            // Automation rules (`automation_rules` task below): dry-run
+            // orders go to the simulator above; live ones become tickets
+            // the trader confirms. Emits `RuleTriggered`.
+            let rule_engine = Arc::new(RuleEngine::new(
+                Arc::clone(&db),
+                Arc::new(IbkrRuleData::new(
+                    Arc::clone(&quote_service),
+                    Arc::clone(&hist_service),
+                    Arc::clone(&fair_value_watcher),
+                    Arc::clone(&drawdown),
+                )),
+                Arc::new(TiltGuardPause::new(Arc::clone(&tilt_guard))),
+                Arc::clone(&paper_trader),
+                Arc::clone(&settings_state.config),
+                Arc::clone(&ibkr_state.event_emitter),
+            ));
+            // Ticker summaries mirrored into Notion (`notion_export`
+            // task below, off by default).
+            let notion_exporter = Arc::new(NotionExporter::new(
+                Arc::new(services::notion_export::client::ReqwestNotion::new()),
+                Arc::clone(&projection_history_store),
+                Arc::clone(&fair_value_watcher),
+                Arc::clone(&settings_state.config),
+            ));
+            // Per-ticker Markdown/PDF research reports, written under
+            // the app data dir and optionally attached to the journal.
+            let report_service = Arc::new(ReportService::new(
+                Arc::clone(&fundamentals_provider),
+                Arc::clone(&projection_history_store),
+                Arc::new(DbBarsReader::new(Arc::clone(&db))),
+                Arc::clone(&db),
+                db_dir.join("reports"),
+            ));
+            // Research pipeline (watching → researched → position →
+            // exited) behind the `*_trade_idea` commands.
+            let trade_ideas = Arc::new(TradeIdeaService::new(Arc::clone(&db)));
+            // Target / stop / thesis per position behind
+            // `get_enriched_positions`; the `position_plan_check` task
+            // below emits `PositionLevelHit`.
+            let position_plans = Arc::new(PositionPlanService::new(
+                Arc::clone(&db),
+                Arc::clone(&positions_source),
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&ibkr_state.event_emitter),
+            ));
+            // Acts on plan stops that have a stop action: alert, with a
+            // market / limit exit ticket for the trader to confirm
+            // (`stop_babysitter` task below). Emits `StopBreached`.
+            let stop_babysitter = Arc::new(StopBabysitter::new(
+                Arc::clone(&db),
+                Arc::clone(&positions_source),
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::position_plans::StopQuotes>,
+                Arc::clone(&settings_state.config),
+                Arc::clone(&ibkr_state.event_emitter),
+            ));
+            // Traffic-light score per stock position behind
+            // `get_position_health` and `/v1/position-health`.
+            let position_health = Arc::new(PositionHealthService::new(
+                Arc::clone(&db),
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&position_plans),
+                Arc::clone(&hist_service) as Arc<dyn services::position_health::AtrSource>,
+                Arc::clone(&settings_state.config),
+            ));
+            // Stored base-case projections weighted into the book's
+            // expected return, behind `get_expected_portfolio_return`.
+            let expected_return = Arc::new(ExpectedReturnService::new(
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&positions_source),
+                Arc::clone(&projection_history_store),
+            ));
+            // Factor model of the stock book behind
+            // `get_factor_exposure`.
+            let factor_exposure = Arc::new(FactorExposureService::new(
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&positions_source),
+                Arc::clone(&hist_service) as Arc<dyn services::historical_data_service::DailyBars>,
+                Arc::clone(&settings_state.config),
+            ));
+            // Shock scenarios priced through fitted proxy betas,
+            // behind `run_stress_test`.
+            let stress_test = Arc::new(StressTestService::new(
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&positions_source),
+                Arc::clone(&hist_service) as Arc<dyn services::historical_data_service::DailyBars>,
+                Arc::clone(&settings_state.config),
+            ));
+            // Holdings and tagged watchlist entries against their
+            // benchmark ETFs, behind `get_benchmark_report`.
+            let benchmarks = Arc::new(BenchmarkService::new(
+                Arc::clone(&db),
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&positions_source),
+                Arc::clone(&ibkr_state.tracker),
+                Arc::clone(&hist_service) as Arc<dyn services::historical_data_service::DailyBars>,
+                Arc::clone(&fx_provider) as Arc<dyn services::fx_service::FxHistory>,
+                Arc::clone(&settings_state.config),
+            ));
+            // Model greeks for option positions (`option_greeks` task
+            // below); emits `PortfolioGreeksUpdate`.
+            let option_greeks = Arc::new(OptionGreeksService::new(
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::option_greeks::GreeksSource>,
+                Arc::clone(&positions_source),
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&ibkr_state.event_emitter),
+            ));
+            // Reg-T (IBKR what-if per bundle) against an estimated
+            // portfolio margin, behind `get_margin_comparison`.
+            let margin_compare = Arc::new(MarginCompareService::new(
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&positions_source),
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::margin_compare::WhatIfMargin>,
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::option_greeks::GreeksSource>,
+                Arc::clone(&settings_state.config),
+            ));
+            // Days to expiry, moneyness and assignment risk of option
+            // positions (`option_expiry_check` task below,
+            // `get_expiry_summary`); emits `OptionAssignmentRisk`.
+            let option_expiry = Arc::new(OptionExpiryService::new(
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&positions_source),
+                Arc::clone(&ibkr_state.client) as Arc<dyn services::option_expiry::UnderlyingPrices>,
+                Arc::clone(&financial_service) as Arc<dyn services::option_expiry::DividendCalendar>,
+                Arc::clone(&settings_state.config),
+                Arc::clone(&ibkr_state.event_emitter),
+            ));
+            // Pre-market briefing behind `generate_briefing`; the
+            // `morning_briefing` task below (off by default) mails it.
+            let morning_briefing = Arc::new(MorningBriefingService::new(
+                Arc::clone(&db),
+                Arc::clone(&portfolio_diff),
+                Arc::clone(&positions_source),
+                Arc::clone(&portfolio_account_source),
+                Arc::clone(&event_calendar),
+                Arc::clone(&fair_value_watcher),
+            ));
+            // Phase 1 (assessment-MCP plan): persist IBKR fills so
+            // assessment tooling (Phases 2/4/6) can query multi-day
+            // history. The store sits behind `ProdAccountReader`'s
+            // `executions(account, date)` so past-day queries are
+            // served from SQLite while today still drains live; the
+            // ingestor primes the store every 5 min during market
+            // hours for off-band fills the live drain may miss.
+            let executions_store = Arc::new(ExecutionsStore::new(Arc::clone(&db)));
+            // Splits, symbol changes and spin-offs applied to lots,
+            // cached bars, plans and rules (`corporate_action_check`
+            // task below, `apply_corporate_action`); emits
+            // `CorporateActionApplied`.
+            let corporate_actions = Arc::new(CorporateActionService::new(
+                Arc::clone(&db),
+                Arc::clone(&executions_store),
+                Arc::clone(&financial_service) as Arc<dyn services::corporate_actions::SplitHistory>,
+                vec![
+                    Arc::clone(&hist_service) as Arc<dyn services::corporate_actions::SymbolCache>,
+                    Arc::clone(&financial_service) as Arc<dyn services::corporate_actions::SymbolCache>,
+                ],
+                Arc::clone(&settings_state.config),
+                Arc::clone(&ibkr_state.event_emitter),
+            ));
+            // Generic recurring-task scheduler. Cron per task lives in
+            // `AppConfig.scheduler`; the loop re-reads it every tick.
+            let task_scheduler = Arc::new(Scheduler::new(
+                Arc::clone(&db),
+                Arc::clone(&settings_state.config),
+                vec![
+                    Arc::clone(&cash_drag) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&corporate_actions) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&fair_value_watcher) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&margin_monitor) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&margin_of_safety) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&morning_briefing) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&notion_exporter) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&option_expiry) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&option_greeks) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&paper_trader) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&portfolio_analyzer) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&portfolio_diff) as Arc<dyn ScheduledTask>,
+                    Arc::new(PortfolioDiffEmail(Arc::clone(&portfolio_diff))) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&portfolio_risk) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&position_plans) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&rule_engine) as Arc<dyn ScheduledTask>,
+                    Arc::clone(&stop_babysitter) as Arc<dyn ScheduledTask>,
+                ],
+            )
+            .with_emitter(Arc::clone(&ibkr_state.event_emitter)));
+            {
+                let scheduler = Arc::clone(&task_scheduler);
+                tauri::async_runtime::spawn(async move {
+                    let _handle = scheduler.spawn();
+                });
+            }
             // Phase 10 — backfill any detector that has no active
             // vintage. Runs in the background so app boot stays fast;
             // the runner uses bounds defaults until the backfill
@@ -667,14 +1223,21 @@ pub fn run() {
             // the bridge binary's default. Started here so we can grab
             // `Arc` clones of `ibkr_state.mcp_handle` and `llm_service`
             // before they're moved into `app.manage` below.
-            // Phase 1 (assessment-MCP plan): persist IBKR fills so
-            // assessment tooling (Phases 2/4/6) can query multi-day
-            // history. The store sits behind `ProdAccountReader`'s
-            // `executions(account, date)` so past-day queries are
-            // served from SQLite while today still drains live; the
-            // ingestor primes the store every 5 min during market
-            // hours for off-band fills the live drain may miss.
-            let executions_store = Arc::new(ExecutionsStore::new(Arc::clone(&db)));
+            // Imported lots + stored fills vs IBKR's positions, behind
+            // `reconcile_cost_basis`.
+            let cost_basis = Arc::new(CostBasisReconciler::new(
+                Arc::clone(&db),
+                Arc::clone(&executions_store),
+                Arc::clone(&positions_source),
+                Arc::clone(&portfolio_account_source),
+            ));
+            // Realized lots and wash sales across accounts, behind
+            // `get_tax_lot_report` and `project_tax`.
+            let tax_lots = Arc::new(TaxLotService::new(
+                Arc::clone(&db),
+                Arc::clone(&executions_store),
+                Arc::clone(&settings_state.config),
+            ));
             // Quant-decisions Phase 2 — TCA. Constructed before the
             // ingestor so the post-record pass can stamp setup_id /
             // slippage on freshly-stored rows. The ingestor's
@@ -755,6 +1318,47 @@ pub fn run() {
                 }
             });
 
+            // Optional localhost REST API for dashboard integrations.
+            // Read-only, off unless opted in; fundamentals and
+            // projections come from what is cached, like the screener.
+            if config.http_api.enabled {
+                let http_state = http_api::HttpApiState::new(
+                    Arc::clone(&account_reader),
+                    Arc::clone(&local_fundamentals),
+                    Arc::clone(&projection_history_store),
+                    Arc::clone(&av_cache_for_guard),
+                    config.http_api.token.clone().unwrap_or_default(),
+                )
+                .with_position_health(Arc::clone(&position_health));
+                let http_server = http_api::HttpApiServer::new(http_state, config.http_api.port);
+                let http_state_handle = Arc::clone(&ibkr_state.http_api_handle);
+                tauri::async_runtime::spawn(async move {
+                    match http_server.start().await {
+                        Ok((handle, _addr)) => {
+                            *http_state_handle.write().await = Some(handle);
+                        }
+                        Err(e) => {
+                            tracing::warn!("HTTP API server failed to start: {e}");
+                        }
+                    }
+                });
+            }
+
+            // Connection heartbeat: `server_time` latency + uptime for
+            // `get_connection_metrics`, `ConnectionDegraded` on spikes.
+            let connection_health = Arc::new(ConnectionHealth::new(
+                Arc::clone(&ibkr_state.client) as Arc<dyn HeartbeatProbe>,
+                Arc::clone(&ibkr_state.event_emitter),
+            ));
+            {
+                let health = Arc::clone(&connection_health);
+                let heartbeat_handle = Arc::clone(&ibkr_state.heartbeat_handle);
+                tauri::async_runtime::spawn(async move {
+                    *heartbeat_handle.write().await =
+                        Some(health.spawn(services::connection_health::HEARTBEAT_INTERVAL));
+                });
+            }
+
             // Quant-decisions Phase 3 — bracket-on-activation.
             // Single chokepoint for setup-linked order submission.
             // Reuses `tca_service` for intent recording and shares the
@@ -829,11 +1433,44 @@ pub fn run() {
 
             app.manage(settings_state);
             app.manage(ibkr_state);
+            app.manage(connection_health);
             app.manage(db);
             app.manage(hist_service);
             app.manage(financial_service);
             app.manage(fundamentals_provider);
             app.manage(manual_fundamentals_store);
+            app.manage(fundamentals_overrides_store);
+            app.manage(projection_history_store);
+            app.manage(ticker_templates);
+            app.manage(fair_value_watcher);
+            app.manage(screener);
+            app.manage(portfolio_analyzer);
+            app.manage(model_portfolios);
+            app.manage(carry_costs);
+            app.manage(hedging);
+            app.manage(cash_management);
+            app.manage(margin_monitor);
+            app.manage(margin_compare);
+            app.manage(cash_drag);
+            app.manage(corporate_actions);
+            app.manage(paper_trader);
+            app.manage(rule_engine);
+            app.manage(drawdown);
+            app.manage(margin_of_safety);
+            app.manage(notifier);
+            app.manage(notion_exporter);
+            app.manage(report_service);
+            app.manage(option_greeks);
+            app.manage(option_expiry);
+            app.manage(option_income);
+            app.manage(option_roll);
+            app.manage(iv_rank);
+            app.manage(insider_activity);
+            app.manage(edgar_service);
+            app.manage(macro_service);
+            app.manage(short_interest);
+            app.manage(job_registry);
+            app.manage(task_scheduler);
             app.manage(news_provider);
             app.manage(av_call_ledger);
             app.manage(tracker_runner);
@@ -861,6 +1498,8 @@ pub fn run() {
             app.manage(risk_engine);
             app.manage(equity_snapshot_svc);
             app.manage(tca_service);
+            app.manage(order_audit);
+            app.manage(Arc::new(OrderGuard::new()));
             app.manage(order_ticket);
             app.manage(bracket_reviser);
             app.manage(event_calendar);
@@ -868,14 +1507,44 @@ pub fn run() {
             app.manage(earnings_cache);
             app.manage(backtester);
             app.manage(portfolio_risk);
+            app.manage(portfolio_diff);
+            app.manage(morning_briefing);
+            app.manage(position_plans);
+            app.manage(stop_babysitter);
+            app.manage(position_health);
+            app.manage(benchmarks);
+            app.manage(expected_return);
+            app.manage(factor_exposure);
+            app.manage(stress_test);
+            app.manage(liquidity);
+            app.manage(trade_ideas);
+            app.manage(cost_basis);
+            app.manage(tax_lots);
             app.manage(regime_service);
             app.manage(param_refit_service);
             app.manage(tilt_guard);
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
+        // A closed window's route would otherwise outlive it.
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::Destroyed = event {
+                if let Some(state) = window.try_state::<IbkrState>() {
+                    let emitter = Arc::clone(&state.event_emitter);
+                    let label = window.label().to_string();
+                    tauri::async_runtime::spawn(async move {
+                        emitter.unroute_window(&label).await;
+                    });
+                }
+            }
+        })
+        // Count every invocation by name for `get_metrics`.
+        .invoke_handler(telemetry::count_commands(tauri::generate_handler![
             ibkr::commands::ibkr_connect,
             ibkr::commands::ibkr_disconnect,
+            ibkr::commands::ibkr_list_requests,
+            ibkr::commands::ibkr_cancel_request,
+            ibkr::commands::get_connection_metrics,
+            telemetry::commands::get_metrics,
             ibkr::commands::ibkr_get_connection_status,
             ibkr::commands::ibkr_get_accounts,
             ibkr::commands::ibkr_get_account_summary,
@@ -885,9 +1554,114 @@ pub fn run() {
             ibkr::commands::ibkr_subscribe_market_data,
             ibkr::commands::ibkr_get_data_tier,
             ibkr::commands::ibkr_place_order,
+            ibkr::commands::ibkr_place_combo_order,
+            ibkr::commands::ibkr_validate_combo_order,
             ibkr::commands::ibkr_get_executions,
+            ibkr::commands::ibkr_get_size_rules,
+            ibkr::commands::get_order_history,
+            ibkr::commands::list_scheduled_orders,
+            ibkr::commands::get_margin_history,
+            ibkr::commands::get_margin_comparison,
+            ibkr::commands::get_cash_drag,
+            ibkr::commands::list_corporate_actions,
+            ibkr::commands::apply_corporate_action,
+            ibkr::commands::sim_place_order,
+            ibkr::commands::sim_cancel_order,
+            ibkr::commands::sim_list_orders,
+            ibkr::commands::sim_list_fills,
+            ibkr::commands::sim_get_portfolio,
+            ibkr::commands::sim_reset,
+            ibkr::commands::sim_replay,
+            ibkr::commands::list_automation_rules,
+            ibkr::commands::create_automation_rule,
+            ibkr::commands::update_automation_rule,
+            ibkr::commands::set_automation_rule_enabled,
+            ibkr::commands::delete_automation_rule,
+            ibkr::commands::list_rule_evaluations,
+            ibkr::commands::run_automation_rules,
+            ibkr::commands::list_rule_tickets,
+            ibkr::commands::confirm_rule_ticket,
+            ibkr::commands::dismiss_rule_ticket,
+            ibkr::commands::margin_of_safety_list,
+            ibkr::commands::margin_of_safety_run_now,
+            ibkr::commands::notifications_send_test,
+            ibkr::commands::notion_export_now,
+            ibkr::commands::report_generate,
+            ibkr::commands::get_market_status,
             ibkr::commands::ibkr_get_executions_for_date,
             ibkr::commands::ibkr_get_fundamental_data,
+            ibkr::commands::get_fundamental_timeseries,
+            ibkr::commands::fundamentals_get_override,
+            ibkr::commands::fundamentals_list_overrides,
+            ibkr::commands::fundamentals_set_override,
+            ibkr::commands::fundamentals_clear_override,
+            ibkr::commands::get_projection_history,
+            ibkr::commands::fair_value_list_flags,
+            ibkr::commands::fair_value_run_now,
+            ibkr::commands::screener,
+            ibkr::commands::analyze_portfolio,
+            ibkr::commands::get_portfolio_diff,
+            ibkr::commands::generate_briefing,
+            ibkr::commands::get_enriched_positions,
+            ibkr::commands::list_position_plans,
+            ibkr::commands::set_position_plan,
+            ibkr::commands::delete_position_plan,
+            ibkr::commands::position_plan_check_now,
+            ibkr::commands::stop_babysitter_check_now,
+            ibkr::commands::confirm_stop_exit,
+            ibkr::commands::dismiss_stop_exit,
+            ibkr::commands::get_position_health,
+            ibkr::commands::get_drawdown_stats,
+            ibkr::commands::list_benchmark_tags,
+            ibkr::commands::set_benchmark_tag,
+            ibkr::commands::clear_benchmark_tag,
+            ibkr::commands::get_benchmark_report,
+            ibkr::commands::get_expected_portfolio_return,
+            ibkr::commands::get_factor_exposure,
+            ibkr::commands::run_stress_test,
+            ibkr::commands::get_liquidity,
+            ibkr::commands::create_trade_idea,
+            ibkr::commands::move_trade_idea,
+            ibkr::commands::link_trade_idea,
+            ibkr::commands::unlink_trade_idea,
+            ibkr::commands::list_trade_ideas,
+            ibkr::commands::get_trade_idea,
+            ibkr::commands::reconcile_cost_basis,
+            ibkr::commands::get_tax_lot_report,
+            ibkr::commands::project_tax,
+            ibkr::commands::register_window,
+            ibkr::commands::unregister_window,
+            ibkr::commands::list_window_routes,
+            ibkr::commands::open_ticker_window,
+            ibkr::commands::model_portfolio_list,
+            ibkr::commands::model_portfolio_save,
+            ibkr::commands::model_portfolio_delete,
+            ibkr::commands::projection_template_list,
+            ibkr::commands::projection_template_save,
+            ibkr::commands::projection_template_delete,
+            ibkr::commands::get_ticker_projection_template,
+            ibkr::commands::clear_ticker_projection_template,
+            ibkr::commands::model_portfolio_evaluate,
+            ibkr::commands::cash_overview,
+            ibkr::commands::ibkr_get_carry_costs,
+            ibkr::commands::ibkr_get_portfolio_greeks,
+            ibkr::commands::get_option_expiries,
+            ibkr::commands::get_expiry_summary,
+            ibkr::commands::ibkr_suggest_option_income,
+            ibkr::commands::suggest_option_rolls,
+            ibkr::commands::get_iv_rank,
+            ibkr::commands::get_insider_activity,
+            ibkr::commands::get_recent_filings,
+            ibkr::commands::get_macro_snapshot,
+            ibkr::commands::ibkr_suggest_hedge,
+            ibkr::commands::start_portfolio_analysis_job,
+            ibkr::commands::list_jobs,
+            ibkr::commands::get_job,
+            ibkr::commands::cancel_job,
+            ibkr::commands::scheduler_list_jobs,
+            ibkr::commands::scheduler_set_job,
+            ibkr::commands::scheduler_run_history,
+            ibkr::commands::scheduler_run_now,
             ibkr::commands::ibkr_get_quote,
             ibkr::commands::ibkr_generate_projections,
             ibkr::commands::ibkr_generate_projection_results,
@@ -947,6 +1721,7 @@ pub fn run() {
             ibkr::commands::risk_refresh_equity,
             ibkr::commands::tca_get_attribution,
             ibkr::commands::tca_get_slippage_distribution,
+            ibkr::commands::get_execution_quality_report,
             ibkr::commands::tca_record_manual_intent,
             ibkr::commands::order_ticket_take_setup,
             ibkr::commands::order_ticket_status,
@@ -982,12 +1757,29 @@ pub fn run() {
             ibkr::commands::tilt_guard_status,
             ibkr::commands::tilt_guard_override,
             ibkr::commands::tilt_guard_history,
+            ibkr::commands::portfolio_import_csv,
+            ibkr::commands::portfolio_list_imported_lots,
+            ibkr::commands::portfolio_clear_imported,
+            ibkr::commands::portfolio_merged_positions,
             #[cfg(debug_assertions)]
             ibkr::commands::tracker_llm_smoke_test,
             config::commands::get_settings,
             config::commands::update_settings,
+            config::commands::validate_settings,
+            config::commands::set_api_key,
+            config::commands::has_api_key,
             config::commands::get_settings_path,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+            config::commands::workspace_list,
+            config::commands::account_set_alias,
+            config::commands::workspace_save,
+            config::commands::workspace_delete,
+            config::commands::workspace_switch,
+        ]))
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                tauri::async_runtime::block_on(shutdown::on_exit(app));
+            }
+        });
 }
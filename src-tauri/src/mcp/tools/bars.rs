@@ -96,7 +96,7 @@ impl McpHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::mcp::tools::test_support::{handler_for_db, make_db};
+    use crate::mcp::tools::test_support::{handler_for_db, make_db, test_today};
     use chrono::TimeZone;
 
     /// Seed `bars_cache` with five daily bars for AAPL, the most recent
@@ -109,11 +109,10 @@ mod tests {
         let (_tmp, db) = make_db();
         let handler = handler_for_db(db);
 
-        // Seed today + 4 prior days at UTC midnight. We use the real
-        // `Utc::now()` because `HistoricalDataService` uses a
-        // `SystemClock` by default; matching the same instant means
-        // `max_cached_day == today` and the gap-fill path is skipped.
-        let today = chrono::Utc::now().date_naive();
+        // Seed today + 4 prior days at UTC midnight. "Today" comes from
+        // the handler's pinned after-close clock, so the newest bar is
+        // settled and the gap-fill path is skipped.
+        let today = test_today();
         let mut expected_close: f64 = 0.0;
         for i in 0..5_i64 {
             let date = today - chrono::Duration::days(4 - i);
@@ -17,6 +17,7 @@ use serde::Deserialize;
 
 use crate::mcp::handler::McpHandler;
 use crate::mcp::tools::map_tool_result;
+use crate::services::fundamentals_growth;
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GetFundamentalsArgs {
@@ -28,7 +29,7 @@ pub struct GetFundamentalsArgs {
 impl McpHandler {
     #[tool(
         name = "get_fundamentals",
-        description = "Return company fundamentals for `symbol`: historical financials, current metrics, and analyst estimates. Returns an error when the upstream provider has no data (missing API key, insufficient history, rate-limit). Use this when reasoning about valuation, growth trajectory, or earnings-driven setups."
+        description = "Return company fundamentals for `symbol`: historical financials with YoY growth, margin trend and 3y/5y CAGR, current metrics, and analyst estimates. Returns an error when the upstream provider has no data (missing API key, insufficient history, rate-limit). Use this when reasoning about valuation, growth trajectory, or earnings-driven setups."
     )]
     pub async fn get_fundamentals(
         &self,
@@ -42,6 +43,10 @@ impl McpHandler {
             .fundamentals_provider
             .fetch(&symbol)
             .await
+            .map(|mut data| {
+                fundamentals_growth::annotate(&mut data);
+                data
+            })
             .map_err(|e| e.to_string());
         map_tool_result(result)
     }
@@ -274,7 +274,7 @@ fn idea_marked_skipped(idea: &RankedIdea) -> bool {
 mod tests {
     use super::*;
     use crate::ibkr::types::historical::HistoricalBar;
-    use crate::mcp::tools::test_support::{handler_for_db, make_db};
+    use crate::mcp::tools::test_support::{handler_for_db, make_db, test_today};
     use crate::services::agent_morning_packs::{NewAgentMorningPack, RankedIdea};
     use crate::services::research_notes::Conviction;
 
@@ -378,7 +378,7 @@ mod tests {
         let handler = handler_for_db(db.clone());
 
         // Pack date is yesterday-ish; bars seeded for that date.
-        let pack_date = (Utc::now() - ChronoDuration::days(1)).date_naive();
+        let pack_date = test_today() - ChronoDuration::days(1);
         let bar_str = pack_date.format("%Y%m%d").to_string();
 
         // Long idea 100-105 invalidation 95; bars hit 107 — hit_entry.
@@ -426,7 +426,7 @@ mod tests {
         let (_tmp, db) = make_db();
         let handler = handler_for_db(db.clone());
 
-        let pack_date = (Utc::now() - ChronoDuration::days(1)).date_naive();
+        let pack_date = test_today() - ChronoDuration::days(1);
         let bar_str = pack_date.format("%Y%m%d").to_string();
         let mut skip_idea = idea("AAPL", "100-105", "close < 95");
         skip_idea.thesis_md = "SKIP: nothing worth taking today".into();
@@ -233,7 +233,7 @@ mod tests {
     use crate::ibkr::mocks::MockIbkrClient;
     use crate::ibkr::types::tracker::{StrategyTag, TrackerSource};
     use crate::ibkr::types::BarSize;
-    use crate::mcp::tools::test_support::{handler_for_mock_ibkr, make_db};
+    use crate::mcp::tools::test_support::{handler_for_mock_ibkr, make_db, test_today};
     use crate::storage::Db;
     use chrono::TimeZone;
     use std::sync::Arc;
@@ -241,7 +241,7 @@ mod tests {
     /// Seed today's daily bar for `symbol` so `get_bars(lookback_days=1)`
     /// is served from cache and the [`PanickingFetcher`] is never invoked.
     async fn seed_today_daily_bar(db: &Arc<Db>, symbol: &str, close: f64) {
-        let today = chrono::Utc::now().date_naive();
+        let today = test_today();
         let bar_time = chrono::Utc
             .from_utc_datetime(&today.and_hms_opt(0, 0, 0).unwrap())
             .timestamp();
@@ -78,6 +78,13 @@ pub async fn resolve_account(
     requested: Option<&str>,
 ) -> Result<String, String> {
     let accounts = reader.list_accounts().await.map_err(|e| e.to_string())?;
+    pick_account(&accounts, requested)
+}
+
+/// Pure half of [`resolve_account`] for callers that already hold the
+/// account list (the HTTP API fetches it first so a disconnected gateway
+/// keeps its typed [`crate::ibkr::error::IbkrError::NotConnected`]).
+pub fn pick_account(accounts: &[String], requested: Option<&str>) -> Result<String, String> {
     if accounts.is_empty() {
         return Err("no IBKR accounts available — is TWS/Gateway connected?".to_string());
     }
@@ -9,7 +9,7 @@
 //!
 //! - **Input:** envelope (`symbol`, `asOfDate`, `source`, optional
 //!   `notes`) flattened with the `FundamentalData` shape (`historical`,
-//!   `analystEstimates`, `currentMetrics`).
+//!   `segments`, `analystEstimates`, `currentMetrics`).
 //! - **Validation:** symbol matches `^[A-Z][A-Z0-9.\-]{0,9}$`, ISO 8601
 //!   date, non-empty source, finite numeric fields, `peRatio >= 0`,
 //!   `sharesOutstanding > 0`. Out-of-range or 5x changes vs. prior emit
@@ -34,15 +34,15 @@ use serde::Deserialize;
 use serde_json::{json, Value};
 
 use crate::events::AppEvent;
-use crate::ibkr::types::{AnalystEstimates, CurrentMetrics, FundamentalData, HistoricalFinancial};
+use crate::ibkr::types::{
+    AnalystEstimates, CurrentMetrics, FundamentalData, HistoricalFinancial, RevenueSegment,
+};
 use crate::mcp::handler::McpHandler;
 use crate::mcp::tools::map_tool_result;
 use crate::mcp::tools::write_support::{emit_event, record_audit, stamp_audit_summary};
+use crate::middleware::validation::validate_symbol;
 use crate::services::manual_fundamentals_store::ManualFundamentalsRow;
 
-/// Maximum allowed symbol length. Matches the regex bound in [`validate_symbol`].
-const MAX_SYMBOL_LEN: usize = 10;
-
 /// Threshold above which a numeric change vs. the prior write surfaces
 /// a `warnings` entry in the tool response. 5x mirrors the
 /// "surprising movement" sanity heuristic from `master.md` § "Open risks".
@@ -69,6 +69,10 @@ pub struct SetFundamentalsArgs {
     /// current-snapshot data.
     #[serde(default)]
     pub historical: Vec<HistoricalFinancial>,
+    /// Revenue by business segment, for projecting by segment. Omit for
+    /// single-segment companies.
+    #[serde(default)]
+    pub segments: Vec<RevenueSegment>,
     /// Optional analyst-estimate vectors. Omit when the operator has
     /// no estimates (typical for small caps, ADRs).
     #[serde(default)]
@@ -93,6 +97,7 @@ impl McpHandler {
             source,
             notes,
             historical,
+            segments,
             analyst_estimates,
             current_metrics,
         } = args;
@@ -114,11 +119,20 @@ impl McpHandler {
         if let Err(msg) = validate_historical(&historical) {
             return map_tool_result::<(), String>(Err(msg));
         }
+        if let Err(msg) = validate_segments(&segments) {
+            return map_tool_result::<(), String>(Err(msg));
+        }
 
         let payload = FundamentalData {
             symbol: symbol.clone(),
             historical,
             analyst_estimates,
+            data_quality: Vec::new(),
+            overrides: Vec::new(),
+            growth: None,
+            currency: None,
+            fx_conversion: None,
+            segments,
             current_metrics,
         };
 
@@ -207,35 +221,6 @@ impl McpHandler {
     }
 }
 
-fn validate_symbol(symbol: &str) -> Result<String, String> {
-    let trimmed = symbol.trim();
-    if trimmed.is_empty() {
-        return Err("symbol must not be empty".to_string());
-    }
-    let upper = trimmed.to_uppercase();
-    if upper.len() > MAX_SYMBOL_LEN {
-        return Err(format!(
-            "symbol must be at most {MAX_SYMBOL_LEN} characters; got {}",
-            upper.len()
-        ));
-    }
-    let mut chars = upper.chars();
-    let first = chars.next().expect("non-empty");
-    if !first.is_ascii_uppercase() {
-        return Err(format!(
-            "symbol must start with an uppercase letter; got `{upper}`"
-        ));
-    }
-    for c in chars {
-        if !(c.is_ascii_uppercase() || c.is_ascii_digit() || c == '.' || c == '-') {
-            return Err(format!(
-                "symbol contains invalid character `{c}`; allowed: A-Z, 0-9, `.`, `-`"
-            ));
-        }
-    }
-    Ok(upper)
-}
-
 fn validate_as_of_date(s: &str) -> Result<(), String> {
     chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
         .map(|_| ())
@@ -282,6 +267,20 @@ fn validate_historical(rows: &[HistoricalFinancial]) -> Result<(), String> {
     Ok(())
 }
 
+fn validate_segments(segments: &[RevenueSegment]) -> Result<(), String> {
+    for (i, s) in segments.iter().enumerate() {
+        if s.name.trim().is_empty() {
+            return Err(format!("segments[{i}] needs a name"));
+        }
+        if s.revenue.iter().any(|r| !r.revenue.is_finite()) {
+            return Err(format!(
+                "segments[{i}] contains non-finite numbers (NaN/Inf disallowed)"
+            ));
+        }
+    }
+    Ok(())
+}
+
 fn build_diff(prior: &Option<ManualFundamentalsRow>, current: &ManualFundamentalsRow) -> Value {
     match prior {
         None => json!({"kind": "new"}),
@@ -418,6 +417,7 @@ mod tests {
             exchange: None,
             market_cap: None,
             dividend_yield: None,
+            short_interest: None,
         }
     }
 
@@ -432,7 +432,9 @@ mod tests {
                 revenue: 100.0,
                 net_income: 10.0,
                 eps: 1.0,
+                split_adjustment: None,
             }],
+            segments: Vec::new(),
             analyst_estimates: Some(AnalystEstimates {
                 revenue: vec![AnalystEstimate {
                     year: 2025,
@@ -757,7 +759,9 @@ mod tests {
                 revenue: 391.0,
                 net_income: 99.8,
                 eps: 6.5,
+                split_adjustment: None,
             }],
+            segments: Vec::new(),
             analyst_estimates: Some(AnalystEstimates {
                 revenue: vec![AnalystEstimate {
                     year: 2025,
@@ -776,6 +780,7 @@ mod tests {
                 exchange: Some("NASDAQ".into()),
                 market_cap: Some("3000000000000".into()),
                 dividend_yield: Some(0.005),
+                short_interest: None,
             },
         };
         let r = handler
@@ -21,7 +21,7 @@ use std::sync::Arc;
 use tempfile::NamedTempFile;
 
 use async_trait::async_trait;
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 
 use crate::config::settings::AutoScannerConfig;
 use crate::events::EventEmitter;
@@ -41,7 +41,9 @@ use crate::services::candidate_universe::CandidateUniverseService;
 use crate::services::financial_data_service::FinancialDataService;
 use crate::services::fundamentals_provider::test_support::FakeFundamentalsProvider;
 use crate::services::fundamentals_provider::FundamentalsProvider;
-use crate::services::historical_data_service::{HistoricalDataFetcher, HistoricalDataService};
+use crate::services::historical_data_service::{
+    Clock as HistoricalClock, HistoricalDataFetcher, HistoricalDataService,
+};
 use crate::services::llm_service::{LlmClock, LlmService};
 use crate::services::manual_fundamentals_store::ManualFundamentalsStore;
 use crate::services::news_provider::test_support::FakeNewsProvider;
@@ -60,6 +62,24 @@ use crate::ibkr::mocks::MockIbkrClient;
 /// as a loud test failure instead of a hang or a real network attempt.
 pub struct PanickingFetcher;
 
+/// Pinned clock for the handler's `HistoricalDataService`: Friday
+/// 2024-06-14 after the US close, so a daily bar seeded for
+/// [`test_today`] is settled and served from cache. Wall time would make
+/// today's bar look unsettled (and refetch it) whenever tests run before
+/// 16:00 ET.
+pub struct AfterCloseClock;
+
+impl HistoricalClock for AfterCloseClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 6, 14, 22, 0, 0).unwrap()
+    }
+}
+
+/// "Today" as the test handler's historical-data service sees it.
+pub fn test_today() -> NaiveDate {
+    AfterCloseClock.today()
+}
+
 /// No-op IBKR seams used by `test_handler_with_seeded_spend` — the
 /// cross-crate integration test only exercises `get_llm_budget_status`,
 /// so the four IBKR-touching Arcs need a stub that satisfies the type
@@ -200,11 +220,14 @@ fn build_handler_with_llm(
     // the AV branch construct their own `FinancialDataService`).
     let financial = Arc::new(FinancialDataService::new(String::new()));
     let fetcher: Arc<dyn HistoricalDataFetcher> = Arc::new(PanickingFetcher);
-    let hist = Arc::new(HistoricalDataService::new(
-        Arc::clone(&db),
-        fetcher,
-        Arc::new(HistoricalRateLimiter::new(60)),
-    ));
+    let hist = Arc::new(
+        HistoricalDataService::new(
+            Arc::clone(&db),
+            fetcher,
+            Arc::new(HistoricalRateLimiter::new(60)),
+        )
+        .with_clock(Arc::new(AfterCloseClock)),
+    );
 
     // Three Arc<dyn _> coercions of the same MockIbkrClient — each tool
     // imports a different trait but they all delegate to the same mock
@@ -337,11 +360,14 @@ pub async fn test_handler_with_seeded_spend(
     let tracker = Arc::new(TrackerService::new(Arc::clone(&db)));
     let financial = Arc::new(FinancialDataService::new(String::new()));
     let fetcher: Arc<dyn HistoricalDataFetcher> = Arc::new(PanickingFetcher);
-    let hist = Arc::new(HistoricalDataService::new(
-        Arc::clone(&db),
-        fetcher,
-        Arc::new(HistoricalRateLimiter::new(60)),
-    ));
+    let hist = Arc::new(
+        HistoricalDataService::new(
+            Arc::clone(&db),
+            fetcher,
+            Arc::new(HistoricalRateLimiter::new(60)),
+        )
+        .with_clock(Arc::new(AfterCloseClock)),
+    );
     // The integration test only exercises `get_llm_budget_status` — none
     // of the live-IBKR tools — so disconnected stubs are fine. Wired
     // through every Arc the tools need so the handler still builds.
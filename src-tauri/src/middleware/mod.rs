@@ -1,6 +1,9 @@
 pub mod alpha_vantage_rate_limit;
 pub mod historical_rate_limit;
 pub mod ibkr_news_rate_limit;
+pub mod rate_limits;
+pub mod token_bucket;
+pub mod validation;
 
 pub use alpha_vantage_rate_limit::AlphaVantageRateLimiter;
 pub use historical_rate_limit::HistoricalRateLimiter;
@@ -0,0 +1,184 @@
+//! Per-endpoint IBKR pacing built on [`TokenBucket`].
+//!
+//! Every `IbkrClient` call takes one token from the *global* bucket
+//! (`ibkr.rate_limit_per_second`, TWS's 50 msg/s ceiling) and, when
+//! `rate_limits.endpoints` configures one, from its endpoint bucket too —
+//! e.g. throttling `market_data` snapshots harder than account reads.
+//! Endpoint names are the [`ENDPOINTS`] constants; unknown names are
+//! rejected by `AppConfig::validate`.
+//!
+//! When a bucket drops below `warn_below_fraction` of its capacity a
+//! `RateLimitWarning` is emitted once (edge-triggered, see
+//! [`super::token_bucket::Taken`]). Limits are read at startup; changes
+//! take effect on the next launch.
+//!
+//! `max_in_flight` is the companion concurrency cap — how many blocking
+//! ibapi calls may run at once (see `ibkr/client/executor.rs`).
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::token_bucket::{Taken, TokenBucket};
+use crate::events::{AppEvent, EventEmitter};
+
+pub const ENDPOINT_GLOBAL: &str = "global";
+pub const ENDPOINT_ACCOUNT: &str = "account";
+pub const ENDPOINT_MARKET_DATA: &str = "market_data";
+pub const ENDPOINT_HISTORICAL: &str = "historical";
+pub const ENDPOINT_NEWS: &str = "news";
+pub const ENDPOINT_ORDERS: &str = "orders";
+pub const ENDPOINT_SCANNER: &str = "scanner";
+
+/// Endpoint keys accepted in `rate_limits.endpoints`.
+pub const ENDPOINTS: &[&str] = &[
+    ENDPOINT_ACCOUNT,
+    ENDPOINT_MARKET_DATA,
+    ENDPOINT_HISTORICAL,
+    ENDPOINT_NEWS,
+    ENDPOINT_ORDERS,
+    ENDPOINT_SCANNER,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitsConfig {
+    /// Share of a bucket's capacity below which `RateLimitWarning`
+    /// fires. `0` disables the warning.
+    #[serde(default = "default_warn_below_fraction")]
+    pub warn_below_fraction: f64,
+    /// Optional per-endpoint buckets layered on top of the global one.
+    #[serde(default)]
+    pub endpoints: BTreeMap<String, EndpointLimit>,
+    /// Blocking ibapi calls allowed in flight; the rest queue.
+    #[serde(default = "default_max_in_flight")]
+    pub max_in_flight: usize,
+}
+
+fn default_warn_below_fraction() -> f64 {
+    0.2
+}
+
+fn default_max_in_flight() -> usize {
+    crate::ibkr::client::executor::DEFAULT_MAX_IN_FLIGHT
+}
+
+impl Default for RateLimitsConfig {
+    fn default() -> Self {
+        Self {
+            warn_below_fraction: default_warn_below_fraction(),
+            endpoints: BTreeMap::new(),
+            max_in_flight: default_max_in_flight(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointLimit {
+    pub per_second: f64,
+    /// Burst capacity. Defaults to one second's worth of tokens.
+    #[serde(default)]
+    pub burst: Option<u32>,
+}
+
+impl EndpointLimit {
+    fn bucket(&self, warn_below_fraction: f64) -> TokenBucket {
+        let burst = self
+            .burst
+            .unwrap_or_else(|| self.per_second.ceil().max(1.0) as u32);
+        TokenBucket::new(burst, self.per_second, warn_below_fraction)
+    }
+}
+
+pub struct RateLimits {
+    global: TokenBucket,
+    endpoints: HashMap<String, TokenBucket>,
+    emitter: Option<Arc<EventEmitter>>,
+}
+
+impl RateLimits {
+    pub fn new(
+        global_per_second: u32,
+        config: &RateLimitsConfig,
+        emitter: Option<Arc<EventEmitter>>,
+    ) -> Self {
+        let warn = config.warn_below_fraction;
+        Self {
+            global: TokenBucket::new(global_per_second, f64::from(global_per_second), warn),
+            endpoints: config
+                .endpoints
+                .iter()
+                .map(|(name, limit)| (name.clone(), limit.bucket(warn)))
+                .collect(),
+            emitter,
+        }
+    }
+
+    /// Wait for a global token, then the endpoint's (if configured).
+    pub async fn acquire(&self, endpoint: &'static str) {
+        let taken = self.global.acquire().await;
+        self.warn_if_low(ENDPOINT_GLOBAL, taken).await;
+        if let Some(bucket) = self.endpoints.get(endpoint) {
+            let taken = bucket.acquire().await;
+            self.warn_if_low(endpoint, taken).await;
+        }
+    }
+
+    async fn warn_if_low(&self, endpoint: &str, taken: Taken) {
+        if !taken.crossed_low {
+            return;
+        }
+        tracing::warn!(
+            "IBKR rate limit `{endpoint}` running low: {} tokens left",
+            taken.remaining
+        );
+        if let Some(emitter) = &self.emitter {
+            let event = AppEvent::RateLimitWarning {
+                endpoint: endpoint.to_string(),
+                remaining: taken.remaining,
+            };
+            if let Err(e) = emitter.emit(event).await {
+                tracing::debug!("RateLimitWarning emit skipped: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn low_bucket_emits_one_warning_per_crossing() {
+        let emitter = Arc::new(EventEmitter::for_capture());
+        let mut config = RateLimitsConfig {
+            warn_below_fraction: 0.5,
+            ..Default::default()
+        };
+        config.endpoints.insert(
+            ENDPOINT_MARKET_DATA.into(),
+            EndpointLimit {
+                per_second: 2.0,
+                burst: Some(2),
+            },
+        );
+        let limits = RateLimits::new(100, &config, Some(Arc::clone(&emitter)));
+
+        // Second take leaves 0 < 1 token; account has no endpoint bucket
+        // and the global one stays far above its threshold.
+        limits.acquire(ENDPOINT_MARKET_DATA).await;
+        limits.acquire(ENDPOINT_MARKET_DATA).await;
+        limits.acquire(ENDPOINT_ACCOUNT).await;
+
+        let warnings: Vec<String> = emitter
+            .captured()
+            .await
+            .into_iter()
+            .filter_map(|e| match e {
+                AppEvent::RateLimitWarning { endpoint, .. } => Some(endpoint),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(warnings, vec![ENDPOINT_MARKET_DATA.to_string()]);
+    }
+}
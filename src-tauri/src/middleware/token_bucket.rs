@@ -0,0 +1,185 @@
+//! Token-bucket limiter for IBKR request pacing.
+//!
+//! A fixed "N per second, counter reset on the tick" window lets a
+//! burst of N land at the end of one second and another N at the start
+//! of the next — 2N in a few milliseconds, which is exactly what trips
+//! TWS's 50 msg/s pacing violation. A bucket refills continuously at
+//! `refill_per_sec`, so sustained throughput is bounded by the rate and
+//! bursts by `capacity`, with no boundary to straddle.
+//!
+//! The refill arithmetic lives on [`BucketState`] and takes `now`
+//! explicitly so it can be tested without sleeping.
+
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Outcome of a successful take. `crossed_low` is edge-triggered: it is
+/// `true` only on the take that drops the bucket below the warning
+/// threshold, and re-arms once the bucket refills above it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Taken {
+    pub remaining: u32,
+    pub crossed_low: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct BucketState {
+    tokens: f64,
+    last: Instant,
+    low_armed: bool,
+}
+
+impl BucketState {
+    fn new(capacity: f64, now: Instant) -> Self {
+        Self {
+            tokens: capacity,
+            last: now,
+            low_armed: true,
+        }
+    }
+
+    fn refill(&mut self, bucket: &TokenBucket, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * bucket.refill_per_sec).min(bucket.capacity);
+        self.last = now;
+        if self.tokens >= bucket.low_water {
+            self.low_armed = true;
+        }
+    }
+
+    /// Take one token, or report how long until one is available.
+    pub(crate) fn take(&mut self, bucket: &TokenBucket, now: Instant) -> Result<Taken, Duration> {
+        self.refill(bucket, now);
+        if self.tokens < 1.0 {
+            let deficit = 1.0 - self.tokens;
+            return Err(Duration::from_secs_f64(deficit / bucket.refill_per_sec));
+        }
+        self.tokens -= 1.0;
+        let crossed_low = self.low_armed && self.tokens < bucket.low_water;
+        if crossed_low {
+            self.low_armed = false;
+        }
+        Ok(Taken {
+            remaining: self.tokens.floor() as u32,
+            crossed_low,
+        })
+    }
+}
+
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    /// Tokens below which a take reports `crossed_low`.
+    low_water: f64,
+    state: Mutex<BucketState>,
+}
+
+impl TokenBucket {
+    /// `capacity` tokens, refilled at `refill_per_sec`. A zero capacity
+    /// becomes 1 and a rate that isn't positive becomes 1/s, so a zero
+    /// config can't wedge every caller forever; fractional rates are
+    /// kept. `warn_below_fraction` is the share of `capacity` under which
+    /// [`Taken::crossed_low`] fires (0 disables the warning).
+    pub fn new(capacity: u32, refill_per_sec: f64, warn_below_fraction: f64) -> Self {
+        let capacity = f64::from(capacity.max(1));
+        Self {
+            capacity,
+            refill_per_sec: if refill_per_sec.is_finite() && refill_per_sec > 0.0 {
+                refill_per_sec
+            } else {
+                1.0
+            },
+            low_water: capacity * warn_below_fraction.clamp(0.0, 1.0),
+            state: Mutex::new(BucketState::new(capacity, Instant::now())),
+        }
+    }
+
+    /// Await one token. The state lock is released before sleeping so
+    /// concurrent waiters contend fairly on wake-up.
+    pub async fn acquire(&self) -> Taken {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                match state.take(self, Instant::now()) {
+                    Ok(taken) => return taken,
+                    Err(wait) => wait,
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_bounds_burst_then_refills_continuously() {
+        let bucket = TokenBucket::new(5, 10.0, 0.0);
+        let t0 = Instant::now();
+        let mut state = BucketState::new(5.0, t0);
+
+        for expected in (0..5).rev() {
+            assert_eq!(state.take(&bucket, t0).unwrap().remaining, expected);
+        }
+        // Empty: next token is 1/10 s away — no window boundary to exploit.
+        let wait = state.take(&bucket, t0).unwrap_err();
+        assert!((wait.as_secs_f64() - 0.1).abs() < 1e-9);
+
+        // 250 ms later only 2.5 tokens have accrued.
+        let later = t0 + Duration::from_millis(250);
+        assert!(state.take(&bucket, later).is_ok());
+        assert!(state.take(&bucket, later).is_ok());
+        assert!(state.take(&bucket, later).is_err());
+    }
+
+    #[test]
+    fn low_water_warning_is_edge_triggered() {
+        let bucket = TokenBucket::new(10, 10.0, 0.3);
+        let t0 = Instant::now();
+        let mut state = BucketState::new(10.0, t0);
+
+        let crossings: Vec<bool> = (0..9)
+            .map(|_| state.take(&bucket, t0).unwrap().crossed_low)
+            .collect();
+        // Fires once, on the take that leaves 2 (< 3) tokens.
+        assert_eq!(crossings.iter().filter(|c| **c).count(), 1);
+        assert!(crossings[7]);
+
+        // Refill to full re-arms it.
+        let later = t0 + Duration::from_secs(1);
+        for _ in 0..7 {
+            assert!(!state.take(&bucket, later).unwrap().crossed_low);
+        }
+        assert!(state.take(&bucket, later).unwrap().crossed_low);
+    }
+
+    #[test]
+    fn fractional_rates_are_kept_and_non_positive_ones_clamped() {
+        let t0 = Instant::now();
+        let slow = TokenBucket::new(1, 0.5, 0.0);
+        let mut state = BucketState::new(1.0, t0);
+        assert!(state.take(&slow, t0).is_ok());
+        let wait = state.take(&slow, t0).unwrap_err();
+        assert!((wait.as_secs_f64() - 2.0).abs() < 1e-9);
+
+        for rate in [0.0, -3.0, f64::NAN] {
+            let bucket = TokenBucket::new(1, rate, 0.0);
+            let mut state = BucketState::new(1.0, t0);
+            assert!(state.take(&bucket, t0).is_ok());
+            let wait = state.take(&bucket, t0).unwrap_err();
+            assert!((wait.as_secs_f64() - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[tokio::test]
+    async fn acquire_waits_for_refill() {
+        let bucket = TokenBucket::new(1, 20.0, 0.0);
+        bucket.acquire().await;
+        let start = std::time::Instant::now();
+        bucket.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,235 @@
+//! Input validation at the Tauri command boundary.
+//!
+//! A command checks its arguments through an [`Inputs`] collector and
+//! returns [`CommandError`]: every rejected argument comes back at once
+//! as a [`FieldError`] the form can pin to its input, rather than the
+//! first problem as a free-form string. `field` is the argument's name
+//! as the frontend passes it (`dateFrom`, `order.quantity`).
+//!
+//! Failures past validation (IBKR, storage) stay messages:
+//! `CommandError` converts from `String`, so `.map_err(|e| e.to_string())?`
+//! keeps working in a command that returns it.
+
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::utils::page::Page;
+use crate::utils::symbols;
+
+pub use crate::config::validation::FieldError;
+
+/// Longest accepted ticker.
+pub const MAX_SYMBOL_LEN: usize = 10;
+
+/// What a validated command rejects with. Serialized with a `kind` tag:
+/// `{ kind: "invalid", fields }` or `{ kind: "failed", message }`.
+#[derive(Error, Debug, Clone, PartialEq, Serialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum CommandError {
+    #[error("{}", summary(fields))]
+    Invalid { fields: Vec<FieldError> },
+    #[error("{message}")]
+    Failed { message: String },
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::Failed { message }
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        CommandError::Failed {
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<Vec<FieldError>> for CommandError {
+    fn from(fields: Vec<FieldError>) -> Self {
+        CommandError::Invalid { fields }
+    }
+}
+
+fn summary(fields: &[FieldError]) -> String {
+    let joined: Vec<String> = fields
+        .iter()
+        .map(|e| format!("{}: {}", e.field, e.message))
+        .collect();
+    format!("invalid input: {}", joined.join("; "))
+}
+
+/// Normalized ticker (see [`symbols::normalize`]): a letter, then
+/// letters, digits, `.` or `-` (`BRK.B`, `BAC-PL`), at most
+/// [`MAX_SYMBOL_LEN`] long.
+pub fn validate_symbol(symbol: &str) -> Result<String, String> {
+    let upper = symbols::normalize(symbol);
+    if upper.is_empty() {
+        return Err("symbol must not be empty".to_string());
+    }
+    if upper.len() > MAX_SYMBOL_LEN {
+        return Err(format!(
+            "symbol must be at most {MAX_SYMBOL_LEN} characters; got {}",
+            upper.len()
+        ));
+    }
+    let mut chars = upper.chars();
+    let first = chars.next().expect("non-empty");
+    if !first.is_ascii_uppercase() {
+        return Err(format!(
+            "symbol must start with an uppercase letter; got `{upper}`"
+        ));
+    }
+    for c in chars {
+        if !(c.is_ascii_uppercase() || c.is_ascii_digit() || c == '.' || c == '-') {
+            return Err(format!(
+                "symbol contains invalid character `{c}`; allowed: A-Z, 0-9, `.`, `-`"
+            ));
+        }
+    }
+    Ok(upper)
+}
+
+/// Collects field errors across a command's arguments. Each check
+/// returns the cleaned value (a placeholder when rejected) so the
+/// command can read its inputs in one pass and call [`Inputs::finish`]
+/// before using them.
+#[derive(Debug, Default)]
+pub struct Inputs {
+    errors: Vec<FieldError>,
+}
+
+impl Inputs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn check(&mut self, ok: bool, field: &str, message: impl Into<String>) {
+        if !ok {
+            self.errors.push(FieldError {
+                field: field.to_string(),
+                message: message.into(),
+            });
+        }
+    }
+
+    /// See [`validate_symbol`].
+    pub fn symbol(&mut self, field: &str, raw: &str) -> String {
+        validate_symbol(raw).unwrap_or_else(|e| {
+            self.check(false, field, e);
+            String::new()
+        })
+    }
+
+    /// Blank is `None`.
+    pub fn optional_symbol(&mut self, field: &str, raw: Option<&str>) -> Option<String> {
+        raw.filter(|s| !s.trim().is_empty())
+            .map(|s| self.symbol(field, s))
+    }
+
+    /// Trimmed; must not be blank.
+    pub fn text(&mut self, field: &str, raw: &str) -> String {
+        let trimmed = raw.trim();
+        self.check(!trimmed.is_empty(), field, "must not be empty");
+        trimmed.to_string()
+    }
+
+    /// Finite and above zero.
+    pub fn positive(&mut self, field: &str, value: f64) -> f64 {
+        self.check(
+            value.is_finite() && value > 0.0,
+            field,
+            format!("must be greater than 0, got {value}"),
+        );
+        value
+    }
+
+    /// `YYYY-MM-DD`.
+    pub fn date(&mut self, field: &str, raw: &str) -> Option<NaiveDate> {
+        match NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d") {
+            Ok(date) => Some(date),
+            Err(_) => {
+                self.check(false, field, format!("`{raw}` is not a YYYY-MM-DD date"));
+                None
+            }
+        }
+    }
+
+    /// `from` ≤ `to`, each `YYYY-MM-DD` and either open-ended when
+    /// `None`. An inverted range is reported on `to_field`.
+    pub fn date_range(
+        &mut self,
+        from_field: &str,
+        from: Option<&str>,
+        to_field: &str,
+        to: Option<&str>,
+    ) -> (Option<NaiveDate>, Option<NaiveDate>) {
+        let from = from.and_then(|d| self.date(from_field, d));
+        let to = to.and_then(|d| self.date(to_field, d));
+        if let (Some(f), Some(t)) = (from, to) {
+            self.check(t >= f, to_field, format!("must not be before {f}"));
+        }
+        (from, to)
+    }
+
+    /// `limit` / `offset` arguments; a zero `limit` is rejected.
+    pub fn page(&mut self, limit: Option<u32>, offset: Option<u32>) -> Page {
+        self.check(limit != Some(0), "limit", "must be greater than 0");
+        Page::new(limit, offset)
+    }
+
+    /// `Ok(())` when every check passed.
+    pub fn finish(self) -> Result<(), CommandError> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(CommandError::Invalid {
+                fields: self.errors,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_every_rejected_field() {
+        let mut inputs = Inputs::new();
+        assert_eq!(inputs.symbol("symbol", " brk b "), "BRK.B");
+        inputs.symbol("hedge", "1tech");
+        inputs.positive("order.quantity", 0.0);
+        inputs.date_range("from", Some("2026-05-04"), "to", Some("2026-05-01"));
+        inputs.date("asOf", "2026/05/04");
+        inputs.page(Some(0), None);
+        assert_eq!(inputs.optional_symbol("filter", Some("  ")), None);
+
+        let err = inputs.finish().unwrap_err();
+        let CommandError::Invalid { fields } = &err else {
+            panic!("expected field errors, got {err:?}");
+        };
+        let names: Vec<_> = fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["hedge", "order.quantity", "to", "asOf", "limit"]
+        );
+        assert_eq!(
+            serde_json::to_value(&err).unwrap()["fields"][1],
+            serde_json::json!({
+                "field": "order.quantity",
+                "message": "must be greater than 0, got 0",
+            })
+        );
+        assert!(err.to_string().starts_with("invalid input: hedge: "));
+
+        let failed = CommandError::from("not connected");
+        assert_eq!(
+            serde_json::to_value(&failed).unwrap(),
+            serde_json::json!({ "kind": "failed", "message": "not connected" })
+        );
+    }
+}
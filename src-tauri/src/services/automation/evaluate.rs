@@ -0,0 +1,383 @@
+//! One rule's evaluation: observe the condition, detect the crossing,
+//! run the action and write the audit row.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use tracing::{info, warn};
+
+use super::{
+    et_day_start, store, AutomationConfig, AutomationError, AutomationRule, CrossDirection,
+    RuleAction, RuleCondition, RuleEngine, RuleEvaluation, RuleOutcome,
+};
+use crate::events::AppEvent;
+use crate::ibkr::types::{OrderAction, OrderRequest, OrderType};
+use crate::services::paper_trading::{SimOrderRequest, SimOrderStatus, SimOrderType, SimSide};
+use crate::strategies::indicators;
+use crate::utils::helpers::unix_to_utc;
+
+/// What a condition compared on one evaluation.
+struct Observation {
+    value: f64,
+    threshold: f64,
+}
+
+/// What running an action did.
+struct Acted {
+    outcome: RuleOutcome,
+    /// Added to the audit detail after the crossing.
+    detail: Option<String>,
+    order_id: Option<i64>,
+    ticket: Option<OrderRequest>,
+}
+
+impl Acted {
+    fn new(outcome: RuleOutcome, detail: Option<String>) -> Self {
+        Self {
+            outcome,
+            detail,
+            order_id: None,
+            ticket: None,
+        }
+    }
+}
+
+impl RuleEngine {
+    pub(super) async fn evaluate(
+        &self,
+        rule: &AutomationRule,
+        prices: &mut HashMap<String, Result<f64, String>>,
+        config: &AutomationConfig,
+        now: DateTime<Utc>,
+    ) -> Result<RuleEvaluation, AutomationError> {
+        let at = now.timestamp();
+        let mut entry = RuleEvaluation {
+            id: 0,
+            rule_id: rule.id,
+            rule_name: rule.name.clone(),
+            symbol: rule.symbol.clone(),
+            action: rule.action.kind().to_string(),
+            evaluated_at: at,
+            price: None,
+            value: None,
+            threshold: None,
+            matched: None,
+            fired: false,
+            outcome: RuleOutcome::Failed,
+            detail: None,
+            order_id: None,
+            ticket: None,
+            resolved_at: None,
+        };
+        match self.observe(&rule.condition, &rule.symbol, prices).await {
+            Err(e) => entry.detail = Some(e),
+            Ok((price, observation)) => {
+                let matched = rule
+                    .condition
+                    .direction()
+                    .holds(observation.value, observation.threshold);
+                entry.price = price;
+                entry.value = Some(observation.value);
+                entry.threshold = Some(observation.threshold);
+                entry.matched = Some(matched);
+                let cooling_until = rule
+                    .last_fired_at
+                    .map(|t| t + i64::from(rule.cooldown_minutes) * 60)
+                    .filter(|until| *until > at);
+                let mut fired_at = None;
+                if !(matched && rule.last_matched == Some(false)) {
+                    entry.outcome = RuleOutcome::Idle;
+                } else if let Some(until) = cooling_until {
+                    entry.outcome = RuleOutcome::Suppressed;
+                    entry.detail = Some(format!(
+                        "crossed while cooling down until {}",
+                        unix_to_utc(until).format("%H:%M UTC")
+                    ));
+                } else {
+                    let unit = rule.condition.unit();
+                    let crossing = format!(
+                        "{} {:.2}{unit} crossed {} {}{:.2}{unit}",
+                        rule.condition.subject(&rule.symbol),
+                        observation.value,
+                        rule.condition.direction().as_str(),
+                        rule.condition.threshold_label(),
+                        observation.threshold
+                    );
+                    let acted = self
+                        .act(&rule.action, &rule.symbol, price, config, now)
+                        .await?;
+                    entry.fired = true;
+                    entry.outcome = acted.outcome;
+                    entry.detail = Some(match acted.detail {
+                        Some(detail) => format!("{crossing}; {detail}"),
+                        None => crossing,
+                    });
+                    entry.order_id = acted.order_id;
+                    entry.ticket = acted.ticket;
+                    fired_at = Some(at);
+                }
+                store::set_state(&self.db, rule.id, matched, fired_at).await?;
+            }
+        }
+
+        let evaluation = store::insert_evaluation(&self.db, entry).await?;
+        if evaluation.fired {
+            info!(
+                "automation: rule {} ({}) {}",
+                evaluation.rule_id,
+                evaluation.rule_name,
+                evaluation.outcome.as_str()
+            );
+            let event = AppEvent::RuleTriggered {
+                rule_id: evaluation.rule_id,
+                name: evaluation.rule_name.clone(),
+                symbol: evaluation.symbol.clone(),
+                action: evaluation.action.clone(),
+                outcome: evaluation.outcome.as_str().to_string(),
+                detail: evaluation.detail.clone(),
+            };
+            if let Err(e) = self.emitter.emit(event).await {
+                warn!("RuleTriggered emit failed: {e}");
+            }
+        }
+        Ok(evaluation)
+    }
+
+    /// What `condition` compares now, with the symbol's price when it
+    /// needed one. Prices are fetched once per symbol per run.
+    async fn observe(
+        &self,
+        condition: &RuleCondition,
+        symbol: &str,
+        prices: &mut HashMap<String, Result<f64, String>>,
+    ) -> Result<(Option<f64>, Observation), String> {
+        match *condition {
+            RuleCondition::Price { level, .. } => {
+                let price = self.price(symbol, prices).await?;
+                Ok((
+                    Some(price),
+                    Observation {
+                        value: price,
+                        threshold: level,
+                    },
+                ))
+            }
+            RuleCondition::Sma { period, .. } => {
+                let price = self.price(symbol, prices).await?;
+                let period = period as usize;
+                let closes = self
+                    .closes(symbol, period.saturating_sub(1), period, price)
+                    .await?;
+                let window = &closes[closes.len() - period..];
+                Ok((
+                    Some(price),
+                    Observation {
+                        value: price,
+                        threshold: window.iter().sum::<f64>() / period as f64,
+                    },
+                ))
+            }
+            RuleCondition::Rsi { period, level, .. } => {
+                let price = self.price(symbol, prices).await?;
+                let period = period as usize;
+                // Wilder smoothing settles with a few periods of history.
+                let closes = self.closes(symbol, period * 4, period + 1, price).await?;
+                let rsi = indicators::rsi(&closes, period)
+                    .ok_or_else(|| format!("no {period}-day RSI for {symbol}"))?;
+                Ok((
+                    Some(price),
+                    Observation {
+                        value: rsi,
+                        threshold: level,
+                    },
+                ))
+            }
+            RuleCondition::FairValue { direction } => {
+                let price = self.price(symbol, prices).await?;
+                let band = self
+                    .data
+                    .fair_value_band(symbol)
+                    .await?
+                    .ok_or_else(|| format!("no fair-value band for {symbol}"))?;
+                Ok((
+                    Some(price),
+                    Observation {
+                        value: price,
+                        threshold: match direction {
+                            CrossDirection::Above => band.bull_value,
+                            CrossDirection::Below => band.bear_value,
+                        },
+                    },
+                ))
+            }
+            RuleCondition::PortfolioDrawdown { level } => {
+                let drawdown = self
+                    .data
+                    .portfolio_drawdown_pct()
+                    .await
+                    .map_err(|e| format!("no portfolio drawdown: {e}"))?;
+                Ok((
+                    None,
+                    Observation {
+                        value: drawdown,
+                        threshold: level,
+                    },
+                ))
+            }
+        }
+    }
+
+    async fn price(
+        &self,
+        symbol: &str,
+        prices: &mut HashMap<String, Result<f64, String>>,
+    ) -> Result<f64, String> {
+        if !prices.contains_key(symbol) {
+            let price = self.data.last_price(symbol).await;
+            prices.insert(symbol.to_string(), price);
+        }
+        prices[symbol]
+            .clone()
+            .map_err(|e| format!("no price for {symbol}: {e}"))
+    }
+
+    /// Up to `want` completed daily closes followed by `price`; at least
+    /// `need` in all.
+    async fn closes(
+        &self,
+        symbol: &str,
+        want: usize,
+        need: usize,
+        price: f64,
+    ) -> Result<Vec<f64>, String> {
+        let mut closes = self.data.daily_closes(symbol, want).await?;
+        closes.push(price);
+        if closes.len() < need {
+            return Err(format!(
+                "{symbol} has {} daily closes, {need} needed",
+                closes.len()
+            ));
+        }
+        Ok(closes)
+    }
+
+    /// Run `action`.
+    async fn act(
+        &self,
+        action: &RuleAction,
+        symbol: &str,
+        price: Option<f64>,
+        config: &AutomationConfig,
+        now: DateTime<Utc>,
+    ) -> Result<Acted, AutomationError> {
+        match *action {
+            RuleAction::Notify => Ok(Acted::new(RuleOutcome::Notified, None)),
+            RuleAction::DryRunOrder { side, quantity } => {
+                let request = SimOrderRequest {
+                    symbol: symbol.to_string(),
+                    side,
+                    quantity,
+                    order_type: SimOrderType::Market,
+                    price: None,
+                };
+                Ok(match self.paper.place(request).await {
+                    Ok(order) => {
+                        let mut detail = format!(
+                            "sim order {} to {} {quantity} {}",
+                            order.id,
+                            side.as_str(),
+                            order.status.as_str()
+                        );
+                        if let (SimOrderStatus::Rejected, Some(reason)) =
+                            (order.status, &order.reason)
+                        {
+                            detail.push_str(&format!(": {reason}"));
+                        }
+                        Acted {
+                            order_id: Some(order.id),
+                            ..Acted::new(RuleOutcome::DryRun, Some(detail))
+                        }
+                    }
+                    Err(e) => Acted::new(RuleOutcome::Failed, Some(format!("sim order: {e}"))),
+                })
+            }
+            RuleAction::LiveOrder { side, quantity } => match price {
+                Some(price) => {
+                    self.post_ticket(symbol, side, quantity, price, config, now)
+                        .await
+                }
+                None => Ok(Acted::new(
+                    RuleOutcome::Failed,
+                    Some("no price to limit the order at".to_string()),
+                )),
+            },
+        }
+    }
+
+    /// A limit order ticket at the quote, if the risk limits allow one.
+    /// It is only recorded; the trader sends it.
+    async fn post_ticket(
+        &self,
+        symbol: &str,
+        side: SimSide,
+        quantity: f64,
+        price: f64,
+        config: &AutomationConfig,
+        now: DateTime<Utc>,
+    ) -> Result<Acted, AutomationError> {
+        let blocked = |reason: String| Ok(Acted::new(RuleOutcome::Blocked, Some(reason)));
+        if !config.live_orders {
+            return blocked("live orders are off (automation.live_orders)".to_string());
+        }
+        let notional = quantity * price;
+        if notional > config.max_order_notional {
+            return blocked(format!(
+                "{notional:.2} notional is over the {:.2} limit",
+                config.max_order_notional
+            ));
+        }
+        let posted_today = store::tickets_since(&self.db, et_day_start(now)).await?;
+        if posted_today >= config.max_live_orders_per_day {
+            return blocked(format!(
+                "{posted_today} order tickets already posted today, the limit"
+            ));
+        }
+        match self.pause.paused().await {
+            Ok(false) => {}
+            Ok(true) => return blocked("the tilt guard has trading paused".to_string()),
+            Err(e) => {
+                return Ok(Acted::new(
+                    RuleOutcome::Failed,
+                    Some(format!("tilt guard: {e}")),
+                ))
+            }
+        }
+
+        let offset = config.live_limit_offset_bps / 10_000.0;
+        let (action, limit) = match side {
+            SimSide::Buy => (OrderAction::Buy, price * (1.0 + offset)),
+            SimSide::Sell => (OrderAction::Sell, price * (1.0 - offset)),
+        };
+        let limit = (limit * 100.0).round() / 100.0;
+        Ok(Acted {
+            ticket: Some(OrderRequest {
+                symbol: symbol.to_string(),
+                action,
+                quantity,
+                order_type: OrderType::Limit,
+                price: Some(limit),
+                start_at: None,
+                algo: None,
+                route: None,
+                option: None,
+            }),
+            ..Acted::new(
+                RuleOutcome::Ticketed,
+                Some(format!(
+                    "ticket to {} {quantity} at {limit:.2} limit awaits confirmation",
+                    side.as_str()
+                )),
+            )
+        })
+    }
+}
@@ -0,0 +1,319 @@
+//! Strategy automation: rules that watch one symbol, or the portfolio,
+//! and act when a condition crosses.
+//!
+//! An [`AutomationRule`] couples a [`RuleCondition`] with a
+//! [`RuleAction`]. The condition compares the symbol's price against a
+//! level, its N-day SMA or its fair-value band
+//! (`services::fair_value_watch`), or its N-day RSI against a level;
+//! or, with no symbol, the account's drawdown from its high-water mark
+//! against a percentage (`services::drawdown`), which can only notify.
+//! The action sends a notification, places a dry-run order on the
+//! paper-trading simulator (`services::paper_trading`) or posts a live
+//! order ticket. The `automation_rules` task evaluates every enabled
+//! rule each minute against a fresh quote; [`RuleEngine::run`] does the
+//! same on demand. Both do nothing outside the regular session
+//! (`market_calendar::sessions`, so DST and early closes count).
+//!
+//! A rule fires on the crossing, not the level: the condition must have
+//! been false at the previous evaluation and be true now. A rule
+//! created (or re-enabled) while its condition already holds waits for
+//! the next cross. After firing, a rule that crosses again within its
+//! `cooldown_minutes` is `suppressed`, so a price flapping around a
+//! level doesn't fire it every minute.
+//!
+//! Nothing here sends a live order (Hard Invariant 1). A `live_order`
+//! action posts a limit order ticket, `automation.live_limit_offset_bps`
+//! through the quote, on its audit row; the trader sends it with
+//! `confirm_rule_ticket`, through the same guarded path as a manual
+//! order, or dismisses it. A ticket lapses with the ET day it was
+//! posted on. Tickets are off unless `automation.live_orders` is set,
+//! and each must clear the risk limits: notional at most
+//! `automation.max_order_notional`, at most
+//! `automation.max_live_orders_per_day` tickets across all rules (ET
+//! day), and none while the tilt guard has trading paused. A ticket
+//! refused by a limit is recorded as `blocked`.
+//!
+//! Every evaluation lands in `rule_evaluations`, the audit log: the
+//! price, the compared value and threshold, whether the rule fired, what
+//! was done and why. Rows where nothing fired are pruned after
+//! `automation.idle_retention_days`; the rest are kept. A fired rule
+//! also emits `AppEvent::RuleTriggered`, which the notifier forwards.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::America::New_York;
+use thiserror::Error;
+use tokio::sync::{Mutex, RwLock};
+
+use crate::config::AppConfig;
+use crate::events::EventEmitter;
+use crate::ibkr::types::OrderRequest;
+use crate::services::paper_trading::PaperTrader;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::market_calendar::is_market_open;
+use crate::utils::page::Page;
+
+mod evaluate;
+mod sources;
+mod store;
+mod types;
+
+#[cfg(test)]
+mod tests;
+
+pub use sources::{IbkrRuleData, TiltGuardPause};
+pub use types::{
+    AutomationConfig, AutomationRule, CrossDirection, RuleAction, RuleCondition, RuleEvaluation,
+    RuleInput, RuleOutcome, RuleRunReport,
+};
+
+/// Trait seam for what conditions read. Production is [`IbkrRuleData`];
+/// tests script it.
+#[async_trait]
+pub trait RuleMarketData: Send + Sync {
+    async fn last_price(&self, symbol: &str) -> Result<f64, String>;
+
+    /// Closes of the last `count` completed daily sessions (fewer when
+    /// there isn't that much history), oldest first.
+    async fn daily_closes(&self, symbol: &str, count: usize) -> Result<Vec<f64>, String>;
+
+    /// The band of the symbol's fair-value flag; `None` when it has no
+    /// projection.
+    async fn fair_value_band(&self, symbol: &str) -> Result<Option<FairValueLevels>, String>;
+
+    /// The current account's drawdown from its high-water mark, in
+    /// percent.
+    async fn portfolio_drawdown_pct(&self) -> Result<f64, String>;
+}
+
+/// The two edges of a fair-value band a rule can cross.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FairValueLevels {
+    pub bear_value: f64,
+    pub bull_value: f64,
+}
+
+/// Trait seam for the tilt guard. Production is [`TiltGuardPause`].
+#[async_trait]
+pub trait TradingPause: Send + Sync {
+    /// Whether the tilt guard refuses new trades right now.
+    async fn paused(&self) -> Result<bool, String>;
+}
+
+#[derive(Error, Debug)]
+pub enum AutomationError {
+    #[error("automation rule {0} not found")]
+    NotFound(i64),
+    #[error("rule evaluation {0} has no order ticket")]
+    NoTicket(i64),
+    #[error("order ticket {0} was already confirmed or dismissed")]
+    TicketClosed(i64),
+    #[error("order ticket {0} lapsed with the session it was posted in")]
+    TicketLapsed(i64),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct RuleEngine {
+    db: Arc<Db>,
+    data: Arc<dyn RuleMarketData>,
+    pause: Arc<dyn TradingPause>,
+    paper: Arc<PaperTrader>,
+    config: Arc<RwLock<AppConfig>>,
+    emitter: Arc<EventEmitter>,
+    /// One run at a time, so the task and a manual run can't both fire a
+    /// rule on the same crossing.
+    running: Mutex<()>,
+}
+
+impl RuleEngine {
+    pub fn new(
+        db: Arc<Db>,
+        data: Arc<dyn RuleMarketData>,
+        pause: Arc<dyn TradingPause>,
+        paper: Arc<PaperTrader>,
+        config: Arc<RwLock<AppConfig>>,
+        emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            db,
+            data,
+            pause,
+            paper,
+            config,
+            emitter,
+            running: Mutex::new(()),
+        }
+    }
+
+    /// Every rule, by name.
+    pub async fn list(&self) -> Result<Vec<AutomationRule>, AutomationError> {
+        Ok(store::rules(&self.db, false).await?)
+    }
+
+    pub async fn create(&self, input: RuleInput) -> Result<AutomationRule, AutomationError> {
+        let id = store::insert_rule(&self.db, &input, Utc::now().timestamp()).await?;
+        self.get(id).await
+    }
+
+    /// Replace a rule. Its crossing state starts over.
+    pub async fn update(
+        &self,
+        id: i64,
+        input: RuleInput,
+    ) -> Result<AutomationRule, AutomationError> {
+        if !store::update_rule(&self.db, id, &input, Utc::now().timestamp()).await? {
+            return Err(AutomationError::NotFound(id));
+        }
+        self.get(id).await
+    }
+
+    /// Switch a rule on or off. Turned on, its crossing state starts
+    /// over.
+    pub async fn set_enabled(
+        &self,
+        id: i64,
+        enabled: bool,
+    ) -> Result<AutomationRule, AutomationError> {
+        if !store::set_enabled(&self.db, id, enabled, Utc::now().timestamp()).await? {
+            return Err(AutomationError::NotFound(id));
+        }
+        self.get(id).await
+    }
+
+    /// Remove a rule. Its audit rows stay.
+    pub async fn delete(&self, id: i64) -> Result<bool, AutomationError> {
+        Ok(store::delete_rule(&self.db, id).await?)
+    }
+
+    /// The audit log, newest first.
+    pub async fn evaluations(
+        &self,
+        rule_id: Option<i64>,
+        fired_only: bool,
+        page: Page,
+    ) -> Result<Vec<RuleEvaluation>, AutomationError> {
+        Ok(store::evaluations(&self.db, rule_id, fired_only, page).await?)
+    }
+
+    /// Evaluate every enabled rule once, if the regular session is
+    /// open.
+    pub async fn run(&self) -> Result<RuleRunReport, AutomationError> {
+        self.run_at(Utc::now()).await
+    }
+
+    /// Order tickets posted today (ET) that await the trader, newest
+    /// first.
+    pub async fn pending_tickets(&self) -> Result<Vec<RuleEvaluation>, AutomationError> {
+        Ok(store::pending_tickets(&self.db, et_day_start(Utc::now())).await?)
+    }
+
+    /// The order of a pending ticket, for the trader to send. A ticket
+    /// lapses with the ET day it was posted on.
+    pub async fn ticket(&self, evaluation_id: i64) -> Result<OrderRequest, AutomationError> {
+        self.ticket_at(evaluation_id, Utc::now()).await
+    }
+
+    /// Record that the trader sent the ticket and IBKR took it.
+    pub async fn ticket_placed(
+        &self,
+        evaluation_id: i64,
+        order_id: i32,
+    ) -> Result<RuleEvaluation, AutomationError> {
+        self.resolve(
+            evaluation_id,
+            RuleOutcome::Placed,
+            Some(i64::from(order_id)),
+        )
+        .await
+    }
+
+    pub async fn dismiss_ticket(
+        &self,
+        evaluation_id: i64,
+    ) -> Result<RuleEvaluation, AutomationError> {
+        self.resolve(evaluation_id, RuleOutcome::Dismissed, None)
+            .await
+    }
+
+    async fn run_at(&self, now: DateTime<Utc>) -> Result<RuleRunReport, AutomationError> {
+        if is_market_open("SMART", now) == Some(false) {
+            return Ok(RuleRunReport {
+                market_closed: true,
+                ..RuleRunReport::default()
+            });
+        }
+        let _running = self.running.lock().await;
+        let config = self.config.read().await.automation.clone();
+        let mut prices: HashMap<String, Result<f64, String>> = HashMap::new();
+        let mut report = RuleRunReport::default();
+        for rule in store::rules(&self.db, true).await? {
+            let evaluation = self.evaluate(&rule, &mut prices, &config, now).await?;
+            report.evaluated += 1;
+            if evaluation.fired {
+                report.fired.push(evaluation);
+            }
+        }
+        let cutoff = now.timestamp() - i64::from(config.idle_retention_days) * 86_400;
+        store::prune_idle(&self.db, cutoff).await?;
+        Ok(report)
+    }
+
+    async fn ticket_at(
+        &self,
+        evaluation_id: i64,
+        now: DateTime<Utc>,
+    ) -> Result<OrderRequest, AutomationError> {
+        let evaluation = store::evaluation(&self.db, evaluation_id)
+            .await?
+            .ok_or(AutomationError::NoTicket(evaluation_id))?;
+        let ticket = evaluation
+            .ticket
+            .ok_or(AutomationError::NoTicket(evaluation_id))?;
+        if evaluation.outcome != RuleOutcome::Ticketed {
+            return Err(AutomationError::TicketClosed(evaluation_id));
+        }
+        if evaluation.evaluated_at < et_day_start(now) {
+            return Err(AutomationError::TicketLapsed(evaluation_id));
+        }
+        Ok(ticket)
+    }
+
+    async fn resolve(
+        &self,
+        evaluation_id: i64,
+        outcome: RuleOutcome,
+        order_id: Option<i64>,
+    ) -> Result<RuleEvaluation, AutomationError> {
+        let at = Utc::now().timestamp();
+        if !store::resolve_ticket(&self.db, evaluation_id, outcome, order_id, at).await? {
+            return Err(match store::evaluation(&self.db, evaluation_id).await? {
+                Some(e) if e.ticket.is_some() => AutomationError::TicketClosed(evaluation_id),
+                _ => AutomationError::NoTicket(evaluation_id),
+            });
+        }
+        store::evaluation(&self.db, evaluation_id)
+            .await?
+            .ok_or(AutomationError::NoTicket(evaluation_id))
+    }
+
+    async fn get(&self, id: i64) -> Result<AutomationRule, AutomationError> {
+        store::rule(&self.db, id)
+            .await?
+            .ok_or(AutomationError::NotFound(id))
+    }
+}
+
+/// Midnight of `now`'s New York date, as unix seconds.
+fn et_day_start(now: DateTime<Utc>) -> i64 {
+    let date = now.with_timezone(&New_York).date_naive();
+    New_York
+        .from_local_datetime(&date.and_time(NaiveTime::MIN))
+        .earliest()
+        .expect("New York's DST shifts skip 02:00, never midnight")
+        .timestamp()
+}
@@ -0,0 +1,121 @@
+//! Production implementations of the rule engine's seams.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use super::{FairValueLevels, RuleMarketData, TradingPause};
+use crate::services::drawdown::DrawdownService;
+use crate::services::fair_value_watch::FairValueWatcher;
+use crate::services::historical_data_service::{DailyBars, HistoricalDataService};
+use crate::services::quote_service::QuoteService;
+use crate::services::tilt_guard::TiltGuardService;
+use crate::utils::market_calendar::et_date;
+
+/// Quotes from [`QuoteService`], daily bars from the cached
+/// [`HistoricalDataService`], bands from the fair-value flags, the
+/// drawdown from [`DrawdownService`].
+pub struct IbkrRuleData {
+    quotes: Arc<QuoteService>,
+    bars: Arc<HistoricalDataService>,
+    fair_value: Arc<FairValueWatcher>,
+    drawdown: Arc<DrawdownService>,
+}
+
+impl IbkrRuleData {
+    pub fn new(
+        quotes: Arc<QuoteService>,
+        bars: Arc<HistoricalDataService>,
+        fair_value: Arc<FairValueWatcher>,
+        drawdown: Arc<DrawdownService>,
+    ) -> Self {
+        Self {
+            quotes,
+            bars,
+            fair_value,
+            drawdown,
+        }
+    }
+}
+
+#[async_trait]
+impl RuleMarketData for IbkrRuleData {
+    /// Last trade, else the prior close.
+    async fn last_price(&self, symbol: &str) -> Result<f64, String> {
+        let quote = self
+            .quotes
+            .fetch_quote(symbol)
+            .await
+            .map_err(|e| e.to_string())?;
+        quote
+            .last_price
+            .or(quote.prev_close)
+            .filter(|p| p.is_finite() && *p > 0.0)
+            .ok_or_else(|| "empty quote".to_string())
+    }
+
+    async fn daily_closes(&self, symbol: &str, count: usize) -> Result<Vec<f64>, String> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        let bars = self
+            .bars
+            .daily_bars(symbol, count)
+            .await
+            .map_err(|e| e.to_string())?;
+        // Today's bar is still forming; the caller adds the live price.
+        let today = et_date(Utc::now()).format("%Y%m%d").to_string();
+        let closes: Vec<f64> = bars
+            .iter()
+            .filter(|b| b.time.get(..8).is_some_and(|d| d < today.as_str()))
+            .map(|b| b.close)
+            .collect();
+        Ok(closes[closes.len().saturating_sub(count)..].to_vec())
+    }
+
+    async fn fair_value_band(&self, symbol: &str) -> Result<Option<FairValueLevels>, String> {
+        let flag = self
+            .fair_value
+            .get(symbol)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(flag.map(|f| FairValueLevels {
+            bear_value: f.band.bear_value,
+            bull_value: f.band.bull_value,
+        }))
+    }
+
+    async fn portfolio_drawdown_pct(&self) -> Result<f64, String> {
+        self.drawdown
+            .current_drawdown_pct()
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Asks the tilt guard about the current account.
+pub struct TiltGuardPause {
+    tilt: Arc<TiltGuardService>,
+}
+
+impl TiltGuardPause {
+    pub fn new(tilt: Arc<TiltGuardService>) -> Self {
+        Self { tilt }
+    }
+}
+
+#[async_trait]
+impl TradingPause for TiltGuardPause {
+    async fn paused(&self) -> Result<bool, String> {
+        let account = self
+            .tilt
+            .current_account()
+            .await
+            .map_err(|e| e.to_string())?;
+        self.tilt
+            .is_paused(&account)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,349 @@
+//! `automation_rules` and `rule_evaluations` reads and writes.
+
+use rusqlite::OptionalExtension;
+
+use super::{AutomationRule, RuleEvaluation, RuleInput, RuleOutcome};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::page::Page;
+
+const RULE_COLUMNS: &str = "id, name, symbol, condition, action, enabled, cooldown_minutes, \
+     last_matched, last_fired_at, created_at, updated_at";
+
+const EVALUATION_COLUMNS: &str = "id, rule_id, rule_name, symbol, action, evaluated_at, price, \
+     value, threshold, matched, fired, outcome, detail, order_id, ticket, resolved_at";
+
+fn json_column<T: serde::de::DeserializeOwned>(
+    row: &rusqlite::Row<'_>,
+    idx: usize,
+) -> rusqlite::Result<T> {
+    let raw: String = row.get(idx)?;
+    parse_json(idx, &raw)
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(idx: usize, raw: &str) -> rusqlite::Result<T> {
+    serde_json::from_str(raw).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+fn rule_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<AutomationRule> {
+    Ok(AutomationRule {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        symbol: row.get(2)?,
+        condition: json_column(row, 3)?,
+        action: json_column(row, 4)?,
+        enabled: row.get(5)?,
+        cooldown_minutes: row.get(6)?,
+        last_matched: row.get(7)?,
+        last_fired_at: row.get(8)?,
+        created_at: row.get(9)?,
+        updated_at: row.get(10)?,
+    })
+}
+
+fn evaluation_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<RuleEvaluation> {
+    let outcome: String = row.get(11)?;
+    Ok(RuleEvaluation {
+        id: row.get(0)?,
+        rule_id: row.get(1)?,
+        rule_name: row.get(2)?,
+        symbol: row.get(3)?,
+        action: row.get(4)?,
+        evaluated_at: row.get(5)?,
+        price: row.get(6)?,
+        value: row.get(7)?,
+        threshold: row.get(8)?,
+        matched: row.get(9)?,
+        fired: row.get(10)?,
+        outcome: RuleOutcome::parse(&outcome).unwrap_or(RuleOutcome::Failed),
+        detail: row.get(12)?,
+        order_id: row.get(13)?,
+        ticket: row
+            .get::<_, Option<String>>(14)?
+            .map(|raw| parse_json(14, &raw))
+            .transpose()?,
+        resolved_at: row.get(15)?,
+    })
+}
+
+/// All rules, or only the enabled ones, by name.
+pub async fn rules(db: &Db, enabled_only: bool) -> Result<Vec<AutomationRule>, StorageError> {
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {RULE_COLUMNS} FROM automation_rules \
+             WHERE (?1 = 0 OR enabled = 1) ORDER BY name COLLATE NOCASE, id"
+        ))?;
+        let rows = stmt.query_map(rusqlite::params![enabled_only], rule_from_row)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
+
+pub async fn rule(db: &Db, id: i64) -> Result<Option<AutomationRule>, StorageError> {
+    db.with_conn(move |conn| {
+        conn.query_row(
+            &format!("SELECT {RULE_COLUMNS} FROM automation_rules WHERE id = ?1"),
+            rusqlite::params![id],
+            rule_from_row,
+        )
+        .optional()
+        .map_err(StorageError::from)
+    })
+    .await
+}
+
+/// Returns the new rule's id.
+pub async fn insert_rule(db: &Db, input: &RuleInput, at: i64) -> Result<i64, StorageError> {
+    let condition = serde_json::to_string(&input.condition)?;
+    let action = serde_json::to_string(&input.action)?;
+    let input = input.clone();
+    db.with_conn(move |conn| {
+        conn.execute(
+            "INSERT INTO automation_rules \
+               (name, symbol, condition, action, enabled, cooldown_minutes, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+            rusqlite::params![
+                input.name,
+                input.symbol,
+                condition,
+                action,
+                input.enabled,
+                input.cooldown_minutes,
+                at
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    })
+    .await
+}
+
+/// Replace a rule and forget its crossing state; `false` when there is
+/// no such rule.
+pub async fn update_rule(
+    db: &Db,
+    id: i64,
+    input: &RuleInput,
+    at: i64,
+) -> Result<bool, StorageError> {
+    let condition = serde_json::to_string(&input.condition)?;
+    let action = serde_json::to_string(&input.action)?;
+    let input = input.clone();
+    db.with_conn(move |conn| {
+        let n = conn.execute(
+            "UPDATE automation_rules SET name = ?2, symbol = ?3, condition = ?4, action = ?5, \
+               enabled = ?6, cooldown_minutes = ?7, last_matched = NULL, updated_at = ?8 \
+             WHERE id = ?1",
+            rusqlite::params![
+                id,
+                input.name,
+                input.symbol,
+                condition,
+                action,
+                input.enabled,
+                input.cooldown_minutes,
+                at
+            ],
+        )?;
+        Ok(n > 0)
+    })
+    .await
+}
+
+/// Turning a rule on forgets its crossing state.
+pub async fn set_enabled(db: &Db, id: i64, enabled: bool, at: i64) -> Result<bool, StorageError> {
+    db.with_conn(move |conn| {
+        let n = conn.execute(
+            "UPDATE automation_rules SET enabled = ?2, updated_at = ?3, \
+               last_matched = CASE WHEN ?2 = 1 AND enabled = 0 THEN NULL ELSE last_matched END \
+             WHERE id = ?1",
+            rusqlite::params![id, enabled, at],
+        )?;
+        Ok(n > 0)
+    })
+    .await
+}
+
+pub async fn delete_rule(db: &Db, id: i64) -> Result<bool, StorageError> {
+    db.with_conn(move |conn| {
+        let n = conn.execute(
+            "DELETE FROM automation_rules WHERE id = ?1",
+            rusqlite::params![id],
+        )?;
+        Ok(n > 0)
+    })
+    .await
+}
+
+/// Record the condition's state and, when it fired, the time.
+pub async fn set_state(
+    db: &Db,
+    id: i64,
+    matched: bool,
+    fired_at: Option<i64>,
+) -> Result<(), StorageError> {
+    db.with_conn(move |conn| {
+        conn.execute(
+            "UPDATE automation_rules SET last_matched = ?2, \
+               last_fired_at = COALESCE(?3, last_fired_at) \
+             WHERE id = ?1",
+            rusqlite::params![id, matched, fired_at],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+/// Append `evaluation` to the audit log; returns it with its id.
+pub async fn insert_evaluation(
+    db: &Db,
+    evaluation: RuleEvaluation,
+) -> Result<RuleEvaluation, StorageError> {
+    let ticket = evaluation
+        .ticket
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()?;
+    db.with_conn(move |conn| {
+        let e = &evaluation;
+        conn.execute(
+            "INSERT INTO rule_evaluations \
+               (rule_id, rule_name, symbol, action, evaluated_at, price, value, threshold, \
+                matched, fired, outcome, detail, order_id, ticket) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            rusqlite::params![
+                e.rule_id,
+                e.rule_name,
+                e.symbol,
+                e.action,
+                e.evaluated_at,
+                e.price,
+                e.value,
+                e.threshold,
+                e.matched,
+                e.fired,
+                e.outcome.as_str(),
+                e.detail,
+                e.order_id,
+                ticket
+            ],
+        )?;
+        let id = conn.last_insert_rowid();
+        Ok(RuleEvaluation { id, ..evaluation })
+    })
+    .await
+}
+
+/// Newest first.
+pub async fn evaluations(
+    db: &Db,
+    rule_id: Option<i64>,
+    fired_only: bool,
+    page: Page,
+) -> Result<Vec<RuleEvaluation>, StorageError> {
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {EVALUATION_COLUMNS} FROM rule_evaluations \
+             WHERE (?1 IS NULL OR rule_id = ?1) AND (?2 = 0 OR fired = 1) \
+             ORDER BY id DESC LIMIT ?3 OFFSET ?4"
+        ))?;
+        let params = rusqlite::params![rule_id, fired_only, page.sql_limit(), page.offset];
+        let rows = stmt.query_map(params, evaluation_from_row)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
+
+pub async fn evaluation(db: &Db, id: i64) -> Result<Option<RuleEvaluation>, StorageError> {
+    db.with_conn(move |conn| {
+        conn.query_row(
+            &format!("SELECT {EVALUATION_COLUMNS} FROM rule_evaluations WHERE id = ?1"),
+            rusqlite::params![id],
+            evaluation_from_row,
+        )
+        .optional()
+        .map_err(StorageError::from)
+    })
+    .await
+}
+
+/// Tickets posted since `since` and not yet confirmed or dismissed,
+/// newest first.
+pub async fn pending_tickets(db: &Db, since: i64) -> Result<Vec<RuleEvaluation>, StorageError> {
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {EVALUATION_COLUMNS} FROM rule_evaluations \
+             WHERE outcome = ?1 AND evaluated_at >= ?2 ORDER BY id DESC"
+        ))?;
+        let params = rusqlite::params![RuleOutcome::Ticketed.as_str(), since];
+        let rows = stmt.query_map(params, evaluation_from_row)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
+
+/// Order tickets posted since `since`, across all rules, whatever
+/// became of them.
+pub async fn tickets_since(db: &Db, since: i64) -> Result<u32, StorageError> {
+    db.with_conn(move |conn| {
+        conn.query_row(
+            "SELECT COUNT(*) FROM rule_evaluations \
+             WHERE ticket IS NOT NULL AND evaluated_at >= ?1",
+            rusqlite::params![since],
+            |row| row.get(0),
+        )
+        .map_err(StorageError::from)
+    })
+    .await
+}
+
+/// Close a pending ticket as `outcome`; `false` when `id` is not a
+/// pending ticket.
+pub async fn resolve_ticket(
+    db: &Db,
+    id: i64,
+    outcome: RuleOutcome,
+    order_id: Option<i64>,
+    at: i64,
+) -> Result<bool, StorageError> {
+    db.with_conn(move |conn| {
+        let n = conn.execute(
+            "UPDATE rule_evaluations SET outcome = ?2, order_id = ?3, resolved_at = ?4 \
+             WHERE id = ?1 AND outcome = ?5",
+            rusqlite::params![
+                id,
+                outcome.as_str(),
+                order_id,
+                at,
+                RuleOutcome::Ticketed.as_str()
+            ],
+        )?;
+        Ok(n > 0)
+    })
+    .await
+}
+
+/// Drop evaluations before `before` where nothing fired.
+pub async fn prune_idle(db: &Db, before: i64) -> Result<usize, StorageError> {
+    db.with_conn(move |conn| {
+        let n = conn.execute(
+            "DELETE FROM rule_evaluations WHERE fired = 0 AND evaluated_at < ?1",
+            rusqlite::params![before],
+        )?;
+        Ok(n)
+    })
+    .await
+}
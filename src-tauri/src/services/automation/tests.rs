@@ -0,0 +1,392 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex as StdMutex;
+
+use tempfile::NamedTempFile;
+
+use super::*;
+use crate::events::AppEvent;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::OrderType;
+use crate::services::paper_trading::{SimPriceSource, SimSide};
+
+/// Wednesday 2026-10-14, 11:00 EDT: the regular session is open.
+fn open() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 14, 15, 0, 0).unwrap()
+}
+
+/// One scripted price for AAPL, fed to both the rules and the
+/// simulator, and a scripted portfolio drawdown.
+struct ScriptedMarket {
+    price: StdMutex<f64>,
+    closes: Vec<f64>,
+    drawdown: StdMutex<f64>,
+}
+
+impl ScriptedMarket {
+    fn set(&self, price: f64) {
+        *self.price.lock().unwrap() = price;
+    }
+}
+
+#[async_trait]
+impl RuleMarketData for ScriptedMarket {
+    async fn last_price(&self, _symbol: &str) -> Result<f64, String> {
+        Ok(*self.price.lock().unwrap())
+    }
+
+    async fn daily_closes(&self, _symbol: &str, count: usize) -> Result<Vec<f64>, String> {
+        Ok(self.closes[self.closes.len().saturating_sub(count)..].to_vec())
+    }
+
+    async fn fair_value_band(&self, _symbol: &str) -> Result<Option<FairValueLevels>, String> {
+        Ok(None)
+    }
+
+    async fn portfolio_drawdown_pct(&self) -> Result<f64, String> {
+        Ok(*self.drawdown.lock().unwrap())
+    }
+}
+
+#[async_trait]
+impl SimPriceSource for ScriptedMarket {
+    async fn last_price(&self, _symbol: &str) -> Result<f64, IbkrError> {
+        Ok(*self.price.lock().unwrap())
+    }
+}
+
+#[derive(Default)]
+struct ScriptedPause {
+    paused: AtomicBool,
+}
+
+#[async_trait]
+impl TradingPause for ScriptedPause {
+    async fn paused(&self) -> Result<bool, String> {
+        Ok(self.paused.load(Ordering::SeqCst))
+    }
+}
+
+struct Harness {
+    engine: RuleEngine,
+    market: Arc<ScriptedMarket>,
+    pause: Arc<ScriptedPause>,
+    paper: Arc<PaperTrader>,
+    config: Arc<RwLock<AppConfig>>,
+    emitter: Arc<EventEmitter>,
+}
+
+fn harness(db: Arc<Db>, closes: Vec<f64>) -> Harness {
+    let market = Arc::new(ScriptedMarket {
+        price: StdMutex::new(0.0),
+        closes,
+        drawdown: StdMutex::new(0.0),
+    });
+    let pause = Arc::new(ScriptedPause::default());
+    let config = Arc::new(RwLock::new(AppConfig::default()));
+    let emitter = Arc::new(EventEmitter::for_capture());
+    let paper = Arc::new(PaperTrader::new(
+        Arc::clone(&db),
+        market.clone(),
+        Arc::clone(&config),
+        Arc::clone(&emitter),
+    ));
+    let engine = RuleEngine::new(
+        db,
+        market.clone(),
+        pause.clone(),
+        Arc::clone(&paper),
+        Arc::clone(&config),
+        Arc::clone(&emitter),
+    );
+    Harness {
+        engine,
+        market,
+        pause,
+        paper,
+        config,
+        emitter,
+    }
+}
+
+fn rule(condition: RuleCondition, action: RuleAction, cooldown_minutes: u32) -> RuleInput {
+    RuleInput {
+        name: action.kind().to_string(),
+        symbol: "AAPL".to_string(),
+        condition,
+        action,
+        enabled: true,
+        cooldown_minutes,
+    }
+}
+
+fn price_above(level: f64) -> RuleCondition {
+    RuleCondition::Price {
+        direction: CrossDirection::Above,
+        level,
+    }
+}
+
+#[tokio::test]
+async fn rules_fire_on_the_crossing_and_then_cool_down() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let h = harness(db, Vec::new());
+
+    // Already above when created: waits for a cross.
+    h.market.set(101.0);
+    let created = h
+        .engine
+        .create(rule(price_above(100.0), RuleAction::Notify, 60))
+        .await
+        .unwrap();
+    assert!(h.engine.run_at(open()).await.unwrap().fired.is_empty());
+
+    h.market.set(99.0);
+    assert!(h.engine.run_at(open()).await.unwrap().fired.is_empty());
+    h.market.set(101.0);
+    let report = h.engine.run_at(open()).await.unwrap();
+    assert_eq!(report.evaluated, 1);
+    assert_eq!(report.fired.len(), 1);
+    let fired = &report.fired[0];
+    assert_eq!(fired.outcome, RuleOutcome::Notified);
+    assert_eq!(
+        fired.detail.as_deref(),
+        Some("AAPL price 101.00 crossed above 100.00")
+    );
+
+    // Staying above is not a cross; crossing back within the hour is
+    // suppressed.
+    h.market.set(102.0);
+    assert!(h.engine.run_at(open()).await.unwrap().fired.is_empty());
+    h.market.set(99.0);
+    h.engine.run_at(open()).await.unwrap();
+    h.market.set(101.0);
+    assert!(h.engine.run_at(open()).await.unwrap().fired.is_empty());
+
+    let log = h.engine.evaluations(None, false, Page::ALL).await.unwrap();
+    assert_eq!(log.len(), 6);
+    assert_eq!(log[0].outcome, RuleOutcome::Suppressed);
+    assert_eq!(log[0].matched, Some(true));
+    let fired_log = h
+        .engine
+        .evaluations(Some(created.id), true, Page::ALL)
+        .await
+        .unwrap();
+    assert_eq!(fired_log.len(), 1);
+    assert_eq!(fired_log[0].threshold, Some(100.0));
+
+    let triggered = h
+        .emitter
+        .captured()
+        .await
+        .into_iter()
+        .filter(|e| matches!(e, AppEvent::RuleTriggered { .. }))
+        .count();
+    assert_eq!(triggered, 1);
+
+    // The audit log outlives the rule.
+    assert!(h.engine.delete(created.id).await.unwrap());
+    assert!(h.engine.list().await.unwrap().is_empty());
+    assert_eq!(
+        h.engine
+            .evaluations(None, false, Page::ALL)
+            .await
+            .unwrap()
+            .len(),
+        6
+    );
+}
+
+#[tokio::test]
+async fn order_actions_run_on_the_simulator_or_within_the_risk_limits() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let h = harness(db, vec![90.0, 95.0, 100.0]);
+
+    let dry_run = h
+        .engine
+        .create(rule(
+            RuleCondition::Sma {
+                direction: CrossDirection::Above,
+                period: 3,
+            },
+            RuleAction::DryRunOrder {
+                side: SimSide::Buy,
+                quantity: 10.0,
+            },
+            0,
+        ))
+        .await
+        .unwrap();
+    h.engine
+        .create(rule(
+            price_above(100.0),
+            RuleAction::LiveOrder {
+                side: SimSide::Buy,
+                quantity: 10.0,
+            },
+            0,
+        ))
+        .await
+        .unwrap();
+
+    // 3-day SMA at 96 is (95 + 100 + 96) / 3 = 97: below.
+    h.market.set(96.0);
+    assert!(h.engine.run_at(open()).await.unwrap().fired.is_empty());
+
+    h.market.set(101.0);
+    let report = h.engine.run_at(open()).await.unwrap();
+    assert_eq!(report.fired.len(), 2);
+    let sim = report
+        .fired
+        .iter()
+        .find(|e| e.rule_id == dry_run.id)
+        .unwrap();
+    assert_eq!(sim.outcome, RuleOutcome::DryRun);
+    assert!(sim.detail.as_deref().unwrap().contains("filled"));
+    assert_eq!(h.paper.portfolio().await.unwrap().positions.len(), 1);
+    let live = report
+        .fired
+        .iter()
+        .find(|e| e.rule_id != dry_run.id)
+        .unwrap();
+    assert_eq!(live.outcome, RuleOutcome::Blocked);
+    assert!(live
+        .detail
+        .as_deref()
+        .unwrap()
+        .contains("live orders are off"));
+
+    async fn recross(h: &Harness) -> RuleEvaluation {
+        h.market.set(99.0);
+        h.engine.run_at(open()).await.unwrap();
+        h.market.set(101.0);
+        let mut fired = h.engine.run_at(open()).await.unwrap().fired;
+        assert_eq!(fired.len(), 1);
+        fired.remove(0)
+    }
+
+    {
+        let mut config = h.config.write().await;
+        config.automation.live_orders = true;
+        config.automation.max_order_notional = 500.0;
+    }
+    let over = recross(&h).await;
+    assert_eq!(over.outcome, RuleOutcome::Blocked);
+    assert!(over.detail.unwrap().contains("over the 500.00 limit"));
+
+    h.config.write().await.automation.max_order_notional = 5_000.0;
+    h.pause.paused.store(true, Ordering::SeqCst);
+    assert!(recross(&h).await.detail.unwrap().contains("tilt guard"));
+
+    // Nothing is sent: the rule only posts a ticket.
+    h.pause.paused.store(false, Ordering::SeqCst);
+    let posted = recross(&h).await;
+    assert_eq!(posted.outcome, RuleOutcome::Ticketed);
+    assert_eq!(posted.order_id, None);
+    let ticket = posted.ticket.clone().unwrap();
+    // 10 bps through the quote.
+    assert_eq!(ticket.price, Some(101.1));
+    assert_eq!(ticket.order_type, OrderType::Limit);
+    assert_eq!(
+        store::pending_tickets(&h.engine.db, et_day_start(open()))
+            .await
+            .unwrap(),
+        vec![posted.clone()]
+    );
+    assert_eq!(h.engine.ticket_at(posted.id, open()).await.unwrap(), ticket);
+
+    let next_day = open() + chrono::Duration::days(1);
+    assert!(matches!(
+        h.engine.ticket_at(posted.id, next_day).await,
+        Err(AutomationError::TicketLapsed(_))
+    ));
+    assert!(matches!(
+        h.engine.ticket_at(sim.id, open()).await,
+        Err(AutomationError::NoTicket(_))
+    ));
+
+    let placed = h.engine.ticket_placed(posted.id, 7).await.unwrap();
+    assert_eq!(placed.outcome, RuleOutcome::Placed);
+    assert_eq!(placed.order_id, Some(7));
+    assert!(placed.resolved_at.is_some());
+    assert!(matches!(
+        h.engine.dismiss_ticket(posted.id).await,
+        Err(AutomationError::TicketClosed(_))
+    ));
+
+    // Confirmed or not, tickets count against the daily limit.
+    h.config.write().await.automation.max_live_orders_per_day = 2;
+    let second = recross(&h).await;
+    assert_eq!(
+        h.engine.dismiss_ticket(second.id).await.unwrap().outcome,
+        RuleOutcome::Dismissed
+    );
+    let capped = recross(&h).await;
+    assert_eq!(capped.outcome, RuleOutcome::Blocked);
+    assert!(capped.detail.unwrap().contains("already posted today"));
+}
+
+#[tokio::test]
+async fn rules_rest_outside_the_regular_session() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let h = harness(db, Vec::new());
+    h.engine
+        .create(rule(price_above(100.0), RuleAction::Notify, 60))
+        .await
+        .unwrap();
+
+    // 09:10 EDT: inside the task's cron window, before the open.
+    let pre_market = Utc.with_ymd_and_hms(2026, 10, 14, 13, 10, 0).unwrap();
+    let report = h.engine.run_at(pre_market).await.unwrap();
+    assert!(report.market_closed);
+    assert_eq!(report.evaluated, 0);
+    assert!(!h.engine.run_at(open()).await.unwrap().market_closed);
+
+    // Midnight EDT is 04:00 UTC; midnight EST is 05:00 UTC.
+    assert_eq!(
+        et_day_start(open()),
+        Utc.with_ymd_and_hms(2026, 10, 14, 4, 0, 0)
+            .unwrap()
+            .timestamp()
+    );
+    assert_eq!(
+        et_day_start(Utc.with_ymd_and_hms(2026, 12, 1, 15, 0, 0).unwrap()),
+        Utc.with_ymd_and_hms(2026, 12, 1, 5, 0, 0)
+            .unwrap()
+            .timestamp()
+    );
+}
+
+#[tokio::test]
+async fn a_portfolio_drawdown_rule_fires_without_a_symbol() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let h = harness(db, Vec::new());
+    h.engine
+        .create(RuleInput {
+            symbol: String::new(),
+            ..rule(
+                RuleCondition::PortfolioDrawdown { level: 10.0 },
+                RuleAction::Notify,
+                60,
+            )
+        })
+        .await
+        .unwrap();
+
+    *h.market.drawdown.lock().unwrap() = 4.0;
+    assert!(h.engine.run_at(open()).await.unwrap().fired.is_empty());
+    *h.market.drawdown.lock().unwrap() = 12.5;
+    let report = h.engine.run_at(open()).await.unwrap();
+    assert_eq!(report.fired.len(), 1);
+    let fired = &report.fired[0];
+    assert_eq!(fired.outcome, RuleOutcome::Notified);
+    assert_eq!(fired.price, None);
+    assert_eq!(fired.value, Some(12.5));
+    assert_eq!(
+        fired.detail.as_deref(),
+        Some("portfolio drawdown 12.50% crossed above 10.00%")
+    );
+}
@@ -0,0 +1,307 @@
+//! Rule, evaluation and configuration types of the rule engine.
+
+use serde::{Deserialize, Serialize};
+
+use crate::ibkr::types::OrderRequest;
+use crate::services::paper_trading::SimSide;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationConfig {
+    /// Master switch for `live_order` actions. Off, they post no ticket
+    /// and are recorded as `blocked`.
+    #[serde(default)]
+    pub live_orders: bool,
+    /// Largest order ticket, in dollars at the quote.
+    #[serde(default = "default_max_order_notional")]
+    pub max_order_notional: f64,
+    /// Order tickets posted by all rules together per ET day, whether
+    /// confirmed, dismissed or still pending.
+    #[serde(default = "default_max_live_orders_per_day")]
+    pub max_live_orders_per_day: u32,
+    /// How far through the quote a ticket's limit is priced, in basis
+    /// points.
+    #[serde(default = "default_live_limit_offset_bps")]
+    pub live_limit_offset_bps: f64,
+    /// Days an evaluation where nothing fired stays in the audit log.
+    #[serde(default = "default_idle_retention_days")]
+    pub idle_retention_days: u32,
+}
+
+fn default_max_order_notional() -> f64 {
+    5_000.0
+}
+
+fn default_max_live_orders_per_day() -> u32 {
+    3
+}
+
+fn default_live_limit_offset_bps() -> f64 {
+    10.0
+}
+
+fn default_idle_retention_days() -> u32 {
+    30
+}
+
+impl Default for AutomationConfig {
+    fn default() -> Self {
+        Self {
+            live_orders: false,
+            max_order_notional: default_max_order_notional(),
+            max_live_orders_per_day: default_max_live_orders_per_day(),
+            live_limit_offset_bps: default_live_limit_offset_bps(),
+            idle_retention_days: default_idle_retention_days(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossDirection {
+    Above,
+    Below,
+}
+
+impl CrossDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CrossDirection::Above => "above",
+            CrossDirection::Below => "below",
+        }
+    }
+
+    pub(super) fn holds(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            CrossDirection::Above => value > threshold,
+            CrossDirection::Below => value < threshold,
+        }
+    }
+}
+
+/// Indicators are on daily closes, today counting at the current price.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RuleCondition {
+    /// The price crosses `level`.
+    Price {
+        direction: CrossDirection,
+        level: f64,
+    },
+    /// The price crosses its `period`-day simple moving average.
+    Sma {
+        direction: CrossDirection,
+        period: u32,
+    },
+    /// The `period`-day RSI crosses `level`.
+    Rsi {
+        direction: CrossDirection,
+        period: u32,
+        level: f64,
+    },
+    /// The price crosses above the bull value or below the bear value.
+    FairValue { direction: CrossDirection },
+    /// The account's drawdown from its high-water mark exceeds `level`
+    /// percent. Not tied to a symbol.
+    PortfolioDrawdown { level: f64 },
+}
+
+impl RuleCondition {
+    pub fn direction(&self) -> CrossDirection {
+        match *self {
+            RuleCondition::Price { direction, .. }
+            | RuleCondition::Sma { direction, .. }
+            | RuleCondition::Rsi { direction, .. }
+            | RuleCondition::FairValue { direction } => direction,
+            RuleCondition::PortfolioDrawdown { .. } => CrossDirection::Above,
+        }
+    }
+
+    /// What is compared, for the audit detail.
+    pub(super) fn subject(&self, symbol: &str) -> String {
+        match self {
+            RuleCondition::Rsi { period, .. } => format!("{symbol} {period}-day RSI"),
+            RuleCondition::PortfolioDrawdown { .. } => "portfolio drawdown".to_string(),
+            _ => format!("{symbol} price"),
+        }
+    }
+
+    /// Suffix of the compared value and threshold, for the audit detail.
+    pub(super) fn unit(&self) -> &'static str {
+        match self {
+            RuleCondition::PortfolioDrawdown { .. } => "%",
+            _ => "",
+        }
+    }
+
+    /// What it is compared against, for the audit detail.
+    pub(super) fn threshold_label(&self) -> String {
+        match self {
+            RuleCondition::Price { .. }
+            | RuleCondition::Rsi { .. }
+            | RuleCondition::PortfolioDrawdown { .. } => String::new(),
+            RuleCondition::Sma { period, .. } => format!("{period}-day SMA "),
+            RuleCondition::FairValue {
+                direction: CrossDirection::Above,
+            } => "bull value ".to_string(),
+            RuleCondition::FairValue {
+                direction: CrossDirection::Below,
+            } => "bear value ".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RuleAction {
+    /// Emit [`AppEvent::RuleTriggered`] and nothing else.
+    Notify,
+    /// A market order on the paper-trading simulator.
+    DryRunOrder { side: SimSide, quantity: f64 },
+    /// A limit order ticket for IBKR, within the `automation` risk
+    /// limits. Nothing is sent until the trader confirms it.
+    LiveOrder { side: SimSide, quantity: f64 },
+}
+
+impl RuleAction {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RuleAction::Notify => "notify",
+            RuleAction::DryRunOrder { .. } => "dry_run_order",
+            RuleAction::LiveOrder { .. } => "live_order",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleInput {
+    pub name: String,
+    /// Empty for a portfolio drawdown rule.
+    pub symbol: String,
+    pub condition: RuleCondition,
+    pub action: RuleAction,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_cooldown_minutes")]
+    pub cooldown_minutes: u32,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_cooldown_minutes() -> u32 {
+    60
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationRule {
+    pub id: i64,
+    pub name: String,
+    pub symbol: String,
+    pub condition: RuleCondition,
+    pub action: RuleAction,
+    pub enabled: bool,
+    pub cooldown_minutes: u32,
+    /// The condition at the last evaluation; `None` before the first.
+    pub last_matched: Option<bool>,
+    /// Unix seconds, as are the other timestamps.
+    pub last_fired_at: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleOutcome {
+    /// No crossing.
+    Idle,
+    /// Crossed within the cooldown.
+    Suppressed,
+    Notified,
+    /// The simulator took the order (it may still have rejected it).
+    DryRun,
+    /// A live order ticket awaits the trader's confirmation.
+    Ticketed,
+    /// The trader confirmed the ticket and IBKR took the order.
+    Placed,
+    /// The trader dismissed the ticket.
+    Dismissed,
+    /// A risk limit refused the ticket.
+    Blocked,
+    /// Data or the action failed.
+    Failed,
+}
+
+impl RuleOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleOutcome::Idle => "idle",
+            RuleOutcome::Suppressed => "suppressed",
+            RuleOutcome::Notified => "notified",
+            RuleOutcome::DryRun => "dry_run",
+            RuleOutcome::Ticketed => "ticketed",
+            RuleOutcome::Placed => "placed",
+            RuleOutcome::Dismissed => "dismissed",
+            RuleOutcome::Blocked => "blocked",
+            RuleOutcome::Failed => "failed",
+        }
+    }
+
+    pub(super) fn parse(s: &str) -> Option<Self> {
+        match s {
+            "idle" => Some(RuleOutcome::Idle),
+            "suppressed" => Some(RuleOutcome::Suppressed),
+            "notified" => Some(RuleOutcome::Notified),
+            "dry_run" => Some(RuleOutcome::DryRun),
+            "ticketed" => Some(RuleOutcome::Ticketed),
+            "placed" => Some(RuleOutcome::Placed),
+            "dismissed" => Some(RuleOutcome::Dismissed),
+            "blocked" => Some(RuleOutcome::Blocked),
+            "failed" => Some(RuleOutcome::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// One row of the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleEvaluation {
+    pub id: i64,
+    pub rule_id: i64,
+    pub rule_name: String,
+    pub symbol: String,
+    /// [`RuleAction::kind`] at the time.
+    pub action: String,
+    pub evaluated_at: i64,
+    pub price: Option<f64>,
+    /// What the condition compared: the price, the RSI or the
+    /// drawdown.
+    pub value: Option<f64>,
+    pub threshold: Option<f64>,
+    /// `None` when the data to decide was missing.
+    pub matched: Option<bool>,
+    /// The condition crossed outside the cooldown and the action ran.
+    pub fired: bool,
+    pub outcome: RuleOutcome,
+    pub detail: Option<String>,
+    /// Sim order id for a dry run, IBKR order id for a confirmed
+    /// ticket.
+    pub order_id: Option<i64>,
+    /// The order a `live_order` action proposes. Only the
+    /// `confirm_rule_ticket` command sends it, when the trader confirms.
+    pub ticket: Option<OrderRequest>,
+    /// When the trader confirmed or dismissed the ticket.
+    pub resolved_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleRunReport {
+    /// Nothing was evaluated: the regular session isn't open.
+    pub market_closed: bool,
+    pub evaluated: usize,
+    pub fired: Vec<RuleEvaluation>,
+}
@@ -61,7 +61,7 @@ impl BarsReader for DbBarsReader {
                 let mut stmt = conn.prepare(
                     "SELECT bar_time, open, high, low, close, volume, wap \
                      FROM bars_cache \
-                     WHERE symbol = ?1 AND bar_size = ?2 \
+                     WHERE symbol = ?1 AND bar_size = ?2 AND what_to_show = 'trades' \
                        AND bar_time >= ?3 AND bar_time <= ?4 \
                      ORDER BY bar_time ASC",
                 )?;
@@ -0,0 +1,403 @@
+//! Benchmark-relative performance of holdings and watchlist entries.
+//!
+//! A symbol is tagged with the ETF it should be measured against (QQQ,
+//! SPY, a sector ETF) in `benchmark_tags`. [`BenchmarkService::report`]
+//! compares each symbol's return with its benchmark's over the
+//! `benchmarks.windows` (in sessions) from cached daily bars, and ranks
+//! the rows by the relative return over `benchmarks.rank_window`, worst
+//! first, so the holdings lagging their benchmark lead the report.
+//!
+//! The report covers the account's open stock positions, tagged or
+//! not (an untagged one is measured against
+//! `benchmarks.default_benchmark`, or left out without one), and the
+//! tagged watchlist entries. Returns are close to close over the
+//! sessions both series have, so a holiday in one doesn't skew the
+//! other; a window longer than the shared history is left empty.
+//! Relative returns are in percentage points: -4.0 is four points
+//! behind the benchmark.
+//!
+//! A position held in another currency than `fx.base_currency` also
+//! gets its return split in two: the local price return from its own
+//! bars and the currency return from daily FX closes
+//! ([`FxHistory`]) on the same two dates, compounding to the return in
+//! the base currency. Its relative return is then the base-currency
+//! return against the benchmark's, and left empty without the rates.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use crate::config::AppConfig;
+use crate::ibkr::error::IbkrError;
+use crate::services::fx_service::FxHistory;
+use crate::services::historical_data_service::DailyBars;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+use crate::services::tracker_service::{TrackerError, TrackerService};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+mod store;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkConfig {
+    /// Benchmark of an untagged stock position; `None` leaves those out
+    /// of the report.
+    #[serde(default = "default_benchmark")]
+    pub default_benchmark: Option<String>,
+    /// Windows the relative return is measured over, in sessions.
+    #[serde(default = "default_windows")]
+    pub windows: Vec<u32>,
+    /// The window the report is ranked on; one of `windows`.
+    #[serde(default = "default_rank_window")]
+    pub rank_window: u32,
+}
+
+fn default_benchmark() -> Option<String> {
+    Some("SPY".to_string())
+}
+
+fn default_windows() -> Vec<u32> {
+    vec![21, 63, 126, 252]
+}
+
+fn default_rank_window() -> u32 {
+    63
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            default_benchmark: default_benchmark(),
+            windows: default_windows(),
+            rank_window: default_rank_window(),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum BenchmarkError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+    #[error("watchlist: {0}")]
+    Watchlist(#[from] TrackerError),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkTag {
+    pub symbol: String,
+    pub benchmark: String,
+    /// Unix seconds.
+    pub updated_at: i64,
+}
+
+/// Returns over one window, in percent; `None` without the history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelativeWindow {
+    pub sessions: u32,
+    /// In the symbol's own currency.
+    pub return_pct: Option<f64>,
+    /// The currency's move against the base currency; `None` for a
+    /// base-currency row.
+    pub fx_return_pct: Option<f64>,
+    /// `return_pct` and `fx_return_pct` compounded; `return_pct` for a
+    /// base-currency row.
+    pub base_return_pct: Option<f64>,
+    pub benchmark_return_pct: Option<f64>,
+    /// `base_return_pct` less `benchmark_return_pct`, in percentage
+    /// points.
+    pub relative_pct: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkRow {
+    pub symbol: String,
+    pub benchmark: String,
+    /// The benchmark is the symbol's tag rather than the default.
+    pub tagged: bool,
+    /// An open stock position.
+    pub held: bool,
+    /// On the watchlist.
+    pub watchlist: bool,
+    /// A held position's currency when it isn't the base currency.
+    pub currency: Option<String>,
+    /// One per configured window, shortest first.
+    pub windows: Vec<RelativeWindow>,
+    /// The relative return over the rank window.
+    pub relative_pct: Option<f64>,
+    /// Behind the benchmark over the rank window.
+    pub lagging: bool,
+    /// Why the returns are missing, when the bars couldn't be fetched.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkReport {
+    pub account: String,
+    pub at: i64,
+    pub base_currency: String,
+    pub rank_window: u32,
+    /// Worst relative return first; rows without one last.
+    pub rows: Vec<BenchmarkRow>,
+}
+
+pub struct BenchmarkService {
+    db: Arc<Db>,
+    account: Arc<dyn AccountSource>,
+    positions: Arc<dyn OpenPositionsSource>,
+    watchlist: Arc<TrackerService>,
+    bars: Arc<dyn DailyBars>,
+    fx: Arc<dyn FxHistory>,
+    config: Arc<RwLock<AppConfig>>,
+}
+
+impl BenchmarkService {
+    pub fn new(
+        db: Arc<Db>,
+        account: Arc<dyn AccountSource>,
+        positions: Arc<dyn OpenPositionsSource>,
+        watchlist: Arc<TrackerService>,
+        bars: Arc<dyn DailyBars>,
+        fx: Arc<dyn FxHistory>,
+        config: Arc<RwLock<AppConfig>>,
+    ) -> Self {
+        Self {
+            db,
+            account,
+            positions,
+            watchlist,
+            bars,
+            fx,
+            config,
+        }
+    }
+
+    /// Every tag, by symbol.
+    pub async fn tags(&self) -> Result<Vec<BenchmarkTag>, BenchmarkError> {
+        Ok(store::list(&self.db).await?)
+    }
+
+    /// Tag `symbol` with `benchmark`, replacing any tag it had.
+    pub async fn set_tag(
+        &self,
+        symbol: &str,
+        benchmark: &str,
+    ) -> Result<BenchmarkTag, BenchmarkError> {
+        let tag = BenchmarkTag {
+            symbol: symbol.trim().to_uppercase(),
+            benchmark: benchmark.trim().to_uppercase(),
+            updated_at: Utc::now().timestamp(),
+        };
+        store::upsert(&self.db, &tag).await?;
+        Ok(tag)
+    }
+
+    /// Whether `symbol` had a tag to remove.
+    pub async fn clear_tag(&self, symbol: &str) -> Result<bool, BenchmarkError> {
+        Ok(store::delete(&self.db, &symbol.trim().to_uppercase()).await?)
+    }
+
+    /// Relative returns of the open stock positions of `account` (the
+    /// current account when `None`) and the tagged watchlist entries,
+    /// worst first.
+    pub async fn report(&self, account: Option<String>) -> Result<BenchmarkReport, BenchmarkError> {
+        let (config, base) = {
+            let config = self.config.read().await;
+            (
+                config.benchmarks.clone(),
+                config.fx.base_currency.trim().to_uppercase(),
+            )
+        };
+        let account = match account.filter(|a| !a.trim().is_empty()) {
+            Some(a) => a.trim().to_string(),
+            None => self.account.current_account().await?,
+        };
+        let tags: HashMap<String, String> = store::list(&self.db)
+            .await?
+            .into_iter()
+            .map(|t| (t.symbol, t.benchmark))
+            .collect();
+
+        // Symbol -> (held, on the watchlist).
+        let mut symbols: BTreeMap<String, (bool, bool)> = BTreeMap::new();
+        // Held symbols traded in another currency than `base`.
+        let mut currencies: HashMap<String, String> = HashMap::new();
+        for position in self.positions.list_open(&account).await? {
+            if position.contract_type == "STK" && position.position != 0.0 {
+                let symbol = position.symbol.to_uppercase();
+                let currency = position.currency.trim().to_uppercase();
+                if !currency.is_empty() && currency != base {
+                    currencies.insert(symbol.clone(), currency);
+                }
+                symbols.entry(symbol).or_default().0 = true;
+            }
+        }
+        for ticker in self.watchlist.list(None).await? {
+            let symbol = ticker.symbol.to_uppercase();
+            if tags.contains_key(&symbol) || symbols.contains_key(&symbol) {
+                symbols.entry(symbol).or_default().1 = true;
+            }
+        }
+
+        let mut windows = config.windows.clone();
+        windows.sort_unstable();
+        windows.dedup();
+        let sessions = windows.last().map_or(0, |w| *w as usize + 1);
+        let mut closes: HashMap<String, Result<Vec<(String, f64)>, String>> = HashMap::new();
+        let mut rates: HashMap<String, Result<Vec<(String, f64)>, String>> = HashMap::new();
+        let mut rows = Vec::new();
+        for (symbol, (held, watchlist)) in symbols {
+            let (benchmark, tagged) = match tags.get(&symbol) {
+                Some(b) => (b.clone(), true),
+                None => match &config.default_benchmark {
+                    Some(b) if held => (b.trim().to_uppercase(), false),
+                    _ => continue,
+                },
+            };
+            if benchmark == symbol {
+                continue;
+            }
+            for s in [&symbol, &benchmark] {
+                if !closes.contains_key(s) {
+                    // Keyed by `YYYYMMDD`, like the FX rates below.
+                    let fetched = self
+                        .bars
+                        .daily_closes(s, sessions)
+                        .await
+                        .map(|series| {
+                            series
+                                .into_iter()
+                                .filter_map(|(t, c)| Some((t.get(..8)?.to_string(), c)))
+                                .collect()
+                        })
+                        .map_err(|e| format!("{s} bars: {e}"));
+                    closes.insert(s.clone(), fetched);
+                }
+            }
+            let currency = currencies.get(&symbol).cloned();
+            if let Some(c) = &currency {
+                if !rates.contains_key(c) {
+                    let fetched = self
+                        .fx
+                        .daily_rates(c, &base)
+                        .await
+                        .map(|series| {
+                            series
+                                .into_iter()
+                                .map(|(d, r)| (d.format("%Y%m%d").to_string(), r))
+                                .collect()
+                        })
+                        .map_err(|e| format!("{c}/{base} rates: {e}"));
+                    rates.insert(c.clone(), fetched);
+                }
+            }
+            let fx = match &currency {
+                Some(c) => rates[c].as_deref().map(Some),
+                None => Ok(None),
+            };
+            let (measured, error) = match (&closes[&symbol], &closes[&benchmark], fx) {
+                (Ok(own), Ok(bench), Ok(fx)) => (relative(own, bench, fx, &windows), None),
+                // Local returns only; nothing to compare in the base currency.
+                (Ok(own), Ok(bench), Err(e)) => {
+                    (relative(own, bench, Some(&[]), &windows), Some(e.clone()))
+                }
+                (Err(e), _, _) | (_, Err(e), _) => {
+                    (relative(&[], &[], None, &windows), Some(e.clone()))
+                }
+            };
+            let relative_pct = measured
+                .iter()
+                .find(|w| w.sessions == config.rank_window)
+                .and_then(|w| w.relative_pct);
+            rows.push(BenchmarkRow {
+                symbol,
+                benchmark,
+                tagged,
+                held,
+                watchlist,
+                currency,
+                windows: measured,
+                relative_pct,
+                lagging: relative_pct.is_some_and(|r| r < 0.0),
+                error,
+            });
+        }
+        rows.sort_by(|a, b| match (a.relative_pct, b.relative_pct) {
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a.symbol.cmp(&b.symbol),
+        });
+        Ok(BenchmarkReport {
+            account,
+            at: Utc::now().timestamp(),
+            base_currency: base,
+            rank_window: config.rank_window,
+            rows,
+        })
+    }
+}
+
+/// Close-to-close returns of `own` and `bench` over each window of
+/// `windows` sessions, on the dates both have. With `fx` (`(YYYYMMDD,
+/// rate)`, oldest first) `own` is in another currency, converted at the
+/// last rate on or before each end of the window.
+fn relative(
+    own: &[(String, f64)],
+    bench: &[(String, f64)],
+    fx: Option<&[(String, f64)]>,
+    windows: &[u32],
+) -> Vec<RelativeWindow> {
+    let bench: HashMap<&str, f64> = bench.iter().map(|(d, c)| (d.as_str(), *c)).collect();
+    let shared: Vec<(&str, f64, f64)> = own
+        .iter()
+        .filter_map(|(d, c)| Some((d.as_str(), *c, *bench.get(d.as_str())?)))
+        .filter(|(_, a, b)| *a > 0.0 && *b > 0.0)
+        .collect();
+    let rate_on = |rates: &[(String, f64)], date: &str| {
+        let i = rates.partition_point(|(d, _)| d.as_str() <= date);
+        (i > 0).then(|| rates[i - 1].1)
+    };
+    let pct = |growth: f64| (growth - 1.0) * 100.0;
+    windows
+        .iter()
+        .map(|&sessions| {
+            let n = sessions as usize;
+            // Growth factors: own, the benchmark's, the currency's.
+            let growth = (shared.len() > n).then(|| {
+                let (then, own_then, bench_then) = shared[shared.len() - 1 - n];
+                let (now, own_now, bench_now) = shared[shared.len() - 1];
+                let currency = fx.map(|rates| Some(rate_on(rates, now)? / rate_on(rates, then)?));
+                (own_now / own_then, bench_now / bench_then, currency)
+            });
+            let base = growth.and_then(|(own, _, currency)| match currency {
+                None => Some(own),
+                Some(currency) => currency.map(|c| own * c),
+            });
+            let benchmark_return_pct = growth.map(|g| pct(g.1));
+            RelativeWindow {
+                sessions,
+                return_pct: growth.map(|g| pct(g.0)),
+                fx_return_pct: growth.and_then(|g| g.2.flatten()).map(pct),
+                base_return_pct: base.map(pct),
+                benchmark_return_pct,
+                relative_pct: base
+                    .zip(benchmark_return_pct)
+                    .map(|(b, bench)| pct(b) - bench),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,57 @@
+//! `benchmark_tags` reads and writes.
+
+use super::BenchmarkTag;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<BenchmarkTag> {
+    Ok(BenchmarkTag {
+        symbol: row.get(0)?,
+        benchmark: row.get(1)?,
+        updated_at: row.get(2)?,
+    })
+}
+
+/// Every tag, by symbol.
+pub async fn list(db: &Db) -> Result<Vec<BenchmarkTag>, StorageError> {
+    db.with_conn(|conn| {
+        let mut stmt = conn
+            .prepare("SELECT symbol, benchmark, updated_at FROM benchmark_tags ORDER BY symbol")?;
+        let rows = stmt.query_map([], from_row)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
+
+pub async fn upsert(db: &Db, tag: &BenchmarkTag) -> Result<(), StorageError> {
+    let tag = tag.clone();
+    db.with_conn(move |conn| {
+        conn.execute(
+            "INSERT INTO benchmark_tags (symbol, benchmark, updated_at) \
+             VALUES (?1, ?2, ?3) \
+             ON CONFLICT(symbol) DO UPDATE SET \
+               benchmark = excluded.benchmark, \
+               updated_at = excluded.updated_at",
+            rusqlite::params![tag.symbol, tag.benchmark, tag.updated_at],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+/// Whether there was a tag to remove.
+pub async fn delete(db: &Db, symbol: &str) -> Result<bool, StorageError> {
+    let symbol = symbol.to_string();
+    db.with_conn(move |conn| {
+        let n = conn.execute(
+            "DELETE FROM benchmark_tags WHERE symbol = ?1",
+            rusqlite::params![symbol],
+        )?;
+        Ok(n > 0)
+    })
+    .await
+}
@@ -0,0 +1,243 @@
+use tempfile::NamedTempFile;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use super::*;
+use crate::ibkr::types::tracker::TrackerSource;
+use crate::ibkr::types::{HistoricalBar, Position};
+use crate::services::fx_service::FxError;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Closes per symbol, a day apart from 2026-03-02; `None` skips the
+/// date.
+struct ScriptedBars(HashMap<&'static str, Vec<Option<f64>>>);
+
+#[async_trait]
+impl DailyBars for ScriptedBars {
+    async fn daily_bars(
+        &self,
+        symbol: &str,
+        _sessions: usize,
+    ) -> Result<Vec<HistoricalBar>, IbkrError> {
+        let closes = self
+            .0
+            .get(symbol)
+            .ok_or_else(|| IbkrError::RequestFailed(format!("no bars for {symbol}")))?;
+        Ok(closes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| Some(bar(format!("202603{:02}", i + 2), (*c)?)))
+            .collect())
+    }
+}
+
+fn bar(time: String, close: f64) -> HistoricalBar {
+    HistoricalBar {
+        time,
+        open: close,
+        high: close,
+        low: close,
+        close,
+        volume: 0,
+        wap: close,
+        count: 0,
+    }
+}
+
+/// Daily rates into USD, by currency, dated 2026-03-DD.
+struct ScriptedFx(HashMap<&'static str, Vec<(u32, f64)>>);
+
+#[async_trait]
+impl FxHistory for ScriptedFx {
+    async fn daily_rates(&self, from: &str, to: &str) -> Result<Vec<(NaiveDate, f64)>, FxError> {
+        let rates = self
+            .0
+            .get(from)
+            .filter(|_| to == "USD")
+            .ok_or_else(|| FxError::NoRate {
+                from: from.to_string(),
+                to: to.to_string(),
+            })?;
+        Ok(rates
+            .iter()
+            .map(|&(d, r)| (NaiveDate::from_ymd_opt(2026, 3, d).unwrap(), r))
+            .collect())
+    }
+}
+
+fn position(symbol: &str, kind: &str) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: 10.0,
+        contract_type: kind.to_string(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn holdings_rank_by_their_return_against_the_benchmark() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let watchlist = Arc::new(TrackerService::new(Arc::clone(&db)));
+    for symbol in ["NVDA", "TSLA"] {
+        watchlist
+            .add(symbol, TrackerSource::Manual, None, vec![], None)
+            .await
+            .unwrap();
+    }
+    let mut config = AppConfig::default();
+    config.benchmarks.windows = vec![4, 2];
+    config.benchmarks.rank_window = 2;
+    let s = Some;
+    let service = BenchmarkService::new(
+        db,
+        Arc::new(FixedAccount),
+        Arc::new(StubPositions(vec![
+            position("AAPL", "STK"),
+            position("MSFT", "STK"),
+            position("SPY", "OPT"),
+        ])),
+        watchlist,
+        Arc::new(ScriptedBars(HashMap::from([
+            // AAPL has a date SPY lacks; it is skipped.
+            (
+                "SPY",
+                vec![None, s(100.0), s(100.0), s(100.0), s(100.0), s(110.0)],
+            ),
+            (
+                "AAPL",
+                vec![s(50.0), s(100.0), s(100.0), s(100.0), s(100.0), s(105.0)],
+            ),
+            ("QQQ", vec![s(100.0); 6]),
+            (
+                "MSFT",
+                vec![None, s(100.0), s(100.0), s(100.0), s(100.0), s(120.0)],
+            ),
+            ("SMH", vec![s(100.0); 6]),
+            ("NVDA", vec![None, None, None, None, s(100.0), s(90.0)]),
+        ]))),
+        Arc::new(ScriptedFx(HashMap::new())),
+        Arc::new(RwLock::new(config)),
+    );
+    service.set_tag("msft", "qqq").await.unwrap();
+    service.set_tag("NVDA", "SMH").await.unwrap();
+    service.set_tag("IBM", "SPY").await.unwrap();
+    assert!(service.clear_tag("IBM").await.unwrap());
+    let tags = service.tags().await.unwrap();
+    assert_eq!(tags.len(), 2);
+    assert_eq!(tags[0].symbol, "MSFT");
+    assert_eq!(tags[0].benchmark, "QQQ");
+
+    let report = service.report(None).await.unwrap();
+    assert_eq!(report.account, "DU1");
+    assert_eq!(report.rank_window, 2);
+    // Untagged TSLA is only on the watchlist: left out.
+    let symbols: Vec<&str> = report.rows.iter().map(|r| r.symbol.as_str()).collect();
+    assert_eq!(symbols, ["AAPL", "MSFT", "NVDA"], "worst first");
+
+    let aapl = &report.rows[0];
+    assert_eq!(aapl.benchmark, "SPY");
+    assert!(!aapl.tagged && aapl.held && !aapl.watchlist);
+    assert_eq!(aapl.windows.len(), 2);
+    assert_eq!(aapl.windows[0].sessions, 2);
+    assert!((aapl.windows[0].return_pct.unwrap() - 5.0).abs() < 1e-9);
+    assert!((aapl.windows[1].benchmark_return_pct.unwrap() - 10.0).abs() < 1e-9);
+    assert!((aapl.relative_pct.unwrap() + 5.0).abs() < 1e-9);
+    assert!(aapl.lagging);
+
+    let msft = &report.rows[1];
+    assert!(msft.tagged);
+    assert!((msft.relative_pct.unwrap() - 20.0).abs() < 1e-9);
+    assert!(!msft.lagging);
+
+    // Two shared sessions are not enough for a 2-session return.
+    let nvda = &report.rows[2];
+    assert!(nvda.watchlist && !nvda.held);
+    assert_eq!(nvda.relative_pct, None);
+    assert_eq!(nvda.error, None);
+}
+
+#[tokio::test]
+async fn foreign_holdings_split_their_return_into_price_and_currency() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let mut config = AppConfig::default();
+    config.benchmarks.windows = vec![2];
+    config.benchmarks.rank_window = 2;
+    let held = |symbol: &str, currency: &str| Position {
+        currency: currency.to_string(),
+        ..position(symbol, "STK")
+    };
+    let s = Some;
+    let service = BenchmarkService::new(
+        Arc::clone(&db),
+        Arc::new(FixedAccount),
+        Arc::new(StubPositions(vec![
+            held("AAPL", "USD"),
+            held("ASML", "EUR"),
+            held("NESN", "CHF"),
+        ])),
+        Arc::new(TrackerService::new(db)),
+        Arc::new(ScriptedBars(HashMap::from([
+            ("SPY", vec![s(100.0); 6]),
+            ("AAPL", vec![s(100.0); 6]),
+            (
+                "ASML",
+                vec![s(100.0), s(100.0), s(100.0), s(100.0), s(100.0), s(110.0)],
+            ),
+            ("NESN", vec![s(100.0); 6]),
+        ]))),
+        // No rate on the 7th: the 6th's stands in.
+        Arc::new(ScriptedFx(HashMap::from([(
+            "EUR",
+            vec![(4, 1.0), (5, 1.0), (6, 1.1)],
+        )]))),
+        Arc::new(RwLock::new(config)),
+    );
+
+    let report = service.report(None).await.unwrap();
+    assert_eq!(report.base_currency, "USD");
+    let rows: HashMap<&str, &BenchmarkRow> =
+        report.rows.iter().map(|r| (r.symbol.as_str(), r)).collect();
+
+    let aapl = rows["AAPL"];
+    assert_eq!(aapl.currency, None);
+    assert_eq!(aapl.windows[0].fx_return_pct, None);
+    assert_eq!(aapl.windows[0].base_return_pct, Some(0.0));
+
+    // 10% in euros, and the euro up 10%: 21% in dollars.
+    let asml = rows["ASML"];
+    assert_eq!(asml.currency.as_deref(), Some("EUR"));
+    let w = &asml.windows[0];
+    assert!((w.return_pct.unwrap() - 10.0).abs() < 1e-9);
+    assert!((w.fx_return_pct.unwrap() - 10.0).abs() < 1e-9);
+    assert!((w.base_return_pct.unwrap() - 21.0).abs() < 1e-9);
+    assert!((asml.relative_pct.unwrap() - 21.0).abs() < 1e-9);
+    assert_eq!(report.rows[0].symbol, "AAPL", "worst first");
+
+    // Without franc rates the local return stands alone, unranked.
+    let nesn = rows["NESN"];
+    assert_eq!(nesn.windows[0].return_pct, Some(0.0));
+    assert_eq!(nesn.windows[0].base_return_pct, None);
+    assert_eq!(nesn.relative_pct, None);
+    assert!(nesn.error.as_deref().unwrap().starts_with("CHF/USD rates"));
+}
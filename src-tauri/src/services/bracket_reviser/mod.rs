@@ -125,8 +125,10 @@ pub trait QuoteSource: Send + Sync {
 
 /// Production `QuoteSource` that pulls a live snapshot via the shared
 /// `QuoteService`. Both `extreme_price` and `current_price` come from
-/// `Quote.last_price`; the reviser's running high-water-mark
-/// accumulates the intraday extreme across polls. Master gotcha:
+/// `Quote::regular_price` — stops only trigger in the regular session,
+/// so pre/post-market prints must not drag the trail. The reviser's
+/// running high-water-mark accumulates the intraday extreme across
+/// polls. Master gotcha:
 /// gap-throughs are accepted (the chandelier can't move a stop the
 /// market has already crossed).
 pub struct IbkrQuoteSource {
@@ -143,7 +145,7 @@ impl IbkrQuoteSource {
 impl QuoteSource for IbkrQuoteSource {
     async fn observe(&self, symbol: &str) -> std::result::Result<PriceObservation, IbkrError> {
         let q = self.quotes.fetch_quote(symbol).await?;
-        let last = q.last_price.unwrap_or(f64::NAN);
+        let last = q.regular_price().unwrap_or(f64::NAN);
         Ok(PriceObservation {
             extreme_price: last,
             current_price: last,
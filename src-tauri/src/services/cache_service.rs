@@ -47,6 +47,12 @@ impl CacheService {
 
     /// Checks if a cached file exists and is still valid
     pub fn is_valid(&self, key: &str) -> bool {
+        let valid = self.check_valid(key);
+        crate::telemetry::record_cache_lookup("file", valid);
+        valid
+    }
+
+    fn check_valid(&self, key: &str) -> bool {
         let cache_path = self.get_cache_path(key);
 
         if !cache_path.exists() {
@@ -236,6 +242,22 @@ impl CacheService {
 
         Ok(keys)
     }
+
+    /// Unique, sorted ticker symbols with at least one valid entry.
+    /// Keys are `{SYMBOL}_{kind}` (e.g. `AAPL_overview`, `AAPL_earnings`),
+    /// so the symbol is everything before the first underscore. Shared by
+    /// the `ibkr_get_cached_tickers` command and the localhost HTTP API.
+    pub fn list_cached_symbols(&self) -> Result<Vec<String>, Box<dyn Error + Send + Sync>> {
+        let mut symbols: Vec<String> = self
+            .list_valid_keys()?
+            .into_iter()
+            .filter_map(|key| key.split('_').next().map(str::to_string))
+            .collect::<std::collections::HashSet<_>>()
+            .into_iter()
+            .collect();
+        symbols.sort();
+        Ok(symbols)
+    }
 }
 
 #[cfg(test)]
@@ -328,4 +350,19 @@ mod tests {
         cache.clear("test_key").unwrap();
         assert!(!cache.is_valid("test_key"));
     }
+
+    #[test]
+    fn test_list_cached_symbols_dedupes_and_sorts() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = CacheService::new(temp_dir.path()).unwrap();
+        let data = TestData {
+            value: "x".to_string(),
+        };
+
+        cache.write("MSFT_overview", &data).unwrap();
+        cache.write("AAPL_overview", &data).unwrap();
+        cache.write("AAPL_earnings", &data).unwrap();
+
+        assert_eq!(cache.list_cached_symbols().unwrap(), vec!["AAPL", "MSFT"]);
+    }
 }
@@ -0,0 +1,241 @@
+//! Carrying costs: margin interest on the debit balance and borrow fees
+//! on short stock, as a daily estimate for the P&L view.
+//!
+//! The debit is the account's negative `TotalCashValue` (base
+//! currency), charged at the blended rate of `carry_costs.margin_tiers`
+//! — each slice of the balance at its own tier's rate, as IBKR does.
+//! Loans in other currencies are folded into that base figure rather
+//! than rated on their own currency's benchmark.
+//!
+//! IBKR doesn't publish a short's borrow rate through the API, so fees
+//! come from `carry_costs.borrow_fees` (per symbol, for hard-to-borrow
+//! names) or `default_borrow_fee_pct`. Rates are annual percentages
+//! accrued over `day_count` days. Estimates only; IBKR's statement is
+//! the source of truth.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::ibkr::client::IbkrClient;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::{AccountSummary, Position};
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateTier {
+    /// Upper bound of the slice (debit balance); `None` = no limit.
+    pub up_to: Option<f64>,
+    pub rate_pct: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CarryCostsConfig {
+    /// Ascending by `up_to`; only the last tier may be unbounded.
+    #[serde(default = "default_margin_tiers")]
+    pub margin_tiers: Vec<RateTier>,
+    /// Days per year for accrual (IBKR uses 360 for USD).
+    #[serde(default = "default_day_count")]
+    pub day_count: u32,
+    /// Annual fee for shorts not listed in `borrow_fees` (general
+    /// collateral).
+    #[serde(default = "default_borrow_fee_pct")]
+    pub default_borrow_fee_pct: f64,
+    /// Symbol → annual borrow fee percent.
+    #[serde(default)]
+    pub borrow_fees: BTreeMap<String, f64>,
+}
+
+/// IBKR Pro USD tiers (benchmark + 1.5 / 1.0 / 0.75) at a 4.33%
+/// benchmark. Update when the Fed moves.
+fn default_margin_tiers() -> Vec<RateTier> {
+    vec![
+        RateTier {
+            up_to: Some(100_000.0),
+            rate_pct: 5.83,
+        },
+        RateTier {
+            up_to: Some(1_000_000.0),
+            rate_pct: 5.33,
+        },
+        RateTier {
+            up_to: None,
+            rate_pct: 5.08,
+        },
+    ]
+}
+
+fn default_day_count() -> u32 {
+    360
+}
+
+fn default_borrow_fee_pct() -> f64 {
+    0.25
+}
+
+impl Default for CarryCostsConfig {
+    fn default() -> Self {
+        Self {
+            margin_tiers: default_margin_tiers(),
+            day_count: default_day_count(),
+            default_borrow_fee_pct: default_borrow_fee_pct(),
+            borrow_fees: BTreeMap::new(),
+        }
+    }
+}
+
+impl CarryCostsConfig {
+    /// Annual interest on `debit`, slice by slice through the tiers.
+    pub fn annual_interest(&self, debit: f64) -> f64 {
+        let mut floor = 0.0;
+        let mut interest = 0.0;
+        for tier in &self.margin_tiers {
+            if debit <= floor {
+                break;
+            }
+            let ceiling = tier.up_to.unwrap_or(f64::INFINITY);
+            interest += (debit.min(ceiling) - floor) * tier.rate_pct / 100.0;
+            floor = ceiling;
+        }
+        interest
+    }
+
+    pub fn borrow_fee_pct(&self, symbol: &str) -> f64 {
+        self.borrow_fees
+            .iter()
+            .find(|(s, _)| s.eq_ignore_ascii_case(symbol))
+            .map_or(self.default_borrow_fee_pct, |(_, fee)| *fee)
+    }
+}
+
+/// Trait seam for the account values read. Production is the live
+/// `IbkrClient`; tests inject canned rows.
+#[async_trait]
+pub trait AccountValuesSource: Send + Sync {
+    async fn account_values(&self, account: &str) -> Result<Vec<AccountSummary>, IbkrError>;
+}
+
+#[async_trait]
+impl AccountValuesSource for IbkrClient {
+    async fn account_values(&self, account: &str) -> Result<Vec<AccountSummary>, IbkrError> {
+        self.get_account_summary(account).await
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortBorrowCost {
+    pub symbol: String,
+    pub quantity: f64,
+    /// Absolute market value of the short.
+    pub market_value: f64,
+    pub fee_pct: f64,
+    pub daily_fee: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CarryCosts {
+    pub account: String,
+    /// Base currency every amount is in.
+    pub currency: String,
+    /// 0 when the account holds net cash.
+    pub debit_balance: f64,
+    /// Blended annual rate across the tiers the debit touches.
+    pub margin_rate_pct: f64,
+    pub margin_interest_daily: f64,
+    pub shorts: Vec<ShortBorrowCost>,
+    pub borrow_fee_daily: f64,
+    pub total_daily: f64,
+}
+
+pub struct CarryCostService {
+    values: Arc<dyn AccountValuesSource>,
+    positions: Arc<dyn OpenPositionsSource>,
+    accounts: Arc<dyn AccountSource>,
+}
+
+impl CarryCostService {
+    pub fn new(
+        values: Arc<dyn AccountValuesSource>,
+        positions: Arc<dyn OpenPositionsSource>,
+        accounts: Arc<dyn AccountSource>,
+    ) -> Self {
+        Self {
+            values,
+            positions,
+            accounts,
+        }
+    }
+
+    /// Daily carrying costs for `account` (default: the first managed
+    /// account) under `config`.
+    pub async fn estimate(
+        &self,
+        config: &CarryCostsConfig,
+        account: Option<String>,
+    ) -> Result<CarryCosts, IbkrError> {
+        let account = match account {
+            Some(a) if !a.trim().is_empty() => a,
+            _ => self.accounts.current_account().await?,
+        };
+        let values = self.values.account_values(&account).await?;
+        let positions = self.positions.list_open(&account).await?;
+        Ok(estimate(config, account, &values, &positions))
+    }
+}
+
+fn estimate(
+    config: &CarryCostsConfig,
+    account: String,
+    values: &[AccountSummary],
+    positions: &[Position],
+) -> CarryCosts {
+    let days = f64::from(config.day_count.max(1));
+    let cash = values
+        .iter()
+        .find(|r| r.tag == "TotalCashValue" && r.currency != "BASE");
+    let currency = cash.map(|r| r.currency.clone()).unwrap_or_default();
+    let debit = cash
+        .and_then(|r| r.value.trim().parse::<f64>().ok())
+        .map_or(0.0, |c| (-c).max(0.0));
+    let annual = config.annual_interest(debit);
+
+    let shorts: Vec<ShortBorrowCost> = positions
+        .iter()
+        .filter(|p| p.contract_type == "STK" && p.position < 0.0)
+        .map(|p| {
+            let market_value = p.market_value.abs();
+            let fee_pct = config.borrow_fee_pct(&p.symbol);
+            ShortBorrowCost {
+                symbol: p.symbol.clone(),
+                quantity: p.position,
+                market_value,
+                fee_pct,
+                daily_fee: market_value * fee_pct / 100.0 / days,
+            }
+        })
+        .collect();
+    let margin_interest_daily = annual / days;
+    let borrow_fee_daily = shorts.iter().map(|s| s.daily_fee).sum();
+    CarryCosts {
+        account,
+        currency,
+        debit_balance: debit,
+        margin_rate_pct: if debit > 0.0 {
+            annual / debit * 100.0
+        } else {
+            0.0
+        },
+        margin_interest_daily,
+        shorts,
+        borrow_fee_daily,
+        total_daily: margin_interest_daily + borrow_fee_daily,
+    }
+}
@@ -0,0 +1,111 @@
+use super::*;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubValues(f64);
+
+#[async_trait]
+impl AccountValuesSource for StubValues {
+    async fn account_values(&self, account: &str) -> Result<Vec<AccountSummary>, IbkrError> {
+        let row = |tag: &str, currency: &str| AccountSummary {
+            account: account.to_string(),
+            tag: tag.to_string(),
+            value: self.0.to_string(),
+            currency: currency.to_string(),
+        };
+        Ok(vec![
+            row("CashBalance", "BASE"),
+            row("TotalCashValue", "USD"),
+        ])
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+fn pos(symbol: &str, contract_type: &str, qty: f64, market_value: f64) -> Position {
+    Position {
+        symbol: symbol.to_string(),
+        contract_type: contract_type.to_string(),
+        position: qty,
+        market_value,
+        ..Default::default()
+    }
+}
+
+fn service(cash: f64, positions: Vec<Position>) -> CarryCostService {
+    CarryCostService::new(
+        Arc::new(StubValues(cash)),
+        Arc::new(StubPositions(positions)),
+        Arc::new(FixedAccount),
+    )
+}
+
+#[test]
+fn annual_interest_charges_each_slice_at_its_tier() {
+    let cfg = CarryCostsConfig::default();
+    assert_eq!(cfg.annual_interest(0.0), 0.0);
+    assert!((cfg.annual_interest(50_000.0) - 2_915.0).abs() < 1e-6);
+    // 100k at 5.83% + 50k at 5.33%.
+    assert!((cfg.annual_interest(150_000.0) - 8_495.0).abs() < 1e-6);
+    // ... + 900k at 5.33% + 1M at 5.08%.
+    assert!((cfg.annual_interest(2_000_000.0) - 104_600.0).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn estimates_margin_interest_and_short_borrow_fees() {
+    let mut cfg = CarryCostsConfig::default();
+    cfg.borrow_fees.insert("GME".to_string(), 36.0);
+    let svc = service(
+        -150_000.0,
+        vec![
+            pos("AAPL", "STK", 100.0, 20_000.0),
+            pos("gme", "STK", -500.0, -10_000.0),
+            pos("TSLA", "STK", -10.0, -2_000.0),
+            pos("SPY", "OPT", -1.0, -300.0),
+        ],
+    );
+    let costs = svc.estimate(&cfg, None).await.unwrap();
+
+    assert_eq!(costs.account, "DU1");
+    assert_eq!(costs.currency, "USD");
+    assert_eq!(costs.debit_balance, 150_000.0);
+    assert!((costs.margin_rate_pct - 8_495.0 / 1_500.0).abs() < 1e-9);
+    assert!((costs.margin_interest_daily - 8_495.0 / 360.0).abs() < 1e-9);
+
+    let fees: Vec<_> = costs
+        .shorts
+        .iter()
+        .map(|s| (s.symbol.as_str(), s.fee_pct))
+        .collect();
+    assert_eq!(fees, vec![("gme", 36.0), ("TSLA", 0.25)]);
+    // 10_000 × 36% / 360 = 10; 2_000 × 0.25% / 360.
+    assert!((costs.shorts[0].daily_fee - 10.0).abs() < 1e-9);
+    let expected = 8_495.0 / 360.0 + 10.0 + 5.0 / 360.0;
+    assert!((costs.total_daily - expected).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn net_cash_accrues_no_interest() {
+    let costs = service(25_000.0, vec![])
+        .estimate(&CarryCostsConfig::default(), Some("DU7".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(costs.account, "DU7");
+    assert_eq!(costs.debit_balance, 0.0);
+    assert_eq!(costs.margin_rate_pct, 0.0);
+    assert_eq!(costs.total_daily, 0.0);
+}
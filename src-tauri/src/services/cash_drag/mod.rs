@@ -0,0 +1,327 @@
+//! Cash drag: how much of the account sits in cash, and what it could
+//! be doing instead.
+//!
+//! The `cash_drag_sample` task (`services::scheduler`, 16:30 ET on
+//! weekdays by default) reads each managed account's `TotalCashValue`
+//! and `NetLiquidation` and keeps one row per account per ET date in
+//! `cash_samples`. [`CashDragService::report`] pairs that history with
+//! a live reading, and once cash is above `cash_drag.threshold_pct` of
+//! NAV it suggests where the idle cash could go: watchlist names priced
+//! below their base-case buy band (`services::margin_of_safety`),
+//! deepest discount first, at most `cash_drag.max_suggestions` of them.
+//!
+//! With `cash_drag.alert` on, a sample that crosses above the threshold
+//! (the previous one was at or below it, or there was none) emits an
+//! [`AppEvent::IdleCash`] carrying the suggestions. Like the margin
+//! cushion alert it fires on the crossing only. Suggestions are a
+//! prompt for the operator; nothing here places an order.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+use crate::events::{AppEvent, EventEmitter};
+use crate::ibkr::client::IbkrClient;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::AccountSummary;
+use crate::services::margin_of_safety::{BandZone, MarginOfSafetyWatcher};
+use crate::services::risk_engine::AccountSource;
+use crate::services::tracker_service::{TrackerError, TrackerService};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::market_calendar::et_date;
+
+mod store;
+
+#[cfg(test)]
+mod tests;
+
+/// Days of history `report` returns when the caller doesn't say.
+pub const DEFAULT_HISTORY_DAYS: u32 = 365;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CashDragConfig {
+    /// Cash above this percent of NAV counts as idle.
+    #[serde(default = "default_threshold_pct")]
+    pub threshold_pct: f64,
+    /// Emit `IdleCash` when a sample crosses above the threshold.
+    #[serde(default = "default_alert")]
+    pub alert: bool,
+    /// Most watchlist names suggested for the idle cash.
+    #[serde(default = "default_max_suggestions")]
+    pub max_suggestions: u32,
+}
+
+fn default_threshold_pct() -> f64 {
+    10.0
+}
+
+fn default_alert() -> bool {
+    true
+}
+
+fn default_max_suggestions() -> u32 {
+    5
+}
+
+impl Default for CashDragConfig {
+    fn default() -> Self {
+        Self {
+            threshold_pct: default_threshold_pct(),
+            alert: default_alert(),
+            max_suggestions: default_max_suggestions(),
+        }
+    }
+}
+
+/// Trait seam for the account reads. Production is the live
+/// `IbkrClient`; tests inject canned rows.
+#[async_trait]
+pub trait CashDragSource: Send + Sync {
+    async fn accounts(&self) -> Result<Vec<String>, IbkrError>;
+    async fn account_values(&self, account: &str) -> Result<Vec<AccountSummary>, IbkrError>;
+}
+
+#[async_trait]
+impl CashDragSource for IbkrClient {
+    async fn accounts(&self) -> Result<Vec<String>, IbkrError> {
+        self.get_accounts().await
+    }
+
+    async fn account_values(&self, account: &str) -> Result<Vec<AccountSummary>, IbkrError> {
+        self.get_account_summary(account).await
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CashDragError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+    #[error("watchlist: {0}")]
+    Watchlist(#[from] TrackerError),
+}
+
+/// One account reading, in the account's base currency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashPoint {
+    pub account: String,
+    /// ET date, `YYYY-MM-DD`.
+    pub date: String,
+    pub cash: f64,
+    pub net_liquidation: f64,
+    /// Unix seconds.
+    pub sampled_at: i64,
+}
+
+impl CashPoint {
+    /// `None` without a positive `NetLiquidation` or a `TotalCashValue`.
+    fn from_values(account: &str, rows: &[AccountSummary], sampled_at: i64) -> Option<Self> {
+        let value = |tag: &str| {
+            rows.iter()
+                .find(|r| r.tag == tag && r.currency != "BASE")
+                .and_then(|r| r.value.trim().parse::<f64>().ok())
+        };
+        let net_liquidation = value("NetLiquidation").filter(|v| *v > 0.0)?;
+        Some(Self {
+            account: account.to_string(),
+            date: et_date(Utc::now()).format("%Y-%m-%d").to_string(),
+            cash: value("TotalCashValue")?,
+            net_liquidation,
+            sampled_at,
+        })
+    }
+
+    /// Cash as a percent of NAV; negative when borrowing.
+    pub fn cash_pct(&self) -> f64 {
+        self.cash * 100.0 / self.net_liquidation
+    }
+}
+
+/// A watchlist name trading below its base-case buy band.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdleCashSuggestion {
+    pub symbol: String,
+    pub price: f64,
+    pub buy_below: f64,
+    pub fair_value: f64,
+    /// How far the price sits below `buy_below`, percent.
+    pub discount_pct: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashDragReport {
+    pub account: String,
+    pub threshold_pct: f64,
+    /// The live reading.
+    pub current: CashPoint,
+    pub cash_pct: f64,
+    /// Cash above the threshold, in the base currency; `0` at or below.
+    pub idle_cash: f64,
+    /// Stored samples, oldest first.
+    pub history: Vec<CashPoint>,
+    /// Mean `cash_pct` over `history`; `None` without one.
+    pub average_cash_pct: Option<f64>,
+    /// Empty unless cash is above the threshold.
+    pub suggestions: Vec<IdleCashSuggestion>,
+}
+
+pub struct CashDragService {
+    db: Arc<Db>,
+    account: Arc<dyn AccountSource>,
+    source: Arc<dyn CashDragSource>,
+    watchlist: Arc<TrackerService>,
+    bands: Arc<MarginOfSafetyWatcher>,
+    config: Arc<RwLock<AppConfig>>,
+    emitter: Arc<EventEmitter>,
+}
+
+impl CashDragService {
+    pub fn new(
+        db: Arc<Db>,
+        account: Arc<dyn AccountSource>,
+        source: Arc<dyn CashDragSource>,
+        watchlist: Arc<TrackerService>,
+        bands: Arc<MarginOfSafetyWatcher>,
+        config: Arc<RwLock<AppConfig>>,
+        emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            db,
+            account,
+            source,
+            watchlist,
+            bands,
+            config,
+            emitter,
+        }
+    }
+
+    /// Sample every managed account once, replacing today's row.
+    /// Accounts without the values are skipped.
+    pub async fn sample(&self) -> Result<Vec<CashPoint>, CashDragError> {
+        let now = Utc::now().timestamp();
+        let config = self.config.read().await.cash_drag.clone();
+        let mut points = Vec::new();
+        for account in self.source.accounts().await? {
+            let rows = self.source.account_values(&account).await?;
+            let Some(point) = CashPoint::from_values(&account, &rows, now) else {
+                warn!("cash_drag: no cash or NLV for {account}, skipped");
+                continue;
+            };
+            let previous = store::latest(&self.db, &account).await?;
+            store::upsert(&self.db, &point).await?;
+
+            let above = |p: &CashPoint| p.cash_pct() > config.threshold_pct;
+            if config.alert && above(&point) && !previous.as_ref().is_some_and(above) {
+                info!(
+                    "cash_drag: {account} cash {:.1}% above {}%",
+                    point.cash_pct(),
+                    config.threshold_pct
+                );
+                let suggestions = self.suggestions(config.max_suggestions).await?;
+                let event = AppEvent::IdleCash {
+                    account: account.clone(),
+                    cash_pct: point.cash_pct(),
+                    threshold_pct: config.threshold_pct,
+                    idle_cash: idle_cash(&point, config.threshold_pct),
+                    suggestions: suggestions.into_iter().map(|s| s.symbol).collect(),
+                };
+                if let Err(e) = self.emitter.emit(event).await {
+                    warn!("IdleCash emit failed: {e}");
+                }
+            }
+            points.push(point);
+        }
+        Ok(points)
+    }
+
+    /// Cash drag of `account` (the current account when `None`) now,
+    /// with the last `days` days of samples.
+    pub async fn report(
+        &self,
+        account: Option<String>,
+        days: Option<u32>,
+    ) -> Result<CashDragReport, CashDragError> {
+        let config = self.config.read().await.cash_drag.clone();
+        let account = match account.filter(|a| !a.trim().is_empty()) {
+            Some(a) => a.trim().to_string(),
+            None => self.account.current_account().await?,
+        };
+        let rows = self.source.account_values(&account).await?;
+        let current =
+            CashPoint::from_values(&account, &rows, Utc::now().timestamp()).ok_or_else(|| {
+                IbkrError::RequestFailed(format!("no cash or NLV reported for {account}"))
+            })?;
+
+        let days = days.unwrap_or(DEFAULT_HISTORY_DAYS);
+        let since = et_date(Utc::now() - Duration::days(days as i64))
+            .format("%Y-%m-%d")
+            .to_string();
+        let history = store::history(&self.db, &account, &since).await?;
+        let average_cash_pct = (!history.is_empty())
+            .then(|| history.iter().map(CashPoint::cash_pct).sum::<f64>() / history.len() as f64);
+
+        let cash_pct = current.cash_pct();
+        let suggestions = if cash_pct > config.threshold_pct {
+            self.suggestions(config.max_suggestions).await?
+        } else {
+            Vec::new()
+        };
+        Ok(CashDragReport {
+            account,
+            threshold_pct: config.threshold_pct,
+            idle_cash: idle_cash(&current, config.threshold_pct),
+            cash_pct,
+            current,
+            history,
+            average_cash_pct,
+            suggestions,
+        })
+    }
+
+    /// Watchlist names whose last price sits in the base scenario's buy
+    /// zone, deepest below `buy_below` first.
+    async fn suggestions(&self, limit: u32) -> Result<Vec<IdleCashSuggestion>, CashDragError> {
+        let watchlist = self.watchlist.list(None).await?;
+        let mut out: Vec<IdleCashSuggestion> = self
+            .bands
+            .list(None)
+            .await?
+            .into_iter()
+            .filter(|b| b.scenario == "base" && b.zone == Some(BandZone::Buy))
+            .filter(|b| {
+                watchlist
+                    .iter()
+                    .any(|t| t.symbol.eq_ignore_ascii_case(&b.symbol))
+            })
+            .filter_map(|b| {
+                let price = b.price?;
+                Some(IdleCashSuggestion {
+                    discount_pct: (1.0 - price / b.buy_below) * 100.0,
+                    symbol: b.symbol,
+                    price,
+                    buy_below: b.buy_below,
+                    fair_value: b.fair_value,
+                })
+            })
+            .collect();
+        out.sort_by(|a, b| b.discount_pct.total_cmp(&a.discount_pct));
+        out.truncate(limit as usize);
+        Ok(out)
+    }
+}
+
+fn idle_cash(point: &CashPoint, threshold_pct: f64) -> f64 {
+    (point.cash - point.net_liquidation * threshold_pct / 100.0).max(0.0)
+}
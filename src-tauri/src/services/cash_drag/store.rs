@@ -0,0 +1,75 @@
+//! `cash_samples` reads and writes.
+
+use rusqlite::OptionalExtension;
+
+use super::CashPoint;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+const COLUMNS: &str = "account, as_of_date, cash, net_liquidation, sampled_at";
+
+fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CashPoint> {
+    Ok(CashPoint {
+        account: row.get(0)?,
+        date: row.get(1)?,
+        cash: row.get(2)?,
+        net_liquidation: row.get(3)?,
+        sampled_at: row.get(4)?,
+    })
+}
+
+/// Replaces the account's row for the point's date.
+pub async fn upsert(db: &Db, point: &CashPoint) -> Result<(), StorageError> {
+    let p = point.clone();
+    db.with_conn(move |conn| {
+        conn.execute(
+            "INSERT INTO cash_samples \
+               (account, as_of_date, cash, net_liquidation, sampled_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5) \
+             ON CONFLICT(account, as_of_date) DO UPDATE SET \
+               cash = excluded.cash, \
+               net_liquidation = excluded.net_liquidation, \
+               sampled_at = excluded.sampled_at",
+            rusqlite::params![p.account, p.date, p.cash, p.net_liquidation, p.sampled_at],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+pub async fn latest(db: &Db, account: &str) -> Result<Option<CashPoint>, StorageError> {
+    let account = account.to_string();
+    db.with_conn(move |conn| {
+        conn.query_row(
+            &format!(
+                "SELECT {COLUMNS} FROM cash_samples WHERE account = ?1 \
+                 ORDER BY as_of_date DESC LIMIT 1"
+            ),
+            rusqlite::params![account],
+            from_row,
+        )
+        .optional()
+        .map_err(StorageError::from)
+    })
+    .await
+}
+
+/// Samples of `account` dated `since` (`YYYY-MM-DD`) or later, oldest
+/// first.
+pub async fn history(db: &Db, account: &str, since: &str) -> Result<Vec<CashPoint>, StorageError> {
+    let account = account.to_string();
+    let since = since.to_string();
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM cash_samples \
+             WHERE account = ?1 AND as_of_date >= ?2 ORDER BY as_of_date"
+        ))?;
+        let rows = stmt.query_map(rusqlite::params![account, since], from_row)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
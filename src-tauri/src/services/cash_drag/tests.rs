@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+
+use tempfile::NamedTempFile;
+
+use super::*;
+use crate::ibkr::types::tracker::TrackerSource;
+use crate::ibkr::types::ProjectionAssumptions;
+use crate::services::fair_value_watch::PriceSource;
+use crate::services::projection_history::ProjectionHistoryStore;
+use crate::services::projection_service::ProjectionService;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+/// `DU1` reports the next cash balance from `cash` against a 100k NLV
+/// on every read; `DU2` reports no `TotalCashValue`.
+struct SequenceSource {
+    cash: StdMutex<Vec<f64>>,
+}
+
+#[async_trait]
+impl CashDragSource for SequenceSource {
+    async fn accounts(&self) -> Result<Vec<String>, IbkrError> {
+        Ok(vec!["DU1".to_string(), "DU2".to_string()])
+    }
+
+    async fn account_values(&self, account: &str) -> Result<Vec<AccountSummary>, IbkrError> {
+        let row = |tag: &str, value: String| AccountSummary {
+            account: account.to_string(),
+            tag: tag.to_string(),
+            value,
+            currency: "USD".to_string(),
+        };
+        if account == "DU2" {
+            return Ok(vec![row("NetLiquidation", "5000".to_string())]);
+        }
+        let cash = self.cash.lock().unwrap().remove(0);
+        Ok(vec![
+            row("NetLiquidation", "100000".to_string()),
+            row("TotalCashValue", format!("{cash}")),
+        ])
+    }
+}
+
+struct FixedPrices(HashMap<String, f64>);
+
+#[async_trait]
+impl PriceSource for FixedPrices {
+    async fn latest_price(&self, symbol: &str) -> Option<f64> {
+        self.0.get(symbol).copied()
+    }
+}
+
+struct Harness {
+    _tmp: NamedTempFile,
+    db: Arc<Db>,
+    service: CashDragService,
+    emitter: Arc<EventEmitter>,
+}
+
+/// NVDA and AAPL sit in their base buy zone (NVDA deeper), MSFT at
+/// fair value, and TSLA in the buy zone but off the watchlist.
+async fn harness(cash: Vec<f64>, config: AppConfig) -> Harness {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let history = Arc::new(ProjectionHistoryStore::new(Arc::clone(&db)));
+    let mut prices = HashMap::new();
+    for (symbol, of_fair) in [("NVDA", 0.3), ("AAPL", 0.7), ("MSFT", 1.0), ("TSLA", 0.5)] {
+        let assumptions = ProjectionAssumptions::default();
+        let data = ProjectionService::generate_mock_fundamental_data(symbol);
+        let results = ProjectionService::generate_projection_results(&data, &assumptions).unwrap();
+        history
+            .record(symbol, &assumptions, &results)
+            .await
+            .unwrap();
+        let base = &results.projections[0].base;
+        let fair = (base.share_price_low + base.share_price_high) / 2.0;
+        prices.insert(symbol.to_string(), fair * of_fair);
+    }
+    let emitter = Arc::new(EventEmitter::for_capture());
+    let config = Arc::new(RwLock::new(config));
+    let bands = Arc::new(MarginOfSafetyWatcher::new(
+        Arc::clone(&db),
+        history,
+        Arc::new(FixedPrices(prices)),
+        Arc::clone(&config),
+        Arc::new(EventEmitter::for_capture()),
+    ));
+    bands.run().await.unwrap();
+    let watchlist = Arc::new(TrackerService::new(Arc::clone(&db)));
+    for symbol in ["NVDA", "AAPL", "MSFT"] {
+        watchlist
+            .add(symbol, TrackerSource::Manual, None, vec![], None)
+            .await
+            .unwrap();
+    }
+    let service = CashDragService::new(
+        Arc::clone(&db),
+        Arc::new(FixedAccount),
+        Arc::new(SequenceSource {
+            cash: StdMutex::new(cash),
+        }),
+        watchlist,
+        bands,
+        config,
+        Arc::clone(&emitter),
+    );
+    Harness {
+        _tmp: tmp,
+        db,
+        service,
+        emitter,
+    }
+}
+
+#[tokio::test]
+async fn alerts_on_each_crossing_above_the_threshold_with_suggestions() {
+    let h = harness(
+        vec![5_000.0, 15_000.0, 20_000.0, 8_000.0, 12_000.0],
+        AppConfig::default(),
+    )
+    .await;
+
+    for _ in 0..5 {
+        let points = h.service.sample().await.unwrap();
+        // DU2 has no cash value and is skipped.
+        assert_eq!(points.len(), 1);
+    }
+    // One row per day: the last sample replaced the earlier ones.
+    let latest = store::latest(&h.db, "DU1").await.unwrap().unwrap();
+    assert_eq!(latest.cash, 12_000.0);
+
+    // Above at 15k (crossing), still above at 20k, back under, above at 12k.
+    let alerts: Vec<_> = h
+        .emitter
+        .captured()
+        .await
+        .into_iter()
+        .filter_map(|e| match e {
+            AppEvent::IdleCash {
+                cash_pct,
+                idle_cash,
+                suggestions,
+                ..
+            } => Some((cash_pct, idle_cash, suggestions)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(alerts.len(), 2);
+    assert_eq!(alerts[0].0, 15.0);
+    assert_eq!(alerts[0].1, 5_000.0);
+    assert_eq!(alerts[0].2, ["NVDA", "AAPL"], "deepest discount first");
+    assert_eq!(alerts[1].0, 12.0);
+}
+
+#[tokio::test]
+async fn report_suggests_only_while_cash_is_idle() {
+    let mut config = AppConfig::default();
+    config.cash_drag.max_suggestions = 1;
+    config.cash_drag.alert = false;
+    let h = harness(vec![30_000.0, 4_000.0], config).await;
+    let today = et_date(Utc::now());
+    for (days_ago, cash) in [(400, 50_000.0), (2, 20_000.0), (1, 10_000.0)] {
+        store::upsert(
+            &h.db,
+            &CashPoint {
+                account: "DU1".to_string(),
+                date: (today - Duration::days(days_ago))
+                    .format("%Y-%m-%d")
+                    .to_string(),
+                cash,
+                net_liquidation: 100_000.0,
+                sampled_at: 0,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let report = h.service.report(None, None).await.unwrap();
+    assert_eq!(report.account, "DU1");
+    assert_eq!(report.cash_pct, 30.0);
+    assert_eq!(report.idle_cash, 20_000.0);
+    // The 400-day-old sample is outside the default year.
+    assert_eq!(report.history.len(), 2);
+    assert_eq!(report.average_cash_pct, Some(15.0));
+    let symbols: Vec<&str> = report
+        .suggestions
+        .iter()
+        .map(|s| s.symbol.as_str())
+        .collect();
+    assert_eq!(symbols, ["NVDA"]);
+    let nvda = &report.suggestions[0];
+    assert!(
+        (nvda.discount_pct - 60.0).abs() < 1e-6,
+        "0.3 of fair against 0.75"
+    );
+
+    let report = h
+        .service
+        .report(Some("DU1".to_string()), Some(500))
+        .await
+        .unwrap();
+    assert_eq!(report.cash_pct, 4.0);
+    assert_eq!(report.idle_cash, 0.0);
+    assert_eq!(report.history.len(), 3);
+    assert!(report.suggestions.is_empty());
+    assert!(h.emitter.captured().await.is_empty());
+}
@@ -0,0 +1,171 @@
+//! Cash management — cash per currency across every managed account,
+//! and what the working orders would do to it.
+//!
+//! [`CashManagementService::overview`] reads each account's IBKR
+//! account values (`CashBalance` per currency, `TotalCashValue`,
+//! `BuyingPower`, `ExchangeRate`) plus all open orders, then projects
+//! cash and buying power as if every pending buy filled (see
+//! `projection.rs`). Conditions worth a look — a currency going
+//! negative, the account borrowing on margin, buys exceeding buying
+//! power — come back as warnings and go out once as
+//! `AppEvent::CashWarning`. Suggested FX sweeps are proposals only;
+//! nothing here converts currency or touches an order.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::events::{AppEvent, EventEmitter};
+use crate::ibkr::client::IbkrClient;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::{AccountSummary, OpenOrder};
+use crate::services::quote_service::QuoteFetcher;
+
+mod projection;
+#[cfg(test)]
+mod tests;
+
+pub use projection::CashOverview;
+
+/// Trait seam for the three IBKR reads the overview needs. Production
+/// is the live `IbkrClient`; tests inject canned rows.
+#[async_trait]
+pub trait CashSource: Send + Sync {
+    async fn accounts(&self) -> Result<Vec<String>, IbkrError>;
+    async fn account_values(&self, account: &str) -> Result<Vec<AccountSummary>, IbkrError>;
+    async fn open_orders(&self) -> Result<Vec<OpenOrder>, IbkrError>;
+}
+
+#[async_trait]
+impl CashSource for IbkrClient {
+    async fn accounts(&self) -> Result<Vec<String>, IbkrError> {
+        self.get_accounts().await
+    }
+
+    async fn account_values(&self, account: &str) -> Result<Vec<AccountSummary>, IbkrError> {
+        self.get_account_summary(account).await
+    }
+
+    async fn open_orders(&self) -> Result<Vec<OpenOrder>, IbkrError> {
+        IbkrClient::open_orders(self).await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CashWarningKind {
+    /// A currency's cash would drop below zero (an FX loan if the
+    /// account holds other currencies).
+    NegativeCash,
+    /// Total cash in the base currency would drop below zero: the
+    /// account borrows on margin.
+    MarginBorrow,
+    /// Pending buys exceed the account's buying power.
+    BuyingPowerExceeded,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CashWarning {
+    pub account: String,
+    pub currency: String,
+    pub kind: CashWarningKind,
+    /// Projected cash (or buying power) after pending buys; negative.
+    pub amount: f64,
+    pub message: String,
+}
+
+type WarningKey = (String, String, CashWarningKind);
+
+pub struct CashManagementService {
+    source: Arc<dyn CashSource>,
+    quotes: Arc<dyn QuoteFetcher>,
+    emitter: Arc<EventEmitter>,
+    /// Warnings already emitted; a key leaves once its condition
+    /// clears so a recurrence fires again.
+    active: Mutex<HashSet<WarningKey>>,
+}
+
+impl CashManagementService {
+    pub fn new(
+        source: Arc<dyn CashSource>,
+        quotes: Arc<dyn QuoteFetcher>,
+        emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            source,
+            quotes,
+            emitter,
+            active: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub async fn overview(&self) -> Result<CashOverview, IbkrError> {
+        let mut values = Vec::new();
+        for account in self.source.accounts().await? {
+            let rows = self.source.account_values(&account).await?;
+            values.push((account, rows));
+        }
+        let orders = self.source.open_orders().await?;
+
+        let mut priced = Vec::with_capacity(orders.len());
+        for order in orders {
+            let price = match order_price(&order) {
+                Some(p) => Some(p),
+                None => self.quote(&order).await,
+            };
+            priced.push((order, price));
+        }
+
+        let overview = projection::build(&values, &priced);
+        self.emit_new(&overview.warnings).await;
+        Ok(overview)
+    }
+
+    /// Last trade (or close) for orders without a limit / stop price.
+    /// Only stocks are quoted; other contracts stay unpriced.
+    async fn quote(&self, order: &OpenOrder) -> Option<f64> {
+        if order.sec_type != "STK" {
+            return None;
+        }
+        match self.quotes.get_market_data_snapshot(&order.symbol).await {
+            Ok(snap) => snap.last_price.or(snap.close).filter(|p| *p > 0.0),
+            Err(e) => {
+                warn!("cash management: quote for {} failed: {e}", order.symbol);
+                None
+            }
+        }
+    }
+
+    async fn emit_new(&self, warnings: &[CashWarning]) {
+        let mut active = self.active.lock().await;
+        let current: HashSet<WarningKey> = warnings
+            .iter()
+            .map(|w| (w.account.clone(), w.currency.clone(), w.kind))
+            .collect();
+        for w in warnings {
+            let key = (w.account.clone(), w.currency.clone(), w.kind);
+            if active.contains(&key) {
+                continue;
+            }
+            let event = AppEvent::CashWarning { warning: w.clone() };
+            if let Err(e) = self.emitter.emit(event).await {
+                warn!("cash management: emit failed: {e}");
+            }
+        }
+        *active = current;
+    }
+}
+
+/// The price the order would pay at worst: its limit, else its stop
+/// trigger.
+fn order_price(order: &OpenOrder) -> Option<f64> {
+    order
+        .limit_price
+        .or(order.aux_price)
+        .filter(|p| *p > 0.0 && *p < f64::MAX)
+}
@@ -0,0 +1,307 @@
+//! Cash and buying-power projection for [`super::CashManagementService`].
+//!
+//! Each pending buy spends `remaining × price × multiplier` of its own
+//! currency. Pending sells are reported but not credited, since a
+//! resting sell may never fill before the buys do. Base-currency totals
+//! convert through IBKR's `ExchangeRate` rows. Buying power is reduced
+//! dollar for dollar, which overstates the hit on a margin account and
+//! is exact for a cash account.
+//!
+//! A sweep is suggested when a non-base currency goes negative and the
+//! account's base-currency cash can cover it.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{CashWarning, CashWarningKind};
+use crate::ibkr::types::{AccountSummary, OpenOrder, OrderAction};
+
+/// Below this a projected balance counts as zero.
+const CASH_EPSILON: f64 = 0.005;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencyCash {
+    pub currency: String,
+    pub cash: f64,
+    pub pending_buys: f64,
+    pub pending_sells: f64,
+    /// `cash - pending_buys`.
+    pub projected_cash: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountCash {
+    pub account: String,
+    pub base_currency: String,
+    pub balances: Vec<CurrencyCash>,
+    /// `TotalCashValue`, in the base currency.
+    pub total_cash: f64,
+    /// `None` when IBKR reports no `BuyingPower` (then no buying-power
+    /// warning is raised).
+    pub buying_power: Option<f64>,
+    /// Pending buys converted to the base currency.
+    pub pending_buys_base: f64,
+    pub projected_cash: f64,
+    pub projected_buying_power: Option<f64>,
+}
+
+/// Proposed FX conversion covering a negative projected balance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashSweep {
+    pub account: String,
+    pub from_currency: String,
+    pub to_currency: String,
+    /// In `to_currency`.
+    pub amount: f64,
+    /// Approximate cost in `from_currency` at IBKR's exchange rate.
+    pub from_amount: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashOverview {
+    /// Per-currency totals across all accounts.
+    pub currencies: Vec<CurrencyCash>,
+    pub accounts: Vec<AccountCash>,
+    pub sweeps: Vec<CashSweep>,
+    pub warnings: Vec<CashWarning>,
+    pub notes: Vec<String>,
+}
+
+/// `values` holds each account's IBKR account values; `orders` pairs
+/// every open order with the price it is projected at (`None` when no
+/// price could be found).
+pub(super) fn build(
+    values: &[(String, Vec<AccountSummary>)],
+    orders: &[(OpenOrder, Option<f64>)],
+) -> CashOverview {
+    let mut notes = Vec::new();
+    let mut by_account: BTreeMap<&str, BTreeMap<String, CurrencyCash>> = values
+        .iter()
+        .map(|(account, rows)| (account.as_str(), cash_balances(rows)))
+        .collect();
+
+    for (order, price) in orders {
+        let account = match (order.account.as_str(), values) {
+            ("", [(only, _)]) => only.as_str(),
+            (account, _) => account,
+        };
+        let Some(balances) = by_account.get_mut(account) else {
+            notes.push(format!(
+                "order {} ({}): account `{account}` not in the overview",
+                order.order_id, order.symbol
+            ));
+            continue;
+        };
+        let Some(price) = price else {
+            notes.push(format!(
+                "order {} ({}): no price, left out",
+                order.order_id, order.symbol
+            ));
+            continue;
+        };
+        let notional = order.remaining * price * order.multiplier;
+        let entry = balances
+            .entry(order.currency.clone())
+            .or_insert_with(|| CurrencyCash {
+                currency: order.currency.clone(),
+                ..Default::default()
+            });
+        match order.action {
+            OrderAction::Buy => entry.pending_buys += notional,
+            OrderAction::Sell => entry.pending_sells += notional,
+        }
+    }
+
+    let mut overview = CashOverview {
+        currencies: Vec::new(),
+        accounts: Vec::new(),
+        sweeps: Vec::new(),
+        warnings: Vec::new(),
+        notes,
+    };
+    let mut totals: BTreeMap<String, CurrencyCash> = BTreeMap::new();
+    for (account, rows) in values {
+        let mut balances: Vec<CurrencyCash> = by_account
+            .remove(account.as_str())
+            .unwrap_or_default()
+            .into_values()
+            .collect();
+        for b in &mut balances {
+            b.projected_cash = b.cash - b.pending_buys;
+            let total = totals.entry(b.currency.clone()).or_default();
+            total.currency = b.currency.clone();
+            total.cash += b.cash;
+            total.pending_buys += b.pending_buys;
+            total.pending_sells += b.pending_sells;
+            total.projected_cash += b.projected_cash;
+        }
+        let cash = account_cash(account, rows, balances, &mut overview.notes);
+        check(&cash, rows, &mut overview);
+        overview.accounts.push(cash);
+    }
+    overview.currencies = totals.into_values().collect();
+    overview
+}
+
+/// `CashBalance` rows per currency (IBKR's `BASE` row is the total and
+/// skipped). Accounts that only report `TotalCashValue` get that as
+/// their single base-currency balance.
+fn cash_balances(rows: &[AccountSummary]) -> BTreeMap<String, CurrencyCash> {
+    let mut out: BTreeMap<String, CurrencyCash> = rows
+        .iter()
+        .filter(|r| r.tag == "CashBalance" && r.currency != "BASE" && !r.currency.is_empty())
+        .map(|r| {
+            let balance = CurrencyCash {
+                currency: r.currency.clone(),
+                cash: parse(&r.value),
+                ..Default::default()
+            };
+            (r.currency.clone(), balance)
+        })
+        .collect();
+    if out.is_empty() {
+        if let Some(row) = rows.iter().find(|r| r.tag == "TotalCashValue") {
+            out.insert(
+                row.currency.clone(),
+                CurrencyCash {
+                    currency: row.currency.clone(),
+                    cash: parse(&row.value),
+                    ..Default::default()
+                },
+            );
+        }
+    }
+    out
+}
+
+fn account_cash(
+    account: &str,
+    rows: &[AccountSummary],
+    balances: Vec<CurrencyCash>,
+    notes: &mut Vec<String>,
+) -> AccountCash {
+    let value = |tag: &str| rows.iter().find(|r| r.tag == tag && r.currency != "BASE");
+    let total = value("TotalCashValue");
+    let base_currency = total.map(|r| r.currency.clone()).unwrap_or_default();
+    let total_cash = total.map_or_else(
+        || balances.iter().map(|b| b.cash).sum(),
+        |r| parse(&r.value),
+    );
+    let buying_power = value("BuyingPower").map(|r| parse(&r.value));
+
+    let mut pending_buys_base = 0.0;
+    for b in balances.iter().filter(|b| b.pending_buys > 0.0) {
+        match rate(rows, &base_currency, &b.currency) {
+            Some(rate) => pending_buys_base += b.pending_buys * rate,
+            None => notes.push(format!(
+                "{account}: no {} exchange rate, {:.2} of pending buys left out of base totals",
+                b.currency, b.pending_buys
+            )),
+        }
+    }
+    AccountCash {
+        account: account.to_string(),
+        base_currency,
+        balances,
+        total_cash,
+        buying_power,
+        pending_buys_base,
+        projected_cash: total_cash - pending_buys_base,
+        projected_buying_power: buying_power.map(|bp| bp - pending_buys_base),
+    }
+}
+
+/// Base-currency value of one unit of `currency`.
+fn rate(rows: &[AccountSummary], base: &str, currency: &str) -> Option<f64> {
+    if currency == base {
+        return Some(1.0);
+    }
+    rows.iter()
+        .find(|r| r.tag == "ExchangeRate" && r.currency == currency)
+        .map(|r| parse(&r.value))
+        .filter(|r| *r > 0.0)
+}
+
+/// Warnings and suggested sweeps for one account.
+fn check(cash: &AccountCash, rows: &[AccountSummary], overview: &mut CashOverview) {
+    let mut warn = |currency: &str, kind, amount: f64, message: String| {
+        overview.warnings.push(CashWarning {
+            account: cash.account.clone(),
+            currency: currency.to_string(),
+            kind,
+            amount,
+            message,
+        })
+    };
+    let base = cash.base_currency.as_str();
+    for b in &cash.balances {
+        if b.projected_cash < -CASH_EPSILON {
+            warn(
+                &b.currency,
+                CashWarningKind::NegativeCash,
+                b.projected_cash,
+                format!(
+                    "{} cash would be {:.2} {} after pending buys",
+                    cash.account, b.projected_cash, b.currency
+                ),
+            );
+        }
+    }
+    if cash.projected_cash < -CASH_EPSILON {
+        warn(
+            base,
+            CashWarningKind::MarginBorrow,
+            cash.projected_cash,
+            format!(
+                "{} would borrow {:.2} {base} on margin after pending buys",
+                cash.account, -cash.projected_cash
+            ),
+        );
+    }
+    if let Some(bp) = cash.projected_buying_power.filter(|bp| *bp < -CASH_EPSILON) {
+        warn(
+            base,
+            CashWarningKind::BuyingPowerExceeded,
+            bp,
+            format!(
+                "{} pending buys exceed buying power by {:.2} {base}",
+                cash.account, -bp
+            ),
+        );
+    }
+
+    let mut base_surplus = cash
+        .balances
+        .iter()
+        .find(|b| b.currency == base)
+        .map_or(0.0, |b| b.projected_cash);
+    for b in cash.balances.iter().filter(|b| b.currency != base) {
+        if b.projected_cash >= -CASH_EPSILON {
+            continue;
+        }
+        let Some(rate) = rate(rows, base, &b.currency) else {
+            continue;
+        };
+        let from_amount = -b.projected_cash * rate;
+        if from_amount > base_surplus {
+            continue;
+        }
+        base_surplus -= from_amount;
+        overview.sweeps.push(CashSweep {
+            account: cash.account.clone(),
+            from_currency: base.to_string(),
+            to_currency: b.currency.clone(),
+            amount: -b.projected_cash,
+            from_amount,
+        });
+    }
+}
+
+fn parse(value: &str) -> f64 {
+    value.trim().parse().unwrap_or(0.0)
+}
@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::*;
+use crate::ibkr::error::Result as IbkrResult;
+use crate::ibkr::types::{MarketDataSnapshot, OrderAction};
+
+struct StubSource {
+    values: Vec<(String, Vec<AccountSummary>)>,
+    orders: Vec<OpenOrder>,
+}
+
+#[async_trait]
+impl CashSource for StubSource {
+    async fn accounts(&self) -> Result<Vec<String>, IbkrError> {
+        Ok(self.values.iter().map(|(a, _)| a.clone()).collect())
+    }
+
+    async fn account_values(&self, account: &str) -> Result<Vec<AccountSummary>, IbkrError> {
+        Ok(self
+            .values
+            .iter()
+            .find(|(a, _)| a == account)
+            .map(|(_, rows)| rows.clone())
+            .unwrap_or_default())
+    }
+
+    async fn open_orders(&self) -> Result<Vec<OpenOrder>, IbkrError> {
+        Ok(self.orders.clone())
+    }
+}
+
+/// Quotes every symbol at 100.
+struct FlatQuotes;
+
+#[async_trait]
+impl QuoteFetcher for FlatQuotes {
+    async fn get_market_data_snapshot(&self, symbol: &str) -> IbkrResult<MarketDataSnapshot> {
+        Ok(MarketDataSnapshot {
+            symbol: symbol.to_string(),
+            bid_price: None,
+            bid_size: None,
+            ask_price: None,
+            ask_size: None,
+            last_price: Some(100.0),
+            last_size: None,
+            high: None,
+            low: None,
+            volume: None,
+            close: None,
+            open: None,
+            last_rth_price: None,
+            session: None,
+            timestamp: 0,
+        })
+    }
+}
+
+fn row(account: &str, tag: &str, value: &str, currency: &str) -> AccountSummary {
+    AccountSummary {
+        account: account.to_string(),
+        tag: tag.to_string(),
+        value: value.to_string(),
+        currency: currency.to_string(),
+    }
+}
+
+fn order(
+    order_id: i32,
+    account: &str,
+    symbol: &str,
+    currency: &str,
+    action: OrderAction,
+    remaining: f64,
+    limit_price: Option<f64>,
+) -> OpenOrder {
+    OpenOrder {
+        order_id,
+        account: account.to_string(),
+        symbol: symbol.to_string(),
+        sec_type: "STK".to_string(),
+        currency: currency.to_string(),
+        action,
+        remaining,
+        order_type: if limit_price.is_some() { "LMT" } else { "MKT" }.to_string(),
+        limit_price,
+        aux_price: None,
+        multiplier: 1.0,
+        status: "Submitted".to_string(),
+    }
+}
+
+fn service(source: StubSource) -> (CashManagementService, Arc<EventEmitter>) {
+    let emitter = Arc::new(EventEmitter::for_capture());
+    let svc = CashManagementService::new(Arc::new(source), Arc::new(FlatQuotes), emitter.clone());
+    (svc, emitter)
+}
+
+#[tokio::test]
+async fn projects_pending_buys_per_currency_and_suggests_fx_sweep() {
+    let values = vec![
+        row("DU1", "CashBalance", "10000", "USD"),
+        row("DU1", "CashBalance", "200", "EUR"),
+        row("DU1", "CashBalance", "10216", "BASE"),
+        row("DU1", "TotalCashValue", "10216", "USD"),
+        row("DU1", "BuyingPower", "40000", "USD"),
+        row("DU1", "ExchangeRate", "1.08", "EUR"),
+    ];
+    let (svc, emitter) = service(StubSource {
+        values: vec![("DU1".to_string(), values)],
+        orders: vec![
+            order(1, "DU1", "SAP", "EUR", OrderAction::Buy, 20.0, Some(150.0)),
+            // Market order: priced at the quoted 100.
+            order(2, "DU1", "AAPL", "USD", OrderAction::Buy, 50.0, None),
+            order(
+                3,
+                "DU1",
+                "MSFT",
+                "USD",
+                OrderAction::Sell,
+                10.0,
+                Some(400.0),
+            ),
+            order(4, "DU9", "TSLA", "USD", OrderAction::Buy, 1.0, Some(200.0)),
+        ],
+    });
+    let overview = svc.overview().await.unwrap();
+
+    let account = &overview.accounts[0];
+    let balances: Vec<_> = account
+        .balances
+        .iter()
+        .map(|b| (b.currency.as_str(), b.pending_buys, b.projected_cash))
+        .collect();
+    assert_eq!(
+        balances,
+        vec![("EUR", 3_000.0, -2_800.0), ("USD", 5_000.0, 5_000.0)]
+    );
+    assert_eq!(account.balances[1].pending_sells, 4_000.0);
+    assert!((account.pending_buys_base - 8_240.0).abs() < 1e-6);
+    assert!((account.projected_cash - 1_976.0).abs() < 1e-6);
+
+    assert_eq!(overview.warnings.len(), 1);
+    assert_eq!(overview.warnings[0].kind, CashWarningKind::NegativeCash);
+    assert_eq!(overview.warnings[0].currency, "EUR");
+    assert_eq!(overview.sweeps.len(), 1);
+    assert_eq!(overview.sweeps[0].to_currency, "EUR");
+    assert!((overview.sweeps[0].from_amount - 3_024.0).abs() < 1e-6);
+    assert_eq!(overview.notes.len(), 1);
+    assert!(overview.notes[0].contains("DU9"));
+
+    // Same condition on the next read: no second event.
+    svc.overview().await.unwrap();
+    let events = emitter.captured().await;
+    assert_eq!(events.len(), 1);
+    assert!(matches!(&events[0], AppEvent::CashWarning { warning } if warning.currency == "EUR"));
+}
+
+#[tokio::test]
+async fn aggregates_accounts_and_flags_margin_borrow() {
+    let account = |id: &str, cash: &str, buying_power: &str| {
+        (
+            id.to_string(),
+            vec![
+                row(id, "TotalCashValue", cash, "USD"),
+                row(id, "BuyingPower", buying_power, "USD"),
+            ],
+        )
+    };
+    let mut option = order(2, "DU2", "AAPL", "USD", OrderAction::Buy, 1.0, None);
+    option.sec_type = "OPT".to_string();
+    option.multiplier = 100.0;
+    let (svc, emitter) = service(StubSource {
+        values: vec![
+            account("DU1", "1000", "1000"),
+            account("DU2", "5000", "20000"),
+        ],
+        orders: vec![
+            order(1, "DU1", "AAPL", "USD", OrderAction::Buy, 15.0, Some(100.0)),
+            option,
+        ],
+    });
+    let overview = svc.overview().await.unwrap();
+
+    assert_eq!(overview.currencies.len(), 1);
+    assert_eq!(overview.currencies[0].cash, 6_000.0);
+    assert_eq!(overview.currencies[0].projected_cash, 4_500.0);
+
+    let kinds: Vec<_> = overview
+        .warnings
+        .iter()
+        .map(|w| (w.account.as_str(), w.kind))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("DU1", CashWarningKind::NegativeCash),
+            ("DU1", CashWarningKind::MarginBorrow),
+            ("DU1", CashWarningKind::BuyingPowerExceeded),
+        ]
+    );
+    assert!(overview.sweeps.is_empty());
+    // Unquoted option market order.
+    assert_eq!(overview.notes, vec!["order 2 (AAPL): no price, left out"]);
+    assert_eq!(emitter.captured().await.len(), 3);
+}
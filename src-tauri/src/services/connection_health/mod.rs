@@ -0,0 +1,196 @@
+//! Connection heartbeat — pings TWS `server_time` on a fixed cadence and
+//! keeps latency / uptime metrics for `get_connection_metrics`.
+//!
+//! Each tick asks the [`HeartbeatProbe`] (the real client's
+//! `get_connection_status`, already bounded by its own 2s timeout) and
+//! records the round trip into a fixed ring of recent samples. A sample
+//! counts as a *spike* when it exceeds both [`SPIKE_FLOOR_MS`] and
+//! [`SPIKE_FACTOR`] × the rolling p50; the first spike emits
+//! `ConnectionDegraded`, and the next normal sample re-arms it.
+//!
+//! A connected → disconnected transition bumps `disconnect_count` and
+//! resets `connected_since`. Ticks while disconnected record nothing, so
+//! the percentiles describe the live connection only.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::events::{AppEvent, EventEmitter};
+use crate::ibkr::client::{IbkrClient, StreamHandle};
+
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+/// Samples kept for the percentiles (~30 min at the default cadence).
+const WINDOW: usize = 120;
+/// Spikes below this are noise on a localhost socket.
+pub const SPIKE_FLOOR_MS: f64 = 250.0;
+pub const SPIKE_FACTOR: f64 = 4.0;
+/// Rolling p50 needs a few samples before spikes mean anything.
+const MIN_SAMPLES_FOR_SPIKE: usize = 5;
+
+/// Narrow seam over `IbkrClient` so tests drive the heartbeat without a
+/// socket. `false` = not connected, or `server_time` failed / timed out.
+#[async_trait]
+pub trait HeartbeatProbe: Send + Sync {
+    async fn ping(&self) -> bool;
+}
+
+#[async_trait]
+impl HeartbeatProbe for IbkrClient {
+    async fn ping(&self) -> bool {
+        self.get_connection_status()
+            .await
+            .map(|s| s.connected)
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct ConnectionMetrics {
+    pub connected: bool,
+    pub latency_last_ms: Option<f64>,
+    pub latency_p50_ms: Option<f64>,
+    pub latency_p95_ms: Option<f64>,
+    pub samples: usize,
+    pub disconnect_count: u32,
+    pub connected_since: Option<DateTime<Utc>>,
+    pub uptime_secs: Option<i64>,
+}
+
+#[derive(Default)]
+struct HealthState {
+    latencies_ms: VecDeque<f64>,
+    connected: bool,
+    connected_since: Option<DateTime<Utc>>,
+    disconnect_count: u32,
+    degraded: bool,
+}
+
+/// Nearest-rank percentile over an unsorted sample.
+fn percentile(samples: &VecDeque<f64>, p: f64) -> Option<f64> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut sorted: Vec<f64> = samples.iter().copied().collect();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+pub struct ConnectionHealth {
+    probe: Arc<dyn HeartbeatProbe>,
+    emitter: Arc<EventEmitter>,
+    state: Mutex<HealthState>,
+}
+
+impl ConnectionHealth {
+    pub fn new(probe: Arc<dyn HeartbeatProbe>, emitter: Arc<EventEmitter>) -> Self {
+        Self {
+            probe,
+            emitter,
+            state: Mutex::new(HealthState::default()),
+        }
+    }
+
+    /// One heartbeat: ping, then fold the result into the metrics.
+    pub async fn tick(&self) {
+        let started = Instant::now();
+        let connected = self.probe.ping().await;
+        let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+        self.record(connected, latency_ms, Utc::now()).await;
+    }
+
+    /// Split out from [`Self::tick`] so tests can feed exact latencies.
+    pub(crate) async fn record(&self, connected: bool, latency_ms: f64, now: DateTime<Utc>) {
+        let degraded_event = {
+            let mut s = self.state.lock().await;
+            if !connected {
+                if s.connected {
+                    s.disconnect_count += 1;
+                    warn!("heartbeat: IBKR connection lost");
+                }
+                s.connected = false;
+                s.connected_since = None;
+                s.degraded = false;
+                return;
+            }
+            if !s.connected {
+                s.connected = true;
+                s.connected_since = Some(now);
+            }
+
+            let baseline = percentile(&s.latencies_ms, 50.0);
+            let spike = s.latencies_ms.len() >= MIN_SAMPLES_FOR_SPIKE
+                && baseline.is_some_and(|p50| {
+                    latency_ms > SPIKE_FLOOR_MS && latency_ms > p50 * SPIKE_FACTOR
+                });
+            if s.latencies_ms.len() == WINDOW {
+                s.latencies_ms.pop_front();
+            }
+            s.latencies_ms.push_back(latency_ms);
+
+            let fire = spike && !s.degraded;
+            s.degraded = spike;
+            fire.then(|| AppEvent::ConnectionDegraded {
+                latency_ms,
+                p50_ms: baseline.unwrap_or_default(),
+            })
+        };
+        if let Some(event) = degraded_event {
+            warn!("heartbeat: IBKR latency spike ({latency_ms:.0}ms)");
+            if let Err(e) = self.emitter.emit(event).await {
+                tracing::debug!("ConnectionDegraded emit skipped: {e}");
+            }
+        }
+    }
+
+    pub async fn metrics(&self) -> ConnectionMetrics {
+        self.metrics_at(Utc::now()).await
+    }
+
+    async fn metrics_at(&self, now: DateTime<Utc>) -> ConnectionMetrics {
+        let s = self.state.lock().await;
+        ConnectionMetrics {
+            connected: s.connected,
+            latency_last_ms: s.latencies_ms.back().copied(),
+            latency_p50_ms: percentile(&s.latencies_ms, 50.0),
+            latency_p95_ms: percentile(&s.latencies_ms, 95.0),
+            samples: s.latencies_ms.len(),
+            disconnect_count: s.disconnect_count,
+            connected_since: s.connected_since,
+            uptime_secs: s.connected_since.map(|t| (now - t).num_seconds()),
+        }
+    }
+
+    /// Spawn the heartbeat loop. Same shape as the other schedulers:
+    /// caller holds the [`StreamHandle`] and stops it on shutdown.
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> StreamHandle {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let shutdown_task = Arc::clone(&shutdown);
+        let health = Arc::clone(&self);
+
+        let join = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if shutdown_task.load(Ordering::Relaxed) {
+                    break;
+                }
+                health.tick().await;
+            }
+            info!("connection heartbeat stopped");
+        });
+
+        StreamHandle::new("connection heartbeat", shutdown, join)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,74 @@
+use super::*;
+
+struct AlwaysUp;
+
+#[async_trait]
+impl HeartbeatProbe for AlwaysUp {
+    async fn ping(&self) -> bool {
+        true
+    }
+}
+
+fn health() -> (ConnectionHealth, Arc<EventEmitter>) {
+    let emitter = Arc::new(EventEmitter::for_capture());
+    (
+        ConnectionHealth::new(Arc::new(AlwaysUp), Arc::clone(&emitter)),
+        emitter,
+    )
+}
+
+fn at(secs: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(1_800_000_000 + secs, 0).unwrap()
+}
+
+async fn degraded_count(emitter: &EventEmitter) -> usize {
+    emitter
+        .captured()
+        .await
+        .iter()
+        .filter(|e| matches!(e, AppEvent::ConnectionDegraded { .. }))
+        .count()
+}
+
+#[tokio::test]
+async fn percentiles_uptime_and_disconnects() {
+    let (health, _) = health();
+    for (i, ms) in [10.0, 20.0, 30.0, 40.0, 50.0].into_iter().enumerate() {
+        health.record(true, ms, at(i as i64 * 15)).await;
+    }
+    let m = health.metrics_at(at(100)).await;
+    assert_eq!(m.latency_p50_ms, Some(30.0));
+    assert_eq!(m.latency_p95_ms, Some(50.0));
+    assert_eq!(m.latency_last_ms, Some(50.0));
+    assert_eq!(m.uptime_secs, Some(100));
+
+    health.record(false, 0.0, at(110)).await;
+    health.record(false, 0.0, at(125)).await;
+    let m = health.metrics_at(at(130)).await;
+    assert!(!m.connected);
+    assert_eq!(m.disconnect_count, 1);
+    assert_eq!(m.uptime_secs, None);
+    assert_eq!(m.samples, 5, "disconnected ticks record no latency");
+
+    health.record(true, 10.0, at(140)).await;
+    assert_eq!(health.metrics_at(at(150)).await.uptime_secs, Some(10));
+}
+
+#[tokio::test]
+async fn latency_spike_emits_once_until_recovered() {
+    let (health, emitter) = health();
+    for i in 0..5 {
+        health.record(true, 20.0, at(i)).await;
+    }
+    // Above the floor but not 4x p50 → not a spike.
+    health.record(true, 60.0, at(5)).await;
+    assert_eq!(degraded_count(&emitter).await, 0);
+
+    health.record(true, 900.0, at(6)).await;
+    health.record(true, 1_200.0, at(7)).await;
+    assert_eq!(degraded_count(&emitter).await, 1);
+
+    health.record(true, 20.0, at(8)).await;
+    health.record(true, 800.0, at(9)).await;
+    assert_eq!(degraded_count(&emitter).await, 2);
+}
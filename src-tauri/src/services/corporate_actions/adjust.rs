@@ -0,0 +1,234 @@
+//! Applying an action: the SQL behind each kind, run inside the
+//! transaction that records it, then the watchlists and caches that
+//! follow.
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use chrono_tz::America::New_York;
+use rusqlite::{params, Connection};
+use tracing::{info, warn};
+
+use super::{
+    store, ActionSource, AppliedAction, CorporateAction, CorporateActionError,
+    CorporateActionService, TableAdjustment,
+};
+use crate::events::AppEvent;
+
+/// Tables whose `symbol` follows a symbol change. `position_plans` is
+/// renamed separately, with its stock contract key.
+const RENAMED: &[&str] = &[
+    "imported_lots",
+    "bars_cache",
+    "bars_cache_coverage",
+    "tracked_tickers",
+    "research_notes",
+    "trade_ideas",
+    "automation_rules",
+    "fundamentals_overrides",
+    "ticker_projection_templates",
+    "margin_of_safety_bands",
+];
+
+/// Unix seconds at the start of `date` in New York: rows written before
+/// it predate the action.
+fn et_start(date: NaiveDate) -> i64 {
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|t| New_York.from_local_datetime(&t).earliest())
+        .map_or(0, |t| t.timestamp())
+}
+
+/// Cached bars are stamped with exchange-local wall time read as UTC
+/// (daily bars at midnight), so their cutoff is `date` at midnight UTC.
+fn bar_start(date: NaiveDate) -> i64 {
+    date.and_hms_opt(0, 0, 0)
+        .map_or(0, |t| t.and_utc().timestamp())
+}
+
+fn push(adjusted: &mut Vec<TableAdjustment>, table: &str, rows: usize) {
+    if rows > 0 {
+        adjusted.push(TableAdjustment {
+            table: table.to_string(),
+            rows,
+        });
+    }
+}
+
+impl CorporateActionService {
+    /// Adjust local data for `action` and record it. An action already
+    /// recorded with the same kind, symbol and date is refused.
+    pub async fn apply(
+        &self,
+        action: CorporateAction,
+        source: ActionSource,
+    ) -> Result<AppliedAction, CorporateActionError> {
+        let action = action.normalized();
+        if store::exists(&self.db, &action).await? {
+            return Err(CorporateActionError::AlreadyApplied(action.describe()));
+        }
+        // Settings first: the row below then records what was renamed.
+        let renamed = match &action {
+            CorporateAction::SymbolChange { from, to, .. } => {
+                self.rename_in_watchlists(from, to).await?
+            }
+            _ => 0,
+        };
+        let applied_at = Utc::now().timestamp();
+        let record = action.clone();
+        let row = self
+            .db
+            .with_conn(move |conn| {
+                let tx = conn.transaction()?;
+                if store::exists_in(&tx, &record)? {
+                    return Ok(None);
+                }
+                let mut adjusted = apply_in(&tx, &record)?;
+                if renamed > 0 {
+                    adjusted.push(TableAdjustment {
+                        table: "workspaces.watchlist".to_string(),
+                        rows: renamed,
+                    });
+                }
+                let id = store::insert(&tx, &record, source, &adjusted, applied_at)?;
+                tx.commit()?;
+                Ok(Some((id, adjusted)))
+            })
+            .await?;
+        let Some((id, adjusted)) = row else {
+            return Err(CorporateActionError::AlreadyApplied(action.describe()));
+        };
+
+        let stale: Vec<&str> = match &action {
+            CorporateAction::Split { symbol, .. } => vec![symbol],
+            CorporateAction::SymbolChange { from, to, .. } => vec![from, to],
+            CorporateAction::SpinOff { .. } => Vec::new(),
+        };
+        for symbol in stale {
+            for cache in &self.caches {
+                cache.invalidate(symbol);
+            }
+        }
+        info!(
+            "corporate_actions: applied {} ({})",
+            action.describe(),
+            source.as_str()
+        );
+        let event = AppEvent::CorporateActionApplied {
+            action: action.clone(),
+            source: source.as_str().to_string(),
+            adjusted: adjusted.clone(),
+        };
+        if let Err(e) = self.emitter.emit(event).await {
+            warn!("corporate_actions: emit failed: {e}");
+        }
+        Ok(AppliedAction {
+            id,
+            action,
+            source,
+            adjusted,
+            applied_at,
+        })
+    }
+
+    /// Rename `from` to `to` in every workspace watchlist and persist
+    /// the settings when one changed.
+    async fn rename_in_watchlists(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<usize, CorporateActionError> {
+        let mut config = self.config.write().await;
+        let mut next = config.clone();
+        let renamed = next.workspaces.rename_symbol(from, to);
+        if renamed > 0 {
+            next.save()
+                .await
+                .map_err(|e| CorporateActionError::Settings(e.to_string()))?;
+            *config = next;
+        }
+        Ok(renamed)
+    }
+}
+
+fn apply_in(conn: &Connection, action: &CorporateAction) -> rusqlite::Result<Vec<TableAdjustment>> {
+    let mut adjusted = Vec::new();
+    match action {
+        CorporateAction::Split {
+            symbol,
+            effective_date,
+            ratio,
+        } => {
+            let before = et_start(*effective_date);
+            let rows = conn.execute(
+                "UPDATE imported_lots \
+                 SET quantity = quantity * ?3, last_price = last_price / ?3 \
+                 WHERE symbol = ?1 AND imported_at_unix < ?2",
+                params![symbol, before, ratio],
+            )?;
+            push(&mut adjusted, "imported_lots", rows);
+            let rows = conn.execute(
+                "UPDATE bars_cache \
+                 SET open = open / ?3, high = high / ?3, low = low / ?3, close = close / ?3, \
+                     wap = wap / ?3, volume = CAST(ROUND(volume * ?3) AS INTEGER) \
+                 WHERE symbol = ?1 AND bar_time < ?2",
+                params![symbol, bar_start(*effective_date), ratio],
+            )?;
+            push(&mut adjusted, "bars_cache", rows);
+            let rows = conn.execute(
+                "UPDATE position_plans \
+                 SET target_price = target_price / ?3, stop_price = stop_price / ?3 \
+                 WHERE contract = ?1 AND updated_at < ?2",
+                params![symbol, before, ratio],
+            )?;
+            push(&mut adjusted, "position_plans", rows);
+            let rows = conn.execute(
+                "UPDATE automation_rules \
+                 SET condition = json_set(condition, '$.level', \
+                                          json_extract(condition, '$.level') / ?3) \
+                 WHERE symbol = ?1 AND updated_at < ?2 \
+                   AND json_extract(condition, '$.kind') = 'price'",
+                params![symbol, before, ratio],
+            )?;
+            push(&mut adjusted, "automation_rules", rows);
+        }
+        CorporateAction::SymbolChange { from, to, .. } => {
+            for table in RENAMED {
+                let rows = conn.execute(
+                    &format!("UPDATE OR IGNORE {table} SET symbol = ?2 WHERE symbol = ?1"),
+                    params![from, to],
+                )?;
+                push(&mut adjusted, table, rows);
+            }
+            let rows = conn.execute(
+                "UPDATE OR IGNORE position_plans \
+                 SET symbol = ?2, contract = CASE WHEN contract = ?1 THEN ?2 ELSE contract END \
+                 WHERE symbol = ?1",
+                params![from, to],
+            )?;
+            push(&mut adjusted, "position_plans", rows);
+        }
+        CorporateAction::SpinOff {
+            symbol,
+            new_symbol,
+            effective_date,
+            ratio,
+            cost_fraction,
+        } => {
+            let before = et_start(*effective_date);
+            let rows = conn.execute(
+                "INSERT INTO imported_lots \
+                   (account, source, symbol, quantity, cost_basis_total, last_price, \
+                    acquired_date, currency, imported_at_unix) \
+                 SELECT account, source, ?2, quantity * ?4, cost_basis_total * ?5, NULL, \
+                        acquired_date, currency, imported_at_unix \
+                 FROM imported_lots WHERE symbol = ?1 AND imported_at_unix < ?3",
+                params![symbol, new_symbol, before, ratio, cost_fraction],
+            )?;
+            conn.execute(
+                "UPDATE imported_lots SET cost_basis_total = cost_basis_total * (1 - ?3) \
+                 WHERE symbol = ?1 AND imported_at_unix < ?2",
+                params![symbol, before, cost_fraction],
+            )?;
+            push(&mut adjusted, "imported_lots", rows);
+        }
+    }
+    Ok(adjusted)
+}
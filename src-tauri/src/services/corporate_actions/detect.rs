@@ -0,0 +1,87 @@
+//! Splits and symbol changes read off two position snapshots.
+
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+
+use super::CorporateAction;
+use crate::services::cost_basis::book::QTY_EPSILON;
+use crate::services::portfolio_diff::Holding;
+
+/// How far the average cost may stray, relatively, from what the
+/// quantity change implies. Brokers round the adjusted cost.
+const COST_TOLERANCE: f64 = 0.01;
+
+fn same(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() <= tolerance * a.abs().max(b.abs())
+}
+
+/// Untraded stock holdings, by symbol.
+fn stocks<'a>(holdings: &'a [Holding], traded: &HashSet<String>) -> Vec<&'a Holding> {
+    holdings
+        .iter()
+        .filter(|h| h.contract_type == "STK" && !traded.contains(&h.symbol))
+        .filter(|h| h.quantity.abs() > QTY_EPSILON && h.average_cost > 0.0)
+        .collect()
+}
+
+/// Actions implied by one account's stock moving from `before` to
+/// `after`, dated `date`. Symbols in `traded` had a fill in between
+/// and are left alone.
+pub(super) fn from_snapshots(
+    before: &[Holding],
+    after: &[Holding],
+    traded: &HashSet<String>,
+    date: NaiveDate,
+) -> Vec<CorporateAction> {
+    let before = stocks(before, traded);
+    let after = stocks(after, traded);
+    let mut found = Vec::new();
+
+    // Quantity times the ratio, average cost divided by it.
+    for b in &before {
+        let Some(a) = after.iter().find(|a| a.symbol == b.symbol) else {
+            continue;
+        };
+        let ratio = a.quantity / b.quantity;
+        if ratio <= 0.0 || same(ratio, 1.0, 1e-6) {
+            continue;
+        }
+        if same(b.average_cost / a.average_cost, ratio, COST_TOLERANCE) {
+            found.push(CorporateAction::Split {
+                symbol: b.symbol.clone(),
+                effective_date: date,
+                ratio: (ratio * 1e6).round() / 1e6,
+            });
+        }
+    }
+
+    // Same position under a new name; ambiguous pairs are left out.
+    let gone: Vec<&Holding> = before
+        .iter()
+        .copied()
+        .filter(|b| !after.iter().any(|a| a.symbol == b.symbol))
+        .collect();
+    let new: Vec<&Holding> = after
+        .iter()
+        .copied()
+        .filter(|a| !before.iter().any(|b| b.symbol == a.symbol))
+        .collect();
+    let matches = |b: &Holding, a: &Holding| {
+        same(a.quantity, b.quantity, 1e-6) && same(a.average_cost, b.average_cost, COST_TOLERANCE)
+    };
+    for &b in &gone {
+        let candidates: Vec<&Holding> = new.iter().copied().filter(|&a| matches(b, a)).collect();
+        let &[a] = candidates.as_slice() else {
+            continue;
+        };
+        if gone.iter().filter(|&&other| matches(other, a)).count() == 1 {
+            found.push(CorporateAction::SymbolChange {
+                from: b.symbol.clone(),
+                to: a.symbol.clone(),
+                effective_date: date,
+            });
+        }
+    }
+    found
+}
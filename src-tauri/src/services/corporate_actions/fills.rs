@@ -0,0 +1,53 @@
+//! Stored fills restated across the splits recorded after them.
+//!
+//! The executions table keeps the shares and price each fill traded
+//! at. The books rebuilt from it (`services::cost_basis`,
+//! `services::tax_lots`) start from imported lots and are compared with
+//! positions a split already adjusted, so they read fills through
+//! [`split_adjusted`].
+
+use crate::ibkr::types::IbkrExecution;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::market_calendar::et_date;
+use crate::utils::symbols;
+
+use super::{store, CorporateAction};
+
+/// `fills` with every stock fill from before a recorded split of its
+/// symbol in post-split shares: quantity times the ratio, price divided
+/// by it. Commissions stay as charged.
+pub async fn split_adjusted(
+    db: &Db,
+    mut fills: Vec<IbkrExecution>,
+) -> Result<Vec<IbkrExecution>, StorageError> {
+    let splits = store::splits(db).await?;
+    restate(&mut fills, &splits);
+    Ok(fills)
+}
+
+pub(super) fn restate(fills: &mut [IbkrExecution], splits: &[CorporateAction]) {
+    for fill in fills.iter_mut().filter(|f| is_stock(f)) {
+        let symbol = symbols::normalize(&fill.symbol);
+        let day = et_date(fill.exec_time);
+        for split in splits {
+            if let CorporateAction::Split {
+                symbol: split_symbol,
+                effective_date,
+                ratio,
+            } = split
+            {
+                if *split_symbol == symbol && day < *effective_date && *ratio > 0.0 {
+                    fill.qty *= ratio;
+                    fill.avg_price /= ratio;
+                }
+            }
+        }
+    }
+}
+
+/// Options on a split stock are adjusted by the exchange into a new
+/// deliverable, not restated per share.
+fn is_stock(fill: &IbkrExecution) -> bool {
+    fill.strike.is_none() && fill.right.is_none()
+}
@@ -0,0 +1,426 @@
+//! Corporate actions: splits, symbol changes and spin-offs, applied to
+//! the local data that holds a symbol or a per-share figure.
+//!
+//! The `corporate_action_check` task (`services::scheduler`, 07:30 ET
+//! on trading days by default) looks in two places. It reads the Alpha
+//! Vantage split history of every held stock (imported lots, and the
+//! newest position snapshot of each account) and takes the splits
+//! effective in the last [`PROVIDER_LOOKBACK_DAYS`]; AV's file cache
+//! keeps a history for up to a week. It also compares each account's
+//! two newest `position_snapshots` (`services::portfolio_diff`). With no
+//! fill on the symbol in between, a stock whose quantity and average
+//! cost moved by inverse ratios was split, and a stock that disappeared
+//! while another appeared with the same quantity and average cost
+//! changed symbol. Those take the newer snapshot's date as their
+//! effective date. A detected split within [`SAME_SPLIT_DAYS`] of one
+//! already applied to the symbol is taken to be the same split.
+//! Spin-offs aren't detected; `apply_corporate_action` records them,
+//! or any other action, by hand.
+//!
+//! Applying an action adjusts, in the transaction that records it in
+//! `corporate_actions` (V48):
+//!
+//! - a split: imported lots from exports taken before the effective
+//!   date (quantity up, basis unchanged, last price down), cached bars
+//!   before it (prices down, volume up), and stock position plans and
+//!   price rules last set before it;
+//! - a symbol change: the symbol on imported lots, cached bars and their
+//!   coverage, the tracker, research notes, trade ideas, position plans,
+//!   automation rules, fundamentals overrides, projection templates and
+//!   margin-of-safety bands. Workspace watchlists in settings follow. A
+//!   row whose key the new symbol already holds stays behind;
+//! - a spin-off: every imported lot of the parent from before the
+//!   effective date gains a lot of the new stock, `ratio` shares per
+//!   parent share, with `cost_fraction` of the parent's basis and the
+//!   parent's acquisition date.
+//!
+//! Fills, order audit rows, setups and outcomes keep the symbol and the
+//! prices they traded at; the cost-basis and tax-lot books restate fills
+//! across later splits as they read them ([`split_adjusted`]). Once an action is applied, the symbol's
+//! in-memory bar series and Alpha Vantage fundamentals are dropped so
+//! they're re-read, and [`AppEvent::CorporateActionApplied`] lists the
+//! rows adjusted per table. Each action is applied at most once.
+//!
+//! [`AppEvent::CorporateActionApplied`]: crate::events::AppEvent::CorporateActionApplied
+
+use std::collections::{BTreeSet, HashSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDate};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::config::AppConfig;
+use crate::events::EventEmitter;
+use crate::services::executions::ExecutionsStore;
+use crate::services::financial_data_service::FinancialDataService;
+use crate::services::historical_data_service::HistoricalDataService;
+use crate::services::portfolio_diff::store as snapshots;
+use crate::services::portfolio_import::{self, ImportError};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::market_calendar::et_date;
+use crate::utils::symbols;
+
+mod adjust;
+mod detect;
+mod fills;
+mod store;
+
+#[cfg(test)]
+mod tests;
+
+pub use fills::split_adjusted;
+
+/// Provider splits effective this many days back are still applied.
+pub const PROVIDER_LOOKBACK_DAYS: i64 = 30;
+
+/// A detected split this close to one already applied to the symbol is
+/// the same split, seen from the other source.
+pub const SAME_SPLIT_DAYS: i64 = 30;
+
+/// Tagged by `kind`; dates are the first session after the action.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CorporateAction {
+    /// `ratio` new shares per old share; below 1 for a reverse split.
+    Split {
+        symbol: String,
+        effective_date: NaiveDate,
+        ratio: f64,
+    },
+    SymbolChange {
+        from: String,
+        to: String,
+        effective_date: NaiveDate,
+    },
+    /// `ratio` shares of `new_symbol` per parent share, carrying
+    /// `cost_fraction` (0 to 1) of the parent's basis.
+    SpinOff {
+        symbol: String,
+        new_symbol: String,
+        effective_date: NaiveDate,
+        ratio: f64,
+        cost_fraction: f64,
+    },
+}
+
+impl CorporateAction {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CorporateAction::Split { .. } => "split",
+            CorporateAction::SymbolChange { .. } => "symbol_change",
+            CorporateAction::SpinOff { .. } => "spin_off",
+        }
+    }
+
+    /// The symbol before the action.
+    pub fn symbol(&self) -> &str {
+        match self {
+            CorporateAction::Split { symbol, .. } | CorporateAction::SpinOff { symbol, .. } => {
+                symbol
+            }
+            CorporateAction::SymbolChange { from, .. } => from,
+        }
+    }
+
+    pub fn effective_date(&self) -> NaiveDate {
+        match *self {
+            CorporateAction::Split { effective_date, .. }
+            | CorporateAction::SymbolChange { effective_date, .. }
+            | CorporateAction::SpinOff { effective_date, .. } => effective_date,
+        }
+    }
+
+    /// E.g. `NVDA 10-for-1 split on 2024-06-10`.
+    pub fn describe(&self) -> String {
+        let what = match self {
+            CorporateAction::Split { symbol, ratio, .. } if *ratio >= 1.0 => {
+                format!("{symbol} {}-for-1 split", ratio_label(*ratio))
+            }
+            CorporateAction::Split { symbol, ratio, .. } => {
+                format!("{symbol} 1-for-{} reverse split", ratio_label(1.0 / ratio))
+            }
+            CorporateAction::SymbolChange { from, to, .. } => format!("{from} renamed {to}"),
+            CorporateAction::SpinOff {
+                symbol,
+                new_symbol,
+                ratio,
+                ..
+            } => format!(
+                "{symbol} spin-off of {} {new_symbol} per share",
+                ratio_label(*ratio)
+            ),
+        };
+        format!("{what} on {}", self.effective_date())
+    }
+
+    /// Every symbol upper-cased the way the rest of the app stores it.
+    fn normalized(mut self) -> Self {
+        match &mut self {
+            CorporateAction::Split { symbol, .. } => *symbol = symbols::normalize(symbol),
+            CorporateAction::SymbolChange { from, to, .. } => {
+                *from = symbols::normalize(from);
+                *to = symbols::normalize(to);
+            }
+            CorporateAction::SpinOff {
+                symbol, new_symbol, ..
+            } => {
+                *symbol = symbols::normalize(symbol);
+                *new_symbol = symbols::normalize(new_symbol);
+            }
+        }
+        self
+    }
+}
+
+/// Up to four decimals, without trailing zeros.
+fn ratio_label(ratio: f64) -> String {
+    let s = format!("{ratio:.4}");
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Stored as `corporate_actions.source`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionSource {
+    /// Alpha Vantage's split history.
+    Provider,
+    /// Two position snapshots.
+    Ibkr,
+    Manual,
+}
+
+impl ActionSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActionSource::Provider => "provider",
+            ActionSource::Ibkr => "ibkr",
+            ActionSource::Manual => "manual",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "provider" => ActionSource::Provider,
+            "ibkr" => ActionSource::Ibkr,
+            _ => ActionSource::Manual,
+        }
+    }
+}
+
+/// Rows one action changed in one table. `workspaces.watchlist` counts
+/// the workspaces whose watchlist was renamed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TableAdjustment {
+    pub table: String,
+    pub rows: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedAction {
+    pub id: i64,
+    pub action: CorporateAction,
+    pub source: ActionSource,
+    /// Tables with at least one row changed.
+    pub adjusted: Vec<TableAdjustment>,
+    /// Unix seconds.
+    pub applied_at: i64,
+}
+
+#[derive(Error, Debug)]
+pub enum CorporateActionError {
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+    #[error("import: {0}")]
+    Import(#[from] ImportError),
+    #[error("{0} is already applied")]
+    AlreadyApplied(String),
+    #[error("settings: {0}")]
+    Settings(String),
+}
+
+/// Trait seam for a stock's split history. Production is the Alpha
+/// Vantage `SPLITS` feed; tests inject splits.
+#[async_trait]
+pub trait SplitHistory: Send + Sync {
+    /// `(effective date, new shares per old share)`, any order.
+    async fn splits(&self, symbol: &str) -> Result<Vec<(NaiveDate, f64)>, String>;
+}
+
+#[async_trait]
+impl SplitHistory for FinancialDataService {
+    async fn splits(&self, symbol: &str) -> Result<Vec<(NaiveDate, f64)>, String> {
+        let history = self
+            .split_history(symbol)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(history
+            .into_iter()
+            .filter_map(|(date, ratio)| {
+                Some((NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok()?, ratio))
+            })
+            .collect())
+    }
+}
+
+/// Per-symbol data that goes stale when a symbol's shares or ticker
+/// change under it.
+pub trait SymbolCache: Send + Sync {
+    fn invalidate(&self, symbol: &str);
+}
+
+impl SymbolCache for HistoricalDataService {
+    fn invalidate(&self, symbol: &str) {
+        self.forget(symbol);
+    }
+}
+
+impl SymbolCache for FinancialDataService {
+    fn invalidate(&self, symbol: &str) {
+        self.clear_fundamentals_cache(symbol);
+        self.clear_splits_cache(symbol);
+    }
+}
+
+pub struct CorporateActionService {
+    db: Arc<Db>,
+    executions: Arc<ExecutionsStore>,
+    splits: Arc<dyn SplitHistory>,
+    caches: Vec<Arc<dyn SymbolCache>>,
+    config: Arc<RwLock<AppConfig>>,
+    emitter: Arc<EventEmitter>,
+}
+
+impl CorporateActionService {
+    pub fn new(
+        db: Arc<Db>,
+        executions: Arc<ExecutionsStore>,
+        splits: Arc<dyn SplitHistory>,
+        caches: Vec<Arc<dyn SymbolCache>>,
+        config: Arc<RwLock<AppConfig>>,
+        emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            db,
+            executions,
+            splits,
+            caches,
+            config,
+            emitter,
+        }
+    }
+
+    /// Applied actions, newest first.
+    pub async fn list(&self, limit: u32) -> Result<Vec<AppliedAction>, CorporateActionError> {
+        Ok(store::list(&self.db, limit).await?)
+    }
+
+    /// Splits and symbol changes seen by the provider or in the position
+    /// snapshots as of `today`, provider splits first. Nothing is
+    /// applied.
+    pub async fn detect(
+        &self,
+        today: NaiveDate,
+    ) -> Result<Vec<(CorporateAction, ActionSource)>, CorporateActionError> {
+        let mut held: BTreeSet<String> = portfolio_import::list_lots(&self.db)
+            .await?
+            .into_iter()
+            .map(|l| l.symbol)
+            .collect();
+        let mut from_snapshots = Vec::new();
+        for account in snapshots::accounts(&self.db).await? {
+            let Some(last) = snapshots::latest_date(&self.db, &account, today).await? else {
+                continue;
+            };
+            let after = snapshots::load(&self.db, &account, last).await?;
+            held.extend(
+                after
+                    .iter()
+                    .filter(|h| h.contract_type == "STK")
+                    .map(|h| h.symbol.clone()),
+            );
+            let Some(prev) =
+                snapshots::latest_date(&self.db, &account, last - Duration::days(1)).await?
+            else {
+                continue;
+            };
+            let before = snapshots::load(&self.db, &account, prev).await?;
+            let since = prev.and_hms_opt(0, 0, 0).map(|t| t.and_utc());
+            let traded: HashSet<String> = self
+                .executions
+                .query_after(&account, since)
+                .await?
+                .into_iter()
+                .filter(|f| {
+                    let day = et_date(f.exec_time);
+                    day > prev && day <= last
+                })
+                .map(|f| f.symbol)
+                .collect();
+            from_snapshots.extend(detect::from_snapshots(&before, &after, &traded, last));
+        }
+
+        let mut found = Vec::new();
+        let earliest = today - Duration::days(PROVIDER_LOOKBACK_DAYS);
+        for symbol in &held {
+            let splits = match self.splits.splits(symbol).await {
+                Ok(splits) => splits,
+                Err(e) => {
+                    warn!("corporate_actions: no split history for {symbol}: {e}");
+                    continue;
+                }
+            };
+            for (effective_date, ratio) in splits {
+                if effective_date > earliest && effective_date <= today {
+                    found.push((
+                        CorporateAction::Split {
+                            symbol: symbol.clone(),
+                            effective_date,
+                            ratio,
+                        },
+                        ActionSource::Provider,
+                    ));
+                }
+            }
+        }
+        for action in from_snapshots {
+            if !found.iter().any(|(a, _)| *a == action) {
+                found.push((action, ActionSource::Ibkr));
+            }
+        }
+        Ok(found)
+    }
+
+    /// Detect and apply what's new. A failing action is logged and
+    /// skipped.
+    pub async fn check(
+        &self,
+        today: NaiveDate,
+    ) -> Result<Vec<AppliedAction>, CorporateActionError> {
+        let mut applied = Vec::new();
+        for (action, source) in self.detect(today).await? {
+            if let CorporateAction::Split {
+                symbol,
+                effective_date,
+                ..
+            } = &action
+            {
+                if store::split_near(&self.db, symbol, *effective_date, SAME_SPLIT_DAYS).await? {
+                    continue;
+                }
+            }
+            match self.apply(action, source).await {
+                Ok(a) => applied.push(a),
+                Err(CorporateActionError::AlreadyApplied(_)) => {}
+                Err(e) => warn!("corporate_actions: {e}"),
+            }
+        }
+        Ok(applied)
+    }
+}
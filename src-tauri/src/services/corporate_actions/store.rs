@@ -0,0 +1,125 @@
+//! `corporate_actions` reads and writes.
+
+use chrono::{Duration, NaiveDate};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::{ActionSource, AppliedAction, CorporateAction, TableAdjustment};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+const DATE_FMT: &str = "%Y-%m-%d";
+
+/// Whether `action`'s kind, symbol and date are recorded.
+pub(super) fn exists_in(conn: &Connection, action: &CorporateAction) -> Result<bool, StorageError> {
+    Ok(conn
+        .query_row(
+            "SELECT 1 FROM corporate_actions \
+             WHERE kind = ?1 AND symbol = ?2 AND effective_date = ?3",
+            params![
+                action.kind(),
+                action.symbol(),
+                action.effective_date().format(DATE_FMT).to_string()
+            ],
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some())
+}
+
+pub(super) async fn exists(db: &Db, action: &CorporateAction) -> Result<bool, StorageError> {
+    let action = action.clone();
+    db.with_conn(move |conn| exists_in(conn, &action)).await
+}
+
+pub(super) fn insert(
+    conn: &Connection,
+    action: &CorporateAction,
+    source: ActionSource,
+    adjusted: &[TableAdjustment],
+    applied_at: i64,
+) -> Result<i64, StorageError> {
+    conn.execute(
+        "INSERT INTO corporate_actions \
+           (kind, symbol, effective_date, action, source, adjusted, applied_at) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            action.kind(),
+            action.symbol(),
+            action.effective_date().format(DATE_FMT).to_string(),
+            serde_json::to_string(action)?,
+            source.as_str(),
+            serde_json::to_string(adjusted)?,
+            applied_at
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Whether a split of `symbol` is recorded within `days` of `date`.
+pub(super) async fn split_near(
+    db: &Db,
+    symbol: &str,
+    date: NaiveDate,
+    days: i64,
+) -> Result<bool, StorageError> {
+    let symbol = symbol.to_string();
+    let from = (date - Duration::days(days)).format(DATE_FMT).to_string();
+    let through = (date + Duration::days(days)).format(DATE_FMT).to_string();
+    db.with_conn(move |conn| {
+        Ok(conn
+            .query_row(
+                "SELECT 1 FROM corporate_actions \
+                 WHERE kind = 'split' AND symbol = ?1 AND effective_date BETWEEN ?2 AND ?3 \
+                 LIMIT 1",
+                params![symbol, from, through],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some())
+    })
+    .await
+}
+
+/// Every recorded split.
+pub(super) async fn splits(db: &Db) -> Result<Vec<CorporateAction>, StorageError> {
+    db.with_conn(|conn| {
+        let mut stmt =
+            conn.prepare("SELECT action FROM corporate_actions WHERE kind = 'split' ORDER BY id")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(serde_json::from_str(&row?)?);
+        }
+        Ok(out)
+    })
+    .await
+}
+
+/// Newest first.
+pub(super) async fn list(db: &Db, limit: u32) -> Result<Vec<AppliedAction>, StorageError> {
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, action, source, adjusted, applied_at FROM corporate_actions \
+             ORDER BY applied_at DESC, id DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit], |row| {
+            let action: String = row.get(1)?;
+            let source: String = row.get(2)?;
+            let adjusted: String = row.get(3)?;
+            Ok((row.get::<_, i64>(0)?, action, source, adjusted, row.get(4)?))
+        })?;
+        let mut out = Vec::new();
+        for row in rows {
+            let (id, action, source, adjusted, applied_at) = row?;
+            out.push(AppliedAction {
+                id,
+                action: serde_json::from_str(&action)?,
+                source: ActionSource::parse(&source),
+                adjusted: serde_json::from_str(&adjusted)?,
+                applied_at,
+            });
+        }
+        Ok(out)
+    })
+    .await
+}
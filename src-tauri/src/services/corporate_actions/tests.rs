@@ -0,0 +1,440 @@
+use std::sync::Mutex as StdMutex;
+
+use chrono::{TimeZone, Utc};
+use rusqlite::params;
+use tempfile::NamedTempFile;
+
+use super::*;
+use crate::events::AppEvent;
+use crate::ibkr::types::{ExecutionSide, IbkrExecution};
+use crate::services::portfolio_diff::Holding;
+
+fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+/// Unix seconds at 17:00 UTC on the day.
+fn at(y: i32, m: u32, d: u32) -> i64 {
+    Utc.with_ymd_and_hms(y, m, d, 17, 0, 0).unwrap().timestamp()
+}
+
+struct FixedSplits(Vec<(&'static str, NaiveDate, f64)>);
+
+#[async_trait]
+impl SplitHistory for FixedSplits {
+    async fn splits(&self, symbol: &str) -> Result<Vec<(NaiveDate, f64)>, String> {
+        Ok(self
+            .0
+            .iter()
+            .filter(|(s, _, _)| *s == symbol)
+            .map(|&(_, date, ratio)| (date, ratio))
+            .collect())
+    }
+}
+
+#[derive(Default)]
+struct RecordingCache(StdMutex<Vec<String>>);
+
+impl SymbolCache for RecordingCache {
+    fn invalidate(&self, symbol: &str) {
+        self.0.lock().unwrap().push(symbol.to_string());
+    }
+}
+
+struct Harness {
+    _tmp: NamedTempFile,
+    db: Arc<Db>,
+    service: CorporateActionService,
+    cache: Arc<RecordingCache>,
+    emitter: Arc<EventEmitter>,
+}
+
+fn harness(splits: Vec<(&'static str, NaiveDate, f64)>) -> Harness {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let cache = Arc::new(RecordingCache::default());
+    let emitter = Arc::new(EventEmitter::for_capture());
+    let service = CorporateActionService::new(
+        Arc::clone(&db),
+        Arc::new(ExecutionsStore::new(Arc::clone(&db))),
+        Arc::new(FixedSplits(splits)),
+        vec![Arc::clone(&cache) as Arc<dyn SymbolCache>],
+        Arc::new(RwLock::new(AppConfig::default())),
+        Arc::clone(&emitter),
+    );
+    Harness {
+        _tmp: tmp,
+        db,
+        service,
+        cache,
+        emitter,
+    }
+}
+
+async fn seed(db: &Db, sql: &'static str) {
+    db.with_conn(move |conn| Ok(conn.execute_batch(sql)?))
+        .await
+        .unwrap();
+}
+
+async fn lot(db: &Db, symbol: &str, quantity: f64, cost: f64, imported_at: i64) {
+    let symbol = symbol.to_string();
+    db.with_conn(move |conn| {
+        conn.execute(
+            "INSERT INTO imported_lots \
+               (account, source, symbol, quantity, cost_basis_total, last_price, \
+                acquired_date, currency, imported_at_unix) \
+             VALUES ('X1', 'schwab', ?1, ?2, ?3, ?4, '2024-03-01', 'USD', ?5)",
+            params![symbol, quantity, cost, cost / quantity * 1.5, imported_at],
+        )?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+}
+
+async fn lots(db: &Db) -> Vec<(String, f64, Option<f64>, Option<f64>)> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT symbol, quantity, cost_basis_total, last_price FROM imported_lots \
+             ORDER BY id",
+        )?;
+        let rows = stmt
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?, r.get(3)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows)
+    })
+    .await
+    .unwrap()
+}
+
+fn stock(symbol: &str, quantity: f64, average_cost: f64) -> Holding {
+    Holding {
+        contract: symbol.to_string(),
+        symbol: symbol.to_string(),
+        contract_type: "STK".to_string(),
+        currency: "USD".to_string(),
+        quantity,
+        average_cost,
+        market_price: average_cost,
+        market_value: quantity * average_cost,
+        unrealized_pnl: 0.0,
+        realized_pnl: 0.0,
+    }
+}
+
+#[tokio::test]
+async fn a_split_adjusts_rows_from_before_it_once() {
+    let h = harness(vec![]);
+    lot(&h.db, "NVDA", 10.0, 4_000.0, at(2026, 6, 1)).await;
+    // Exported after the split: already in post-split shares.
+    lot(&h.db, "NVDA", 100.0, 4_000.0, at(2026, 6, 12)).await;
+    seed(
+        &h.db,
+        "INSERT INTO bars_cache \
+           (symbol, bar_size, what_to_show, bar_time, open, high, low, close, volume, wap) \
+         VALUES ('NVDA', '1 day', 'TRADES', 1780963200, 1000, 1100, 900, 1050, 500, NULL), \
+                ('NVDA', '1 day', 'TRADES', 1781049600, 105, 110, 100, 108, 6000, NULL); \
+         INSERT INTO position_plans (account, contract, symbol, target_price, stop_price, updated_at) \
+         VALUES ('DU1', 'NVDA', 'NVDA', 1500, 800, 1780000000); \
+         INSERT INTO automation_rules \
+           (name, symbol, condition, action, cooldown_minutes, created_at, updated_at) \
+         VALUES ('breakout', 'NVDA', '{\"kind\":\"price\",\"direction\":\"above\",\"level\":1200.0}', \
+                 '{\"kind\":\"notify\"}', 60, 1780000000, 1780000000), \
+                ('rsi', 'NVDA', '{\"kind\":\"rsi\",\"direction\":\"below\",\"period\":14,\"level\":30.0}', \
+                 '{\"kind\":\"notify\"}', 60, 1780000000, 1780000000);",
+    )
+    .await;
+    let split = CorporateAction::Split {
+        symbol: "nvda".to_string(),
+        effective_date: day(2026, 6, 10),
+        ratio: 10.0,
+    };
+
+    let applied = h
+        .service
+        .apply(split.clone(), ActionSource::Manual)
+        .await
+        .unwrap();
+    assert_eq!(applied.action.symbol(), "NVDA");
+    let tables: Vec<(&str, usize)> = applied
+        .adjusted
+        .iter()
+        .map(|a| (a.table.as_str(), a.rows))
+        .collect();
+    assert_eq!(
+        tables,
+        [
+            ("imported_lots", 1),
+            ("bars_cache", 1),
+            ("position_plans", 1),
+            ("automation_rules", 1)
+        ]
+    );
+    let lots = lots(&h.db).await;
+    assert_eq!(lots[0], ("NVDA".into(), 100.0, Some(4_000.0), Some(60.0)));
+    assert_eq!(lots[1].1, 100.0);
+
+    let (bars, plan, level): (Vec<(f64, i64)>, (f64, f64), f64) =
+        h.db.with_conn(|conn| {
+            let mut stmt =
+                conn.prepare("SELECT close, volume FROM bars_cache ORDER BY bar_time")?;
+            let bars = stmt
+                .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            let plan = conn.query_row(
+                "SELECT target_price, stop_price FROM position_plans",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )?;
+            let level = conn.query_row(
+                "SELECT json_extract(condition, '$.level') FROM automation_rules \
+                 WHERE name = 'breakout'",
+                [],
+                |r| r.get(0),
+            )?;
+            Ok((bars, plan, level))
+        })
+        .await
+        .unwrap();
+    assert_eq!(bars, [(105.0, 5_000), (108.0, 6_000)]);
+    assert_eq!(plan, (150.0, 80.0));
+    assert_eq!(level, 120.0);
+
+    assert_eq!(*h.cache.0.lock().unwrap(), ["NVDA"]);
+    let events = h.emitter.captured().await;
+    assert!(matches!(
+        &events[..],
+        [AppEvent::CorporateActionApplied { source, adjusted, .. }]
+            if source == "manual" && adjusted.len() == 4
+    ));
+
+    // The same split again is refused and changes nothing.
+    let again = h.service.apply(split, ActionSource::Provider).await;
+    assert!(matches!(
+        again,
+        Err(CorporateActionError::AlreadyApplied(_))
+    ));
+    assert_eq!(h.service.list(10).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn a_symbol_change_renames_and_a_spin_off_adds_lots() {
+    let h = harness(vec![]);
+    lot(&h.db, "FB", 20.0, 3_000.0, at(2022, 6, 1)).await;
+    seed(
+        &h.db,
+        "INSERT INTO position_plans (account, contract, symbol, target_price, stop_price, updated_at) \
+         VALUES ('DU1', 'FB', 'FB', 400, 150, 1650000000), \
+                ('DU1', 'FB  230120C00200000', 'FB', 20, NULL, 1650000000); \
+         INSERT INTO trade_ideas (symbol, stage, note, created_at, stage_changed_at) \
+         VALUES ('FB', 'watching', NULL, 1650000000, 1650000000);",
+    )
+    .await;
+
+    let renamed = h
+        .service
+        .apply(
+            CorporateAction::SymbolChange {
+                from: "FB".to_string(),
+                to: "META".to_string(),
+                effective_date: day(2022, 6, 9),
+            },
+            ActionSource::Ibkr,
+        )
+        .await
+        .unwrap();
+    let tables: Vec<(&str, usize)> = renamed
+        .adjusted
+        .iter()
+        .map(|a| (a.table.as_str(), a.rows))
+        .collect();
+    assert_eq!(
+        tables,
+        [
+            ("imported_lots", 1),
+            ("trade_ideas", 1),
+            ("position_plans", 2)
+        ]
+    );
+    let contracts: Vec<(String, String)> =
+        h.db.with_conn(|conn| {
+            let mut stmt =
+                conn.prepare("SELECT contract, symbol FROM position_plans ORDER BY contract")?;
+            let rows = stmt
+                .query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(rows)
+        })
+        .await
+        .unwrap();
+    // The option keeps its local symbol.
+    assert_eq!(
+        contracts,
+        [
+            ("FB  230120C00200000".to_string(), "META".to_string()),
+            ("META".to_string(), "META".to_string())
+        ]
+    );
+    assert_eq!(*h.cache.0.lock().unwrap(), ["FB", "META"]);
+
+    h.service
+        .apply(
+            CorporateAction::SpinOff {
+                symbol: "META".to_string(),
+                new_symbol: "MTVR".to_string(),
+                effective_date: day(2026, 3, 2),
+                ratio: 0.5,
+                cost_fraction: 0.2,
+            },
+            ActionSource::Manual,
+        )
+        .await
+        .unwrap();
+    let lots = lots(&h.db).await;
+    assert_eq!(lots.len(), 2);
+    assert_eq!((lots[0].0.as_str(), lots[0].1), ("META", 20.0));
+    assert!((lots[0].2.unwrap() - 2_400.0).abs() < 1e-9);
+    assert_eq!((lots[1].0.as_str(), lots[1].1), ("MTVR", 10.0));
+    assert!((lots[1].2.unwrap() - 600.0).abs() < 1e-9);
+    assert_eq!(lots[1].3, None);
+
+    let listed = h.service.list(10).await.unwrap();
+    assert_eq!(listed[0].action.kind(), "spin_off");
+    assert_eq!(listed[1].source, ActionSource::Ibkr);
+}
+
+#[tokio::test]
+async fn the_daily_check_takes_each_split_once_across_sources() {
+    // The provider dates the split a day before the snapshot shows it.
+    let h = harness(vec![
+        ("AAPL", day(2026, 8, 31), 4.0),
+        ("AAPL", day(2020, 8, 31), 4.0),
+    ]);
+    snapshots::replace(
+        &h.db,
+        "DU1",
+        day(2026, 8, 28),
+        vec![
+            stock("AAPL", 10.0, 600.0),
+            stock("FB", 5.0, 300.0),
+            stock("MSFT", 10.0, 400.0),
+        ],
+        at(2026, 8, 28),
+    )
+    .await
+    .unwrap();
+    snapshots::replace(
+        &h.db,
+        "DU1",
+        day(2026, 9, 1),
+        vec![
+            stock("AAPL", 40.0, 150.0),
+            stock("META", 5.0, 300.0),
+            // Bought more in between: not a split.
+            stock("MSFT", 20.0, 200.0),
+        ],
+        at(2026, 9, 1),
+    )
+    .await
+    .unwrap();
+    let fill = crate::ibkr::types::IbkrExecution {
+        symbol: "MSFT".to_string(),
+        side: crate::ibkr::types::ExecutionSide::Bought,
+        qty: 10.0,
+        avg_price: 0.0,
+        exec_time: Utc.with_ymd_and_hms(2026, 8, 31, 15, 0, 0).unwrap(),
+        order_id: 1,
+        exec_id: "e1".to_string(),
+        account: "DU1".to_string(),
+        contract_type: "STK".to_string(),
+        expiry: None,
+        strike: None,
+        right: None,
+        multiplier: None,
+        commission: None,
+        realized_pnl: None,
+        currency: Some("USD".to_string()),
+        commission_currency: None,
+    };
+    h.service.executions.record(&[fill]).await.unwrap();
+
+    let detected = h.service.detect(day(2026, 9, 1)).await.unwrap();
+    let described: Vec<(String, ActionSource)> =
+        detected.iter().map(|(a, s)| (a.describe(), *s)).collect();
+    assert_eq!(
+        described,
+        [
+            (
+                "AAPL 4-for-1 split on 2026-08-31".to_string(),
+                ActionSource::Provider
+            ),
+            (
+                "AAPL 4-for-1 split on 2026-09-01".to_string(),
+                ActionSource::Ibkr
+            ),
+            (
+                "FB renamed META on 2026-09-01".to_string(),
+                ActionSource::Ibkr
+            ),
+        ]
+    );
+
+    let applied = h.service.check(day(2026, 9, 1)).await.unwrap();
+    let kinds: Vec<(&str, ActionSource)> = applied
+        .iter()
+        .map(|a| (a.action.kind(), a.source))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            ("split", ActionSource::Provider),
+            ("symbol_change", ActionSource::Ibkr)
+        ]
+    );
+    assert!(h.service.check(day(2026, 9, 1)).await.unwrap().is_empty());
+}
+
+#[test]
+fn fills_before_a_split_are_restated() {
+    let fill = |symbol: &str, qty: f64, price: f64, d: u32| IbkrExecution {
+        symbol: symbol.to_string(),
+        side: ExecutionSide::Bought,
+        qty,
+        avg_price: price,
+        exec_time: Utc.timestamp_opt(at(2026, 6, d), 0).unwrap(),
+        order_id: 1,
+        exec_id: format!("{symbol}-{d}"),
+        account: "DU1".to_string(),
+        contract_type: "STK".to_string(),
+        expiry: None,
+        strike: None,
+        right: None,
+        multiplier: None,
+        commission: None,
+        realized_pnl: None,
+        currency: None,
+        commission_currency: None,
+    };
+    let mut call = fill("NVDA", 1.0, 50.0, 3);
+    call.strike = Some(1_000.0);
+    call.right = Some("C".to_string());
+    let mut fills = vec![
+        fill("nvda", 10.0, 1_000.0, 3),
+        fill("NVDA", 100.0, 110.0, 10),
+        fill("AMD", 10.0, 150.0, 3),
+        call,
+    ];
+    let splits = [CorporateAction::Split {
+        symbol: "NVDA".to_string(),
+        effective_date: day(2026, 6, 10),
+        ratio: 10.0,
+    }];
+
+    fills::restate(&mut fills, &splits);
+    let restated: Vec<(f64, f64)> = fills.iter().map(|f| (f.qty, f.avg_price)).collect();
+    assert_eq!(
+        restated,
+        [(100.0, 100.0), (100.0, 110.0), (10.0, 150.0), (1.0, 50.0)]
+    );
+}
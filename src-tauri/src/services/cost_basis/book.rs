@@ -0,0 +1,117 @@
+//! FIFO lot book for one contract, rebuilt from imported lots and
+//! stored fills.
+
+use std::collections::VecDeque;
+
+use crate::ibkr::types::{ExecutionSide, IbkrExecution, Position};
+
+/// Below this a quantity is treated as zero.
+pub const QTY_EPSILON: f64 = 1e-6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Lot {
+    /// Signed: negative for a short lot.
+    qty: f64,
+    /// Per unit, multiplier and commission included, as IBKR reports
+    /// `average_cost`. `None` for an imported lot without a basis.
+    unit_cost: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Book {
+    lots: VecDeque<Lot>,
+}
+
+impl Book {
+    /// Add a signed quantity. Against an open position of the other
+    /// sign it closes the oldest lots first; any remainder opens a new
+    /// lot at `unit_cost`.
+    pub fn apply(&mut self, qty: f64, unit_cost: Option<f64>) {
+        let mut remaining = qty;
+        while remaining.abs() > QTY_EPSILON {
+            match self.lots.front_mut() {
+                Some(lot) if lot.qty.signum() != remaining.signum() => {
+                    if lot.qty.abs() <= remaining.abs() + QTY_EPSILON {
+                        remaining += lot.qty;
+                        self.lots.pop_front();
+                    } else {
+                        lot.qty += remaining;
+                        remaining = 0.0;
+                    }
+                }
+                _ => {
+                    self.lots.push_back(Lot {
+                        qty: remaining,
+                        unit_cost,
+                    });
+                    remaining = 0.0;
+                }
+            }
+        }
+    }
+
+    pub fn quantity(&self) -> f64 {
+        self.lots.iter().map(|l| l.qty).sum()
+    }
+
+    /// Quantity-weighted cost of the open lots; `None` when flat or
+    /// when a lot has no basis.
+    pub fn average_cost(&self) -> Option<f64> {
+        let qty: f64 = self.lots.iter().map(|l| l.qty.abs()).sum();
+        if qty <= QTY_EPSILON {
+            return None;
+        }
+        let mut total = 0.0;
+        for lot in &self.lots {
+            total += lot.unit_cost? * lot.qty.abs();
+        }
+        Some(total / qty)
+    }
+}
+
+/// Key a position and a fill of the same contract agree on: the ticker
+/// for stock, `SYMBOL YYYYMMDD STRIKE RIGHT` for an option.
+pub fn position_key(p: &Position) -> String {
+    key(&p.symbol, p.expiry.as_deref(), p.strike, p.right.as_deref())
+}
+
+pub fn execution_key(e: &IbkrExecution) -> String {
+    let expiry = e.expiry.map(|d| d.format("%Y%m%d").to_string());
+    key(&e.symbol, expiry.as_deref(), e.strike, e.right.as_deref())
+}
+
+fn key(symbol: &str, expiry: Option<&str>, strike: Option<f64>, right: Option<&str>) -> String {
+    let symbol = symbol.trim().to_uppercase();
+    match (expiry, strike, right) {
+        (Some(expiry), Some(strike), Some(right)) => {
+            let right = right
+                .trim()
+                .chars()
+                .next()
+                .unwrap_or(' ')
+                .to_ascii_uppercase();
+            format!("{symbol} {expiry} {strike} {right}")
+        }
+        _ => symbol,
+    }
+}
+
+/// Signed quantity and per-unit cost of a fill: commission raises a
+/// buy's cost and lowers a sale's proceeds.
+pub fn execution_lot(e: &IbkrExecution) -> (f64, f64) {
+    let multiplier = e
+        .multiplier
+        .as_deref()
+        .and_then(|m| m.trim().parse::<f64>().ok())
+        .filter(|m| *m > 0.0)
+        .unwrap_or(1.0);
+    let commission_per_unit = match e.commission {
+        Some(c) if e.qty > 0.0 => c.abs() / e.qty,
+        _ => 0.0,
+    };
+    let price = e.avg_price * multiplier;
+    match e.side {
+        ExecutionSide::Bought => (e.qty, price + commission_per_unit),
+        ExecutionSide::Sold => (-e.qty, price - commission_per_unit),
+    }
+}
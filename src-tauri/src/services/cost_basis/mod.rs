@@ -0,0 +1,238 @@
+//! Cost-basis reconciliation: the locally tracked book against what
+//! IBKR reports.
+//!
+//! The local book for an account is rebuilt per contract from
+//!
+//!   - its imported lots (`services::portfolio_import`), taken as the
+//!     holdings at import time, then
+//!   - every stored fill after that (`executions`), matched FIFO,
+//!
+//! or from every stored fill when nothing was imported. Fills from
+//! before a recorded split count in post-split shares. Each contract's
+//! open quantity and average cost (per unit, multiplier and
+//! commissions included, like IBKR's `average_cost`) is compared with
+//! the live position, and every mismatch comes back as a
+//! [`Discrepancy`].
+//!
+//! The executions table only starts when the app first ran, so a
+//! position opened earlier shows as a quantity gap until an import
+//! covers it; `first_fill_at` in the report says how far back the
+//! fills go.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ibkr::error::IbkrError;
+use crate::services::corporate_actions;
+use crate::services::executions::ExecutionsStore;
+use crate::services::portfolio_import::{self, ImportError};
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+pub mod book;
+
+#[cfg(test)]
+mod tests;
+
+use book::{Book, QTY_EPSILON};
+
+/// Average costs further apart than this, in percent of IBKR's, are
+/// reported.
+pub const COST_TOLERANCE_PCT: f64 = 0.5;
+
+#[derive(Error, Debug)]
+pub enum ReconcileError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+    #[error("imported lots: {0}")]
+    Import(#[from] ImportError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscrepancyKind {
+    /// IBKR holds it; the local book is flat.
+    MissingLocally,
+    /// The local book holds it; IBKR doesn't.
+    MissingAtBroker,
+    Quantity,
+    AverageCost,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Discrepancy {
+    /// Ticker for stock; `SYMBOL YYYYMMDD STRIKE RIGHT` for an option.
+    pub contract: String,
+    pub kind: DiscrepancyKind,
+    pub broker_quantity: f64,
+    pub local_quantity: f64,
+    pub broker_average_cost: Option<f64>,
+    /// `None` when flat or when an imported lot had no basis.
+    pub local_average_cost: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reconciliation {
+    pub account: String,
+    /// Unix seconds.
+    pub as_of: i64,
+    /// Import time (unix seconds) of the lots the book starts from.
+    pub baseline_at: Option<i64>,
+    pub imported_lots: usize,
+    /// Fills applied on top of the baseline.
+    pub fills: usize,
+    pub first_fill_at: Option<DateTime<Utc>>,
+    /// Contracts that agree.
+    pub matched: usize,
+    /// By contract.
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+pub struct CostBasisReconciler {
+    db: Arc<Db>,
+    executions: Arc<ExecutionsStore>,
+    positions: Arc<dyn OpenPositionsSource>,
+    account: Arc<dyn AccountSource>,
+}
+
+impl CostBasisReconciler {
+    pub fn new(
+        db: Arc<Db>,
+        executions: Arc<ExecutionsStore>,
+        positions: Arc<dyn OpenPositionsSource>,
+        account: Arc<dyn AccountSource>,
+    ) -> Self {
+        Self {
+            db,
+            executions,
+            positions,
+            account,
+        }
+    }
+
+    /// Reconcile `account` (the current account when `None`).
+    pub async fn reconcile(
+        &self,
+        account: Option<String>,
+    ) -> Result<Reconciliation, ReconcileError> {
+        let account = match account.filter(|a| !a.trim().is_empty()) {
+            Some(a) => a.trim().to_string(),
+            None => self.account.current_account().await?,
+        };
+        let broker = self.positions.list_open(&account).await?;
+
+        let lots: Vec<_> = portfolio_import::list_lots(&self.db)
+            .await?
+            .into_iter()
+            .filter(|l| l.account == account)
+            .collect();
+        let baseline_at = lots.iter().map(|l| l.imported_at_unix).max();
+        let mut books: BTreeMap<String, Book> = BTreeMap::new();
+        for lot in &lots {
+            let unit_cost = lot
+                .cost_basis_total
+                .filter(|_| lot.quantity.abs() > QTY_EPSILON)
+                .map(|total| total / lot.quantity);
+            books
+                .entry(lot.symbol.to_uppercase())
+                .or_default()
+                .apply(lot.quantity, unit_cost);
+        }
+
+        let after = baseline_at.and_then(|t| Utc.timestamp_opt(t, 0).single());
+        let fills = self.executions.query_after(&account, after).await?;
+        let fills = corporate_actions::split_adjusted(&self.db, fills).await?;
+        for fill in &fills {
+            let (qty, unit_cost) = book::execution_lot(fill);
+            books
+                .entry(book::execution_key(fill))
+                .or_default()
+                .apply(qty, Some(unit_cost));
+        }
+
+        let mut report = Reconciliation {
+            account,
+            as_of: Utc::now().timestamp(),
+            baseline_at,
+            imported_lots: lots.len(),
+            fills: fills.len(),
+            first_fill_at: fills.first().map(|f| f.exec_time),
+            matched: 0,
+            discrepancies: Vec::new(),
+        };
+        let mut seen = BTreeSet::new();
+        for position in &broker {
+            let contract = book::position_key(position);
+            let local = books.get(&contract);
+            seen.insert(contract.clone());
+            match compare(
+                &contract,
+                position.position,
+                Some(position.average_cost),
+                local,
+            ) {
+                Some(d) => report.discrepancies.push(d),
+                None => report.matched += 1,
+            }
+        }
+        for (contract, local) in &books {
+            if seen.contains(contract) || local.quantity().abs() <= QTY_EPSILON {
+                continue;
+            }
+            if let Some(d) = compare(contract, 0.0, None, Some(local)) {
+                report.discrepancies.push(d);
+            }
+        }
+        report
+            .discrepancies
+            .sort_by(|a, b| a.contract.cmp(&b.contract));
+        Ok(report)
+    }
+}
+
+fn compare(
+    contract: &str,
+    broker_quantity: f64,
+    broker_average_cost: Option<f64>,
+    local: Option<&Book>,
+) -> Option<Discrepancy> {
+    let local_quantity = local.map_or(0.0, Book::quantity);
+    let local_average_cost = local.and_then(Book::average_cost);
+    let broker_flat = broker_quantity.abs() <= QTY_EPSILON;
+    let local_flat = local_quantity.abs() <= QTY_EPSILON;
+    let kind = if local_flat && !broker_flat {
+        DiscrepancyKind::MissingLocally
+    } else if broker_flat && !local_flat {
+        DiscrepancyKind::MissingAtBroker
+    } else if (broker_quantity - local_quantity).abs() > QTY_EPSILON {
+        DiscrepancyKind::Quantity
+    } else {
+        match (broker_average_cost, local_average_cost) {
+            (Some(broker), Some(local))
+                if broker.abs() > 0.0
+                    && ((local - broker) / broker).abs() * 100.0 > COST_TOLERANCE_PCT =>
+            {
+                DiscrepancyKind::AverageCost
+            }
+            _ => return None,
+        }
+    };
+    Some(Discrepancy {
+        contract: contract.to_string(),
+        kind,
+        broker_quantity,
+        local_quantity,
+        broker_average_cost,
+        local_average_cost,
+    })
+}
@@ -0,0 +1,192 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, TimeZone};
+use tempfile::NamedTempFile;
+
+use super::*;
+use crate::ibkr::types::{ExecutionSide, IbkrExecution, Position};
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+fn held(symbol: &str, qty: f64, average_cost: f64) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: qty,
+        average_cost,
+        contract_type: "STK".to_string(),
+        currency: "USD".to_string(),
+        ..Default::default()
+    }
+}
+
+fn fill(exec_id: &str, symbol: &str, side: ExecutionSide, qty: f64, price: f64) -> IbkrExecution {
+    IbkrExecution {
+        symbol: symbol.to_string(),
+        side,
+        qty,
+        avg_price: price,
+        exec_time: Utc.with_ymd_and_hms(2026, 5, 4, 14, 30, 0).unwrap(),
+        order_id: 1,
+        exec_id: exec_id.to_string(),
+        account: "DU1".to_string(),
+        contract_type: "STK".to_string(),
+        expiry: None,
+        strike: None,
+        right: None,
+        multiplier: None,
+        commission: Some(1.0),
+        realized_pnl: None,
+        currency: Some("USD".to_string()),
+        commission_currency: Some("USD".to_string()),
+    }
+}
+
+async fn reconciler(
+    positions: Vec<Position>,
+    fills: &[IbkrExecution],
+) -> (NamedTempFile, Arc<Db>, CostBasisReconciler) {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let executions = Arc::new(ExecutionsStore::new(Arc::clone(&db)));
+    executions.record(fills).await.unwrap();
+    let svc = CostBasisReconciler::new(
+        Arc::clone(&db),
+        executions,
+        Arc::new(StubPositions(positions)),
+        Arc::new(FixedAccount),
+    );
+    (tmp, db, svc)
+}
+
+#[tokio::test]
+async fn fills_rebuild_the_book_fifo() {
+    let mut option = fill("e4", "NVDA", ExecutionSide::Bought, 2.0, 10.0);
+    option.contract_type = "OPT".to_string();
+    option.expiry = NaiveDate::from_ymd_opt(2026, 12, 18);
+    option.strike = Some(500.0);
+    option.right = Some("C".to_string());
+    option.multiplier = Some("100".to_string());
+    let fills = [
+        fill("e1", "AAPL", ExecutionSide::Bought, 100.0, 150.0),
+        fill("e2", "AAPL", ExecutionSide::Sold, 40.0, 160.0),
+        fill("e3", "TSLA", ExecutionSide::Bought, 5.0, 200.0),
+        option,
+    ];
+    let nvda_call = Position {
+        contract_type: "OPT".to_string(),
+        expiry: Some("20261218".to_string()),
+        strike: Some(500.0),
+        right: Some("C".to_string()),
+        multiplier: Some("100".to_string()),
+        ..held("NVDA", 2.0, 1_100.0)
+    };
+    let positions = vec![
+        held("AAPL", 60.0, 150.01),
+        held("MSFT", 10.0, 400.0),
+        nvda_call,
+    ];
+    let (_tmp, _db, svc) = reconciler(positions, &fills).await;
+
+    let report = svc.reconcile(None).await.unwrap();
+    assert_eq!(report.account, "DU1");
+    assert_eq!(report.fills, 4);
+    assert_eq!(report.baseline_at, None);
+    assert_eq!(report.matched, 1);
+    let kinds: Vec<_> = report
+        .discrepancies
+        .iter()
+        .map(|d| (d.contract.as_str(), d.kind))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("MSFT", DiscrepancyKind::MissingLocally),
+            ("NVDA 20261218 500 C", DiscrepancyKind::AverageCost),
+            ("TSLA", DiscrepancyKind::MissingAtBroker),
+        ]
+    );
+    // 2 contracts at $10 × 100 plus $1 commission spread over both.
+    let call = &report.discrepancies[1];
+    assert!((call.local_average_cost.unwrap() - 1_000.5).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn imported_lots_are_the_baseline() {
+    let mut early = fill("e1", "AAPL", ExecutionSide::Bought, 25.0, 90.0);
+    early.exec_time = Utc.with_ymd_and_hms(2026, 4, 1, 14, 30, 0).unwrap();
+    let fills = [early, fill("e2", "AAPL", ExecutionSide::Sold, 50.0, 120.0)];
+    let (_tmp, db, svc) = reconciler(vec![held("AAPL", 60.0, 100.0)], &fills).await;
+    let imported_at = Utc
+        .with_ymd_and_hms(2026, 5, 1, 0, 0, 0)
+        .unwrap()
+        .timestamp();
+    db.with_conn(move |conn| {
+        for (account, qty, cost) in [("DU1", 100.0, 10_000.0), ("DU2", 7.0, 700.0)] {
+            conn.execute(
+                "INSERT INTO imported_lots \
+                 (account, source, symbol, quantity, cost_basis_total, currency, imported_at_unix) \
+                 VALUES (?1, 'generic', 'AAPL', ?2, ?3, 'USD', ?4)",
+                rusqlite::params![account, qty, cost, imported_at],
+            )?;
+        }
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    // The fill before the import is already in the lot; the sale after
+    // it leaves 50 where IBKR holds 60.
+    let report = svc.reconcile(None).await.unwrap();
+    assert_eq!(report.baseline_at, Some(imported_at));
+    assert_eq!(report.imported_lots, 1);
+    assert_eq!(report.fills, 1);
+    assert_eq!(report.discrepancies.len(), 1);
+    let d = &report.discrepancies[0];
+    assert_eq!(d.kind, DiscrepancyKind::Quantity);
+    assert_eq!(d.local_quantity, 50.0);
+    assert_eq!(d.local_average_cost, Some(100.0));
+}
+
+#[tokio::test]
+async fn fills_before_a_split_count_in_post_split_shares() {
+    // 10 shares at $1,000 on 2026-05-04, then a 10-for-1 split.
+    let fills = [fill("e1", "AAPL", ExecutionSide::Bought, 10.0, 1_000.0)];
+    let (_tmp, db, svc) = reconciler(vec![held("AAPL", 100.0, 100.01)], &fills).await;
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO corporate_actions \
+               (kind, symbol, effective_date, action, source, adjusted, applied_at) \
+             VALUES ('split', 'AAPL', '2026-05-11', \
+                     '{\"kind\":\"split\",\"symbol\":\"AAPL\",\"effective_date\":\"2026-05-11\",\"ratio\":10.0}', \
+                     'manual', '[]', 0)",
+            [],
+        )?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    let report = svc.reconcile(None).await.unwrap();
+    assert_eq!(report.matched, 1);
+    assert!(
+        report.discrepancies.is_empty(),
+        "{:?}",
+        report.discrepancies
+    );
+}
@@ -0,0 +1,233 @@
+//! Portfolio drawdown: how far the account's net liquidation value sits
+//! below its high-water mark, and the worst peak-to-trough falls.
+//!
+//! The history is the daily `equity_snapshots` rows the risk engine
+//! already keeps (one NLV per ET date, written on the day's first
+//! equity read), so nothing new is stored. Today's point is replaced by
+//! a live NLV from IBKR when one can be had, so an intraday slide shows
+//! up before tomorrow's snapshot.
+//!
+//! [`DrawdownStats`] carries:
+//!
+//!   - the current drawdown from the all-time high-water mark;
+//!   - the rolling max drawdown: the worst peak-to-trough fall within
+//!     the last `drawdown.window_sessions` snapshots (or the caller's
+//!     window), per point and as of today;
+//!   - the all-history max drawdown, with its peak and trough dates.
+//!
+//! Drawdowns are positive percentages: 12.5 is 12.5% under the peak.
+//! The automation rules engine reads the current drawdown for its
+//! `portfolio_drawdown` condition (`services::automation`).
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::config::AppConfig;
+use crate::ibkr::error::IbkrError;
+use crate::services::risk_engine::{AccountSource, EquityFetcher};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::market_calendar::et_date;
+
+mod store;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrawdownConfig {
+    /// Snapshots in the rolling max drawdown window; 252 is a trading
+    /// year.
+    #[serde(default = "default_window_sessions")]
+    pub window_sessions: u32,
+}
+
+fn default_window_sessions() -> u32 {
+    252
+}
+
+impl Default for DrawdownConfig {
+    fn default() -> Self {
+        Self {
+            window_sessions: default_window_sessions(),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum DrawdownError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawdownPoint {
+    /// ET date, `YYYY-MM-DD`.
+    pub date: String,
+    pub nlv: f64,
+    /// Below the high-water mark up to this point.
+    pub drawdown_pct: f64,
+    /// Worst fall within the window ending at this point.
+    pub rolling_max_drawdown_pct: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawdownStats {
+    pub account: String,
+    pub at: i64,
+    /// Snapshots in the rolling window.
+    pub window_sessions: u32,
+    /// Today's point is a live NLV rather than the stored snapshot.
+    pub live: bool,
+    /// The latest NLV; `None` without any history.
+    pub nlv: Option<f64>,
+    pub high_water_mark: Option<f64>,
+    pub high_water_date: Option<String>,
+    pub current_drawdown_pct: f64,
+    pub rolling_max_drawdown_pct: f64,
+    pub max_drawdown_pct: f64,
+    pub max_drawdown_peak_date: Option<String>,
+    pub max_drawdown_trough_date: Option<String>,
+    /// Oldest first.
+    pub series: Vec<DrawdownPoint>,
+}
+
+pub struct DrawdownService {
+    db: Arc<Db>,
+    account: Arc<dyn AccountSource>,
+    live: Arc<dyn EquityFetcher>,
+    config: Arc<RwLock<AppConfig>>,
+}
+
+impl DrawdownService {
+    pub fn new(
+        db: Arc<Db>,
+        account: Arc<dyn AccountSource>,
+        live: Arc<dyn EquityFetcher>,
+        config: Arc<RwLock<AppConfig>>,
+    ) -> Self {
+        Self {
+            db,
+            account,
+            live,
+            config,
+        }
+    }
+
+    /// Drawdown stats of `account` (the current account when `None`),
+    /// with the rolling window at `window` snapshots (the configured
+    /// window when `None`).
+    pub async fn stats(
+        &self,
+        account: Option<String>,
+        window: Option<u32>,
+    ) -> Result<DrawdownStats, DrawdownError> {
+        let window = match window {
+            Some(w) => w,
+            None => self.config.read().await.drawdown.window_sessions,
+        };
+        let account = match account.filter(|a| !a.trim().is_empty()) {
+            Some(a) => a.trim().to_string(),
+            None => self.account.current_account().await?,
+        };
+        let mut history = store::history(&self.db, &account).await?;
+
+        let today = et_date(Utc::now()).format("%Y-%m-%d").to_string();
+        let live = match self.live.fetch_nlv(&account).await {
+            Ok(nlv) if nlv.is_finite() && nlv > 0.0 => {
+                if history.last().is_some_and(|(date, _)| *date == today) {
+                    history.pop();
+                }
+                history.push((today, nlv));
+                true
+            }
+            Ok(nlv) => {
+                warn!("drawdown: ignoring a live NLV of {nlv} for {account}");
+                false
+            }
+            Err(e) => {
+                warn!("drawdown: live NLV for {account} failed ({e}); using the snapshots");
+                false
+            }
+        };
+
+        let series = series(&history, window as usize);
+        let mut stats = DrawdownStats {
+            account,
+            at: Utc::now().timestamp(),
+            window_sessions: window,
+            live,
+            nlv: series.last().map(|p| p.nlv),
+            high_water_mark: None,
+            high_water_date: None,
+            current_drawdown_pct: series.last().map_or(0.0, |p| p.drawdown_pct),
+            rolling_max_drawdown_pct: series.last().map_or(0.0, |p| p.rolling_max_drawdown_pct),
+            max_drawdown_pct: 0.0,
+            max_drawdown_peak_date: None,
+            max_drawdown_trough_date: None,
+            series: Vec::new(),
+        };
+        let mut peak: Option<&DrawdownPoint> = None;
+        for point in &series {
+            if peak.is_none_or(|p| point.nlv > p.nlv) {
+                peak = Some(point);
+            }
+            if point.drawdown_pct > stats.max_drawdown_pct {
+                stats.max_drawdown_pct = point.drawdown_pct;
+                stats.max_drawdown_peak_date = peak.map(|p| p.date.clone());
+                stats.max_drawdown_trough_date = Some(point.date.clone());
+            }
+        }
+        stats.high_water_mark = peak.map(|p| p.nlv);
+        stats.high_water_date = peak.map(|p| p.date.clone());
+        stats.series = series;
+        Ok(stats)
+    }
+
+    /// The current account's drawdown from its high-water mark, in
+    /// percent.
+    pub async fn current_drawdown_pct(&self) -> Result<f64, DrawdownError> {
+        Ok(self.stats(None, None).await?.current_drawdown_pct)
+    }
+}
+
+/// Drawdown per point from the running peak, and the worst fall within
+/// the `window` points ending at each.
+fn series(history: &[(String, f64)], window: usize) -> Vec<DrawdownPoint> {
+    let pct = |peak: f64, nlv: f64| {
+        if peak > 0.0 {
+            (peak - nlv) / peak * 100.0
+        } else {
+            0.0
+        }
+    };
+    let mut peak = f64::MIN;
+    history
+        .iter()
+        .enumerate()
+        .map(|(i, (date, nlv))| {
+            peak = peak.max(*nlv);
+            let mut window_peak = f64::MIN;
+            let mut rolling: f64 = 0.0;
+            for (_, v) in &history[(i + 1).saturating_sub(window.max(1))..=i] {
+                window_peak = window_peak.max(*v);
+                rolling = rolling.max(pct(window_peak, *v));
+            }
+            DrawdownPoint {
+                date: date.clone(),
+                nlv: *nlv,
+                drawdown_pct: pct(peak, *nlv),
+                rolling_max_drawdown_pct: rolling,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,27 @@
+//! `equity_snapshots` reads.
+
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+/// `(as_of_date, nlv)` for every snapshot of `account`, oldest first.
+pub async fn history(db: &Db, account: &str) -> Result<Vec<(String, f64)>, StorageError> {
+    let account = account.to_string();
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT as_of_date, nlv_cents FROM equity_snapshots \
+             WHERE account = ?1 ORDER BY as_of_date",
+        )?;
+        let rows = stmt.query_map(rusqlite::params![account], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)? as f64 / 100.0,
+            ))
+        })?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
@@ -0,0 +1,93 @@
+use std::sync::Mutex as StdMutex;
+
+use async_trait::async_trait;
+use tempfile::NamedTempFile;
+
+use super::*;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+/// Live NLV; `None` fails the fetch.
+struct LiveNlv(StdMutex<Option<f64>>);
+
+#[async_trait]
+impl EquityFetcher for LiveNlv {
+    async fn fetch_nlv(&self, _account: &str) -> Result<f64, IbkrError> {
+        (*self.0.lock().unwrap()).ok_or(IbkrError::NotConnected)
+    }
+}
+
+#[tokio::test]
+async fn drawdown_runs_from_the_high_water_mark_over_the_snapshots() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    db.with_conn(|conn| {
+        for (day, nlv) in [(2, 100), (5, 120), (6, 90), (7, 110), (8, 130), (9, 117)] {
+            conn.execute(
+                "INSERT INTO equity_snapshots \
+                   (account, as_of_date, nlv_cents, source, fetched_at) \
+                 VALUES ('DU1', ?1, ?2, 'ibkr_account_summary', 0)",
+                rusqlite::params![format!("2026-01-{day:02}"), nlv * 100],
+            )?;
+        }
+        Ok(())
+    })
+    .await
+    .unwrap();
+    let live = Arc::new(LiveNlv(StdMutex::new(None)));
+    let service = DrawdownService::new(
+        db,
+        Arc::new(FixedAccount),
+        live.clone(),
+        Arc::new(RwLock::new(AppConfig::default())),
+    );
+
+    let stats = service.stats(None, Some(3)).await.unwrap();
+    assert!(!stats.live);
+    assert_eq!(stats.nlv, Some(117.0));
+    assert_eq!(stats.high_water_mark, Some(130.0));
+    assert_eq!(stats.high_water_date.as_deref(), Some("2026-01-08"));
+    assert!((stats.current_drawdown_pct - 10.0).abs() < 1e-9);
+    // 120 to 90 has left the 3-snapshot window.
+    assert!((stats.rolling_max_drawdown_pct - 10.0).abs() < 1e-9);
+    assert_eq!(stats.max_drawdown_pct, 25.0);
+    assert_eq!(stats.max_drawdown_peak_date.as_deref(), Some("2026-01-05"));
+    assert_eq!(
+        stats.max_drawdown_trough_date.as_deref(),
+        Some("2026-01-06")
+    );
+    let rolling: Vec<f64> = stats
+        .series
+        .iter()
+        .map(|p| p.rolling_max_drawdown_pct)
+        .collect();
+    assert_eq!(rolling, [0.0, 0.0, 25.0, 25.0, 0.0, 10.0]);
+
+    // A live NLV becomes today's point.
+    *live.0.lock().unwrap() = Some(104.0);
+    let stats = service
+        .stats(Some("DU1".to_string()), Some(3))
+        .await
+        .unwrap();
+    assert!(stats.live);
+    assert_eq!(stats.series.len(), 7);
+    assert!((stats.current_drawdown_pct - 20.0).abs() < 1e-9);
+    assert!((stats.rolling_max_drawdown_pct - 20.0).abs() < 1e-9);
+    assert!((service.current_drawdown_pct().await.unwrap() - 20.0).abs() < 1e-9);
+
+    // No history, no live NLV: zeros.
+    *live.0.lock().unwrap() = None;
+    let empty = service.stats(Some("DU2".to_string()), None).await.unwrap();
+    assert_eq!(empty.window_sessions, 252);
+    assert_eq!(empty.nlv, None);
+    assert_eq!(empty.high_water_mark, None);
+    assert_eq!(empty.max_drawdown_pct, 0.0);
+    assert!(empty.series.is_empty());
+}
@@ -0,0 +1,274 @@
+//! Recent SEC filings per symbol, with links to the primary documents.
+//!
+//! A ticker resolves to its CIK through SEC's ticker map, and the
+//! filer's submissions index lists what it filed; [`sec`] reads both.
+//! Only 10-K, 10-Q and 8-K filings (and their amendments) are kept,
+//! newest first, each with a link to the primary document and to the
+//! filing index.
+//!
+//! Both are cached in their own directory: a symbol's filings for
+//! [`CACHE_TTL`], the ticker map for [`TICKERS_MAX_AGE_SECS`]. On a
+//! failed fetch the last cached copy is served regardless of age.
+//!
+//! SEC refuses requests whose `User-Agent` doesn't name a contact, so
+//! nothing is fetched until `edgar.user_agent` carries an email; until
+//! then every lookup fails with [`EdgarError::NoContact`].
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+use crate::services::cache_service::CacheService;
+use crate::utils::symbols;
+
+pub mod sec;
+
+#[cfg(test)]
+mod tests;
+
+/// Filings change a few times a quarter; a few hours is fresh enough
+/// to pick up an 8-K the same day.
+pub const CACHE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Ticker-to-CIK assignments rarely change.
+pub const TICKERS_MAX_AGE_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Forms kept, without the `/A` of an amendment.
+pub const FILING_FORMS: [&str; 3] = ["10-K", "10-Q", "8-K"];
+
+/// Filings returned per symbol.
+pub const MAX_FILINGS: usize = 40;
+
+const TICKERS_KEY: &str = "company_tickers";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EdgarConfig {
+    /// Sent as the `User-Agent` of every SEC request. SEC asks for a
+    /// name and a contact email (`Jane Doe jane@example.com`) and
+    /// refuses requests that don't carry one. Empty (the default)
+    /// until the trader sets it; filings aren't fetched until then.
+    #[serde(default)]
+    pub user_agent: String,
+}
+
+impl EdgarConfig {
+    /// The user agent, once it names a contact email.
+    pub fn contact(&self) -> Option<&str> {
+        let user_agent = self.user_agent.trim();
+        user_agent
+            .split_whitespace()
+            .any(is_email)
+            .then_some(user_agent)
+    }
+}
+
+/// `local@domain.tld`, loosely.
+fn is_email(word: &str) -> bool {
+    word.split_once('@').is_some_and(|(local, domain)| {
+        !local.is_empty()
+            && domain.contains('.')
+            && !domain.starts_with('.')
+            && !domain.ends_with('.')
+    })
+}
+
+#[derive(Debug, Error)]
+pub enum EdgarError {
+    #[error("SEC EDGAR: {0}")]
+    Upstream(String),
+    #[error("no SEC filer for {0}")]
+    UnknownSymbol(String),
+    #[error("invalid symbol: {0}")]
+    Invalid(String),
+    #[error("set edgar.user_agent to a name and contact email (SEC refuses requests without one)")]
+    NoContact,
+}
+
+/// A filer as listed in SEC's ticker map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct CompanyCik {
+    pub(super) cik: u64,
+    pub(super) name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Filing {
+    /// `10-K`, `10-Q`, `8-K`, or one of those with `/A`.
+    pub form: String,
+    pub filing_date: NaiveDate,
+    /// Period the filing covers (the event date for an 8-K), when SEC
+    /// lists one.
+    pub report_date: Option<NaiveDate>,
+    pub accession_number: String,
+    pub description: Option<String>,
+    /// Primary document.
+    pub url: String,
+    /// Filing index page, with the exhibits.
+    pub index_url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentFilings {
+    pub symbol: String,
+    /// Ten digits, zero-padded, as EDGAR shows it.
+    pub cik: String,
+    pub company: Option<String>,
+    /// Newest first, at most [`MAX_FILINGS`].
+    pub filings: Vec<Filing>,
+}
+
+/// Trait seam for SEC's JSON endpoints. Production is [`sec::SecHttp`];
+/// tests return canned payloads.
+#[async_trait]
+pub trait EdgarHttp: Send + Sync {
+    /// GET `url`, sending `user_agent` as the `User-Agent`.
+    async fn get_json(&self, url: &str, user_agent: &str) -> Result<Value, String>;
+}
+
+pub struct EdgarService {
+    http: Arc<dyn EdgarHttp>,
+    config: Arc<RwLock<AppConfig>>,
+    cache: Option<CacheService>,
+}
+
+impl EdgarService {
+    pub fn new(http: Arc<dyn EdgarHttp>, config: Arc<RwLock<AppConfig>>) -> Self {
+        Self {
+            http,
+            config,
+            cache: None,
+        }
+    }
+
+    pub fn with_cache(mut self, cache: CacheService) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// `symbol`'s recent 10-K, 10-Q and 8-K filings; cached, falling
+    /// back to a stale copy when SEC can't be reached.
+    pub async fn recent_filings(&self, symbol: &str) -> Result<RecentFilings, EdgarError> {
+        let symbol = symbols::normalize(symbol);
+        if symbol.is_empty() {
+            return Err(EdgarError::Invalid("symbol is empty".to_string()));
+        }
+        let user_agent = {
+            let config = self.config.read().await;
+            config
+                .edgar
+                .contact()
+                .ok_or(EdgarError::NoContact)?
+                .to_string()
+        };
+        let key = format!("{symbol}_filings");
+        if let Some(cached) = self
+            .cache
+            .as_ref()
+            .and_then(|c| c.read::<RecentFilings>(&key).ok())
+        {
+            return Ok(cached);
+        }
+        match self.fetch(&symbol, &user_agent).await {
+            Ok(filings) => {
+                if let Some(c) = &self.cache {
+                    if let Err(e) = c.write(&key, &filings) {
+                        warn!("edgar cache write failed for {symbol}: {e}");
+                    }
+                }
+                info!("edgar: {} filing(s) for {symbol}", filings.filings.len());
+                Ok(filings)
+            }
+            Err(e @ EdgarError::Upstream(_)) => {
+                let stale = self
+                    .cache
+                    .as_ref()
+                    .and_then(|c| c.read_ignoring_ttl::<RecentFilings>(&key).ok());
+                match stale {
+                    Some((filings, age)) => {
+                        warn!("edgar: {e}; serving cached {symbol} filings (age {age}s)");
+                        Ok(filings)
+                    }
+                    None => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn fetch(&self, symbol: &str, user_agent: &str) -> Result<RecentFilings, EdgarError> {
+        let tickers = self.tickers(user_agent).await?;
+        let company = tickers
+            .get(&symbols::sec(symbol))
+            .or_else(|| tickers.get(symbol))
+            .cloned()
+            .ok_or_else(|| EdgarError::UnknownSymbol(symbol.to_string()))?;
+        let json = self
+            .http
+            .get_json(&sec::submissions_url(company.cik), user_agent)
+            .await
+            .map_err(EdgarError::Upstream)?;
+        let mut filings = sec::parse_filings(company.cik, &json);
+        filings.sort_by_key(|f| Reverse(f.filing_date));
+        filings.truncate(MAX_FILINGS);
+        Ok(RecentFilings {
+            symbol: symbol.to_string(),
+            cik: format!("{:010}", company.cik),
+            company: company
+                .name
+                .or_else(|| json.get("name").and_then(Value::as_str).map(str::to_string)),
+            filings,
+        })
+    }
+
+    /// SEC's ticker map, re-read once it is [`TICKERS_MAX_AGE_SECS`]
+    /// old.
+    async fn tickers(&self, user_agent: &str) -> Result<HashMap<String, CompanyCik>, EdgarError> {
+        let cached = self.cache.as_ref().and_then(|c| {
+            c.read_ignoring_ttl::<HashMap<String, CompanyCik>>(TICKERS_KEY)
+                .ok()
+        });
+        let cached = match cached {
+            Some((map, age)) if age < TICKERS_MAX_AGE_SECS => return Ok(map),
+            stale => stale,
+        };
+        let fetched = self
+            .http
+            .get_json(sec::TICKERS_URL, user_agent)
+            .await
+            .map(|json| sec::parse_tickers(&json))
+            .and_then(|map| {
+                if map.is_empty() {
+                    Err("ticker map is empty".to_string())
+                } else {
+                    Ok(map)
+                }
+            });
+        match (fetched, cached) {
+            (Ok(map), _) => {
+                if let Some(c) = &self.cache {
+                    if let Err(e) = c.write(TICKERS_KEY, &map) {
+                        warn!("edgar ticker map cache write failed: {e}");
+                    }
+                }
+                Ok(map)
+            }
+            (Err(e), Some((map, age))) => {
+                warn!("edgar: ticker map refresh failed ({e}); using cached (age {age}s)");
+                Ok(map)
+            }
+            (Err(e), None) => Err(EdgarError::Upstream(e)),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! SEC EDGAR over its public JSON endpoints (no key needed).
+//!
+//! `company_tickers.json` maps tickers to CIKs; `submissions/CIK….json`
+//! lists a filer's most recent filings as parallel arrays. SEC turns
+//! away requests without a `User-Agent` naming the requester, so every
+//! request carries `edgar.user_agent` (see [`super::EdgarConfig::contact`]),
+//! and it asks for no more than ten
+//! requests a second, which one lookup per symbol stays well under.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use reqwest::Client;
+use serde_json::Value;
+
+use super::{CompanyCik, EdgarHttp, Filing, FILING_FORMS};
+
+pub const TICKERS_URL: &str = "https://www.sec.gov/files/company_tickers.json";
+
+const SUBMISSIONS_URL: &str = "https://data.sec.gov/submissions";
+
+const ARCHIVES_URL: &str = "https://www.sec.gov/Archives/edgar/data";
+
+#[derive(Default)]
+pub struct SecHttp {
+    client: Client,
+}
+
+impl SecHttp {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl EdgarHttp for SecHttp {
+    async fn get_json(&self, url: &str, user_agent: &str) -> Result<Value, String> {
+        let response = self
+            .client
+            .get(url)
+            .header("User-Agent", user_agent)
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("SEC returned {}", response.status()));
+        }
+        response.json::<Value>().await.map_err(|e| e.to_string())
+    }
+}
+
+/// Submissions index of the filer `cik`.
+pub fn submissions_url(cik: u64) -> String {
+    format!("{SUBMISSIONS_URL}/CIK{cik:010}.json")
+}
+
+/// Ticker (upper-case, SEC's `BRK-B` spelling) to CIK and company name,
+/// from `company_tickers.json`. Rows without a ticker or CIK are
+/// skipped; a ticker listed twice keeps its first CIK.
+pub(super) fn parse_tickers(json: &Value) -> HashMap<String, CompanyCik> {
+    let mut map = HashMap::new();
+    let Some(rows) = json.as_object() else {
+        return map;
+    };
+    for row in rows.values() {
+        let Some(ticker) = row.get("ticker").and_then(Value::as_str) else {
+            continue;
+        };
+        let Some(cik) = row.get("cik_str").and_then(Value::as_u64) else {
+            continue;
+        };
+        map.entry(ticker.trim().to_uppercase())
+            .or_insert_with(|| CompanyCik {
+                cik,
+                name: row.get("title").and_then(Value::as_str).map(str::to_string),
+            });
+    }
+    map
+}
+
+/// 10-K, 10-Q and 8-K filings (amendments included) from a submissions
+/// payload, in SEC's order (newest first). Rows missing an accession
+/// number, a date or a document are skipped.
+pub(super) fn parse_filings(cik: u64, json: &Value) -> Vec<Filing> {
+    let Some(recent) = json.pointer("/filings/recent") else {
+        return Vec::new();
+    };
+    let column = |name: &str| -> Vec<Option<&str>> {
+        recent
+            .get(name)
+            .and_then(Value::as_array)
+            .map(|values| values.iter().map(Value::as_str).collect())
+            .unwrap_or_default()
+    };
+    fn at<'a>(values: &[Option<&'a str>], i: usize) -> Option<&'a str> {
+        values
+            .get(i)
+            .copied()
+            .flatten()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    }
+    let forms = column("form");
+    let accessions = column("accessionNumber");
+    let filing_dates = column("filingDate");
+    let report_dates = column("reportDate");
+    let documents = column("primaryDocument");
+    let descriptions = column("primaryDocDescription");
+    let date = |raw: Option<&str>| raw.and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+
+    (0..forms.len())
+        .filter_map(|i| {
+            let form = at(&forms, i)?;
+            if !FILING_FORMS.contains(&form.trim_end_matches("/A")) {
+                return None;
+            }
+            let accession = at(&accessions, i)?;
+            let document = at(&documents, i)?;
+            let folder = format!("{ARCHIVES_URL}/{cik}/{}", accession.replace('-', ""));
+            Some(Filing {
+                form: form.to_string(),
+                filing_date: date(at(&filing_dates, i))?,
+                report_date: date(at(&report_dates, i)),
+                description: at(&descriptions, i).map(str::to_string),
+                url: format!("{folder}/{document}"),
+                index_url: format!("{folder}/{accession}-index.htm"),
+                accession_number: accession.to_string(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,211 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use tokio::sync::RwLock;
+
+use crate::config::AppConfig;
+use crate::services::cache_service::CacheService;
+
+use super::sec::{parse_filings, parse_tickers, submissions_url, TICKERS_URL};
+use super::{EdgarConfig, EdgarError, EdgarHttp, EdgarService};
+
+const USER_AGENT: &str = "Jane Doe jane@example.com";
+
+fn config(user_agent: &str) -> Arc<RwLock<AppConfig>> {
+    let mut config = AppConfig::default();
+    config.edgar.user_agent = user_agent.to_string();
+    Arc::new(RwLock::new(config))
+}
+
+fn day(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+fn tickers() -> Value {
+    json!({
+        "0": { "cik_str": 1067983, "ticker": "BRK-B", "title": "BERKSHIRE HATHAWAY INC" },
+        "1": { "cik_str": 320193, "ticker": "AAPL", "title": "Apple Inc." },
+        // Same ticker under a second CIK: the first listing wins.
+        "2": { "cik_str": 999, "ticker": "AAPL", "title": "Someone Else" },
+        "3": { "ticker": "NOCIK", "title": "Missing CIK" },
+    })
+}
+
+fn submissions() -> Value {
+    json!({
+        "cik": "320193",
+        "name": "Apple Inc.",
+        "filings": { "recent": {
+            "form": ["8-K", "4", "10-Q", "10-K/A", "10-K", "8-K"],
+            "accessionNumber": [
+                "0000320193-26-000090",
+                "0000320193-26-000089",
+                "0000320193-26-000070",
+                "0000320193-26-000010",
+                "0000320193-25-000100",
+                "",
+            ],
+            "filingDate": [
+                "2026-08-01", "2026-07-30", "2026-08-02", "2026-01-15", "2025-10-31", "2025-09-01",
+            ],
+            "reportDate": ["2026-07-31", "", "2026-06-28", "2025-09-27", "2025-09-27", ""],
+            "primaryDocument": [
+                "aapl-20260731.htm", "xslF345X05/wk-form4.xml", "aapl-20260628.htm",
+                "aapl-10ka.htm", "aapl-20250927.htm", "x.htm",
+            ],
+            "primaryDocDescription": ["8-K", "FORM 4", "10-Q", "", "10-K", "8-K"],
+        }},
+    })
+}
+
+#[test]
+fn parses_ticker_map_and_filing_links() {
+    let map = parse_tickers(&tickers());
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["AAPL"].cik, 320193);
+    assert_eq!(map["BRK-B"].name.as_deref(), Some("BERKSHIRE HATHAWAY INC"));
+
+    let filings = parse_filings(320193, &submissions());
+    let forms: Vec<_> = filings.iter().map(|f| f.form.as_str()).collect();
+    assert_eq!(forms, vec!["8-K", "10-Q", "10-K/A", "10-K"]);
+    let q = &filings[1];
+    assert_eq!(q.filing_date, day("2026-08-02"));
+    assert_eq!(q.report_date, Some(day("2026-06-28")));
+    assert_eq!(
+        q.url,
+        "https://www.sec.gov/Archives/edgar/data/320193/000032019326000070/aapl-20260628.htm"
+    );
+    assert_eq!(
+        q.index_url,
+        "https://www.sec.gov/Archives/edgar/data/320193/000032019326000070/0000320193-26-000070-index.htm"
+    );
+    assert_eq!(filings[2].description, None);
+    assert!(parse_filings(1, &json!({})).is_empty());
+    assert_eq!(
+        submissions_url(320193),
+        "https://data.sec.gov/submissions/CIK0000320193.json"
+    );
+}
+
+struct CountingHttp {
+    calls: AtomicUsize,
+    fail: bool,
+}
+
+#[async_trait]
+impl EdgarHttp for CountingHttp {
+    async fn get_json(&self, url: &str, user_agent: &str) -> Result<Value, String> {
+        assert_eq!(user_agent, USER_AGENT);
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if self.fail {
+            return Err("SEC returned 503".to_string());
+        }
+        if url == TICKERS_URL {
+            Ok(tickers())
+        } else {
+            assert_eq!(url, submissions_url(320193));
+            Ok(submissions())
+        }
+    }
+}
+
+#[tokio::test]
+async fn resolves_symbol_sorts_newest_first_and_caches() {
+    let dir = TempDir::new().unwrap();
+    let http = Arc::new(CountingHttp {
+        calls: AtomicUsize::new(0),
+        fail: false,
+    });
+    let service = EdgarService::new(http.clone(), config(USER_AGENT))
+        .with_cache(CacheService::new(dir.path()).unwrap());
+
+    let recent = service.recent_filings(" aapl").await.unwrap();
+    assert_eq!(recent.symbol, "AAPL");
+    assert_eq!(recent.cik, "0000320193");
+    assert_eq!(recent.company.as_deref(), Some("Apple Inc."));
+    let dates: Vec<_> = recent.filings.iter().map(|f| f.filing_date).collect();
+    assert_eq!(dates[0], day("2026-08-02"));
+    assert!(dates.windows(2).all(|w| w[0] >= w[1]));
+
+    assert_eq!(service.recent_filings("AAPL").await.unwrap(), recent);
+    assert_eq!(http.calls.load(Ordering::SeqCst), 2);
+
+    // The ticker map is cached on its own; an unknown symbol doesn't
+    // refetch it.
+    assert!(matches!(
+        service.recent_filings("ZZZZ").await,
+        Err(EdgarError::UnknownSymbol(s)) if s == "ZZZZ"
+    ));
+    assert_eq!(http.calls.load(Ordering::SeqCst), 2);
+    assert!(matches!(
+        service.recent_filings("  ").await,
+        Err(EdgarError::Invalid(_))
+    ));
+}
+
+#[tokio::test]
+async fn serves_stale_filings_when_sec_is_unreachable() {
+    let dir = TempDir::new().unwrap();
+    let http = Arc::new(CountingHttp {
+        calls: AtomicUsize::new(0),
+        fail: false,
+    });
+    let service = EdgarService::new(http, config(USER_AGENT))
+        .with_cache(CacheService::new(dir.path()).unwrap());
+    let fresh = service.recent_filings("AAPL").await.unwrap();
+
+    // A zero TTL forces a refetch, which fails and falls back.
+    let failing = Arc::new(CountingHttp {
+        calls: AtomicUsize::new(0),
+        fail: true,
+    });
+    let expired = CacheService::with_ttl(dir.path(), std::time::Duration::ZERO).unwrap();
+    let service = EdgarService::new(failing.clone(), config(USER_AGENT)).with_cache(expired);
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    assert_eq!(service.recent_filings("AAPL").await.unwrap(), fresh);
+    assert_eq!(failing.calls.load(Ordering::SeqCst), 1);
+
+    let uncached = EdgarService::new(failing, config(USER_AGENT));
+    assert!(matches!(
+        uncached.recent_filings("AAPL").await,
+        Err(EdgarError::Upstream(_))
+    ));
+}
+
+#[test]
+fn user_agent_needs_a_contact_email() {
+    let ua = |s: &str| EdgarConfig {
+        user_agent: s.to_string(),
+    };
+    assert_eq!(ua(USER_AGENT).contact(), Some(USER_AGENT));
+    assert_eq!(ua(" acme ops@acme.io ").contact(), Some("acme ops@acme.io"));
+    assert_eq!(EdgarConfig::default().contact(), None);
+    assert_eq!(ua("quantum-kapital").contact(), None);
+    assert_eq!(ua("Jane @example.com").contact(), None);
+    assert_eq!(ua("Jane jane@localhost").contact(), None);
+}
+
+#[tokio::test]
+async fn refuses_to_fetch_until_a_contact_is_configured() {
+    let http = Arc::new(CountingHttp {
+        calls: AtomicUsize::new(0),
+        fail: false,
+    });
+    let config = config("");
+    let service = EdgarService::new(http.clone(), Arc::clone(&config));
+    assert!(matches!(
+        service.recent_filings("AAPL").await,
+        Err(EdgarError::NoContact)
+    ));
+    assert_eq!(http.calls.load(Ordering::SeqCst), 0);
+
+    config.write().await.edgar.user_agent = USER_AGENT.to_string();
+    assert_eq!(
+        service.recent_filings("AAPL").await.unwrap().cik,
+        "0000320193"
+    );
+}
@@ -18,6 +18,7 @@ use thiserror::Error;
 use tracing::warn;
 
 use crate::storage::error::StorageError;
+use crate::utils::symbols;
 
 use super::earnings_store::{EarningsCacheStore, EarningsOverridesStore};
 use super::types::BlackoutConfidence;
@@ -113,7 +114,7 @@ impl EarningsCalendar for CompositeEarningsCalendar {
         symbol: &str,
         now: DateTime<Utc>,
     ) -> Result<Option<EarningsEntry>> {
-        let key = symbol.trim().to_uppercase();
+        let key = symbols::normalize(symbol);
         if key.is_empty() {
             return Ok(None);
         }
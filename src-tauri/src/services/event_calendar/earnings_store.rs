@@ -14,6 +14,7 @@ use chrono::{NaiveDate, Utc};
 
 use crate::storage::error::StorageError;
 use crate::storage::Db;
+use crate::utils::symbols;
 
 use super::types::BlackoutConfidence;
 
@@ -52,7 +53,7 @@ impl EarningsOverridesStore {
     }
 
     pub async fn get(&self, symbol: &str) -> Result<Option<OverrideRow>, StorageError> {
-        let key = symbol.trim().to_uppercase();
+        let key = symbols::normalize(symbol);
         if key.is_empty() {
             return Ok(None);
         }
@@ -105,7 +106,7 @@ impl EarningsOverridesStore {
         written_by: &str,
         notes: Option<String>,
     ) -> Result<OverrideRow, StorageError> {
-        let key = symbol.trim().to_uppercase();
+        let key = symbols::normalize(symbol);
         if key.is_empty() {
             return Err(StorageError::Migration(
                 "event_calendar_overrides symbol must be non-empty".to_string(),
@@ -153,7 +154,7 @@ impl EarningsOverridesStore {
     }
 
     pub async fn clear(&self, symbol: &str) -> Result<(), StorageError> {
-        let key = symbol.trim().to_uppercase();
+        let key = symbols::normalize(symbol);
         if key.is_empty() {
             return Ok(());
         }
@@ -180,7 +181,7 @@ impl EarningsCacheStore {
     }
 
     pub async fn get(&self, symbol: &str) -> Result<Option<EarningsRow>, StorageError> {
-        let key = symbol.trim().to_uppercase();
+        let key = symbols::normalize(symbol);
         if key.is_empty() {
             return Ok(None);
         }
@@ -229,7 +230,7 @@ impl EarningsCacheStore {
         confidence: BlackoutConfidence,
         source: &str,
     ) -> Result<EarningsRow, StorageError> {
-        let key = symbol.trim().to_uppercase();
+        let key = symbols::normalize(symbol);
         if key.is_empty() {
             return Err(StorageError::Migration(
                 "event_calendar_cache symbol must be non-empty".to_string(),
@@ -31,6 +31,7 @@ use thiserror::Error;
 use tracing::warn;
 
 use crate::utils::market_calendar::{et_date, et_offset, is_holiday, trading_days_before};
+use crate::utils::symbols;
 
 pub mod earnings;
 pub mod earnings_store;
@@ -185,7 +186,7 @@ impl EventCalendarService {
             }
         });
         Ok(EventCalendarLookup {
-            symbol: symbol.trim().to_uppercase(),
+            symbol: symbols::normalize(symbol),
             next_earnings,
             days_to_fomc: self.fomc.days_to_next(at),
             fomc_dataset_stale: self.fomc.is_stale(at),
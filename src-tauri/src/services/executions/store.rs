@@ -270,6 +270,50 @@ impl ExecutionsStore {
             .await
     }
 
+    /// Every stored fill for `account` after `after` (all of them when
+    /// `None`), oldest first. Feeds the cost-basis rebuild in
+    /// `services::cost_basis`.
+    pub async fn query_after(
+        &self,
+        account: &str,
+        after: Option<DateTime<Utc>>,
+    ) -> StorageResult<Vec<IbkrExecution>> {
+        let account = account.to_string();
+        let after = after.map(|t| t.to_rfc3339());
+        self.db
+            .with_conn(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT exec_id, account, symbol, contract_type, expiry, strike,
+                            \"right\", multiplier, side, qty, avg_price, currency,
+                            exec_time, order_id, commission, realized_pnl,
+                            commission_currency
+                     FROM executions
+                     WHERE account = ?1 AND (?2 IS NULL OR exec_time > ?2)
+                     ORDER BY exec_time ASC, exec_id ASC",
+                )?;
+                let rows = stmt
+                    .query_map(params![account, after], map_row)?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok(rows)
+            })
+            .await
+    }
+
+    /// Every account with a stored fill, sorted. Feeds the tax-lot
+    /// rebuild in `services::tax_lots`, which runs across accounts.
+    pub async fn accounts(&self) -> StorageResult<Vec<String>> {
+        self.db
+            .with_conn(|conn| {
+                let mut stmt =
+                    conn.prepare("SELECT DISTINCT account FROM executions ORDER BY account")?;
+                let rows = stmt
+                    .query_map([], |r| r.get(0))?
+                    .collect::<rusqlite::Result<Vec<String>>>()?;
+                Ok(rows)
+            })
+            .await
+    }
+
     /// Read fills for an ET trading day enriched with the Phase 2
     /// linkage columns (`setup_id`, `strategy`, `slippage_bps`).
     /// Wires `executions` LEFT JOIN `setups` so unattributed fills
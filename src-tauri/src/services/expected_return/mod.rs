@@ -0,0 +1,167 @@
+//! Expected portfolio return from the stored projections.
+//!
+//! Each long stock position's base-case share-price CAGR comes from the
+//! ticker's newest projection snapshot (`services::projection_history`).
+//! Weighted by market value over the projected holdings, the CAGRs sum
+//! to the portfolio's expected annual return; each holding's term of
+//! that sum is its contribution, so the report shows which names carry
+//! the expectation and which drag it.
+//!
+//! A holding without a stored projection is listed in `unprojected` and
+//! left out of the weighting, with `coverage` showing how much of the
+//! long stock book the figure speaks for. Options and shorts are left
+//! out altogether, as in `services::portfolio_analysis`. Unlike that
+//! bulk analysis nothing is fetched or regenerated here: a stale
+//! projection stays stale, and `projected_at` says how old it is.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ibkr::error::IbkrError;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::projection_history::ProjectionHistoryStore;
+use crate::services::risk_engine::AccountSource;
+use crate::storage::error::StorageError;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Error, Debug)]
+pub enum ExpectedReturnError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnContribution {
+    pub symbol: String,
+    pub market_value: f64,
+    /// Share of the projected market value, 0..1.
+    pub weight: f64,
+    /// Base-case share-price CAGR of the newest projection, percent.
+    pub base_cagr_pct: f64,
+    /// `weight × base_cagr_pct`, in percentage points of the expected
+    /// return.
+    pub contribution_pct: f64,
+    /// Unix seconds the projection was generated.
+    pub projected_at: i64,
+    pub snapshot_id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpectedPortfolioReturn {
+    pub account: String,
+    pub at: i64,
+    /// Annual percent; `None` when no holding has a projection.
+    pub expected_return_pct: Option<f64>,
+    /// Largest contribution first.
+    pub contributions: Vec<ReturnContribution>,
+    /// Market value of the long stock positions.
+    pub total_value: f64,
+    /// Market value of the ones with a projection.
+    pub projected_value: f64,
+    /// `projected_value / total_value`; `0` for an empty book.
+    pub coverage: f64,
+    /// Long stock positions without a stored projection.
+    pub unprojected: Vec<String>,
+}
+
+pub struct ExpectedReturnService {
+    account: Arc<dyn AccountSource>,
+    positions: Arc<dyn OpenPositionsSource>,
+    history: Arc<ProjectionHistoryStore>,
+}
+
+impl ExpectedReturnService {
+    pub fn new(
+        account: Arc<dyn AccountSource>,
+        positions: Arc<dyn OpenPositionsSource>,
+        history: Arc<ProjectionHistoryStore>,
+    ) -> Self {
+        Self {
+            account,
+            positions,
+            history,
+        }
+    }
+
+    /// Expected return of the long stock positions of `account` (the
+    /// current account when `None`).
+    pub async fn report(
+        &self,
+        account: Option<String>,
+    ) -> Result<ExpectedPortfolioReturn, ExpectedReturnError> {
+        let account = match account.filter(|a| !a.trim().is_empty()) {
+            Some(a) => a.trim().to_string(),
+            None => self.account.current_account().await?,
+        };
+        // Symbol -> market value, lots of one name summed.
+        let mut holdings: BTreeMap<String, f64> = BTreeMap::new();
+        for position in self.positions.list_open(&account).await? {
+            if position.contract_type == "STK" && position.position > 0.0 {
+                *holdings.entry(position.symbol.to_uppercase()).or_default() +=
+                    position.market_value;
+            }
+        }
+        let snapshots: HashMap<String, _> = self
+            .history
+            .latest_per_symbol()
+            .await?
+            .into_iter()
+            .map(|s| (s.symbol.clone(), s))
+            .collect();
+
+        let total_value: f64 = holdings.values().sum();
+        let mut contributions = Vec::new();
+        let mut unprojected = Vec::new();
+        for (symbol, market_value) in holdings {
+            match snapshots.get(&symbol) {
+                Some(snapshot) => contributions.push(ReturnContribution {
+                    symbol,
+                    market_value,
+                    weight: 0.0,
+                    base_cagr_pct: snapshot.results.cagr.base.share_price,
+                    contribution_pct: 0.0,
+                    projected_at: snapshot.generated_at,
+                    snapshot_id: snapshot.id,
+                }),
+                None => unprojected.push(symbol),
+            }
+        }
+        let projected_value: f64 = contributions.iter().map(|c| c.market_value).sum();
+        if projected_value > 0.0 {
+            for c in &mut contributions {
+                c.weight = c.market_value / projected_value;
+                c.contribution_pct = c.weight * c.base_cagr_pct;
+            }
+        }
+        contributions.sort_by(|a, b| {
+            b.contribution_pct
+                .total_cmp(&a.contribution_pct)
+                .then_with(|| a.symbol.cmp(&b.symbol))
+        });
+        Ok(ExpectedPortfolioReturn {
+            account,
+            at: Utc::now().timestamp(),
+            expected_return_pct: (projected_value > 0.0)
+                .then(|| contributions.iter().map(|c| c.contribution_pct).sum()),
+            contributions,
+            total_value,
+            projected_value,
+            coverage: if total_value > 0.0 {
+                projected_value / total_value
+            } else {
+                0.0
+            },
+            unprojected,
+        })
+    }
+}
@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+use tempfile::NamedTempFile;
+
+use super::*;
+use crate::ibkr::types::{Position, ProjectionAssumptions};
+use crate::services::projection_service::ProjectionService;
+use crate::storage::Db;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+fn position(symbol: &str, kind: &str, quantity: f64, market_value: f64) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: quantity,
+        market_value,
+        contract_type: kind.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Records a projection for `symbol`; returns its base-case CAGR.
+async fn project(history: &ProjectionHistoryStore, symbol: &str) -> f64 {
+    let assumptions = ProjectionAssumptions::default();
+    let data = ProjectionService::generate_mock_fundamental_data(symbol);
+    let results = ProjectionService::generate_projection_results(&data, &assumptions).unwrap();
+    history
+        .record(symbol, &assumptions, &results)
+        .await
+        .unwrap();
+    results.cagr.base.share_price
+}
+
+#[tokio::test]
+async fn holdings_contribute_their_weighted_base_cagr() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let history = Arc::new(ProjectionHistoryStore::new(db));
+    let aapl = project(&history, "AAPL").await;
+    let msft = project(&history, "MSFT").await;
+    project(&history, "XOM").await;
+
+    let service = ExpectedReturnService::new(
+        Arc::new(FixedAccount),
+        Arc::new(StubPositions(vec![
+            position("AAPL", "STK", 100.0, 20_000.0),
+            position("AAPL", "STK", 50.0, 10_000.0),
+            position("MSFT", "STK", 20.0, 10_000.0),
+            position("TSLA", "STK", 10.0, 10_000.0),
+            // Neither is part of the long stock book.
+            position("XOM", "STK", -50.0, -5_000.0),
+            position("MSFT", "OPT", 1.0, 800.0),
+        ])),
+        history,
+    );
+
+    let report = service.report(None).await.unwrap();
+    assert_eq!(report.account, "DU1");
+    assert_eq!(report.unprojected, ["TSLA"]);
+    assert_eq!(report.total_value, 50_000.0);
+    assert_eq!(report.projected_value, 40_000.0);
+    assert!((report.coverage - 0.8).abs() < 1e-12);
+
+    let by_symbol: HashMap<&str, &ReturnContribution> = report
+        .contributions
+        .iter()
+        .map(|c| (c.symbol.as_str(), c))
+        .collect();
+    assert_eq!(by_symbol.len(), 2);
+    assert_eq!(by_symbol["AAPL"].weight, 0.75);
+    assert_eq!(by_symbol["AAPL"].base_cagr_pct, aapl);
+    assert!((by_symbol["MSFT"].contribution_pct - 0.25 * msft).abs() < 1e-12);
+    assert!(
+        report.contributions[0].contribution_pct >= report.contributions[1].contribution_pct,
+        "largest contribution first"
+    );
+    let expected = report.expected_return_pct.unwrap();
+    assert!((expected - (0.75 * aapl + 0.25 * msft)).abs() < 1e-12);
+}
+
+#[tokio::test]
+async fn a_book_without_projections_has_no_expected_return() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let service = ExpectedReturnService::new(
+        Arc::new(FixedAccount),
+        Arc::new(StubPositions(vec![position("AAPL", "STK", 10.0, 2_000.0)])),
+        Arc::new(ProjectionHistoryStore::new(db)),
+    );
+
+    let report = service.report(Some("DU2".to_string())).await.unwrap();
+    assert_eq!(report.account, "DU2");
+    assert_eq!(report.expected_return_pct, None);
+    assert!(report.contributions.is_empty());
+    assert_eq!(report.coverage, 0.0);
+}
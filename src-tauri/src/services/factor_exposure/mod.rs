@@ -0,0 +1,382 @@
+//! Factor exposure: a lightweight factor model of the stock book.
+//!
+//! Each factor is an ETF return, or the spread between two (long less
+//! short): by default the market (SPY), size (IWM less SPY), value (IWD
+//! less IWF) and momentum (MTUM less SPY), set in
+//! `factor_exposure.factors`. A position's simple daily returns are
+//! regressed on the factor returns, with an intercept, by least squares
+//! over the most recent `factor_exposure.lookback_returns` dates every
+//! series shares; the slopes are its exposures. Fewer than
+//! `factor_exposure.min_overlap` shared returns, or factors that move in
+//! lockstep, leave the position unmeasured.
+//!
+//! The portfolio's exposure to a factor is the positions' exposures
+//! weighted by market value over the measured gross (shorts count
+//! negative), and in dollars, `Σ market value × exposure`. Options
+//! aren't delta-adjusted and are listed as unmeasured.
+//!
+//! Daily bars come from the cached `HistoricalDataService`. Nothing is
+//! stored: `get_factor_exposure` refits on every call for the risk
+//! report.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::config::AppConfig;
+use crate::ibkr::error::IbkrError;
+use crate::services::historical_data_service::DailyBars;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+
+#[cfg(test)]
+mod tests;
+
+/// One factor: the return of `long`, less that of `short` when set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactorProxy {
+    pub name: String,
+    pub long: String,
+    #[serde(default)]
+    pub short: Option<String>,
+}
+
+impl FactorProxy {
+    fn new(name: &str, long: &str, short: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            long: long.to_string(),
+            short: short.map(str::to_string),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactorExposureConfig {
+    #[serde(default = "default_factors")]
+    pub factors: Vec<FactorProxy>,
+    /// Most recent shared daily returns a position is fitted on.
+    #[serde(default = "default_lookback_returns")]
+    pub lookback_returns: u32,
+    /// Fewer shared returns leave a position unmeasured.
+    #[serde(default = "default_min_overlap")]
+    pub min_overlap: u32,
+}
+
+fn default_factors() -> Vec<FactorProxy> {
+    vec![
+        FactorProxy::new("market", "SPY", None),
+        FactorProxy::new("size", "IWM", Some("SPY")),
+        FactorProxy::new("value", "IWD", Some("IWF")),
+        FactorProxy::new("momentum", "MTUM", Some("SPY")),
+    ]
+}
+
+fn default_lookback_returns() -> u32 {
+    252
+}
+
+fn default_min_overlap() -> u32 {
+    60
+}
+
+impl Default for FactorExposureConfig {
+    fn default() -> Self {
+        Self {
+            factors: default_factors(),
+            lookback_returns: default_lookback_returns(),
+            min_overlap: default_min_overlap(),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum FactorExposureError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactorLoading {
+    pub factor: String,
+    pub beta: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionFactors {
+    pub symbol: String,
+    pub market_value: f64,
+    /// In `factor_exposure.factors` order.
+    pub loadings: Vec<FactorLoading>,
+    /// Mean daily return the factors don't explain.
+    pub alpha: f64,
+    /// Share of the return variance the factors explain.
+    pub r_squared: f64,
+    /// Daily returns fitted.
+    pub observations: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioFactor {
+    pub factor: String,
+    /// Market-value-weighted over the measured gross.
+    pub beta: f64,
+    /// `Σ market value × exposure`.
+    pub dollars: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactorExposureReport {
+    pub account: String,
+    pub at: i64,
+    pub factors: Vec<FactorProxy>,
+    pub portfolio: Vec<PortfolioFactor>,
+    pub positions: Vec<PositionFactors>,
+    /// Gross market value of the measured positions.
+    pub measured_gross: f64,
+    /// Left out: options, and stocks without enough history.
+    pub unmeasured: Vec<String>,
+}
+
+pub struct FactorExposureService {
+    account: Arc<dyn AccountSource>,
+    positions: Arc<dyn OpenPositionsSource>,
+    bars: Arc<dyn DailyBars>,
+    config: Arc<RwLock<AppConfig>>,
+}
+
+impl FactorExposureService {
+    pub fn new(
+        account: Arc<dyn AccountSource>,
+        positions: Arc<dyn OpenPositionsSource>,
+        bars: Arc<dyn DailyBars>,
+        config: Arc<RwLock<AppConfig>>,
+    ) -> Self {
+        Self {
+            account,
+            positions,
+            bars,
+            config,
+        }
+    }
+
+    /// Fit the open stock positions of `account` (the current account
+    /// when `None`) on the configured factors.
+    pub async fn report(
+        &self,
+        account: Option<String>,
+    ) -> Result<FactorExposureReport, FactorExposureError> {
+        let config = self.config.read().await.factor_exposure.clone();
+        let account = match account.filter(|a| !a.trim().is_empty()) {
+            Some(a) => a.trim().to_string(),
+            None => self.account.current_account().await?,
+        };
+        // One more close than returns.
+        let sessions = config.lookback_returns as usize + 1;
+
+        let mut proxy_returns: HashMap<String, HashMap<String, f64>> = HashMap::new();
+        for proxy in &config.factors {
+            for symbol in std::iter::once(&proxy.long).chain(&proxy.short) {
+                if !proxy_returns.contains_key(symbol) {
+                    let closes = self.bars.daily_closes(symbol, sessions).await?;
+                    proxy_returns.insert(symbol.clone(), returns(&closes).into_iter().collect());
+                }
+            }
+        }
+        let factor_returns: Vec<HashMap<String, f64>> = config
+            .factors
+            .iter()
+            .map(|proxy| {
+                let long = &proxy_returns[&proxy.long];
+                long.iter()
+                    .filter_map(|(date, l)| {
+                        let short = match &proxy.short {
+                            Some(s) => *proxy_returns[s].get(date)?,
+                            None => 0.0,
+                        };
+                        Some((date.clone(), l - short))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut positions = Vec::new();
+        let mut unmeasured = Vec::new();
+        for position in self.positions.list_open(&account).await? {
+            let label = if position.local_symbol.is_empty() {
+                position.symbol.clone()
+            } else {
+                position.local_symbol.clone()
+            };
+            if position.contract_type != "STK" || position.position == 0.0 {
+                unmeasured.push(label);
+                continue;
+            }
+            let closes = match self.bars.daily_closes(&position.symbol, sessions).await {
+                Ok(closes) => closes,
+                Err(e) => {
+                    warn!("factor_exposure: bars for {} failed: {e}", position.symbol);
+                    unmeasured.push(label);
+                    continue;
+                }
+            };
+            match fit(&returns(&closes), &factor_returns, &config) {
+                Some(fitted) => positions.push(PositionFactors {
+                    symbol: position.symbol.clone(),
+                    market_value: position.market_value,
+                    loadings: config
+                        .factors
+                        .iter()
+                        .zip(&fitted.betas)
+                        .map(|(proxy, beta)| FactorLoading {
+                            factor: proxy.name.clone(),
+                            beta: *beta,
+                        })
+                        .collect(),
+                    alpha: fitted.alpha,
+                    r_squared: fitted.r_squared,
+                    observations: fitted.observations,
+                }),
+                None => unmeasured.push(label),
+            }
+        }
+
+        let measured_gross: f64 = positions.iter().map(|p| p.market_value.abs()).sum();
+        let portfolio = config
+            .factors
+            .iter()
+            .enumerate()
+            .map(|(k, proxy)| {
+                let dollars: f64 = positions
+                    .iter()
+                    .map(|p| p.market_value * p.loadings[k].beta)
+                    .sum();
+                PortfolioFactor {
+                    factor: proxy.name.clone(),
+                    beta: if measured_gross > 0.0 {
+                        dollars / measured_gross
+                    } else {
+                        0.0
+                    },
+                    dollars,
+                }
+            })
+            .collect();
+        Ok(FactorExposureReport {
+            account,
+            at: Utc::now().timestamp(),
+            factors: config.factors,
+            portfolio,
+            positions,
+            measured_gross,
+            unmeasured,
+        })
+    }
+}
+
+pub(crate) struct Fit {
+    pub(crate) alpha: f64,
+    pub(crate) betas: Vec<f64>,
+    pub(crate) r_squared: f64,
+    pub(crate) observations: usize,
+}
+
+/// Least squares of `asset` on `factors` with an intercept, over the
+/// most recent shared dates. Also fits `services::stress_test`'s shock
+/// proxies.
+pub(crate) fn fit(
+    asset: &[(String, f64)],
+    factors: &[HashMap<String, f64>],
+    config: &FactorExposureConfig,
+) -> Option<Fit> {
+    let k = factors.len();
+    let rows: Vec<(f64, Vec<f64>)> = asset
+        .iter()
+        .filter_map(|(date, r)| {
+            let x: Option<Vec<f64>> = factors.iter().map(|f| f.get(date).copied()).collect();
+            Some((*r, x?))
+        })
+        .collect();
+    let rows = &rows[rows.len().saturating_sub(config.lookback_returns as usize)..];
+    if rows.len() < (config.min_overlap as usize).max(k + 2) {
+        return None;
+    }
+
+    // Normal equations: (XᵀX) b = Xᵀy, X with a leading column of ones.
+    let n = k + 1;
+    let mut a = vec![vec![0.0; n + 1]; n];
+    for (y, x) in rows {
+        let xs: Vec<f64> = std::iter::once(1.0).chain(x.iter().copied()).collect();
+        for (row, xi) in a.iter_mut().zip(&xs) {
+            for (cell, xj) in row.iter_mut().zip(&xs) {
+                *cell += xi * xj;
+            }
+            row[n] += xi * y;
+        }
+    }
+    let coef = solve(a)?;
+
+    let mean = rows.iter().map(|(y, _)| y).sum::<f64>() / rows.len() as f64;
+    let (mut ss_res, mut ss_tot) = (0.0, 0.0);
+    for (y, x) in rows {
+        let fitted = coef[0] + x.iter().zip(&coef[1..]).map(|(x, b)| x * b).sum::<f64>();
+        ss_res += (y - fitted).powi(2);
+        ss_tot += (y - mean).powi(2);
+    }
+    Some(Fit {
+        alpha: coef[0],
+        betas: coef[1..].to_vec(),
+        r_squared: if ss_tot > 0.0 {
+            1.0 - ss_res / ss_tot
+        } else {
+            0.0
+        },
+        observations: rows.len(),
+    })
+}
+
+/// Gaussian elimination with partial pivoting on an augmented `n × (n +
+/// 1)` matrix; `None` when it is singular.
+fn solve(mut a: Vec<Vec<f64>>) -> Option<Vec<f64>> {
+    let n = a.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for row in lower {
+            let factor = row[col] / pivot_row[col];
+            for (cell, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *cell -= factor * p;
+            }
+        }
+    }
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let rest: f64 = (i + 1..n).map(|j| a[i][j] * x[j]).sum();
+        x[i] = (a[i][n] - rest) / a[i][i];
+    }
+    Some(x)
+}
+
+/// Simple daily returns, dated by the later close.
+pub(crate) fn returns(closes: &[(String, f64)]) -> Vec<(String, f64)> {
+    closes
+        .windows(2)
+        .filter(|w| w[0].1 > 0.0)
+        .map(|w| (w[1].0.clone(), w[1].1 / w[0].1 - 1.0))
+        .collect()
+}
@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+
+use super::*;
+use crate::ibkr::types::{HistoricalBar, Position};
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+struct SeriesBars(HashMap<String, Vec<(String, f64)>>);
+
+#[async_trait]
+impl DailyBars for SeriesBars {
+    async fn daily_bars(
+        &self,
+        symbol: &str,
+        _sessions: usize,
+    ) -> Result<Vec<HistoricalBar>, IbkrError> {
+        let closes = self
+            .0
+            .get(symbol)
+            .ok_or_else(|| IbkrError::RequestFailed(format!("no bars for {symbol}")))?;
+        Ok(closes
+            .iter()
+            .map(|(time, close)| bar(time.clone(), *close, 0))
+            .collect())
+    }
+}
+
+fn bar(time: String, close: f64, volume: i64) -> HistoricalBar {
+    HistoricalBar {
+        time,
+        open: close,
+        high: close,
+        low: close,
+        close,
+        volume,
+        wap: close,
+        count: 0,
+    }
+}
+
+/// Deterministic daily returns of about ±1%.
+fn noise(seed: u64, n: usize) -> Vec<f64> {
+    let mut state = seed;
+    (0..n)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) as f64 / (1u64 << 31) as f64 - 0.5) * 0.02
+        })
+        .collect()
+}
+
+fn closes(returns: &[f64]) -> Vec<(String, f64)> {
+    let mut close = 100.0;
+    let mut out = vec![("d0000".to_string(), close)];
+    for (i, r) in returns.iter().enumerate() {
+        close *= 1.0 + r;
+        out.push((format!("d{:04}", i + 1), close));
+    }
+    out
+}
+
+fn position(symbol: &str, kind: &str, market_value: f64) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: market_value.signum(),
+        market_value,
+        contract_type: kind.to_string(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn exposures_are_the_slopes_on_the_factor_returns() {
+    let n = 300;
+    let etfs: HashMap<&str, Vec<f64>> = ["SPY", "IWM", "IWD", "IWF", "MTUM"]
+        .into_iter()
+        .enumerate()
+        .map(|(i, s)| (s, noise(i as u64 + 1, n)))
+        .collect();
+    // market, size, value, momentum.
+    let factor = |t: usize| {
+        [
+            etfs["SPY"][t],
+            etfs["IWM"][t] - etfs["SPY"][t],
+            etfs["IWD"][t] - etfs["IWF"][t],
+            etfs["MTUM"][t] - etfs["SPY"][t],
+        ]
+    };
+    let asset = |alpha: f64, betas: [f64; 4]| -> Vec<f64> {
+        (0..n)
+            .map(|t| alpha + factor(t).iter().zip(betas).map(|(f, b)| f * b).sum::<f64>())
+            .collect()
+    };
+    let mut series: HashMap<String, Vec<(String, f64)>> = etfs
+        .iter()
+        .map(|(s, r)| (s.to_string(), closes(r)))
+        .collect();
+    series.insert(
+        "AAPL".to_string(),
+        closes(&asset(0.0005, [1.2, -0.4, -0.3, 0.5])),
+    );
+    series.insert(
+        "XOM".to_string(),
+        closes(&asset(0.0, [0.8, 0.2, 0.9, -0.6])),
+    );
+    // Not enough history to fit.
+    series.insert("NEW".to_string(), closes(&noise(9, 20)));
+
+    let service = FactorExposureService::new(
+        Arc::new(FixedAccount),
+        Arc::new(StubPositions(vec![
+            position("AAPL", "STK", 30_000.0),
+            position("XOM", "STK", -10_000.0),
+            position("NEW", "STK", 5_000.0),
+            position("SPY", "OPT", 2_000.0),
+        ])),
+        Arc::new(SeriesBars(series)),
+        Arc::new(RwLock::new(AppConfig::default())),
+    );
+
+    let report = service.report(None).await.unwrap();
+    assert_eq!(report.account, "DU1");
+    assert_eq!(report.unmeasured, ["NEW", "SPY"]);
+    assert_eq!(report.positions.len(), 2);
+
+    let aapl = &report.positions[0];
+    assert_eq!(aapl.observations, 252);
+    assert!((aapl.alpha - 0.0005).abs() < 1e-9);
+    assert!((aapl.r_squared - 1.0).abs() < 1e-9);
+    let names: Vec<&str> = aapl.loadings.iter().map(|l| l.factor.as_str()).collect();
+    assert_eq!(names, ["market", "size", "value", "momentum"]);
+    for (loading, want) in aapl.loadings.iter().zip([1.2, -0.4, -0.3, 0.5]) {
+        assert!((loading.beta - want).abs() < 1e-6, "{loading:?}");
+    }
+
+    // (30k × 1.2 − 10k × 0.8) / 40k of measured gross.
+    assert_eq!(report.measured_gross, 40_000.0);
+    let market = &report.portfolio[0];
+    assert!((market.dollars - 28_000.0).abs() < 1e-3);
+    assert!((market.beta - 0.7).abs() < 1e-6);
+    let value = &report.portfolio[2];
+    assert!((value.dollars + 18_000.0).abs() < 1e-3);
+}
@@ -0,0 +1,297 @@
+//! Fair value vs market price tracking.
+//!
+//! Once a day (the `fair_value_check` task in `services::scheduler`,
+//! 16:20 ET by default) every ticker with a stored projection
+//! (`services::projection_history`) has its latest price compared
+//! against the fair-value band of its newest snapshot. The band is the
+//! first projected year's range — bear low, base low/high, bull high —
+//! so it answers "what is this worth a year out under each scenario".
+//!
+//! The reading lands in `fair_value_flags` (one row per symbol, the
+//! dashboard flag) and, when the zone changes into `below_bear` or
+//! `above_bull`, an [`AppEvent::FairValueCrossed`] is emitted. A price
+//! sitting below bear for a week fires once, on the crossing day.
+//!
+//! Surveillance only: a crossing is a signal for the operator, never an
+//! order.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::events::{AppEvent, EventEmitter};
+use crate::ibkr::types::ProjectionResults;
+use crate::services::projection_history::ProjectionHistoryStore;
+use crate::services::quote_service::QuoteService;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FairValueZone {
+    BelowBear,
+    Within,
+    AboveBull,
+}
+
+impl FairValueZone {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FairValueZone::BelowBear => "below_bear",
+            FairValueZone::Within => "within",
+            FairValueZone::AboveBull => "above_bull",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "below_bear" => FairValueZone::BelowBear,
+            "above_bull" => FairValueZone::AboveBull,
+            _ => FairValueZone::Within,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FairValueBand {
+    pub bear_value: f64,
+    pub base_low: f64,
+    pub base_high: f64,
+    pub bull_value: f64,
+}
+
+impl FairValueBand {
+    /// Band of the first projected year; `None` when the projection has
+    /// no forward years.
+    pub fn from_results(results: &ProjectionResults) -> Option<Self> {
+        let year = results.projections.first()?;
+        Some(Self {
+            bear_value: year.bear.share_price_low,
+            base_low: year.base.share_price_low,
+            base_high: year.base.share_price_high,
+            bull_value: year.bull.share_price_high,
+        })
+    }
+
+    pub fn zone(&self, price: f64) -> FairValueZone {
+        if price < self.bear_value {
+            FairValueZone::BelowBear
+        } else if price > self.bull_value {
+            FairValueZone::AboveBull
+        } else {
+            FairValueZone::Within
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FairValueFlag {
+    pub symbol: String,
+    pub zone: FairValueZone,
+    pub price: f64,
+    #[serde(flatten)]
+    pub band: FairValueBand,
+    pub snapshot_id: i64,
+    pub evaluated_at: i64,
+    pub changed_at: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FairValueReport {
+    pub evaluated: usize,
+    /// Flags whose zone moved into `below_bear` / `above_bull` this run.
+    pub crossings: Vec<FairValueFlag>,
+    /// Symbols skipped for want of a price or a usable band.
+    pub skipped: Vec<String>,
+}
+
+/// Latest tradable price for a symbol. Seam over [`QuoteService`] so
+/// tests don't need an IBKR connection.
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    async fn latest_price(&self, symbol: &str) -> Option<f64>;
+}
+
+#[async_trait]
+impl PriceSource for QuoteService {
+    /// Regular-session price, else the prior close — an after-hours
+    /// print doesn't move a symbol across its band.
+    async fn latest_price(&self, symbol: &str) -> Option<f64> {
+        match self.fetch_quote(symbol).await {
+            Ok(quote) => quote.regular_price().or(quote.prev_close),
+            Err(e) => {
+                warn!("fair_value_watch: quote for {symbol} failed: {e}");
+                None
+            }
+        }
+    }
+}
+
+pub struct FairValueWatcher {
+    db: Arc<Db>,
+    history: Arc<ProjectionHistoryStore>,
+    prices: Arc<dyn PriceSource>,
+    emitter: Arc<EventEmitter>,
+}
+
+impl FairValueWatcher {
+    pub fn new(
+        db: Arc<Db>,
+        history: Arc<ProjectionHistoryStore>,
+        prices: Arc<dyn PriceSource>,
+        emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            db,
+            history,
+            prices,
+            emitter,
+        }
+    }
+
+    /// Evaluate every projected ticker once.
+    pub async fn run(&self) -> Result<FairValueReport, StorageError> {
+        let now = Utc::now().timestamp();
+        let mut report = FairValueReport::default();
+        for snapshot in self.history.latest_per_symbol().await? {
+            let symbol = snapshot.symbol.clone();
+            let Some(band) = FairValueBand::from_results(&snapshot.results) else {
+                report.skipped.push(symbol);
+                continue;
+            };
+            let Some(price) = self.prices.latest_price(&symbol).await.filter(|p| *p > 0.0) else {
+                report.skipped.push(symbol);
+                continue;
+            };
+            let zone = band.zone(price);
+            let previous = self.get(&symbol).await?;
+            let changed = previous.as_ref().map(|p| p.zone) != Some(zone);
+            let flag = FairValueFlag {
+                symbol,
+                zone,
+                price,
+                band,
+                snapshot_id: snapshot.id,
+                evaluated_at: now,
+                changed_at: match &previous {
+                    Some(p) if !changed => p.changed_at,
+                    _ => now,
+                },
+            };
+            self.upsert(&flag).await?;
+            report.evaluated += 1;
+
+            if changed && zone != FairValueZone::Within {
+                info!(
+                    "fair_value_watch: {} {} at {price} (bear {}, bull {})",
+                    flag.symbol,
+                    zone.as_str(),
+                    band.bear_value,
+                    band.bull_value
+                );
+                if let Err(e) = self
+                    .emitter
+                    .emit(AppEvent::FairValueCrossed {
+                        symbol: flag.symbol.clone(),
+                        zone,
+                        price,
+                        bear_value: band.bear_value,
+                        bull_value: band.bull_value,
+                    })
+                    .await
+                {
+                    warn!("FairValueCrossed emit failed: {e}");
+                }
+                report.crossings.push(flag);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Every flag, by symbol — the dashboard view.
+    pub async fn list(&self) -> Result<Vec<FairValueFlag>, StorageError> {
+        self.query(None).await
+    }
+
+    pub async fn get(&self, symbol: &str) -> Result<Option<FairValueFlag>, StorageError> {
+        Ok(self.query(Some(symbol.trim().to_uppercase())).await?.pop())
+    }
+
+    async fn upsert(&self, flag: &FairValueFlag) -> Result<(), StorageError> {
+        let f = flag.clone();
+        self.db
+            .with_conn(move |conn| {
+                conn.execute(
+                    "INSERT INTO fair_value_flags \
+                       (symbol, zone, price, bear_value, base_low, base_high, bull_value, \
+                        snapshot_id, evaluated_at, changed_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10) \
+                     ON CONFLICT(symbol) DO UPDATE SET \
+                       zone = excluded.zone, price = excluded.price, \
+                       bear_value = excluded.bear_value, base_low = excluded.base_low, \
+                       base_high = excluded.base_high, bull_value = excluded.bull_value, \
+                       snapshot_id = excluded.snapshot_id, \
+                       evaluated_at = excluded.evaluated_at, changed_at = excluded.changed_at",
+                    rusqlite::params![
+                        f.symbol,
+                        f.zone.as_str(),
+                        f.price,
+                        f.band.bear_value,
+                        f.band.base_low,
+                        f.band.base_high,
+                        f.band.bull_value,
+                        f.snapshot_id,
+                        f.evaluated_at,
+                        f.changed_at
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn query(&self, symbol: Option<String>) -> Result<Vec<FairValueFlag>, StorageError> {
+        self.db
+            .with_conn(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT symbol, zone, price, bear_value, base_low, base_high, bull_value, \
+                            snapshot_id, evaluated_at, changed_at \
+                     FROM fair_value_flags \
+                     WHERE ?1 IS NULL OR symbol = ?1 \
+                     ORDER BY symbol ASC",
+                )?;
+                let rows = stmt.query_map(rusqlite::params![symbol], |row| {
+                    Ok(FairValueFlag {
+                        symbol: row.get(0)?,
+                        zone: FairValueZone::parse(&row.get::<_, String>(1)?),
+                        price: row.get(2)?,
+                        band: FairValueBand {
+                            bear_value: row.get(3)?,
+                            base_low: row.get(4)?,
+                            base_high: row.get(5)?,
+                            bull_value: row.get(6)?,
+                        },
+                        snapshot_id: row.get(7)?,
+                        evaluated_at: row.get(8)?,
+                        changed_at: row.get(9)?,
+                    })
+                })?;
+                let mut out = Vec::new();
+                for row in rows {
+                    out.push(row?);
+                }
+                Ok(out)
+            })
+            .await
+    }
+}
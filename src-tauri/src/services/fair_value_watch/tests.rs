@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use tempfile::NamedTempFile;
+
+use crate::events::{AppEvent, EventEmitter};
+use crate::ibkr::types::ProjectionAssumptions;
+use crate::services::projection_history::ProjectionHistoryStore;
+use crate::services::projection_service::ProjectionService;
+use crate::storage::Db;
+
+use super::{FairValueBand, FairValueWatcher, FairValueZone, PriceSource};
+
+#[derive(Default)]
+struct FixedPrices(Mutex<HashMap<String, f64>>);
+
+impl FixedPrices {
+    fn set(&self, symbol: &str, price: f64) {
+        self.0.lock().unwrap().insert(symbol.to_string(), price);
+    }
+}
+
+#[async_trait]
+impl PriceSource for FixedPrices {
+    async fn latest_price(&self, symbol: &str) -> Option<f64> {
+        self.0.lock().unwrap().get(symbol).copied()
+    }
+}
+
+struct Harness {
+    _tmp: NamedTempFile,
+    watcher: FairValueWatcher,
+    prices: Arc<FixedPrices>,
+    emitter: Arc<EventEmitter>,
+    band: FairValueBand,
+}
+
+async fn harness() -> Harness {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let history = Arc::new(ProjectionHistoryStore::new(Arc::clone(&db)));
+    let assumptions = ProjectionAssumptions::default();
+    let data = ProjectionService::generate_mock_fundamental_data("NVDA");
+    let results = ProjectionService::generate_projection_results(&data, &assumptions).unwrap();
+    history
+        .record("NVDA", &assumptions, &results)
+        .await
+        .unwrap();
+
+    let prices = Arc::new(FixedPrices::default());
+    let emitter = Arc::new(EventEmitter::for_capture());
+    let watcher = FairValueWatcher::new(
+        db,
+        history,
+        Arc::clone(&prices) as Arc<dyn PriceSource>,
+        Arc::clone(&emitter),
+    );
+    Harness {
+        _tmp: tmp,
+        watcher,
+        prices,
+        emitter,
+        band: FairValueBand::from_results(&results).unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn crossing_below_bear_flags_and_emits_once() {
+    let h = harness().await;
+    h.prices.set("NVDA", h.band.bear_value * 0.9);
+
+    let first = h.watcher.run().await.unwrap();
+    assert_eq!(first.evaluated, 1);
+    assert_eq!(first.crossings.len(), 1);
+    let second = h.watcher.run().await.unwrap();
+    assert!(
+        second.crossings.is_empty(),
+        "standing zone must not re-fire"
+    );
+
+    let events = h.emitter.captured().await;
+    assert_eq!(events.len(), 1);
+    assert!(matches!(
+        &events[0],
+        AppEvent::FairValueCrossed { symbol, zone: FairValueZone::BelowBear, .. } if symbol == "NVDA"
+    ));
+    let flag = h.watcher.get("nvda").await.unwrap().unwrap();
+    assert_eq!(flag.zone, FairValueZone::BelowBear);
+}
+
+#[tokio::test]
+async fn inside_band_flags_without_event_and_re_crossing_fires() {
+    let h = harness().await;
+    h.prices
+        .set("NVDA", (h.band.base_low + h.band.base_high) / 2.0);
+    assert!(h.watcher.run().await.unwrap().crossings.is_empty());
+    assert_eq!(
+        h.watcher.list().await.unwrap()[0].zone,
+        FairValueZone::Within
+    );
+
+    h.prices.set("NVDA", h.band.bull_value * 1.1);
+    let report = h.watcher.run().await.unwrap();
+    assert_eq!(report.crossings[0].zone, FairValueZone::AboveBull);
+    assert_eq!(h.emitter.captured().await.len(), 1);
+}
+
+#[tokio::test]
+async fn missing_price_is_skipped() {
+    let h = harness().await;
+    let report = h.watcher.run().await.unwrap();
+    assert_eq!(report.evaluated, 0);
+    assert_eq!(report.skipped, vec!["NVDA".to_string()]);
+    assert!(h.watcher.list().await.unwrap().is_empty());
+}
@@ -0,0 +1,56 @@
+//! Cash flow statements (`function=CASH_FLOW`).
+//!
+//! Only what free cash flow needs is kept: operating cash flow and
+//! capital expenditures, which Alpha Vantage reports as a positive
+//! outflow. The fundamentals time series reads them.
+
+use crate::middleware::AlphaVantageRateLimiter;
+use crate::services::cache_service::CacheService;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+use super::AvHttp;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[allow(dead_code)]
+pub(super) struct AlphaVantageCashFlow {
+    #[serde(default)]
+    pub(super) symbol: Option<String>,
+    #[serde(rename = "annualReports", default)]
+    pub(super) annual_reports: Vec<CashFlowReport>,
+    #[serde(rename = "quarterlyReports", default)]
+    pub(super) quarterly_reports: Vec<CashFlowReport>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct CashFlowReport {
+    #[serde(rename = "fiscalDateEnding")]
+    pub(super) fiscal_date_ending: String,
+    #[serde(rename = "operatingCashflow", default)]
+    pub(super) operating_cashflow: Option<String>,
+    #[serde(rename = "capitalExpenditures", default)]
+    pub(super) capital_expenditures: Option<String>,
+    #[serde(rename = "reportedCurrency", default)]
+    pub(super) reported_currency: Option<String>,
+}
+
+pub(super) async fn fetch_cash_flow(
+    http: &dyn AvHttp,
+    rate_limiter: Option<&AlphaVantageRateLimiter>,
+    api_key: &str,
+    base_url: &str,
+    cache: &Option<CacheService>,
+    symbol: &str,
+) -> Result<AlphaVantageCashFlow, Box<dyn Error + Send + Sync>> {
+    super::fetch_av_function(
+        http,
+        rate_limiter,
+        api_key,
+        base_url,
+        cache,
+        symbol,
+        "CASH_FLOW",
+        "cash_flow",
+    )
+    .await
+}
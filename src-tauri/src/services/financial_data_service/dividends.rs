@@ -0,0 +1,82 @@
+//! Dividend history (`function=DIVIDENDS`), declared ones included.
+//!
+//! Alpha Vantage lists past and declared-but-unpaid dividends alike,
+//! newest first, with `"None"` for dates it doesn't have. Only the
+//! ex-dividend date and amount are kept; the option expiry monitor
+//! reads them to spot early-assignment risk on short calls.
+
+use crate::middleware::AlphaVantageRateLimiter;
+use crate::services::cache_service::CacheService;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+use super::{AvHttp, FinancialDataService};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[allow(dead_code)]
+pub(super) struct AlphaVantageDividends {
+    #[serde(default)]
+    pub(super) symbol: Option<String>,
+    #[serde(default)]
+    pub(super) data: Vec<DividendRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct DividendRecord {
+    /// `YYYY-MM-DD`, or `"None"`.
+    pub(super) ex_dividend_date: String,
+    /// Per share, e.g. `"1.67"`.
+    pub(super) amount: String,
+}
+
+pub(super) async fn fetch_dividends(
+    http: &dyn AvHttp,
+    rate_limiter: Option<&AlphaVantageRateLimiter>,
+    api_key: &str,
+    base_url: &str,
+    cache: &Option<CacheService>,
+    symbol: &str,
+) -> Result<AlphaVantageDividends, Box<dyn Error + Send + Sync>> {
+    super::fetch_av_function(
+        http,
+        rate_limiter,
+        api_key,
+        base_url,
+        cache,
+        symbol,
+        "DIVIDENDS",
+        "dividends",
+    )
+    .await
+}
+
+impl FinancialDataService {
+    /// `symbol`'s dividends as `(YYYY-MM-DD ex-dividend date, amount per
+    /// share)`, declared ones included, through the same file cache.
+    /// Empty without an API key, as in demo mode. Rows without a date
+    /// or a positive amount are skipped.
+    pub async fn dividend_history(
+        &self,
+        symbol: &str,
+    ) -> Result<Vec<(String, f64)>, Box<dyn Error + Send + Sync>> {
+        if self.api_key.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let av_dividends = fetch_dividends(
+            self.av_http.as_ref(),
+            self.rate_limiter.as_deref(),
+            &self.api_key,
+            &self.base_url,
+            &self.cache,
+            symbol,
+        )
+        .await?;
+        Ok(av_dividends
+            .data
+            .into_iter()
+            .filter(|d| d.ex_dividend_date != "None")
+            .filter_map(|d| Some((d.ex_dividend_date, d.amount.parse::<f64>().ok()?)))
+            .filter(|(_, amount)| *amount > 0.0)
+            .collect())
+    }
+}
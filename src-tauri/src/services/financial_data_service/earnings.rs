@@ -32,6 +32,8 @@ pub(super) struct QuarterlyEarning {
     pub(super) fiscal_date_ending: String,
     #[serde(rename = "estimatedEPS")]
     pub(super) estimated_eps: Option<String>,
+    #[serde(rename = "reportedEPS", default)]
+    pub(super) reported_eps: Option<String>,
 }
 
 pub(super) async fn fetch_earnings(
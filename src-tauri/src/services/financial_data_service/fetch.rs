@@ -0,0 +1,147 @@
+//! One Alpha Vantage `function=...` payload through the file cache,
+//! with the stale-cache fallback every endpoint module shares.
+
+use crate::middleware::AlphaVantageRateLimiter;
+use crate::services::cache_service::CacheService;
+use crate::utils::symbols;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::error::Error;
+use tracing::{info, warn};
+
+use super::AvHttp;
+
+/// Stale fundamentals older than this trigger a louder warning when
+/// served from the stale-cache fallback path. Day-stale is acceptable
+/// when AV is rate-limited; year-stale is a smell.
+const STALE_FUNDAMENTALS_WARN_AGE_SECS: u64 = 30 * 24 * 60 * 60;
+
+#[derive(Debug)]
+enum AvCheck {
+    Hard(String),
+    SoftSkip(String),
+}
+
+/// Classify an Alpha Vantage JSON payload. `Hard` errors propagate
+/// immediately; `SoftSkip` (rate-limit `Note` or quota `Information`)
+/// triggers the stale-cache fallback in `fetch_av_function`.
+fn classify_av_response(json: &Value) -> Result<(), AvCheck> {
+    if let Some(error_msg) = json.get("Error Message").and_then(|v| v.as_str()) {
+        return Err(AvCheck::Hard(format!(
+            "Alpha Vantage API error: {error_msg}"
+        )));
+    }
+    if let Some(note) = json.get("Note").and_then(|v| v.as_str()) {
+        return Err(AvCheck::SoftSkip(note.to_string()));
+    }
+    if let Some(info) = json.get("Information").and_then(|v| v.as_str()) {
+        return Err(AvCheck::SoftSkip(info.to_string()));
+    }
+    Ok(())
+}
+
+/// Fetch a single Alpha Vantage `function=...` payload, hitting the local
+/// JSON cache first and falling back to the live API. Cache key is namespaced
+/// per (symbol, suffix) so the three callers — OVERVIEW, INCOME_STATEMENT,
+/// EARNINGS — don't collide on disk.
+///
+/// On rate-limit / quota soft-skip responses, falls back to the most
+/// recently cached payload (even if past TTL) and emits a `warn!`. This
+/// mirrors the news-path behavior in `news.rs` and keeps the surrounding
+/// agent loop alive when AV says "you're done for the day."
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn fetch_av_function<T>(
+    http: &dyn AvHttp,
+    rate_limiter: Option<&AlphaVantageRateLimiter>,
+    api_key: &str,
+    base_url: &str,
+    cache: &Option<CacheService>,
+    symbol: &str,
+    function: &str,
+    cache_suffix: &str,
+) -> Result<T, Box<dyn Error + Send + Sync>>
+where
+    T: DeserializeOwned + Serialize,
+{
+    let cache_key = format!("{}_{}", symbols::normalize(symbol), cache_suffix);
+
+    if let Some(ref c) = cache {
+        if let Ok(cached) = c.read::<T>(&cache_key) {
+            info!("Using cached {function} data for {symbol}");
+            return Ok(cached);
+        }
+    }
+
+    info!("Fetching {function} data from API for {symbol}");
+    let av_symbol = symbols::alpha_vantage(symbol);
+    let url = format!("{base_url}?function={function}&symbol={av_symbol}&apikey={api_key}");
+
+    if let Some(limiter) = rate_limiter {
+        limiter.acquire().await;
+    }
+
+    let json = match http.fetch(&url).await {
+        Ok(v) => v,
+        Err(e) => {
+            warn!("Alpha Vantage {function} HTTP fetch failed for {symbol}: {e}");
+            if let Some(value) = read_stale_cache::<T>(cache, &cache_key, function, symbol) {
+                return Ok(value);
+            }
+            return Err(Box::new(e));
+        }
+    };
+
+    match classify_av_response(&json) {
+        Ok(()) => {
+            let parsed: T = serde_json::from_value(json)?;
+            if let Some(ref c) = cache {
+                let _ = c.write(&cache_key, &parsed);
+            }
+            Ok(parsed)
+        }
+        Err(AvCheck::SoftSkip(msg)) => {
+            warn!("Alpha Vantage {function} soft-skip for {symbol}: {msg}");
+            if let Some(value) = read_stale_cache::<T>(cache, &cache_key, function, symbol) {
+                return Ok(value);
+            }
+            Err(format!("Alpha Vantage {function}: {msg}").into())
+        }
+        Err(AvCheck::Hard(msg)) => Err(msg.into()),
+    }
+}
+
+/// Read a stale-allowed cache entry for the given key. Returns `None`
+/// when no cache row exists, or when the cache hasn't been provisioned.
+/// Logs at `warn!` on hit so operators can see the fallback firing; the
+/// log gets louder when the entry is older than 30 days (don't silently
+/// serve year-old fundamentals).
+fn read_stale_cache<T>(
+    cache: &Option<CacheService>,
+    cache_key: &str,
+    function: &str,
+    symbol: &str,
+) -> Option<T>
+where
+    T: DeserializeOwned,
+{
+    let c = cache.as_ref()?;
+    match c.read_ignoring_ttl::<T>(cache_key) {
+        Ok((value, age)) => {
+            if age >= STALE_FUNDAMENTALS_WARN_AGE_SECS {
+                warn!(
+                    "serving very stale cached {function} data for {symbol} \
+                     (age {}s, > {} day threshold) — refresh next time AV is reachable",
+                    age,
+                    STALE_FUNDAMENTALS_WARN_AGE_SECS / 86_400
+                );
+            } else {
+                warn!(
+                    "serving stale cached {function} data for {symbol} (age {}s)",
+                    age
+                );
+            }
+            Some(value)
+        }
+        Err(_) => None,
+    }
+}
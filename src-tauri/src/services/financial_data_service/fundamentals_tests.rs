@@ -5,6 +5,9 @@
 //!   to AV exactly once.
 //! - Stale-cache fallback: rate-limit (Information) responses serve the
 //!   most recently cached payload instead of erroring out.
+//! - Split adjustment: pre-split EPS is restated on today's share basis.
+//! - Time series: quarterly statements roll into TTM points, gaps
+//!   included.
 
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -14,7 +17,7 @@ use async_trait::async_trait;
 use serde_json::{json, Value};
 use tempfile::TempDir;
 
-use super::{AvHttp, AvHttpError, FinancialDataService};
+use super::{AvHttp, AvHttpError, FinancialDataService, FundamentalMetric};
 use crate::services::cache_service::CacheService;
 
 // ---------- fixtures ----------
@@ -61,6 +64,55 @@ fn earnings_json() -> Value {
     })
 }
 
+fn splits_json() -> Value {
+    json!({
+        "symbol": "AAPL",
+        "data": [
+            {"effective_date": "2020-08-31", "split_factor": "4.0000"},
+            {"effective_date": "2024-06-10", "split_factor": "2.0000"}
+        ]
+    })
+}
+
+/// Five consecutive quarters plus one a year earlier, newest first as
+/// Alpha Vantage lists them.
+fn quarterly_income_json() -> Value {
+    let quarter = |date: &str, revenue: &str, gross: &str| json!({"fiscalDateEnding": date, "totalRevenue": revenue, "grossProfit": gross});
+    json!({
+        "symbol": "AAPL",
+        "annualReports": [],
+        "quarterlyReports": [
+            quarter("2024-12-31", "140", "70"),
+            quarter("2024-09-30", "130", "52"),
+            quarter("2024-06-30", "120", "48"),
+            quarter("2024-03-31", "110", "44"),
+            quarter("2023-12-31", "100", "40"),
+            quarter("2022-12-31", "90", "None"),
+        ]
+    })
+}
+
+fn cash_flow_json() -> Value {
+    json!({
+        "symbol": "AAPL",
+        "annualReports": [
+            {
+                "fiscalDateEnding": "2024-09-30",
+                "reportedCurrency": "USD",
+                "operatingCashflow": "118000000000",
+                "capitalExpenditures": "9000000000"
+            },
+            {
+                "fiscalDateEnding": "2023-09-30",
+                "reportedCurrency": "USD",
+                "operatingCashflow": "110000000000",
+                "capitalExpenditures": "None"
+            }
+        ],
+        "quarterlyReports": []
+    })
+}
+
 fn rate_limit_payload() -> Value {
     json!({
         "Information": "Thank you for using Alpha Vantage! Our standard API rate limit is 25 requests per day. Please subscribe to any of the premium plans..."
@@ -70,13 +122,15 @@ fn rate_limit_payload() -> Value {
 // ---------- AvHttp test doubles ----------
 
 /// Routes responses by inspecting the AV `function=` query param so a
-/// single fake can serve all three endpoint URLs.
+/// single fake can serve all four endpoint URLs.
 struct RoutedAvHttp {
     counter: Arc<AtomicUsize>,
     delay: Duration,
     overview: Value,
     income: Value,
     earnings: Value,
+    splits: Value,
+    cash_flow: Value,
 }
 
 impl RoutedAvHttp {
@@ -87,6 +141,8 @@ impl RoutedAvHttp {
             overview: overview_json(),
             income: income_json(),
             earnings: earnings_json(),
+            splits: splits_json(),
+            cash_flow: cash_flow_json(),
         }
     }
 }
@@ -104,6 +160,10 @@ impl AvHttp for RoutedAvHttp {
             self.income.clone()
         } else if url.contains("function=EARNINGS") {
             self.earnings.clone()
+        } else if url.contains("function=SPLITS") {
+            self.splits.clone()
+        } else if url.contains("function=CASH_FLOW") {
+            self.cash_flow.clone()
         } else {
             return Err(AvHttpError::Status(format!("unexpected url: {url}")));
         })
@@ -129,7 +189,7 @@ impl AvHttp for AlwaysRateLimited {
 #[tokio::test]
 async fn ten_concurrent_fetches_fan_out_to_av_exactly_once() {
     // No cache → every miss-then-call would go to AV; coalescing is
-    // the only thing keeping the request count to 3.
+    // the only thing keeping the request count to 4.
     let temp = TempDir::new().unwrap();
     let cache = CacheService::new(temp.path()).unwrap();
     let fake = Arc::new(RoutedAvHttp::new(Duration::from_millis(80)));
@@ -158,11 +218,49 @@ async fn ten_concurrent_fetches_fan_out_to_av_exactly_once() {
 
     let calls = counter.load(Ordering::SeqCst);
     assert_eq!(
-        calls, 3,
-        "10 concurrent callers must coalesce to exactly 3 AV requests (overview/income/earnings); got {calls}"
+        calls, 4,
+        "10 concurrent callers must coalesce to exactly 4 AV requests (overview/income/earnings/splits); got {calls}"
     );
 }
 
+// ---------- split adjustment ----------
+
+#[tokio::test]
+async fn pre_split_eps_is_restated_and_marked() {
+    let temp = TempDir::new().unwrap();
+    let cache = CacheService::new(temp.path()).unwrap();
+    let fake = Arc::new(RoutedAvHttp::new(Duration::ZERO));
+    let svc = FinancialDataService::new("KEY".into())
+        .with_http(fake as Arc<dyn AvHttp>)
+        .with_cache(cache);
+
+    let data = svc.fetch_fundamental_data("AAPL").await.expect("fetch ok");
+    let fy2023 = data.historical.iter().find(|h| h.year == 2023).unwrap();
+    let fy2024 = data.historical.iter().find(|h| h.year == 2024).unwrap();
+
+    // FY2023 (ended 2023-09-30) predates the 2024-06-10 2:1 split only;
+    // the 2020 split was already in its reported figure.
+    assert_eq!(fy2023.split_adjustment, Some(2.0));
+    assert!((fy2023.eps - 3.0).abs() < 1e-9);
+    assert_eq!(fy2024.split_adjustment, None);
+    assert!((fy2024.eps - 6.5).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn missing_split_history_leaves_eps_as_reported() {
+    let fake = Arc::new(RoutedAvHttp {
+        splits: json!({"Error Message": "Invalid API call"}),
+        ..RoutedAvHttp::new(Duration::ZERO)
+    });
+    let temp = TempDir::new().unwrap();
+    let svc = FinancialDataService::new("KEY".into())
+        .with_http(fake as Arc<dyn AvHttp>)
+        .with_cache(CacheService::new(temp.path()).unwrap());
+
+    let data = svc.fetch_fundamental_data("AAPL").await.expect("fetch ok");
+    assert!(data.historical.iter().all(|h| h.split_adjustment.is_none()));
+}
+
 #[tokio::test]
 async fn failed_leader_does_not_poison_the_slot() {
     // First fetch errors (transport); flip the toggle and the next
@@ -217,6 +315,78 @@ async fn failed_leader_does_not_poison_the_slot() {
     );
 }
 
+// ---------- time series ----------
+
+#[tokio::test]
+async fn quarterly_statements_roll_into_ttm_without_bridging_gaps() {
+    let fake = Arc::new(RoutedAvHttp {
+        income: quarterly_income_json(),
+        ..RoutedAvHttp::new(Duration::ZERO)
+    });
+    let temp = TempDir::new().unwrap();
+    let svc = FinancialDataService::new("KEY".into())
+        .with_http(fake as Arc<dyn AvHttp>)
+        .with_cache(CacheService::new(temp.path()).unwrap());
+
+    let revenue = svc
+        .fundamental_timeseries("aapl", FundamentalMetric::Revenue)
+        .await
+        .unwrap();
+    assert_eq!(revenue.symbol, "AAPL");
+    assert_eq!(revenue.quarterly.len(), 6);
+    assert_eq!(revenue.quarterly[0].period_end, "2022-12-31");
+    // The window reaching back to 2022 skips three quarters.
+    let ttm: Vec<(&str, f64)> = revenue
+        .ttm
+        .iter()
+        .map(|p| (p.period_end.as_str(), p.value))
+        .collect();
+    assert_eq!(ttm, [("2024-09-30", 460.0), ("2024-12-31", 500.0)]);
+
+    // Summed gross profit over summed revenue, not a mean of margins;
+    // the 2022 quarter has no gross profit and drops out.
+    let margin = svc
+        .fundamental_timeseries("AAPL", FundamentalMetric::GrossMargin)
+        .await
+        .unwrap();
+    assert_eq!(margin.currency, None);
+    assert_eq!(margin.quarterly.len(), 5);
+    assert!((margin.quarterly[4].value - 50.0).abs() < 1e-9);
+    assert!((margin.ttm[1].value - 42.8).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn eps_is_split_adjusted_and_fcf_nets_capex() {
+    let fake = Arc::new(RoutedAvHttp::new(Duration::ZERO));
+    let temp = TempDir::new().unwrap();
+    let svc = FinancialDataService::new("KEY".into())
+        .with_http(fake as Arc<dyn AvHttp>)
+        .with_cache(CacheService::new(temp.path()).unwrap());
+
+    let eps = svc
+        .fundamental_timeseries("AAPL", FundamentalMetric::Eps)
+        .await
+        .unwrap();
+    let annual: Vec<f64> = eps.annual.iter().map(|p| p.value).collect();
+    assert_eq!(annual, [3.0, 6.5]);
+    assert!(eps.ttm.is_empty());
+
+    let fcf = svc
+        .fundamental_timeseries("AAPL", FundamentalMetric::FreeCashFlow)
+        .await
+        .unwrap();
+    assert_eq!(fcf.currency.as_deref(), Some("USD"));
+    assert_eq!(fcf.annual.len(), 1);
+    assert_eq!(fcf.annual[0].period_end, "2024-09-30");
+    assert!((fcf.annual[0].value - 109e9).abs() < 1.0);
+
+    let demo = FinancialDataService::new(String::new())
+        .fundamental_timeseries("AAPL", FundamentalMetric::Revenue)
+        .await
+        .unwrap();
+    assert!(demo.annual.is_empty() && demo.ttm.is_empty());
+}
+
 // ---------- stale-cache fallback ----------
 
 #[tokio::test]
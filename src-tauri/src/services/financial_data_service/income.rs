@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use std::error::Error;
 
 use super::earnings::AlphaVantageEarnings;
+use super::splits::{self, AlphaVantageSplits};
 use super::AvHttp;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -13,17 +14,27 @@ pub(super) struct AlphaVantageIncomeStatement {
     #[serde(default)]
     pub(super) symbol: Option<String>,
     #[serde(rename = "annualReports", default)]
-    pub(super) annual_reports: Vec<AnnualReport>,
+    pub(super) annual_reports: Vec<IncomeReport>,
+    /// Absent from rows cached before the fundamentals time series
+    /// read them; they fill in on the next refresh.
+    #[serde(rename = "quarterlyReports", default)]
+    pub(super) quarterly_reports: Vec<IncomeReport>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-pub(super) struct AnnualReport {
+pub(super) struct IncomeReport {
     #[serde(rename = "fiscalDateEnding")]
     pub(super) fiscal_date_ending: String,
     #[serde(rename = "totalRevenue")]
     pub(super) total_revenue: Option<String>,
+    #[serde(rename = "grossProfit", default)]
+    pub(super) gross_profit: Option<String>,
+    #[serde(rename = "operatingIncome", default)]
+    pub(super) operating_income: Option<String>,
     #[serde(rename = "netIncome")]
     pub(super) net_income: Option<String>,
+    #[serde(rename = "reportedCurrency", default)]
+    pub(super) reported_currency: Option<String>,
 }
 
 pub(super) async fn fetch_income_statement(
@@ -47,9 +58,28 @@ pub(super) async fn fetch_income_statement(
     .await
 }
 
+/// Currency of the most recent annual report, else `fallback` (the
+/// OVERVIEW `Currency`). The income statement's own code wins because
+/// revenue and EPS come from it.
+pub(super) fn reporting_currency(
+    income_statement: &AlphaVantageIncomeStatement,
+    fallback: Option<&str>,
+) -> Option<String> {
+    income_statement
+        .annual_reports
+        .iter()
+        .max_by(|a, b| a.fiscal_date_ending.cmp(&b.fiscal_date_ending))
+        .and_then(|r| r.reported_currency.as_deref())
+        .or(fallback)
+        .map(str::trim)
+        .filter(|c| !c.is_empty() && *c != "None")
+        .map(str::to_uppercase)
+}
+
 pub(super) fn process_historical_data(
     income_statement: &AlphaVantageIncomeStatement,
     earnings: &AlphaVantageEarnings,
+    split_history: &AlphaVantageSplits,
 ) -> Vec<HistoricalFinancial> {
     let mut historical: Vec<HistoricalFinancial> = income_statement
         .annual_reports
@@ -81,11 +111,15 @@ pub(super) fn process_historical_data(
                 .and_then(|eps_str| eps_str.parse::<f64>().ok())
                 .unwrap_or(0.0);
 
+            let factor = splits::factor_after(split_history, &report.fiscal_date_ending);
+            let split_adjustment = (factor != 1.0).then_some(factor);
+
             Some(HistoricalFinancial {
                 year,
                 revenue,
                 net_income,
-                eps,
+                eps: eps / factor,
+                split_adjustment,
             })
         })
         .collect();
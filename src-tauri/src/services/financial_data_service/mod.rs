@@ -4,9 +4,9 @@
 
 use crate::middleware::AlphaVantageRateLimiter;
 use crate::services::cache_service::CacheService;
+use crate::utils::symbols;
 use async_trait::async_trait;
 use reqwest::Client;
-use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::error::Error;
@@ -16,9 +16,17 @@ use thiserror::Error;
 use tokio::sync::broadcast;
 use tracing::{debug, info, warn};
 
+mod cash_flow;
+mod dividends;
 mod earnings;
+mod fetch;
 mod income;
 mod overview;
+mod splits;
+mod timeseries;
+
+use fetch::fetch_av_function;
+pub use timeseries::{FundamentalMetric, FundamentalTimeseries};
 
 #[cfg(test)]
 mod fundamentals_tests;
@@ -89,11 +97,6 @@ enum FetchSlot {
     Leader(broadcast::Sender<Arc<FundamentalsResult>>),
 }
 
-/// Stale fundamentals older than this trigger a louder warning when
-/// served from the stale-cache fallback path. Day-stale is acceptable
-/// when AV is rate-limited; year-stale is a smell.
-const STALE_FUNDAMENTALS_WARN_AGE_SECS: u64 = 30 * 24 * 60 * 60;
-
 /// Cache-key suffixes for the three AV fundamentals endpoints. Shared
 /// between the writers (`overview::fetch_overview`, etc.) and the
 /// invalidators (`clear_fundamentals_cache`, the composite provider's
@@ -119,135 +122,6 @@ pub struct FinancialDataService {
         Arc<StdMutex<HashMap<String, broadcast::Sender<Arc<FundamentalsResult>>>>>,
 }
 
-#[derive(Debug)]
-enum AvCheck {
-    Hard(String),
-    SoftSkip(String),
-}
-
-/// Classify an Alpha Vantage JSON payload. `Hard` errors propagate
-/// immediately; `SoftSkip` (rate-limit `Note` or quota `Information`)
-/// triggers the stale-cache fallback in `fetch_av_function`.
-fn classify_av_response(json: &Value) -> Result<(), AvCheck> {
-    if let Some(error_msg) = json.get("Error Message").and_then(|v| v.as_str()) {
-        return Err(AvCheck::Hard(format!(
-            "Alpha Vantage API error: {error_msg}"
-        )));
-    }
-    if let Some(note) = json.get("Note").and_then(|v| v.as_str()) {
-        return Err(AvCheck::SoftSkip(note.to_string()));
-    }
-    if let Some(info) = json.get("Information").and_then(|v| v.as_str()) {
-        return Err(AvCheck::SoftSkip(info.to_string()));
-    }
-    Ok(())
-}
-
-/// Fetch a single Alpha Vantage `function=...` payload, hitting the local
-/// JSON cache first and falling back to the live API. Cache key is namespaced
-/// per (symbol, suffix) so the three callers — OVERVIEW, INCOME_STATEMENT,
-/// EARNINGS — don't collide on disk.
-///
-/// On rate-limit / quota soft-skip responses, falls back to the most
-/// recently cached payload (even if past TTL) and emits a `warn!`. This
-/// mirrors the news-path behavior in `news.rs` and keeps the surrounding
-/// agent loop alive when AV says "you're done for the day."
-#[allow(clippy::too_many_arguments)]
-pub(super) async fn fetch_av_function<T>(
-    http: &dyn AvHttp,
-    rate_limiter: Option<&AlphaVantageRateLimiter>,
-    api_key: &str,
-    base_url: &str,
-    cache: &Option<CacheService>,
-    symbol: &str,
-    function: &str,
-    cache_suffix: &str,
-) -> Result<T, Box<dyn Error + Send + Sync>>
-where
-    T: DeserializeOwned + Serialize,
-{
-    let cache_key = format!("{}_{}", symbol.to_uppercase(), cache_suffix);
-
-    if let Some(ref c) = cache {
-        if let Ok(cached) = c.read::<T>(&cache_key) {
-            info!("Using cached {function} data for {symbol}");
-            return Ok(cached);
-        }
-    }
-
-    info!("Fetching {function} data from API for {symbol}");
-    let url = format!("{base_url}?function={function}&symbol={symbol}&apikey={api_key}");
-
-    if let Some(limiter) = rate_limiter {
-        limiter.acquire().await;
-    }
-
-    let json = match http.fetch(&url).await {
-        Ok(v) => v,
-        Err(e) => {
-            warn!("Alpha Vantage {function} HTTP fetch failed for {symbol}: {e}");
-            if let Some(value) = read_stale_cache::<T>(cache, &cache_key, function, symbol) {
-                return Ok(value);
-            }
-            return Err(Box::new(e));
-        }
-    };
-
-    match classify_av_response(&json) {
-        Ok(()) => {
-            let parsed: T = serde_json::from_value(json)?;
-            if let Some(ref c) = cache {
-                let _ = c.write(&cache_key, &parsed);
-            }
-            Ok(parsed)
-        }
-        Err(AvCheck::SoftSkip(msg)) => {
-            warn!("Alpha Vantage {function} soft-skip for {symbol}: {msg}");
-            if let Some(value) = read_stale_cache::<T>(cache, &cache_key, function, symbol) {
-                return Ok(value);
-            }
-            Err(format!("Alpha Vantage {function}: {msg}").into())
-        }
-        Err(AvCheck::Hard(msg)) => Err(msg.into()),
-    }
-}
-
-/// Read a stale-allowed cache entry for the given key. Returns `None`
-/// when no cache row exists, or when the cache hasn't been provisioned.
-/// Logs at `warn!` on hit so operators can see the fallback firing; the
-/// log gets louder when the entry is older than 30 days (don't silently
-/// serve year-old fundamentals).
-fn read_stale_cache<T>(
-    cache: &Option<CacheService>,
-    cache_key: &str,
-    function: &str,
-    symbol: &str,
-) -> Option<T>
-where
-    T: DeserializeOwned,
-{
-    let c = cache.as_ref()?;
-    match c.read_ignoring_ttl::<T>(cache_key) {
-        Ok((value, age)) => {
-            if age >= STALE_FUNDAMENTALS_WARN_AGE_SECS {
-                warn!(
-                    "serving very stale cached {function} data for {symbol} \
-                     (age {}s, > {} day threshold) — refresh next time AV is reachable",
-                    age,
-                    STALE_FUNDAMENTALS_WARN_AGE_SECS / 86_400
-                );
-            } else {
-                warn!(
-                    "serving stale cached {function} data for {symbol} (age {}s)",
-                    age
-                );
-            }
-            Some(value)
-        }
-        Err(_) => None,
-    }
-}
-
 impl FinancialDataService {
     /// Creates a new FinancialDataService instance for Alpha Vantage
     pub fn new(api_key: String) -> Self {
@@ -325,7 +199,7 @@ impl FinancialDataService {
         let Some(cache) = self.cache.as_ref() else {
             return;
         };
-        let upper = symbol.trim().to_uppercase();
+        let upper = symbols::normalize(symbol);
         if upper.is_empty() {
             return;
         }
@@ -337,6 +211,24 @@ impl FinancialDataService {
         }
     }
 
+    /// Drop the cached `SPLITS` payload for `symbol`, so the next
+    /// fundamentals fetch re-reads the split history. The corporate
+    /// actions service calls this with [`Self::clear_fundamentals_cache`]
+    /// after applying a split.
+    pub fn clear_splits_cache(&self, symbol: &str) {
+        let Some(cache) = self.cache.as_ref() else {
+            return;
+        };
+        let upper = symbols::normalize(symbol);
+        if upper.is_empty() {
+            return;
+        }
+        let key = format!("{upper}_splits");
+        if let Err(e) = cache.clear(&key) {
+            warn!("AV cache clear failed for {key}: {e}");
+        }
+    }
+
     /// Reconstruct a [`FundamentalData`] from the AV file cache for
     /// `symbol`, allowing TTL-expired entries. Returns `None` if any
     /// of the three endpoint rows is missing or unparseable. Phase 5
@@ -348,7 +240,7 @@ impl FinancialDataService {
         cache: &CacheService,
         symbol: &str,
     ) -> Option<crate::ibkr::types::fundamentals::FundamentalData> {
-        let upper = symbol.trim().to_uppercase();
+        let upper = symbols::normalize(symbol);
         if upper.is_empty() {
             return None;
         }
@@ -364,7 +256,11 @@ impl FinancialDataService {
             .read_ignoring_ttl(&format!("{upper}_earnings"))
             .ok()
             .map(|(v, _age)| v)?;
-        let historical = income::process_historical_data(&income, &earnings);
+        let av_splits: splits::AlphaVantageSplits = cache
+            .read_ignoring_ttl(&format!("{upper}_splits"))
+            .map(|(v, _age)| v)
+            .unwrap_or_default();
+        let historical = income::process_historical_data(&income, &earnings, &av_splits);
         if historical.is_empty() {
             return None;
         }
@@ -372,6 +268,12 @@ impl FinancialDataService {
             symbol: upper,
             historical,
             analyst_estimates: earnings::process_analyst_estimates(&earnings),
+            data_quality: Vec::new(),
+            overrides: Vec::new(),
+            growth: None,
+            currency: income::reporting_currency(&income, overview.currency.as_deref()),
+            fx_conversion: None,
+            segments: Vec::new(),
             current_metrics: overview::process_current_metrics(&overview),
         })
     }
@@ -379,7 +281,7 @@ impl FinancialDataService {
     /// Fetches fundamental data for a given symbol.
     ///
     /// Concurrent calls for the same symbol coalesce: the first call
-    /// fans out to AV (3 endpoints) while later callers join the
+    /// fans out to AV (4 endpoints) while later callers join the
     /// in-flight broadcast and reuse the result. The slot is cleared on
     /// completion so a failed fetch never poisons future attempts.
     pub async fn fetch_fundamental_data(
@@ -387,7 +289,7 @@ impl FinancialDataService {
         symbol: &str,
     ) -> Result<crate::ibkr::types::fundamentals::FundamentalData, Box<dyn Error + Send + Sync>>
     {
-        let key = symbol.to_uppercase();
+        let key = symbols::normalize(symbol);
 
         let leader_tx = match self.claim_or_join(&key) {
             FetchSlot::Joined(mut rx) => {
@@ -447,7 +349,8 @@ impl FinancialDataService {
     }
 
     /// Underlying fan-out fetch: fires OVERVIEW + INCOME_STATEMENT +
-    /// EARNINGS in parallel. Each call honours the rate limiter, so
+    /// EARNINGS in parallel, then SPLITS, so every uncached fetch is
+    /// four AV requests. Each call honours the rate limiter, so
     /// `try_join!` issues them on the wire in 1-req-per-second order
     /// even though the futures themselves run concurrently.
     async fn do_fetch_fundamental_data(
@@ -484,7 +387,23 @@ impl FinancialDataService {
             )
         )?;
 
-        let historical = income::process_historical_data(&av_income, &av_earnings);
+        // Split history only refines EPS; fetch it after the core three
+        // and fall back to unadjusted figures if it's unavailable.
+        let av_splits = splits::fetch_splits(
+            http_ref,
+            limiter_ref,
+            &self.api_key,
+            &self.base_url,
+            &self.cache,
+            symbol,
+        )
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Alpha Vantage SPLITS unavailable for {symbol}, EPS left unadjusted: {e}");
+            splits::AlphaVantageSplits::default()
+        });
+
+        let historical = income::process_historical_data(&av_income, &av_earnings, &av_splits);
 
         if historical.is_empty() {
             return Err(format!(
@@ -496,9 +415,15 @@ impl FinancialDataService {
         let analyst_estimates = earnings::process_analyst_estimates(&av_earnings);
 
         Ok(crate::ibkr::types::fundamentals::FundamentalData {
-            symbol: symbol.to_uppercase(),
+            symbol: symbols::normalize(symbol),
             historical,
             analyst_estimates,
+            data_quality: Vec::new(),
+            overrides: Vec::new(),
+            growth: None,
+            currency: income::reporting_currency(&av_income, av_overview.currency.as_deref()),
+            fx_conversion: None,
+            segments: Vec::new(),
             current_metrics,
         })
     }
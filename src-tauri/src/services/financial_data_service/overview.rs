@@ -25,6 +25,8 @@ pub(super) struct AlphaVantageOverview {
     pub(super) week_52_high: Option<String>,
     #[serde(rename = "DividendYield")]
     pub(super) dividend_yield: Option<String>,
+    #[serde(rename = "Currency", default)]
+    pub(super) currency: Option<String>,
 }
 
 pub(super) async fn fetch_overview(
@@ -85,6 +87,7 @@ pub(super) fn process_current_metrics(overview: &AlphaVantageOverview) -> Curren
         exchange: overview.exchange.clone(),
         market_cap,
         dividend_yield,
+        short_interest: None,
     }
 }
 
@@ -115,6 +118,7 @@ mod tests {
             shares_outstanding: Some("15000000000".into()),
             week_52_high: Some("202.49".into()),
             dividend_yield: Some("0.005".into()),
+            currency: Some("USD".into()),
         };
 
         let metrics = process_current_metrics(&overview);
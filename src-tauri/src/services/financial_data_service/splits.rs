@@ -0,0 +1,101 @@
+//! Stock-split history (`function=SPLITS`) and the EPS adjustment it
+//! drives.
+//!
+//! Alpha Vantage's `reportedEPS` is as-reported: NVDA's FY2024 EPS shows
+//! the pre-10:1 figure, so a naive growth rate across the split reads as
+//! a 90% collapse. Every fiscal year that ended before a split has its
+//! EPS divided by the cumulative factor of all later splits, and the
+//! factor is recorded on `HistoricalFinancial::split_adjustment`.
+//!
+//! SPLITS is a fourth AV request on every uncached fundamentals fetch,
+//! after OVERVIEW, INCOME_STATEMENT and EARNINGS; it is cached like
+//! them under `<SYMBOL>_splits`.
+//!
+//! Revenue and net income are totals and need no adjustment; current
+//! share counts from OVERVIEW are already post-split. Dividends don't
+//! change per-share earnings, so only splits are applied here.
+
+use crate::middleware::AlphaVantageRateLimiter;
+use crate::services::cache_service::CacheService;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+use super::{AvHttp, FinancialDataService};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct AlphaVantageSplits {
+    #[serde(default)]
+    pub(super) data: Vec<SplitRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct SplitRecord {
+    /// `YYYY-MM-DD`.
+    pub(super) effective_date: String,
+    /// New shares per old share, e.g. `"10.0000"`; reverse splits are < 1.
+    pub(super) split_factor: String,
+}
+
+pub(super) async fn fetch_splits(
+    http: &dyn AvHttp,
+    rate_limiter: Option<&AlphaVantageRateLimiter>,
+    api_key: &str,
+    base_url: &str,
+    cache: &Option<CacheService>,
+    symbol: &str,
+) -> Result<AlphaVantageSplits, Box<dyn Error + Send + Sync>> {
+    super::fetch_av_function(
+        http,
+        rate_limiter,
+        api_key,
+        base_url,
+        cache,
+        symbol,
+        "SPLITS",
+        "splits",
+    )
+    .await
+}
+
+/// Cumulative factor of splits effective after `fiscal_date_ending`
+/// (ISO dates compare lexically). `1.0` when none apply; unparseable
+/// or non-positive factors are skipped.
+pub(super) fn factor_after(splits: &AlphaVantageSplits, fiscal_date_ending: &str) -> f64 {
+    splits
+        .data
+        .iter()
+        .filter(|s| s.effective_date.as_str() > fiscal_date_ending)
+        .filter_map(|s| s.split_factor.parse::<f64>().ok())
+        .filter(|f| *f > 0.0)
+        .product()
+}
+
+impl FinancialDataService {
+    /// `symbol`'s split history as `(YYYY-MM-DD effective date, new
+    /// shares per old share)`, through the same file cache as the EPS
+    /// adjustment. Empty without an API key, as in demo mode.
+    /// Unparseable or non-positive factors are skipped.
+    pub async fn split_history(
+        &self,
+        symbol: &str,
+    ) -> Result<Vec<(String, f64)>, Box<dyn Error + Send + Sync>> {
+        if self.api_key.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let av_splits = fetch_splits(
+            self.av_http.as_ref(),
+            self.rate_limiter.as_deref(),
+            &self.api_key,
+            &self.base_url,
+            &self.cache,
+            symbol,
+        )
+        .await?;
+        Ok(av_splits
+            .data
+            .into_iter()
+            .filter_map(|s| Some((s.effective_date, s.split_factor.parse::<f64>().ok()?)))
+            .filter(|(_, factor)| *factor > 0.0)
+            .collect())
+    }
+}
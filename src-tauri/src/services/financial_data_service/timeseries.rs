@@ -0,0 +1,301 @@
+//! Fundamentals as chartable time series.
+//!
+//! Built from the same cached Alpha Vantage statements as the
+//! fundamentals fetch (plus `CASH_FLOW` for free cash flow): each
+//! metric comes back annual, quarterly and trailing-twelve-month,
+//! oldest first, so the frontend never parses the provider's strings.
+//!
+//! A TTM point sums four consecutive quarters ending on its date;
+//! margins are the summed numerator over the summed revenue rather
+//! than an average of quarterly margins. Quarters missing from the
+//! statements break the run, so a gap yields no TTM point instead of
+//! one spanning five quarters. EPS is restated for later splits like
+//! `HistoricalFinancial::eps`.
+
+use std::error::Error;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::cash_flow::{self, CashFlowReport};
+use super::income::{self, IncomeReport};
+use super::splits::{self, AlphaVantageSplits};
+use super::{earnings, FinancialDataService};
+use crate::utils::symbols;
+
+/// Four quarter-ends a year apart span about 273 days; anything much
+/// longer skips a quarter.
+const TTM_SPAN_DAYS: std::ops::RangeInclusive<i64> = 250..=300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FundamentalMetric {
+    /// Total revenue, in the reporting currency.
+    Revenue,
+    /// Gross profit over revenue, in percent.
+    GrossMargin,
+    /// Operating income over revenue, in percent.
+    OperatingMargin,
+    /// Net income over revenue, in percent.
+    NetMargin,
+    /// Reported diluted EPS, split-adjusted.
+    Eps,
+    /// Operating cash flow less capital expenditures, in the reporting
+    /// currency.
+    FreeCashFlow,
+}
+
+impl FundamentalMetric {
+    /// Margins are ratios of two sums; everything else sums as is.
+    pub(super) fn is_margin(self) -> bool {
+        matches!(
+            self,
+            Self::GrossMargin | Self::OperatingMargin | Self::NetMargin
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundamentalPoint {
+    /// Fiscal period end, `YYYY-MM-DD`.
+    pub period_end: String,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundamentalTimeseries {
+    pub symbol: String,
+    pub metric: FundamentalMetric,
+    /// Reporting currency of the statement read; `None` for margins,
+    /// EPS, or when the provider doesn't say.
+    pub currency: Option<String>,
+    /// Oldest first, as are the other two.
+    pub annual: Vec<FundamentalPoint>,
+    pub quarterly: Vec<FundamentalPoint>,
+    pub ttm: Vec<FundamentalPoint>,
+}
+
+/// One fiscal period's figure and, for a margin, its revenue.
+#[derive(Debug, Clone)]
+pub(super) struct Period {
+    end: NaiveDate,
+    value: f64,
+    revenue: f64,
+}
+
+fn parse(raw: Option<&str>) -> Option<f64> {
+    raw?.trim().parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+fn period_end(raw: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d").ok()
+}
+
+/// `metric` for each income statement report; margins skip periods
+/// without positive revenue.
+pub(super) fn income_periods(reports: &[IncomeReport], metric: FundamentalMetric) -> Vec<Period> {
+    reports
+        .iter()
+        .filter_map(|r| {
+            let revenue = parse(r.total_revenue.as_deref())?;
+            let value = match metric {
+                FundamentalMetric::GrossMargin => parse(r.gross_profit.as_deref())?,
+                FundamentalMetric::OperatingMargin => parse(r.operating_income.as_deref())?,
+                FundamentalMetric::NetMargin => parse(r.net_income.as_deref())?,
+                _ => revenue,
+            };
+            if metric.is_margin() && revenue <= 0.0 {
+                return None;
+            }
+            Some(Period {
+                end: period_end(&r.fiscal_date_ending)?,
+                value,
+                revenue,
+            })
+        })
+        .collect()
+}
+
+/// `(fiscal date ending, reported EPS)` rows, restated for the splits
+/// after each.
+pub(super) fn eps_periods<'a>(
+    rows: impl Iterator<Item = (&'a str, Option<&'a str>)>,
+    split_history: &AlphaVantageSplits,
+) -> Vec<Period> {
+    rows.filter_map(|(date, eps)| {
+        let factor = splits::factor_after(split_history, date);
+        Some(Period {
+            end: period_end(date)?,
+            value: parse(eps)? / factor,
+            revenue: 0.0,
+        })
+    })
+    .collect()
+}
+
+pub(super) fn free_cash_flow_periods(reports: &[CashFlowReport]) -> Vec<Period> {
+    reports
+        .iter()
+        .filter_map(|r| {
+            let operating = parse(r.operating_cashflow.as_deref())?;
+            let capex = parse(r.capital_expenditures.as_deref())?;
+            Some(Period {
+                end: period_end(&r.fiscal_date_ending)?,
+                value: operating - capex.abs(),
+                revenue: 0.0,
+            })
+        })
+        .collect()
+}
+
+/// Currency of the latest cash flow report.
+pub(super) fn cash_flow_currency(reports: &[CashFlowReport]) -> Option<String> {
+    reports
+        .iter()
+        .max_by(|a, b| a.fiscal_date_ending.cmp(&b.fiscal_date_ending))
+        .and_then(|r| r.reported_currency.as_deref())
+        .map(str::trim)
+        .filter(|c| !c.is_empty() && *c != "None")
+        .map(str::to_uppercase)
+}
+
+fn point(end: NaiveDate, value: f64, revenue: f64, margin: bool) -> FundamentalPoint {
+    FundamentalPoint {
+        period_end: end.to_string(),
+        value: if margin {
+            value / revenue * 100.0
+        } else {
+            value
+        },
+    }
+}
+
+/// `periods` oldest first, one point per period end.
+pub(super) fn points(mut periods: Vec<Period>, margin: bool) -> Vec<FundamentalPoint> {
+    periods.sort_by_key(|p| p.end);
+    periods.dedup_by_key(|p| p.end);
+    periods
+        .iter()
+        .map(|p| point(p.end, p.value, p.revenue, margin))
+        .collect()
+}
+
+/// Trailing-twelve-month points from `quarters`, oldest first.
+pub(super) fn ttm(mut quarters: Vec<Period>, margin: bool) -> Vec<FundamentalPoint> {
+    quarters.sort_by_key(|p| p.end);
+    quarters.dedup_by_key(|p| p.end);
+    quarters
+        .windows(4)
+        .filter(|w| TTM_SPAN_DAYS.contains(&(w[3].end - w[0].end).num_days()))
+        .map(|w| {
+            let value = w.iter().map(|p| p.value).sum();
+            let revenue = w.iter().map(|p| p.revenue).sum();
+            point(w[3].end, value, revenue, margin)
+        })
+        .collect()
+}
+
+impl FinancialDataService {
+    /// `metric` for `symbol` as annual, quarterly and TTM series (see
+    /// `timeseries`), through the same file cache as the fundamentals
+    /// fetch. Only the statements the metric needs are read. Empty
+    /// without an API key, as in demo mode.
+    pub async fn fundamental_timeseries(
+        &self,
+        symbol: &str,
+        metric: FundamentalMetric,
+    ) -> Result<FundamentalTimeseries, Box<dyn Error + Send + Sync>> {
+        let mut series = FundamentalTimeseries {
+            symbol: symbols::normalize(symbol),
+            metric,
+            currency: None,
+            annual: Vec::new(),
+            quarterly: Vec::new(),
+            ttm: Vec::new(),
+        };
+        if self.api_key.trim().is_empty() {
+            return Ok(series);
+        }
+        let (annual, quarterly) = match metric {
+            FundamentalMetric::Eps => {
+                let av_earnings = earnings::fetch_earnings(
+                    self.av_http.as_ref(),
+                    self.rate_limiter.as_deref(),
+                    &self.api_key,
+                    &self.base_url,
+                    &self.cache,
+                    symbol,
+                )
+                .await?;
+                let av_splits = splits::fetch_splits(
+                    self.av_http.as_ref(),
+                    self.rate_limiter.as_deref(),
+                    &self.api_key,
+                    &self.base_url,
+                    &self.cache,
+                    symbol,
+                )
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("AV splits fetch failed for {symbol}, EPS left unadjusted: {e}");
+                    splits::AlphaVantageSplits::default()
+                });
+                let annual = av_earnings
+                    .annual_earnings
+                    .iter()
+                    .map(|e| (e.fiscal_date_ending.as_str(), e.reported_eps.as_deref()));
+                let quarterly = av_earnings
+                    .quarterly_earnings
+                    .iter()
+                    .flatten()
+                    .map(|e| (e.fiscal_date_ending.as_str(), e.reported_eps.as_deref()));
+                (
+                    eps_periods(annual, &av_splits),
+                    eps_periods(quarterly, &av_splits),
+                )
+            }
+            FundamentalMetric::FreeCashFlow => {
+                let av_cash_flow = cash_flow::fetch_cash_flow(
+                    self.av_http.as_ref(),
+                    self.rate_limiter.as_deref(),
+                    &self.api_key,
+                    &self.base_url,
+                    &self.cache,
+                    symbol,
+                )
+                .await?;
+                series.currency = cash_flow_currency(&av_cash_flow.annual_reports);
+                (
+                    free_cash_flow_periods(&av_cash_flow.annual_reports),
+                    free_cash_flow_periods(&av_cash_flow.quarterly_reports),
+                )
+            }
+            _ => {
+                let av_income = income::fetch_income_statement(
+                    self.av_http.as_ref(),
+                    self.rate_limiter.as_deref(),
+                    &self.api_key,
+                    &self.base_url,
+                    &self.cache,
+                    symbol,
+                )
+                .await?;
+                if !metric.is_margin() {
+                    series.currency = income::reporting_currency(&av_income, None);
+                }
+                (
+                    income_periods(&av_income.annual_reports, metric),
+                    income_periods(&av_income.quarterly_reports, metric),
+                )
+            }
+        };
+        let margin = metric.is_margin();
+        series.ttm = ttm(quarterly.clone(), margin);
+        series.annual = points(annual, margin);
+        series.quarterly = points(quarterly, margin);
+        Ok(series)
+    }
+}
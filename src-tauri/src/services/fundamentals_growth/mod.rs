@@ -0,0 +1,87 @@
+//! Growth decomposition of the historical fundamentals series.
+//!
+//! The UI's historical table and any export read YoY growth, margin
+//! trend and trailing CAGRs off [`FundamentalData::growth`] rather than
+//! recomputing them, so both show the same numbers. [`decompose`] is a
+//! pure function of `historical`; [`annotate`] stores its result on the
+//! record and runs after overrides have been merged, so a corrected
+//! year flows into the growth figures too.
+//!
+//! YoY figures need the immediately preceding fiscal year — across a
+//! gap (see `fundamentals_quality`'s `missing_years`) they are `None`
+//! rather than silently spanning two years. Growth off a non-positive
+//! base is undefined and also `None`.
+
+use crate::ibkr::types::{
+    FundamentalData, GrowthAnalysis, HistoricalCagr, HistoricalFinancial, YearGrowth,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Trailing CAGR windows, in years.
+pub const CAGR_WINDOWS: [u32; 2] = [3, 5];
+
+/// Fill `data.growth` from `data.historical`; `None` for an empty series.
+pub fn annotate(data: &mut FundamentalData) {
+    data.growth = (!data.historical.is_empty()).then(|| decompose(&data.historical));
+}
+
+pub fn decompose(historical: &[HistoricalFinancial]) -> GrowthAnalysis {
+    let mut rows: Vec<&HistoricalFinancial> = historical.iter().collect();
+    rows.sort_by_key(|h| h.year);
+
+    let find = |year: u32| rows.iter().copied().find(|h| h.year == year);
+
+    let years = rows
+        .iter()
+        .map(|cur| {
+            let prev = cur.year.checked_sub(1).and_then(find);
+            let margin = net_margin(cur);
+            YearGrowth {
+                year: cur.year,
+                revenue_growth: prev.and_then(|p| growth(p.revenue, cur.revenue)),
+                net_income_growth: prev.and_then(|p| growth(p.net_income, cur.net_income)),
+                eps_growth: prev.and_then(|p| growth(p.eps, cur.eps)),
+                net_margin: margin,
+                margin_change: prev
+                    .and_then(net_margin)
+                    .zip(margin)
+                    .map(|(before, after)| after - before),
+            }
+        })
+        .collect();
+
+    let cagr = match rows.last() {
+        Some(latest) => CAGR_WINDOWS
+            .iter()
+            .map(|&n| {
+                let start = latest.year.checked_sub(n).and_then(find);
+                HistoricalCagr {
+                    years: n,
+                    revenue: start.and_then(|s| cagr(s.revenue, latest.revenue, n)),
+                    net_income: start.and_then(|s| cagr(s.net_income, latest.net_income, n)),
+                    eps: start.and_then(|s| cagr(s.eps, latest.eps, n)),
+                }
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    GrowthAnalysis { years, cagr }
+}
+
+fn growth(before: f64, after: f64) -> Option<f64> {
+    (before > 0.0).then(|| (after / before - 1.0) * 100.0)
+}
+
+fn net_margin(h: &HistoricalFinancial) -> Option<f64> {
+    (h.revenue > 0.0).then(|| h.net_income / h.revenue * 100.0)
+}
+
+/// `((end / begin) ^ (1 / years)) - 1`, same formula as the projection
+/// CAGR. Needs both ends positive.
+fn cagr(begin: f64, end: f64, years: u32) -> Option<f64> {
+    (begin > 0.0 && end > 0.0 && years > 0)
+        .then(|| ((end / begin).powf(1.0 / years as f64) - 1.0) * 100.0)
+}
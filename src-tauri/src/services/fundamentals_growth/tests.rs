@@ -0,0 +1,77 @@
+use crate::ibkr::types::HistoricalFinancial;
+
+use super::decompose;
+
+fn year(year: u32, revenue: f64, net_income: f64, eps: f64) -> HistoricalFinancial {
+    HistoricalFinancial {
+        year,
+        revenue,
+        net_income,
+        eps,
+        split_adjustment: None,
+    }
+}
+
+fn close(a: Option<f64>, b: f64) -> bool {
+    a.is_some_and(|a| (a - b).abs() < 1e-6)
+}
+
+#[test]
+fn yoy_growth_and_margin_trend_per_year() {
+    let g = decompose(&[year(2023, 120.0, 18.0, 1.8), year(2022, 100.0, 10.0, 1.0)]);
+    assert_eq!(
+        g.years.iter().map(|y| y.year).collect::<Vec<_>>(),
+        [2022, 2023]
+    );
+
+    let first = &g.years[0];
+    assert!(first.revenue_growth.is_none() && first.margin_change.is_none());
+    assert!(close(first.net_margin, 10.0));
+
+    let second = &g.years[1];
+    assert!(close(second.revenue_growth, 20.0));
+    assert!(close(second.net_income_growth, 80.0));
+    assert!(close(second.eps_growth, 80.0));
+    assert!(close(second.net_margin, 15.0));
+    assert!(close(second.margin_change, 5.0));
+}
+
+#[test]
+fn trailing_cagr_over_three_and_five_years() {
+    let series: Vec<_> = (0..6)
+        .map(|i| {
+            let r = 100.0 * 1.1f64.powi(i);
+            year(2019 + i as u32, r, r / 10.0, r / 100.0)
+        })
+        .collect();
+    let g = decompose(&series);
+
+    assert_eq!(g.cagr.iter().map(|c| c.years).collect::<Vec<_>>(), [3, 5]);
+    for window in &g.cagr {
+        assert!(close(window.revenue, 10.0), "{window:?}");
+        assert!(close(window.net_income, 10.0));
+        assert!(close(window.eps, 10.0));
+    }
+}
+
+#[test]
+fn gaps_and_non_positive_bases_are_undefined() {
+    let g = decompose(&[
+        year(2020, 50.0, -5.0, -0.5),
+        year(2021, 60.0, 6.0, 0.6),
+        year(2023, 80.0, 8.0, 0.8),
+    ]);
+    let fy2021 = &g.years[1];
+    assert!(close(fy2021.revenue_growth, 20.0));
+    assert!(fy2021.net_income_growth.is_none(), "loss-making base");
+    let fy2023 = &g.years[2];
+    assert!(fy2023.revenue_growth.is_none(), "FY2022 missing");
+
+    let three = &g.cagr[0];
+    assert!(close(
+        three.revenue,
+        ((80.0f64 / 50.0).powf(1.0 / 3.0) - 1.0) * 100.0
+    ));
+    assert!(three.eps.is_none(), "negative starting EPS");
+    assert!(g.cagr[1].revenue.is_none(), "no FY2018");
+}
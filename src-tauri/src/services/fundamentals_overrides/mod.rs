@@ -0,0 +1,286 @@
+//! Per-field fundamentals overrides.
+//!
+//! Provider data is sometimes wrong (AV double-counting a restated
+//! year) or missing (a year absent from the income statement). Rather
+//! than replacing the whole record through `set_fundamentals`, the
+//! operator stores just the corrections here; [`apply`] lays them over
+//! the provider's [`FundamentalData`] and records every replaced value
+//! in `FundamentalData::overrides` so the UI can badge it.
+//!
+//! Wired as a decorator around the production provider
+//! ([`crate::services::fundamentals_provider::overrides`]), so the
+//! analysis commands, MCP tools and HTTP API all see the same merged
+//! record. A year override for a fiscal year the provider lacks adds
+//! the row, provided it carries both revenue and net income. Segment
+//! revenue, which no provider reports, is entered here too and replaces
+//! the record's segments wholesale.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::ibkr::types::{FundamentalData, HistoricalFinancial, OverriddenField, RevenueSegment};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::symbols;
+
+#[cfg(test)]
+mod tests;
+
+/// Corrections for one fiscal year; `None` leaves the provider value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct YearOverride {
+    pub year: u32,
+    /// Billions, like `HistoricalFinancial::revenue`.
+    #[serde(default)]
+    pub revenue: Option<f64>,
+    #[serde(default)]
+    pub net_income: Option<f64>,
+    #[serde(default)]
+    pub eps: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FundamentalsOverride {
+    #[serde(default)]
+    pub years: Vec<YearOverride>,
+    /// Millions, like `CurrentMetrics::shares_outstanding`.
+    #[serde(default)]
+    pub shares_outstanding: Option<f64>,
+    #[serde(default)]
+    pub price: Option<f64>,
+    /// Revenue by business segment, for projecting by segment.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<RevenueSegment>,
+}
+
+impl FundamentalsOverride {
+    pub fn is_empty(&self) -> bool {
+        self.shares_outstanding.is_none()
+            && self.price.is_none()
+            && self.segments.is_empty()
+            && self
+                .years
+                .iter()
+                .all(|y| y.revenue.is_none() && y.net_income.is_none() && y.eps.is_none())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrideRow {
+    pub symbol: String,
+    pub overrides: FundamentalsOverride,
+    pub written_at: i64,
+    pub written_by: String,
+    pub notes: Option<String>,
+}
+
+/// Lay `ov` over `data`, appending an [`OverriddenField`] per change.
+pub fn apply(data: &mut FundamentalData, ov: &FundamentalsOverride) {
+    let mut flagged = Vec::new();
+    let mut flag = |field: &str, year: Option<u32>, provider_value: Option<f64>, value: f64| {
+        flagged.push(OverriddenField {
+            field: field.to_string(),
+            year,
+            provider_value,
+            value,
+        });
+    };
+
+    for y in &ov.years {
+        match data.historical.iter_mut().find(|h| h.year == y.year) {
+            Some(row) => {
+                for (field, slot, value) in [
+                    ("revenue", &mut row.revenue, y.revenue),
+                    ("net_income", &mut row.net_income, y.net_income),
+                    ("eps", &mut row.eps, y.eps),
+                ] {
+                    if let Some(value) = value {
+                        flag(field, Some(y.year), Some(*slot), value);
+                        *slot = value;
+                    }
+                }
+            }
+            None => {
+                let (Some(revenue), Some(net_income)) = (y.revenue, y.net_income) else {
+                    warn!(
+                        "fundamentals override for {} FY{} skipped: year missing upstream and override lacks revenue + net income",
+                        data.symbol, y.year
+                    );
+                    continue;
+                };
+                flag("revenue", Some(y.year), None, revenue);
+                flag("net_income", Some(y.year), None, net_income);
+                if let Some(eps) = y.eps {
+                    flag("eps", Some(y.year), None, eps);
+                }
+                data.historical.push(HistoricalFinancial {
+                    year: y.year,
+                    revenue,
+                    net_income,
+                    eps: y.eps.unwrap_or(0.0),
+                    split_adjustment: None,
+                });
+            }
+        }
+    }
+    data.historical.sort_by_key(|h| h.year);
+
+    let metrics = &mut data.current_metrics;
+    if let Some(shares) = ov.shares_outstanding {
+        flag(
+            "shares_outstanding",
+            None,
+            Some(metrics.shares_outstanding),
+            shares,
+        );
+        metrics.shares_outstanding = shares;
+    }
+    if let Some(price) = ov.price {
+        flag("price", None, metrics.price, price);
+        metrics.price = Some(price);
+    }
+
+    if !ov.segments.is_empty() {
+        data.segments = ov.segments.clone();
+    }
+
+    data.overrides.extend(flagged);
+}
+
+/// Thin wrapper over `Arc<Db>` for the `fundamentals_overrides` table.
+#[derive(Clone)]
+pub struct FundamentalsOverridesStore {
+    db: Arc<Db>,
+}
+
+impl FundamentalsOverridesStore {
+    pub fn new(db: Arc<Db>) -> Self {
+        Self { db }
+    }
+
+    pub async fn get(&self, symbol: &str) -> Result<Option<OverrideRow>, StorageError> {
+        let key = symbols::normalize(symbol);
+        if key.is_empty() {
+            return Ok(None);
+        }
+        let mut rows = self.query(Some(key)).await?;
+        Ok(rows.pop())
+    }
+
+    /// Every stored override, by symbol.
+    pub async fn list(&self) -> Result<Vec<OverrideRow>, StorageError> {
+        self.query(None).await
+    }
+
+    /// Replace the override for `symbol`. An empty override clears it.
+    pub async fn upsert(
+        &self,
+        symbol: &str,
+        overrides: FundamentalsOverride,
+        written_by: &str,
+        notes: Option<String>,
+    ) -> Result<Option<OverrideRow>, StorageError> {
+        let key = symbols::normalize(symbol);
+        if key.is_empty() {
+            return Err(StorageError::Migration(
+                "fundamentals_overrides symbol must be non-empty".to_string(),
+            ));
+        }
+        if overrides.is_empty() {
+            self.clear(&key).await?;
+            return Ok(None);
+        }
+        let row = OverrideRow {
+            symbol: key,
+            overrides,
+            written_at: Utc::now().timestamp(),
+            written_by: written_by.to_string(),
+            notes,
+        };
+        let payload = serde_json::to_string(&row.overrides)?;
+        let params = (
+            row.symbol.clone(),
+            row.written_at,
+            row.written_by.clone(),
+            row.notes.clone(),
+        );
+        self.db
+            .with_conn(move |conn| {
+                conn.execute(
+                    "INSERT INTO fundamentals_overrides \
+                       (symbol, payload_json, written_at, written_by, notes) \
+                     VALUES (?1, ?2, ?3, ?4, ?5) \
+                     ON CONFLICT(symbol) DO UPDATE SET \
+                       payload_json = excluded.payload_json, \
+                       written_at = excluded.written_at, \
+                       written_by = excluded.written_by, \
+                       notes = excluded.notes",
+                    rusqlite::params![params.0, payload, params.1, params.2, params.3],
+                )?;
+                Ok(())
+            })
+            .await?;
+        Ok(Some(row))
+    }
+
+    /// `true` when a row was removed.
+    pub async fn clear(&self, symbol: &str) -> Result<bool, StorageError> {
+        let key = symbols::normalize(symbol);
+        let removed = self
+            .db
+            .with_conn(move |conn| {
+                Ok(conn.execute(
+                    "DELETE FROM fundamentals_overrides WHERE symbol = ?1",
+                    rusqlite::params![key],
+                )?)
+            })
+            .await?;
+        Ok(removed > 0)
+    }
+
+    async fn query(&self, symbol: Option<String>) -> Result<Vec<OverrideRow>, StorageError> {
+        let raw = self
+            .db
+            .with_conn(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT symbol, payload_json, written_at, written_by, notes \
+                     FROM fundamentals_overrides \
+                     WHERE ?1 IS NULL OR symbol = ?1 \
+                     ORDER BY symbol ASC",
+                )?;
+                let rows = stmt.query_map(rusqlite::params![symbol], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, i64>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, Option<String>>(4)?,
+                    ))
+                })?;
+                let mut out = Vec::new();
+                for row in rows {
+                    out.push(row?);
+                }
+                Ok(out)
+            })
+            .await?;
+        raw.into_iter()
+            .map(|(symbol, payload, written_at, written_by, notes)| {
+                Ok(OverrideRow {
+                    symbol,
+                    overrides: serde_json::from_str(&payload)?,
+                    written_at,
+                    written_by,
+                    notes,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,219 @@
+use std::sync::Arc;
+
+use tempfile::NamedTempFile;
+
+use crate::ibkr::types::{
+    CurrentMetrics, FundamentalData, HistoricalFinancial, RevenueSegment, SegmentRevenue,
+};
+use crate::services::fundamentals_provider::overrides::OverridingFundamentalsProvider;
+use crate::services::fundamentals_provider::test_support::FakeFundamentalsProvider;
+use crate::services::fundamentals_provider::FundamentalsProvider;
+use crate::storage::Db;
+
+use super::{apply, FundamentalsOverride, FundamentalsOverridesStore, YearOverride};
+
+fn open_store() -> (NamedTempFile, Arc<FundamentalsOverridesStore>) {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    (tmp, Arc::new(FundamentalsOverridesStore::new(db)))
+}
+
+fn provider_data() -> FundamentalData {
+    FundamentalData {
+        symbol: "ACME".to_string(),
+        historical: vec![
+            HistoricalFinancial {
+                year: 2022,
+                revenue: 10.0,
+                net_income: 1.0,
+                eps: 1.0,
+                split_adjustment: None,
+            },
+            HistoricalFinancial {
+                year: 2024,
+                revenue: 14.0,
+                net_income: 1.4,
+                eps: 1.4,
+                split_adjustment: None,
+            },
+        ],
+        analyst_estimates: None,
+        data_quality: Vec::new(),
+        overrides: Vec::new(),
+        growth: None,
+        currency: None,
+        fx_conversion: None,
+        segments: Vec::new(),
+        current_metrics: CurrentMetrics {
+            price: None,
+            pe_ratio: 20.0,
+            shares_outstanding: 1_000.0,
+            name: None,
+            exchange: None,
+            market_cap: None,
+            dividend_yield: None,
+            short_interest: None,
+        },
+    }
+}
+
+fn correction() -> FundamentalsOverride {
+    FundamentalsOverride {
+        years: vec![
+            YearOverride {
+                year: 2024,
+                eps: Some(1.5),
+                ..Default::default()
+            },
+            YearOverride {
+                year: 2023,
+                revenue: Some(12.0),
+                net_income: Some(1.2),
+                eps: None,
+            },
+        ],
+        shares_outstanding: Some(950.0),
+        price: Some(31.0),
+        segments: Vec::new(),
+    }
+}
+
+#[test]
+fn apply_replaces_named_fields_and_flags_them() {
+    let mut data = provider_data();
+    apply(&mut data, &correction());
+
+    let years: Vec<u32> = data.historical.iter().map(|h| h.year).collect();
+    assert_eq!(years, vec![2022, 2023, 2024], "missing year added in order");
+    let fy2024 = &data.historical[2];
+    assert_eq!((fy2024.revenue, fy2024.eps), (14.0, 1.5));
+    assert_eq!(data.current_metrics.shares_outstanding, 950.0);
+    assert_eq!(data.current_metrics.price, Some(31.0));
+
+    let eps_flag = data
+        .overrides
+        .iter()
+        .find(|o| o.field == "eps" && o.year == Some(2024))
+        .expect("eps flagged");
+    assert_eq!(eps_flag.provider_value, Some(1.4));
+    let added = data
+        .overrides
+        .iter()
+        .find(|o| o.field == "revenue" && o.year == Some(2023))
+        .expect("added year flagged");
+    assert_eq!(added.provider_value, None);
+    assert_eq!(data.overrides.len(), 5);
+}
+
+#[test]
+fn partial_override_for_missing_year_is_skipped() {
+    let mut data = provider_data();
+    let ov = FundamentalsOverride {
+        years: vec![YearOverride {
+            year: 2023,
+            eps: Some(1.2),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    apply(&mut data, &ov);
+    assert_eq!(data.historical.len(), 2);
+    assert!(data.overrides.is_empty());
+}
+
+#[test]
+fn segment_override_alone_is_kept_and_replaces_segments() {
+    let mut data = provider_data();
+    let ov = FundamentalsOverride {
+        segments: vec![RevenueSegment {
+            name: "Rockets".to_string(),
+            revenue: vec![SegmentRevenue {
+                year: 2024,
+                revenue: 9.0,
+            }],
+        }],
+        ..Default::default()
+    };
+    assert!(!ov.is_empty());
+    apply(&mut data, &ov);
+    assert_eq!(data.segments, ov.segments);
+    assert!(data.overrides.is_empty());
+}
+
+#[tokio::test]
+async fn store_round_trips_and_empty_override_clears() {
+    let (_tmp, store) = open_store();
+    store
+        .upsert(
+            "acme",
+            correction(),
+            "interactive",
+            Some("AV FY2023 missing".into()),
+        )
+        .await
+        .unwrap();
+
+    let row = store.get("ACME").await.unwrap().expect("row stored");
+    assert_eq!(row.symbol, "ACME");
+    assert_eq!(row.overrides, correction());
+    assert_eq!(store.list().await.unwrap().len(), 1);
+
+    let cleared = store
+        .upsert("ACME", FundamentalsOverride::default(), "interactive", None)
+        .await
+        .unwrap();
+    assert!(cleared.is_none());
+    assert!(store.get("ACME").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn store_round_trips_a_segment_override() {
+    let (_tmp, store) = open_store();
+    let ov = FundamentalsOverride {
+        segments: vec![RevenueSegment {
+            name: "Rockets".to_string(),
+            revenue: vec![
+                SegmentRevenue {
+                    year: 2023,
+                    revenue: 7.5,
+                },
+                SegmentRevenue {
+                    year: 2024,
+                    revenue: 9.0,
+                },
+            ],
+        }],
+        ..correction()
+    };
+    store
+        .upsert("ACME", ov.clone(), "interactive", None)
+        .await
+        .unwrap();
+
+    let row = store.get("acme").await.unwrap().expect("row stored");
+    assert_eq!(row.overrides, ov);
+    assert_eq!(row.overrides.segments[0].revenue[1].revenue, 9.0);
+}
+
+#[tokio::test]
+async fn decorator_merges_overrides_over_provider_data() {
+    let (_tmp, store) = open_store();
+    let fake = FakeFundamentalsProvider::new();
+    fake.insert("ACME", provider_data());
+    fake.insert("PLAIN", provider_data());
+    store
+        .upsert("ACME", correction(), "interactive", None)
+        .await
+        .unwrap();
+    let provider = OverridingFundamentalsProvider::new(Arc::new(fake), Arc::clone(&store));
+
+    let merged = provider.fetch("acme").await.unwrap();
+    assert_eq!(merged.current_metrics.price, Some(31.0));
+    assert!(!merged.overrides.is_empty());
+
+    let plain = provider.fetch("PLAIN").await.unwrap();
+    assert!(
+        plain.overrides.is_empty(),
+        "no row → provider data untouched"
+    );
+}
@@ -34,6 +34,7 @@ use tracing::warn;
 
 use crate::storage::error::StorageError;
 use crate::storage::Db;
+use crate::utils::symbols;
 
 /// Default daily soft cap. Past this, the ledger emits a `warn!` per
 /// consult so operators can see the trip-wire fire.
@@ -159,11 +160,12 @@ impl AvCallLedger {
     /// (so a transport error doesn't silently exhaust the daily quota).
     ///
     /// Counts are 1-per-`composite.fetch`, NOT 1-per-endpoint. The AV
-    /// adapter under the hood fans out 3 endpoint calls (`OVERVIEW` /
-    /// `INCOME_STATEMENT` / `EARNINGS`) — we count this as one
-    /// operator-cost unit.
+    /// adapter under the hood makes 4 endpoint calls (`OVERVIEW` /
+    /// `INCOME_STATEMENT` / `EARNINGS`, then `SPLITS`) — we count this
+    /// as one operator-cost unit, so a full day at the hard cap is four
+    /// times that many requests against AV's own quota.
     pub async fn check(&self, symbol: &str) -> Result<ReserveOutcome, AvLedgerError> {
-        let symbol = symbol.trim().to_uppercase();
+        let symbol = symbols::normalize(symbol);
         if symbol.is_empty() {
             return Err(AvLedgerError::Storage(StorageError::Migration(
                 "AvCallLedger.check: symbol must be non-empty".to_string(),
@@ -215,7 +217,7 @@ impl AvCallLedger {
     /// counters are bumped only after the row write succeeds so disk
     /// and memory cannot drift on a failed write.
     pub async fn commit(&self, symbol: &str) -> Result<(), AvLedgerError> {
-        let symbol = symbol.trim().to_uppercase();
+        let symbol = symbols::normalize(symbol);
         if symbol.is_empty() {
             return Err(AvLedgerError::Storage(StorageError::Migration(
                 "AvCallLedger.commit: symbol must be non-empty".to_string(),
@@ -300,7 +302,7 @@ impl AvCallLedger {
     /// Read the current per-symbol count for inspection / tests.
     #[allow(dead_code)] // exposed for the planned UI banner; only tests consume it today
     pub async fn per_symbol_count_today(&self, symbol: &str) -> Result<u32, AvLedgerError> {
-        let symbol = symbol.trim().to_uppercase();
+        let symbol = symbols::normalize(symbol);
         let today = self.date_source.today();
         self.hydrate_if_empty(today).await?;
         let count = self
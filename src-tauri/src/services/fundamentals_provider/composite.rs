@@ -27,6 +27,7 @@ use tracing::{info, warn};
 
 use crate::ibkr::types::FundamentalData;
 use crate::services::cache_service::CacheService;
+use crate::utils::symbols;
 
 use super::av_call_ledger::{AvCallLedger, AvLedgerError, ReserveOutcome};
 use super::manual::ManualFundamentalsProvider;
@@ -100,7 +101,7 @@ impl FundamentalsProvider for CompositeFundamentalsProvider {
             }
         }
 
-        let key = symbol.trim().to_uppercase();
+        let key = symbols::normalize(symbol);
 
         let Some(guard) = self.av_guard.as_ref() else {
             // No guard configured — preserve Phase 4 behaviour for
@@ -229,8 +230,15 @@ mod tests {
                 revenue: 100.0,
                 net_income: 10.0,
                 eps: 1.0,
+                split_adjustment: None,
             }],
             analyst_estimates: None,
+            data_quality: Vec::new(),
+            overrides: Vec::new(),
+            growth: None,
+            currency: None,
+            fx_conversion: None,
+            segments: Vec::new(),
             current_metrics: CurrentMetrics {
                 price: None,
                 pe_ratio: pe,
@@ -239,6 +247,7 @@ mod tests {
                 exchange: None,
                 market_cap: None,
                 dividend_yield: None,
+                short_interest: None,
             },
         }
     }
@@ -391,7 +400,7 @@ mod tests {
             self.rows
                 .lock()
                 .unwrap()
-                .insert(symbol.to_uppercase(), data);
+                .insert(symbols::normalize(symbol), data);
         }
 
         fn calls(&self) -> usize {
@@ -403,7 +412,7 @@ mod tests {
     impl FundamentalsProvider for CountingFake {
         async fn fetch(&self, symbol: &str) -> Result<FundamentalData, FundamentalsError> {
             self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-            let key = symbol.trim().to_uppercase();
+            let key = symbols::normalize(symbol);
             self.rows
                 .lock()
                 .unwrap()
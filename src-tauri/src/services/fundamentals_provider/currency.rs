@@ -0,0 +1,68 @@
+//! [`CurrencyConvertingFundamentalsProvider`] — decorator that puts
+//! every record into the configured base currency
+//! ([`crate::services::fx_service`]).
+//!
+//! Sits inside the override decorator, so operator overrides are
+//! entered and shown in the base currency like everything else. When no
+//! rate is available the record passes through in its reporting
+//! currency — still labelled by `FundamentalData::currency`, so the UI
+//! never shows local-currency figures as if they were base.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{info, warn};
+
+use crate::ibkr::types::FundamentalData;
+use crate::services::fx_service::{self, FxRateProvider};
+
+use super::{FundamentalsError, FundamentalsProvider};
+
+pub struct CurrencyConvertingFundamentalsProvider {
+    inner: Arc<dyn FundamentalsProvider>,
+    fx: Arc<dyn FxRateProvider>,
+    base: String,
+}
+
+impl CurrencyConvertingFundamentalsProvider {
+    pub fn new(
+        inner: Arc<dyn FundamentalsProvider>,
+        fx: Arc<dyn FxRateProvider>,
+        base_currency: &str,
+    ) -> Self {
+        Self {
+            inner,
+            fx,
+            base: base_currency.trim().to_uppercase(),
+        }
+    }
+}
+
+#[async_trait]
+impl FundamentalsProvider for CurrencyConvertingFundamentalsProvider {
+    async fn fetch(&self, symbol: &str) -> Result<FundamentalData, FundamentalsError> {
+        let mut data = self.inner.fetch(symbol).await?;
+        let from = fx_service::currency_of(&data);
+        if from == self.base {
+            data.currency = Some(from);
+            return Ok(data);
+        }
+        match self.fx.rate(&from, &self.base).await {
+            Ok(rate) => {
+                fx_service::convert(&mut data, &self.base, rate);
+                info!(
+                    "fundamentals(fx): {symbol} converted {from} → {} at {rate}",
+                    self.base
+                );
+            }
+            Err(e) => {
+                warn!(
+                    "fundamentals(fx): {symbol} left in {from}, no rate to {}: {e}",
+                    self.base
+                );
+                data.currency = Some(from);
+            }
+        }
+        Ok(data)
+    }
+}
@@ -0,0 +1,109 @@
+//! [`DemoFundamentalsProvider`] — made-up fundamentals for demo mode,
+//! standing in for the Alpha Vantage adapter behind the composite.
+//!
+//! Every symbol gets a record: a P/E, share count, margin and growth
+//! rate derived from the symbol (see `ibkr::client::demo::seed`), five
+//! years of history growing into today's earnings, and the demo feed's
+//! current price. The same symbol always gets the same company.
+
+use async_trait::async_trait;
+use chrono::{Datelike, Utc};
+
+use crate::ibkr::client::demo;
+use crate::ibkr::types::{CurrentMetrics, FundamentalData, HistoricalFinancial};
+use crate::utils::symbols;
+
+use super::{FundamentalsError, FundamentalsProvider};
+
+const YEARS: u32 = 5;
+
+#[derive(Default)]
+pub struct DemoFundamentalsProvider;
+
+impl DemoFundamentalsProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl FundamentalsProvider for DemoFundamentalsProvider {
+    async fn fetch(&self, symbol: &str) -> Result<FundamentalData, FundamentalsError> {
+        let key = symbols::normalize(symbol);
+        if key.is_empty() {
+            return Err(FundamentalsError::NotFound(key));
+        }
+        Ok(synthesize(&key))
+    }
+}
+
+fn synthesize(symbol: &str) -> FundamentalData {
+    let seed = demo::seed(symbol);
+    let price = demo::last_price(symbol);
+    let pe_ratio = 12.0 + (seed % 2_400) as f64 / 100.0;
+    let shares_outstanding = 200.0 + ((seed >> 16) % 9_800) as f64; // millions
+    let margin = 0.08 + ((seed >> 32) % 180) as f64 / 1_000.0;
+    let growth = 0.02 + ((seed >> 40) % 130) as f64 / 1_000.0;
+
+    let eps_now = price / pe_ratio;
+    let last_year = Utc::now().year() as u32 - 1;
+    let historical = (0..YEARS)
+        .rev()
+        .map(|back| {
+            let eps = eps_now / (1.0 + growth).powi(back as i32);
+            let net_income = eps * shares_outstanding / 1_000.0; // billions
+            HistoricalFinancial {
+                year: last_year - back,
+                revenue: net_income / margin,
+                net_income,
+                eps,
+                split_adjustment: None,
+            }
+        })
+        .collect();
+
+    FundamentalData {
+        symbol: symbol.to_string(),
+        historical,
+        analyst_estimates: None,
+        current_metrics: CurrentMetrics {
+            price: Some(price),
+            pe_ratio,
+            shares_outstanding,
+            name: Some(format!("{symbol} Demo Corp")),
+            exchange: Some("NASDAQ".to_string()),
+            market_cap: Some(format!("{:.1}B", price * shares_outstanding / 1_000.0)),
+            dividend_yield: seed.is_multiple_of(3).then_some(0.015),
+            short_interest: None,
+        },
+        data_quality: Vec::new(),
+        overrides: Vec::new(),
+        growth: None,
+        currency: None,
+        fx_conversion: None,
+        segments: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn same_symbol_same_company() {
+        let provider = DemoFundamentalsProvider::new();
+        let a = provider.fetch("msft").await.unwrap();
+        let b = provider.fetch("MSFT").await.unwrap();
+        assert_eq!(a.symbol, "MSFT");
+        assert_eq!(a.current_metrics.pe_ratio, b.current_metrics.pe_ratio);
+        assert_eq!(a.historical.len(), YEARS as usize);
+        assert!(a
+            .historical
+            .windows(2)
+            .all(|w| w[0].year < w[1].year && w[0].eps < w[1].eps));
+        assert!(matches!(
+            provider.fetch("  ").await,
+            Err(FundamentalsError::NotFound(_))
+        ));
+    }
+}
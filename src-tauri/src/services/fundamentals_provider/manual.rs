@@ -15,6 +15,7 @@ use async_trait::async_trait;
 
 use crate::ibkr::types::FundamentalData;
 use crate::services::manual_fundamentals_store::ManualFundamentalsStore;
+use crate::utils::symbols;
 
 use super::{FundamentalsError, FundamentalsProvider};
 
@@ -31,7 +32,7 @@ impl ManualFundamentalsProvider {
 #[async_trait]
 impl FundamentalsProvider for ManualFundamentalsProvider {
     async fn fetch(&self, symbol: &str) -> Result<FundamentalData, FundamentalsError> {
-        let key = symbol.trim().to_uppercase();
+        let key = symbols::normalize(symbol);
         match self.store.get(&key).await {
             Ok(Some(row)) => Ok(row.data),
             Ok(None) => Err(FundamentalsError::NotFound(key)),
@@ -66,8 +67,15 @@ mod tests {
                 revenue: 100.0,
                 net_income: 10.0,
                 eps: 1.0,
+                split_adjustment: None,
             }],
             analyst_estimates: None,
+            data_quality: Vec::new(),
+            overrides: Vec::new(),
+            growth: None,
+            currency: None,
+            fx_conversion: None,
+            segments: Vec::new(),
             current_metrics: CurrentMetrics {
                 price: None,
                 pe_ratio: 12.5,
@@ -76,6 +84,7 @@ mod tests {
                 exchange: None,
                 market_cap: None,
                 dividend_yield: None,
+                short_interest: None,
             },
         }
     }
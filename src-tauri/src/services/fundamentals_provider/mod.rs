@@ -25,7 +25,11 @@ use crate::ibkr::types::FundamentalData;
 pub mod alpha_vantage;
 pub mod av_call_ledger;
 pub mod composite;
+pub mod currency;
+pub mod demo;
 pub mod manual;
+pub mod overrides;
+pub mod refreshing;
 pub mod test_support;
 
 #[cfg(test)]
@@ -0,0 +1,53 @@
+//! [`OverridingFundamentalsProvider`] — decorator that lays the
+//! operator's per-field corrections
+//! ([`crate::services::fundamentals_overrides`]) over whatever the inner
+//! provider returns.
+//!
+//! Wraps the whole fetch chain in `lib.rs` (only the projection
+//! re-run decorator sits outside it) so manual rows and AV data are
+//! corrected the same way. A store read failure is logged and the provider record
+//! passes through uncorrected — an override is a refinement, not a
+//! reason to fail the fetch.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::{info, warn};
+
+use crate::ibkr::types::FundamentalData;
+use crate::services::fundamentals_overrides::{self, FundamentalsOverridesStore};
+
+use super::{FundamentalsError, FundamentalsProvider};
+
+pub struct OverridingFundamentalsProvider {
+    inner: Arc<dyn FundamentalsProvider>,
+    store: Arc<FundamentalsOverridesStore>,
+}
+
+impl OverridingFundamentalsProvider {
+    pub fn new(
+        inner: Arc<dyn FundamentalsProvider>,
+        store: Arc<FundamentalsOverridesStore>,
+    ) -> Self {
+        Self { inner, store }
+    }
+}
+
+#[async_trait]
+impl FundamentalsProvider for OverridingFundamentalsProvider {
+    async fn fetch(&self, symbol: &str) -> Result<FundamentalData, FundamentalsError> {
+        let mut data = self.inner.fetch(symbol).await?;
+        match self.store.get(symbol).await {
+            Ok(Some(row)) => {
+                fundamentals_overrides::apply(&mut data, &row.overrides);
+                info!(
+                    "fundamentals(override): {} field(s) overridden for {symbol}",
+                    data.overrides.len()
+                );
+            }
+            Ok(None) => {}
+            Err(e) => warn!("fundamentals(override): store read failed for {symbol}: {e}"),
+        }
+        Ok(data)
+    }
+}
@@ -0,0 +1,41 @@
+//! [`RefreshingFundamentalsProvider`] — decorator that passes every
+//! fetched record to [`ProjectionRefresher`], so a new fiscal year
+//! re-runs the ticker's stored projection
+//! ([`crate::services::projection_refresh`]).
+//!
+//! Sits outermost in `lib.rs`, after overrides, so the re-run sees the
+//! same corrected record the caller does. A refresh failure is logged
+//! and the record passes through — re-running is a side effect, not a
+//! reason to fail the fetch.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use crate::ibkr::types::FundamentalData;
+use crate::services::projection_refresh::ProjectionRefresher;
+
+use super::{FundamentalsError, FundamentalsProvider};
+
+pub struct RefreshingFundamentalsProvider {
+    inner: Arc<dyn FundamentalsProvider>,
+    refresher: Arc<ProjectionRefresher>,
+}
+
+impl RefreshingFundamentalsProvider {
+    pub fn new(inner: Arc<dyn FundamentalsProvider>, refresher: Arc<ProjectionRefresher>) -> Self {
+        Self { inner, refresher }
+    }
+}
+
+#[async_trait]
+impl FundamentalsProvider for RefreshingFundamentalsProvider {
+    async fn fetch(&self, symbol: &str) -> Result<FundamentalData, FundamentalsError> {
+        let data = self.inner.fetch(symbol).await?;
+        if let Err(e) = self.refresher.refresh(std::slice::from_ref(&data)).await {
+            warn!("projection_refresh: {symbol} failed: {e}");
+        }
+        Ok(data)
+    }
+}
@@ -15,6 +15,7 @@ use std::sync::Mutex;
 use async_trait::async_trait;
 
 use crate::ibkr::types::FundamentalData;
+use crate::utils::symbols;
 
 use super::{FundamentalsError, FundamentalsProvider};
 
@@ -76,7 +77,7 @@ impl FundamentalsProvider for FakeFundamentalsProvider {
         {
             return Err(FundamentalsError::Other(msg));
         }
-        let key = symbol.trim().to_uppercase();
+        let key = symbols::normalize(symbol);
         let rows = self
             .rows
             .lock()
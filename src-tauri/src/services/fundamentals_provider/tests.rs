@@ -42,8 +42,15 @@ async fn fake_provider_returns_inserted_row() {
             revenue: 390.0,
             net_income: 100.0,
             eps: 6.5,
+            split_adjustment: None,
         }],
         analyst_estimates: None,
+        data_quality: Vec::new(),
+        overrides: Vec::new(),
+        growth: None,
+        currency: None,
+        fx_conversion: None,
+        segments: Vec::new(),
         current_metrics: CurrentMetrics {
             price: None,
             pe_ratio: 30.0,
@@ -52,6 +59,7 @@ async fn fake_provider_returns_inserted_row() {
             exchange: Some("NASDAQ".into()),
             market_cap: Some("3000000000000".into()),
             dividend_yield: Some(0.005),
+            short_interest: None,
         },
     };
     fake.insert("AAPL", data.clone());
@@ -102,6 +110,8 @@ impl AvHttp for CountingFakeAv {
             self.income.clone()
         } else if url.contains("function=EARNINGS") {
             self.earnings.clone()
+        } else if url.contains("function=SPLITS") {
+            json!({"symbol": "AAPL", "data": []})
         } else {
             return Err(AvHttpError::Status(format!("unexpected url: {url}")));
         })
@@ -127,7 +137,7 @@ async fn av_adapter_round_trips_canned_payloads_into_fundamental_data() {
         income: json!({
             "symbol": "AAPL",
             "annualReports": [
-                {"fiscalDateEnding": "2024-09-30", "totalRevenue": "390000000000", "netIncome": "100000000000"},
+                {"fiscalDateEnding": "2024-09-30", "totalRevenue": "390000000000", "netIncome": "100000000000", "reportedCurrency": "USD"},
                 {"fiscalDateEnding": "2023-09-30", "totalRevenue": "380000000000", "netIncome": "90000000000"}
             ]
         }),
@@ -151,10 +161,11 @@ async fn av_adapter_round_trips_canned_payloads_into_fundamental_data() {
     assert_eq!(data.symbol, "AAPL");
     assert!(!data.historical.is_empty(), "historical must be populated");
     assert_eq!(data.current_metrics.pe_ratio, 30.0);
+    assert_eq!(data.currency.as_deref(), Some("USD"));
     assert_eq!(
         counter.load(Ordering::SeqCst),
-        3,
-        "fan-out hits 3 endpoints"
+        4,
+        "fan-out hits 4 endpoints"
     );
 }
 
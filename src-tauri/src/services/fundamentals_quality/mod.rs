@@ -0,0 +1,95 @@
+//! Data-quality checks run on every [`FundamentalData`] before it
+//! reaches the UI or the projection engine.
+//!
+//! Upstream feeds occasionally hand back garbage — a restatement that
+//! multiplies revenue by 1000, a sign-flipped share count, a year that
+//! silently drops out of the income statement. Projections happily
+//! extrapolate any of those into a confident price target, so the
+//! checks here attach structured [`DataQualityWarning`]s to the record:
+//!
+//! - `revenue_jump` (warning): year-over-year revenue change beyond
+//!   [`MAX_REVENUE_CHANGE`] either way.
+//! - `negative_shares_outstanding` (error): no per-share figure can be
+//!   derived from it.
+//! - `missing_years` (warning): a gap in the fiscal-year series, which
+//!   skews CAGR-style growth math.
+//!
+//! Errors make `ProjectionService` refuse the record; warnings travel
+//! alongside the data in `FundamentalData::data_quality`.
+
+use crate::ibkr::types::{
+    DataQualityCode, DataQualitySeverity, DataQualityWarning, FundamentalData,
+};
+
+/// 400% — a 5× (or 1/5×) year-over-year revenue move.
+pub const MAX_REVENUE_CHANGE: f64 = 4.0;
+
+/// Every anomaly in `data`, in series order.
+pub fn validate(data: &FundamentalData) -> Vec<DataQualityWarning> {
+    let mut warnings = Vec::new();
+
+    if data.current_metrics.shares_outstanding < 0.0 {
+        warnings.push(DataQualityWarning {
+            code: DataQualityCode::NegativeSharesOutstanding,
+            severity: DataQualitySeverity::Error,
+            year: None,
+            message: format!(
+                "shares outstanding is negative ({}M)",
+                data.current_metrics.shares_outstanding
+            ),
+        });
+    }
+
+    let mut years: Vec<_> = data.historical.iter().collect();
+    years.sort_by_key(|h| h.year);
+    for pair in years.windows(2) {
+        let (prev, cur) = (pair[0], pair[1]);
+        if cur.year > prev.year + 1 {
+            let missing: Vec<String> = (prev.year + 1..cur.year).map(|y| y.to_string()).collect();
+            warnings.push(DataQualityWarning {
+                code: DataQualityCode::MissingYears,
+                severity: DataQualitySeverity::Warning,
+                year: Some(prev.year + 1),
+                message: format!("no financials for {}", missing.join(", ")),
+            });
+        }
+        if prev.revenue > 0.0 && cur.revenue >= 0.0 {
+            let change = cur.revenue / prev.revenue - 1.0;
+            let shrink = if cur.revenue > 0.0 {
+                prev.revenue / cur.revenue - 1.0
+            } else {
+                f64::INFINITY
+            };
+            if change > MAX_REVENUE_CHANGE || shrink > MAX_REVENUE_CHANGE {
+                warnings.push(DataQualityWarning {
+                    code: DataQualityCode::RevenueJump,
+                    severity: DataQualitySeverity::Warning,
+                    year: Some(cur.year),
+                    message: format!(
+                        "revenue moved {:+.0}% year over year ({:.2}B → {:.2}B)",
+                        change * 100.0,
+                        prev.revenue,
+                        cur.revenue
+                    ),
+                });
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Replace `data.data_quality` with a fresh [`validate`] pass.
+pub fn annotate(data: &mut FundamentalData) {
+    data.data_quality = validate(data);
+}
+
+/// First error-severity warning, if any — the reason to refuse a projection.
+pub fn blocking(warnings: &[DataQualityWarning]) -> Option<&DataQualityWarning> {
+    warnings
+        .iter()
+        .find(|w| w.severity == DataQualitySeverity::Error)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,91 @@
+use crate::ibkr::types::{
+    CurrentMetrics, DataQualityCode, DataQualitySeverity, FundamentalData, HistoricalFinancial,
+    ProjectionAssumptions,
+};
+use crate::services::projection_service::ProjectionService;
+
+use super::{annotate, blocking, validate};
+
+fn year(year: u32, revenue: f64) -> HistoricalFinancial {
+    HistoricalFinancial {
+        year,
+        revenue,
+        net_income: revenue * 0.1,
+        eps: 1.0,
+        split_adjustment: None,
+    }
+}
+
+fn data(historical: Vec<HistoricalFinancial>, shares_outstanding: f64) -> FundamentalData {
+    FundamentalData {
+        symbol: "TEST".to_string(),
+        historical,
+        analyst_estimates: None,
+        data_quality: Vec::new(),
+        overrides: Vec::new(),
+        growth: None,
+        currency: None,
+        fx_conversion: None,
+        segments: Vec::new(),
+        current_metrics: CurrentMetrics {
+            price: Some(50.0),
+            pe_ratio: 20.0,
+            shares_outstanding,
+            name: None,
+            exchange: None,
+            market_cap: None,
+            dividend_yield: None,
+            short_interest: None,
+        },
+    }
+}
+
+#[test]
+fn clean_series_has_no_findings() {
+    let d = data(
+        vec![year(2022, 10.0), year(2023, 12.0), year(2024, 15.0)],
+        100.0,
+    );
+    assert!(validate(&d).is_empty());
+}
+
+#[test]
+fn flags_revenue_jumps_in_both_directions() {
+    let d = data(
+        vec![year(2022, 1.0), year(2023, 6.0), year(2024, 1.0)],
+        100.0,
+    );
+    let findings = validate(&d);
+    let jumps: Vec<_> = findings
+        .iter()
+        .filter(|w| w.code == DataQualityCode::RevenueJump)
+        .map(|w| w.year)
+        .collect();
+    assert_eq!(jumps, vec![Some(2023), Some(2024)]);
+    assert!(blocking(&findings).is_none(), "jumps only warn");
+}
+
+#[test]
+fn flags_gaps_in_the_year_series() {
+    let d = data(
+        vec![year(2024, 12.0), year(2020, 10.0), year(2021, 11.0)],
+        100.0,
+    );
+    let findings = validate(&d);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].code, DataQualityCode::MissingYears);
+    assert_eq!(findings[0].year, Some(2022));
+    assert!(findings[0].message.contains("2022, 2023"));
+}
+
+#[test]
+fn negative_shares_block_projections() {
+    let mut d = data(vec![year(2023, 10.0), year(2024, 12.0)], -5.0);
+    annotate(&mut d);
+    assert_eq!(d.data_quality.len(), 1);
+    assert_eq!(d.data_quality[0].severity, DataQualitySeverity::Error);
+
+    let err = ProjectionService::generate_projection_results(&d, &ProjectionAssumptions::default())
+        .expect_err("projection must refuse");
+    assert!(err.to_string().contains("data quality"));
+}
@@ -0,0 +1,158 @@
+//! [`AlphaVantageFxProvider`] — spot rates from Alpha Vantage's
+//! `CURRENCY_EXCHANGE_RATE` endpoint, daily closes from `FX_DAILY`.
+//!
+//! Shares the AV rate limiter with the fundamentals adapter (the free
+//! tier's 1 req/sec cap is account-wide). Rates are kept in memory for
+//! [`RATE_TTL`]: fundamentals are annual figures, so an intraday-stale
+//! spot rate is noise next to the restatement risk in the inputs. A
+//! daily series is kept as long; only its last close moves in a day.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use tracing::info;
+
+use crate::middleware::AlphaVantageRateLimiter;
+use crate::services::financial_data_service::AvHttp;
+
+use super::{FxError, FxHistory, FxRateProvider};
+
+pub const RATE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+const BASE_URL: &str = "https://www.alphavantage.co/query";
+
+/// Per `(from, to)` pair: the value and when it was fetched.
+type RateCache<T> = StdMutex<HashMap<(String, String), (T, Instant)>>;
+
+pub struct AlphaVantageFxProvider {
+    http: Arc<dyn AvHttp>,
+    api_key: String,
+    base_url: String,
+    rate_limiter: Option<Arc<AlphaVantageRateLimiter>>,
+    rates: RateCache<f64>,
+    daily: RateCache<Vec<(NaiveDate, f64)>>,
+}
+
+impl AlphaVantageFxProvider {
+    pub fn new(http: Arc<dyn AvHttp>, api_key: String) -> Self {
+        Self {
+            http,
+            api_key,
+            base_url: BASE_URL.to_string(),
+            rate_limiter: None,
+            rates: StdMutex::new(HashMap::new()),
+            daily: StdMutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_rate_limiter(mut self, limiter: Arc<AlphaVantageRateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    fn cached(&self, key: &(String, String)) -> Option<f64> {
+        let rates = self.rates.lock().unwrap_or_else(|e| e.into_inner());
+        rates
+            .get(key)
+            .filter(|(_, at)| at.elapsed() < RATE_TTL)
+            .map(|(rate, _)| *rate)
+    }
+
+    fn cached_daily(&self, key: &(String, String)) -> Option<Vec<(NaiveDate, f64)>> {
+        let daily = self.daily.lock().unwrap_or_else(|e| e.into_inner());
+        daily
+            .get(key)
+            .filter(|(_, at)| at.elapsed() < RATE_TTL)
+            .map(|(series, _)| series.clone())
+    }
+}
+
+#[async_trait]
+impl FxRateProvider for AlphaVantageFxProvider {
+    async fn rate(&self, from: &str, to: &str) -> Result<f64, FxError> {
+        let key = (from.to_uppercase(), to.to_uppercase());
+        if key.0 == key.1 {
+            return Ok(1.0);
+        }
+        if let Some(rate) = self.cached(&key) {
+            return Ok(rate);
+        }
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
+        }
+        let url = format!(
+            "{}?function=CURRENCY_EXCHANGE_RATE&from_currency={}&to_currency={}&apikey={}",
+            self.base_url, key.0, key.1, self.api_key
+        );
+        let json = self
+            .http
+            .fetch(&url)
+            .await
+            .map_err(|e| FxError::Upstream(e.to_string()))?;
+        let rate = json
+            .get("Realtime Currency Exchange Rate")
+            .and_then(|r| r.get("5. Exchange Rate"))
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<f64>().ok())
+            .filter(|r| r.is_finite() && *r > 0.0)
+            .ok_or_else(|| FxError::NoRate {
+                from: key.0.clone(),
+                to: key.1.clone(),
+            })?;
+        info!("fx: {}/{} = {rate}", key.0, key.1);
+        self.rates
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, (rate, Instant::now()));
+        Ok(rate)
+    }
+}
+
+#[async_trait]
+impl FxHistory for AlphaVantageFxProvider {
+    async fn daily_rates(&self, from: &str, to: &str) -> Result<Vec<(NaiveDate, f64)>, FxError> {
+        let key = (from.to_uppercase(), to.to_uppercase());
+        if let Some(series) = self.cached_daily(&key) {
+            return Ok(series);
+        }
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
+        }
+        let url = format!(
+            "{}?function=FX_DAILY&from_symbol={}&to_symbol={}&outputsize=full&apikey={}",
+            self.base_url, key.0, key.1, self.api_key
+        );
+        let json = self
+            .http
+            .fetch(&url)
+            .await
+            .map_err(|e| FxError::Upstream(e.to_string()))?;
+        let mut series: Vec<(NaiveDate, f64)> = json
+            .get("Time Series FX (Daily)")
+            .and_then(|s| s.as_object())
+            .into_iter()
+            .flatten()
+            .filter_map(|(date, bar)| {
+                let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+                let close = bar.get("4. close")?.as_str()?.parse::<f64>().ok()?;
+                (close.is_finite() && close > 0.0).then_some((date, close))
+            })
+            .collect();
+        if series.is_empty() {
+            return Err(FxError::NoRate {
+                from: key.0,
+                to: key.1,
+            });
+        }
+        series.sort_by_key(|(date, _)| *date);
+        info!("fx: {}/{} daily, {} closes", key.0, key.1, series.len());
+        self.daily
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, (series.clone(), Instant::now()));
+        Ok(series)
+    }
+}
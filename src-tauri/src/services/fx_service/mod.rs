@@ -0,0 +1,115 @@
+//! Currency conversion for fundamentals.
+//!
+//! Alpha Vantage reports non-US filers in their reporting currency
+//! (TSM in TWD, ASML in EUR) while the projection engine and the UI
+//! assume one currency. [`FundamentalData::currency`]
+//! carries the ISO code of a record's monetary figures; [`convert`]
+//! rescales a record into the configured base currency
+//! ([`FxConfig::base_currency`]) and records the rate it used in
+//! `FundamentalData::fx_conversion`.
+//!
+//! Rates come from an [`FxRateProvider`]; production wires
+//! [`alpha_vantage::AlphaVantageFxProvider`]. The conversion itself runs
+//! in the `fundamentals_provider::currency` decorator so every consumer
+//! sees the same numbers.
+//!
+//! The same provider serves daily closing rates ([`FxHistory`]), which
+//! `services::benchmarks` uses to split a foreign holding's return into
+//! its local price and currency parts.
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ibkr::types::{FundamentalData, FxConversion};
+
+pub mod alpha_vantage;
+
+#[cfg(test)]
+mod tests;
+
+/// Currency assumed when a record doesn't say.
+pub const DEFAULT_CURRENCY: &str = "USD";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FxConfig {
+    /// ISO 4217 code every fundamentals record is converted into.
+    #[serde(default = "default_base_currency")]
+    pub base_currency: String,
+}
+
+fn default_base_currency() -> String {
+    DEFAULT_CURRENCY.to_string()
+}
+
+impl Default for FxConfig {
+    fn default() -> Self {
+        Self {
+            base_currency: default_base_currency(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FxError {
+    #[error("fx upstream: {0}")]
+    Upstream(String),
+    #[error("no {from}/{to} rate available")]
+    NoRate { from: String, to: String },
+}
+
+/// Source of spot rates: units of `to` per one unit of `from`.
+#[async_trait]
+pub trait FxRateProvider: Send + Sync {
+    async fn rate(&self, from: &str, to: &str) -> Result<f64, FxError>;
+}
+
+/// Source of daily closing rates, in the same units as
+/// [`FxRateProvider::rate`].
+#[async_trait]
+pub trait FxHistory: Send + Sync {
+    /// `(date, rate)`, oldest first.
+    async fn daily_rates(&self, from: &str, to: &str) -> Result<Vec<(NaiveDate, f64)>, FxError>;
+}
+
+/// `true` for a three-letter ISO 4217-shaped code.
+pub fn is_currency_code(code: &str) -> bool {
+    code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// The currency `data`'s monetary figures are in.
+pub fn currency_of(data: &FundamentalData) -> String {
+    data.currency
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .unwrap_or(DEFAULT_CURRENCY)
+        .to_uppercase()
+}
+
+/// Rescale every monetary figure in `data` by `rate` and relabel it as
+/// `base`. Share counts and ratios (P/E, dividend yield) are currency
+/// neutral and left alone; `market_cap` is a preformatted upstream
+/// string and is left as reported.
+pub fn convert(data: &mut FundamentalData, base: &str, rate: f64) {
+    let from = currency_of(data);
+    for h in &mut data.historical {
+        h.revenue *= rate;
+        h.net_income *= rate;
+        h.eps *= rate;
+    }
+    for s in data.segments.iter_mut().flat_map(|s| s.revenue.iter_mut()) {
+        s.revenue *= rate;
+    }
+    if let Some(estimates) = data.analyst_estimates.as_mut() {
+        for e in estimates.revenue.iter_mut().chain(estimates.eps.iter_mut()) {
+            e.estimate *= rate;
+        }
+    }
+    if let Some(price) = data.current_metrics.price.as_mut() {
+        *price *= rate;
+    }
+    data.currency = Some(base.to_uppercase());
+    data.fx_conversion = Some(FxConversion { from, rate });
+}
@@ -0,0 +1,192 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::ibkr::types::{
+    AnalystEstimate, AnalystEstimates, CurrentMetrics, FundamentalData, HistoricalFinancial,
+};
+use crate::services::financial_data_service::{AvHttp, AvHttpError};
+use crate::services::fundamentals_provider::currency::CurrencyConvertingFundamentalsProvider;
+use crate::services::fundamentals_provider::test_support::FakeFundamentalsProvider;
+use crate::services::fundamentals_provider::FundamentalsProvider;
+
+use super::alpha_vantage::AlphaVantageFxProvider;
+use super::{convert, FxError, FxHistory, FxRateProvider};
+
+fn record(currency: Option<&str>) -> FundamentalData {
+    FundamentalData {
+        symbol: "TSM".to_string(),
+        historical: vec![HistoricalFinancial {
+            year: 2024,
+            revenue: 2_900.0,
+            net_income: 1_170.0,
+            eps: 45.0,
+            split_adjustment: None,
+        }],
+        analyst_estimates: Some(AnalystEstimates {
+            revenue: vec![AnalystEstimate {
+                year: 2025,
+                estimate: 3_600.0,
+            }],
+            eps: vec![AnalystEstimate {
+                year: 2025,
+                estimate: 58.0,
+            }],
+        }),
+        data_quality: Vec::new(),
+        overrides: Vec::new(),
+        growth: None,
+        currency: currency.map(str::to_string),
+        fx_conversion: None,
+        segments: Vec::new(),
+        current_metrics: CurrentMetrics {
+            price: Some(1_000.0),
+            pe_ratio: 22.0,
+            shares_outstanding: 5_186.0,
+            name: None,
+            exchange: None,
+            market_cap: None,
+            dividend_yield: Some(0.015),
+            short_interest: None,
+        },
+    }
+}
+
+struct FixedRate(Option<f64>);
+
+#[async_trait]
+impl FxRateProvider for FixedRate {
+    async fn rate(&self, from: &str, to: &str) -> Result<f64, FxError> {
+        self.0.ok_or_else(|| FxError::NoRate {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+}
+
+fn decorated(data: FundamentalData, rate: Option<f64>) -> CurrencyConvertingFundamentalsProvider {
+    let fake = FakeFundamentalsProvider::new();
+    fake.insert("TSM", data);
+    CurrencyConvertingFundamentalsProvider::new(Arc::new(fake), Arc::new(FixedRate(rate)), "usd")
+}
+
+#[test]
+fn convert_scales_money_and_leaves_counts_and_ratios() {
+    let mut data = record(Some("TWD"));
+    convert(&mut data, "USD", 0.03);
+
+    let h = &data.historical[0];
+    assert!((h.revenue - 87.0).abs() < 1e-9);
+    assert!((h.eps - 1.35).abs() < 1e-9);
+    let est = data.analyst_estimates.as_ref().unwrap();
+    assert!((est.eps[0].estimate - 1.74).abs() < 1e-9);
+    assert!((data.current_metrics.price.unwrap() - 30.0).abs() < 1e-9);
+    assert_eq!(data.current_metrics.shares_outstanding, 5_186.0);
+    assert_eq!(data.current_metrics.pe_ratio, 22.0);
+
+    assert_eq!(data.currency.as_deref(), Some("USD"));
+    let fx = data.fx_conversion.unwrap();
+    assert_eq!((fx.from.as_str(), fx.rate), ("TWD", 0.03));
+}
+
+#[tokio::test]
+async fn decorator_converts_foreign_records_only() {
+    let foreign = decorated(record(Some("TWD")), Some(0.03))
+        .fetch("TSM")
+        .await
+        .unwrap();
+    assert!(foreign.fx_conversion.is_some());
+
+    // Unlabelled records are USD already: no rate lookup, just a label.
+    let domestic = decorated(record(None), None).fetch("TSM").await.unwrap();
+    assert_eq!(domestic.currency.as_deref(), Some("USD"));
+    assert!(domestic.fx_conversion.is_none());
+    assert_eq!(domestic.historical[0].revenue, 2_900.0);
+}
+
+#[tokio::test]
+async fn missing_rate_passes_record_through_in_reporting_currency() {
+    let data = decorated(record(Some("twd")), None)
+        .fetch("TSM")
+        .await
+        .unwrap();
+    assert_eq!(data.currency.as_deref(), Some("TWD"));
+    assert!(data.fx_conversion.is_none());
+    assert_eq!(data.historical[0].revenue, 2_900.0);
+}
+
+struct CountingFx {
+    calls: AtomicUsize,
+    body: Value,
+}
+
+#[async_trait]
+impl AvHttp for CountingFx {
+    async fn fetch(&self, url: &str) -> Result<Value, AvHttpError> {
+        assert!(url.contains("function=CURRENCY_EXCHANGE_RATE"), "{url}");
+        assert!(url.contains("from_currency=EUR&to_currency=USD"), "{url}");
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(self.body.clone())
+    }
+}
+
+#[tokio::test]
+async fn av_provider_parses_and_caches_rate() {
+    let http = Arc::new(CountingFx {
+        calls: AtomicUsize::new(0),
+        body: json!({
+            "Realtime Currency Exchange Rate": {
+                "1. From_Currency Code": "EUR",
+                "3. To_Currency Code": "USD",
+                "5. Exchange Rate": "1.08500000"
+            }
+        }),
+    });
+    let fx = AlphaVantageFxProvider::new(Arc::clone(&http) as Arc<dyn AvHttp>, "KEY".into());
+
+    assert_eq!(fx.rate("eur", "usd").await.unwrap(), 1.085);
+    assert_eq!(fx.rate("EUR", "USD").await.unwrap(), 1.085);
+    assert_eq!(fx.rate("USD", "USD").await.unwrap(), 1.0);
+    assert_eq!(http.calls.load(Ordering::SeqCst), 1);
+}
+
+struct DailyFx(Value);
+
+#[async_trait]
+impl AvHttp for DailyFx {
+    async fn fetch(&self, url: &str) -> Result<Value, AvHttpError> {
+        assert!(url.contains("function=FX_DAILY"), "{url}");
+        assert!(url.contains("from_symbol=EUR&to_symbol=USD"), "{url}");
+        Ok(self.0.clone())
+    }
+}
+
+#[tokio::test]
+async fn av_provider_reads_daily_closes_oldest_first() {
+    let fx = AlphaVantageFxProvider::new(
+        Arc::new(DailyFx(json!({
+            "Time Series FX (Daily)": {
+                "2026-03-03": { "1. open": "1.0800", "4. close": "1.0900" },
+                "2026-03-02": { "1. open": "1.0750", "4. close": "1.0800" },
+                "2026-02-27": { "4. close": "n/a" }
+            }
+        }))),
+        "KEY".into(),
+    );
+    let day = |d| chrono::NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+    assert_eq!(
+        fx.daily_rates("eur", "usd").await.unwrap(),
+        [(day(2), 1.08), (day(3), 1.09)]
+    );
+
+    let empty = AlphaVantageFxProvider::new(
+        Arc::new(DailyFx(json!({ "Information": "rate limited" }))),
+        "KEY".into(),
+    );
+    assert!(matches!(
+        empty.daily_rates("EUR", "USD").await,
+        Err(FxError::NoRate { .. })
+    ));
+}
@@ -0,0 +1,323 @@
+//! Exposure hedging suggestions.
+//!
+//! Reads the account's beta-weighted exposure to SPY
+//! (`portfolio_risk::beta`), works out how many SPY-dollars to take
+//! off to bring it down to `target_exposure_pct` of today's level, and
+//! sizes three ways to do it:
+//!
+//! - **SPY puts** near `put_dte` days out, `put_otm_pct` below spot,
+//!   sized by the put's model delta — a buy-to-open ticket.
+//! - **Short SPY** shares — a sell ticket (needs a borrow, like any
+//!   short).
+//! - **Micro E-mini S&P (MES)** futures, with the S&P 500 taken as
+//!   10 × SPY. Futures aren't on the order form, so this one carries
+//!   no ticket; enter it in TWS.
+//!
+//! Suggestions only: tickets go back to the trader, who places them
+//! through `ibkr_place_order` after review (Hard Invariant 1). Only a
+//! net long exposure is hedged.
+
+use std::sync::Arc;
+
+use chrono::{NaiveDate, Utc};
+use chrono_tz::America::New_York;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::{OptionContract, OptionRight, OrderAction, OrderRequest, OrderType};
+use crate::services::historical_data_service::DailyBars;
+use crate::services::option_income::OptionMarket;
+use crate::services::portfolio_risk::beta::{self, BetaExposure};
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+
+#[cfg(test)]
+mod tests;
+
+pub const BENCHMARK: &str = "SPY";
+/// MES: $5 × the S&P 500 index.
+const MES_MULTIPLIER: f64 = 5.0;
+/// S&P 500 index level per SPY share, near enough for sizing.
+const SPX_PER_SPY: f64 = 10.0;
+/// Daily bars read for beta (≈ one year of returns).
+const CLOSES_SESSIONS: usize = 280;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HedgeRequest {
+    /// Percent of today's beta-weighted exposure to keep; 0 hedges
+    /// it all.
+    #[serde(default)]
+    pub target_exposure_pct: f64,
+    #[serde(default = "default_put_dte")]
+    pub put_dte: i64,
+    #[serde(default = "default_put_otm_pct")]
+    pub put_otm_pct: f64,
+    /// Default: the first managed account.
+    #[serde(default)]
+    pub account: Option<String>,
+}
+
+fn default_put_dte() -> i64 {
+    45
+}
+
+fn default_put_otm_pct() -> f64 {
+    5.0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HedgeKind {
+    SpyPuts,
+    ShortSpy,
+    MicroFutures,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HedgeSuggestion {
+    pub kind: HedgeKind,
+    /// Contracts or shares.
+    pub quantity: f64,
+    /// SPY-dollars the hedge offsets at today's prices.
+    pub hedged_dollars: f64,
+    /// Beta-weighted exposure left after the hedge.
+    pub residual_exposure: f64,
+    /// Premium for puts; 0 otherwise.
+    pub cost: f64,
+    /// `None` for futures (not on the order form).
+    pub order: Option<OrderRequest>,
+    pub note: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HedgePlan {
+    pub account: String,
+    pub benchmark_price: f64,
+    pub exposure: BetaExposure,
+    pub target_exposure: f64,
+    /// SPY-dollars to take off; 0 when already at or below target.
+    pub hedge_dollars: f64,
+    pub suggestions: Vec<HedgeSuggestion>,
+}
+
+#[derive(Error, Debug)]
+pub enum HedgingError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+    #[error("{0}")]
+    Invalid(String),
+}
+
+pub struct HedgingService {
+    closes: Arc<dyn DailyBars>,
+    market: Arc<dyn OptionMarket>,
+    positions: Arc<dyn OpenPositionsSource>,
+    accounts: Arc<dyn AccountSource>,
+}
+
+impl HedgingService {
+    pub fn new(
+        closes: Arc<dyn DailyBars>,
+        market: Arc<dyn OptionMarket>,
+        positions: Arc<dyn OpenPositionsSource>,
+        accounts: Arc<dyn AccountSource>,
+    ) -> Self {
+        Self {
+            closes,
+            market,
+            positions,
+            accounts,
+        }
+    }
+
+    pub async fn suggest(&self, req: &HedgeRequest) -> Result<HedgePlan, HedgingError> {
+        let today = Utc::now().with_timezone(&New_York).date_naive();
+        self.suggest_on(req, today).await
+    }
+
+    async fn suggest_on(
+        &self,
+        req: &HedgeRequest,
+        today: NaiveDate,
+    ) -> Result<HedgePlan, HedgingError> {
+        if !(0.0..=100.0).contains(&req.target_exposure_pct) || req.put_dte <= 0 {
+            return Err(HedgingError::Invalid(
+                "target must be 0-100% and put DTE positive".into(),
+            ));
+        }
+        let account = match &req.account {
+            Some(a) if !a.trim().is_empty() => a.clone(),
+            _ => self.accounts.current_account().await?,
+        };
+        let exposure = self.exposure(&account).await?;
+        let price = self.market.underlying_price(BENCHMARK).await?;
+        let target = exposure.beta_weighted.max(0.0) * req.target_exposure_pct / 100.0;
+        let hedge_dollars = (exposure.beta_weighted - target).max(0.0);
+
+        let mut suggestions = Vec::new();
+        if hedge_dollars > 0.0 {
+            if let Some(puts) = self
+                .put_hedge(req, today, price, &exposure, hedge_dollars)
+                .await?
+            {
+                suggestions.push(puts);
+            }
+            suggestions.push(short_spy(price, &exposure, hedge_dollars));
+            suggestions.push(micro_futures(price, &exposure, hedge_dollars));
+            suggestions.retain(|s| s.quantity > 0.0);
+        }
+        Ok(HedgePlan {
+            account,
+            benchmark_price: price,
+            exposure,
+            target_exposure: target,
+            hedge_dollars,
+            suggestions,
+        })
+    }
+
+    async fn exposure(&self, account: &str) -> Result<BetaExposure, IbkrError> {
+        let bench = self.closes.daily_closes(BENCHMARK, CLOSES_SESSIONS).await?;
+        let mut rows = Vec::new();
+        for position in self.positions.list_open(account).await? {
+            let measured = if position.contract_type != "STK" {
+                None
+            } else if position.symbol.eq_ignore_ascii_case(BENCHMARK) {
+                Some(1.0)
+            } else {
+                // A symbol without bars is unmeasured, not an error.
+                match self
+                    .closes
+                    .daily_closes(&position.symbol, CLOSES_SESSIONS)
+                    .await
+                {
+                    Ok(closes) => beta::beta(&closes, &bench),
+                    Err(_) => None,
+                }
+            };
+            rows.push((position, measured));
+        }
+        Ok(beta::beta_weighted(BENCHMARK, &rows))
+    }
+
+    /// `None` when no put near the target expiry and strike prices.
+    async fn put_hedge(
+        &self,
+        req: &HedgeRequest,
+        today: NaiveDate,
+        price: f64,
+        exposure: &BetaExposure,
+        hedge_dollars: f64,
+    ) -> Result<Option<HedgeSuggestion>, IbkrError> {
+        let chain = self.market.option_chain(BENCHMARK).await?;
+        let Some((expiry, dte)) = chain
+            .expirations
+            .iter()
+            .filter_map(|e| {
+                let date = NaiveDate::parse_from_str(e.trim(), "%Y%m%d").ok()?;
+                Some((e.trim().to_string(), (date - today).num_days()))
+            })
+            .filter(|(_, dte)| *dte > 0)
+            .min_by_key(|(_, dte)| (dte - req.put_dte).abs())
+        else {
+            return Ok(None);
+        };
+        let wanted = price * (1.0 - req.put_otm_pct / 100.0);
+        let Some(strike) = chain
+            .strikes
+            .iter()
+            .copied()
+            .min_by(|a, b| (a - wanted).abs().total_cmp(&(b - wanted).abs()))
+        else {
+            return Ok(None);
+        };
+        let multiplier = chain.multiplier.trim().parse::<f64>().unwrap_or(100.0);
+        let option = OptionContract {
+            symbol: BENCHMARK.to_string(),
+            expiry,
+            strike,
+            right: OptionRight::Put,
+            multiplier: Some(chain.multiplier.clone()).filter(|m| !m.is_empty()),
+            currency: None,
+        };
+        let greeks = self.market.option_greeks(&option).await?;
+        let (Some(delta), Some(premium)) = (greeks.delta, greeks.option_price) else {
+            return Ok(None);
+        };
+        let per_contract = delta.abs() * multiplier * price;
+        if per_contract <= 0.0 {
+            return Ok(None);
+        }
+        // Round up, but not for float noise on an exact fit.
+        let contracts = (hedge_dollars / per_contract - 1e-6).ceil();
+        let hedged = contracts * per_contract;
+        let note = format!(
+            "{contracts} × SPY {} {strike}P ({dte} DTE, delta {delta:.2}); \
+             delta drifts, re-check as SPY moves",
+            option.expiry
+        );
+        Ok(Some(HedgeSuggestion {
+            kind: HedgeKind::SpyPuts,
+            quantity: contracts,
+            hedged_dollars: hedged,
+            residual_exposure: exposure.beta_weighted - hedged,
+            cost: contracts * premium * multiplier,
+            order: Some(OrderRequest {
+                symbol: BENCHMARK.to_string(),
+                action: OrderAction::Buy,
+                quantity: contracts,
+                order_type: OrderType::Limit,
+                price: Some((premium * 100.0).round() / 100.0),
+                start_at: None,
+                algo: None,
+                route: None,
+                option: Some(option),
+            }),
+            note,
+        }))
+    }
+}
+
+fn short_spy(price: f64, exposure: &BetaExposure, hedge_dollars: f64) -> HedgeSuggestion {
+    let shares = (hedge_dollars / price).round();
+    HedgeSuggestion {
+        kind: HedgeKind::ShortSpy,
+        quantity: shares,
+        hedged_dollars: shares * price,
+        residual_exposure: exposure.beta_weighted - shares * price,
+        cost: 0.0,
+        order: Some(OrderRequest {
+            symbol: BENCHMARK.to_string(),
+            action: OrderAction::Sell,
+            quantity: shares,
+            order_type: OrderType::Limit,
+            price: Some((price * 100.0).round() / 100.0),
+            start_at: None,
+            algo: None,
+            route: None,
+            option: None,
+        }),
+        note: format!("sell {shares} SPY short; needs a borrow and margin"),
+    }
+}
+
+fn micro_futures(price: f64, exposure: &BetaExposure, hedge_dollars: f64) -> HedgeSuggestion {
+    let notional = price * SPX_PER_SPY * MES_MULTIPLIER;
+    let contracts = (hedge_dollars / notional).round();
+    HedgeSuggestion {
+        kind: HedgeKind::MicroFutures,
+        quantity: contracts,
+        hedged_dollars: contracts * notional,
+        residual_exposure: exposure.beta_weighted - contracts * notional,
+        cost: 0.0,
+        order: None,
+        note: format!(
+            "sell {contracts} MES front-month (≈ ${notional:.0} notional each); \
+             place in TWS, futures aren't on the order form"
+        ),
+    }
+}
@@ -0,0 +1,195 @@
+use async_trait::async_trait;
+
+use super::*;
+use crate::ibkr::types::{HistoricalBar, OptionChain, OptionGreeks, Position};
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// 120 sessions; AAPL moves exactly twice SPY every day. `NOBARS`
+/// has no history.
+struct StubCloses;
+
+#[async_trait]
+impl DailyBars for StubCloses {
+    async fn daily_bars(
+        &self,
+        symbol: &str,
+        _sessions: usize,
+    ) -> Result<Vec<HistoricalBar>, IbkrError> {
+        let scale = match symbol {
+            "SPY" => 1.0,
+            "AAPL" => 2.0,
+            _ => return Err(IbkrError::Timeout(1000)),
+        };
+        let mut close = 100.0;
+        Ok((0..120)
+            .map(|i| {
+                close *= 1.0 + scale * 0.01 * (i as f64).sin();
+                bar(format!("day{i:03}"), close)
+            })
+            .collect())
+    }
+}
+
+fn bar(time: String, close: f64) -> HistoricalBar {
+    HistoricalBar {
+        time,
+        open: close,
+        high: close,
+        low: close,
+        close,
+        volume: 0,
+        wap: close,
+        count: 0,
+    }
+}
+
+/// SPY at 500; every put is -0.30 delta at 6.00.
+struct StubMarket;
+
+#[async_trait]
+impl OptionMarket for StubMarket {
+    async fn option_chain(&self, _symbol: &str) -> Result<OptionChain, IbkrError> {
+        Ok(OptionChain {
+            symbol: "SPY".to_string(),
+            multiplier: "100".to_string(),
+            expirations: vec![
+                "20261120".to_string(),
+                "20261127".to_string(),
+                "20261218".to_string(),
+            ],
+            strikes: vec![470.0, 475.0, 480.0],
+        })
+    }
+
+    async fn option_greeks(&self, _option: &OptionContract) -> Result<OptionGreeks, IbkrError> {
+        Ok(OptionGreeks {
+            delta: Some(-0.30),
+            option_price: Some(6.0),
+            ..Default::default()
+        })
+    }
+
+    async fn underlying_price(&self, _symbol: &str) -> Result<f64, IbkrError> {
+        Ok(500.0)
+    }
+}
+
+fn pos(symbol: &str, contract_type: &str, market_value: f64) -> Position {
+    Position {
+        symbol: symbol.to_string(),
+        contract_type: contract_type.to_string(),
+        position: 1.0,
+        market_value,
+        ..Default::default()
+    }
+}
+
+fn service(positions: Vec<Position>) -> HedgingService {
+    HedgingService::new(
+        Arc::new(StubCloses),
+        Arc::new(StubMarket),
+        Arc::new(StubPositions(positions)),
+        Arc::new(FixedAccount),
+    )
+}
+
+fn request(target_exposure_pct: f64) -> HedgeRequest {
+    HedgeRequest {
+        target_exposure_pct,
+        put_dte: 45,
+        put_otm_pct: 5.0,
+        account: None,
+    }
+}
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, 14).unwrap()
+}
+
+fn book() -> Vec<Position> {
+    vec![
+        pos("AAPL", "STK", 50_000.0),
+        pos("SPY", "STK", 20_000.0),
+        pos("NOBARS", "STK", 10_000.0),
+        pos("QQQ", "OPT", 1_000.0),
+    ]
+}
+
+#[tokio::test]
+async fn sizes_each_hedge_to_the_exposure_above_target() {
+    let plan = service(book())
+        .suggest_on(&request(25.0), today())
+        .await
+        .unwrap();
+
+    let betas: Vec<_> = plan
+        .exposure
+        .positions
+        .iter()
+        .map(|p| (p.symbol.as_str(), (p.beta * 100.0).round() / 100.0))
+        .collect();
+    assert_eq!(betas, vec![("AAPL", 2.0), ("SPY", 1.0)]);
+    assert_eq!(plan.exposure.unmeasured, vec!["NOBARS", "QQQ"]);
+    assert!((plan.exposure.beta_weighted - 120_000.0).abs() < 1.0);
+    assert!((plan.hedge_dollars - 90_000.0).abs() < 1.0);
+
+    let kinds: Vec<_> = plan.suggestions.iter().map(|s| s.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            HedgeKind::SpyPuts,
+            HedgeKind::ShortSpy,
+            HedgeKind::MicroFutures
+        ]
+    );
+
+    // 90k / (0.30 × 100 × 500) = 6 puts, nearest 45 DTE and 5% OTM.
+    let puts = &plan.suggestions[0];
+    assert_eq!(puts.quantity, 6.0);
+    assert!((puts.cost - 3_600.0).abs() < 1e-9);
+    let order = puts.order.as_ref().unwrap();
+    assert!(matches!(order.action, OrderAction::Buy));
+    let option = order.option.as_ref().unwrap();
+    assert_eq!((option.expiry.as_str(), option.strike), ("20261127", 475.0));
+
+    assert_eq!(plan.suggestions[1].quantity, 180.0);
+    // 90k / 25k per MES rounds to 4.
+    let futures = &plan.suggestions[2];
+    assert_eq!(futures.quantity, 4.0);
+    assert!(futures.order.is_none());
+    assert!((futures.residual_exposure - 20_000.0).abs() < 1.0);
+}
+
+#[tokio::test]
+async fn nothing_to_hedge_at_target_or_when_net_short() {
+    let plan = service(book())
+        .suggest_on(&request(100.0), today())
+        .await
+        .unwrap();
+    assert_eq!(plan.hedge_dollars, 0.0);
+    assert!(plan.suggestions.is_empty());
+
+    let plan = service(vec![pos("SPY", "STK", -30_000.0)])
+        .suggest_on(&request(0.0), today())
+        .await
+        .unwrap();
+    assert_eq!(plan.hedge_dollars, 0.0);
+    assert!(plan.suggestions.is_empty());
+}
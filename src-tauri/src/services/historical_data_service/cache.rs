@@ -3,33 +3,36 @@
 //! The service module owns the orchestration (rate limiting, gap-fill,
 //! merge); this module owns the SQL.
 
-use chrono::{TimeZone, Utc};
+use chrono::{NaiveDate, TimeZone, Utc};
 use rusqlite;
 
 use crate::ibkr::error::{IbkrError, Result as IbkrResult};
-use crate::ibkr::types::historical::{parse_ibkr_time, BarSize, HistoricalBar};
+use crate::ibkr::types::historical::{parse_ibkr_time, HistoricalBar};
 use crate::storage::error::StorageError;
 use crate::storage::Db;
 
+use super::memory::BarKey;
+use super::ranges::DaySpan;
+
 pub(super) async fn read_cache(
     db: &Db,
-    symbol: &str,
-    bar_size: BarSize,
+    key: &BarKey,
     start_unix: i64,
     end_unix: i64,
 ) -> IbkrResult<Vec<HistoricalBar>> {
-    let symbol = symbol.to_string();
-    let bar_size_str = bar_size.as_str().to_string();
+    let symbol = key.symbol.clone();
+    let bar_size_str = key.bar_size.as_str();
+    let what_to_show = key.what_to_show.as_str();
     db.with_conn(move |conn| {
         let mut stmt = conn.prepare(
             "SELECT bar_time, open, high, low, close, volume, wap \
              FROM bars_cache \
-             WHERE symbol = ?1 AND bar_size = ?2 \
-               AND bar_time >= ?3 AND bar_time <= ?4 \
+             WHERE symbol = ?1 AND bar_size = ?2 AND what_to_show = ?3 \
+               AND bar_time >= ?4 AND bar_time <= ?5 \
              ORDER BY bar_time ASC",
         )?;
         let rows = stmt.query_map(
-            rusqlite::params![symbol, bar_size_str, start_unix, end_unix],
+            rusqlite::params![symbol, bar_size_str, what_to_show, start_unix, end_unix],
             |row| {
                 let ts: i64 = row.get(0)?;
                 let open: f64 = row.get(1)?;
@@ -60,17 +63,13 @@ pub(super) async fn read_cache(
     .map_err(map_storage_err)
 }
 
-pub(super) async fn write_cache(
-    db: &Db,
-    symbol: &str,
-    bar_size: BarSize,
-    bars: &[HistoricalBar],
-) -> IbkrResult<()> {
+pub(super) async fn write_cache(db: &Db, key: &BarKey, bars: &[HistoricalBar]) -> IbkrResult<()> {
     if bars.is_empty() {
         return Ok(());
     }
-    let symbol = symbol.to_string();
-    let bar_size_str = bar_size.as_str().to_string();
+    let symbol = key.symbol.clone();
+    let bar_size_str = key.bar_size.as_str();
+    let what_to_show = key.what_to_show.as_str();
     let bars_owned: Vec<HistoricalBar> = bars.to_vec();
 
     db.with_conn(move |conn| {
@@ -78,8 +77,8 @@ pub(super) async fn write_cache(
         {
             let mut stmt = tx.prepare(
                 "INSERT OR REPLACE INTO bars_cache \
-                 (symbol, bar_size, bar_time, open, high, low, close, volume, wap) \
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                 (symbol, bar_size, what_to_show, bar_time, open, high, low, close, volume, wap) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             )?;
             for bar in &bars_owned {
                 let ts = parse_ibkr_time(&bar.time).map_err(|e| {
@@ -88,6 +87,7 @@ pub(super) async fn write_cache(
                 stmt.execute(rusqlite::params![
                     symbol,
                     bar_size_str,
+                    what_to_show,
                     ts,
                     bar.open,
                     bar.high,
@@ -105,6 +105,73 @@ pub(super) async fn write_cache(
     .map_err(map_storage_err)
 }
 
+/// Recorded fetch coverage for `key`, ascending.
+pub(super) async fn read_coverage(db: &Db, key: &BarKey) -> IbkrResult<Vec<DaySpan>> {
+    let symbol = key.symbol.clone();
+    let bar_size_str = key.bar_size.as_str();
+    let what_to_show = key.what_to_show.as_str();
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT start_day, end_day FROM bars_cache_coverage \
+             WHERE symbol = ?1 AND bar_size = ?2 AND what_to_show = ?3 \
+             ORDER BY start_day ASC",
+        )?;
+        let rows = stmt.query_map(
+            rusqlite::params![symbol, bar_size_str, what_to_show],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )?;
+        let mut out = Vec::new();
+        for row in rows {
+            let (start, end) = row?;
+            out.push(DaySpan::new(parse_day(&start)?, parse_day(&end)?));
+        }
+        Ok(out)
+    })
+    .await
+    .map_err(map_storage_err)
+}
+
+/// Replace `key`'s coverage rows with `spans` (already merged).
+pub(super) async fn write_coverage(db: &Db, key: &BarKey, spans: &[DaySpan]) -> IbkrResult<()> {
+    let symbol = key.symbol.clone();
+    let bar_size_str = key.bar_size.as_str();
+    let what_to_show = key.what_to_show.as_str();
+    let spans = spans.to_vec();
+    db.with_conn(move |conn| {
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM bars_cache_coverage \
+             WHERE symbol = ?1 AND bar_size = ?2 AND what_to_show = ?3",
+            rusqlite::params![symbol, bar_size_str, what_to_show],
+        )?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO bars_cache_coverage \
+                 (symbol, bar_size, what_to_show, start_day, end_day) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            for span in &spans {
+                stmt.execute(rusqlite::params![
+                    symbol,
+                    bar_size_str,
+                    what_to_show,
+                    span.start.to_string(),
+                    span.end.to_string(),
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    })
+    .await
+    .map_err(map_storage_err)
+}
+
+fn parse_day(s: &str) -> Result<NaiveDate, StorageError> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|e| StorageError::Migration(format!("bad coverage day '{s}': {e}")))
+}
+
 fn map_storage_err(e: StorageError) -> IbkrError {
     IbkrError::RequestFailed(format!("storage: {e}"))
 }
@@ -0,0 +1,138 @@
+//! In-process layer in front of the SQLite bars cache.
+//!
+//! Repeated chart loads for the same symbol re-read the same window; this
+//! keeps the resolved bars per (symbol, bar size, what-to-show) so a hit
+//! skips both SQLite and the gap computation. An entry only answers for
+//! day spans it has been handed in full (`loaded`) — anything wider falls
+//! through to the disk path, which refills it. Least-recently-used keys
+//! are evicted past [`MEMORY_KEYS`].
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::ibkr::types::historical::{parse_ibkr_time, BarSize, HistoricalBar, WhatToShow};
+
+use super::ranges::{self, DaySpan};
+
+/// Series kept in memory at once.
+pub(super) const MEMORY_KEYS: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) struct BarKey {
+    pub symbol: String,
+    pub bar_size: BarSize,
+    pub what_to_show: WhatToShow,
+}
+
+struct Entry {
+    bars: BTreeMap<i64, HistoricalBar>,
+    loaded: Vec<DaySpan>,
+    last_used: u64,
+}
+
+#[derive(Default)]
+pub(super) struct BarMemoryCache {
+    entries: HashMap<BarKey, Entry>,
+    tick: u64,
+}
+
+impl BarMemoryCache {
+    /// Bars in `[start_unix, end_unix]` when `span` is fully loaded.
+    pub fn get(
+        &mut self,
+        key: &BarKey,
+        span: DaySpan,
+        start_unix: i64,
+        end_unix: i64,
+    ) -> Option<Vec<HistoricalBar>> {
+        self.tick += 1;
+        let entry = self.entries.get_mut(key)?;
+        if !entry.loaded.iter().any(|s| s.contains(&span)) {
+            return None;
+        }
+        entry.last_used = self.tick;
+        Some(
+            entry
+                .bars
+                .range(start_unix..=end_unix)
+                .map(|(_, bar)| bar.clone())
+                .collect(),
+        )
+    }
+
+    /// Record that `bars` is the complete answer for `span`.
+    pub fn insert(&mut self, key: BarKey, span: DaySpan, bars: &[HistoricalBar]) {
+        self.tick += 1;
+        if !self.entries.contains_key(&key) && self.entries.len() >= MEMORY_KEYS {
+            self.evict_oldest();
+        }
+        let entry = self.entries.entry(key).or_insert_with(|| Entry {
+            bars: BTreeMap::new(),
+            loaded: Vec::new(),
+            last_used: 0,
+        });
+        for bar in bars {
+            if let Ok(ts) = parse_ibkr_time(&bar.time) {
+                entry.bars.insert(ts, bar.clone());
+            }
+        }
+        let mut loaded = std::mem::take(&mut entry.loaded);
+        loaded.push(span);
+        entry.loaded = ranges::merge(loaded);
+        entry.last_used = self.tick;
+    }
+
+    /// Every series of `symbol`, whatever the bar size.
+    pub fn remove_symbol(&mut self, symbol: &str) {
+        self.entries.retain(|key, _| key.symbol != symbol);
+    }
+
+    fn evict_oldest(&mut self) {
+        if let Some(oldest) = self
+            .entries
+            .iter()
+            .min_by_key(|(_, e)| e.last_used)
+            .map(|(k, _)| k.clone())
+        {
+            self.entries.remove(&oldest);
+        }
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn key(symbol: &str) -> BarKey {
+        BarKey {
+            symbol: symbol.to_string(),
+            bar_size: BarSize::Day1,
+            what_to_show: WhatToShow::Trades,
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used_series() {
+        let day = NaiveDate::from_ymd_opt(2024, 6, 14).unwrap();
+        let span = DaySpan::new(day, day);
+        let mut cache = BarMemoryCache::default();
+        for i in 0..MEMORY_KEYS {
+            cache.insert(key(&format!("S{i}")), span, &[]);
+        }
+        // Touch S0 so S1 becomes the oldest.
+        assert!(cache.get(&key("S0"), span, 0, i64::MAX).is_some());
+        cache.insert(key("NEW"), span, &[]);
+
+        assert_eq!(cache.len(), MEMORY_KEYS);
+        assert!(cache.get(&key("S0"), span, 0, i64::MAX).is_some());
+        assert!(cache.get(&key("S1"), span, 0, i64::MAX).is_none());
+        // A wider span than what was loaded falls through.
+        let wider = DaySpan::new(day - chrono::Duration::days(1), day);
+        assert!(cache.get(&key("S0"), wider, 0, i64::MAX).is_none());
+    }
+}
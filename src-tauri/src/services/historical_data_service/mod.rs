@@ -5,14 +5,31 @@
 //! limiter. Designed so the same instance can serve UI queries, the
 //! detector framework, and the EOD scheduler from later phases.
 //!
+//! The cache is keyed by (symbol, bar size, what-to-show). Next to the
+//! bars it records which calendar-day spans have been fetched
+//! (`bars_cache_coverage`), so a request only goes to IBKR for the days
+//! nobody has asked for yet — one call per gap, older history included.
+//! An LRU memory layer ([`memory`]) answers repeat loads of an
+//! already-resolved window without touching SQLite.
+//!
+//! Neither layer vouches for a day whose US session hasn't closed: its
+//! bars are still forming. Coverage stops at the last settled day
+//! ([`settled_through`]) and a window reaching past it stays out of
+//! memory, so today is refetched on every load until the close.
+//!
+//! Rows written before coverage existed (or seeded directly into
+//! `bars_cache`) have no coverage record; for those the newest cached
+//! bar is trusted as "covered from the window start up to here", which
+//! is the old tail-gap behavior.
+//!
 //! Production wiring lives in `lib.rs`; tests use a mock fetcher that
 //! records calls and returns canned bar batches.
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
-use chrono::{Duration as ChronoDuration, NaiveDate, TimeZone, Utc};
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, TimeZone, Utc};
 use tokio::sync::Mutex as TokioMutex;
 
 use crate::ibkr::client::IbkrClient;
@@ -22,8 +39,15 @@ use crate::ibkr::types::historical::{
 };
 use crate::middleware::HistoricalRateLimiter;
 use crate::storage::Db;
+use crate::utils::market_calendar::sessions::US_EQUITIES;
+use crate::utils::symbols;
 
 mod cache;
+mod memory;
+mod ranges;
+
+use memory::{BarKey, BarMemoryCache};
+use ranges::DaySpan;
 
 #[cfg(test)]
 mod tests;
@@ -51,17 +75,52 @@ impl HistoricalDataFetcher for IbkrClient {
     }
 }
 
+/// Trait seam for a symbol's recent daily bars, for services that only
+/// look back a number of sessions (factor exposure, benchmarks, hedging,
+/// liquidity). Production is the cached [`HistoricalDataService`];
+/// tests inject series.
+#[async_trait]
+pub trait DailyBars: Send + Sync {
+    /// At least the last `sessions` daily bars when there is that much
+    /// history, oldest first.
+    async fn daily_bars(&self, symbol: &str, sessions: usize) -> IbkrResult<Vec<HistoricalBar>>;
+
+    /// `(bar time, close)` of [`Self::daily_bars`].
+    async fn daily_closes(&self, symbol: &str, sessions: usize) -> IbkrResult<Vec<(String, f64)>> {
+        let bars = self.daily_bars(symbol, sessions).await?;
+        Ok(bars.into_iter().map(|b| (b.time, b.close)).collect())
+    }
+}
+
+#[async_trait]
+impl DailyBars for HistoricalDataService {
+    async fn daily_bars(&self, symbol: &str, sessions: usize) -> IbkrResult<Vec<HistoricalBar>> {
+        let lookback = Lookback::Days(session_days(sessions));
+        self.fetch_bars(symbol, BarSize::Day1, lookback).await
+    }
+}
+
+/// Calendar days that hold `sessions` sessions, with room for holidays.
+pub fn session_days(sessions: usize) -> u32 {
+    (sessions * 7 / 5 + 10) as u32
+}
+
 /// Injectable clock so the staleness rules (e.g. "intraday cache only
-/// honored same-day") are deterministic in tests.
+/// honored same-day", "today isn't settled before the close") are
+/// deterministic in tests.
 pub trait Clock: Send + Sync {
-    fn today(&self) -> NaiveDate;
+    fn now(&self) -> DateTime<Utc>;
+
+    fn today(&self) -> NaiveDate {
+        self.now().date_naive()
+    }
 }
 
 pub struct SystemClock;
 
 impl Clock for SystemClock {
-    fn today(&self) -> NaiveDate {
-        Utc::now().date_naive()
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
     }
 }
 
@@ -84,6 +143,7 @@ pub struct HistoricalDataService {
     rate_limit: Arc<HistoricalRateLimiter>,
     clock: Arc<dyn Clock>,
     inflight: Arc<TokioMutex<HashMap<String, Arc<TokioMutex<()>>>>>,
+    memory: Mutex<BarMemoryCache>,
 }
 
 impl HistoricalDataService {
@@ -98,16 +158,16 @@ impl HistoricalDataService {
             rate_limit,
             clock: Arc::new(SystemClock),
             inflight: Arc::new(TokioMutex::new(HashMap::new())),
+            memory: Mutex::new(BarMemoryCache::default()),
         }
     }
 
-    #[allow(dead_code)]
     pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
         self.clock = clock;
         self
     }
 
-    /// Public entry point. Returns bars for the requested window,
+    /// Public entry point. Returns TRADES bars for the requested window,
     /// reading from cache when possible and fetching only the missing
     /// portion. Combined output is sorted ascending by `bar_time` and
     /// deduplicated.
@@ -117,10 +177,29 @@ impl HistoricalDataService {
         bar_size: BarSize,
         lookback: Lookback,
     ) -> IbkrResult<Vec<HistoricalBar>> {
+        self.fetch_series(symbol, bar_size, WhatToShow::Trades, lookback)
+            .await
+    }
+
+    /// [`Self::fetch_bars`] for any `what_to_show` series.
+    pub async fn fetch_series(
+        &self,
+        symbol: &str,
+        bar_size: BarSize,
+        what_to_show: WhatToShow,
+        lookback: Lookback,
+    ) -> IbkrResult<Vec<HistoricalBar>> {
+        let symbol = symbols::normalize(symbol);
+        let bar_key = BarKey {
+            symbol: symbol.clone(),
+            bar_size,
+            what_to_show,
+        };
         let key = format!(
-            "{}|{}|{}",
+            "{}|{}|{}|{}",
             symbol,
             bar_size.as_str(),
+            what_to_show.as_str(),
             lookback_key(&lookback)
         );
 
@@ -133,7 +212,7 @@ impl HistoricalDataService {
         };
         let _per_key_guard = per_key.lock().await;
 
-        let result = self.fetch_bars_inner(symbol, bar_size, &lookback).await;
+        let result = self.fetch_bars_inner(&bar_key, &lookback).await;
 
         // Best-effort cleanup so the map doesn't grow without bound.
         // We hold the guard, so strong_count == 2 means: us + the map.
@@ -150,50 +229,91 @@ impl HistoricalDataService {
         result
     }
 
+    /// Drop `symbol`'s in-memory series, so the next load re-reads the
+    /// SQLite cache. Called after the corporate actions service rewrites
+    /// cached bars underneath (a split, a symbol change).
+    pub fn forget(&self, symbol: &str) {
+        self.memory
+            .lock()
+            .expect("bar memory poisoned")
+            .remove_symbol(&symbols::normalize(symbol));
+    }
+
     async fn fetch_bars_inner(
         &self,
-        symbol: &str,
-        bar_size: BarSize,
+        key: &BarKey,
         lookback: &Lookback,
     ) -> IbkrResult<Vec<HistoricalBar>> {
-        let today = self.clock.today();
-        let (start_unix, end_unix) = window_bounds(today, lookback);
-
-        // Decide whether the cache is useable for this lookup.
-        let cache_usable = !is_cache_stale(bar_size, lookback, today);
-
-        let cached = if cache_usable {
-            cache::read_cache(&self.db, symbol, bar_size, start_unix, end_unix).await?
-        } else {
-            Vec::new()
-        };
-
-        let missing = if cache_usable {
-            compute_missing_range(&cached, lookback, today)
-        } else {
-            Some(missing_for_full_window(lookback))
-        };
+        let now = self.clock.now();
+        let today = now.date_naive();
+        let settled = settled_through(now);
+        let wanted = requested_span(today, lookback);
+        let (start_unix, end_unix) = window_bounds(wanted);
+
+        // Stale intraday lookups bypass both cache layers and refetch.
+        if is_cache_stale(key.bar_size, lookback, today) {
+            let fetched = self.fetch_span(key, wanted).await?;
+            return Ok(merge_sorted_unique(Vec::new(), fetched));
+        }
 
-        if let Some(gap_days) = missing {
-            self.rate_limit.acquire().await;
+        if let Some(bars) = self
+            .memory
+            .lock()
+            .expect("bar memory poisoned")
+            .get(key, wanted, start_unix, end_unix)
+        {
+            crate::telemetry::record_cache_lookup("bars", true);
+            return Ok(bars);
+        }
 
-            let request = HistoricalDataRequest {
-                symbol: symbol.to_string(),
-                end_date_time: format!("{} 23:59:59", today.format("%Y%m%d")),
-                duration: format!("{gap_days} D"),
-                bar_size,
-                what_to_show: WhatToShow::Trades,
-                use_rth: true,
-            };
+        let cached = cache::read_cache(&self.db, key, start_unix, end_unix).await?;
+        let mut coverage = cache::read_coverage(&self.db, key).await?;
+        if coverage.is_empty() {
+            if let Some(newest) = newest_bar_day(&cached) {
+                coverage.push(DaySpan::new(wanted.start, newest.min(settled)));
+            }
+        }
 
-            let fetched = self.fetcher.fetch_historical(request).await?;
-            cache::write_cache(&self.db, symbol, bar_size, &fetched).await?;
+        let gaps = ranges::missing(wanted, &coverage);
+        crate::telemetry::record_cache_lookup("bars", gaps.is_empty());
+        let mut fetched = Vec::new();
+        for gap in &gaps {
+            fetched.extend(self.fetch_span(key, *gap).await?);
+        }
+        let known: Vec<DaySpan> = gaps
+            .iter()
+            .map(|gap| DaySpan::new(gap.start, gap.end.min(settled)))
+            .filter(|gap| !gap.is_empty())
+            .collect();
+        if !known.is_empty() {
+            coverage.extend(known);
+            cache::write_coverage(&self.db, key, &ranges::merge(coverage)).await?;
+        }
 
-            let combined = merge_sorted_unique(cached, fetched);
-            return Ok(combined);
+        let combined = merge_sorted_unique(cached, fetched);
+        if wanted.end <= settled {
+            self.memory
+                .lock()
+                .expect("bar memory poisoned")
+                .insert(key.clone(), wanted, &combined);
         }
+        Ok(combined)
+    }
 
-        Ok(cached)
+    /// One IBKR request for exactly `span`, written through to SQLite.
+    async fn fetch_span(&self, key: &BarKey, span: DaySpan) -> IbkrResult<Vec<HistoricalBar>> {
+        self.rate_limit.acquire().await;
+        let request = HistoricalDataRequest {
+            symbol: key.symbol.clone(),
+            end_date_time: format!("{} 23:59:59", span.end.format("%Y%m%d")),
+            duration: format!("{} D", span.days().max(1)),
+            bar_size: key.bar_size,
+            what_to_show: key.what_to_show,
+            use_rth: true,
+        };
+        let fetched = self.fetcher.fetch_historical(request).await?;
+        cache::write_cache(&self.db, key, &fetched).await?;
+        Ok(fetched)
     }
 }
 
@@ -224,14 +344,8 @@ fn day_to_eod_unix(date: NaiveDate) -> i64 {
     .timestamp()
 }
 
-fn window_bounds(today: NaiveDate, lookback: &Lookback) -> (i64, i64) {
-    match lookback {
-        Lookback::Days(n) => {
-            let start = today - ChronoDuration::days(*n as i64);
-            (day_to_midnight_unix(start), day_to_eod_unix(today))
-        }
-        Lookback::TradingDay(d) => (day_to_midnight_unix(*d), day_to_eod_unix(*d)),
-    }
+fn window_bounds(span: DaySpan) -> (i64, i64) {
+    (day_to_midnight_unix(span.start), day_to_eod_unix(span.end))
 }
 
 /// Intraday cache rows are only honored when the lookup is for *today*
@@ -249,61 +363,46 @@ fn is_cache_stale(bar_size: BarSize, lookback: &Lookback, today: NaiveDate) -> b
     }
 }
 
-/// Returns `Some(gap_days)` when a fetch is needed, `None` if cache covers
-/// the request fully. `gap_days` is `>= 1`.
-fn compute_missing_range(
-    cached: &[HistoricalBar],
-    lookback: &Lookback,
-    today: NaiveDate,
-) -> Option<u32> {
+/// Last day whose bars are final at `now`: today once the US session
+/// has closed (or when there is none), else yesterday.
+fn settled_through(now: DateTime<Utc>) -> NaiveDate {
+    let today = now.date_naive();
+    match US_EQUITIES.session(today) {
+        Some((_, close)) if now < close => today - ChronoDuration::days(1),
+        _ => today,
+    }
+}
+
+/// Calendar days a lookback asks for: `Days(N)` is the N days ending
+/// today (inclusive), `TradingDay(d)` is just `d`.
+fn requested_span(today: NaiveDate, lookback: &Lookback) -> DaySpan {
     match lookback {
         Lookback::Days(n) => {
-            if cached.is_empty() {
-                return Some((*n).max(1));
-            }
-            let max_cached_ts = cached
-                .iter()
-                .filter_map(|b| parse_ibkr_time(&b.time).ok())
-                .max()
-                .unwrap_or(0);
-            let max_cached_day = Utc
-                .timestamp_opt(max_cached_ts, 0)
-                .single()
-                .map(|dt| dt.date_naive())
-                .unwrap_or(today);
-            if max_cached_day >= today {
-                // Cache reaches today — for daily bars we treat this as fully covered.
-                // Conservative for intraday Days(1), but is_cache_stale would have
-                // already pre-empted the intraday-multi-day case.
-                return None;
-            }
-            let gap = (today - max_cached_day).num_days() as u32;
-            if gap == 0 {
-                None
-            } else {
-                Some(gap)
-            }
-        }
-        Lookback::TradingDay(_) => {
-            if cached.is_empty() {
-                Some(1)
-            } else {
-                None
-            }
+            let back = i64::from((*n).max(1)) - 1;
+            DaySpan::new(today - ChronoDuration::days(back), today)
         }
+        Lookback::TradingDay(d) => DaySpan::new(*d, *d),
     }
 }
 
-fn missing_for_full_window(lookback: &Lookback) -> u32 {
-    match lookback {
-        Lookback::Days(n) => (*n).max(1),
-        Lookback::TradingDay(_) => 1,
-    }
+fn newest_bar_day(bars: &[HistoricalBar]) -> Option<NaiveDate> {
+    bars.iter()
+        .filter_map(|b| parse_ibkr_time(&b.time).ok())
+        .max()
+        .and_then(|ts| Utc.timestamp_opt(ts, 0).single())
+        .map(|dt| dt.date_naive())
 }
 
-/// Merge cached + fetched bar lists, sort ascending by bar_time, dedupe by ts.
-fn merge_sorted_unique(a: Vec<HistoricalBar>, b: Vec<HistoricalBar>) -> Vec<HistoricalBar> {
-    let mut combined: Vec<HistoricalBar> = a.into_iter().chain(b).collect();
+/// Merge cached + fetched bar lists, sort ascending by bar_time, dedupe by
+/// ts. A fetched bar replaces the cached one at its ts: today's bar is
+/// refetched while the session is open, and the cached copy is stale.
+fn merge_sorted_unique(
+    cached: Vec<HistoricalBar>,
+    fetched: Vec<HistoricalBar>,
+) -> Vec<HistoricalBar> {
+    // The sort is stable, so fetched bars stay ahead of their cached
+    // twins and win the dedupe.
+    let mut combined: Vec<HistoricalBar> = fetched.into_iter().chain(cached).collect();
     combined.sort_by(|x, y| {
         let xt = parse_ibkr_time(&x.time).unwrap_or(0);
         let yt = parse_ibkr_time(&y.time).unwrap_or(0);
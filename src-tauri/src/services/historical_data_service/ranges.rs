@@ -0,0 +1,107 @@
+//! Calendar-day span arithmetic for the bars cache.
+//!
+//! Coverage is tracked in whole UTC days, both ends inclusive: a fetch
+//! for `[start, end]` marks every settled day in it as known, bars or
+//! not; a day whose session is still open stays unknown. The
+//! service asks [`missing`] which parts of a request are not yet known
+//! and fetches exactly those; [`merge`] folds the new spans back into
+//! the stored list.
+
+use chrono::{Duration as ChronoDuration, NaiveDate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) struct DaySpan {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DaySpan {
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Self {
+        Self { start, end }
+    }
+
+    /// Inclusive day count; `0` for an inverted span.
+    pub fn days(&self) -> u32 {
+        ((self.end - self.start).num_days() + 1).max(0) as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+
+    pub fn contains(&self, other: &DaySpan) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+}
+
+/// Sort and coalesce overlapping or adjacent spans. Empty spans drop out.
+pub(super) fn merge(mut spans: Vec<DaySpan>) -> Vec<DaySpan> {
+    spans.retain(|s| !s.is_empty());
+    spans.sort();
+    let mut out: Vec<DaySpan> = Vec::with_capacity(spans.len());
+    for span in spans {
+        match out.last_mut() {
+            Some(last) if span.start <= last.end + ChronoDuration::days(1) => {
+                last.end = last.end.max(span.end);
+            }
+            _ => out.push(span),
+        }
+    }
+    out
+}
+
+/// The parts of `wanted` not covered by `coverage`, ascending.
+pub(super) fn missing(wanted: DaySpan, coverage: &[DaySpan]) -> Vec<DaySpan> {
+    let mut gaps = Vec::new();
+    let mut cursor = wanted.start;
+    for span in merge(coverage.to_vec()) {
+        if span.end < cursor {
+            continue;
+        }
+        if span.start > wanted.end {
+            break;
+        }
+        if span.start > cursor {
+            gaps.push(DaySpan::new(cursor, span.start - ChronoDuration::days(1)));
+        }
+        cursor = span.end + ChronoDuration::days(1);
+        if cursor > wanted.end {
+            return gaps;
+        }
+    }
+    if cursor <= wanted.end {
+        gaps.push(DaySpan::new(cursor, wanted.end));
+    }
+    gaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, day).unwrap()
+    }
+
+    fn span(a: u32, b: u32) -> DaySpan {
+        DaySpan::new(d(a), d(b))
+    }
+
+    #[test]
+    fn merge_coalesces_overlapping_and_adjacent_spans() {
+        let merged = merge(vec![span(10, 12), span(1, 3), span(4, 5), span(11, 15)]);
+        assert_eq!(merged, vec![span(1, 5), span(10, 15)]);
+    }
+
+    #[test]
+    fn missing_returns_only_the_uncovered_gaps() {
+        let coverage = vec![span(5, 8), span(12, 14)];
+        assert_eq!(
+            missing(span(1, 20), &coverage),
+            vec![span(1, 4), span(9, 11), span(15, 20)]
+        );
+        assert!(missing(span(6, 7), &coverage).is_empty());
+        assert_eq!(missing(span(13, 16), &coverage), vec![span(15, 16)]);
+        assert_eq!(missing(span(1, 3), &[]), vec![span(1, 3)]);
+    }
+}
@@ -5,7 +5,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use chrono::{Duration as ChronoDuration, NaiveDate, TimeZone, Utc};
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, TimeZone, Utc};
 use tempfile::NamedTempFile;
 use tokio::sync::{Mutex, Notify};
 
@@ -70,11 +70,26 @@ impl HistoricalDataFetcher for MockHistoricalFetcher {
     }
 }
 
+/// Noon UTC of a fixed day.
 struct FixedClock(NaiveDate);
 
 impl Clock for FixedClock {
-    fn today(&self) -> NaiveDate {
-        self.0
+    fn now(&self) -> DateTime<Utc> {
+        Utc.from_utc_datetime(&self.0.and_hms_opt(12, 0, 0).unwrap())
+    }
+}
+
+struct SettableClock(std::sync::Mutex<DateTime<Utc>>);
+
+impl SettableClock {
+    fn set(&self, now: DateTime<Utc>) {
+        *self.0.lock().unwrap() = now;
+    }
+}
+
+impl Clock for SettableClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.0.lock().unwrap()
     }
 }
 
@@ -174,10 +189,14 @@ async fn bars_round_trip_through_sqlite_preserve_floats_and_volume() {
         .await;
     }
 
+    let key = super::memory::BarKey {
+        symbol: "AAPL".to_string(),
+        bar_size: BarSize::Day1,
+        what_to_show: WhatToShow::Trades,
+    };
     let read_back = super::cache::read_cache(
         &db,
-        "AAPL",
-        BarSize::Day1,
+        &key,
         day_unix(day0),
         day_unix(day0 + ChronoDuration::days(999)),
     )
@@ -615,6 +634,208 @@ async fn unrelated_smoke_storage_error_propagates_as_request_failed() {
     assert!(matches!(err, IbkrError::RequestFailed(_)));
 }
 
-// Suppress "unused" warnings for parts only some tests use.
-#[allow(dead_code)]
-fn _shut_up(_w: WhatToShow) {}
+// ------------- 9: coverage → one call per gap, head gaps included -------------
+
+fn daily_bar(date: NaiveDate, close: f64) -> HistoricalBar {
+    HistoricalBar {
+        time: ibkr_date_str(date),
+        open: close,
+        high: close,
+        low: close,
+        close,
+        volume: 1_000,
+        wap: close,
+        count: 1,
+    }
+}
+
+#[tokio::test]
+async fn wider_window_fetches_only_uncovered_spans() {
+    let (_tmp, db) = make_db();
+    let today = fixed_today();
+    let fetcher = Arc::new(MockHistoricalFetcher::new());
+    let service = HistoricalDataService::new(db, fetcher.clone(), rate_limiter())
+        .with_clock(Arc::new(FixedClock(today)));
+
+    fetcher
+        .enqueue(
+            (0..10)
+                .map(|i| daily_bar(today - ChronoDuration::days(i), 1.0))
+                .collect(),
+        )
+        .await;
+    service
+        .fetch_bars("AAPL", BarSize::Day1, Lookback::Days(10))
+        .await
+        .expect("first fetch ok");
+
+    // Widening to 30 days asks only for the 20 older days.
+    fetcher
+        .enqueue(
+            (10..30)
+                .map(|i| daily_bar(today - ChronoDuration::days(i), 2.0))
+                .collect(),
+        )
+        .await;
+    let bars = service
+        .fetch_bars("AAPL", BarSize::Day1, Lookback::Days(30))
+        .await
+        .expect("wider fetch ok");
+
+    let calls = fetcher.calls().await;
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[1].duration, "20 D");
+    let head_end = today - ChronoDuration::days(10);
+    assert_eq!(
+        calls[1].end_date_time,
+        format!("{} 23:59:59", head_end.format("%Y%m%d"))
+    );
+    assert_eq!(bars.len(), 30);
+
+    // Both spans are now recorded; a third load is served without IBKR.
+    service
+        .fetch_bars("AAPL", BarSize::Day1, Lookback::Days(30))
+        .await
+        .expect("cached fetch ok");
+    assert_eq!(fetcher.call_count().await, 2);
+}
+
+#[tokio::test]
+async fn todays_bar_is_refetched_until_the_session_closes() {
+    let (_tmp, db) = make_db();
+    // Friday 2024-06-14, 11:00 EDT; the session closes at 20:00 UTC.
+    let today = NaiveDate::from_ymd_opt(2024, 6, 14).unwrap();
+    let clock = Arc::new(SettableClock(std::sync::Mutex::new(
+        Utc.with_ymd_and_hms(2024, 6, 14, 15, 0, 0).unwrap(),
+    )));
+    let fetcher = Arc::new(MockHistoricalFetcher::new());
+    let service =
+        HistoricalDataService::new(db, fetcher.clone(), rate_limiter()).with_clock(clock.clone());
+
+    let mut week: Vec<_> = (1..5)
+        .map(|i| daily_bar(today - ChronoDuration::days(i), 1.0))
+        .collect();
+    week.push(daily_bar(today, 100.0));
+    fetcher.enqueue(week).await;
+    service
+        .fetch_bars("AAPL", BarSize::Day1, Lookback::Days(5))
+        .await
+        .expect("mid-session fetch ok");
+
+    // Still mid-session: only today goes back to IBKR.
+    fetcher.enqueue(vec![daily_bar(today, 101.0)]).await;
+    let bars = service
+        .fetch_bars("AAPL", BarSize::Day1, Lookback::Days(5))
+        .await
+        .expect("second mid-session fetch ok");
+    assert_eq!(bars.len(), 5);
+    assert_eq!(bars[4].close, 101.0);
+    let calls = fetcher.calls().await;
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[1].duration, "1 D");
+
+    // After the close the final bar is fetched once, then cached.
+    clock.set(Utc.with_ymd_and_hms(2024, 6, 14, 20, 30, 0).unwrap());
+    fetcher.enqueue(vec![daily_bar(today, 102.0)]).await;
+    for _ in 0..2 {
+        let bars = service
+            .fetch_bars("AAPL", BarSize::Day1, Lookback::Days(5))
+            .await
+            .expect("post-close fetch ok");
+        assert_eq!(bars[4].close, 102.0);
+    }
+    assert_eq!(fetcher.call_count().await, 3);
+}
+
+#[tokio::test]
+async fn empty_span_counts_as_covered() {
+    let (_tmp, db) = make_db();
+    let today = fixed_today();
+    let fetcher = Arc::new(MockHistoricalFetcher::new());
+    let service = HistoricalDataService::new(db, fetcher.clone(), rate_limiter())
+        .with_clock(Arc::new(FixedClock(today)));
+
+    // e.g. a weekend / pre-IPO window: IBKR answers with no bars.
+    fetcher.enqueue(Vec::new()).await;
+    for _ in 0..2 {
+        let bars = service
+            .fetch_bars("NEW", BarSize::Day1, Lookback::Days(3))
+            .await
+            .expect("fetch ok");
+        assert!(bars.is_empty());
+    }
+    assert_eq!(
+        fetcher.call_count().await,
+        1,
+        "known-empty span not refetched"
+    );
+}
+
+// ------------- 10: memory layer + what-to-show keying -------------
+
+#[tokio::test]
+async fn repeat_load_is_served_from_memory() {
+    let (_tmp, db) = make_db();
+    let today = fixed_today();
+    let fetcher = Arc::new(MockHistoricalFetcher::new());
+    fetcher
+        .enqueue(
+            (0..5)
+                .map(|i| daily_bar(today - ChronoDuration::days(i), 3.0))
+                .collect(),
+        )
+        .await;
+    let service = HistoricalDataService::new(db.clone(), fetcher.clone(), rate_limiter())
+        .with_clock(Arc::new(FixedClock(today)));
+
+    service
+        .fetch_bars("MSFT", BarSize::Day1, Lookback::Days(5))
+        .await
+        .expect("first fetch ok");
+
+    // Wipe the disk layer: the second load must come from memory.
+    db.with_conn(|conn| {
+        conn.execute("DELETE FROM bars_cache", [])?;
+        Ok(())
+    })
+    .await
+    .expect("wipe ok");
+
+    let bars = service
+        .fetch_bars("MSFT", BarSize::Day1, Lookback::Days(3))
+        .await
+        .expect("memory fetch ok");
+    assert_eq!(bars.len(), 3, "narrower window sliced from memory");
+    assert_eq!(fetcher.call_count().await, 1);
+}
+
+#[tokio::test]
+async fn series_are_cached_per_what_to_show() {
+    let (_tmp, db) = make_db();
+    let today = fixed_today();
+    let fetcher = Arc::new(MockHistoricalFetcher::new());
+    fetcher.enqueue(vec![daily_bar(today, 10.0)]).await;
+    fetcher.enqueue(vec![daily_bar(today, 9.5)]).await;
+    let service = HistoricalDataService::new(db, fetcher.clone(), rate_limiter())
+        .with_clock(Arc::new(FixedClock(today)));
+
+    let trades = service
+        .fetch_bars("AAPL", BarSize::Day1, Lookback::Days(1))
+        .await
+        .expect("trades ok");
+    let midpoint = service
+        .fetch_series(
+            "AAPL",
+            BarSize::Day1,
+            WhatToShow::Midpoint,
+            Lookback::Days(1),
+        )
+        .await
+        .expect("midpoint ok");
+
+    let calls = fetcher.calls().await;
+    assert_eq!(calls.len(), 2, "midpoint is its own series");
+    assert_eq!(calls[1].what_to_show, WhatToShow::Midpoint);
+    assert_eq!(trades[0].close, 10.0);
+    assert_eq!(midpoint[0].close, 9.5);
+}
@@ -0,0 +1,155 @@
+//! [`AlphaVantageInsiderFeed`] — Form 4 transactions from Alpha
+//! Vantage's `INSIDER_TRANSACTIONS` endpoint.
+//!
+//! Shares the AV rate limiter with the fundamentals adapter. Payloads
+//! are cached per symbol for [`CACHE_TTL`] in their own directory (not
+//! `cache/alphavantage`, whose keys list the cached fundamentals
+//! tickers); on a failed or rate-limited fetch the last cached payload
+//! is served regardless of age.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::middleware::AlphaVantageRateLimiter;
+use crate::services::cache_service::CacheService;
+use crate::services::financial_data_service::AvHttp;
+use crate::utils::symbols;
+
+use super::{InsiderError, InsiderFeed, InsiderSide, InsiderTransaction};
+
+pub const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+const BASE_URL: &str = "https://www.alphavantage.co/query";
+
+pub struct AlphaVantageInsiderFeed {
+    http: Arc<dyn AvHttp>,
+    api_key: String,
+    base_url: String,
+    cache: Option<CacheService>,
+    rate_limiter: Option<Arc<AlphaVantageRateLimiter>>,
+}
+
+impl AlphaVantageInsiderFeed {
+    pub fn new(http: Arc<dyn AvHttp>, api_key: String) -> Self {
+        Self {
+            http,
+            api_key,
+            base_url: BASE_URL.to_string(),
+            cache: None,
+            rate_limiter: None,
+        }
+    }
+
+    pub fn with_cache(mut self, cache: CacheService) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, limiter: Arc<AlphaVantageRateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    async fn fetch(&self, symbol: &str) -> Result<Value, InsiderError> {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
+        }
+        let url = format!(
+            "{}?function=INSIDER_TRANSACTIONS&symbol={}&apikey={}",
+            self.base_url,
+            symbols::alpha_vantage(symbol),
+            self.api_key
+        );
+        let json = self
+            .http
+            .fetch(&url)
+            .await
+            .map_err(|e| InsiderError::Upstream(e.to_string()))?;
+        for key in ["Error Message", "Note", "Information"] {
+            if let Some(msg) = json.get(key).and_then(|v| v.as_str()) {
+                return Err(InsiderError::Upstream(msg.to_string()));
+            }
+        }
+        Ok(json)
+    }
+}
+
+#[async_trait]
+impl InsiderFeed for AlphaVantageInsiderFeed {
+    async fn transactions(&self, symbol: &str) -> Result<Vec<InsiderTransaction>, InsiderError> {
+        let key = format!("{symbol}_insider_transactions");
+        if let Some(cached) = self.cache.as_ref().and_then(|c| c.read::<Value>(&key).ok()) {
+            return Ok(parse_transactions(&cached));
+        }
+        match self.fetch(symbol).await {
+            Ok(json) => {
+                if let Some(c) = &self.cache {
+                    if let Err(e) = c.write(&key, &json) {
+                        warn!("insider cache write failed for {symbol}: {e}");
+                    }
+                }
+                let rows = parse_transactions(&json);
+                info!("insider: {} transaction(s) for {symbol}", rows.len());
+                Ok(rows)
+            }
+            Err(e) => {
+                let stale = self
+                    .cache
+                    .as_ref()
+                    .and_then(|c| c.read_ignoring_ttl::<Value>(&key).ok());
+                match stale {
+                    Some((json, age)) => {
+                        warn!("insider: {e}; serving cached {symbol} rows (age {age}s)");
+                        Ok(parse_transactions(&json))
+                    }
+                    None => Err(e),
+                }
+            }
+        }
+    }
+}
+
+/// Rows of an `INSIDER_TRANSACTIONS` payload. Rows without a date, a
+/// side or a share count are skipped.
+pub(super) fn parse_transactions(json: &Value) -> Vec<InsiderTransaction> {
+    let Some(rows) = json.get("data").and_then(|d| d.as_array()) else {
+        return Vec::new();
+    };
+    rows.iter().filter_map(parse_row).collect()
+}
+
+fn parse_row(row: &Value) -> Option<InsiderTransaction> {
+    let text = |field: &str| {
+        row.get(field)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+    let number = |field: &str| text(field).and_then(|s| s.parse::<f64>().ok());
+    let date = NaiveDate::parse_from_str(text("transaction_date")?, "%Y-%m-%d").ok()?;
+    let side = match text("acquisition_or_disposal")? {
+        "A" => InsiderSide::Buy,
+        "D" => InsiderSide::Sell,
+        _ => return None,
+    };
+    let shares = number("shares").filter(|s| s.is_finite() && *s > 0.0)?;
+    let price = number("share_price")
+        .filter(|p| p.is_finite() && *p > 0.0)
+        .unwrap_or(0.0);
+    let security_type = text("security_type").unwrap_or_default().to_string();
+    Some(InsiderTransaction {
+        date,
+        insider: text("executive").unwrap_or("Unknown").to_string(),
+        title: text("executive_title").map(str::to_string),
+        open_market: price > 0.0 && security_type.to_ascii_lowercase().contains("common"),
+        security_type,
+        side,
+        shares,
+        price,
+    })
+}
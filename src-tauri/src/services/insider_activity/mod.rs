@@ -0,0 +1,177 @@
+//! Insider buying and selling per symbol.
+//!
+//! Transactions come from an [`InsiderFeed`]; production wires
+//! [`alpha_vantage::AlphaVantageInsiderFeed`] (`INSIDER_TRANSACTIONS`,
+//! Form 4 filings). [`summarize`] nets them over the trailing 3, 6 and
+//! 12 months for the analysis view.
+//!
+//! Only open-market trades in common stock count toward the net: Form 4
+//! also reports option exercises, RSU vesting and gifts, which AV lists
+//! as derivative security types or at a zero price. Those rows are kept
+//! in the transaction list with `open_market == false` so the UI can show
+//! them without them swamping the signal.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::utils::symbols;
+
+pub mod alpha_vantage;
+
+#[cfg(test)]
+mod tests;
+
+/// Trailing windows the summary nets over, in months.
+pub const WINDOWS_MONTHS: [u32; 3] = [3, 6, 12];
+
+#[derive(Debug, Error)]
+pub enum InsiderError {
+    #[error("insider feed: {0}")]
+    Upstream(String),
+    #[error("invalid symbol: {0}")]
+    Invalid(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InsiderSide {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsiderTransaction {
+    pub date: NaiveDate,
+    pub insider: String,
+    pub title: Option<String>,
+    pub security_type: String,
+    pub side: InsiderSide,
+    pub shares: f64,
+    /// Per share; 0 for grants, vesting and gifts.
+    pub price: f64,
+    /// Common stock at a non-zero price.
+    pub open_market: bool,
+}
+
+impl InsiderTransaction {
+    pub fn value(&self) -> f64 {
+        self.shares * self.price
+    }
+}
+
+/// Net open-market activity over one trailing window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsiderWindow {
+    pub months: u32,
+    pub buy_shares: f64,
+    pub sell_shares: f64,
+    pub net_shares: f64,
+    pub buy_value: f64,
+    pub sell_value: f64,
+    /// Positive when insiders bought more than they sold.
+    pub net_value: f64,
+    /// Distinct insiders with at least one buy / sell.
+    pub buyers: usize,
+    pub sellers: usize,
+    pub transactions: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsiderActivity {
+    pub symbol: String,
+    pub as_of: NaiveDate,
+    /// One per [`WINDOWS_MONTHS`], shortest first.
+    pub windows: Vec<InsiderWindow>,
+    /// The last 12 months, newest first.
+    pub transactions: Vec<InsiderTransaction>,
+}
+
+/// Trait seam for the filings source. Tests inject canned rows.
+#[async_trait]
+pub trait InsiderFeed: Send + Sync {
+    /// Every reported transaction for `symbol`, in any order.
+    async fn transactions(&self, symbol: &str) -> Result<Vec<InsiderTransaction>, InsiderError>;
+}
+
+pub struct InsiderActivityService {
+    feed: Arc<dyn InsiderFeed>,
+}
+
+impl InsiderActivityService {
+    pub fn new(feed: Arc<dyn InsiderFeed>) -> Self {
+        Self { feed }
+    }
+
+    pub async fn activity(&self, symbol: &str) -> Result<InsiderActivity, InsiderError> {
+        let symbol = symbols::normalize(symbol);
+        if symbol.is_empty() {
+            return Err(InsiderError::Invalid("symbol is empty".to_string()));
+        }
+        let transactions = self.feed.transactions(&symbol).await?;
+        Ok(summarize(&symbol, transactions, Utc::now().date_naive()))
+    }
+}
+
+/// Net the open-market rows of `transactions` over each trailing window
+/// ending `today`. Rows dated after `today` are dropped.
+pub fn summarize(
+    symbol: &str,
+    mut transactions: Vec<InsiderTransaction>,
+    today: NaiveDate,
+) -> InsiderActivity {
+    let since = |months: u32| today.checked_sub_months(Months::new(months));
+    let year_ago = since(12).unwrap_or(NaiveDate::MIN);
+    transactions.retain(|t| t.date > year_ago && t.date <= today);
+    transactions.sort_by_key(|t| std::cmp::Reverse(t.date));
+
+    let windows = WINDOWS_MONTHS
+        .iter()
+        .map(|&months| {
+            let from = since(months).unwrap_or(NaiveDate::MIN);
+            let rows: Vec<_> = transactions
+                .iter()
+                .filter(|t| t.open_market && t.date > from)
+                .collect();
+            let side = |side: InsiderSide| rows.iter().filter(move |t| t.side == side);
+            let buy_shares = side(InsiderSide::Buy).map(|t| t.shares).sum::<f64>();
+            let sell_shares = side(InsiderSide::Sell).map(|t| t.shares).sum::<f64>();
+            let buy_value = side(InsiderSide::Buy).map(|t| t.value()).sum::<f64>();
+            let sell_value = side(InsiderSide::Sell).map(|t| t.value()).sum::<f64>();
+            let distinct = |side: InsiderSide| side_names(&rows, side).len();
+            InsiderWindow {
+                months,
+                buy_shares,
+                sell_shares,
+                net_shares: buy_shares - sell_shares,
+                buy_value,
+                sell_value,
+                net_value: buy_value - sell_value,
+                buyers: distinct(InsiderSide::Buy),
+                sellers: distinct(InsiderSide::Sell),
+                transactions: rows.len(),
+            }
+        })
+        .collect();
+
+    InsiderActivity {
+        symbol: symbol.to_string(),
+        as_of: today,
+        windows,
+        transactions,
+    }
+}
+
+fn side_names<'a>(rows: &[&'a InsiderTransaction], side: InsiderSide) -> BTreeSet<&'a str> {
+    rows.iter()
+        .filter(|t| t.side == side)
+        .map(|t| t.insider.as_str())
+        .collect()
+}
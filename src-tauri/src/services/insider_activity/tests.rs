@@ -0,0 +1,132 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+use crate::services::cache_service::CacheService;
+use crate::services::financial_data_service::{AvHttp, AvHttpError};
+
+use super::alpha_vantage::{parse_transactions, AlphaVantageInsiderFeed};
+use super::{summarize, InsiderFeed, InsiderSide};
+
+fn day(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+fn row(date: &str, who: &str, security: &str, side: &str, shares: &str, price: &str) -> Value {
+    json!({
+        "transaction_date": date,
+        "ticker": "ACME",
+        "executive": who,
+        "executive_title": "Director",
+        "security_type": security,
+        "acquisition_or_disposal": side,
+        "shares": shares,
+        "share_price": price,
+    })
+}
+
+fn payload() -> Value {
+    json!({ "data": [
+        row("2026-09-20", "DOE, JANE", "Common Stock", "A", "1000.0", "50.0"),
+        row("2026-08-01", "ROE, RICH", "Common Stock", "A", "500.0", "40.0"),
+        // RSU vesting: zero price, listed but not counted.
+        row("2026-07-15", "DOE, JANE", "Common Stock", "A", "9000.0", "0.0"),
+        row("2026-05-10", "CEO, THE", "Common Stock", "D", "2000.0", "45.0"),
+        // Option exercise: derivative security type.
+        row("2026-02-01", "CEO, THE", "Stock Option (Right to Buy)", "A", "4000.0", "10.0"),
+        row("2025-12-01", "ROE, RICH", "Common Stock", "D", "100.0", "30.0"),
+        // Outside the 12-month window.
+        row("2025-09-01", "DOE, JANE", "Common Stock", "A", "7000.0", "20.0"),
+        // Malformed rows are skipped.
+        row("2026-04-01", "X", "Common Stock", "?", "1.0", "1.0"),
+        row("not a date", "X", "Common Stock", "A", "1.0", "1.0"),
+    ]})
+}
+
+#[test]
+fn parses_rows_and_flags_open_market_trades() {
+    let rows = parse_transactions(&payload());
+    assert_eq!(rows.len(), 7);
+    assert_eq!(rows[0].side, InsiderSide::Buy);
+    assert_eq!(rows[0].title.as_deref(), Some("Director"));
+    assert!(rows[0].open_market);
+    assert!(!rows[2].open_market);
+    assert!(!rows[4].open_market);
+    assert!(parse_transactions(&json!({})).is_empty());
+}
+
+#[test]
+fn nets_open_market_activity_over_trailing_windows() {
+    let activity = summarize("ACME", parse_transactions(&payload()), day("2026-10-14"));
+    assert_eq!(activity.transactions.len(), 6);
+    assert_eq!(activity.transactions[0].date, day("2026-09-20"));
+
+    let months: Vec<_> = activity.windows.iter().map(|w| w.months).collect();
+    assert_eq!(months, vec![3, 6, 12]);
+
+    let q = &activity.windows[0];
+    assert_eq!((q.buy_shares, q.sell_shares), (1_500.0, 0.0));
+    assert_eq!(q.net_value, 70_000.0);
+    assert_eq!((q.buyers, q.sellers, q.transactions), (2, 0, 2));
+
+    // Six months picks up the CEO's May sale.
+    let half = &activity.windows[1];
+    assert_eq!(half.net_shares, -500.0);
+    assert_eq!(half.net_value, 70_000.0 - 90_000.0);
+
+    let year = &activity.windows[2];
+    assert_eq!(year.sell_value, 93_000.0);
+    assert_eq!((year.buyers, year.sellers, year.transactions), (2, 2, 4));
+}
+
+struct CountingHttp {
+    calls: AtomicUsize,
+    fail: bool,
+}
+
+#[async_trait]
+impl AvHttp for CountingHttp {
+    async fn fetch(&self, url: &str) -> Result<Value, AvHttpError> {
+        assert!(
+            url.contains("function=INSIDER_TRANSACTIONS&symbol=ACME"),
+            "{url}"
+        );
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if self.fail {
+            return Ok(json!({ "Information": "daily limit reached" }));
+        }
+        Ok(payload())
+    }
+}
+
+#[tokio::test]
+async fn av_feed_caches_payloads_and_serves_stale_rows_on_failure() {
+    let dir = TempDir::new().unwrap();
+    let http = Arc::new(CountingHttp {
+        calls: AtomicUsize::new(0),
+        fail: false,
+    });
+    let feed = AlphaVantageInsiderFeed::new(http.clone(), "k".to_string())
+        .with_cache(CacheService::new(dir.path()).unwrap());
+    assert_eq!(feed.transactions("ACME").await.unwrap().len(), 7);
+    assert_eq!(feed.transactions("ACME").await.unwrap().len(), 7);
+    assert_eq!(http.calls.load(Ordering::SeqCst), 1);
+
+    // A zero TTL forces a refetch; the rate-limit payload falls back.
+    let failing = Arc::new(CountingHttp {
+        calls: AtomicUsize::new(0),
+        fail: true,
+    });
+    let expired = CacheService::with_ttl(dir.path(), std::time::Duration::ZERO).unwrap();
+    let feed = AlphaVantageInsiderFeed::new(failing.clone(), "k".to_string()).with_cache(expired);
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    assert_eq!(feed.transactions("ACME").await.unwrap().len(), 7);
+    assert_eq!(failing.calls.load(Ordering::SeqCst), 1);
+
+    let uncached = AlphaVantageInsiderFeed::new(failing, "k".to_string());
+    assert!(uncached.transactions("ACME").await.is_err());
+}
@@ -0,0 +1,141 @@
+//! 52-week implied-volatility rank and percentile per symbol.
+//!
+//! The series is IBKR's daily `OPTION_IMPLIED_VOLATILITY` history for
+//! the underlying (a 30-day constant-maturity IV, annualized, as a
+//! fraction), read through the historical bar cache.
+//!
+//! - **IV rank**: where today's IV sits between the year's low (0) and
+//!   high (100).
+//! - **IV percentile**: the share of the year's days that closed with
+//!   a lower IV.
+//!
+//! Rank reacts to a single spike; percentile doesn't, so both are
+//! reported. The option income helper uses the rank to flag (or, with
+//! `minIvRank`, skip) premium selling when options are cheap.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::historical::{BarSize, WhatToShow};
+use crate::services::historical_data_service::{HistoricalDataService, Lookback};
+use crate::utils::symbols;
+
+#[cfg(test)]
+mod tests;
+
+/// Trading days in the ranking window.
+const WINDOW: usize = 252;
+/// Fewer daily observations than this and the range isn't meaningful.
+const MIN_OBSERVATIONS: usize = 60;
+/// Calendar days fetched to cover `WINDOW`.
+const LOOKBACK_DAYS: u32 = 366;
+
+/// Trait seam for the IV history read. Production is the cached
+/// `HistoricalDataService`; tests inject a series.
+#[async_trait]
+pub trait IvHistory: Send + Sync {
+    /// `(bar time, implied volatility)`, oldest first.
+    async fn implied_vol(&self, symbol: &str) -> Result<Vec<(String, f64)>, IbkrError>;
+}
+
+#[async_trait]
+impl IvHistory for HistoricalDataService {
+    async fn implied_vol(&self, symbol: &str) -> Result<Vec<(String, f64)>, IbkrError> {
+        let bars = self
+            .fetch_series(
+                symbol,
+                BarSize::Day1,
+                WhatToShow::OptionImpliedVolatility,
+                Lookback::Days(LOOKBACK_DAYS),
+            )
+            .await?;
+        Ok(bars.into_iter().map(|b| (b.time, b.close)).collect())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IvRank {
+    pub symbol: String,
+    /// Annualized, as a fraction (0.32 = 32%).
+    pub current_iv: f64,
+    pub low_iv: f64,
+    pub high_iv: f64,
+    /// 0–100.
+    pub iv_rank: f64,
+    /// 0–100.
+    pub iv_percentile: f64,
+    pub observations: usize,
+    /// Bar time of `current_iv`.
+    pub as_of: String,
+}
+
+#[derive(Error, Debug)]
+pub enum IvRankError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+    #[error("{0}")]
+    Invalid(String),
+}
+
+pub struct IvRankService {
+    history: Arc<dyn IvHistory>,
+}
+
+impl IvRankService {
+    pub fn new(history: Arc<dyn IvHistory>) -> Self {
+        Self { history }
+    }
+
+    pub async fn rank(&self, symbol: &str) -> Result<IvRank, IvRankError> {
+        let symbol = symbols::normalize(symbol);
+        if symbol.is_empty() {
+            return Err(IvRankError::Invalid("symbol is required".into()));
+        }
+        let series = self.history.implied_vol(&symbol).await?;
+        iv_rank(&symbol, &series).ok_or_else(|| {
+            IvRankError::Invalid(format!(
+                "{symbol} has too little implied-volatility history (need {MIN_OBSERVATIONS} days)"
+            ))
+        })
+    }
+}
+
+/// Rank and percentile of the last observation over the trailing
+/// `WINDOW` days. Non-positive values (no options that day) are
+/// dropped.
+pub fn iv_rank(symbol: &str, series: &[(String, f64)]) -> Option<IvRank> {
+    let valid: Vec<&(String, f64)> = series
+        .iter()
+        .filter(|(_, iv)| iv.is_finite() && *iv > 0.0)
+        .collect();
+    let window = &valid[valid.len().saturating_sub(WINDOW)..];
+    if window.len() < MIN_OBSERVATIONS {
+        return None;
+    }
+    let (as_of, current) = window.last().map(|(t, iv)| (t.clone(), *iv))?;
+    let low = window
+        .iter()
+        .map(|(_, iv)| *iv)
+        .fold(f64::INFINITY, f64::min);
+    let high = window.iter().map(|(_, iv)| *iv).fold(0.0, f64::max);
+    let lower = window.iter().filter(|(_, iv)| *iv < current).count();
+    Some(IvRank {
+        symbol: symbol.to_string(),
+        current_iv: current,
+        low_iv: low,
+        high_iv: high,
+        iv_rank: if high > low {
+            (current - low) / (high - low) * 100.0
+        } else {
+            0.0
+        },
+        iv_percentile: lower as f64 / (window.len() - 1) as f64 * 100.0,
+        observations: window.len(),
+        as_of,
+    })
+}
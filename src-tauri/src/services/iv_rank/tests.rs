@@ -0,0 +1,40 @@
+use super::*;
+
+fn series(values: impl IntoIterator<Item = f64>) -> Vec<(String, f64)> {
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(i, iv)| (format!("day{i:03}"), iv))
+        .collect()
+}
+
+#[test]
+fn ranks_the_last_value_in_the_trailing_year() {
+    // 300 days: an old 0.90 spike falls outside the 252-day window;
+    // inside it IV runs 0.20..0.45 once, then today's 0.3025.
+    let mut values = vec![0.90; 48];
+    values.extend((0..251).map(|i| 0.20 + 0.25 * i as f64 / 250.0));
+    values.push(0.3025);
+    let rank = iv_rank("AAPL", &series(values)).unwrap();
+
+    assert_eq!(rank.observations, 252);
+    assert_eq!(rank.as_of, "day299");
+    assert_eq!(rank.low_iv, 0.20);
+    assert!((rank.high_iv - 0.45).abs() < 1e-12);
+    assert!((rank.iv_rank - 41.0).abs() < 1e-9);
+    // 103 of the other 251 days closed lower.
+    assert!((rank.iv_percentile - 103.0 / 251.0 * 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn needs_enough_history_and_skips_empty_days() {
+    let short = series((0..59).map(|i| 0.2 + i as f64 / 1000.0));
+    assert!(iv_rank("NEW", &short).is_none());
+
+    let mut gappy = series((0..60).map(|_| 0.25));
+    gappy.push(("day060".to_string(), 0.0));
+    let rank = iv_rank("FLAT", &gappy).unwrap();
+    assert_eq!(rank.observations, 60);
+    assert_eq!(rank.iv_rank, 0.0);
+    assert_eq!(rank.iv_percentile, 0.0);
+}
@@ -0,0 +1,237 @@
+//! In-process registry for long-running background jobs.
+//!
+//! A job is started with [`JobRegistry::start`], which hands back a
+//! [`JobHandle`] the worker uses to report progress, poll for
+//! cancellation and record its outcome. Every progress step and the
+//! terminal transition emit [`AppEvent::JobProgress`] so the UI can
+//! render a progress bar without polling; [`JobRegistry::list`] is the
+//! pull-side view for a window that opens mid-run.
+//!
+//! Cancellation is cooperative: [`JobRegistry::cancel`] raises a flag
+//! and the worker is expected to check [`JobHandle::is_cancelled`]
+//! between steps (one ticker, one sheet) and stop with whatever it has.
+//! Jobs live in memory only — a restart forgets them. The newest
+//! [`RETAINED_FINISHED`] finished jobs are kept for `list`.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use chrono::Utc;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::events::{AppEvent, EventEmitter};
+
+#[cfg(test)]
+mod tests;
+
+/// Finished jobs kept around for `list`; older ones are dropped.
+pub const RETAINED_FINISHED: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JobInfo {
+    pub id: u64,
+    /// What the job does, e.g. `"portfolio_analysis"`.
+    pub kind: String,
+    pub status: JobStatus,
+    pub done: u32,
+    pub total: u32,
+    /// Label of the step in progress (ticker, sheet name).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<String>,
+    pub started_at: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Worker output, kept for completed and cancelled (partial) jobs.
+    /// Omitted from `JobProgress` events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+}
+
+struct Entry {
+    info: JobInfo,
+    cancel: Arc<AtomicBool>,
+}
+
+pub struct JobRegistry {
+    jobs: Mutex<BTreeMap<u64, Entry>>,
+    next_id: AtomicU64,
+    emitter: Arc<EventEmitter>,
+}
+
+impl JobRegistry {
+    pub fn new(emitter: Arc<EventEmitter>) -> Self {
+        Self {
+            jobs: Mutex::new(BTreeMap::new()),
+            next_id: AtomicU64::new(1),
+            emitter,
+        }
+    }
+
+    /// Register a running job of `total` steps (`0` when not yet known).
+    pub async fn start(self: &Arc<Self>, kind: &str, total: u32) -> JobHandle {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let cancel = Arc::new(AtomicBool::new(false));
+        let info = JobInfo {
+            id,
+            kind: kind.to_string(),
+            status: JobStatus::Running,
+            done: 0,
+            total,
+            step: None,
+            started_at: Utc::now().timestamp(),
+            finished_at: None,
+            error: None,
+            result: None,
+        };
+        self.jobs.lock().await.insert(
+            id,
+            Entry {
+                info: info.clone(),
+                cancel: Arc::clone(&cancel),
+            },
+        );
+        self.emit(info).await;
+        JobHandle {
+            id,
+            registry: Arc::clone(self),
+            cancel,
+        }
+    }
+
+    /// Every known job, newest first.
+    pub async fn list(&self) -> Vec<JobInfo> {
+        self.jobs
+            .lock()
+            .await
+            .values()
+            .rev()
+            .map(|e| e.info.clone())
+            .collect()
+    }
+
+    pub async fn get(&self, id: u64) -> Option<JobInfo> {
+        self.jobs.lock().await.get(&id).map(|e| e.info.clone())
+    }
+
+    /// Ask job `id` to stop. `false` when it is unknown or already done.
+    pub async fn cancel(&self, id: u64) -> bool {
+        match self.jobs.lock().await.get(&id) {
+            Some(e) if e.info.status == JobStatus::Running => {
+                e.cancel.store(true, Ordering::Relaxed);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    async fn update(&self, id: u64, f: impl FnOnce(&mut JobInfo)) {
+        let info = {
+            let mut jobs = self.jobs.lock().await;
+            let Some(entry) = jobs.get_mut(&id) else {
+                return;
+            };
+            f(&mut entry.info);
+            let info = entry.info.clone();
+            if info.status != JobStatus::Running {
+                prune(&mut jobs);
+            }
+            info
+        };
+        self.emit(info).await;
+    }
+
+    async fn emit(&self, mut job: JobInfo) {
+        job.result = None;
+        if let Err(e) = self.emitter.emit(AppEvent::JobProgress { job }).await {
+            warn!("JobProgress emit failed: {e}");
+        }
+    }
+}
+
+/// Drop the oldest finished jobs beyond [`RETAINED_FINISHED`].
+fn prune(jobs: &mut BTreeMap<u64, Entry>) {
+    let finished: Vec<u64> = jobs
+        .iter()
+        .filter(|(_, e)| e.info.status != JobStatus::Running)
+        .map(|(id, _)| *id)
+        .collect();
+    let excess = finished.len().saturating_sub(RETAINED_FINISHED);
+    for id in finished.into_iter().take(excess) {
+        jobs.remove(&id);
+    }
+}
+
+/// Worker side of a job. Terminal methods consume the handle.
+pub struct JobHandle {
+    id: u64,
+    registry: Arc<JobRegistry>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl JobHandle {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// `done` of `total` steps finished; `step` is what runs next. The
+    /// total is re-sent each time because some workers only learn it
+    /// once running (the position count, the sheet list).
+    pub async fn progress(&self, done: u32, total: u32, step: Option<String>) {
+        self.registry
+            .update(self.id, |info| {
+                info.done = done;
+                info.total = total;
+                info.step = step;
+            })
+            .await;
+    }
+
+    /// Record the outcome: `Cancelled` if a cancel was requested while
+    /// running (the result is then partial), `Completed` otherwise.
+    pub async fn finish(self, result: serde_json::Value) {
+        let status = if self.is_cancelled() {
+            JobStatus::Cancelled
+        } else {
+            JobStatus::Completed
+        };
+        self.registry
+            .update(self.id, |info| {
+                info.status = status;
+                info.step = None;
+                info.finished_at = Some(Utc::now().timestamp());
+                info.result = Some(result);
+            })
+            .await;
+    }
+
+    pub async fn fail(self, error: String) {
+        self.registry
+            .update(self.id, |info| {
+                info.status = JobStatus::Failed;
+                info.step = None;
+                info.finished_at = Some(Utc::now().timestamp());
+                info.error = Some(error);
+            })
+            .await;
+    }
+}
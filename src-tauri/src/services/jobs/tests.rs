@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::events::{AppEvent, EventEmitter};
+
+use super::{JobRegistry, JobStatus, RETAINED_FINISHED};
+
+fn registry() -> (Arc<EventEmitter>, Arc<JobRegistry>) {
+    let emitter = Arc::new(EventEmitter::for_capture());
+    (Arc::clone(&emitter), Arc::new(JobRegistry::new(emitter)))
+}
+
+#[tokio::test]
+async fn progress_and_completion_emit_events_without_the_result() {
+    let (emitter, jobs) = registry();
+    let job = jobs.start("export", 0).await;
+    let id = job.id();
+    job.progress(1, 3, Some("NVDA".into())).await;
+    job.finish(json!({ "rows": 3 })).await;
+
+    let info = jobs.get(id).await.unwrap();
+    assert_eq!(info.status, JobStatus::Completed);
+    assert_eq!((info.done, info.total), (1, 3));
+    assert_eq!(info.result, Some(json!({ "rows": 3 })));
+    assert!(!jobs.cancel(id).await, "finished jobs can't be cancelled");
+
+    let events = emitter.captured().await;
+    assert_eq!(events.len(), 3);
+    match &events[1] {
+        AppEvent::JobProgress { job } => assert_eq!(job.step.as_deref(), Some("NVDA")),
+        other => panic!("unexpected event {other:?}"),
+    }
+    match &events[2] {
+        AppEvent::JobProgress { job } => {
+            assert_eq!(job.status, JobStatus::Completed);
+            assert!(job.result.is_none());
+        }
+        other => panic!("unexpected event {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn cancel_is_cooperative_and_marks_partial_result() {
+    let (_emitter, jobs) = registry();
+    let job = jobs.start("export", 2).await;
+    let id = job.id();
+    assert!(jobs.cancel(id).await);
+    assert!(job.is_cancelled());
+    // Still running until the worker notices.
+    assert_eq!(jobs.get(id).await.unwrap().status, JobStatus::Running);
+    job.finish(json!([])).await;
+    assert_eq!(jobs.get(id).await.unwrap().status, JobStatus::Cancelled);
+
+    let failing = jobs.start("export", 1).await;
+    let failed_id = failing.id();
+    failing.fail("sheet quota".into()).await;
+    let list = jobs.list().await;
+    assert_eq!(list[0].id, failed_id, "newest first");
+    assert_eq!(list[0].error.as_deref(), Some("sheet quota"));
+    assert!(!jobs.cancel(999).await);
+}
+
+#[tokio::test]
+async fn retains_a_bounded_number_of_finished_jobs() {
+    let (_emitter, jobs) = registry();
+    let running = jobs.start("long", 0).await;
+    for _ in 0..RETAINED_FINISHED + 5 {
+        jobs.start("short", 0).await.finish(json!(null)).await;
+    }
+    let list = jobs.list().await;
+    assert_eq!(list.len(), RETAINED_FINISHED + 1);
+    assert!(list.iter().any(|j| j.id == running.id()));
+}
@@ -0,0 +1,249 @@
+//! Liquidity: how long each holding would take to sell.
+//!
+//! A name's average daily volume (ADV) is the mean share volume of its
+//! last `liquidity.adv_sessions` daily bars. Selling no more than
+//! `liquidity.participation_pct` percent of that a day, a position of
+//! `shares` takes `shares / (ADV × participation)` days to exit. Holdings
+//! that take longer than `liquidity.max_days` are flagged as illiquid.
+//!
+//! The same cap bounds new orders: with [`RiskEngine::with_liquidity`],
+//! sizing clips a setup to the shares that could be sold again within
+//! `max_days` (see `risk_engine::cap_to_liquidity`).
+//!
+//! Options aren't measured (their volume is in contracts, and thin), and
+//! neither are stocks with fewer than [`MIN_SESSIONS`] bars. Daily bars
+//! come from the cached `HistoricalDataService`. Nothing is stored:
+//! `get_liquidity` recomputes on every call for the risk report.
+//!
+//! [`RiskEngine::with_liquidity`]: crate::services::risk_engine::RiskEngine::with_liquidity
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::config::AppConfig;
+use crate::ibkr::error::IbkrError;
+use crate::services::historical_data_service::DailyBars;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+
+#[cfg(test)]
+mod tests;
+
+/// Fewer daily bars leave a name unmeasured.
+pub const MIN_SESSIONS: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidityConfig {
+    /// Daily bars the average volume is taken over.
+    #[serde(default = "default_adv_sessions")]
+    pub adv_sessions: u32,
+    /// Share of the average daily volume a day's selling may take, in
+    /// percent.
+    #[serde(default = "default_participation_pct")]
+    pub participation_pct: f64,
+    /// Holdings slower to exit are flagged, and new orders are sized to
+    /// exit within it.
+    #[serde(default = "default_max_days")]
+    pub max_days: f64,
+}
+
+fn default_adv_sessions() -> u32 {
+    20
+}
+
+fn default_participation_pct() -> f64 {
+    10.0
+}
+
+fn default_max_days() -> f64 {
+    3.0
+}
+
+impl Default for LiquidityConfig {
+    fn default() -> Self {
+        Self {
+            adv_sessions: default_adv_sessions(),
+            participation_pct: default_participation_pct(),
+            max_days: default_max_days(),
+        }
+    }
+}
+
+impl LiquidityConfig {
+    /// Shares a day's selling may take out of `adv`.
+    fn daily_capacity(&self, adv: f64) -> f64 {
+        adv * self.participation_pct / 100.0
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum LiquidityError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+}
+
+/// Mean share volume and mean dollar volume of `bars`; `None` below
+/// [`MIN_SESSIONS`] bars or without volume.
+pub fn average_daily_volume(bars: &[(f64, f64)]) -> Option<(f64, f64)> {
+    if bars.len() < MIN_SESSIONS {
+        return None;
+    }
+    let n = bars.len() as f64;
+    let shares = bars.iter().map(|(_, v)| v).sum::<f64>() / n;
+    let dollars = bars.iter().map(|(c, v)| c * v).sum::<f64>() / n;
+    (shares > 0.0).then_some((shares, dollars))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionLiquidity {
+    pub symbol: String,
+    /// Negative when short.
+    pub shares: f64,
+    pub market_value: f64,
+    /// Average daily volume, in shares.
+    pub adv: f64,
+    pub adv_dollars: f64,
+    /// The position as a percentage of one day's volume.
+    pub pct_of_adv: f64,
+    pub days_to_liquidate: f64,
+    /// Slower to exit than `liquidity.max_days`.
+    pub illiquid: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidityReport {
+    pub account: String,
+    pub at: i64,
+    pub participation_pct: f64,
+    pub max_days: f64,
+    /// Slowest to exit first.
+    pub positions: Vec<PositionLiquidity>,
+    /// Symbols of the illiquid positions, slowest first.
+    pub illiquid: Vec<String>,
+    /// Options, and stocks without enough volume history.
+    pub unmeasured: Vec<String>,
+}
+
+pub struct LiquidityService {
+    account: Arc<dyn AccountSource>,
+    positions: Arc<dyn OpenPositionsSource>,
+    bars: Arc<dyn DailyBars>,
+    config: Arc<RwLock<AppConfig>>,
+}
+
+impl LiquidityService {
+    pub fn new(
+        account: Arc<dyn AccountSource>,
+        positions: Arc<dyn OpenPositionsSource>,
+        bars: Arc<dyn DailyBars>,
+        config: Arc<RwLock<AppConfig>>,
+    ) -> Self {
+        Self {
+            account,
+            positions,
+            bars,
+            config,
+        }
+    }
+
+    /// Days to liquidate each open stock position of `account` (the
+    /// current account when `None`).
+    pub async fn report(&self, account: Option<String>) -> Result<LiquidityReport, LiquidityError> {
+        let config = self.config.read().await.liquidity.clone();
+        let account = match account.filter(|a| !a.trim().is_empty()) {
+            Some(a) => a.trim().to_string(),
+            None => self.account.current_account().await?,
+        };
+
+        let mut positions = Vec::new();
+        let mut unmeasured = Vec::new();
+        for position in self.positions.list_open(&account).await? {
+            let label = if position.local_symbol.is_empty() {
+                position.symbol.clone()
+            } else {
+                position.local_symbol.clone()
+            };
+            if position.contract_type != "STK" || position.position == 0.0 {
+                unmeasured.push(label);
+                continue;
+            }
+            let bars = match self
+                .daily_volume(&position.symbol, config.adv_sessions as usize)
+                .await
+            {
+                Ok(bars) => bars,
+                Err(e) => {
+                    warn!("liquidity: bars for {} failed: {e}", position.symbol);
+                    unmeasured.push(label);
+                    continue;
+                }
+            };
+            let Some((adv, adv_dollars)) = average_daily_volume(&bars) else {
+                unmeasured.push(label);
+                continue;
+            };
+            let days = position.position.abs() / config.daily_capacity(adv);
+            positions.push(PositionLiquidity {
+                symbol: position.symbol.clone(),
+                shares: position.position,
+                market_value: position.market_value,
+                adv,
+                adv_dollars,
+                pct_of_adv: position.position.abs() / adv * 100.0,
+                days_to_liquidate: days,
+                illiquid: days > config.max_days,
+            });
+        }
+        positions.sort_by(|a, b| b.days_to_liquidate.total_cmp(&a.days_to_liquidate));
+        let illiquid = positions
+            .iter()
+            .filter(|p| p.illiquid)
+            .map(|p| p.symbol.clone())
+            .collect();
+        Ok(LiquidityReport {
+            account,
+            at: Utc::now().timestamp(),
+            participation_pct: config.participation_pct,
+            max_days: config.max_days,
+            positions,
+            illiquid,
+            unmeasured,
+        })
+    }
+
+    /// The most shares of `symbol` that could be sold again within
+    /// `liquidity.max_days`; `None` without enough volume history.
+    pub async fn max_order_shares(&self, symbol: &str) -> Result<Option<f64>, LiquidityError> {
+        let config = self.config.read().await.liquidity.clone();
+        let bars = self
+            .daily_volume(symbol, config.adv_sessions as usize)
+            .await?;
+        Ok(
+            average_daily_volume(&bars)
+                .map(|(adv, _)| config.daily_capacity(adv) * config.max_days),
+        )
+    }
+
+    /// `(close, volume)` of the last `sessions` daily bars, ascending;
+    /// fewer without that much history.
+    async fn daily_volume(
+        &self,
+        symbol: &str,
+        sessions: usize,
+    ) -> Result<Vec<(f64, f64)>, IbkrError> {
+        let bars = self.bars.daily_bars(symbol, sessions).await?;
+        let skip = bars.len().saturating_sub(sessions);
+        Ok(bars
+            .into_iter()
+            .skip(skip)
+            .map(|b| (b.close, b.volume as f64))
+            .collect())
+    }
+}
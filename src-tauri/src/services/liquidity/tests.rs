@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use super::*;
+use crate::ibkr::types::{HistoricalBar, Position};
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// `(close, volume)` bars per symbol, most recent last.
+struct StubVolume(HashMap<String, Vec<(f64, f64)>>);
+
+#[async_trait]
+impl DailyBars for StubVolume {
+    async fn daily_bars(
+        &self,
+        symbol: &str,
+        _sessions: usize,
+    ) -> Result<Vec<HistoricalBar>, IbkrError> {
+        let bars = self
+            .0
+            .get(symbol)
+            .ok_or_else(|| IbkrError::RequestFailed(format!("no bars for {symbol}")))?;
+        Ok(bars
+            .iter()
+            .enumerate()
+            .map(|(i, &(close, volume))| bar(format!("d{i:04}"), close, volume as i64))
+            .collect())
+    }
+}
+
+fn bar(time: String, close: f64, volume: i64) -> HistoricalBar {
+    HistoricalBar {
+        time,
+        open: close,
+        high: close,
+        low: close,
+        close,
+        volume,
+        wap: close,
+        count: 0,
+    }
+}
+
+fn position(symbol: &str, kind: &str, shares: f64) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: shares,
+        market_value: shares * 10.0,
+        contract_type: kind.to_string(),
+        ..Default::default()
+    }
+}
+
+fn service() -> LiquidityService {
+    // THIN traded 50k shares a day until its last 20 sessions, 1k since.
+    let mut thin = vec![(10.0, 50_000.0); 30];
+    thin.extend(vec![(10.0, 1_000.0); 20]);
+    let volume = HashMap::from([
+        ("BIG".to_string(), vec![(10.0, 1_000_000.0); 50]),
+        ("THIN".to_string(), thin),
+        ("NEW".to_string(), vec![(10.0, 5_000.0); 3]),
+    ]);
+    let mut option = position("BIG", "OPT", 10.0);
+    option.local_symbol = "BIG   260619C00010000".to_string();
+    LiquidityService::new(
+        Arc::new(FixedAccount),
+        Arc::new(StubPositions(vec![
+            position("BIG", "STK", 50_000.0),
+            position("THIN", "STK", -600.0),
+            position("NEW", "STK", 100.0),
+            position("NOBARS", "STK", 100.0),
+            option,
+        ])),
+        Arc::new(StubVolume(volume)),
+        Arc::new(RwLock::new(AppConfig::default())),
+    )
+}
+
+#[tokio::test]
+async fn report_ranks_holdings_by_days_to_liquidate() {
+    let report = service().report(None).await.unwrap();
+    assert_eq!(report.account, "DU1");
+    assert_eq!(report.participation_pct, 10.0);
+
+    // At 10% of 1k a day, 600 shares short take 6 days: over the 3-day
+    // limit. BIG's 50k shares are 5% of a day, half a day at 10%.
+    let symbols: Vec<&str> = report.positions.iter().map(|p| p.symbol.as_str()).collect();
+    assert_eq!(symbols, ["THIN", "BIG"]);
+    let thin = &report.positions[0];
+    assert!((thin.adv - 1_000.0).abs() < 1e-9);
+    assert!((thin.adv_dollars - 10_000.0).abs() < 1e-9);
+    assert!((thin.pct_of_adv - 60.0).abs() < 1e-9);
+    assert!((thin.days_to_liquidate - 6.0).abs() < 1e-9);
+    assert!(thin.illiquid);
+    let big = &report.positions[1];
+    assert!((big.days_to_liquidate - 0.5).abs() < 1e-9);
+    assert!(!big.illiquid);
+
+    assert_eq!(report.illiquid, ["THIN"]);
+    assert_eq!(
+        report.unmeasured,
+        ["NEW", "NOBARS", "BIG   260619C00010000"]
+    );
+}
+
+#[tokio::test]
+async fn orders_are_capped_at_what_max_days_of_volume_absorbs() {
+    let service = service();
+    // 10% of 1k a day over 3 days.
+    let cap = service.max_order_shares("THIN").await.unwrap().unwrap();
+    assert!((cap - 300.0).abs() < 1e-9);
+    assert_eq!(service.max_order_shares("NEW").await.unwrap(), None);
+    assert!(service.max_order_shares("NOBARS").await.is_err());
+
+    service.config.write().await.liquidity.max_days = 1.0;
+    let cap = service.max_order_shares("BIG").await.unwrap().unwrap();
+    assert!((cap - 100_000.0).abs() < 1e-9);
+}
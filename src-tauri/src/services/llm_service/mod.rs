@@ -198,7 +198,19 @@ impl LlmService {
         // the kill-switch is on the verge of tripping.
         let max_per_call = (self.daily_budget_usd - cost_today).clamp(0.001, 1.0);
 
-        let parsed = self.backend.call(&req, max_per_call).await?;
+        let started = std::time::Instant::now();
+        let result = self.backend.call(&req, max_per_call).await;
+        let kind_label = req.kind.as_str();
+        metrics::histogram!(crate::telemetry::LLM_CALL_DURATION, "kind" => kind_label)
+            .record(started.elapsed().as_secs_f64());
+        let outcome = if result.is_ok() { "ok" } else { "error" };
+        metrics::counter!(
+            crate::telemetry::LLM_CALLS_TOTAL,
+            "kind" => kind_label,
+            "outcome" => outcome
+        )
+        .increment(1);
+        let parsed = result?;
 
         // Cost: prefer the backend's authoritative figure (CLI path),
         // fall back to the local pricing table (API path + CLI when
@@ -0,0 +1,145 @@
+//! [`AlphaVantageMacroFeed`] — economic indicators from Alpha Vantage.
+//!
+//! Shares the AV rate limiter with the fundamentals adapter. Each series
+//! is cached on disk for [`CACHE_TTL`] (daily series move once a day,
+//! CPI once a month); a failed or rate-limited fetch serves the last
+//! cached payload regardless of age.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde_json::Value;
+use tracing::warn;
+
+use crate::middleware::AlphaVantageRateLimiter;
+use crate::services::cache_service::CacheService;
+use crate::services::financial_data_service::AvHttp;
+
+use super::{MacroError, MacroFeed, MacroSeries, Observation};
+
+pub const CACHE_TTL: Duration = Duration::from_secs(12 * 60 * 60);
+
+const BASE_URL: &str = "https://www.alphavantage.co/query";
+
+/// Query string (without the key) and cache key for `series`.
+fn endpoint(series: MacroSeries) -> (&'static str, &'static str) {
+    match series {
+        MacroSeries::FedFunds => ("function=FEDERAL_FUNDS_RATE&interval=daily", "fed_funds"),
+        MacroSeries::Treasury3M => (
+            "function=TREASURY_YIELD&interval=daily&maturity=3month",
+            "treasury_3m",
+        ),
+        MacroSeries::Treasury2Y => (
+            "function=TREASURY_YIELD&interval=daily&maturity=2year",
+            "treasury_2y",
+        ),
+        MacroSeries::Treasury10Y => (
+            "function=TREASURY_YIELD&interval=daily&maturity=10year",
+            "treasury_10y",
+        ),
+        MacroSeries::Cpi => ("function=CPI&interval=monthly", "cpi"),
+    }
+}
+
+pub struct AlphaVantageMacroFeed {
+    http: Arc<dyn AvHttp>,
+    api_key: String,
+    base_url: String,
+    cache: Option<CacheService>,
+    rate_limiter: Option<Arc<AlphaVantageRateLimiter>>,
+}
+
+impl AlphaVantageMacroFeed {
+    pub fn new(http: Arc<dyn AvHttp>, api_key: String) -> Self {
+        Self {
+            http,
+            api_key,
+            base_url: BASE_URL.to_string(),
+            cache: None,
+            rate_limiter: None,
+        }
+    }
+
+    pub fn with_cache(mut self, cache: CacheService) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, limiter: Arc<AlphaVantageRateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    async fn fetch(&self, query: &str) -> Result<Value, MacroError> {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
+        }
+        let url = format!("{}?{query}&apikey={}", self.base_url, self.api_key);
+        let json = self
+            .http
+            .fetch(&url)
+            .await
+            .map_err(|e| MacroError::Upstream(e.to_string()))?;
+        for key in ["Error Message", "Note", "Information"] {
+            if let Some(msg) = json.get(key).and_then(|v| v.as_str()) {
+                return Err(MacroError::Upstream(msg.to_string()));
+            }
+        }
+        Ok(json)
+    }
+}
+
+#[async_trait]
+impl MacroFeed for AlphaVantageMacroFeed {
+    async fn series(&self, series: MacroSeries) -> Result<Vec<Observation>, MacroError> {
+        let (query, key) = endpoint(series);
+        if let Some(cached) = self.cache.as_ref().and_then(|c| c.read::<Value>(key).ok()) {
+            return Ok(parse_series(&cached));
+        }
+        match self.fetch(query).await {
+            Ok(json) => {
+                if let Some(c) = &self.cache {
+                    if let Err(e) = c.write(key, &json) {
+                        warn!("macro cache write failed for {key}: {e}");
+                    }
+                }
+                Ok(parse_series(&json))
+            }
+            Err(e) => match self
+                .cache
+                .as_ref()
+                .and_then(|c| c.read_ignoring_ttl::<Value>(key).ok())
+            {
+                Some((json, age)) => {
+                    warn!("macro: {e}; serving cached {key} (age {age}s)");
+                    Ok(parse_series(&json))
+                }
+                None => Err(e),
+            },
+        }
+    }
+}
+
+/// `data` rows of an AV economic-indicator payload, newest first. AV
+/// marks missing days with `"."`; those rows are skipped.
+pub(super) fn parse_series(json: &Value) -> Vec<Observation> {
+    let Some(rows) = json.get("data").and_then(|d| d.as_array()) else {
+        return Vec::new();
+    };
+    let mut out: Vec<Observation> = rows
+        .iter()
+        .filter_map(|row| {
+            let date = row.get("date")?.as_str()?;
+            let value = row.get("value")?.as_str()?.trim().parse::<f64>().ok()?;
+            Some(Observation {
+                date: NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?,
+                value,
+            })
+        })
+        .filter(|o| o.value.is_finite())
+        .collect();
+    out.sort_by_key(|o| std::cmp::Reverse(o.date));
+    out
+}
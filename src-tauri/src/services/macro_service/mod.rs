@@ -0,0 +1,185 @@
+//! Rate environment: fed funds, Treasury yields and CPI inflation.
+//!
+//! Series come from a [`MacroFeed`]; production wires
+//! [`alpha_vantage::AlphaVantageMacroFeed`] (`FEDERAL_FUNDS_RATE`,
+//! `TREASURY_YIELD`, `CPI`), which caches each series on disk. A
+//! snapshot costs one AV call per uncached series, so five on a cold
+//! cache.
+//!
+//! [`MacroService::snapshot`] is best-effort per series: a series that
+//! can't be read is left `None` with a note, and the call only fails
+//! when every series does.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+
+pub mod alpha_vantage;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Error)]
+pub enum MacroError {
+    #[error("macro feed: {0}")]
+    Upstream(String),
+    #[error("no macro series available: {0}")]
+    Unavailable(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MacroSeries {
+    FedFunds,
+    Treasury3M,
+    Treasury2Y,
+    Treasury10Y,
+    /// CPI-U index level (not a rate).
+    Cpi,
+}
+
+impl MacroSeries {
+    pub const ALL: [MacroSeries; 5] = [
+        MacroSeries::FedFunds,
+        MacroSeries::Treasury3M,
+        MacroSeries::Treasury2Y,
+        MacroSeries::Treasury10Y,
+        MacroSeries::Cpi,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MacroSeries::FedFunds => "fed funds",
+            MacroSeries::Treasury3M => "3-month Treasury",
+            MacroSeries::Treasury2Y => "2-year Treasury",
+            MacroSeries::Treasury10Y => "10-year Treasury",
+            MacroSeries::Cpi => "CPI",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Observation {
+    pub date: NaiveDate,
+    pub value: f64,
+}
+
+/// Latest value of one series. Rates are annual percents.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MacroReading {
+    pub value: f64,
+    pub as_of: NaiveDate,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MacroSnapshot {
+    pub fed_funds: Option<MacroReading>,
+    pub treasury_3m: Option<MacroReading>,
+    pub treasury_2y: Option<MacroReading>,
+    pub treasury_10y: Option<MacroReading>,
+    /// Year-over-year CPI change, percent.
+    pub cpi_yoy: Option<MacroReading>,
+    /// 10-year minus 2-year yield, percentage points.
+    pub curve_10y_2y: Option<f64>,
+    /// 10-year minus 3-month yield, percentage points.
+    pub curve_10y_3m: Option<f64>,
+    /// Either spread below zero.
+    pub inverted: bool,
+    /// One line per series that couldn't be read.
+    pub notes: Vec<String>,
+    /// Unix seconds.
+    pub fetched_at: i64,
+}
+
+/// Trait seam for the economic data source. Tests inject fixed series.
+#[async_trait]
+pub trait MacroFeed: Send + Sync {
+    /// Observations of `series`, newest first.
+    async fn series(&self, series: MacroSeries) -> Result<Vec<Observation>, MacroError>;
+}
+
+pub struct MacroService {
+    feed: Arc<dyn MacroFeed>,
+}
+
+impl MacroService {
+    pub fn new(feed: Arc<dyn MacroFeed>) -> Self {
+        Self { feed }
+    }
+
+    pub async fn snapshot(&self) -> Result<MacroSnapshot, MacroError> {
+        let mut notes = Vec::new();
+        let fed_funds = self.reading(MacroSeries::FedFunds, &mut notes).await;
+        let treasury_3m = self.reading(MacroSeries::Treasury3M, &mut notes).await;
+        let treasury_2y = self.reading(MacroSeries::Treasury2Y, &mut notes).await;
+        let treasury_10y = self.reading(MacroSeries::Treasury10Y, &mut notes).await;
+        let cpi_yoy = self.reading(MacroSeries::Cpi, &mut notes).await;
+        if notes.len() == MacroSeries::ALL.len() {
+            return Err(MacroError::Unavailable(notes.join("; ")));
+        }
+        let spread = |short: Option<MacroReading>| Some(treasury_10y?.value - short?.value);
+        let curve_10y_2y = spread(treasury_2y);
+        let curve_10y_3m = spread(treasury_3m);
+        Ok(MacroSnapshot {
+            fed_funds,
+            treasury_3m,
+            treasury_2y,
+            treasury_10y,
+            cpi_yoy,
+            curve_10y_2y,
+            curve_10y_3m,
+            inverted: [curve_10y_2y, curve_10y_3m]
+                .iter()
+                .flatten()
+                .any(|s| *s < 0.0),
+            notes,
+            fetched_at: Utc::now().timestamp(),
+        })
+    }
+
+    /// Latest reading of one series (for CPI, the year-over-year
+    /// change). `None` when the feed returned too few observations.
+    pub async fn latest(&self, series: MacroSeries) -> Result<Option<MacroReading>, MacroError> {
+        let obs = self.feed.series(series).await?;
+        Ok(latest_reading(series, &obs))
+    }
+
+    async fn reading(&self, series: MacroSeries, notes: &mut Vec<String>) -> Option<MacroReading> {
+        let reading = match self.latest(series).await {
+            Ok(reading) => reading,
+            Err(e) => {
+                warn!("macro: {} unavailable: {e}", series.label());
+                None
+            }
+        };
+        if reading.is_none() {
+            notes.push(format!("{} unavailable", series.label()));
+        }
+        reading
+    }
+}
+
+/// The newest observation, or for CPI the change against the reading a
+/// year before it.
+fn latest_reading(series: MacroSeries, obs: &[Observation]) -> Option<MacroReading> {
+    let latest = obs.first()?;
+    if series != MacroSeries::Cpi {
+        return Some(MacroReading {
+            value: latest.value,
+            as_of: latest.date,
+        });
+    }
+    let year_ago = latest.date.checked_sub_months(Months::new(12))?;
+    let base = obs.iter().find(|o| o.date <= year_ago)?;
+    (base.value > 0.0).then(|| MacroReading {
+        value: (latest.value / base.value - 1.0) * 100.0,
+        as_of: latest.date,
+    })
+}
@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+use crate::services::cache_service::CacheService;
+use crate::services::financial_data_service::{AvHttp, AvHttpError};
+
+use super::alpha_vantage::{parse_series, AlphaVantageMacroFeed};
+use super::{MacroError, MacroFeed, MacroSeries, MacroService, Observation};
+
+fn obs(rows: &[(&str, f64)]) -> Vec<Observation> {
+    rows.iter()
+        .map(|(d, v)| Observation {
+            date: d.parse().unwrap(),
+            value: *v,
+        })
+        .collect()
+}
+
+struct FixedFeed(HashMap<MacroSeries, Vec<Observation>>);
+
+#[async_trait]
+impl MacroFeed for FixedFeed {
+    async fn series(&self, series: MacroSeries) -> Result<Vec<Observation>, MacroError> {
+        self.0
+            .get(&series)
+            .cloned()
+            .ok_or_else(|| MacroError::Upstream("rate limited".to_string()))
+    }
+}
+
+#[tokio::test]
+async fn snapshot_reads_latest_values_curve_and_cpi_inflation() {
+    let feed = FixedFeed(HashMap::from([
+        (MacroSeries::FedFunds, obs(&[("2026-10-13", 4.33)])),
+        (MacroSeries::Treasury3M, obs(&[("2026-10-13", 4.40)])),
+        (MacroSeries::Treasury2Y, obs(&[("2026-10-13", 3.90)])),
+        (
+            MacroSeries::Treasury10Y,
+            obs(&[("2026-10-13", 4.10), ("2026-10-10", 4.00)]),
+        ),
+        (
+            MacroSeries::Cpi,
+            obs(&[
+                ("2026-09-01", 321.0),
+                ("2026-01-01", 318.0),
+                ("2025-09-01", 312.0),
+            ]),
+        ),
+    ]));
+    let snap = MacroService::new(Arc::new(feed)).snapshot().await.unwrap();
+
+    assert_eq!(snap.fed_funds.unwrap().value, 4.33);
+    assert_eq!(
+        snap.treasury_10y.unwrap().as_of,
+        "2026-10-13".parse().unwrap()
+    );
+    let cpi = snap.cpi_yoy.unwrap();
+    assert!((cpi.value - (321.0 / 312.0 - 1.0) * 100.0).abs() < 1e-9);
+    assert!((snap.curve_10y_2y.unwrap() - 0.2).abs() < 1e-9);
+    assert!((snap.curve_10y_3m.unwrap() + 0.3).abs() < 1e-9);
+    assert!(snap.inverted);
+    assert!(snap.notes.is_empty());
+}
+
+#[tokio::test]
+async fn missing_series_are_noted_and_all_missing_is_an_error() {
+    let feed = FixedFeed(HashMap::from([(
+        MacroSeries::Treasury10Y,
+        obs(&[("2026-10-13", 4.10)]),
+    )]));
+    let snap = MacroService::new(Arc::new(feed)).snapshot().await.unwrap();
+    assert!(snap.curve_10y_2y.is_none());
+    assert!(!snap.inverted);
+    assert_eq!(snap.notes.len(), 4);
+    assert!(snap.notes.contains(&"CPI unavailable".to_string()));
+
+    let empty = MacroService::new(Arc::new(FixedFeed(HashMap::new())));
+    assert!(matches!(
+        empty.snapshot().await,
+        Err(MacroError::Unavailable(_))
+    ));
+}
+
+#[test]
+fn parses_newest_first_and_skips_missing_days() {
+    let rows = parse_series(&json!({ "data": [
+        { "date": "2026-10-10", "value": "4.00" },
+        { "date": "2026-10-13", "value": "4.10" },
+        { "date": "2026-10-12", "value": "." },
+    ]}));
+    assert_eq!(rows, obs(&[("2026-10-13", 4.10), ("2026-10-10", 4.00)]));
+}
+
+struct CountingHttp(AtomicUsize);
+
+#[async_trait]
+impl AvHttp for CountingHttp {
+    async fn fetch(&self, url: &str) -> Result<Value, AvHttpError> {
+        assert!(
+            url.contains("function=TREASURY_YIELD&interval=daily&maturity=10year"),
+            "{url}"
+        );
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Ok(json!({ "data": [{ "date": "2026-10-13", "value": "4.10" }] }))
+    }
+}
+
+#[tokio::test]
+async fn av_feed_caches_each_series() {
+    let dir = TempDir::new().unwrap();
+    let http = Arc::new(CountingHttp(AtomicUsize::new(0)));
+    let feed = AlphaVantageMacroFeed::new(http.clone(), "k".to_string())
+        .with_cache(CacheService::new(dir.path()).unwrap());
+    for _ in 0..2 {
+        let rows = feed.series(MacroSeries::Treasury10Y).await.unwrap();
+        assert_eq!(rows[0].value, 4.10);
+    }
+    assert_eq!(http.0.load(Ordering::SeqCst), 1);
+}
@@ -17,6 +17,7 @@ use serde::{Deserialize, Serialize};
 use crate::ibkr::types::FundamentalData;
 use crate::storage::error::StorageError;
 use crate::storage::Db;
+use crate::utils::symbols;
 
 #[cfg(test)]
 mod tests;
@@ -64,7 +65,7 @@ impl ManualFundamentalsStore {
     /// Read the manual row for `symbol`, if present. Symbol is matched
     /// case-insensitively (uppercased before query).
     pub async fn get(&self, symbol: &str) -> Result<Option<ManualFundamentalsRow>, StorageError> {
-        let key = symbol.trim().to_uppercase();
+        let key = symbols::normalize(symbol);
         if key.is_empty() {
             return Ok(None);
         }
@@ -117,7 +118,7 @@ impl ManualFundamentalsStore {
         written_by: &str,
         written_at: i64,
     ) -> Result<UpsertOutcome, StorageError> {
-        let key = symbol.trim().to_uppercase();
+        let key = symbols::normalize(symbol);
         if key.is_empty() {
             return Err(StorageError::Migration(
                 "manual_fundamentals symbol must be non-empty".to_string(),
@@ -166,7 +167,7 @@ impl ManualFundamentalsStore {
 
     /// Remove the row for `symbol`. No-op when the row is absent.
     pub async fn clear(&self, symbol: &str) -> Result<(), StorageError> {
-        let key = symbol.trim().to_uppercase();
+        let key = symbols::normalize(symbol);
         if key.is_empty() {
             return Ok(());
         }
@@ -32,6 +32,7 @@ fn sample_fd(symbol: &str) -> FundamentalData {
             revenue: 391.0,
             net_income: 99.8,
             eps: 6.5,
+            split_adjustment: None,
         }],
         analyst_estimates: Some(AnalystEstimates {
             revenue: vec![AnalystEstimate {
@@ -51,7 +52,14 @@ fn sample_fd(symbol: &str) -> FundamentalData {
             exchange: Some("NASDAQ".into()),
             market_cap: Some("3000000000000".into()),
             dividend_yield: Some(0.005),
+            short_interest: None,
         },
+        data_quality: Vec::new(),
+        overrides: Vec::new(),
+        growth: None,
+        currency: None,
+        fx_conversion: None,
+        segments: Vec::new(),
     }
 }
 
@@ -0,0 +1,361 @@
+//! Reg-T against portfolio margin for the current book.
+//!
+//! Positions are bundled by underlying (the stock and every option on
+//! it), since both regimes margin a hedged bundle together.
+//!
+//! - **Reg-T** is IBKR's own figure: a what-if order that would close
+//!   the whole bundle (see `IbkrClient::what_if_margin`), whose margin
+//!   change, negated, is what the bundle ties up. IBKR answers under the
+//!   account's own regime, so this reads as Reg-T for a Reg-T account.
+//!   Fractional shares are rounded to whole ones for the check.
+//! - **Portfolio margin** is estimated the way the risk-based (TIMS)
+//!   model works: the bundle's worst loss as the underlying moves
+//!   through ±`margin_compare.pm_move_pct` in ten steps each way, with
+//!   options revalued from TWS model delta and gamma, and at least
+//!   `margin_compare.pm_option_floor` per option contract.
+//!
+//! The totals cover bundles measured both ways, so `freed` is the
+//! initial margin a switch to portfolio margin would release on those;
+//! negative means it would need more. Nothing is stored;
+//! `get_margin_comparison` recomputes on every call.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use crate::config::AppConfig;
+use crate::ibkr::client::IbkrClient;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::{MarginImpact, OptionContract, OrderAction, Position, WhatIfLeg};
+use crate::services::option_greeks::GreeksSource;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+
+#[cfg(test)]
+mod tests;
+
+/// Scenario steps each side of an unchanged price.
+const SCAN_STEPS: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarginCompareConfig {
+    /// Underlying move, in percent either way, that portfolio margin
+    /// is estimated over.
+    #[serde(default = "default_pm_move_pct")]
+    pub pm_move_pct: f64,
+    /// Least portfolio margin per option contract, in currency.
+    #[serde(default = "default_pm_option_floor")]
+    pub pm_option_floor: f64,
+}
+
+fn default_pm_move_pct() -> f64 {
+    15.0
+}
+
+fn default_pm_option_floor() -> f64 {
+    37.5
+}
+
+impl Default for MarginCompareConfig {
+    fn default() -> Self {
+        Self {
+            pm_move_pct: default_pm_move_pct(),
+            pm_option_floor: default_pm_option_floor(),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum MarginCompareError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+}
+
+/// Trait seam for IBKR's what-if margin check. Production is the live
+/// `IbkrClient`; tests inject impacts.
+#[async_trait]
+pub trait WhatIfMargin: Send + Sync {
+    async fn what_if_margin(
+        &self,
+        account: &str,
+        legs: Vec<WhatIfLeg>,
+    ) -> Result<MarginImpact, IbkrError>;
+}
+
+#[async_trait]
+impl WhatIfMargin for IbkrClient {
+    async fn what_if_margin(
+        &self,
+        account: &str,
+        legs: Vec<WhatIfLeg>,
+    ) -> Result<MarginImpact, IbkrError> {
+        IbkrClient::what_if_margin(self, account, legs).await
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleMargin {
+    /// Underlying.
+    pub symbol: String,
+    /// Contract keys of the bundle's positions.
+    pub legs: Vec<String>,
+    pub market_value: f64,
+    pub reg_t_initial: Option<f64>,
+    pub reg_t_maintenance: Option<f64>,
+    pub portfolio_margin: Option<f64>,
+    /// Underlying move of the worst scenario, in percent.
+    pub worst_move_pct: Option<f64>,
+    /// `reg_t_initial` less `portfolio_margin`, when both are known.
+    pub difference: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginComparison {
+    pub account: String,
+    pub at: i64,
+    pub pm_move_pct: f64,
+    /// Largest Reg-T requirement first; unmeasured ones last.
+    pub bundles: Vec<BundleMargin>,
+    /// Totals over the bundles measured both ways.
+    pub reg_t_initial: f64,
+    pub reg_t_maintenance: f64,
+    pub portfolio_margin: f64,
+    /// Initial margin portfolio margin would free; negative when it
+    /// needs more.
+    pub freed: f64,
+    /// `"SYMBOL: reason"` for each side of a bundle that couldn't be
+    /// measured.
+    pub unmeasured: Vec<String>,
+}
+
+pub struct MarginCompareService {
+    account: Arc<dyn AccountSource>,
+    positions: Arc<dyn OpenPositionsSource>,
+    what_if: Arc<dyn WhatIfMargin>,
+    greeks: Arc<dyn GreeksSource>,
+    config: Arc<RwLock<AppConfig>>,
+}
+
+impl MarginCompareService {
+    pub fn new(
+        account: Arc<dyn AccountSource>,
+        positions: Arc<dyn OpenPositionsSource>,
+        what_if: Arc<dyn WhatIfMargin>,
+        greeks: Arc<dyn GreeksSource>,
+        config: Arc<RwLock<AppConfig>>,
+    ) -> Self {
+        Self {
+            account,
+            positions,
+            what_if,
+            greeks,
+            config,
+        }
+    }
+
+    /// Both regimes' requirements for `account` (the current account
+    /// when `None`).
+    pub async fn compare(
+        &self,
+        account: Option<String>,
+    ) -> Result<MarginComparison, MarginCompareError> {
+        let config = self.config.read().await.margin_compare.clone();
+        let account = match account.filter(|a| !a.trim().is_empty()) {
+            Some(a) => a.trim().to_string(),
+            None => self.account.current_account().await?,
+        };
+
+        let mut by_symbol: BTreeMap<String, Vec<Position>> = BTreeMap::new();
+        for position in self.positions.list_open(&account).await? {
+            if position.position != 0.0 && matches!(position.contract_type.as_str(), "STK" | "OPT")
+            {
+                by_symbol
+                    .entry(position.symbol.to_uppercase())
+                    .or_default()
+                    .push(position);
+            }
+        }
+
+        let mut bundles = Vec::new();
+        let mut unmeasured = Vec::new();
+        for (symbol, positions) in by_symbol {
+            let what_if = match closing_legs(&positions) {
+                Some(legs) => self
+                    .what_if
+                    .what_if_margin(&account, legs)
+                    .await
+                    .map_err(|e| format!("Reg-T what-if failed ({e})")),
+                None => Err("an option leg without expiry, strike or right".to_string()),
+            };
+            let (reg_t_initial, reg_t_maintenance) = match what_if {
+                Ok(impact) => (
+                    Some((-impact.initial_change).max(0.0)),
+                    Some((-impact.maintenance_change).max(0.0)),
+                ),
+                Err(reason) => {
+                    unmeasured.push(format!("{symbol}: {reason}"));
+                    (None, None)
+                }
+            };
+            let (portfolio_margin, worst_move_pct) = match self.bundle_scan(&positions).await {
+                Ok(scan) => {
+                    let floor = config.pm_option_floor * option_contracts(&positions);
+                    let (loss, mv) = worst_loss(&scan, config.pm_move_pct);
+                    (Some(loss.max(floor)), Some(mv))
+                }
+                Err(reason) => {
+                    unmeasured.push(format!("{symbol}: {reason}"));
+                    (None, None)
+                }
+            };
+            bundles.push(BundleMargin {
+                legs: positions.iter().map(Position::contract_key).collect(),
+                market_value: positions.iter().map(|p| p.market_value).sum(),
+                reg_t_initial,
+                reg_t_maintenance,
+                portfolio_margin,
+                worst_move_pct,
+                difference: reg_t_initial.zip(portfolio_margin).map(|(r, p)| r - p),
+                symbol,
+            });
+        }
+        bundles.sort_by(|a, b| {
+            b.reg_t_initial
+                .unwrap_or(f64::NEG_INFINITY)
+                .total_cmp(&a.reg_t_initial.unwrap_or(f64::NEG_INFINITY))
+        });
+
+        let both: Vec<&BundleMargin> = bundles.iter().filter(|b| b.difference.is_some()).collect();
+        let reg_t_initial: f64 = both.iter().filter_map(|b| b.reg_t_initial).sum();
+        let portfolio_margin: f64 = both.iter().filter_map(|b| b.portfolio_margin).sum();
+        Ok(MarginComparison {
+            account,
+            at: Utc::now().timestamp(),
+            pm_move_pct: config.pm_move_pct,
+            reg_t_initial,
+            reg_t_maintenance: both.iter().filter_map(|b| b.reg_t_maintenance).sum(),
+            portfolio_margin,
+            freed: reg_t_initial - portfolio_margin,
+            bundles,
+            unmeasured,
+        })
+    }
+
+    /// The bundle as an underlying price and its P&L sensitivities.
+    async fn bundle_scan(&self, positions: &[Position]) -> Result<BundleScan, String> {
+        let mut scan = BundleScan::default();
+        let mut option_spot = None;
+        for position in positions {
+            if position.contract_type == "STK" {
+                scan.shares += position.position;
+                scan.price = Some(position.market_price).filter(|p| *p > 0.0);
+                continue;
+            }
+            let greeks =
+                self.greeks.option_greeks(position).await.map_err(|e| {
+                    format!("no model greeks for {} ({e})", position.contract_key())
+                })?;
+            let (Some(delta), Some(gamma)) = (greeks.delta, greeks.gamma) else {
+                return Err(format!("no model greeks for {}", position.contract_key()));
+            };
+            let size = position.position * multiplier(position);
+            scan.delta += delta * size;
+            scan.gamma += gamma * size;
+            option_spot = option_spot.or(greeks.underlying_price.filter(|p| *p > 0.0));
+        }
+        if scan.price.is_none() {
+            scan.price = option_spot;
+        }
+        if scan.price.is_none() {
+            return Err("no underlying price".to_string());
+        }
+        Ok(scan)
+    }
+}
+
+/// Share-equivalent exposure of one bundle around `price`.
+#[derive(Debug, Default)]
+struct BundleScan {
+    price: Option<f64>,
+    shares: f64,
+    /// Options' delta × quantity × multiplier.
+    delta: f64,
+    gamma: f64,
+}
+
+impl BundleScan {
+    fn pnl(&self, move_pct: f64) -> f64 {
+        let ds = self.price.unwrap_or(0.0) * move_pct / 100.0;
+        (self.shares + self.delta) * ds + 0.5 * self.gamma * ds * ds
+    }
+}
+
+/// Worst loss (0 when every scenario gains) across ±`max_move_pct`,
+/// and the move it happens at.
+fn worst_loss(scan: &BundleScan, max_move_pct: f64) -> (f64, f64) {
+    let step = max_move_pct / SCAN_STEPS as f64;
+    (0..=2 * SCAN_STEPS)
+        .map(|i| (i as f64 - SCAN_STEPS as f64) * step)
+        .map(|mv| (-scan.pnl(mv), mv))
+        .fold(
+            (0.0, 0.0),
+            |worst, (loss, mv)| {
+                if loss > worst.0 {
+                    (loss, mv)
+                } else {
+                    worst
+                }
+            },
+        )
+}
+
+/// Orders that would flatten every leg of the bundle; `None` when an
+/// option position can't be read as a contract.
+fn closing_legs(positions: &[Position]) -> Option<Vec<WhatIfLeg>> {
+    let mut legs = Vec::new();
+    for p in positions {
+        let quantity = p.position.abs().round();
+        if quantity == 0.0 {
+            continue;
+        }
+        let option = match p.contract_type.as_str() {
+            "OPT" => Some(OptionContract::from_position(p)?),
+            _ => None,
+        };
+        legs.push(WhatIfLeg {
+            symbol: p.symbol.clone(),
+            option,
+            action: if p.position > 0.0 {
+                OrderAction::Sell
+            } else {
+                OrderAction::Buy
+            },
+            quantity,
+        });
+    }
+    Some(legs)
+}
+
+fn option_contracts(positions: &[Position]) -> f64 {
+    positions
+        .iter()
+        .filter(|p| p.contract_type == "OPT")
+        .map(|p| p.position.abs())
+        .sum()
+}
+
+fn multiplier(position: &Position) -> f64 {
+    position
+        .multiplier
+        .as_deref()
+        .and_then(|m| m.parse::<f64>().ok())
+        .filter(|m| *m > 0.0)
+        .unwrap_or(100.0)
+}
@@ -0,0 +1,176 @@
+use std::sync::Mutex;
+
+use super::*;
+use crate::ibkr::types::OptionGreeks;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Closing AAPL frees 5k initial margin and MSFT 10k; NVDA's check
+/// fails. Records the legs asked about.
+#[derive(Default)]
+struct StubWhatIf {
+    asked: Mutex<Vec<Vec<WhatIfLeg>>>,
+}
+
+#[async_trait]
+impl WhatIfMargin for StubWhatIf {
+    async fn what_if_margin(
+        &self,
+        _account: &str,
+        legs: Vec<WhatIfLeg>,
+    ) -> Result<MarginImpact, IbkrError> {
+        let symbol = legs[0].symbol.clone();
+        self.asked.lock().unwrap().push(legs);
+        let (initial, maintenance) = match symbol.as_str() {
+            "AAPL" => (-5_000.0, -4_000.0),
+            "MSFT" => (-10_000.0, -6_000.0),
+            _ => return Err(IbkrError::Timeout(10_000)),
+        };
+        Ok(MarginImpact {
+            initial_change: initial,
+            maintenance_change: maintenance,
+            equity_with_loan_change: None,
+        })
+    }
+}
+
+/// Every option: delta 0.30, gamma 0.02, underlying at 200.
+struct StubGreeks;
+
+#[async_trait]
+impl GreeksSource for StubGreeks {
+    async fn option_greeks(&self, _position: &Position) -> Result<OptionGreeks, IbkrError> {
+        Ok(OptionGreeks {
+            delta: Some(0.3),
+            gamma: Some(0.02),
+            underlying_price: Some(200.0),
+            ..Default::default()
+        })
+    }
+}
+
+fn stock(symbol: &str, shares: f64, price: f64) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: shares,
+        market_price: price,
+        market_value: shares * price,
+        contract_type: "STK".to_string(),
+        ..Default::default()
+    }
+}
+
+fn harness() -> (MarginCompareService, Arc<StubWhatIf>) {
+    let call = Position {
+        account: "DU1".to_string(),
+        symbol: "AAPL".to_string(),
+        position: -1.0,
+        market_value: -300.0,
+        contract_type: "OPT".to_string(),
+        local_symbol: "AAPL  261120C00210000".to_string(),
+        expiry: Some("20261120".to_string()),
+        strike: Some(210.0),
+        right: Some("C".to_string()),
+        multiplier: Some("100".to_string()),
+        ..Default::default()
+    };
+    let what_if = Arc::new(StubWhatIf::default());
+    let service = MarginCompareService::new(
+        Arc::new(FixedAccount),
+        Arc::new(StubPositions(vec![
+            stock("NVDA", 10.0, 100.0),
+            stock("MSFT", -50.0, 400.0),
+            stock("AAPL", 100.0, 200.0),
+            call,
+        ])),
+        what_if.clone(),
+        Arc::new(StubGreeks),
+        Arc::new(RwLock::new(AppConfig::default())),
+    );
+    (service, what_if)
+}
+
+#[tokio::test]
+async fn bundles_compare_the_what_if_against_a_scanned_worst_loss() {
+    let (service, what_if) = harness();
+    let report = service.compare(None).await.unwrap();
+    assert_eq!(report.account, "DU1");
+    let symbols: Vec<&str> = report.bundles.iter().map(|b| b.symbol.as_str()).collect();
+    assert_eq!(symbols, ["MSFT", "AAPL", "NVDA"]);
+
+    // The covered call closes as one order: sell the shares, buy back
+    // the call.
+    let asked = what_if.asked.lock().unwrap();
+    let aapl_legs = asked.iter().find(|l| l[0].symbol == "AAPL").unwrap();
+    assert_eq!(aapl_legs.len(), 2);
+    assert!(aapl_legs[0].option.is_none());
+    assert!(matches!(aapl_legs[0].action, OrderAction::Sell));
+    assert_eq!(aapl_legs[0].quantity, 100.0);
+    assert!(aapl_legs[1].option.is_some());
+    assert!(matches!(aapl_legs[1].action, OrderAction::Buy));
+    assert_eq!(aapl_legs[1].quantity, 1.0);
+
+    // 100 shares less 30 deltas of short call, and -2 of gamma: -15%
+    // (-30) loses 2,100 plus 900.
+    let aapl = &report.bundles[1];
+    assert_eq!(aapl.legs, ["AAPL", "AAPL  261120C00210000"]);
+    assert_eq!(aapl.reg_t_initial, Some(5_000.0));
+    assert!((aapl.portfolio_margin.unwrap() - 3_000.0).abs() < 1e-6);
+    assert_eq!(aapl.worst_move_pct, Some(-15.0));
+    assert!((aapl.difference.unwrap() - 2_000.0).abs() < 1e-6);
+
+    // Short stock loses on the way up.
+    let msft = &report.bundles[0];
+    assert_eq!(msft.reg_t_maintenance, Some(6_000.0));
+    assert!((msft.portfolio_margin.unwrap() - 3_000.0).abs() < 1e-6);
+    assert_eq!(msft.worst_move_pct, Some(15.0));
+
+    // NVDA has a PM estimate but no Reg-T figure: out of the totals.
+    let nvda = &report.bundles[2];
+    assert_eq!(nvda.reg_t_initial, None);
+    assert!((nvda.portfolio_margin.unwrap() - 150.0).abs() < 1e-6);
+    assert_eq!(nvda.difference, None);
+    assert_eq!(report.unmeasured.len(), 1);
+    assert!(report.unmeasured[0].starts_with("NVDA: Reg-T what-if failed"));
+
+    assert!((report.reg_t_initial - 15_000.0).abs() < 1e-6);
+    assert!((report.reg_t_maintenance - 10_000.0).abs() < 1e-6);
+    assert!((report.portfolio_margin - 6_000.0).abs() < 1e-6);
+    assert!((report.freed - 9_000.0).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn option_contracts_carry_a_floor_and_the_scan_follows_config() {
+    let (service, _) = harness();
+    {
+        let mut config = service.config.write().await;
+        config.margin_compare.pm_move_pct = 10.0;
+        config.margin_compare.pm_option_floor = 5_000.0;
+    }
+    let report = service.compare(Some("DU2".to_string())).await.unwrap();
+    assert_eq!(report.account, "DU2");
+    assert_eq!(report.pm_move_pct, 10.0);
+    let aapl = report.bundles.iter().find(|b| b.symbol == "AAPL").unwrap();
+    // -10% (-20) loses 1,400 plus 400: under the one-contract floor.
+    assert_eq!(aapl.portfolio_margin, Some(5_000.0));
+    assert_eq!(aapl.worst_move_pct, Some(-10.0));
+    let msft = report.bundles.iter().find(|b| b.symbol == "MSFT").unwrap();
+    assert!((msft.portfolio_margin.unwrap() - 2_000.0).abs() < 1e-6);
+}
@@ -0,0 +1,256 @@
+//! Margin utilization over time.
+//!
+//! The `margin_sample` task (`services::scheduler`, every 15 minutes
+//! from 09:00 to 16:45 ET on weekdays by default) reads each managed account's
+//! `MaintMarginReq`, `ExcessLiquidity`, `Cushion` and `NetLiquidation`
+//! account values and appends a row to `margin_samples`;
+//! `get_margin_history` reads them back for the chart.
+//!
+//! When an account's cushion drops below `margin.cushion_alert_pct`
+//! an [`AppEvent::MarginCushionLow`] is emitted. It fires on the
+//! crossing (the previous sample was above the threshold, or there was
+//! none), so a cushion that stays low alerts once.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+use crate::events::{AppEvent, EventEmitter};
+use crate::ibkr::client::IbkrClient;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::AccountSummary;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::page::Page;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarginConfig {
+    /// Alert when `Cushion` (excess liquidity as a percent of NLV)
+    /// falls below this. 0 disables the alert.
+    #[serde(default = "default_cushion_alert_pct")]
+    pub cushion_alert_pct: f64,
+}
+
+fn default_cushion_alert_pct() -> f64 {
+    10.0
+}
+
+impl Default for MarginConfig {
+    fn default() -> Self {
+        Self {
+            cushion_alert_pct: default_cushion_alert_pct(),
+        }
+    }
+}
+
+/// Trait seam for the account reads. Production is the live
+/// `IbkrClient`; tests inject canned rows.
+#[async_trait]
+pub trait MarginSource: Send + Sync {
+    async fn accounts(&self) -> Result<Vec<String>, IbkrError>;
+    async fn account_values(&self, account: &str) -> Result<Vec<AccountSummary>, IbkrError>;
+}
+
+#[async_trait]
+impl MarginSource for IbkrClient {
+    async fn accounts(&self) -> Result<Vec<String>, IbkrError> {
+        self.get_accounts().await
+    }
+
+    async fn account_values(&self, account: &str) -> Result<Vec<AccountSummary>, IbkrError> {
+        self.get_account_summary(account).await
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum MarginMonitorError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// One account reading, in the account's base currency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginSample {
+    pub account: String,
+    /// Unix seconds.
+    pub sampled_at: i64,
+    pub maint_margin_req: f64,
+    pub excess_liquidity: f64,
+    /// IBKR's fraction (0.25 = 25%).
+    pub cushion: f64,
+    pub net_liquidation: f64,
+}
+
+impl MarginSample {
+    /// `None` when IBKR reported no `Cushion` (or it didn't parse).
+    fn from_values(account: &str, rows: &[AccountSummary], sampled_at: i64) -> Option<Self> {
+        let value = |tag: &str| {
+            rows.iter()
+                .find(|r| r.tag == tag && r.currency != "BASE")
+                .and_then(|r| r.value.trim().parse::<f64>().ok())
+        };
+        Some(Self {
+            account: account.to_string(),
+            sampled_at,
+            maint_margin_req: value("MaintMarginReq").unwrap_or(0.0),
+            excess_liquidity: value("ExcessLiquidity").unwrap_or(0.0),
+            cushion: value("Cushion")?,
+            net_liquidation: value("NetLiquidation").unwrap_or(0.0),
+        })
+    }
+}
+
+pub struct MarginMonitor {
+    db: Arc<Db>,
+    source: Arc<dyn MarginSource>,
+    config: Arc<RwLock<AppConfig>>,
+    emitter: Arc<EventEmitter>,
+}
+
+impl MarginMonitor {
+    pub fn new(
+        db: Arc<Db>,
+        source: Arc<dyn MarginSource>,
+        config: Arc<RwLock<AppConfig>>,
+        emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            db,
+            source,
+            config,
+            emitter,
+        }
+    }
+
+    /// Sample every managed account once. Accounts without a `Cushion`
+    /// value are skipped.
+    pub async fn sample(&self) -> Result<Vec<MarginSample>, MarginMonitorError> {
+        let now = Utc::now().timestamp();
+        let threshold = self.config.read().await.margin.cushion_alert_pct;
+        let mut samples = Vec::new();
+        for account in self.source.accounts().await? {
+            let rows = self.source.account_values(&account).await?;
+            let Some(sample) = MarginSample::from_values(&account, &rows, now) else {
+                warn!("margin_monitor: no Cushion for {account}, skipped");
+                continue;
+            };
+            let previous = self.latest(&account).await?;
+            self.insert(&sample).await?;
+
+            let below = |s: &MarginSample| s.cushion * 100.0 < threshold;
+            if below(&sample) && !previous.as_ref().is_some_and(below) {
+                info!(
+                    "margin_monitor: {account} cushion {:.1}% below {threshold}%",
+                    sample.cushion * 100.0
+                );
+                let event = AppEvent::MarginCushionLow {
+                    account: account.clone(),
+                    cushion: sample.cushion,
+                    threshold_pct: threshold,
+                    excess_liquidity: sample.excess_liquidity,
+                };
+                if let Err(e) = self.emitter.emit(event).await {
+                    warn!("MarginCushionLow emit failed: {e}");
+                }
+            }
+            samples.push(sample);
+        }
+        Ok(samples)
+    }
+
+    /// Samples with `from <= sampled_at < to` (unix seconds), oldest
+    /// first, optionally for one account, windowed by `page`.
+    pub async fn history(
+        &self,
+        from: i64,
+        to: i64,
+        account: Option<&str>,
+        page: Page,
+    ) -> Result<Vec<MarginSample>, StorageError> {
+        let account = account.map(|a| a.trim().to_string());
+        self.db
+            .with_conn(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT account, sampled_at, maint_margin_req, excess_liquidity, \
+                            cushion, net_liquidation \
+                     FROM margin_samples \
+                     WHERE sampled_at >= ?1 AND sampled_at < ?2 \
+                       AND (?3 IS NULL OR account = ?3) \
+                     ORDER BY sampled_at ASC, id ASC LIMIT ?4 OFFSET ?5",
+                )?;
+                let params = rusqlite::params![from, to, account, page.sql_limit(), page.offset];
+                let rows = stmt.query_map(params, row_to_sample)?;
+                let mut out = Vec::new();
+                for row in rows {
+                    out.push(row?);
+                }
+                Ok(out)
+            })
+            .await
+    }
+
+    async fn latest(&self, account: &str) -> Result<Option<MarginSample>, StorageError> {
+        let account = account.to_string();
+        self.db
+            .with_conn(move |conn| {
+                conn.query_row(
+                    "SELECT account, sampled_at, maint_margin_req, excess_liquidity, \
+                            cushion, net_liquidation \
+                     FROM margin_samples WHERE account = ?1 \
+                     ORDER BY sampled_at DESC, id DESC LIMIT 1",
+                    rusqlite::params![account],
+                    row_to_sample,
+                )
+                .optional()
+                .map_err(StorageError::from)
+            })
+            .await
+    }
+
+    async fn insert(&self, sample: &MarginSample) -> Result<(), StorageError> {
+        let s = sample.clone();
+        self.db
+            .with_conn(move |conn| {
+                conn.execute(
+                    "INSERT INTO margin_samples \
+                       (account, sampled_at, maint_margin_req, excess_liquidity, \
+                        cushion, net_liquidation) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    rusqlite::params![
+                        s.account,
+                        s.sampled_at,
+                        s.maint_margin_req,
+                        s.excess_liquidity,
+                        s.cushion,
+                        s.net_liquidation
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+fn row_to_sample(row: &rusqlite::Row<'_>) -> rusqlite::Result<MarginSample> {
+    Ok(MarginSample {
+        account: row.get(0)?,
+        sampled_at: row.get(1)?,
+        maint_margin_req: row.get(2)?,
+        excess_liquidity: row.get(3)?,
+        cushion: row.get(4)?,
+        net_liquidation: row.get(5)?,
+    })
+}
@@ -0,0 +1,113 @@
+use std::sync::Mutex as StdMutex;
+
+use tempfile::NamedTempFile;
+
+use super::*;
+
+/// `DU1` reports the next cushion from `cushions` on every read; `DU2`
+/// is a cash account without a `Cushion` row.
+struct SequenceSource {
+    cushions: StdMutex<Vec<f64>>,
+}
+
+#[async_trait]
+impl MarginSource for SequenceSource {
+    async fn accounts(&self) -> Result<Vec<String>, IbkrError> {
+        Ok(vec!["DU1".to_string(), "DU2".to_string()])
+    }
+
+    async fn account_values(&self, account: &str) -> Result<Vec<AccountSummary>, IbkrError> {
+        let row = |tag: &str, value: String| AccountSummary {
+            account: account.to_string(),
+            tag: tag.to_string(),
+            value,
+            currency: "USD".to_string(),
+        };
+        if account == "DU2" {
+            return Ok(vec![row("NetLiquidation", "5000".to_string())]);
+        }
+        let cushion = self.cushions.lock().unwrap().remove(0);
+        Ok(vec![
+            row("NetLiquidation", "100000".to_string()),
+            row("MaintMarginReq", "30000".to_string()),
+            row("ExcessLiquidity", format!("{}", cushion * 100_000.0)),
+            row("Cushion", format!("{cushion}")),
+        ])
+    }
+}
+
+fn monitor(db: Arc<Db>, cushions: Vec<f64>, threshold: f64) -> (MarginMonitor, Arc<EventEmitter>) {
+    let mut config = AppConfig::default();
+    config.margin.cushion_alert_pct = threshold;
+    let emitter = Arc::new(EventEmitter::for_capture());
+    let monitor = MarginMonitor::new(
+        db,
+        Arc::new(SequenceSource {
+            cushions: StdMutex::new(cushions),
+        }),
+        Arc::new(RwLock::new(config)),
+        emitter.clone(),
+    );
+    (monitor, emitter)
+}
+
+#[tokio::test]
+async fn samples_accounts_and_alerts_on_each_crossing_below_threshold() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let (monitor, emitter) = monitor(db, vec![0.25, 0.08, 0.05, 0.2, 0.07], 10.0);
+
+    for _ in 0..5 {
+        let samples = monitor.sample().await.unwrap();
+        // DU2 has no cushion and is skipped.
+        assert_eq!(samples.len(), 1);
+    }
+
+    let history = monitor
+        .history(0, i64::MAX, Some("DU1"), Page::ALL)
+        .await
+        .unwrap();
+    let cushions: Vec<_> = history.iter().map(|s| s.cushion).collect();
+    assert_eq!(cushions, vec![0.25, 0.08, 0.05, 0.2, 0.07]);
+    assert_eq!(history[1].maint_margin_req, 30_000.0);
+    assert_eq!(history[1].excess_liquidity, 8_000.0);
+    let page = monitor
+        .history(0, i64::MAX, Some("DU1"), Page::new(Some(2), Some(1)))
+        .await
+        .unwrap();
+    let cushions: Vec<_> = page.iter().map(|s| s.cushion).collect();
+    assert_eq!(cushions, vec![0.08, 0.05]);
+    assert!(monitor
+        .history(0, i64::MAX, Some("DU2"), Page::ALL)
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Below at 0.08 (crossing), still below at 0.05, back above, below at 0.07.
+    let alerts: Vec<_> = emitter
+        .captured()
+        .await
+        .into_iter()
+        .filter_map(|e| match e {
+            AppEvent::MarginCushionLow { cushion, .. } => Some(cushion),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(alerts, vec![0.08, 0.07]);
+}
+
+#[tokio::test]
+async fn zero_threshold_disables_the_alert() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let (monitor, emitter) = monitor(db, vec![0.01], 0.0);
+
+    monitor.sample().await.unwrap();
+    assert!(emitter.captured().await.is_empty());
+    let future = Utc::now().timestamp() + 60;
+    assert!(monitor
+        .history(future, i64::MAX, None, Page::ALL)
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,321 @@
+//! Margin-of-safety buy/sell bands.
+//!
+//! For every ticker with a stored projection (`services::projection_history`)
+//! each scenario's fair value — the midpoint of its first projected
+//! year, the same horizon `services::fair_value_watch` uses — gets a
+//! buy-below and a sell-above price at `margin_of_safety.margin_pct`
+//! (or the ticker's entry in `per_symbol`):
+//!
+//! ```text
+//! buy_below  = fair_value × (1 − margin / 100)
+//! sell_above = fair_value × (1 + margin / 100)
+//! ```
+//!
+//! The `margin_of_safety_check` task (`services::scheduler`, every 15
+//! minutes through the session by default) rebuilds the bands into
+//! `margin_of_safety_bands`, prices each ticker and emits an
+//! [`AppEvent::MarginOfSafetyEntered`] when the price moves into a
+//! scenario's buy or sell zone. Like the fair-value watch it fires on
+//! the entry only, and it is a signal for the operator, never an order.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+use crate::events::{AppEvent, EventEmitter};
+use crate::ibkr::types::FinancialProjection;
+use crate::services::fair_value_watch::PriceSource;
+use crate::services::projection_history::ProjectionHistoryStore;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarginOfSafetyConfig {
+    /// Percent below / above fair value for the buy / sell bands.
+    #[serde(default = "default_margin_pct")]
+    pub margin_pct: f64,
+    /// Symbol → margin percent, for names that deserve a wider (or
+    /// narrower) cushion.
+    #[serde(default)]
+    pub per_symbol: BTreeMap<String, f64>,
+}
+
+fn default_margin_pct() -> f64 {
+    25.0
+}
+
+impl Default for MarginOfSafetyConfig {
+    fn default() -> Self {
+        Self {
+            margin_pct: default_margin_pct(),
+            per_symbol: BTreeMap::new(),
+        }
+    }
+}
+
+impl MarginOfSafetyConfig {
+    pub fn margin_pct_for(&self, symbol: &str) -> f64 {
+        self.per_symbol
+            .iter()
+            .find(|(s, _)| s.eq_ignore_ascii_case(symbol))
+            .map_or(self.margin_pct, |(_, pct)| *pct)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BandZone {
+    /// At or below `buy_below`.
+    Buy,
+    Hold,
+    /// At or above `sell_above`.
+    Sell,
+}
+
+impl BandZone {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BandZone::Buy => "buy",
+            BandZone::Hold => "hold",
+            BandZone::Sell => "sell",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "buy" => BandZone::Buy,
+            "sell" => BandZone::Sell,
+            _ => BandZone::Hold,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafetyBand {
+    pub symbol: String,
+    /// `bear`, `base` or `bull`.
+    pub scenario: String,
+    pub fair_value: f64,
+    pub buy_below: f64,
+    pub sell_above: f64,
+    pub margin_pct: f64,
+    pub snapshot_id: i64,
+    /// `None` until the ticker has been priced.
+    pub zone: Option<BandZone>,
+    pub price: Option<f64>,
+    pub evaluated_at: i64,
+    pub changed_at: Option<i64>,
+}
+
+impl SafetyBand {
+    fn new(symbol: &str, scenario: &str, year: &FinancialProjection, margin_pct: f64) -> Self {
+        let fair_value = (year.share_price_low + year.share_price_high) / 2.0;
+        Self {
+            symbol: symbol.to_string(),
+            scenario: scenario.to_string(),
+            fair_value,
+            buy_below: fair_value * (1.0 - margin_pct / 100.0),
+            sell_above: fair_value * (1.0 + margin_pct / 100.0),
+            margin_pct,
+            snapshot_id: 0,
+            zone: None,
+            price: None,
+            evaluated_at: 0,
+            changed_at: None,
+        }
+    }
+
+    pub fn zone_at(&self, price: f64) -> BandZone {
+        if price <= self.buy_below {
+            BandZone::Buy
+        } else if price >= self.sell_above {
+            BandZone::Sell
+        } else {
+            BandZone::Hold
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginOfSafetyReport {
+    /// Tickers whose bands were rebuilt.
+    pub evaluated: usize,
+    /// Bands whose zone moved into `buy` / `sell` this run.
+    pub entered: Vec<SafetyBand>,
+    /// Symbols without a price (bands rebuilt, zones left as they were)
+    /// or without forward projection years.
+    pub skipped: Vec<String>,
+}
+
+pub struct MarginOfSafetyWatcher {
+    db: Arc<Db>,
+    history: Arc<ProjectionHistoryStore>,
+    prices: Arc<dyn PriceSource>,
+    config: Arc<RwLock<AppConfig>>,
+    emitter: Arc<EventEmitter>,
+}
+
+impl MarginOfSafetyWatcher {
+    pub fn new(
+        db: Arc<Db>,
+        history: Arc<ProjectionHistoryStore>,
+        prices: Arc<dyn PriceSource>,
+        config: Arc<RwLock<AppConfig>>,
+        emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            db,
+            history,
+            prices,
+            config,
+            emitter,
+        }
+    }
+
+    /// Rebuild and price every projected ticker's bands once.
+    pub async fn run(&self) -> Result<MarginOfSafetyReport, StorageError> {
+        let now = Utc::now().timestamp();
+        let config = self.config.read().await.margin_of_safety.clone();
+        let mut report = MarginOfSafetyReport::default();
+        for snapshot in self.history.latest_per_symbol().await? {
+            let symbol = snapshot.symbol.clone();
+            let Some(year) = snapshot.results.projections.first() else {
+                report.skipped.push(symbol);
+                continue;
+            };
+            let margin_pct = config.margin_pct_for(&symbol);
+            let price = self.prices.latest_price(&symbol).await.filter(|p| *p > 0.0);
+            if price.is_none() {
+                report.skipped.push(symbol.clone());
+            }
+            let previous = self.list(Some(&symbol)).await?;
+            for (scenario, projection) in [
+                ("bear", &year.bear),
+                ("base", &year.base),
+                ("bull", &year.bull),
+            ] {
+                let prev = previous.iter().find(|b| b.scenario == scenario);
+                let mut band = SafetyBand::new(&symbol, scenario, projection, margin_pct);
+                band.snapshot_id = snapshot.id;
+                band.evaluated_at = now;
+                band.price = price.or(prev.and_then(|p| p.price));
+                band.zone = band.price.map(|p| band.zone_at(p));
+                let changed = price.is_some() && prev.and_then(|p| p.zone) != band.zone;
+                band.changed_at = if changed {
+                    Some(now)
+                } else {
+                    prev.and_then(|p| p.changed_at)
+                };
+                self.upsert(&band).await?;
+
+                if let (true, Some(zone @ (BandZone::Buy | BandZone::Sell)), Some(price)) =
+                    (changed, band.zone, price)
+                {
+                    info!(
+                        "margin_of_safety: {symbol} entered {scenario} {} zone at {price}",
+                        zone.as_str()
+                    );
+                    let event = AppEvent::MarginOfSafetyEntered {
+                        symbol: symbol.clone(),
+                        scenario: scenario.to_string(),
+                        zone,
+                        price,
+                        buy_below: band.buy_below,
+                        sell_above: band.sell_above,
+                    };
+                    if let Err(e) = self.emitter.emit(event).await {
+                        warn!("MarginOfSafetyEntered emit failed: {e}");
+                    }
+                    report.entered.push(band);
+                }
+            }
+            report.evaluated += 1;
+        }
+        Ok(report)
+    }
+
+    /// Stored bands, by symbol then bear/base/bull, optionally for one
+    /// ticker.
+    pub async fn list(&self, symbol: Option<&str>) -> Result<Vec<SafetyBand>, StorageError> {
+        let symbol = symbol.map(|s| s.trim().to_uppercase());
+        self.db
+            .with_conn(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT symbol, scenario, fair_value, buy_below, sell_above, margin_pct, \
+                            snapshot_id, zone, price, evaluated_at, changed_at \
+                     FROM margin_of_safety_bands \
+                     WHERE ?1 IS NULL OR symbol = ?1 \
+                     ORDER BY symbol ASC, CASE scenario \
+                       WHEN 'bear' THEN 0 WHEN 'base' THEN 1 ELSE 2 END",
+                )?;
+                let rows = stmt.query_map(rusqlite::params![symbol], |row| {
+                    Ok(SafetyBand {
+                        symbol: row.get(0)?,
+                        scenario: row.get(1)?,
+                        fair_value: row.get(2)?,
+                        buy_below: row.get(3)?,
+                        sell_above: row.get(4)?,
+                        margin_pct: row.get(5)?,
+                        snapshot_id: row.get(6)?,
+                        zone: row
+                            .get::<_, Option<String>>(7)?
+                            .map(|z| BandZone::parse(&z)),
+                        price: row.get(8)?,
+                        evaluated_at: row.get(9)?,
+                        changed_at: row.get(10)?,
+                    })
+                })?;
+                let mut out = Vec::new();
+                for row in rows {
+                    out.push(row?);
+                }
+                Ok(out)
+            })
+            .await
+    }
+
+    async fn upsert(&self, band: &SafetyBand) -> Result<(), StorageError> {
+        let b = band.clone();
+        self.db
+            .with_conn(move |conn| {
+                conn.execute(
+                    "INSERT INTO margin_of_safety_bands \
+                       (symbol, scenario, fair_value, buy_below, sell_above, margin_pct, \
+                        snapshot_id, zone, price, evaluated_at, changed_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11) \
+                     ON CONFLICT(symbol, scenario) DO UPDATE SET \
+                       fair_value = excluded.fair_value, buy_below = excluded.buy_below, \
+                       sell_above = excluded.sell_above, margin_pct = excluded.margin_pct, \
+                       snapshot_id = excluded.snapshot_id, zone = excluded.zone, \
+                       price = excluded.price, evaluated_at = excluded.evaluated_at, \
+                       changed_at = excluded.changed_at",
+                    rusqlite::params![
+                        b.symbol,
+                        b.scenario,
+                        b.fair_value,
+                        b.buy_below,
+                        b.sell_above,
+                        b.margin_pct,
+                        b.snapshot_id,
+                        b.zone.map(|z| z.as_str()),
+                        b.price,
+                        b.evaluated_at,
+                        b.changed_at
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tempfile::NamedTempFile;
+
+use super::*;
+use crate::ibkr::types::ProjectionAssumptions;
+use crate::services::projection_service::ProjectionService;
+
+#[derive(Default)]
+struct FixedPrices(Mutex<HashMap<String, f64>>);
+
+impl FixedPrices {
+    fn set(&self, symbol: &str, price: f64) {
+        self.0.lock().unwrap().insert(symbol.to_string(), price);
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceSource for FixedPrices {
+    async fn latest_price(&self, symbol: &str) -> Option<f64> {
+        self.0.lock().unwrap().get(symbol).copied()
+    }
+}
+
+struct Harness {
+    _tmp: NamedTempFile,
+    watcher: MarginOfSafetyWatcher,
+    prices: Arc<FixedPrices>,
+    emitter: Arc<EventEmitter>,
+    /// Base-case fair value of the first projected year.
+    base_fair: f64,
+}
+
+async fn harness(config: AppConfig) -> Harness {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let history = Arc::new(ProjectionHistoryStore::new(Arc::clone(&db)));
+    let assumptions = ProjectionAssumptions::default();
+    let data = ProjectionService::generate_mock_fundamental_data("NVDA");
+    let results = ProjectionService::generate_projection_results(&data, &assumptions).unwrap();
+    history
+        .record("NVDA", &assumptions, &results)
+        .await
+        .unwrap();
+    let base = &results.projections[0].base;
+
+    let prices = Arc::new(FixedPrices::default());
+    let emitter = Arc::new(EventEmitter::for_capture());
+    let watcher = MarginOfSafetyWatcher::new(
+        db,
+        history,
+        Arc::clone(&prices) as Arc<dyn PriceSource>,
+        Arc::new(RwLock::new(config)),
+        Arc::clone(&emitter),
+    );
+    Harness {
+        _tmp: tmp,
+        watcher,
+        prices,
+        emitter,
+        base_fair: (base.share_price_low + base.share_price_high) / 2.0,
+    }
+}
+
+#[tokio::test]
+async fn bands_sit_at_the_margin_around_each_scenario() {
+    let mut config = AppConfig::default();
+    config
+        .margin_of_safety
+        .per_symbol
+        .insert("nvda".to_string(), 40.0);
+    let h = harness(config).await;
+
+    let report = h.watcher.run().await.unwrap();
+    assert_eq!(report.evaluated, 1);
+    assert_eq!(report.skipped, vec!["NVDA".to_string()]);
+
+    let bands = h.watcher.list(Some("nvda")).await.unwrap();
+    let scenarios: Vec<_> = bands.iter().map(|b| b.scenario.as_str()).collect();
+    assert_eq!(scenarios, vec!["bear", "base", "bull"]);
+    let base = &bands[1];
+    assert_eq!(base.margin_pct, 40.0);
+    assert!((base.fair_value - h.base_fair).abs() < 1e-9);
+    assert!((base.buy_below - h.base_fair * 0.6).abs() < 1e-9);
+    assert!((base.sell_above - h.base_fair * 1.4).abs() < 1e-9);
+    assert_eq!(base.zone, None, "unpriced");
+    assert!(h.emitter.captured().await.is_empty());
+}
+
+#[tokio::test]
+async fn entering_a_buy_band_emits_once_per_scenario() {
+    let h = harness(AppConfig::default()).await;
+    h.prices.set("NVDA", h.base_fair * 0.5);
+
+    let first = h.watcher.run().await.unwrap();
+    assert!(first.entered.iter().any(|b| b.scenario == "base"));
+    assert!(first.entered.iter().all(|b| b.zone == Some(BandZone::Buy)));
+    let second = h.watcher.run().await.unwrap();
+    assert!(second.entered.is_empty(), "standing zone must not re-fire");
+
+    let base_events = h
+        .emitter
+        .captured()
+        .await
+        .into_iter()
+        .filter(|e| {
+            matches!(
+                e,
+                AppEvent::MarginOfSafetyEntered { scenario, zone: BandZone::Buy, .. }
+                    if scenario == "base"
+            )
+        })
+        .count();
+    assert_eq!(base_events, 1);
+
+    // Back to fair value, then above the sell band: one sell entry.
+    h.prices.set("NVDA", h.base_fair);
+    h.watcher.run().await.unwrap();
+    assert_eq!(
+        h.watcher.list(Some("NVDA")).await.unwrap()[1].zone,
+        Some(BandZone::Hold)
+    );
+    h.prices.set("NVDA", h.base_fair * 1.3);
+    let report = h.watcher.run().await.unwrap();
+    let base = report
+        .entered
+        .iter()
+        .find(|b| b.scenario == "base")
+        .unwrap();
+    assert_eq!(base.zone, Some(BandZone::Sell));
+}
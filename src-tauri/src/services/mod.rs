@@ -1,51 +1,105 @@
 pub mod agent_morning_packs;
 pub mod alerts;
 pub mod auto_scanner;
+pub mod automation;
 pub mod backtester;
+pub mod benchmarks;
 pub mod bracket_reviser;
 pub mod cache_service;
 pub mod candidate_promoter;
 pub mod candidate_scheduler;
 pub mod candidate_universe;
+pub mod carry_costs;
+pub mod cash_drag;
+pub mod cash_management;
+pub mod connection_health;
+pub mod corporate_actions;
+pub mod cost_basis;
 pub mod daily_ranker;
 pub mod decay_watcher;
+pub mod drawdown;
+pub mod edgar_service;
 pub mod eod_scheduler;
 pub mod eval_harness;
 pub mod event_calendar;
 pub mod executions;
+pub mod expected_return;
+pub mod factor_exposure;
+pub mod fair_value_watch;
 pub mod financial_data_service;
+pub mod fundamentals_growth;
+pub mod fundamentals_overrides;
 pub mod fundamentals_provider;
+pub mod fundamentals_quality;
+pub mod fx_service;
+pub mod hedging;
 pub mod historical_data_service;
+pub mod insider_activity;
 pub mod intraday_scheduler;
+pub mod iv_rank;
+pub mod jobs;
 pub mod journal_writer;
+pub mod liquidity;
 pub mod llm_service;
+pub mod macro_service;
 pub mod manual_fundamentals_store;
+pub mod margin_compare;
+pub mod margin_monitor;
+pub mod margin_of_safety;
 pub mod mcp_audit;
+pub mod model_portfolio;
+pub mod morning_briefing;
 pub mod news_cache;
 pub mod news_interpreter;
 pub mod news_provider;
+pub mod notifications;
+pub mod notion_export;
+pub mod option_expiry;
+pub mod option_greeks;
+pub mod option_income;
+pub mod option_roll;
+pub mod order_audit;
+pub mod order_guard;
 pub mod order_ticket;
 pub mod outcome_extractor;
+pub mod paper_trading;
 pub mod param_refit;
 pub mod playbooks;
+pub mod portfolio_analysis;
+pub mod portfolio_diff;
+pub mod portfolio_import;
 pub mod portfolio_risk;
+pub mod position_health;
+pub mod position_plans;
 pub mod predictions;
+pub mod projection_history;
+pub mod projection_refresh;
 pub mod projection_service;
+pub mod projection_templates;
 pub mod quote_service;
 pub mod regime;
+pub mod report_service;
 pub mod research_notes;
 pub mod risk_engine;
+pub mod scheduler;
+pub mod screener;
 pub mod sentiment_surge_scanner;
+pub mod short_interest;
 pub mod social_sentiment;
 pub mod social_sentiment_scheduler;
+pub mod stress_test;
+pub mod tax_lots;
 pub mod tca;
+pub mod telegram_bot;
 pub mod thesis_generator;
 pub mod ticker_primer;
 pub mod tilt_guard;
 pub mod tracker_runner;
 pub mod tracker_service;
 pub mod tracker_state_machine;
+pub mod trade_ideas;
 pub mod trade_legs;
 pub mod trade_reviews;
 pub mod trader_profile;
+pub mod valuation;
 pub mod watchlist_briefing;
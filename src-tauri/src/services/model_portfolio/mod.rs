@@ -0,0 +1,172 @@
+//! Model portfolios — reusable target allocations (say 60/30/10 across
+//! tickers or sectors) kept in `AppConfig.model_portfolios`.
+//!
+//! [`ModelPortfolioService::evaluate`] compares an account's long stock
+//! positions against a template and returns the drift per bucket plus
+//! a whole-share rebalancing trade list (see `plan.rs`). The list is a
+//! proposal for the trader to work through the order ticket; nothing
+//! here places an order.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+
+use crate::ibkr::error::IbkrError;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::quote_service::QuoteFetcher;
+use crate::services::risk_engine::AccountSource;
+
+mod plan;
+#[cfg(test)]
+mod tests;
+
+pub use plan::RebalancePlan;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelPortfoliosConfig {
+    #[serde(default)]
+    pub templates: Vec<AllocationTemplate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AllocationBasis {
+    Ticker,
+    /// Sector labels from `portfolio_risk::SectorMap` (`tech`, `semis`, …).
+    Sector,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationTemplate {
+    /// Unique, case-insensitive key. Trimmed on save.
+    pub name: String,
+    pub basis: AllocationBasis,
+    /// Weights in percent; must sum to 100.
+    pub targets: Vec<AllocationTarget>,
+    /// Buckets within this many percentage points of target are left
+    /// alone.
+    #[serde(default = "default_band_pct")]
+    pub band_pct: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AllocationTarget {
+    /// Ticker or sector label, depending on the template's basis.
+    pub key: String,
+    pub weight: f64,
+}
+
+fn default_band_pct() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ModelPortfolioError {
+    #[error("model portfolio name must be non-empty")]
+    EmptyName,
+    #[error("model portfolio `{0}` not found")]
+    NotFound(String),
+}
+
+impl ModelPortfoliosConfig {
+    pub fn find(&self, name: &str) -> Option<&AllocationTemplate> {
+        self.templates
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Insert or replace (by case-insensitive name). Ticker keys are
+    /// upper-cased, sector keys lower-cased.
+    pub fn upsert(&mut self, mut template: AllocationTemplate) -> Result<(), ModelPortfolioError> {
+        template.name = template.name.trim().to_string();
+        if template.name.is_empty() {
+            return Err(ModelPortfolioError::EmptyName);
+        }
+        for target in &mut template.targets {
+            let key = target.key.trim();
+            target.key = match template.basis {
+                AllocationBasis::Ticker => key.to_uppercase(),
+                AllocationBasis::Sector => key.to_lowercase(),
+            };
+        }
+        match self
+            .templates
+            .iter_mut()
+            .find(|t| t.name.eq_ignore_ascii_case(&template.name))
+        {
+            Some(existing) => *existing = template,
+            None => self.templates.push(template),
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<(), ModelPortfolioError> {
+        let before = self.templates.len();
+        self.templates
+            .retain(|t| !t.name.eq_ignore_ascii_case(name.trim()));
+        if self.templates.len() == before {
+            return Err(ModelPortfolioError::NotFound(name.to_string()));
+        }
+        Ok(())
+    }
+}
+
+pub struct ModelPortfolioService {
+    positions: Arc<dyn OpenPositionsSource>,
+    accounts: Arc<dyn AccountSource>,
+    quotes: Arc<dyn QuoteFetcher>,
+}
+
+impl ModelPortfolioService {
+    pub fn new(
+        positions: Arc<dyn OpenPositionsSource>,
+        accounts: Arc<dyn AccountSource>,
+        quotes: Arc<dyn QuoteFetcher>,
+    ) -> Self {
+        Self {
+            positions,
+            accounts,
+            quotes,
+        }
+    }
+
+    /// Drift and rebalancing trades for `account` (default: the first
+    /// managed account) against `template`. Template tickers the
+    /// account doesn't hold are priced with a live snapshot.
+    pub async fn evaluate(
+        &self,
+        template: &AllocationTemplate,
+        account: Option<String>,
+    ) -> Result<RebalancePlan, IbkrError> {
+        let account = match account {
+            Some(a) if !a.trim().is_empty() => a,
+            _ => self.accounts.current_account().await?,
+        };
+        let positions = self.positions.list_open(&account).await?;
+
+        let mut prices = HashMap::new();
+        if template.basis == AllocationBasis::Ticker {
+            for target in &template.targets {
+                let held = positions
+                    .iter()
+                    .any(|p| p.symbol.eq_ignore_ascii_case(&target.key));
+                if held {
+                    continue;
+                }
+                match self.quotes.get_market_data_snapshot(&target.key).await {
+                    Ok(snap) => {
+                        if let Some(last) = snap.last_price.or(snap.close).filter(|p| *p > 0.0) {
+                            prices.insert(target.key.clone(), last);
+                        }
+                    }
+                    Err(e) => warn!("model portfolio: quote for {} failed: {e}", target.key),
+                }
+            }
+        }
+        Ok(plan::build(template, account, &positions, &prices))
+    }
+}
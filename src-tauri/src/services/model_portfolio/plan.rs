@@ -0,0 +1,227 @@
+//! Drift and trade-list math for [`super::ModelPortfolioService`].
+//!
+//! The book is the account's long stock positions at their IBKR marks;
+//! cash, shorts and options stay out, so the trade list is
+//! self-funding (sells before buys). Holdings outside the template are
+//! a 0% target. A sector bucket's change is spread across the tickers
+//! already held in it, pro rata to value; a sector with nothing held
+//! gets a note instead of a guessed ticker. Quantities are whole shares.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{AllocationBasis, AllocationTemplate};
+use crate::ibkr::types::{OrderAction, Position, SizeRules};
+use crate::services::portfolio_risk::SectorMap;
+
+/// Bucket for symbols the sector map doesn't know (as in the
+/// concentration gate).
+const UNKNOWN_SECTOR: &str = "unknown";
+
+/// Weights and drift in percent of the book.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationDrift {
+    pub key: String,
+    pub target_weight: f64,
+    pub current_weight: f64,
+    pub current_value: f64,
+    pub target_value: f64,
+    /// `current_weight - target_weight`; positive = overweight.
+    pub drift: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalanceTrade {
+    pub symbol: String,
+    /// Template key the trade moves toward target.
+    pub bucket: String,
+    pub action: OrderAction,
+    pub quantity: f64,
+    pub price: f64,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalancePlan {
+    pub template: String,
+    pub account: String,
+    /// Market value of the long stock positions.
+    pub total_value: f64,
+    pub allocations: Vec<AllocationDrift>,
+    /// Sells first, then buys.
+    pub trades: Vec<RebalanceTrade>,
+    pub notes: Vec<String>,
+}
+
+struct Holding {
+    symbol: String,
+    bucket: String,
+    quantity: f64,
+    price: f64,
+    value: f64,
+}
+
+/// `prices` covers template tickers the account doesn't hold.
+pub(super) fn build(
+    template: &AllocationTemplate,
+    account: String,
+    positions: &[Position],
+    prices: &HashMap<String, f64>,
+) -> RebalancePlan {
+    let mut notes = Vec::new();
+    let holdings = holdings(template.basis, positions, &mut notes);
+    let total_value: f64 = holdings.iter().map(|h| h.value).sum();
+
+    let mut buckets: Vec<(String, f64)> = template
+        .targets
+        .iter()
+        .map(|t| (t.key.clone(), t.weight))
+        .collect();
+    for h in &holdings {
+        if !buckets.iter().any(|(key, _)| *key == h.bucket) {
+            buckets.push((h.bucket.clone(), 0.0));
+        }
+    }
+
+    let mut allocations = Vec::with_capacity(buckets.len());
+    let mut trades = Vec::new();
+    if total_value <= 0.0 {
+        notes.push("no long stock positions to rebalance".to_string());
+    }
+    for (key, weight) in buckets {
+        let members: Vec<&Holding> = holdings.iter().filter(|h| h.bucket == key).collect();
+        let current_value: f64 = members.iter().map(|h| h.value).sum();
+        let target_value = total_value * weight / 100.0;
+        let current_weight = if total_value > 0.0 {
+            current_value / total_value * 100.0
+        } else {
+            0.0
+        };
+        let drift = current_weight - weight;
+        allocations.push(AllocationDrift {
+            key: key.clone(),
+            target_weight: weight,
+            current_weight,
+            current_value,
+            target_value,
+            drift,
+        });
+        if total_value <= 0.0 || drift.abs() < template.band_pct {
+            continue;
+        }
+
+        let delta = target_value - current_value;
+        if members.is_empty() {
+            match (template.basis, prices.get(&key)) {
+                (AllocationBasis::Ticker, Some(&price)) => {
+                    push_trade(&mut trades, &key, &key, delta, price, None)
+                }
+                (AllocationBasis::Ticker, None) => {
+                    notes.push(format!("{key}: no price, buy of ${delta:.0} left out"))
+                }
+                (AllocationBasis::Sector, _) => notes.push(format!(
+                    "{key}: nothing held in this sector; buy ${delta:.0} of a ticker of your choice"
+                )),
+            }
+            continue;
+        }
+        for h in members {
+            let share = if current_value > 0.0 {
+                h.value / current_value
+            } else {
+                0.0
+            };
+            push_trade(
+                &mut trades,
+                &h.symbol,
+                &key,
+                delta * share,
+                h.price,
+                Some(h.quantity),
+            );
+        }
+    }
+    trades.sort_by_key(|t| matches!(t.action, OrderAction::Buy));
+
+    RebalancePlan {
+        template: template.name.clone(),
+        account,
+        total_value,
+        allocations,
+        trades,
+        notes,
+    }
+}
+
+fn holdings(
+    basis: AllocationBasis,
+    positions: &[Position],
+    notes: &mut Vec<String>,
+) -> Vec<Holding> {
+    let mut out = Vec::new();
+    for p in positions {
+        let symbol = p.symbol.to_uppercase();
+        if p.contract_type != "STK" || p.position <= 0.0 {
+            notes.push(format!("{symbol}: {} position left out", p.contract_type));
+            continue;
+        }
+        let price = if p.market_price > 0.0 {
+            p.market_price
+        } else {
+            p.market_value / p.position
+        };
+        let bucket = match basis {
+            AllocationBasis::Ticker => symbol.clone(),
+            AllocationBasis::Sector => SectorMap::lookup_static(&symbol)
+                .unwrap_or(UNKNOWN_SECTOR)
+                .to_string(),
+        };
+        out.push(Holding {
+            symbol,
+            bucket,
+            quantity: p.position,
+            price,
+            value: p.market_value,
+        });
+    }
+    out
+}
+
+/// Whole shares worth `delta` at `price`; a sell never exceeds `held`.
+fn push_trade(
+    trades: &mut Vec<RebalanceTrade>,
+    symbol: &str,
+    bucket: &str,
+    delta: f64,
+    price: f64,
+    held: Option<f64>,
+) {
+    if price <= 0.0 || !delta.is_finite() {
+        return;
+    }
+    let mut quantity = SizeRules::WHOLE_SHARES.round_down(delta.abs() / price);
+    if delta < 0.0 {
+        if let Some(held) = held {
+            quantity = quantity.min(held);
+        }
+    }
+    if quantity <= 0.0 {
+        return;
+    }
+    trades.push(RebalanceTrade {
+        symbol: symbol.to_string(),
+        bucket: bucket.to_string(),
+        action: if delta > 0.0 {
+            OrderAction::Buy
+        } else {
+            OrderAction::Sell
+        },
+        quantity,
+        price,
+        value: quantity * price,
+    });
+}
@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::*;
+use crate::ibkr::error::Result as IbkrResult;
+use crate::ibkr::types::{MarketDataSnapshot, OrderAction, Position};
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Quotes every symbol at 50, except `NOQUOTE`.
+struct FlatQuotes;
+
+#[async_trait]
+impl QuoteFetcher for FlatQuotes {
+    async fn get_market_data_snapshot(&self, symbol: &str) -> IbkrResult<MarketDataSnapshot> {
+        if symbol == "NOQUOTE" {
+            return Err(IbkrError::MarketDataPermissionDenied);
+        }
+        Ok(MarketDataSnapshot {
+            symbol: symbol.to_string(),
+            bid_price: None,
+            bid_size: None,
+            ask_price: None,
+            ask_size: None,
+            last_price: Some(50.0),
+            last_size: None,
+            high: None,
+            low: None,
+            volume: None,
+            close: None,
+            open: None,
+            last_rth_price: None,
+            session: None,
+            timestamp: 0,
+        })
+    }
+}
+
+fn pos(symbol: &str, contract_type: &str, qty: f64, price: f64) -> Position {
+    Position {
+        symbol: symbol.to_string(),
+        contract_type: contract_type.to_string(),
+        position: qty,
+        market_price: price,
+        market_value: qty * price,
+        ..Default::default()
+    }
+}
+
+fn template(basis: AllocationBasis, targets: &[(&str, f64)]) -> AllocationTemplate {
+    AllocationTemplate {
+        name: "core".to_string(),
+        basis,
+        targets: targets
+            .iter()
+            .map(|(key, weight)| AllocationTarget {
+                key: key.to_string(),
+                weight: *weight,
+            })
+            .collect(),
+        band_pct: 1.0,
+    }
+}
+
+fn service(positions: Vec<Position>) -> ModelPortfolioService {
+    ModelPortfolioService::new(
+        Arc::new(StubPositions(positions)),
+        Arc::new(FixedAccount),
+        Arc::new(FlatQuotes),
+    )
+}
+
+#[tokio::test]
+async fn ticker_template_reports_drift_and_self_funding_trades() {
+    // Book: AAPL 8_000, MSFT 2_000, TSLA 0 → total 10_000.
+    let svc = service(vec![
+        pos("AAPL", "STK", 80.0, 100.0),
+        pos("MSFT", "STK", 20.0, 100.0),
+        pos("AAPL", "OPT", 1.0, 4.0),
+    ]);
+    let tpl = template(
+        AllocationBasis::Ticker,
+        &[("AAPL", 60.0), ("MSFT", 30.0), ("TSLA", 10.0)],
+    );
+    let plan = svc.evaluate(&tpl, None).await.unwrap();
+
+    assert_eq!(plan.account, "DU1");
+    assert_eq!(plan.total_value, 10_000.0);
+    let drift: Vec<_> = plan
+        .allocations
+        .iter()
+        .map(|a| (a.key.as_str(), a.drift))
+        .collect();
+    assert_eq!(
+        drift,
+        vec![("AAPL", 20.0), ("MSFT", -10.0), ("TSLA", -10.0)]
+    );
+
+    let trades: Vec<_> = plan
+        .trades
+        .iter()
+        .map(|t| {
+            (
+                t.symbol.as_str(),
+                matches!(t.action, OrderAction::Buy),
+                t.quantity,
+            )
+        })
+        .collect();
+    // Sell 2_000 of AAPL, buy 1_000 MSFT at 100 and 1_000 TSLA at the quoted 50.
+    assert_eq!(
+        trades,
+        vec![
+            ("AAPL", false, 20.0),
+            ("MSFT", true, 10.0),
+            ("TSLA", true, 20.0)
+        ]
+    );
+    assert_eq!(plan.notes, vec!["AAPL: OPT position left out".to_string()]);
+}
+
+#[tokio::test]
+async fn sector_template_spreads_across_holdings_and_sells_untargeted() {
+    // tech: AAPL 3_000 + MSFT 1_000; semis: NVDA 4_000; JPM (financials) 2_000.
+    let svc = service(vec![
+        pos("AAPL", "STK", 30.0, 100.0),
+        pos("MSFT", "STK", 10.0, 100.0),
+        pos("NVDA", "STK", 40.0, 100.0),
+        pos("JPM", "STK", 20.0, 100.0),
+    ]);
+    let tpl = template(
+        AllocationBasis::Sector,
+        &[("tech", 60.0), ("semis", 30.0), ("energy", 10.0)],
+    );
+    let plan = svc.evaluate(&tpl, Some("DU2".to_string())).await.unwrap();
+
+    assert_eq!(plan.account, "DU2");
+    let keys: Vec<_> = plan.allocations.iter().map(|a| a.key.as_str()).collect();
+    assert_eq!(keys, vec!["tech", "semis", "energy", "financials"]);
+
+    let trades: Vec<_> = plan
+        .trades
+        .iter()
+        .map(|t| (t.symbol.as_str(), t.quantity))
+        .collect();
+    // semis -1_000 and financials -2_000 first; tech +2_000 split 3:1.
+    assert_eq!(
+        trades,
+        vec![("NVDA", 10.0), ("JPM", 20.0), ("AAPL", 15.0), ("MSFT", 5.0)]
+    );
+    assert_eq!(plan.notes.len(), 1);
+    assert!(plan.notes[0].starts_with("energy: nothing held"));
+}
+
+#[test]
+fn upsert_normalises_keys_and_remove_reports_missing() {
+    let mut cfg = ModelPortfoliosConfig::default();
+    let mut tpl = template(AllocationBasis::Ticker, &[(" aapl ", 100.0)]);
+    tpl.name = "  Core ".to_string();
+    cfg.upsert(tpl).unwrap();
+    assert_eq!(cfg.find("core").unwrap().targets[0].key, "AAPL");
+
+    assert_eq!(
+        cfg.remove("other"),
+        Err(ModelPortfolioError::NotFound("other".to_string()))
+    );
+    cfg.remove("CORE").unwrap();
+    assert!(cfg.templates.is_empty());
+}
@@ -0,0 +1,113 @@
+//! Markdown rendering of a [`Briefing`]. Empty sections are left out.
+
+use std::fmt::Write;
+
+use super::Briefing;
+use crate::services::portfolio_diff::HoldingChange;
+
+pub fn render(b: &Briefing) -> String {
+    let mut md = format!("# Morning briefing, {}\n", b.date);
+    if let Some(account) = &b.account {
+        let _ = writeln!(md, "\nAccount {account}.");
+    }
+
+    if let Some(diff) = &b.diff {
+        let _ = writeln!(md, "\n## Portfolio since {}\n", diff.baseline_date);
+        let list = |changes: &[HoldingChange]| {
+            changes
+                .iter()
+                .map(|c| c.contract.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        if diff.opened.is_empty() && diff.closed.is_empty() && diff.resized.is_empty() {
+            md.push_str("- No positions opened, closed or resized.\n");
+        }
+        if !diff.opened.is_empty() {
+            let _ = writeln!(md, "- Opened: {}", list(&diff.opened));
+        }
+        if !diff.closed.is_empty() {
+            let _ = writeln!(md, "- Closed: {}", list(&diff.closed));
+        }
+        for c in &diff.resized {
+            let _ = writeln!(
+                md,
+                "- Resized {}: {} → {}",
+                c.contract, c.quantity_before, c.quantity_now
+            );
+        }
+        for c in &diff.movers {
+            let _ = writeln!(
+                md,
+                "- Mover {}: {:+.2} {}",
+                c.contract,
+                c.pnl_change.unwrap_or(0.0),
+                c.currency
+            );
+        }
+        for t in &diff.totals {
+            let _ = writeln!(
+                md,
+                "- {} market value {:.2} → {:.2}, P&L {:+.2}",
+                t.currency, t.market_value_before, t.market_value_now, t.pnl_change
+            );
+        }
+    }
+
+    if !b.earnings.is_empty() || b.days_to_fomc.is_some() {
+        md.push_str("\n## Events\n\n");
+        for e in &b.earnings {
+            let _ = writeln!(
+                md,
+                "- {} earnings {} (in {} trading days, {})",
+                e.symbol,
+                e.date,
+                e.trading_days_until,
+                e.confidence.as_str()
+            );
+        }
+        if let Some(days) = b.days_to_fomc {
+            let _ = writeln!(md, "- FOMC in {days} days");
+        }
+    }
+
+    if !b.alerts.is_empty() {
+        md.push_str("\n## Alerts (last 24h)\n\n");
+        for a in &b.alerts {
+            let symbol = a.payload["symbol"].as_str().unwrap_or("setup");
+            let _ = writeln!(
+                md,
+                "- {} {} {} (setup #{})",
+                a.fired_at.format("%m-%d %H:%M UTC"),
+                symbol,
+                a.kind.as_str().replace('_', " "),
+                a.setup_id
+            );
+        }
+    }
+
+    if !b.fair_value.is_empty() {
+        md.push_str("\n## Fair value\n\n");
+        md.push_str("| Symbol | Price | Bear | Bull | Zone |\n");
+        md.push_str("|---|---:|---:|---:|---|\n");
+        for f in &b.fair_value {
+            let _ = writeln!(
+                md,
+                "| {} | {:.2} | {:.2} | {:.2} | {} |",
+                f.symbol,
+                f.price,
+                f.band.bear_value,
+                f.band.bull_value,
+                f.zone.as_str().replace('_', " ")
+            );
+        }
+    }
+
+    if !b.notes.is_empty() {
+        md.push_str("\n## Notes\n\n");
+        for note in &b.notes {
+            let _ = writeln!(md, "- {note}");
+        }
+    }
+    md
+}
@@ -0,0 +1,214 @@
+//! Pre-market briefing: one read of everything worth knowing before
+//! the open.
+//!
+//! [`MorningBriefingService::generate`] pulls together
+//!
+//!   - the last session's portfolio changes (`services::portfolio_diff`),
+//!   - earnings of held symbols in the next [`EARNINGS_WINDOW`] trading
+//!     days, and days to the next FOMC meeting (`services::event_calendar`),
+//!   - tracker alerts fired in the last 24 hours (`services::alerts`),
+//!   - fair-value flags of held symbols and of anything outside its band
+//!     (`services::fair_value_watch`),
+//!
+//! into a [`Briefing`], optionally rendered to Markdown. A section that
+//! can't be built (IBKR down, no position snapshot yet) is left empty
+//! and explained in `notes`; the rest of the briefing still comes back.
+//!
+//! The `morning_briefing` task (off by default, 08:30 ET on trading
+//! days) generates the Markdown and returns it as the run summary, so
+//! with notifications on it arrives as the morning email.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::tracker::Alert;
+use crate::services::alerts::{list_alerts, ListAlertsQuery};
+use crate::services::event_calendar::{BlackoutConfidence, EventCalendarService};
+use crate::services::fair_value_watch::{FairValueFlag, FairValueWatcher, FairValueZone};
+use crate::services::portfolio_diff::{PortfolioDiff, PortfolioDiffService};
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+use crate::storage::Db;
+use crate::utils::market_calendar;
+
+pub mod markdown;
+
+#[cfg(test)]
+mod tests;
+
+/// Trading days ahead that earnings are listed for.
+pub const EARNINGS_WINDOW: u32 = 10;
+/// How far back alerts are listed.
+const ALERT_LOOKBACK_HOURS: i64 = 24;
+const ALERT_LIMIT: u32 = 50;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingEarnings {
+    pub symbol: String,
+    pub date: NaiveDate,
+    pub trading_days_until: u32,
+    pub confidence: BlackoutConfidence,
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Briefing {
+    /// ET date the briefing is for.
+    pub date: NaiveDate,
+    /// Unix seconds.
+    pub generated_at: i64,
+    pub account: Option<String>,
+    pub diff: Option<PortfolioDiff>,
+    /// Soonest first.
+    pub earnings: Vec<UpcomingEarnings>,
+    pub days_to_fomc: Option<i64>,
+    /// Newest first.
+    pub alerts: Vec<Alert>,
+    /// Outside-band flags first, then by symbol.
+    pub fair_value: Vec<FairValueFlag>,
+    /// Sections that couldn't be built, and why.
+    pub notes: Vec<String>,
+    pub markdown: Option<String>,
+}
+
+pub struct MorningBriefingService {
+    db: Arc<Db>,
+    diffs: Arc<PortfolioDiffService>,
+    positions: Arc<dyn OpenPositionsSource>,
+    account: Arc<dyn AccountSource>,
+    calendar: Arc<EventCalendarService>,
+    fair_value: Arc<FairValueWatcher>,
+}
+
+impl MorningBriefingService {
+    pub fn new(
+        db: Arc<Db>,
+        diffs: Arc<PortfolioDiffService>,
+        positions: Arc<dyn OpenPositionsSource>,
+        account: Arc<dyn AccountSource>,
+        calendar: Arc<EventCalendarService>,
+        fair_value: Arc<FairValueWatcher>,
+    ) -> Self {
+        Self {
+            db,
+            diffs,
+            positions,
+            account,
+            calendar,
+            fair_value,
+        }
+    }
+
+    pub async fn generate(&self, with_markdown: bool) -> Briefing {
+        self.generate_at(Utc::now(), with_markdown).await
+    }
+
+    pub(crate) async fn generate_at(&self, now: DateTime<Utc>, with_markdown: bool) -> Briefing {
+        let mut notes = Vec::new();
+
+        let (account, held) = match self.held_symbols().await {
+            Ok((account, held)) => (Some(account), held),
+            Err(e) => {
+                notes.push(format!("Positions unavailable: {e}"));
+                (None, BTreeSet::new())
+            }
+        };
+
+        let diff = match self.diffs.diff_last_session().await {
+            Ok(diff) => Some(diff),
+            Err(e) => {
+                notes.push(format!("Portfolio diff unavailable: {e}"));
+                None
+            }
+        };
+
+        let mut earnings = Vec::new();
+        for symbol in &held {
+            match self.calendar.lookup(symbol, now).await {
+                Ok(lookup) => {
+                    if let Some(e) = lookup.next_earnings {
+                        if e.trading_days_until <= EARNINGS_WINDOW {
+                            earnings.push(UpcomingEarnings {
+                                symbol: symbol.clone(),
+                                date: e.date,
+                                trading_days_until: e.trading_days_until,
+                                confidence: e.confidence,
+                                source: e.source,
+                            });
+                        }
+                    }
+                }
+                Err(e) => notes.push(format!("Earnings for {symbol} unavailable: {e}")),
+            }
+        }
+        earnings.sort_by(|a, b| a.date.cmp(&b.date).then(a.symbol.cmp(&b.symbol)));
+
+        let alerts = match list_alerts(
+            &self.db,
+            ListAlertsQuery {
+                limit: ALERT_LIMIT,
+                since: Some(now - Duration::hours(ALERT_LOOKBACK_HOURS)),
+                ..Default::default()
+            },
+        )
+        .await
+        {
+            Ok(alerts) => alerts,
+            Err(e) => {
+                notes.push(format!("Alerts unavailable: {e}"));
+                Vec::new()
+            }
+        };
+
+        let fair_value = match self.fair_value.list().await {
+            Ok(flags) => {
+                let mut flags: Vec<_> = flags
+                    .into_iter()
+                    .filter(|f| f.zone != FairValueZone::Within || held.contains(&f.symbol))
+                    .collect();
+                flags.sort_by_key(|f| (f.zone == FairValueZone::Within, f.symbol.clone()));
+                flags
+            }
+            Err(e) => {
+                notes.push(format!("Fair value flags unavailable: {e}"));
+                Vec::new()
+            }
+        };
+
+        let mut briefing = Briefing {
+            date: market_calendar::et_date(now),
+            generated_at: now.timestamp(),
+            account,
+            diff,
+            earnings,
+            days_to_fomc: self.calendar.fomc().days_to_next(now),
+            alerts,
+            fair_value,
+            notes,
+            markdown: None,
+        };
+        if with_markdown {
+            briefing.markdown = Some(markdown::render(&briefing));
+        }
+        briefing
+    }
+
+    /// Current account and the underlying symbols it holds.
+    async fn held_symbols(&self) -> Result<(String, BTreeSet<String>), IbkrError> {
+        let account = self.account.current_account().await?;
+        let held = self
+            .positions
+            .list_open(&account)
+            .await?
+            .into_iter()
+            .map(|p| p.symbol.to_uppercase())
+            .collect();
+        Ok((account, held))
+    }
+}
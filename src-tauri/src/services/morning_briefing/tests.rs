@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::TimeZone;
+use tempfile::NamedTempFile;
+
+use super::*;
+use crate::events::EventEmitter;
+use crate::ibkr::types::{Position, ProjectionAssumptions};
+use crate::services::event_calendar::{
+    EarningsCalendar, EarningsEntry, EarningsError, FomcCalendar,
+};
+use crate::services::fair_value_watch::{FairValueBand, PriceSource};
+use crate::services::projection_history::ProjectionHistoryStore;
+use crate::services::projection_service::ProjectionService;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+/// `None` is a disconnected gateway.
+struct StubPositions(Option<Vec<Position>>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        self.0.clone().ok_or(IbkrError::NotConnected)
+    }
+}
+
+struct FixedEarnings(HashMap<&'static str, NaiveDate>);
+
+#[async_trait]
+impl EarningsCalendar for FixedEarnings {
+    async fn next_earnings_date(
+        &self,
+        symbol: &str,
+        _now: DateTime<Utc>,
+    ) -> Result<Option<EarningsEntry>, EarningsError> {
+        Ok(self.0.get(symbol).map(|date| EarningsEntry {
+            symbol: symbol.to_string(),
+            date: *date,
+            confidence: BlackoutConfidence::Confirmed,
+            source: "manual".to_string(),
+        }))
+    }
+}
+
+struct FixedPrice(f64);
+
+#[async_trait]
+impl PriceSource for FixedPrice {
+    async fn latest_price(&self, _symbol: &str) -> Option<f64> {
+        Some(self.0)
+    }
+}
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+fn stock(symbol: &str, qty: f64, value: f64) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: qty,
+        market_value: value,
+        market_price: value / qty,
+        contract_type: "STK".to_string(),
+        currency: "USD".to_string(),
+        ..Default::default()
+    }
+}
+
+/// NVDA projected and priced below its bear value; AAPL and MSFT held,
+/// AAPL reporting in four trading days.
+async fn service(tmp: &NamedTempFile, positions: Option<Vec<Position>>) -> MorningBriefingService {
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+
+    let history = Arc::new(ProjectionHistoryStore::new(Arc::clone(&db)));
+    let assumptions = ProjectionAssumptions::default();
+    let data = ProjectionService::generate_mock_fundamental_data("NVDA");
+    let results = ProjectionService::generate_projection_results(&data, &assumptions).unwrap();
+    history
+        .record("NVDA", &assumptions, &results)
+        .await
+        .unwrap();
+    let band = FairValueBand::from_results(&results).unwrap();
+    let fair_value = Arc::new(FairValueWatcher::new(
+        Arc::clone(&db),
+        history,
+        Arc::new(FixedPrice(band.bear_value * 0.9)),
+        Arc::new(EventEmitter::for_capture()),
+    ));
+    fair_value.run().await.unwrap();
+
+    let positions: Arc<dyn OpenPositionsSource> = Arc::new(StubPositions(positions));
+    let diffs = Arc::new(PortfolioDiffService::new(
+        Arc::clone(&db),
+        Arc::clone(&positions),
+        Arc::new(FixedAccount),
+    ));
+    let calendar = Arc::new(EventCalendarService::new(
+        Arc::new(FixedEarnings(HashMap::from([
+            ("AAPL", date("2026-10-20")),
+            ("MSFT", date("2026-12-01")),
+        ]))),
+        Arc::new(FomcCalendar::from_dates(vec![date("2026-10-28")])),
+    ));
+    MorningBriefingService::new(
+        db,
+        diffs,
+        positions,
+        Arc::new(FixedAccount),
+        calendar,
+        fair_value,
+    )
+}
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 14, 12, 0, 0).unwrap()
+}
+
+#[tokio::test]
+async fn combines_every_section() {
+    let tmp = NamedTempFile::new().unwrap();
+    let svc = service(
+        &tmp,
+        Some(vec![
+            stock("AAPL", 100.0, 18_000.0),
+            stock("MSFT", 10.0, 4_000.0),
+        ]),
+    )
+    .await;
+    // Snapshot long before "the session before last", so the diff has a
+    // baseline whatever today is.
+    svc.diffs.snapshot_on(date("2020-01-02")).await.unwrap();
+
+    let b = svc.generate_at(now(), true).await;
+    assert_eq!(b.date, date("2026-10-14"));
+    assert_eq!(b.account.as_deref(), Some("DU1"));
+    assert_eq!(b.diff.unwrap().baseline_date, date("2020-01-02"));
+    assert_eq!(b.earnings.len(), 1, "MSFT reports outside the window");
+    assert_eq!(b.earnings[0].symbol, "AAPL");
+    assert_eq!(b.earnings[0].trading_days_until, 4);
+    assert_eq!(b.days_to_fomc, Some(14));
+    assert_eq!(b.fair_value[0].symbol, "NVDA");
+    assert_eq!(b.fair_value[0].zone, FairValueZone::BelowBear);
+    assert!(b.notes.is_empty(), "{:?}", b.notes);
+
+    let md = b.markdown.unwrap();
+    assert!(md.starts_with("# Morning briefing, 2026-10-14\n"));
+    assert!(md.contains("- AAPL earnings 2026-10-20 (in 4 trading days, confirmed)"));
+    assert!(md.contains("- FOMC in 14 days"));
+    assert!(md.contains("| NVDA |"));
+    assert!(!md.contains("## Notes"));
+}
+
+#[tokio::test]
+async fn missing_sections_become_notes() {
+    let tmp = NamedTempFile::new().unwrap();
+    let svc = service(&tmp, None).await;
+
+    let b = svc.generate_at(now(), false).await;
+    assert!(b.account.is_none());
+    assert!(b.diff.is_none());
+    assert!(b.earnings.is_empty());
+    assert!(b.markdown.is_none());
+    assert_eq!(b.notes.len(), 2);
+    assert!(b.notes[0].starts_with("Positions unavailable"));
+    assert!(b.notes[1].starts_with("Portfolio diff unavailable"));
+    // Stored sections still come back.
+    assert_eq!(b.fair_value.len(), 1);
+}
@@ -1,6 +1,7 @@
 //! Vendor-neutral SQLite cache for `NewsItem` payloads + LLM
-//! `NewsVerdict`s, keyed by symbol. Producers (today: `IbkrNewsProvider`)
-//! call [`write_cache`] after a successful fetch; consumers
+//! `NewsVerdict`s, keyed by normalized symbol (`utils::symbols`).
+//! Producers (today: `IbkrNewsProvider`) call [`write_cache`] after a
+//! successful fetch; consumers
 //! (`NewsInterpreter`, the MCP `get_news` tool) read through
 //! [`read_cache_with_verdict`] and the interpreter writes its verdict
 //! back via [`write_verdict`].
@@ -11,6 +12,7 @@
 
 use crate::ibkr::types::news::NewsItem;
 use crate::storage::Db;
+use crate::utils::symbols;
 
 /// Cached news row, including the optional LLM-derived verdict JSON.
 #[derive(Debug, Clone)]
@@ -33,7 +35,7 @@ pub async fn read_cache_with_verdict(
     db: &Db,
     symbol: &str,
 ) -> Result<Option<CachedNews>, Box<dyn std::error::Error + Send + Sync>> {
-    let symbol = symbol.to_string();
+    let symbol = symbols::normalize(symbol);
     let row = db
         .with_conn(move |conn| {
             let mut stmt = conn.prepare(
@@ -50,6 +52,7 @@ pub async fn read_cache_with_verdict(
             }
         })
         .await?;
+    crate::telemetry::record_cache_lookup("news", row.is_some());
 
     match row {
         Some((fetched_at, payload, verdict_json)) => {
@@ -72,7 +75,7 @@ pub async fn write_verdict(
     symbol: &str,
     verdict_json: &str,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let symbol = symbol.to_string();
+    let symbol = symbols::normalize(symbol);
     let verdict_json = verdict_json.to_string();
     db.with_conn(move |conn| {
         conn.execute(
@@ -95,7 +98,7 @@ pub async fn write_cache(
     fetched_at: i64,
     items: &[NewsItem],
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let symbol = symbol.to_string();
+    let symbol = symbols::normalize(symbol);
     let payload = serde_json::to_string(items)?;
     db.with_conn(move |conn| {
         conn.execute(
@@ -0,0 +1,194 @@
+//! Human wording for the events worth a notification. Everything else
+//! renders to `None` and is never sent, whatever `notifications.events`
+//! says.
+
+use crate::events::AppEvent;
+use crate::services::option_expiry::AssignmentRisk;
+use crate::strategies::Direction;
+
+use super::Notification;
+
+pub fn render(event: &AppEvent) -> Option<Notification> {
+    let (title, body) = match event {
+        AppEvent::SetupDetected { setup, thesis } => {
+            let side = match setup.direction {
+                Direction::Long => "long",
+                Direction::Short => "short",
+            };
+            let mut body = format!(
+                "{} {side} setup: trigger {:.2}, stop {:.2}.",
+                setup.strategy, setup.trigger_price, setup.stop_price
+            );
+            if let Some(thesis) = thesis {
+                body.push_str("\n\n");
+                body.push_str(thesis);
+            }
+            (format!("{}: {} setup", setup.symbol, setup.strategy), body)
+        }
+        AppEvent::FairValueCrossed {
+            symbol,
+            zone,
+            price,
+            bear_value,
+            bull_value,
+        } => (
+            format!("{symbol}: fair value {}", zone.as_str().replace('_', " ")),
+            format!("Price {price:.2} against a bear value of {bear_value:.2} and a bull value of {bull_value:.2}."),
+        ),
+        AppEvent::MarginOfSafetyEntered {
+            symbol,
+            scenario,
+            zone,
+            price,
+            buy_below,
+            sell_above,
+        } => (
+            format!("{symbol}: {} zone ({scenario})", zone.as_str()),
+            format!(
+                "Price {price:.2}; buy below {buy_below:.2}, sell above {sell_above:.2}."
+            ),
+        ),
+        AppEvent::PositionLevelHit {
+            account,
+            contract,
+            level,
+            level_price,
+            price,
+            thesis,
+        } => {
+            let mut body = format!(
+                "Price {price:.2} reached the {} at {level_price:.2}.",
+                level.as_str()
+            );
+            if let Some(thesis) = thesis {
+                body.push_str("\n\nThesis: ");
+                body.push_str(thesis);
+            }
+            (format!("{account} {contract}: {} hit", level.as_str()), body)
+        }
+        AppEvent::StopBreached {
+            account,
+            contract,
+            stop_price,
+            price,
+            result,
+            ..
+        } => (
+            format!("{account} {contract}: stop breached"),
+            format!("Price {price:.2} breached the stop at {stop_price:.2}: {result}."),
+        ),
+        AppEvent::OrderFilled {
+            order_id,
+            filled_qty,
+        } => (
+            format!("Order {order_id} filled"),
+            format!("{filled_qty} filled."),
+        ),
+        AppEvent::MarginCushionLow {
+            account,
+            cushion,
+            threshold_pct,
+            excess_liquidity,
+        } => (
+            format!("{account}: margin cushion low"),
+            format!(
+                "Cushion {:.1}% is below {threshold_pct:.1}%; excess liquidity {excess_liquidity:.2}.",
+                cushion * 100.0
+            ),
+        ),
+        AppEvent::IdleCash {
+            account,
+            cash_pct,
+            threshold_pct,
+            idle_cash,
+            suggestions,
+        } => {
+            let mut body = format!(
+                "Cash is {cash_pct:.1}% of NAV, above {threshold_pct:.1}%; {idle_cash:.2} idle."
+            );
+            if !suggestions.is_empty() {
+                body.push_str(&format!(
+                    "\n\nBelow their buy band: {}.",
+                    suggestions.join(", ")
+                ));
+            }
+            (format!("{account}: idle cash"), body)
+        }
+        AppEvent::CorporateActionApplied {
+            action, adjusted, ..
+        } => {
+            let tables: Vec<String> = adjusted
+                .iter()
+                .map(|a| format!("{} {}", a.rows, a.table))
+                .collect();
+            (
+                format!("{}: corporate action", action.symbol()),
+                if tables.is_empty() {
+                    format!("Applied the {}; nothing local to adjust.", action.describe())
+                } else {
+                    format!("Applied the {}; adjusted {}.", action.describe(), tables.join(", "))
+                },
+            )
+        }
+        AppEvent::OptionAssignmentRisk {
+            local_symbol,
+            risk,
+            expiry,
+            dte,
+            moneyness_pct,
+            ex_dividend_date,
+            ..
+        } => (
+            format!("{local_symbol}: assignment risk"),
+            match (risk, ex_dividend_date) {
+                (AssignmentRisk::EarlyExercise, Some(ex_date)) => format!(
+                    "Short call {moneyness_pct:.1}% in the money; the dividend going ex on {ex_date} is worth more than its time value. Early exercise is likely before then."
+                ),
+                _ => format!(
+                    "Short option at {moneyness_pct:+.1}% moneyness expires {expiry} ({dte}d); likely assigned, or pinned at the strike."
+                ),
+            },
+        ),
+        AppEvent::TiltActivated {
+            account,
+            trigger_kind,
+            cumulative_r,
+            auto_reset_at,
+            ..
+        } => (
+            format!("{account}: tilt guard paused trading"),
+            format!(
+                "Trigger {trigger_kind}, cumulative {cumulative_r:.2}R. Resets {}.",
+                auto_reset_at.format("%Y-%m-%d %H:%M UTC")
+            ),
+        ),
+        AppEvent::CashWarning { warning } => (
+            format!("{} {}: cash warning", warning.account, warning.currency),
+            warning.message.clone(),
+        ),
+        AppEvent::RuleTriggered {
+            name,
+            symbol,
+            outcome,
+            detail,
+            ..
+        } => (
+            format!(
+                "{}: rule \"{name}\" {}",
+                if symbol.is_empty() { "Portfolio" } else { symbol },
+                outcome.replace('_', " ")
+            ),
+            detail.clone().unwrap_or_default(),
+        ),
+        AppEvent::ScheduledJobFinished { id, ok, summary } => (
+            format!("{id} {}", if *ok { "finished" } else { "failed" }),
+            summary.clone(),
+        ),
+        _ => return None,
+    };
+    Some(Notification {
+        event: event.name().to_string(),
+        title,
+        body,
+    })
+}
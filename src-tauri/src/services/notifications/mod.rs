@@ -0,0 +1,308 @@
+//! Off-device notifications: SMTP email, webhooks and Telegram.
+//!
+//! `Notifier` is attached to the `EventEmitter` as its [`EventSink`], so
+//! it sees every `AppEvent` the UI does. It does not depend on the
+//! window being open. Events named in `notifications.events` that
+//! [`messages::render`] knows how to phrase go out to every configured
+//! channel: one email to `email.to`, one POST per webhook, and one
+//! Telegram message per allowed chat when the bot is enabled. The
+//! webhook body is shaped for Discord, Slack or a generic JSON consumer.
+//! By default that covers tracker setups, fair-value and
+//! margin-of-safety crossings, breached babysat stops, order fills,
+//! margin and tilt alarms, finished scheduled jobs (the Notion export
+//! and friends) and fired automation rules.
+//!
+//! Delivery runs in a spawned task, so `emit` never waits on SMTP. A
+//! failing channel is logged and doesn't stop the others. The SMTP
+//! password lives in the keychain (`SecretProvider::SmtpPassword`).
+//! Webhook URLs embed their own token and sit in settings.json like
+//! any other endpoint. Settings are re-read per event, so edits take
+//! effect without a restart.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::config::AppConfig;
+use crate::events::{AppEvent, EventSink};
+use crate::services::telegram_bot::{TelegramApi, TelegramConfig};
+
+pub mod messages;
+pub mod transport;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Event names (as the UI sees them, e.g. `fair-value-crossed`) to
+    /// forward. Names with no message are ignored.
+    #[serde(default = "default_events")]
+    pub events: Vec<String>,
+    #[serde(default)]
+    pub email: Option<EmailConfig>,
+    /// SMTP password. Kept in the OS keychain; see `config/secrets.rs`.
+    #[serde(default)]
+    pub smtp_password: Option<String>,
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            events: default_events(),
+            email: None,
+            smtp_password: None,
+            webhooks: Vec::new(),
+        }
+    }
+}
+
+fn default_events() -> Vec<String> {
+    [
+        "setup-detected",
+        "fair-value-crossed",
+        "margin-of-safety-entered",
+        "position-level-hit",
+        "stop-breached",
+        "order-filled",
+        "margin-cushion-low",
+        "tilt-activated",
+        "scheduled-job-finished",
+        "rule-triggered",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailConfig {
+    pub smtp_host: String,
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    /// Login; no authentication when `None`.
+    #[serde(default)]
+    pub username: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpSecurity {
+    /// STARTTLS upgrade, usually port 587.
+    #[default]
+    StartTls,
+    /// Implicit TLS, usually port 465.
+    Tls,
+    /// Plaintext; only for a local relay.
+    None,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    #[serde(default)]
+    pub kind: WebhookKind,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookKind {
+    /// `{ "title", "body", "event" }`.
+    #[default]
+    Generic,
+    /// `{ "content" }`.
+    Discord,
+    /// `{ "text" }`.
+    Slack,
+}
+
+/// One message, channel-agnostic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    /// Event name it came from, or `test`.
+    pub event: String,
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Error, Debug)]
+pub enum NotificationError {
+    #[error("notifications are not configured: {0}")]
+    NotConfigured(&'static str),
+    #[error("email: {0}")]
+    Email(String),
+    #[error("webhook: {0}")]
+    Webhook(String),
+    #[error("telegram: {0}")]
+    Telegram(String),
+}
+
+/// Trait seam over the network. Production is
+/// [`transport::LiveTransport`]; tests record what would be sent.
+#[async_trait]
+pub trait NotificationTransport: Send + Sync {
+    async fn send_email(
+        &self,
+        email: &EmailConfig,
+        password: Option<&str>,
+        notification: &Notification,
+    ) -> Result<(), NotificationError>;
+    async fn post_webhook(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> Result<(), NotificationError>;
+}
+
+/// Per-channel outcome of one delivery.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryReport {
+    pub delivered: Vec<String>,
+    /// `(channel, error)`.
+    pub failed: Vec<(String, String)>,
+}
+
+#[derive(Clone)]
+pub struct Notifier {
+    transport: Arc<dyn NotificationTransport>,
+    config: Arc<RwLock<AppConfig>>,
+    telegram: Option<Arc<dyn TelegramApi>>,
+}
+
+impl Notifier {
+    pub fn new(transport: Arc<dyn NotificationTransport>, config: Arc<RwLock<AppConfig>>) -> Self {
+        Self {
+            transport,
+            config,
+            telegram: None,
+        }
+    }
+
+    /// Also push to the Telegram bot's allowed chats.
+    pub fn with_telegram(mut self, api: Arc<dyn TelegramApi>) -> Self {
+        self.telegram = Some(api);
+        self
+    }
+
+    /// Forward `event` if it is enabled and has a message.
+    pub async fn handle(&self, event: &AppEvent) -> Option<DeliveryReport> {
+        let (config, telegram) = self.settings().await;
+        if !config.enabled || !config.events.iter().any(|e| e == event.name()) {
+            return None;
+        }
+        let notification = messages::render(event)?;
+        Some(self.deliver(&config, &telegram, &notification).await)
+    }
+
+    /// Send a fixed message through every channel, enabled or not, so
+    /// the settings can be checked.
+    pub async fn send_test(&self) -> Result<DeliveryReport, NotificationError> {
+        let (config, telegram) = self.settings().await;
+        if config.email.is_none() && config.webhooks.is_empty() && !self.telegram_on(&telegram) {
+            return Err(NotificationError::NotConfigured(
+                "no email, webhook or Telegram channel",
+            ));
+        }
+        let notification = Notification {
+            event: "test".to_string(),
+            title: "Quantum Kapital test notification".to_string(),
+            body: "Notifications are set up correctly.".to_string(),
+        };
+        Ok(self.deliver(&config, &telegram, &notification).await)
+    }
+
+    async fn settings(&self) -> (NotificationsConfig, TelegramConfig) {
+        let config = self.config.read().await;
+        (config.notifications.clone(), config.telegram.clone())
+    }
+
+    fn telegram_on(&self, telegram: &TelegramConfig) -> bool {
+        self.telegram.is_some()
+            && telegram.active_token().is_some()
+            && !telegram.allowed_chat_ids.is_empty()
+    }
+
+    async fn deliver(
+        &self,
+        config: &NotificationsConfig,
+        telegram: &TelegramConfig,
+        notification: &Notification,
+    ) -> DeliveryReport {
+        let mut report = DeliveryReport::default();
+        if let Some(email) = &config.email {
+            let outcome = self
+                .transport
+                .send_email(email, config.smtp_password.as_deref(), notification)
+                .await;
+            record(&mut report, "email".to_string(), outcome);
+        }
+        for hook in &config.webhooks {
+            let body = transport::webhook_body(hook.kind, notification);
+            let outcome = self.transport.post_webhook(&hook.url, &body).await;
+            record(&mut report, webhook_label(&hook.url), outcome);
+        }
+        if let (Some(api), Some(token)) = (&self.telegram, telegram.active_token()) {
+            let text = format!("{}\n{}", notification.title, notification.body);
+            for chat_id in &telegram.allowed_chat_ids {
+                let outcome = api
+                    .send_message(token, *chat_id, &text)
+                    .await
+                    .map_err(|e| NotificationError::Telegram(e.to_string()));
+                record(&mut report, format!("telegram {chat_id}"), outcome);
+            }
+        }
+        report
+    }
+}
+
+impl EventSink for Notifier {
+    fn observe(&self, event: &AppEvent) {
+        // Cheap filter before spawning; `handle` re-checks the settings.
+        if messages::render(event).is_none() {
+            return;
+        }
+        let notifier = self.clone();
+        let event = event.clone();
+        tokio::spawn(async move {
+            notifier.handle(&event).await;
+        });
+    }
+}
+
+fn record(report: &mut DeliveryReport, channel: String, outcome: Result<(), NotificationError>) {
+    match outcome {
+        Ok(()) => report.delivered.push(channel),
+        Err(e) => {
+            warn!("notifications: {channel} failed: {e}");
+            report.failed.push((channel, e.to_string()));
+        }
+    }
+}
+
+/// Host of a webhook URL, so reports and logs never carry its token.
+fn webhook_label(url: &str) -> String {
+    let host = url
+        .split("://")
+        .nth(1)
+        .and_then(|rest| rest.split('/').next())
+        .unwrap_or("invalid url");
+    format!("webhook {host}")
+}
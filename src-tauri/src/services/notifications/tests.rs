@@ -0,0 +1,240 @@
+use std::sync::Mutex;
+
+use serde_json::Value;
+
+use super::*;
+use crate::services::fair_value_watch::FairValueZone;
+use crate::services::telegram_bot::{TelegramError, Update};
+
+/// Records every send; fails email when `email_down`.
+#[derive(Default)]
+struct Recording {
+    emails: Mutex<Vec<(String, Option<String>, Notification)>>,
+    posts: Mutex<Vec<(String, Value)>>,
+    email_down: bool,
+}
+
+#[async_trait]
+impl NotificationTransport for Recording {
+    async fn send_email(
+        &self,
+        email: &EmailConfig,
+        password: Option<&str>,
+        notification: &Notification,
+    ) -> Result<(), NotificationError> {
+        if self.email_down {
+            return Err(NotificationError::Email("connection refused".into()));
+        }
+        self.emails.lock().unwrap().push((
+            email.smtp_host.clone(),
+            password.map(str::to_string),
+            notification.clone(),
+        ));
+        Ok(())
+    }
+
+    async fn post_webhook(&self, url: &str, body: &Value) -> Result<(), NotificationError> {
+        self.posts
+            .lock()
+            .unwrap()
+            .push((url.to_string(), body.clone()));
+        Ok(())
+    }
+}
+
+fn configured() -> AppConfig {
+    let mut config = AppConfig::default();
+    config.notifications.enabled = true;
+    config.notifications.email = Some(EmailConfig {
+        smtp_host: "smtp.example.com".into(),
+        smtp_port: 587,
+        security: SmtpSecurity::StartTls,
+        username: Some("me".into()),
+        from: "qk@example.com".into(),
+        to: vec!["me@example.com".into()],
+    });
+    config.notifications.smtp_password = Some("hunter2".into());
+    config.notifications.webhooks = vec![
+        WebhookConfig {
+            url: "https://discord.com/api/webhooks/1/secret".into(),
+            kind: WebhookKind::Discord,
+        },
+        WebhookConfig {
+            url: "https://hooks.slack.com/services/T/B/secret".into(),
+            kind: WebhookKind::Slack,
+        },
+    ];
+    config
+}
+
+fn notifier(transport: Arc<Recording>, config: AppConfig) -> Notifier {
+    Notifier::new(transport, Arc::new(RwLock::new(config)))
+}
+
+fn crossed() -> AppEvent {
+    AppEvent::FairValueCrossed {
+        symbol: "NVDA".into(),
+        zone: FairValueZone::BelowBear,
+        price: 90.0,
+        bear_value: 100.0,
+        bull_value: 180.0,
+    }
+}
+
+#[tokio::test]
+async fn forwards_enabled_events_to_every_channel() {
+    let transport = Arc::new(Recording::default());
+    let notifier = notifier(Arc::clone(&transport), configured());
+
+    let report = notifier.handle(&crossed()).await.unwrap();
+    assert_eq!(
+        report.delivered,
+        vec!["email", "webhook discord.com", "webhook hooks.slack.com"]
+    );
+
+    let emails = transport.emails.lock().unwrap();
+    assert_eq!(emails[0].1.as_deref(), Some("hunter2"));
+    assert_eq!(emails[0].2.title, "NVDA: fair value below bear");
+    let posts = transport.posts.lock().unwrap();
+    assert!(posts[0].1["content"]
+        .as_str()
+        .unwrap()
+        .starts_with("**NVDA: fair value below bear**"));
+    assert!(posts[1].1["text"].as_str().unwrap().contains("90.00"));
+}
+
+#[tokio::test]
+async fn disabled_unlisted_and_unworded_events_are_dropped() {
+    let transport = Arc::new(Recording::default());
+
+    let mut config = configured();
+    config.notifications.enabled = false;
+    assert!(notifier(Arc::clone(&transport), config)
+        .handle(&crossed())
+        .await
+        .is_none());
+
+    let mut config = configured();
+    config.notifications.events = vec!["order-filled".into()];
+    assert!(notifier(Arc::clone(&transport), config)
+        .handle(&crossed())
+        .await
+        .is_none());
+
+    let mut config = configured();
+    config
+        .notifications
+        .events
+        .push("positions-refreshed".into());
+    assert!(notifier(Arc::clone(&transport), config)
+        .handle(&AppEvent::PositionsRefreshed)
+        .await
+        .is_none());
+
+    assert!(transport.emails.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn a_failing_channel_does_not_stop_the_others() {
+    let transport = Arc::new(Recording {
+        email_down: true,
+        ..Default::default()
+    });
+    let notifier = notifier(Arc::clone(&transport), configured());
+
+    let report = notifier
+        .handle(&AppEvent::ScheduledJobFinished {
+            id: "notion_export".into(),
+            ok: true,
+            summary: "2 created, 0 updated, 0 failed".into(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(report.failed[0].0, "email");
+    assert_eq!(report.delivered.len(), 2);
+    assert_eq!(
+        transport.posts.lock().unwrap()[0].1["content"],
+        "**notion_export finished**\n2 created, 0 updated, 0 failed"
+    );
+}
+
+#[tokio::test]
+async fn send_test_needs_a_channel_but_not_enabled() {
+    let transport = Arc::new(Recording::default());
+    let mut config = configured();
+    config.notifications.enabled = false;
+    let report = notifier(Arc::clone(&transport), config)
+        .send_test()
+        .await
+        .unwrap();
+    assert_eq!(report.delivered.len(), 3);
+
+    assert!(matches!(
+        notifier(transport, AppConfig::default()).send_test().await,
+        Err(NotificationError::NotConfigured(_))
+    ));
+}
+
+/// Records every Telegram message.
+#[derive(Default)]
+struct RecordingBot {
+    sent: Mutex<Vec<(i64, String)>>,
+}
+
+#[async_trait]
+impl TelegramApi for RecordingBot {
+    async fn get_updates(
+        &self,
+        _token: &str,
+        _offset: i64,
+        _timeout_secs: u64,
+    ) -> Result<Vec<Update>, TelegramError> {
+        Ok(Vec::new())
+    }
+
+    async fn send_message(
+        &self,
+        _token: &str,
+        chat_id: i64,
+        text: &str,
+    ) -> Result<(), TelegramError> {
+        self.sent.lock().unwrap().push((chat_id, text.to_string()));
+        Ok(())
+    }
+}
+
+fn notifier_with(config: AppConfig, bot: Arc<RecordingBot>) -> Notifier {
+    notifier(Arc::new(Recording::default()), config).with_telegram(bot)
+}
+
+#[tokio::test]
+async fn telegram_gets_alerts_only_while_the_bot_is_enabled() {
+    let bot = Arc::new(RecordingBot::default());
+    let mut config = configured();
+    config.telegram.token = Some("123:abc".into());
+    config.telegram.allowed_chat_ids = vec![42];
+
+    let report = notifier_with(config.clone(), Arc::clone(&bot))
+        .handle(&crossed())
+        .await
+        .unwrap();
+    assert!(!report.delivered.iter().any(|c| c.starts_with("telegram")));
+
+    config.telegram.enabled = true;
+    let report = notifier_with(config, Arc::clone(&bot))
+        .handle(&crossed())
+        .await
+        .unwrap();
+    assert_eq!(report.delivered.last().unwrap(), "telegram 42");
+    assert!(bot.sent.lock().unwrap()[0]
+        .1
+        .starts_with("NVDA: fair value below bear\nPrice 90.00"));
+}
+
+#[test]
+fn generic_webhook_carries_the_event_name() {
+    let body =
+        transport::webhook_body(WebhookKind::Generic, &messages::render(&crossed()).unwrap());
+    assert_eq!(body["event"], "fair-value-crossed");
+    assert_eq!(body["title"], "NVDA: fair value below bear");
+}
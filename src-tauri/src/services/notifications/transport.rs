@@ -0,0 +1,111 @@
+//! Live delivery: SMTP through lettre, webhooks through reqwest.
+
+use async_trait::async_trait;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use reqwest::Client;
+use serde_json::{json, Value};
+
+use super::{
+    EmailConfig, Notification, NotificationError, NotificationTransport, SmtpSecurity, WebhookKind,
+};
+
+/// Webhook JSON for `kind`. Discord and Slack both render Markdown-ish
+/// bold, so the title leads in `**`/`*`.
+pub fn webhook_body(kind: WebhookKind, notification: &Notification) -> Value {
+    match kind {
+        WebhookKind::Generic => json!({
+            "event": notification.event,
+            "title": notification.title,
+            "body": notification.body,
+        }),
+        WebhookKind::Discord => json!({
+            "content": format!("**{}**\n{}", notification.title, notification.body),
+        }),
+        WebhookKind::Slack => json!({
+            "text": format!("*{}*\n{}", notification.title, notification.body),
+        }),
+    }
+}
+
+pub struct LiveTransport {
+    client: Client,
+}
+
+impl LiveTransport {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+        }
+    }
+}
+
+impl Default for LiveTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn mailbox(address: &str) -> Result<Mailbox, NotificationError> {
+    address
+        .parse()
+        .map_err(|e| NotificationError::Email(format!("bad address `{address}`: {e}")))
+}
+
+#[async_trait]
+impl NotificationTransport for LiveTransport {
+    async fn send_email(
+        &self,
+        email: &EmailConfig,
+        password: Option<&str>,
+        notification: &Notification,
+    ) -> Result<(), NotificationError> {
+        let mut builder = Message::builder()
+            .from(mailbox(&email.from)?)
+            .subject(&notification.title)
+            .header(ContentType::TEXT_PLAIN);
+        for to in &email.to {
+            builder = builder.to(mailbox(to)?);
+        }
+        let message = builder
+            .body(notification.body.clone())
+            .map_err(|e| NotificationError::Email(e.to_string()))?;
+
+        let host = email.smtp_host.as_str();
+        let mut smtp = match email.security {
+            SmtpSecurity::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host),
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host),
+            SmtpSecurity::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                host,
+            )),
+        }
+        .map_err(|e| NotificationError::Email(e.to_string()))?
+        .port(email.smtp_port);
+        if let (Some(user), Some(password)) = (&email.username, password) {
+            smtp = smtp.credentials(Credentials::new(user.clone(), password.to_string()));
+        }
+        smtp.build()
+            .send(message)
+            .await
+            .map_err(|e| NotificationError::Email(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn post_webhook(&self, url: &str, body: &Value) -> Result<(), NotificationError> {
+        let response = self
+            .client
+            .post(url)
+            .json(body)
+            .send()
+            .await
+            // reqwest errors quote the URL, which carries the token.
+            .map_err(|e| NotificationError::Webhook(e.without_url().to_string()))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(NotificationError::Webhook(format!("returned {status}")));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+//! Notion REST client: query a database by title, create and update
+//! pages. Properties are passed through as Notion's JSON.
+
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder};
+use serde_json::{json, Value};
+
+use super::{NotionApi, NotionError};
+
+const BASE_URL: &str = "https://api.notion.com/v1";
+const NOTION_VERSION: &str = "2022-06-28";
+
+pub struct ReqwestNotion {
+    client: Client,
+}
+
+impl ReqwestNotion {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+        }
+    }
+
+    async fn send(&self, request: RequestBuilder, token: &str) -> Result<Value, NotionError> {
+        let response = request
+            .bearer_auth(token)
+            .header("Notion-Version", NOTION_VERSION)
+            .send()
+            .await
+            .map_err(|e| NotionError::Http(e.to_string()))?;
+        let status = response.status();
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| NotionError::Http(e.to_string()))?;
+        if !status.is_success() {
+            return Err(NotionError::Api {
+                status: status.as_u16(),
+                message: body
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("no message")
+                    .to_string(),
+            });
+        }
+        Ok(body)
+    }
+}
+
+impl Default for ReqwestNotion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl NotionApi for ReqwestNotion {
+    async fn find_page(
+        &self,
+        token: &str,
+        database_id: &str,
+        title: &str,
+    ) -> Result<Option<String>, NotionError> {
+        let body = json!({
+            "filter": { "property": super::TITLE_PROPERTY, "title": { "equals": title } },
+            "page_size": 1,
+        });
+        let url = format!("{BASE_URL}/databases/{database_id}/query");
+        let found = self.send(self.client.post(url).json(&body), token).await?;
+        Ok(found["results"]
+            .get(0)
+            .and_then(|page| page["id"].as_str())
+            .map(str::to_string))
+    }
+
+    async fn create_page(
+        &self,
+        token: &str,
+        database_id: &str,
+        properties: Value,
+    ) -> Result<(), NotionError> {
+        let body = json!({
+            "parent": { "database_id": database_id },
+            "properties": properties,
+        });
+        let url = format!("{BASE_URL}/pages");
+        self.send(self.client.post(url).json(&body), token).await?;
+        Ok(())
+    }
+
+    async fn update_page(
+        &self,
+        token: &str,
+        page_id: &str,
+        properties: Value,
+    ) -> Result<(), NotionError> {
+        let body = json!({ "properties": properties });
+        let url = format!("{BASE_URL}/pages/{page_id}");
+        self.send(self.client.patch(url).json(&body), token).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,254 @@
+//! Optional Notion mirror of the ticker summaries, for research kept in
+//! Notion rather than a spreadsheet.
+//!
+//! Every ticker with a stored projection (`services::projection_history`)
+//! becomes one page of `notion.database_id`, keyed by its title
+//! property (`Name` = symbol): created the first time, updated in place
+//! afterwards. A page carries the baseline year, the final-year
+//! scenario targets, the probability-weighted target, base-case CAGR
+//! and the fair-value zone (`services::fair_value_watch`). Viewing the
+//! database as a table gives the dashboard.
+//!
+//! The database must already have those properties (see
+//! [`TickerSummary::properties`]) and be shared with the integration
+//! whose token is in the keychain (`SecretProvider::Notion`). Export runs
+//! on demand (`notion_export_now`) or as the `notion_export` task,
+//! disabled by default. One ticker failing doesn't stop the rest.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+use crate::services::fair_value_watch::{FairValueFlag, FairValueWatcher};
+use crate::services::projection_history::{ProjectionHistoryStore, ProjectionSnapshot};
+use crate::storage::error::StorageError;
+
+pub mod client;
+
+#[cfg(test)]
+mod tests;
+
+/// Title property the pages are matched on.
+pub const TITLE_PROPERTY: &str = "Name";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotionConfig {
+    /// Database the ticker pages live in (the id from its URL).
+    #[serde(default)]
+    pub database_id: Option<String>,
+    /// Integration token. Kept in the OS keychain; see `config/secrets.rs`.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum NotionError {
+    #[error("Notion export is not configured: {0}")]
+    NotConfigured(&'static str),
+    #[error("notion http: {0}")]
+    Http(String),
+    #[error("notion returned {status}: {message}")]
+    Api { status: u16, message: String },
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// Trait seam over the Notion API. Production is
+/// [`client::ReqwestNotion`]; tests record calls.
+#[async_trait]
+pub trait NotionApi: Send + Sync {
+    /// Id of the page whose title is `title`, if any.
+    async fn find_page(
+        &self,
+        token: &str,
+        database_id: &str,
+        title: &str,
+    ) -> Result<Option<String>, NotionError>;
+    async fn create_page(
+        &self,
+        token: &str,
+        database_id: &str,
+        properties: Value,
+    ) -> Result<(), NotionError>;
+    async fn update_page(
+        &self,
+        token: &str,
+        page_id: &str,
+        properties: Value,
+    ) -> Result<(), NotionError>;
+}
+
+/// One ticker's row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TickerSummary {
+    pub symbol: String,
+    pub baseline_year: u32,
+    /// Final projected year the targets are for.
+    pub target_year: u32,
+    /// Scenario midpoints of the final projected year.
+    pub bear_target: f64,
+    pub base_target: f64,
+    pub bull_target: f64,
+    pub expected_target: Option<f64>,
+    pub base_cagr_pct: f64,
+    pub fair_value_zone: Option<String>,
+    pub price: Option<f64>,
+    /// Unix seconds the projection was generated.
+    pub generated_at: i64,
+}
+
+impl TickerSummary {
+    /// `None` when the snapshot has no forward years.
+    pub fn new(snapshot: &ProjectionSnapshot, flag: Option<&FairValueFlag>) -> Option<Self> {
+        let results = &snapshot.results;
+        let last = results.projections.last()?;
+        let mid = |p: &crate::ibkr::types::FinancialProjection| {
+            (p.share_price_low + p.share_price_high) / 2.0
+        };
+        Some(Self {
+            symbol: snapshot.symbol.clone(),
+            baseline_year: results.baseline.year,
+            target_year: last.year,
+            bear_target: mid(&last.bear),
+            base_target: mid(&last.base),
+            bull_target: mid(&last.bull),
+            expected_target: results.expected.as_ref().map(|e| e.target_price),
+            base_cagr_pct: results.cagr.base.share_price,
+            fair_value_zone: flag.map(|f| f.zone.as_str().to_string()),
+            price: flag.map(|f| f.price),
+            generated_at: snapshot.generated_at,
+        })
+    }
+
+    /// Notion page properties. The database needs `Name` (title),
+    /// `Baseline year`, `Target year`, `Bear target`, `Base target`,
+    /// `Bull target`, `Expected target`, `Base CAGR %`, `Price` (numbers),
+    /// `Fair value` (select) and `Projected` (date).
+    pub fn properties(&self) -> Value {
+        let number = |v: Option<f64>| json!({ "number": v });
+        let generated = Utc
+            .timestamp_opt(self.generated_at, 0)
+            .single()
+            .map(|t| t.to_rfc3339());
+        json!({
+            TITLE_PROPERTY: { "title": [{ "text": { "content": self.symbol } }] },
+            "Baseline year": number(Some(f64::from(self.baseline_year))),
+            "Target year": number(Some(f64::from(self.target_year))),
+            "Bear target": number(Some(round2(self.bear_target))),
+            "Base target": number(Some(round2(self.base_target))),
+            "Bull target": number(Some(round2(self.bull_target))),
+            "Expected target": number(self.expected_target.map(round2)),
+            "Base CAGR %": number(Some(round2(self.base_cagr_pct))),
+            "Price": number(self.price),
+            "Fair value": {
+                "select": self.fair_value_zone.as_ref().map(|z| json!({ "name": z }))
+            },
+            "Projected": { "date": generated.map(|d| json!({ "start": d })) },
+        })
+    }
+}
+
+fn round2(v: f64) -> f64 {
+    (v * 100.0).round() / 100.0
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotionExportReport {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    /// `(symbol, error)` for tickers that failed.
+    pub failed: Vec<(String, String)>,
+}
+
+pub struct NotionExporter {
+    api: Arc<dyn NotionApi>,
+    history: Arc<ProjectionHistoryStore>,
+    fair_value: Arc<FairValueWatcher>,
+    config: Arc<RwLock<AppConfig>>,
+}
+
+impl NotionExporter {
+    pub fn new(
+        api: Arc<dyn NotionApi>,
+        history: Arc<ProjectionHistoryStore>,
+        fair_value: Arc<FairValueWatcher>,
+        config: Arc<RwLock<AppConfig>>,
+    ) -> Self {
+        Self {
+            api,
+            history,
+            fair_value,
+            config,
+        }
+    }
+
+    /// Mirror every projected ticker into the database.
+    pub async fn export(&self) -> Result<NotionExportReport, NotionError> {
+        let notion = self.config.read().await.notion.clone();
+        let token = notion
+            .token
+            .filter(|t| !t.trim().is_empty())
+            .ok_or(NotionError::NotConfigured("no Notion token"))?;
+        let database_id = notion
+            .database_id
+            .filter(|d| !d.trim().is_empty())
+            .ok_or(NotionError::NotConfigured("no notion.database_id"))?;
+
+        let flags = self.fair_value.list().await?;
+        let mut report = NotionExportReport::default();
+        for snapshot in self.history.latest_per_symbol().await? {
+            let flag = flags.iter().find(|f| f.symbol == snapshot.symbol);
+            let Some(summary) = TickerSummary::new(&snapshot, flag) else {
+                continue;
+            };
+            match self.upsert(&token, &database_id, &summary).await {
+                Ok(true) => report.created.push(summary.symbol),
+                Ok(false) => report.updated.push(summary.symbol),
+                Err(e) => {
+                    warn!("notion_export: {} failed: {e}", summary.symbol);
+                    report.failed.push((summary.symbol, e.to_string()));
+                }
+            }
+        }
+        info!(
+            "notion_export: {} created, {} updated, {} failed",
+            report.created.len(),
+            report.updated.len(),
+            report.failed.len()
+        );
+        Ok(report)
+    }
+
+    /// `true` when the page was created.
+    async fn upsert(
+        &self,
+        token: &str,
+        database_id: &str,
+        summary: &TickerSummary,
+    ) -> Result<bool, NotionError> {
+        let properties = summary.properties();
+        match self
+            .api
+            .find_page(token, database_id, &summary.symbol)
+            .await?
+        {
+            Some(page_id) => {
+                self.api.update_page(token, &page_id, properties).await?;
+                Ok(false)
+            }
+            None => {
+                self.api.create_page(token, database_id, properties).await?;
+                Ok(true)
+            }
+        }
+    }
+}
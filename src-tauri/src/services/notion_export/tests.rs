@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tempfile::NamedTempFile;
+
+use super::*;
+use crate::events::EventEmitter;
+use crate::ibkr::types::ProjectionAssumptions;
+use crate::services::fair_value_watch::PriceSource;
+use crate::services::projection_service::ProjectionService;
+use crate::storage::Db;
+
+/// In-memory database: title → page id, plus every write.
+#[derive(Default)]
+struct FakeNotion {
+    pages: Mutex<HashMap<String, String>>,
+    writes: Mutex<Vec<(String, Value)>>,
+    reject: Option<String>,
+}
+
+#[async_trait]
+impl NotionApi for FakeNotion {
+    async fn find_page(
+        &self,
+        token: &str,
+        _database_id: &str,
+        title: &str,
+    ) -> Result<Option<String>, NotionError> {
+        assert_eq!(token, "secret_abc");
+        Ok(self.pages.lock().unwrap().get(title).cloned())
+    }
+
+    async fn create_page(
+        &self,
+        _token: &str,
+        database_id: &str,
+        properties: Value,
+    ) -> Result<(), NotionError> {
+        let title = properties[TITLE_PROPERTY]["title"][0]["text"]["content"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        if self.reject.as_deref() == Some(title.as_str()) {
+            return Err(NotionError::Api {
+                status: 400,
+                message: "validation_error".into(),
+            });
+        }
+        let id = format!("page-{title}");
+        self.pages.lock().unwrap().insert(title, id.clone());
+        self.writes
+            .lock()
+            .unwrap()
+            .push((format!("create {database_id}"), properties));
+        Ok(())
+    }
+
+    async fn update_page(
+        &self,
+        _token: &str,
+        page_id: &str,
+        properties: Value,
+    ) -> Result<(), NotionError> {
+        self.writes
+            .lock()
+            .unwrap()
+            .push((format!("update {page_id}"), properties));
+        Ok(())
+    }
+}
+
+struct NoPrices;
+
+#[async_trait]
+impl PriceSource for NoPrices {
+    async fn latest_price(&self, _symbol: &str) -> Option<f64> {
+        None
+    }
+}
+
+async fn exporter(notion: Arc<FakeNotion>, config: AppConfig) -> (NamedTempFile, NotionExporter) {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let history = Arc::new(ProjectionHistoryStore::new(Arc::clone(&db)));
+    let assumptions = ProjectionAssumptions::default();
+    for symbol in ["AMD", "NVDA"] {
+        let data = ProjectionService::generate_mock_fundamental_data(symbol);
+        let results = ProjectionService::generate_projection_results(&data, &assumptions).unwrap();
+        history
+            .record(symbol, &assumptions, &results)
+            .await
+            .unwrap();
+    }
+    let fair_value = Arc::new(FairValueWatcher::new(
+        db,
+        Arc::clone(&history),
+        Arc::new(NoPrices),
+        Arc::new(EventEmitter::for_capture()),
+    ));
+    let exporter = NotionExporter::new(notion, history, fair_value, Arc::new(RwLock::new(config)));
+    (tmp, exporter)
+}
+
+fn configured() -> AppConfig {
+    let mut config = AppConfig::default();
+    config.notion.token = Some("secret_abc".into());
+    config.notion.database_id = Some("db1".into());
+    config
+}
+
+#[tokio::test]
+async fn creates_pages_once_then_updates_in_place() {
+    let notion = Arc::new(FakeNotion::default());
+    let (_tmp, exporter) = exporter(Arc::clone(&notion), configured()).await;
+
+    let first = exporter.export().await.unwrap();
+    assert_eq!(first.created, vec!["AMD", "NVDA"]);
+    assert!(first.updated.is_empty());
+    let second = exporter.export().await.unwrap();
+    assert_eq!(second.updated, vec!["AMD", "NVDA"]);
+
+    let writes = notion.writes.lock().unwrap();
+    assert_eq!(writes[0].0, "create db1");
+    assert_eq!(writes[3].0, "update page-NVDA");
+    let props = &writes[3].1;
+    assert_eq!(props["Baseline year"]["number"], 2024.0);
+    assert!(props["Expected target"]["number"].as_f64().unwrap() > 0.0);
+    assert!(props["Fair value"]["select"].is_null(), "never priced");
+}
+
+#[tokio::test]
+async fn one_rejected_ticker_does_not_stop_the_rest() {
+    let notion = Arc::new(FakeNotion {
+        reject: Some("AMD".into()),
+        ..Default::default()
+    });
+    let (_tmp, exporter) = exporter(notion, configured()).await;
+
+    let report = exporter.export().await.unwrap();
+    assert_eq!(report.created, vec!["NVDA"]);
+    assert_eq!(report.failed.len(), 1);
+    assert!(report.failed[0].1.contains("400"));
+}
+
+#[tokio::test]
+async fn missing_token_is_not_configured() {
+    let mut config = configured();
+    config.notion.token = None;
+    let (_tmp, exporter) = exporter(Arc::new(FakeNotion::default()), config).await;
+    assert!(matches!(
+        exporter.export().await,
+        Err(NotionError::NotConfigured(_))
+    ));
+}
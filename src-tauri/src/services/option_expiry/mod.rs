@@ -0,0 +1,439 @@
+//! Option expiry and assignment risk.
+//!
+//! For each open option position: calendar days to expiry, and
+//! moneyness, `underlying / strike − 1` for a call and `strike /
+//! underlying − 1` for a put, in percent, so positive is in the money.
+//! Within `option_expiry.atm_band_pct` either side of zero it counts as
+//! at the money.
+//!
+//! Assignment risk is flagged on short options only:
+//!
+//! - **Early exercise.** A short call in the money with an ex-dividend
+//!   date on or before expiry, whose dividend is more than the call's
+//!   time value (its price less intrinsic value). The holder gains by
+//!   exercising the day before the ex-date to collect the dividend.
+//! - **At expiry.** A short option in or at the money within
+//!   `option_expiry.alert_dte` days of expiry: likely assigned, or
+//!   pinned near the strike.
+//!
+//! The `option_expiry_check` task emits [`AppEvent::OptionAssignmentRisk`]
+//! for every flagged position each trading morning. `get_expiry_summary`
+//! lists what expires over the coming days by date, with the shares and
+//! cash that change hands if the options in the money are exercised.
+//!
+//! Underlying prices are TWS snapshots; a position whose underlying
+//! can't be priced is listed without moneyness and never flagged.
+//! Ex-dividend dates come from Alpha Vantage's `DIVIDENDS` feed, read
+//! only for short calls in the money.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDate};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::config::AppConfig;
+use crate::events::{AppEvent, EventEmitter};
+use crate::ibkr::client::IbkrClient;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::{OptionContract, OptionRight};
+use crate::services::financial_data_service::FinancialDataService;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionExpiryConfig {
+    /// Short options in or at the money this close to expiry are
+    /// flagged.
+    #[serde(default = "default_alert_dte")]
+    pub alert_dte: u32,
+    /// Moneyness within this many percent of zero is at the money.
+    #[serde(default = "default_atm_band_pct")]
+    pub atm_band_pct: f64,
+}
+
+fn default_alert_dte() -> u32 {
+    5
+}
+
+fn default_atm_band_pct() -> f64 {
+    1.0
+}
+
+impl Default for OptionExpiryConfig {
+    fn default() -> Self {
+        Self {
+            alert_dte: default_alert_dte(),
+            atm_band_pct: default_atm_band_pct(),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum OptionExpiryError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+}
+
+/// Trait seam for the underlying's price. Production is a TWS snapshot;
+/// tests fix prices.
+#[async_trait]
+pub trait UnderlyingPrices: Send + Sync {
+    async fn price(&self, symbol: &str) -> Result<f64, IbkrError>;
+}
+
+#[async_trait]
+impl UnderlyingPrices for IbkrClient {
+    async fn price(&self, symbol: &str) -> Result<f64, IbkrError> {
+        let snapshot = self.get_market_data_snapshot(symbol).await?;
+        snapshot
+            .last_price
+            .or(snapshot.close)
+            .filter(|p| *p > 0.0)
+            .ok_or_else(|| IbkrError::RequestFailed(format!("no price for {symbol}")))
+    }
+}
+
+/// Trait seam for ex-dividend dates. Production is the Alpha Vantage
+/// `DIVIDENDS` feed; tests inject dividends.
+#[async_trait]
+pub trait DividendCalendar: Send + Sync {
+    /// `(ex-dividend date, amount per share)`, declared ones included,
+    /// any order.
+    async fn ex_dividends(&self, symbol: &str) -> Result<Vec<(NaiveDate, f64)>, String>;
+}
+
+#[async_trait]
+impl DividendCalendar for FinancialDataService {
+    async fn ex_dividends(&self, symbol: &str) -> Result<Vec<(NaiveDate, f64)>, String> {
+        let history = self
+            .dividend_history(symbol)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(history
+            .into_iter()
+            .filter_map(|(date, amount)| {
+                Some((NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok()?, amount))
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Moneyness {
+    Itm,
+    Atm,
+    Otm,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssignmentRisk {
+    EarlyExercise,
+    AtExpiry,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExDividend {
+    pub date: NaiveDate,
+    /// Per share.
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionExpiryRow {
+    pub symbol: String,
+    pub local_symbol: String,
+    pub right: OptionRight,
+    pub strike: f64,
+    pub expiry: NaiveDate,
+    /// Calendar days from today.
+    pub dte: i64,
+    /// Contracts; negative when short.
+    pub quantity: f64,
+    pub multiplier: f64,
+    pub underlying_price: Option<f64>,
+    /// Positive in the money, in percent.
+    pub moneyness_pct: Option<f64>,
+    pub moneyness: Option<Moneyness>,
+    /// Per share: the option's price less its intrinsic value.
+    pub time_value: Option<f64>,
+    /// The next ex-dividend date through expiry; read for short calls
+    /// in the money only.
+    pub ex_dividend: Option<ExDividend>,
+    pub assignment_risk: Option<AssignmentRisk>,
+    /// Shares received (positive) or delivered if exercised or
+    /// assigned at expiry; zero out of the money.
+    pub shares_if_exercised: f64,
+    /// Cash received (positive) or paid for those shares at the strike.
+    pub cash_if_exercised: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionExpiryReport {
+    pub account: String,
+    pub as_of: NaiveDate,
+    /// Nearest expiry first.
+    pub positions: Vec<OptionExpiryRow>,
+    /// Option positions IBKR left the expiry, strike or right off.
+    pub unreadable: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiryDay {
+    pub expiry: NaiveDate,
+    pub positions: Vec<OptionExpiryRow>,
+    /// Net of `cash_if_exercised` over the day's positions.
+    pub cash_if_exercised: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpirySummary {
+    pub account: String,
+    pub as_of: NaiveDate,
+    /// Last expiry date included.
+    pub through: NaiveDate,
+    pub days: Vec<ExpiryDay>,
+    /// Positions in the window with an assignment risk.
+    pub at_risk: usize,
+}
+
+pub struct OptionExpiryService {
+    account: Arc<dyn AccountSource>,
+    positions: Arc<dyn OpenPositionsSource>,
+    prices: Arc<dyn UnderlyingPrices>,
+    dividends: Arc<dyn DividendCalendar>,
+    config: Arc<RwLock<AppConfig>>,
+    emitter: Arc<EventEmitter>,
+}
+
+impl OptionExpiryService {
+    pub fn new(
+        account: Arc<dyn AccountSource>,
+        positions: Arc<dyn OpenPositionsSource>,
+        prices: Arc<dyn UnderlyingPrices>,
+        dividends: Arc<dyn DividendCalendar>,
+        config: Arc<RwLock<AppConfig>>,
+        emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            account,
+            positions,
+            prices,
+            dividends,
+            config,
+            emitter,
+        }
+    }
+
+    /// Every open option position of `account` (the current account
+    /// when `None`) as of `today`.
+    pub async fn report(
+        &self,
+        account: Option<String>,
+        today: NaiveDate,
+    ) -> Result<OptionExpiryReport, OptionExpiryError> {
+        let config = self.config.read().await.option_expiry.clone();
+        let account = match account.filter(|a| !a.trim().is_empty()) {
+            Some(a) => a.trim().to_string(),
+            None => self.account.current_account().await?,
+        };
+
+        let mut positions = Vec::new();
+        let mut unreadable = Vec::new();
+        for position in self.positions.list_open(&account).await? {
+            if position.contract_type != "OPT" || position.position == 0.0 {
+                continue;
+            }
+            let Some((option, expiry)) = OptionContract::from_position(&position).and_then(|o| {
+                let expiry = NaiveDate::parse_from_str(&o.expiry, "%Y%m%d").ok()?;
+                Some((o, expiry))
+            }) else {
+                unreadable.push(position.contract_key());
+                continue;
+            };
+            let underlying_price = match self.prices.price(&option.symbol).await {
+                Ok(price) => Some(price),
+                Err(e) => {
+                    warn!("option_expiry: price for {} failed: {e}", option.symbol);
+                    None
+                }
+            };
+            let mut row = OptionExpiryRow {
+                symbol: option.symbol.clone(),
+                local_symbol: position.local_symbol.clone(),
+                right: option.right,
+                strike: option.strike,
+                expiry,
+                dte: (expiry - today).num_days(),
+                quantity: position.position,
+                multiplier: option
+                    .multiplier
+                    .as_deref()
+                    .and_then(|m| m.parse::<f64>().ok())
+                    .filter(|m| *m > 0.0)
+                    .unwrap_or(100.0),
+                underlying_price,
+                moneyness_pct: None,
+                moneyness: None,
+                time_value: None,
+                ex_dividend: None,
+                assignment_risk: None,
+                shares_if_exercised: 0.0,
+                cash_if_exercised: 0.0,
+            };
+            if let Some(price) = underlying_price {
+                assess(&mut row, price, position.market_price, &config);
+            }
+            if row.quantity < 0.0
+                && row.right == OptionRight::Call
+                && row.moneyness == Some(Moneyness::Itm)
+            {
+                match self.dividends.ex_dividends(&row.symbol).await {
+                    Ok(dividends) => {
+                        row.ex_dividend = dividends
+                            .into_iter()
+                            .filter(|(date, _)| *date >= today && *date <= expiry)
+                            .min_by_key(|(date, _)| *date)
+                            .map(|(date, amount)| ExDividend { date, amount });
+                    }
+                    Err(e) => warn!("option_expiry: dividends for {} failed: {e}", row.symbol),
+                }
+            }
+            row.assignment_risk = assignment_risk(&row, &config);
+            positions.push(row);
+        }
+        positions.sort_by(|a, b| {
+            a.expiry
+                .cmp(&b.expiry)
+                .then_with(|| a.local_symbol.cmp(&b.local_symbol))
+        });
+        Ok(OptionExpiryReport {
+            account,
+            as_of: today,
+            positions,
+            unreadable,
+        })
+    }
+
+    /// Positions of `account` expiring from `today` through `days`
+    /// calendar days ahead, by expiry date.
+    pub async fn summary(
+        &self,
+        account: Option<String>,
+        today: NaiveDate,
+        days: u32,
+    ) -> Result<ExpirySummary, OptionExpiryError> {
+        let report = self.report(account, today).await?;
+        let through = today + Duration::days(days as i64);
+        let mut out: Vec<ExpiryDay> = Vec::new();
+        for row in report.positions.into_iter().filter(|r| r.expiry <= through) {
+            match out.last_mut() {
+                Some(day) if day.expiry == row.expiry => day.positions.push(row),
+                _ => out.push(ExpiryDay {
+                    expiry: row.expiry,
+                    positions: vec![row],
+                    cash_if_exercised: 0.0,
+                }),
+            }
+        }
+        for day in &mut out {
+            day.cash_if_exercised = day.positions.iter().map(|p| p.cash_if_exercised).sum();
+        }
+        let at_risk = out
+            .iter()
+            .flat_map(|d| &d.positions)
+            .filter(|p| p.assignment_risk.is_some())
+            .count();
+        Ok(ExpirySummary {
+            account: report.account,
+            as_of: today,
+            through,
+            days: out,
+            at_risk,
+        })
+    }
+
+    /// Emit [`AppEvent::OptionAssignmentRisk`] for each flagged position
+    /// of the current account, and return those positions.
+    pub async fn check(&self, today: NaiveDate) -> Result<Vec<OptionExpiryRow>, OptionExpiryError> {
+        let report = self.report(None, today).await?;
+        let flagged: Vec<OptionExpiryRow> = report
+            .positions
+            .into_iter()
+            .filter(|p| p.assignment_risk.is_some())
+            .collect();
+        for row in &flagged {
+            let _ = self
+                .emitter
+                .emit(AppEvent::OptionAssignmentRisk {
+                    account: report.account.clone(),
+                    symbol: row.symbol.clone(),
+                    local_symbol: row.local_symbol.clone(),
+                    risk: row.assignment_risk.unwrap_or(AssignmentRisk::AtExpiry),
+                    expiry: row.expiry.to_string(),
+                    dte: row.dte,
+                    moneyness_pct: row.moneyness_pct.unwrap_or(0.0),
+                    ex_dividend_date: row.ex_dividend.as_ref().map(|d| d.date.to_string()),
+                })
+                .await;
+        }
+        Ok(flagged)
+    }
+}
+
+/// Fill `row`'s moneyness, time value and exercise outcome from the
+/// underlying's `price` and the option's per-share `mark`.
+fn assess(row: &mut OptionExpiryRow, price: f64, mark: f64, config: &OptionExpiryConfig) {
+    let (pct, intrinsic) = match row.right {
+        OptionRight::Call => ((price / row.strike - 1.0) * 100.0, price - row.strike),
+        OptionRight::Put => ((row.strike / price - 1.0) * 100.0, row.strike - price),
+    };
+    row.moneyness_pct = Some(pct);
+    row.moneyness = Some(if pct.abs() <= config.atm_band_pct {
+        Moneyness::Atm
+    } else if pct > 0.0 {
+        Moneyness::Itm
+    } else {
+        Moneyness::Otm
+    });
+    row.time_value = Some((mark - intrinsic.max(0.0)).max(0.0));
+    if pct > 0.0 {
+        let side = match row.right {
+            OptionRight::Call => 1.0,
+            OptionRight::Put => -1.0,
+        };
+        row.shares_if_exercised = row.quantity * row.multiplier * side;
+        row.cash_if_exercised = -row.shares_if_exercised * row.strike;
+    }
+}
+
+fn assignment_risk(row: &OptionExpiryRow, config: &OptionExpiryConfig) -> Option<AssignmentRisk> {
+    if row.quantity >= 0.0 {
+        return None;
+    }
+    if let (Some(dividend), Some(time_value)) = (&row.ex_dividend, row.time_value) {
+        if dividend.amount > time_value {
+            return Some(AssignmentRisk::EarlyExercise);
+        }
+    }
+    match row.moneyness {
+        Some(Moneyness::Itm | Moneyness::Atm) if row.dte <= config.alert_dte as i64 => {
+            Some(AssignmentRisk::AtExpiry)
+        }
+        _ => None,
+    }
+}
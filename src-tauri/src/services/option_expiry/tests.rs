@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::*;
+use crate::ibkr::types::Position;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+struct StubPrices(HashMap<String, f64>);
+
+#[async_trait]
+impl UnderlyingPrices for StubPrices {
+    async fn price(&self, symbol: &str) -> Result<f64, IbkrError> {
+        self.0
+            .get(symbol)
+            .copied()
+            .ok_or_else(|| IbkrError::RequestFailed(format!("no price for {symbol}")))
+    }
+}
+
+/// Records which symbols were asked for.
+#[derive(Default)]
+struct StubDividends {
+    asked: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl DividendCalendar for StubDividends {
+    async fn ex_dividends(&self, symbol: &str) -> Result<Vec<(NaiveDate, f64)>, String> {
+        self.asked.lock().unwrap().push(symbol.to_string());
+        Ok(vec![
+            (date("2026-03-20"), 0.25),
+            (date("2026-09-18"), 0.25),
+            (date("2026-06-20"), 0.25),
+        ])
+    }
+}
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+fn option(
+    symbol: &str,
+    right: &str,
+    strike: f64,
+    expiry: &str,
+    quantity: f64,
+    mark: f64,
+) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: quantity,
+        market_price: mark,
+        contract_type: "OPT".to_string(),
+        local_symbol: format!(
+            "{symbol:<6}{}{right}{:08}",
+            &expiry[2..],
+            (strike * 1000.0) as u64
+        ),
+        expiry: Some(expiry.to_string()),
+        strike: Some(strike),
+        right: Some(right.to_string()),
+        multiplier: Some("100".to_string()),
+        ..Default::default()
+    }
+}
+
+struct Harness {
+    service: OptionExpiryService,
+    dividends: Arc<StubDividends>,
+    emitter: Arc<EventEmitter>,
+}
+
+fn harness() -> Harness {
+    let mut unreadable = option("BAD", "C", 10.0, "20260619", -1.0, 1.0);
+    unreadable.strike = None;
+    let stock = Position {
+        account: "DU1".to_string(),
+        symbol: "AAPL".to_string(),
+        position: 200.0,
+        contract_type: "STK".to_string(),
+        ..Default::default()
+    };
+    let positions = vec![
+        // In the money by 10 with 0.20 of time value, against a 0.25
+        // dividend before expiry.
+        option("AAPL", "C", 180.0, "20260626", -2.0, 10.2),
+        // Half a percent in the money four days out: at the money.
+        option("MSFT", "P", 400.0, "20260619", -1.0, 3.0),
+        option("SPY", "C", 500.0, "20260619", 3.0, 20.5),
+        option("QQQ", "C", 500.0, "20260619", -1.0, 0.1),
+        option("NOPX", "P", 50.0, "20260717", -1.0, 1.0),
+        unreadable,
+        stock,
+    ];
+    let prices = HashMap::from([
+        ("AAPL".to_string(), 190.0),
+        ("MSFT".to_string(), 398.0),
+        ("SPY".to_string(), 520.0),
+        ("QQQ".to_string(), 450.0),
+    ]);
+    let dividends = Arc::new(StubDividends::default());
+    let emitter = Arc::new(EventEmitter::for_capture());
+    let service = OptionExpiryService::new(
+        Arc::new(FixedAccount),
+        Arc::new(StubPositions(positions)),
+        Arc::new(StubPrices(prices)),
+        dividends.clone(),
+        Arc::new(RwLock::new(AppConfig::default())),
+        emitter.clone(),
+    );
+    Harness {
+        service,
+        dividends,
+        emitter,
+    }
+}
+
+fn row<'a>(report: &'a OptionExpiryReport, symbol: &str) -> &'a OptionExpiryRow {
+    report
+        .positions
+        .iter()
+        .find(|r| r.symbol == symbol)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn report_flags_short_options_at_risk_of_assignment() {
+    let h = harness();
+    let report = h.service.report(None, date("2026-06-15")).await.unwrap();
+    assert_eq!(report.account, "DU1");
+    let order: Vec<&str> = report.positions.iter().map(|r| r.symbol.as_str()).collect();
+    assert_eq!(order, ["MSFT", "QQQ", "SPY", "AAPL", "NOPX"]);
+    assert_eq!(report.unreadable, ["BAD   260619C00010000"]);
+
+    let aapl = row(&report, "AAPL");
+    assert_eq!(aapl.dte, 11);
+    assert_eq!(aapl.moneyness, Some(Moneyness::Itm));
+    assert!((aapl.time_value.unwrap() - 0.2).abs() < 1e-9);
+    // The March and September dividends fall outside the window.
+    assert_eq!(
+        aapl.ex_dividend,
+        Some(ExDividend {
+            date: date("2026-06-20"),
+            amount: 0.25
+        })
+    );
+    assert_eq!(aapl.assignment_risk, Some(AssignmentRisk::EarlyExercise));
+    assert!((aapl.shares_if_exercised + 200.0).abs() < 1e-9);
+    assert!((aapl.cash_if_exercised - 36_000.0).abs() < 1e-9);
+
+    let msft = row(&report, "MSFT");
+    assert_eq!(msft.moneyness, Some(Moneyness::Atm));
+    assert_eq!(msft.assignment_risk, Some(AssignmentRisk::AtExpiry));
+    assert!((msft.shares_if_exercised - 100.0).abs() < 1e-9);
+    assert!((msft.cash_if_exercised + 40_000.0).abs() < 1e-9);
+
+    // Long, out of the money, or unpriced: never flagged.
+    let spy = row(&report, "SPY");
+    assert_eq!(spy.moneyness, Some(Moneyness::Itm));
+    assert_eq!(spy.assignment_risk, None);
+    let qqq = row(&report, "QQQ");
+    assert_eq!(qqq.moneyness, Some(Moneyness::Otm));
+    assert_eq!(qqq.shares_if_exercised, 0.0);
+    assert_eq!(qqq.assignment_risk, None);
+    let nopx = row(&report, "NOPX");
+    assert_eq!(nopx.moneyness_pct, None);
+    assert_eq!(nopx.assignment_risk, None);
+
+    // Only the short call in the money needs its dividends.
+    assert_eq!(*h.dividends.asked.lock().unwrap(), ["AAPL"]);
+}
+
+#[tokio::test]
+async fn summary_groups_the_window_by_expiry_with_exercise_cash() {
+    let h = harness();
+    let summary = h
+        .service
+        .summary(Some("DU2".to_string()), date("2026-06-15"), 7)
+        .await
+        .unwrap();
+    assert_eq!(summary.account, "DU2");
+    assert_eq!(summary.through, date("2026-06-22"));
+    assert_eq!(summary.days.len(), 1);
+    let day = &summary.days[0];
+    assert_eq!(day.expiry, date("2026-06-19"));
+    assert_eq!(day.positions.len(), 3);
+    // Buying 100 MSFT at 400 and 300 SPY at 500.
+    assert!((day.cash_if_exercised + 190_000.0).abs() < 1e-9);
+    assert_eq!(summary.at_risk, 1);
+
+    let summary = h
+        .service
+        .summary(None, date("2026-06-15"), 14)
+        .await
+        .unwrap();
+    assert_eq!(summary.days.len(), 2);
+    assert_eq!(summary.at_risk, 2);
+}
+
+#[tokio::test]
+async fn check_emits_an_event_per_flagged_position() {
+    let h = harness();
+    let flagged = h.service.check(date("2026-06-15")).await.unwrap();
+    assert_eq!(flagged.len(), 2);
+    let events: Vec<(String, AssignmentRisk, Option<String>)> = h
+        .emitter
+        .captured()
+        .await
+        .into_iter()
+        .filter_map(|e| match e {
+            AppEvent::OptionAssignmentRisk {
+                symbol,
+                risk,
+                ex_dividend_date,
+                ..
+            } => Some((symbol, risk, ex_dividend_date)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        events,
+        [
+            ("MSFT".to_string(), AssignmentRisk::AtExpiry, None),
+            (
+                "AAPL".to_string(),
+                AssignmentRisk::EarlyExercise,
+                Some("2026-06-20".to_string())
+            ),
+        ]
+    );
+
+    // Four days out is outside a three-day alert window.
+    h.service.config.write().await.option_expiry.alert_dte = 3;
+    let flagged = h.service.check(date("2026-06-15")).await.unwrap();
+    assert_eq!(flagged.len(), 1);
+    assert_eq!(flagged[0].symbol, "AAPL");
+}
@@ -0,0 +1,175 @@
+//! Live greeks for option positions.
+//!
+//! Each `OPT` position is priced through TWS's option model
+//! ([`GreeksSource`]); per-contract greeks are scaled by quantity ×
+//! multiplier into position greeks and summed for the portfolio, so
+//! `delta` reads as share-equivalents, `theta` as currency per day and
+//! `vega` as currency per vol point. Stock positions are not folded
+//! into the aggregate.
+//!
+//! `ibkr_get_portfolio_greeks` answers on demand; the `option_greeks`
+//! task publishes [`AppEvent::PortfolioGreeksUpdate`] on a cadence
+//! while the market is open. A position whose greeks can't be fetched
+//! is listed in `unpriced` and left out of the totals.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::events::{AppEvent, EventEmitter};
+use crate::ibkr::client::IbkrClient;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::{OptionContract, OptionGreeks, Position};
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+
+#[cfg(test)]
+mod tests;
+
+/// Trait seam for the model-greeks read. Production is the live
+/// `IbkrClient`; tests inject fixed greeks.
+#[async_trait]
+pub trait GreeksSource: Send + Sync {
+    async fn option_greeks(&self, position: &Position) -> Result<OptionGreeks, IbkrError>;
+}
+
+#[async_trait]
+impl GreeksSource for IbkrClient {
+    async fn option_greeks(&self, position: &Position) -> Result<OptionGreeks, IbkrError> {
+        let option = OptionContract::from_position(position).ok_or_else(|| {
+            IbkrError::RequestFailed(format!("{} is not a priced option", position.local_symbol))
+        })?;
+        IbkrClient::option_greeks(self, &option).await
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionGreeks {
+    pub symbol: String,
+    pub local_symbol: String,
+    pub quantity: f64,
+    pub multiplier: f64,
+    /// Per contract, as TWS reports them.
+    pub model: OptionGreeks,
+    pub delta: f64,
+    pub gamma: f64,
+    pub theta: f64,
+    pub vega: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioGreeks {
+    pub account: String,
+    pub positions: Vec<PositionGreeks>,
+    pub delta: f64,
+    pub gamma: f64,
+    pub theta: f64,
+    pub vega: f64,
+    /// Local symbols of option positions without model greeks.
+    pub unpriced: Vec<String>,
+    /// Unix seconds.
+    pub as_of: i64,
+}
+
+pub struct OptionGreeksService {
+    greeks: Arc<dyn GreeksSource>,
+    positions: Arc<dyn OpenPositionsSource>,
+    accounts: Arc<dyn AccountSource>,
+    emitter: Arc<EventEmitter>,
+}
+
+impl OptionGreeksService {
+    pub fn new(
+        greeks: Arc<dyn GreeksSource>,
+        positions: Arc<dyn OpenPositionsSource>,
+        accounts: Arc<dyn AccountSource>,
+        emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            greeks,
+            positions,
+            accounts,
+            emitter,
+        }
+    }
+
+    /// Greeks for `account` (default: the first managed account).
+    pub async fn portfolio_greeks(
+        &self,
+        account: Option<String>,
+    ) -> Result<PortfolioGreeks, IbkrError> {
+        let account = match account {
+            Some(a) if !a.trim().is_empty() => a,
+            _ => self.accounts.current_account().await?,
+        };
+        let mut out = PortfolioGreeks {
+            account: account.clone(),
+            as_of: Utc::now().timestamp(),
+            ..Default::default()
+        };
+        for position in self.positions.list_open(&account).await? {
+            if position.contract_type != "OPT" || position.position == 0.0 {
+                continue;
+            }
+            match self.greeks.option_greeks(&position).await {
+                Ok(model) if model.delta.is_some() => out.add(&position, model),
+                Ok(_) => out.unpriced.push(position.local_symbol.clone()),
+                Err(e) => {
+                    warn!("option_greeks: {} unpriced: {e}", position.local_symbol);
+                    out.unpriced.push(position.local_symbol.clone());
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Compute and emit `PortfolioGreeksUpdate`. Nothing is emitted
+    /// for an account without option positions.
+    pub async fn publish(&self) -> Result<PortfolioGreeks, IbkrError> {
+        let greeks = self.portfolio_greeks(None).await?;
+        if !greeks.positions.is_empty() || !greeks.unpriced.is_empty() {
+            let event = AppEvent::PortfolioGreeksUpdate {
+                greeks: greeks.clone(),
+            };
+            if let Err(e) = self.emitter.emit(event).await {
+                warn!("PortfolioGreeksUpdate emit failed: {e}");
+            }
+        }
+        Ok(greeks)
+    }
+}
+
+impl PortfolioGreeks {
+    fn add(&mut self, position: &Position, model: OptionGreeks) {
+        let multiplier = position
+            .multiplier
+            .as_deref()
+            .and_then(|m| m.trim().parse::<f64>().ok())
+            .filter(|m| *m > 0.0)
+            .unwrap_or(100.0);
+        let scale = position.position * multiplier;
+        let scaled = |g: Option<f64>| g.unwrap_or(0.0) * scale;
+        let row = PositionGreeks {
+            symbol: position.symbol.clone(),
+            local_symbol: position.local_symbol.clone(),
+            quantity: position.position,
+            multiplier,
+            delta: scaled(model.delta),
+            gamma: scaled(model.gamma),
+            theta: scaled(model.theta),
+            vega: scaled(model.vega),
+            model,
+        };
+        self.delta += row.delta;
+        self.gamma += row.gamma;
+        self.theta += row.theta;
+        self.vega += row.vega;
+        self.positions.push(row);
+    }
+}
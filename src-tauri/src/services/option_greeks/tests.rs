@@ -0,0 +1,105 @@
+use super::*;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Calls are 0.5 delta, puts -0.3; `BAD` options time out.
+struct StubGreeks;
+
+#[async_trait]
+impl GreeksSource for StubGreeks {
+    async fn option_greeks(&self, position: &Position) -> Result<OptionGreeks, IbkrError> {
+        if position.symbol == "BAD" {
+            return Err(IbkrError::Timeout(5000));
+        }
+        let call = position.right.as_deref() == Some("C");
+        Ok(OptionGreeks {
+            delta: Some(if call { 0.5 } else { -0.3 }),
+            gamma: Some(0.02),
+            theta: Some(-0.05),
+            vega: Some(0.1),
+            ..Default::default()
+        })
+    }
+}
+
+fn option(symbol: &str, right: &str, qty: f64, multiplier: Option<&str>) -> Position {
+    Position {
+        symbol: symbol.to_string(),
+        local_symbol: format!("{symbol} {right}"),
+        contract_type: "OPT".to_string(),
+        position: qty,
+        right: Some(right.to_string()),
+        multiplier: multiplier.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+fn service(positions: Vec<Position>) -> (OptionGreeksService, Arc<EventEmitter>) {
+    let emitter = Arc::new(EventEmitter::for_capture());
+    let svc = OptionGreeksService::new(
+        Arc::new(StubGreeks),
+        Arc::new(StubPositions(positions)),
+        Arc::new(FixedAccount),
+        emitter.clone(),
+    );
+    (svc, emitter)
+}
+
+#[tokio::test]
+async fn scales_greeks_by_position_and_aggregates() {
+    let stock = Position {
+        symbol: "AAPL".to_string(),
+        contract_type: "STK".to_string(),
+        position: 100.0,
+        ..Default::default()
+    };
+    let (svc, _) = service(vec![
+        stock,
+        option("AAPL", "C", 2.0, Some("100")),
+        option("SPY", "P", -1.0, None),
+        option("BAD", "C", 1.0, Some("100")),
+    ]);
+    let greeks = svc.portfolio_greeks(None).await.unwrap();
+
+    assert_eq!(greeks.account, "DU1");
+    assert_eq!(greeks.positions.len(), 2);
+    assert_eq!(greeks.unpriced, vec!["BAD C".to_string()]);
+    // 2 × 100 × 0.5 and -1 × 100 (default multiplier) × -0.3.
+    assert!((greeks.positions[0].delta - 100.0).abs() < 1e-9);
+    assert!((greeks.positions[1].delta - 30.0).abs() < 1e-9);
+    assert!((greeks.delta - 130.0).abs() < 1e-9);
+    assert!((greeks.gamma - 2.0).abs() < 1e-9);
+    assert!((greeks.theta - -5.0).abs() < 1e-9);
+    assert!((greeks.vega - 10.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn publish_emits_only_when_options_are_held() {
+    let (svc, emitter) = service(vec![]);
+    svc.publish().await.unwrap();
+    assert!(emitter.captured().await.is_empty());
+
+    let (svc, emitter) = service(vec![option("AAPL", "C", 1.0, Some("100"))]);
+    svc.publish().await.unwrap();
+    let events = emitter.captured().await;
+    assert!(matches!(
+        events.as_slice(),
+        [AppEvent::PortfolioGreeksUpdate { greeks }] if greeks.positions.len() == 1
+    ));
+}
@@ -0,0 +1,104 @@
+//! Candidate selection and per-contract math for
+//! [`super::OptionIncomeService`].
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::ibkr::types::{OptionChain, OptionContract, OptionGreeks, OptionRight, OrderRequest};
+
+use super::sell_to_open;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomeSuggestion {
+    pub contract: OptionContract,
+    pub dte: i64,
+    pub delta: f64,
+    /// Model price per share.
+    pub premium: f64,
+    /// Premium for all contracts.
+    pub premium_total: f64,
+    pub annualized_yield_pct: f64,
+    /// Underlying price at expiry where the trade breaks even: today's
+    /// price less premium for a covered call, strike less premium for
+    /// a CSP.
+    pub breakeven: f64,
+    /// Cash a CSP ties up (strike × multiplier × contracts); 0 for a
+    /// covered call.
+    pub cash_required: f64,
+    pub order: OrderRequest,
+}
+
+/// Expirations with `min_dte <= DTE <= max_dte`, nearest first.
+pub(super) fn expiries(
+    chain: &OptionChain,
+    today: NaiveDate,
+    min_dte: i64,
+    max_dte: i64,
+) -> Vec<(String, i64)> {
+    chain
+        .expirations
+        .iter()
+        .filter_map(|e| {
+            let date = NaiveDate::parse_from_str(e.trim(), "%Y%m%d").ok()?;
+            let dte = (date - today).num_days();
+            (min_dte..=max_dte)
+                .contains(&dte)
+                .then(|| (e.trim().to_string(), dte))
+        })
+        .collect()
+}
+
+/// Up to `limit` strikes out of the money for `right`, nearest first.
+pub(super) fn otm_strikes(
+    chain: &OptionChain,
+    price: f64,
+    right: OptionRight,
+    limit: usize,
+) -> Vec<f64> {
+    let mut strikes: Vec<f64> = chain
+        .strikes
+        .iter()
+        .copied()
+        .filter(|s| match right {
+            OptionRight::Call => *s > price,
+            OptionRight::Put => *s < price && *s > 0.0,
+        })
+        .collect();
+    strikes.sort_by(|a, b| (a - price).abs().total_cmp(&(b - price).abs()));
+    strikes.truncate(limit);
+    strikes
+}
+
+/// `None` when the model gave no positive price or no delta.
+pub(super) fn evaluate(
+    option: OptionContract,
+    greeks: &OptionGreeks,
+    dte: i64,
+    price: f64,
+    multiplier: f64,
+    contracts: u32,
+) -> Option<IncomeSuggestion> {
+    let premium = greeks.option_price.filter(|p| *p > 0.0)?;
+    let delta = greeks.delta?;
+    let (capital, breakeven, cash_required) = match option.right {
+        OptionRight::Call => (price, price - premium, 0.0),
+        OptionRight::Put => (
+            option.strike,
+            option.strike - premium,
+            option.strike * multiplier * f64::from(contracts),
+        ),
+    };
+    let annualized_yield_pct = premium / capital * 365.0 / dte.max(1) as f64 * 100.0;
+    Some(IncomeSuggestion {
+        dte,
+        delta,
+        premium,
+        premium_total: premium * multiplier * f64::from(contracts),
+        annualized_yield_pct,
+        breakeven,
+        cash_required,
+        order: sell_to_open(option.clone(), contracts, premium),
+        contract: option,
+    })
+}
@@ -0,0 +1,349 @@
+//! Covered-call and cash-secured-put helper.
+//!
+//! For `symbol`, pulls the option chain, prices out-of-the-money calls
+//! (covered call, against shares held) or puts (CSP) over the next few
+//! expirations in the DTE window, and keeps those inside the delta
+//! limit that pay at least the target annualized yield. Yield is the
+//! model premium over the capital at risk — the share price for a
+//! covered call, the strike for a CSP — scaled to 365 days.
+//!
+//! Each suggestion carries a ready `OrderRequest` (sell to open, limit
+//! at the model price, `option` set) for the order form. Nothing is
+//! placed here: the trader reviews it and sends it through
+//! `ibkr_place_order` like any other manual order (Hard Invariant 1).
+//! The stock is already held for a covered call, so both strategies
+//! are a single short option leg.
+//!
+//! The symbol's 52-week IV rank (`services::iv_rank`) rides along:
+//! below 30 the ideas carry a note that premium is cheap, and a
+//! request with `minIvRank` gets no suggestions under that rank.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use chrono_tz::America::New_York;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::debug;
+
+use crate::ibkr::client::IbkrClient;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::{
+    OptionChain, OptionContract, OptionGreeks, OptionRight, OrderAction, OrderRequest, OrderType,
+};
+use crate::services::iv_rank::{IvRank, IvRankService};
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+
+mod candidates;
+
+#[cfg(test)]
+mod tests;
+
+pub use candidates::IncomeSuggestion;
+
+/// Nearest expirations inside the DTE window that get priced.
+const MAX_EXPIRIES: usize = 4;
+/// Nearest OTM strikes per expiration that get priced.
+const MAX_STRIKES: usize = 6;
+/// IV rank under which premium counts as cheap.
+const LOW_IV_RANK: f64 = 30.0;
+
+/// Trait seam for the option market reads. Production is the live
+/// `IbkrClient`; tests inject a fixed chain and greeks.
+#[async_trait]
+pub trait OptionMarket: Send + Sync {
+    async fn option_chain(&self, symbol: &str) -> Result<OptionChain, IbkrError>;
+    async fn option_greeks(&self, option: &OptionContract) -> Result<OptionGreeks, IbkrError>;
+    async fn underlying_price(&self, symbol: &str) -> Result<f64, IbkrError>;
+}
+
+#[async_trait]
+impl OptionMarket for IbkrClient {
+    async fn option_chain(&self, symbol: &str) -> Result<OptionChain, IbkrError> {
+        IbkrClient::option_chain(self, symbol).await
+    }
+
+    async fn option_greeks(&self, option: &OptionContract) -> Result<OptionGreeks, IbkrError> {
+        IbkrClient::option_greeks(self, option).await
+    }
+
+    async fn underlying_price(&self, symbol: &str) -> Result<f64, IbkrError> {
+        let snapshot = self.get_market_data_snapshot(symbol).await?;
+        snapshot
+            .last_price
+            .or(snapshot.close)
+            .filter(|p| *p > 0.0)
+            .ok_or_else(|| IbkrError::RequestFailed(format!("no price for {symbol}")))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IncomeStrategy {
+    CoveredCall,
+    CashSecuredPut,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomeRequest {
+    pub symbol: String,
+    pub strategy: IncomeStrategy,
+    /// Minimum annualized premium yield, percent.
+    #[serde(default = "default_min_yield_pct")]
+    pub min_annualized_yield_pct: f64,
+    /// Maximum absolute delta of the short option.
+    #[serde(default = "default_max_delta")]
+    pub max_delta: f64,
+    #[serde(default = "default_min_dte")]
+    pub min_dte: i64,
+    #[serde(default = "default_max_dte")]
+    pub max_dte: i64,
+    /// Contracts to write. Covered calls default to (and are capped
+    /// at) what the shares cover; CSPs default to 1.
+    #[serde(default)]
+    pub contracts: Option<u32>,
+    /// Skip premium selling below this 52-week IV rank (0–100).
+    #[serde(default)]
+    pub min_iv_rank: Option<f64>,
+    /// Default: the first managed account.
+    #[serde(default)]
+    pub account: Option<String>,
+}
+
+fn default_min_yield_pct() -> f64 {
+    10.0
+}
+
+fn default_max_delta() -> f64 {
+    0.30
+}
+
+fn default_min_dte() -> i64 {
+    7
+}
+
+fn default_max_dte() -> i64 {
+    45
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomeIdeas {
+    pub symbol: String,
+    pub strategy: IncomeStrategy,
+    pub underlying_price: f64,
+    pub shares_held: f64,
+    pub contracts: u32,
+    /// Best annualized yield first.
+    pub suggestions: Vec<IncomeSuggestion>,
+    /// Contracts TWS returned no usable model price for.
+    pub unpriced: usize,
+    /// `None` when the IV history couldn't be read.
+    pub iv_rank: Option<IvRank>,
+    pub note: Option<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum OptionIncomeError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+    #[error("{0}")]
+    Invalid(String),
+}
+
+pub struct OptionIncomeService {
+    market: Arc<dyn OptionMarket>,
+    positions: Arc<dyn OpenPositionsSource>,
+    accounts: Arc<dyn AccountSource>,
+    iv: Arc<IvRankService>,
+}
+
+impl OptionIncomeService {
+    pub fn new(
+        market: Arc<dyn OptionMarket>,
+        positions: Arc<dyn OpenPositionsSource>,
+        accounts: Arc<dyn AccountSource>,
+        iv: Arc<IvRankService>,
+    ) -> Self {
+        Self {
+            market,
+            positions,
+            accounts,
+            iv,
+        }
+    }
+
+    pub async fn suggest(&self, req: &IncomeRequest) -> Result<IncomeIdeas, OptionIncomeError> {
+        let today = Utc::now().with_timezone(&New_York).date_naive();
+        self.suggest_on(req, today).await
+    }
+
+    async fn suggest_on(
+        &self,
+        req: &IncomeRequest,
+        today: NaiveDate,
+    ) -> Result<IncomeIdeas, OptionIncomeError> {
+        let symbol = req.symbol.trim().to_uppercase();
+        if symbol.is_empty() {
+            return Err(OptionIncomeError::Invalid("symbol is required".into()));
+        }
+        if req.min_dte > req.max_dte
+            || !(0.0..=1.0).contains(&req.max_delta)
+            || req.min_iv_rank.is_some_and(|r| !(0.0..=100.0).contains(&r))
+        {
+            return Err(OptionIncomeError::Invalid(
+                "DTE window, delta limit or IV rank minimum out of range".into(),
+            ));
+        }
+        let account = match &req.account {
+            Some(a) if !a.trim().is_empty() => a.clone(),
+            _ => self.accounts.current_account().await?,
+        };
+        let shares_held: f64 = self
+            .positions
+            .list_open(&account)
+            .await?
+            .iter()
+            .filter(|p| p.contract_type == "STK" && p.symbol.eq_ignore_ascii_case(&symbol))
+            .map(|p| p.position)
+            .sum();
+
+        let chain = self.market.option_chain(&symbol).await?;
+        let multiplier = chain.multiplier.trim().parse::<f64>().unwrap_or(100.0);
+        let contracts = contracts_for(req, shares_held, multiplier)?;
+        let price = self.market.underlying_price(&symbol).await?;
+        let right = match req.strategy {
+            IncomeStrategy::CoveredCall => OptionRight::Call,
+            IncomeStrategy::CashSecuredPut => OptionRight::Put,
+        };
+
+        let iv_rank = match self.iv.rank(&symbol).await {
+            Ok(rank) => Some(rank),
+            Err(e) => {
+                debug!("option_income: no IV rank for {symbol}: {e}");
+                None
+            }
+        };
+        let (skip, note) = iv_gate(req.min_iv_rank, iv_rank.as_ref());
+        let expiries = if skip {
+            Vec::new()
+        } else {
+            candidates::expiries(&chain, today, req.min_dte, req.max_dte)
+        };
+
+        let mut suggestions = Vec::new();
+        let mut unpriced = 0;
+        for (expiry, dte) in expiries.into_iter().take(MAX_EXPIRIES) {
+            for strike in candidates::otm_strikes(&chain, price, right, MAX_STRIKES) {
+                let option = OptionContract {
+                    symbol: symbol.clone(),
+                    expiry: expiry.clone(),
+                    strike,
+                    right,
+                    multiplier: Some(chain.multiplier.clone()).filter(|m| !m.is_empty()),
+                    currency: None,
+                };
+                let greeks = match self.market.option_greeks(&option).await {
+                    Ok(g) => g,
+                    Err(e) => {
+                        debug!("option_income: {symbol} {expiry} {strike} unpriced: {e}");
+                        unpriced += 1;
+                        continue;
+                    }
+                };
+                match candidates::evaluate(option, &greeks, dte, price, multiplier, contracts) {
+                    Some(s)
+                        if s.delta.abs() <= req.max_delta
+                            && s.annualized_yield_pct >= req.min_annualized_yield_pct =>
+                    {
+                        suggestions.push(s)
+                    }
+                    Some(_) => {}
+                    None => unpriced += 1,
+                }
+            }
+        }
+        suggestions.sort_by(|a, b| b.annualized_yield_pct.total_cmp(&a.annualized_yield_pct));
+
+        Ok(IncomeIdeas {
+            symbol,
+            strategy: req.strategy,
+            underlying_price: price,
+            shares_held,
+            contracts,
+            suggestions,
+            unpriced,
+            iv_rank,
+            note,
+        })
+    }
+}
+
+/// Whether to skip pricing under `min_rank`, and the note to show.
+fn iv_gate(min_rank: Option<f64>, rank: Option<&IvRank>) -> (bool, Option<String>) {
+    match (rank, min_rank) {
+        (Some(r), Some(min)) if r.iv_rank < min => (
+            true,
+            Some(format!(
+                "IV rank {:.0} is below the {min:.0} minimum; no premium to sell",
+                r.iv_rank
+            )),
+        ),
+        (Some(r), _) if r.iv_rank < LOW_IV_RANK => (
+            false,
+            Some(format!(
+                "IV rank {:.0}: premium is cheap against the past year",
+                r.iv_rank
+            )),
+        ),
+        (None, Some(_)) => (
+            false,
+            Some("IV rank unavailable; the minimum was not applied".to_string()),
+        ),
+        _ => (false, None),
+    }
+}
+
+fn contracts_for(
+    req: &IncomeRequest,
+    shares_held: f64,
+    multiplier: f64,
+) -> Result<u32, OptionIncomeError> {
+    match req.strategy {
+        IncomeStrategy::CoveredCall => {
+            let covered = (shares_held / multiplier).floor().max(0.0) as u32;
+            if covered == 0 {
+                return Err(OptionIncomeError::Invalid(format!(
+                    "{shares_held} shares of {} cover no contracts",
+                    req.symbol.trim().to_uppercase()
+                )));
+            }
+            match req.contracts {
+                Some(n) if n > covered => Err(OptionIncomeError::Invalid(format!(
+                    "shares cover {covered} contract(s), {n} requested"
+                ))),
+                Some(n) if n > 0 => Ok(n),
+                _ => Ok(covered),
+            }
+        }
+        IncomeStrategy::CashSecuredPut => Ok(req.contracts.filter(|n| *n > 0).unwrap_or(1)),
+    }
+}
+
+/// Sell-to-open limit order for `contracts` of `option` at `premium`.
+fn sell_to_open(option: OptionContract, contracts: u32, premium: f64) -> OrderRequest {
+    OrderRequest {
+        symbol: option.symbol.clone(),
+        action: OrderAction::Sell,
+        quantity: f64::from(contracts),
+        order_type: OrderType::Limit,
+        price: Some((premium * 100.0).round() / 100.0),
+        start_at: None,
+        algo: None,
+        route: None,
+        option: Some(option),
+    }
+}
@@ -0,0 +1,210 @@
+use super::*;
+use crate::ibkr::types::Position;
+use crate::services::iv_rank::IvHistory;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(f64);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(vec![Position {
+            symbol: "AAPL".to_string(),
+            contract_type: "STK".to_string(),
+            position: self.0,
+            ..Default::default()
+        }])
+    }
+}
+
+/// AAPL at 100. Delta falls 0.1 per 5 points out of the money from
+/// 0.45; the 5-point-OTM premium is 2.00 at 30 DTE and 2.60 at 37,
+/// halving per further 5 points. The 105 call in 20261120 doesn't
+/// price.
+struct StubMarket;
+
+#[async_trait]
+impl OptionMarket for StubMarket {
+    async fn option_chain(&self, _symbol: &str) -> Result<OptionChain, IbkrError> {
+        Ok(OptionChain {
+            symbol: "AAPL".to_string(),
+            multiplier: "100".to_string(),
+            expirations: vec![
+                "20261016".to_string(),
+                "20261113".to_string(),
+                "20261120".to_string(),
+                "20270115".to_string(),
+            ],
+            strikes: vec![85.0, 90.0, 95.0, 100.0, 105.0, 110.0, 115.0],
+        })
+    }
+
+    async fn option_greeks(&self, option: &OptionContract) -> Result<OptionGreeks, IbkrError> {
+        if option.expiry == "20261120" && option.strike == 105.0 {
+            return Err(IbkrError::Timeout(5000));
+        }
+        let steps = ((option.strike - 100.0).abs() / 5.0).round();
+        let base = if option.expiry == "20261113" {
+            2.0
+        } else {
+            2.6
+        };
+        let delta = 0.45 - 0.1 * steps;
+        Ok(OptionGreeks {
+            delta: Some(if option.right == OptionRight::Put {
+                -delta
+            } else {
+                delta
+            }),
+            option_price: Some(base / 2f64.powf(steps - 1.0)),
+            ..Default::default()
+        })
+    }
+
+    async fn underlying_price(&self, _symbol: &str) -> Result<f64, IbkrError> {
+        Ok(100.0)
+    }
+}
+
+/// 100 days of IV from 0.20 up to `today`; rank = `today` in percent
+/// of that 0.20..0.40 range.
+struct StubIv(f64);
+
+#[async_trait]
+impl IvHistory for StubIv {
+    async fn implied_vol(&self, _symbol: &str) -> Result<Vec<(String, f64)>, IbkrError> {
+        let mut series: Vec<_> = (0..99)
+            .map(|i| (format!("d{i}"), 0.20 + 0.20 * i as f64 / 98.0))
+            .collect();
+        series.push(("today".to_string(), 0.20 + 0.20 * self.0 / 100.0));
+        Ok(series)
+    }
+}
+
+fn service_with_iv(shares: f64, iv_rank: f64) -> OptionIncomeService {
+    OptionIncomeService::new(
+        Arc::new(StubMarket),
+        Arc::new(StubPositions(shares)),
+        Arc::new(FixedAccount),
+        Arc::new(IvRankService::new(Arc::new(StubIv(iv_rank)))),
+    )
+}
+
+fn service(shares: f64) -> OptionIncomeService {
+    service_with_iv(shares, 60.0)
+}
+
+fn request(strategy: IncomeStrategy) -> IncomeRequest {
+    IncomeRequest {
+        symbol: "aapl".to_string(),
+        strategy,
+        min_annualized_yield_pct: 10.0,
+        max_delta: 0.30,
+        min_dte: 7,
+        max_dte: 45,
+        contracts: None,
+        min_iv_rank: None,
+        account: None,
+    }
+}
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, 14).unwrap()
+}
+
+#[tokio::test]
+async fn covered_call_suggests_otm_calls_within_limits_for_covered_shares() {
+    let ideas = service(250.0)
+        .suggest_on(&request(IncomeStrategy::CoveredCall), today())
+        .await
+        .unwrap();
+
+    assert_eq!(ideas.symbol, "AAPL");
+    assert_eq!(ideas.contracts, 2);
+    assert_eq!(ideas.unpriced, 1);
+    // 2 DTE and 93 DTE fall outside the window; 105 (delta 0.35) is
+    // over the limit, 115 (≈6% annualized) under the target.
+    let picked: Vec<_> = ideas
+        .suggestions
+        .iter()
+        .map(|s| (s.contract.expiry.as_str(), s.contract.strike))
+        .collect();
+    assert_eq!(picked, vec![("20261120", 110.0), ("20261113", 110.0)]);
+
+    let best = &ideas.suggestions[0];
+    assert_eq!(best.dte, 37);
+    assert!((best.annualized_yield_pct - 1.3 / 100.0 * 365.0 / 37.0 * 100.0).abs() < 1e-9);
+    assert_eq!(best.cash_required, 0.0);
+    assert!(matches!(best.order.action, OrderAction::Sell));
+    assert_eq!(best.order.quantity, 2.0);
+    assert_eq!(best.order.price, Some(1.3));
+    assert_eq!(best.order.option.as_ref().unwrap().right, OptionRight::Call);
+}
+
+#[tokio::test]
+async fn cash_secured_put_reports_cash_required() {
+    let mut req = request(IncomeStrategy::CashSecuredPut);
+    req.contracts = Some(3);
+    let ideas = service(0.0).suggest_on(&req, today()).await.unwrap();
+
+    assert_eq!(ideas.contracts, 3);
+    let strikes: Vec<_> = ideas
+        .suggestions
+        .iter()
+        .map(|s| s.contract.strike)
+        .collect();
+    // 95 is over the delta limit, 85 under the yield target.
+    assert_eq!(strikes, vec![90.0, 90.0]);
+    let s = &ideas.suggestions[1];
+    assert_eq!(s.cash_required, 90.0 * 100.0 * 3.0);
+    assert!((s.breakeven - 89.0).abs() < 1e-9);
+    assert!((s.premium_total - 300.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn covered_call_needs_enough_shares() {
+    let err = service(50.0)
+        .suggest_on(&request(IncomeStrategy::CoveredCall), today())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("cover no contracts"));
+
+    let mut req = request(IncomeStrategy::CoveredCall);
+    req.contracts = Some(3);
+    let err = service(250.0).suggest_on(&req, today()).await.unwrap_err();
+    assert!(err.to_string().contains("cover 2 contract(s)"));
+}
+
+#[tokio::test]
+async fn low_iv_rank_notes_cheap_premium_and_honours_the_minimum() {
+    let ideas = service(250.0)
+        .suggest_on(&request(IncomeStrategy::CoveredCall), today())
+        .await
+        .unwrap();
+    assert!((ideas.iv_rank.unwrap().iv_rank - 60.0).abs() < 1e-9);
+    assert!(ideas.note.is_none());
+
+    let ideas = service_with_iv(250.0, 20.0)
+        .suggest_on(&request(IncomeStrategy::CoveredCall), today())
+        .await
+        .unwrap();
+    assert!(ideas.note.unwrap().contains("cheap"));
+    assert_eq!(ideas.suggestions.len(), 2);
+
+    let mut req = request(IncomeStrategy::CoveredCall);
+    req.min_iv_rank = Some(25.0);
+    let ideas = service_with_iv(250.0, 20.0)
+        .suggest_on(&req, today())
+        .await
+        .unwrap();
+    assert!(ideas.suggestions.is_empty());
+    assert!(ideas.note.unwrap().contains("below the 25 minimum"));
+}
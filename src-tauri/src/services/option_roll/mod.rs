@@ -0,0 +1,328 @@
+//! Roll suggestions for expiring short options.
+//!
+//! A short option within `option_roll.within_dte` days of expiry can be
+//! rolled: bought back and sold again at the chain's next expiration.
+//! For each one, the strikes nearest its own are priced at that next
+//! expiry and the `option_roll.candidates` whose delta is closest to the
+//! current option's are kept, so the roll keeps roughly the same risk.
+//!
+//! Prices are TWS model prices from the live chain. The net credit of a
+//! roll is the new option's premium less the cost of buying back the
+//! old one, per share; negative is a debit.
+//!
+//! Each candidate carries a ready two-leg `ComboOrderRequest` (a
+//! calendar or diagonal: buy to close, sell to open) limited at that
+//! net. Nothing is placed here: the trader reviews it and sends it
+//! through `ibkr_place_combo_order`, behind the `OrderGuard` and under
+//! the same confirmation rule as any manual order (Hard Invariant 1).
+
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use crate::config::AppConfig;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::{
+    ComboLeg, ComboOrderRequest, OptionChain, OptionContract, OrderAction, OrderType,
+};
+use crate::services::option_income::OptionMarket;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+
+#[cfg(test)]
+mod tests;
+
+/// Strikes nearest the current one that get priced at the next expiry.
+const MAX_STRIKES: usize = 6;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionRollConfig {
+    /// Short options this close to expiry get roll suggestions.
+    #[serde(default = "default_within_dte")]
+    pub within_dte: u32,
+    /// Candidates kept per position, closest delta first.
+    #[serde(default = "default_candidates")]
+    pub candidates: u32,
+}
+
+fn default_within_dte() -> u32 {
+    7
+}
+
+fn default_candidates() -> u32 {
+    3
+}
+
+impl Default for OptionRollConfig {
+    fn default() -> Self {
+        Self {
+            within_dte: default_within_dte(),
+            candidates: default_candidates(),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum OptionRollError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollCandidate {
+    pub contract: OptionContract,
+    pub dte: i64,
+    pub delta: f64,
+    /// Model price per share.
+    pub premium: f64,
+    /// `premium` less the current option's buy-back price, per share;
+    /// negative is a debit.
+    pub net_credit: f64,
+    /// `net_credit` for all contracts.
+    pub net_credit_total: f64,
+    pub order: ComboOrderRequest,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionRolls {
+    pub contract: OptionContract,
+    pub local_symbol: String,
+    /// Negative: short.
+    pub contracts: f64,
+    pub dte: i64,
+    pub delta: f64,
+    /// Model price per share to buy the position back.
+    pub close_price: f64,
+    /// Closest delta first; empty when the next expiry couldn't be
+    /// priced.
+    pub candidates: Vec<RollCandidate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollReport {
+    pub account: String,
+    pub as_of: NaiveDate,
+    /// Nearest expiry first.
+    pub positions: Vec<PositionRolls>,
+    /// Expiring short options that couldn't be rolled, with why.
+    pub skipped: Vec<String>,
+}
+
+pub struct OptionRollService {
+    account: Arc<dyn AccountSource>,
+    positions: Arc<dyn OpenPositionsSource>,
+    market: Arc<dyn OptionMarket>,
+    config: Arc<RwLock<AppConfig>>,
+}
+
+impl OptionRollService {
+    pub fn new(
+        account: Arc<dyn AccountSource>,
+        positions: Arc<dyn OpenPositionsSource>,
+        market: Arc<dyn OptionMarket>,
+        config: Arc<RwLock<AppConfig>>,
+    ) -> Self {
+        Self {
+            account,
+            positions,
+            market,
+            config,
+        }
+    }
+
+    /// Roll candidates for the expiring short options of `account` (the
+    /// current account when `None`), on `symbol` only when given.
+    pub async fn suggest(
+        &self,
+        account: Option<String>,
+        symbol: Option<String>,
+        today: NaiveDate,
+    ) -> Result<RollReport, OptionRollError> {
+        let config = self.config.read().await.option_roll.clone();
+        let account = match account.filter(|a| !a.trim().is_empty()) {
+            Some(a) => a.trim().to_string(),
+            None => self.account.current_account().await?,
+        };
+        let symbol = symbol
+            .map(|s| s.trim().to_uppercase())
+            .filter(|s| !s.is_empty());
+
+        let mut positions = Vec::new();
+        let mut skipped = Vec::new();
+        for position in self.positions.list_open(&account).await? {
+            if position.position >= 0.0
+                || symbol
+                    .as_deref()
+                    .is_some_and(|s| !position.symbol.eq_ignore_ascii_case(s))
+            {
+                continue;
+            }
+            let Some((option, expiry)) = OptionContract::from_position(&position).and_then(|o| {
+                let expiry = NaiveDate::parse_from_str(&o.expiry, "%Y%m%d").ok()?;
+                Some((o, expiry))
+            }) else {
+                continue;
+            };
+            let dte = (expiry - today).num_days();
+            if dte < 0 || dte > config.within_dte as i64 {
+                continue;
+            }
+            let label = position.contract_key();
+            let current = match self.market.option_greeks(&option).await {
+                Ok(g) => g,
+                Err(e) => {
+                    skipped.push(format!("{label}: not priced ({e})"));
+                    continue;
+                }
+            };
+            let (Some(close_price), Some(delta)) =
+                (current.option_price.filter(|p| *p > 0.0), current.delta)
+            else {
+                skipped.push(format!("{label}: no model price"));
+                continue;
+            };
+            let chain = match self.market.option_chain(&option.symbol).await {
+                Ok(chain) => chain,
+                Err(e) => {
+                    skipped.push(format!("{label}: no option chain ({e})"));
+                    continue;
+                }
+            };
+            let Some((next, next_dte)) = next_expiry(&chain, expiry, today) else {
+                skipped.push(format!("{label}: no later expiration listed"));
+                continue;
+            };
+            let multiplier = option
+                .multiplier
+                .as_deref()
+                .and_then(|m| m.parse::<f64>().ok())
+                .filter(|m| *m > 0.0)
+                .unwrap_or(100.0);
+            let contracts = position.position.abs();
+
+            let mut candidates = Vec::new();
+            for strike in nearest_strikes(&chain, option.strike, MAX_STRIKES) {
+                let contract = OptionContract {
+                    expiry: next.clone(),
+                    strike,
+                    ..option.clone()
+                };
+                let greeks = match self.market.option_greeks(&contract).await {
+                    Ok(g) => g,
+                    Err(e) => {
+                        debug!(
+                            "option_roll: {} {next} {strike} unpriced: {e}",
+                            option.symbol
+                        );
+                        continue;
+                    }
+                };
+                let (Some(premium), Some(new_delta)) =
+                    (greeks.option_price.filter(|p| *p > 0.0), greeks.delta)
+                else {
+                    continue;
+                };
+                let net_credit = premium - close_price;
+                candidates.push(RollCandidate {
+                    order: roll_order(&option, &contract, contracts, net_credit),
+                    contract,
+                    dte: next_dte,
+                    delta: new_delta,
+                    premium,
+                    net_credit,
+                    net_credit_total: net_credit * multiplier * contracts,
+                });
+            }
+            candidates.sort_by(|a, b| {
+                (a.delta - delta)
+                    .abs()
+                    .total_cmp(&(b.delta - delta).abs())
+                    .then_with(|| b.net_credit.total_cmp(&a.net_credit))
+            });
+            candidates.truncate(config.candidates as usize);
+            positions.push(PositionRolls {
+                contract: option,
+                local_symbol: position.local_symbol.clone(),
+                contracts: position.position,
+                dte,
+                delta,
+                close_price,
+                candidates,
+            });
+        }
+        positions.sort_by(|a, b| {
+            a.dte
+                .cmp(&b.dte)
+                .then_with(|| a.local_symbol.cmp(&b.local_symbol))
+        });
+        Ok(RollReport {
+            account,
+            as_of: today,
+            positions,
+            skipped,
+        })
+    }
+}
+
+/// The first listed expiration after `expiry`, with its DTE.
+fn next_expiry(chain: &OptionChain, expiry: NaiveDate, today: NaiveDate) -> Option<(String, i64)> {
+    chain
+        .expirations
+        .iter()
+        .filter_map(|e| {
+            let date = NaiveDate::parse_from_str(e.trim(), "%Y%m%d").ok()?;
+            (date > expiry).then(|| (e.trim().to_string(), date))
+        })
+        .min_by_key(|(_, date)| *date)
+        .map(|(e, date)| (e, (date - today).num_days()))
+}
+
+/// Up to `limit` listed strikes nearest `strike`, nearest first.
+fn nearest_strikes(chain: &OptionChain, strike: f64, limit: usize) -> Vec<f64> {
+    let mut strikes: Vec<f64> = chain.strikes.iter().copied().filter(|s| *s > 0.0).collect();
+    strikes.sort_by(|a, b| (a - strike).abs().total_cmp(&(b - strike).abs()));
+    strikes.truncate(limit);
+    strikes
+}
+
+/// Buy `current` back and sell `next` as one combo, limited at
+/// `net_credit` per share. A credit sells the combo (legs reversed),
+/// a debit buys it; either way the limit is positive, at least a cent.
+fn roll_order(
+    current: &OptionContract,
+    next: &OptionContract,
+    contracts: f64,
+    net_credit: f64,
+) -> ComboOrderRequest {
+    let (action, close, open) = if net_credit >= 0.0 {
+        (OrderAction::Sell, OrderAction::Sell, OrderAction::Buy)
+    } else {
+        (OrderAction::Buy, OrderAction::Buy, OrderAction::Sell)
+    };
+    ComboOrderRequest {
+        symbol: current.symbol.clone(),
+        legs: vec![
+            ComboLeg {
+                option: current.clone(),
+                action: close,
+                ratio: 1,
+            },
+            ComboLeg {
+                option: next.clone(),
+                action: open,
+                ratio: 1,
+            },
+        ],
+        action,
+        quantity: contracts,
+        order_type: OrderType::Limit,
+        price: Some(((net_credit.abs() * 100.0).round() / 100.0).max(0.01)),
+    }
+}
@@ -0,0 +1,165 @@
+use async_trait::async_trait;
+
+use super::*;
+use crate::ibkr::types::{ComboKind, OptionGreeks, OptionRight, Position};
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// SPY lists 480–520 every 5. The expiring 500 call is worth 3.00 at
+/// delta 0.50; a week later the 500 call is worth 4.20 at 0.52, and
+/// each strike up loses 1.40 and 0.05 of delta. Nothing is listed after
+/// 20260717. QQQ has no chain.
+struct StubMarket;
+
+#[async_trait]
+impl OptionMarket for StubMarket {
+    async fn option_chain(&self, symbol: &str) -> Result<OptionChain, IbkrError> {
+        if symbol != "SPY" {
+            return Err(IbkrError::RequestFailed(format!("no chain for {symbol}")));
+        }
+        Ok(OptionChain {
+            symbol: "SPY".to_string(),
+            multiplier: "100".to_string(),
+            expirations: vec![
+                "20260612".to_string(),
+                "20260619".to_string(),
+                "20260717".to_string(),
+                "20260626".to_string(),
+            ],
+            strikes: (0..9).map(|i| 480.0 + 5.0 * i as f64).collect(),
+        })
+    }
+
+    async fn option_greeks(&self, option: &OptionContract) -> Result<OptionGreeks, IbkrError> {
+        let (delta, price) = match option.expiry.as_str() {
+            "20260619" => (0.5, 3.0),
+            "20260717" => (-0.3, 2.0),
+            _ => {
+                let steps = (option.strike - 500.0) / 5.0;
+                (0.52 - 0.05 * steps, 4.2 - 1.4 * steps)
+            }
+        };
+        Ok(OptionGreeks {
+            delta: Some(delta),
+            option_price: Some(price),
+            ..Default::default()
+        })
+    }
+
+    async fn underlying_price(&self, _symbol: &str) -> Result<f64, IbkrError> {
+        Ok(500.0)
+    }
+}
+
+fn option(symbol: &str, right: &str, strike: f64, expiry: &str, quantity: f64) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: quantity,
+        contract_type: "OPT".to_string(),
+        local_symbol: format!("{symbol} {expiry} {strike}{right}"),
+        expiry: Some(expiry.to_string()),
+        strike: Some(strike),
+        right: Some(right.to_string()),
+        multiplier: Some("100".to_string()),
+        ..Default::default()
+    }
+}
+
+fn service() -> OptionRollService {
+    OptionRollService::new(
+        Arc::new(FixedAccount),
+        Arc::new(StubPositions(vec![
+            option("SPY", "C", 500.0, "20260619", -2.0),
+            option("SPY", "P", 480.0, "20260717", -1.0),
+            option("SPY", "C", 510.0, "20260619", 1.0),
+            option("QQQ", "P", 400.0, "20260619", -1.0),
+        ])),
+        Arc::new(StubMarket),
+        Arc::new(RwLock::new(AppConfig::default())),
+    )
+}
+
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 6, 15).unwrap()
+}
+
+#[tokio::test]
+async fn expiring_shorts_roll_to_the_next_expiry_at_a_similar_delta() {
+    let report = service().suggest(None, None, today()).await.unwrap();
+    assert_eq!(report.account, "DU1");
+    // Long, and not yet expiring: no rolls.
+    assert_eq!(report.positions.len(), 1);
+    assert_eq!(report.skipped.len(), 1);
+    assert!(report.skipped[0].starts_with("QQQ"), "{:?}", report.skipped);
+
+    let spy = &report.positions[0];
+    assert_eq!(spy.dte, 4);
+    assert_eq!(spy.contracts, -2.0);
+    assert_eq!(spy.close_price, 3.0);
+    let strikes: Vec<f64> = spy.candidates.iter().map(|c| c.contract.strike).collect();
+    assert_eq!(strikes, [500.0, 505.0, 495.0]);
+
+    // Same strike a week out: a 1.20 credit, sold as a calendar.
+    let calendar = &spy.candidates[0];
+    assert_eq!(calendar.contract.expiry, "20260626");
+    assert_eq!(calendar.contract.right, OptionRight::Call);
+    assert_eq!(calendar.dte, 11);
+    assert!((calendar.net_credit - 1.2).abs() < 1e-9);
+    assert!((calendar.net_credit_total - 240.0).abs() < 1e-6);
+    let order = &calendar.order;
+    assert_eq!(order.validate(), Ok(ComboKind::Calendar));
+    assert!(matches!(order.action, OrderAction::Sell));
+    assert!(matches!(order.legs[0].action, OrderAction::Sell));
+    assert_eq!(order.legs[0].option.expiry, "20260619");
+    assert_eq!(order.quantity, 2.0);
+    assert_eq!(order.price, Some(1.2));
+
+    // Up a strike costs 0.20 more than the buy-back: bought as a debit.
+    let diagonal = &spy.candidates[1];
+    assert!((diagonal.net_credit + 0.2).abs() < 1e-9);
+    assert_eq!(diagonal.order.validate(), Ok(ComboKind::Diagonal));
+    assert!(matches!(diagonal.order.action, OrderAction::Buy));
+    assert!(matches!(diagonal.order.legs[0].action, OrderAction::Buy));
+    assert_eq!(diagonal.order.price, Some(0.2));
+}
+
+#[tokio::test]
+async fn window_candidates_and_symbol_follow_the_request_and_config() {
+    let service = service();
+    {
+        let mut config = service.config.write().await;
+        config.option_roll.within_dte = 40;
+        config.option_roll.candidates = 1;
+    }
+    let report = service
+        .suggest(Some("DU2".to_string()), Some(" spy ".to_string()), today())
+        .await
+        .unwrap();
+    assert_eq!(report.account, "DU2");
+    assert_eq!(report.positions.len(), 1);
+    assert_eq!(report.positions[0].candidates.len(), 1);
+    // The July put is in the window now, but nothing is listed after it.
+    assert_eq!(report.skipped.len(), 1);
+    assert!(
+        report.skipped[0].contains("no later expiration"),
+        "{:?}",
+        report.skipped
+    );
+}
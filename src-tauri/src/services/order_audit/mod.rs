@@ -0,0 +1,231 @@
+//! Persistent order audit trail.
+//!
+//! `IbkrClient` publishes an [`OrderAuditEvent`] for every order it
+//! submits — the exact fields handed to ibapi, then the gateway's
+//! status transitions, fills and notices for that order id (see
+//! `ibkr/client/order_audit.rs`). [`OrderAuditStore::spawn_ingest`]
+//! appends each one to `order_audit`, and `get_order_history` reads
+//! them back for review of what the app actually sent. A fill also
+//! drops the cached positions ([`OrderAuditStore::with_positions`]) so
+//! the next read shows it.
+//!
+//! The trail is append-only; nothing here edits or prunes rows.
+//!
+//! Scheduled orders are Good-After-Time orders IBKR holds until their
+//! start time, so the trail is also where "what's still waiting to go
+//! live" comes from: [`OrderAuditStore::pending_scheduled`].
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedReceiver;
+use tracing::warn;
+
+use crate::ibkr::position_cache::PositionCache;
+use crate::ibkr::types::{parse_good_after, OrderAuditEvent, SentOrder};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::page::Page;
+use crate::utils::symbols;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAuditEntry {
+    pub id: i64,
+    /// Unix milliseconds.
+    pub recorded_at: i64,
+    pub order_id: i32,
+    pub symbol: String,
+    pub event: OrderAuditEvent,
+}
+
+/// A sent order whose start time is still ahead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledOrder {
+    pub order: SentOrder,
+    /// Unix seconds.
+    pub starts_at: i64,
+    /// Unix milliseconds.
+    pub sent_at: i64,
+    /// Latest IBKR status seen (`PreSubmitted` while held).
+    pub last_status: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct OrderAuditStore {
+    db: Arc<Db>,
+    positions: Option<Arc<PositionCache>>,
+}
+
+impl OrderAuditStore {
+    pub fn new(db: Arc<Db>) -> Self {
+        Self {
+            db,
+            positions: None,
+        }
+    }
+
+    /// Clear `positions` whenever the feed reports a fill.
+    pub fn with_positions(mut self, positions: Arc<PositionCache>) -> Self {
+        self.positions = Some(positions);
+        self
+    }
+
+    pub async fn record(&self, event: &OrderAuditEvent) -> Result<(), StorageError> {
+        let recorded_at = Utc::now().timestamp_millis();
+        let order_id = event.order_id();
+        let symbol = symbols::normalize(event.symbol());
+        let kind = event.kind();
+        let payload = serde_json::to_string(event)?;
+        self.db
+            .with_conn(move |conn| {
+                conn.execute(
+                    "INSERT INTO order_audit (recorded_at, order_id, symbol, kind, payload_json) \
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    rusqlite::params![recorded_at, order_id, symbol, kind, payload],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Entries with `from_ms <= recorded_at < to_ms`, oldest first,
+    /// optionally for one symbol, windowed by `page`.
+    pub async fn history(
+        &self,
+        from_ms: i64,
+        to_ms: i64,
+        symbol: Option<&str>,
+        page: Page,
+    ) -> Result<Vec<OrderAuditEntry>, StorageError> {
+        let symbol = symbol.map(symbols::normalize);
+        let raw = self
+            .db
+            .with_conn(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, recorded_at, order_id, symbol, payload_json \
+                     FROM order_audit \
+                     WHERE recorded_at >= ?1 AND recorded_at < ?2 \
+                       AND (?3 IS NULL OR symbol = ?3) \
+                     ORDER BY id ASC LIMIT ?4 OFFSET ?5",
+                )?;
+                let params =
+                    rusqlite::params![from_ms, to_ms, symbol, page.sql_limit(), page.offset];
+                let rows = stmt.query_map(params, |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, i64>(1)?,
+                        row.get::<_, i32>(2)?,
+                        row.get::<_, String>(3)?,
+                        row.get::<_, String>(4)?,
+                    ))
+                })?;
+                let mut out = Vec::new();
+                for row in rows {
+                    out.push(row?);
+                }
+                Ok(out)
+            })
+            .await?;
+        raw.into_iter()
+            .map(|(id, recorded_at, order_id, symbol, payload)| {
+                Ok(OrderAuditEntry {
+                    id,
+                    recorded_at,
+                    order_id,
+                    symbol,
+                    event: serde_json::from_str(&payload)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Good-After-Time orders starting after `now` that IBKR hasn't
+    /// reported cancelled, filled or inactive. Soonest first.
+    pub async fn pending_scheduled(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<ScheduledOrder>, StorageError> {
+        let raw = self
+            .db
+            .with_conn(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT s.recorded_at, s.payload_json, \
+                       (SELECT u.payload_json FROM order_audit u \
+                        WHERE u.kind = 'status' AND u.order_id = s.order_id AND u.id > s.id \
+                        ORDER BY u.id DESC LIMIT 1) \
+                     FROM order_audit s \
+                     WHERE s.kind = 'sent' \
+                       AND json_extract(s.payload_json, '$.good_after_time') IS NOT NULL",
+                )?;
+                let rows = stmt.query_map([], |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                    ))
+                })?;
+                let mut out = Vec::new();
+                for row in rows {
+                    out.push(row?);
+                }
+                Ok(out)
+            })
+            .await?;
+
+        let mut pending = Vec::new();
+        for (sent_at, sent, status) in raw {
+            let OrderAuditEvent::Sent(order) = serde_json::from_str(&sent)? else {
+                continue;
+            };
+            let Some(starts_at) = order.good_after_time.as_deref().and_then(parse_good_after)
+            else {
+                continue;
+            };
+            let status: Option<OrderAuditEvent> =
+                status.map(|s| serde_json::from_str(&s)).transpose()?;
+            if starts_at <= now || status.as_ref().is_some_and(OrderAuditEvent::is_terminal) {
+                continue;
+            }
+            let last_status = match status {
+                Some(OrderAuditEvent::Status { status, .. }) => Some(status),
+                _ => None,
+            };
+            pending.push(ScheduledOrder {
+                order,
+                starts_at: starts_at.timestamp(),
+                sent_at,
+                last_status,
+            });
+        }
+        pending.sort_by_key(|p| p.starts_at);
+        Ok(pending)
+    }
+
+    /// Drain the adapter's audit feed into the table until the sender
+    /// side is dropped.
+    pub fn spawn_ingest(
+        self,
+        mut rx: UnboundedReceiver<OrderAuditEvent>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                if let (OrderAuditEvent::Fill { .. }, Some(positions)) = (&event, &self.positions) {
+                    positions.clear();
+                }
+                if let Err(e) = self.record(&event).await {
+                    warn!(
+                        "order audit: recording {} for order {} failed: {e}",
+                        event.kind(),
+                        event.order_id()
+                    );
+                }
+            }
+        })
+    }
+}
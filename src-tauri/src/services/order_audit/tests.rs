@@ -0,0 +1,187 @@
+use tempfile::NamedTempFile;
+use tokio::sync::mpsc;
+
+use super::*;
+use crate::ibkr::types::{format_good_after, SentOrder};
+
+fn sent(order_id: i32, symbol: &str) -> OrderAuditEvent {
+    OrderAuditEvent::Sent(SentOrder {
+        order_id,
+        symbol: symbol.to_string(),
+        action: "BUY".to_string(),
+        quantity: 10.0,
+        order_type: "LMT".to_string(),
+        limit_price: Some(101.25),
+        aux_price: None,
+        parent_id: 0,
+        transmit: true,
+        good_after_time: None,
+        algo_strategy: None,
+        algo_params: Vec::new(),
+    })
+}
+
+fn status(order_id: i32, symbol: &str, status: &str) -> OrderAuditEvent {
+    OrderAuditEvent::Status {
+        order_id,
+        symbol: symbol.to_string(),
+        status: status.to_string(),
+        filled: 0.0,
+        remaining: 10.0,
+        avg_fill_price: 0.0,
+    }
+}
+
+#[tokio::test]
+async fn ingest_persists_events_and_history_filters_by_symbol_and_range() {
+    let tmp = NamedTempFile::new().unwrap();
+    let store = OrderAuditStore::new(Arc::new(Db::open(tmp.path()).unwrap()));
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    let ingest = store.clone().spawn_ingest(rx);
+    tx.send(sent(1, "aapl")).unwrap();
+    tx.send(status(1, "aapl", "Submitted")).unwrap();
+    tx.send(sent(2, "MSFT")).unwrap();
+    tx.send(OrderAuditEvent::Fill {
+        order_id: 1,
+        symbol: "aapl".to_string(),
+        exec_id: "0001.01".to_string(),
+        side: "BOT".to_string(),
+        shares: 10.0,
+        price: 101.2,
+        time: "20250205 10:31:02".to_string(),
+    })
+    .unwrap();
+    drop(tx);
+    ingest.await.unwrap();
+
+    let all = store.history(0, i64::MAX, None, Page::ALL).await.unwrap();
+    assert_eq!(all.len(), 4);
+    assert_eq!(all[0].event, sent(1, "aapl"));
+
+    let page = store
+        .history(0, i64::MAX, None, Page::new(Some(2), Some(1)))
+        .await
+        .unwrap();
+    let ids: Vec<_> = page.iter().map(|e| e.id).collect();
+    assert_eq!(ids, vec![all[1].id, all[2].id]);
+
+    let aapl = store
+        .history(0, i64::MAX, Some("AAPL"), Page::ALL)
+        .await
+        .unwrap();
+    let kinds: Vec<_> = aapl.iter().map(|e| e.event.kind()).collect();
+    assert_eq!(kinds, vec!["sent", "status", "fill"]);
+    assert!(aapl.iter().all(|e| e.order_id == 1 && e.symbol == "AAPL"));
+
+    let future = Utc::now().timestamp_millis() + 60_000;
+    assert!(store
+        .history(future, i64::MAX, None, Page::ALL)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn ingest_clears_cached_positions_on_fill() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::ibkr::position_cache::PositionCache;
+
+    let tmp = NamedTempFile::new().unwrap();
+    let positions = Arc::new(PositionCache::default());
+    let store = OrderAuditStore::new(Arc::new(Db::open(tmp.path()).unwrap()))
+        .with_positions(Arc::clone(&positions));
+    let fetches = AtomicUsize::new(0);
+    let read = || async {
+        positions
+            .get_or_refresh("DU1", || async {
+                fetches.fetch_add(1, Ordering::SeqCst);
+                Ok::<_, String>(Vec::new())
+            })
+            .await
+            .unwrap()
+    };
+    read().await;
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    let ingest = store.spawn_ingest(rx);
+    tx.send(status(1, "AAPL", "Submitted")).unwrap();
+    tx.send(OrderAuditEvent::Fill {
+        order_id: 1,
+        symbol: "AAPL".to_string(),
+        exec_id: "0001.01".to_string(),
+        side: "BOT".to_string(),
+        shares: 10.0,
+        price: 101.2,
+        time: "20250205 10:31:02".to_string(),
+    })
+    .unwrap();
+    drop(tx);
+    ingest.await.unwrap();
+
+    read().await;
+    assert_eq!(fetches.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn history_matches_share_class_symbols_in_any_spelling() {
+    let tmp = NamedTempFile::new().unwrap();
+    let store = OrderAuditStore::new(Arc::new(Db::open(tmp.path()).unwrap()));
+    store.record(&sent(1, "BRK B")).await.unwrap();
+
+    for query in ["BRK.B", "brk b", "BRK/B"] {
+        let rows = store
+            .history(0, i64::MAX, Some(query), Page::ALL)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1, "{query}");
+        assert_eq!(rows[0].symbol, "BRK.B");
+    }
+}
+
+#[tokio::test]
+async fn pending_scheduled_lists_future_gat_orders_until_terminal() {
+    let tmp = NamedTempFile::new().unwrap();
+    let store = OrderAuditStore::new(Arc::new(Db::open(tmp.path()).unwrap()));
+    let now = DateTime::from_timestamp(1_738_790_000, 0).unwrap();
+    let scheduled = |order_id: i32, at: DateTime<Utc>| {
+        let OrderAuditEvent::Sent(mut order) = sent(order_id, "AAPL") else {
+            unreachable!()
+        };
+        order.good_after_time = Some(format_good_after(at));
+        OrderAuditEvent::Sent(order)
+    };
+
+    store.record(&sent(1, "AAPL")).await.unwrap();
+    store
+        .record(&scheduled(2, now + chrono::Duration::hours(20)))
+        .await
+        .unwrap();
+    store
+        .record(&status(2, "AAPL", "PreSubmitted"))
+        .await
+        .unwrap();
+    store
+        .record(&scheduled(3, now + chrono::Duration::hours(2)))
+        .await
+        .unwrap();
+    store
+        .record(&scheduled(4, now + chrono::Duration::hours(1)))
+        .await
+        .unwrap();
+    store.record(&status(4, "AAPL", "Cancelled")).await.unwrap();
+    store
+        .record(&scheduled(5, now - chrono::Duration::hours(1)))
+        .await
+        .unwrap();
+
+    let pending = store.pending_scheduled(now).await.unwrap();
+    let ids: Vec<_> = pending.iter().map(|p| p.order.order_id).collect();
+    assert_eq!(ids, vec![3, 2]);
+    assert_eq!(pending[1].last_status.as_deref(), Some("PreSubmitted"));
+    assert_eq!(
+        pending[0].starts_at,
+        (now + chrono::Duration::hours(2)).timestamp()
+    );
+}
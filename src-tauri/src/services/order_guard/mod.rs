@@ -0,0 +1,182 @@
+//! Duplicate-order protection for `ibkr_place_order` and
+//! `ibkr_place_combo_order`.
+//!
+//! Two independent checks, both in memory (a restart forgets them):
+//!
+//! - **Idempotency key.** A caller-supplied key is remembered for
+//!   [`KEY_RETENTION`]. Re-sending it returns the order id of the first
+//!   placement instead of placing again; re-sending while the first is
+//!   still in flight, or with a different request, is rejected.
+//! - **Identical request.** Without a key, an order whose request is
+//!   identical (symbol normalized) to one placed within
+//!   `order_guard.duplicate_window_secs` is rejected. `0` disables it.
+//!
+//! A placement that fails releases its reservation, so a retry after
+//! a gateway error goes through.
+
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::ibkr::types::{ComboOrderRequest, OrderRequest};
+use crate::utils::symbols;
+
+#[cfg(test)]
+mod tests;
+
+/// How long an idempotency key is remembered.
+pub const KEY_RETENTION: Duration = Duration::hours(24);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderGuardConfig {
+    /// Identical requests within this many seconds are rejected.
+    #[serde(default = "default_duplicate_window_secs")]
+    pub duplicate_window_secs: u64,
+}
+
+fn default_duplicate_window_secs() -> u64 {
+    10
+}
+
+impl Default for OrderGuardConfig {
+    fn default() -> Self {
+        Self {
+            duplicate_window_secs: default_duplicate_window_secs(),
+        }
+    }
+}
+
+/// What the caller should do with an order that passed the guard.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Admission {
+    /// Place it, then report the outcome with [`OrderGuard::settle`].
+    Place(Reservation),
+    /// Already placed under this idempotency key; don't place again.
+    Replay(i32),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reservation {
+    fingerprint: String,
+    key: Option<String>,
+}
+
+struct Entry {
+    fingerprint: String,
+    key: Option<String>,
+    at: DateTime<Utc>,
+    /// `None` while the placement is in flight.
+    order_id: Option<i32>,
+}
+
+#[derive(Default)]
+pub struct OrderGuard {
+    entries: Mutex<Vec<Entry>>,
+}
+
+impl OrderGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn admit(
+        &self,
+        order: &impl Fingerprint,
+        key: Option<&str>,
+        window: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<Admission, String> {
+        let fingerprint = order.fingerprint();
+        let key = key.map(str::trim).filter(|k| !k.is_empty());
+        let mut entries = self.entries.lock().expect("order guard poisoned");
+        let horizon = window.max(KEY_RETENTION);
+        entries.retain(|e| now - e.at < horizon);
+
+        if let Some(key) = key {
+            if let Some(prev) = entries.iter().find(|e| e.key.as_deref() == Some(key)) {
+                if prev.fingerprint != fingerprint {
+                    return Err(format!(
+                        "idempotency key `{key}` was already used for a different order"
+                    ));
+                }
+                return match prev.order_id {
+                    Some(id) => Ok(Admission::Replay(id)),
+                    None => Err(format!(
+                        "order with idempotency key `{key}` is already being placed"
+                    )),
+                };
+            }
+        } else if let Some(prev) = entries
+            .iter()
+            .find(|e| e.fingerprint == fingerprint && now - e.at < window)
+        {
+            let ago = (now - prev.at).num_seconds();
+            return Err(match prev.order_id {
+                Some(id) => format!(
+                    "duplicate order: identical to order {id} placed {ago}s ago; resend with an idempotency key or wait {}s",
+                    window.num_seconds()
+                ),
+                None => "duplicate order: an identical order is already being placed".to_string(),
+            });
+        }
+
+        let reservation = Reservation {
+            fingerprint,
+            key: key.map(str::to_string),
+        };
+        entries.push(Entry {
+            fingerprint: reservation.fingerprint.clone(),
+            key: reservation.key.clone(),
+            at: now,
+            order_id: None,
+        });
+        Ok(Admission::Place(reservation))
+    }
+
+    /// Record the placed order id for `reservation`, or `None` when the
+    /// placement failed — that drops it so the same order can be
+    /// retried straight away.
+    pub fn settle(&self, reservation: &Reservation, order_id: Option<i32>) {
+        let mut entries = self.entries.lock().expect("order guard poisoned");
+        let Some(pos) = entries.iter().position(|e| {
+            e.order_id.is_none()
+                && e.fingerprint == reservation.fingerprint
+                && e.key == reservation.key
+        }) else {
+            return;
+        };
+        match order_id {
+            Some(id) => entries[pos].order_id = Some(id),
+            None => {
+                entries.remove(pos);
+            }
+        }
+    }
+}
+
+/// An order request the guard can compare: the request as JSON with
+/// the symbol normalized, so any field the request grows takes part
+/// in "identical".
+pub trait Fingerprint {
+    fn fingerprint(&self) -> String;
+}
+
+impl Fingerprint for OrderRequest {
+    fn fingerprint(&self) -> String {
+        let mut order = self.clone();
+        order.symbol = symbols::normalize(&order.symbol);
+        serde_json::to_string(&order).unwrap_or_default()
+    }
+}
+
+impl Fingerprint for ComboOrderRequest {
+    fn fingerprint(&self) -> String {
+        let mut order = self.clone();
+        order.symbol = symbols::normalize(&order.symbol);
+        format!(
+            "combo:{}",
+            serde_json::to_string(&order).unwrap_or_default()
+        )
+    }
+}
@@ -0,0 +1,171 @@
+use super::*;
+use crate::ibkr::types::{OrderAction, OrderType};
+
+fn order(symbol: &str, qty: f64) -> OrderRequest {
+    OrderRequest {
+        symbol: symbol.to_string(),
+        action: OrderAction::Buy,
+        quantity: qty,
+        order_type: OrderType::Limit,
+        price: Some(101.5),
+        start_at: None,
+        algo: None,
+        route: None,
+        option: None,
+    }
+}
+
+fn t0() -> DateTime<Utc> {
+    DateTime::from_timestamp(1_738_790_000, 0).unwrap()
+}
+
+fn place(guard: &OrderGuard, o: &OrderRequest, key: Option<&str>, at: DateTime<Utc>, id: i32) {
+    match guard.admit(o, key, Duration::seconds(10), at).unwrap() {
+        Admission::Place(r) => guard.settle(&r, Some(id)),
+        Admission::Replay(_) => panic!("expected a fresh placement"),
+    }
+}
+
+#[test]
+fn identical_request_inside_window_is_rejected() {
+    let guard = OrderGuard::new();
+    let window = Duration::seconds(10);
+    place(&guard, &order("aapl", 10.0), None, t0(), 1);
+
+    let err = guard
+        .admit(
+            &order("AAPL", 10.0),
+            None,
+            window,
+            t0() + Duration::seconds(3),
+        )
+        .unwrap_err();
+    assert!(err.contains("order 1"), "{err}");
+    // Different quantity is a different order.
+    assert!(guard
+        .admit(
+            &order("AAPL", 11.0),
+            None,
+            window,
+            t0() + Duration::seconds(3)
+        )
+        .is_ok());
+    // Past the window it goes through.
+    assert!(guard
+        .admit(
+            &order("AAPL", 10.0),
+            None,
+            window,
+            t0() + Duration::seconds(11)
+        )
+        .is_ok());
+}
+
+#[test]
+fn idempotency_key_replays_the_first_order_id() {
+    let guard = OrderGuard::new();
+    let window = Duration::seconds(10);
+    place(&guard, &order("MSFT", 5.0), Some("click-1"), t0(), 7);
+
+    let again = guard
+        .admit(
+            &order("MSFT", 5.0),
+            Some("click-1"),
+            window,
+            t0() + Duration::hours(1),
+        )
+        .unwrap();
+    assert_eq!(again, Admission::Replay(7));
+    // A fresh key places even an identical request.
+    assert!(matches!(
+        guard.admit(&order("MSFT", 5.0), Some("click-2"), window, t0()),
+        Ok(Admission::Place(_))
+    ));
+}
+
+#[test]
+fn idempotency_key_reused_for_a_different_order_is_rejected() {
+    let guard = OrderGuard::new();
+    let window = Duration::seconds(10);
+    place(&guard, &order("BRK.B", 5.0), Some("click-1"), t0(), 7);
+
+    // Same order spelled differently still replays.
+    assert_eq!(
+        guard
+            .admit(&order("$brk/b", 5.0), Some("click-1"), window, t0())
+            .unwrap(),
+        Admission::Replay(7)
+    );
+    let err = guard
+        .admit(&order("BRK.B", 6.0), Some("click-1"), window, t0())
+        .unwrap_err();
+    assert!(err.contains("different order"), "{err}");
+}
+
+#[test]
+fn in_flight_blocks_and_failure_releases() {
+    let guard = OrderGuard::new();
+    let window = Duration::seconds(10);
+    let Admission::Place(first) = guard
+        .admit(&order("NVDA", 1.0), Some("k"), window, t0())
+        .unwrap()
+    else {
+        panic!("expected a fresh placement");
+    };
+    assert!(guard
+        .admit(&order("NVDA", 1.0), Some("k"), window, t0())
+        .is_err());
+
+    guard.settle(&first, None);
+    assert!(matches!(
+        guard.admit(&order("NVDA", 1.0), Some("k"), window, t0()),
+        Ok(Admission::Place(_))
+    ));
+}
+
+#[test]
+fn identical_combo_inside_window_is_rejected() {
+    use crate::ibkr::types::{ComboLeg, ComboOrderRequest, OptionContract, OptionRight};
+
+    let leg = |strike: f64, action: OrderAction| ComboLeg {
+        option: OptionContract {
+            symbol: "SPY".to_string(),
+            expiry: "20261120".to_string(),
+            strike,
+            right: OptionRight::Call,
+            multiplier: None,
+            currency: None,
+        },
+        action,
+        ratio: 1,
+    };
+    let combo = |symbol: &str| ComboOrderRequest {
+        symbol: symbol.to_string(),
+        legs: vec![leg(500.0, OrderAction::Buy), leg(505.0, OrderAction::Sell)],
+        action: OrderAction::Buy,
+        quantity: 1.0,
+        order_type: OrderType::Limit,
+        price: Some(2.1),
+    };
+    let guard = OrderGuard::new();
+    match guard
+        .admit(&combo("SPY"), None, Duration::seconds(10), t0())
+        .unwrap()
+    {
+        Admission::Place(r) => guard.settle(&r, Some(9)),
+        Admission::Replay(_) => panic!("expected a fresh placement"),
+    }
+    let err = guard
+        .admit(
+            &combo("spy"),
+            None,
+            Duration::seconds(10),
+            t0() + Duration::seconds(3),
+        )
+        .unwrap_err();
+    assert!(err.contains("order 9"), "{err}");
+    // A plain order on the same symbol isn't the combo.
+    assert!(guard
+        .admit(&order("SPY", 1.0), None, Duration::seconds(10), t0())
+        .is_ok());
+}
@@ -6,6 +6,7 @@
 //! placer, and the audit row see byte-identical rungs.
 
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// One rung of the take-profit ladder. `qty` is whole-share; the
@@ -44,7 +45,7 @@ pub struct TicketReceipt {
 /// (P3 only writes `Open`; partial / stopped / canceled / filled flips
 /// happen via `order_ticket_cancel_bracket` and the future fill-status
 /// stream). Stored as `as_str()` in `bracket_groups.last_status`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum BracketStatus {
     Open,
@@ -0,0 +1,109 @@
+//! The fill engine: where a tick fills an order, at what price and
+//! commission, and the cash and holdings a run of fills adds up to.
+
+use std::collections::BTreeMap;
+
+use super::{PaperTradingConfig, SimFill, SimOrder, SimOrderType, SimSide};
+
+/// One step of the tape: a polled price (`open == high == low`) or a
+/// replayed bar.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Tick {
+    pub(super) open: f64,
+    pub(super) high: f64,
+    pub(super) low: f64,
+    pub(super) at: i64,
+}
+
+impl Tick {
+    pub(super) fn at_price(price: f64, at: i64) -> Self {
+        Self {
+            open: price,
+            high: price,
+            low: price,
+            at,
+        }
+    }
+}
+
+/// Tape price `order` fills at in `tick`, before slippage; `None` when
+/// the tick doesn't reach it. A tick that opens through the level fills
+/// at the open.
+pub(super) fn reference_price(order: &SimOrder, tick: &Tick) -> Option<f64> {
+    let level = order.price.unwrap_or(tick.open);
+    match (order.order_type, order.side) {
+        (SimOrderType::Market, _) => Some(tick.open),
+        (SimOrderType::Limit, SimSide::Buy) => (tick.low <= level).then(|| level.min(tick.open)),
+        (SimOrderType::Limit, SimSide::Sell) => (tick.high >= level).then(|| level.max(tick.open)),
+        (SimOrderType::Stop, SimSide::Buy) => (tick.high >= level).then(|| level.max(tick.open)),
+        (SimOrderType::Stop, SimSide::Sell) => (tick.low <= level).then(|| level.min(tick.open)),
+    }
+}
+
+/// `reference` moved `slippage_bps` against the order, but never past
+/// a limit.
+pub(super) fn slipped(order: &SimOrder, reference: f64, config: &PaperTradingConfig) -> f64 {
+    let slip = reference * config.slippage_bps / 10_000.0;
+    let price = match (order.side, order.order_type, order.price) {
+        (SimSide::Buy, SimOrderType::Limit, Some(limit)) => (reference + slip).min(limit),
+        (SimSide::Sell, SimOrderType::Limit, Some(limit)) => (reference - slip).max(limit),
+        (SimSide::Buy, _, _) => reference + slip,
+        (SimSide::Sell, _, _) => reference - slip,
+    };
+    (price * 10_000.0).round() / 10_000.0
+}
+
+pub(super) fn commission(quantity: f64, config: &PaperTradingConfig) -> f64 {
+    (quantity * config.commission_per_share).max(config.min_commission)
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Lot {
+    pub(super) quantity: f64,
+    pub(super) average_cost: f64,
+}
+
+/// Cash and holdings after replaying `fills` in order.
+pub(super) struct Ledger {
+    pub(super) cash: f64,
+    pub(super) realized_pnl: f64,
+    pub(super) commissions: f64,
+    pub(super) lots: BTreeMap<String, Lot>,
+}
+
+impl Ledger {
+    pub(super) fn from_fills(starting_cash: f64, fills: &[SimFill]) -> Self {
+        let mut ledger = Ledger {
+            cash: starting_cash,
+            realized_pnl: 0.0,
+            commissions: 0.0,
+            lots: BTreeMap::new(),
+        };
+        for fill in fills {
+            ledger.cash -= fill.commission;
+            ledger.commissions += fill.commission;
+            let lot = ledger.lots.entry(fill.symbol.clone()).or_default();
+            match fill.side {
+                SimSide::Buy => {
+                    ledger.cash -= fill.quantity * fill.price;
+                    let cost = lot.average_cost * lot.quantity + fill.price * fill.quantity;
+                    lot.quantity += fill.quantity;
+                    lot.average_cost = cost / lot.quantity;
+                }
+                SimSide::Sell => {
+                    ledger.cash += fill.quantity * fill.price;
+                    ledger.realized_pnl += (fill.price - lot.average_cost) * fill.quantity;
+                    lot.quantity -= fill.quantity;
+                }
+            }
+            if lot.quantity.abs() < 1e-9 {
+                ledger.lots.remove(&fill.symbol);
+            }
+        }
+        ledger
+    }
+
+    pub(super) fn held(&self, symbol: &str) -> f64 {
+        self.lots.get(symbol).map_or(0.0, |lot| lot.quantity)
+    }
+}
@@ -0,0 +1,371 @@
+//! Paper-trading simulator: a local fill engine and a simulated
+//! portfolio for practicing a strategy without sending anything to
+//! IBKR.
+//!
+//! `sim_place_order` records a [`SimOrder`]. A market order fills at
+//! once against the current price from [`SimPriceSource`]; limit and
+//! stop orders stay `working` until the tape reaches them, either from
+//! the `sim_fill` task (polls the price of every symbol with a working
+//! order, every minute of the session) or from [`PaperTrader::replay`],
+//! which walks historical bars as if they were the tape. A fill pays
+//! `paper_trading.slippage_bps` against the order (never past its
+//! limit) and a per-share commission with a minimum. Buys are capped at
+//! simulated cash and sells at the simulated holding; an order that
+//! would break either is rejected when it would have filled.
+//!
+//! The portfolio is derived from `sim_fills` at average cost, starting
+//! from `sim_account.starting_cash`. [`PaperTrader::reset`] drops every
+//! order and fill and starts a new run.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use thiserror::Error;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+use crate::events::{AppEvent, EventEmitter};
+use crate::ibkr::client::IbkrClient;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::historical::{parse_ibkr_time, HistoricalBar};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::page::Page;
+use crate::utils::symbols;
+
+mod fills;
+mod store;
+mod types;
+
+#[cfg(test)]
+mod tests;
+
+use fills::{commission, reference_price, slipped, Ledger, Tick};
+pub(crate) use types::SimAccount;
+pub use types::{
+    PaperTradingConfig, SimFill, SimOrder, SimOrderRequest, SimOrderStatus, SimOrderType,
+    SimPortfolio, SimPosition, SimSide,
+};
+
+/// Trait seam for the tape. Production is the live `IbkrClient` (the
+/// demo feed in demo mode); tests script prices.
+#[async_trait]
+pub trait SimPriceSource: Send + Sync {
+    async fn last_price(&self, symbol: &str) -> Result<f64, IbkrError>;
+}
+
+#[async_trait]
+impl SimPriceSource for IbkrClient {
+    /// Last trade, else the bid/ask midpoint, else the prior close.
+    async fn last_price(&self, symbol: &str) -> Result<f64, IbkrError> {
+        let snapshot = self.get_market_data_snapshot(symbol).await?;
+        let positive = |p: Option<f64>| p.filter(|p| p.is_finite() && *p > 0.0);
+        let mid = match (positive(snapshot.bid_price), positive(snapshot.ask_price)) {
+            (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
+            _ => None,
+        };
+        positive(snapshot.last_price)
+            .or(mid)
+            .or(positive(snapshot.close))
+            .ok_or_else(|| IbkrError::RequestFailed(format!("no price for {symbol}")))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum PaperTradingError {
+    #[error("sim order {0} not found")]
+    NotFound(i64),
+    #[error("sim order {id} is {status}, not working")]
+    NotWorking { id: i64, status: &'static str },
+    #[error("{0}")]
+    InvalidBar(String),
+    #[error("price: {0}")]
+    Price(#[from] IbkrError),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct PaperTrader {
+    db: Arc<Db>,
+    prices: Arc<dyn SimPriceSource>,
+    config: Arc<RwLock<AppConfig>>,
+    emitter: Arc<EventEmitter>,
+    /// Held while orders fill, so the `sim_fill` task, a replay and a
+    /// new order never spend the same cash or shares twice.
+    fills: Mutex<()>,
+}
+
+impl PaperTrader {
+    pub fn new(
+        db: Arc<Db>,
+        prices: Arc<dyn SimPriceSource>,
+        config: Arc<RwLock<AppConfig>>,
+        emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            db,
+            prices,
+            config,
+            emitter,
+            fills: Mutex::new(()),
+        }
+    }
+
+    /// Record `request` and fill it if the current price reaches it. A
+    /// market order needs a price now; a limit or stop order is kept
+    /// working when none is available.
+    pub async fn place(&self, request: SimOrderRequest) -> Result<SimOrder, PaperTradingError> {
+        let symbol = symbols::normalize(&request.symbol);
+        let last = match request.order_type {
+            SimOrderType::Market => Some(self.prices.last_price(&symbol).await?),
+            SimOrderType::Limit | SimOrderType::Stop => self.prices.last_price(&symbol).await.ok(),
+        };
+        let price = match request.order_type {
+            SimOrderType::Market => None,
+            SimOrderType::Limit | SimOrderType::Stop => request.price,
+        };
+
+        let _fills = self.fills.lock().await;
+        self.account().await?;
+        let now = Utc::now().timestamp();
+        let id = store::insert_order(
+            &self.db,
+            &symbol,
+            request.side,
+            request.quantity,
+            request.order_type,
+            price,
+            now,
+        )
+        .await?;
+        let order = self.order(id).await?;
+        match last {
+            Some(last) => {
+                self.try_fill(order, &Tick::at_price(last, now)).await?;
+                self.order(id).await
+            }
+            None => Ok(order),
+        }
+    }
+
+    pub async fn cancel(&self, id: i64) -> Result<SimOrder, PaperTradingError> {
+        let _fills = self.fills.lock().await;
+        let order = self.order(id).await?;
+        if order.status != SimOrderStatus::Working {
+            return Err(PaperTradingError::NotWorking {
+                id,
+                status: order.status.as_str(),
+            });
+        }
+        let now = Utc::now().timestamp();
+        store::close_order(&self.db, id, SimOrderStatus::Cancelled, None, now).await?;
+        self.order(id).await
+    }
+
+    /// Newest first, optionally with one status.
+    pub async fn orders(
+        &self,
+        status: Option<SimOrderStatus>,
+        page: Page,
+    ) -> Result<Vec<SimOrder>, PaperTradingError> {
+        Ok(store::orders(&self.db, status, page).await?)
+    }
+
+    /// Newest first, optionally for one symbol.
+    pub async fn fills(
+        &self,
+        symbol: Option<&str>,
+        page: Page,
+    ) -> Result<Vec<SimFill>, PaperTradingError> {
+        let symbol = symbol.map(symbols::normalize);
+        Ok(store::fills(&self.db, symbol, page).await?)
+    }
+
+    /// Cash, holdings at average cost and P&L, with positions marked at
+    /// the current price where one is available.
+    pub async fn portfolio(&self) -> Result<SimPortfolio, PaperTradingError> {
+        let account = self.account().await?;
+        let fills = store::all_fills(&self.db).await?;
+        let ledger = Ledger::from_fills(account.starting_cash, &fills);
+        let mut equity = ledger.cash;
+        let mut positions = Vec::new();
+        for (symbol, lot) in ledger.lots {
+            let market_price = self.prices.last_price(&symbol).await.ok();
+            equity += lot.quantity * market_price.unwrap_or(lot.average_cost);
+            positions.push(SimPosition {
+                unrealized_pnl: market_price.map(|p| (p - lot.average_cost) * lot.quantity),
+                symbol,
+                quantity: lot.quantity,
+                average_cost: lot.average_cost,
+                market_price,
+            });
+        }
+        Ok(SimPortfolio {
+            starting_cash: account.starting_cash,
+            started_at: account.started_at,
+            cash: ledger.cash,
+            positions,
+            realized_pnl: ledger.realized_pnl,
+            commissions: ledger.commissions,
+            equity,
+        })
+    }
+
+    /// Drop every sim order and fill and start a new run with
+    /// `starting_cash` (`paper_trading.starting_cash` when `None`).
+    pub async fn reset(
+        &self,
+        starting_cash: Option<f64>,
+    ) -> Result<SimPortfolio, PaperTradingError> {
+        let starting_cash = match starting_cash {
+            Some(cash) => cash,
+            None => self.config.read().await.paper_trading.starting_cash,
+        };
+        {
+            let _fills = self.fills.lock().await;
+            store::reset(&self.db, starting_cash, Utc::now().timestamp()).await?;
+        }
+        info!("paper_trading: new run with {starting_cash:.2}");
+        self.portfolio().await
+    }
+
+    /// Check every working order against the current price of its
+    /// symbol. A symbol without a price is skipped until the next poll.
+    pub async fn poll(&self) -> Result<Vec<SimFill>, PaperTradingError> {
+        let mut fills = Vec::new();
+        for symbol in store::working_symbols(&self.db).await? {
+            let price = match self.prices.last_price(&symbol).await {
+                Ok(price) => price,
+                Err(e) => {
+                    warn!("paper_trading: no price for {symbol}: {e}");
+                    continue;
+                }
+            };
+            let tick = Tick::at_price(price, Utc::now().timestamp());
+            fills.extend(self.apply(&symbol, &tick).await?);
+        }
+        Ok(fills)
+    }
+
+    /// Walk `bars` (oldest first) as the tape for `symbol`: each bar's
+    /// range fills the working orders it reaches, at the bar's time.
+    pub async fn replay(
+        &self,
+        symbol: &str,
+        bars: &[HistoricalBar],
+    ) -> Result<Vec<SimFill>, PaperTradingError> {
+        let symbol = symbols::normalize(symbol);
+        let mut fills = Vec::new();
+        for bar in bars {
+            let at = parse_ibkr_time(&bar.time).map_err(PaperTradingError::InvalidBar)?;
+            let tick = Tick {
+                open: bar.open,
+                high: bar.high,
+                low: bar.low,
+                at,
+            };
+            fills.extend(self.apply(&symbol, &tick).await?);
+        }
+        Ok(fills)
+    }
+
+    async fn apply(&self, symbol: &str, tick: &Tick) -> Result<Vec<SimFill>, PaperTradingError> {
+        let _fills = self.fills.lock().await;
+        let mut fills = Vec::new();
+        for order in store::working_orders(&self.db, symbol).await? {
+            if let Some(fill) = self.try_fill(order, tick).await? {
+                fills.push(fill);
+            }
+        }
+        Ok(fills)
+    }
+
+    /// Fill `order` if `tick` reaches it, or reject it when the fill
+    /// would overdraw cash or sell shares not held. Callers hold
+    /// `self.fills`.
+    async fn try_fill(
+        &self,
+        order: SimOrder,
+        tick: &Tick,
+    ) -> Result<Option<SimFill>, PaperTradingError> {
+        let Some(reference) = reference_price(&order, tick) else {
+            return Ok(None);
+        };
+        let config = self.config.read().await.paper_trading.clone();
+        let price = slipped(&order, reference, &config);
+        let commission = commission(order.quantity, &config);
+
+        let account = self.account().await?;
+        let ledger = Ledger::from_fills(account.starting_cash, &store::all_fills(&self.db).await?);
+        let rejection = match order.side {
+            SimSide::Buy if order.quantity * price + commission > ledger.cash + 1e-9 => {
+                Some(format!(
+                    "needs {:.2} with commission; cash is {:.2}",
+                    order.quantity * price + commission,
+                    ledger.cash
+                ))
+            }
+            SimSide::Sell if order.quantity > ledger.held(&order.symbol) + 1e-9 => Some(format!(
+                "sells {} {}; {} held",
+                order.quantity,
+                order.symbol,
+                ledger.held(&order.symbol)
+            )),
+            _ => None,
+        };
+        if let Some(reason) = rejection {
+            info!("paper_trading: order {} rejected: {reason}", order.id);
+            store::close_order(
+                &self.db,
+                order.id,
+                SimOrderStatus::Rejected,
+                Some(reason),
+                tick.at,
+            )
+            .await?;
+            return Ok(None);
+        }
+
+        let fill = store::insert_fill(
+            &self.db,
+            SimFill {
+                id: 0,
+                order_id: order.id,
+                symbol: order.symbol,
+                side: order.side,
+                quantity: order.quantity,
+                price,
+                reference_price: reference,
+                commission,
+                filled_at: tick.at,
+            },
+        )
+        .await?;
+        let event = AppEvent::SimOrderFilled {
+            order_id: fill.order_id,
+            symbol: fill.symbol.clone(),
+            side: fill.side.as_str().to_string(),
+            quantity: fill.quantity,
+            price: fill.price,
+        };
+        if let Err(e) = self.emitter.emit(event).await {
+            warn!("SimOrderFilled emit failed: {e}");
+        }
+        Ok(Some(fill))
+    }
+
+    async fn order(&self, id: i64) -> Result<SimOrder, PaperTradingError> {
+        store::order(&self.db, id)
+            .await?
+            .ok_or(PaperTradingError::NotFound(id))
+    }
+
+    /// The current run, started with `paper_trading.starting_cash` on
+    /// first use.
+    async fn account(&self) -> Result<SimAccount, PaperTradingError> {
+        let starting_cash = self.config.read().await.paper_trading.starting_cash;
+        Ok(store::ensure_account(&self.db, starting_cash, Utc::now().timestamp()).await?)
+    }
+}
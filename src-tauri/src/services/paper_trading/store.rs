@@ -0,0 +1,279 @@
+//! `sim_account`, `sim_orders` and `sim_fills` reads and writes.
+
+use rusqlite::OptionalExtension;
+
+use super::{SimAccount, SimFill, SimOrder, SimOrderStatus, SimOrderType, SimSide};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::page::Page;
+
+const ORDER_COLUMNS: &str =
+    "id, symbol, side, quantity, order_type, price, status, reason, created_at, closed_at";
+
+const FILL_COLUMNS: &str =
+    "id, order_id, symbol, side, quantity, price, reference_price, commission, filled_at";
+
+fn side(s: String) -> SimSide {
+    SimSide::parse(&s).unwrap_or(SimSide::Buy)
+}
+
+fn order_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SimOrder> {
+    let order_type: String = row.get(4)?;
+    let status: String = row.get(6)?;
+    Ok(SimOrder {
+        id: row.get(0)?,
+        symbol: row.get(1)?,
+        side: side(row.get(2)?),
+        quantity: row.get(3)?,
+        order_type: SimOrderType::parse(&order_type).unwrap_or(SimOrderType::Market),
+        price: row.get(5)?,
+        status: SimOrderStatus::parse(&status).unwrap_or(SimOrderStatus::Rejected),
+        reason: row.get(7)?,
+        created_at: row.get(8)?,
+        closed_at: row.get(9)?,
+    })
+}
+
+fn fill_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SimFill> {
+    Ok(SimFill {
+        id: row.get(0)?,
+        order_id: row.get(1)?,
+        symbol: row.get(2)?,
+        side: side(row.get(3)?),
+        quantity: row.get(4)?,
+        price: row.get(5)?,
+        reference_price: row.get(6)?,
+        commission: row.get(7)?,
+        filled_at: row.get(8)?,
+    })
+}
+
+/// The current run, created with `starting_cash` if there is none.
+pub async fn ensure_account(
+    db: &Db,
+    starting_cash: f64,
+    at: i64,
+) -> Result<SimAccount, StorageError> {
+    db.with_conn(move |conn| {
+        conn.execute(
+            "INSERT OR IGNORE INTO sim_account (id, starting_cash, started_at) VALUES (1, ?1, ?2)",
+            rusqlite::params![starting_cash, at],
+        )?;
+        conn.query_row(
+            "SELECT starting_cash, started_at FROM sim_account WHERE id = 1",
+            [],
+            |row| {
+                Ok(SimAccount {
+                    starting_cash: row.get(0)?,
+                    started_at: row.get(1)?,
+                })
+            },
+        )
+        .map_err(StorageError::from)
+    })
+    .await
+}
+
+/// Drop every order and fill and start a new run.
+pub async fn reset(db: &Db, starting_cash: f64, at: i64) -> Result<(), StorageError> {
+    db.with_conn(move |conn| {
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM sim_fills", [])?;
+        tx.execute("DELETE FROM sim_orders", [])?;
+        tx.execute(
+            "INSERT OR REPLACE INTO sim_account (id, starting_cash, started_at) VALUES (1, ?1, ?2)",
+            rusqlite::params![starting_cash, at],
+        )?;
+        tx.commit()?;
+        Ok(())
+    })
+    .await
+}
+
+/// New `working` order; returns the id.
+pub async fn insert_order(
+    db: &Db,
+    symbol: &str,
+    side: SimSide,
+    quantity: f64,
+    order_type: SimOrderType,
+    price: Option<f64>,
+    at: i64,
+) -> Result<i64, StorageError> {
+    let symbol = symbol.to_string();
+    db.with_conn(move |conn| {
+        conn.execute(
+            "INSERT INTO sim_orders (symbol, side, quantity, order_type, price, status, created_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![
+                symbol,
+                side.as_str(),
+                quantity,
+                order_type.as_str(),
+                price,
+                SimOrderStatus::Working.as_str(),
+                at
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    })
+    .await
+}
+
+pub async fn order(db: &Db, id: i64) -> Result<Option<SimOrder>, StorageError> {
+    db.with_conn(move |conn| {
+        conn.query_row(
+            &format!("SELECT {ORDER_COLUMNS} FROM sim_orders WHERE id = ?1"),
+            rusqlite::params![id],
+            order_from_row,
+        )
+        .optional()
+        .map_err(StorageError::from)
+    })
+    .await
+}
+
+/// Newest first.
+pub async fn orders(
+    db: &Db,
+    status: Option<SimOrderStatus>,
+    page: Page,
+) -> Result<Vec<SimOrder>, StorageError> {
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {ORDER_COLUMNS} FROM sim_orders \
+             WHERE (?1 IS NULL OR status = ?1) \
+             ORDER BY id DESC LIMIT ?2 OFFSET ?3"
+        ))?;
+        let params = rusqlite::params![status.map(|s| s.as_str()), page.sql_limit(), page.offset];
+        let rows = stmt.query_map(params, order_from_row)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
+
+/// Working orders for `symbol`, oldest first (the order they fill in).
+pub async fn working_orders(db: &Db, symbol: &str) -> Result<Vec<SimOrder>, StorageError> {
+    let symbol = symbol.to_string();
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {ORDER_COLUMNS} FROM sim_orders \
+             WHERE symbol = ?1 AND status = 'working' ORDER BY id ASC"
+        ))?;
+        let rows = stmt.query_map(rusqlite::params![symbol], order_from_row)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
+
+pub async fn working_symbols(db: &Db) -> Result<Vec<String>, StorageError> {
+    db.with_conn(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT symbol FROM sim_orders WHERE status = 'working' ORDER BY symbol",
+        )?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
+
+pub async fn close_order(
+    db: &Db,
+    id: i64,
+    status: SimOrderStatus,
+    reason: Option<String>,
+    at: i64,
+) -> Result<(), StorageError> {
+    db.with_conn(move |conn| {
+        conn.execute(
+            "UPDATE sim_orders SET status = ?2, reason = ?3, closed_at = ?4 WHERE id = ?1",
+            rusqlite::params![id, status.as_str(), reason, at],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+/// Record `fill` and mark its order filled; returns it with its id.
+pub async fn insert_fill(db: &Db, fill: SimFill) -> Result<SimFill, StorageError> {
+    db.with_conn(move |conn| {
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO sim_fills \
+               (order_id, symbol, side, quantity, price, reference_price, commission, filled_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                fill.order_id,
+                fill.symbol,
+                fill.side.as_str(),
+                fill.quantity,
+                fill.price,
+                fill.reference_price,
+                fill.commission,
+                fill.filled_at
+            ],
+        )?;
+        let id = tx.last_insert_rowid();
+        tx.execute(
+            "UPDATE sim_orders SET status = ?2, closed_at = ?3 WHERE id = ?1",
+            rusqlite::params![
+                fill.order_id,
+                SimOrderStatus::Filled.as_str(),
+                fill.filled_at
+            ],
+        )?;
+        tx.commit()?;
+        Ok(SimFill { id, ..fill })
+    })
+    .await
+}
+
+/// Newest first.
+pub async fn fills(
+    db: &Db,
+    symbol: Option<String>,
+    page: Page,
+) -> Result<Vec<SimFill>, StorageError> {
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {FILL_COLUMNS} FROM sim_fills \
+             WHERE (?1 IS NULL OR symbol = ?1) \
+             ORDER BY id DESC LIMIT ?2 OFFSET ?3"
+        ))?;
+        let params = rusqlite::params![symbol, page.sql_limit(), page.offset];
+        let rows = stmt.query_map(params, fill_from_row)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
+
+/// Every fill of the run, in the order it happened.
+pub async fn all_fills(db: &Db) -> Result<Vec<SimFill>, StorageError> {
+    db.with_conn(|conn| {
+        let mut stmt =
+            conn.prepare(&format!("SELECT {FILL_COLUMNS} FROM sim_fills ORDER BY id"))?;
+        let rows = stmt.query_map([], fill_from_row)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+
+use tempfile::NamedTempFile;
+
+use super::*;
+
+/// Quotes whatever the test last set; unknown symbols fail.
+#[derive(Default)]
+struct ScriptedPrices {
+    prices: StdMutex<HashMap<String, f64>>,
+}
+
+impl ScriptedPrices {
+    fn set(&self, symbol: &str, price: f64) {
+        self.prices
+            .lock()
+            .unwrap()
+            .insert(symbol.to_string(), price);
+    }
+}
+
+#[async_trait]
+impl SimPriceSource for ScriptedPrices {
+    async fn last_price(&self, symbol: &str) -> Result<f64, IbkrError> {
+        self.prices
+            .lock()
+            .unwrap()
+            .get(symbol)
+            .copied()
+            .ok_or(IbkrError::NotConnected)
+    }
+}
+
+fn trader(db: Arc<Db>) -> (PaperTrader, Arc<ScriptedPrices>, Arc<EventEmitter>) {
+    let config = AppConfig {
+        paper_trading: PaperTradingConfig {
+            starting_cash: 10_000.0,
+            slippage_bps: 10.0,
+            commission_per_share: 0.01,
+            min_commission: 1.0,
+        },
+        ..AppConfig::default()
+    };
+    let prices = Arc::new(ScriptedPrices::default());
+    let emitter = Arc::new(EventEmitter::for_capture());
+    let trader = PaperTrader::new(
+        db,
+        prices.clone(),
+        Arc::new(RwLock::new(config)),
+        emitter.clone(),
+    );
+    (trader, prices, emitter)
+}
+
+fn request(
+    side: SimSide,
+    quantity: f64,
+    order_type: SimOrderType,
+    price: Option<f64>,
+) -> SimOrderRequest {
+    SimOrderRequest {
+        symbol: "aapl".to_string(),
+        side,
+        quantity,
+        order_type,
+        price,
+    }
+}
+
+fn bar(time: &str, open: f64, high: f64, low: f64) -> HistoricalBar {
+    HistoricalBar {
+        time: time.to_string(),
+        open,
+        high,
+        low,
+        close: open,
+        volume: 0,
+        wap: open,
+        count: 0,
+    }
+}
+
+#[tokio::test]
+async fn market_orders_fill_with_slippage_and_commission_and_move_the_portfolio() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let (trader, prices, emitter) = trader(db);
+
+    assert!(matches!(
+        trader
+            .place(request(SimSide::Buy, 10.0, SimOrderType::Market, None))
+            .await,
+        Err(PaperTradingError::Price(_))
+    ));
+
+    prices.set("AAPL", 100.0);
+    let buy = trader
+        .place(request(SimSide::Buy, 50.0, SimOrderType::Market, None))
+        .await
+        .unwrap();
+    assert_eq!(buy.status, SimOrderStatus::Filled);
+    let fill = &trader.fills(None, Page::ALL).await.unwrap()[0];
+    assert_eq!(fill.reference_price, 100.0);
+    assert_eq!(fill.price, 100.1);
+    assert_eq!(fill.commission, 1.0);
+
+    prices.set("AAPL", 110.0);
+    let portfolio = trader.portfolio().await.unwrap();
+    assert_eq!(portfolio.starting_cash, 10_000.0);
+    assert!((portfolio.cash - (10_000.0 - 5_005.0 - 1.0)).abs() < 1e-6);
+    assert_eq!(portfolio.positions.len(), 1);
+    let position = &portfolio.positions[0];
+    assert_eq!((position.quantity, position.average_cost), (50.0, 100.1));
+    assert!((position.unrealized_pnl.unwrap() - 495.0).abs() < 1e-6);
+
+    let oversell = trader
+        .place(request(SimSide::Sell, 60.0, SimOrderType::Market, None))
+        .await
+        .unwrap();
+    assert_eq!(oversell.status, SimOrderStatus::Rejected);
+    assert!(oversell.reason.unwrap().contains("50 held"));
+    let overbuy = trader
+        .place(request(SimSide::Buy, 100.0, SimOrderType::Market, None))
+        .await
+        .unwrap();
+    assert_eq!(overbuy.status, SimOrderStatus::Rejected);
+
+    trader
+        .place(request(SimSide::Sell, 50.0, SimOrderType::Market, None))
+        .await
+        .unwrap();
+    let portfolio = trader.portfolio().await.unwrap();
+    assert!(portfolio.positions.is_empty());
+    // Sold at 110 less 10 bps.
+    assert!((portfolio.realized_pnl - (109.89 - 100.1) * 50.0).abs() < 1e-6);
+    assert_eq!(portfolio.commissions, 2.0);
+    assert!((portfolio.equity - portfolio.cash).abs() < 1e-9);
+
+    let filled = emitter
+        .captured()
+        .await
+        .into_iter()
+        .filter(|e| matches!(e, AppEvent::SimOrderFilled { .. }))
+        .count();
+    assert_eq!(filled, 2);
+}
+
+#[tokio::test]
+async fn resting_orders_fill_when_the_tape_reaches_them() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let (trader, prices, _) = trader(db);
+
+    // No quote yet: a limit order still rests.
+    let limit = trader
+        .place(request(SimSide::Buy, 10.0, SimOrderType::Limit, Some(95.0)))
+        .await
+        .unwrap();
+    assert_eq!(limit.status, SimOrderStatus::Working);
+    let stop = trader
+        .place(request(SimSide::Buy, 10.0, SimOrderType::Stop, Some(105.0)))
+        .await
+        .unwrap();
+    let doomed = trader
+        .place(request(SimSide::Buy, 10.0, SimOrderType::Limit, Some(80.0)))
+        .await
+        .unwrap();
+    assert_eq!(
+        trader.cancel(doomed.id).await.unwrap().status,
+        SimOrderStatus::Cancelled
+    );
+    assert!(matches!(
+        trader.cancel(doomed.id).await,
+        Err(PaperTradingError::NotWorking { .. })
+    ));
+
+    prices.set("AAPL", 100.0);
+    assert!(trader.poll().await.unwrap().is_empty());
+
+    // The first bar dips to the limit; the second gaps over the stop
+    // and fills at its open.
+    let fills = trader
+        .replay(
+            "AAPL",
+            &[
+                bar("20260105 10:00:00", 97.0, 98.0, 94.0),
+                bar("20260105 10:01:00", 107.0, 108.0, 106.0),
+            ],
+        )
+        .await
+        .unwrap();
+    assert_eq!(fills.len(), 2);
+    assert_eq!(fills[0].order_id, limit.id);
+    // Slippage never takes a limit past its price.
+    assert_eq!((fills[0].reference_price, fills[0].price), (95.0, 95.0));
+    assert_eq!(fills[0].filled_at, 1_767_607_200);
+    assert_eq!(fills[1].order_id, stop.id);
+    assert_eq!(fills[1].reference_price, 107.0);
+
+    let working = trader
+        .orders(Some(SimOrderStatus::Working), Page::ALL)
+        .await
+        .unwrap();
+    assert!(working.is_empty());
+    assert_eq!(trader.orders(None, Page::ALL).await.unwrap().len(), 3);
+
+    let portfolio = trader.reset(Some(25_000.0)).await.unwrap();
+    assert_eq!(portfolio.cash, 25_000.0);
+    assert!(portfolio.positions.is_empty());
+    assert!(trader.orders(None, Page::ALL).await.unwrap().is_empty());
+}
@@ -0,0 +1,205 @@
+//! Config, orders, fills and the portfolio view of the simulator.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperTradingConfig {
+    /// Cash a new simulated run starts with.
+    #[serde(default = "default_starting_cash")]
+    pub starting_cash: f64,
+    /// Adverse slippage per fill, in basis points of the tape price.
+    #[serde(default = "default_slippage_bps")]
+    pub slippage_bps: f64,
+    #[serde(default = "default_commission_per_share")]
+    pub commission_per_share: f64,
+    /// Least commission charged on one fill.
+    #[serde(default = "default_min_commission")]
+    pub min_commission: f64,
+}
+
+fn default_starting_cash() -> f64 {
+    100_000.0
+}
+
+fn default_slippage_bps() -> f64 {
+    5.0
+}
+
+fn default_commission_per_share() -> f64 {
+    0.005
+}
+
+fn default_min_commission() -> f64 {
+    1.0
+}
+
+impl Default for PaperTradingConfig {
+    fn default() -> Self {
+        Self {
+            starting_cash: default_starting_cash(),
+            slippage_bps: default_slippage_bps(),
+            commission_per_share: default_commission_per_share(),
+            min_commission: default_min_commission(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimSide {
+    Buy,
+    Sell,
+}
+
+impl SimSide {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SimSide::Buy => "buy",
+            SimSide::Sell => "sell",
+        }
+    }
+
+    pub(super) fn parse(s: &str) -> Option<Self> {
+        match s {
+            "buy" => Some(SimSide::Buy),
+            "sell" => Some(SimSide::Sell),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimOrderType {
+    Market,
+    Limit,
+    Stop,
+}
+
+impl SimOrderType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SimOrderType::Market => "market",
+            SimOrderType::Limit => "limit",
+            SimOrderType::Stop => "stop",
+        }
+    }
+
+    pub(super) fn parse(s: &str) -> Option<Self> {
+        match s {
+            "market" => Some(SimOrderType::Market),
+            "limit" => Some(SimOrderType::Limit),
+            "stop" => Some(SimOrderType::Stop),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimOrderStatus {
+    Working,
+    Filled,
+    Cancelled,
+    Rejected,
+}
+
+impl SimOrderStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SimOrderStatus::Working => "working",
+            SimOrderStatus::Filled => "filled",
+            SimOrderStatus::Cancelled => "cancelled",
+            SimOrderStatus::Rejected => "rejected",
+        }
+    }
+
+    pub(super) fn parse(s: &str) -> Option<Self> {
+        match s {
+            "working" => Some(SimOrderStatus::Working),
+            "filled" => Some(SimOrderStatus::Filled),
+            "cancelled" => Some(SimOrderStatus::Cancelled),
+            "rejected" => Some(SimOrderStatus::Rejected),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimOrderRequest {
+    pub symbol: String,
+    pub side: SimSide,
+    pub quantity: f64,
+    pub order_type: SimOrderType,
+    /// Limit or stop price; ignored for market orders.
+    #[serde(default)]
+    pub price: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimOrder {
+    pub id: i64,
+    pub symbol: String,
+    pub side: SimSide,
+    pub quantity: f64,
+    pub order_type: SimOrderType,
+    pub price: Option<f64>,
+    pub status: SimOrderStatus,
+    /// Why the order was rejected.
+    pub reason: Option<String>,
+    /// Unix seconds, as are the other timestamps.
+    pub created_at: i64,
+    /// When it filled, was cancelled or was rejected.
+    pub closed_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimFill {
+    pub id: i64,
+    pub order_id: i64,
+    pub symbol: String,
+    pub side: SimSide,
+    pub quantity: f64,
+    /// What the order paid, slippage included.
+    pub price: f64,
+    /// Tape price the fill was taken from.
+    pub reference_price: f64,
+    pub commission: f64,
+    /// Bar time for a replayed fill.
+    pub filled_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimPosition {
+    pub symbol: String,
+    pub quantity: f64,
+    pub average_cost: f64,
+    /// `None` when the price source couldn't quote the symbol.
+    pub market_price: Option<f64>,
+    pub unrealized_pnl: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimPortfolio {
+    pub starting_cash: f64,
+    pub started_at: i64,
+    pub cash: f64,
+    pub positions: Vec<SimPosition>,
+    /// Closed P&L at average cost, before commissions.
+    pub realized_pnl: f64,
+    pub commissions: f64,
+    /// Cash plus positions at market; an unquoted position counts at
+    /// cost.
+    pub equity: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SimAccount {
+    pub starting_cash: f64,
+    pub started_at: i64,
+}
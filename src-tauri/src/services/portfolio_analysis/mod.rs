@@ -0,0 +1,247 @@
+//! Bulk analysis of the current stock positions.
+//!
+//! [`PortfolioAnalyzer::analyze`] walks the account's open positions,
+//! pulls fundamentals for each through the production provider chain
+//! (manual store → AV cache → AV API, budget-guarded — so a second run
+//! is served from cache), generates bear/base/bull projections and
+//! records them in the projection history, then weights each ticker's
+//! share-price CAGR by its market value into a portfolio-level
+//! expectation.
+//!
+//! Projections use the position's IBKR mark as the current price
+//! (unless an operator price override exists), so the whole run costs
+//! no extra quote requests. Options, other non-stock rows and shorts are
+//! listed in `skipped`; a ticker whose fundamentals fail is reported on
+//! its row and left out of the weighting, which `coverage` makes
+//! visible.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::{Position, ProjectionAssumptions, ScenarioCagr};
+use crate::services::fundamentals_provider::FundamentalsProvider;
+use crate::services::jobs::JobHandle;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::projection_history::ProjectionHistoryStore;
+use crate::services::projection_service::ProjectionService;
+use crate::services::risk_engine::AccountSource;
+use crate::services::{fundamentals_growth, fundamentals_quality};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionAnalysis {
+    pub symbol: String,
+    pub quantity: f64,
+    pub market_value: f64,
+    /// Share of the analysed market value, 0..1. `0` for failed rows.
+    pub weight: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cagr: Option<ScenarioCagr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Market-value-weighted share-price CAGR per scenario, percent.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeightedCagr {
+    pub bear: f64,
+    pub base: f64,
+    pub bull: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedPosition {
+    pub symbol: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioAnalysis {
+    pub account: String,
+    pub positions: Vec<PositionAnalysis>,
+    /// `None` when no position could be projected.
+    pub expected_cagr: Option<WeightedCagr>,
+    /// Market value of long stock positions.
+    pub total_value: f64,
+    /// Market value of the positions that made it into `expected_cagr`.
+    pub analyzed_value: f64,
+    /// `analyzed_value / total_value`; `0` for an empty book.
+    pub coverage: f64,
+    pub skipped: Vec<SkippedPosition>,
+}
+
+pub struct PortfolioAnalyzer {
+    positions: Arc<dyn OpenPositionsSource>,
+    accounts: Arc<dyn AccountSource>,
+    fundamentals: Arc<dyn FundamentalsProvider>,
+    history: Arc<ProjectionHistoryStore>,
+}
+
+impl PortfolioAnalyzer {
+    pub fn new(
+        positions: Arc<dyn OpenPositionsSource>,
+        accounts: Arc<dyn AccountSource>,
+        fundamentals: Arc<dyn FundamentalsProvider>,
+        history: Arc<ProjectionHistoryStore>,
+    ) -> Self {
+        Self {
+            positions,
+            accounts,
+            fundamentals,
+            history,
+        }
+    }
+
+    /// Analyse `account` (default: the first managed account) with
+    /// `assumptions` applied to every ticker. Under a `job`, progress is
+    /// reported per ticker and a cancel stops the walk, returning what
+    /// was analysed so far.
+    pub async fn analyze(
+        &self,
+        account: Option<String>,
+        assumptions: &ProjectionAssumptions,
+        job: Option<&JobHandle>,
+    ) -> Result<PortfolioAnalysis, IbkrError> {
+        let account = match account {
+            Some(a) if !a.trim().is_empty() => a,
+            _ => self.accounts.current_account().await?,
+        };
+        let (stocks, skipped) = partition(self.positions.list_open(&account).await?);
+        info!(
+            "portfolio analysis: {} stock position(s) in {account}, {} skipped",
+            stocks.len(),
+            skipped.len()
+        );
+
+        let mut rows = Vec::with_capacity(stocks.len());
+        for (i, position) in stocks.iter().enumerate() {
+            if let Some(job) = job {
+                if job.is_cancelled() {
+                    info!("portfolio analysis: cancelled after {i} position(s)");
+                    break;
+                }
+                job.progress(i as u32, stocks.len() as u32, Some(position.symbol.clone()))
+                    .await;
+            }
+            rows.push(self.analyze_position(position, assumptions).await);
+        }
+        if let Some(job) = job {
+            job.progress(rows.len() as u32, stocks.len() as u32, None)
+                .await;
+        }
+        Ok(aggregate(account, rows, skipped))
+    }
+
+    async fn analyze_position(
+        &self,
+        position: &Position,
+        assumptions: &ProjectionAssumptions,
+    ) -> PositionAnalysis {
+        let symbol = position.symbol.to_uppercase();
+        let mut row = PositionAnalysis {
+            symbol: symbol.clone(),
+            quantity: position.position,
+            market_value: position.market_value,
+            weight: 0.0,
+            cagr: None,
+            error: None,
+        };
+        let mut data = match self.fundamentals.fetch(&symbol).await {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("portfolio analysis: fundamentals for {symbol} failed: {e}");
+                row.error = Some(e.to_string());
+                return row;
+            }
+        };
+        fundamentals_quality::annotate(&mut data);
+        fundamentals_growth::annotate(&mut data);
+        if position.market_price > 0.0 && !data.overrides.iter().any(|o| o.field == "price") {
+            data.current_metrics.price = Some(position.market_price);
+        }
+        match ProjectionService::generate_projection_results(&data, assumptions) {
+            Ok(results) => {
+                if let Err(e) = self.history.record(&symbol, assumptions, &results).await {
+                    warn!("projection history: failed to record {symbol}: {e}");
+                }
+                row.cagr = Some(results.cagr);
+            }
+            Err(e) => row.error = Some(e.to_string()),
+        }
+        row
+    }
+}
+
+/// Long stock positions to analyse, and everything else with a reason.
+fn partition(positions: Vec<Position>) -> (Vec<Position>, Vec<SkippedPosition>) {
+    let mut stocks = Vec::new();
+    let mut skipped = Vec::new();
+    for p in positions {
+        let reason = if p.contract_type != "STK" {
+            format!("{} position", p.contract_type)
+        } else if p.position < 0.0 {
+            "short position".to_string()
+        } else {
+            stocks.push(p);
+            continue;
+        };
+        skipped.push(SkippedPosition {
+            symbol: p.symbol,
+            reason,
+        });
+    }
+    (stocks, skipped)
+}
+
+fn aggregate(
+    account: String,
+    mut positions: Vec<PositionAnalysis>,
+    skipped: Vec<SkippedPosition>,
+) -> PortfolioAnalysis {
+    let total_value: f64 = positions.iter().map(|p| p.market_value).sum();
+    let analyzed_value: f64 = positions
+        .iter()
+        .filter(|p| p.cagr.is_some())
+        .map(|p| p.market_value)
+        .sum();
+
+    let mut expected = WeightedCagr {
+        bear: 0.0,
+        base: 0.0,
+        bull: 0.0,
+    };
+    for p in positions.iter_mut() {
+        let Some(cagr) = &p.cagr else { continue };
+        if analyzed_value <= 0.0 {
+            break;
+        }
+        p.weight = p.market_value / analyzed_value;
+        expected.bear += p.weight * cagr.bear.share_price;
+        expected.base += p.weight * cagr.base.share_price;
+        expected.bull += p.weight * cagr.bull.share_price;
+    }
+    positions.sort_by(|a, b| b.market_value.total_cmp(&a.market_value));
+
+    PortfolioAnalysis {
+        account,
+        positions,
+        expected_cagr: (analyzed_value > 0.0).then_some(expected),
+        total_value,
+        analyzed_value,
+        coverage: if total_value > 0.0 {
+            analyzed_value / total_value
+        } else {
+            0.0
+        },
+        skipped,
+    }
+}
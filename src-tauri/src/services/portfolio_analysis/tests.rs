@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tempfile::NamedTempFile;
+
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::positions::Position;
+use crate::ibkr::types::ProjectionAssumptions;
+use crate::services::fundamentals_provider::test_support::FakeFundamentalsProvider;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::projection_history::ProjectionHistoryStore;
+use crate::services::projection_service::ProjectionService;
+use crate::services::risk_engine::AccountSource;
+use crate::storage::Db;
+
+use super::PortfolioAnalyzer;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+fn pos(symbol: &str, contract_type: &str, qty: f64, price: f64) -> Position {
+    Position {
+        symbol: symbol.to_string(),
+        contract_type: contract_type.to_string(),
+        position: qty,
+        market_price: price,
+        market_value: qty * price,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn weights_scenario_cagr_by_market_value_and_reports_gaps() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let history = Arc::new(ProjectionHistoryStore::new(db));
+
+    let fundamentals = Arc::new(FakeFundamentalsProvider::new());
+    fundamentals.insert(
+        "NVDA",
+        ProjectionService::generate_mock_fundamental_data("NVDA"),
+    );
+    fundamentals.insert(
+        "AAPL",
+        ProjectionService::generate_mock_fundamental_data("AAPL"),
+    );
+
+    let analyzer = PortfolioAnalyzer::new(
+        Arc::new(StubPositions(vec![
+            pos("NVDA", "STK", 30.0, 100.0),
+            pos("AAPL", "STK", 10.0, 100.0),
+            pos("NOFUND", "STK", 10.0, 100.0),
+            pos("NVDA", "OPT", 1.0, 5.0),
+            pos("TSLA", "STK", -5.0, 200.0),
+        ])),
+        Arc::new(FixedAccount),
+        fundamentals,
+        Arc::clone(&history),
+    );
+    let analysis = analyzer
+        .analyze(None, &ProjectionAssumptions::default(), None)
+        .await
+        .unwrap();
+
+    assert_eq!(analysis.account, "DU1");
+    assert_eq!(analysis.skipped.len(), 2);
+    assert_eq!(analysis.total_value, 5000.0);
+    assert_eq!(analysis.analyzed_value, 4000.0);
+    assert!((analysis.coverage - 0.8).abs() < 1e-9);
+
+    let nvda = &analysis.positions[0];
+    let aapl = analysis
+        .positions
+        .iter()
+        .find(|p| p.symbol == "AAPL")
+        .unwrap();
+    let missing = analysis
+        .positions
+        .iter()
+        .find(|p| p.symbol == "NOFUND")
+        .unwrap();
+    assert_eq!(nvda.symbol, "NVDA");
+    assert!((nvda.weight - 0.75).abs() < 1e-9);
+    assert!(missing.error.is_some() && missing.weight == 0.0);
+
+    let expected = analysis.expected_cagr.unwrap();
+    let want = 0.75 * nvda.cagr.as_ref().unwrap().base.share_price
+        + 0.25 * aapl.cagr.as_ref().unwrap().base.share_price;
+    assert!((expected.base - want).abs() < 1e-9);
+    assert!(expected.bear <= expected.base && expected.base <= expected.bull);
+
+    // Every projected ticker lands in the projection history.
+    assert_eq!(history.latest_per_symbol().await.unwrap().len(), 2);
+}
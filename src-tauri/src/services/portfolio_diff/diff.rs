@@ -0,0 +1,109 @@
+//! Pure comparison of two position sets, keyed by contract.
+
+use std::collections::{BTreeMap, HashMap};
+
+use super::{CurrencyTotals, Holding, HoldingChange};
+
+/// How many movers [`compare`] keeps.
+pub const MOVERS: usize = 5;
+
+pub struct Comparison {
+    pub opened: Vec<HoldingChange>,
+    pub closed: Vec<HoldingChange>,
+    pub resized: Vec<HoldingChange>,
+    pub movers: Vec<HoldingChange>,
+    pub totals: Vec<CurrencyTotals>,
+}
+
+pub fn compare(before: &[Holding], now: &[Holding]) -> Comparison {
+    let before_by: HashMap<&str, &Holding> =
+        before.iter().map(|h| (h.contract.as_str(), h)).collect();
+    let now_by: HashMap<&str, &Holding> = now.iter().map(|h| (h.contract.as_str(), h)).collect();
+
+    let mut opened = Vec::new();
+    let mut resized = Vec::new();
+    let mut held = Vec::new();
+    for h in now {
+        let previous = before_by.get(h.contract.as_str()).copied();
+        let change = change(previous, Some(h));
+        match previous {
+            None => opened.push(change.clone()),
+            Some(b) if (h.quantity - b.quantity).abs() > 1e-9 => resized.push(change.clone()),
+            Some(_) => {}
+        }
+        held.push(change);
+    }
+    let closed: Vec<_> = before
+        .iter()
+        .filter(|b| !now_by.contains_key(b.contract.as_str()))
+        .map(|b| change(Some(b), None))
+        .collect();
+
+    let totals = totals(before, now, &held);
+    let mut movers: Vec<_> = held
+        .into_iter()
+        .filter(|c| c.pnl_change.is_some_and(|p| p != 0.0))
+        .collect();
+    movers.sort_by(|a, b| {
+        let size = |c: &HoldingChange| c.pnl_change.unwrap_or(0.0).abs();
+        size(b).total_cmp(&size(a))
+    });
+    movers.truncate(MOVERS);
+
+    Comparison {
+        opened: by_value(opened),
+        closed: by_value(closed),
+        resized: by_value(resized),
+        movers,
+        totals,
+    }
+}
+
+/// `pnl_change` is `None` for a closed position: its final realized
+/// P&L left the position list with it.
+fn change(before: Option<&Holding>, now: Option<&Holding>) -> HoldingChange {
+    let any = now.or(before).expect("one side is present");
+    HoldingChange {
+        contract: any.contract.clone(),
+        symbol: any.symbol.clone(),
+        currency: any.currency.clone(),
+        quantity_before: before.map_or(0.0, |h| h.quantity),
+        quantity_now: now.map_or(0.0, |h| h.quantity),
+        market_value_before: before.map_or(0.0, |h| h.market_value),
+        market_value_now: now.map_or(0.0, |h| h.market_value),
+        pnl_change: now.map(|h| h.pnl() - before.map_or(0.0, Holding::pnl)),
+    }
+}
+
+/// Market value on both days per currency, and the P&L change of every
+/// position still open.
+fn totals(before: &[Holding], now: &[Holding], held: &[HoldingChange]) -> Vec<CurrencyTotals> {
+    let mut by: BTreeMap<String, CurrencyTotals> = BTreeMap::new();
+    for h in before {
+        currency_entry(&mut by, &h.currency).market_value_before += h.market_value;
+    }
+    for h in now {
+        currency_entry(&mut by, &h.currency).market_value_now += h.market_value;
+    }
+    for c in held {
+        currency_entry(&mut by, &c.currency).pnl_change += c.pnl_change.unwrap_or(0.0);
+    }
+    by.into_values().collect()
+}
+
+fn currency_entry<'a>(
+    by: &'a mut BTreeMap<String, CurrencyTotals>,
+    currency: &str,
+) -> &'a mut CurrencyTotals {
+    by.entry(currency.to_string())
+        .or_insert_with(|| CurrencyTotals {
+            currency: currency.to_string(),
+            ..Default::default()
+        })
+}
+
+fn by_value(mut changes: Vec<HoldingChange>) -> Vec<HoldingChange> {
+    let size = |c: &HoldingChange| c.market_value_now.abs().max(c.market_value_before.abs());
+    changes.sort_by(|a, b| size(b).total_cmp(&size(a)));
+    changes
+}
@@ -0,0 +1,275 @@
+//! What changed in the portfolio since a given day.
+//!
+//! The `position_snapshot` task (`services::scheduler`, 16:15 ET on
+//! trading days by default) stores every open position of the current
+//! account in `position_snapshots`, one set per ET date.
+//! `get_portfolio_diff(date)` compares the live positions against the
+//! newest snapshot on or before `date` and reports the new and closed
+//! positions, size changes, the biggest P&L movers, and market value
+//! per currency on both sides. Without a date it compares against the
+//! last snapshot before today.
+//!
+//! P&L change is unrealized plus realized P&L now minus the same on the
+//! baseline day, so a trim that booked a gain doesn't read as a loss.
+//! A closed position has none: its final realized P&L isn't in the
+//! position list any more.
+//!
+//! `portfolio_diff_email` (off by default, 08:00 ET) runs the diff
+//! against the session before last and returns [`PortfolioDiff::summary`]
+//! as its run summary. With notifications on, that summary is the
+//! morning email.
+
+use std::sync::Arc;
+
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::Position;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::market_calendar;
+
+pub mod diff;
+pub mod store;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Error, Debug)]
+pub enum PortfolioDiffError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+    #[error("no position snapshot on or before {0}")]
+    NoSnapshot(NaiveDate),
+}
+
+/// One position as stored in a snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Holding {
+    /// Ticker for stock; IBKR local symbol otherwise, so each option
+    /// series is its own row.
+    pub contract: String,
+    pub symbol: String,
+    pub contract_type: String,
+    pub currency: String,
+    pub quantity: f64,
+    pub average_cost: f64,
+    pub market_price: f64,
+    pub market_value: f64,
+    pub unrealized_pnl: f64,
+    pub realized_pnl: f64,
+}
+
+impl Holding {
+    pub fn from_position(p: &Position) -> Self {
+        Self {
+            contract: p.contract_key(),
+            symbol: p.symbol.clone(),
+            contract_type: p.contract_type.clone(),
+            currency: p.currency.clone(),
+            quantity: p.position,
+            average_cost: p.average_cost,
+            market_price: p.market_price,
+            market_value: p.market_value,
+            unrealized_pnl: p.unrealized_pnl,
+            realized_pnl: p.realized_pnl,
+        }
+    }
+
+    fn pnl(&self) -> f64 {
+        self.unrealized_pnl + self.realized_pnl
+    }
+}
+
+/// One contract on both sides of the diff; a missing side is zero.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HoldingChange {
+    pub contract: String,
+    pub symbol: String,
+    pub currency: String,
+    pub quantity_before: f64,
+    pub quantity_now: f64,
+    pub market_value_before: f64,
+    pub market_value_now: f64,
+    /// `None` for a closed position.
+    pub pnl_change: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencyTotals {
+    pub currency: String,
+    pub market_value_before: f64,
+    pub market_value_now: f64,
+    /// Sum of `pnl_change` over positions open now.
+    pub pnl_change: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioDiff {
+    pub account: String,
+    /// Date of the snapshot compared against; the newest on or before
+    /// the requested date.
+    pub baseline_date: NaiveDate,
+    /// Unix seconds the live positions were read.
+    pub as_of: i64,
+    /// Largest market value first, as are `closed` and `resized`.
+    pub opened: Vec<HoldingChange>,
+    pub closed: Vec<HoldingChange>,
+    pub resized: Vec<HoldingChange>,
+    /// Up to [`diff::MOVERS`] positions by absolute P&L change.
+    pub movers: Vec<HoldingChange>,
+    pub totals: Vec<CurrencyTotals>,
+}
+
+impl PortfolioDiff {
+    /// A few lines of plain text for the run log and the morning email.
+    pub fn summary(&self) -> String {
+        let names = |changes: &[HoldingChange]| {
+            changes
+                .iter()
+                .map(|c| c.contract.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut lines = vec![format!("{} since {}:", self.account, self.baseline_date)];
+        if self.opened.is_empty() && self.closed.is_empty() && self.resized.is_empty() {
+            lines.push("No positions opened, closed or resized.".to_string());
+        }
+        if !self.opened.is_empty() {
+            lines.push(format!("Opened: {}", names(&self.opened)));
+        }
+        if !self.closed.is_empty() {
+            lines.push(format!("Closed: {}", names(&self.closed)));
+        }
+        if !self.resized.is_empty() {
+            lines.push(format!("Resized: {}", names(&self.resized)));
+        }
+        if !self.movers.is_empty() {
+            let movers = self
+                .movers
+                .iter()
+                .map(|c| {
+                    format!(
+                        "{} {:+.2} {}",
+                        c.contract,
+                        c.pnl_change.unwrap_or(0.0),
+                        c.currency
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(format!("Top movers: {movers}"));
+        }
+        for t in &self.totals {
+            lines.push(format!(
+                "{} market value {:.2} -> {:.2}, P&L {:+.2}",
+                t.currency, t.market_value_before, t.market_value_now, t.pnl_change
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// The `portfolio_diff_email` task; a separate type so the service can
+/// register a second scheduled job.
+pub struct PortfolioDiffEmail(pub Arc<PortfolioDiffService>);
+
+pub struct PortfolioDiffService {
+    db: Arc<Db>,
+    positions: Arc<dyn OpenPositionsSource>,
+    account: Arc<dyn AccountSource>,
+}
+
+impl PortfolioDiffService {
+    pub fn new(
+        db: Arc<Db>,
+        positions: Arc<dyn OpenPositionsSource>,
+        account: Arc<dyn AccountSource>,
+    ) -> Self {
+        Self {
+            db,
+            positions,
+            account,
+        }
+    }
+
+    /// Store today's (ET) positions, replacing an earlier run's.
+    /// Returns how many were stored.
+    pub async fn snapshot(&self) -> Result<usize, PortfolioDiffError> {
+        self.snapshot_on(market_calendar::et_date(Utc::now())).await
+    }
+
+    pub(crate) async fn snapshot_on(&self, date: NaiveDate) -> Result<usize, PortfolioDiffError> {
+        let (account, holdings) = self.live().await?;
+        let count = holdings.len();
+        store::replace(&self.db, &account, date, holdings, Utc::now().timestamp()).await?;
+        Ok(count)
+    }
+
+    /// Live positions against the newest snapshot on or before `since`;
+    /// the last one before today when `since` is `None`.
+    pub async fn diff(
+        &self,
+        since: Option<NaiveDate>,
+    ) -> Result<PortfolioDiff, PortfolioDiffError> {
+        let today = market_calendar::et_date(Utc::now());
+        let since = since.unwrap_or_else(|| today.pred_opt().unwrap_or(today));
+        self.diff_since(since).await
+    }
+
+    /// For a pre-market run: the last session's changes, i.e. live
+    /// positions against the snapshot from the session before it.
+    pub async fn diff_last_session(&self) -> Result<PortfolioDiff, PortfolioDiffError> {
+        let today = market_calendar::et_date(Utc::now());
+        self.diff_since(market_calendar::trading_days_before(today, 2))
+            .await
+    }
+
+    async fn diff_since(&self, since: NaiveDate) -> Result<PortfolioDiff, PortfolioDiffError> {
+        let (account, now) = self.live().await?;
+        let as_of = Utc::now().timestamp();
+        let Some(baseline_date) = store::latest_date(&self.db, &account, since).await? else {
+            return Err(PortfolioDiffError::NoSnapshot(since));
+        };
+        let before = store::load(&self.db, &account, baseline_date).await?;
+        let diff::Comparison {
+            opened,
+            closed,
+            resized,
+            movers,
+            totals,
+        } = diff::compare(&before, &now);
+        Ok(PortfolioDiff {
+            account,
+            baseline_date,
+            as_of,
+            opened,
+            closed,
+            resized,
+            movers,
+            totals,
+        })
+    }
+
+    async fn live(&self) -> Result<(String, Vec<Holding>), PortfolioDiffError> {
+        let account = self.account.current_account().await?;
+        let holdings = self
+            .positions
+            .list_open(&account)
+            .await?
+            .iter()
+            .map(Holding::from_position)
+            .collect();
+        Ok((account, holdings))
+    }
+}
@@ -0,0 +1,127 @@
+//! `position_snapshots` reads and writes.
+
+use chrono::NaiveDate;
+use rusqlite::OptionalExtension;
+
+use super::Holding;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+const DATE_FMT: &str = "%Y-%m-%d";
+
+/// Replace `account`'s snapshot for `date` with `holdings`.
+pub async fn replace(
+    db: &Db,
+    account: &str,
+    date: NaiveDate,
+    holdings: Vec<Holding>,
+    taken_at: i64,
+) -> Result<(), StorageError> {
+    let account = account.to_string();
+    let date = date.format(DATE_FMT).to_string();
+    db.with_conn(move |conn| {
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM position_snapshots WHERE account = ?1 AND snapshot_date = ?2",
+            rusqlite::params![account, date],
+        )?;
+        for h in &holdings {
+            tx.execute(
+                "INSERT OR REPLACE INTO position_snapshots \
+                   (account, snapshot_date, contract, symbol, contract_type, currency, \
+                    quantity, average_cost, market_price, market_value, \
+                    unrealized_pnl, realized_pnl, taken_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                rusqlite::params![
+                    account,
+                    date,
+                    h.contract,
+                    h.symbol,
+                    h.contract_type,
+                    h.currency,
+                    h.quantity,
+                    h.average_cost,
+                    h.market_price,
+                    h.market_value,
+                    h.unrealized_pnl,
+                    h.realized_pnl,
+                    taken_at
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    })
+    .await
+}
+
+/// Every account with a snapshot, sorted.
+pub async fn accounts(db: &Db) -> Result<Vec<String>, StorageError> {
+    db.with_conn(|conn| {
+        let mut stmt =
+            conn.prepare("SELECT DISTINCT account FROM position_snapshots ORDER BY account")?;
+        let rows = stmt
+            .query_map([], |r| r.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(rows)
+    })
+    .await
+}
+
+/// Newest snapshot date for `account` on or before `on_or_before`.
+pub async fn latest_date(
+    db: &Db,
+    account: &str,
+    on_or_before: NaiveDate,
+) -> Result<Option<NaiveDate>, StorageError> {
+    let account = account.to_string();
+    let bound = on_or_before.format(DATE_FMT).to_string();
+    let date: Option<String> = db
+        .with_conn(move |conn| {
+            conn.query_row(
+                "SELECT MAX(snapshot_date) FROM position_snapshots \
+                 WHERE account = ?1 AND snapshot_date <= ?2",
+                rusqlite::params![account, bound],
+                |row| row.get::<_, Option<String>>(0),
+            )
+            .optional()
+            .map(Option::flatten)
+            .map_err(StorageError::from)
+        })
+        .await?;
+    Ok(date.and_then(|d| NaiveDate::parse_from_str(&d, DATE_FMT).ok()))
+}
+
+pub async fn load(db: &Db, account: &str, date: NaiveDate) -> Result<Vec<Holding>, StorageError> {
+    let account = account.to_string();
+    let date = date.format(DATE_FMT).to_string();
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT contract, symbol, contract_type, currency, quantity, average_cost, \
+                    market_price, market_value, unrealized_pnl, realized_pnl \
+             FROM position_snapshots \
+             WHERE account = ?1 AND snapshot_date = ?2 \
+             ORDER BY contract",
+        )?;
+        let rows = stmt.query_map(rusqlite::params![account, date], |row| {
+            Ok(Holding {
+                contract: row.get(0)?,
+                symbol: row.get(1)?,
+                contract_type: row.get(2)?,
+                currency: row.get(3)?,
+                quantity: row.get(4)?,
+                average_cost: row.get(5)?,
+                market_price: row.get(6)?,
+                market_value: row.get(7)?,
+                unrealized_pnl: row.get(8)?,
+                realized_pnl: row.get(9)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
@@ -0,0 +1,137 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use tempfile::NamedTempFile;
+
+use super::*;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+/// Positions the test can swap between snapshot and diff.
+struct LivePositions(Mutex<Vec<Position>>);
+
+#[async_trait]
+impl OpenPositionsSource for LivePositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.lock().unwrap().clone())
+    }
+}
+
+fn pos(symbol: &str, qty: f64, value: f64, unrealized: f64, realized: f64) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: qty,
+        market_value: value,
+        market_price: value / qty,
+        unrealized_pnl: unrealized,
+        realized_pnl: realized,
+        contract_type: "STK".to_string(),
+        currency: "USD".to_string(),
+        ..Default::default()
+    }
+}
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+fn service(tmp: &NamedTempFile) -> (PortfolioDiffService, Arc<LivePositions>) {
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let live = Arc::new(LivePositions(Mutex::new(Vec::new())));
+    let service = PortfolioDiffService::new(
+        db,
+        Arc::clone(&live) as Arc<dyn OpenPositionsSource>,
+        Arc::new(FixedAccount),
+    );
+    (service, live)
+}
+
+#[tokio::test]
+async fn reports_opened_closed_resized_and_movers() {
+    let tmp = NamedTempFile::new().unwrap();
+    let (service, live) = service(&tmp);
+
+    *live.0.lock().unwrap() = vec![
+        pos("AAPL", 100.0, 18_000.0, 1_000.0, 0.0),
+        pos("MSFT", 50.0, 20_000.0, 2_000.0, 0.0),
+        pos("TSLA", 10.0, 2_500.0, -300.0, 0.0),
+    ];
+    assert_eq!(service.snapshot_on(date("2026-10-09")).await.unwrap(), 3);
+
+    *live.0.lock().unwrap() = vec![
+        // Price up: +800 unrealized.
+        pos("AAPL", 100.0, 18_800.0, 1_800.0, 0.0),
+        // Trimmed half at a profit: realized 1,100, unrealized 1,000.
+        pos("MSFT", 25.0, 10_100.0, 1_000.0, 1_100.0),
+        pos("NVDA", 20.0, 3_000.0, -50.0, 0.0),
+    ];
+    let diff = service.diff(Some(date("2026-10-12"))).await.unwrap();
+
+    assert_eq!(diff.baseline_date, date("2026-10-09"));
+    assert_eq!(diff.opened[0].contract, "NVDA");
+    assert_eq!(diff.closed[0].contract, "TSLA");
+    assert_eq!(diff.closed[0].pnl_change, None);
+    assert_eq!(diff.resized.len(), 1);
+    assert_eq!(diff.resized[0].quantity_before, 50.0);
+    assert_eq!(diff.resized[0].quantity_now, 25.0);
+
+    let movers: Vec<_> = diff
+        .movers
+        .iter()
+        .map(|c| (c.contract.as_str(), c.pnl_change.unwrap()))
+        .collect();
+    assert_eq!(
+        movers,
+        vec![("AAPL", 800.0), ("MSFT", 100.0), ("NVDA", -50.0)]
+    );
+
+    assert_eq!(diff.totals.len(), 1);
+    assert_eq!(diff.totals[0].market_value_before, 40_500.0);
+    assert_eq!(diff.totals[0].market_value_now, 31_900.0);
+    assert_eq!(diff.totals[0].pnl_change, 850.0);
+
+    let summary = diff.summary();
+    assert!(summary.starts_with("DU1 since 2026-10-09:\nOpened: NVDA\nClosed: TSLA"));
+    assert!(summary.contains("Top movers: AAPL +800.00 USD"));
+}
+
+#[tokio::test]
+async fn resnapshotting_a_day_replaces_it() {
+    let tmp = NamedTempFile::new().unwrap();
+    let (service, live) = service(&tmp);
+
+    *live.0.lock().unwrap() = vec![
+        pos("AAPL", 100.0, 18_000.0, 0.0, 0.0),
+        pos("TSLA", 10.0, 2_500.0, 0.0, 0.0),
+    ];
+    service.snapshot_on(date("2026-10-09")).await.unwrap();
+    *live.0.lock().unwrap() = vec![pos("AAPL", 100.0, 18_000.0, 0.0, 0.0)];
+    service.snapshot_on(date("2026-10-09")).await.unwrap();
+
+    let diff = service.diff(Some(date("2026-10-09"))).await.unwrap();
+    assert!(diff.closed.is_empty());
+    assert!(diff.movers.is_empty());
+    assert!(diff
+        .summary()
+        .contains("No positions opened, closed or resized."));
+}
+
+#[tokio::test]
+async fn a_date_before_the_first_snapshot_is_an_error() {
+    let tmp = NamedTempFile::new().unwrap();
+    let (service, _live) = service(&tmp);
+    service.snapshot_on(date("2026-10-09")).await.unwrap();
+
+    assert!(matches!(
+        service.diff(Some(date("2026-10-08"))).await,
+        Err(PortfolioDiffError::NoSnapshot(d)) if d == date("2026-10-08")
+    ));
+}
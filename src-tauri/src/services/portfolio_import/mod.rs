@@ -0,0 +1,290 @@
+//! Portfolio import from broker CSV exports.
+//!
+//! Users who hold accounts outside IBKR can drop a positions export
+//! (generic `symbol,quantity,...` CSV, Schwab "Positions" export, or
+//! Fidelity `Portfolio_Positions_*.csv`) into the app. Rows land in
+//! `imported_lots` (V28) and are projected into the same [`Position`]
+//! shape IBKR returns, so portfolio-level views can run over the merged
+//! book via [`merged_positions`].
+//!
+//! Free functions over `&Arc<Db>`, mirroring `research_notes`. Each
+//! import is a full snapshot of the accounts it touches — see the V28
+//! header for why there's no incremental diffing.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ibkr::types::Position;
+use crate::mcp::ibkr_seam::AccountReader;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+pub mod parser;
+
+#[cfg(test)]
+mod tests;
+
+/// Supported export flavours. Stored as `imported_lots.source`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrokerFormat {
+    Generic,
+    Schwab,
+    Fidelity,
+}
+
+impl BrokerFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BrokerFormat::Generic => "generic",
+            BrokerFormat::Schwab => "schwab",
+            BrokerFormat::Fidelity => "fidelity",
+        }
+    }
+}
+
+/// One parsed CSV row before it's bound to an account label.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedLot {
+    /// Per-row account from the export (Fidelity's `Account Number`).
+    /// `None` falls back to the label passed to [`import_csv`].
+    pub account: Option<String>,
+    pub symbol: String,
+    pub quantity: f64,
+    pub cost_basis_total: Option<f64>,
+    pub last_price: Option<f64>,
+    pub acquired_date: Option<String>,
+    pub currency: Option<String>,
+}
+
+/// A persisted `imported_lots` row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedLot {
+    pub id: i64,
+    pub account: String,
+    pub source: String,
+    pub symbol: String,
+    pub quantity: f64,
+    pub cost_basis_total: Option<f64>,
+    pub last_price: Option<f64>,
+    pub acquired_date: Option<String>,
+    pub currency: String,
+    pub imported_at_unix: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub format: BrokerFormat,
+    pub accounts: Vec<String>,
+    pub lots_imported: usize,
+}
+
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("account label must be non-empty")]
+    EmptyAccount,
+    #[error("no header row with symbol and quantity columns found")]
+    MissingHeader,
+    #[error("no holdings found in export")]
+    NoRows,
+    #[error("csv: {0}")]
+    Csv(String),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// Parse `text` and replace every stored lot for the accounts it
+/// mentions. `format = None` sniffs the flavour via
+/// [`parser::detect_format`].
+pub async fn import_csv(
+    db: &Arc<Db>,
+    account_label: &str,
+    text: &str,
+    format: Option<BrokerFormat>,
+) -> Result<ImportSummary, ImportError> {
+    let label = account_label.trim();
+    if label.is_empty() {
+        return Err(ImportError::EmptyAccount);
+    }
+    let format = format.unwrap_or_else(|| parser::detect_format(text));
+    let parsed = parser::parse(text, format)?;
+    if parsed.is_empty() {
+        return Err(ImportError::NoRows);
+    }
+
+    let lots: Vec<(String, ParsedLot)> = parsed
+        .into_iter()
+        .map(|lot| {
+            (
+                lot.account.clone().unwrap_or_else(|| label.to_string()),
+                lot,
+            )
+        })
+        .collect();
+    let mut accounts: Vec<String> = lots.iter().map(|(a, _)| a.clone()).collect();
+    accounts.sort();
+    accounts.dedup();
+    let lots_imported = lots.len();
+    let source = format.as_str();
+    let now = Utc::now().timestamp();
+    let accounts_for_db = accounts.clone();
+
+    db.with_conn(move |conn| {
+        let tx = conn.transaction()?;
+        for account in &accounts_for_db {
+            tx.execute("DELETE FROM imported_lots WHERE account = ?1", [account])?;
+        }
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO imported_lots \
+                 (account, source, symbol, quantity, cost_basis_total, last_price, \
+                  acquired_date, currency, imported_at_unix) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            )?;
+            for (account, lot) in &lots {
+                stmt.execute(rusqlite::params![
+                    account,
+                    source,
+                    lot.symbol,
+                    lot.quantity,
+                    lot.cost_basis_total,
+                    lot.last_price,
+                    lot.acquired_date,
+                    lot.currency.as_deref().unwrap_or("USD"),
+                    now,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    })
+    .await?;
+
+    Ok(ImportSummary {
+        format,
+        accounts,
+        lots_imported,
+    })
+}
+
+/// Every stored lot, ordered by account then symbol.
+pub async fn list_lots(db: &Arc<Db>) -> Result<Vec<ImportedLot>, ImportError> {
+    let rows = db
+        .with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, account, source, symbol, quantity, cost_basis_total, last_price, \
+                        acquired_date, currency, imported_at_unix \
+                 FROM imported_lots ORDER BY account, symbol, id",
+            )?;
+            let rows = stmt
+                .query_map([], |r| {
+                    Ok(ImportedLot {
+                        id: r.get(0)?,
+                        account: r.get(1)?,
+                        source: r.get(2)?,
+                        symbol: r.get(3)?,
+                        quantity: r.get(4)?,
+                        cost_basis_total: r.get(5)?,
+                        last_price: r.get(6)?,
+                        acquired_date: r.get(7)?,
+                        currency: r.get(8)?,
+                        imported_at_unix: r.get(9)?,
+                    })
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(rows)
+        })
+        .await?;
+    Ok(rows)
+}
+
+/// Drop every lot for `account`. Returns the number of rows removed.
+pub async fn clear_account(db: &Arc<Db>, account: &str) -> Result<usize, ImportError> {
+    let account = account.trim().to_string();
+    let removed = db
+        .with_conn(move |conn| {
+            Ok(conn.execute("DELETE FROM imported_lots WHERE account = ?1", [account])?)
+        })
+        .await?;
+    Ok(removed)
+}
+
+/// Collapse lots into one [`Position`] per `(account, symbol)`. Cost is
+/// quantity-weighted; lots with no cost basis contribute quantity only.
+/// `market_price` is the export's last price (0 when absent).
+pub fn lots_to_positions(lots: &[ImportedLot]) -> Vec<Position> {
+    let mut grouped: BTreeMap<(&str, &str), Vec<&ImportedLot>> = BTreeMap::new();
+    for lot in lots {
+        grouped
+            .entry((lot.account.as_str(), lot.symbol.as_str()))
+            .or_default()
+            .push(lot);
+    }
+    grouped
+        .into_iter()
+        .map(|((account, symbol), group)| {
+            let quantity: f64 = group.iter().map(|l| l.quantity).sum();
+            let costed_qty: f64 = group
+                .iter()
+                .filter(|l| l.cost_basis_total.is_some())
+                .map(|l| l.quantity)
+                .sum();
+            let cost_total: f64 = group.iter().filter_map(|l| l.cost_basis_total).sum();
+            let average_cost = if costed_qty != 0.0 {
+                cost_total / costed_qty
+            } else {
+                0.0
+            };
+            let market_price = group.iter().find_map(|l| l.last_price).unwrap_or(0.0);
+            let market_value = quantity * market_price;
+            Position {
+                account: account.to_string(),
+                symbol: symbol.to_string(),
+                position: quantity,
+                average_cost,
+                market_price,
+                market_value,
+                unrealized_pnl: if market_price > 0.0 && costed_qty != 0.0 {
+                    market_value - average_cost * quantity
+                } else {
+                    0.0
+                },
+                realized_pnl: 0.0,
+                contract_type: "STK".to_string(),
+                currency: group[0].currency.clone(),
+                exchange: String::new(),
+                local_symbol: symbol.to_string(),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+/// Live IBKR positions for every managed account followed by imported
+/// positions. IBKR failures (disconnected gateway) degrade to the
+/// imported set alone — an offline merged view is still useful.
+pub async fn merged_positions(
+    db: &Arc<Db>,
+    reader: &dyn AccountReader,
+) -> Result<Vec<Position>, ImportError> {
+    let mut out = Vec::new();
+    match reader.list_accounts().await {
+        Ok(accounts) => {
+            for account in accounts {
+                match reader.get_positions(&account).await {
+                    Ok(rows) => out.extend(rows),
+                    Err(e) => tracing::warn!("merged_positions: {account} positions failed: {e}"),
+                }
+            }
+        }
+        Err(e) => tracing::debug!("merged_positions: IBKR unavailable, imported only: {e}"),
+    }
+    out.extend(lots_to_positions(&list_lots(db).await?));
+    Ok(out)
+}
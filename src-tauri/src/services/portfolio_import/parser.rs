@@ -0,0 +1,177 @@
+//! Header-driven CSV parsing for the supported export formats.
+//!
+//! All three formats are located by their header row rather than by
+//! fixed line offsets: Schwab prefixes a `"Positions for account ..."`
+//! title line and Fidelity appends a quoted disclaimer footer, and both
+//! have shifted those between export versions. Rows that don't resolve
+//! to a symbol + numeric quantity (cash sweeps, `Account Total`,
+//! `Pending Activity`, disclaimer text) are skipped, not errors.
+
+use chrono::NaiveDate;
+
+use super::{BrokerFormat, ImportError, ParsedLot};
+
+/// Column aliases, matched case-insensitively against trimmed headers.
+/// First match wins, so more specific names go first.
+const SYMBOL: &[&str] = &["symbol", "ticker"];
+const QUANTITY: &[&str] = &["quantity", "shares", "qty"];
+const COST_TOTAL: &[&str] = &["cost basis total", "cost basis", "total cost"];
+const COST_PER_SHARE: &[&str] = &[
+    "average cost basis",
+    "average cost",
+    "avg cost",
+    "cost per share",
+];
+const LAST_PRICE: &[&str] = &["last price", "price"];
+const ACQUIRED: &[&str] = &["acquired date", "acquired", "date acquired", "open date"];
+const CURRENCY: &[&str] = &["currency"];
+const ACCOUNT: &[&str] = &["account number", "account"];
+
+/// Symbol prefixes / names that are cash or summary rows, not holdings.
+const NON_HOLDING_ROWS: &[&str] = &[
+    "cash & cash investments",
+    "account total",
+    "pending activity",
+];
+
+struct Columns {
+    symbol: usize,
+    quantity: usize,
+    cost_total: Option<usize>,
+    cost_per_share: Option<usize>,
+    last_price: Option<usize>,
+    acquired: Option<usize>,
+    currency: Option<usize>,
+    account: Option<usize>,
+}
+
+impl Columns {
+    fn resolve(header: &csv::StringRecord) -> Option<Self> {
+        let find = |aliases: &[&str]| {
+            aliases.iter().find_map(|alias| {
+                header
+                    .iter()
+                    .position(|h| h.trim().eq_ignore_ascii_case(alias))
+            })
+        };
+        Some(Self {
+            symbol: find(SYMBOL)?,
+            quantity: find(QUANTITY)?,
+            cost_total: find(COST_TOTAL),
+            cost_per_share: find(COST_PER_SHARE),
+            last_price: find(LAST_PRICE),
+            acquired: find(ACQUIRED),
+            currency: find(CURRENCY),
+            account: find(ACCOUNT),
+        })
+    }
+}
+
+/// Sniff the export flavour from its first few lines. Falls back to
+/// [`BrokerFormat::Generic`], which accepts any header set that carries
+/// a symbol and a quantity column.
+pub fn detect_format(text: &str) -> BrokerFormat {
+    let head: String = text
+        .lines()
+        .take(5)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_lowercase();
+    if head.contains("positions for account") || head.contains("reinvest dividends?") {
+        BrokerFormat::Schwab
+    } else if head.contains("account number") && head.contains("average cost basis") {
+        BrokerFormat::Fidelity
+    } else {
+        BrokerFormat::Generic
+    }
+}
+
+/// Parse `text` into lots. `format` only affects cost-basis precedence
+/// (Schwab / Fidelity totals are authoritative; generic files may carry
+/// either a total or a per-share figure).
+pub fn parse(text: &str, format: BrokerFormat) -> Result<Vec<ParsedLot>, ImportError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+
+    let mut columns: Option<Columns> = None;
+    let mut lots = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| ImportError::Csv(e.to_string()))?;
+        let Some(cols) = columns.as_ref() else {
+            columns = Columns::resolve(&record);
+            continue;
+        };
+        if let Some(lot) = parse_row(&record, cols, format) {
+            lots.push(lot);
+        }
+    }
+    if columns.is_none() {
+        return Err(ImportError::MissingHeader);
+    }
+    Ok(lots)
+}
+
+fn parse_row(
+    record: &csv::StringRecord,
+    cols: &Columns,
+    format: BrokerFormat,
+) -> Option<ParsedLot> {
+    let field = |idx: Option<usize>| idx.and_then(|i| record.get(i)).filter(|s| !s.is_empty());
+
+    let raw_symbol = field(Some(cols.symbol))?;
+    let lowered = raw_symbol.to_lowercase();
+    // Fidelity marks core money-market positions with a `**` suffix.
+    if raw_symbol.ends_with("**") || NON_HOLDING_ROWS.iter().any(|n| lowered.starts_with(n)) {
+        return None;
+    }
+    let quantity = field(Some(cols.quantity)).and_then(parse_number)?;
+    if quantity == 0.0 {
+        return None;
+    }
+
+    let cost_total = field(cols.cost_total).and_then(parse_number);
+    let cost_per_share = field(cols.cost_per_share).and_then(parse_number);
+    let cost_basis_total = match format {
+        BrokerFormat::Schwab | BrokerFormat::Fidelity => {
+            cost_total.or(cost_per_share.map(|c| c * quantity))
+        }
+        BrokerFormat::Generic => cost_per_share.map(|c| c * quantity).or(cost_total),
+    };
+
+    Some(ParsedLot {
+        account: field(cols.account).map(str::to_string),
+        symbol: raw_symbol.to_uppercase(),
+        quantity,
+        cost_basis_total,
+        last_price: field(cols.last_price).and_then(parse_number),
+        acquired_date: field(cols.acquired).and_then(parse_date),
+        currency: field(cols.currency).map(str::to_uppercase),
+    })
+}
+
+/// Broker-formatted number → `f64`. Handles `$1,234.50`, `+12.5`,
+/// `(450.00)` accounting negatives and `--` / `n/a` placeholders.
+pub(crate) fn parse_number(raw: &str) -> Option<f64> {
+    let trimmed = raw.trim();
+    let (negative, body) = match trimmed.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        Some(inner) => (true, inner),
+        None => (false, trimmed),
+    };
+    let cleaned: String = body
+        .chars()
+        .filter(|c| !matches!(c, '$' | ',' | '+' | ' ' | '%'))
+        .collect();
+    let value: f64 = cleaned.parse().ok().filter(|v: &f64| v.is_finite())?;
+    Some(if negative { -value } else { value })
+}
+
+/// Accepts ISO (`2024-03-15`) and US (`03/15/2024`) dates; normalises to ISO.
+fn parse_date(raw: &str) -> Option<String> {
+    ["%Y-%m-%d", "%m/%d/%Y", "%Y/%m/%d"]
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(raw.trim(), fmt).ok())
+        .map(|d| d.format("%Y-%m-%d").to_string())
+}
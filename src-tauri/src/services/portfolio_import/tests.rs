@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use tempfile::NamedTempFile;
+
+use super::parser::{detect_format, parse, parse_number};
+use super::*;
+use crate::ibkr::mocks::MockIbkrClient;
+use crate::storage::Db;
+
+fn open_db() -> (NamedTempFile, Arc<Db>) {
+    let tmp = NamedTempFile::new().expect("tempfile");
+    let db = Db::open(tmp.path()).expect("open db");
+    (tmp, Arc::new(db))
+}
+
+const SCHWAB: &str = r#""Positions for account Individual ...123 as of 04:10 PM ET, 2026/10/13"
+
+"Symbol","Description","Quantity","Price","Price Change %","Market Value","Cost Basis","Reinvest Dividends?","Security Type"
+"AAPL","APPLE INC","10","$230.00","1.2%","$2,300.00","$1,500.00","Yes","Equity"
+"VTI","VANGUARD TOTAL STOCK","5","$280.50","0.3%","$1,402.50","--","No","ETFs & Closed End Funds"
+"Cash & Cash Investments","--","--","--","--","$812.20","--","--","Cash and Money Market"
+"Account Total","--","--","--","--","$4,514.70","$1,500.00","--","--"
+"#;
+
+const FIDELITY: &str = r#"Account Number,Account Name,Symbol,Description,Quantity,Last Price,Current Value,Cost Basis Total,Average Cost Basis,Type
+Z12345678,ROTH IRA,SPAXX**,HELD IN MONEY MARKET,,,$1200.00,,,Cash
+Z12345678,ROTH IRA,MSFT,MICROSOFT CORP,4,$410.00,$1640.00,$1000.00,$250.00,Cash
+X98765432,BROKERAGE,MSFT,MICROSOFT CORP,2,$410.00,$820.00,$700.00,$350.00,Margin
+Pending Activity,,,,,,$-15.00,,,
+
+"The data and information in this spreadsheet is provided to you solely for your use."
+"#;
+
+const GENERIC: &str =
+    "symbol,shares,avg cost,acquired date\nnvda,3,100,03/15/2024\nNVDA,1,140,2025-01-02\n";
+
+#[test]
+fn detects_formats_from_header() {
+    assert_eq!(detect_format(SCHWAB), BrokerFormat::Schwab);
+    assert_eq!(detect_format(FIDELITY), BrokerFormat::Fidelity);
+    assert_eq!(detect_format(GENERIC), BrokerFormat::Generic);
+}
+
+#[test]
+fn parse_number_handles_broker_formatting() {
+    assert_eq!(parse_number("$1,234.50"), Some(1234.5));
+    assert_eq!(parse_number("(450.00)"), Some(-450.0));
+    assert_eq!(parse_number("+12.5%"), Some(12.5));
+    assert_eq!(parse_number("--"), None);
+}
+
+#[test]
+fn schwab_skips_preamble_cash_and_totals() {
+    let lots = parse(SCHWAB, BrokerFormat::Schwab).unwrap();
+    assert_eq!(lots.len(), 2);
+    assert_eq!(lots[0].symbol, "AAPL");
+    assert_eq!(lots[0].cost_basis_total, Some(1500.0));
+    assert_eq!(lots[0].last_price, Some(230.0));
+    assert_eq!(lots[1].cost_basis_total, None);
+}
+
+#[test]
+fn fidelity_keeps_per_row_accounts_and_drops_core_cash() {
+    let lots = parse(FIDELITY, BrokerFormat::Fidelity).unwrap();
+    assert_eq!(lots.len(), 2);
+    assert_eq!(lots[0].account.as_deref(), Some("Z12345678"));
+    assert_eq!(lots[1].account.as_deref(), Some("X98765432"));
+    assert_eq!(lots[1].cost_basis_total, Some(700.0));
+}
+
+#[test]
+fn missing_header_is_an_error() {
+    assert!(matches!(
+        parse("foo,bar\n1,2\n", BrokerFormat::Generic),
+        Err(ImportError::MissingHeader)
+    ));
+}
+
+#[tokio::test]
+async fn reimport_replaces_account_snapshot_and_aggregates_lots() {
+    let (_tmp, db) = open_db();
+    let summary = import_csv(&db, "Schwab IRA", GENERIC, None).await.unwrap();
+    assert_eq!(summary.format, BrokerFormat::Generic);
+    assert_eq!(summary.lots_imported, 2);
+
+    let lots = list_lots(&db).await.unwrap();
+    assert_eq!(lots.len(), 2);
+    assert_eq!(lots[0].acquired_date.as_deref(), Some("2024-03-15"));
+
+    let positions = lots_to_positions(&lots);
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions[0].position, 4.0);
+    assert!((positions[0].average_cost - 110.0).abs() < 1e-9);
+
+    // Second import of the same account is a full replacement.
+    import_csv(&db, "Schwab IRA", "symbol,quantity\nAMD,7\n", None)
+        .await
+        .unwrap();
+    let lots = list_lots(&db).await.unwrap();
+    assert_eq!(lots.len(), 1);
+    assert_eq!(lots[0].symbol, "AMD");
+
+    assert_eq!(clear_account(&db, "Schwab IRA").await.unwrap(), 1);
+    assert!(list_lots(&db).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn merged_positions_falls_back_to_imported_when_disconnected() {
+    let (_tmp, db) = open_db();
+    import_csv(&db, "ignored", FIDELITY, None).await.unwrap();
+    let mock = MockIbkrClient::new();
+
+    let merged = merged_positions(&db, &mock).await.unwrap();
+    assert_eq!(merged.len(), 2);
+
+    mock.set_connected(true).await;
+    mock.set_positions(vec![Position {
+        account: "DU123456".into(),
+        symbol: "AAPL".into(),
+        position: 1.0,
+        ..Default::default()
+    }])
+    .await;
+    let merged = merged_positions(&db, &mock).await.unwrap();
+    assert_eq!(merged.len(), 3);
+    assert_eq!(merged[0].account, "DU123456");
+}
@@ -0,0 +1,108 @@
+//! Beta-weighted exposure: each stock position's market value scaled
+//! by its beta to a benchmark (SPY), so a book can be read as "this
+//! many dollars of SPY". Pure, like `exposure`; the daily closes come
+//! from the caller.
+//!
+//! Beta is cov(asset, benchmark) / var(benchmark) over simple daily
+//! returns on the dates both series share, using the most recent
+//! [`LOOKBACK_RETURNS`]. Fewer than [`MIN_OVERLAP`] shared returns
+//! leaves the position unmeasured. Options aren't delta-adjusted here
+//! and are listed as unmeasured too.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ibkr::types::positions::Position;
+
+pub const LOOKBACK_RETURNS: usize = 252;
+pub const MIN_OVERLAP: usize = 60;
+
+/// `(date, close)`, ascending.
+pub type Closes = [(String, f64)];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionBeta {
+    pub symbol: String,
+    pub market_value: f64,
+    pub beta: f64,
+    pub beta_dollars: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BetaExposure {
+    pub benchmark: String,
+    pub positions: Vec<PositionBeta>,
+    /// Sum of measured market values (shorts negative).
+    pub net_market_value: f64,
+    /// Sum of `beta_dollars`.
+    pub beta_weighted: f64,
+    /// Positions left out: options, and stocks without enough history.
+    pub unmeasured: Vec<String>,
+}
+
+/// `None` below [`MIN_OVERLAP`] shared returns or with a flat benchmark.
+pub fn beta(asset: &Closes, benchmark: &Closes) -> Option<f64> {
+    let asset = returns_by_date(asset);
+    let pairs: Vec<(f64, f64)> = returns(benchmark)
+        .into_iter()
+        .filter_map(|(date, b)| asset.get(date).map(|a| (*a, b)))
+        .collect();
+    let pairs = &pairs[pairs.len().saturating_sub(LOOKBACK_RETURNS)..];
+    if pairs.len() < MIN_OVERLAP {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_a = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_b = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+    let cov = pairs
+        .iter()
+        .map(|(a, b)| (a - mean_a) * (b - mean_b))
+        .sum::<f64>();
+    let var = pairs.iter().map(|(_, b)| (b - mean_b).powi(2)).sum::<f64>();
+    (var > 0.0).then(|| cov / var)
+}
+
+/// Exposure of `positions` given each symbol's beta (`None` =
+/// unmeasured).
+pub fn beta_weighted(benchmark: &str, positions: &[(Position, Option<f64>)]) -> BetaExposure {
+    let mut out = BetaExposure {
+        benchmark: benchmark.to_string(),
+        ..Default::default()
+    };
+    for (position, beta) in positions {
+        match beta {
+            Some(beta) if position.contract_type == "STK" => {
+                let row = PositionBeta {
+                    symbol: position.symbol.clone(),
+                    market_value: position.market_value,
+                    beta: *beta,
+                    beta_dollars: position.market_value * beta,
+                };
+                out.net_market_value += row.market_value;
+                out.beta_weighted += row.beta_dollars;
+                out.positions.push(row);
+            }
+            _ => out.unmeasured.push(if position.local_symbol.is_empty() {
+                position.symbol.clone()
+            } else {
+                position.local_symbol.clone()
+            }),
+        }
+    }
+    out
+}
+
+fn returns(closes: &Closes) -> Vec<(&str, f64)> {
+    closes
+        .windows(2)
+        .filter(|w| w[0].1 > 0.0)
+        .map(|w| (w[1].0.as_str(), w[1].1 / w[0].1 - 1.0))
+        .collect()
+}
+
+fn returns_by_date(closes: &Closes) -> HashMap<&str, f64> {
+    returns(closes).into_iter().collect()
+}
@@ -51,6 +51,7 @@ use crate::services::risk_engine::{
 use crate::storage::error::StorageError;
 use crate::storage::Db;
 
+pub mod beta;
 mod concentration_gate;
 mod exposure;
 mod factors;
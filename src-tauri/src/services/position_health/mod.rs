@@ -0,0 +1,324 @@
+//! Position health: a 0–100 score per open stock position, with a
+//! traffic light, for the dashboard.
+//!
+//! Three components, each scored from 0 (bad) to 100 (fine), averaged:
+//!
+//!   - stop distance: how far the price is above the stop (below it,
+//!     for a short), in ATRs. `position_health.stop_atr` or more scores
+//!     100; at or through the stop, 0. The stop is the position plan's
+//!     (`services::position_plans`), else the open bracket's
+//!     (`bracket_groups`). With neither, the component is left out and
+//!     `stop_source` is `None`.
+//!   - drawdown: how far the price is below the average cost (above it,
+//!     for a short), in ATRs. In profit scores 100;
+//!     `position_health.drawdown_atr` or more under water, 0.
+//!   - weight: share of the account's gross position value. Nothing
+//!     scores 100; `position_health.max_weight_pct` or more, 0.
+//!
+//! ATR is Wilder's over `position_health.atr_period` daily bars from the
+//! cached `HistoricalDataService`; without it both ATR components are
+//! left out. The light is green from `green_min` up, red below
+//! `red_below`, amber between.
+//!
+//! Nothing is stored: `get_position_health` recomputes the report from
+//! the live positions on every dashboard refresh, and the HTTP API
+//! serves the same report at `/v1/position-health` for external
+//! dashboards.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::config::AppConfig;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::Position;
+use crate::services::historical_data_service::{DailyBars, HistoricalDataService};
+use crate::services::portfolio_risk::{PortfolioRiskError, SnapshotStore};
+use crate::services::position_plans::{EnrichedPosition, PositionPlanError, PositionPlanService};
+use crate::services::risk_engine::AccountSource;
+use crate::storage::Db;
+use crate::strategies::indicators;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionHealthConfig {
+    /// Daily bars in the ATR.
+    #[serde(default = "default_atr_period")]
+    pub atr_period: u32,
+    /// Stop distance, in ATRs, that scores full marks.
+    #[serde(default = "default_stop_atr")]
+    pub stop_atr: f64,
+    /// Drawdown from cost, in ATRs, that scores zero.
+    #[serde(default = "default_drawdown_atr")]
+    pub drawdown_atr: f64,
+    /// Weight, in percent of gross position value, that scores zero.
+    #[serde(default = "default_max_weight_pct")]
+    pub max_weight_pct: f64,
+    /// Lowest green score.
+    #[serde(default = "default_green_min")]
+    pub green_min: f64,
+    /// Scores below this are red.
+    #[serde(default = "default_red_below")]
+    pub red_below: f64,
+}
+
+fn default_atr_period() -> u32 {
+    14
+}
+
+fn default_stop_atr() -> f64 {
+    3.0
+}
+
+fn default_drawdown_atr() -> f64 {
+    3.0
+}
+
+fn default_max_weight_pct() -> f64 {
+    25.0
+}
+
+fn default_green_min() -> f64 {
+    70.0
+}
+
+fn default_red_below() -> f64 {
+    40.0
+}
+
+impl Default for PositionHealthConfig {
+    fn default() -> Self {
+        Self {
+            atr_period: default_atr_period(),
+            stop_atr: default_stop_atr(),
+            drawdown_atr: default_drawdown_atr(),
+            max_weight_pct: default_max_weight_pct(),
+            green_min: default_green_min(),
+            red_below: default_red_below(),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum PositionHealthError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+    #[error("positions: {0}")]
+    Positions(#[from] PositionPlanError),
+    #[error("bracket stops: {0}")]
+    Brackets(#[from] PortfolioRiskError),
+}
+
+/// Trait seam for the volatility yardstick. Production is the cached
+/// [`HistoricalDataService`]; tests fix it per symbol.
+#[async_trait]
+pub trait AtrSource: Send + Sync {
+    /// Wilder's ATR over `period` daily bars; `None` without enough
+    /// history.
+    async fn daily_atr(&self, symbol: &str, period: usize) -> Result<Option<f64>, IbkrError>;
+}
+
+#[async_trait]
+impl AtrSource for HistoricalDataService {
+    async fn daily_atr(&self, symbol: &str, period: usize) -> Result<Option<f64>, IbkrError> {
+        let bars = self.daily_bars(symbol, period + 1).await?;
+        Ok(indicators::atr(&bars, period))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthLight {
+    Green,
+    Amber,
+    Red,
+}
+
+/// Where a position's stop came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopSource {
+    Plan,
+    Bracket,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionHealth {
+    pub symbol: String,
+    pub quantity: f64,
+    pub price: f64,
+    pub average_cost: f64,
+    pub stop_price: Option<f64>,
+    pub stop_source: Option<StopSource>,
+    pub atr: Option<f64>,
+    /// Price to stop, in ATRs; negative once through it.
+    pub stop_distance_atr: Option<f64>,
+    /// Cost to price against the position, in ATRs; negative in profit.
+    pub drawdown_atr: Option<f64>,
+    /// Percent of the account's gross position value.
+    pub weight_pct: f64,
+    pub stop_score: Option<f64>,
+    pub drawdown_score: Option<f64>,
+    pub weight_score: f64,
+    /// Mean of the component scores present, 0–100.
+    pub score: f64,
+    pub light: HealthLight,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionHealthReport {
+    pub account: String,
+    pub at: i64,
+    /// Open stock positions, worst score first.
+    pub positions: Vec<PositionHealth>,
+}
+
+pub struct PositionHealthService {
+    account: Arc<dyn AccountSource>,
+    plans: Arc<PositionPlanService>,
+    brackets: SnapshotStore,
+    atr: Arc<dyn AtrSource>,
+    config: Arc<RwLock<AppConfig>>,
+}
+
+impl PositionHealthService {
+    pub fn new(
+        db: Arc<Db>,
+        account: Arc<dyn AccountSource>,
+        plans: Arc<PositionPlanService>,
+        atr: Arc<dyn AtrSource>,
+        config: Arc<RwLock<AppConfig>>,
+    ) -> Self {
+        Self {
+            account,
+            plans,
+            brackets: SnapshotStore::new(db),
+            atr,
+            config,
+        }
+    }
+
+    /// Score the open stock positions of `account` (the current account
+    /// when `None`).
+    pub async fn report(
+        &self,
+        account: Option<String>,
+    ) -> Result<PositionHealthReport, PositionHealthError> {
+        let config = self.config.read().await.position_health.clone();
+        let account = match account.filter(|a| !a.trim().is_empty()) {
+            Some(a) => a.trim().to_string(),
+            None => self.account.current_account().await?,
+        };
+        let positions = self.plans.enriched(Some(account.clone())).await?;
+        let bracket_stops = self.brackets.open_bracket_stops(&account).await?;
+        let gross: f64 = positions
+            .iter()
+            .map(|p| p.position.market_value.abs())
+            .sum();
+
+        let mut scored = Vec::new();
+        for EnrichedPosition { position, plan } in positions {
+            if position.contract_type != "STK" {
+                continue;
+            }
+            let (stop_price, stop_source) = match plan.and_then(|p| p.stop_price) {
+                Some(stop) => (Some(stop), Some(StopSource::Plan)),
+                None => match bracket_stops.get(&position.symbol.to_uppercase()) {
+                    Some(cents) => (Some(*cents as f64 / 100.0), Some(StopSource::Bracket)),
+                    None => (None, None),
+                },
+            };
+            let atr = match self
+                .atr
+                .daily_atr(&position.symbol, config.atr_period as usize)
+                .await
+            {
+                Ok(atr) => atr.filter(|a| a.is_finite() && *a > 0.0),
+                Err(e) => {
+                    warn!("position_health: ATR for {} failed: {e}", position.symbol);
+                    None
+                }
+            };
+            let weight_pct = if gross > 0.0 {
+                position.market_value.abs() / gross * 100.0
+            } else {
+                0.0
+            };
+            scored.push(assess(
+                &position,
+                stop_price,
+                stop_source,
+                atr,
+                weight_pct,
+                &config,
+            ));
+        }
+        scored.sort_by(|a, b| a.score.total_cmp(&b.score));
+        Ok(PositionHealthReport {
+            account,
+            at: Utc::now().timestamp(),
+            positions: scored,
+        })
+    }
+}
+
+fn assess(
+    position: &Position,
+    stop_price: Option<f64>,
+    stop_source: Option<StopSource>,
+    atr: Option<f64>,
+    weight_pct: f64,
+    config: &PositionHealthConfig,
+) -> PositionHealth {
+    let price = position.market_price;
+    // Distances are measured in the position's favour: up for a long.
+    let side = if position.position >= 0.0 { 1.0 } else { -1.0 };
+    let in_atr = |diff: f64| atr.map(|a| side * diff / a);
+    let stop_distance_atr = stop_price.and_then(|stop| in_atr(price - stop));
+    let drawdown_atr = in_atr(position.average_cost - price);
+
+    // 0 at or below zero, 100 at `full` and above.
+    let ramp = |x: f64, full: f64| (x / full).clamp(0.0, 1.0) * 100.0;
+    let stop_score = stop_distance_atr.map(|d| ramp(d, config.stop_atr));
+    let drawdown_score = drawdown_atr.map(|d| 100.0 - ramp(d, config.drawdown_atr));
+    let weight_score = 100.0 - ramp(weight_pct, config.max_weight_pct);
+    let parts: Vec<f64> = [stop_score, drawdown_score, Some(weight_score)]
+        .into_iter()
+        .flatten()
+        .collect();
+    let score = parts.iter().sum::<f64>() / parts.len() as f64;
+    let light = if score >= config.green_min {
+        HealthLight::Green
+    } else if score < config.red_below {
+        HealthLight::Red
+    } else {
+        HealthLight::Amber
+    };
+
+    PositionHealth {
+        symbol: position.symbol.clone(),
+        quantity: position.position,
+        price,
+        average_cost: position.average_cost,
+        stop_price,
+        stop_source,
+        atr,
+        stop_distance_atr,
+        drawdown_atr,
+        weight_pct,
+        stop_score,
+        drawdown_score,
+        weight_score,
+        score,
+        light,
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use tempfile::NamedTempFile;
+
+use super::*;
+use crate::events::EventEmitter;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::position_plans::PositionPlanInput;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// ATR per symbol; symbols not listed have no history.
+struct FixedAtr(HashMap<&'static str, f64>);
+
+#[async_trait]
+impl AtrSource for FixedAtr {
+    async fn daily_atr(&self, symbol: &str, _period: usize) -> Result<Option<f64>, IbkrError> {
+        Ok(self.0.get(symbol).copied())
+    }
+}
+
+fn position(symbol: &str, kind: &str, qty: f64, price: f64, cost: f64) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: qty,
+        average_cost: cost,
+        market_price: price,
+        market_value: qty * price,
+        contract_type: kind.to_string(),
+        currency: "USD".to_string(),
+        local_symbol: format!("{symbol} {kind}"),
+        ..Default::default()
+    }
+}
+
+fn stop(symbol: &str, stop_price: f64) -> PositionPlanInput {
+    PositionPlanInput {
+        account: "DU1".to_string(),
+        contract: symbol.to_string(),
+        symbol: symbol.to_string(),
+        stop_price: Some(stop_price),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn scores_blend_stop_distance_drawdown_and_weight() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let positions = Arc::new(StubPositions(vec![
+        position("AAPL", "STK", 100.0, 200.0, 180.0),
+        position("TSLA", "STK", 50.0, 200.0, 230.0),
+        position("MSFT", "STK", -20.0, 250.0, 260.0),
+        // Counts toward the gross, but isn't scored.
+        position("SPY", "OPT", 10.0, 500.0, 450.0),
+    ]));
+    let plans = Arc::new(PositionPlanService::new(
+        Arc::clone(&db),
+        positions,
+        Arc::new(FixedAccount),
+        Arc::new(EventEmitter::for_capture()),
+    ));
+    plans.set(stop("AAPL", 190.0)).await.unwrap();
+    plans.set(stop("TSLA", 195.0)).await.unwrap();
+    let service = PositionHealthService::new(
+        db,
+        Arc::new(FixedAccount),
+        plans,
+        Arc::new(FixedAtr(HashMap::from([
+            ("AAPL", 5.0),
+            ("TSLA", 5.0),
+            ("MSFT", 5.0),
+        ]))),
+        Arc::new(RwLock::new(AppConfig::default())),
+    );
+
+    let report = service.report(None).await.unwrap();
+    assert_eq!(report.account, "DU1");
+    let symbols: Vec<&str> = report.positions.iter().map(|p| p.symbol.as_str()).collect();
+    assert_eq!(symbols, ["TSLA", "AAPL", "MSFT"], "worst first");
+
+    // 1 ATR above the stop, 6 under water, a quarter of the book.
+    let tsla = &report.positions[0];
+    assert_eq!(tsla.stop_distance_atr, Some(1.0));
+    assert_eq!(tsla.drawdown_score, Some(0.0));
+    assert_eq!(tsla.weight_pct, 25.0);
+    assert!((tsla.score - 100.0 / 9.0).abs() < 1e-9);
+    assert_eq!(tsla.light, HealthLight::Red);
+
+    // 2 ATRs above the stop and in profit, but half the book.
+    let aapl = &report.positions[1];
+    assert_eq!(aapl.stop_source, Some(StopSource::Plan));
+    assert_eq!(aapl.drawdown_atr, Some(-4.0));
+    assert_eq!(aapl.weight_score, 0.0);
+    assert_eq!(aapl.light, HealthLight::Amber);
+
+    // A short in profit with no stop: drawdown and weight only.
+    let msft = &report.positions[2];
+    assert_eq!(msft.stop_source, None);
+    assert_eq!(msft.stop_score, None);
+    assert_eq!(msft.drawdown_atr, Some(-2.0));
+    assert_eq!(msft.score, 75.0);
+    assert_eq!(msft.light, HealthLight::Green);
+}
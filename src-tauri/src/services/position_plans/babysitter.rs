@@ -0,0 +1,340 @@
+//! The stop babysitter: acts on plans whose stop has a [`StopAction`].
+//!
+//! It never sends an order itself (Hard Invariant 1). An exit action
+//! leaves an exit ticket on the plan; the trader sends it with
+//! `confirm_stop_exit`, through the same guarded path as a manual
+//! order, or dismisses it.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use chrono_tz::America::New_York;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use super::{store, PositionPlan, PositionPlanError, StopAction};
+use crate::config::AppConfig;
+use crate::events::{AppEvent, EventEmitter};
+use crate::ibkr::client::IbkrClient;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::{OrderAction, OrderRequest, OrderType, Position};
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+use crate::storage::Db;
+use crate::utils::market_calendar::is_market_open;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StopBabysitterConfig {
+    /// How far through the quote a `limit_exit` is priced, in basis
+    /// points.
+    #[serde(default = "default_limit_offset_bps")]
+    pub limit_offset_bps: f64,
+}
+
+fn default_limit_offset_bps() -> f64 {
+    25.0
+}
+
+impl Default for StopBabysitterConfig {
+    fn default() -> Self {
+        Self {
+            limit_offset_bps: default_limit_offset_bps(),
+        }
+    }
+}
+
+/// Trait seam for the live price. Production is the live `IbkrClient`;
+/// tests script it.
+#[async_trait]
+pub trait StopQuotes: Send + Sync {
+    /// Last trade; `None` when the snapshot had none. No fallback to the
+    /// prior close, which could breach a stop the market hasn't.
+    async fn last_trade(&self, symbol: &str) -> Result<Option<f64>, IbkrError>;
+}
+
+#[async_trait]
+impl StopQuotes for IbkrClient {
+    async fn last_trade(&self, symbol: &str) -> Result<Option<f64>, IbkrError> {
+        let snapshot = self.get_market_data_snapshot(symbol).await?;
+        Ok(snapshot.last_price.filter(|p| p.is_finite() && *p > 0.0))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StopWatchReport {
+    /// Open positions with an armed, babysat stop.
+    pub watched: usize,
+    /// Plans acted on this run; `stop_result` says what was done.
+    pub breaches: Vec<PositionPlan>,
+    /// Contracts left alone: not a US stock, or no last trade.
+    pub skipped: Vec<String>,
+}
+
+pub struct StopBabysitter {
+    db: Arc<Db>,
+    positions: Arc<dyn OpenPositionsSource>,
+    account: Arc<dyn AccountSource>,
+    quotes: Arc<dyn StopQuotes>,
+    config: Arc<RwLock<AppConfig>>,
+    emitter: Arc<EventEmitter>,
+}
+
+impl StopBabysitter {
+    pub fn new(
+        db: Arc<Db>,
+        positions: Arc<dyn OpenPositionsSource>,
+        account: Arc<dyn AccountSource>,
+        quotes: Arc<dyn StopQuotes>,
+        config: Arc<RwLock<AppConfig>>,
+        emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            db,
+            positions,
+            account,
+            quotes,
+            config,
+            emitter,
+        }
+    }
+
+    /// Check the current account's babysat stops once. Does nothing
+    /// outside the regular session.
+    pub async fn check(&self) -> Result<StopWatchReport, PositionPlanError> {
+        self.check_at(Utc::now()).await
+    }
+
+    pub(crate) async fn check_at(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<StopWatchReport, PositionPlanError> {
+        let mut report = StopWatchReport::default();
+        if is_market_open("SMART", now) == Some(false) {
+            return Ok(report);
+        }
+        let account = self.account.current_account().await?;
+        let mut plans: HashMap<String, PositionPlan> = store::list(&self.db, Some(&account), None)
+            .await?
+            .into_iter()
+            .filter(|p| p.stop_action.is_some() && p.stop_triggered_at.is_none())
+            .map(|p| (p.contract.clone(), p))
+            .collect();
+        if plans.is_empty() {
+            return Ok(report);
+        }
+        let positions = self.positions.list_open(&account).await?;
+        let offset_bps = self.config.read().await.stop_babysitter.limit_offset_bps;
+
+        for position in positions {
+            let Some(mut plan) = plans.remove(&position.contract_key()) else {
+                continue;
+            };
+            let (Some(action), Some(stop)) = (plan.stop_action, plan.stop_price) else {
+                continue;
+            };
+            if position.position == 0.0 {
+                continue;
+            }
+            report.watched += 1;
+            if position.contract_type != "STK" || !matches!(position.currency.as_str(), "USD" | "")
+            {
+                report.skipped.push(plan.contract);
+                continue;
+            }
+            let price = match self.quotes.last_trade(&position.symbol).await {
+                Ok(Some(price)) => price,
+                Ok(None) => {
+                    report.skipped.push(plan.contract);
+                    continue;
+                }
+                Err(e) => {
+                    warn!("stop_babysitter: quote for {} failed: {e}", position.symbol);
+                    report.skipped.push(plan.contract);
+                    continue;
+                }
+            };
+            let long = position.position > 0.0;
+            if (long && price > stop) || (!long && price < stop) {
+                continue;
+            }
+
+            let (ticket, result) = match action {
+                StopAction::Alert => (None, "alert only".to_string()),
+                StopAction::MarketExit | StopAction::LimitExit => {
+                    let order = exit_order(&position, action, price, offset_bps);
+                    let result = format!("exit ticket to {} awaits confirmation", describe(&order));
+                    (Some(order), result)
+                }
+            };
+            info!(
+                "stop_babysitter: {} {} breached its {stop} stop at {price}; {result}",
+                plan.account, plan.contract
+            );
+            plan.stop_triggered_at = Some(now.timestamp());
+            plan.stop_result = Some(result.clone());
+            plan.exit_ticket = ticket;
+            store::set_stop_triggered(&self.db, &plan).await?;
+
+            let event = AppEvent::StopBreached {
+                account: plan.account.clone(),
+                contract: plan.contract.clone(),
+                stop_price: stop,
+                price,
+                action: action.as_str().to_string(),
+                exit_ticket: plan.exit_ticket.is_some(),
+                result,
+            };
+            if let Err(e) = self.emitter.emit(event).await {
+                warn!("StopBreached emit failed: {e}");
+            }
+            report.breaches.push(plan);
+        }
+        Ok(report)
+    }
+
+    /// The plan with its pending exit ticket, for the trader to send. A
+    /// ticket lapses with the ET day of the breach, and is refused once
+    /// the position no longer matches it.
+    pub async fn pending_exit(
+        &self,
+        account: &str,
+        contract: &str,
+    ) -> Result<PositionPlan, PositionPlanError> {
+        self.pending_exit_at(account, contract, Utc::now()).await
+    }
+
+    /// Record that the trader sent the exit and IBKR took it.
+    pub async fn exit_placed(
+        &self,
+        account: &str,
+        contract: &str,
+        order_id: i32,
+    ) -> Result<PositionPlan, PositionPlanError> {
+        let mut plan = self.pending(account, contract).await?;
+        let order = plan
+            .exit_ticket
+            .take()
+            .expect("pending plans have a ticket");
+        plan.stop_result = Some(format!("order {order_id} to {}", describe(&order)));
+        store::set_stop_triggered(&self.db, &plan).await?;
+        Ok(plan)
+    }
+
+    pub async fn dismiss_exit(
+        &self,
+        account: &str,
+        contract: &str,
+    ) -> Result<PositionPlan, PositionPlanError> {
+        let mut plan = self.pending(account, contract).await?;
+        let order = plan
+            .exit_ticket
+            .take()
+            .expect("pending plans have a ticket");
+        plan.stop_result = Some(format!("exit ticket to {} dismissed", describe(&order)));
+        store::set_stop_triggered(&self.db, &plan).await?;
+        Ok(plan)
+    }
+
+    pub(crate) async fn pending_exit_at(
+        &self,
+        account: &str,
+        contract: &str,
+        now: DateTime<Utc>,
+    ) -> Result<PositionPlan, PositionPlanError> {
+        let plan = self.pending(account, contract).await?;
+        let stale = |why: String| PositionPlanError::StaleExitTicket(plan.contract.clone(), why);
+        let day = |at: DateTime<Utc>| at.with_timezone(&New_York).date_naive();
+        let posted = plan
+            .stop_triggered_at
+            .and_then(|t| DateTime::from_timestamp(t, 0))
+            .map(day);
+        if posted != Some(day(now)) {
+            return Err(stale(
+                "it lapsed with the session of the breach".to_string(),
+            ));
+        }
+        let order = plan
+            .exit_ticket
+            .as_ref()
+            .expect("pending plans have a ticket");
+        let held = self
+            .positions
+            .list_open(&plan.account)
+            .await?
+            .into_iter()
+            .find(|p| p.contract_key() == plan.contract)
+            .map_or(0.0, |p| p.position);
+        let closes = match order.action {
+            OrderAction::Sell => held,
+            OrderAction::Buy => -held,
+        };
+        if closes != order.quantity {
+            return Err(stale(format!("the position is now {held}")));
+        }
+        Ok(plan)
+    }
+
+    async fn pending(
+        &self,
+        account: &str,
+        contract: &str,
+    ) -> Result<PositionPlan, PositionPlanError> {
+        store::get(&self.db, account.trim(), contract.trim())
+            .await?
+            .filter(|p| p.exit_ticket.is_some())
+            .ok_or_else(|| PositionPlanError::NoExitTicket(contract.trim().to_string()))
+    }
+}
+
+/// Close all of `position`: sell a long, buy back a short. A limit is
+/// `offset_bps` through `price`, in cents.
+fn exit_order(
+    position: &Position,
+    action: StopAction,
+    price: f64,
+    offset_bps: f64,
+) -> OrderRequest {
+    let long = position.position > 0.0;
+    let (order_type, limit) = match action {
+        StopAction::LimitExit => {
+            let offset = offset_bps / 10_000.0;
+            let limit = if long {
+                price * (1.0 - offset)
+            } else {
+                price * (1.0 + offset)
+            };
+            (OrderType::Limit, Some((limit * 100.0).round() / 100.0))
+        }
+        StopAction::Alert | StopAction::MarketExit => (OrderType::Market, None),
+    };
+    OrderRequest {
+        symbol: position.symbol.clone(),
+        action: if long {
+            OrderAction::Sell
+        } else {
+            OrderAction::Buy
+        },
+        quantity: position.position.abs(),
+        order_type,
+        price: limit,
+        start_at: None,
+        algo: None,
+        route: None,
+        option: None,
+    }
+}
+
+fn describe(order: &OrderRequest) -> String {
+    let side = match order.action {
+        OrderAction::Buy => "buy",
+        OrderAction::Sell => "sell",
+    };
+    match order.price {
+        Some(limit) => format!("{side} {} limit {limit:.2}", order.quantity),
+        None => format!("{side} {} at market", order.quantity),
+    }
+}
@@ -0,0 +1,422 @@
+//! Per-position plan: a target price, a stop level and a short thesis,
+//! set by the user and stored locally in `position_plans`.
+//!
+//! Plans are keyed by account and contract (ticker for stock, local
+//! symbol for an option series, as in `services::portfolio_diff`), so a
+//! plan outlives the position and comes back if the contract is
+//! re-opened. `get_enriched_positions` returns the live positions each
+//! with its plan as an [`EnrichedPosition`]; research reports
+//! (`services::report_service`) list the plans of their ticker.
+//!
+//! The `position_plan_check` task (every 15 minutes through the session
+//! by default) compares each open position's market price with its
+//! plan and emits [`AppEvent::PositionLevelHit`] when the price reaches
+//! the target or the stop. For a short position the levels are
+//! mirrored: the target is below the price, the stop above. Like the
+//! other watches it fires on the crossing only; the standing reading is
+//! the plan's `hit`.
+//!
+//! A plan can also hand its stop to the babysitter ([`StopBabysitter`],
+//! the `stop_babysitter` task) by setting a [`StopAction`], for when a
+//! GTC stop resting at the broker isn't wanted. Each minute of the
+//! regular session it reads a live quote for every such US stock
+//! position and, on a breach, alerts or posts a market or limit exit
+//! ticket for the whole position, which the trader confirms or
+//! dismisses. It acts once: the plan records `stop_triggered_at`,
+//! `stop_result` and the pending `exit_ticket`, and saving the plan
+//! with a new stop or action re-arms it. The plan check leaves babysat
+//! stops to it.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::events::{AppEvent, EventEmitter};
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::{OrderRequest, Position};
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+mod babysitter;
+mod store;
+
+#[cfg(test)]
+mod tests;
+
+pub use babysitter::{StopBabysitter, StopBabysitterConfig, StopQuotes, StopWatchReport};
+
+/// Longest thesis kept, in characters.
+pub const MAX_THESIS_CHARS: usize = 500;
+
+#[derive(Error, Debug)]
+pub enum PositionPlanError {
+    #[error("invalid plan: {0}")]
+    Invalid(String),
+    #[error("no pending exit ticket for {0}")]
+    NoExitTicket(String),
+    #[error("the exit ticket for {0} is stale: {1}")]
+    StaleExitTicket(String, String),
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanLevel {
+    Target,
+    Stop,
+}
+
+impl PlanLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlanLevel::Target => "target",
+            PlanLevel::Stop => "stop",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "target" => Some(PlanLevel::Target),
+            "stop" => Some(PlanLevel::Stop),
+            _ => None,
+        }
+    }
+}
+
+/// What the stop babysitter does when the price breaches a plan's stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopAction {
+    /// Emit [`AppEvent::StopBreached`] only.
+    Alert,
+    /// Alert with a ticket to close the position at market.
+    MarketExit,
+    /// Alert with a ticket to close the position with a limit order
+    /// `stop_babysitter.limit_offset_bps` through the quote.
+    LimitExit,
+}
+
+impl StopAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StopAction::Alert => "alert",
+            StopAction::MarketExit => "market_exit",
+            StopAction::LimitExit => "limit_exit",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "alert" => Some(StopAction::Alert),
+            "market_exit" => Some(StopAction::MarketExit),
+            "limit_exit" => Some(StopAction::LimitExit),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionPlan {
+    pub account: String,
+    pub contract: String,
+    pub symbol: String,
+    pub target_price: Option<f64>,
+    pub stop_price: Option<f64>,
+    pub thesis: Option<String>,
+    /// Level the price was past at the last check.
+    pub hit: Option<PlanLevel>,
+    pub hit_at: Option<i64>,
+    /// Set when the stop babysitter watches the stop; `None` leaves it
+    /// to the plan check's alert.
+    pub stop_action: Option<StopAction>,
+    /// When the babysitter acted on a breach; `None` while armed.
+    pub stop_triggered_at: Option<i64>,
+    /// What came of it: an alert, the pending exit ticket, or the order
+    /// the trader sent from it.
+    pub stop_result: Option<String>,
+    /// The exit order awaiting the trader; cleared once confirmed or
+    /// dismissed.
+    pub exit_ticket: Option<OrderRequest>,
+    pub updated_at: i64,
+}
+
+impl PositionPlan {
+    /// Level `price` is at or past for a position of `quantity`, stop
+    /// first when both are (a plan with crossed levels).
+    pub fn level_at(&self, quantity: f64, price: f64) -> Option<PlanLevel> {
+        let long = quantity >= 0.0;
+        let past = |level: f64, above: bool| {
+            if above {
+                price >= level
+            } else {
+                price <= level
+            }
+        };
+        if self.stop_price.is_some_and(|s| past(s, !long)) {
+            Some(PlanLevel::Stop)
+        } else if self.target_price.is_some_and(|t| past(t, long)) {
+            Some(PlanLevel::Target)
+        } else {
+            None
+        }
+    }
+
+    fn level_price(&self, level: PlanLevel) -> f64 {
+        match level {
+            PlanLevel::Target => self.target_price,
+            PlanLevel::Stop => self.stop_price,
+        }
+        .unwrap_or_default()
+    }
+}
+
+/// What `set_position_plan` takes. A plan with no target, stop or
+/// thesis is deleted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionPlanInput {
+    pub account: String,
+    pub contract: String,
+    pub symbol: String,
+    pub target_price: Option<f64>,
+    pub stop_price: Option<f64>,
+    pub thesis: Option<String>,
+    /// Needs a stop price.
+    #[serde(default)]
+    pub stop_action: Option<StopAction>,
+}
+
+/// A live position with its plan, if one is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichedPosition {
+    #[serde(flatten)]
+    pub position: Position,
+    pub plan: Option<PositionPlan>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanCheckReport {
+    /// Open positions with a target or stop.
+    pub checked: usize,
+    /// Plans whose price reached a level this run.
+    pub hits: Vec<PositionPlan>,
+}
+
+/// Every account's plans for an underlying symbol.
+pub async fn plans_for_symbol(db: &Db, symbol: &str) -> Result<Vec<PositionPlan>, StorageError> {
+    let symbol = symbol.trim().to_uppercase();
+    store::list(db, None, Some(&symbol)).await
+}
+
+pub struct PositionPlanService {
+    db: Arc<Db>,
+    positions: Arc<dyn OpenPositionsSource>,
+    account: Arc<dyn AccountSource>,
+    emitter: Arc<EventEmitter>,
+}
+
+impl PositionPlanService {
+    pub fn new(
+        db: Arc<Db>,
+        positions: Arc<dyn OpenPositionsSource>,
+        account: Arc<dyn AccountSource>,
+        emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            db,
+            positions,
+            account,
+            emitter,
+        }
+    }
+
+    /// Create or replace a plan; `Ok(None)` when the input cleared it.
+    /// Changing the target or stop resets `hit`, so the new level can
+    /// fire; changing the stop or its action re-arms the babysitter.
+    pub async fn set(
+        &self,
+        input: PositionPlanInput,
+    ) -> Result<Option<PositionPlan>, PositionPlanError> {
+        let account = input.account.trim().to_string();
+        let contract = input.contract.trim().to_string();
+        if account.is_empty() || contract.is_empty() {
+            return Err(PositionPlanError::Invalid(
+                "account and contract are required".to_string(),
+            ));
+        }
+        for (name, level) in [("target", input.target_price), ("stop", input.stop_price)] {
+            if level.is_some_and(|v| !v.is_finite() || v <= 0.0) {
+                return Err(PositionPlanError::Invalid(format!(
+                    "{name} must be a positive price"
+                )));
+            }
+        }
+        if input.stop_action.is_some() && input.stop_price.is_none() {
+            return Err(PositionPlanError::Invalid(
+                "a stop action needs a stop price".to_string(),
+            ));
+        }
+        let thesis = input
+            .thesis
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty());
+        if thesis
+            .as_ref()
+            .is_some_and(|t| t.chars().count() > MAX_THESIS_CHARS)
+        {
+            return Err(PositionPlanError::Invalid(format!(
+                "thesis is longer than {MAX_THESIS_CHARS} characters"
+            )));
+        }
+        if input.target_price.is_none() && input.stop_price.is_none() && thesis.is_none() {
+            store::delete(&self.db, &account, &contract).await?;
+            return Ok(None);
+        }
+
+        let existing = store::get(&self.db, &account, &contract).await?;
+        let (hit, hit_at) = match &existing {
+            Some(p) if p.target_price == input.target_price && p.stop_price == input.stop_price => {
+                (p.hit, p.hit_at)
+            }
+            _ => (None, None),
+        };
+        let (stop_triggered_at, stop_result, exit_ticket) = match existing {
+            Some(p) if p.stop_price == input.stop_price && p.stop_action == input.stop_action => {
+                (p.stop_triggered_at, p.stop_result, p.exit_ticket)
+            }
+            _ => (None, None, None),
+        };
+        let symbol = match input.symbol.trim() {
+            "" => contract.clone(),
+            s => s.to_uppercase(),
+        };
+        let plan = PositionPlan {
+            account,
+            contract,
+            symbol,
+            target_price: input.target_price,
+            stop_price: input.stop_price,
+            thesis,
+            hit,
+            hit_at,
+            stop_action: input.stop_action,
+            stop_triggered_at,
+            stop_result,
+            exit_ticket,
+            updated_at: Utc::now().timestamp(),
+        };
+        store::upsert(&self.db, &plan).await?;
+        Ok(Some(plan))
+    }
+
+    pub async fn delete(&self, account: &str, contract: &str) -> Result<bool, PositionPlanError> {
+        Ok(store::delete(&self.db, account.trim(), contract.trim()).await?)
+    }
+
+    /// Stored plans, optionally for one account, by account then
+    /// contract.
+    pub async fn list(&self, account: Option<&str>) -> Result<Vec<PositionPlan>, StorageError> {
+        store::list(&self.db, account.map(str::trim), None).await
+    }
+
+    /// Open positions of `account` (the current account when `None`),
+    /// each with its plan.
+    pub async fn enriched(
+        &self,
+        account: Option<String>,
+    ) -> Result<Vec<EnrichedPosition>, PositionPlanError> {
+        let account = match account.filter(|a| !a.trim().is_empty()) {
+            Some(a) => a,
+            None => self.account.current_account().await?,
+        };
+        let positions = self.positions.list_open(&account).await?;
+        let mut plans = self.plans_by_contract(&account).await?;
+        Ok(positions
+            .into_iter()
+            .map(|position| {
+                let plan = plans.remove(&position.contract_key());
+                EnrichedPosition { position, plan }
+            })
+            .collect())
+    }
+
+    /// Check the current account's open positions against their plans
+    /// once.
+    pub async fn check(&self) -> Result<PlanCheckReport, PositionPlanError> {
+        let account = self.account.current_account().await?;
+        let positions = self.positions.list_open(&account).await?;
+        let mut plans = self.plans_by_contract(&account).await?;
+        let now = Utc::now().timestamp();
+        let mut report = PlanCheckReport::default();
+        for position in positions {
+            let Some(mut plan) = plans.remove(&position.contract_key()) else {
+                continue;
+            };
+            if plan.target_price.is_none() && plan.stop_price.is_none() {
+                continue;
+            }
+            report.checked += 1;
+            let price = position.market_price;
+            if !(price.is_finite() && price > 0.0) {
+                continue;
+            }
+            let level = plan.level_at(position.position, price);
+            if level == plan.hit {
+                continue;
+            }
+            plan.hit = level;
+            plan.hit_at = level.map(|_| now);
+            store::set_hit(&self.db, &plan).await?;
+
+            let Some(level) = level else {
+                continue;
+            };
+            // The babysitter alerts on its own breach.
+            if level == PlanLevel::Stop && plan.stop_action.is_some() {
+                continue;
+            }
+            info!(
+                "position_plans: {} {} reached its {} at {price}",
+                plan.account,
+                plan.contract,
+                level.as_str()
+            );
+            let event = AppEvent::PositionLevelHit {
+                account: plan.account.clone(),
+                contract: plan.contract.clone(),
+                level,
+                level_price: plan.level_price(level),
+                price,
+                thesis: plan.thesis.clone(),
+            };
+            if let Err(e) = self.emitter.emit(event).await {
+                warn!("PositionLevelHit emit failed: {e}");
+            }
+            report.hits.push(plan);
+        }
+        Ok(report)
+    }
+
+    async fn plans_by_contract(
+        &self,
+        account: &str,
+    ) -> Result<HashMap<String, PositionPlan>, StorageError> {
+        Ok(store::list(&self.db, Some(account), None)
+            .await?
+            .into_iter()
+            .map(|p| (p.contract.clone(), p))
+            .collect())
+    }
+}
@@ -0,0 +1,175 @@
+//! `position_plans` reads and writes.
+
+use rusqlite::OptionalExtension;
+
+use super::{PlanLevel, PositionPlan, StopAction};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+const COLUMNS: &str = "account, contract, symbol, target_price, stop_price, thesis, \
+                       hit, hit_at, stop_action, stop_triggered_at, stop_result, updated_at, \
+                       exit_ticket";
+
+fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<PositionPlan> {
+    Ok(PositionPlan {
+        account: row.get(0)?,
+        contract: row.get(1)?,
+        symbol: row.get(2)?,
+        target_price: row.get(3)?,
+        stop_price: row.get(4)?,
+        thesis: row.get(5)?,
+        hit: row
+            .get::<_, Option<String>>(6)?
+            .and_then(|h| PlanLevel::parse(&h)),
+        hit_at: row.get(7)?,
+        stop_action: row
+            .get::<_, Option<String>>(8)?
+            .and_then(|a| StopAction::parse(&a)),
+        stop_triggered_at: row.get(9)?,
+        stop_result: row.get(10)?,
+        updated_at: row.get(11)?,
+        exit_ticket: row
+            .get::<_, Option<String>>(12)?
+            .and_then(|t| serde_json::from_str(&t).ok()),
+    })
+}
+
+pub async fn upsert(db: &Db, plan: &PositionPlan) -> Result<(), StorageError> {
+    let p = plan.clone();
+    let ticket = ticket_json(plan)?;
+    db.with_conn(move |conn| {
+        conn.execute(
+            "INSERT INTO position_plans \
+               (account, contract, symbol, target_price, stop_price, thesis, \
+                hit, hit_at, stop_action, stop_triggered_at, stop_result, updated_at, \
+                exit_ticket) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13) \
+             ON CONFLICT(account, contract) DO UPDATE SET \
+               symbol = excluded.symbol, target_price = excluded.target_price, \
+               stop_price = excluded.stop_price, thesis = excluded.thesis, \
+               hit = excluded.hit, hit_at = excluded.hit_at, \
+               stop_action = excluded.stop_action, \
+               stop_triggered_at = excluded.stop_triggered_at, \
+               stop_result = excluded.stop_result, updated_at = excluded.updated_at, \
+               exit_ticket = excluded.exit_ticket",
+            rusqlite::params![
+                p.account,
+                p.contract,
+                p.symbol,
+                p.target_price,
+                p.stop_price,
+                p.thesis,
+                p.hit.map(|h| h.as_str()),
+                p.hit_at,
+                p.stop_action.map(|a| a.as_str()),
+                p.stop_triggered_at,
+                p.stop_result,
+                p.updated_at,
+                ticket
+            ],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+pub async fn set_hit(db: &Db, plan: &PositionPlan) -> Result<(), StorageError> {
+    let p = plan.clone();
+    db.with_conn(move |conn| {
+        conn.execute(
+            "UPDATE position_plans SET hit = ?3, hit_at = ?4 \
+             WHERE account = ?1 AND contract = ?2",
+            rusqlite::params![p.account, p.contract, p.hit.map(|h| h.as_str()), p.hit_at],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+/// Record that the babysitter acted on the stop, or what became of
+/// its exit ticket.
+pub async fn set_stop_triggered(db: &Db, plan: &PositionPlan) -> Result<(), StorageError> {
+    let p = plan.clone();
+    let ticket = ticket_json(plan)?;
+    db.with_conn(move |conn| {
+        conn.execute(
+            "UPDATE position_plans \
+             SET stop_triggered_at = ?3, stop_result = ?4, exit_ticket = ?5 \
+             WHERE account = ?1 AND contract = ?2",
+            rusqlite::params![
+                p.account,
+                p.contract,
+                p.stop_triggered_at,
+                p.stop_result,
+                ticket
+            ],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+fn ticket_json(plan: &PositionPlan) -> Result<Option<String>, StorageError> {
+    Ok(plan
+        .exit_ticket
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()?)
+}
+
+pub async fn get(
+    db: &Db,
+    account: &str,
+    contract: &str,
+) -> Result<Option<PositionPlan>, StorageError> {
+    let account = account.to_string();
+    let contract = contract.to_string();
+    db.with_conn(move |conn| {
+        conn.query_row(
+            &format!("SELECT {COLUMNS} FROM position_plans WHERE account = ?1 AND contract = ?2"),
+            rusqlite::params![account, contract],
+            from_row,
+        )
+        .optional()
+        .map_err(StorageError::from)
+    })
+    .await
+}
+
+/// Returns whether a plan was there.
+pub async fn delete(db: &Db, account: &str, contract: &str) -> Result<bool, StorageError> {
+    let account = account.to_string();
+    let contract = contract.to_string();
+    db.with_conn(move |conn| {
+        let n = conn.execute(
+            "DELETE FROM position_plans WHERE account = ?1 AND contract = ?2",
+            rusqlite::params![account, contract],
+        )?;
+        Ok(n > 0)
+    })
+    .await
+}
+
+/// Plans filtered by account and/or symbol, by account then contract.
+pub async fn list(
+    db: &Db,
+    account: Option<&str>,
+    symbol: Option<&str>,
+) -> Result<Vec<PositionPlan>, StorageError> {
+    let account = account.map(str::to_string);
+    let symbol = symbol.map(str::to_string);
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM position_plans \
+             WHERE (?1 IS NULL OR account = ?1) AND (?2 IS NULL OR symbol = ?2) \
+             ORDER BY account, contract"
+        ))?;
+        let rows = stmt.query_map(rusqlite::params![account, symbol], from_row)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
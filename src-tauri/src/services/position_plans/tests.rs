@@ -0,0 +1,326 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::TimeZone;
+use tempfile::NamedTempFile;
+use tokio::sync::RwLock;
+
+use super::*;
+use crate::config::AppConfig;
+use crate::ibkr::types::{OrderAction, OrderType};
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+#[derive(Default)]
+struct StubPositions(Mutex<Vec<Position>>);
+
+impl StubPositions {
+    fn set(&self, positions: Vec<Position>) {
+        *self.0.lock().unwrap() = positions;
+    }
+}
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.lock().unwrap().clone())
+    }
+}
+
+fn stock(symbol: &str, qty: f64, price: f64) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: qty,
+        market_price: price,
+        market_value: qty * price,
+        contract_type: "STK".to_string(),
+        currency: "USD".to_string(),
+        ..Default::default()
+    }
+}
+
+fn plan(contract: &str, target: Option<f64>, stop: Option<f64>) -> PositionPlanInput {
+    PositionPlanInput {
+        account: "DU1".to_string(),
+        contract: contract.to_string(),
+        symbol: contract.to_string(),
+        target_price: target,
+        stop_price: stop,
+        thesis: None,
+        stop_action: None,
+    }
+}
+
+struct Harness {
+    _tmp: NamedTempFile,
+    db: Arc<Db>,
+    service: PositionPlanService,
+    positions: Arc<StubPositions>,
+    emitter: Arc<EventEmitter>,
+}
+
+fn harness() -> Harness {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let positions = Arc::new(StubPositions::default());
+    let emitter = Arc::new(EventEmitter::for_capture());
+    let service = PositionPlanService::new(
+        Arc::clone(&db),
+        Arc::clone(&positions) as Arc<dyn OpenPositionsSource>,
+        Arc::new(FixedAccount),
+        Arc::clone(&emitter),
+    );
+    Harness {
+        _tmp: tmp,
+        db,
+        service,
+        positions,
+        emitter,
+    }
+}
+
+#[tokio::test]
+async fn plans_are_validated_and_joined_to_positions() {
+    let h = harness();
+    assert!(matches!(
+        h.service.set(plan("AAPL", Some(-1.0), None)).await,
+        Err(PositionPlanError::Invalid(_))
+    ));
+    let mut long_thesis = plan("AAPL", None, None);
+    long_thesis.thesis = Some("x".repeat(MAX_THESIS_CHARS + 1));
+    assert!(matches!(
+        h.service.set(long_thesis).await,
+        Err(PositionPlanError::Invalid(_))
+    ));
+
+    let mut input = plan("AAPL", Some(250.0), Some(190.0));
+    input.thesis = Some("  Services margin keeps expanding. ".to_string());
+    let stored = h.service.set(input).await.unwrap().unwrap();
+    assert_eq!(
+        stored.thesis.as_deref(),
+        Some("Services margin keeps expanding.")
+    );
+
+    h.positions.set(vec![
+        stock("AAPL", 100.0, 200.0),
+        stock("MSFT", 10.0, 400.0),
+    ]);
+    let enriched = h.service.enriched(None).await.unwrap();
+    assert_eq!(enriched.len(), 2);
+    assert_eq!(enriched[0].plan.as_ref().unwrap().target_price, Some(250.0));
+    assert!(enriched[1].plan.is_none());
+    let json = serde_json::to_value(&enriched[0]).unwrap();
+    assert_eq!(json["symbol"], "AAPL", "position fields stay top-level");
+    assert_eq!(json["plan"]["stopPrice"], 190.0);
+
+    // Clearing every field removes the plan.
+    assert!(h
+        .service
+        .set(plan("AAPL", None, None))
+        .await
+        .unwrap()
+        .is_none());
+    assert!(h.service.list(Some("DU1")).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn reaching_a_level_fires_once() {
+    let h = harness();
+    h.service
+        .set(plan("AAPL", Some(250.0), Some(190.0)))
+        .await
+        .unwrap();
+    h.positions.set(vec![stock("AAPL", 100.0, 200.0)]);
+    let report = h.service.check().await.unwrap();
+    assert_eq!(report.checked, 1);
+    assert!(report.hits.is_empty());
+
+    h.positions.set(vec![stock("AAPL", 100.0, 251.0)]);
+    let report = h.service.check().await.unwrap();
+    assert_eq!(report.hits.len(), 1);
+    assert_eq!(report.hits[0].hit, Some(PlanLevel::Target));
+    assert!(h.service.check().await.unwrap().hits.is_empty());
+
+    let events = h.emitter.captured().await;
+    assert_eq!(events.len(), 1);
+    assert!(matches!(
+        &events[0],
+        AppEvent::PositionLevelHit { level: PlanLevel::Target, level_price, price, .. }
+            if *level_price == 250.0 && *price == 251.0
+    ));
+
+    // Moving the target re-arms it.
+    h.service
+        .set(plan("AAPL", Some(260.0), Some(190.0)))
+        .await
+        .unwrap();
+    assert_eq!(h.service.list(None).await.unwrap()[0].hit, None);
+}
+
+#[test]
+fn short_positions_mirror_the_levels() {
+    let p = PositionPlan {
+        account: "DU1".to_string(),
+        contract: "TSLA".to_string(),
+        symbol: "TSLA".to_string(),
+        target_price: Some(150.0),
+        stop_price: Some(220.0),
+        thesis: None,
+        hit: None,
+        hit_at: None,
+        stop_action: None,
+        stop_triggered_at: None,
+        stop_result: None,
+        exit_ticket: None,
+        updated_at: 0,
+    };
+    assert_eq!(p.level_at(-10.0, 140.0), Some(PlanLevel::Target));
+    assert_eq!(p.level_at(-10.0, 225.0), Some(PlanLevel::Stop));
+    assert_eq!(p.level_at(-10.0, 200.0), None);
+    // Read as a long, 140 is under the 220 stop.
+    assert_eq!(p.level_at(10.0, 140.0), Some(PlanLevel::Stop));
+}
+
+#[derive(Default)]
+struct ScriptedQuotes(Mutex<HashMap<String, f64>>);
+
+impl ScriptedQuotes {
+    fn set(&self, symbol: &str, price: f64) {
+        self.0.lock().unwrap().insert(symbol.to_string(), price);
+    }
+}
+
+#[async_trait]
+impl StopQuotes for ScriptedQuotes {
+    async fn last_trade(&self, symbol: &str) -> Result<Option<f64>, IbkrError> {
+        Ok(self.0.lock().unwrap().get(symbol).copied())
+    }
+}
+
+#[tokio::test]
+async fn the_babysitter_acts_once_on_a_breached_stop() {
+    let h = harness();
+    let quotes = Arc::new(ScriptedQuotes::default());
+    let babysitter = StopBabysitter::new(
+        Arc::clone(&h.db),
+        Arc::clone(&h.positions) as Arc<dyn OpenPositionsSource>,
+        Arc::new(FixedAccount),
+        quotes.clone(),
+        Arc::new(RwLock::new(AppConfig::default())),
+        Arc::clone(&h.emitter),
+    );
+    // Tuesday 09:45 EDT (08:45 on a fixed EST clock), then a Saturday.
+    let open = Utc.with_ymd_and_hms(2026, 5, 5, 13, 45, 0).unwrap();
+    let weekend = Utc.with_ymd_and_hms(2026, 5, 9, 15, 0, 0).unwrap();
+
+    let mut no_stop = plan("AAPL", Some(250.0), None);
+    no_stop.stop_action = Some(StopAction::Alert);
+    assert!(matches!(
+        h.service.set(no_stop).await,
+        Err(PositionPlanError::Invalid(_))
+    ));
+    let mut aapl = plan("AAPL", None, Some(190.0));
+    aapl.stop_action = Some(StopAction::LimitExit);
+    h.service.set(aapl.clone()).await.unwrap();
+    let mut msft = plan("MSFT", None, Some(420.0));
+    msft.stop_action = Some(StopAction::Alert);
+    h.service.set(msft).await.unwrap();
+    // No stop action: left to the plan check.
+    h.service
+        .set(plan("NVDA", None, Some(100.0)))
+        .await
+        .unwrap();
+
+    h.positions.set(vec![
+        stock("AAPL", 100.0, 189.0),
+        stock("MSFT", -10.0, 410.0),
+        stock("NVDA", 50.0, 120.0),
+    ]);
+    quotes.set("AAPL", 189.0);
+    quotes.set("MSFT", 410.0);
+    assert_eq!(babysitter.check_at(weekend).await.unwrap().watched, 0);
+
+    let report = babysitter.check_at(open).await.unwrap();
+    assert_eq!(report.watched, 2);
+    assert_eq!(report.breaches.len(), 1);
+    assert_eq!(
+        report.breaches[0].stop_result.as_deref(),
+        Some("exit ticket to sell 100 limit 188.53 awaits confirmation")
+    );
+    // Nothing is sent: the exit waits on the plan.
+    let ticket = report.breaches[0].exit_ticket.clone().unwrap();
+    assert!(matches!(ticket.action, OrderAction::Sell));
+    assert!(matches!(ticket.order_type, OrderType::Limit));
+    // 25 bps through the quote.
+    assert_eq!(ticket.price, Some(188.53));
+    assert_eq!(
+        babysitter
+            .pending_exit_at("DU1", "AAPL", open)
+            .await
+            .unwrap()
+            .exit_ticket,
+        Some(ticket)
+    );
+
+    // Once only; the short's stop is above the price.
+    quotes.set("MSFT", 421.0);
+    let report = babysitter.check_at(open).await.unwrap();
+    assert_eq!(report.watched, 1);
+    assert_eq!(report.breaches[0].contract, "MSFT");
+    assert_eq!(
+        report.breaches[0].stop_result.as_deref(),
+        Some("alert only")
+    );
+    assert_eq!(report.breaches[0].exit_ticket, None);
+
+    let breached = h
+        .emitter
+        .captured()
+        .await
+        .into_iter()
+        .filter(|e| matches!(e, AppEvent::StopBreached { .. }))
+        .count();
+    assert_eq!(breached, 2);
+
+    // The ticket lapses with the day, and won't close a changed position.
+    let next_day = open + chrono::Duration::days(1);
+    assert!(matches!(
+        babysitter.pending_exit_at("DU1", "AAPL", next_day).await,
+        Err(PositionPlanError::StaleExitTicket(..))
+    ));
+    h.positions.set(vec![stock("AAPL", 60.0, 189.0)]);
+    assert!(matches!(
+        babysitter.pending_exit_at("DU1", "AAPL", open).await,
+        Err(PositionPlanError::StaleExitTicket(..))
+    ));
+    let placed = babysitter.exit_placed("DU1", "AAPL", 7).await.unwrap();
+    assert_eq!(placed.exit_ticket, None);
+    assert_eq!(
+        placed.stop_result.as_deref(),
+        Some("order 7 to sell 100 limit 188.53")
+    );
+    assert!(matches!(
+        babysitter.dismiss_exit("DU1", "AAPL").await,
+        Err(PositionPlanError::NoExitTicket(_))
+    ));
+
+    // The plan check only alerts on the stop nobody babysits.
+    let hits = h.service.check().await.unwrap().hits;
+    assert_eq!(hits.len(), 0);
+    h.positions.set(vec![stock("NVDA", 50.0, 99.0)]);
+    assert_eq!(h.service.check().await.unwrap().hits.len(), 1);
+
+    // A new stop re-arms it.
+    aapl.stop_price = Some(185.0);
+    let rearmed = h.service.set(aapl).await.unwrap().unwrap();
+    assert_eq!(rearmed.stop_triggered_at, None);
+    assert_eq!(rearmed.stop_result, None);
+}
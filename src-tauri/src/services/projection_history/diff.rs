@@ -0,0 +1,138 @@
+//! What changed between two projection snapshots.
+//!
+//! Assumptions are compared field by field through their serialized
+//! form, so a field added to `ProjectionAssumptions` shows up here
+//! without touching this file. The baseline row is compared on the
+//! inputs a new annual report moves (year, revenue, net income, EPS);
+//! targets on each scenario's final projected year.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::ibkr::types::{FinancialProjection, ProjectionResults};
+
+use super::ProjectionSnapshot;
+
+/// Changes smaller than this are float noise, not edits.
+const EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    /// camelCase field name, as the UI sees it.
+    pub field: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioTarget {
+    pub year: u32,
+    pub share_price_low: f64,
+    pub share_price_high: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetChange {
+    /// `bear` | `base` | `bull`.
+    pub scenario: String,
+    pub before: ScenarioTarget,
+    pub after: ScenarioTarget,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiff {
+    /// Assumption fields the operator changed.
+    pub assumptions: Vec<FieldChange>,
+    /// Baseline inputs that moved — a `year` entry means a new fiscal
+    /// year rolled in.
+    pub baseline: Vec<FieldChange>,
+    /// Final-year targets whose range moved.
+    pub targets: Vec<TargetChange>,
+}
+
+pub fn diff(before: &ProjectionSnapshot, after: &ProjectionSnapshot) -> SnapshotDiff {
+    SnapshotDiff {
+        assumptions: numeric_changes(
+            &serde_json::to_value(&before.assumptions).unwrap_or(Value::Null),
+            &serde_json::to_value(&after.assumptions).unwrap_or(Value::Null),
+        ),
+        baseline: baseline_changes(&before.results.baseline, &after.results.baseline),
+        targets: target_changes(&before.results, &after.results),
+    }
+}
+
+fn numeric_changes(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let (Some(b), Some(a)) = (before.as_object(), after.as_object()) else {
+        return Vec::new();
+    };
+    let mut out: Vec<FieldChange> = a
+        .iter()
+        .filter_map(|(field, after)| {
+            let before = b.get(field)?.as_f64()?;
+            let after = after.as_f64()?;
+            changed(before, after).then(|| FieldChange {
+                field: field.clone(),
+                before,
+                after,
+            })
+        })
+        .collect();
+    out.sort_by(|x, y| x.field.cmp(&y.field));
+    out
+}
+
+fn baseline_changes(before: &FinancialProjection, after: &FinancialProjection) -> Vec<FieldChange> {
+    [
+        ("year", before.year as f64, after.year as f64),
+        ("revenue", before.revenue, after.revenue),
+        ("netIncome", before.net_income, after.net_income),
+        ("eps", before.eps, after.eps),
+    ]
+    .into_iter()
+    .filter(|(_, b, a)| changed(*b, *a))
+    .map(|(field, before, after)| FieldChange {
+        field: field.to_string(),
+        before,
+        after,
+    })
+    .collect()
+}
+
+fn target_changes(before: &ProjectionResults, after: &ProjectionResults) -> Vec<TargetChange> {
+    let (Some(b), Some(a)) = (before.projections.last(), after.projections.last()) else {
+        return Vec::new();
+    };
+    [
+        ("bear", &b.bear, &a.bear),
+        ("base", &b.base, &a.base),
+        ("bull", &b.bull, &a.bull),
+    ]
+    .into_iter()
+    .map(|(scenario, b, a)| TargetChange {
+        scenario: scenario.to_string(),
+        before: target(b),
+        after: target(a),
+    })
+    .filter(|t| {
+        t.before.year != t.after.year
+            || changed(t.before.share_price_low, t.after.share_price_low)
+            || changed(t.before.share_price_high, t.after.share_price_high)
+    })
+    .collect()
+}
+
+fn target(p: &FinancialProjection) -> ScenarioTarget {
+    ScenarioTarget {
+        year: p.year,
+        share_price_low: p.share_price_low,
+        share_price_high: p.share_price_high,
+    }
+}
+
+fn changed(before: f64, after: f64) -> bool {
+    (before - after).abs() > EPSILON
+}
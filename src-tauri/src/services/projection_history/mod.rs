@@ -0,0 +1,218 @@
+//! Projection snapshots and assumption history.
+//!
+//! Every `ProjectionResults` the analysis screen generates is stored
+//! with the assumptions behind it ([`ProjectionHistoryStore::record`]).
+//! [`ProjectionHistoryStore::history`] replays them oldest-first, each
+//! paired with a [`SnapshotDiff`] against its predecessor: which
+//! assumptions moved, whether a new fiscal year replaced the baseline,
+//! and how each scenario's final-year price target shifted — the "why
+//! did my target change" view.
+//!
+//! Re-rendering the same projection (same assumptions, same inputs)
+//! does not add a row, so the history only grows when something did.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::ibkr::types::{ProjectionAssumptions, ProjectionResults};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+pub mod diff;
+
+use diff::{diff, SnapshotDiff};
+
+#[cfg(test)]
+mod tests;
+
+/// Rows returned by [`ProjectionHistoryStore::history`] when the caller
+/// doesn't pass a limit.
+pub const DEFAULT_HISTORY_LIMIT: u32 = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectionSnapshot {
+    pub id: i64,
+    pub symbol: String,
+    pub generated_at: i64,
+    pub assumptions: ProjectionAssumptions,
+    pub results: ProjectionResults,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectionHistoryEntry {
+    #[serde(flatten)]
+    pub snapshot: ProjectionSnapshot,
+    /// Against the previous snapshot; `None` for the first one.
+    pub diff: Option<SnapshotDiff>,
+}
+
+/// Thin wrapper over `Arc<Db>` for the `projection_snapshots` table.
+#[derive(Clone)]
+pub struct ProjectionHistoryStore {
+    db: Arc<Db>,
+}
+
+impl ProjectionHistoryStore {
+    pub fn new(db: Arc<Db>) -> Self {
+        Self { db }
+    }
+
+    /// Store a generated projection. Returns the new row id, or `None`
+    /// when it is identical to the symbol's latest snapshot.
+    pub async fn record(
+        &self,
+        symbol: &str,
+        assumptions: &ProjectionAssumptions,
+        results: &ProjectionResults,
+    ) -> Result<Option<i64>, StorageError> {
+        let symbol = symbol.trim().to_uppercase();
+        let assumptions_json = serde_json::to_string(assumptions)?;
+        let results_json = serde_json::to_string(results)?;
+        let baseline_year = results.baseline.year;
+        let now = Utc::now().timestamp();
+        self.db
+            .with_conn(move |conn| {
+                let latest: Option<(String, String)> = conn
+                    .query_row(
+                        "SELECT assumptions_json, results_json FROM projection_snapshots \
+                         WHERE symbol = ?1 ORDER BY id DESC LIMIT 1",
+                        rusqlite::params![symbol],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .map(Some)
+                    .or_else(|e| match e {
+                        rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                        other => Err(other),
+                    })?;
+                if latest == Some((assumptions_json.clone(), results_json.clone())) {
+                    return Ok(None);
+                }
+                conn.execute(
+                    "INSERT INTO projection_snapshots \
+                       (symbol, generated_at, baseline_year, assumptions_json, results_json) \
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    rusqlite::params![symbol, now, baseline_year, assumptions_json, results_json],
+                )?;
+                Ok(Some(conn.last_insert_rowid()))
+            })
+            .await
+    }
+
+    /// The latest `limit` snapshots for `symbol`, oldest first, each
+    /// diffed against the one before it.
+    pub async fn history(
+        &self,
+        symbol: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<ProjectionHistoryEntry>, StorageError> {
+        let symbol = symbol.trim().to_uppercase();
+        let limit = limit.unwrap_or(DEFAULT_HISTORY_LIMIT).max(1);
+        let raw = self
+            .db
+            .with_conn(move |conn| {
+                // One extra row so the oldest returned snapshot still
+                // gets a diff.
+                let mut stmt = conn.prepare(
+                    "SELECT id, symbol, generated_at, assumptions_json, results_json \
+                     FROM projection_snapshots WHERE symbol = ?1 \
+                     ORDER BY id DESC LIMIT ?2",
+                )?;
+                let rows = stmt.query_map(rusqlite::params![symbol, limit + 1], read_row)?;
+                let mut out = Vec::new();
+                for row in rows {
+                    out.push(row?);
+                }
+                Ok(out)
+            })
+            .await?;
+
+        let mut snapshots = parse_rows(raw)?;
+        snapshots.reverse();
+
+        let skip = snapshots.len().saturating_sub(limit as usize);
+        let mut entries = Vec::with_capacity(snapshots.len() - skip);
+        for i in skip..snapshots.len() {
+            let diff = i.checked_sub(1).map(|p| diff(&snapshots[p], &snapshots[i]));
+            entries.push(ProjectionHistoryEntry {
+                snapshot: snapshots[i].clone(),
+                diff,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// The newest snapshot of `symbol`; `None` when it has none.
+    pub async fn latest(&self, symbol: &str) -> Result<Option<ProjectionSnapshot>, StorageError> {
+        let symbol = symbol.trim().to_uppercase();
+        let raw = self
+            .db
+            .with_conn(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, symbol, generated_at, assumptions_json, results_json \
+                     FROM projection_snapshots WHERE symbol = ?1 \
+                     ORDER BY id DESC LIMIT 1",
+                )?;
+                let rows = stmt.query_map(rusqlite::params![symbol], read_row)?;
+                let mut out = Vec::new();
+                for row in rows {
+                    out.push(row?);
+                }
+                Ok(out)
+            })
+            .await?;
+        Ok(parse_rows(raw)?.pop())
+    }
+
+    /// The newest snapshot of every symbol, by symbol. Feeds the
+    /// fair-value watch.
+    pub async fn latest_per_symbol(&self) -> Result<Vec<ProjectionSnapshot>, StorageError> {
+        let raw = self
+            .db
+            .with_conn(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, symbol, generated_at, assumptions_json, results_json \
+                     FROM projection_snapshots \
+                     WHERE id IN (SELECT MAX(id) FROM projection_snapshots GROUP BY symbol) \
+                     ORDER BY symbol ASC",
+                )?;
+                let rows = stmt.query_map([], read_row)?;
+                let mut out = Vec::new();
+                for row in rows {
+                    out.push(row?);
+                }
+                Ok(out)
+            })
+            .await?;
+        parse_rows(raw)
+    }
+}
+
+type RawRow = (i64, String, i64, String, String);
+
+fn read_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<RawRow> {
+    Ok((
+        row.get(0)?,
+        row.get(1)?,
+        row.get(2)?,
+        row.get(3)?,
+        row.get(4)?,
+    ))
+}
+
+fn parse_rows(raw: Vec<RawRow>) -> Result<Vec<ProjectionSnapshot>, StorageError> {
+    raw.into_iter()
+        .map(|(id, symbol, generated_at, assumptions, results)| {
+            Ok(ProjectionSnapshot {
+                id,
+                symbol,
+                generated_at,
+                assumptions: serde_json::from_str(&assumptions)?,
+                results: serde_json::from_str(&results)?,
+            })
+        })
+        .collect()
+}
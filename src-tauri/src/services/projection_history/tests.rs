@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use tempfile::NamedTempFile;
+
+use crate::ibkr::types::{HistoricalFinancial, ProjectionAssumptions, ProjectionResults};
+use crate::services::projection_service::ProjectionService;
+use crate::storage::Db;
+
+use super::ProjectionHistoryStore;
+
+fn open_store() -> (NamedTempFile, ProjectionHistoryStore) {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    (tmp, ProjectionHistoryStore::new(db))
+}
+
+fn results(
+    assumptions: &ProjectionAssumptions,
+    latest_year: Option<HistoricalFinancial>,
+) -> ProjectionResults {
+    let mut data = ProjectionService::generate_mock_fundamental_data("NVDA");
+    data.historical.extend(latest_year);
+    ProjectionService::generate_projection_results(&data, assumptions).unwrap()
+}
+
+#[tokio::test]
+async fn identical_rerender_is_not_recorded() {
+    let (_tmp, store) = open_store();
+    let assumptions = ProjectionAssumptions::default();
+    let r = results(&assumptions, None);
+
+    assert!(store
+        .record("nvda", &assumptions, &r)
+        .await
+        .unwrap()
+        .is_some());
+    assert!(store
+        .record("NVDA", &assumptions, &r)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(store.history("NVDA", None).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn history_diffs_assumption_and_baseline_changes() {
+    let (_tmp, store) = open_store();
+    let first = ProjectionAssumptions::default();
+    store
+        .record("NVDA", &first, &results(&first, None))
+        .await
+        .unwrap();
+
+    let tweaked = ProjectionAssumptions {
+        base_revenue_growth: 25.0,
+        ..ProjectionAssumptions::default()
+    };
+    store
+        .record("NVDA", &tweaked, &results(&tweaked, None))
+        .await
+        .unwrap();
+
+    let fy2025 = HistoricalFinancial {
+        year: 2025,
+        revenue: 165.0,
+        net_income: 90.0,
+        eps: 3.7,
+        split_adjustment: None,
+    };
+    store
+        .record("NVDA", &tweaked, &results(&tweaked, Some(fy2025)))
+        .await
+        .unwrap();
+
+    let history = store.history("NVDA", None).await.unwrap();
+    assert_eq!(history.len(), 3);
+    assert!(history[0].diff.is_none());
+
+    let edit = history[1].diff.as_ref().unwrap();
+    assert_eq!(edit.assumptions.len(), 1);
+    assert_eq!(edit.assumptions[0].field, "baseRevenueGrowth");
+    assert_eq!(
+        (edit.assumptions[0].before, edit.assumptions[0].after),
+        (35.0, 25.0)
+    );
+    assert!(edit.baseline.is_empty());
+    let base = edit.targets.iter().find(|t| t.scenario == "base").unwrap();
+    assert!(base.after.share_price_high < base.before.share_price_high);
+    assert!(
+        edit.targets.iter().all(|t| t.scenario != "bear"),
+        "bear untouched"
+    );
+
+    let roll = history[2].diff.as_ref().unwrap();
+    assert!(roll.assumptions.is_empty());
+    let year = roll.baseline.iter().find(|c| c.field == "year").unwrap();
+    assert_eq!((year.before, year.after), (2024.0, 2025.0));
+}
+
+#[tokio::test]
+async fn limit_keeps_latest_and_still_diffs_the_oldest() {
+    let (_tmp, store) = open_store();
+    for growth in [30.0, 32.0, 34.0] {
+        let a = ProjectionAssumptions {
+            base_revenue_growth: growth,
+            ..ProjectionAssumptions::default()
+        };
+        store.record("NVDA", &a, &results(&a, None)).await.unwrap();
+    }
+
+    let history = store.history("NVDA", Some(2)).await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].snapshot.assumptions.base_revenue_growth, 32.0);
+    assert!(history[0].diff.is_some(), "diffed against the trimmed row");
+    assert!(store.history("AMD", None).await.unwrap().is_empty());
+}
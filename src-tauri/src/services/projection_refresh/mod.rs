@@ -0,0 +1,175 @@
+//! Re-run stored projections when a new fiscal year lands.
+//!
+//! [`RefreshingFundamentalsProvider`](crate::services::fundamentals_provider::refreshing::RefreshingFundamentalsProvider)
+//! hands every fetched record to [`ProjectionRefresher::refresh`]. When
+//! a record's latest fiscal year is newer than the baseline of the
+//! symbol's newest projection snapshot (`services::projection_history`),
+//! the projection is regenerated with that snapshot's assumptions and
+//! recorded as a new snapshot. The base-case target of the final
+//! projected year is compared before and after; a move of at least
+//! `projection_refresh.material_change_pct` is flagged as material.
+//! One [`AppEvent::ProjectionsUpdated`] lists every re-run ticker.
+//!
+//! Tickers never projected, or whose baseline is already current, are
+//! left alone; so is a record the projection service refuses (a
+//! blocking data-quality finding).
+
+use std::sync::Arc;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+use crate::events::{AppEvent, EventEmitter};
+use crate::ibkr::types::{FundamentalData, ProjectionResults};
+use crate::services::projection_history::ProjectionHistoryStore;
+use crate::services::projection_service::ProjectionService;
+use crate::storage::error::StorageError;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectionRefreshConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Percent move in the base-case target that counts as material.
+    #[serde(default = "default_material_change_pct")]
+    pub material_change_pct: f64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_material_change_pct() -> f64 {
+    10.0
+}
+
+impl Default for ProjectionRefreshConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            material_change_pct: default_material_change_pct(),
+        }
+    }
+}
+
+/// One re-run ticker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectionUpdate {
+    pub symbol: String,
+    pub previous_baseline_year: u32,
+    pub baseline_year: u32,
+    /// Base-case midpoint of the final projected year.
+    pub previous_target: Option<f64>,
+    pub target: Option<f64>,
+    pub change_pct: Option<f64>,
+    pub material: bool,
+    /// `projection_snapshots.id` of the new snapshot.
+    pub snapshot_id: Option<i64>,
+}
+
+pub struct ProjectionRefresher {
+    history: Arc<ProjectionHistoryStore>,
+    config: Arc<RwLock<AppConfig>>,
+    emitter: Arc<EventEmitter>,
+}
+
+impl ProjectionRefresher {
+    pub fn new(
+        history: Arc<ProjectionHistoryStore>,
+        config: Arc<RwLock<AppConfig>>,
+        emitter: Arc<EventEmitter>,
+    ) -> Self {
+        Self {
+            history,
+            config,
+            emitter,
+        }
+    }
+
+    /// Re-run the stored projection of every record whose fiscal year
+    /// moved past its snapshot's baseline.
+    pub async fn refresh(
+        &self,
+        records: &[FundamentalData],
+    ) -> Result<Vec<ProjectionUpdate>, StorageError> {
+        let config = self.config.read().await.projection_refresh.clone();
+        if !config.enabled {
+            return Ok(Vec::new());
+        }
+        let mut updates = Vec::new();
+        for data in records {
+            let Some(latest_year) = data.historical.last().map(|h| h.year) else {
+                continue;
+            };
+            let Some(snapshot) = self
+                .history
+                .history(&data.symbol, Some(1))
+                .await?
+                .pop()
+                .map(|e| e.snapshot)
+            else {
+                continue;
+            };
+            let previous_baseline_year = snapshot.results.baseline.year;
+            if latest_year <= previous_baseline_year {
+                continue;
+            }
+            let results =
+                match ProjectionService::generate_projection_results(data, &snapshot.assumptions) {
+                    Ok(r) => r,
+                    Err(e) => {
+                        warn!("projection_refresh: {} not re-run: {e}", snapshot.symbol);
+                        continue;
+                    }
+                };
+            let snapshot_id = self
+                .history
+                .record(&snapshot.symbol, &snapshot.assumptions, &results)
+                .await?;
+            let previous_target = base_target(&snapshot.results);
+            let target = base_target(&results);
+            let change_pct = match (previous_target, target) {
+                (Some(before), Some(after)) if before != 0.0 => {
+                    Some((after - before) / before.abs() * 100.0)
+                }
+                _ => None,
+            };
+            updates.push(ProjectionUpdate {
+                symbol: snapshot.symbol,
+                previous_baseline_year,
+                baseline_year: results.baseline.year,
+                previous_target,
+                target,
+                change_pct,
+                material: change_pct.is_some_and(|c| c.abs() >= config.material_change_pct),
+                snapshot_id,
+            });
+        }
+
+        if !updates.is_empty() {
+            info!(
+                "projection_refresh: re-ran {} projection(s), {} material",
+                updates.len(),
+                updates.iter().filter(|u| u.material).count()
+            );
+            let event = AppEvent::ProjectionsUpdated {
+                updates: updates.clone(),
+            };
+            if let Err(e) = self.emitter.emit(event).await {
+                warn!("ProjectionsUpdated emit failed: {e}");
+            }
+        }
+        Ok(updates)
+    }
+}
+
+fn base_target(results: &ProjectionResults) -> Option<f64> {
+    let last = results.projections.last()?;
+    Some((last.base.share_price_low + last.base.share_price_high) / 2.0)
+}
@@ -0,0 +1,87 @@
+use tempfile::NamedTempFile;
+
+use super::*;
+use crate::ibkr::types::ProjectionAssumptions;
+use crate::storage::Db;
+
+struct Harness {
+    _tmp: NamedTempFile,
+    refresher: ProjectionRefresher,
+    history: Arc<ProjectionHistoryStore>,
+    emitter: Arc<EventEmitter>,
+}
+
+/// NVDA projected from FY2023, before FY2024 was reported.
+async fn harness(config: AppConfig) -> Harness {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let history = Arc::new(ProjectionHistoryStore::new(db));
+    let assumptions = ProjectionAssumptions {
+        base_revenue_growth: 30.0,
+        ..ProjectionAssumptions::default()
+    };
+    let mut stale = ProjectionService::generate_mock_fundamental_data("NVDA");
+    stale.historical.pop();
+    let results = ProjectionService::generate_projection_results(&stale, &assumptions).unwrap();
+    history
+        .record("NVDA", &assumptions, &results)
+        .await
+        .unwrap();
+
+    let emitter = Arc::new(EventEmitter::for_capture());
+    let refresher = ProjectionRefresher::new(
+        Arc::clone(&history),
+        Arc::new(RwLock::new(config)),
+        Arc::clone(&emitter),
+    );
+    Harness {
+        _tmp: tmp,
+        refresher,
+        history,
+        emitter,
+    }
+}
+
+#[tokio::test]
+async fn new_fiscal_year_reruns_with_saved_assumptions() {
+    let h = harness(AppConfig::default()).await;
+    let fresh = ProjectionService::generate_mock_fundamental_data("NVDA");
+    let mut untracked = fresh.clone();
+    untracked.symbol = "AMD".to_string();
+
+    let updates = h.refresher.refresh(&[fresh, untracked]).await.unwrap();
+    assert_eq!(updates.len(), 1, "AMD was never projected");
+    let u = &updates[0];
+    assert_eq!((u.previous_baseline_year, u.baseline_year), (2023, 2024));
+    assert!(u.snapshot_id.is_some());
+    assert!(u.change_pct.unwrap().abs() >= 10.0);
+    assert!(u.material);
+
+    let latest = h.history.history("NVDA", Some(1)).await.unwrap();
+    let snapshot = &latest[0].snapshot;
+    assert_eq!(snapshot.results.baseline.year, 2024);
+    assert_eq!(snapshot.assumptions.base_revenue_growth, 30.0);
+
+    let events = h.emitter.captured().await;
+    assert!(matches!(
+        &events[..],
+        [AppEvent::ProjectionsUpdated { updates }] if updates[0].symbol == "NVDA"
+    ));
+
+    // The baseline is current now, so a second refresh is a no-op.
+    let again = ProjectionService::generate_mock_fundamental_data("NVDA");
+    assert!(h.refresher.refresh(&[again]).await.unwrap().is_empty());
+    assert_eq!(h.emitter.captured().await.len(), 1);
+}
+
+#[tokio::test]
+async fn disabled_refresh_leaves_snapshots_alone() {
+    let mut config = AppConfig::default();
+    config.projection_refresh.enabled = false;
+    let h = harness(config).await;
+    let fresh = ProjectionService::generate_mock_fundamental_data("NVDA");
+
+    assert!(h.refresher.refresh(&[fresh]).await.unwrap().is_empty());
+    assert_eq!(h.history.history("NVDA", None).await.unwrap().len(), 1);
+    assert!(h.emitter.captured().await.is_empty());
+}
@@ -0,0 +1,29 @@
+use crate::ibkr::types::{
+    CagrMetrics, ExpectedValue, FinancialProjection, ProjectionAssumptions, ScenarioCagr,
+    YearlyProjection,
+};
+
+/// Weight the final year's price midpoints and the scenario CAGRs by
+/// the scenario probabilities.
+pub(super) fn expected_value(
+    assumptions: &ProjectionAssumptions,
+    last: &YearlyProjection,
+    cagr: &ScenarioCagr,
+) -> ExpectedValue {
+    let weights = [
+        (assumptions.bear_probability / 100.0, &last.bear, &cagr.bear),
+        (assumptions.base_probability / 100.0, &last.base, &cagr.base),
+        (assumptions.bull_probability / 100.0, &last.bull, &cagr.bull),
+    ];
+    let weigh = |f: &dyn Fn(&FinancialProjection, &CagrMetrics) -> f64| {
+        weights.iter().map(|(p, proj, c)| p * f(proj, c)).sum()
+    };
+    ExpectedValue {
+        year: last.year,
+        target_price: weigh(&|p, _| (p.share_price_low + p.share_price_high) / 2.0),
+        cagr: CagrMetrics {
+            revenue: weigh(&|_, c| c.revenue),
+            share_price: weigh(&|_, c| c.share_price),
+        },
+    }
+}
@@ -1,7 +1,9 @@
 use crate::ibkr::types::{
-    CagrMetrics, FinancialProjection, FundamentalData, ProjectionAssumptions,
+    CagrMetrics, FinancialProjection, FundamentalData, ProjectedSegment, ProjectionAssumptions,
 };
 
+use super::segments::{self, Scenario};
+
 /// The three projected scenarios produced from one fundamental input.
 pub(super) struct ScenarioBatch {
     pub(super) bear: Vec<FinancialProjection>,
@@ -22,7 +24,15 @@ pub(super) fn generate_three_scenarios(
     initial_net_income: f64,
     projection_start_year: u32,
 ) -> ScenarioBatch {
-    let scenario = |revenue_growth: f64, margin_change: f64| {
+    let scenario = |which: Scenario, revenue_growth: f64, margin_change: f64| {
+        let segment_path = segments::project(
+            fundamental,
+            assumptions,
+            which,
+            revenue_growth,
+            projection_start_year - 1,
+            initial_revenue,
+        );
         generate_scenario_projection(ScenarioParams {
             initial_revenue,
             initial_net_income,
@@ -37,19 +47,23 @@ pub(super) fn generate_three_scenarios(
             start_year: projection_start_year,
             num_years: assumptions.years,
             analyst_estimates: fundamental.analyst_estimates.as_ref(),
+            segment_path: segment_path.as_deref(),
         })
     };
 
     ScenarioBatch {
         bear: scenario(
+            Scenario::Bear,
             assumptions.bear_revenue_growth,
             assumptions.bear_margin_change,
         ),
         base: scenario(
+            Scenario::Base,
             assumptions.base_revenue_growth,
             assumptions.base_margin_change,
         ),
         bull: scenario(
+            Scenario::Bull,
             assumptions.bull_revenue_growth,
             assumptions.bull_margin_change,
         ),
@@ -71,6 +85,9 @@ pub(super) struct ScenarioParams<'a> {
     pub(super) start_year: u32,
     pub(super) num_years: u32,
     pub(super) analyst_estimates: Option<&'a crate::ibkr::types::AnalystEstimates>,
+    /// Segment revenues per projected year; when set, revenue is their
+    /// sum (see `segments`).
+    pub(super) segment_path: Option<&'a [Vec<ProjectedSegment>]>,
 }
 
 fn generate_scenario_projection(params: ScenarioParams<'_>) -> Vec<FinancialProjection> {
@@ -88,39 +105,38 @@ fn generate_scenario_projection(params: ScenarioParams<'_>) -> Vec<FinancialProj
         // For the first projection year, check if we have analyst forward estimates
         // If available, use them as baseline instead of growing from historical data
         // This ensures projections reflect what the market is already pricing in
-        let net_income = if year_offset == 0 {
-            if let Some(estimates) = params.analyst_estimates {
-                // Try to get analyst revenue estimate for this year
-                revenue = estimates
-                    .revenue
-                    .iter()
-                    .find(|e| e.year == year)
-                    .map(|e| e.estimate)
-                    .unwrap_or_else(|| revenue * (1.0 + params.revenue_growth_rate / 100.0));
-
-                // Try to get analyst EPS estimate and back-calculate net income
-                if let Some(eps_est) = estimates.eps.iter().find(|e| e.year == year) {
-                    // Back-calculate net income from analyst EPS estimate
-                    // EPS = (net_income / shares) * 1000, so net_income = EPS * shares / 1000
-                    let net_income = eps_est.estimate * shares / 1_000.0;
-                    margin = (net_income / revenue) * 100.0;
-                    net_income
-                } else {
-                    // No analyst EPS, calculate from revenue and margin
-                    margin += params.margin_change_rate;
-                    revenue * (margin / 100.0)
-                }
-            } else {
-                // No analyst estimates, apply growth rates to historical baseline
-                revenue *= 1.0 + (params.revenue_growth_rate / 100.0);
+        let estimates = params.analyst_estimates.filter(|_| year_offset == 0);
+        let year_segments = params
+            .segment_path
+            .and_then(|path| path.get(year_offset as usize));
+        let prev_revenue = revenue;
+
+        // Segment projections take precedence over the analyst revenue
+        // consensus; otherwise compound from the prior year.
+        revenue = match year_segments {
+            Some(segments) => segments.iter().map(|s| s.revenue).sum(),
+            None => estimates
+                .and_then(|e| e.revenue.iter().find(|e| e.year == year))
+                .map(|e| e.estimate)
+                .unwrap_or_else(|| revenue * (1.0 + params.revenue_growth_rate / 100.0)),
+        };
+        let revenue_growth = match year_segments {
+            Some(_) if prev_revenue != 0.0 => (revenue / prev_revenue - 1.0) * 100.0,
+            _ => params.revenue_growth_rate,
+        };
+
+        let net_income = match estimates.and_then(|e| e.eps.iter().find(|e| e.year == year)) {
+            Some(eps_est) => {
+                // Back-calculate net income from analyst EPS estimate
+                // EPS = (net_income / shares) * 1000, so net_income = EPS * shares / 1000
+                let net_income = eps_est.estimate * shares / 1_000.0;
+                margin = (net_income / revenue) * 100.0;
+                net_income
+            }
+            None => {
                 margin += params.margin_change_rate;
                 revenue * (margin / 100.0)
             }
-        } else {
-            // For subsequent years (year_offset > 0), always compound growth from year 1
-            revenue *= 1.0 + (params.revenue_growth_rate / 100.0);
-            margin += params.margin_change_rate;
-            revenue * (margin / 100.0)
         };
 
         shares *= 1.0 + (params.shares_growth_rate / 100.0);
@@ -167,7 +183,7 @@ fn generate_scenario_projection(params: ScenarioParams<'_>) -> Vec<FinancialProj
         projections.push(FinancialProjection {
             year,
             revenue,
-            revenue_growth: params.revenue_growth_rate,
+            revenue_growth,
             net_income,
             net_income_growth,
             net_income_margins: margin,
@@ -180,6 +196,7 @@ fn generate_scenario_projection(params: ScenarioParams<'_>) -> Vec<FinancialProj
             ps_low_est,
             ps_high_est,
             analyst_eps_estimate,
+            segments: year_segments.cloned().unwrap_or_default(),
         });
 
         prev_net_income = net_income;
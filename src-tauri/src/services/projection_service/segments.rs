@@ -0,0 +1,111 @@
+//! Revenue projected segment by segment.
+//!
+//! A conglomerate's total growth rate hides segments that grow at very
+//! different speeds. When the record carries segment revenue for the
+//! baseline year (`FundamentalData::segments`, entered through a manual
+//! record or an override) and the assumptions carry
+//! `segment_growth`, each segment compounds at its own scenario rate
+//! and the projected revenue is their sum. A segment without a growth
+//! assumption grows at the scenario's total rate, as does any gap
+//! between the baseline total and the segment sum (corporate,
+//! eliminations), carried as an `Unallocated` segment.
+//!
+//! Segment revenue replaces the analyst revenue consensus in the first
+//! year; analyst EPS still sets that year's net income.
+
+use crate::ibkr::types::{FundamentalData, ProjectedSegment, ProjectionAssumptions};
+
+/// Name of the baseline total not covered by a reported segment.
+pub(super) const UNALLOCATED: &str = "Unallocated";
+
+/// Which scenario's rates to read from a `SegmentGrowth`.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Scenario {
+    Bear,
+    Base,
+    Bull,
+}
+
+/// Segment revenues of `baseline_year`, plus the unallocated rest of
+/// `baseline_revenue`; empty when no segment reported that year.
+pub(super) fn baseline(
+    fundamental: &FundamentalData,
+    baseline_year: u32,
+    baseline_revenue: f64,
+) -> Vec<ProjectedSegment> {
+    let mut segments: Vec<ProjectedSegment> = fundamental
+        .segments
+        .iter()
+        .filter_map(|s| {
+            let revenue = s.revenue.iter().find(|r| r.year == baseline_year)?.revenue;
+            Some(ProjectedSegment {
+                name: s.name.trim().to_string(),
+                revenue,
+                revenue_growth: 0.0,
+            })
+        })
+        .filter(|s| !s.name.is_empty() && s.revenue.is_finite())
+        .collect();
+    if segments.is_empty() {
+        return segments;
+    }
+    let rest = baseline_revenue - segments.iter().map(|s| s.revenue).sum::<f64>();
+    if rest.abs() > 1e-9 {
+        segments.push(ProjectedSegment {
+            name: UNALLOCATED.to_string(),
+            revenue: rest,
+            revenue_growth: 0.0,
+        });
+    }
+    segments
+}
+
+/// Per-year segment revenues for one scenario over `years` years, or
+/// `None` when the projection doesn't run by segment.
+pub(super) fn project(
+    fundamental: &FundamentalData,
+    assumptions: &ProjectionAssumptions,
+    scenario: Scenario,
+    total_growth: f64,
+    baseline_year: u32,
+    baseline_revenue: f64,
+) -> Option<Vec<Vec<ProjectedSegment>>> {
+    if assumptions.segment_growth.is_empty() {
+        return None;
+    }
+    let start = baseline(fundamental, baseline_year, baseline_revenue);
+    if start.is_empty() {
+        return None;
+    }
+    let rates: Vec<f64> = start
+        .iter()
+        .map(|s| {
+            assumptions
+                .segment_growth
+                .iter()
+                .find(|g| g.segment.trim().eq_ignore_ascii_case(&s.name))
+                .map(|g| match scenario {
+                    Scenario::Bear => g.bear_revenue_growth,
+                    Scenario::Base => g.base_revenue_growth,
+                    Scenario::Bull => g.bull_revenue_growth,
+                })
+                .unwrap_or(total_growth)
+        })
+        .collect();
+
+    let mut path = Vec::with_capacity(assumptions.years as usize);
+    let mut current = start;
+    for _ in 0..assumptions.years {
+        current = current
+            .iter()
+            .zip(&rates)
+            .map(|(s, rate)| ProjectedSegment {
+                name: s.name.clone(),
+                revenue: s.revenue * (1.0 + rate / 100.0),
+                revenue_growth: *rate,
+            })
+            .collect();
+        path.push(current.clone());
+    }
+    Some(path)
+}
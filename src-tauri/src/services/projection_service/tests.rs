@@ -0,0 +1,333 @@
+use super::*;
+use crate::ibkr::types::{
+    CurrentMetrics, HistoricalFinancial, RevenueSegment, SegmentGrowth, SegmentRevenue,
+};
+
+#[test]
+fn test_generate_projections() {
+    let fundamental = ProjectionService::generate_mock_fundamental_data("NVDA");
+    let assumptions = ProjectionAssumptions::default();
+
+    let projections = ProjectionService::generate_projections(&fundamental, &assumptions)
+        .expect("mock fundamental fixture must produce projections");
+
+    // Verify we have 5 years of projections for each scenario
+    assert_eq!(projections.base.len(), 5);
+    assert_eq!(projections.bear.len(), 5);
+    assert_eq!(projections.bull.len(), 5);
+
+    // Verify revenue growth in base case
+    let base_first = &projections.base[0];
+    let base_last = &projections.base[4];
+    assert!(base_last.revenue > base_first.revenue);
+
+    // Verify CAGR is calculated
+    assert!(projections.cagr.base.revenue > 0.0);
+    assert!(projections.cagr.base.share_price > 0.0);
+}
+
+#[test]
+fn explain_traces_every_year_down_to_the_target() {
+    let fundamental = ProjectionService::generate_mock_fundamental_data("NVDA");
+    let assumptions = ProjectionAssumptions::default();
+    let results =
+        ProjectionService::generate_projection_results(&fundamental, &assumptions).unwrap();
+    let trace = ProjectionService::explain(&fundamental, &assumptions, &results);
+
+    let names: Vec<_> = trace
+        .scenarios
+        .iter()
+        .map(|s| s.scenario.as_str())
+        .collect();
+    assert_eq!(names, vec!["bear", "base", "bull"]);
+    let base = &trace.scenarios[1];
+    assert_eq!(base.years.len(), results.projections.len());
+    // The first year leans on analyst consensus; later years compound.
+    assert!(base.years[0].steps[0]
+        .formula
+        .starts_with("analyst consensus"));
+    assert!(base.years[1].steps[0].formula.contains('×'));
+    for (year, row) in base.years.iter().zip(&results.projections) {
+        let last = year.steps.last().unwrap();
+        assert_eq!(last.label, "Price high ($)");
+        assert_eq!(last.value, row.base.share_price_high);
+        let shares = year.steps.iter().find(|s| s.label == "Shares (M)").unwrap();
+        assert!((row.base.net_income / shares.value * 1_000.0 - row.base.eps).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn expected_value_weights_scenarios_by_probability() {
+    let fundamental = ProjectionService::generate_mock_fundamental_data("NVDA");
+    let assumptions = ProjectionAssumptions {
+        bear_probability: 20.0,
+        base_probability: 30.0,
+        bull_probability: 50.0,
+        ..ProjectionAssumptions::default()
+    };
+    let results =
+        ProjectionService::generate_projection_results(&fundamental, &assumptions).unwrap();
+    let expected = results.expected.as_ref().unwrap();
+    let last = results.projections.last().unwrap();
+    let mid = |p: &FinancialProjection| (p.share_price_low + p.share_price_high) / 2.0;
+    let target = 0.2 * mid(&last.bear) + 0.3 * mid(&last.base) + 0.5 * mid(&last.bull);
+
+    assert_eq!(expected.year, last.year);
+    assert!((expected.target_price - target).abs() < 1e-9);
+    let cagr = 0.2 * results.cagr.bear.share_price
+        + 0.3 * results.cagr.base.share_price
+        + 0.5 * results.cagr.bull.share_price;
+    assert!((expected.cagr.share_price - cagr).abs() < 1e-9);
+}
+
+#[test]
+fn probabilities_must_sum_to_one_hundred() {
+    let fundamental = ProjectionService::generate_mock_fundamental_data("NVDA");
+    let lopsided = ProjectionAssumptions {
+        bull_probability: 40.0,
+        ..ProjectionAssumptions::default()
+    };
+    let err = ProjectionService::generate_projection_results(&fundamental, &lopsided)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("sum to 100%"), "{err}");
+
+    let negative = ProjectionAssumptions {
+        bear_probability: -10.0,
+        base_probability: 85.0,
+        ..ProjectionAssumptions::default()
+    };
+    assert!(negative.probability_error().is_some());
+    assert!(ProjectionAssumptions::default()
+        .probability_error()
+        .is_none());
+}
+
+/// FY2024 revenue of 100: Cloud 60, Devices 30, 10 unallocated.
+fn conglomerate() -> FundamentalData {
+    let segment = |name: &str, revenue: f64| RevenueSegment {
+        name: name.to_string(),
+        revenue: vec![SegmentRevenue {
+            year: 2024,
+            revenue,
+        }],
+    };
+    FundamentalData {
+        historical: vec![HistoricalFinancial {
+            year: 2024,
+            revenue: 100.0,
+            net_income: 10.0,
+            eps: 10.0,
+            split_adjustment: None,
+        }],
+        analyst_estimates: None,
+        segments: vec![segment("Cloud", 60.0), segment("Devices", 30.0)],
+        ..ProjectionService::generate_mock_fundamental_data("CONG")
+    }
+}
+
+#[test]
+fn segment_growth_rolls_up_to_total_revenue() {
+    let fundamental = conglomerate();
+    let assumptions = ProjectionAssumptions {
+        years: 2,
+        segment_growth: vec![SegmentGrowth {
+            segment: "cloud".to_string(),
+            bear_revenue_growth: 10.0,
+            base_revenue_growth: 20.0,
+            bull_revenue_growth: 30.0,
+        }],
+        ..ProjectionAssumptions::default()
+    };
+    let results =
+        ProjectionService::generate_projection_results(&fundamental, &assumptions).unwrap();
+    assert_eq!(results.baseline.segments.len(), 3);
+    assert_eq!(results.baseline.segments[2].name, "Unallocated");
+
+    // Cloud at its own 20%; Devices and the rest at the base 35%.
+    let first = &results.projections[0].base;
+    for (segment, expected) in first.segments.iter().zip([72.0, 40.5, 13.5]) {
+        assert!((segment.revenue - expected).abs() < 1e-9, "{segment:?}");
+    }
+    assert!((first.revenue - 126.0).abs() < 1e-9);
+    assert!((first.revenue_growth - 26.0).abs() < 1e-9);
+    let second = &results.projections[1].base;
+    assert!((second.revenue - (86.4 + 54.675 + 18.225)).abs() < 1e-9);
+    assert!((results.projections[0].bull.segments[0].revenue - 78.0).abs() < 1e-9);
+
+    let trace = ProjectionService::explain(&fundamental, &assumptions, &results);
+    let base = &trace.scenarios[1];
+    assert!(base.years[0].steps[0]
+        .formula
+        .starts_with("Σ segments: Cloud 72.00"));
+    assert!(base
+        .inputs
+        .iter()
+        .any(|s| s.label == "Cloud growth (%/yr)" && s.formula == "base segment assumption"));
+
+    // Without segment assumptions the total rate applies as before.
+    let plain = ProjectionAssumptions {
+        years: 2,
+        ..ProjectionAssumptions::default()
+    };
+    let results = ProjectionService::generate_projection_results(&fundamental, &plain).unwrap();
+    assert!(results.projections[0].base.segments.is_empty());
+    assert!((results.projections[0].base.revenue - 135.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_cagr_calculation() {
+    let projections = vec![
+        FinancialProjection {
+            year: 2025,
+            revenue: 100.0,
+            revenue_growth: 20.0,
+            net_income: 20.0,
+            net_income_growth: None,
+            net_income_margins: 20.0,
+            eps: 10.0,
+            pe_low_est: 50.0,
+            pe_high_est: 60.0,
+            share_price_low: 500.0,
+            share_price_high: 600.0,
+            valuation_method: "P/E".to_string(),
+            ps_low_est: None,
+            ps_high_est: None,
+            analyst_eps_estimate: None,
+            segments: Vec::new(),
+        },
+        FinancialProjection {
+            year: 2030,
+            revenue: 200.0,
+            revenue_growth: 20.0,
+            net_income: 40.0,
+            net_income_growth: Some(20.0),
+            net_income_margins: 20.0,
+            eps: 20.0,
+            pe_low_est: 50.0,
+            pe_high_est: 60.0,
+            share_price_low: 1000.0,
+            share_price_high: 1200.0,
+            valuation_method: "P/E".to_string(),
+            ps_low_est: None,
+            ps_high_est: None,
+            analyst_eps_estimate: None,
+            segments: Vec::new(),
+        },
+    ];
+
+    let cagr = calculate_cagr(&projections);
+
+    // CAGR for doubling over 5 years is approximately 14.87%
+    assert!((cagr.revenue - 14.87).abs() < 0.1);
+}
+
+#[test]
+fn test_negative_eps_uses_ps_valuation() {
+    // Test that companies with negative EPS use P/S valuation
+    let fundamental = FundamentalData {
+        symbol: "LOSSMAKER".to_string(),
+        historical: vec![HistoricalFinancial {
+            year: 2024,
+            revenue: 10.0,    // $10B revenue
+            net_income: -2.0, // Losing $2B
+            eps: -0.5,        // Negative EPS
+            split_adjustment: None,
+        }],
+        analyst_estimates: None,
+        data_quality: Vec::new(),
+        overrides: Vec::new(),
+        growth: None,
+        currency: None,
+        fx_conversion: None,
+        segments: Vec::new(),
+        current_metrics: CurrentMetrics {
+            price: Some(50.0),
+            pe_ratio: -1.0,             // N/A for negative earnings
+            shares_outstanding: 1000.0, // 1B shares
+            name: Some("Loss Maker Inc".to_string()),
+            exchange: Some("NASDAQ".to_string()),
+            market_cap: Some("50B".to_string()),
+            dividend_yield: None,
+            short_interest: None,
+        },
+    };
+
+    let assumptions = ProjectionAssumptions {
+        years: 3,
+        bear_revenue_growth: 10.0,
+        base_revenue_growth: 20.0,
+        bull_revenue_growth: 30.0,
+        bear_margin_change: -1.0, // Margins worsen
+        base_margin_change: 2.0,  // Margins improve
+        bull_margin_change: 5.0,  // Margins improve rapidly
+        pe_low: 40.0,
+        pe_high: 60.0,
+        ps_low: 3.0,
+        ps_high: 8.0,
+        shares_growth: 0.0,
+        ..ProjectionAssumptions::default()
+    };
+
+    let projections = ProjectionService::generate_projections(&fundamental, &assumptions)
+        .expect("mock fundamental fixture must produce projections");
+
+    // Check that bear case uses P/S (negative EPS)
+    let bear_first = &projections.bear[0];
+    assert!(bear_first.eps < 0.0, "Bear case should have negative EPS");
+    assert_eq!(
+        bear_first.valuation_method, "P/S",
+        "Should use P/S valuation"
+    );
+    assert!(
+        bear_first.ps_low_est.is_some(),
+        "Should have P/S low estimate"
+    );
+    assert!(
+        bear_first.ps_high_est.is_some(),
+        "Should have P/S high estimate"
+    );
+    assert!(
+        bear_first.share_price_low > 0.0,
+        "Share price should be positive"
+    );
+    assert!(
+        bear_first.share_price_high > 0.0,
+        "Share price should be positive"
+    );
+
+    // Check that bull case might transition to P/E (positive EPS if margins improve enough)
+    let bull_last = &projections.bull[projections.bull.len() - 1];
+    if bull_last.eps > 0.0 {
+        assert_eq!(
+            bull_last.valuation_method, "P/E",
+            "Should use P/E valuation for positive EPS"
+        );
+        assert!(
+            bull_last.ps_low_est.is_none(),
+            "Should not have P/S estimates"
+        );
+    } else {
+        assert_eq!(
+            bull_last.valuation_method, "P/S",
+            "Should use P/S valuation for negative EPS"
+        );
+        assert!(bull_last.ps_low_est.is_some(), "Should have P/S estimates");
+    }
+
+    println!("✓ Negative EPS correctly uses P/S valuation");
+    println!(
+        "  Bear EPS: {:.2}, Price: ${:.2}-${:.2} ({})",
+        bear_first.eps,
+        bear_first.share_price_low,
+        bear_first.share_price_high,
+        bear_first.valuation_method
+    );
+    println!(
+        "  Bull EPS: {:.2}, Price: ${:.2}-${:.2} ({})",
+        bull_last.eps,
+        bull_last.share_price_low,
+        bull_last.share_price_high,
+        bull_last.valuation_method
+    );
+}
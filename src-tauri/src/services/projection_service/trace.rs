@@ -0,0 +1,301 @@
+//! Explain-the-math trace for a projection run: the inputs each
+//! scenario started from and, per year, every intermediate value with
+//! the formula that produced it, so a target price can be audited line
+//! by line.
+//!
+//! The trace is rebuilt from the finished `ProjectionResults` rather
+//! than recorded inside the scenario loop, so it costs nothing when not
+//! asked for. It follows the same branches as `scenarios.rs` (segment
+//! roll-ups, analyst estimates in the first year, P/S when EPS is
+//! negative); keep the two in step.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::ibkr::types::{
+    FinancialProjection, FundamentalData, ProjectionAssumptions, ProjectionResults,
+};
+
+/// One computed (or input) figure.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceStep {
+    pub label: String,
+    /// How the value was derived, with the operands filled in.
+    pub formula: String,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct YearTrace {
+    pub year: u32,
+    pub steps: Vec<TraceStep>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioTrace {
+    /// `bear`, `base` or `bull`.
+    pub scenario: String,
+    pub inputs: Vec<TraceStep>,
+    pub years: Vec<YearTrace>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectionTrace {
+    /// Bear, base, bull.
+    pub scenarios: Vec<ScenarioTrace>,
+}
+
+fn step(label: &str, formula: String, value: f64) -> TraceStep {
+    TraceStep {
+        label: label.to_string(),
+        formula,
+        value,
+    }
+}
+
+pub(super) fn build(
+    fundamental: &FundamentalData,
+    assumptions: &ProjectionAssumptions,
+    results: &ProjectionResults,
+) -> ProjectionTrace {
+    let scenarios = [
+        (
+            "bear",
+            assumptions.bear_revenue_growth,
+            assumptions.bear_margin_change,
+        ),
+        (
+            "base",
+            assumptions.base_revenue_growth,
+            assumptions.base_margin_change,
+        ),
+        (
+            "bull",
+            assumptions.bull_revenue_growth,
+            assumptions.bull_margin_change,
+        ),
+    ];
+    ProjectionTrace {
+        scenarios: scenarios
+            .into_iter()
+            .map(|(name, growth, margin_change)| {
+                let rows: Vec<&FinancialProjection> = results
+                    .projections
+                    .iter()
+                    .map(|y| match name {
+                        "bear" => &y.bear,
+                        "base" => &y.base,
+                        _ => &y.bull,
+                    })
+                    .collect();
+                scenario(
+                    name,
+                    fundamental,
+                    assumptions,
+                    &results.baseline,
+                    growth,
+                    margin_change,
+                    &rows,
+                )
+            })
+            .collect(),
+    }
+}
+
+fn scenario(
+    name: &str,
+    fundamental: &FundamentalData,
+    assumptions: &ProjectionAssumptions,
+    baseline: &FinancialProjection,
+    growth: f64,
+    margin_change: f64,
+    rows: &[&FinancialProjection],
+) -> ScenarioTrace {
+    let initial_shares = fundamental.current_metrics.shares_outstanding;
+    let mut inputs = vec![
+        step(
+            "Baseline revenue ($B)",
+            format!("FY{} actual", baseline.year),
+            baseline.revenue,
+        ),
+        step(
+            "Baseline net income ($B)",
+            format!("FY{} actual", baseline.year),
+            baseline.net_income,
+        ),
+        step(
+            "Baseline margin (%)",
+            format!("{:.2} / {:.2} × 100", baseline.net_income, baseline.revenue),
+            baseline.net_income_margins,
+        ),
+        step(
+            "Shares outstanding (M)",
+            "current metrics".to_string(),
+            initial_shares,
+        ),
+        step(
+            "Revenue growth (%/yr)",
+            format!("{name} assumption"),
+            growth,
+        ),
+        step(
+            "Margin change (pts/yr)",
+            format!("{name} assumption"),
+            margin_change,
+        ),
+        step(
+            "Share growth (%/yr)",
+            "assumption".to_string(),
+            assumptions.shares_growth,
+        ),
+    ];
+    for segment in rows
+        .first()
+        .map(|r| r.segments.as_slice())
+        .unwrap_or_default()
+    {
+        let assumed = assumptions
+            .segment_growth
+            .iter()
+            .any(|g| g.segment.trim().eq_ignore_ascii_case(&segment.name));
+        inputs.push(step(
+            &format!("{} growth (%/yr)", segment.name),
+            if assumed {
+                format!("{name} segment assumption")
+            } else {
+                "total revenue growth".to_string()
+            },
+            segment.revenue_growth,
+        ));
+    }
+
+    let estimates = fundamental.analyst_estimates.as_ref();
+    let shares_factor = 1.0 + assumptions.shares_growth / 100.0;
+    let mut prev_revenue = baseline.revenue;
+    let mut prev_margin = baseline.net_income_margins;
+    let mut prev_shares = initial_shares;
+    let mut years = Vec::with_capacity(rows.len());
+    for (i, row) in rows.iter().enumerate() {
+        let mut steps = Vec::new();
+        let analyst_revenue = estimates
+            .filter(|_| i == 0)
+            .and_then(|e| e.revenue.iter().find(|r| r.year == row.year));
+        let analyst_eps = estimates
+            .filter(|_| i == 0)
+            .and_then(|e| e.eps.iter().find(|r| r.year == row.year));
+
+        let segment_sum = row
+            .segments
+            .iter()
+            .map(|s| format!("{} {:.2}", s.name, s.revenue))
+            .collect::<Vec<_>>()
+            .join(" + ");
+        steps.push(match analyst_revenue {
+            _ if !row.segments.is_empty() => step(
+                "Revenue ($B)",
+                format!("Σ segments: {segment_sum}"),
+                row.revenue,
+            ),
+            Some(est) => step(
+                "Revenue ($B)",
+                format!("analyst consensus for {}", est.year),
+                row.revenue,
+            ),
+            None => step(
+                "Revenue ($B)",
+                format!("{prev_revenue:.2} × (1 + {growth}%)"),
+                row.revenue,
+            ),
+        });
+        let shares = prev_shares * shares_factor;
+        match analyst_eps {
+            Some(est) => {
+                steps.push(step(
+                    "Net income ($B)",
+                    format!(
+                        "analyst EPS {:.2} × {prev_shares:.1}M shares / 1000",
+                        est.estimate
+                    ),
+                    row.net_income,
+                ));
+                steps.push(step(
+                    "Margin (%)",
+                    format!("{:.2} / {:.2} × 100", row.net_income, row.revenue),
+                    row.net_income_margins,
+                ));
+            }
+            None => {
+                steps.push(step(
+                    "Margin (%)",
+                    format!("{prev_margin:.2} + {margin_change}"),
+                    row.net_income_margins,
+                ));
+                steps.push(step(
+                    "Net income ($B)",
+                    format!("{:.2} × {:.2}%", row.revenue, row.net_income_margins),
+                    row.net_income,
+                ));
+            }
+        }
+        steps.push(step(
+            "Shares (M)",
+            format!("{prev_shares:.1} × (1 + {}%)", assumptions.shares_growth),
+            shares,
+        ));
+        steps.push(step(
+            "EPS ($)",
+            format!("{:.2} / {shares:.1} × 1000", row.net_income),
+            row.eps,
+        ));
+        if row.valuation_method == "P/S" {
+            let per_share = row.revenue / shares * 1_000.0;
+            steps.push(step(
+                "Revenue per share ($)",
+                format!("{:.2} / {shares:.1} × 1000 (EPS < 0, so P/S)", row.revenue),
+                per_share,
+            ));
+            let (low, high) = (
+                row.ps_low_est.unwrap_or(0.0),
+                row.ps_high_est.unwrap_or(0.0),
+            );
+            steps.push(step(
+                "Price low ($)",
+                format!("{per_share:.2} × P/S {low}"),
+                row.share_price_low,
+            ));
+            steps.push(step(
+                "Price high ($)",
+                format!("{per_share:.2} × P/S {high}"),
+                row.share_price_high,
+            ));
+        } else {
+            steps.push(step(
+                "Price low ($)",
+                format!("{:.2} × P/E {}", row.eps, row.pe_low_est),
+                row.share_price_low,
+            ));
+            steps.push(step(
+                "Price high ($)",
+                format!("{:.2} × P/E {}", row.eps, row.pe_high_est),
+                row.share_price_high,
+            ));
+        }
+        years.push(YearTrace {
+            year: row.year,
+            steps,
+        });
+        prev_revenue = row.revenue;
+        prev_margin = row.net_income_margins;
+        prev_shares = shares;
+    }
+
+    ScenarioTrace {
+        scenario: name.to_string(),
+        inputs,
+        years,
+    }
+}
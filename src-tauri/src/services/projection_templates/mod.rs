@@ -0,0 +1,297 @@
+//! Named projection-assumption templates ("hyper-growth", "mature
+//! dividend payer", "cyclical", …) kept in
+//! `AppConfig.projection_templates`, plus the template chosen per ticker.
+//!
+//! The shipped set ([`builtin_templates`]) is the default list; once the
+//! operator saves or deletes one the whole list lives in settings.
+//! Choosing a template for a ticker (`template` on
+//! `ibkr_generate_projection_results`) is remembered in
+//! `ticker_projection_templates`, so re-opening the ticker projects with
+//! the same profile.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+
+use crate::ibkr::types::ProjectionAssumptions;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectionTemplatesConfig {
+    #[serde(default = "builtin_templates")]
+    pub templates: Vec<ProjectionTemplate>,
+}
+
+impl Default for ProjectionTemplatesConfig {
+    fn default() -> Self {
+        Self {
+            templates: builtin_templates(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectionTemplate {
+    /// Unique, case-insensitive key. Trimmed on save.
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub assumptions: ProjectionAssumptions,
+}
+
+#[derive(Debug, Error)]
+pub enum ProjectionTemplateError {
+    #[error("projection template name must be non-empty")]
+    EmptyName,
+    #[error("projection template `{0}` not found")]
+    NotFound(String),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// Growth (%/yr), margin change (pp/yr), P/E and P/S bands and share
+/// count change (%/yr), bear/base/bull.
+#[allow(clippy::too_many_arguments)]
+fn template(
+    name: &str,
+    description: &str,
+    growth: [f64; 3],
+    margin: [f64; 3],
+    pe: [f64; 2],
+    ps: [f64; 2],
+    shares_growth: f64,
+) -> ProjectionTemplate {
+    ProjectionTemplate {
+        name: name.to_string(),
+        description: description.to_string(),
+        assumptions: ProjectionAssumptions {
+            years: 5,
+            bear_revenue_growth: growth[0],
+            base_revenue_growth: growth[1],
+            bull_revenue_growth: growth[2],
+            bear_margin_change: margin[0],
+            base_margin_change: margin[1],
+            bull_margin_change: margin[2],
+            pe_low: pe[0],
+            pe_high: pe[1],
+            ps_low: ps[0],
+            ps_high: ps[1],
+            shares_growth,
+            ..ProjectionAssumptions::default()
+        },
+    }
+}
+
+pub fn builtin_templates() -> Vec<ProjectionTemplate> {
+    vec![
+        template(
+            "hyper-growth",
+            "Early, fast-scaling revenue; margins expanding from a low base, stock-based dilution",
+            [25.0, 40.0, 55.0],
+            [-0.5, 1.0, 2.0],
+            [40.0, 60.0],
+            [6.0, 12.0],
+            2.0,
+        ),
+        template(
+            "growth",
+            "Established grower compounding in the teens",
+            [10.0, 18.0, 25.0],
+            [0.0, 0.5, 1.0],
+            [25.0, 35.0],
+            [3.0, 6.0],
+            0.5,
+        ),
+        template(
+            "mature dividend payer",
+            "Low single-digit growth, stable margins, steady buybacks",
+            [1.0, 3.0, 5.0],
+            [-0.5, 0.0, 0.3],
+            [14.0, 18.0],
+            [1.0, 2.0],
+            -1.0,
+        ),
+        template(
+            "value",
+            "Out-of-favour business valued on a low multiple with buybacks",
+            [0.0, 4.0, 7.0],
+            [-0.5, 0.25, 0.5],
+            [10.0, 15.0],
+            [1.0, 2.0],
+            -2.0,
+        ),
+        template(
+            "cyclical",
+            "Revenue that can shrink in a downturn; trough-to-mid-cycle multiples",
+            [-5.0, 3.0, 8.0],
+            [-2.0, 0.0, 1.0],
+            [8.0, 14.0],
+            [0.5, 1.5],
+            0.0,
+        ),
+    ]
+}
+
+impl ProjectionTemplatesConfig {
+    pub fn find(&self, name: &str) -> Option<&ProjectionTemplate> {
+        self.templates
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Insert or replace (by case-insensitive name).
+    pub fn upsert(
+        &mut self,
+        mut template: ProjectionTemplate,
+    ) -> Result<(), ProjectionTemplateError> {
+        template.name = template.name.trim().to_string();
+        if template.name.is_empty() {
+            return Err(ProjectionTemplateError::EmptyName);
+        }
+        match self
+            .templates
+            .iter_mut()
+            .find(|t| t.name.eq_ignore_ascii_case(&template.name))
+        {
+            Some(existing) => *existing = template,
+            None => self.templates.push(template),
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<(), ProjectionTemplateError> {
+        let before = self.templates.len();
+        self.templates
+            .retain(|t| !t.name.eq_ignore_ascii_case(name.trim()));
+        if self.templates.len() == before {
+            return Err(ProjectionTemplateError::NotFound(name.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Assumptions a projection request resolved to, and the template they
+/// came from (if any).
+#[derive(Debug, Clone)]
+pub struct ResolvedAssumptions {
+    pub assumptions: ProjectionAssumptions,
+    pub template: Option<String>,
+}
+
+/// Per-ticker template choice, backed by `ticker_projection_templates`.
+pub struct TickerTemplateStore {
+    db: Arc<Db>,
+}
+
+impl TickerTemplateStore {
+    pub fn new(db: Arc<Db>) -> Self {
+        Self { db }
+    }
+
+    pub async fn chosen(&self, symbol: &str) -> Result<Option<String>, StorageError> {
+        let symbol = symbol.trim().to_uppercase();
+        self.db
+            .with_conn(move |conn| {
+                conn.query_row(
+                    "SELECT template FROM ticker_projection_templates WHERE symbol = ?1",
+                    rusqlite::params![symbol],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(StorageError::from)
+            })
+            .await
+    }
+
+    pub async fn choose(&self, symbol: &str, template: &str) -> Result<(), StorageError> {
+        let symbol = symbol.trim().to_uppercase();
+        let template = template.trim().to_string();
+        let now = Utc::now().timestamp();
+        self.db
+            .with_conn(move |conn| {
+                conn.execute(
+                    "INSERT INTO ticker_projection_templates (symbol, template, chosen_at) \
+                     VALUES (?1, ?2, ?3) \
+                     ON CONFLICT(symbol) DO UPDATE SET \
+                       template = excluded.template, chosen_at = excluded.chosen_at",
+                    rusqlite::params![symbol, template, now],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// `true` when a choice was removed.
+    pub async fn clear(&self, symbol: &str) -> Result<bool, StorageError> {
+        let symbol = symbol.trim().to_uppercase();
+        self.db
+            .with_conn(move |conn| {
+                let n = conn.execute(
+                    "DELETE FROM ticker_projection_templates WHERE symbol = ?1",
+                    rusqlite::params![symbol],
+                )?;
+                Ok(n > 0)
+            })
+            .await
+    }
+
+    /// Pick the assumptions for a projection of `symbol`:
+    ///
+    /// 1. `template`, when given, is looked up and remembered for the
+    ///    ticker (an unknown name is an error);
+    /// 2. explicit `assumptions` win over any template's;
+    /// 3. otherwise the ticker's remembered template, if it still
+    ///    exists;
+    /// 4. otherwise [`ProjectionAssumptions::default`].
+    pub async fn resolve(
+        &self,
+        config: &ProjectionTemplatesConfig,
+        symbol: &str,
+        template: Option<&str>,
+        assumptions: Option<ProjectionAssumptions>,
+    ) -> Result<ResolvedAssumptions, ProjectionTemplateError> {
+        let chosen = match template.map(str::trim).filter(|t| !t.is_empty()) {
+            Some(name) => {
+                let found = config
+                    .find(name)
+                    .ok_or_else(|| ProjectionTemplateError::NotFound(name.to_string()))?;
+                self.choose(symbol, &found.name).await?;
+                Some(found)
+            }
+            None if assumptions.is_some() => None,
+            None => match self.chosen(symbol).await? {
+                Some(name) => {
+                    let found = config.find(&name);
+                    if found.is_none() {
+                        warn!("projection template `{name}` for {symbol} no longer exists");
+                    }
+                    found
+                }
+                None => None,
+            },
+        };
+        Ok(match (assumptions, chosen) {
+            (Some(assumptions), _) => ResolvedAssumptions {
+                assumptions,
+                template: None,
+            },
+            (None, Some(t)) => ResolvedAssumptions {
+                assumptions: t.assumptions.clone(),
+                template: Some(t.name.clone()),
+            },
+            (None, None) => ResolvedAssumptions {
+                assumptions: ProjectionAssumptions::default(),
+                template: None,
+            },
+        })
+    }
+}
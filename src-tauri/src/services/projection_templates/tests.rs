@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use tempfile::NamedTempFile;
+
+use crate::ibkr::types::ProjectionAssumptions;
+use crate::storage::Db;
+
+use super::{ProjectionTemplateError, ProjectionTemplatesConfig, TickerTemplateStore};
+
+fn open_store() -> (NamedTempFile, TickerTemplateStore) {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    (tmp, TickerTemplateStore::new(db))
+}
+
+#[test]
+fn builtins_ship_by_default_and_are_editable() {
+    let mut config = ProjectionTemplatesConfig::default();
+    let names: Vec<_> = config.templates.iter().map(|t| t.name.as_str()).collect();
+    assert!(names.contains(&"hyper-growth"));
+    assert!(names.contains(&"mature dividend payer"));
+    assert!(names.contains(&"cyclical"));
+
+    let mut mine = config.find("Cyclical").unwrap().clone();
+    mine.name = " cyclical ".to_string();
+    mine.assumptions.pe_high = 12.0;
+    config.upsert(mine).unwrap();
+    assert_eq!(config.find("cyclical").unwrap().assumptions.pe_high, 12.0);
+    assert_eq!(config.templates.len(), 5);
+
+    config.remove("CYCLICAL").unwrap();
+    assert!(config.find("cyclical").is_none());
+    assert!(matches!(
+        config.remove("cyclical"),
+        Err(ProjectionTemplateError::NotFound(_))
+    ));
+
+    // An explicit empty list in settings stays empty.
+    let parsed: ProjectionTemplatesConfig = serde_json::from_str(r#"{"templates":[]}"#).unwrap();
+    assert!(parsed.templates.is_empty());
+    let parsed: ProjectionTemplatesConfig = serde_json::from_str("{}").unwrap();
+    assert_eq!(parsed.templates.len(), 5);
+}
+
+#[tokio::test]
+async fn a_chosen_template_is_remembered_per_ticker() {
+    let (_tmp, store) = open_store();
+    let config = ProjectionTemplatesConfig::default();
+    let cyclical = config.find("cyclical").unwrap().assumptions.clone();
+
+    // Nothing chosen: defaults.
+    let r = store.resolve(&config, "ford", None, None).await.unwrap();
+    assert_eq!(r.assumptions, ProjectionAssumptions::default());
+    assert!(r.template.is_none());
+
+    let r = store
+        .resolve(&config, "ford", Some("Cyclical"), None)
+        .await
+        .unwrap();
+    assert_eq!(r.template.as_deref(), Some("cyclical"));
+    assert_eq!(r.assumptions, cyclical);
+    assert_eq!(
+        store.chosen("FORD").await.unwrap().as_deref(),
+        Some("cyclical")
+    );
+
+    // Re-opening the ticker reuses it; explicit assumptions still win.
+    let r = store.resolve(&config, "FORD", None, None).await.unwrap();
+    assert_eq!(r.assumptions, cyclical);
+    let tweaked = ProjectionAssumptions {
+        years: 3,
+        ..cyclical.clone()
+    };
+    let r = store
+        .resolve(&config, "FORD", None, Some(tweaked.clone()))
+        .await
+        .unwrap();
+    assert_eq!(r.assumptions, tweaked);
+    assert!(r.template.is_none());
+
+    assert!(matches!(
+        store.resolve(&config, "FORD", Some("nope"), None).await,
+        Err(ProjectionTemplateError::NotFound(_))
+    ));
+    assert!(store.clear("ford").await.unwrap());
+    let r = store.resolve(&config, "FORD", None, None).await.unwrap();
+    assert!(r.template.is_none());
+}
+
+#[tokio::test]
+async fn a_deleted_template_falls_back_to_defaults() {
+    let (_tmp, store) = open_store();
+    let mut config = ProjectionTemplatesConfig::default();
+    store.choose("KO", "value").await.unwrap();
+    config.remove("value").unwrap();
+    let r = store.resolve(&config, "KO", None, None).await.unwrap();
+    assert_eq!(r.assumptions, ProjectionAssumptions::default());
+    assert!(r.template.is_none());
+}
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 
 use crate::ibkr::error::Result;
-use crate::ibkr::types::{MarketDataSnapshot, Quote};
+use crate::ibkr::types::{ContractRoute, MarketDataSnapshot, Quote};
 
 /// Narrow IBKR seam for the live-quote path. Mirrors the
 /// `HistoricalDataFetcher` / `MarketScanner` pattern: the real client
@@ -12,6 +12,16 @@ use crate::ibkr::types::{MarketDataSnapshot, Quote};
 #[async_trait]
 pub trait QuoteFetcher: Send + Sync {
     async fn get_market_data_snapshot(&self, symbol: &str) -> Result<MarketDataSnapshot>;
+
+    /// Snapshot on an explicit exchange / currency. Fetchers with no
+    /// notion of venue (test stubs) ignore `route`.
+    async fn get_routed_snapshot(
+        &self,
+        symbol: &str,
+        _route: &ContractRoute,
+    ) -> Result<MarketDataSnapshot> {
+        self.get_market_data_snapshot(symbol).await
+    }
 }
 
 pub struct QuoteService {
@@ -30,6 +40,13 @@ impl QuoteService {
         let snapshot = self.fetcher.get_market_data_snapshot(symbol).await?;
         Ok(snapshot_to_quote(snapshot))
     }
+
+    /// [`Self::fetch_quote`] for a dual-listed / non-US symbol whose
+    /// venue and currency the caller knows (from `ContractDetails`).
+    pub async fn fetch_quote_routed(&self, symbol: &str, route: &ContractRoute) -> Result<Quote> {
+        let snapshot = self.fetcher.get_routed_snapshot(symbol, route).await?;
+        Ok(snapshot_to_quote(snapshot))
+    }
 }
 
 fn snapshot_to_quote(snapshot: MarketDataSnapshot) -> Quote {
@@ -38,6 +55,8 @@ fn snapshot_to_quote(snapshot: MarketDataSnapshot) -> Quote {
         last_price: snapshot.last_price,
         prev_close: snapshot.close,
         volume: snapshot.volume,
+        last_rth_price: snapshot.last_rth_price,
+        session: snapshot.session,
         timestamp: snapshot.timestamp,
     }
 }
@@ -47,6 +66,19 @@ impl QuoteFetcher for crate::ibkr::client::IbkrClient {
     async fn get_market_data_snapshot(&self, symbol: &str) -> Result<MarketDataSnapshot> {
         crate::ibkr::client::IbkrClient::get_market_data_snapshot(self, symbol).await
     }
+
+    async fn get_routed_snapshot(
+        &self,
+        symbol: &str,
+        route: &ContractRoute,
+    ) -> Result<MarketDataSnapshot> {
+        crate::ibkr::client::IbkrClient::get_market_data_snapshot_on(
+            self,
+            symbol,
+            Some(route.clone()),
+        )
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -106,6 +138,8 @@ mod tests {
             volume: Some(1_234_567),
             close: Some(149.80),
             open: Some(150.00),
+            last_rth_price: None,
+            session: None,
             timestamp: 1_730_000_000,
         }
     }
@@ -124,6 +158,24 @@ mod tests {
         assert_eq!(quote.timestamp, 1_730_000_000);
     }
 
+    #[tokio::test]
+    async fn extended_hours_quote_prices_on_the_regular_session() {
+        use crate::utils::market_calendar::TradingSession;
+
+        let mut snapshot = sample_snapshot();
+        snapshot.last_price = Some(153.10);
+        snapshot.last_rth_price = Some(150.25);
+        snapshot.session = Some(TradingSession::Post);
+        let quote = QuoteService::new(StubFetcher::ok(snapshot))
+            .fetch_quote("AAPL")
+            .await
+            .expect("ok");
+
+        assert_eq!(quote.session, Some(TradingSession::Post));
+        assert_eq!(quote.last_price, Some(153.10));
+        assert_eq!(quote.regular_price(), Some(150.25));
+    }
+
     #[tokio::test]
     async fn fetch_quote_propagates_missing_fields_as_none() {
         let mut snapshot = sample_snapshot();
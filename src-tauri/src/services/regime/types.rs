@@ -5,11 +5,12 @@
 //! fine-grain regimes overfit. Adding a fifth axis requires a written
 //! P6-backtest justification, not a casual code change.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// SPY trend axis. Computed from SPY's relationship to its 50-day and
 /// 200-day moving averages plus the 50-DMA's slope.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum TrendAxis {
     Up,
@@ -20,7 +21,7 @@ pub enum TrendAxis {
 /// Volatility axis. Computed from VIX level (or fallback) bucketed
 /// against the master-committed thresholds. Falls back to `Normal` if
 /// the VIX series is missing.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum VolAxis {
     Low,
@@ -31,7 +32,7 @@ pub enum VolAxis {
 /// Breadth axis. Computed from "% of the SP500 universe trading above
 /// its 50-day MA". Defaults to `Mixed` when the breadth proxy can't
 /// be computed (< 80% fresh-bar coverage of the universe).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum BreadthAxis {
     Healthy,
@@ -43,7 +44,7 @@ pub enum BreadthAxis {
 /// correlation across the SP500 universe; bucketed Low under 0.5 and
 /// High at-or-above 0.5. Mid-band collapsed to `Mixed` so the bucket
 /// count stays at three, consistent with the other axes.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum CorrAxis {
     Low,
@@ -94,7 +95,7 @@ impl CorrAxis {
 /// One classification of the four-axis regime. Persisted to
 /// `regime_snapshots.regime_json` as the raw read; the gate-time
 /// "stable" view applies the 3-day persistence rule on top.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub struct Regime {
     pub trend: TrendAxis,
     pub vol: VolAxis,
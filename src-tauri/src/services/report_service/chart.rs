@@ -0,0 +1,58 @@
+//! Daily-close line chart. The SVG sits beside the Markdown; the PDF
+//! draws the same [`polyline`] itself.
+
+pub const WIDTH: f64 = 480.0;
+pub const HEIGHT: f64 = 180.0;
+
+/// Close range of the series, `(low, high)`.
+pub fn range(closes: &[(i64, f64)]) -> Option<(f64, f64)> {
+    let mut values = closes.iter().map(|(_, c)| *c);
+    let first = values.next()?;
+    Some(values.fold((first, first), |(lo, hi), c| (lo.min(c), hi.max(c))))
+}
+
+/// Points of the series scaled into a `width` × `height` box, origin
+/// bottom-left. `None` with fewer than two closes.
+pub fn polyline(closes: &[(i64, f64)], width: f64, height: f64) -> Option<Vec<(f64, f64)>> {
+    if closes.len() < 2 {
+        return None;
+    }
+    let (lo, hi) = range(closes)?;
+    let t0 = closes[0].0 as f64;
+    let span_t = (closes[closes.len() - 1].0 as f64 - t0).max(1.0);
+    let span_c = (hi - lo).max(f64::EPSILON);
+    Some(
+        closes
+            .iter()
+            .map(|(t, c)| {
+                (
+                    (*t as f64 - t0) / span_t * width,
+                    (c - lo) / span_c * height,
+                )
+            })
+            .collect(),
+    )
+}
+
+pub fn svg(closes: &[(i64, f64)]) -> Option<String> {
+    let points = polyline(closes, WIDTH, HEIGHT)?;
+    let (lo, hi) = range(closes)?;
+    let path = points
+        .iter()
+        .map(|(x, y)| format!("{x:.1},{:.1}", HEIGHT - y))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"-50 -10 {vw} {vh}\">\n\
+         <rect x=\"0\" y=\"0\" width=\"{WIDTH}\" height=\"{HEIGHT}\" fill=\"none\" stroke=\"#ccc\"/>\n\
+         <polyline points=\"{path}\" fill=\"none\" stroke=\"#1f6feb\" stroke-width=\"1.5\"/>\n\
+         <text x=\"-4\" y=\"10\" font-size=\"10\" text-anchor=\"end\">{hi:.2}</text>\n\
+         <text x=\"-4\" y=\"{HEIGHT}\" font-size=\"10\" text-anchor=\"end\">{lo:.2}</text>\n\
+         </svg>\n",
+        w = WIDTH + 60.0,
+        h = HEIGHT + 20.0,
+        vw = WIDTH + 60.0,
+        vh = HEIGHT + 20.0,
+    ))
+}
@@ -0,0 +1,241 @@
+//! Per-ticker research report, rendered to Markdown and PDF.
+//!
+//! A report pulls together what the app already knows about a symbol:
+//! the company overview and historicals (`FundamentalsProvider`), the
+//! newest stored projection (`services::projection_history`) and a
+//! one-year daily close chart read from `bars_cache` through the
+//! backtester's `BarsReader` seam — never a live IBKR fetch, so a
+//! symbol without cached bars just gets no chart. Target, stop and
+//! thesis set on positions in the symbol (`services::position_plans`)
+//! get their own section.
+//!
+//! Markdown comes from the `report.md.j2` template (minijinja) over a
+//! pre-formatted [`view::ReportView`]; the chart is saved beside it as
+//! an SVG the Markdown embeds. The PDF is written by [`pdf`] from the
+//! same Markdown lines with the chart drawn natively, so both files say
+//! the same thing. Files land in `<app data>/reports/` as
+//! `{SYMBOL}-{YYYYMMDD}.{md,pdf}`; re-running the same day overwrites.
+//!
+//! `attach_to_journal` adds a `Research report: {SYMBOL}` section to
+//! that day's `journal_entries` (see `services::journal_writer`)
+//! pointing at the files.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use minijinja::Environment;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::ibkr::types::historical::{parse_ibkr_time, BarSize};
+use crate::services::backtester::BarsReader;
+use crate::services::fundamentals_provider::{FundamentalsError, FundamentalsProvider};
+use crate::services::journal_writer::{self, JournalWriterError, NewJournalEntry};
+use crate::services::position_plans;
+use crate::services::projection_history::ProjectionHistoryStore;
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+pub mod chart;
+pub mod pdf;
+pub mod view;
+
+#[cfg(test)]
+mod tests;
+
+const TEMPLATE: &str = include_str!("report.md.j2");
+
+/// Days of daily closes the chart covers.
+const CHART_LOOKBACK_DAYS: i64 = 365;
+
+/// `written_by` of the journal section a report is attached as.
+pub const JOURNAL_AUTHOR: &str = "report_service";
+
+#[derive(Error, Debug)]
+pub enum ReportError {
+    #[error("symbol must be non-empty")]
+    EmptySymbol,
+    #[error("fundamentals: {0}")]
+    Fundamentals(#[from] FundamentalsError),
+    #[error("template: {0}")]
+    Template(#[from] minijinja::Error),
+    #[error("write {path:?}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("journal: {0}")]
+    Journal(#[from] JournalWriterError),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+}
+
+/// Where one report was written.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportArtifact {
+    pub symbol: String,
+    pub markdown_path: PathBuf,
+    pub pdf_path: PathBuf,
+    /// `None` when `bars_cache` had no daily bars for the symbol.
+    pub chart_path: Option<PathBuf>,
+    /// `journal_entries.id` of the section, when attached.
+    pub journal_entry_id: Option<i64>,
+    pub generated_at: DateTime<Utc>,
+}
+
+pub struct ReportService {
+    fundamentals: Arc<dyn FundamentalsProvider>,
+    history: Arc<ProjectionHistoryStore>,
+    bars: Arc<dyn BarsReader>,
+    db: Arc<Db>,
+    dir: PathBuf,
+}
+
+impl ReportService {
+    pub fn new(
+        fundamentals: Arc<dyn FundamentalsProvider>,
+        history: Arc<ProjectionHistoryStore>,
+        bars: Arc<dyn BarsReader>,
+        db: Arc<Db>,
+        dir: PathBuf,
+    ) -> Self {
+        Self {
+            fundamentals,
+            history,
+            bars,
+            db,
+            dir,
+        }
+    }
+
+    /// Render and save the report for `symbol`, optionally adding it to
+    /// today's journal.
+    pub async fn generate(
+        &self,
+        symbol: &str,
+        attach_to_journal: bool,
+    ) -> Result<ReportArtifact, ReportError> {
+        let symbol = symbol.trim().to_uppercase();
+        if symbol.is_empty() {
+            return Err(ReportError::EmptySymbol);
+        }
+        let now = Utc::now();
+
+        let fundamentals = self.fundamentals.fetch(&symbol).await?;
+        let snapshot = self
+            .history
+            .history(&symbol, Some(1))
+            .await?
+            .pop()
+            .map(|e| e.snapshot);
+        let closes = self.closes(&symbol, now).await;
+
+        std::fs::create_dir_all(&self.dir).map_err(|source| ReportError::Io {
+            path: self.dir.clone(),
+            source,
+        })?;
+        let stem = format!("{symbol}-{}", now.format("%Y%m%d"));
+
+        let chart_path = match chart::svg(&closes) {
+            Some(svg) => {
+                let path = self.dir.join(format!("{stem}-price.svg"));
+                write(&path, svg.as_bytes())?;
+                Some(path)
+            }
+            None => None,
+        };
+        let chart_file = chart_path
+            .as_deref()
+            .and_then(Path::file_name)
+            .map(|f| f.to_string_lossy().into_owned());
+
+        let mut view = view::ReportView::new(&fundamentals, snapshot.as_ref(), chart_file, now);
+        view.plans = position_plans::plans_for_symbol(&self.db, &symbol)
+            .await?
+            .iter()
+            .map(view::PlanRow::new)
+            .collect();
+        let markdown = render_markdown(&view)?;
+        let markdown_path = self.dir.join(format!("{stem}.md"));
+        write(&markdown_path, markdown.as_bytes())?;
+
+        let pdf_path = self.dir.join(format!("{stem}.pdf"));
+        write(&pdf_path, &pdf::render(&markdown, &closes))?;
+
+        let journal_entry_id = if attach_to_journal {
+            let entry = journal_writer::upsert_entry(
+                &self.db,
+                NewJournalEntry {
+                    journal_date: now.date_naive(),
+                    section: format!("Research report: {symbol}"),
+                    body_md: journal_body(&view, &markdown_path, &pdf_path),
+                    written_by: JOURNAL_AUTHOR.to_string(),
+                },
+            )
+            .await?;
+            Some(entry.id)
+        } else {
+            None
+        };
+
+        info!("report_service: wrote {}", markdown_path.display());
+        Ok(ReportArtifact {
+            symbol,
+            markdown_path,
+            pdf_path,
+            chart_path,
+            journal_entry_id,
+            generated_at: now,
+        })
+    }
+
+    /// `(unix seconds, close)` of the cached daily bars over the chart
+    /// window. A cache read failure only costs the chart.
+    async fn closes(&self, symbol: &str, now: DateTime<Utc>) -> Vec<(i64, f64)> {
+        let start = (now - Duration::days(CHART_LOOKBACK_DAYS)).timestamp();
+        match self
+            .bars
+            .read_window(symbol, BarSize::Day1, start, now.timestamp())
+            .await
+        {
+            Ok(bars) => bars
+                .iter()
+                .filter_map(|b| parse_ibkr_time(&b.time).ok().map(|t| (t, b.close)))
+                .collect(),
+            Err(e) => {
+                warn!("report_service: bars for {symbol} unavailable: {e}");
+                Vec::new()
+            }
+        }
+    }
+}
+
+pub fn render_markdown(view: &view::ReportView) -> Result<String, ReportError> {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.add_template("report", TEMPLATE)?;
+    Ok(env.get_template("report")?.render(view)?)
+}
+
+fn journal_body(view: &view::ReportView, markdown: &Path, pdf: &Path) -> String {
+    let mut body = format!(
+        "- Markdown: `{}`\n- PDF: `{}`\n",
+        markdown.display(),
+        pdf.display()
+    );
+    if let Some(headline) = &view.headline {
+        body.push_str(&format!("\n{headline}\n"));
+    }
+    body
+}
+
+fn write(path: &Path, bytes: &[u8]) -> Result<(), ReportError> {
+    std::fs::write(path, bytes).map_err(|source| ReportError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
@@ -0,0 +1,231 @@
+//! Minimal PDF writer for the report: the rendered Markdown laid out in
+//! Courier (tables padded into columns, headings bold) on US-letter
+//! pages, with the price chart drawn as vector lines where the Markdown
+//! embeds the image. Only the standard 14 fonts are used, so text
+//! outside Latin-1 is transliterated or dropped.
+
+use super::chart;
+
+const PAGE_WIDTH: f64 = 612.0;
+const PAGE_HEIGHT: f64 = 792.0;
+const MARGIN: f64 = 50.0;
+const FONT_SIZE: f64 = 9.0;
+const LEADING: f64 = 12.0;
+/// Courier characters that fit between the margins at `FONT_SIZE`.
+const WRAP_CHARS: usize = 94;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Line {
+    Text(String),
+    Heading(String),
+    Chart,
+}
+
+/// Markdown to printable lines.
+pub fn layout(markdown: &str) -> Vec<Line> {
+    let mut out = Vec::new();
+    let mut table: Vec<Vec<String>> = Vec::new();
+    for raw in markdown.lines() {
+        let line = raw.trim_end();
+        if line.starts_with('|') {
+            let cells: Vec<String> = line
+                .trim_matches('|')
+                .split('|')
+                .map(|c| c.trim().to_string())
+                .collect();
+            if !cells.iter().all(|c| c.chars().all(|ch| ch == '-')) {
+                table.push(cells);
+            }
+            continue;
+        }
+        flush_table(&mut table, &mut out);
+        if let Some(heading) = line.strip_prefix('#') {
+            out.push(Line::Heading(
+                heading.trim_start_matches('#').trim().to_string(),
+            ));
+        } else if line.starts_with("![") {
+            out.push(Line::Chart);
+        } else {
+            out.extend(
+                wrap(&strip_emphasis(line), WRAP_CHARS)
+                    .into_iter()
+                    .map(Line::Text),
+            );
+        }
+    }
+    flush_table(&mut table, &mut out);
+    out
+}
+
+fn flush_table(table: &mut Vec<Vec<String>>, out: &mut Vec<Line>) {
+    let mut widths: Vec<usize> = Vec::new();
+    for row in table.iter() {
+        for (i, cell) in row.iter().enumerate() {
+            let len = cell.chars().count();
+            match widths.get_mut(i) {
+                Some(w) => *w = (*w).max(len),
+                None => widths.push(len),
+            }
+        }
+    }
+    for row in table.drain(..) {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(i, c)| format!("{c:<w$}", w = widths[i]))
+            .collect();
+        out.push(Line::Text(cells.join("  ").trim_end().to_string()));
+    }
+}
+
+/// Split prose longer than `width` at spaces; a single longer word
+/// stays whole.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    if line.chars().count() <= width {
+        return vec![line.to_string()];
+    }
+    let mut out = Vec::new();
+    let mut current = String::new();
+    for word in line.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+            out.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        out.push(current);
+    }
+    out
+}
+
+fn strip_emphasis(line: &str) -> String {
+    let line = line.replace("**", "");
+    match line.strip_prefix('_').and_then(|l| l.strip_suffix('_')) {
+        Some(inner) => inner.to_string(),
+        None => line,
+    }
+}
+
+/// Render `markdown` (and the chart, if it has one) to PDF bytes.
+pub fn render(markdown: &str, closes: &[(i64, f64)]) -> Vec<u8> {
+    let chart_height = chart::HEIGHT + LEADING;
+    let mut pages: Vec<String> = Vec::new();
+    let mut content = String::new();
+    let mut y = PAGE_HEIGHT - MARGIN;
+    for line in layout(markdown) {
+        let needed = match line {
+            Line::Chart => chart_height,
+            _ => LEADING,
+        };
+        if y - needed < MARGIN {
+            pages.push(std::mem::take(&mut content));
+            y = PAGE_HEIGHT - MARGIN;
+        }
+        match line {
+            Line::Text(text) => content.push_str(&text_op("F1", y, &text)),
+            Line::Heading(text) => content.push_str(&text_op("F2", y, &text)),
+            Line::Chart => {
+                if let Some(ops) = chart_ops(closes, y - chart::HEIGHT) {
+                    content.push_str(&ops);
+                    y -= chart::HEIGHT;
+                }
+            }
+        }
+        y -= LEADING;
+    }
+    pages.push(content);
+    assemble(&pages)
+}
+
+fn text_op(font: &str, y: f64, text: &str) -> String {
+    format!(
+        "BT /{font} {FONT_SIZE} Tf {MARGIN} {y:.1} Td ({}) Tj ET\n",
+        escape(text)
+    )
+}
+
+fn chart_ops(closes: &[(i64, f64)], bottom: f64) -> Option<String> {
+    let points = chart::polyline(closes, chart::WIDTH, chart::HEIGHT)?;
+    let mut ops = format!(
+        "0.8 G 0.5 w {MARGIN} {bottom:.1} {} {} re S\n0.12 0.44 0.92 RG 1 w\n",
+        chart::WIDTH,
+        chart::HEIGHT
+    );
+    for (i, (x, y)) in points.iter().enumerate() {
+        let op = if i == 0 { "m" } else { "l" };
+        ops.push_str(&format!("{:.1} {:.1} {op}\n", MARGIN + x, bottom + y));
+    }
+    ops.push_str("S 0 G\n");
+    Some(ops)
+}
+
+/// PDF string literal body: Latin-1 only, with `\`, `(` and `)` escaped.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '(' | ')' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '—' | '–' => out.push('-'),
+            c if c.is_ascii() && !c.is_ascii_control() => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Catalog, page tree, two fonts, then a page + content stream per page.
+fn assemble(pages: &[String]) -> Vec<u8> {
+    let mut objects: Vec<String> = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        String::new(), // page tree, filled once the page ids are known
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier-Bold >>".to_string(),
+    ];
+    let mut kids = Vec::new();
+    for content in pages {
+        let page_id = objects.len() + 1;
+        kids.push(format!("{page_id} 0 R"));
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+             /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+            page_id + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{content}endstream",
+            content.len()
+        ));
+    }
+    objects[1] = format!(
+        "<< /Type /Pages /Kids [{}] /Count {} >>",
+        kids.join(" "),
+        pages.len()
+    );
+
+    let mut out = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n{object}\nendobj\n", i + 1).as_bytes());
+    }
+    let xref = out.len();
+    out.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        out.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    out.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .as_bytes(),
+    );
+    out
+}
@@ -0,0 +1,197 @@
+use async_trait::async_trait;
+use tempfile::{NamedTempFile, TempDir};
+
+use super::*;
+use crate::ibkr::types::historical::HistoricalBar;
+use crate::ibkr::types::ProjectionAssumptions;
+use crate::services::fundamentals_provider::test_support::FakeFundamentalsProvider;
+use crate::services::projection_service::ProjectionService;
+use crate::storage::error::Result as StorageResult;
+
+struct CannedBars(Vec<HistoricalBar>);
+
+#[async_trait]
+impl BarsReader for CannedBars {
+    async fn read_window(
+        &self,
+        _symbol: &str,
+        _bar_size: BarSize,
+        _start_unix: i64,
+        _end_unix_inclusive: i64,
+    ) -> StorageResult<Vec<HistoricalBar>> {
+        Ok(self.0.clone())
+    }
+}
+
+fn bar(day: &str, close: f64) -> HistoricalBar {
+    HistoricalBar {
+        time: day.to_string(),
+        open: close,
+        high: close,
+        low: close,
+        close,
+        volume: 1_000,
+        wap: close,
+        count: 0,
+    }
+}
+
+struct Fixture {
+    _db_file: NamedTempFile,
+    dir: TempDir,
+    db: Arc<Db>,
+    service: ReportService,
+}
+
+async fn fixture(bars: Vec<HistoricalBar>, with_projection: bool) -> Fixture {
+    let db_file = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(db_file.path()).unwrap());
+    let data = ProjectionService::generate_mock_fundamental_data("NVDA");
+    let fundamentals = Arc::new(FakeFundamentalsProvider::new());
+    fundamentals.insert("NVDA", data.clone());
+    let history = Arc::new(ProjectionHistoryStore::new(Arc::clone(&db)));
+    if with_projection {
+        let assumptions = ProjectionAssumptions::default();
+        let results = ProjectionService::generate_projection_results(&data, &assumptions).unwrap();
+        history
+            .record("NVDA", &assumptions, &results)
+            .await
+            .unwrap();
+    }
+    let dir = TempDir::new().unwrap();
+    let service = ReportService::new(
+        fundamentals,
+        history,
+        Arc::new(CannedBars(bars)),
+        Arc::clone(&db),
+        dir.path().join("reports"),
+    );
+    Fixture {
+        _db_file: db_file,
+        dir,
+        db,
+        service,
+    }
+}
+
+#[tokio::test]
+async fn writes_markdown_pdf_and_chart() {
+    let bars = vec![
+        bar("20260102", 100.0),
+        bar("20260105", 110.0),
+        bar("20260106", 105.0),
+    ];
+    let f = fixture(bars, true).await;
+
+    let artifact = f.service.generate("nvda", false).await.unwrap();
+    assert_eq!(artifact.symbol, "NVDA");
+    assert!(artifact.markdown_path.starts_with(f.dir.path()));
+    assert!(artifact.journal_entry_id.is_none());
+
+    let md = std::fs::read_to_string(&artifact.markdown_path).unwrap();
+    assert!(md.starts_with("# NVDA"));
+    assert!(md.contains("## Historicals"));
+    assert!(md.contains("| Year | Scenario |"));
+    assert!(md.contains("| Base | "));
+    let chart = artifact.chart_path.unwrap();
+    let chart_name = chart.file_name().unwrap().to_string_lossy().into_owned();
+    assert!(md.contains(&format!("]({chart_name})")));
+    assert!(std::fs::read_to_string(&chart)
+        .unwrap()
+        .contains("<polyline"));
+
+    let pdf = std::fs::read(&artifact.pdf_path).unwrap();
+    assert!(pdf.starts_with(b"%PDF-1.4"));
+    assert!(pdf.ends_with(b"%%EOF\n"));
+}
+
+#[tokio::test]
+async fn no_projection_or_bars_still_renders() {
+    let f = fixture(Vec::new(), false).await;
+
+    let artifact = f.service.generate("NVDA", false).await.unwrap();
+    assert!(artifact.chart_path.is_none());
+    let md = std::fs::read_to_string(&artifact.markdown_path).unwrap();
+    assert!(md.contains("No projection has been stored for NVDA yet."));
+    assert!(!md.contains("## Price"));
+}
+
+#[tokio::test]
+async fn attaching_adds_a_journal_section() {
+    let f = fixture(Vec::new(), true).await;
+
+    let artifact = f.service.generate("NVDA", true).await.unwrap();
+    let entries = journal_writer::list_entries_for_date(&f.db, artifact.generated_at.date_naive())
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(Some(entries[0].id), artifact.journal_entry_id);
+    assert_eq!(entries[0].section, "Research report: NVDA");
+    assert_eq!(entries[0].written_by, JOURNAL_AUTHOR);
+    assert!(entries[0]
+        .body_md
+        .contains(&artifact.pdf_path.display().to_string()));
+}
+
+#[tokio::test]
+async fn unknown_symbol_surfaces_the_provider_error() {
+    let f = fixture(Vec::new(), false).await;
+    assert!(matches!(
+        f.service.generate("ZZZZ", false).await,
+        Err(ReportError::Fundamentals(FundamentalsError::NotFound(_)))
+    ));
+    assert!(matches!(
+        f.service.generate("  ", false).await,
+        Err(ReportError::EmptySymbol)
+    ));
+}
+
+#[test]
+fn pdf_layout_pads_tables_and_skips_separators() {
+    let lines = pdf::layout("## Title\n\n| A | Long header |\n|---|---|\n| 1 | x |\n_note_");
+    assert_eq!(
+        lines,
+        vec![
+            pdf::Line::Heading("Title".into()),
+            pdf::Line::Text(String::new()),
+            pdf::Line::Text("A  Long header".into()),
+            pdf::Line::Text("1  x".into()),
+            pdf::Line::Text("note".into()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn lists_position_plans_for_the_symbol() {
+    let f = fixture(Vec::new(), false).await;
+    f.db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO position_plans \
+               (account, contract, symbol, target_price, stop_price, thesis, updated_at) \
+             VALUES ('DU1', 'NVDA', 'NVDA', 180.0, NULL, 'Data-centre capex cycle.', 0), \
+                    ('DU1', 'AAPL', 'AAPL', 250.0, 190.0, NULL, 0)",
+            [],
+        )?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    let artifact = f.service.generate("NVDA", false).await.unwrap();
+    let md = std::fs::read_to_string(&artifact.markdown_path).unwrap();
+    assert!(md.contains("## Position plan"));
+    assert!(md.contains("| DU1 | NVDA | $180.00 | — |"));
+    assert!(md.contains("NVDA thesis: Data-centre capex cycle."));
+    assert!(!md.contains("AAPL"));
+}
+
+#[test]
+fn pdf_layout_wraps_long_prose() {
+    let prose = "word ".repeat(40);
+    let lines = pdf::layout(prose.trim_end());
+    assert_eq!(lines.len(), 3);
+    assert!(lines.iter().all(|l| match l {
+        pdf::Line::Text(t) => t.chars().count() <= 94,
+        _ => false,
+    }));
+}
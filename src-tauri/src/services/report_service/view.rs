@@ -0,0 +1,249 @@
+//! What the report template sees. Every number is formatted here so the
+//! template only lays text out.
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+
+use crate::ibkr::types::{FinancialProjection, FundamentalData};
+use crate::services::position_plans::PositionPlan;
+use crate::services::projection_history::ProjectionSnapshot;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportView {
+    pub symbol: String,
+    pub name: Option<String>,
+    pub generated: String,
+    pub overview: Vec<Fact>,
+    pub historical: Vec<HistoricalRow>,
+    pub projection: Option<ProjectionView>,
+    /// File name of the price chart, relative to the Markdown file.
+    pub chart_file: Option<String>,
+    /// One-line base-case summary.
+    pub headline: Option<String>,
+    /// Target / stop / thesis set on positions in the symbol.
+    pub plans: Vec<PlanRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Fact {
+    pub label: &'static str,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoricalRow {
+    pub year: u32,
+    pub revenue: String,
+    pub revenue_growth: String,
+    pub net_income: String,
+    pub net_margin: String,
+    pub eps: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectionView {
+    pub generated: String,
+    pub baseline_year: u32,
+    pub rows: Vec<ProjectionRow>,
+    pub cagr: Vec<CagrRow>,
+    pub expected: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectionRow {
+    pub year: u32,
+    pub scenario: &'static str,
+    pub revenue: String,
+    pub net_income: String,
+    pub eps: String,
+    pub multiple: String,
+    pub price_range: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanRow {
+    pub account: String,
+    pub contract: String,
+    pub target: String,
+    pub stop: String,
+    pub thesis: Option<String>,
+}
+
+impl PlanRow {
+    pub fn new(plan: &PositionPlan) -> Self {
+        let level = |v: Option<f64>| v.map(money).unwrap_or_else(|| "—".to_string());
+        Self {
+            account: plan.account.clone(),
+            contract: plan.contract.clone(),
+            target: level(plan.target_price),
+            stop: level(plan.stop_price),
+            thesis: plan.thesis.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CagrRow {
+    pub scenario: &'static str,
+    pub revenue: String,
+    pub share_price: String,
+}
+
+impl ReportView {
+    pub fn new(
+        fundamentals: &FundamentalData,
+        snapshot: Option<&ProjectionSnapshot>,
+        chart_file: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let metrics = &fundamentals.current_metrics;
+        let currency = fundamentals.currency.as_deref().unwrap_or("USD");
+        let mut overview = Vec::new();
+        if let Some(exchange) = &metrics.exchange {
+            overview.push(fact("Exchange", exchange.clone()));
+        }
+        overview.push(fact("Currency", currency.to_string()));
+        if let Some(price) = metrics.price {
+            overview.push(fact("Price", money(price)));
+        }
+        if let Some(cap) = &metrics.market_cap {
+            overview.push(fact("Market cap", cap.clone()));
+        }
+        overview.push(fact("P/E", format!("{:.1}", metrics.pe_ratio)));
+        overview.push(fact(
+            "Shares outstanding",
+            format!("{:.1}M", metrics.shares_outstanding),
+        ));
+        if let Some(dy) = metrics.dividend_yield {
+            overview.push(fact("Dividend yield", pct(dy)));
+        }
+
+        let mut years: Vec<_> = fundamentals.historical.iter().collect();
+        years.sort_by_key(|h| h.year);
+        let historical = years
+            .into_iter()
+            .map(|h| {
+                let growth = fundamentals
+                    .growth
+                    .as_ref()
+                    .and_then(|g| g.years.iter().find(|y| y.year == h.year));
+                HistoricalRow {
+                    year: h.year,
+                    revenue: billions(h.revenue),
+                    revenue_growth: opt_pct(growth.and_then(|g| g.revenue_growth)),
+                    net_income: billions(h.net_income),
+                    net_margin: opt_pct(growth.and_then(|g| g.net_margin)),
+                    eps: money(h.eps),
+                }
+            })
+            .collect();
+
+        let projection = snapshot.map(projection_view);
+        let headline = snapshot.and_then(|s| {
+            let last = s.results.projections.last()?;
+            Some(format!(
+                "Base case {} target {} ({} CAGR).",
+                last.year,
+                price_range(&last.base),
+                pct(s.results.cagr.base.share_price)
+            ))
+        });
+
+        Self {
+            symbol: fundamentals.symbol.clone(),
+            name: metrics.name.clone(),
+            generated: now.format("%Y-%m-%d %H:%M UTC").to_string(),
+            overview,
+            historical,
+            projection,
+            chart_file,
+            headline,
+            plans: Vec::new(),
+        }
+    }
+}
+
+fn projection_view(snapshot: &ProjectionSnapshot) -> ProjectionView {
+    let results = &snapshot.results;
+    let mut rows = Vec::new();
+    for year in &results.projections {
+        for (scenario, p) in [
+            ("Bear", &year.bear),
+            ("Base", &year.base),
+            ("Bull", &year.bull),
+        ] {
+            rows.push(ProjectionRow {
+                year: year.year,
+                scenario,
+                revenue: billions(p.revenue),
+                net_income: billions(p.net_income),
+                eps: money(p.eps),
+                multiple: multiple(p),
+                price_range: price_range(p),
+            });
+        }
+    }
+    let cagr = [
+        ("Bear", &results.cagr.bear),
+        ("Base", &results.cagr.base),
+        ("Bull", &results.cagr.bull),
+    ]
+    .into_iter()
+    .map(|(scenario, c)| CagrRow {
+        scenario,
+        revenue: pct(c.revenue),
+        share_price: pct(c.share_price),
+    })
+    .collect();
+    let expected = results.expected.as_ref().map(|e| {
+        format!(
+            "Probability-weighted {} target {} ({} CAGR).",
+            e.year,
+            money(e.target_price),
+            pct(e.cagr.share_price)
+        )
+    });
+    ProjectionView {
+        generated: Utc
+            .timestamp_opt(snapshot.generated_at, 0)
+            .single()
+            .map(|t| t.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        baseline_year: results.baseline.year,
+        rows,
+        cagr,
+        expected,
+    }
+}
+
+fn fact(label: &'static str, value: String) -> Fact {
+    Fact { label, value }
+}
+
+fn multiple(p: &FinancialProjection) -> String {
+    match (p.ps_low_est, p.ps_high_est) {
+        (Some(lo), Some(hi)) if p.valuation_method == "P/S" => format!("P/S {lo:.1}–{hi:.1}"),
+        _ => format!("P/E {:.1}–{:.1}", p.pe_low_est, p.pe_high_est),
+    }
+}
+
+fn price_range(p: &FinancialProjection) -> String {
+    format!("{}–{}", money(p.share_price_low), money(p.share_price_high))
+}
+
+fn money(v: f64) -> String {
+    format!("${v:.2}")
+}
+
+/// Figures are stored in billions.
+fn billions(v: f64) -> String {
+    format!("${v:.2}B")
+}
+
+fn pct(v: f64) -> String {
+    format!("{v:.1}%")
+}
+
+fn opt_pct(v: Option<f64>) -> String {
+    v.map(pct).unwrap_or_else(|| "—".to_string())
+}
@@ -20,6 +20,7 @@ use tracing::warn;
 
 use crate::ibkr::client::IbkrClient;
 use crate::ibkr::error::IbkrError;
+use crate::services::liquidity::LiquidityService;
 use crate::services::tilt_guard::{TiltError, TiltGuardService};
 use crate::strategies::SetupCandidate;
 
@@ -28,7 +29,7 @@ mod sizing;
 mod types;
 
 pub use equity_snapshot::{EquityFetcher, EquitySnapshotError, EquitySnapshotService};
-pub use sizing::compute_sizing;
+pub use sizing::{cap_to_liquidity, compute_sizing};
 // `EquitySource` is part of `EquitySnapshot`'s public shape; the
 // re-export keeps callers outside this module from having to dig
 // into the private `types` submodule.
@@ -83,6 +84,10 @@ pub struct RiskEngine {
     /// Optional so existing tests + the bootstrap path before the
     /// service is wired keep working.
     tilt_guard: Option<Arc<TiltGuardService>>,
+    /// When set, sizings are clipped to what the name's volume could
+    /// absorb on the way out. Fails open: a volume lookup error sizes
+    /// without the cap.
+    liquidity: Option<Arc<LiquidityService>>,
 }
 
 impl RiskEngine {
@@ -96,6 +101,7 @@ impl RiskEngine {
             account_source,
             config: Arc::new(RwLock::new(config)),
             tilt_guard: None,
+            liquidity: None,
         }
     }
 
@@ -106,6 +112,13 @@ impl RiskEngine {
         self
     }
 
+    /// Builder — opt the engine into the liquidity cap. Wired in
+    /// `lib.rs::run` alongside the tilt guard.
+    pub fn with_liquidity(mut self, liquidity: Arc<LiquidityService>) -> Self {
+        self.liquidity = Some(liquidity);
+        self
+    }
+
     /// Read a clone of the live config. Hot-reload safe — the
     /// returned struct is a snapshot, mutating the engine after
     /// this point doesn't affect the caller's copy.
@@ -121,13 +134,15 @@ impl RiskEngine {
     }
 
     /// Resolve the active account, fetch its current equity snapshot,
-    /// and run `compute_sizing`. Snapshot lookup follows the
+    /// and run `compute_sizing` for `symbol`, capped by its liquidity
+    /// when the engine has it. Snapshot lookup follows the
     /// fresh-then-stale fallback policy in `EquitySnapshotService`.
     /// Returns the `EquitySnapshot` alongside `Sizing` so callers
     /// (commands, runner) can persist and emit it without a second
     /// DB hop.
     pub async fn size_for_candidate(
         &self,
+        symbol: &str,
         candidate: &SetupCandidate,
     ) -> Result<(Sizing, EquitySnapshot)> {
         let account = self.account_source.current_account().await?;
@@ -155,7 +170,16 @@ impl RiskEngine {
             }
         }
 
-        let sizing = compute_sizing(candidate, &snapshot, &cfg);
+        let mut sizing = compute_sizing(candidate, &snapshot, &cfg);
+        if let (Some(liquidity), false) = (&self.liquidity, sizing.is_skipped()) {
+            match liquidity.max_order_shares(symbol).await {
+                Ok(Some(max_qty)) => sizing = cap_to_liquidity(sizing, max_qty, &cfg),
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("risk_engine: liquidity for {symbol} failed: {e} — sizing uncapped")
+                }
+            }
+        }
         if let Some(reason) = sizing.skipped_reason {
             warn!(
                 "risk_engine: skipped sizing for {} ({:?}) — equity={}c, grade={:?}",
@@ -239,7 +263,7 @@ mod tests {
     async fn size_for_candidate_returns_a_grade_sizing() {
         let (_tmp, engine) = engine_with_nlv(100_000.0);
         let (sizing, snap) = engine
-            .size_for_candidate(&cand(0.9, 105.0, 100.0))
+            .size_for_candidate("AAPL", &cand(0.9, 105.0, 100.0))
             .await
             .unwrap();
         assert_eq!(sizing.qty, 100);
@@ -255,7 +279,7 @@ mod tests {
         cfg.risk_pct_a = 0.01; // 1% — double the default.
         engine.set_config(cfg).await;
         let (sizing, _) = engine
-            .size_for_candidate(&cand(0.9, 105.0, 100.0))
+            .size_for_candidate("AAPL", &cand(0.9, 105.0, 100.0))
             .await
             .unwrap();
         // 1% * 100k / $5 = 200 sh.
@@ -266,11 +290,11 @@ mod tests {
     async fn snapshot_is_cached_across_size_calls() {
         let (_tmp, engine) = engine_with_nlv(100_000.0);
         let (s1, snap1) = engine
-            .size_for_candidate(&cand(0.9, 105.0, 100.0))
+            .size_for_candidate("AAPL", &cand(0.9, 105.0, 100.0))
             .await
             .unwrap();
         let (s2, snap2) = engine
-            .size_for_candidate(&cand(0.9, 110.0, 105.0))
+            .size_for_candidate("AAPL", &cand(0.9, 110.0, 105.0))
             .await
             .unwrap();
         // Same as_of_date implies same trading day cache hit.
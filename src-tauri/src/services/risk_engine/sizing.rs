@@ -121,6 +121,35 @@ pub fn compute_sizing(
     }
 }
 
+/// Clip `sizing` to `max_qty`, the most shares the name's volume could
+/// absorb on the way out (see `services::liquidity`). Floors to the
+/// round lot like the notional cap; when not even one lot fits, the
+/// setup is skipped as `Illiquid`. Pure, like `compute_sizing`.
+pub fn cap_to_liquidity(sizing: Sizing, max_qty: f64, cfg: &RiskConfig) -> Sizing {
+    if sizing.is_skipped() || sizing.qty as f64 <= max_qty {
+        return sizing;
+    }
+    let capped = max_qty.floor();
+    let qty = if capped.is_finite() && capped > 0.0 {
+        round_to_lot(capped as u32, cfg.round_lot)
+    } else {
+        0
+    };
+    if qty == 0 {
+        return Sizing::skipped(
+            SizingSkippedReason::Illiquid,
+            sizing.equity_at_decision_cents,
+            sizing.conviction_grade,
+        );
+    }
+    Sizing {
+        qty,
+        dollar_risk_cents: qty as i64 * sizing.r_per_share_cents,
+        cap_applied: true,
+        ..sizing
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -328,6 +357,28 @@ mod tests {
         assert_eq!(s.dollar_risk_cents, 40_000);
     }
 
+    // --- liquidity cap ---
+
+    #[test]
+    fn liquidity_cap_clips_to_round_lots() {
+        // A = 100 sh at $5 R; the volume allows 57.9 -> 50 in lots of 10.
+        let snap = snapshot(100_000.0);
+        let cfg = RiskConfig {
+            round_lot: 10,
+            ..RiskConfig::default()
+        };
+        let sized = compute_sizing(&candidate(0.9, Direction::Long, 105.0, 100.0), &snap, &cfg);
+        let s = cap_to_liquidity(sized.clone(), 57.9, &cfg);
+        assert_eq!(s.qty, 50);
+        assert_eq!(s.dollar_risk_cents, 25_000);
+        assert!(s.cap_applied);
+        assert_eq!(cap_to_liquidity(sized.clone(), 500.0, &cfg), sized);
+
+        let s = cap_to_liquidity(sized, 9.0, &cfg);
+        assert_eq!(s.skipped_reason, Some(SizingSkippedReason::Illiquid));
+        assert_eq!(s.equity_at_decision_cents, 10_000_000);
+    }
+
     // --- skipped variants are not silently sized ---
 
     #[test]
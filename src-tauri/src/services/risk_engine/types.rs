@@ -12,6 +12,7 @@
 //! C=0.16%, max_position_pct=0.25, min_dollar_risk=$10.
 
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// `Sizing` schema revision. Bump when `compute_sizing` changes
@@ -25,7 +26,7 @@ pub const SIZING_VERSION: i32 = 1;
 /// via [`ConvictionGrade::from_signal`]; the LLM thesis (P17) can
 /// later override on a per-setup basis but P1 sizes from the
 /// detector signal alone.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Hash, JsonSchema)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum ConvictionGrade {
     A,
@@ -133,7 +134,7 @@ impl EquitySnapshot {
 /// Why the engine refused to size this setup. Persisted on the
 /// row's `sizing_skipped_reason` so the UI can render an explicit
 /// "skipped: below_min_risk" badge instead of a phantom zero.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum SizingSkippedReason {
     /// `r_per_share` is zero or non-finite (defensive — detectors
@@ -153,6 +154,9 @@ pub enum SizingSkippedReason {
     TiltPaused,
     /// Trigger or stop is non-finite or non-positive.
     InvalidPrice,
+    /// Not even one round lot could be sold again within
+    /// `liquidity.max_days` at the participation cap.
+    Illiquid,
 }
 
 impl SizingSkippedReason {
@@ -163,6 +167,7 @@ impl SizingSkippedReason {
             SizingSkippedReason::StaleSnapshot => "stale_snapshot",
             SizingSkippedReason::TiltPaused => "tilt_paused",
             SizingSkippedReason::InvalidPrice => "invalid_price",
+            SizingSkippedReason::Illiquid => "illiquid",
         }
     }
 
@@ -173,6 +178,7 @@ impl SizingSkippedReason {
             "stale_snapshot" => Some(SizingSkippedReason::StaleSnapshot),
             "tilt_paused" => Some(SizingSkippedReason::TiltPaused),
             "invalid_price" => Some(SizingSkippedReason::InvalidPrice),
+            "illiquid" => Some(SizingSkippedReason::Illiquid),
             _ => None,
         }
     }
@@ -231,7 +237,7 @@ impl RiskConfig {
 /// Output of `compute_sizing`. Lands on the `setups` row via
 /// `TrackerService::update_setup_sizing`. Cent-encoded fields keep
 /// the SQLite schema integer-only.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct Sizing {
     /// Whole-share quantity. Zero only when `skipped_reason` is
     /// `Some(_)` — a non-skipped sizing always emits at least one
@@ -245,8 +251,8 @@ pub struct Sizing {
     /// points. 10000 = 1.0×. Capped at `conviction_multiplier_cap *
     /// 10000` so the field can't carry an unvetted scale.
     pub conviction_multiplier_bps: u32,
-    /// True when `max_position_pct` clipped the qty below what the
-    /// dollar-risk math would have produced. Useful diagnostic for
+    /// True when `max_position_pct`, or the liquidity cap, clipped the
+    /// qty below what the dollar-risk math would have produced. Useful diagnostic for
     /// "why did sizing pick fewer shares than I expected".
     pub cap_applied: bool,
     /// `Some(_)` when the engine refused to size; `qty` is then 0.
@@ -0,0 +1,142 @@
+//! Five-field cron expressions (`minute hour day-of-month month
+//! day-of-week`), evaluated in New York time, DST included: `30 9` is
+//! 09:30 ET all year. A minute the spring-forward shift skips doesn't
+//! fire that day; one the fall-back shift repeats fires once, the first
+//! time.
+//!
+//! Supported per field: `*`, a number, `a-b`, `*/n`, `a-b/n` and
+//! comma-separated lists of those. Day-of-week is `0..=7` with both 0
+//! and 7 meaning Sunday. As in classic cron, when both day fields are
+//! restricted a day matching either one fires. Names (`MON`, `JAN`) and
+//! the `@daily` shorthands are not supported. Holidays are not skipped
+//! here; a task opts in with `ScheduledTask::trading_days_only`.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use chrono_tz::America::New_York;
+
+/// Search horizon for [`CronSchedule::next_after`]. Long enough for any
+/// satisfiable expression (Feb 29 recurs within eight years).
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "expected 5 fields (minute hour day month weekday), got {}",
+                fields.len()
+            ));
+        };
+        let mut weekdays = parse_field(weekday, 0, 7, "weekday")?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, "minute")?,
+            hours: parse_field(hour, 0, 23, "hour")?,
+            days: parse_field(day, 1, 31, "day")?,
+            months: parse_field(month, 1, 12, "month")?,
+            weekdays,
+            days_restricted: day != "*",
+            weekdays_restricted: weekday != "*",
+        })
+    }
+
+    /// First matching minute strictly after `now`; `None` for an
+    /// expression that never fires (e.g. `0 0 31 2 *`).
+    pub fn next_after(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let today = now.with_timezone(&New_York).date_naive();
+        for d in 0..MAX_LOOKAHEAD_DAYS {
+            let date = today + Duration::days(d);
+            if !self.matches_day(date) {
+                continue;
+            }
+            for hour in bits(self.hours) {
+                for minute in bits(self.minutes) {
+                    let Some(at) = New_York
+                        .with_ymd_and_hms(date.year(), date.month(), date.day(), hour, minute, 0)
+                        .earliest()
+                    else {
+                        continue;
+                    };
+                    let at = at.with_timezone(&Utc);
+                    if at > now {
+                        return Some(at);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        if !has(self.months, date.month()) {
+            return false;
+        }
+        let dom = has(self.days, date.day());
+        let dow = has(self.weekdays, date.weekday().num_days_from_sunday());
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+}
+
+fn has(mask: u64, n: u32) -> bool {
+    mask & (1 << n) != 0
+}
+
+fn bits(mask: u64) -> impl Iterator<Item = u32> {
+    (0..64).filter(move |n| has(mask, *n))
+}
+
+fn parse_field(field: &str, min: u32, max: u32, name: &str) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("{name}: bad step in `{part}`"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (lo, hi) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (number(a, name)?, number(b, name)?)
+        } else {
+            let n = number(range, name)?;
+            // `5/15` means "from 5 every 15", as in Vixie cron.
+            (n, if step > 1 { max } else { n })
+        };
+        if lo < min || hi > max || lo > hi {
+            return Err(format!("{name}: `{part}` outside {min}-{max}"));
+        }
+        for n in (lo..=hi).step_by(step as usize) {
+            mask |= 1 << n;
+        }
+    }
+    Ok(mask)
+}
+
+fn number(s: &str, name: &str) -> Result<u32, String> {
+    s.parse()
+        .map_err(|_| format!("{name}: `{s}` is not a number"))
+}
@@ -0,0 +1,100 @@
+//! `scheduler_runs` — the run log behind `scheduler_list_jobs` and
+//! `scheduler_run_history`.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobRun {
+    pub id: i64,
+    pub job_id: String,
+    pub started_at: i64,
+    pub finished_at: i64,
+    pub ok: bool,
+    /// Task summary on success, the error on failure.
+    pub message: String,
+}
+
+#[derive(Clone)]
+pub struct RunHistoryStore {
+    db: Arc<Db>,
+}
+
+impl RunHistoryStore {
+    pub fn new(db: Arc<Db>) -> Self {
+        Self { db }
+    }
+
+    pub async fn record(
+        &self,
+        job_id: &str,
+        started_at: i64,
+        finished_at: i64,
+        outcome: &Result<String, String>,
+    ) -> Result<(), StorageError> {
+        let job_id = job_id.to_string();
+        let (ok, message) = match outcome {
+            Ok(m) => (true, m.clone()),
+            Err(e) => (false, e.clone()),
+        };
+        self.db
+            .with_conn(move |conn| {
+                conn.execute(
+                    "INSERT INTO scheduler_runs (job_id, started_at, finished_at, ok, message) \
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    rusqlite::params![job_id, started_at, finished_at, ok, message],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Newest first.
+    pub async fn recent(&self, job_id: &str, limit: u32) -> Result<Vec<JobRun>, StorageError> {
+        self.query(job_id, false, limit).await
+    }
+
+    pub async fn last_error(&self, job_id: &str) -> Result<Option<JobRun>, StorageError> {
+        Ok(self.query(job_id, true, 1).await?.pop())
+    }
+
+    async fn query(
+        &self,
+        job_id: &str,
+        failures_only: bool,
+        limit: u32,
+    ) -> Result<Vec<JobRun>, StorageError> {
+        let job_id = job_id.to_string();
+        self.db
+            .with_conn(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, job_id, started_at, finished_at, ok, message \
+                     FROM scheduler_runs \
+                     WHERE job_id = ?1 AND (?2 = 0 OR ok = 0) \
+                     ORDER BY id DESC LIMIT ?3",
+                )?;
+                let rows =
+                    stmt.query_map(rusqlite::params![job_id, failures_only, limit], |row| {
+                        Ok(JobRun {
+                            id: row.get(0)?,
+                            job_id: row.get(1)?,
+                            started_at: row.get(2)?,
+                            finished_at: row.get(3)?,
+                            ok: row.get(4)?,
+                            message: row.get(5)?,
+                        })
+                    })?;
+                let mut out = Vec::new();
+                for row in rows {
+                    out.push(row?);
+                }
+                Ok(out)
+            })
+            .await
+    }
+}
@@ -0,0 +1,285 @@
+//! Generic recurring-task scheduler.
+//!
+//! Features that need a background cadence implement
+//! [`ScheduledTask`] and are registered once in `lib.rs`; when each one
+//! fires is data, not code: a cron expression per task id in
+//! `AppConfig.scheduler` (see [`cron`] for the dialect — ET, five
+//! fields). A task without a settings entry runs on its
+//! `default_cron` if it is `enabled_by_default`, so a fresh install
+//! behaves sensibly and the settings file only records deliberate
+//! changes.
+//!
+//! The loop re-reads the settings every tick, so enabling, disabling
+//! or re-timing a job takes effect without a restart. Every run lands
+//! in `scheduler_runs` ([`history`]), which is where "last run" and
+//! "last error" come from. Runs are sequential: a slow task delays the
+//! next one rather than overlapping it.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+use crate::events::{AppEvent, EventEmitter};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::market_calendar;
+
+pub mod cron;
+pub mod history;
+pub mod tasks;
+
+#[cfg(test)]
+mod tests;
+
+use cron::CronSchedule;
+use history::{JobRun, RunHistoryStore};
+
+/// How often the loop checks for due jobs. Cron has minute resolution.
+const TICK: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchedulerConfig {
+    #[serde(default)]
+    pub jobs: Vec<ScheduleDef>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleDef {
+    /// A registered [`ScheduledTask::id`].
+    pub id: String,
+    pub cron: String,
+    pub enabled: bool,
+}
+
+impl SchedulerConfig {
+    pub fn get(&self, id: &str) -> Option<&ScheduleDef> {
+        self.jobs.iter().find(|d| d.id == id)
+    }
+
+    pub fn upsert(&mut self, def: ScheduleDef) {
+        match self.jobs.iter_mut().find(|d| d.id == def.id) {
+            Some(existing) => *existing = def,
+            None => self.jobs.push(def),
+        }
+    }
+}
+
+#[async_trait]
+pub trait ScheduledTask: Send + Sync {
+    /// Stable key used in settings and the run log.
+    fn id(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn default_cron(&self) -> &'static str;
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+    /// Skip fires that land on a weekend or exchange holiday (US
+    /// equities calendar). The run is not made up later.
+    fn trading_days_only(&self) -> bool {
+        false
+    }
+    /// One run. `Ok` carries a short summary for the run log.
+    async fn run(&self) -> Result<String, String>;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledJobView {
+    pub id: String,
+    pub description: String,
+    pub cron: String,
+    pub enabled: bool,
+    /// Unix seconds; `None` when disabled or the cron never fires.
+    pub next_run_at: Option<i64>,
+    pub last_run: Option<JobRun>,
+    pub last_error: Option<JobRun>,
+}
+
+pub struct Scheduler {
+    tasks: Vec<Arc<dyn ScheduledTask>>,
+    config: Arc<RwLock<AppConfig>>,
+    history: RunHistoryStore,
+    emitter: Option<Arc<EventEmitter>>,
+}
+
+impl Scheduler {
+    pub fn new(
+        db: Arc<Db>,
+        config: Arc<RwLock<AppConfig>>,
+        tasks: Vec<Arc<dyn ScheduledTask>>,
+    ) -> Self {
+        Self {
+            tasks,
+            config,
+            history: RunHistoryStore::new(db),
+            emitter: None,
+        }
+    }
+
+    /// Emit `ScheduledJobFinished` after every run.
+    pub fn with_emitter(mut self, emitter: Arc<EventEmitter>) -> Self {
+        self.emitter = Some(emitter);
+        self
+    }
+
+    pub fn task(&self, id: &str) -> Option<&Arc<dyn ScheduledTask>> {
+        self.tasks.iter().find(|t| t.id() == id)
+    }
+
+    /// The settings entry for `task`, or its built-in default.
+    pub fn effective(task: &dyn ScheduledTask, config: &SchedulerConfig) -> ScheduleDef {
+        config
+            .get(task.id())
+            .cloned()
+            .unwrap_or_else(|| ScheduleDef {
+                id: task.id().to_string(),
+                cron: task.default_cron().to_string(),
+                enabled: task.enabled_by_default(),
+            })
+    }
+
+    pub async fn list(&self) -> Result<Vec<ScheduledJobView>, StorageError> {
+        let config = self.config.read().await.scheduler.clone();
+        let now = Utc::now();
+        let mut out = Vec::with_capacity(self.tasks.len());
+        for task in &self.tasks {
+            let def = Self::effective(task.as_ref(), &config);
+            let next_run_at = def
+                .enabled
+                .then(|| CronSchedule::parse(&def.cron).ok())
+                .flatten()
+                .and_then(|c| c.next_after(now))
+                .map(|t| t.timestamp());
+            out.push(ScheduledJobView {
+                id: def.id,
+                description: task.description().to_string(),
+                cron: def.cron,
+                enabled: def.enabled,
+                next_run_at,
+                last_run: self.history.recent(task.id(), 1).await?.pop(),
+                last_error: self.history.last_error(task.id()).await?,
+            });
+        }
+        Ok(out)
+    }
+
+    pub async fn history(&self, id: &str, limit: u32) -> Result<Vec<JobRun>, StorageError> {
+        self.history.recent(id, limit).await
+    }
+
+    /// Run `id` now, outside its schedule; the run is logged as usual.
+    pub async fn run_now(&self, id: &str) -> Result<Result<String, String>, String> {
+        let task = self
+            .task(id)
+            .cloned()
+            .ok_or_else(|| format!("unknown scheduled job `{id}`"))?;
+        Ok(self.execute(task.as_ref()).await)
+    }
+
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut due = HashMap::new();
+            loop {
+                self.tick(Utc::now(), &mut due).await;
+                tokio::time::sleep(TICK).await;
+            }
+        })
+    }
+
+    /// Run every enabled task whose next fire time has passed. `due`
+    /// maps task id → (cron it was computed from, next fire time); a
+    /// task seen for the first time, or whose cron changed, is
+    /// scheduled from `now` rather than run immediately.
+    pub(crate) async fn tick(
+        &self,
+        now: DateTime<Utc>,
+        due: &mut HashMap<&'static str, (String, DateTime<Utc>)>,
+    ) {
+        let config = self.config.read().await.scheduler.clone();
+        for task in &self.tasks {
+            let def = Self::effective(task.as_ref(), &config);
+            if !def.enabled {
+                due.remove(task.id());
+                continue;
+            }
+            let schedule = match CronSchedule::parse(&def.cron) {
+                Ok(s) => s,
+                Err(e) => {
+                    warn!(
+                        "scheduler: {} has an invalid cron `{}`: {e}",
+                        def.id, def.cron
+                    );
+                    continue;
+                }
+            };
+            let next = match due.get(task.id()) {
+                Some((cron, at)) if *cron == def.cron => *at,
+                _ => {
+                    let Some(at) = schedule.next_after(now) else {
+                        continue;
+                    };
+                    info!("scheduler: {} next at {}", def.id, at.to_rfc3339());
+                    due.insert(task.id(), (def.cron.clone(), at));
+                    continue;
+                }
+            };
+            if now < next {
+                continue;
+            }
+            let us = &market_calendar::sessions::US_EQUITIES;
+            let after = if task.trading_days_only() && !us.is_trading_day(us.local_date(now)) {
+                info!("scheduler: {} skipped, not a trading day", def.id);
+                now
+            } else {
+                // The outcome is logged and recorded by `execute`.
+                let _ = self.execute(task.as_ref()).await;
+                Utc::now().max(now)
+            };
+            match schedule.next_after(after) {
+                Some(at) => {
+                    due.insert(task.id(), (def.cron, at));
+                }
+                None => {
+                    due.remove(task.id());
+                }
+            }
+        }
+    }
+
+    async fn execute(&self, task: &dyn ScheduledTask) -> Result<String, String> {
+        let started = Utc::now().timestamp();
+        let outcome = task.run().await;
+        match &outcome {
+            Ok(summary) => info!("scheduler: {} ok: {summary}", task.id()),
+            Err(e) => warn!("scheduler: {} failed: {e}", task.id()),
+        }
+        if let Err(e) = self
+            .history
+            .record(task.id(), started, Utc::now().timestamp(), &outcome)
+            .await
+        {
+            warn!("scheduler: recording {} run failed: {e}", task.id());
+        }
+        if let Some(emitter) = &self.emitter {
+            let (ok, summary) = match &outcome {
+                Ok(s) => (true, s.clone()),
+                Err(e) => (false, e.clone()),
+            };
+            let _ = emitter
+                .emit(AppEvent::ScheduledJobFinished {
+                    id: task.id().to_string(),
+                    ok,
+                    summary,
+                })
+                .await;
+        }
+        outcome
+    }
+}
@@ -0,0 +1,10 @@
+//! [`ScheduledTask`] impls for the services that run on a cadence, one
+//! file per domain. Each is registered in `lib.rs`; the cron per task
+//! lives in settings.
+//!
+//! [`ScheduledTask`]: super::ScheduledTask
+
+mod options;
+mod portfolio;
+mod trading;
+mod valuation;
@@ -0,0 +1,82 @@
+//! Option tasks: portfolio greeks and the expiry watch.
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::services::option_expiry::OptionExpiryService;
+use crate::services::option_greeks::OptionGreeksService;
+use crate::utils::market_calendar::et_date;
+
+use super::super::ScheduledTask;
+
+#[async_trait]
+impl ScheduledTask for OptionGreeksService {
+    fn id(&self) -> &'static str {
+        "option_greeks"
+    }
+
+    fn description(&self) -> &'static str {
+        "Publish model greeks for option positions"
+    }
+
+    /// Every 5 minutes from 09:00 to 16:55 ET on weekdays.
+    fn default_cron(&self) -> &'static str {
+        "*/5 9-16 * * 1-5"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let greeks = self.publish().await.map_err(|e| e.to_string())?;
+        Ok(format!(
+            "{} option position(s), delta {:.1}, theta {:.2}/day, {} unpriced",
+            greeks.positions.len(),
+            greeks.delta,
+            greeks.theta,
+            greeks.unpriced.len()
+        ))
+    }
+}
+
+#[async_trait]
+impl ScheduledTask for OptionExpiryService {
+    fn id(&self) -> &'static str {
+        "option_expiry_check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Alert on short options at risk of assignment"
+    }
+
+    /// 09:45 ET, once the open has priced the underlyings.
+    fn default_cron(&self) -> &'static str {
+        "45 9 * * 1-5"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let flagged = self
+            .check(et_date(Utc::now()))
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(if flagged.is_empty() {
+            "no option at risk of assignment".to_string()
+        } else {
+            let symbols: Vec<&str> = flagged.iter().map(|r| r.local_symbol.as_str()).collect();
+            format!("assignment risk on {}", symbols.join(", "))
+        })
+    }
+}
@@ -0,0 +1,277 @@
+//! Account and portfolio tasks: cash drag, corporate actions, margin,
+//! analysis, risk, snapshots and the morning briefing.
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::ibkr::types::ProjectionAssumptions;
+use crate::services::cash_drag::CashDragService;
+use crate::services::corporate_actions::CorporateActionService;
+use crate::services::margin_monitor::MarginMonitor;
+use crate::services::morning_briefing::MorningBriefingService;
+use crate::services::portfolio_analysis::PortfolioAnalyzer;
+use crate::services::portfolio_diff::{PortfolioDiffEmail, PortfolioDiffService};
+use crate::services::portfolio_risk::PortfolioRiskService;
+use crate::utils::market_calendar::et_date;
+
+use super::super::ScheduledTask;
+
+#[async_trait]
+impl ScheduledTask for CashDragService {
+    fn id(&self) -> &'static str {
+        "cash_drag_sample"
+    }
+
+    fn description(&self) -> &'static str {
+        "Record cash as a share of NAV and flag idle cash"
+    }
+
+    /// 16:30 ET, after the close and the afternoon band checks.
+    fn default_cron(&self) -> &'static str {
+        "30 16 * * 1-5"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let points = self.sample().await.map_err(|e| e.to_string())?;
+        let highest = points
+            .iter()
+            .map(|p| p.cash_pct())
+            .fold(f64::NEG_INFINITY, f64::max);
+        Ok(if points.is_empty() {
+            "no account reported cash and NLV".to_string()
+        } else {
+            format!(
+                "{} account(s), highest cash {:.1}% of NAV",
+                points.len(),
+                highest
+            )
+        })
+    }
+}
+
+#[async_trait]
+impl ScheduledTask for CorporateActionService {
+    fn id(&self) -> &'static str {
+        "corporate_action_check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detect splits and symbol changes and adjust local data"
+    }
+
+    /// 07:30 ET, ahead of the morning briefing.
+    fn default_cron(&self) -> &'static str {
+        "30 7 * * 1-5"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let applied = self
+            .check(et_date(Utc::now()))
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(if applied.is_empty() {
+            "no new corporate actions".to_string()
+        } else {
+            let described: Vec<String> = applied.iter().map(|a| a.action.describe()).collect();
+            format!("applied {}", described.join("; "))
+        })
+    }
+}
+
+#[async_trait]
+impl ScheduledTask for MarginMonitor {
+    fn id(&self) -> &'static str {
+        "margin_sample"
+    }
+
+    fn description(&self) -> &'static str {
+        "Record margin requirement, excess liquidity and cushion"
+    }
+
+    /// Every 15 minutes from 09:00 to 16:45 ET on weekdays, so the
+    /// open and the close are both bracketed.
+    fn default_cron(&self) -> &'static str {
+        "*/15 9-16 * * 1-5"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let samples = self.sample().await.map_err(|e| e.to_string())?;
+        let lowest = samples
+            .iter()
+            .map(|s| s.cushion)
+            .fold(f64::INFINITY, f64::min);
+        Ok(if samples.is_empty() {
+            "no account reported a cushion".to_string()
+        } else {
+            format!(
+                "{} account(s), lowest cushion {:.1}%",
+                samples.len(),
+                lowest * 100.0
+            )
+        })
+    }
+}
+
+#[async_trait]
+impl ScheduledTask for PortfolioAnalyzer {
+    fn id(&self) -> &'static str {
+        "portfolio_analysis"
+    }
+
+    fn description(&self) -> &'static str {
+        "Project every position and snapshot the results"
+    }
+
+    /// Fridays after the close. Off by default: a cold AV cache makes
+    /// this spend fundamentals budget.
+    fn default_cron(&self) -> &'static str {
+        "30 16 * * 5"
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let analysis = self
+            .analyze(None, &ProjectionAssumptions::default(), None)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(match analysis.expected_cagr {
+            Some(cagr) => format!(
+                "{} position(s), base CAGR {:.1}%, coverage {:.0}%",
+                analysis.positions.len(),
+                cagr.base,
+                analysis.coverage * 100.0
+            ),
+            None => format!("{} position(s), none projected", analysis.positions.len()),
+        })
+    }
+}
+
+#[async_trait]
+impl ScheduledTask for PortfolioRiskService {
+    fn id(&self) -> &'static str {
+        "portfolio_risk_snapshot"
+    }
+
+    fn description(&self) -> &'static str {
+        "Record a portfolio risk snapshot"
+    }
+
+    fn default_cron(&self) -> &'static str {
+        "5 16 * * 1-5"
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let risk = self.snapshot().await.map_err(|e| e.to_string())?;
+        Ok(format!(
+            "snapshot {}: {} open position(s)",
+            risk.snapshot_id,
+            risk.open_positions.len()
+        ))
+    }
+}
+
+#[async_trait]
+impl ScheduledTask for PortfolioDiffService {
+    fn id(&self) -> &'static str {
+        "position_snapshot"
+    }
+
+    fn description(&self) -> &'static str {
+        "Store open positions for day-over-day diffs"
+    }
+
+    /// 16:15 ET, once closing marks are in.
+    fn default_cron(&self) -> &'static str {
+        "15 16 * * 1-5"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let count = self.snapshot().await.map_err(|e| e.to_string())?;
+        Ok(format!("{count} position(s) stored"))
+    }
+}
+
+#[async_trait]
+impl ScheduledTask for PortfolioDiffEmail {
+    fn id(&self) -> &'static str {
+        "portfolio_diff_email"
+    }
+
+    fn description(&self) -> &'static str {
+        "Summarize the last session's portfolio changes (sent as a notification)"
+    }
+
+    /// 08:00 ET, before the open.
+    fn default_cron(&self) -> &'static str {
+        "0 8 * * 1-5"
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let diff = self
+            .0
+            .diff_last_session()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(diff.summary())
+    }
+}
+
+#[async_trait]
+impl ScheduledTask for MorningBriefingService {
+    fn id(&self) -> &'static str {
+        "morning_briefing"
+    }
+
+    fn description(&self) -> &'static str {
+        "Build the pre-market briefing (sent as a notification)"
+    }
+
+    /// 08:30 ET, an hour before the open.
+    fn default_cron(&self) -> &'static str {
+        "30 8 * * 1-5"
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let briefing = self.generate(true).await;
+        Ok(briefing.markdown.unwrap_or_default())
+    }
+}
@@ -0,0 +1,147 @@
+//! Order-side tasks: the paper-trading fills, automation rules, position
+//! plans and the stop babysitter.
+
+use async_trait::async_trait;
+
+use crate::services::automation::RuleEngine;
+use crate::services::paper_trading::PaperTrader;
+use crate::services::position_plans::{PositionPlanService, StopBabysitter};
+
+use super::super::ScheduledTask;
+
+#[async_trait]
+impl ScheduledTask for PaperTrader {
+    fn id(&self) -> &'static str {
+        "sim_fill"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fill working paper-trading orders the price has reached"
+    }
+
+    /// Every minute from 09:00 to 16:59 ET on weekdays; the engine
+    /// itself skips the minutes outside the regular session.
+    fn default_cron(&self) -> &'static str {
+        "* 9-16 * * 1-5"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let fills = self.poll().await.map_err(|e| e.to_string())?;
+        Ok(format!("{} sim order(s) filled", fills.len()))
+    }
+}
+
+#[async_trait]
+impl ScheduledTask for RuleEngine {
+    fn id(&self) -> &'static str {
+        "automation_rules"
+    }
+
+    fn description(&self) -> &'static str {
+        "Evaluate automation rules and run the ones that crossed"
+    }
+
+    /// Every minute from 09:00 to 16:59 ET on weekdays; the engine
+    /// itself skips the minutes outside the regular session.
+    fn default_cron(&self) -> &'static str {
+        "* 9-16 * * 1-5"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let report = RuleEngine::run(self).await.map_err(|e| e.to_string())?;
+        if report.market_closed {
+            return Ok("market closed, no rule evaluated".to_string());
+        }
+        Ok(format!(
+            "{} rule(s) evaluated, {} fired",
+            report.evaluated,
+            report.fired.len()
+        ))
+    }
+}
+
+#[async_trait]
+impl ScheduledTask for PositionPlanService {
+    fn id(&self) -> &'static str {
+        "position_plan_check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Alert when open positions reach their target or stop"
+    }
+
+    /// Every 15 minutes from 09:00 to 16:45 ET on weekdays, like the
+    /// margin-of-safety check.
+    fn default_cron(&self) -> &'static str {
+        "*/15 9-16 * * 1-5"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let report = self.check().await.map_err(|e| e.to_string())?;
+        Ok(format!(
+            "{} checked, {} level{} hit",
+            report.checked,
+            report.hits.len(),
+            if report.hits.len() == 1 { "" } else { "s" }
+        ))
+    }
+}
+
+#[async_trait]
+impl ScheduledTask for StopBabysitter {
+    fn id(&self) -> &'static str {
+        "stop_babysitter"
+    }
+
+    fn description(&self) -> &'static str {
+        "Alert with an exit ticket when positions breach a locally watched stop"
+    }
+
+    /// Every minute from 09:00 to 15:59 ET on weekdays; the check
+    /// itself skips outside the regular session.
+    fn default_cron(&self) -> &'static str {
+        "* 9-15 * * 1-5"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let report = self.check().await.map_err(|e| e.to_string())?;
+        Ok(format!(
+            "{} watched, {} breached, {} skipped",
+            report.watched,
+            report.breaches.len(),
+            report.skipped.len()
+        ))
+    }
+}
@@ -0,0 +1,123 @@
+//! Valuation tasks: fair-value and margin-of-safety watches and the
+//! Notion export.
+
+use async_trait::async_trait;
+
+use crate::services::fair_value_watch::FairValueWatcher;
+use crate::services::margin_of_safety::MarginOfSafetyWatcher;
+use crate::services::notion_export::NotionExporter;
+
+use super::super::ScheduledTask;
+
+#[async_trait]
+impl ScheduledTask for FairValueWatcher {
+    fn id(&self) -> &'static str {
+        "fair_value_check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Compare prices against projected fair-value bands"
+    }
+
+    /// 16:20 ET, after the 16:05 EOD sweep has settled.
+    fn default_cron(&self) -> &'static str {
+        "20 16 * * 1-5"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let report = FairValueWatcher::run(self)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(format!(
+            "{} evaluated, {} crossing(s), {} skipped",
+            report.evaluated,
+            report.crossings.len(),
+            report.skipped.len()
+        ))
+    }
+}
+
+#[async_trait]
+impl ScheduledTask for MarginOfSafetyWatcher {
+    fn id(&self) -> &'static str {
+        "margin_of_safety_check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Alert when prices enter margin-of-safety buy/sell bands"
+    }
+
+    /// Every 15 minutes from 09:00 to 16:45 ET on weekdays, like the
+    /// margin samples.
+    fn default_cron(&self) -> &'static str {
+        "*/15 9-16 * * 1-5"
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let report = MarginOfSafetyWatcher::run(self)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(format!(
+            "{} evaluated, {} band entr{}, {} skipped",
+            report.evaluated,
+            report.entered.len(),
+            if report.entered.len() == 1 {
+                "y"
+            } else {
+                "ies"
+            },
+            report.skipped.len()
+        ))
+    }
+}
+
+#[async_trait]
+impl ScheduledTask for NotionExporter {
+    fn id(&self) -> &'static str {
+        "notion_export"
+    }
+
+    fn description(&self) -> &'static str {
+        "Mirror ticker summaries into the Notion database"
+    }
+
+    /// 16:30 ET, after the fair-value check has refreshed the zones.
+    fn default_cron(&self) -> &'static str {
+        "30 16 * * 1-5"
+    }
+
+    /// Needs a token and a database first.
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn trading_days_only(&self) -> bool {
+        true
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let report = self.export().await.map_err(|e| e.to_string())?;
+        Ok(format!(
+            "{} created, {} updated, {} failed",
+            report.created.len(),
+            report.updated.len(),
+            report.failed.len()
+        ))
+    }
+}
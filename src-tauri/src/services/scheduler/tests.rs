@@ -0,0 +1,258 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use chrono::TimeZone;
+use tempfile::NamedTempFile;
+
+use super::*;
+
+fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+}
+
+#[test]
+fn weekday_close_cron_skips_to_monday() {
+    // 16:20 EST = 21:20Z.
+    let cron = CronSchedule::parse("20 16 * * 1-5").unwrap();
+    // Wed 2025-02-05.
+    assert_eq!(
+        cron.next_after(at(2025, 2, 5, 20, 0)),
+        Some(at(2025, 2, 5, 21, 20))
+    );
+    assert_eq!(
+        cron.next_after(at(2025, 2, 5, 21, 20)),
+        Some(at(2025, 2, 6, 21, 20))
+    );
+    // Fri evening → Mon.
+    assert_eq!(
+        cron.next_after(at(2025, 2, 7, 23, 0)),
+        Some(at(2025, 2, 10, 21, 20))
+    );
+}
+
+#[test]
+fn cron_follows_new_york_daylight_saving() {
+    let open = CronSchedule::parse("30 9 * * 1-5").unwrap();
+    // Fri 2026-03-06, EST → Mon 03-09, EDT (DST began Sun 03-08).
+    assert_eq!(
+        open.next_after(at(2026, 3, 6, 15, 0)),
+        Some(at(2026, 3, 9, 13, 30))
+    );
+    // Fri 2026-10-30, EDT → Mon 11-02, EST (DST ended Sun 11-01).
+    assert_eq!(
+        open.next_after(at(2026, 10, 30, 14, 0)),
+        Some(at(2026, 11, 2, 14, 30))
+    );
+
+    // 02:30 doesn't exist on 03-08; 01:30 happens twice on 11-01 and
+    // fires on the first (EDT) pass only.
+    let skipped = CronSchedule::parse("30 2 * * *").unwrap();
+    assert_eq!(
+        skipped.next_after(at(2026, 3, 8, 5, 0)),
+        Some(at(2026, 3, 9, 6, 30))
+    );
+    let repeated = CronSchedule::parse("30 1 * * *").unwrap();
+    assert_eq!(
+        repeated.next_after(at(2026, 11, 1, 4, 0)),
+        Some(at(2026, 11, 1, 5, 30))
+    );
+    assert_eq!(
+        repeated.next_after(at(2026, 11, 1, 5, 30)),
+        Some(at(2026, 11, 2, 6, 30))
+    );
+}
+
+#[test]
+fn steps_lists_and_day_rule() {
+    let every_15 = CronSchedule::parse("*/15 * * * *").unwrap();
+    assert_eq!(
+        every_15.next_after(at(2025, 2, 5, 20, 1)),
+        Some(at(2025, 2, 5, 20, 15))
+    );
+
+    // dom and dow both restricted: either matches. 1st of the month or
+    // a Sunday, at 09:00 ET (14:00Z).
+    let either = CronSchedule::parse("0 9 1 * 0").unwrap();
+    // Wed 2025-02-05 → Sun 2025-02-09.
+    assert_eq!(
+        either.next_after(at(2025, 2, 5, 20, 0)),
+        Some(at(2025, 2, 9, 14, 0))
+    );
+    // Fri 2025-02-28 → Sat 2025-03-01 (the 1st).
+    assert_eq!(
+        either.next_after(at(2025, 2, 28, 20, 0)),
+        Some(at(2025, 3, 1, 14, 0))
+    );
+
+    let list = CronSchedule::parse("0,30 10-11 * * 7").unwrap();
+    // dow 7 is Sunday.
+    assert_eq!(
+        list.next_after(at(2025, 2, 9, 15, 10)),
+        Some(at(2025, 2, 9, 15, 30))
+    );
+}
+
+#[test]
+fn rejects_malformed_expressions() {
+    for bad in [
+        "",
+        "every day",
+        "* * * *",
+        "60 * * * *",
+        "* 24 * * *",
+        "*/0 * * * *",
+        "5-1 * * * *",
+    ] {
+        assert!(CronSchedule::parse(bad).is_err(), "accepted `{bad}`");
+    }
+}
+
+struct Counting {
+    runs: AtomicUsize,
+    fail: bool,
+    trading_days: bool,
+}
+
+#[async_trait]
+impl ScheduledTask for Counting {
+    fn id(&self) -> &'static str {
+        "counting"
+    }
+    fn description(&self) -> &'static str {
+        "test task"
+    }
+    fn default_cron(&self) -> &'static str {
+        "20 16 * * 1-5"
+    }
+    fn trading_days_only(&self) -> bool {
+        self.trading_days
+    }
+    async fn run(&self) -> Result<String, String> {
+        let n = self.runs.fetch_add(1, Ordering::SeqCst) + 1;
+        if self.fail {
+            Err(format!("boom {n}"))
+        } else {
+            Ok(format!("run {n}"))
+        }
+    }
+}
+
+fn scheduler(task: Arc<Counting>, enabled: bool) -> (Scheduler, Arc<RwLock<AppConfig>>) {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let mut config = AppConfig::default();
+    config.scheduler.upsert(ScheduleDef {
+        id: "counting".to_string(),
+        cron: "20 16 * * 1-5".to_string(),
+        enabled,
+    });
+    let config = Arc::new(RwLock::new(config));
+    let tasks: Vec<Arc<dyn ScheduledTask>> = vec![task];
+    (Scheduler::new(db, Arc::clone(&config), tasks), config)
+}
+
+#[tokio::test]
+async fn tick_schedules_first_then_runs_when_due_and_logs() {
+    let task = Arc::new(Counting {
+        runs: AtomicUsize::new(0),
+        fail: false,
+        trading_days: false,
+    });
+    let (scheduler, _config) = scheduler(Arc::clone(&task), true);
+    let mut due = HashMap::new();
+
+    // First sight only schedules, even though a fire time just passed.
+    scheduler.tick(at(2025, 2, 5, 21, 21), &mut due).await;
+    assert_eq!(task.runs.load(Ordering::SeqCst), 0);
+    assert_eq!(due["counting"].1, at(2025, 2, 6, 21, 20));
+
+    scheduler.tick(at(2025, 2, 6, 21, 0), &mut due).await;
+    assert_eq!(task.runs.load(Ordering::SeqCst), 0);
+    scheduler.tick(at(2025, 2, 6, 21, 20), &mut due).await;
+    assert_eq!(task.runs.load(Ordering::SeqCst), 1);
+
+    let runs = scheduler.history("counting", 10).await.unwrap();
+    assert_eq!(runs.len(), 1);
+    assert!(runs[0].ok);
+    assert_eq!(runs[0].message, "run 1");
+
+    let view = scheduler.list().await.unwrap();
+    assert_eq!(view.len(), 1);
+    assert!(view[0].enabled);
+    assert!(view[0].next_run_at.is_some());
+    assert!(view[0].last_run.is_some());
+    assert!(view[0].last_error.is_none());
+}
+
+#[tokio::test]
+async fn disabled_jobs_never_run_and_failures_surface_as_last_error() {
+    let task = Arc::new(Counting {
+        runs: AtomicUsize::new(0),
+        fail: true,
+        trading_days: false,
+    });
+    let (scheduler, config) = scheduler(Arc::clone(&task), false);
+    let mut due = HashMap::new();
+
+    scheduler.tick(at(2025, 2, 5, 20, 0), &mut due).await;
+    scheduler.tick(at(2025, 2, 5, 21, 20), &mut due).await;
+    assert_eq!(task.runs.load(Ordering::SeqCst), 0);
+    assert!(due.is_empty());
+    assert!(scheduler.list().await.unwrap()[0].next_run_at.is_none());
+
+    // Enabling via settings takes effect on the next tick.
+    config.write().await.scheduler.jobs[0].enabled = true;
+    scheduler.tick(at(2025, 2, 5, 20, 0), &mut due).await;
+    scheduler.tick(at(2025, 2, 5, 21, 20), &mut due).await;
+    assert_eq!(task.runs.load(Ordering::SeqCst), 1);
+
+    let outcome = scheduler.run_now("counting").await.unwrap();
+    assert_eq!(outcome, Err("boom 2".to_string()));
+    let last_error = scheduler.list().await.unwrap()[0]
+        .last_error
+        .clone()
+        .unwrap();
+    assert!(!last_error.ok);
+    assert_eq!(last_error.message, "boom 2");
+    assert!(scheduler.run_now("missing").await.is_err());
+}
+
+#[tokio::test]
+async fn trading_days_only_tasks_skip_holidays_but_stay_scheduled() {
+    let task = Arc::new(Counting {
+        runs: AtomicUsize::new(0),
+        fail: false,
+        trading_days: true,
+    });
+    let (scheduler, _config) = scheduler(Arc::clone(&task), true);
+    let mut due = HashMap::new();
+
+    // Thanksgiving 2025 (Thu 11-27) fire is skipped; Friday's runs.
+    scheduler.tick(at(2025, 11, 27, 12, 0), &mut due).await;
+    scheduler.tick(at(2025, 11, 27, 21, 20), &mut due).await;
+    assert_eq!(task.runs.load(Ordering::SeqCst), 0);
+    assert_eq!(due["counting"].1, at(2025, 11, 28, 21, 20));
+    scheduler.tick(at(2025, 11, 28, 21, 20), &mut due).await;
+    assert_eq!(task.runs.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn runs_emit_scheduled_job_finished_when_an_emitter_is_attached() {
+    let task = Arc::new(Counting {
+        runs: AtomicUsize::new(0),
+        fail: false,
+        trading_days: false,
+    });
+    let (scheduler, _config) = scheduler(task, true);
+    let emitter = Arc::new(EventEmitter::for_capture());
+    let scheduler = scheduler.with_emitter(Arc::clone(&emitter));
+
+    scheduler.run_now("counting").await.unwrap().unwrap();
+    match emitter.captured().await.as_slice() {
+        [AppEvent::ScheduledJobFinished { id, ok, summary }] => {
+            assert_eq!(id, "counting");
+            assert!(ok);
+            assert_eq!(summary, "run 1");
+        }
+        other => panic!("unexpected events: {other:?}"),
+    }
+}
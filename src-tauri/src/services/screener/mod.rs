@@ -0,0 +1,216 @@
+//! Fundamentals screener over locally cached tickers.
+//!
+//! Filters every ticker we already hold fundamentals for — the AV file
+//! cache plus the manual store — by revenue CAGR, P/E and net-margin
+//! trend, and by stored short interest, so the analysis library can be
+//! searched instead of opened one ticker at a time. Screening never fetches: a ticker without cached
+//! data is reported in `skipped`, not pulled from Alpha Vantage, so a
+//! screen costs no AV budget.
+//!
+//! To combine with the IBKR scanner, pass the scanner's symbols as
+//! `universe`; the screen then runs over just those tickers.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::ibkr::types::FundamentalData;
+use crate::services::fundamentals_growth::{self, CAGR_WINDOWS};
+
+pub mod source;
+
+#[cfg(test)]
+mod tests;
+
+/// Window used for `min_revenue_cagr` when the criteria don't name one.
+pub const DEFAULT_CAGR_YEARS: u32 = 3;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenerCriteria {
+    /// Percent, e.g. `15.0` for a 15% revenue CAGR.
+    #[serde(default)]
+    pub min_revenue_cagr: Option<f64>,
+    /// One of [`CAGR_WINDOWS`]; defaults to [`DEFAULT_CAGR_YEARS`].
+    #[serde(default)]
+    pub cagr_years: Option<u32>,
+    /// Upper bound on trailing P/E. Loss-makers (P/E ≤ 0) never pass.
+    #[serde(default)]
+    pub max_pe: Option<f64>,
+    /// Require the latest year's net margin to be above the prior year's.
+    #[serde(default)]
+    pub margin_trend_positive: bool,
+    /// Short interest as a percent of shares outstanding.
+    #[serde(default)]
+    pub min_short_interest_pct: Option<f64>,
+    #[serde(default)]
+    pub min_days_to_cover: Option<f64>,
+    /// Restrict the screen to these symbols (e.g. IBKR scanner output)
+    /// instead of every cached ticker.
+    #[serde(default)]
+    pub universe: Option<Vec<String>>,
+}
+
+impl ScreenerCriteria {
+    pub fn validate(&self) -> Result<(), String> {
+        let years = self.cagr_years.unwrap_or(DEFAULT_CAGR_YEARS);
+        if !CAGR_WINDOWS.contains(&years) {
+            return Err(format!(
+                "cagrYears must be one of {CAGR_WINDOWS:?}, got {years}"
+            ));
+        }
+        if self.max_pe.is_some_and(|pe| !pe.is_finite() || pe <= 0.0) {
+            return Err("maxPe must be a positive number".to_string());
+        }
+        if self.min_revenue_cagr.is_some_and(|c| !c.is_finite()) {
+            return Err("minRevenueCagr must be a finite number".to_string());
+        }
+        if [self.min_short_interest_pct, self.min_days_to_cover]
+            .iter()
+            .flatten()
+            .any(|v| !v.is_finite() || *v < 0.0)
+        {
+            return Err("short interest minimums must be non-negative numbers".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenerMatch {
+    pub symbol: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub pe_ratio: f64,
+    /// Revenue CAGR over the criteria's window, percent.
+    pub revenue_cagr: Option<f64>,
+    pub net_margin: Option<f64>,
+    /// Latest YoY change in net margin, percentage points.
+    pub margin_change: Option<f64>,
+    pub latest_year: Option<u32>,
+    pub short_interest_pct: Option<f64>,
+    pub days_to_cover: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenerResult {
+    /// Matches, highest revenue CAGR first.
+    pub matches: Vec<ScreenerMatch>,
+    /// Tickers with cached fundamentals that were evaluated.
+    pub screened: usize,
+    /// Universe symbols with no cached fundamentals.
+    pub skipped: Vec<String>,
+}
+
+/// Where the screener reads fundamentals from. Seam so tests don't
+/// need the on-disk AV cache.
+#[async_trait]
+pub trait CachedFundamentals: Send + Sync {
+    /// Every symbol with cached fundamentals, sorted.
+    async fn symbols(&self) -> Vec<String>;
+    /// Cached record for `symbol`, overrides applied; `None` when absent.
+    async fn load(&self, symbol: &str) -> Option<FundamentalData>;
+}
+
+pub struct Screener {
+    source: Arc<dyn CachedFundamentals>,
+}
+
+impl Screener {
+    pub fn new(source: Arc<dyn CachedFundamentals>) -> Self {
+        Self { source }
+    }
+
+    pub async fn screen(&self, criteria: &ScreenerCriteria) -> Result<ScreenerResult, String> {
+        criteria.validate()?;
+        let universe = match &criteria.universe {
+            Some(symbols) => {
+                let mut symbols: Vec<String> = symbols
+                    .iter()
+                    .map(|s| s.trim().to_uppercase())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                symbols.sort();
+                symbols.dedup();
+                symbols
+            }
+            None => self.source.symbols().await,
+        };
+
+        let mut result = ScreenerResult::default();
+        for symbol in universe {
+            let Some(mut data) = self.source.load(&symbol).await else {
+                result.skipped.push(symbol);
+                continue;
+            };
+            result.screened += 1;
+            fundamentals_growth::annotate(&mut data);
+            if let Some(m) = evaluate(&data, criteria) {
+                result.matches.push(m);
+            }
+        }
+        result.matches.sort_by(|a, b| {
+            b.revenue_cagr
+                .unwrap_or(f64::NEG_INFINITY)
+                .total_cmp(&a.revenue_cagr.unwrap_or(f64::NEG_INFINITY))
+                .then_with(|| a.symbol.cmp(&b.symbol))
+        });
+        Ok(result)
+    }
+}
+
+/// `Some` when `data` (with `growth` annotated) passes every criterion.
+/// A criterion whose input is unavailable — too little history for the
+/// CAGR window, no prior year for the margin trend — fails.
+pub fn evaluate(data: &FundamentalData, criteria: &ScreenerCriteria) -> Option<ScreenerMatch> {
+    let years = criteria.cagr_years.unwrap_or(DEFAULT_CAGR_YEARS);
+    let growth = data.growth.as_ref();
+    let revenue_cagr = growth
+        .and_then(|g| g.cagr.iter().find(|c| c.years == years))
+        .and_then(|c| c.revenue);
+    let latest = growth.and_then(|g| g.years.last());
+    let margin_change = latest.and_then(|y| y.margin_change);
+    let pe = data.current_metrics.pe_ratio;
+    let short = data.current_metrics.short_interest.as_ref();
+    let short_interest_pct = short.and_then(|s| s.percent_of_shares);
+    let days_to_cover = short.and_then(|s| s.days_to_cover);
+
+    if let Some(min) = criteria.min_revenue_cagr {
+        if revenue_cagr.is_none_or(|c| c <= min) {
+            return None;
+        }
+    }
+    if let Some(max) = criteria.max_pe {
+        if !(pe > 0.0 && pe < max) {
+            return None;
+        }
+    }
+    if criteria.margin_trend_positive && margin_change.is_none_or(|d| d <= 0.0) {
+        return None;
+    }
+    if let Some(min) = criteria.min_short_interest_pct {
+        if short_interest_pct.is_none_or(|p| p < min) {
+            return None;
+        }
+    }
+    if let Some(min) = criteria.min_days_to_cover {
+        if days_to_cover.is_none_or(|d| d < min) {
+            return None;
+        }
+    }
+
+    Some(ScreenerMatch {
+        symbol: data.symbol.clone(),
+        name: data.current_metrics.name.clone(),
+        pe_ratio: pe,
+        revenue_cagr,
+        net_margin: latest.and_then(|y| y.net_margin),
+        margin_change,
+        latest_year: latest.map(|y| y.year),
+        short_interest_pct,
+        days_to_cover,
+    })
+}
@@ -0,0 +1,82 @@
+//! Production [`CachedFundamentals`]: the manual store first (as in
+//! `CompositeFundamentalsProvider`), then the AV file cache read
+//! regardless of TTL — a screen would rather use week-old numbers than
+//! spend AV budget. Per-field overrides are laid over either, and the
+//! stored short interest (never refreshed from here) on top.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use crate::ibkr::types::FundamentalData;
+use crate::services::cache_service::CacheService;
+use crate::services::financial_data_service::FinancialDataService;
+use crate::services::fundamentals_overrides::{self, FundamentalsOverridesStore};
+use crate::services::manual_fundamentals_store::ManualFundamentalsStore;
+use crate::services::short_interest::{self, ShortInterestService};
+
+use super::CachedFundamentals;
+
+pub struct LocalFundamentalsSource {
+    av_cache: Arc<CacheService>,
+    manual: Arc<ManualFundamentalsStore>,
+    overrides: Arc<FundamentalsOverridesStore>,
+    short_interest: Arc<ShortInterestService>,
+}
+
+impl LocalFundamentalsSource {
+    pub fn new(
+        av_cache: Arc<CacheService>,
+        manual: Arc<ManualFundamentalsStore>,
+        overrides: Arc<FundamentalsOverridesStore>,
+        short_interest: Arc<ShortInterestService>,
+    ) -> Self {
+        Self {
+            av_cache,
+            manual,
+            overrides,
+            short_interest,
+        }
+    }
+}
+
+#[async_trait]
+impl CachedFundamentals for LocalFundamentalsSource {
+    async fn symbols(&self) -> Vec<String> {
+        let mut symbols = self.av_cache.list_cached_symbols().unwrap_or_else(|e| {
+            warn!("screener: listing AV cache failed: {e}");
+            Vec::new()
+        });
+        match self.manual.list_with_freshness().await {
+            Ok(rows) => symbols.extend(rows.into_iter().map(|(symbol, _, _)| symbol)),
+            Err(e) => warn!("screener: listing manual fundamentals failed: {e}"),
+        }
+        symbols.sort();
+        symbols.dedup();
+        symbols
+    }
+
+    async fn load(&self, symbol: &str) -> Option<FundamentalData> {
+        let manual = match self.manual.get(symbol).await {
+            Ok(row) => row.map(|r| r.data),
+            Err(e) => {
+                warn!("screener: manual fundamentals read failed for {symbol}: {e}");
+                None
+            }
+        };
+        let mut data = manual.or_else(|| {
+            FinancialDataService::read_cached_fundamentals_ignoring_ttl(&self.av_cache, symbol)
+        })?;
+        match self.overrides.get(symbol).await {
+            Ok(Some(row)) => fundamentals_overrides::apply(&mut data, &row.overrides),
+            Ok(None) => {}
+            Err(e) => warn!("screener: overrides read failed for {symbol}: {e}"),
+        }
+        match self.short_interest.cached(symbol).await {
+            Ok(si) => short_interest::attach(&mut data, si),
+            Err(e) => warn!("screener: short interest read failed for {symbol}: {e}"),
+        }
+        Some(data)
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::ibkr::types::{FundamentalData, ShortInterest};
+use crate::services::projection_service::ProjectionService;
+
+use super::{CachedFundamentals, Screener, ScreenerCriteria};
+
+/// Four fiscal years (2021–2024) of revenue and net income, billions.
+fn company(symbol: &str, revenue: [f64; 4], net_income: [f64; 4], pe: f64) -> FundamentalData {
+    let mut data = ProjectionService::generate_mock_fundamental_data(symbol);
+    for (i, row) in data.historical.iter_mut().enumerate() {
+        row.revenue = revenue[i];
+        row.net_income = net_income[i];
+    }
+    data.current_metrics.pe_ratio = pe;
+    data
+}
+
+struct FakeCache(BTreeMap<String, FundamentalData>);
+
+#[async_trait]
+impl CachedFundamentals for FakeCache {
+    async fn symbols(&self) -> Vec<String> {
+        self.0.keys().cloned().collect()
+    }
+
+    async fn load(&self, symbol: &str) -> Option<FundamentalData> {
+        self.0.get(symbol).cloned()
+    }
+}
+
+fn screener() -> Screener {
+    let rows = [
+        // ~26% revenue CAGR, margin widening, P/E 22.
+        company(
+            "GROW",
+            [50.0, 62.0, 80.0, 100.0],
+            [5.0, 6.5, 9.0, 12.0],
+            22.0,
+        ),
+        // Same growth but margins compressing.
+        company(
+            "SQZ",
+            [50.0, 62.0, 80.0, 100.0],
+            [8.0, 9.0, 10.0, 11.0],
+            18.0,
+        ),
+        // Cheap and flat.
+        company("FLAT", [40.0, 40.5, 41.0, 41.5], [4.0, 4.0, 4.1, 4.3], 9.0),
+        // Loss-maker: P/E reported as 0.
+        company(
+            "LOSS",
+            [10.0, 15.0, 22.0, 30.0],
+            [-1.0, -0.5, -0.2, 0.1],
+            0.0,
+        ),
+    ];
+    let mut map: BTreeMap<_, _> = rows.into_iter().map(|d| (d.symbol.clone(), d)).collect();
+    // SQZ: 12% of shares short, 6 days to cover.
+    let sqz = map.get_mut("SQZ").unwrap();
+    let shares = sqz.current_metrics.shares_outstanding * 1e6;
+    crate::services::short_interest::attach(
+        sqz,
+        Some(ShortInterest {
+            settlement_date: "2026-09-30".to_string(),
+            shares_short: shares * 0.12,
+            previous_shares_short: None,
+            avg_daily_volume: Some(shares * 0.02),
+            days_to_cover: Some(6.0),
+            percent_of_shares: None,
+        }),
+    );
+    Screener::new(Arc::new(FakeCache(map)))
+}
+
+fn symbols(result: &super::ScreenerResult) -> Vec<&str> {
+    result.matches.iter().map(|m| m.symbol.as_str()).collect()
+}
+
+#[tokio::test]
+async fn criteria_combine_growth_valuation_and_margin_trend() {
+    let s = screener();
+
+    let growth = ScreenerCriteria {
+        min_revenue_cagr: Some(20.0),
+        ..Default::default()
+    };
+    let result = s.screen(&growth).await.unwrap();
+    assert_eq!(result.screened, 4);
+    // Sorted by revenue CAGR, highest first.
+    assert_eq!(symbols(&result), vec!["LOSS", "GROW", "SQZ"]);
+
+    let result = s
+        .screen(&ScreenerCriteria {
+            max_pe: Some(25.0),
+            margin_trend_positive: true,
+            ..growth
+        })
+        .await
+        .unwrap();
+    assert_eq!(symbols(&result), vec!["GROW"]);
+    let m = &result.matches[0];
+    assert!((m.revenue_cagr.unwrap() - 25.99).abs() < 0.01);
+    assert_eq!(m.latest_year, Some(2024));
+    assert!(m.margin_change.unwrap() > 0.0);
+}
+
+#[tokio::test]
+async fn universe_restricts_to_scanner_symbols_and_reports_uncached() {
+    let result = screener()
+        .screen(&ScreenerCriteria {
+            max_pe: Some(30.0),
+            universe: Some(vec!["flat".into(), "SQZ".into(), "NEWCO".into()]),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(result.screened, 2);
+    assert_eq!(result.skipped, vec!["NEWCO".to_string()]);
+    assert_eq!(symbols(&result), vec!["SQZ", "FLAT"]);
+}
+
+#[tokio::test]
+async fn rejects_unsupported_cagr_window() {
+    let err = screener()
+        .screen(&ScreenerCriteria {
+            cagr_years: Some(4),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert!(err.contains("cagrYears"));
+}
+
+#[tokio::test]
+async fn short_interest_minimums_need_stored_figures() {
+    let result = screener()
+        .screen(&ScreenerCriteria {
+            min_short_interest_pct: Some(10.0),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(symbols(&result), vec!["SQZ"]);
+    assert!((result.matches[0].short_interest_pct.unwrap() - 12.0).abs() < 1e-9);
+
+    let result = screener()
+        .screen(&ScreenerCriteria {
+            min_days_to_cover: Some(7.0),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(result.matches.is_empty());
+}
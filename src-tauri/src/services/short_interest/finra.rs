@@ -0,0 +1,85 @@
+//! FINRA consolidated short interest via the public Query API (no key
+//! needed). Covers exchange-listed and OTC equities.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{json, Value};
+
+use crate::ibkr::types::ShortInterest;
+
+use super::ShortInterestFeed;
+
+const URL: &str = "https://api.finra.org/data/group/otcMarket/name/consolidatedShortInterest";
+
+pub struct FinraShortInterest {
+    client: Client,
+}
+
+impl FinraShortInterest {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+        }
+    }
+}
+
+impl Default for FinraShortInterest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ShortInterestFeed for FinraShortInterest {
+    async fn latest(&self, symbol: &str) -> Result<Option<ShortInterest>, String> {
+        let body = json!({
+            "limit": 1,
+            "compareFilters": [{
+                "compareType": "EQUAL",
+                "fieldName": "symbolCode",
+                "fieldValue": symbol,
+            }],
+            "sortFields": ["-settlementDate"],
+        });
+        let response = self
+            .client
+            .post(URL)
+            .header("Accept", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        // No rows comes back as 204 with an empty body.
+        if response.status() == reqwest::StatusCode::NO_CONTENT {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(format!("FINRA returned {}", response.status()));
+        }
+        let rows: Value = response.json().await.map_err(|e| e.to_string())?;
+        Ok(parse_latest(&rows))
+    }
+}
+
+/// Newest row of a FINRA response array.
+pub(super) fn parse_latest(rows: &Value) -> Option<ShortInterest> {
+    rows.as_array()?
+        .iter()
+        .filter_map(parse_row)
+        .max_by(|a, b| a.settlement_date.cmp(&b.settlement_date))
+}
+
+fn parse_row(row: &Value) -> Option<ShortInterest> {
+    let num = |field: &str| row.get(field).and_then(Value::as_f64);
+    let shares_short = num("currentShortPositionQuantity")?;
+    let avg_daily_volume = num("averageDailyVolumeQuantity").filter(|v| *v > 0.0);
+    Some(ShortInterest {
+        settlement_date: row.get("settlementDate")?.as_str()?.to_string(),
+        shares_short,
+        previous_shares_short: num("previousShortPositionQuantity"),
+        avg_daily_volume,
+        days_to_cover: num("daysToCoverQuantity")
+            .or_else(|| avg_daily_volume.map(|v| shares_short / v)),
+        percent_of_shares: None,
+    })
+}
@@ -0,0 +1,166 @@
+//! Short interest and days-to-cover per symbol.
+//!
+//! The feed is FINRA's consolidated short interest ([`finra`]): shares
+//! sold short as of each mid- and end-of-month settlement date, with
+//! average daily volume and days to cover. Neither Alpha Vantage nor
+//! the IBKR API publish it.
+//!
+//! The latest figure is kept in `short_interest` and re-read from the
+//! feed at most once per [`REFRESH_AFTER_SECS`]. The fundamentals
+//! command lays it over `CurrentMetrics.short_interest` (fetching when
+//! stale); the screener only ever reads the stored row, in keeping
+//! with screening never spending a network call.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rusqlite::OptionalExtension;
+use thiserror::Error;
+use tracing::warn;
+
+use crate::ibkr::types::{FundamentalData, ShortInterest};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+use crate::utils::symbols;
+
+pub mod finra;
+
+#[cfg(test)]
+mod tests;
+
+/// FINRA publishes twice a month; a day-old read is fresh enough.
+pub const REFRESH_AFTER_SECS: i64 = 24 * 60 * 60;
+
+/// Trait seam for the short interest feed. Production is
+/// [`finra::FinraShortInterest`]; tests inject fixed rows.
+#[async_trait]
+pub trait ShortInterestFeed: Send + Sync {
+    /// Most recent settlement for `symbol`; `None` when the feed has
+    /// no row for it.
+    async fn latest(&self, symbol: &str) -> Result<Option<ShortInterest>, String>;
+}
+
+#[derive(Error, Debug)]
+pub enum ShortInterestError {
+    #[error("short interest feed: {0}")]
+    Feed(String),
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+}
+
+pub struct ShortInterestService {
+    db: Arc<Db>,
+    feed: Arc<dyn ShortInterestFeed>,
+}
+
+impl ShortInterestService {
+    pub fn new(db: Arc<Db>, feed: Arc<dyn ShortInterestFeed>) -> Self {
+        Self { db, feed }
+    }
+
+    /// Stored figure when fresh, otherwise a feed read (stored on
+    /// success). A failed read falls back to the stored figure.
+    pub async fn latest(&self, symbol: &str) -> Result<Option<ShortInterest>, ShortInterestError> {
+        let symbol = symbols::normalize(symbol);
+        let now = Utc::now().timestamp();
+        let stored = self.stored(&symbol).await?;
+        if let Some((si, fetched_at)) = &stored {
+            if now - fetched_at < REFRESH_AFTER_SECS {
+                return Ok(Some(si.clone()));
+            }
+        }
+        match self.feed.latest(&symbol).await {
+            Ok(Some(si)) => {
+                self.store(&symbol, &si, now).await?;
+                Ok(Some(si))
+            }
+            Ok(None) => Ok(stored.map(|(si, _)| si)),
+            Err(e) if stored.is_some() => {
+                warn!("short_interest: {symbol} refresh failed, using stored: {e}");
+                Ok(stored.map(|(si, _)| si))
+            }
+            Err(e) => Err(ShortInterestError::Feed(e)),
+        }
+    }
+
+    /// Stored figure only, however old.
+    pub async fn cached(&self, symbol: &str) -> Result<Option<ShortInterest>, StorageError> {
+        Ok(self
+            .stored(&symbols::normalize(symbol))
+            .await?
+            .map(|(si, _)| si))
+    }
+
+    /// Fill `data.current_metrics.short_interest` from
+    /// [`Self::latest`], sized against the shares outstanding.
+    pub async fn annotate(&self, data: &mut FundamentalData) -> Result<(), ShortInterestError> {
+        let si = self.latest(&data.symbol).await?;
+        attach(data, si);
+        Ok(())
+    }
+
+    async fn stored(&self, symbol: &str) -> Result<Option<(ShortInterest, i64)>, StorageError> {
+        let symbol = symbol.to_string();
+        self.db
+            .with_conn(move |conn| {
+                conn.query_row(
+                    "SELECT settlement_date, shares_short, previous_shares_short, \
+                            avg_daily_volume, days_to_cover, fetched_at \
+                     FROM short_interest WHERE symbol = ?1",
+                    rusqlite::params![symbol],
+                    |row| {
+                        Ok((
+                            ShortInterest {
+                                settlement_date: row.get(0)?,
+                                shares_short: row.get(1)?,
+                                previous_shares_short: row.get(2)?,
+                                avg_daily_volume: row.get(3)?,
+                                days_to_cover: row.get(4)?,
+                                percent_of_shares: None,
+                            },
+                            row.get(5)?,
+                        ))
+                    },
+                )
+                .optional()
+                .map_err(StorageError::from)
+            })
+            .await
+    }
+
+    async fn store(&self, symbol: &str, si: &ShortInterest, now: i64) -> Result<(), StorageError> {
+        let symbol = symbol.to_string();
+        let si = si.clone();
+        self.db
+            .with_conn(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO short_interest \
+                       (symbol, settlement_date, shares_short, previous_shares_short, \
+                        avg_daily_volume, days_to_cover, fetched_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    rusqlite::params![
+                        symbol,
+                        si.settlement_date,
+                        si.shares_short,
+                        si.previous_shares_short,
+                        si.avg_daily_volume,
+                        si.days_to_cover,
+                        now
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Set `si` on `data`, with `percent_of_shares` from
+/// `shares_outstanding` (millions) when that is known.
+pub fn attach(data: &mut FundamentalData, si: Option<ShortInterest>) {
+    let outstanding = data.current_metrics.shares_outstanding * 1_000_000.0;
+    data.current_metrics.short_interest = si.map(|mut si| {
+        si.percent_of_shares = (outstanding > 0.0).then(|| si.shares_short / outstanding * 100.0);
+        si
+    });
+}
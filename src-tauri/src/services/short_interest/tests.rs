@@ -0,0 +1,117 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde_json::json;
+use tempfile::NamedTempFile;
+
+use super::*;
+use crate::ibkr::types::CurrentMetrics;
+
+struct CountingFeed {
+    calls: AtomicUsize,
+    fail: bool,
+}
+
+#[async_trait]
+impl ShortInterestFeed for CountingFeed {
+    async fn latest(&self, _symbol: &str) -> Result<Option<ShortInterest>, String> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if self.fail {
+            return Err("offline".to_string());
+        }
+        Ok(Some(ShortInterest {
+            settlement_date: "2026-09-30".to_string(),
+            shares_short: 30_000_000.0,
+            previous_shares_short: Some(25_000_000.0),
+            avg_daily_volume: Some(5_000_000.0),
+            days_to_cover: Some(6.0),
+            percent_of_shares: None,
+        }))
+    }
+}
+
+fn service(db: Arc<Db>, fail: bool) -> (ShortInterestService, Arc<CountingFeed>) {
+    let feed = Arc::new(CountingFeed {
+        calls: AtomicUsize::new(0),
+        fail,
+    });
+    (ShortInterestService::new(db, feed.clone()), feed)
+}
+
+#[test]
+fn parses_the_newest_finra_row() {
+    let rows = json!([
+        {
+            "symbolCode": "GME",
+            "settlementDate": "2026-09-15",
+            "currentShortPositionQuantity": 40000000,
+            "averageDailyVolumeQuantity": 8000000,
+        },
+        {
+            "symbolCode": "GME",
+            "settlementDate": "2026-09-30",
+            "currentShortPositionQuantity": 45000000,
+            "previousShortPositionQuantity": 40000000,
+            "averageDailyVolumeQuantity": 9000000,
+            "daysToCoverQuantity": 5.0,
+        },
+    ]);
+    let si = finra::parse_latest(&rows).unwrap();
+    assert_eq!(si.settlement_date, "2026-09-30");
+    assert_eq!(si.shares_short, 45_000_000.0);
+    assert_eq!(si.previous_shares_short, Some(40_000_000.0));
+    assert_eq!(si.days_to_cover, Some(5.0));
+
+    // Days to cover derived when FINRA leaves it out.
+    let older = finra::parse_latest(&json!([rows[0].clone()])).unwrap();
+    assert_eq!(older.days_to_cover, Some(5.0));
+    assert!(finra::parse_latest(&json!([])).is_none());
+}
+
+#[tokio::test]
+async fn reads_the_feed_once_per_refresh_window_and_annotates_fundamentals() {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let (svc, feed) = service(Arc::clone(&db), false);
+
+    assert!(svc.cached("gme").await.unwrap().is_none());
+    let mut data = FundamentalData {
+        symbol: "gme".to_string(),
+        historical: Vec::new(),
+        analyst_estimates: None,
+        data_quality: Vec::new(),
+        overrides: Vec::new(),
+        growth: None,
+        currency: None,
+        fx_conversion: None,
+        segments: Vec::new(),
+        current_metrics: CurrentMetrics {
+            price: None,
+            pe_ratio: 0.0,
+            shares_outstanding: 300.0,
+            name: None,
+            exchange: None,
+            market_cap: None,
+            dividend_yield: None,
+            short_interest: None,
+        },
+    };
+    svc.annotate(&mut data).await.unwrap();
+    svc.latest("GME").await.unwrap();
+    assert_eq!(feed.calls.load(Ordering::SeqCst), 1);
+
+    let si = data.current_metrics.short_interest.unwrap();
+    assert_eq!(si.percent_of_shares, Some(10.0));
+    assert_eq!(svc.cached("GME").await.unwrap().unwrap().shares_short, 30e6);
+
+    // A failing feed with a stale row falls back to it.
+    db.with_conn(|conn| {
+        conn.execute("UPDATE short_interest SET fetched_at = 0", [])?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+    let (offline, feed) = service(db, true);
+    assert!(offline.latest("GME").await.unwrap().is_some());
+    assert_eq!(feed.calls.load(Ordering::SeqCst), 1);
+    assert!(offline.latest("AMC").await.is_err());
+}
@@ -0,0 +1,353 @@
+//! Stress tests: what predefined shocks would do to the stock book.
+//!
+//! A scenario (`stress_test.scenarios`) is a set of simultaneous shocks,
+//! each a move in one proxy: an index or sector ETF's price (`price`),
+//! or Treasury yields (`rates`), priced as the move of the bond ETF
+//! `stress_test.rates_proxy`, −duration × Δyield at
+//! `stress_test.rates_proxy_duration`.
+//!
+//! Each position's daily returns are regressed on the daily returns of
+//! the scenario's proxies, jointly and with an intercept, the way
+//! `services::factor_exposure` fits its factors (same lookback and
+//! minimum overlap). Its projected return is `Σ beta × proxy move` and
+//! its P&L that times its market value. Fitting the proxies jointly
+//! keeps an index and a sector shocked together from counting the
+//! sector's market move twice.
+//!
+//! The projection is linear: no convexity, and correlations as they
+//! were over the fit window. Options aren't delta-adjusted and, with
+//! stocks lacking the history, are listed as unmeasured. Nothing is
+//! stored: `run_stress_test` refits on every call.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::config::AppConfig;
+use crate::ibkr::error::IbkrError;
+use crate::services::factor_exposure::{fit, returns};
+use crate::services::historical_data_service::DailyBars;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::risk_engine::AccountSource;
+use crate::utils::symbols;
+
+#[cfg(test)]
+mod tests;
+
+/// One move in a scenario. Tagged by `kind`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Shock {
+    /// `symbol` (an index or sector ETF) moves `move_pct` percent.
+    Price { symbol: String, move_pct: f64 },
+    /// Treasury yields move `bp` basis points.
+    Rates { bp: f64 },
+}
+
+impl Shock {
+    /// The proxy this shock moves, and by how much.
+    pub fn proxy_move(&self, config: &StressTestConfig) -> ProxyMove {
+        match self {
+            Shock::Price { symbol, move_pct } => ProxyMove {
+                symbol: symbols::normalize(symbol),
+                move_pct: *move_pct,
+            },
+            Shock::Rates { bp } => ProxyMove {
+                symbol: symbols::normalize(&config.rates_proxy),
+                move_pct: -config.rates_proxy_duration * bp / 100.0,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StressScenario {
+    pub name: String,
+    pub shocks: Vec<Shock>,
+}
+
+impl StressScenario {
+    fn new(name: &str, shocks: Vec<Shock>) -> Self {
+        Self {
+            name: name.to_string(),
+            shocks,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StressTestConfig {
+    #[serde(default = "default_scenarios")]
+    pub scenarios: Vec<StressScenario>,
+    /// Bond ETF a `rates` shock is priced through.
+    #[serde(default = "default_rates_proxy")]
+    pub rates_proxy: String,
+    /// Its modified duration, in years.
+    #[serde(default = "default_rates_proxy_duration")]
+    pub rates_proxy_duration: f64,
+}
+
+fn default_scenarios() -> Vec<StressScenario> {
+    vec![
+        StressScenario::new("Rates +200bp", vec![Shock::Rates { bp: 200.0 }]),
+        StressScenario::new(
+            "Index -20%",
+            vec![Shock::Price {
+                symbol: "SPY".to_string(),
+                move_pct: -20.0,
+            }],
+        ),
+        StressScenario::new(
+            "Tech sector -30%",
+            vec![Shock::Price {
+                symbol: "XLK".to_string(),
+                move_pct: -30.0,
+            }],
+        ),
+    ]
+}
+
+fn default_rates_proxy() -> String {
+    "TLT".to_string()
+}
+
+fn default_rates_proxy_duration() -> f64 {
+    16.5
+}
+
+impl Default for StressTestConfig {
+    fn default() -> Self {
+        Self {
+            scenarios: default_scenarios(),
+            rates_proxy: default_rates_proxy(),
+            rates_proxy_duration: default_rates_proxy_duration(),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum StressTestError {
+    #[error("ibkr: {0}")]
+    Ibkr(#[from] IbkrError),
+    #[error("no stress scenario named {0:?}")]
+    UnknownScenario(String),
+}
+
+/// A proxy's move under a scenario, in percent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyMove {
+    pub symbol: String,
+    pub move_pct: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyBeta {
+    pub proxy: String,
+    pub beta: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionImpact {
+    pub symbol: String,
+    pub market_value: f64,
+    /// In the scenario's proxy order.
+    pub betas: Vec<ProxyBeta>,
+    /// Share of the return variance the proxies explain.
+    pub r_squared: f64,
+    /// Projected return, in percent.
+    pub return_pct: f64,
+    pub pnl: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioResult {
+    pub name: String,
+    pub shocks: Vec<Shock>,
+    /// One per shock.
+    pub proxies: Vec<ProxyMove>,
+    /// Largest loss first.
+    pub positions: Vec<PositionImpact>,
+    pub total_pnl: f64,
+    /// `total_pnl` over `measured_gross`, in percent.
+    pub total_pct: f64,
+    /// Gross market value of the measured positions.
+    pub measured_gross: f64,
+    /// Left out: options, and stocks without enough history.
+    pub unmeasured: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StressTestReport {
+    pub account: String,
+    pub at: i64,
+    /// In `stress_test.scenarios` order.
+    pub scenarios: Vec<ScenarioResult>,
+}
+
+/// A stock position with its daily returns.
+struct Held {
+    symbol: String,
+    market_value: f64,
+    returns: Vec<(String, f64)>,
+}
+
+pub struct StressTestService {
+    account: Arc<dyn AccountSource>,
+    positions: Arc<dyn OpenPositionsSource>,
+    bars: Arc<dyn DailyBars>,
+    config: Arc<RwLock<AppConfig>>,
+}
+
+impl StressTestService {
+    pub fn new(
+        account: Arc<dyn AccountSource>,
+        positions: Arc<dyn OpenPositionsSource>,
+        bars: Arc<dyn DailyBars>,
+        config: Arc<RwLock<AppConfig>>,
+    ) -> Self {
+        Self {
+            account,
+            positions,
+            bars,
+            config,
+        }
+    }
+
+    /// Project every configured scenario, or only the one named
+    /// `scenario`, on the open positions of `account` (the current
+    /// account when `None`).
+    pub async fn run(
+        &self,
+        account: Option<String>,
+        scenario: Option<String>,
+    ) -> Result<StressTestReport, StressTestError> {
+        let (config, fitting) = {
+            let config = self.config.read().await;
+            (config.stress_test.clone(), config.factor_exposure.clone())
+        };
+        let scenarios = match scenario.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            Some(name) => vec![config
+                .scenarios
+                .iter()
+                .find(|s| s.name.trim().eq_ignore_ascii_case(name))
+                .cloned()
+                .ok_or_else(|| StressTestError::UnknownScenario(name.to_string()))?],
+            None => config.scenarios.clone(),
+        };
+        let account = match account.filter(|a| !a.trim().is_empty()) {
+            Some(a) => a.trim().to_string(),
+            None => self.account.current_account().await?,
+        };
+        // One more close than returns.
+        let sessions = fitting.lookback_returns as usize + 1;
+
+        let mut held = Vec::new();
+        let mut unmeasured = Vec::new();
+        for position in self.positions.list_open(&account).await? {
+            let label = if position.local_symbol.is_empty() {
+                position.symbol.clone()
+            } else {
+                position.local_symbol.clone()
+            };
+            if position.contract_type != "STK" || position.position == 0.0 {
+                unmeasured.push(label);
+                continue;
+            }
+            match self.bars.daily_closes(&position.symbol, sessions).await {
+                Ok(closes) => held.push(Held {
+                    symbol: position.symbol.clone(),
+                    market_value: position.market_value,
+                    returns: returns(&closes),
+                }),
+                Err(e) => {
+                    warn!("stress_test: bars for {} failed: {e}", position.symbol);
+                    unmeasured.push(label);
+                }
+            }
+        }
+
+        let mut proxy_returns: HashMap<String, HashMap<String, f64>> = HashMap::new();
+        let mut results = Vec::new();
+        for scenario in scenarios {
+            let proxies: Vec<ProxyMove> = scenario
+                .shocks
+                .iter()
+                .map(|s| s.proxy_move(&config))
+                .collect();
+            for proxy in &proxies {
+                if !proxy_returns.contains_key(&proxy.symbol) {
+                    let closes = self.bars.daily_closes(&proxy.symbol, sessions).await?;
+                    proxy_returns
+                        .insert(proxy.symbol.clone(), returns(&closes).into_iter().collect());
+                }
+            }
+            let factors: Vec<HashMap<String, f64>> = proxies
+                .iter()
+                .map(|p| proxy_returns[&p.symbol].clone())
+                .collect();
+
+            let mut positions = Vec::new();
+            let mut missed = unmeasured.clone();
+            for h in &held {
+                let Some(fitted) = fit(&h.returns, &factors, &fitting) else {
+                    missed.push(h.symbol.clone());
+                    continue;
+                };
+                let projected: f64 = fitted
+                    .betas
+                    .iter()
+                    .zip(&proxies)
+                    .map(|(beta, p)| beta * p.move_pct / 100.0)
+                    .sum();
+                positions.push(PositionImpact {
+                    symbol: h.symbol.clone(),
+                    market_value: h.market_value,
+                    betas: proxies
+                        .iter()
+                        .zip(&fitted.betas)
+                        .map(|(p, beta)| ProxyBeta {
+                            proxy: p.symbol.clone(),
+                            beta: *beta,
+                        })
+                        .collect(),
+                    r_squared: fitted.r_squared,
+                    return_pct: projected * 100.0,
+                    pnl: h.market_value * projected,
+                });
+            }
+            positions.sort_by(|a, b| a.pnl.total_cmp(&b.pnl));
+            let measured_gross: f64 = positions.iter().map(|p| p.market_value.abs()).sum();
+            let total_pnl: f64 = positions.iter().map(|p| p.pnl).sum();
+            results.push(ScenarioResult {
+                name: scenario.name,
+                shocks: scenario.shocks,
+                proxies,
+                positions,
+                total_pnl,
+                total_pct: if measured_gross > 0.0 {
+                    total_pnl / measured_gross * 100.0
+                } else {
+                    0.0
+                },
+                measured_gross,
+                unmeasured: missed,
+            });
+        }
+        Ok(StressTestReport {
+            account,
+            at: Utc::now().timestamp(),
+            scenarios: results,
+        })
+    }
+}
@@ -0,0 +1,204 @@
+use async_trait::async_trait;
+
+use super::*;
+use crate::ibkr::types::{HistoricalBar, Position};
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+struct SeriesBars(HashMap<String, Vec<(String, f64)>>);
+
+#[async_trait]
+impl DailyBars for SeriesBars {
+    async fn daily_bars(
+        &self,
+        symbol: &str,
+        _sessions: usize,
+    ) -> Result<Vec<HistoricalBar>, IbkrError> {
+        let closes = self
+            .0
+            .get(symbol)
+            .ok_or_else(|| IbkrError::RequestFailed(format!("no bars for {symbol}")))?;
+        Ok(closes
+            .iter()
+            .map(|(time, close)| bar(time.clone(), *close, 0))
+            .collect())
+    }
+}
+
+fn bar(time: String, close: f64, volume: i64) -> HistoricalBar {
+    HistoricalBar {
+        time,
+        open: close,
+        high: close,
+        low: close,
+        close,
+        volume,
+        wap: close,
+        count: 0,
+    }
+}
+
+/// Deterministic daily returns of about ±1%.
+fn noise(seed: u64, n: usize) -> Vec<f64> {
+    let mut state = seed;
+    (0..n)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) as f64 / (1u64 << 31) as f64 - 0.5) * 0.02
+        })
+        .collect()
+}
+
+fn closes(returns: &[f64]) -> Vec<(String, f64)> {
+    let mut close = 100.0;
+    let mut out = vec![("d0000".to_string(), close)];
+    for (i, r) in returns.iter().enumerate() {
+        close *= 1.0 + r;
+        out.push((format!("d{:04}", i + 1), close));
+    }
+    out
+}
+
+fn position(symbol: &str, kind: &str, market_value: f64) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: market_value.signum(),
+        market_value,
+        contract_type: kind.to_string(),
+        ..Default::default()
+    }
+}
+
+fn service() -> StressTestService {
+    let n = 120;
+    let spy = noise(1, n);
+    // The sector moves with the market, plus its own noise.
+    let xlk: Vec<f64> = spy.iter().zip(noise(2, n)).map(|(m, e)| m + e).collect();
+    let tlt = noise(3, n);
+    let scaled = |series: &[f64], k: f64| -> Vec<f64> { series.iter().map(|r| r * k).collect() };
+    let bars = HashMap::from([
+        ("SPY".to_string(), closes(&spy)),
+        ("XLK".to_string(), closes(&xlk)),
+        ("TLT".to_string(), closes(&tlt)),
+        ("AAA".to_string(), closes(&scaled(&spy, 1.5))),
+        ("TECH".to_string(), closes(&scaled(&xlk, 1.2))),
+        ("BANK".to_string(), closes(&scaled(&tlt, -0.5))),
+    ]);
+
+    let mut config = AppConfig::default();
+    config.stress_test.scenarios.push(StressScenario::new(
+        "Tech selloff",
+        vec![
+            Shock::Price {
+                symbol: "spy".to_string(),
+                move_pct: -20.0,
+            },
+            Shock::Price {
+                symbol: "XLK".to_string(),
+                move_pct: -30.0,
+            },
+        ],
+    ));
+    let mut option = position("AAA", "OPT", 500.0);
+    option.local_symbol = "AAA   260619C00100000".to_string();
+    StressTestService::new(
+        Arc::new(FixedAccount),
+        Arc::new(StubPositions(vec![
+            position("AAA", "STK", 10_000.0),
+            position("TECH", "STK", 5_000.0),
+            position("BANK", "STK", 2_000.0),
+            position("NOBARS", "STK", 1_000.0),
+            option,
+        ])),
+        Arc::new(SeriesBars(bars)),
+        Arc::new(RwLock::new(config)),
+    )
+}
+
+fn impact<'a>(scenario: &'a ScenarioResult, symbol: &str) -> &'a PositionImpact {
+    scenario
+        .positions
+        .iter()
+        .find(|p| p.symbol == symbol)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn scenarios_project_each_position_through_its_proxy_betas() {
+    let report = service().run(None, None).await.unwrap();
+    assert_eq!(report.account, "DU1");
+    let names: Vec<&str> = report.scenarios.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "Rates +200bp",
+            "Index -20%",
+            "Tech sector -30%",
+            "Tech selloff"
+        ]
+    );
+
+    // +200bp at a 16.5 duration: TLT -33%, and BANK moves against it.
+    let rates = &report.scenarios[0];
+    assert_eq!(rates.proxies[0].symbol, "TLT");
+    assert!((rates.proxies[0].move_pct + 33.0).abs() < 1e-9);
+    let bank = impact(rates, "BANK");
+    assert!((bank.betas[0].beta + 0.5).abs() < 1e-6);
+    assert!((bank.return_pct - 16.5).abs() < 1e-6);
+    assert!((bank.pnl - 330.0).abs() < 1e-3);
+    assert_eq!(rates.unmeasured, ["NOBARS", "AAA   260619C00100000"]);
+
+    let index = &report.scenarios[1];
+    assert!((impact(index, "AAA").pnl + 3_000.0).abs() < 1e-3);
+    assert_eq!(index.positions[0].symbol, "AAA", "largest loss first");
+    let total: f64 = index.positions.iter().map(|p| p.pnl).sum();
+    assert!((index.total_pnl - total).abs() < 1e-9);
+    assert!((index.measured_gross - 17_000.0).abs() < 1e-9);
+    assert!((index.total_pct - total / 17_000.0 * 100.0).abs() < 1e-9);
+
+    // Fitted jointly, TECH loads on the sector alone and AAA on the
+    // market alone: neither takes the other's shock.
+    let selloff = &report.scenarios[3];
+    let tech = impact(selloff, "TECH");
+    assert!(tech.betas[0].beta.abs() < 1e-6);
+    assert!((tech.betas[1].beta - 1.2).abs() < 1e-6);
+    assert!((tech.pnl + 1_800.0).abs() < 1e-3);
+    assert!((impact(selloff, "AAA").return_pct + 30.0).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn one_scenario_runs_by_name() {
+    let service = service();
+    let report = service
+        .run(Some("DU2".to_string()), Some(" tech SELLOFF ".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(report.account, "DU2");
+    assert_eq!(report.scenarios.len(), 1);
+    assert_eq!(report.scenarios[0].name, "Tech selloff");
+    assert_eq!(report.scenarios[0].proxies[0].symbol, "SPY");
+
+    assert!(matches!(
+        service.run(None, Some("Crash".to_string())).await,
+        Err(StressTestError::UnknownScenario(name)) if name == "Crash"
+    ));
+}
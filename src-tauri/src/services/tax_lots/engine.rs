@@ -0,0 +1,328 @@
+//! Chronological FIFO lot engine behind the tax-lot report: realized
+//! lots per closing fill, wash sales matched to replacement lots.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Duration, Months, Utc};
+
+use super::{OpenLot, RealizedLot, WashSale, WASH_WINDOW_DAYS};
+use crate::ibkr::types::IbkrExecution;
+use crate::services::cost_basis::book::{self, QTY_EPSILON};
+
+/// A lot held before the first fill the engine sees (an imported lot).
+#[derive(Debug, Clone)]
+pub struct Opening {
+    pub account: String,
+    pub contract: String,
+    /// Signed: negative for a short lot.
+    pub quantity: f64,
+    pub unit_cost: Option<f64>,
+    pub acquired_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default)]
+pub struct Ledger {
+    /// In the order the lots were closed.
+    pub realized: Vec<RealizedLot>,
+    pub wash_sales: Vec<WashSale>,
+    /// Every lot still open at the end, FIFO order per book.
+    pub open: Vec<OpenLot>,
+}
+
+#[derive(Debug, Clone)]
+struct Lot {
+    account: String,
+    contract: String,
+    /// The fill that opened it; `None` for an opening. Shares from the
+    /// same purchase never replace each other.
+    origin: Option<String>,
+    /// Signed; negative for a short lot.
+    qty: f64,
+    /// Per unit, before `adjustment`.
+    unit_cost: Option<f64>,
+    /// Disallowed loss carried in, per unit.
+    adjustment: f64,
+    acquired_at: Option<DateTime<Utc>>,
+    /// Start of the holding period: `acquired_at`, moved back by the
+    /// holding period of a washed lot.
+    held_since: Option<DateTime<Utc>>,
+    /// A long lot bought through a fill that hasn't absorbed a wash
+    /// sale yet.
+    can_replace: bool,
+}
+
+/// The part of a loss no replacement lot has absorbed yet; a buy
+/// inside the window after the sale still can.
+#[derive(Debug, Clone)]
+struct PendingLoss {
+    realized: usize,
+    sold_at: DateTime<Utc>,
+    qty: f64,
+    loss_per_unit: f64,
+    held_for: Option<Duration>,
+}
+
+#[derive(Default)]
+struct Engine {
+    lots: Vec<Lot>,
+    /// `(account, contract)` -> indexes into `lots`, oldest first.
+    books: HashMap<(String, String), VecDeque<usize>>,
+    /// By contract.
+    pending: HashMap<String, Vec<PendingLoss>>,
+    ledger: Ledger,
+}
+
+/// Apply `openings`, then `fills` in execution order, across every
+/// account at once.
+pub fn run(openings: &[Opening], fills: &[IbkrExecution]) -> Ledger {
+    let mut engine = Engine::default();
+    for o in openings {
+        engine.push_lot(Lot {
+            account: o.account.clone(),
+            contract: o.contract.clone(),
+            origin: None,
+            qty: o.quantity,
+            unit_cost: o.unit_cost,
+            adjustment: 0.0,
+            acquired_at: o.acquired_at,
+            held_since: o.acquired_at,
+            can_replace: false,
+        });
+    }
+    let mut ordered: Vec<&IbkrExecution> = fills.iter().collect();
+    ordered.sort_by(|a, b| {
+        a.exec_time
+            .cmp(&b.exec_time)
+            .then_with(|| a.exec_id.cmp(&b.exec_id))
+    });
+    for fill in ordered {
+        engine.apply(fill);
+    }
+    let mut open = Vec::new();
+    let mut keys: Vec<&(String, String)> = engine.books.keys().collect();
+    keys.sort();
+    for key in keys {
+        for &i in &engine.books[key] {
+            let lot = &engine.lots[i];
+            open.push(OpenLot {
+                account: lot.account.clone(),
+                contract: lot.contract.clone(),
+                quantity: lot.qty,
+                acquired_at: lot.acquired_at,
+                unit_cost: lot.unit_cost.map(|c| c + lot.adjustment),
+                wash_adjustment: lot.adjustment,
+            });
+        }
+    }
+    engine.ledger.open = open;
+    engine.ledger
+}
+
+impl Engine {
+    fn push_lot(&mut self, lot: Lot) -> usize {
+        let key = (lot.account.clone(), lot.contract.clone());
+        self.lots.push(lot);
+        let i = self.lots.len() - 1;
+        self.books.entry(key).or_default().push_back(i);
+        i
+    }
+
+    fn apply(&mut self, fill: &IbkrExecution) {
+        let (qty, unit) = book::execution_lot(fill);
+        let contract = book::execution_key(fill);
+        let key = (fill.account.clone(), contract.clone());
+        let mut remaining = qty;
+        let mut losses = Vec::new();
+        while remaining.abs() > QTY_EPSILON {
+            let front = self.books.get(&key).and_then(|b| b.front().copied());
+            match front {
+                Some(i) if self.lots[i].qty.signum() != remaining.signum() => {
+                    let closed = self.lots[i].qty.abs().min(remaining.abs());
+                    if let Some(loss) = self.close(i, closed, unit, fill) {
+                        losses.push(loss);
+                    }
+                    let lot = &mut self.lots[i];
+                    lot.qty -= closed * lot.qty.signum();
+                    remaining -= closed * remaining.signum();
+                    if lot.qty.abs() <= QTY_EPSILON {
+                        self.books.get_mut(&key).expect("front").pop_front();
+                    }
+                }
+                _ => {
+                    let i = self.push_lot(Lot {
+                        account: fill.account.clone(),
+                        contract: contract.clone(),
+                        origin: Some(fill.exec_id.clone()),
+                        qty: remaining,
+                        unit_cost: Some(unit),
+                        adjustment: 0.0,
+                        acquired_at: Some(fill.exec_time),
+                        held_since: Some(fill.exec_time),
+                        can_replace: remaining > 0.0,
+                    });
+                    if remaining > 0.0 {
+                        self.absorb_pending(i);
+                    }
+                    remaining = 0.0;
+                }
+            }
+        }
+        // Replacements are looked for once the sale is applied, so
+        // shares sold in the same fill never replace each other.
+        for (loss, origin) in losses {
+            self.wash_against_open(loss, &contract, origin.as_deref());
+        }
+    }
+
+    /// Record `qty` of lot `i` closed by `fill` at `unit` per unit; a
+    /// long lot closed at a loss comes back for the wash-sale check.
+    fn close(
+        &mut self,
+        i: usize,
+        qty: f64,
+        unit: f64,
+        fill: &IbkrExecution,
+    ) -> Option<(PendingLoss, Option<String>)> {
+        let at = fill.exec_time;
+        let lot = &self.lots[i];
+        let short = lot.qty < 0.0;
+        let basis = lot.unit_cost.map(|c| c + lot.adjustment);
+        // A short lot's proceeds are the short sale, its cost the
+        // cover.
+        let (proceeds, cost_basis) = if short {
+            (basis.map(|b| b * qty), Some(unit * qty))
+        } else {
+            (Some(unit * qty), basis.map(|b| b * qty))
+        };
+        let gain = proceeds.zip(cost_basis).map(|(p, c)| p - c);
+        let long_term = lot.held_since.map(|since| {
+            since
+                .checked_add_months(Months::new(12))
+                .is_some_and(|year_on| at.date_naive() > year_on.date_naive())
+        });
+        self.ledger.realized.push(RealizedLot {
+            account: lot.account.clone(),
+            contract: lot.contract.clone(),
+            quantity: qty,
+            short,
+            acquired_at: lot.acquired_at,
+            sold_at: at,
+            closed_by: fill.exec_id.clone(),
+            proceeds,
+            cost_basis,
+            wash_adjustment: lot.adjustment * qty,
+            gain,
+            disallowed_loss: 0.0,
+            long_term,
+        });
+        let loss_per_unit = basis.map(|b| b - unit).filter(|l| *l > 0.0 && !short)?;
+        Some((
+            PendingLoss {
+                realized: self.ledger.realized.len() - 1,
+                sold_at: at,
+                qty,
+                loss_per_unit,
+                held_for: lot.held_since.map(|since| at - since),
+            },
+            lot.origin.clone(),
+        ))
+    }
+
+    /// Wash `loss` against open lots of the contract bought inside the
+    /// window before the sale, oldest first; what's left waits for a
+    /// buy inside the window after it.
+    fn wash_against_open(&mut self, mut loss: PendingLoss, contract: &str, origin: Option<&str>) {
+        let since = loss.sold_at - Duration::days(WASH_WINDOW_DAYS);
+        let mut candidates: Vec<usize> = self
+            .books
+            .iter()
+            .filter(|((_, c), _)| c == contract)
+            .flat_map(|(_, lots)| lots.iter().copied())
+            .filter(|&i| {
+                let lot = &self.lots[i];
+                lot.can_replace
+                    && lot.qty > QTY_EPSILON
+                    && lot.origin.as_deref() != origin
+                    && lot
+                        .acquired_at
+                        .is_some_and(|t| t >= since && t <= loss.sold_at)
+            })
+            .collect();
+        candidates.sort_by_key(|&i| (self.lots[i].acquired_at, i));
+        for i in candidates {
+            if loss.qty <= QTY_EPSILON {
+                break;
+            }
+            self.wash(&mut loss, i);
+        }
+        if loss.qty > QTY_EPSILON {
+            self.pending
+                .entry(contract.to_string())
+                .or_default()
+                .push(loss);
+        }
+    }
+
+    /// A long lot just bought absorbs the pending losses on its
+    /// contract from inside the window, oldest sale first.
+    fn absorb_pending(&mut self, i: usize) {
+        let contract = self.lots[i].contract.clone();
+        let Some(bought_at) = self.lots[i].acquired_at else {
+            return;
+        };
+        let Some(mut pending) = self.pending.remove(&contract) else {
+            return;
+        };
+        pending.retain(|p| bought_at - p.sold_at <= Duration::days(WASH_WINDOW_DAYS));
+        for loss in pending.iter_mut() {
+            if !self.lots[i].can_replace {
+                break;
+            }
+            // The unwashed remainder of a split stays at `i`.
+            self.wash(loss, i);
+        }
+        pending.retain(|p| p.qty > QTY_EPSILON);
+        if !pending.is_empty() {
+            self.pending.insert(contract, pending);
+        }
+    }
+
+    /// Carry as much of `loss` as lot `i` holds into its basis,
+    /// splitting the washed shares off when it holds more.
+    fn wash(&mut self, loss: &mut PendingLoss, i: usize) {
+        let washed = loss.qty.min(self.lots[i].qty);
+        let target = if self.lots[i].qty - washed > QTY_EPSILON {
+            let mut piece = self.lots[i].clone();
+            piece.qty = washed;
+            self.lots[i].qty -= washed;
+            self.lots.push(piece);
+            let j = self.lots.len() - 1;
+            let key = (self.lots[i].account.clone(), self.lots[i].contract.clone());
+            let book = self.books.get_mut(&key).expect("open lot");
+            let at = book.iter().position(|&k| k == i).expect("open lot");
+            book.insert(at, j);
+            j
+        } else {
+            i
+        };
+        let disallowed = loss.loss_per_unit * washed;
+        let lot = &mut self.lots[target];
+        lot.adjustment += loss.loss_per_unit;
+        lot.can_replace = false;
+        if let (Some(since), Some(held)) = (lot.held_since, loss.held_for) {
+            lot.held_since = Some(since - held);
+        }
+        loss.qty -= washed;
+        let realized = &mut self.ledger.realized[loss.realized];
+        realized.disallowed_loss += disallowed;
+        self.ledger.wash_sales.push(WashSale {
+            account: realized.account.clone(),
+            contract: realized.contract.clone(),
+            sold_at: realized.sold_at,
+            quantity: washed,
+            disallowed_loss: disallowed,
+            replacement_account: lot.account.clone(),
+            replacement_acquired_at: lot.acquired_at,
+        });
+    }
+}
@@ -0,0 +1,323 @@
+//! Realized tax lots for a calendar year, with wash sales flagged
+//! across accounts.
+//!
+//! The lots are rebuilt the way `services::cost_basis` rebuilds its
+//! book: per account, the imported lots as the holdings at import time,
+//! then every stored fill after that (restated across later splits),
+//! matched FIFO per contract. Every account's fills run through one
+//! chronological pass so a loss in one account can be washed by a
+//! purchase in another.
+//!
+//! A long lot sold at a loss is a wash sale when the same contract is
+//! bought within [`WASH_WINDOW_DAYS`] before or after the sale, in any
+//! account. The disallowed part of the loss is added to the basis of
+//! the replacement shares (oldest purchase first, each share replacing
+//! once) and their holding period starts earlier by the washed lot's.
+//! Shares from the same purchase as the sold lot, or sold in the same
+//! fill, don't count as replacements. A replacement later sold in the
+//! year carries the adjusted basis into its own gain.
+//!
+//! [`TaxLotService::project`] runs hypothetical sales through the same
+//! books and nets each year's reportable gains into an estimated tax at
+//! the `tax` rates, carrying net capital losses forward (see
+//! `planned` and `projection`).
+//!
+//! Not covered: losses on short lots, options on the same underlying
+//! (only the identical contract is a replacement), and imported lots
+//! as replacements: they are holdings at import time, not purchases.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
+use chrono_tz::America::New_York;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use crate::config::AppConfig;
+use crate::ibkr::types::IbkrExecution;
+use crate::services::corporate_actions;
+use crate::services::cost_basis::book::QTY_EPSILON;
+use crate::services::executions::ExecutionsStore;
+use crate::services::portfolio_import::{self, ImportError};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+mod engine;
+mod planned;
+mod projection;
+
+#[cfg(test)]
+mod tests;
+
+use engine::Opening;
+pub use planned::{PlannedSale, TaxProjection};
+
+/// Days either side of a loss sale a purchase of the same contract
+/// washes it.
+pub const WASH_WINDOW_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxConfig {
+    /// Rate on net short-term gains (ordinary income), percent.
+    #[serde(default = "default_short_term_rate_pct")]
+    pub short_term_rate_pct: f64,
+    /// Rate on net long-term gains, percent.
+    #[serde(default = "default_long_term_rate_pct")]
+    pub long_term_rate_pct: f64,
+    /// Net capital loss deductible against ordinary income per year;
+    /// the rest carries forward.
+    #[serde(default = "default_loss_deduction_limit")]
+    pub loss_deduction_limit: f64,
+}
+
+fn default_short_term_rate_pct() -> f64 {
+    32.0
+}
+
+fn default_long_term_rate_pct() -> f64 {
+    15.0
+}
+
+fn default_loss_deduction_limit() -> f64 {
+    3_000.0
+}
+
+impl Default for TaxConfig {
+    fn default() -> Self {
+        Self {
+            short_term_rate_pct: default_short_term_rate_pct(),
+            long_term_rate_pct: default_long_term_rate_pct(),
+            loss_deduction_limit: default_loss_deduction_limit(),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum TaxLotError {
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+    #[error("imported lots: {0}")]
+    Import(#[from] ImportError),
+    #[error("{0}")]
+    Invalid(String),
+}
+
+/// One lot, or part of one, closed by a fill.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RealizedLot {
+    pub account: String,
+    /// Ticker for stock; `SYMBOL YYYYMMDD STRIKE RIGHT` for an option.
+    pub contract: String,
+    pub quantity: f64,
+    /// A short lot: proceeds are the short sale, the cost the cover.
+    pub short: bool,
+    /// `None` for an imported lot without a date.
+    pub acquired_at: Option<DateTime<Utc>>,
+    pub sold_at: DateTime<Utc>,
+    /// Exec id of the closing fill.
+    pub closed_by: String,
+    /// Multiplier and commissions included. `None` on the side an
+    /// imported lot without a basis leaves unknown.
+    pub proceeds: Option<f64>,
+    /// Including `wash_adjustment`.
+    pub cost_basis: Option<f64>,
+    /// Losses disallowed on earlier sales and carried into this lot.
+    pub wash_adjustment: f64,
+    /// `proceeds - cost_basis`, before any disallowance.
+    pub gain: Option<f64>,
+    /// The part of a loss a replacement purchase deferred; positive.
+    pub disallowed_loss: f64,
+    /// Held more than a year, counting a washed lot's holding period.
+    pub long_term: Option<bool>,
+}
+
+impl RealizedLot {
+    /// The gain to report: a loss less its disallowed part.
+    pub fn reportable_gain(&self) -> Option<f64> {
+        self.gain.map(|g| g + self.disallowed_loss)
+    }
+}
+
+/// A loss deferred into one replacement lot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WashSale {
+    pub account: String,
+    pub contract: String,
+    pub sold_at: DateTime<Utc>,
+    /// Shares washed by this replacement.
+    pub quantity: f64,
+    pub disallowed_loss: f64,
+    pub replacement_account: String,
+    pub replacement_acquired_at: Option<DateTime<Utc>>,
+}
+
+/// A lot still held.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenLot {
+    pub account: String,
+    pub contract: String,
+    /// Negative for a short lot.
+    pub quantity: f64,
+    pub acquired_at: Option<DateTime<Utc>>,
+    /// Per unit, including `wash_adjustment`.
+    pub unit_cost: Option<f64>,
+    /// Disallowed loss carried in, per unit.
+    pub wash_adjustment: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxTotals {
+    pub proceeds: f64,
+    pub cost_basis: f64,
+    pub disallowed_loss: f64,
+    /// Reportable: losses net of their disallowed part.
+    pub short_term_gain: f64,
+    pub long_term_gain: f64,
+    /// Lots without a basis or a holding period, left out of the
+    /// sums.
+    pub incomplete_lots: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxLotReport {
+    pub year: i32,
+    /// Unix seconds.
+    pub as_of: i64,
+    pub accounts: Vec<String>,
+    pub first_fill_at: Option<DateTime<Utc>>,
+    /// Lots sold in the year (ET), oldest sale first.
+    pub realized: Vec<RealizedLot>,
+    /// Wash sales on the year's losses, listed apart from `realized`.
+    pub wash_sales: Vec<WashSale>,
+    /// Lots still held whose basis carries a disallowed loss.
+    pub adjusted_lots: Vec<OpenLot>,
+    pub totals: TaxTotals,
+}
+
+pub struct TaxLotService {
+    db: Arc<Db>,
+    executions: Arc<ExecutionsStore>,
+    config: Arc<RwLock<AppConfig>>,
+}
+
+impl TaxLotService {
+    pub fn new(
+        db: Arc<Db>,
+        executions: Arc<ExecutionsStore>,
+        config: Arc<RwLock<AppConfig>>,
+    ) -> Self {
+        Self {
+            db,
+            executions,
+            config,
+        }
+    }
+
+    /// Lots realized in `year` across every account.
+    pub async fn report(&self, year: i32) -> Result<TaxLotReport, TaxLotError> {
+        let (accounts, openings, fills) = self.history().await?;
+        let first_fill_at = fills.iter().map(|f| f.exec_time).min();
+        let ledger = engine::run(&openings, &fills);
+
+        let in_year = |t: &DateTime<Utc>| t.with_timezone(&New_York).year() == year;
+        let mut realized: Vec<RealizedLot> = ledger
+            .realized
+            .into_iter()
+            .filter(|r| in_year(&r.sold_at))
+            .collect();
+        realized.sort_by_key(|r| r.sold_at);
+        let wash_sales = ledger
+            .wash_sales
+            .into_iter()
+            .filter(|w| in_year(&w.sold_at))
+            .collect();
+        let adjusted_lots = ledger
+            .open
+            .into_iter()
+            .filter(|l| l.wash_adjustment > 0.0)
+            .collect();
+        Ok(TaxLotReport {
+            year,
+            as_of: Utc::now().timestamp(),
+            accounts: accounts.into_iter().collect(),
+            first_fill_at,
+            totals: totals(&realized),
+            realized,
+            wash_sales,
+            adjusted_lots,
+        })
+    }
+
+    /// Every account, the imported lots the books start from, and the
+    /// stored fills after each account's import.
+    async fn history(
+        &self,
+    ) -> Result<(BTreeSet<String>, Vec<Opening>, Vec<IbkrExecution>), TaxLotError> {
+        let lots = portfolio_import::list_lots(&self.db).await?;
+        let mut accounts: BTreeSet<String> =
+            self.executions.accounts().await?.into_iter().collect();
+        accounts.extend(lots.iter().map(|l| l.account.clone()));
+
+        let mut openings = Vec::new();
+        let mut fills = Vec::new();
+        for account in &accounts {
+            let own: Vec<_> = lots.iter().filter(|l| &l.account == account).collect();
+            for lot in &own {
+                openings.push(Opening {
+                    account: account.clone(),
+                    contract: lot.symbol.to_uppercase(),
+                    quantity: lot.quantity,
+                    unit_cost: lot
+                        .cost_basis_total
+                        .filter(|_| lot.quantity.abs() > QTY_EPSILON)
+                        .map(|total| total / lot.quantity),
+                    acquired_at: lot
+                        .acquired_date
+                        .as_deref()
+                        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+                        .and_then(|d| d.and_hms_opt(0, 0, 0))
+                        .map(|t| t.and_utc()),
+                });
+            }
+            let after = own
+                .iter()
+                .map(|l| l.imported_at_unix)
+                .max()
+                .and_then(|t| Utc.timestamp_opt(t, 0).single());
+            fills.extend(self.executions.query_after(account, after).await?);
+        }
+        let fills = corporate_actions::split_adjusted(&self.db, fills).await?;
+        Ok((accounts, openings, fills))
+    }
+}
+
+fn totals(realized: &[RealizedLot]) -> TaxTotals {
+    let mut t = TaxTotals::default();
+    for lot in realized {
+        let (Some(proceeds), Some(cost), Some(gain), Some(long_term)) = (
+            lot.proceeds,
+            lot.cost_basis,
+            lot.reportable_gain(),
+            lot.long_term,
+        ) else {
+            t.incomplete_lots += 1;
+            continue;
+        };
+        t.proceeds += proceeds;
+        t.cost_basis += cost;
+        t.disallowed_loss += lot.disallowed_loss;
+        if long_term {
+            t.long_term_gain += gain;
+        } else {
+            t.short_term_gain += gain;
+        }
+    }
+    t
+}
@@ -0,0 +1,244 @@
+//! Hypothetical sales run through the books: the lots each would
+//! close and what it adds to each year's estimated tax.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use chrono::{Datelike, NaiveDate, TimeZone, Utc};
+use chrono_tz::America::New_York;
+use serde::{Deserialize, Serialize};
+
+use super::projection::{self, Carryover, Netting};
+use super::{engine, RealizedLot, TaxLotError, TaxLotService};
+use crate::ibkr::types::{ExecutionSide, IbkrExecution};
+use crate::services::cost_basis::book::QTY_EPSILON;
+
+/// A hypothetical stock sale.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedSale {
+    /// `None` picks the one account holding the stock.
+    pub account: Option<String>,
+    pub symbol: String,
+    pub quantity: f64,
+    /// Per share, net of commission.
+    pub price: f64,
+    /// Placed at the ET close of the day.
+    pub date: NaiveDate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedSaleOutcome {
+    pub account: String,
+    pub symbol: String,
+    pub quantity: f64,
+    pub price: f64,
+    pub date: NaiveDate,
+    /// The lots it would close, FIFO.
+    pub lots: Vec<RealizedLot>,
+    /// Reportable: losses net of their disallowed part.
+    pub short_term_gain: f64,
+    pub long_term_gain: f64,
+    /// Losses a purchase within the wash window would defer.
+    pub disallowed_loss: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YearProjection {
+    pub year: i32,
+    /// Reportable gains already realized in the year.
+    pub realized_short_term: f64,
+    pub realized_long_term: f64,
+    /// Reportable gains of the planned sales dated in the year.
+    pub planned_short_term: f64,
+    pub planned_long_term: f64,
+    pub carryover_in: Carryover,
+    pub netting: Netting,
+    /// `netting.estimated_tax` less the estimate without the planned
+    /// sales: what they add (negative: save) this year.
+    pub planned_tax: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxProjection {
+    /// Unix seconds.
+    pub as_of: i64,
+    pub short_term_rate_pct: f64,
+    pub long_term_rate_pct: f64,
+    /// In the order given.
+    pub sales: Vec<PlannedSaleOutcome>,
+    /// From the current year through the last planned sale's; losses
+    /// carried in come from every year of stored fills.
+    pub years: Vec<YearProjection>,
+    /// Lots in those years without a basis or a holding period, left
+    /// out of the sums.
+    pub incomplete_lots: usize,
+}
+
+impl TaxLotService {
+    /// Project `sales` (dated `today` or later, ET) onto the books and
+    /// estimate each year's tax from the current year on.
+    pub async fn project(
+        &self,
+        sales: &[PlannedSale],
+        today: NaiveDate,
+    ) -> Result<TaxProjection, TaxLotError> {
+        let rates = self.config.read().await.tax.clone();
+        let (_, openings, mut fills) = self.history().await?;
+        let held = engine::run(&openings, &fills).open;
+
+        let mut resolved = Vec::with_capacity(sales.len());
+        for (n, sale) in sales.iter().enumerate() {
+            let symbol = sale.symbol.trim().to_uppercase();
+            let account = match sale.account.as_deref().map(str::trim) {
+                Some(a) if !a.is_empty() => a.to_string(),
+                _ => {
+                    let holders: BTreeSet<&str> = held
+                        .iter()
+                        .filter(|l| l.contract == symbol && l.quantity > QTY_EPSILON)
+                        .map(|l| l.account.as_str())
+                        .collect();
+                    let holders: Vec<&str> = holders.into_iter().collect();
+                    match holders.as_slice() {
+                        [only] => only.to_string(),
+                        [] => {
+                            return Err(TaxLotError::Invalid(format!("no account holds {symbol}")))
+                        }
+                        several => {
+                            return Err(TaxLotError::Invalid(format!(
+                                "{symbol} is held in {}; pass the account",
+                                several.join(", ")
+                            )))
+                        }
+                    }
+                }
+            };
+            let close = sale
+                .date
+                .and_hms_opt(16, 0, 0)
+                .and_then(|t| New_York.from_local_datetime(&t).earliest())
+                .map(|t| t.with_timezone(&Utc))
+                .ok_or_else(|| TaxLotError::Invalid(format!("no ET close on {}", sale.date)))?;
+            let exec_id = format!("planned-{n:04}");
+            fills.push(IbkrExecution {
+                symbol: symbol.clone(),
+                side: ExecutionSide::Sold,
+                qty: sale.quantity,
+                avg_price: sale.price,
+                exec_time: close,
+                order_id: 0,
+                exec_id: exec_id.clone(),
+                account: account.clone(),
+                contract_type: "STK".to_string(),
+                expiry: None,
+                strike: None,
+                right: None,
+                multiplier: None,
+                commission: None,
+                realized_pnl: None,
+                currency: None,
+                commission_currency: None,
+            });
+            resolved.push((exec_id, account, symbol, sale));
+        }
+        let ledger = engine::run(&openings, &fills);
+
+        let mut planned_lots: HashMap<&str, Vec<RealizedLot>> = HashMap::new();
+        let current_year = today.year();
+        // Year -> reportable [realized st, realized lt, planned st, planned lt].
+        let mut by_year: BTreeMap<i32, [f64; 4]> = BTreeMap::new();
+        let mut incomplete_lots = 0;
+        for lot in &ledger.realized {
+            let planned = resolved.iter().find(|(id, ..)| *id == lot.closed_by);
+            let year = lot.sold_at.with_timezone(&New_York).year();
+            if let Some((id, ..)) = planned {
+                planned_lots
+                    .entry(id.as_str())
+                    .or_default()
+                    .push(lot.clone());
+            }
+            let (Some(gain), Some(long_term)) = (lot.reportable_gain(), lot.long_term) else {
+                if year >= current_year {
+                    incomplete_lots += 1;
+                }
+                continue;
+            };
+            let slot = usize::from(long_term) + if planned.is_some() { 2 } else { 0 };
+            by_year.entry(year).or_default()[slot] += gain;
+        }
+
+        let mut outcomes = Vec::with_capacity(resolved.len());
+        for (id, account, symbol, sale) in &resolved {
+            let lots = planned_lots.remove(id.as_str()).unwrap_or_default();
+            let sold: f64 = lots.iter().map(|l| l.quantity).sum();
+            if sold + QTY_EPSILON < sale.quantity {
+                return Err(TaxLotError::Invalid(format!(
+                    "the {} sale of {} {symbol} in {account} exceeds the {sold} shares held",
+                    sale.date, sale.quantity
+                )));
+            }
+            let term = |long: bool| -> f64 {
+                lots.iter()
+                    .filter(|l| l.long_term == Some(long))
+                    .filter_map(RealizedLot::reportable_gain)
+                    .sum()
+            };
+            outcomes.push(PlannedSaleOutcome {
+                account: account.clone(),
+                symbol: symbol.clone(),
+                quantity: sale.quantity,
+                price: sale.price,
+                date: sale.date,
+                short_term_gain: term(false),
+                long_term_gain: term(true),
+                disallowed_loss: lots.iter().map(|l| l.disallowed_loss).sum(),
+                lots,
+            });
+        }
+
+        let first_year = by_year.keys().next().copied().unwrap_or(current_year);
+        let last_year = by_year
+            .keys()
+            .next_back()
+            .copied()
+            .unwrap_or(current_year)
+            .max(current_year);
+        let (mut carry, mut carry_without) = (Carryover::default(), Carryover::default());
+        let mut years = Vec::new();
+        for year in first_year.min(current_year)..=last_year {
+            let [realized_st, realized_lt, planned_st, planned_lt] =
+                by_year.get(&year).copied().unwrap_or_default();
+            let netting = projection::net(
+                realized_st + planned_st,
+                realized_lt + planned_lt,
+                carry,
+                &rates,
+            );
+            let without = projection::net(realized_st, realized_lt, carry_without, &rates);
+            if year >= current_year {
+                years.push(YearProjection {
+                    year,
+                    realized_short_term: realized_st,
+                    realized_long_term: realized_lt,
+                    planned_short_term: planned_st,
+                    planned_long_term: planned_lt,
+                    carryover_in: carry,
+                    netting,
+                    planned_tax: netting.estimated_tax - without.estimated_tax,
+                });
+            }
+            carry = netting.carryover_out;
+            carry_without = without.carryover_out;
+        }
+        Ok(TaxProjection {
+            as_of: Utc::now().timestamp(),
+            short_term_rate_pct: rates.short_term_rate_pct,
+            long_term_rate_pct: rates.long_term_rate_pct,
+            sales: outcomes,
+            years,
+            incomplete_lots,
+        })
+    }
+}
@@ -0,0 +1,106 @@
+//! Year-by-year netting of reportable gains into an estimated tax, with
+//! net capital losses carried forward.
+
+use serde::{Deserialize, Serialize};
+
+use super::TaxConfig;
+
+/// Capital loss carried into the next year, by character; positive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Carryover {
+    pub short_term: f64,
+    pub long_term: f64,
+}
+
+/// One year's netting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Netting {
+    /// Net short-term gain after offsets; zero on a net loss.
+    pub taxable_short_term: f64,
+    pub taxable_long_term: f64,
+    /// Net loss deducted against ordinary income, up to
+    /// `tax.loss_deduction_limit`.
+    pub loss_deduction: f64,
+    pub carryover_out: Carryover,
+    /// Negative when the deduction saves more than the gains cost.
+    pub estimated_tax: f64,
+}
+
+/// Net a year's short- and long-term gains, after the losses carried
+/// in: a net loss on one side offsets a net gain on the other; what's
+/// left of a net loss is deducted at the short-term rate up to the
+/// limit, short-term first, and the rest carries forward keeping its
+/// character.
+pub fn net(short_term: f64, long_term: f64, carry_in: Carryover, rates: &TaxConfig) -> Netting {
+    let st = short_term - carry_in.short_term;
+    let lt = long_term - carry_in.long_term;
+    let (mut taxable_st, mut taxable_lt, mut loss_st, mut loss_lt) = (0.0, 0.0, 0.0, 0.0);
+    match (st >= 0.0, lt >= 0.0) {
+        (true, true) => (taxable_st, taxable_lt) = (st, lt),
+        (false, true) if st + lt >= 0.0 => taxable_lt = st + lt,
+        (false, true) => loss_st = -(st + lt),
+        (true, false) if st + lt >= 0.0 => taxable_st = st + lt,
+        (true, false) => loss_lt = -(st + lt),
+        (false, false) => (loss_st, loss_lt) = (-st, -lt),
+    }
+    let limit = rates.loss_deduction_limit.max(0.0);
+    let deduct_st = loss_st.min(limit);
+    let deduct_lt = loss_lt.min(limit - deduct_st);
+    let loss_deduction = deduct_st + deduct_lt;
+    Netting {
+        taxable_short_term: taxable_st,
+        taxable_long_term: taxable_lt,
+        loss_deduction,
+        carryover_out: Carryover {
+            short_term: loss_st - deduct_st,
+            long_term: loss_lt - deduct_lt,
+        },
+        estimated_tax: (taxable_st - loss_deduction) * rates.short_term_rate_pct / 100.0
+            + taxable_lt * rates.long_term_rate_pct / 100.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rates() -> TaxConfig {
+        TaxConfig {
+            short_term_rate_pct: 30.0,
+            long_term_rate_pct: 15.0,
+            loss_deduction_limit: 3_000.0,
+        }
+    }
+
+    #[test]
+    fn losses_offset_across_terms_then_deduct_and_carry() {
+        let none = Carryover::default();
+        let both = net(1_000.0, 2_000.0, none, &rates());
+        assert_eq!(both.estimated_tax, 600.0);
+
+        // A short-term loss eats into the long-term gain.
+        let offset = net(-500.0, 2_000.0, none, &rates());
+        assert_eq!(offset.taxable_long_term, 1_500.0);
+        assert_eq!(offset.estimated_tax, 225.0);
+
+        // $10k of net loss: $3k deducted, short-term first.
+        let loss = net(-2_000.0, -8_000.0, none, &rates());
+        assert_eq!(loss.loss_deduction, 3_000.0);
+        assert_eq!(loss.estimated_tax, -900.0);
+        assert_eq!(
+            loss.carryover_out,
+            Carryover {
+                short_term: 0.0,
+                long_term: 7_000.0
+            }
+        );
+
+        // The carryover keeps its character next year.
+        let next = net(1_000.0, 4_000.0, loss.carryover_out, &rates());
+        assert_eq!(next.taxable_short_term, 0.0);
+        assert_eq!(next.loss_deduction, 2_000.0);
+        assert_eq!(next.carryover_out, Carryover::default());
+    }
+}
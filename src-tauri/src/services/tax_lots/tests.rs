@@ -0,0 +1,257 @@
+use chrono::TimeZone;
+use tempfile::NamedTempFile;
+
+use super::*;
+use crate::ibkr::types::{ExecutionSide, IbkrExecution};
+
+fn fill(
+    exec_id: &str,
+    account: &str,
+    symbol: &str,
+    side: ExecutionSide,
+    qty: f64,
+    price: f64,
+    (y, m, d): (i32, u32, u32),
+) -> IbkrExecution {
+    IbkrExecution {
+        symbol: symbol.to_string(),
+        side,
+        qty,
+        avg_price: price,
+        exec_time: Utc.with_ymd_and_hms(y, m, d, 15, 0, 0).unwrap(),
+        order_id: 1,
+        exec_id: exec_id.to_string(),
+        account: account.to_string(),
+        contract_type: "STK".to_string(),
+        expiry: None,
+        strike: None,
+        right: None,
+        multiplier: None,
+        commission: None,
+        realized_pnl: None,
+        currency: Some("USD".to_string()),
+        commission_currency: None,
+    }
+}
+
+#[tokio::test]
+async fn losses_are_washed_by_purchases_in_any_account() {
+    use ExecutionSide::{Bought, Sold};
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let executions = Arc::new(ExecutionsStore::new(Arc::clone(&db)));
+    executions
+        .record(&[
+            // Sold at a loss in DU1, bought back 18 days later in DU2.
+            fill("a1", "DU1", "AAPL", Bought, 100.0, 50.0, (2026, 1, 5)),
+            fill("a2", "DU1", "AAPL", Sold, 100.0, 40.0, (2026, 3, 2)),
+            fill("a3", "DU2", "AAPL", Bought, 60.0, 42.0, (2026, 3, 20)),
+            fill("a4", "DU2", "AAPL", Sold, 60.0, 45.0, (2026, 5, 1)),
+            // The second purchase replaces part of the first; the
+            // shares sold with it don't.
+            fill("m1", "DU1", "MSFT", Bought, 100.0, 100.0, (2026, 6, 1)),
+            fill("m2", "DU1", "MSFT", Bought, 50.0, 95.0, (2026, 6, 20)),
+            fill("m3", "DU1", "MSFT", Sold, 120.0, 90.0, (2026, 6, 25)),
+            // A larger purchase: only the washed shares carry the loss.
+            fill("d1", "DU1", "AMD", Bought, 10.0, 100.0, (2026, 8, 3)),
+            fill("d2", "DU1", "AMD", Sold, 10.0, 90.0, (2026, 8, 10)),
+            fill("d3", "DU2", "AMD", Bought, 25.0, 91.0, (2026, 8, 12)),
+            // A December loss washed in January.
+            fill("t1", "DU1", "TSLA", Bought, 10.0, 300.0, (2025, 6, 2)),
+            fill("t2", "DU1", "TSLA", Sold, 10.0, 200.0, (2025, 12, 19)),
+            fill("t3", "DU1", "TSLA", Bought, 10.0, 210.0, (2026, 1, 9)),
+            fill("n1", "DU1", "NVDA", Bought, 10.0, 100.0, (2025, 1, 2)),
+            fill("n2", "DU1", "NVDA", Sold, 10.0, 150.0, (2026, 2, 2)),
+        ])
+        .await
+        .unwrap();
+    let service = TaxLotService::new(db, executions, Arc::new(RwLock::new(AppConfig::default())));
+
+    let report = service.report(2026).await.unwrap();
+    assert_eq!(report.accounts, ["DU1", "DU2"]);
+    let lots: Vec<(&str, &str, f64, f64)> = report
+        .realized
+        .iter()
+        .map(|r| {
+            (
+                r.account.as_str(),
+                r.contract.as_str(),
+                r.quantity,
+                r.disallowed_loss,
+            )
+        })
+        .collect();
+    assert_eq!(
+        lots,
+        [
+            ("DU1", "NVDA", 10.0, 0.0),
+            ("DU1", "AAPL", 100.0, 600.0),
+            ("DU2", "AAPL", 60.0, 0.0),
+            ("DU1", "MSFT", 100.0, 300.0),
+            ("DU1", "MSFT", 20.0, 0.0),
+            ("DU1", "AMD", 10.0, 100.0),
+        ]
+    );
+    assert_eq!(report.realized[0].long_term, Some(true));
+    assert_eq!(report.realized[1].reportable_gain(), Some(-400.0));
+
+    // The replacement carries $10 a share and the washed lot's 56 days.
+    let replacement = &report.realized[2];
+    assert_eq!(replacement.wash_adjustment, 600.0);
+    assert_eq!(replacement.cost_basis, Some(3_120.0));
+    assert_eq!(replacement.gain, Some(-420.0));
+    assert_eq!(replacement.long_term, Some(false));
+
+    let washes: Vec<(&str, f64, f64, &str)> = report
+        .wash_sales
+        .iter()
+        .map(|w| {
+            (
+                w.contract.as_str(),
+                w.quantity,
+                w.disallowed_loss,
+                w.replacement_account.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        washes,
+        [
+            ("AAPL", 60.0, 600.0, "DU2"),
+            ("MSFT", 30.0, 300.0, "DU1"),
+            ("AMD", 10.0, 100.0, "DU2"),
+        ]
+    );
+
+    let adjusted: Vec<(&str, f64, Option<f64>)> = report
+        .adjusted_lots
+        .iter()
+        .map(|l| (l.contract.as_str(), l.quantity, l.unit_cost))
+        .collect();
+    assert_eq!(
+        adjusted,
+        [
+            ("MSFT", 30.0, Some(105.0)),
+            ("TSLA", 10.0, Some(310.0)),
+            ("AMD", 10.0, Some(101.0)),
+        ]
+    );
+
+    assert_eq!(report.totals.disallowed_loss, 1_000.0);
+    assert_eq!(report.totals.short_term_gain, -1_620.0);
+    assert_eq!(report.totals.long_term_gain, 500.0);
+    assert_eq!(report.totals.incomplete_lots, 0);
+
+    let last_year = service.report(2025).await.unwrap();
+    assert_eq!(last_year.realized.len(), 1);
+    assert_eq!(last_year.realized[0].disallowed_loss, 1_000.0);
+    assert_eq!(last_year.wash_sales.len(), 1);
+    assert_eq!(last_year.totals.short_term_gain, 0.0);
+}
+
+#[tokio::test]
+async fn planned_sales_are_netted_into_each_years_tax() {
+    use ExecutionSide::{Bought, Sold};
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let executions = Arc::new(ExecutionsStore::new(Arc::clone(&db)));
+    executions
+        .record(&[
+            fill("a1", "DU1", "AAPL", Bought, 100.0, 50.0, (2025, 3, 3)),
+            fill("a2", "DU1", "AAPL", Bought, 50.0, 80.0, (2026, 2, 2)),
+            fill("m1", "DU1", "MSFT", Bought, 10.0, 100.0, (2026, 1, 5)),
+            fill("m2", "DU1", "MSFT", Sold, 10.0, 90.0, (2026, 2, 10)),
+        ])
+        .await
+        .unwrap();
+    let mut config = AppConfig::default();
+    config.tax.short_term_rate_pct = 30.0;
+    config.tax.long_term_rate_pct = 15.0;
+    let service = TaxLotService::new(db, executions, Arc::new(RwLock::new(config)));
+    let today = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+    let sale = |symbol: &str, quantity, price, (y, m, d)| PlannedSale {
+        account: None,
+        symbol: symbol.to_string(),
+        quantity,
+        price,
+        date: NaiveDate::from_ymd_opt(y, m, d).unwrap(),
+    };
+
+    let projection = service
+        .project(
+            &[
+                // 100 long-term at +$20, then 20 short-term at -$10.
+                sale("aapl", 120.0, 70.0, (2026, 6, 15)),
+                // The other 30 of February's, still short-term.
+                sale("AAPL", 30.0, 60.0, (2027, 1, 15)),
+            ],
+            today,
+        )
+        .await
+        .unwrap();
+    let first = &projection.sales[0];
+    assert_eq!(first.account, "DU1");
+    assert_eq!(first.lots.len(), 2);
+    assert_eq!(first.long_term_gain, 2_000.0);
+    assert_eq!(first.short_term_gain, -200.0);
+    assert_eq!(first.disallowed_loss, 0.0);
+    assert_eq!(projection.sales[1].short_term_gain, -600.0);
+
+    let years: Vec<i32> = projection.years.iter().map(|y| y.year).collect();
+    assert_eq!(years, [2026, 2027]);
+    let this_year = &projection.years[0];
+    assert_eq!(this_year.realized_short_term, -100.0);
+    assert_eq!(this_year.netting.taxable_long_term, 1_700.0);
+    assert_eq!(this_year.netting.estimated_tax, 255.0);
+    // Without the sales MSFT's loss would have saved $30.
+    assert_eq!(this_year.planned_tax, 285.0);
+    let next_year = &projection.years[1];
+    assert_eq!(next_year.netting.loss_deduction, 600.0);
+    assert_eq!(next_year.planned_tax, -180.0);
+
+    let oversold = service
+        .project(&[sale("AAPL", 151.0, 70.0, (2026, 6, 15))], today)
+        .await;
+    assert!(matches!(oversold, Err(TaxLotError::Invalid(_))));
+    let unheld = service
+        .project(&[sale("NVDA", 1.0, 70.0, (2026, 6, 15))], today)
+        .await;
+    assert!(matches!(unheld, Err(TaxLotError::Invalid(_))));
+}
+
+#[tokio::test]
+async fn fills_before_a_split_count_in_post_split_shares() {
+    use ExecutionSide::{Bought, Sold};
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let executions = Arc::new(ExecutionsStore::new(Arc::clone(&db)));
+    executions
+        .record(&[
+            fill("n1", "DU1", "NVDA", Bought, 10.0, 1_000.0, (2026, 6, 3)),
+            fill("n2", "DU1", "NVDA", Sold, 100.0, 120.0, (2026, 7, 1)),
+        ])
+        .await
+        .unwrap();
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO corporate_actions \
+               (kind, symbol, effective_date, action, source, adjusted, applied_at) \
+             VALUES ('split', 'NVDA', '2026-06-10', \
+                     '{\"kind\":\"split\",\"symbol\":\"NVDA\",\"effective_date\":\"2026-06-10\",\"ratio\":10.0}', \
+                     'manual', '[]', 0)",
+            [],
+        )?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+    let service = TaxLotService::new(db, executions, Arc::new(RwLock::new(AppConfig::default())));
+
+    let report = service.report(2026).await.unwrap();
+    assert_eq!(report.realized.len(), 1);
+    let lot = &report.realized[0];
+    assert_eq!(lot.quantity, 100.0);
+    assert_eq!(lot.cost_basis, Some(10_000.0));
+    assert_eq!(lot.gain, Some(2_000.0));
+    assert!(report.adjusted_lots.is_empty());
+}
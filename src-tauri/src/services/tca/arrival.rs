@@ -0,0 +1,234 @@
+//! `order_arrivals` I/O and the execution-quality report.
+//!
+//! `ibkr_place_order` records an [`ArrivalQuote`] per placed order;
+//! [`ArrivalStore::execution_quality`] joins each to its fills through
+//! `executions(account, order_id)` and measures the average fill
+//! against the arrival mid. Unlike the intent slippage (fill vs the
+//! price the trader meant to pay), this is the cost of the execution
+//! itself — spread crossed plus drift while the order worked — so
+//! limit and market orders can be compared on the same footing.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rusqlite::params;
+
+use crate::storage::error::Result as StorageResult;
+use crate::storage::Db;
+
+use super::attribution::et_range_utc;
+use super::intent::parse_rfc3339;
+use super::types::{
+    ArrivalQuote, ExecutionQualityBucket, ExecutionQualityReport, IntentSide, OrderExecutionQuality,
+};
+
+#[derive(Clone)]
+pub struct ArrivalStore {
+    db: Arc<Db>,
+}
+
+impl ArrivalStore {
+    pub fn new(db: Arc<Db>) -> Self {
+        Self { db }
+    }
+
+    /// Insert the arrival, replacing any row under the same order id.
+    pub async fn record(&self, quote: ArrivalQuote) -> StorageResult<()> {
+        self.db
+            .with_conn(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO order_arrivals (
+                        account, order_id, symbol, side, order_type, qty,
+                        limit_price_cents, bid_cents, ask_cents, placed_at
+                     ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                    params![
+                        quote.account,
+                        quote.order_id,
+                        quote.symbol,
+                        quote.side.as_str(),
+                        quote.order_type,
+                        quote.qty,
+                        quote.limit_price_cents,
+                        quote.bid_cents,
+                        quote.ask_cents,
+                        quote.placed_at.to_rfc3339(),
+                    ],
+                )?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Orders placed over `[date_from, date_to]` ET trading days that
+    /// filled, against their arrival mids, rolled up by symbol and by
+    /// order type.
+    pub async fn execution_quality(
+        &self,
+        date_from: NaiveDate,
+        date_to_inclusive: NaiveDate,
+        account: &str,
+    ) -> StorageResult<ExecutionQualityReport> {
+        let (start, end) = et_range_utc(date_from, date_to_inclusive);
+        let account = account.to_string();
+        let (rows, without_arrival) = self
+            .db
+            .with_conn(move |conn| {
+                let (start, end) = (start.to_rfc3339(), end.to_rfc3339());
+                // Fills a minute before `placed_at` still count: the
+                // timestamp is the local clock, the fill IBKR's.
+                let mut stmt = conn.prepare(
+                    "SELECT a.account, a.order_id, a.symbol, a.side, a.order_type,
+                            a.qty, a.limit_price_cents, a.bid_cents, a.ask_cents,
+                            a.placed_at, SUM(e.qty), SUM(e.qty * e.avg_price),
+                            MIN(e.exec_time), MAX(e.exec_time)
+                     FROM order_arrivals a
+                     JOIN executions e
+                       ON e.account = a.account AND e.order_id = a.order_id
+                      AND julianday(e.exec_time) >= julianday(a.placed_at) - 60.0 / 86400
+                     WHERE a.account = ?1 AND a.placed_at >= ?2 AND a.placed_at < ?3
+                     GROUP BY a.account, a.order_id
+                     ORDER BY a.placed_at ASC",
+                )?;
+                let rows = stmt
+                    .query_map(params![account, start, end], |row| {
+                        let time = |i: usize| parse_rfc3339(&row.get::<_, String>(i)?, i);
+                        let side: String = row.get(3)?;
+                        let quote = ArrivalQuote {
+                            account: row.get(0)?,
+                            order_id: row.get(1)?,
+                            symbol: row.get(2)?,
+                            side: IntentSide::parse(&side).unwrap_or(IntentSide::Buy),
+                            order_type: row.get(4)?,
+                            qty: row.get(5)?,
+                            limit_price_cents: row.get(6)?,
+                            bid_cents: row.get(7)?,
+                            ask_cents: row.get(8)?,
+                            placed_at: time(9)?,
+                        };
+                        let filled: f64 = row.get(10)?;
+                        let notional: f64 = row.get(11)?;
+                        Ok((quote, filled, notional, time(12)?, time(13)?))
+                    })?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                // Filled orders in the window without an arrival row.
+                let missing: i64 = conn.query_row(
+                    "SELECT COUNT(DISTINCT e.order_id)
+                     FROM executions e
+                     LEFT JOIN order_arrivals a
+                       ON a.account = e.account AND a.order_id = e.order_id
+                     WHERE e.account = ?1 AND e.exec_time >= ?2 AND e.exec_time < ?3
+                       AND a.order_id IS NULL",
+                    params![account, start, end],
+                    |r| r.get(0),
+                )?;
+                Ok((rows, missing))
+            })
+            .await?;
+
+        let orders: Vec<OrderExecutionQuality> = rows
+            .into_iter()
+            .filter(|(_, filled, ..)| *filled > 0.0)
+            .map(|(quote, filled, notional, first, last)| {
+                measure(quote, filled, notional, first, last)
+            })
+            .collect();
+        let unmeasured_orders =
+            without_arrival + orders.iter().filter(|o| o.slippage_bps.is_none()).count() as i64;
+        Ok(ExecutionQualityReport {
+            by_symbol: buckets(&orders, |o| o.symbol.clone()),
+            by_order_type: buckets(&orders, |o| o.order_type.clone()),
+            overall: bucket("all".to_string(), orders.iter()),
+            orders,
+            unmeasured_orders,
+        })
+    }
+}
+
+fn measure(
+    quote: ArrivalQuote,
+    filled_qty: f64,
+    notional: f64,
+    first_fill_at: DateTime<Utc>,
+    last_fill_at: DateTime<Utc>,
+) -> OrderExecutionQuality {
+    let avg_fill_price = notional / filled_qty;
+    let mid = quote.mid();
+    // Positive is a cost on either side.
+    let per_share = mid.map(|mid| match quote.side {
+        IntentSide::Buy => avg_fill_price - mid,
+        IntentSide::Sell => mid - avg_fill_price,
+    });
+    OrderExecutionQuality {
+        arrival_spread_bps: mid
+            .zip(quote.bid_cents.zip(quote.ask_cents))
+            .map(|(mid, (bid, ask))| (ask - bid) as f64 / 100.0 / mid * 10_000.0),
+        slippage_bps: per_share.zip(mid).map(|(s, mid)| s / mid * 10_000.0),
+        cost_cents: per_share.map(|s| (s * filled_qty * 100.0).round() as i64),
+        arrival_mid: mid,
+        account: quote.account,
+        order_id: quote.order_id,
+        symbol: quote.symbol,
+        side: quote.side,
+        order_type: quote.order_type,
+        placed_at: quote.placed_at,
+        filled_qty,
+        avg_fill_price,
+        first_fill_at,
+        last_fill_at,
+    }
+}
+
+/// One bucket per key over the measured orders, highest cost first.
+fn buckets(
+    orders: &[OrderExecutionQuality],
+    key: impl Fn(&OrderExecutionQuality) -> String,
+) -> Vec<ExecutionQualityBucket> {
+    let mut grouped: BTreeMap<String, Vec<&OrderExecutionQuality>> = BTreeMap::new();
+    for order in orders {
+        grouped.entry(key(order)).or_default().push(order);
+    }
+    let mut out: Vec<ExecutionQualityBucket> = grouped
+        .into_iter()
+        .map(|(key, orders)| bucket(key, orders.into_iter()))
+        .filter(|b| b.orders > 0)
+        .collect();
+    out.sort_by(|a, b| {
+        b.cost_cents
+            .cmp(&a.cost_cents)
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    out
+}
+
+fn bucket<'a>(
+    key: String,
+    orders: impl Iterator<Item = &'a OrderExecutionQuality>,
+) -> ExecutionQualityBucket {
+    let mut b = ExecutionQualityBucket {
+        key,
+        orders: 0,
+        filled_qty: 0.0,
+        notional_cents: 0,
+        avg_slippage_bps: 0.0,
+        cost_cents: 0,
+    };
+    let mut weighted_bps = 0.0;
+    let mut notional = 0.0;
+    for o in orders {
+        let (Some(bps), Some(cost)) = (o.slippage_bps, o.cost_cents) else {
+            continue;
+        };
+        let value = o.filled_qty * o.avg_fill_price;
+        b.orders += 1;
+        b.filled_qty += o.filled_qty;
+        b.cost_cents += cost;
+        weighted_bps += bps * value;
+        notional += value;
+    }
+    b.notional_cents = (notional * 100.0).round() as i64;
+    if notional > 0.0 {
+        b.avg_slippage_bps = weighted_bps / notional;
+    }
+    b
+}
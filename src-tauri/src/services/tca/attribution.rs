@@ -169,7 +169,10 @@ fn bucket_index(bps: i64, edges: &[(i64, i64)]) -> Option<usize> {
 /// Convert a `[date_from, date_to_inclusive]` ET range to a
 /// `[start_utc, end_utc)` half-open window. Matches the executions
 /// store's day-bounds convention.
-fn et_range_utc(from: NaiveDate, to_inclusive: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+pub(super) fn et_range_utc(
+    from: NaiveDate,
+    to_inclusive: NaiveDate,
+) -> (DateTime<Utc>, DateTime<Utc>) {
     let day_start_naive = from.and_hms_opt(0, 0, 0).expect("midnight valid");
     let next_day_naive = (to_inclusive + chrono::Duration::days(1))
         .and_hms_opt(0, 0, 0)
@@ -254,7 +254,7 @@ fn map_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<OrderIntent> {
     })
 }
 
-fn parse_rfc3339(s: &str, col_idx: usize) -> rusqlite::Result<DateTime<Utc>> {
+pub(super) fn parse_rfc3339(s: &str, col_idx: usize) -> rusqlite::Result<DateTime<Utc>> {
     DateTime::parse_from_rfc3339(s)
         .map(|dt| dt.with_timezone(&Utc))
         .map_err(|e| {
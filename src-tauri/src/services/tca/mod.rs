@@ -1,12 +1,14 @@
 //! Phase 2 — `services/tca/`: setup ↔ execution linkage + per-strategy
 //! attribution.
 //!
-//! `TcaService` is the public seam over three pieces:
+//! `TcaService` is the public seam over four pieces:
 //! - `OrderIntentStore` — DB I/O for `order_intents` + linkage UPDATE
 //!   on `executions`.
 //! - `matcher` (pure) — picks an open intent for a freshly-arrived
 //!   fill and computes slippage.
 //! - `AttributionService` — read-only rollup queries for the UI.
+//! - `ArrivalStore` — the quote at order placement and the
+//!   execution-quality report measured against it.
 //!
 //! Wired into `lib.rs::run` and called by:
 //! - `ExecutionsIngestor::tick_once` — after a `store.record` batch
@@ -15,13 +17,17 @@
 //! - `tca_record_manual_intent` Tauri command — trader-initiated
 //!   intent for an order placed outside our UI.
 //! - `ibkr_place_order` — extended in this phase to record an intent
-//!   before sending to IBKR.
+//!   before sending to IBKR, and its arrival quote once IBKR has
+//!   assigned the order id.
 
+mod arrival;
 mod attribution;
 mod intent;
 mod matcher;
 mod types;
 
+#[cfg(test)]
+mod report_tests;
 #[cfg(test)]
 mod tests;
 
@@ -36,11 +42,13 @@ use crate::services::executions::ExecutionsStore;
 use crate::storage::error::StorageError;
 use crate::storage::Db;
 
+pub use arrival::ArrivalStore;
 pub use attribution::AttributionService;
 pub use intent::{NewOrderIntent, OrderIntentStore};
 pub use matcher::{execution_side_to_intent_side, match_fill};
 pub use types::{
-    AttributionRow, IntendedPriceSource, IntentSide, MatchWindow, SlippageDistributionRow,
+    ArrivalQuote, AttributionRow, ExecutionQualityReport, IntendedPriceSource, IntentSide,
+    MatchWindow, SlippageDistributionRow,
 };
 // Re-exports kept available for downstream consumers (P3 brackets,
 // MCP read tools) that need the full type set even when the lib's
@@ -63,12 +71,13 @@ pub enum TcaError {
 
 pub type Result<T> = std::result::Result<T, TcaError>;
 
-/// The orchestrating service. Cheap to clone — internally it's two
+/// The orchestrating service. Cheap to clone — internally it's
 /// Arcs (`Db`-backed stores).
 #[derive(Clone)]
 pub struct TcaService {
     intents: Arc<OrderIntentStore>,
     attribution: Arc<AttributionService>,
+    arrivals: Arc<ArrivalStore>,
     executions: Arc<ExecutionsStore>,
     /// Reserved for P3 — bracket-attach uses a tighter window than
     /// the parent intent. Default value is the same MatchWindow the
@@ -81,7 +90,8 @@ impl TcaService {
     pub fn new(db: Arc<Db>, executions: Arc<ExecutionsStore>) -> Self {
         Self {
             intents: Arc::new(OrderIntentStore::new(Arc::clone(&db))),
-            attribution: Arc::new(AttributionService::new(db)),
+            attribution: Arc::new(AttributionService::new(Arc::clone(&db))),
+            arrivals: Arc::new(ArrivalStore::new(db)),
             executions,
             window: MatchWindow::default(),
         }
@@ -115,6 +125,40 @@ impl TcaService {
         Ok(())
     }
 
+    /// Record the quote an order arrived to. Validates that qty > 0
+    /// and that a quoted side is > 0.
+    pub async fn record_arrival(&self, quote: ArrivalQuote) -> Result<()> {
+        if !quote.qty.is_finite() || quote.qty <= 0.0 {
+            return Err(TcaError::Invalid("qty must be > 0".to_string()));
+        }
+        if quote.bid_cents.is_some_and(|c| c <= 0) || quote.ask_cents.is_some_and(|c| c <= 0) {
+            return Err(TcaError::Invalid(
+                "bid_cents / ask_cents must be > 0 when set".to_string(),
+            ));
+        }
+        self.arrivals.record(quote).await?;
+        Ok(())
+    }
+
+    /// Fills over `[date_from, date_to_inclusive]` (ET) measured
+    /// against their orders' arrival mids.
+    pub async fn execution_quality(
+        &self,
+        date_from: NaiveDate,
+        date_to_inclusive: NaiveDate,
+        account: &str,
+    ) -> Result<ExecutionQualityReport> {
+        if date_to_inclusive < date_from {
+            return Err(TcaError::Invalid(
+                "date_to must be >= date_from".to_string(),
+            ));
+        }
+        Ok(self
+            .arrivals
+            .execution_quality(date_from, date_to_inclusive, account)
+            .await?)
+    }
+
     /// Try to attach an intent linkage to a single fill. Public for
     /// test ergonomics; production flow uses
     /// `attach_fills_for_account_today`.
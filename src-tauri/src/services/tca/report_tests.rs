@@ -0,0 +1,309 @@
+//! Phase 2 — `services/tca/` reports over matched fills: the attribution
+//! rollup, the slippage distribution and execution quality (fills
+//! against the arrival mid, by symbol and order type; orders without a
+//! quote counted as unmeasured).
+
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+
+use crate::ibkr::types::ExecutionSide;
+
+use super::tests::{fill_at, fresh, new_intent, seed_setup};
+use super::types::{ArrivalQuote, IntentSide};
+
+#[tokio::test]
+async fn attribution_rollup_returns_one_row_per_strategy_plus_unattributed() {
+    let (_tmp, db, store, svc) = fresh();
+    let s_breakout = seed_setup(&db, "breakout", "AAPL").await;
+    let s_pivot = seed_setup(&db, "episodic_pivot", "MSFT").await;
+    let posted = Utc::now() - Duration::minutes(1);
+    svc.record_intent(new_intent(
+        "i_b",
+        Some(s_breakout),
+        "DU1",
+        "AAPL",
+        IntentSide::Buy,
+        10.0,
+        10_000,
+        posted,
+        60,
+    ))
+    .await
+    .unwrap();
+    svc.record_intent(new_intent(
+        "i_p",
+        Some(s_pivot),
+        "DU1",
+        "MSFT",
+        IntentSide::Buy,
+        20.0,
+        30_000,
+        posted,
+        60,
+    ))
+    .await
+    .unwrap();
+    let now = Utc::now();
+    let fills = vec![
+        fill_at(
+            "e_b",
+            "DU1",
+            "AAPL",
+            ExecutionSide::Bought,
+            10.0,
+            100.5,
+            now,
+        ),
+        fill_at(
+            "e_p",
+            "DU1",
+            "MSFT",
+            ExecutionSide::Bought,
+            20.0,
+            300.0,
+            now,
+        ),
+        fill_at(
+            "e_oob",
+            "DU1",
+            "GOOG",
+            ExecutionSide::Bought,
+            5.0,
+            150.0,
+            now,
+        ),
+    ];
+    store.record(&fills).await.unwrap();
+    svc.attach_fills_for_account_today("DU1").await.unwrap();
+
+    // Cover the day in ET so the half-open range catches it.
+    let today_et = Utc::now()
+        .with_timezone(&chrono_tz::America::New_York)
+        .date_naive();
+    let yesterday_et = today_et - chrono::Duration::days(1);
+    let tomorrow_et = today_et + chrono::Duration::days(1);
+    let rows = svc
+        .attribution()
+        .attribution(yesterday_et, tomorrow_et, "DU1")
+        .await
+        .unwrap();
+
+    let strategies: std::collections::BTreeSet<Option<String>> =
+        rows.iter().map(|r| r.strategy.clone()).collect();
+    assert!(strategies.contains(&Some("breakout".to_string())));
+    assert!(strategies.contains(&Some("episodic_pivot".to_string())));
+    assert!(strategies.contains(&None), "unattributed bucket present");
+    for r in &rows {
+        assert!(r.n_trades >= 1);
+    }
+    let breakout = rows
+        .iter()
+        .find(|r| r.strategy.as_deref() == Some("breakout"))
+        .unwrap();
+    assert_eq!(breakout.n_with_slippage, 1);
+}
+
+#[tokio::test]
+async fn attribution_handles_empty_window() {
+    let (_tmp, _db, _store, svc) = fresh();
+    let d = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let rows = svc.attribution().attribution(d, d, "DU1").await.unwrap();
+    assert!(rows.is_empty());
+}
+
+#[tokio::test]
+async fn slippage_distribution_buckets_by_strategy() {
+    let (_tmp, db, store, svc) = fresh();
+    let s = seed_setup(&db, "breakout", "AAPL").await;
+    let posted = Utc::now() - Duration::minutes(1);
+    // Three intents → three fills landing in three different buckets:
+    // 0bps (perfect), ~25bps, ~150bps.
+    let prices = [
+        (100.00, "i_a", "e_a"),
+        (100.25, "i_b", "e_b"),
+        (101.50, "i_c", "e_c"),
+    ];
+    let mut fills = Vec::new();
+    for (price, intent_id, exec_id) in prices {
+        svc.record_intent(new_intent(
+            intent_id,
+            Some(s),
+            "DU1",
+            "AAPL",
+            IntentSide::Buy,
+            10.0,
+            10_000,
+            posted,
+            60,
+        ))
+        .await
+        .unwrap();
+        fills.push(fill_at(
+            exec_id,
+            "DU1",
+            "AAPL",
+            ExecutionSide::Bought,
+            10.0,
+            price,
+            Utc::now(),
+        ));
+    }
+    store.record(&fills).await.unwrap();
+    svc.attach_fills_for_account_today("DU1").await.unwrap();
+
+    let today_et = Utc::now()
+        .with_timezone(&chrono_tz::America::New_York)
+        .date_naive();
+    let dist = svc
+        .attribution()
+        .slippage_distribution(today_et, today_et, "DU1", None)
+        .await
+        .unwrap();
+    assert_eq!(dist.len(), 1);
+    let row = &dist[0];
+    assert_eq!(row.strategy, Some("breakout".to_string()));
+    let total: i64 = row.buckets.iter().map(|b| b.n).sum();
+    assert_eq!(total, 3);
+    // Bucket 0 (0–1 bps): the perfect fill.
+    assert_eq!(row.buckets[0].n, 1);
+    // Bucket (10, 25] for 25bps fill ⇒ idx 3 (10–25). 25bps lands on
+    // the boundary; lower-inclusive ⇒ bucket index 4 (25–50).
+    let twenty_five = row
+        .buckets
+        .iter()
+        .find(|b| b.lower_bps == 25 && b.upper_bps == 50)
+        .unwrap();
+    assert_eq!(twenty_five.n, 1);
+    let one_fifty = row.buckets.iter().find(|b| b.lower_bps == 100).unwrap();
+    assert_eq!(one_fifty.n, 1);
+}
+
+fn arrival(
+    order_id: i32,
+    symbol: &str,
+    side: IntentSide,
+    order_type: &str,
+    quote: Option<(i64, i64)>,
+    placed_at: chrono::DateTime<Utc>,
+) -> ArrivalQuote {
+    ArrivalQuote {
+        account: "DU1".to_string(),
+        order_id,
+        symbol: symbol.to_string(),
+        side,
+        order_type: order_type.to_string(),
+        qty: 100.0,
+        limit_price_cents: None,
+        bid_cents: quote.map(|q| q.0),
+        ask_cents: quote.map(|q| q.1),
+        placed_at,
+    }
+}
+
+#[tokio::test]
+async fn execution_quality_measures_fills_against_the_arrival_mid() {
+    let (_tmp, _db, store, svc) = fresh();
+    // 10:00 ET.
+    let placed = Utc.with_ymd_and_hms(2026, 3, 2, 15, 0, 0).unwrap();
+    let quoted = [
+        arrival(
+            11,
+            "AAPL",
+            IntentSide::Buy,
+            "market",
+            Some((10_000, 10_010)),
+            placed,
+        ),
+        arrival(
+            12,
+            "MSFT",
+            IntentSide::Sell,
+            "limit",
+            Some((20_000, 20_020)),
+            placed,
+        ),
+        arrival(
+            13,
+            "AAPL",
+            IntentSide::Buy,
+            "limit",
+            Some((10_000, 10_010)),
+            placed,
+        ),
+        arrival(14, "NVDA", IntentSide::Buy, "market", None, placed),
+        // Never filled: left out.
+        arrival(
+            15,
+            "AMD",
+            IntentSide::Buy,
+            "limit",
+            Some((9_000, 9_010)),
+            placed,
+        ),
+    ];
+    for q in quoted {
+        svc.record_arrival(q).await.unwrap();
+    }
+    let at = |secs| placed + Duration::seconds(secs);
+    let fill = |exec_id, order_id, symbol, side, qty, price, secs| {
+        let mut f = fill_at(exec_id, "DU1", symbol, side, qty, price, at(secs));
+        f.order_id = order_id;
+        f
+    };
+    store
+        .record(&[
+            fill("e1", 11, "AAPL", ExecutionSide::Bought, 60.0, 100.10, 1),
+            fill("e2", 11, "AAPL", ExecutionSide::Bought, 40.0, 100.15, 3),
+            fill("e3", 12, "MSFT", ExecutionSide::Sold, 50.0, 200.00, 30),
+            fill("e4", 13, "AAPL", ExecutionSide::Bought, 10.0, 100.00, 60),
+            fill("e5", 14, "NVDA", ExecutionSide::Bought, 5.0, 900.00, 2),
+            // Placed outside the app: no arrival.
+            fill("e6", 99, "GOOG", ExecutionSide::Bought, 5.0, 150.00, 2),
+        ])
+        .await
+        .unwrap();
+
+    let day = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+    let report = svc.execution_quality(day, day, "DU1").await.unwrap();
+    assert_eq!(report.unmeasured_orders, 2);
+    let ids: Vec<i32> = report.orders.iter().map(|o| o.order_id).collect();
+    assert_eq!(ids, [11, 12, 13, 14]);
+
+    // Bought at 100.12 against a 100.05 mid.
+    let aapl = &report.orders[0];
+    assert_eq!(aapl.filled_qty, 100.0);
+    assert!((aapl.avg_fill_price - 100.12).abs() < 1e-9);
+    assert_eq!(aapl.arrival_mid, Some(100.05));
+    assert!((aapl.slippage_bps.unwrap() - 0.07 / 100.05 * 1e4).abs() < 1e-6);
+    assert_eq!(aapl.cost_cents, Some(700));
+    assert_eq!(aapl.first_fill_at, at(1));
+    assert_eq!(aapl.last_fill_at, at(3));
+    // Sold 0.10 under the mid: a cost as well.
+    assert_eq!(report.orders[1].cost_cents, Some(500));
+    // Bought under the mid: negative.
+    assert_eq!(report.orders[2].cost_cents, Some(-50));
+    assert_eq!(report.orders[3].slippage_bps, None);
+
+    let symbols: Vec<(&str, i64)> = report
+        .by_symbol
+        .iter()
+        .map(|b| (b.key.as_str(), b.cost_cents))
+        .collect();
+    assert_eq!(symbols, [("AAPL", 650), ("MSFT", 500)]);
+    let types: Vec<(&str, i64)> = report
+        .by_order_type
+        .iter()
+        .map(|b| (b.key.as_str(), b.cost_cents))
+        .collect();
+    assert_eq!(types, [("market", 700), ("limit", 450)]);
+    assert_eq!(report.overall.orders, 3);
+    assert_eq!(report.overall.cost_cents, 1_150);
+    assert_eq!(report.overall.notional_cents, 2_101_200);
+
+    assert!(svc
+        .record_arrival(ArrivalQuote {
+            qty: 0.0,
+            ..arrival(16, "AAPL", IntentSide::Buy, "market", None, placed)
+        })
+        .await
+        .is_err());
+}
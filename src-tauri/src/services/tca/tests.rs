@@ -9,13 +9,13 @@
 //! - slippage sign by side (long pays positive bps, short pays
 //!   positive bps; both convey "trader cost").
 //!
-//! Plus the integration walk: place a setup-linked intent, simulate
-//! fills via the `ExecutionsStore`, run `attach_fills_for_account_*`,
-//! verify the linkage columns + the attribution rollup.
+//! The reports built over matched fills (attribution, slippage
+//! distribution, execution quality) are in `report_tests.rs`, which
+//! shares the fixtures here.
 
 use std::sync::Arc;
 
-use chrono::{Duration, NaiveDate, Utc};
+use chrono::{Duration, Utc};
 use tempfile::NamedTempFile;
 
 use crate::ibkr::types::{ExecutionSide, IbkrExecution};
@@ -25,7 +25,7 @@ use crate::storage::Db;
 use super::types::{IntendedPriceSource, IntentSide, IntentStatus};
 use super::{NewOrderIntent, TcaService};
 
-fn fresh() -> (NamedTempFile, Arc<Db>, Arc<ExecutionsStore>, TcaService) {
+pub(super) fn fresh() -> (NamedTempFile, Arc<Db>, Arc<ExecutionsStore>, TcaService) {
     let tmp = NamedTempFile::new().unwrap();
     let db = Arc::new(Db::open(tmp.path()).unwrap());
     let store = Arc::new(ExecutionsStore::new(Arc::clone(&db)));
@@ -33,7 +33,7 @@ fn fresh() -> (NamedTempFile, Arc<Db>, Arc<ExecutionsStore>, TcaService) {
     (tmp, db, store, svc)
 }
 
-async fn seed_setup(db: &Db, strategy: &str, symbol: &str) -> i64 {
+pub(super) async fn seed_setup(db: &Db, strategy: &str, symbol: &str) -> i64 {
     db.with_conn({
         let strategy = strategy.to_string();
         let symbol = symbol.to_string();
@@ -59,7 +59,7 @@ async fn seed_setup(db: &Db, strategy: &str, symbol: &str) -> i64 {
     .unwrap()
 }
 
-fn fill_at(
+pub(super) fn fill_at(
     exec_id: &str,
     account: &str,
     symbol: &str,
@@ -90,7 +90,7 @@ fn fill_at(
 }
 
 #[allow(clippy::too_many_arguments)] // test fixture; one big fn beats N small ones
-fn new_intent(
+pub(super) fn new_intent(
     intent_id: &str,
     setup_id: Option<i64>,
     account: &str,
@@ -379,170 +379,3 @@ async fn long_pays_positive_bps_short_pays_positive_bps() {
         "short signed slippage should be positive when fill < intended"
     );
 }
-
-#[tokio::test]
-async fn attribution_rollup_returns_one_row_per_strategy_plus_unattributed() {
-    let (_tmp, db, store, svc) = fresh();
-    let s_breakout = seed_setup(&db, "breakout", "AAPL").await;
-    let s_pivot = seed_setup(&db, "episodic_pivot", "MSFT").await;
-    let posted = Utc::now() - Duration::minutes(1);
-    svc.record_intent(new_intent(
-        "i_b",
-        Some(s_breakout),
-        "DU1",
-        "AAPL",
-        IntentSide::Buy,
-        10.0,
-        10_000,
-        posted,
-        60,
-    ))
-    .await
-    .unwrap();
-    svc.record_intent(new_intent(
-        "i_p",
-        Some(s_pivot),
-        "DU1",
-        "MSFT",
-        IntentSide::Buy,
-        20.0,
-        30_000,
-        posted,
-        60,
-    ))
-    .await
-    .unwrap();
-    let now = Utc::now();
-    let fills = vec![
-        fill_at(
-            "e_b",
-            "DU1",
-            "AAPL",
-            ExecutionSide::Bought,
-            10.0,
-            100.5,
-            now,
-        ),
-        fill_at(
-            "e_p",
-            "DU1",
-            "MSFT",
-            ExecutionSide::Bought,
-            20.0,
-            300.0,
-            now,
-        ),
-        fill_at(
-            "e_oob",
-            "DU1",
-            "GOOG",
-            ExecutionSide::Bought,
-            5.0,
-            150.0,
-            now,
-        ),
-    ];
-    store.record(&fills).await.unwrap();
-    svc.attach_fills_for_account_today("DU1").await.unwrap();
-
-    // Cover the day in ET so the half-open range catches it.
-    let today_et = Utc::now()
-        .with_timezone(&chrono_tz::America::New_York)
-        .date_naive();
-    let yesterday_et = today_et - chrono::Duration::days(1);
-    let tomorrow_et = today_et + chrono::Duration::days(1);
-    let rows = svc
-        .attribution()
-        .attribution(yesterday_et, tomorrow_et, "DU1")
-        .await
-        .unwrap();
-
-    let strategies: std::collections::BTreeSet<Option<String>> =
-        rows.iter().map(|r| r.strategy.clone()).collect();
-    assert!(strategies.contains(&Some("breakout".to_string())));
-    assert!(strategies.contains(&Some("episodic_pivot".to_string())));
-    assert!(strategies.contains(&None), "unattributed bucket present");
-    for r in &rows {
-        assert!(r.n_trades >= 1);
-    }
-    let breakout = rows
-        .iter()
-        .find(|r| r.strategy.as_deref() == Some("breakout"))
-        .unwrap();
-    assert_eq!(breakout.n_with_slippage, 1);
-}
-
-#[tokio::test]
-async fn attribution_handles_empty_window() {
-    let (_tmp, _db, _store, svc) = fresh();
-    let d = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
-    let rows = svc.attribution().attribution(d, d, "DU1").await.unwrap();
-    assert!(rows.is_empty());
-}
-
-#[tokio::test]
-async fn slippage_distribution_buckets_by_strategy() {
-    let (_tmp, db, store, svc) = fresh();
-    let s = seed_setup(&db, "breakout", "AAPL").await;
-    let posted = Utc::now() - Duration::minutes(1);
-    // Three intents → three fills landing in three different buckets:
-    // 0bps (perfect), ~25bps, ~150bps.
-    let prices = [
-        (100.00, "i_a", "e_a"),
-        (100.25, "i_b", "e_b"),
-        (101.50, "i_c", "e_c"),
-    ];
-    let mut fills = Vec::new();
-    for (price, intent_id, exec_id) in prices {
-        svc.record_intent(new_intent(
-            intent_id,
-            Some(s),
-            "DU1",
-            "AAPL",
-            IntentSide::Buy,
-            10.0,
-            10_000,
-            posted,
-            60,
-        ))
-        .await
-        .unwrap();
-        fills.push(fill_at(
-            exec_id,
-            "DU1",
-            "AAPL",
-            ExecutionSide::Bought,
-            10.0,
-            price,
-            Utc::now(),
-        ));
-    }
-    store.record(&fills).await.unwrap();
-    svc.attach_fills_for_account_today("DU1").await.unwrap();
-
-    let today_et = Utc::now()
-        .with_timezone(&chrono_tz::America::New_York)
-        .date_naive();
-    let dist = svc
-        .attribution()
-        .slippage_distribution(today_et, today_et, "DU1", None)
-        .await
-        .unwrap();
-    assert_eq!(dist.len(), 1);
-    let row = &dist[0];
-    assert_eq!(row.strategy, Some("breakout".to_string()));
-    let total: i64 = row.buckets.iter().map(|b| b.n).sum();
-    assert_eq!(total, 3);
-    // Bucket 0 (0–1 bps): the perfect fill.
-    assert_eq!(row.buckets[0].n, 1);
-    // Bucket (10, 25] for 25bps fill ⇒ idx 3 (10–25). 25bps lands on
-    // the boundary; lower-inclusive ⇒ bucket index 4 (25–50).
-    let twenty_five = row
-        .buckets
-        .iter()
-        .find(|b| b.lower_bps == 25 && b.upper_bps == 50)
-        .unwrap();
-    assert_eq!(twenty_five.n, 1);
-    let one_fifty = row.buckets.iter().find(|b| b.lower_bps == 100).unwrap();
-    assert_eq!(one_fifty.n, 1);
-}
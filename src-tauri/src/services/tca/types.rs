@@ -257,6 +257,89 @@ pub fn default_histogram_edges() -> Vec<(i64, i64)> {
     ]
 }
 
+/// Bid / ask snapshot taken just before an order went to IBKR
+/// (`order_arrivals`). Keyed by the IBKR order id its fills carry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArrivalQuote {
+    pub account: String,
+    pub order_id: i32,
+    pub symbol: String,
+    pub side: IntentSide,
+    /// `market`, `limit`, `stop`, `stop_limit` or `midprice`.
+    pub order_type: String,
+    pub qty: f64,
+    pub limit_price_cents: Option<i64>,
+    /// `None` when the snapshot had no bid / ask (closed market, no
+    /// market-data subscription).
+    pub bid_cents: Option<i64>,
+    pub ask_cents: Option<i64>,
+    pub placed_at: DateTime<Utc>,
+}
+
+impl ArrivalQuote {
+    /// Arrival mid in dollars; `None` without a two-sided, uncrossed
+    /// quote.
+    pub fn mid(&self) -> Option<f64> {
+        match (self.bid_cents, self.ask_cents) {
+            (Some(bid), Some(ask)) if bid > 0 && ask >= bid => Some((bid + ask) as f64 / 200.0),
+            _ => None,
+        }
+    }
+}
+
+/// One order's fills measured against its arrival mid
+/// (`get_execution_quality_report`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderExecutionQuality {
+    pub account: String,
+    pub order_id: i32,
+    pub symbol: String,
+    pub side: IntentSide,
+    pub order_type: String,
+    pub placed_at: DateTime<Utc>,
+    pub filled_qty: f64,
+    /// Quantity-weighted across the order's fills.
+    pub avg_fill_price: f64,
+    pub arrival_mid: Option<f64>,
+    /// Quoted spread at arrival, in bps of the mid.
+    pub arrival_spread_bps: Option<f64>,
+    /// Fill against arrival mid in bps, with the `slippage_signed`
+    /// convention: positive is a cost on either side (paid above the
+    /// mid on a buy, received below it on a sell). `None` without an
+    /// arrival mid.
+    pub slippage_bps: Option<f64>,
+    /// Slippage × filled qty, integer cents; positive is a cost.
+    pub cost_cents: Option<i64>,
+    pub first_fill_at: DateTime<Utc>,
+    pub last_fill_at: DateTime<Utc>,
+}
+
+/// Roll-up of the measured orders under one symbol / order type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionQualityBucket {
+    /// Symbol, order type, or `all`.
+    pub key: String,
+    pub orders: i64,
+    pub filled_qty: f64,
+    pub notional_cents: i64,
+    /// Notional-weighted slippage, bps.
+    pub avg_slippage_bps: f64,
+    pub cost_cents: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionQualityReport {
+    /// Filled orders with an arrival row, oldest first.
+    pub orders: Vec<OrderExecutionQuality>,
+    /// Highest cost first.
+    pub by_symbol: Vec<ExecutionQualityBucket>,
+    pub by_order_type: Vec<ExecutionQualityBucket>,
+    pub overall: ExecutionQualityBucket,
+    /// Filled orders in the window that can't be measured: placed
+    /// outside `ibkr_place_order`, or without a two-sided quote.
+    pub unmeasured_orders: i64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,131 @@
+//! Bot API over reqwest. Both calls are outbound HTTPS to
+//! api.telegram.org; `getUpdates` is long-polled.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::{TelegramApi, TelegramError, Update};
+
+const API_BASE: &str = "https://api.telegram.org";
+
+pub struct BotApiClient {
+    client: Client,
+}
+
+impl BotApiClient {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+        }
+    }
+
+    async fn call(
+        &self,
+        token: &str,
+        method: &str,
+        body: Value,
+        timeout: Duration,
+    ) -> Result<Value, TelegramError> {
+        let response = self
+            .client
+            .post(format!("{API_BASE}/bot{token}/{method}"))
+            .json(&body)
+            .timeout(timeout)
+            .send()
+            .await
+            // reqwest errors quote the URL, which carries the token.
+            .map_err(|e| TelegramError::Http(e.without_url().to_string()))?;
+        let envelope: Envelope = response
+            .json()
+            .await
+            .map_err(|e| TelegramError::Http(e.without_url().to_string()))?;
+        if !envelope.ok {
+            return Err(TelegramError::Api(
+                envelope
+                    .description
+                    .unwrap_or_else(|| format!("{method} failed")),
+            ));
+        }
+        Ok(envelope.result.unwrap_or(Value::Null))
+    }
+}
+
+impl Default for BotApiClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    ok: bool,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    result: Option<Value>,
+}
+
+#[async_trait]
+impl TelegramApi for BotApiClient {
+    async fn get_updates(
+        &self,
+        token: &str,
+        offset: i64,
+        timeout_secs: u64,
+    ) -> Result<Vec<Update>, TelegramError> {
+        let result = self
+            .call(
+                token,
+                "getUpdates",
+                json!({
+                    "offset": offset,
+                    "timeout": timeout_secs,
+                    "allowed_updates": ["message"],
+                }),
+                Duration::from_secs(timeout_secs + 10),
+            )
+            .await?;
+        Ok(parse_updates(&result))
+    }
+
+    async fn send_message(
+        &self,
+        token: &str,
+        chat_id: i64,
+        text: &str,
+    ) -> Result<(), TelegramError> {
+        self.call(
+            token,
+            "sendMessage",
+            json!({ "chat_id": chat_id, "text": text }),
+            Duration::from_secs(15),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// Updates out of a `getUpdates` result. Ones without a text message
+/// (stickers, joins, edits) come back with empty `text` rather than
+/// being dropped, so the poll offset still moves past them.
+pub fn parse_updates(result: &Value) -> Vec<Update> {
+    let Some(items) = result.as_array() else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            let update_id = item["update_id"].as_i64()?;
+            let message = &item["message"];
+            Some(Update {
+                update_id,
+                chat_id: message["chat"]["id"].as_i64().unwrap_or_default(),
+                text: message["text"].as_str().unwrap_or_default().to_string(),
+            })
+        })
+        .collect()
+}
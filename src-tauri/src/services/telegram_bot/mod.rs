@@ -0,0 +1,222 @@
+//! Read-only Telegram bot for checking the portfolio from a phone.
+//!
+//! The bot long-polls `getUpdates`, so nothing listens on a port and the
+//! app can sit behind NAT. It answers three commands from the chats in
+//! `telegram.allowed_chat_ids`:
+//!
+//!   - `/positions`: open positions with cost, mark and unrealized P&L.
+//!   - `/pnl`: unrealized and realized P&L and market value, per currency.
+//!   - `/price AAPL`: a live snapshot through [`QuoteService`].
+//!
+//! Nothing it can be asked places, modifies or cancels an order. Other
+//! chats are told their chat id and otherwise ignored, so adding a phone
+//! is a matter of messaging the bot and copying the id into settings.
+//! Alerts reach the same chats through `Notifier`, which treats Telegram
+//! as one more channel (see `services/notifications`).
+//!
+//! The bot token lives in the keychain (`SecretProvider::Telegram`).
+//! Settings are re-read every poll, so enabling the bot or adding a chat
+//! takes effect without a restart.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::config::AppConfig;
+use crate::ibkr::error::IbkrError;
+use crate::ibkr::types::Position;
+use crate::services::portfolio_risk::OpenPositionsSource;
+use crate::services::quote_service::QuoteService;
+use crate::services::risk_engine::AccountSource;
+
+pub mod client;
+pub mod replies;
+
+#[cfg(test)]
+mod tests;
+
+/// Server-side wait of one `getUpdates` call.
+const POLL_TIMEOUT_SECS: u64 = 25;
+/// Pause while the bot is disabled, or after a failed poll.
+const IDLE: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TelegramConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Bot token from @BotFather. Kept in the OS keychain; see
+    /// `config/secrets.rs`.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Chats the bot answers and pushes alerts to.
+    #[serde(default)]
+    pub allowed_chat_ids: Vec<i64>,
+}
+
+impl TelegramConfig {
+    /// Token, when the bot is enabled and has one.
+    pub fn active_token(&self) -> Option<&str> {
+        self.token
+            .as_deref()
+            .filter(|t| self.enabled && !t.is_empty())
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum TelegramError {
+    #[error("telegram request failed: {0}")]
+    Http(String),
+    #[error("telegram API error: {0}")]
+    Api(String),
+}
+
+/// One incoming message; `text` is empty for anything but text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Update {
+    pub update_id: i64,
+    pub chat_id: i64,
+    pub text: String,
+}
+
+/// Trait seam over the Bot API. Production is
+/// [`client::BotApiClient`]; tests script updates and record replies.
+#[async_trait]
+pub trait TelegramApi: Send + Sync {
+    /// Text messages after `offset`, waiting up to `timeout_secs` for
+    /// one to arrive.
+    async fn get_updates(
+        &self,
+        token: &str,
+        offset: i64,
+        timeout_secs: u64,
+    ) -> Result<Vec<Update>, TelegramError>;
+    async fn send_message(
+        &self,
+        token: &str,
+        chat_id: i64,
+        text: &str,
+    ) -> Result<(), TelegramError>;
+}
+
+pub struct TelegramBot {
+    api: Arc<dyn TelegramApi>,
+    positions: Arc<dyn OpenPositionsSource>,
+    account: Arc<dyn AccountSource>,
+    quotes: Arc<QuoteService>,
+    config: Arc<RwLock<AppConfig>>,
+}
+
+impl TelegramBot {
+    pub fn new(
+        api: Arc<dyn TelegramApi>,
+        positions: Arc<dyn OpenPositionsSource>,
+        account: Arc<dyn AccountSource>,
+        quotes: Arc<QuoteService>,
+        config: Arc<RwLock<AppConfig>>,
+    ) -> Self {
+        Self {
+            api,
+            positions,
+            account,
+            quotes,
+            config,
+        }
+    }
+
+    /// Reply to one message text. Errors become the reply, so a
+    /// disconnected gateway reads as such on the phone.
+    pub async fn answer(&self, text: &str) -> String {
+        let mut words = text.split_whitespace();
+        // `/price@SomeBot AAPL` in group chats.
+        let command = words
+            .next()
+            .unwrap_or_default()
+            .split('@')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match command.as_str() {
+            "/positions" => match self.open_positions().await {
+                Ok((account, positions)) => replies::positions(&account, &positions),
+                Err(e) => format!("Could not load positions: {e}"),
+            },
+            "/pnl" => match self.open_positions().await {
+                Ok((account, positions)) => replies::pnl(&account, &positions),
+                Err(e) => format!("Could not load P&L: {e}"),
+            },
+            "/price" => {
+                let Some(symbol) = words.next().and_then(replies::parse_symbol) else {
+                    return "Usage: /price AAPL".to_string();
+                };
+                match self.quotes.fetch_quote(&symbol).await {
+                    Ok(quote) => replies::price(&quote),
+                    Err(e) => format!("No quote for {symbol}: {e}"),
+                }
+            }
+            _ => replies::HELP.to_string(),
+        }
+    }
+
+    async fn open_positions(&self) -> Result<(String, Vec<Position>), IbkrError> {
+        let account = self.account.current_account().await?;
+        let positions = self.positions.list_open(&account).await?;
+        Ok((account, positions))
+    }
+
+    /// One `getUpdates` round. Returns the offset for the next call.
+    pub async fn poll_once(&self, token: &str, offset: i64) -> Result<i64, TelegramError> {
+        let updates = self
+            .api
+            .get_updates(token, offset, POLL_TIMEOUT_SECS)
+            .await?;
+        let allowed = self.config.read().await.telegram.allowed_chat_ids.clone();
+        let mut next = offset;
+        for update in updates {
+            next = next.max(update.update_id + 1);
+            if update.text.is_empty() {
+                continue;
+            }
+            let reply = if allowed.contains(&update.chat_id) {
+                self.answer(&update.text).await
+            } else {
+                info!("telegram: ignoring chat {}", update.chat_id);
+                replies::not_allowed(update.chat_id)
+            };
+            if let Err(e) = self.api.send_message(token, update.chat_id, &reply).await {
+                warn!("telegram: reply to {} failed: {e}", update.chat_id);
+            }
+        }
+        Ok(next)
+    }
+
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut offset = 0;
+            loop {
+                let token = self
+                    .config
+                    .read()
+                    .await
+                    .telegram
+                    .active_token()
+                    .map(str::to_string);
+                let Some(token) = token else {
+                    tokio::time::sleep(IDLE).await;
+                    continue;
+                };
+                match self.poll_once(&token, offset).await {
+                    Ok(next) => offset = next,
+                    Err(e) => {
+                        warn!("telegram: poll failed: {e}");
+                        tokio::time::sleep(IDLE).await;
+                    }
+                }
+            }
+        })
+    }
+}
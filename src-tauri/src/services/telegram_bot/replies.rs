@@ -0,0 +1,102 @@
+//! Reply text. Plain text rather than Markdown, so symbols and amounts
+//! never need escaping.
+
+use std::collections::BTreeMap;
+
+use crate::ibkr::types::{Position, Quote};
+
+pub const HELP: &str =
+    "Commands:\n/positions: open positions\n/pnl: P&L and market value\n/price AAPL: live quote";
+
+pub fn not_allowed(chat_id: i64) -> String {
+    format!("This chat is not allowed. Add {chat_id} to telegram.allowed_chat_ids to use the bot.")
+}
+
+/// Upper-cased ticker, or `None` if `raw` isn't one.
+pub fn parse_symbol(raw: &str) -> Option<String> {
+    let symbol = raw.trim().to_ascii_uppercase();
+    let valid = !symbol.is_empty()
+        && symbol.len() <= 12
+        && symbol
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+    valid.then_some(symbol)
+}
+
+pub fn positions(account: &str, positions: &[Position]) -> String {
+    if positions.is_empty() {
+        return format!("{account}: no open positions.");
+    }
+    let mut sorted: Vec<_> = positions.iter().collect();
+    sorted.sort_by(|a, b| b.market_value.abs().total_cmp(&a.market_value.abs()));
+    let mut out = format!("{account}: {} open positions", sorted.len());
+    for p in sorted {
+        let name = if p.local_symbol.is_empty() || p.contract_type == "STK" {
+            &p.symbol
+        } else {
+            &p.local_symbol
+        };
+        out.push_str(&format!(
+            "\n{name} {} @ {:.2}, now {:.2} ({} {})",
+            qty(p.position),
+            p.average_cost,
+            p.market_price,
+            signed(p.unrealized_pnl),
+            p.currency
+        ));
+    }
+    out
+}
+
+pub fn pnl(account: &str, positions: &[Position]) -> String {
+    if positions.is_empty() {
+        return format!("{account}: no open positions.");
+    }
+    // currency -> (market value, unrealized, realized)
+    let mut totals: BTreeMap<&str, (f64, f64, f64)> = BTreeMap::new();
+    for p in positions {
+        let t = totals.entry(p.currency.as_str()).or_default();
+        t.0 += p.market_value;
+        t.1 += p.unrealized_pnl;
+        t.2 += p.realized_pnl;
+    }
+    let mut out = format!("{account} P&L");
+    for (currency, (value, unrealized, realized)) in totals {
+        out.push_str(&format!(
+            "\n{currency}: unrealized {}, realized {}, market value {value:.2}",
+            signed(unrealized),
+            signed(realized)
+        ));
+    }
+    out
+}
+
+pub fn price(quote: &Quote) -> String {
+    let Some(last) = quote.last_price.or(quote.prev_close) else {
+        return format!("{}: no price yet.", quote.symbol);
+    };
+    let mut out = format!("{} {last:.2}", quote.symbol);
+    if let (Some(_), Some(prev)) = (quote.last_price, quote.prev_close) {
+        if prev > 0.0 {
+            out.push_str(&format!(
+                " ({:+.2}% on the day)",
+                (last / prev - 1.0) * 100.0
+            ));
+        }
+    } else if quote.last_price.is_none() {
+        out.push_str(" (previous close)");
+    }
+    out
+}
+
+fn signed(v: f64) -> String {
+    format!("{v:+.2}")
+}
+
+fn qty(v: f64) -> String {
+    if v.fract() == 0.0 {
+        format!("{v:.0}")
+    } else {
+        format!("{v}")
+    }
+}
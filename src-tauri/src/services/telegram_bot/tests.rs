@@ -0,0 +1,186 @@
+use std::sync::Mutex;
+
+use super::*;
+use crate::ibkr::error::Result as IbkrResult;
+use crate::ibkr::types::MarketDataSnapshot;
+use crate::services::quote_service::QuoteFetcher;
+
+struct FixedAccount;
+
+#[async_trait]
+impl AccountSource for FixedAccount {
+    async fn current_account(&self) -> Result<String, IbkrError> {
+        Ok("DU1".to_string())
+    }
+}
+
+struct StubPositions(Vec<Position>);
+
+#[async_trait]
+impl OpenPositionsSource for StubPositions {
+    async fn list_open(&self, _account: &str) -> Result<Vec<Position>, IbkrError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Last 102, previous close 100; `NOQUOTE` has no permission.
+struct FixedQuotes;
+
+#[async_trait]
+impl QuoteFetcher for FixedQuotes {
+    async fn get_market_data_snapshot(&self, symbol: &str) -> IbkrResult<MarketDataSnapshot> {
+        if symbol == "NOQUOTE" {
+            return Err(IbkrError::MarketDataPermissionDenied);
+        }
+        Ok(MarketDataSnapshot {
+            symbol: symbol.to_string(),
+            bid_price: None,
+            bid_size: None,
+            ask_price: None,
+            ask_size: None,
+            last_price: Some(102.0),
+            last_size: None,
+            high: None,
+            low: None,
+            volume: None,
+            close: Some(100.0),
+            open: None,
+            last_rth_price: None,
+            session: None,
+            timestamp: 0,
+        })
+    }
+}
+
+/// Hands out `updates` once and records replies.
+#[derive(Default)]
+struct ScriptedApi {
+    updates: Mutex<Vec<Update>>,
+    sent: Mutex<Vec<(i64, String)>>,
+}
+
+#[async_trait]
+impl TelegramApi for ScriptedApi {
+    async fn get_updates(
+        &self,
+        _token: &str,
+        _offset: i64,
+        _timeout_secs: u64,
+    ) -> Result<Vec<Update>, TelegramError> {
+        Ok(std::mem::take(&mut *self.updates.lock().unwrap()))
+    }
+
+    async fn send_message(
+        &self,
+        _token: &str,
+        chat_id: i64,
+        text: &str,
+    ) -> Result<(), TelegramError> {
+        self.sent.lock().unwrap().push((chat_id, text.to_string()));
+        Ok(())
+    }
+}
+
+fn pos(symbol: &str, currency: &str, qty: f64, value: f64, unrealized: f64) -> Position {
+    Position {
+        account: "DU1".to_string(),
+        symbol: symbol.to_string(),
+        position: qty,
+        average_cost: 100.0,
+        market_price: value / qty,
+        market_value: value,
+        unrealized_pnl: unrealized,
+        realized_pnl: 10.0,
+        contract_type: "STK".to_string(),
+        currency: currency.to_string(),
+        ..Default::default()
+    }
+}
+
+fn bot(api: Arc<ScriptedApi>) -> TelegramBot {
+    let mut config = AppConfig::default();
+    config.telegram.enabled = true;
+    config.telegram.token = Some("123:abc".into());
+    config.telegram.allowed_chat_ids = vec![42];
+    TelegramBot::new(
+        api,
+        Arc::new(StubPositions(vec![
+            pos("AAPL", "USD", 10.0, 1_200.0, 200.0),
+            pos("MSFT", "USD", 5.0, 2_000.0, -50.0),
+            pos("SAP", "EUR", 4.0, 600.0, 25.5),
+        ])),
+        Arc::new(FixedAccount),
+        Arc::new(QuoteService::new(Arc::new(FixedQuotes))),
+        Arc::new(RwLock::new(config)),
+    )
+}
+
+#[tokio::test]
+async fn answers_positions_pnl_and_price() {
+    let bot = bot(Arc::default());
+
+    let positions = bot.answer("/positions").await;
+    assert!(positions.starts_with("DU1: 3 open positions\nMSFT 5 @ 100.00, now 400.00"));
+    assert!(positions.contains("AAPL 10 @ 100.00, now 120.00 (+200.00 USD)"));
+
+    let pnl = bot.answer("/pnl").await;
+    assert_eq!(
+        pnl,
+        "DU1 P&L\n\
+         EUR: unrealized +25.50, realized +10.00, market value 600.00\n\
+         USD: unrealized +150.00, realized +20.00, market value 3200.00"
+    );
+
+    assert_eq!(
+        bot.answer("/price@QkBot aapl").await,
+        "AAPL 102.00 (+2.00% on the day)"
+    );
+    assert!(bot
+        .answer("/price NOQUOTE")
+        .await
+        .starts_with("No quote for NOQUOTE"));
+    assert_eq!(bot.answer("/price").await, "Usage: /price AAPL");
+    assert_eq!(bot.answer("/buy AAPL 100").await, replies::HELP);
+}
+
+#[tokio::test]
+async fn only_allowed_chats_get_answers() {
+    let api = Arc::new(ScriptedApi::default());
+    *api.updates.lock().unwrap() = vec![
+        Update {
+            update_id: 7,
+            chat_id: 42,
+            text: "/price MSFT".into(),
+        },
+        Update {
+            update_id: 8,
+            chat_id: 99,
+            text: "/positions".into(),
+        },
+        Update {
+            update_id: 9,
+            chat_id: 42,
+            text: String::new(),
+        },
+    ];
+    let next = bot(Arc::clone(&api)).poll_once("123:abc", 0).await.unwrap();
+    assert_eq!(next, 10);
+
+    let sent = api.sent.lock().unwrap();
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[0], (42, "MSFT 102.00 (+2.00% on the day)".to_string()));
+    assert_eq!(sent[1].0, 99);
+    assert!(sent[1].1.contains("Add 99 to telegram.allowed_chat_ids"));
+}
+
+#[test]
+fn parses_update_payloads() {
+    let result = serde_json::json!([
+        { "update_id": 3, "message": { "chat": { "id": 42 }, "text": "/pnl" } },
+        { "update_id": 4, "message": { "chat": { "id": 42 }, "sticker": {} } },
+    ]);
+    let updates = client::parse_updates(&result);
+    assert_eq!(updates[0].text, "/pnl");
+    assert_eq!(updates[1].update_id, 4);
+    assert!(updates[1].text.is_empty());
+}
@@ -88,12 +88,14 @@ fn sample_fundamentals(symbol: &str) -> FundamentalData {
                 revenue: 60.0,
                 net_income: 30.0,
                 eps: 1.2,
+                split_adjustment: None,
             },
             HistoricalFinancial {
                 year: 2024,
                 revenue: 130.0,
                 net_income: 70.0,
                 eps: 2.9,
+                split_adjustment: None,
             },
         ],
         analyst_estimates: Some(AnalystEstimates {
@@ -114,7 +116,14 @@ fn sample_fundamentals(symbol: &str) -> FundamentalData {
             exchange: Some("NASDAQ".into()),
             market_cap: Some("3T".into()),
             dividend_yield: None,
+            short_interest: None,
         },
+        data_quality: Vec::new(),
+        overrides: Vec::new(),
+        growth: None,
+        currency: None,
+        fx_conversion: None,
+        segments: Vec::new(),
     }
 }
 
@@ -386,7 +386,10 @@ async fn full_stack_pause_then_override_unblocks_sizing() {
         timeframe: BarSize::Day1,
         detected_at: Utc::now(),
     };
-    let (sizing, _snap) = risk_engine.size_for_candidate(&candidate).await.unwrap();
+    let (sizing, _snap) = risk_engine
+        .size_for_candidate("AAPL", &candidate)
+        .await
+        .unwrap();
     assert_eq!(sizing.skipped_reason, Some(SizingSkippedReason::TiltPaused));
     assert_eq!(sizing.qty, 0);
 
@@ -453,7 +456,10 @@ async fn full_stack_pause_then_override_unblocks_sizing() {
         .override_pause("DU1", "I see what I did".to_string())
         .await
         .unwrap();
-    let (sizing2, _) = risk_engine.size_for_candidate(&candidate).await.unwrap();
+    let (sizing2, _) = risk_engine
+        .size_for_candidate("AAPL", &candidate)
+        .await
+        .unwrap();
     assert!(
         sizing2.skipped_reason.is_none(),
         "sizing must be unskipped after override"
@@ -508,7 +508,10 @@ impl TrackerRunner {
                     // typical case — nothing extra to do.
                     let mut gate_warning: Option<String> = None;
                     if let (Some(engine), Some(prisk)) = (&self.risk_engine, &self.portfolio_risk) {
-                        match engine.size_for_candidate(&candidate).await {
+                        match engine
+                            .size_for_candidate(&ctx_owned.symbol, &candidate)
+                            .await
+                        {
                             Ok((pre_sizing, _)) if pre_sizing.dollar_risk_cents > 0 => {
                                 match prisk.gate().await {
                                     Ok(gate) => {
@@ -630,7 +633,10 @@ impl TrackerRunner {
                             // the UI surfaces an "ungated" warning when
                             // sizing is None.
                             if let Some(engine) = &self.risk_engine {
-                                match engine.size_for_candidate(&candidate).await {
+                                match engine
+                                    .size_for_candidate(&ctx_owned.symbol, &candidate)
+                                    .await
+                                {
                                     Ok((sizing, _snapshot)) => {
                                         match self
                                             .tracker
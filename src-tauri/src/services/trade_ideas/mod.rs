@@ -0,0 +1,263 @@
+//! Trade idea pipeline: a ticker's path from first look to closed
+//! trade, beside the live-position views.
+//!
+//! An idea starts `watching` and moves forward through `researched`
+//! and `position` to `exited`; it may skip stages but never goes back,
+//! and `exited` is final. Each move is kept in `trade_idea_transitions`
+//! with an optional note, so the detail view reads as a timeline. At
+//! most one idea per symbol is open; coming back to a name after an
+//! exit starts a new idea.
+//!
+//! Ideas link to the work behind them: stored projections
+//! (`projection_snapshots`, same symbol only) and journal sections
+//! (`journal_entries`). Links are checked on the way in and listed with
+//! the idea by [`TradeIdeaService::get`].
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+mod store;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Error, Debug)]
+pub enum TradeIdeaError {
+    #[error("symbol must be non-empty")]
+    EmptySymbol,
+    #[error("trade idea {0} not found")]
+    NotFound(i64),
+    #[error("{symbol} already has an open idea (#{id})")]
+    AlreadyOpen { symbol: String, id: i64 },
+    #[error("can't move an idea from {from} to {to}")]
+    InvalidMove {
+        from: &'static str,
+        to: &'static str,
+    },
+    #[error("{kind} {id} not found")]
+    MissingLink { kind: &'static str, id: i64 },
+    #[error("projection {id} is for {symbol}, not this idea's ticker")]
+    WrongSymbol { id: i64, symbol: String },
+    #[error("storage: {0}")]
+    Storage(#[from] StorageError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdeaStage {
+    Watching,
+    Researched,
+    Position,
+    Exited,
+}
+
+impl IdeaStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdeaStage::Watching => "watching",
+            IdeaStage::Researched => "researched",
+            IdeaStage::Position => "position",
+            IdeaStage::Exited => "exited",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "watching" => Some(IdeaStage::Watching),
+            "researched" => Some(IdeaStage::Researched),
+            "position" => Some(IdeaStage::Position),
+            "exited" => Some(IdeaStage::Exited),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdeaLinkKind {
+    /// `projection_snapshots.id`
+    Projection,
+    /// `journal_entries.id`
+    Journal,
+}
+
+impl IdeaLinkKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdeaLinkKind::Projection => "projection",
+            IdeaLinkKind::Journal => "journal",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "projection" => Some(IdeaLinkKind::Projection),
+            "journal" => Some(IdeaLinkKind::Journal),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeIdea {
+    pub id: i64,
+    pub symbol: String,
+    pub stage: IdeaStage,
+    /// Note of the latest move.
+    pub note: Option<String>,
+    /// Unix seconds, as are the other timestamps.
+    pub created_at: i64,
+    pub stage_changed_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdeaTransition {
+    /// `None` for the creation.
+    pub from_stage: Option<IdeaStage>,
+    pub to_stage: IdeaStage,
+    pub note: Option<String>,
+    pub at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdeaLink {
+    pub kind: IdeaLinkKind,
+    pub ref_id: i64,
+    /// Projection generation date or journal date and section.
+    pub label: String,
+    pub linked_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeIdeaDetail {
+    #[serde(flatten)]
+    pub idea: TradeIdea,
+    /// Oldest first.
+    pub transitions: Vec<IdeaTransition>,
+    pub links: Vec<IdeaLink>,
+}
+
+pub struct TradeIdeaService {
+    db: Arc<Db>,
+}
+
+impl TradeIdeaService {
+    pub fn new(db: Arc<Db>) -> Self {
+        Self { db }
+    }
+
+    /// Start watching `symbol`.
+    pub async fn create(
+        &self,
+        symbol: &str,
+        note: Option<String>,
+    ) -> Result<TradeIdea, TradeIdeaError> {
+        let symbol = symbol.trim().to_uppercase();
+        if symbol.is_empty() {
+            return Err(TradeIdeaError::EmptySymbol);
+        }
+        if let Some(open) = store::open_for_symbol(&self.db, &symbol).await? {
+            return Err(TradeIdeaError::AlreadyOpen {
+                symbol,
+                id: open.id,
+            });
+        }
+        let note = clean(note);
+        let id = store::insert(&self.db, &symbol, note.clone(), Utc::now().timestamp()).await?;
+        self.idea(id).await
+    }
+
+    /// Move an idea forward to `stage`.
+    pub async fn move_to(
+        &self,
+        id: i64,
+        stage: IdeaStage,
+        note: Option<String>,
+    ) -> Result<TradeIdea, TradeIdeaError> {
+        let idea = self.idea(id).await?;
+        if stage <= idea.stage {
+            return Err(TradeIdeaError::InvalidMove {
+                from: idea.stage.as_str(),
+                to: stage.as_str(),
+            });
+        }
+        store::set_stage(
+            &self.db,
+            id,
+            idea.stage,
+            stage,
+            clean(note),
+            Utc::now().timestamp(),
+        )
+        .await?;
+        self.idea(id).await
+    }
+
+    /// Link a projection snapshot or journal entry. Linking twice is a
+    /// no-op.
+    pub async fn link(
+        &self,
+        id: i64,
+        kind: IdeaLinkKind,
+        ref_id: i64,
+    ) -> Result<TradeIdeaDetail, TradeIdeaError> {
+        let idea = self.idea(id).await?;
+        let Some(symbol) = store::link_target(&self.db, kind, ref_id).await? else {
+            return Err(TradeIdeaError::MissingLink {
+                kind: kind.as_str(),
+                id: ref_id,
+            });
+        };
+        if let Some(symbol) = symbol.filter(|s| *s != idea.symbol) {
+            return Err(TradeIdeaError::WrongSymbol { id: ref_id, symbol });
+        }
+        store::insert_link(&self.db, id, kind, ref_id, Utc::now().timestamp()).await?;
+        self.get(id).await
+    }
+
+    pub async fn unlink(
+        &self,
+        id: i64,
+        kind: IdeaLinkKind,
+        ref_id: i64,
+    ) -> Result<TradeIdeaDetail, TradeIdeaError> {
+        self.idea(id).await?;
+        store::delete_link(&self.db, id, kind, ref_id).await?;
+        self.get(id).await
+    }
+
+    /// Ideas, optionally in one stage, by stage then most recently
+    /// moved.
+    pub async fn list(&self, stage: Option<IdeaStage>) -> Result<Vec<TradeIdea>, TradeIdeaError> {
+        Ok(store::list(&self.db, stage).await?)
+    }
+
+    pub async fn get(&self, id: i64) -> Result<TradeIdeaDetail, TradeIdeaError> {
+        let idea = self.idea(id).await?;
+        Ok(TradeIdeaDetail {
+            transitions: store::transitions(&self.db, id).await?,
+            links: store::links(&self.db, id).await?,
+            idea,
+        })
+    }
+
+    async fn idea(&self, id: i64) -> Result<TradeIdea, TradeIdeaError> {
+        store::get(&self.db, id)
+            .await?
+            .ok_or(TradeIdeaError::NotFound(id))
+    }
+}
+
+fn clean(note: Option<String>) -> Option<String> {
+    note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty())
+}
@@ -0,0 +1,249 @@
+//! `trade_ideas`, `trade_idea_transitions` and `trade_idea_links` reads
+//! and writes.
+
+use chrono::{TimeZone, Utc};
+use rusqlite::OptionalExtension;
+
+use super::{IdeaLink, IdeaLinkKind, IdeaStage, IdeaTransition, TradeIdea};
+use crate::storage::error::StorageError;
+use crate::storage::Db;
+
+const IDEA_COLUMNS: &str = "id, symbol, stage, note, created_at, stage_changed_at";
+
+fn stage(s: String) -> IdeaStage {
+    IdeaStage::parse(&s).unwrap_or(IdeaStage::Watching)
+}
+
+fn idea_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<TradeIdea> {
+    Ok(TradeIdea {
+        id: row.get(0)?,
+        symbol: row.get(1)?,
+        stage: stage(row.get(2)?),
+        note: row.get(3)?,
+        created_at: row.get(4)?,
+        stage_changed_at: row.get(5)?,
+    })
+}
+
+/// New `watching` idea plus its creation transition; returns the id.
+pub async fn insert(
+    db: &Db,
+    symbol: &str,
+    note: Option<String>,
+    at: i64,
+) -> Result<i64, StorageError> {
+    let symbol = symbol.to_string();
+    db.with_conn(move |conn| {
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO trade_ideas (symbol, stage, note, created_at, stage_changed_at) \
+             VALUES (?1, ?2, ?3, ?4, ?4)",
+            rusqlite::params![symbol, IdeaStage::Watching.as_str(), note, at],
+        )?;
+        let id = tx.last_insert_rowid();
+        tx.execute(
+            "INSERT INTO trade_idea_transitions (idea_id, from_stage, to_stage, note, at) \
+             VALUES (?1, NULL, ?2, ?3, ?4)",
+            rusqlite::params![id, IdeaStage::Watching.as_str(), note, at],
+        )?;
+        tx.commit()?;
+        Ok(id)
+    })
+    .await
+}
+
+pub async fn set_stage(
+    db: &Db,
+    id: i64,
+    from: IdeaStage,
+    to: IdeaStage,
+    note: Option<String>,
+    at: i64,
+) -> Result<(), StorageError> {
+    db.with_conn(move |conn| {
+        let tx = conn.transaction()?;
+        tx.execute(
+            "UPDATE trade_ideas SET stage = ?2, note = ?3, stage_changed_at = ?4 WHERE id = ?1",
+            rusqlite::params![id, to.as_str(), note, at],
+        )?;
+        tx.execute(
+            "INSERT INTO trade_idea_transitions (idea_id, from_stage, to_stage, note, at) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![id, from.as_str(), to.as_str(), note, at],
+        )?;
+        tx.commit()?;
+        Ok(())
+    })
+    .await
+}
+
+pub async fn get(db: &Db, id: i64) -> Result<Option<TradeIdea>, StorageError> {
+    db.with_conn(move |conn| {
+        conn.query_row(
+            &format!("SELECT {IDEA_COLUMNS} FROM trade_ideas WHERE id = ?1"),
+            rusqlite::params![id],
+            idea_from_row,
+        )
+        .optional()
+        .map_err(StorageError::from)
+    })
+    .await
+}
+
+pub async fn open_for_symbol(db: &Db, symbol: &str) -> Result<Option<TradeIdea>, StorageError> {
+    let symbol = symbol.to_string();
+    db.with_conn(move |conn| {
+        conn.query_row(
+            &format!(
+                "SELECT {IDEA_COLUMNS} FROM trade_ideas WHERE symbol = ?1 AND stage != 'exited'"
+            ),
+            rusqlite::params![symbol],
+            idea_from_row,
+        )
+        .optional()
+        .map_err(StorageError::from)
+    })
+    .await
+}
+
+pub async fn list(db: &Db, stage: Option<IdeaStage>) -> Result<Vec<TradeIdea>, StorageError> {
+    let stage = stage.map(|s| s.as_str());
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {IDEA_COLUMNS} FROM trade_ideas \
+             WHERE ?1 IS NULL OR stage = ?1 \
+             ORDER BY CASE stage \
+               WHEN 'watching' THEN 0 WHEN 'researched' THEN 1 \
+               WHEN 'position' THEN 2 ELSE 3 END, \
+               stage_changed_at DESC, id DESC"
+        ))?;
+        let rows = stmt.query_map(rusqlite::params![stage], idea_from_row)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
+
+pub async fn transitions(db: &Db, id: i64) -> Result<Vec<IdeaTransition>, StorageError> {
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT from_stage, to_stage, note, at FROM trade_idea_transitions \
+             WHERE idea_id = ?1 ORDER BY at ASC, id ASC",
+        )?;
+        let rows = stmt.query_map(rusqlite::params![id], |row| {
+            Ok(IdeaTransition {
+                from_stage: row.get::<_, Option<String>>(0)?.map(stage),
+                to_stage: stage(row.get(1)?),
+                note: row.get(2)?,
+                at: row.get(3)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    })
+    .await
+}
+
+/// `None` when the referenced row doesn't exist; otherwise the
+/// projection's symbol, or `Some(None)` for a journal entry.
+pub async fn link_target(
+    db: &Db,
+    kind: IdeaLinkKind,
+    ref_id: i64,
+) -> Result<Option<Option<String>>, StorageError> {
+    db.with_conn(move |conn| {
+        let sql = match kind {
+            IdeaLinkKind::Projection => "SELECT symbol FROM projection_snapshots WHERE id = ?1",
+            IdeaLinkKind::Journal => "SELECT NULL FROM journal_entries WHERE id = ?1",
+        };
+        conn.query_row(sql, rusqlite::params![ref_id], |row| {
+            row.get::<_, Option<String>>(0)
+        })
+        .optional()
+        .map_err(StorageError::from)
+    })
+    .await
+}
+
+pub async fn insert_link(
+    db: &Db,
+    id: i64,
+    kind: IdeaLinkKind,
+    ref_id: i64,
+    at: i64,
+) -> Result<(), StorageError> {
+    db.with_conn(move |conn| {
+        conn.execute(
+            "INSERT OR IGNORE INTO trade_idea_links (idea_id, kind, ref_id, linked_at) \
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![id, kind.as_str(), ref_id, at],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+pub async fn delete_link(
+    db: &Db,
+    id: i64,
+    kind: IdeaLinkKind,
+    ref_id: i64,
+) -> Result<(), StorageError> {
+    db.with_conn(move |conn| {
+        conn.execute(
+            "DELETE FROM trade_idea_links WHERE idea_id = ?1 AND kind = ?2 AND ref_id = ?3",
+            rusqlite::params![id, kind.as_str(), ref_id],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+/// Links with a label from the row they point at; a link whose row
+/// has since been deleted is labelled `(deleted)`.
+pub async fn links(db: &Db, id: i64) -> Result<Vec<IdeaLink>, StorageError> {
+    db.with_conn(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT l.kind, l.ref_id, l.linked_at, p.generated_at, \
+                    j.journal_date || ' ' || j.section \
+             FROM trade_idea_links l \
+             LEFT JOIN projection_snapshots p \
+               ON l.kind = 'projection' AND p.id = l.ref_id \
+             LEFT JOIN journal_entries j \
+               ON l.kind = 'journal' AND j.id = l.ref_id \
+             WHERE l.idea_id = ?1 \
+             ORDER BY l.linked_at ASC, l.kind, l.ref_id",
+        )?;
+        let rows = stmt.query_map(rusqlite::params![id], |row| {
+            let kind: String = row.get(0)?;
+            let generated_at: Option<i64> = row.get(3)?;
+            let journal: Option<String> = row.get(4)?;
+            let label = generated_at
+                .and_then(|t| Utc.timestamp_opt(t, 0).single())
+                .map(|t| format!("projection {}", t.format("%Y-%m-%d")))
+                .or(journal)
+                .unwrap_or_else(|| "(deleted)".to_string());
+            Ok((kind, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?, label))
+        })?;
+        let mut out = Vec::new();
+        for row in rows {
+            let (kind, ref_id, linked_at, label) = row?;
+            if let Some(kind) = IdeaLinkKind::parse(&kind) {
+                out.push(IdeaLink {
+                    kind,
+                    ref_id,
+                    label,
+                    linked_at,
+                });
+            }
+        }
+        Ok(out)
+    })
+    .await
+}
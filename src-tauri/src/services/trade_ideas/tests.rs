@@ -0,0 +1,138 @@
+use chrono::NaiveDate;
+use tempfile::NamedTempFile;
+
+use super::*;
+use crate::ibkr::types::ProjectionAssumptions;
+use crate::services::journal_writer::{self, NewJournalEntry};
+use crate::services::projection_history::ProjectionHistoryStore;
+use crate::services::projection_service::ProjectionService;
+
+fn service() -> (NamedTempFile, Arc<Db>, TradeIdeaService) {
+    let tmp = NamedTempFile::new().unwrap();
+    let db = Arc::new(Db::open(tmp.path()).unwrap());
+    let svc = TradeIdeaService::new(Arc::clone(&db));
+    (tmp, db, svc)
+}
+
+async fn projection(db: &Arc<Db>, symbol: &str) -> i64 {
+    let history = ProjectionHistoryStore::new(Arc::clone(db));
+    let assumptions = ProjectionAssumptions::default();
+    let data = ProjectionService::generate_mock_fundamental_data(symbol);
+    let results = ProjectionService::generate_projection_results(&data, &assumptions).unwrap();
+    history
+        .record(symbol, &assumptions, &results)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn ideas_move_forward_only() {
+    let (_tmp, _db, svc) = service();
+    let idea = svc.create(" nvda ", None).await.unwrap();
+    assert_eq!(idea.symbol, "NVDA");
+    assert_eq!(idea.stage, IdeaStage::Watching);
+    assert!(matches!(
+        svc.create("NVDA", None).await,
+        Err(TradeIdeaError::AlreadyOpen { id, .. }) if id == idea.id
+    ));
+
+    // Skipping a stage is fine; going back isn't.
+    let moved = svc
+        .move_to(idea.id, IdeaStage::Position, Some("Bought the dip".into()))
+        .await
+        .unwrap();
+    assert_eq!(moved.stage, IdeaStage::Position);
+    assert_eq!(moved.note.as_deref(), Some("Bought the dip"));
+    assert!(matches!(
+        svc.move_to(idea.id, IdeaStage::Researched, None).await,
+        Err(TradeIdeaError::InvalidMove {
+            from: "position",
+            to: "researched"
+        })
+    ));
+    svc.move_to(idea.id, IdeaStage::Exited, None).await.unwrap();
+    assert!(matches!(
+        svc.move_to(idea.id, IdeaStage::Exited, None).await,
+        Err(TradeIdeaError::InvalidMove { .. })
+    ));
+
+    let detail = svc.get(idea.id).await.unwrap();
+    let stages: Vec<_> = detail
+        .transitions
+        .iter()
+        .map(|t| (t.from_stage, t.to_stage))
+        .collect();
+    assert_eq!(
+        stages,
+        vec![
+            (None, IdeaStage::Watching),
+            (Some(IdeaStage::Watching), IdeaStage::Position),
+            (Some(IdeaStage::Position), IdeaStage::Exited),
+        ]
+    );
+
+    // After the exit the symbol can start over.
+    let again = svc.create("NVDA", None).await.unwrap();
+    assert_ne!(again.id, idea.id);
+    let watching = svc.list(Some(IdeaStage::Watching)).await.unwrap();
+    assert_eq!(watching.len(), 1);
+    assert_eq!(svc.list(None).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn links_are_checked_and_labelled() {
+    let (_tmp, db, svc) = service();
+    let idea = svc.create("NVDA", None).await.unwrap();
+    let nvda = projection(&db, "NVDA").await;
+    let aapl = projection(&db, "AAPL").await;
+    let entry = journal_writer::upsert_entry(
+        &db,
+        NewJournalEntry {
+            journal_date: NaiveDate::from_ymd_opt(2026, 10, 14).unwrap(),
+            section: "NVDA notes".into(),
+            body_md: "Supply checks.".into(),
+            written_by: "interactive".into(),
+        },
+    )
+    .await
+    .unwrap();
+
+    assert!(matches!(
+        svc.link(idea.id, IdeaLinkKind::Projection, aapl).await,
+        Err(TradeIdeaError::WrongSymbol { .. })
+    ));
+    assert!(matches!(
+        svc.link(idea.id, IdeaLinkKind::Journal, 9_999).await,
+        Err(TradeIdeaError::MissingLink {
+            kind: "journal",
+            ..
+        })
+    ));
+
+    svc.link(idea.id, IdeaLinkKind::Projection, nvda)
+        .await
+        .unwrap();
+    let detail = svc
+        .link(idea.id, IdeaLinkKind::Journal, entry.id)
+        .await
+        .unwrap();
+    assert_eq!(detail.links.len(), 2);
+    let label = |kind| {
+        detail
+            .links
+            .iter()
+            .find(|l| l.kind == kind)
+            .map(|l| l.label.clone())
+            .unwrap()
+    };
+    assert!(label(IdeaLinkKind::Projection).starts_with("projection "));
+    assert_eq!(label(IdeaLinkKind::Journal), "2026-10-14 NVDA notes");
+
+    let detail = svc
+        .unlink(idea.id, IdeaLinkKind::Projection, nvda)
+        .await
+        .unwrap();
+    assert_eq!(detail.links.len(), 1);
+    assert_eq!(detail.links[0].kind, IdeaLinkKind::Journal);
+}
@@ -0,0 +1,180 @@
+//! Discounting projected share prices back to today.
+//!
+//! The projection engine values each forward year with P/E or P/S
+//! multiples. This module prices the time between now and then: the
+//! discount rate is the risk-free rate plus `valuation.equity_risk_premium_pct`,
+//! and every scenario's target is divided by `(1 + rate)^years`.
+//!
+//! The risk-free rate is `valuation.risk_free_rate_pct` when set, else
+//! the latest 10-year Treasury yield from `services::macro_service`,
+//! else [`DEFAULT_RISK_FREE_PCT`]. [`DiscountRate::source`] says which.
+//!
+//! Each year also carries the rate the current price implies: the
+//! annual return from today's price to the base-case midpoint. A rate
+//! above the discount rate means the price is below the discounted
+//! base case.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::time::timeout;
+use tracing::warn;
+
+use crate::ibkr::types::ProjectionResults;
+use crate::services::macro_service::{MacroReading, MacroSeries, MacroService};
+
+#[cfg(test)]
+mod tests;
+
+/// Used when there's no override and the macro feed has no yield.
+pub const DEFAULT_RISK_FREE_PCT: f64 = 4.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValuationConfig {
+    /// Manual risk-free rate, annual percent. `None` reads the 10-year
+    /// Treasury.
+    #[serde(default)]
+    pub risk_free_rate_pct: Option<f64>,
+    #[serde(default = "default_equity_risk_premium_pct")]
+    pub equity_risk_premium_pct: f64,
+}
+
+fn default_equity_risk_premium_pct() -> f64 {
+    5.0
+}
+
+impl Default for ValuationConfig {
+    fn default() -> Self {
+        Self {
+            risk_free_rate_pct: None,
+            equity_risk_premium_pct: default_equity_risk_premium_pct(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskFreeSource {
+    Manual,
+    Treasury10y,
+    Default,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscountRate {
+    pub risk_free_pct: f64,
+    pub source: RiskFreeSource,
+    /// Date of the Treasury reading when `source` is `treasury10y`.
+    pub as_of: Option<NaiveDate>,
+    pub equity_risk_premium_pct: f64,
+    /// `risk_free_pct + equity_risk_premium_pct`.
+    pub discount_rate_pct: f64,
+}
+
+/// One projected year's targets in today's dollars.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PresentValue {
+    pub year: u32,
+    pub years_out: u32,
+    pub bear_low: f64,
+    pub base_low: f64,
+    pub base_high: f64,
+    pub bull_high: f64,
+    /// Annual return, percent, from the current price to the base-case
+    /// midpoint. `None` without a price or a positive target.
+    pub implied_rate_pct: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscountedValuation {
+    pub discount_rate: DiscountRate,
+    pub current_price: Option<f64>,
+    pub years: Vec<PresentValue>,
+}
+
+/// Trait seam for the market risk-free rate. Production is the
+/// [`MacroService`]; tests inject a fixed reading.
+#[async_trait]
+pub trait RiskFreeRate: Send + Sync {
+    async fn treasury_10y(&self) -> Option<MacroReading>;
+}
+
+/// Cold-cache reads are one AV round trip; past this the default rate
+/// is used rather than holding up the projection.
+const MARKET_RATE_DEADLINE: Duration = Duration::from_secs(3);
+
+#[async_trait]
+impl RiskFreeRate for MacroService {
+    async fn treasury_10y(&self) -> Option<MacroReading> {
+        match timeout(MARKET_RATE_DEADLINE, self.latest(MacroSeries::Treasury10Y)).await {
+            Ok(Ok(reading)) => reading,
+            Ok(Err(e)) => {
+                warn!("valuation: 10-year yield unavailable, using default: {e}");
+                None
+            }
+            Err(_) => {
+                warn!("valuation: 10-year yield timed out, using default");
+                None
+            }
+        }
+    }
+}
+
+pub async fn discount_rate(config: &ValuationConfig, market: &dyn RiskFreeRate) -> DiscountRate {
+    let (risk_free_pct, source, as_of) = match config.risk_free_rate_pct {
+        Some(rate) => (rate, RiskFreeSource::Manual, None),
+        None => match market.treasury_10y().await {
+            Some(r) => (r.value, RiskFreeSource::Treasury10y, Some(r.as_of)),
+            None => (DEFAULT_RISK_FREE_PCT, RiskFreeSource::Default, None),
+        },
+    };
+    DiscountRate {
+        risk_free_pct,
+        source,
+        as_of,
+        equity_risk_premium_pct: config.equity_risk_premium_pct,
+        discount_rate_pct: risk_free_pct + config.equity_risk_premium_pct,
+    }
+}
+
+/// Discount every projected year of `results` at `rate`, counting years
+/// from the baseline (actual) year.
+pub fn discount(
+    results: &ProjectionResults,
+    rate: DiscountRate,
+    current_price: Option<f64>,
+) -> DiscountedValuation {
+    let r = rate.discount_rate_pct / 100.0;
+    let price = current_price.filter(|p| p.is_finite() && *p > 0.0);
+    let years = results
+        .projections
+        .iter()
+        .map(|p| {
+            let years_out = p.year.saturating_sub(results.baseline.year).max(1);
+            let factor = (1.0 + r).powi(-(years_out as i32));
+            let mid = (p.base.share_price_low + p.base.share_price_high) / 2.0;
+            PresentValue {
+                year: p.year,
+                years_out,
+                bear_low: p.bear.share_price_low * factor,
+                base_low: p.base.share_price_low * factor,
+                base_high: p.base.share_price_high * factor,
+                bull_high: p.bull.share_price_high * factor,
+                implied_rate_pct: price
+                    .filter(|_| mid > 0.0)
+                    .map(|price| ((mid / price).powf(1.0 / f64::from(years_out)) - 1.0) * 100.0),
+            }
+        })
+        .collect();
+    DiscountedValuation {
+        discount_rate: rate,
+        current_price: price,
+        years,
+    }
+}
@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+
+use crate::ibkr::types::ProjectionAssumptions;
+use crate::services::macro_service::MacroReading;
+use crate::services::projection_service::ProjectionService;
+
+use super::{discount, discount_rate, RiskFreeRate, RiskFreeSource, ValuationConfig};
+
+struct Market(Option<MacroReading>);
+
+#[async_trait]
+impl RiskFreeRate for Market {
+    async fn treasury_10y(&self) -> Option<MacroReading> {
+        self.0
+    }
+}
+
+fn ten_year(value: f64) -> Market {
+    Market(Some(MacroReading {
+        value,
+        as_of: "2026-10-13".parse().unwrap(),
+    }))
+}
+
+#[tokio::test]
+async fn rate_prefers_the_override_then_the_treasury_then_the_default() {
+    let mut config = ValuationConfig::default();
+    let rate = discount_rate(&config, &ten_year(4.1)).await;
+    assert_eq!(rate.source, RiskFreeSource::Treasury10y);
+    assert!((rate.discount_rate_pct - 9.1).abs() < 1e-9);
+    assert!(rate.as_of.is_some());
+
+    let rate = discount_rate(&config, &Market(None)).await;
+    assert_eq!(rate.source, RiskFreeSource::Default);
+    assert_eq!(rate.discount_rate_pct, 9.0);
+
+    config.risk_free_rate_pct = Some(3.5);
+    config.equity_risk_premium_pct = 4.5;
+    let rate = discount_rate(&config, &ten_year(4.1)).await;
+    assert_eq!(rate.source, RiskFreeSource::Manual);
+    assert_eq!(rate.discount_rate_pct, 8.0);
+}
+
+#[tokio::test]
+async fn discounts_each_year_and_reports_the_price_implied_rate() {
+    let data = ProjectionService::generate_mock_fundamental_data("NVDA");
+    let results =
+        ProjectionService::generate_projection_results(&data, &ProjectionAssumptions::default())
+            .unwrap();
+    let rate = discount_rate(&ValuationConfig::default(), &Market(None)).await;
+    let valuation = discount(&results, rate, Some(100.0));
+
+    assert_eq!(valuation.years.len(), results.projections.len());
+    for (pv, p) in valuation.years.iter().zip(&results.projections) {
+        assert_eq!(pv.years_out, p.year - results.baseline.year);
+        let factor = 1.09_f64.powi(-(pv.years_out as i32));
+        assert!((pv.base_low - p.base.share_price_low * factor).abs() < 1e-6);
+        assert!((pv.bull_high - p.bull.share_price_high * factor).abs() < 1e-6);
+        // At the implied rate the base midpoint discounts to the price.
+        let mid = (p.base.share_price_low + p.base.share_price_high) / 2.0;
+        let implied = pv.implied_rate_pct.unwrap() / 100.0;
+        assert!((mid / (1.0 + implied).powi(pv.years_out as i32) - 100.0).abs() < 1e-6);
+    }
+
+    let no_price = discount(&results, valuation.discount_rate.clone(), None);
+    assert!(no_price.years.iter().all(|y| y.implied_rate_pct.is_none()));
+}
@@ -0,0 +1,98 @@
+//! Orderly teardown when the app exits.
+//!
+//! Without it the process ends with the IBKR socket still open (TWS
+//! holds the client id until it notices), background loops mid-pass
+//! and order audit events still queued in memory. [`on_exit`] runs from
+//! `RunEvent::Exit`, before the async runtime goes away:
+//!
+//! 1. stop the streams, schedulers and listeners `IbkrState` owns;
+//! 2. close the order audit feed and let the ingest loop write what is
+//!    queued ([`PendingWrites`]);
+//! 3. disconnect from IBKR, which releases the client id;
+//! 4. checkpoint the database so everything is in the main file.
+//!
+//! Each step is bounded by [`STEP_TIMEOUT`]; a wedged gateway or loop
+//! is logged and skipped rather than holding the app open. Positions
+//! are not written anywhere: IBKR is their record, and the position
+//! cache only spares round-trips while the app runs.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::ibkr::IbkrState;
+use crate::storage::Db;
+
+/// Longest any one teardown step may take.
+pub const STEP_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Background writers to wait on at exit. Managed by `lib.rs::run`.
+pub struct PendingWrites {
+    order_audit: StdMutex<Option<JoinHandle<()>>>,
+}
+
+impl PendingWrites {
+    /// `order_audit` is the `OrderAuditStore` ingest loop; it ends once
+    /// the client's order sink is closed and the queue is drained.
+    pub fn new(order_audit: JoinHandle<()>) -> Self {
+        Self {
+            order_audit: StdMutex::new(Some(order_audit)),
+        }
+    }
+
+    fn take_order_audit(&self) -> Option<JoinHandle<()>> {
+        self.order_audit
+            .lock()
+            .expect("pending writes poisoned")
+            .take()
+    }
+}
+
+pub async fn on_exit(app: &AppHandle) {
+    info!("shutdown: starting");
+    if let Some(ibkr) = app.try_state::<IbkrState>() {
+        step("stop background tasks", ibkr.stop_all()).await;
+
+        ibkr.client.close_order_sink();
+        if let Some(ingest) = app
+            .try_state::<PendingWrites>()
+            .and_then(|p| p.take_order_audit())
+        {
+            step("flush order audit", async {
+                if let Err(e) = ingest.await {
+                    warn!("shutdown: order audit ingest failed: {e}");
+                }
+            })
+            .await;
+        }
+
+        if ibkr.client.is_connected().await {
+            step("disconnect from IBKR", async {
+                if let Err(e) = ibkr.client.disconnect().await {
+                    warn!("shutdown: disconnect failed: {e}");
+                }
+            })
+            .await;
+        }
+    }
+
+    if let Some(db) = app.try_state::<Arc<Db>>() {
+        step("checkpoint database", async {
+            if let Err(e) = db.checkpoint().await {
+                warn!("shutdown: checkpoint failed: {e}");
+            }
+        })
+        .await;
+    }
+    info!("shutdown: done");
+}
+
+async fn step(name: &str, work: impl Future<Output = ()>) {
+    if tokio::time::timeout(STEP_TIMEOUT, work).await.is_err() {
+        warn!("shutdown: {name} timed out after {STEP_TIMEOUT:?}");
+    }
+}
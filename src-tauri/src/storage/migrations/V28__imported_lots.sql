@@ -0,0 +1,36 @@
+-- V28__imported_lots.sql
+-- Holdings imported from broker CSV exports (generic / Schwab /
+-- Fidelity) for accounts tracked outside IBKR. Read alongside live
+-- IBKR positions so portfolio-level analysis sees the merged book.
+--
+-- Each import is a full snapshot of the accounts it mentions: the
+-- importer deletes every existing row for those accounts and writes
+-- the new set in one transaction. There is no incremental diffing —
+-- broker exports don't carry stable lot ids to diff against.
+--
+-- `account` is the user-chosen label (or the export's own account
+-- number column when present). It never collides with IBKR account
+-- ids in practice, but the merged view keeps `source` so the UI can
+-- badge imported rows regardless.
+
+CREATE TABLE IF NOT EXISTS imported_lots (
+    id                 INTEGER PRIMARY KEY AUTOINCREMENT,
+    account            TEXT    NOT NULL,
+    -- 'generic' | 'schwab' | 'fidelity'
+    source             TEXT    NOT NULL,
+    symbol             TEXT    NOT NULL,                -- upper-cased
+    quantity           REAL    NOT NULL,
+    -- Total cost basis for the lot, in `currency`. NULL when the
+    -- export omitted it (Schwab prints `--` for transferred lots).
+    cost_basis_total   REAL,
+    -- Last price printed in the export. Stale by design — used only
+    -- when no live quote is available.
+    last_price         REAL,
+    -- ISO date (YYYY-MM-DD) when the export carries per-lot dates.
+    acquired_date      TEXT,
+    currency           TEXT    NOT NULL DEFAULT 'USD',
+    imported_at_unix   INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_imported_lots_account
+    ON imported_lots(account, symbol);
@@ -0,0 +1,46 @@
+-- V29__bars_cache_what_to_show.sql
+-- Key the bars cache by (symbol, bar_size, what_to_show) so MIDPOINT /
+-- BID_ASK series can live next to TRADES without overwriting each
+-- other, and record which calendar-day spans have actually been
+-- fetched so the service only requests the gaps.
+--
+-- SQLite can't change a primary key in place: rebuild `bars_cache`
+-- with the new column and copy existing rows across as 'trades' (the
+-- only series the service ever wrote). Direct writers that omit the
+-- column (test seeding, backtester priming) get the same default.
+
+CREATE TABLE bars_cache_v29 (
+    symbol       TEXT    NOT NULL,
+    bar_size     TEXT    NOT NULL,
+    what_to_show TEXT    NOT NULL DEFAULT 'trades',
+    bar_time     INTEGER NOT NULL,
+    open         REAL    NOT NULL,
+    high         REAL    NOT NULL,
+    low          REAL    NOT NULL,
+    close        REAL    NOT NULL,
+    volume       INTEGER NOT NULL,
+    wap          REAL,
+    PRIMARY KEY(symbol, bar_size, what_to_show, bar_time)
+);
+
+INSERT INTO bars_cache_v29
+    (symbol, bar_size, what_to_show, bar_time, open, high, low, close, volume, wap)
+SELECT symbol, bar_size, 'trades', bar_time, open, high, low, close, volume, wap
+FROM bars_cache;
+
+DROP TABLE bars_cache;
+ALTER TABLE bars_cache_v29 RENAME TO bars_cache;
+
+-- Fetched coverage, one row per merged span of calendar days (ISO
+-- YYYY-MM-DD, both ends inclusive). A span with no bars in it (a
+-- weekend, a holiday, pre-IPO history) still counts as covered — that
+-- is the point: bar timestamps alone can't tell "no bars exist" from
+-- "never asked". The service rewrites a key's rows on every fetch.
+CREATE TABLE IF NOT EXISTS bars_cache_coverage (
+    symbol       TEXT NOT NULL,
+    bar_size     TEXT NOT NULL,
+    what_to_show TEXT NOT NULL,
+    start_day    TEXT NOT NULL,
+    end_day      TEXT NOT NULL,
+    PRIMARY KEY(symbol, bar_size, what_to_show, start_day)
+);
@@ -0,0 +1,20 @@
+-- V30__fundamentals_overrides.sql
+-- Per-field operator corrections layered over whatever the
+-- fundamentals provider returns (manual row or Alpha Vantage).
+-- Unlike `manual_fundamentals` (V09), which replaces the whole record,
+-- an override touches only the fields it names: revenue / net income /
+-- EPS for specific fiscal years, the share count, the current price.
+--
+--   * symbol        primary key (uppercase)
+--   * payload_json  serialized `FundamentalsOverride` (camelCase)
+--   * written_at    unix seconds
+--   * written_by    "interactive" for the editor, agent name otherwise
+--   * notes         free-text reason ("AV double-counted FY2022 revenue")
+
+CREATE TABLE IF NOT EXISTS fundamentals_overrides (
+    symbol       TEXT PRIMARY KEY,
+    payload_json TEXT NOT NULL,
+    written_at   INTEGER NOT NULL,
+    written_by   TEXT NOT NULL,
+    notes        TEXT
+);
@@ -0,0 +1,23 @@
+-- V31__projection_snapshots.sql
+-- Every `ProjectionResults` the analysis screen generates, with the
+-- assumptions it was run under, so targets can be compared across
+-- fiscal years and assumption tweaks.
+--
+--   * symbol            uppercase ticker
+--   * generated_at      unix seconds
+--   * baseline_year     fiscal year of the actual-data baseline row;
+--                       a bump means a new annual report rolled in
+--   * assumptions_json  serialized `ProjectionAssumptions` (camelCase)
+--   * results_json      serialized `ProjectionResults` (camelCase)
+
+CREATE TABLE IF NOT EXISTS projection_snapshots (
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    symbol           TEXT    NOT NULL,
+    generated_at     INTEGER NOT NULL,
+    baseline_year    INTEGER NOT NULL,
+    assumptions_json TEXT    NOT NULL,
+    results_json     TEXT    NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_projection_snapshots_symbol
+    ON projection_snapshots(symbol, generated_at);
@@ -0,0 +1,24 @@
+-- V32__fair_value_flags.sql
+-- Latest fair-value reading per ticker, written by the daily
+-- fair-value watch (`services/fair_value_watch`). One row per symbol;
+-- the dashboard reads it directly.
+--
+--   * zone          below_bear | within | above_bull
+--   * price         market price at evaluation
+--   * bear_value / base_low / base_high / bull_value
+--                   band from the projection snapshot `snapshot_id`
+--   * evaluated_at  unix seconds of the last evaluation
+--   * changed_at    unix seconds the zone last changed
+
+CREATE TABLE IF NOT EXISTS fair_value_flags (
+    symbol       TEXT PRIMARY KEY,
+    zone         TEXT    NOT NULL,
+    price        REAL    NOT NULL,
+    bear_value   REAL    NOT NULL,
+    base_low     REAL    NOT NULL,
+    base_high    REAL    NOT NULL,
+    bull_value   REAL    NOT NULL,
+    snapshot_id  INTEGER NOT NULL,
+    evaluated_at INTEGER NOT NULL,
+    changed_at   INTEGER NOT NULL
+);
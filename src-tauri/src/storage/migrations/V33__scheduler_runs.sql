@@ -0,0 +1,21 @@
+-- V33__scheduler_runs.sql
+-- One row per run of a `services::scheduler` task, so the settings
+-- screen can show when each job last ran and why it last failed.
+--
+--   * job_id       task id (`fair_value_check`, ...)
+--   * started_at   unix seconds
+--   * finished_at  unix seconds
+--   * ok           1 on success, 0 on failure
+--   * message      task summary on success, error text on failure
+
+CREATE TABLE IF NOT EXISTS scheduler_runs (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    job_id      TEXT    NOT NULL,
+    started_at  INTEGER NOT NULL,
+    finished_at INTEGER NOT NULL,
+    ok          INTEGER NOT NULL,
+    message     TEXT    NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduler_runs_job
+    ON scheduler_runs(job_id, id);
@@ -0,0 +1,23 @@
+-- V34__order_audit.sql
+-- Append-only trail of what the IBKR adapter sent and heard back, one
+-- row per `OrderAuditEvent` (see `services::order_audit`).
+--
+--   * recorded_at   unix milliseconds, when the app saw the event
+--   * order_id      IBKR order id (per-client, resets with the gateway)
+--   * symbol        upper-case ticker
+--   * kind          sent | rejected | status | fill | notice
+--   * payload_json  the full event
+
+CREATE TABLE IF NOT EXISTS order_audit (
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    recorded_at  INTEGER NOT NULL,
+    order_id     INTEGER NOT NULL,
+    symbol       TEXT    NOT NULL,
+    kind         TEXT    NOT NULL,
+    payload_json TEXT    NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_order_audit_time
+    ON order_audit(recorded_at);
+CREATE INDEX IF NOT EXISTS idx_order_audit_symbol
+    ON order_audit(symbol, recorded_at);
@@ -0,0 +1,22 @@
+-- V35__margin_samples.sql
+-- Margin utilization over time, one row per account per sample, written
+-- by the `margin_sample` scheduled task (`services/margin_monitor`).
+-- Values are IBKR account values in the account's base currency.
+--
+--   * maint_margin_req  MaintMarginReq
+--   * excess_liquidity  ExcessLiquidity
+--   * cushion           Cushion (excess liquidity / NLV, 0..1)
+--   * net_liquidation   NetLiquidation
+--   * sampled_at        unix seconds
+
+CREATE TABLE IF NOT EXISTS margin_samples (
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    account          TEXT    NOT NULL,
+    sampled_at       INTEGER NOT NULL,
+    maint_margin_req REAL    NOT NULL,
+    excess_liquidity REAL    NOT NULL,
+    cushion          REAL    NOT NULL,
+    net_liquidation  REAL    NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_margin_samples_account_time
+    ON margin_samples(account, sampled_at);
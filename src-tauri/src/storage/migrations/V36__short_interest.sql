@@ -0,0 +1,16 @@
+-- V36__short_interest.sql
+-- Latest short interest per symbol (`services/short_interest`), as of
+-- the FINRA settlement date. One row per symbol, replaced on refresh.
+--
+--   * settlement_date  YYYY-MM-DD
+--   * fetched_at       unix seconds of the last feed read
+
+CREATE TABLE IF NOT EXISTS short_interest (
+    symbol                TEXT    PRIMARY KEY,
+    settlement_date       TEXT    NOT NULL,
+    shares_short          REAL    NOT NULL,
+    previous_shares_short REAL,
+    avg_daily_volume      REAL,
+    days_to_cover         REAL,
+    fetched_at            INTEGER NOT NULL
+);
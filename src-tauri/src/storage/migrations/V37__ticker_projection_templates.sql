@@ -0,0 +1,11 @@
+-- Projection template chosen per ticker (`services/projection_templates`).
+-- Written when `ibkr_generate_projection_results` is called with a
+-- template name; later calls without assumptions reuse it. The name
+-- refers to `AppConfig.projection_templates` and is matched
+-- case-insensitively.
+
+CREATE TABLE IF NOT EXISTS ticker_projection_templates (
+    symbol     TEXT    PRIMARY KEY,
+    template   TEXT    NOT NULL,
+    chosen_at  INTEGER NOT NULL
+);
@@ -0,0 +1,27 @@
+-- V38__margin_of_safety_bands.sql
+-- Buy-below / sell-above bands per ticker and scenario, written by the
+-- margin-of-safety watch (`services/margin_of_safety`). Rebuilt from
+-- the newest projection snapshot on every run.
+--
+--   * scenario      bear | base | bull
+--   * fair_value    midpoint of the scenario's first projected year
+--   * buy_below / sell_above
+--                   fair_value × (1 ∓ margin_pct / 100)
+--   * zone          buy | hold | sell at `price`; NULL until the ticker
+--                   has been priced
+--   * changed_at    unix seconds the zone last changed
+
+CREATE TABLE IF NOT EXISTS margin_of_safety_bands (
+    symbol       TEXT    NOT NULL,
+    scenario     TEXT    NOT NULL,
+    fair_value   REAL    NOT NULL,
+    buy_below    REAL    NOT NULL,
+    sell_above   REAL    NOT NULL,
+    margin_pct   REAL    NOT NULL,
+    snapshot_id  INTEGER NOT NULL,
+    zone         TEXT,
+    price        REAL,
+    evaluated_at INTEGER NOT NULL,
+    changed_at   INTEGER,
+    PRIMARY KEY (symbol, scenario)
+);
@@ -0,0 +1,28 @@
+-- V39__position_snapshots.sql
+-- End-of-day open positions, one row per account, ET date and contract,
+-- written by the `position_snapshot` scheduled task
+-- (`services/portfolio_diff`). Re-running a date replaces its rows, so
+-- a position closed since the earlier run drops out.
+--
+--   * contract        ticker for stock; IBKR local symbol otherwise
+--                     (one row per option series)
+--   * snapshot_date   YYYY-MM-DD, ET
+--   * values are IBKR position fields in the position's currency
+--   * taken_at        unix seconds
+
+CREATE TABLE IF NOT EXISTS position_snapshots (
+    account        TEXT    NOT NULL,
+    snapshot_date  TEXT    NOT NULL,
+    contract       TEXT    NOT NULL,
+    symbol         TEXT    NOT NULL,
+    contract_type  TEXT    NOT NULL,
+    currency       TEXT    NOT NULL,
+    quantity       REAL    NOT NULL,
+    average_cost   REAL    NOT NULL,
+    market_price   REAL    NOT NULL,
+    market_value   REAL    NOT NULL,
+    unrealized_pnl REAL    NOT NULL,
+    realized_pnl   REAL    NOT NULL,
+    taken_at       INTEGER NOT NULL,
+    PRIMARY KEY (account, snapshot_date, contract)
+);
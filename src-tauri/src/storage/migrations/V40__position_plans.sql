@@ -0,0 +1,25 @@
+-- V40__position_plans.sql
+-- User-set plan per open position: target price, stop level and a
+-- short thesis (`services/position_plans`). Kept after the position
+-- closes so re-opening the contract brings the plan back.
+--
+--   * contract   ticker for stock; IBKR local symbol otherwise
+--   * hit        target | stop while the last check found the price
+--                past that level; NULL otherwise
+--   * hit_at / updated_at
+--                unix seconds
+
+CREATE TABLE IF NOT EXISTS position_plans (
+    account       TEXT    NOT NULL,
+    contract      TEXT    NOT NULL,
+    symbol        TEXT    NOT NULL,
+    target_price  REAL,
+    stop_price    REAL,
+    thesis        TEXT,
+    hit           TEXT,
+    hit_at        INTEGER,
+    updated_at    INTEGER NOT NULL,
+    PRIMARY KEY (account, contract)
+);
+
+CREATE INDEX IF NOT EXISTS idx_position_plans_symbol ON position_plans (symbol);
@@ -0,0 +1,43 @@
+-- V41__trade_ideas.sql
+-- Research pipeline per ticker (`services/trade_ideas`): an idea moves
+-- watching → researched → position → exited, forward only. At most one
+-- idea per symbol is open (not exited); a later idea in the same name
+-- is a new row.
+--
+--   * stage / stage_changed_at / created_at   unix seconds
+--   * trade_idea_transitions   every stage change, with its note;
+--                              from_stage is NULL for the creation
+--   * trade_idea_links         kind projection → projection_snapshots.id,
+--                              kind journal → journal_entries.id
+
+CREATE TABLE IF NOT EXISTS trade_ideas (
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    symbol            TEXT    NOT NULL,
+    stage             TEXT    NOT NULL,
+    note              TEXT,
+    created_at        INTEGER NOT NULL,
+    stage_changed_at  INTEGER NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_trade_ideas_open_symbol
+    ON trade_ideas(symbol) WHERE stage != 'exited';
+
+CREATE TABLE IF NOT EXISTS trade_idea_transitions (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    idea_id     INTEGER NOT NULL REFERENCES trade_ideas(id) ON DELETE CASCADE,
+    from_stage  TEXT,
+    to_stage    TEXT    NOT NULL,
+    note        TEXT,
+    at          INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_trade_idea_transitions_idea
+    ON trade_idea_transitions(idea_id, at);
+
+CREATE TABLE IF NOT EXISTS trade_idea_links (
+    idea_id    INTEGER NOT NULL REFERENCES trade_ideas(id) ON DELETE CASCADE,
+    kind       TEXT    NOT NULL,
+    ref_id     INTEGER NOT NULL,
+    linked_at  INTEGER NOT NULL,
+    PRIMARY KEY (idea_id, kind, ref_id)
+);
@@ -0,0 +1,46 @@
+-- V42__sim_trading.sql
+-- Paper-trading simulator (`services/paper_trading`): orders placed in
+-- sim mode are filled locally against polled or replayed prices and
+-- never reach IBKR. The simulated portfolio is derived from the fills.
+--
+--   * sim_account        one row: the cash the current run started with
+--   * sim_orders.status  working | filled | cancelled | rejected
+--   * sim_fills          reference_price is the tape price before
+--                        slippage; price is what the order paid
+--   * timestamps         unix seconds
+
+CREATE TABLE IF NOT EXISTS sim_account (
+    id             INTEGER PRIMARY KEY CHECK (id = 1),
+    starting_cash  REAL    NOT NULL,
+    started_at     INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS sim_orders (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    symbol      TEXT    NOT NULL,
+    side        TEXT    NOT NULL,
+    quantity    REAL    NOT NULL,
+    order_type  TEXT    NOT NULL,
+    price       REAL,
+    status      TEXT    NOT NULL,
+    reason      TEXT,
+    created_at  INTEGER NOT NULL,
+    closed_at   INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS idx_sim_orders_working
+    ON sim_orders(symbol) WHERE status = 'working';
+
+CREATE TABLE IF NOT EXISTS sim_fills (
+    id               INTEGER PRIMARY KEY AUTOINCREMENT,
+    order_id         INTEGER NOT NULL REFERENCES sim_orders(id) ON DELETE CASCADE,
+    symbol           TEXT    NOT NULL,
+    side             TEXT    NOT NULL,
+    quantity         REAL    NOT NULL,
+    price            REAL    NOT NULL,
+    reference_price  REAL    NOT NULL,
+    commission       REAL    NOT NULL,
+    filled_at        INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_sim_fills_symbol ON sim_fills(symbol, filled_at);
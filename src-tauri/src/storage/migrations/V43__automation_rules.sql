@@ -0,0 +1,54 @@
+-- V43__automation_rules.sql
+-- Strategy automation rules (`services/automation`): a condition on one
+-- symbol coupled with an action, evaluated every minute of the session.
+--
+--   * automation_rules.condition / action   JSON, tagged by `kind`
+--   * automation_rules.last_matched         the condition at the last
+--                                           evaluation; NULL until the
+--                                           first one (a rule fires on
+--                                           the false -> true edge)
+--   * rule_evaluations                      the audit log. Rule name,
+--                                           symbol and action kind are
+--                                           copied so the log outlives
+--                                           a deleted rule.
+--   * rule_evaluations.outcome              idle | suppressed | notified
+--                                           | dry_run | placed | blocked
+--                                           | failed
+--   * timestamps                            unix seconds
+
+CREATE TABLE IF NOT EXISTS automation_rules (
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    name              TEXT    NOT NULL,
+    symbol            TEXT    NOT NULL,
+    condition         TEXT    NOT NULL,
+    action            TEXT    NOT NULL,
+    enabled           INTEGER NOT NULL DEFAULT 1,
+    cooldown_minutes  INTEGER NOT NULL,
+    last_matched      INTEGER,
+    last_fired_at     INTEGER,
+    created_at        INTEGER NOT NULL,
+    updated_at        INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS rule_evaluations (
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    rule_id       INTEGER NOT NULL,
+    rule_name     TEXT    NOT NULL,
+    symbol        TEXT    NOT NULL,
+    action        TEXT    NOT NULL,
+    evaluated_at  INTEGER NOT NULL,
+    price         REAL,
+    value         REAL,
+    threshold     REAL,
+    matched       INTEGER,
+    fired         INTEGER NOT NULL,
+    outcome       TEXT    NOT NULL,
+    detail        TEXT,
+    order_id      INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS idx_rule_evaluations_rule
+    ON rule_evaluations(rule_id, evaluated_at);
+
+CREATE INDEX IF NOT EXISTS idx_rule_evaluations_fired
+    ON rule_evaluations(fired, evaluated_at);
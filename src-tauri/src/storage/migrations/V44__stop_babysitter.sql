@@ -0,0 +1,15 @@
+-- V44__stop_babysitter.sql
+-- Local stop handling on position plans (`services/position_plans`,
+-- the `stop_babysitter` task): what to do when the price breaches the
+-- plan's stop, and what was done.
+--
+--   * stop_action        alert | market_exit | limit_exit; NULL leaves
+--                        the stop to the plan check's alert
+--   * stop_triggered_at  unix seconds of the breach that was acted on;
+--                        NULL while armed
+--   * stop_result        what was done: the exit order, or why placing
+--                        it failed
+
+ALTER TABLE position_plans ADD COLUMN stop_action TEXT;
+ALTER TABLE position_plans ADD COLUMN stop_triggered_at INTEGER;
+ALTER TABLE position_plans ADD COLUMN stop_result TEXT;
@@ -0,0 +1,12 @@
+-- V45__benchmark_tags.sql
+-- Benchmarks tagged on positions and watchlist entries
+-- (`services/benchmarks`): the ETF a symbol's performance is measured
+-- against (QQQ, SPY, a sector ETF). One tag per symbol, upper-cased.
+--
+--   * updated_at   unix seconds
+
+CREATE TABLE IF NOT EXISTS benchmark_tags (
+    symbol      TEXT    PRIMARY KEY,
+    benchmark   TEXT    NOT NULL,
+    updated_at  INTEGER NOT NULL
+);
@@ -0,0 +1,19 @@
+-- V46__cash_samples.sql
+-- Cash as a share of NAV over time, one row per account per ET date,
+-- written by the `cash_drag_sample` scheduled task (`services/cash_drag`).
+-- A later sample on the same date replaces the row. Values are IBKR
+-- account values in the account's base currency.
+--
+--   * as_of_date       ET date, `YYYY-MM-DD`
+--   * cash             TotalCashValue
+--   * net_liquidation  NetLiquidation
+--   * sampled_at       unix seconds
+
+CREATE TABLE IF NOT EXISTS cash_samples (
+    account          TEXT    NOT NULL,
+    as_of_date       TEXT    NOT NULL,
+    cash             REAL    NOT NULL,
+    net_liquidation  REAL    NOT NULL,
+    sampled_at       INTEGER NOT NULL,
+    PRIMARY KEY(account, as_of_date)
+);
@@ -0,0 +1,32 @@
+-- V47__order_arrivals.sql
+-- Arrival quotes for execution-quality analysis (`services/tca`).
+--
+-- `ibkr_place_order` snapshots the stock's bid / ask just before the
+-- order goes to IBKR and writes one row per placed order, keyed by the
+-- IBKR order id. Fills find their arrival through
+-- `executions(account, order_id)`; slippage is measured against the
+-- arrival mid, so it captures the spread paid as well as the drift
+-- while the order worked. A reused order id replaces the row.
+--
+--   * side        'buy' | 'sell'
+--   * order_type  'market' | 'limit' | 'stop' | 'stop_limit' | 'midprice'
+--   * *_cents     integer cents, as on `order_intents`; NULL when the
+--                 snapshot had no bid or ask
+--   * placed_at   ISO 8601 UTC
+
+CREATE TABLE IF NOT EXISTS order_arrivals (
+    account           TEXT    NOT NULL,
+    order_id          INTEGER NOT NULL,
+    symbol            TEXT    NOT NULL,
+    side              TEXT    NOT NULL,
+    order_type        TEXT    NOT NULL,
+    qty               REAL    NOT NULL,
+    limit_price_cents INTEGER,
+    bid_cents         INTEGER,
+    ask_cents         INTEGER,
+    placed_at         TEXT    NOT NULL,
+    PRIMARY KEY(account, order_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_order_arrivals_placed
+    ON order_arrivals(placed_at);
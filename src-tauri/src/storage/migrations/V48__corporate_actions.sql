@@ -0,0 +1,30 @@
+-- V48__corporate_actions.sql
+-- Splits, symbol changes and spin-offs applied to local data
+-- (`services/corporate_actions`).
+--
+-- One row per action, written in the same transaction as the
+-- adjustments it made, so an action is applied at most once: the
+-- unique key is what the daily detection checks before touching
+-- anything.
+--
+--   * kind            'split' | 'symbol_change' | 'spin_off'
+--   * symbol          the symbol before the action
+--   * effective_date  ISO date (YYYY-MM-DD), the first session after it
+--   * action          JSON `CorporateAction`
+--   * source          'provider' | 'ibkr' | 'manual'
+--   * adjusted        JSON array of `{ table, rows }`
+
+CREATE TABLE IF NOT EXISTS corporate_actions (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind            TEXT    NOT NULL,
+    symbol          TEXT    NOT NULL,
+    effective_date  TEXT    NOT NULL,
+    action          TEXT    NOT NULL,
+    source          TEXT    NOT NULL,
+    adjusted        TEXT    NOT NULL,
+    applied_at      INTEGER NOT NULL,
+    UNIQUE(kind, symbol, effective_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_corporate_actions_applied
+    ON corporate_actions(applied_at DESC);
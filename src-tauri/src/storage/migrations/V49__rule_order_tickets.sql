@@ -0,0 +1,15 @@
+-- V49__rule_order_tickets.sql
+-- Automation rules no longer send live orders (`services/automation`):
+-- a `live_order` action posts a ticket the trader confirms or
+-- dismisses.
+--
+--   * rule_evaluations.ticket       JSON `OrderRequest` of a posted
+--                                   ticket; NULL for other actions
+--   * rule_evaluations.resolved_at  unix seconds the trader confirmed
+--                                   or dismissed it
+--   * rule_evaluations.outcome      gains ticketed | dismissed;
+--                                   placed now means a confirmed
+--                                   ticket
+
+ALTER TABLE rule_evaluations ADD COLUMN ticket TEXT;
+ALTER TABLE rule_evaluations ADD COLUMN resolved_at INTEGER;
@@ -0,0 +1,10 @@
+-- V50__stop_exit_tickets.sql
+-- The stop babysitter no longer sends exits (`services/position_plans`):
+-- a `market_exit` or `limit_exit` breach posts a ticket the trader
+-- confirms or dismisses.
+--
+--   * position_plans.exit_ticket  JSON `OrderRequest` awaiting the
+--                                 trader; NULL once confirmed,
+--                                 dismissed, or for an alert
+
+ALTER TABLE position_plans ADD COLUMN exit_ticket TEXT;
@@ -40,6 +40,16 @@ impl Db {
         })
     }
 
+    /// Fold the WAL back into the database file, so the file is
+    /// complete without its `-wal` sidecar. Run on app exit.
+    pub async fn checkpoint(&self) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")?;
+            Ok(())
+        })
+        .await
+    }
+
     pub async fn with_conn<F, T>(&self, f: F) -> Result<T>
     where
         F: FnOnce(&mut Connection) -> Result<T> + Send + 'static,
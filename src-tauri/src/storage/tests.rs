@@ -416,3 +416,23 @@ async fn bars_cache_pk_dedup() {
         "second row must overwrite first"
     );
 }
+
+#[tokio::test]
+async fn checkpoint_empties_the_wal() {
+    let tmp = temp_db_path();
+    let db = Db::open(tmp.path()).expect("open db");
+    db.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO tracked_tickers (symbol, source, added_at) VALUES ('AAPL', 'manual', 0)",
+            [],
+        )?;
+        Ok(())
+    })
+    .await
+    .expect("insert");
+    let wal = std::path::PathBuf::from(format!("{}-wal", tmp.path().display()));
+    assert!(std::fs::metadata(&wal).unwrap().len() > 0);
+
+    db.checkpoint().await.expect("checkpoint");
+    assert_eq!(std::fs::metadata(&wal).unwrap().len(), 0);
+}
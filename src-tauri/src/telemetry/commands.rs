@@ -0,0 +1,6 @@
+/// Prometheus text exposition of the app's counters and histograms
+/// (see `telemetry/mod.rs` for the metric catalogue).
+#[tauri::command]
+pub async fn get_metrics() -> Result<String, String> {
+    Ok(super::render())
+}
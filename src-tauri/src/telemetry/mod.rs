@@ -0,0 +1,129 @@
+//! Process metrics (counters + histograms) via the `metrics` facade,
+//! rendered as Prometheus text for `get_metrics` and the HTTP API's
+//! `/v1/metrics`.
+//!
+//! [`init`] installs the recorder once at startup. Until then — and in
+//! every unit test — the `metrics::counter!` / `histogram!` macros are
+//! no-ops, so instrumented code needs no test-only branches.
+//!
+//! Metric names live here as constants so the call sites and the
+//! `describe_*` registrations can't drift:
+//!
+//! | name | type | labels |
+//! |------|------|--------|
+//! | `qk_commands_total` | counter | `command` |
+//! | `qk_events_emitted_total` | counter | `event` |
+//! | `qk_cache_lookups_total` | counter | `cache`, `result` (`hit`/`miss`) |
+//! | `qk_ibkr_requests_total` | counter | `endpoint` |
+//! | `qk_ibkr_request_duration_seconds` | histogram | — |
+//! | `qk_llm_calls_total` | counter | `kind`, `outcome` (`ok`/`error`) |
+//! | `qk_llm_call_duration_seconds` | histogram | `kind` |
+
+pub mod commands;
+
+use std::sync::OnceLock;
+
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+
+pub const COMMANDS_TOTAL: &str = "qk_commands_total";
+pub const EVENTS_EMITTED_TOTAL: &str = "qk_events_emitted_total";
+pub const CACHE_LOOKUPS_TOTAL: &str = "qk_cache_lookups_total";
+pub const IBKR_REQUESTS_TOTAL: &str = "qk_ibkr_requests_total";
+pub const IBKR_REQUEST_DURATION: &str = "qk_ibkr_request_duration_seconds";
+pub const LLM_CALLS_TOTAL: &str = "qk_llm_calls_total";
+pub const LLM_CALL_DURATION: &str = "qk_llm_call_duration_seconds";
+
+/// Latency buckets shared by every `_seconds` histogram: IBKR calls sit
+/// in the tens of ms, LLM calls in the tens of seconds.
+const SECONDS_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+fn builder() -> PrometheusBuilder {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".into()), SECONDS_BUCKETS)
+        .expect("non-empty bucket list")
+}
+
+fn describe() {
+    metrics::describe_counter!(COMMANDS_TOTAL, "Tauri commands invoked");
+    metrics::describe_counter!(EVENTS_EMITTED_TOTAL, "AppEvents emitted");
+    metrics::describe_counter!(CACHE_LOOKUPS_TOTAL, "Cache lookups by result");
+    metrics::describe_counter!(IBKR_REQUESTS_TOTAL, "IBKR requests by endpoint");
+    metrics::describe_histogram!(
+        IBKR_REQUEST_DURATION,
+        metrics::Unit::Seconds,
+        "Blocking IBKR call duration, including executor queue wait"
+    );
+    metrics::describe_counter!(LLM_CALLS_TOTAL, "LlmService calls by kind and outcome");
+    metrics::describe_histogram!(
+        LLM_CALL_DURATION,
+        metrics::Unit::Seconds,
+        "LLM backend round-trip duration"
+    );
+}
+
+/// Install the global recorder. Idempotent; a second recorder (another
+/// library got there first) is logged and metrics stay disabled.
+pub fn init() {
+    if HANDLE.get().is_some() {
+        return;
+    }
+    match builder().install_recorder() {
+        Ok(handle) => {
+            describe();
+            let _ = HANDLE.set(handle);
+        }
+        Err(e) => tracing::warn!("metrics recorder not installed: {e}"),
+    }
+}
+
+/// Prometheus text exposition of everything recorded so far. Empty when
+/// [`init`] never ran.
+pub fn render() -> String {
+    HANDLE
+        .get()
+        .map(PrometheusHandle::render)
+        .unwrap_or_default()
+}
+
+/// Wrap the `generate_handler!` dispatcher so every invocation bumps
+/// `qk_commands_total{command}`. Counts only — async commands resolve
+/// after the dispatcher returns, so durations aren't observable here.
+pub fn count_commands<R: tauri::Runtime>(
+    handler: impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        metrics::counter!(COMMANDS_TOTAL, "command" => invoke.message.command().to_string())
+            .increment(1);
+        handler(invoke)
+    }
+}
+
+pub fn record_cache_lookup(cache: &'static str, hit: bool) {
+    let result = if hit { "hit" } else { "miss" };
+    metrics::counter!(CACHE_LOOKUPS_TOTAL, "cache" => cache, "result" => result).increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_counters_and_histograms_as_prometheus_text() {
+        let recorder = builder().build_recorder();
+        let handle = recorder.handle();
+        metrics::with_local_recorder(&recorder, || {
+            metrics::counter!(COMMANDS_TOTAL, "command" => "get_settings").increment(2);
+            record_cache_lookup("alpha_vantage", false);
+            metrics::histogram!(IBKR_REQUEST_DURATION).record(0.02);
+        });
+
+        let text = handle.render();
+        assert!(text.contains(r#"qk_commands_total{command="get_settings"} 2"#));
+        assert!(text.contains(r#"qk_cache_lookups_total{cache="alpha_vantage",result="miss"} 1"#));
+        assert!(text.contains(r#"qk_ibkr_request_duration_seconds_bucket{le="0.025"} 1"#));
+    }
+}
@@ -4,9 +4,9 @@
 // 2025–2028 inclusive. When a year falls off the front, append the next year's
 // dates and update the comment below.
 //
-// Half-day sessions (e.g., the day after Thanksgiving, Christmas Eve when it
-// falls on a weekday) are intentionally NOT included — see the module-level
-// `Known follow-ups` in the phase doc.
+// Half-day sessions (13:00 ET close) are listed separately in `EARLY_CLOSES`
+// and only the exchange-aware session API (`sessions`) honours them; the
+// legacy RTH helpers treat them as full days.
 
 use chrono::NaiveDate;
 
@@ -66,3 +66,17 @@ pub const HOLIDAYS: &[NaiveDate] = &[
     d(2028, 11, 23), // Thanksgiving
     d(2028, 12, 25), // Christmas
 ];
+
+/// NYSE 13:00 ET early closes: July 3 when it's a trading day, the day
+/// after Thanksgiving, and Christmas Eve when it's a trading day. Extend
+/// with `HOLIDAYS`.
+pub const EARLY_CLOSES: &[NaiveDate] = &[
+    d(2025, 7, 3),
+    d(2025, 11, 28),
+    d(2025, 12, 24),
+    d(2026, 11, 27),
+    d(2026, 12, 24),
+    d(2027, 11, 26),
+    d(2028, 7, 3),
+    d(2028, 11, 24),
+];
@@ -1,5 +1,9 @@
 //! US equity market calendar helpers.
 //!
+//! Exchange-aware, DST-correct sessions (`is_market_open`, next open /
+//! close, early closes) live in [`sessions`]; the helpers below predate
+//! them and keep the fixed offset.
+//!
 //! TODO(EST/EDT): Eastern time is hardcoded to EST (UTC-5). DST is intentionally
 //! skipped for the MVP — the EOD sweep at 16:05 ET and the 5-minute intraday
 //! tick are coarse enough that an hour drift during DST is harmless. Revisit
@@ -10,6 +14,7 @@
 #![allow(dead_code)]
 
 mod holidays;
+pub mod sessions;
 
 #[cfg(test)]
 mod tests;
@@ -17,6 +22,9 @@ mod tests;
 use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
 
 use holidays::HOLIDAYS;
+pub use sessions::{
+    is_market_open, market_status, next_open, trading_session, MarketStatus, TradingSession,
+};
 
 const RTH_OPEN: (u32, u32) = (9, 30);
 const RTH_CLOSE: (u32, u32) = (16, 0);
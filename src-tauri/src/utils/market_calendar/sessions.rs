@@ -0,0 +1,211 @@
+//! Exchange-aware trading sessions.
+//!
+//! Unlike the legacy RTH helpers in the parent module (fixed UTC-5),
+//! sessions here are evaluated in the exchange's own time zone, so
+//! daylight saving is handled, and they honour early closes. Only US
+//! equity and option venues are known so far; every other exchange
+//! answers `None` rather than a guess.
+//!
+//! Extended hours (pre-market from 04:00, post-market to 20:00, 17:00
+//! on early-close days) are reported by [`trading_session`] only;
+//! "open" always means the regular session.
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::America::New_York;
+use chrono_tz::Tz;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::holidays::{EARLY_CLOSES, HOLIDAYS};
+
+/// Regular session of one calendar.
+#[derive(Debug)]
+pub struct MarketHours {
+    pub name: &'static str,
+    tz: Tz,
+    open: (u32, u32),
+    close: (u32, u32),
+    early_close: (u32, u32),
+    pre_open: (u32, u32),
+    post_close: (u32, u32),
+    early_post_close: (u32, u32),
+    holidays: &'static [NaiveDate],
+    early_closes: &'static [NaiveDate],
+}
+
+pub static US_EQUITIES: MarketHours = MarketHours {
+    name: "US equities",
+    tz: New_York,
+    open: (9, 30),
+    close: (16, 0),
+    early_close: (13, 0),
+    pre_open: (4, 0),
+    post_close: (20, 0),
+    early_post_close: (17, 0),
+    holidays: HOLIDAYS,
+    early_closes: EARLY_CLOSES,
+};
+
+/// IBKR exchange codes routed on the US equity calendar. Empty means
+/// the default SMART route.
+const US_VENUES: &[&str] = &[
+    "",
+    "SMART",
+    "NYSE",
+    "NASDAQ",
+    "ISLAND",
+    "ARCA",
+    "NYSEARCA",
+    "AMEX",
+    "NYSEAMERICAN",
+    "BATS",
+    "BYX",
+    "EDGX",
+    "IEX",
+    "CBOE",
+    "BOX",
+    "PHLX",
+    "ISE",
+];
+
+/// Calendar for an IBKR exchange code (case-insensitive).
+pub fn hours_for(exchange: &str) -> Option<&'static MarketHours> {
+    let exchange = exchange.trim().to_uppercase();
+    US_VENUES
+        .contains(&exchange.as_str())
+        .then_some(&US_EQUITIES)
+}
+
+/// `None` for an exchange without a known calendar.
+pub fn is_market_open(exchange: &str, now: DateTime<Utc>) -> Option<bool> {
+    hours_for(exchange).map(|h| h.is_open(now))
+}
+
+/// Start of the next session at or after `now` (now, if one is open
+/// from exactly `now`).
+pub fn next_open(exchange: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    hours_for(exchange).map(|h| h.next_open(now))
+}
+
+/// End of the session in progress, or of the next one.
+pub fn next_close(exchange: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    hours_for(exchange).map(|h| h.next_close(now))
+}
+
+/// Which part of the trading day an instant falls in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TradingSession {
+    Pre,
+    Regular,
+    Post,
+    /// Overnight, weekends and holidays.
+    Closed,
+}
+
+/// `None` for an exchange without a known calendar.
+pub fn trading_session(exchange: &str, now: DateTime<Utc>) -> Option<TradingSession> {
+    hours_for(exchange).map(|h| h.trading_session(now))
+}
+
+/// Snapshot for the UI's market-status badge.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketStatus {
+    pub exchange: String,
+    pub calendar: &'static str,
+    pub open: bool,
+    pub session: TradingSession,
+    pub next_open: DateTime<Utc>,
+    pub next_close: DateTime<Utc>,
+}
+
+/// `None` for an exchange without a known calendar.
+pub fn market_status(exchange: &str, now: DateTime<Utc>) -> Option<MarketStatus> {
+    let hours = hours_for(exchange)?;
+    Some(MarketStatus {
+        exchange: exchange.trim().to_uppercase(),
+        calendar: hours.name,
+        open: hours.is_open(now),
+        session: hours.trading_session(now),
+        next_open: next_open(exchange, now)?,
+        next_close: next_close(exchange, now)?,
+    })
+}
+
+impl MarketHours {
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+            && self.holidays.binary_search(&date).is_err()
+    }
+
+    /// The exchange-local date of `now`.
+    pub fn local_date(&self, now: DateTime<Utc>) -> NaiveDate {
+        now.with_timezone(&self.tz).date_naive()
+    }
+
+    pub fn is_open(&self, now: DateTime<Utc>) -> bool {
+        self.session(self.local_date(now))
+            .is_some_and(|(open, close)| open <= now && now < close)
+    }
+
+    pub fn trading_session(&self, now: DateTime<Utc>) -> TradingSession {
+        let date = self.local_date(now);
+        let Some((open, close)) = self.session(date) else {
+            return TradingSession::Closed;
+        };
+        let post_close = if self.early_closes.binary_search(&date).is_ok() {
+            self.early_post_close
+        } else {
+            self.post_close
+        };
+        match (self.at(date, self.pre_open), self.at(date, post_close)) {
+            _ if open <= now && now < close => TradingSession::Regular,
+            (Some(pre), _) if pre <= now && now < open => TradingSession::Pre,
+            (_, Some(post)) if close <= now && now < post => TradingSession::Post,
+            _ => TradingSession::Closed,
+        }
+    }
+
+    pub fn next_open(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.sessions_from(self.local_date(now))
+            .map(|(open, _)| open)
+            .find(|open| *open >= now)
+            .expect("a trading day always follows")
+    }
+
+    pub fn next_close(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.sessions_from(self.local_date(now))
+            .map(|(_, close)| close)
+            .find(|close| *close > now)
+            .expect("a trading day always follows")
+    }
+
+    /// `(open, close)` of `date`'s session; `None` on a non-trading day.
+    pub fn session(&self, date: NaiveDate) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        if !self.is_trading_day(date) {
+            return None;
+        }
+        let close = if self.early_closes.binary_search(&date).is_ok() {
+            self.early_close
+        } else {
+            self.close
+        };
+        Some((self.at(date, self.open)?, self.at(date, close)?))
+    }
+
+    fn sessions_from(
+        &self,
+        date: NaiveDate,
+    ) -> impl Iterator<Item = (DateTime<Utc>, DateTime<Utc>)> + '_ {
+        date.iter_days().take(366).filter_map(|d| self.session(d))
+    }
+
+    fn at(&self, date: NaiveDate, (h, m): (u32, u32)) -> Option<DateTime<Utc>> {
+        let naive = date.and_time(NaiveTime::from_hms_opt(h, m, 0)?);
+        self.tz
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|t| t.with_timezone(&Utc))
+    }
+}
@@ -1,3 +1,4 @@
+use super::sessions::next_close;
 use super::*;
 use chrono::{FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
 
@@ -166,3 +167,78 @@ fn trading_days_after_close_returns_1600_et_on_target_date() {
     let expected = et_dt(NaiveDate::from_ymd_opt(2026, 5, 6).unwrap(), 16, 0);
     assert_eq!(trading_days_after_close(now, 3), expected);
 }
+
+fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> chrono::DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+}
+
+#[test]
+fn sessions_follow_daylight_saving() {
+    // 2026-04-28 is EDT: the session is 13:30–20:00 UTC.
+    assert_eq!(
+        is_market_open("SMART", utc(2026, 4, 28, 13, 45)),
+        Some(true)
+    );
+    assert_eq!(
+        is_market_open("nasdaq", utc(2026, 4, 28, 20, 0)),
+        Some(false)
+    );
+    // 2026-01-06 is EST: 14:30–21:00 UTC.
+    assert_eq!(is_market_open("NYSE", utc(2026, 1, 6, 14, 0)), Some(false));
+    assert_eq!(
+        next_open("NYSE", utc(2026, 1, 6, 14, 0)),
+        Some(utc(2026, 1, 6, 14, 30))
+    );
+    assert_eq!(
+        next_close("", utc(2026, 1, 6, 14, 0)),
+        Some(utc(2026, 1, 6, 21, 0))
+    );
+}
+
+#[test]
+fn sessions_honour_early_closes_and_holidays() {
+    // Day after Thanksgiving 2026 closes at 13:00 EST.
+    assert_eq!(
+        next_close("SMART", utc(2026, 11, 27, 15, 0)),
+        Some(utc(2026, 11, 27, 18, 0))
+    );
+    assert_eq!(
+        is_market_open("SMART", utc(2026, 11, 27, 18, 30)),
+        Some(false)
+    );
+    assert_eq!(
+        next_open("SMART", utc(2026, 11, 27, 18, 30)),
+        Some(utc(2026, 11, 30, 14, 30))
+    );
+    // Thanksgiving itself.
+    assert_eq!(
+        next_open("SMART", utc(2026, 11, 26, 15, 0)),
+        Some(utc(2026, 11, 27, 14, 30))
+    );
+}
+
+#[test]
+fn unknown_exchange_has_no_calendar() {
+    assert_eq!(is_market_open("LSE", utc(2026, 4, 28, 13, 45)), None);
+    assert_eq!(next_open("TSEJ", utc(2026, 4, 28, 13, 45)), None);
+}
+
+#[test]
+fn trading_session_tags_extended_hours() {
+    let at = |h, m| trading_session("SMART", utc(2026, 4, 28, h, m));
+    // EDT: pre from 08:00Z, regular 13:30–20:00Z, post to 00:00Z.
+    assert_eq!(at(7, 59), Some(TradingSession::Closed));
+    assert_eq!(at(8, 0), Some(TradingSession::Pre));
+    assert_eq!(at(13, 30), Some(TradingSession::Regular));
+    assert_eq!(at(20, 0), Some(TradingSession::Post));
+    assert_eq!(at(23, 59), Some(TradingSession::Post));
+    // Early close: post-market ends at 17:00 EST (22:00Z).
+    let friday = |h| trading_session("SMART", utc(2026, 11, 27, h, 0));
+    assert_eq!(friday(18), Some(TradingSession::Post));
+    assert_eq!(friday(22), Some(TradingSession::Closed));
+    assert_eq!(
+        trading_session("SMART", utc(2026, 5, 2, 15, 0)),
+        Some(TradingSession::Closed)
+    );
+    assert_eq!(trading_session("LSE", utc(2026, 4, 28, 13, 30)), None);
+}
@@ -1,2 +1,4 @@
 pub mod helpers;
 pub mod market_calendar;
+pub mod page;
+pub mod symbols;
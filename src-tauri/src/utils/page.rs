@@ -0,0 +1,58 @@
+//! Limit/offset window over a list result.
+//!
+//! List commands take optional `limit` / `offset` arguments so the
+//! frontend can fetch a large account's positions or history a page at
+//! a time. Stores push the window into SQL ([`Page::sql_limit`]); lists
+//! assembled in memory (IBKR positions, executions) are cut with
+//! [`Page::apply`].
+
+/// `limit` rows after skipping `offset`. No limit is the whole list.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Page {
+    pub limit: Option<u32>,
+    pub offset: u32,
+}
+
+impl Page {
+    /// Every row.
+    #[cfg(test)]
+    pub const ALL: Page = Page {
+        limit: None,
+        offset: 0,
+    };
+
+    pub fn new(limit: Option<u32>, offset: Option<u32>) -> Self {
+        Self {
+            limit,
+            offset: offset.unwrap_or(0),
+        }
+    }
+
+    /// SQLite `LIMIT` value; `-1` is unbounded.
+    pub fn sql_limit(&self) -> i64 {
+        self.limit.map_or(-1, i64::from)
+    }
+
+    pub fn apply<T>(&self, items: Vec<T>) -> Vec<T> {
+        let rows = items.into_iter().skip(self.offset as usize);
+        match self.limit {
+            Some(limit) => rows.take(limit as usize).collect(),
+            None => rows.collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_a_list() {
+        let rows: Vec<u32> = (0..10).collect();
+        assert_eq!(Page::ALL.apply(rows.clone()), rows);
+        assert_eq!(Page::new(Some(3), Some(4)).apply(rows.clone()), [4, 5, 6]);
+        assert_eq!(Page::new(None, Some(8)).apply(rows.clone()), [8, 9]);
+        assert!(Page::new(Some(5), Some(20)).apply(rows).is_empty());
+        assert_eq!(Page::ALL.sql_limit(), -1);
+    }
+}
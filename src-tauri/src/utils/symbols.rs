@@ -0,0 +1,89 @@
+//! One spelling per ticker.
+//!
+//! Symbols arrive in mixed case and in each source's notation for a
+//! share class: `BRK.B` (our UI, most exports), `BRK B` (IBKR), `BRK-B`
+//! (Alpha Vantage), `BRK/B` (some broker statements), `$brk.b` (social
+//! cashtags). [`normalize`] folds all of them into `BRK.B`. Cache keys,
+//! store keys and in-memory maps are built from the normalized form,
+//! so the same company is never fetched or stored twice; the spelling a
+//! vendor expects ([`ibkr`], [`alpha_vantage`], [`sec`]) is derived at
+//! the call.
+//!
+//! Only a class letter in [`SHARE_CLASSES`] after an all-letter root
+//! counts as a share class. Exchange suffixes (`SHOP.TO`, `XYZ.V`,
+//! `XYZ.L`), preferreds (`BAC-PL`) and numeric tickers (`7203`) are
+//! only trimmed and upper-cased.
+
+const SEPARATORS: [char; 4] = ['.', ' ', '-', '/'];
+
+/// Share-class letters US listings use. Other single letters are
+/// exchange suffixes (`.V` TSX Venture, `.L` London, `.T` Tokyo).
+const SHARE_CLASSES: [char; 3] = ['A', 'B', 'C'];
+
+/// Canonical spelling: trimmed, upper-case, share class after a `.`.
+pub fn normalize(raw: &str) -> String {
+    let upper = raw.trim().trim_start_matches('$').to_uppercase();
+    match share_class(&upper) {
+        Some((root, class)) => format!("{root}.{class}"),
+        None => upper,
+    }
+}
+
+/// IBKR contract symbol: share class after a space (`BRK B`).
+pub fn ibkr(symbol: &str) -> String {
+    with_class_separator(symbol, ' ')
+}
+
+/// Alpha Vantage `symbol=` parameter: share class after a `-`
+/// (`BRK-B`).
+pub fn alpha_vantage(symbol: &str) -> String {
+    with_class_separator(symbol, '-')
+}
+
+/// SEC EDGAR ticker map key: share class after a `-` (`BRK-B`).
+pub fn sec(symbol: &str) -> String {
+    with_class_separator(symbol, '-')
+}
+
+fn with_class_separator(symbol: &str, separator: char) -> String {
+    let symbol = normalize(symbol);
+    match share_class(&symbol) {
+        Some((root, class)) => format!("{root}{separator}{class}"),
+        None => symbol,
+    }
+}
+
+/// `("BRK", 'B')` out of any upper-cased `BRK.B` spelling.
+fn share_class(symbol: &str) -> Option<(&str, char)> {
+    let (root, class) = symbol.rsplit_once(SEPARATORS)?;
+    let root = root.trim_end_matches(SEPARATORS);
+    let mut chars = class.chars();
+    let class = chars.next().filter(|c| SHARE_CLASSES.contains(c))?;
+    let is_root = !root.is_empty() && root.chars().all(|c| c.is_ascii_uppercase());
+    (chars.next().is_none() && is_root).then_some((root, class))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn share_class_notations_fold_together() {
+        for raw in ["BRK.B", "brk b", "BRK-B", "BRK/B", " $brk.b ", "BRK  B"] {
+            assert_eq!(normalize(raw), "BRK.B", "{raw:?}");
+        }
+        assert_eq!(ibkr("brk-b"), "BRK B");
+        assert_eq!(alpha_vantage("BRK B"), "BRK-B");
+        assert_eq!(sec("brk.b"), "BRK-B");
+
+        // Not share classes.
+        assert_eq!(normalize(" aapl"), "AAPL");
+        assert_eq!(normalize("shop.to"), "SHOP.TO");
+        assert_eq!(normalize("BAC-PL"), "BAC-PL");
+        assert_eq!(normalize("7203"), "7203");
+        assert_eq!(ibkr("SHOP.TO"), "SHOP.TO");
+        assert_eq!(normalize("xyz.v"), "XYZ.V");
+        assert_eq!(ibkr("XYZ.V"), "XYZ.V");
+        assert_eq!(ibkr("XYZ.L"), "XYZ.L");
+    }
+}
@@ -0,0 +1,72 @@
+import { useEffect, useState } from "react"
+import { Card, CardContent, CardHeader, CardTitle } from "../../../shared/components/ui/card"
+import { Skeleton } from "../../../shared/components/ui/skeleton"
+import { getInsiderActivity, type InsiderActivity } from "../../../shared/api/insiderActivity"
+
+interface InsiderActivityCardProps {
+  symbol: string
+}
+
+function formatValue(value: number): string {
+  const abs = Math.abs(value)
+  const sign = value < 0 ? "-" : value > 0 ? "+" : ""
+  if (abs >= 1_000_000) return `${sign}$${(abs / 1_000_000).toFixed(1)}M`
+  if (abs >= 1_000) return `${sign}$${(abs / 1_000).toFixed(0)}K`
+  return `${sign}$${abs.toFixed(0)}`
+}
+
+export function InsiderActivityCard({ symbol }: InsiderActivityCardProps) {
+  const [activity, setActivity] = useState<InsiderActivity | null>(null)
+  const [error, setError] = useState<string | null>(null)
+
+  useEffect(() => {
+    let cancelled = false
+    setActivity(null)
+    setError(null)
+    getInsiderActivity(symbol)
+      .then((a) => !cancelled && setActivity(a))
+      .catch((e) => !cancelled && setError(String(e)))
+    return () => {
+      cancelled = true
+    }
+  }, [symbol])
+
+  return (
+    <Card className="border-border bg-card/50">
+      <CardHeader className="pb-2">
+        <CardTitle className="text-sm font-medium">Insider activity</CardTitle>
+      </CardHeader>
+      <CardContent>
+        {error ? (
+          <p className="text-muted-foreground text-sm">Insider data unavailable: {error}</p>
+        ) : !activity ? (
+          <Skeleton className="bg-secondary h-16" />
+        ) : (
+          <div className="grid grid-cols-3 gap-3">
+            {activity.windows.map((w) => (
+              <div key={w.months} className="border-border bg-background/40 rounded-md border p-3">
+                <p className="text-muted-foreground text-xs tracking-wide uppercase">
+                  {w.months} months
+                </p>
+                <p
+                  className={`mt-1 text-base font-semibold ${
+                    w.netValue > 0
+                      ? "text-emerald-400"
+                      : w.netValue < 0
+                        ? "text-rose-400"
+                        : "text-foreground"
+                  }`}
+                >
+                  {w.transactions === 0 ? "—" : formatValue(w.netValue)}
+                </p>
+                <p className="text-muted-foreground text-xs">
+                  {w.buyers} buying · {w.sellers} selling
+                </p>
+              </div>
+            ))}
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  )
+}
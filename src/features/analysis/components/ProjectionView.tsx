@@ -1,15 +1,27 @@
 import { Card, CardContent, CardHeader, CardTitle } from "../../../shared/components/ui/card"
 import { ProjectionTable } from "./ProjectionTable"
 import { ProjectionSummary } from "./ProjectionSummary"
-import type { ProjectionResults, ProjectionAssumptions } from "../../../shared/types"
+import type {
+  DiscountedValuation,
+  ExpectedValue,
+  ProjectionResults,
+  ProjectionAssumptions,
+} from "../../../shared/types"
 
 interface ProjectionViewProps {
   results: ProjectionResults
   symbol: string
   assumptions?: ProjectionAssumptions
+  valuation?: DiscountedValuation | null
 }
 
-export function ProjectionView({ results, symbol, assumptions }: ProjectionViewProps) {
+const RISK_FREE_LABEL = {
+  manual: "manual risk-free",
+  treasury10y: "10-year Treasury",
+  default: "default risk-free",
+} as const
+
+export function ProjectionView({ results, symbol, assumptions, valuation }: ProjectionViewProps) {
   // Validate results data
   if (!results || !results.baseline || !results.projections || results.projections.length === 0) {
     return (
@@ -40,6 +52,8 @@ export function ProjectionView({ results, symbol, assumptions }: ProjectionViewP
             {results.projections[results.projections.length - 1]?.year}
           </span>
         </p>
+        {results.expected && <ExpectedLine expected={results.expected} />}
+        {valuation && <DiscountLine valuation={valuation} />}
       </CardHeader>
 
       <CardContent className="space-y-6">
@@ -58,3 +72,33 @@ export function ProjectionView({ results, symbol, assumptions }: ProjectionViewP
     </Card>
   )
 }
+
+function ExpectedLine({ expected }: { expected: ExpectedValue }) {
+  return (
+    <p className="text-muted-foreground text-sm">
+      Probability-weighted {expected.year} target:{" "}
+      <span className="text-foreground font-medium">${expected.targetPrice.toFixed(2)}</span>{" "}
+      • expected CAGR {expected.cagr.sharePrice.toFixed(1)}%
+    </p>
+  )
+}
+
+function DiscountLine({ valuation }: { valuation: DiscountedValuation }) {
+  const rate = valuation.discountRate
+  const last = valuation.years[valuation.years.length - 1]
+  return (
+    <p className="text-muted-foreground text-sm">
+      Discount rate:{" "}
+      <span className="text-foreground font-medium">{rate.discountRatePct.toFixed(2)}%</span> (
+      {RISK_FREE_LABEL[rate.source]} {rate.riskFreePct.toFixed(2)}% + ERP{" "}
+      {rate.equityRiskPremiumPct.toFixed(2)}%)
+      {last && (
+        <>
+          {" "}
+          • {last.year} base case today: ${last.baseLow.toFixed(2)}–${last.baseHigh.toFixed(2)}
+          {last.impliedRatePct !== null && <> • price implies {last.impliedRatePct.toFixed(1)}%/yr</>}
+        </>
+      )}
+    </p>
+  )
+}
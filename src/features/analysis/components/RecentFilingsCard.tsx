@@ -0,0 +1,73 @@
+import { useEffect, useState } from "react"
+import { ExternalLink } from "lucide-react"
+import { Card, CardContent, CardHeader, CardTitle } from "../../../shared/components/ui/card"
+import { Skeleton } from "../../../shared/components/ui/skeleton"
+import { getRecentFilings, type RecentFilings } from "../../../shared/api/edgar"
+
+interface RecentFilingsCardProps {
+  symbol: string
+}
+
+const SHOWN = 8
+
+export function RecentFilingsCard({ symbol }: RecentFilingsCardProps) {
+  const [recent, setRecent] = useState<RecentFilings | null>(null)
+  const [error, setError] = useState<string | null>(null)
+
+  useEffect(() => {
+    let cancelled = false
+    setRecent(null)
+    setError(null)
+    getRecentFilings(symbol)
+      .then((r) => !cancelled && setRecent(r))
+      .catch((e) => !cancelled && setError(String(e)))
+    return () => {
+      cancelled = true
+    }
+  }, [symbol])
+
+  return (
+    <Card className="border-border bg-card/50">
+      <CardHeader className="pb-2">
+        <CardTitle className="text-sm font-medium">SEC filings</CardTitle>
+      </CardHeader>
+      <CardContent>
+        {error ? (
+          <p className="text-muted-foreground text-sm">Filings unavailable: {error}</p>
+        ) : !recent ? (
+          <Skeleton className="bg-secondary h-16" />
+        ) : recent.filings.length === 0 ? (
+          <p className="text-muted-foreground text-sm">No 10-K, 10-Q or 8-K filings on EDGAR.</p>
+        ) : (
+          <ul className="divide-border divide-y">
+            {recent.filings.slice(0, SHOWN).map((f) => (
+              <li key={f.accessionNumber} className="flex items-center gap-3 py-1.5 text-sm">
+                <span className="w-14 font-medium">{f.form}</span>
+                <span className="text-muted-foreground w-24 text-xs">{f.filingDate}</span>
+                <a
+                  href={f.url}
+                  target="_blank"
+                  rel="noopener noreferrer"
+                  className="text-primary hover:text-primary/80 inline-flex min-w-0 flex-1 items-center gap-1 text-xs"
+                >
+                  <span className="truncate">
+                    {f.description ?? (f.reportDate ? `Period ${f.reportDate}` : "Document")}
+                  </span>
+                  <ExternalLink className="h-3 w-3 shrink-0" />
+                </a>
+                <a
+                  href={f.indexUrl}
+                  target="_blank"
+                  rel="noopener noreferrer"
+                  className="text-muted-foreground hover:text-foreground text-[11px]"
+                >
+                  Index
+                </a>
+              </li>
+            ))}
+          </ul>
+        )}
+      </CardContent>
+    </Card>
+  )
+}
@@ -32,13 +32,15 @@ export function TickerCards({ ticker, quote, quoteError }: TickerCardsProps) {
   const lastPrice = quote?.lastPrice
   const prevClose = quote?.prevClose
   const change =
-    lastPrice !== undefined && prevClose !== undefined ? lastPrice - prevClose : undefined
+    lastPrice != null && prevClose != null ? lastPrice - prevClose : undefined
   const changePercent =
-    change !== undefined && prevClose !== undefined && prevClose !== 0
+    change !== undefined && prevClose != null && prevClose !== 0
       ? (change / prevClose) * 100
       : undefined
   const isPositive = (change ?? 0) >= 0
   const statusMessage = quoteStatusMessage(quoteError)
+  const extendedLabel =
+    quote?.session === "pre" ? "Pre-market" : quote?.session === "post" ? "After hours" : null
 
   return (
     <div className="grid grid-cols-1 gap-4 md:grid-cols-2 lg:grid-cols-4">
@@ -53,7 +55,7 @@ export function TickerCards({ ticker, quote, quoteError }: TickerCardsProps) {
         <CardContent>
           <div className="space-y-1">
             <p className="text-foreground text-3xl font-bold">
-              {lastPrice !== undefined ? `$${lastPrice.toFixed(2)}` : "—"}
+              {lastPrice != null ? `$${lastPrice.toFixed(2)}` : "—"}
             </p>
             {change !== undefined && changePercent !== undefined && (
               <div
@@ -71,6 +73,12 @@ export function TickerCards({ ticker, quote, quoteError }: TickerCardsProps) {
                 </span>
               </div>
             )}
+            {extendedLabel && (
+              <p className="text-muted-foreground text-xs">
+                {extendedLabel}
+                {quote?.lastRthPrice != null && ` · regular close $${quote.lastRthPrice.toFixed(2)}`}
+              </p>
+            )}
             {statusMessage && <p className="text-muted-foreground text-xs">{statusMessage}</p>}
           </div>
         </CardContent>
@@ -87,7 +95,7 @@ export function TickerCards({ ticker, quote, quoteError }: TickerCardsProps) {
         <CardContent>
           <div className="space-y-1">
             <p className="text-foreground text-3xl font-bold">
-              {quote?.volume !== undefined ? formatVolume(quote.volume) : "—"}
+              {quote?.volume != null ? formatVolume(quote.volume) : "—"}
             </p>
             <p className="text-muted-foreground text-sm">Trading Volume</p>
           </div>
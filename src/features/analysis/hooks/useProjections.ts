@@ -1,6 +1,7 @@
 import { useState, useEffect } from "react"
 import { ibkrApi } from "../../../shared/api/ibkr"
 import type {
+  DiscountedValuation,
   ProjectionResults,
   ProjectionAssumptions,
   FundamentalData,
@@ -9,6 +10,7 @@ import type {
 export function useProjections(symbol: string | null, assumptions?: ProjectionAssumptions) {
   const [results, setResults] = useState<ProjectionResults | null>(null)
   const [fundamentalData, setFundamentalData] = useState<FundamentalData | null>(null)
+  const [valuation, setValuation] = useState<DiscountedValuation | null>(null)
   const [loading, setLoading] = useState(false)
   const [error, setError] = useState<string | null>(null)
 
@@ -16,6 +18,7 @@ export function useProjections(symbol: string | null, assumptions?: ProjectionAs
     if (!symbol) {
       setResults(null)
       setFundamentalData(null)
+      setValuation(null)
       return
     }
 
@@ -30,6 +33,7 @@ export function useProjections(symbol: string | null, assumptions?: ProjectionAs
         const bundle = await ibkrApi.generateProjectionResults(symbol, assumptions)
         setFundamentalData(bundle.fundamentals)
         setResults(bundle.results)
+        setValuation(bundle.valuation ?? null)
       } catch (err) {
         setError(err instanceof Error ? err.message : "Failed to fetch projections")
         console.error("Error fetching projections:", err)
@@ -41,5 +45,5 @@ export function useProjections(symbol: string | null, assumptions?: ProjectionAs
     fetchProjections()
   }, [symbol, assumptions])
 
-  return { results, fundamentalData, loading, error }
+  return { results, fundamentalData, valuation, loading, error }
 }
@@ -12,6 +12,7 @@ export function useConnection() {
     host: "127.0.0.1",
     port: 4004,
     client_id: 100,
+    market_data_type: "delayed_frozen",
   })
   const [loading, setLoading] = useState(false)
   const [disconnecting, setDisconnecting] = useState(false)
@@ -6,11 +6,15 @@ import {
   CardTitle,
 } from "../../../shared/components/ui/card"
 import { Settings, Clock } from "lucide-react"
-import { formatCurrency, anonymizeAccountNumber } from "../utils"
-import type { AccountSummary as AccountSummaryType, ConnectionStatus } from "../../../shared/types"
+import { formatCurrency, accountLabel } from "../utils"
+import type {
+  AccountInfo,
+  AccountSummary as AccountSummaryType,
+  ConnectionStatus,
+} from "../../../shared/types"
 
 interface AccountDetailsProps {
-  accounts: string[]
+  accounts: AccountInfo[]
   accountSummary: AccountSummaryType[]
   connectionStatus: ConnectionStatus
 }
@@ -111,7 +115,7 @@ export function AccountDetails({
             <div>
               <h4 className="text-foreground text-sm font-medium">Account ID</h4>
               <p className="text-foreground font-mono text-lg">
-                {accounts[0] ? anonymizeAccountNumber(accounts[0]) : "N/A"}
+                {accounts[0] ? accountLabel(accounts[0]) : "N/A"}
               </p>
             </div>
             <div className="text-right">
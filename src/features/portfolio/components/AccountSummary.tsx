@@ -1,17 +1,23 @@
 import { Card, CardContent, CardHeader, CardTitle } from "../../../shared/components/ui/card"
 import { DollarSign, Activity, TrendingUp, TrendingDown, PieChart } from "lucide-react"
-import { formatCurrency, anonymizeAccountNumber } from "../utils"
+import { formatCurrency, accountLabel } from "../utils"
 import { useDailyPnL } from "../hooks/useDailyPnL"
-import type { AccountSummary as AccountSummaryType, Position } from "../../../shared/types"
+import { useCarryCosts } from "../hooks/useCarryCosts"
+import type {
+  AccountInfo,
+  AccountSummary as AccountSummaryType,
+  Position,
+} from "../../../shared/types"
 
 interface AccountSummaryProps {
-  accounts: string[]
+  accounts: AccountInfo[]
   accountSummary: AccountSummaryType[]
   positions: Position[]
 }
 
 export function AccountSummary({ accounts, accountSummary, positions }: AccountSummaryProps) {
-  const dailyPnL = useDailyPnL(accounts[0])
+  const dailyPnL = useDailyPnL(accounts[0]?.id)
+  const carryCosts = useCarryCosts(accounts[0]?.id)
 
   // Calculate account values from summary - check multiple possible tag names
   const getAccountValue = (tags: string[]): number => {
@@ -53,7 +59,7 @@ export function AccountSummary({ accounts, accountSummary, positions }: AccountS
             <p className="text-foreground text-3xl font-bold">{formatCurrency(totalEquity)}</p>
             <p className="text-muted-foreground text-sm">
               {accounts.length > 0
-                ? `Account: ${anonymizeAccountNumber(accounts[0])}`
+                ? `Account: ${accountLabel(accounts[0])}`
                 : "No account"}
             </p>
           </div>
@@ -101,6 +107,9 @@ export function AccountSummary({ accounts, accountSummary, positions }: AccountS
             )}
             <p className="text-muted-foreground text-sm">
               {dailyValue === null ? "Awaiting first tick" : "Today's change"}
+              {carryCosts && carryCosts.totalDaily > 0 && (
+                <> · Carry −{formatCurrency(carryCosts.totalDaily)}/day</>
+              )}
             </p>
           </div>
         </CardContent>
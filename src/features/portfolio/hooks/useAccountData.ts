@@ -1,9 +1,9 @@
 import { useState, useCallback } from "react"
 import { ibkrApi } from "../../../shared/api/ibkr"
-import type { AccountSummary, Position } from "../../../shared/types"
+import type { AccountInfo, AccountSummary, Position } from "../../../shared/types"
 
 export function useAccountData() {
-  const [accounts, setAccounts] = useState<string[]>([])
+  const [accounts, setAccounts] = useState<AccountInfo[]>([])
   const [accountSummary, setAccountSummary] = useState<AccountSummary[]>([])
   const [positions, setPositions] = useState<Position[]>([])
 
@@ -15,7 +15,7 @@ export function useAccountData() {
 
       if (accountList.length > 0) {
         // Get account summary for the first account
-        const summary = await ibkrApi.getAccountSummary(accountList[0])
+        const summary = await ibkrApi.getAccountSummary(accountList[0].id)
         console.log("Account summary received:", summary)
         setAccountSummary(summary)
 
@@ -0,0 +1,28 @@
+import { useEffect, useState } from "react"
+import { getCarryCosts, type CarryCosts } from "../../../shared/api/carryCosts"
+
+export function useCarryCosts(account: string | undefined) {
+  const [carryCosts, setCarryCosts] = useState<CarryCosts | null>(null)
+
+  useEffect(() => {
+    if (!account) {
+      setCarryCosts(null)
+      return
+    }
+
+    let cancelled = false
+    getCarryCosts(account)
+      .then((costs) => {
+        if (!cancelled) setCarryCosts(costs)
+      })
+      .catch((err) => {
+        console.error("Failed to load carry costs:", err)
+      })
+
+    return () => {
+      cancelled = true
+    }
+  }, [account])
+
+  return carryCosts
+}
@@ -1,3 +1,5 @@
+import type { AccountInfo } from "../../shared/types"
+
 export const formatCurrency = (value: number) => {
   // For values over 10k, show as "10.5k", etc
   if (Math.abs(value) >= 10000) {
@@ -15,6 +17,10 @@ export const formatPercent = (value: number) => {
   return `${value >= 0 ? "+" : ""}${value.toFixed(2)}%`
 }
 
+/** The alias when set, else the masked id. */
+export const accountLabel = (account: AccountInfo) =>
+  account.alias ?? anonymizeAccountNumber(account.id)
+
 export const anonymizeAccountNumber = (accountNumber: string) => {
   if (!accountNumber || accountNumber.length <= 2) {
     return accountNumber
@@ -57,11 +57,11 @@ export function ScannerFilters({ isRunning, onStart, onStop }: ScannerFiltersPro
   const [marketCapAbove, setMarketCapAbove] = useState<string>("")
   const [marketCapBelow, setMarketCapBelow] = useState<string>("")
 
-  const parseOptional = (s: string): number | undefined => {
+  const parseOptional = (s: string): number | null => {
     const trimmed = s.trim()
-    if (!trimmed) return undefined
+    if (!trimmed) return null
     const n = Number(trimmed)
-    return Number.isFinite(n) ? n : undefined
+    return Number.isFinite(n) ? n : null
   }
 
   const handleStart = () => {
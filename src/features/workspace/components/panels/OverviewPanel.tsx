@@ -3,7 +3,9 @@ import { Skeleton } from "../../../../shared/components/ui/skeleton"
 import { Alert, AlertDescription } from "../../../../shared/components/ui/alert"
 import { AlertCircle } from "lucide-react"
 import { TickerCards } from "../../../analysis/components/TickerCards"
+import { InsiderActivityCard } from "../../../analysis/components/InsiderActivityCard"
 import { ProjectionView } from "../../../analysis/components/ProjectionView"
+import { RecentFilingsCard } from "../../../analysis/components/RecentFilingsCard"
 import { SentimentWidget } from "../../../sentiment/components/SentimentWidget"
 import { useProjections } from "../../../analysis/hooks/useProjections"
 import { useQuote } from "../../../analysis/hooks/useQuote"
@@ -15,6 +17,7 @@ export function OverviewPanel() {
   const {
     results,
     fundamentalData,
+    valuation,
     loading: projectionsLoading,
     error: projectionsError,
   } = useProjections(symbol)
@@ -48,6 +51,8 @@ export function OverviewPanel() {
     <div className="space-y-6">
       <TickerCards ticker={ticker} quote={quote} quoteError={quoteError} />
       <SentimentWidget symbol={symbol} />
+      <InsiderActivityCard symbol={symbol} />
+      <RecentFilingsCard symbol={symbol} />
       {projectionsLoading ? (
         <Card className="border-border bg-card/50">
           <CardContent className="pt-6">
@@ -62,7 +67,7 @@ export function OverviewPanel() {
           </AlertDescription>
         </Alert>
       ) : results ? (
-        <ProjectionView results={results} symbol={symbol} />
+        <ProjectionView results={results} symbol={symbol} valuation={valuation} />
       ) : null}
     </div>
   )
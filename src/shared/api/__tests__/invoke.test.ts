@@ -0,0 +1,35 @@
+import { describe, expect, it, vi, beforeEach } from "vitest"
+
+const { invokeMock } = vi.hoisted(() => ({ invokeMock: vi.fn() }))
+vi.mock("@tauri-apps/api/core", () => ({
+  invoke: invokeMock,
+}))
+
+import { CommandError, invoke } from "../invoke"
+
+describe("invoke", () => {
+  beforeEach(() => invokeMock.mockReset())
+
+  it("rethrows field errors as a CommandError", async () => {
+    invokeMock.mockRejectedValueOnce({
+      kind: "invalid",
+      fields: [{ field: "order.quantity", message: "must be greater than 0, got 0" }],
+    })
+    const err = await invoke("ibkr_place_order", {}).catch((e: unknown) => e)
+    expect(err).toBeInstanceOf(CommandError)
+    expect((err as CommandError).fieldError("order.quantity")).toBe(
+      "must be greater than 0, got 0",
+    )
+    expect((err as CommandError).message).toBe(
+      "invalid input: order.quantity: must be greater than 0, got 0",
+    )
+  })
+
+  it("keeps failures and plain strings readable", async () => {
+    invokeMock.mockRejectedValueOnce({ kind: "failed", message: "not connected" })
+    await expect(invoke("get_margin_history")).rejects.toThrow("not connected")
+
+    invokeMock.mockRejectedValueOnce("no IBKR accounts available")
+    await expect(invoke("tca_get_attribution")).rejects.toBe("no IBKR accounts available")
+  })
+})
@@ -0,0 +1,163 @@
+import type { OrderRequest } from "../types"
+import { invoke, type Page } from "./invoke"
+import type { SimSide } from "./paperTrading"
+
+// Mirrors `services::automation`. Conditions and actions are tagged by
+// `kind` and keep the Rust field names. Times are unix seconds. The
+// `rule-triggered` event payload keeps the enum variant's snake_case
+// field names.
+
+export type CrossDirection = "above" | "below"
+
+/** Indicators are on daily closes, today counting at the current price. */
+export type RuleCondition =
+  | { kind: "price"; direction: CrossDirection; level: number }
+  | { kind: "sma"; direction: CrossDirection; period: number }
+  | { kind: "rsi"; direction: CrossDirection; period: number; level: number }
+  /** Above the bull value or below the bear value. */
+  | { kind: "fair_value"; direction: CrossDirection }
+  /** Drawdown from the high-water mark, in percent, rises above `level`. */
+  | { kind: "portfolio_drawdown"; level: number }
+
+export type RuleAction =
+  | { kind: "notify" }
+  /** Market order on the paper-trading simulator. */
+  | { kind: "dry_run_order"; side: SimSide; quantity: number }
+  /**
+   * IBKR limit order ticket, within the `automation` risk limits. Nothing
+   * is sent until the trader confirms it.
+   */
+  | { kind: "live_order"; side: SimSide; quantity: number }
+
+export type RuleOutcome =
+  | "idle"
+  | "suppressed"
+  | "notified"
+  | "dry_run"
+  /** A live order ticket awaits the trader. */
+  | "ticketed"
+  /** The trader confirmed the ticket and IBKR took the order. */
+  | "placed"
+  | "dismissed"
+  | "blocked"
+  | "failed"
+
+export interface RuleInput {
+  name: string
+  /** Empty for a portfolio drawdown rule, which can only notify. */
+  symbol: string
+  condition: RuleCondition
+  action: RuleAction
+  /** Defaults to true. */
+  enabled?: boolean
+  /** Defaults to 60. */
+  cooldownMinutes?: number
+}
+
+export interface AutomationRule {
+  id: number
+  name: string
+  symbol: string
+  condition: RuleCondition
+  action: RuleAction
+  enabled: boolean
+  cooldownMinutes: number
+  /** The condition at the last evaluation; null before the first. */
+  lastMatched: boolean | null
+  lastFiredAt: number | null
+  createdAt: number
+  updatedAt: number
+}
+
+export interface RuleEvaluation {
+  id: number
+  ruleId: number
+  ruleName: string
+  symbol: string
+  /** The action's `kind` at the time. */
+  action: RuleAction["kind"]
+  evaluatedAt: number
+  price: number | null
+  /** What the condition compared: the price, the RSI or the drawdown. */
+  value: number | null
+  threshold: number | null
+  /** Null when the data to decide was missing. */
+  matched: boolean | null
+  fired: boolean
+  outcome: RuleOutcome
+  detail: string | null
+  /** Sim order id for a dry run, IBKR order id for a confirmed ticket. */
+  orderId: number | null
+  /** The order a `live_order` rule posted, for the trader to confirm. */
+  ticket: OrderRequest | null
+  /** When the ticket was confirmed or dismissed. */
+  resolvedAt: number | null
+}
+
+export interface RuleRunReport {
+  evaluated: number
+  fired: RuleEvaluation[]
+  /** Outside the regular session nothing is evaluated. */
+  marketClosed: boolean
+}
+
+export interface RuleTriggeredPayload {
+  rule_id: number
+  name: string
+  symbol: string
+  action: RuleAction["kind"]
+  outcome: RuleOutcome
+  detail?: string | null
+}
+
+export async function listAutomationRules(): Promise<AutomationRule[]> {
+  return await invoke("list_automation_rules")
+}
+
+export async function createAutomationRule(rule: RuleInput): Promise<AutomationRule> {
+  return await invoke("create_automation_rule", { rule })
+}
+
+/** The rule waits for a fresh crossing. */
+export async function updateAutomationRule(id: number, rule: RuleInput): Promise<AutomationRule> {
+  return await invoke("update_automation_rule", { id, rule })
+}
+
+export async function setAutomationRuleEnabled(
+  id: number,
+  enabled: boolean,
+): Promise<AutomationRule> {
+  return await invoke("set_automation_rule_enabled", { id, enabled })
+}
+
+/** The rule's audit rows are kept. */
+export async function deleteAutomationRule(id: number): Promise<boolean> {
+  return await invoke("delete_automation_rule", { id })
+}
+
+/** The audit log, newest first. */
+export async function listRuleEvaluations(
+  ruleId?: number,
+  firedOnly = false,
+  page: Page = {},
+): Promise<RuleEvaluation[]> {
+  return await invoke("list_rule_evaluations", { ruleId, firedOnly, ...page })
+}
+
+export async function runAutomationRules(): Promise<RuleRunReport> {
+  return await invoke("run_automation_rules")
+}
+
+/** Order tickets posted today (ET) that await the trader, newest first. */
+export async function listRuleTickets(): Promise<RuleEvaluation[]> {
+  return await invoke("list_rule_tickets")
+}
+
+/** Sends the ticket's order through the same checks as a manual order. */
+export async function confirmRuleTicket(evaluationId: number): Promise<RuleEvaluation> {
+  return await invoke("confirm_rule_ticket", { evaluationId })
+}
+
+export async function dismissRuleTicket(evaluationId: number): Promise<RuleEvaluation> {
+  return await invoke("dismiss_rule_ticket", { evaluationId })
+}
@@ -0,0 +1,70 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::benchmarks`. Returns are close to close, in
+// percent; relative returns are in percentage points (-4 is four points
+// behind the benchmark). Windows are in sessions. A holding in another
+// currency than `fx.base_currency` splits its return into the local
+// price move and the currency move.
+
+export interface BenchmarkTag {
+  symbol: string
+  benchmark: string
+  updatedAt: number
+}
+
+/** Null fields mean there wasn't the history for the window. */
+export interface RelativeWindow {
+  sessions: number
+  /** In the symbol's own currency. */
+  returnPct: number | null
+  /** Null for a base-currency row. */
+  fxReturnPct: number | null
+  /** `returnPct` and `fxReturnPct` compounded. */
+  baseReturnPct: number | null
+  benchmarkReturnPct: number | null
+  /** `baseReturnPct` less `benchmarkReturnPct`. */
+  relativePct: number | null
+}
+
+export interface BenchmarkRow {
+  symbol: string
+  benchmark: string
+  /** False when the benchmark is `benchmarks.default_benchmark`. */
+  tagged: boolean
+  held: boolean
+  watchlist: boolean
+  /** A held position's currency when it isn't the base currency. */
+  currency: string | null
+  /** Shortest window first. */
+  windows: RelativeWindow[]
+  /** Over the rank window. */
+  relativePct: number | null
+  lagging: boolean
+  error: string | null
+}
+
+export interface BenchmarkReport {
+  account: string
+  at: number
+  baseCurrency: string
+  rankWindow: number
+  /** Worst relative return first. */
+  rows: BenchmarkRow[]
+}
+
+export async function listBenchmarkTags(): Promise<BenchmarkTag[]> {
+  return await invoke("list_benchmark_tags")
+}
+
+export async function setBenchmarkTag(symbol: string, benchmark: string): Promise<BenchmarkTag> {
+  return await invoke("set_benchmark_tag", { symbol, benchmark })
+}
+
+export async function clearBenchmarkTag(symbol: string): Promise<boolean> {
+  return await invoke("clear_benchmark_tag", { symbol })
+}
+
+/** `account` defaults to the current one. */
+export async function getBenchmarkReport(account?: string): Promise<BenchmarkReport> {
+  return await invoke("get_benchmark_report", { account })
+}
@@ -0,0 +1,27 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::carry_costs`. Estimates in the account's base
+// currency; rates are annual percentages from `carry_costs` settings.
+
+export interface ShortBorrowCost {
+  symbol: string
+  quantity: number
+  marketValue: number
+  feePct: number
+  dailyFee: number
+}
+
+export interface CarryCosts {
+  account: string
+  currency: string
+  debitBalance: number
+  marginRatePct: number
+  marginInterestDaily: number
+  shorts: ShortBorrowCost[]
+  borrowFeeDaily: number
+  totalDaily: number
+}
+
+export async function getCarryCosts(account?: string): Promise<CarryCosts> {
+  return await invoke("ibkr_get_carry_costs", { account: account ?? null })
+}
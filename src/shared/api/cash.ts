@@ -0,0 +1,60 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::cash_management`. Amounts are in each row's
+// currency; `*Base` / account totals are in the account's base
+// currency. Sweeps are suggestions only. The `cash-warning` event
+// payload is a `CashWarning`.
+
+export interface CurrencyCash {
+  currency: string
+  cash: number
+  pendingBuys: number
+  /** Reported, not credited to `projectedCash`. */
+  pendingSells: number
+  projectedCash: number
+}
+
+export interface AccountCash {
+  account: string
+  baseCurrency: string
+  balances: CurrencyCash[]
+  totalCash: number
+  buyingPower: number | null
+  pendingBuysBase: number
+  projectedCash: number
+  projectedBuyingPower: number | null
+}
+
+export interface CashSweep {
+  account: string
+  fromCurrency: string
+  toCurrency: string
+  /** In `toCurrency`. */
+  amount: number
+  fromAmount: number
+}
+
+export type CashWarningKind = "negative_cash" | "margin_borrow" | "buying_power_exceeded"
+
+export interface CashWarning {
+  account: string
+  currency: string
+  kind: CashWarningKind
+  amount: number
+  message: string
+  /** Set on event payloads when the account has an alias. */
+  account_alias?: string
+}
+
+export interface CashOverview {
+  /** Totals across all accounts. */
+  currencies: CurrencyCash[]
+  accounts: AccountCash[]
+  sweeps: CashSweep[]
+  warnings: CashWarning[]
+  notes: string[]
+}
+
+export async function cashOverview(): Promise<CashOverview> {
+  return await invoke("cash_overview")
+}
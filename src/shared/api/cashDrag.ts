@@ -0,0 +1,51 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::cash_drag`. Amounts are in the account's base
+// currency; percentages are of NAV. The `idle-cash` event payload keeps
+// the enum variant's snake_case field names.
+
+export interface CashPoint {
+  account: string
+  /** ET `YYYY-MM-DD`. */
+  date: string
+  cash: number
+  netLiquidation: number
+  sampledAt: number
+}
+
+export interface IdleCashSuggestion {
+  symbol: string
+  price: number
+  buyBelow: number
+  fairValue: number
+  /** Below `buyBelow`, percent. */
+  discountPct: number
+}
+
+export interface CashDragReport {
+  account: string
+  thresholdPct: number
+  current: CashPoint
+  cashPct: number
+  /** Cash above the threshold; 0 at or below it. */
+  idleCash: number
+  /** Daily samples, oldest first. */
+  history: CashPoint[]
+  averageCashPct: number | null
+  /** Empty unless cash is above the threshold. Deepest discount first. */
+  suggestions: IdleCashSuggestion[]
+}
+
+export interface IdleCashPayload {
+  account: string
+  cash_pct: number
+  threshold_pct: number
+  idle_cash: number
+  suggestions: string[]
+  account_alias?: string
+}
+
+/** `account` defaults to the current one, `days` to a year. */
+export async function getCashDrag(account?: string, days?: number): Promise<CashDragReport> {
+  return await invoke("get_cash_drag", { account, days })
+}
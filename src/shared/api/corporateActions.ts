@@ -0,0 +1,59 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::corporate_actions`. Actions are tagged by `kind`
+// and keep the Rust snake_case field names, in commands and in the
+// `corporate-action-applied` event payload alike. Dates are ISO
+// `YYYY-MM-DD`, the first session after the action.
+
+export type CorporateAction =
+  | {
+      kind: "split"
+      symbol: string
+      effective_date: string
+      /** New shares per old share; below 1 for a reverse split. */
+      ratio: number
+    }
+  | { kind: "symbol_change"; from: string; to: string; effective_date: string }
+  | {
+      kind: "spin_off"
+      symbol: string
+      new_symbol: string
+      effective_date: string
+      /** Shares of `new_symbol` per parent share. */
+      ratio: number
+      /** Share of the parent's basis the new shares carry, 0 to 1. */
+      cost_fraction: number
+    }
+
+export type ActionSource = "provider" | "ibkr" | "manual"
+
+export interface TableAdjustment {
+  /** `workspaces.watchlist` counts workspaces, not rows. */
+  table: string
+  rows: number
+}
+
+export interface AppliedAction {
+  id: number
+  action: CorporateAction
+  source: ActionSource
+  adjusted: TableAdjustment[]
+  appliedAt: number
+}
+
+export interface CorporateActionAppliedPayload {
+  action: CorporateAction
+  source: ActionSource
+  adjusted: TableAdjustment[]
+  account_alias?: string
+}
+
+/** Newest first; `limit` defaults to 50. */
+export async function listCorporateActions(limit?: number): Promise<AppliedAction[]> {
+  return await invoke("list_corporate_actions", { limit })
+}
+
+/** Rejected when the same kind, symbol and date is already applied. */
+export async function applyCorporateAction(action: CorporateAction): Promise<AppliedAction> {
+  return await invoke("apply_corporate_action", { action })
+}
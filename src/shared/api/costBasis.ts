@@ -0,0 +1,41 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::cost_basis`. The local book is imported lots plus
+// every stored fill since the import (all fills when nothing was
+// imported), matched FIFO. Average costs are per unit with the
+// multiplier and commissions in, like IBKR's `average_cost`.
+
+export type DiscrepancyKind =
+  | "missing_locally"
+  | "missing_at_broker"
+  | "quantity"
+  | "average_cost"
+
+export interface Discrepancy {
+  /** Ticker for stock; `SYMBOL YYYYMMDD STRIKE RIGHT` for an option. */
+  contract: string
+  kind: DiscrepancyKind
+  brokerQuantity: number
+  localQuantity: number
+  brokerAverageCost: number | null
+  /** `null` when flat or when an imported lot had no basis. */
+  localAverageCost: number | null
+}
+
+export interface Reconciliation {
+  account: string
+  /** Unix seconds. */
+  asOf: number
+  /** Import time (unix seconds) of the lots the book starts from. */
+  baselineAt: number | null
+  importedLots: number
+  fills: number
+  /** ISO timestamp of the oldest fill applied. */
+  firstFillAt: string | null
+  matched: number
+  discrepancies: Discrepancy[]
+}
+
+export async function reconcileCostBasis(account?: string): Promise<Reconciliation> {
+  return await invoke("reconcile_cost_basis", { account })
+}
@@ -0,0 +1,37 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::drawdown`. Drawdowns are positive percentages
+// below the peak; dates are ET `YYYY-MM-DD`.
+
+export interface DrawdownPoint {
+  date: string
+  nlv: number
+  /** Below the high-water mark up to this point. */
+  drawdownPct: number
+  /** Worst fall within the window ending at this point. */
+  rollingMaxDrawdownPct: number
+}
+
+export interface DrawdownStats {
+  account: string
+  at: number
+  windowSessions: number
+  /** Today's point is a live NLV rather than the stored snapshot. */
+  live: boolean
+  /** Null without any history. */
+  nlv: number | null
+  highWaterMark: number | null
+  highWaterDate: string | null
+  currentDrawdownPct: number
+  rollingMaxDrawdownPct: number
+  maxDrawdownPct: number
+  maxDrawdownPeakDate: string | null
+  maxDrawdownTroughDate: string | null
+  /** Oldest first. */
+  series: DrawdownPoint[]
+}
+
+/** `account` defaults to the current one, `window` to `drawdown.window_sessions`. */
+export async function getDrawdownStats(account?: string, window?: number): Promise<DrawdownStats> {
+  return await invoke("get_drawdown_stats", { account, window })
+}
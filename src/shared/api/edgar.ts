@@ -0,0 +1,29 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::edgar_service`. 10-K, 10-Q and 8-K filings (with
+// their `/A` amendments), newest first; `url` opens the primary
+// document and `indexUrl` the filing index with its exhibits.
+
+export interface Filing {
+  form: string
+  /** YYYY-MM-DD. */
+  filingDate: string
+  /** Period covered, or the event date of an 8-K. */
+  reportDate: string | null
+  accessionNumber: string
+  description: string | null
+  url: string
+  indexUrl: string
+}
+
+export interface RecentFilings {
+  symbol: string
+  /** Ten digits, zero-padded. */
+  cik: string
+  company: string | null
+  filings: Filing[]
+}
+
+export async function getRecentFilings(symbol: string): Promise<RecentFilings> {
+  return await invoke("get_recent_filings", { symbol })
+}
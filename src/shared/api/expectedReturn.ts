@@ -0,0 +1,37 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::expected_return`. Returns and CAGRs are annual
+// percentages; weights are 0..1 of the projected market value.
+
+export interface ReturnContribution {
+  symbol: string
+  marketValue: number
+  weight: number
+  /** Base-case share-price CAGR of the newest stored projection. */
+  baseCagrPct: number
+  /** `weight × baseCagrPct`, in points of the expected return. */
+  contributionPct: number
+  projectedAt: number
+  snapshotId: number
+}
+
+export interface ExpectedPortfolioReturn {
+  account: string
+  at: number
+  /** Null when no holding has a projection. */
+  expectedReturnPct: number | null
+  /** Largest contribution first. */
+  contributions: ReturnContribution[]
+  totalValue: number
+  projectedValue: number
+  coverage: number
+  /** Long stock positions without a stored projection. */
+  unprojected: string[]
+}
+
+/** `account` defaults to the current one. */
+export async function getExpectedPortfolioReturn(
+  account?: string,
+): Promise<ExpectedPortfolioReturn> {
+  return await invoke("get_expected_portfolio_return", { account })
+}
@@ -0,0 +1,49 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::factor_exposure`. A factor is an ETF's daily
+// return, or the spread between two (long less short).
+
+export interface FactorProxy {
+  name: string
+  long: string
+  short: string | null
+}
+
+export interface FactorLoading {
+  factor: string
+  beta: number
+}
+
+export interface PositionFactors {
+  symbol: string
+  marketValue: number
+  /** In `factors` order. */
+  loadings: FactorLoading[]
+  /** Mean daily return the factors don't explain. */
+  alpha: number
+  rSquared: number
+  observations: number
+}
+
+export interface PortfolioFactor {
+  factor: string
+  /** Market-value-weighted over the measured gross. */
+  beta: number
+  dollars: number
+}
+
+export interface FactorExposureReport {
+  account: string
+  at: number
+  factors: FactorProxy[]
+  portfolio: PortfolioFactor[]
+  positions: PositionFactors[]
+  measuredGross: number
+  /** Options, and stocks without enough history. */
+  unmeasured: string[]
+}
+
+/** `account` defaults to the current one. */
+export async function getFactorExposure(account?: string): Promise<FactorExposureReport> {
+  return await invoke("get_factor_exposure", { account })
+}
@@ -0,0 +1,45 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::fair_value_watch`. Flags are camelCase (serde
+// `rename_all`); the `fair-value-crossed` event payload keeps the
+// enum variant's snake_case field names.
+
+export type FairValueZone = "below_bear" | "within" | "above_bull"
+
+export interface FairValueFlag {
+  symbol: string
+  zone: FairValueZone
+  price: number
+  bearValue: number
+  baseLow: number
+  baseHigh: number
+  bullValue: number
+  /** `projection_snapshots.id` the band was read from. */
+  snapshotId: number
+  /** Unix seconds. */
+  evaluatedAt: number
+  /** Unix seconds the zone last changed. */
+  changedAt: number
+}
+
+export interface FairValueReport {
+  evaluated: number
+  crossings: FairValueFlag[]
+  skipped: string[]
+}
+
+export interface FairValueCrossedPayload {
+  symbol: string
+  zone: FairValueZone
+  price: number
+  bear_value: number
+  bull_value: number
+}
+
+export async function fairValueListFlags(): Promise<FairValueFlag[]> {
+  return await invoke("fair_value_list_flags")
+}
+
+export async function fairValueRunNow(): Promise<FairValueReport> {
+  return await invoke("fair_value_run_now")
+}
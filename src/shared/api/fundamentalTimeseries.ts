@@ -0,0 +1,36 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::financial_data_service::timeseries`. Every series
+// is oldest first; margins are percent, EPS is split-adjusted, and a
+// TTM point sums the four consecutive quarters ending on its date.
+
+export type FundamentalMetric =
+  | "revenue"
+  | "gross_margin"
+  | "operating_margin"
+  | "net_margin"
+  | "eps"
+  | "free_cash_flow"
+
+export interface FundamentalPoint {
+  /** Fiscal period end, `YYYY-MM-DD`. */
+  periodEnd: string
+  value: number
+}
+
+export interface FundamentalTimeseries {
+  symbol: string
+  metric: FundamentalMetric
+  /** Reporting currency; null for margins and EPS. */
+  currency: string | null
+  annual: FundamentalPoint[]
+  quarterly: FundamentalPoint[]
+  ttm: FundamentalPoint[]
+}
+
+export async function getFundamentalTimeseries(
+  symbol: string,
+  metric: FundamentalMetric
+): Promise<FundamentalTimeseries> {
+  return await invoke("get_fundamental_timeseries", { symbol, metric })
+}
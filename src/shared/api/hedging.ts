@@ -0,0 +1,58 @@
+import { invoke } from "@tauri-apps/api/core"
+import type { OrderRequest } from "../types"
+
+// Mirrors `services::hedging` and `services::portfolio_risk::beta`.
+// Dollar amounts are SPY-dollars (market value × beta). Suggestions
+// only: tickets are placed, after review, through `ibkr_place_order`.
+
+export interface HedgeRequest {
+  /** Percent of today's beta-weighted exposure to keep; default 0. */
+  targetExposurePct?: number
+  /** Default 45. */
+  putDte?: number
+  /** Default 5. */
+  putOtmPct?: number
+  account?: string
+}
+
+export interface PositionBeta {
+  symbol: string
+  marketValue: number
+  beta: number
+  betaDollars: number
+}
+
+export interface BetaExposure {
+  benchmark: string
+  positions: PositionBeta[]
+  netMarketValue: number
+  betaWeighted: number
+  /** Options, and stocks without enough history. */
+  unmeasured: string[]
+}
+
+export type HedgeKind = "spy_puts" | "short_spy" | "micro_futures"
+
+export interface HedgeSuggestion {
+  kind: HedgeKind
+  quantity: number
+  hedgedDollars: number
+  residualExposure: number
+  cost: number
+  /** `null` for futures, which aren't on the order form. */
+  order: OrderRequest | null
+  note: string
+}
+
+export interface HedgePlan {
+  account: string
+  benchmarkPrice: number
+  exposure: BetaExposure
+  targetExposure: number
+  hedgeDollars: number
+  suggestions: HedgeSuggestion[]
+}
+
+export async function suggestHedge(request: HedgeRequest): Promise<HedgePlan> {
+  return await invoke("ibkr_suggest_hedge", { request })
+}
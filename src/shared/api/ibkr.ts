@@ -1,13 +1,20 @@
-import { invoke } from "@tauri-apps/api/core"
+import { invoke, type Page } from "./invoke"
 import type {
   ConnectionConfig,
   ConnectionStatus,
+  AccountInfo,
   AccountSummary,
   Position,
   OrderRequest,
+  ComboOrderRequest,
+  ComboKind,
+  ContractRoute,
+  SizeRules,
   FundamentalData,
   ProjectionAssumptions,
   ProjectionResultsWithFundamentals,
+  ProjectionHistoryEntry,
+  PortfolioAnalysis,
   ScenarioProjectionsWithFundamentals,
   ScannerSubscription,
   Quote,
@@ -54,15 +61,21 @@ export const ibkrApi = {
   },
 
   getAccounts: async () => {
-    return invoke<string[]>("ibkr_get_accounts")
+    return invoke<AccountInfo[]>("ibkr_get_accounts")
+  },
+
+  /** Set an account alias; `null` or blank clears it. */
+  setAccountAlias: async (account: string, alias: string | null) => {
+    return invoke<{ aliases: Record<string, string> }>("account_set_alias", { account, alias })
   },
 
   getAccountSummary: async (account: string) => {
     return invoke<AccountSummary[]>("ibkr_get_account_summary", { account })
   },
 
-  getPositions: async () => {
-    return invoke<Position[]>("ibkr_get_positions")
+  /** No `account` is the first managed account; `symbol` narrows to one ticker. */
+  getPositions: async (filter: { account?: string; symbol?: string } & Page = {}) => {
+    return invoke<Position[]>("ibkr_get_positions", filter)
   },
 
   startDailyPnL: async (account: string) => {
@@ -77,16 +90,32 @@ export const ibkrApi = {
     return invoke("ibkr_subscribe_market_data", { symbol })
   },
 
-  placeOrder: async (order: OrderRequest) => {
-    return invoke("ibkr_place_order", { order })
+  /**
+   * Pass the same `idempotencyKey` on a retry to get the first order id
+   * back instead of a second order.
+   */
+  placeOrder: async (order: OrderRequest, idempotencyKey?: string) => {
+    return invoke<number>("ibkr_place_order", { order, idempotencyKey })
+  },
+
+  placeComboOrder: async (order: ComboOrderRequest, idempotencyKey?: string) => {
+    return invoke<number>("ibkr_place_combo_order", { order, idempotencyKey })
+  },
+
+  validateComboOrder: async (order: ComboOrderRequest) => {
+    return invoke<ComboKind>("ibkr_validate_combo_order", { order })
+  },
+
+  getSizeRules: async (symbol: string, route?: ContractRoute) => {
+    return invoke<SizeRules>("ibkr_get_size_rules", { symbol, route })
   },
 
   getFundamentalData: async (symbol: string) => {
     return invoke<FundamentalData>("ibkr_get_fundamental_data", { symbol })
   },
 
-  getQuote: async (symbol: string) => {
-    return invoke<Quote>("ibkr_get_quote", { symbol })
+  getQuote: async (symbol: string, route?: ContractRoute) => {
+    return invoke<Quote>("ibkr_get_quote", { symbol, route })
   },
 
   getDataTier: async () => {
@@ -102,14 +131,36 @@ export const ibkrApi = {
 
   /** Returns projection results bundled with the fundamentals they were
    *  computed from, so callers don't need a parallel `getFundamentalData`
-   *  fetch (which doubled the daily AV quota burn). */
-  generateProjectionResults: async (symbol: string, assumptions?: ProjectionAssumptions) => {
+   *  fetch (which doubled the daily AV quota burn). `template` picks a
+   *  projection template by name and remembers it for the ticker;
+   *  `includeTrace` adds the per-year calculation trace. */
+  generateProjectionResults: async (
+    symbol: string,
+    assumptions?: ProjectionAssumptions,
+    template?: string,
+    includeTrace?: boolean,
+  ) => {
     return invoke<ProjectionResultsWithFundamentals>("ibkr_generate_projection_results", {
       symbol,
       assumptions,
+      template,
+      includeTrace,
     })
   },
 
+  /** Stored projection runs for `symbol`, oldest first, each diffed
+   *  against the one before it. */
+  getProjectionHistory: async (symbol: string, limit?: number) => {
+    return invoke<ProjectionHistoryEntry[]>("get_projection_history", { symbol, limit })
+  },
+
+  /** Project every long stock position and weight the scenario CAGRs
+   *  by market value. Fundamentals go through the normal provider
+   *  chain, so a repeat run is served from cache. */
+  analyzePortfolio: async (account?: string, assumptions?: ProjectionAssumptions) => {
+    return invoke<PortfolioAnalysis>("analyze_portfolio", { account, assumptions })
+  },
+
   getCachedTickers: async () => {
     return invoke<string[]>("ibkr_get_cached_tickers")
   },
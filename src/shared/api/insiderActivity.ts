@@ -0,0 +1,46 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::insider_activity`. Windows net open-market trades in
+// common stock only; grants, vesting and option exercises are listed
+// with `openMarket: false` and left out of the totals.
+
+export type InsiderSide = "buy" | "sell"
+
+export interface InsiderTransaction {
+  /** YYYY-MM-DD. */
+  date: string
+  insider: string
+  title: string | null
+  securityType: string
+  side: InsiderSide
+  shares: number
+  /** Per share; 0 for grants, vesting and gifts. */
+  price: number
+  openMarket: boolean
+}
+
+export interface InsiderWindow {
+  months: number
+  buyShares: number
+  sellShares: number
+  netShares: number
+  buyValue: number
+  sellValue: number
+  netValue: number
+  buyers: number
+  sellers: number
+  transactions: number
+}
+
+export interface InsiderActivity {
+  symbol: string
+  asOf: string
+  /** 3, 6 and 12 months. */
+  windows: InsiderWindow[]
+  /** Last 12 months, newest first. */
+  transactions: InsiderTransaction[]
+}
+
+export async function getInsiderActivity(symbol: string): Promise<InsiderActivity> {
+  return await invoke("get_insider_activity", { symbol })
+}
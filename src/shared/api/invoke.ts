@@ -0,0 +1,58 @@
+import { invoke as tauriInvoke, type InvokeArgs } from "@tauri-apps/api/core"
+
+// Mirrors `middleware::validation`. Validated commands reject with a
+// tagged `CommandError` instead of a string; `invoke` rethrows it as an
+// `Error` so callers showing `err.message` keep working, and forms can
+// pin `fields` to their inputs. String rejections pass through as-is.
+
+export interface FieldError {
+  /** Argument name as passed to `invoke` (`dateFrom`, `order.quantity`). */
+  field: string
+  message: string
+}
+
+type WireCommandError =
+  | { kind: "invalid"; fields: FieldError[] }
+  | { kind: "failed"; message: string }
+
+export class CommandError extends Error {
+  readonly fields: FieldError[]
+
+  constructor(message: string, fields: FieldError[] = []) {
+    super(message)
+    this.name = "CommandError"
+    this.fields = fields
+  }
+
+  /** The message for `field`, when it was rejected. */
+  fieldError(field: string): string | undefined {
+    return this.fields.find((f) => f.field === field)?.message
+  }
+}
+
+/** Mirrors `utils::page::Page`: `limit` rows after skipping `offset`. */
+export interface Page {
+  limit?: number
+  offset?: number
+}
+
+function isWireCommandError(err: unknown): err is WireCommandError {
+  if (typeof err !== "object" || err === null || !("kind" in err)) return false
+  return err.kind === "invalid" || err.kind === "failed"
+}
+
+export function toCommandError(err: unknown): unknown {
+  if (!isWireCommandError(err)) return err
+  if (err.kind === "failed") return new CommandError(err.message)
+  const summary = err.fields.map((f) => `${f.field}: ${f.message}`).join("; ")
+  return new CommandError(`invalid input: ${summary}`, err.fields)
+}
+
+/** `invoke` from `@tauri-apps/api/core`, with `CommandError` rejections. */
+export async function invoke<T>(cmd: string, args?: InvokeArgs): Promise<T> {
+  try {
+    return await tauriInvoke<T>(cmd, args)
+  } catch (err) {
+    throw toCommandError(err)
+  }
+}
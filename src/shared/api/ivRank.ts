@@ -0,0 +1,20 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::iv_rank`. IVs are annualized fractions (0.32 =
+// 32%); rank and percentile are 0–100 over the trailing 252 days.
+
+export interface IvRank {
+  symbol: string
+  currentIv: number
+  lowIv: number
+  highIv: number
+  ivRank: number
+  ivPercentile: number
+  observations: number
+  /** Bar time of `currentIv`. */
+  asOf: string
+}
+
+export async function getIvRank(symbol: string): Promise<IvRank> {
+  return await invoke("get_iv_rank", { symbol })
+}
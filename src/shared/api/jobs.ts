@@ -0,0 +1,49 @@
+import { invoke } from "@tauri-apps/api/core"
+import type { ProjectionAssumptions } from "../types"
+
+// Mirrors `services::jobs`. Progress arrives as `job-progress` events
+// (payload `{ job: JobInfo }`, never carrying `result`); fetch the
+// result with `getJob` once the status is terminal.
+
+export type JobStatus = "running" | "completed" | "failed" | "cancelled"
+
+export interface JobInfo {
+  id: number
+  kind: string
+  status: JobStatus
+  done: number
+  /** 0 until the worker knows how many steps it has. */
+  total: number
+  step?: string
+  /** Unix seconds. */
+  startedAt: number
+  finishedAt?: number
+  error?: string
+  /** Partial for cancelled jobs. */
+  result?: unknown
+}
+
+export interface JobProgressPayload {
+  job: JobInfo
+}
+
+export async function listJobs(): Promise<JobInfo[]> {
+  return await invoke("list_jobs")
+}
+
+export async function getJob(id: number): Promise<JobInfo> {
+  return await invoke("get_job", { id })
+}
+
+/** `false` when the job is unknown or already finished. */
+export async function cancelJob(id: number): Promise<boolean> {
+  return await invoke("cancel_job", { id })
+}
+
+/** Background `analyze_portfolio`; the result is a `PortfolioAnalysis`. */
+export async function startPortfolioAnalysisJob(
+  account?: string,
+  assumptions?: ProjectionAssumptions,
+): Promise<number> {
+  return await invoke("start_portfolio_analysis_job", { account, assumptions })
+}
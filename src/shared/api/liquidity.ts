@@ -0,0 +1,37 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::liquidity`. Days to liquidate assume selling
+// `participationPct` percent of the average daily volume a day.
+
+export interface PositionLiquidity {
+  symbol: string
+  /** Negative when short. */
+  shares: number
+  marketValue: number
+  /** Average daily volume, in shares. */
+  adv: number
+  advDollars: number
+  /** The position as a percentage of one day's volume. */
+  pctOfAdv: number
+  daysToLiquidate: number
+  /** Slower to exit than `maxDays`. */
+  illiquid: boolean
+}
+
+export interface LiquidityReport {
+  account: string
+  at: number
+  participationPct: number
+  maxDays: number
+  /** Slowest to exit first. */
+  positions: PositionLiquidity[]
+  /** Symbols of the illiquid positions, slowest first. */
+  illiquid: string[]
+  /** Options, and stocks without enough volume history. */
+  unmeasured: string[]
+}
+
+/** `account` defaults to the current one. */
+export async function getLiquidity(account?: string): Promise<LiquidityReport> {
+  return await invoke("get_liquidity", { account })
+}
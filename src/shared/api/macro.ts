@@ -0,0 +1,30 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::macro_service`. Rates and spreads are in percent
+// (4.33 = 4.33%); `cpiYoy` is the year-over-year CPI change. A series
+// that couldn't be read is null and listed in `notes`.
+
+export interface MacroReading {
+  value: number
+  /** YYYY-MM-DD of the observation. */
+  asOf: string
+}
+
+export interface MacroSnapshot {
+  fedFunds: MacroReading | null
+  treasury3m: MacroReading | null
+  treasury2y: MacroReading | null
+  treasury10y: MacroReading | null
+  cpiYoy: MacroReading | null
+  /** 10-year minus 2-year, percentage points. */
+  curve10y2y: number | null
+  curve10y3m: number | null
+  inverted: boolean
+  notes: string[]
+  /** Unix seconds. */
+  fetchedAt: number
+}
+
+export async function getMacroSnapshot(): Promise<MacroSnapshot> {
+  return await invoke("get_macro_snapshot")
+}
@@ -0,0 +1,33 @@
+import { invoke, type Page } from "./invoke"
+
+// Mirrors `services::margin_monitor`. Values are in the account's base
+// currency; `cushion` is IBKR's 0..1 fraction. The `margin-cushion-low`
+// event payload keeps the enum variant's snake_case field names.
+
+export interface MarginSample {
+  account: string
+  /** Unix seconds. */
+  sampledAt: number
+  maintMarginReq: number
+  excessLiquidity: number
+  cushion: number
+  netLiquidation: number
+}
+
+export interface MarginCushionLowPayload {
+  account: string
+  cushion: number
+  threshold_pct: number
+  excess_liquidity: number
+  account_alias?: string
+}
+
+/** `from` / `to` are inclusive `YYYY-MM-DD` ET dates. */
+export async function getMarginHistory(
+  from?: string,
+  to?: string,
+  account?: string,
+  page: Page = {},
+): Promise<MarginSample[]> {
+  return await invoke("get_margin_history", { from, to, account, ...page })
+}
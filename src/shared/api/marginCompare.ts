@@ -0,0 +1,40 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::margin_compare`. Positions are bundled by
+// underlying; Reg-T figures are IBKR what-if results, portfolio margin
+// is a worst-loss estimate over a ±`pmMovePct` price scan.
+
+export interface BundleMargin {
+  symbol: string
+  /** Contract keys of the bundle's positions. */
+  legs: string[]
+  marketValue: number
+  regTInitial: number | null
+  regTMaintenance: number | null
+  portfolioMargin: number | null
+  /** Underlying move of the worst scenario, in percent. */
+  worstMovePct: number | null
+  /** `regTInitial` less `portfolioMargin`, when both are known. */
+  difference: number | null
+}
+
+export interface MarginComparison {
+  account: string
+  at: number
+  pmMovePct: number
+  /** Largest Reg-T requirement first. */
+  bundles: BundleMargin[]
+  /** Totals over the bundles measured both ways. */
+  regTInitial: number
+  regTMaintenance: number
+  portfolioMargin: number
+  /** Initial margin portfolio margin would free; negative when it needs more. */
+  freed: number
+  /** `"SYMBOL: reason"` per side of a bundle that couldn't be measured. */
+  unmeasured: string[]
+}
+
+/** `account` defaults to the current one. */
+export async function getMarginComparison(account?: string): Promise<MarginComparison> {
+  return await invoke("get_margin_comparison", { account })
+}
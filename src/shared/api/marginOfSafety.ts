@@ -0,0 +1,47 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::margin_of_safety`. Bands are camelCase (serde
+// `rename_all`); the `margin-of-safety-entered` event payload keeps the
+// enum variant's snake_case field names. Signals only, never orders.
+
+export type BandZone = "buy" | "hold" | "sell"
+
+export interface SafetyBand {
+  symbol: string
+  scenario: "bear" | "base" | "bull"
+  /** Midpoint of the scenario's first projected year. */
+  fairValue: number
+  buyBelow: number
+  sellAbove: number
+  marginPct: number
+  snapshotId: number
+  /** `null` until the ticker has been priced. */
+  zone: BandZone | null
+  price: number | null
+  /** Unix seconds. */
+  evaluatedAt: number
+  changedAt: number | null
+}
+
+export interface MarginOfSafetyReport {
+  evaluated: number
+  entered: SafetyBand[]
+  skipped: string[]
+}
+
+export interface MarginOfSafetyEnteredPayload {
+  symbol: string
+  scenario: "bear" | "base" | "bull"
+  zone: BandZone
+  price: number
+  buy_below: number
+  sell_above: number
+}
+
+export async function marginOfSafetyList(symbol?: string): Promise<SafetyBand[]> {
+  return await invoke("margin_of_safety_list", { symbol })
+}
+
+export async function marginOfSafetyRunNow(): Promise<MarginOfSafetyReport> {
+  return await invoke("margin_of_safety_run_now")
+}
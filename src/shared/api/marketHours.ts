@@ -0,0 +1,23 @@
+import { invoke } from "@tauri-apps/api/core"
+
+import type { TradingSession } from "../types"
+
+// Mirrors `utils::market_calendar::sessions`. Times are UTC RFC 3339;
+// sessions are DST-aware and honour early closes.
+
+export interface MarketStatus {
+  exchange: string
+  /** Calendar the exchange maps to, e.g. "US equities". */
+  calendar: string
+  /** Regular session only. */
+  open: boolean
+  session: TradingSession
+  nextOpen: string
+  /** End of the current session, or of the next one when closed. */
+  nextClose: string
+}
+
+/** Rejects for an exchange without a known calendar. */
+export async function getMarketStatus(exchange?: string): Promise<MarketStatus> {
+  return await invoke("get_market_status", { exchange })
+}
@@ -0,0 +1,70 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::model_portfolio`. Weights and drift are percent.
+// Evaluation only proposes trades; nothing here places an order.
+
+export type AllocationBasis = "ticker" | "sector"
+
+export interface AllocationTarget {
+  /** Ticker, or a sector label (`tech`, `semis`, …). */
+  key: string
+  weight: number
+}
+
+export interface AllocationTemplate {
+  name: string
+  basis: AllocationBasis
+  targets: AllocationTarget[]
+  /** Buckets within this many points of target are left alone. */
+  bandPct?: number
+}
+
+export interface AllocationDrift {
+  key: string
+  targetWeight: number
+  currentWeight: number
+  currentValue: number
+  targetValue: number
+  /** Positive = overweight. */
+  drift: number
+}
+
+export interface RebalanceTrade {
+  symbol: string
+  bucket: string
+  action: "Buy" | "Sell"
+  quantity: number
+  price: number
+  value: number
+}
+
+export interface RebalancePlan {
+  template: string
+  account: string
+  totalValue: number
+  allocations: AllocationDrift[]
+  /** Sells first, then buys. */
+  trades: RebalanceTrade[]
+  notes: string[]
+}
+
+export async function listModelPortfolios(): Promise<AllocationTemplate[]> {
+  return await invoke("model_portfolio_list")
+}
+
+export async function saveModelPortfolio(
+  template: AllocationTemplate,
+): Promise<AllocationTemplate[]> {
+  return await invoke("model_portfolio_save", { template })
+}
+
+export async function deleteModelPortfolio(name: string): Promise<AllocationTemplate[]> {
+  return await invoke("model_portfolio_delete", { name })
+}
+
+export async function evaluateModelPortfolio(
+  name: string,
+  account?: string,
+): Promise<RebalancePlan> {
+  return await invoke("model_portfolio_evaluate", { name, account })
+}
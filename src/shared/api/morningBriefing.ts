@@ -0,0 +1,37 @@
+import { invoke } from "@tauri-apps/api/core"
+
+import type { Alert } from "../../features/tracker/types"
+import type { BlackoutConfidence } from "./eventCalendar"
+import type { FairValueFlag } from "./fairValue"
+import type { PortfolioDiff } from "./portfolioDiff"
+
+// Mirrors `services::morning_briefing`. A section that couldn't be
+// built is empty (or null) and explained in `notes`.
+
+export interface UpcomingEarnings {
+  symbol: string
+  /** `YYYY-MM-DD`. */
+  date: string
+  tradingDaysUntil: number
+  confidence: BlackoutConfidence
+  source: string
+}
+
+export interface Briefing {
+  /** ET `YYYY-MM-DD`. */
+  date: string
+  /** Unix seconds. */
+  generatedAt: number
+  account: string | null
+  diff: PortfolioDiff | null
+  earnings: UpcomingEarnings[]
+  daysToFomc: number | null
+  alerts: Alert[]
+  fairValue: FairValueFlag[]
+  notes: string[]
+  markdown: string | null
+}
+
+export async function generateBriefing(markdown = false): Promise<Briefing> {
+  return await invoke("generate_briefing", { markdown })
+}
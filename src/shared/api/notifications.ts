@@ -0,0 +1,18 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::notifications`. Channels live under
+// `notifications` in settings; the SMTP password is stored with
+// `set_api_key("smtp_password", ...)`. Webhook channels are labelled by
+// host only, so reports never echo the URL's token. Telegram chats
+// (`telegram` in settings, token via `set_api_key("telegram", ...)`)
+// show up as `telegram <chat id>`.
+
+export interface DeliveryReport {
+  delivered: string[]
+  /** `[channel, error]` pairs. */
+  failed: [string, string][]
+}
+
+export async function sendTestNotification(): Promise<DeliveryReport> {
+  return await invoke("notifications_send_test")
+}
@@ -0,0 +1,16 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::notion_export`. Needs `notion.database_id` in
+// settings and a token stored with `set_api_key("notion", ...)`; the
+// database must have the properties listed on `TickerSummary::properties`.
+
+export interface NotionExportReport {
+  created: string[]
+  updated: string[]
+  /** `[symbol, error]` pairs. */
+  failed: [string, string][]
+}
+
+export async function notionExportNow(): Promise<NotionExportReport> {
+  return await invoke("notion_export_now")
+}
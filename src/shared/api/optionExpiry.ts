@@ -0,0 +1,84 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::option_expiry`. Moneyness is in percent, positive
+// in the money; dates are `YYYY-MM-DD`.
+
+export type Moneyness = "itm" | "atm" | "otm"
+
+export type AssignmentRisk = "early_exercise" | "at_expiry"
+
+export interface ExDividend {
+  date: string
+  /** Per share. */
+  amount: number
+}
+
+export interface OptionExpiryRow {
+  symbol: string
+  localSymbol: string
+  right: "C" | "P"
+  strike: number
+  expiry: string
+  /** Calendar days from today. */
+  dte: number
+  /** Contracts; negative when short. */
+  quantity: number
+  multiplier: number
+  underlyingPrice: number | null
+  moneynessPct: number | null
+  moneyness: Moneyness | null
+  /** Per share: the option's price less its intrinsic value. */
+  timeValue: number | null
+  /** Read for short calls in the money only. */
+  exDividend: ExDividend | null
+  assignmentRisk: AssignmentRisk | null
+  /** Shares received (positive) or delivered; zero out of the money. */
+  sharesIfExercised: number
+  /** Cash received (positive) or paid for those shares. */
+  cashIfExercised: number
+}
+
+export interface OptionExpiryReport {
+  account: string
+  asOf: string
+  /** Nearest expiry first. */
+  positions: OptionExpiryRow[]
+  /** Option positions missing an expiry, strike or right. */
+  unreadable: string[]
+}
+
+export interface ExpiryDay {
+  expiry: string
+  positions: OptionExpiryRow[]
+  cashIfExercised: number
+}
+
+export interface ExpirySummary {
+  account: string
+  asOf: string
+  /** Last expiry date included. */
+  through: string
+  days: ExpiryDay[]
+  atRisk: number
+}
+
+export interface OptionAssignmentRiskPayload {
+  account: string
+  symbol: string
+  local_symbol: string
+  risk: AssignmentRisk
+  expiry: string
+  dte: number
+  moneyness_pct: number
+  ex_dividend_date?: string
+}
+
+/** `account` defaults to the current one. */
+export async function getOptionExpiries(account?: string): Promise<OptionExpiryReport> {
+  return await invoke("get_option_expiries", { account })
+}
+
+/** `days` defaults to 7, at most 90. */
+export async function getExpirySummary(account?: string, days?: number): Promise<ExpirySummary> {
+  return await invoke("get_expiry_summary", { account, days })
+}
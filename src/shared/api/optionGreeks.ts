@@ -0,0 +1,47 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::option_greeks`. Position greeks are per-contract
+// model greeks × quantity × multiplier: `delta` in share-equivalents,
+// `theta` in currency per day, `vega` per vol point. The
+// `portfolio-greeks-update` event payload is a `PortfolioGreeks`.
+
+export interface OptionGreeks {
+  impliedVolatility: number | null
+  delta: number | null
+  gamma: number | null
+  theta: number | null
+  vega: number | null
+  optionPrice: number | null
+  underlyingPrice: number | null
+}
+
+export interface PositionGreeks {
+  symbol: string
+  localSymbol: string
+  quantity: number
+  multiplier: number
+  model: OptionGreeks
+  delta: number
+  gamma: number
+  theta: number
+  vega: number
+}
+
+export interface PortfolioGreeks {
+  account: string
+  positions: PositionGreeks[]
+  delta: number
+  gamma: number
+  theta: number
+  vega: number
+  /** Option positions TWS returned no model greeks for. */
+  unpriced: string[]
+  /** Unix seconds. */
+  asOf: number
+  /** Set on event payloads when the account has an alias. */
+  account_alias?: string
+}
+
+export async function getPortfolioGreeks(account?: string): Promise<PortfolioGreeks> {
+  return await invoke("ibkr_get_portfolio_greeks", { account: account ?? null })
+}
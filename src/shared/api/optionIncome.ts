@@ -0,0 +1,56 @@
+import { invoke } from "@tauri-apps/api/core"
+import type { OptionContract, OrderRequest } from "../types"
+import type { IvRank } from "./ivRank"
+
+// Mirrors `services::option_income`. Suggestions only: each `order` is
+// a sell-to-open limit ticket for the order form, placed (after
+// confirmation) through `ibkr_place_order`.
+
+export type IncomeStrategy = "covered_call" | "cash_secured_put"
+
+export interface IncomeRequest {
+  symbol: string
+  strategy: IncomeStrategy
+  /** Percent; default 10. */
+  minAnnualizedYieldPct?: number
+  /** Absolute delta; default 0.30. */
+  maxDelta?: number
+  /** Default 7. */
+  minDte?: number
+  /** Default 45. */
+  maxDte?: number
+  contracts?: number
+  /** Skip premium selling below this 52-week IV rank (0–100). */
+  minIvRank?: number
+  account?: string
+}
+
+export interface IncomeSuggestion {
+  contract: OptionContract
+  dte: number
+  delta: number
+  /** Per share. */
+  premium: number
+  premiumTotal: number
+  annualizedYieldPct: number
+  breakeven: number
+  /** 0 for covered calls. */
+  cashRequired: number
+  order: OrderRequest
+}
+
+export interface IncomeIdeas {
+  symbol: string
+  strategy: IncomeStrategy
+  underlyingPrice: number
+  sharesHeld: number
+  contracts: number
+  suggestions: IncomeSuggestion[]
+  unpriced: number
+  ivRank: IvRank | null
+  note: string | null
+}
+
+export async function suggestOptionIncome(request: IncomeRequest): Promise<IncomeIdeas> {
+  return await invoke("ibkr_suggest_option_income", { request })
+}
@@ -0,0 +1,52 @@
+import { invoke } from "./invoke"
+import type { ComboOrderRequest, OptionContract } from "../types"
+
+// Mirrors `services::option_roll`. Suggestions only: each `order` is a
+// buy-to-close / sell-to-open combo ticket, placed (after confirmation)
+// through `ibkr_place_combo_order`.
+
+export interface RollCandidate {
+  contract: OptionContract
+  dte: number
+  delta: number
+  /** Model price per share. */
+  premium: number
+  /** Per share; negative is a debit. */
+  netCredit: number
+  netCreditTotal: number
+  order: ComboOrderRequest
+}
+
+export interface PositionRolls {
+  contract: OptionContract
+  localSymbol: string
+  /** Negative: short. */
+  contracts: number
+  dte: number
+  delta: number
+  /** Model price per share to buy the position back. */
+  closePrice: number
+  /** Closest delta first. */
+  candidates: RollCandidate[]
+}
+
+export interface RollReport {
+  account: string
+  asOf: string
+  /** Nearest expiry first. */
+  positions: PositionRolls[]
+  /** Expiring short options that couldn't be rolled, with why. */
+  skipped: string[]
+}
+
+/** `account` defaults to the current one; `symbol` narrows to one
+ * underlying. */
+export async function suggestOptionRolls(account?: string, symbol?: string): Promise<RollReport> {
+  return await invoke("suggest_option_rolls", { account, symbol })
+}
+
+/** Send a candidate's combo ticket. Pass the same `idempotencyKey` on a
+ * retry to get the first order id back. */
+export async function submitRoll(candidate: RollCandidate, idempotencyKey?: string): Promise<number> {
+  return await invoke("ibkr_place_combo_order", { order: candidate.order, idempotencyKey })
+}
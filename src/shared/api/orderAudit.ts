@@ -0,0 +1,78 @@
+import { invoke, type Page } from "./invoke"
+
+// Mirrors `services::order_audit`. `event` keeps the Rust field names
+// (snake_case), tagged by `kind`.
+
+export interface SentOrder {
+  order_id: number
+  symbol: string
+  action: string
+  quantity: number
+  order_type: string
+  limit_price?: number | null
+  aux_price?: number | null
+  parent_id: number
+  transmit: boolean
+  /** IBKR `goodAfterTime`, e.g. `"20250206 14:30:00 UTC"`. */
+  good_after_time?: string | null
+  algo_strategy?: string | null
+  /** `[tag, value]` pairs. */
+  algo_params: [string, string][]
+}
+
+export type OrderAuditEvent =
+  | ({ kind: "sent" } & SentOrder)
+  | { kind: "rejected"; order: SentOrder; error: string }
+  | {
+      kind: "status"
+      order_id: number
+      symbol: string
+      status: string
+      filled: number
+      remaining: number
+      avg_fill_price: number
+    }
+  | {
+      kind: "fill"
+      order_id: number
+      symbol: string
+      exec_id: string
+      side: string
+      shares: number
+      price: number
+      time: string
+    }
+  | { kind: "notice"; order_id: number; symbol: string; code: number; message: string }
+
+export interface OrderAuditEntry {
+  id: number
+  /** Unix milliseconds. */
+  recordedAt: number
+  orderId: number
+  symbol: string
+  event: OrderAuditEvent
+}
+
+export interface ScheduledOrder {
+  order: SentOrder
+  /** Unix seconds. */
+  startsAt: number
+  /** Unix milliseconds. */
+  sentAt: number
+  lastStatus?: string
+}
+
+/** `from` / `to` are inclusive `YYYY-MM-DD` ET dates. Oldest first. */
+export async function getOrderHistory(
+  from?: string,
+  to?: string,
+  symbol?: string,
+  page: Page = {},
+): Promise<OrderAuditEntry[]> {
+  return await invoke("get_order_history", { from, to, symbol, ...page })
+}
+
+/** Good-After-Time orders IBKR is still holding. Soonest first. */
+export async function listScheduledOrders(): Promise<ScheduledOrder[]> {
+  return await invoke("list_scheduled_orders")
+}
@@ -0,0 +1,110 @@
+import type { BarSize } from "../../features/tracker/types"
+import { invoke, type Page } from "./invoke"
+
+// Mirrors `services::paper_trading`. Sim orders fill locally and never
+// reach IBKR. Times are unix seconds. The `sim-order-filled` event
+// payload keeps the enum variant's snake_case field names.
+
+export type SimSide = "buy" | "sell"
+export type SimOrderType = "market" | "limit" | "stop"
+export type SimOrderStatus = "working" | "filled" | "cancelled" | "rejected"
+
+export interface SimOrderRequest {
+  symbol: string
+  side: SimSide
+  quantity: number
+  orderType: SimOrderType
+  /** Limit or stop level; ignored for market orders. */
+  price?: number | null
+}
+
+export interface SimOrder {
+  id: number
+  symbol: string
+  side: SimSide
+  quantity: number
+  orderType: SimOrderType
+  price?: number | null
+  status: SimOrderStatus
+  /** Why a rejected order was rejected. */
+  reason?: string | null
+  createdAt: number
+  closedAt?: number | null
+}
+
+export interface SimFill {
+  id: number
+  orderId: number
+  symbol: string
+  side: SimSide
+  quantity: number
+  price: number
+  /** Tape price before slippage. */
+  referencePrice: number
+  commission: number
+  filledAt: number
+}
+
+export interface SimPosition {
+  symbol: string
+  quantity: number
+  averageCost: number
+  marketPrice?: number | null
+  unrealizedPnl?: number | null
+}
+
+export interface SimPortfolio {
+  startingCash: number
+  startedAt: number
+  cash: number
+  positions: SimPosition[]
+  realizedPnl: number
+  commissions: number
+  equity: number
+}
+
+export interface SimOrderFilledPayload {
+  order_id: number
+  symbol: string
+  side: SimSide
+  quantity: number
+  price: number
+}
+
+/** A market order comes back already filled (or rejected). */
+export async function placeSimOrder(order: SimOrderRequest): Promise<SimOrder> {
+  return await invoke("sim_place_order", { order })
+}
+
+export async function cancelSimOrder(id: number): Promise<SimOrder> {
+  return await invoke("sim_cancel_order", { id })
+}
+
+export async function listSimOrders(
+  status?: SimOrderStatus,
+  page: Page = {},
+): Promise<SimOrder[]> {
+  return await invoke("sim_list_orders", { status, ...page })
+}
+
+export async function listSimFills(symbol?: string, page: Page = {}): Promise<SimFill[]> {
+  return await invoke("sim_list_fills", { symbol, ...page })
+}
+
+export async function getSimPortfolio(): Promise<SimPortfolio> {
+  return await invoke("sim_get_portfolio")
+}
+
+/** Drops every sim order and fill; `startingCash` defaults to the config's. */
+export async function resetSim(startingCash?: number): Promise<SimPortfolio> {
+  return await invoke("sim_reset", { startingCash })
+}
+
+/** `duration` is an IBKR duration such as `"5 D"`. Returns the fills. */
+export async function replaySim(
+  symbol: string,
+  duration: string,
+  barSize: BarSize,
+): Promise<SimFill[]> {
+  return await invoke("sim_replay", { symbol, duration, barSize })
+}
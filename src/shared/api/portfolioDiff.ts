@@ -0,0 +1,43 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::portfolio_diff`. Values are in each position's own
+// currency; `totals` sums them per currency. `pnlChange` is unrealized
+// plus realized P&L now minus the baseline's, and `null` for a closed
+// position.
+
+export interface HoldingChange {
+  contract: string
+  symbol: string
+  currency: string
+  quantityBefore: number
+  quantityNow: number
+  marketValueBefore: number
+  marketValueNow: number
+  pnlChange: number | null
+}
+
+export interface CurrencyTotals {
+  currency: string
+  marketValueBefore: number
+  marketValueNow: number
+  pnlChange: number
+}
+
+export interface PortfolioDiff {
+  account: string
+  /** `YYYY-MM-DD` of the snapshot compared against. */
+  baselineDate: string
+  /** Unix seconds. */
+  asOf: number
+  opened: HoldingChange[]
+  closed: HoldingChange[]
+  resized: HoldingChange[]
+  movers: HoldingChange[]
+  totals: CurrencyTotals[]
+}
+
+/** Against the snapshot on or before `date` (`YYYY-MM-DD`, ET); the
+ * last one before today when omitted. */
+export async function getPortfolioDiff(date?: string): Promise<PortfolioDiff> {
+  return await invoke("get_portfolio_diff", { date })
+}
@@ -0,0 +1,43 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::position_health`. Scores run from 0 (bad) to 100
+// (fine); `score` averages the components present. Distances are in
+// ATRs, measured in the position's favour.
+
+export type HealthLight = "green" | "amber" | "red"
+
+export type StopSource = "plan" | "bracket"
+
+export interface PositionHealth {
+  symbol: string
+  quantity: number
+  price: number
+  averageCost: number
+  stopPrice: number | null
+  /** Null when the position has neither a plan stop nor an open bracket. */
+  stopSource: StopSource | null
+  atr: number | null
+  /** Negative once through the stop. */
+  stopDistanceAtr: number | null
+  /** Negative in profit. */
+  drawdownAtr: number | null
+  /** Percent of the account's gross position value. */
+  weightPct: number
+  stopScore: number | null
+  drawdownScore: number | null
+  weightScore: number
+  score: number
+  light: HealthLight
+}
+
+export interface PositionHealthReport {
+  account: string
+  at: number
+  /** Open stock positions, worst score first. */
+  positions: PositionHealth[]
+}
+
+/** Recomputed on every call; `account` defaults to the current one. */
+export async function getPositionHealth(account?: string): Promise<PositionHealthReport> {
+  return await invoke("get_position_health", { account })
+}
@@ -0,0 +1,119 @@
+import { invoke } from "@tauri-apps/api/core"
+import type { OrderRequest, Position } from "../types"
+
+// Mirrors `services::position_plans`. A plan is keyed by account and
+// contract (ticker for stock, local symbol for an option series). The
+// `position-level-hit` and `stop-breached` event payloads keep the Rust
+// field names (`level_price`, `stop_price`).
+
+export type PlanLevel = "target" | "stop"
+
+/** What the stop babysitter does on a breach; exits are tickets the trader confirms. */
+export type StopAction = "alert" | "market_exit" | "limit_exit"
+
+export interface PositionPlan {
+  account: string
+  contract: string
+  symbol: string
+  targetPrice: number | null
+  stopPrice: number | null
+  thesis: string | null
+  /** Level the price was past at the last check. */
+  hit: PlanLevel | null
+  hitAt: number | null
+  /** Set when the stop babysitter watches the stop. */
+  stopAction: StopAction | null
+  /** When the babysitter acted on a breach; null while armed. */
+  stopTriggeredAt: number | null
+  /** An alert, the pending exit ticket, or the order sent from it. */
+  stopResult: string | null
+  /** The exit order awaiting the trader; null once confirmed or dismissed. */
+  exitTicket: OrderRequest | null
+  updatedAt: number
+}
+
+export interface PositionPlanInput {
+  account: string
+  contract: string
+  symbol: string
+  targetPrice?: number | null
+  stopPrice?: number | null
+  /** Up to 500 characters. */
+  thesis?: string | null
+  /** Needs a stop price. A new stop or action re-arms the babysitter. */
+  stopAction?: StopAction | null
+}
+
+export interface EnrichedPosition extends Position {
+  plan: PositionPlan | null
+}
+
+export interface PlanCheckReport {
+  checked: number
+  hits: PositionPlan[]
+}
+
+export interface StopWatchReport {
+  watched: number
+  breaches: PositionPlan[]
+  /** Contracts left alone: not a US stock, or no last trade. */
+  skipped: string[]
+}
+
+export interface PositionLevelHitPayload {
+  account: string
+  contract: string
+  level: PlanLevel
+  level_price: number
+  price: number
+  thesis: string | null
+}
+
+export interface StopBreachedPayload {
+  account: string
+  contract: string
+  stop_price: number
+  price: number
+  action: StopAction
+  /** An exit ticket awaits the trader's confirmation. */
+  exit_ticket: boolean
+  result: string
+}
+
+/** Open positions of `account` (the current one when omitted). */
+export async function getEnrichedPositions(account?: string): Promise<EnrichedPosition[]> {
+  return await invoke("get_enriched_positions", { account })
+}
+
+export async function listPositionPlans(account?: string): Promise<PositionPlan[]> {
+  return await invoke("list_position_plans", { account })
+}
+
+/** Resolves to `null` when the input had no target, stop or thesis
+ * and the plan was removed. */
+export async function setPositionPlan(plan: PositionPlanInput): Promise<PositionPlan | null> {
+  return await invoke("set_position_plan", { plan })
+}
+
+export async function deletePositionPlan(account: string, contract: string): Promise<boolean> {
+  return await invoke("delete_position_plan", { account, contract })
+}
+
+export async function positionPlanCheckNow(): Promise<PlanCheckReport> {
+  return await invoke("position_plan_check_now")
+}
+
+/** Posts exit tickets on a breach like the `stop_babysitter` task does. */
+export async function stopBabysitterCheckNow(): Promise<StopWatchReport> {
+  return await invoke("stop_babysitter_check_now")
+}
+
+/** Sends the plan's exit ticket through the same checks as a manual
+ * order. Refused once it lapsed or the position changed. */
+export async function confirmStopExit(account: string, contract: string): Promise<PositionPlan> {
+  return await invoke("confirm_stop_exit", { account, contract })
+}
+
+export async function dismissStopExit(account: string, contract: string): Promise<PositionPlan> {
+  return await invoke("dismiss_stop_exit", { account, contract })
+}
@@ -0,0 +1,35 @@
+import { invoke } from "@tauri-apps/api/core"
+import type { ProjectionAssumptions } from "../types"
+
+// Mirrors `services::projection_templates`. Templates live in settings
+// (the shipped set is the default list); the per-ticker choice is made
+// by passing `template` to `generateProjectionResults`.
+
+export interface ProjectionTemplate {
+  name: string
+  description?: string
+  assumptions: ProjectionAssumptions
+}
+
+export async function listProjectionTemplates(): Promise<ProjectionTemplate[]> {
+  return await invoke("projection_template_list")
+}
+
+/** Create or replace by case-insensitive name. */
+export async function saveProjectionTemplate(
+  template: ProjectionTemplate,
+): Promise<ProjectionTemplate[]> {
+  return await invoke("projection_template_save", { template })
+}
+
+export async function deleteProjectionTemplate(name: string): Promise<ProjectionTemplate[]> {
+  return await invoke("projection_template_delete", { name })
+}
+
+export async function getTickerProjectionTemplate(symbol: string): Promise<string | null> {
+  return await invoke("get_ticker_projection_template", { symbol })
+}
+
+export async function clearTickerProjectionTemplate(symbol: string): Promise<boolean> {
+  return await invoke("clear_ticker_projection_template", { symbol })
+}
@@ -0,0 +1,22 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::report_service`. Files are written under the app
+// data dir's `reports/` folder; paths are absolute.
+
+export interface ReportArtifact {
+  symbol: string
+  markdownPath: string
+  pdfPath: string
+  /** `null` when no daily bars are cached for the symbol. */
+  chartPath: string | null
+  /** Set when the report was attached to today's journal. */
+  journalEntryId: number | null
+  generatedAt: string
+}
+
+export async function generateReport(
+  symbol: string,
+  attachToJournal = false,
+): Promise<ReportArtifact> {
+  return await invoke("report_generate", { symbol, attachToJournal })
+}
@@ -13,6 +13,7 @@ export type SizingSkippedReason =
   | "stale_snapshot"
   | "tilt_paused"
   | "invalid_price"
+  | "illiquid"
 
 export interface Sizing {
   qty: number
@@ -96,4 +97,5 @@ export const SIZING_SKIPPED_LABELS: Record<SizingSkippedReason, string> = {
   stale_snapshot: "Stale equity",
   tilt_paused: "Tilt paused",
   invalid_price: "Invalid price",
+  illiquid: "Illiquid",
 }
@@ -0,0 +1,49 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::scheduler`. Cron expressions are five fields
+// evaluated in ET (`min hour dom month dow`).
+
+export interface JobRun {
+  id: number
+  jobId: string
+  /** Unix seconds. */
+  startedAt: number
+  finishedAt: number
+  ok: boolean
+  /** Summary on success, the error otherwise. */
+  message: string
+}
+
+export interface ScheduledJobView {
+  id: string
+  description: string
+  cron: string
+  enabled: boolean
+  /** Unix seconds; absent when disabled. */
+  nextRunAt?: number
+  lastRun?: JobRun
+  lastError?: JobRun
+}
+
+export async function listScheduledJobs(): Promise<ScheduledJobView[]> {
+  return await invoke("scheduler_list_jobs")
+}
+
+/** Enable / disable a job, optionally with a new cron. */
+export async function setScheduledJob(
+  id: string,
+  enabled: boolean,
+  cron?: string,
+): Promise<ScheduledJobView[]> {
+  return await invoke("scheduler_set_job", { id, enabled, cron })
+}
+
+/** Newest first. */
+export async function scheduledJobHistory(id: string, limit?: number): Promise<JobRun[]> {
+  return await invoke("scheduler_run_history", { id, limit })
+}
+
+/** Rejects with the task's error when the run fails. */
+export async function runScheduledJobNow(id: string): Promise<string> {
+  return await invoke("scheduler_run_now", { id })
+}
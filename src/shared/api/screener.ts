@@ -0,0 +1,45 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::screener`. Screens cached fundamentals only — no
+// Alpha Vantage calls. Pass IBKR scanner symbols as `universe` to
+// screen a scan's output.
+
+export interface ScreenerCriteria {
+  /** Percent, e.g. 15 for a 15% revenue CAGR. */
+  minRevenueCagr?: number
+  /** 3 or 5; defaults to 3. */
+  cagrYears?: number
+  /** Loss-makers (P/E ≤ 0) never pass. */
+  maxPe?: number
+  /** Latest net margin above the prior year's. */
+  marginTrendPositive?: boolean
+  /** Percent of shares outstanding, from stored FINRA short interest. */
+  minShortInterestPct?: number
+  minDaysToCover?: number
+  universe?: string[]
+}
+
+export interface ScreenerMatch {
+  symbol: string
+  name?: string
+  peRatio: number
+  revenueCagr: number | null
+  netMargin: number | null
+  /** Percentage points, latest year vs prior. */
+  marginChange: number | null
+  latestYear: number | null
+  shortInterestPct: number | null
+  daysToCover: number | null
+}
+
+export interface ScreenerResult {
+  /** Highest revenue CAGR first. */
+  matches: ScreenerMatch[]
+  screened: number
+  /** Universe symbols with no cached fundamentals. */
+  skipped: string[]
+}
+
+export async function runScreener(criteria: ScreenerCriteria): Promise<ScreenerResult> {
+  return await invoke("screener", { criteria })
+}
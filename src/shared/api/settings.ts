@@ -1,4 +1,4 @@
-import { invoke } from "@tauri-apps/api/core"
+import { invoke } from "./invoke"
 
 // Types matching Rust structures
 
@@ -7,6 +7,8 @@ export interface AppConfig {
   logging: LoggingConfig
   ui: UiConfig
   api: ApiConfig
+  /** Simulated account and market data instead of IBKR / Alpha Vantage; applies on restart. */
+  demo?: boolean
 }
 
 export interface IbkrConfig {
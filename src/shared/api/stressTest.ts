@@ -0,0 +1,56 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::stress_test`. Shocks are tagged by `kind` and keep
+// the Rust snake_case field names. Moves and returns are in percent.
+
+export type Shock =
+  | { kind: "price"; symbol: string; move_pct: number }
+  /** Treasury yields, priced through `stress_test.rates_proxy`. */
+  | { kind: "rates"; bp: number }
+
+export interface ProxyMove {
+  symbol: string
+  movePct: number
+}
+
+export interface ProxyBeta {
+  proxy: string
+  beta: number
+}
+
+export interface PositionImpact {
+  symbol: string
+  marketValue: number
+  /** In the scenario's proxy order. */
+  betas: ProxyBeta[]
+  rSquared: number
+  returnPct: number
+  pnl: number
+}
+
+export interface ScenarioResult {
+  name: string
+  shocks: Shock[]
+  /** One per shock. */
+  proxies: ProxyMove[]
+  /** Largest loss first. */
+  positions: PositionImpact[]
+  totalPnl: number
+  /** `totalPnl` over `measuredGross`. */
+  totalPct: number
+  measuredGross: number
+  /** Options, and stocks without enough history. */
+  unmeasured: string[]
+}
+
+export interface StressTestReport {
+  account: string
+  at: number
+  scenarios: ScenarioResult[]
+}
+
+/** Every configured scenario unless `scenario` names one; `account`
+ * defaults to the current one. */
+export async function runStressTest(account?: string, scenario?: string): Promise<StressTestReport> {
+  return await invoke("run_stress_test", { account, scenario })
+}
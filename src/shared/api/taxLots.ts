@@ -0,0 +1,151 @@
+import { invoke } from "./invoke"
+
+// Mirrors `services::tax_lots`. Lots are rebuilt FIFO per account from
+// imported lots plus stored fills, with every account in one pass so a
+// loss in one account can be washed by a purchase in another. Amounts
+// are dollars with the multiplier and commissions in.
+
+export interface RealizedLot {
+  account: string
+  /** Ticker for stock; `SYMBOL YYYYMMDD STRIKE RIGHT` for an option. */
+  contract: string
+  quantity: number
+  /** Proceeds are the short sale, the cost the cover. */
+  short: boolean
+  /** ISO timestamp; `null` for an imported lot without a date. */
+  acquiredAt: string | null
+  soldAt: string
+  /** Exec id of the closing fill. */
+  closedBy: string
+  proceeds: number | null
+  /** Including `washAdjustment`. */
+  costBasis: number | null
+  /** Losses disallowed on earlier sales and carried into this lot. */
+  washAdjustment: number
+  /** Before any disallowance. */
+  gain: number | null
+  /** The part of a loss a replacement purchase deferred; positive. */
+  disallowedLoss: number
+  longTerm: boolean | null
+}
+
+export interface WashSale {
+  account: string
+  contract: string
+  soldAt: string
+  quantity: number
+  disallowedLoss: number
+  replacementAccount: string
+  replacementAcquiredAt: string | null
+}
+
+export interface OpenLot {
+  account: string
+  contract: string
+  quantity: number
+  acquiredAt: string | null
+  /** Per unit, including `washAdjustment`. */
+  unitCost: number | null
+  /** Per unit. */
+  washAdjustment: number
+}
+
+export interface TaxTotals {
+  proceeds: number
+  costBasis: number
+  disallowedLoss: number
+  /** Losses net of their disallowed part. */
+  shortTermGain: number
+  longTermGain: number
+  /** Lots without a basis or a holding period, left out of the sums. */
+  incompleteLots: number
+}
+
+export interface TaxLotReport {
+  year: number
+  /** Unix seconds. */
+  asOf: number
+  accounts: string[]
+  firstFillAt: string | null
+  realized: RealizedLot[]
+  washSales: WashSale[]
+  /** Lots still held whose basis carries a disallowed loss. */
+  adjustedLots: OpenLot[]
+  totals: TaxTotals
+}
+
+export async function getTaxLotReport(year: number): Promise<TaxLotReport> {
+  return await invoke("get_tax_lot_report", { year })
+}
+
+/** A hypothetical stock sale. */
+export interface PlannedSale {
+  /** Omit to pick the one account holding the stock. */
+  account?: string | null
+  symbol: string
+  quantity: number
+  /** Per share, net of commission. */
+  price: number
+  /** `YYYY-MM-DD`, placed at the ET close. */
+  date: string
+}
+
+export interface PlannedSaleOutcome {
+  account: string
+  symbol: string
+  quantity: number
+  price: number
+  date: string
+  /** The lots it would close, FIFO. */
+  lots: RealizedLot[]
+  /** Reportable: losses net of their disallowed part. */
+  shortTermGain: number
+  longTermGain: number
+  /** Losses a purchase within the wash window would defer. */
+  disallowedLoss: number
+}
+
+/** Capital loss carried into the next year, by character; positive. */
+export interface Carryover {
+  shortTerm: number
+  longTerm: number
+}
+
+export interface Netting {
+  /** Net short-term gain after offsets; zero on a net loss. */
+  taxableShortTerm: number
+  taxableLongTerm: number
+  /** Net loss deducted against ordinary income, up to `tax.loss_deduction_limit`. */
+  lossDeduction: number
+  carryoverOut: Carryover
+  /** Negative when the deduction saves more than the gains cost. */
+  estimatedTax: number
+}
+
+export interface YearProjection {
+  year: number
+  realizedShortTerm: number
+  realizedLongTerm: number
+  plannedShortTerm: number
+  plannedLongTerm: number
+  carryoverIn: Carryover
+  netting: Netting
+  /** What the planned sales add to the year's tax; negative when they save. */
+  plannedTax: number
+}
+
+export interface TaxProjection {
+  /** Unix seconds. */
+  asOf: number
+  shortTermRatePct: number
+  longTermRatePct: number
+  /** In the order given. */
+  sales: PlannedSaleOutcome[]
+  /** From the current year through the last planned sale's. */
+  years: YearProjection[]
+  incompleteLots: number
+}
+
+export async function projectTax(sales: PlannedSale[]): Promise<TaxProjection> {
+  return await invoke("project_tax", { sales })
+}
@@ -1,4 +1,4 @@
-import { invoke } from "@tauri-apps/api/core"
+import { invoke } from "./invoke"
 
 // Mirrors `services::tca::types`. Money fields are integer cents to
 // dodge f64 round-trip drift through SQLite. Strategy is `null`
@@ -39,6 +39,48 @@ export interface ManualIntentArgs {
   account?: string | null
 }
 
+export type ArrivalOrderType = "market" | "limit" | "stop" | "stop_limit" | "midprice"
+
+/** One order's fills against the bid / ask mid when it was placed. */
+export interface OrderExecutionQuality {
+  account: string
+  order_id: number
+  symbol: string
+  side: IntentSide
+  order_type: ArrivalOrderType
+  placed_at: string
+  filled_qty: number
+  avg_fill_price: number
+  arrival_mid: number | null
+  arrival_spread_bps: number | null
+  /** Positive is a cost on either side. `null` without an arrival mid. */
+  slippage_bps: number | null
+  cost_cents: number | null
+  first_fill_at: string
+  last_fill_at: string
+}
+
+export interface ExecutionQualityBucket {
+  /** Symbol, order type, or "all". */
+  key: string
+  orders: number
+  filled_qty: number
+  notional_cents: number
+  /** Notional-weighted. */
+  avg_slippage_bps: number
+  cost_cents: number
+}
+
+export interface ExecutionQualityReport {
+  orders: OrderExecutionQuality[]
+  /** Highest cost first. */
+  by_symbol: ExecutionQualityBucket[]
+  by_order_type: ExecutionQualityBucket[]
+  overall: ExecutionQualityBucket
+  /** Filled orders without an arrival quote to measure against. */
+  unmeasured_orders: number
+}
+
 /** Per-strategy roll-up over `[date_from, date_to]` ET trading days. */
 export async function tcaGetAttribution(
   dateFrom: string,
@@ -65,6 +107,19 @@ export async function tcaGetSlippageDistribution(
   })
 }
 
+/** Slippage vs arrival mid over `[date_from, date_to]` ET trading days. */
+export async function getExecutionQualityReport(
+  dateFrom: string,
+  dateTo: string,
+  account?: string,
+): Promise<ExecutionQualityReport> {
+  return await invoke("get_execution_quality_report", {
+    dateFrom,
+    dateTo,
+    account: account ?? null,
+  })
+}
+
 /** Trader-initiated intent for an order placed outside our UI. */
 export async function tcaRecordManualIntent(args: ManualIntentArgs): Promise<string> {
   return await invoke("tca_record_manual_intent", { args })
@@ -0,0 +1,76 @@
+import { invoke } from "@tauri-apps/api/core"
+
+// Mirrors `services::trade_ideas`. Ideas only move forward; `exited`
+// is final and a new idea in the same symbol starts over at
+// `watching`. Timestamps are unix seconds.
+
+export type IdeaStage = "watching" | "researched" | "position" | "exited"
+
+export type IdeaLinkKind = "projection" | "journal"
+
+export interface TradeIdea {
+  id: number
+  symbol: string
+  stage: IdeaStage
+  /** Note of the latest move. */
+  note: string | null
+  createdAt: number
+  stageChangedAt: number
+}
+
+export interface IdeaTransition {
+  /** `null` for the creation. */
+  fromStage: IdeaStage | null
+  toStage: IdeaStage
+  note: string | null
+  at: number
+}
+
+export interface IdeaLink {
+  kind: IdeaLinkKind
+  /** `projection_snapshots.id` or `journal_entries.id`. */
+  refId: number
+  label: string
+  linkedAt: number
+}
+
+export interface TradeIdeaDetail extends TradeIdea {
+  transitions: IdeaTransition[]
+  links: IdeaLink[]
+}
+
+export async function createTradeIdea(symbol: string, note?: string): Promise<TradeIdea> {
+  return await invoke("create_trade_idea", { symbol, note })
+}
+
+export async function moveTradeIdea(
+  id: number,
+  stage: IdeaStage,
+  note?: string,
+): Promise<TradeIdea> {
+  return await invoke("move_trade_idea", { id, stage, note })
+}
+
+export async function linkTradeIdea(
+  id: number,
+  kind: IdeaLinkKind,
+  refId: number,
+): Promise<TradeIdeaDetail> {
+  return await invoke("link_trade_idea", { id, kind, refId })
+}
+
+export async function unlinkTradeIdea(
+  id: number,
+  kind: IdeaLinkKind,
+  refId: number,
+): Promise<TradeIdeaDetail> {
+  return await invoke("unlink_trade_idea", { id, kind, refId })
+}
+
+export async function listTradeIdeas(stage?: IdeaStage): Promise<TradeIdea[]> {
+  return await invoke("list_trade_ideas", { stage })
+}
+
+export async function getTradeIdea(id: number): Promise<TradeIdeaDetail> {
+  return await invoke("get_trade_idea", { id })
+}
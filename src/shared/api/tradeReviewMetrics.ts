@@ -7,7 +7,7 @@
  * only place that names the command strings.
  */
 
-import { invoke } from "@tauri-apps/api/core"
+import { invoke } from "./invoke"
 
 import type { EquityPoint, RiskMetrics, StrategyRollup } from "../../features/trade-review/types"
 
@@ -0,0 +1,40 @@
+import { invoke } from "./invoke"
+import type { EventCallback, UnlistenFn } from "@tauri-apps/api/event"
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow"
+
+// Mirrors `events::windows`. A registered window is only sent the
+// events its route accepts; unregistered windows get everything.
+// Routing applies to listeners bound on the window itself, so routed
+// windows subscribe through `listenRouted` rather than the global
+// `listen`.
+
+export interface WindowRoute {
+  /** Event names (`market-data-update`, ...); empty for all. */
+  events?: string[]
+  /** Tickers; empty for any. Symbol-less events then need naming in `events`. */
+  symbols?: string[]
+}
+
+export async function registerWindow(route: WindowRoute): Promise<void> {
+  return await invoke("register_window", { route })
+}
+
+export async function unregisterWindow(): Promise<boolean> {
+  return await invoke("unregister_window")
+}
+
+export async function listWindowRoutes(): Promise<Record<string, WindowRoute>> {
+  return await invoke("list_window_routes")
+}
+
+/** Opens (or focuses) the pop-out for `symbol`; resolves to its label. */
+export async function openTickerWindow(symbol: string): Promise<string> {
+  return await invoke("open_ticker_window", { symbol })
+}
+
+export async function listenRouted<T>(
+  event: string,
+  handler: EventCallback<T>,
+): Promise<UnlistenFn> {
+  return await getCurrentWebviewWindow().listen<T>(event, handler)
+}
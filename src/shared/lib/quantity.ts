@@ -0,0 +1,12 @@
+import type { SizeRules } from "../types"
+
+/**
+ * Largest quantity not above `qty` that `rules` accepts; 0 below the
+ * minimum. Mirrors `SizeRules::round_down` on the Rust side, which
+ * rejects anything this wouldn't return unchanged.
+ */
+export function roundQuantity(qty: number, rules: SizeRules): number {
+  const steps = Math.floor(qty / rules.sizeIncrement + 1e-9)
+  const rounded = Math.round(steps * rules.sizeIncrement * 1e8) / 1e8
+  return rounded + 1e-9 < rules.minSize ? 0 : rounded
+}
@@ -1,124 +1,113 @@
-// Financial projection for a single year
-export interface FinancialProjection {
-  year: number
-  revenue: number // in billions
-  revenueGrowth: number // percentage (e.g., 35.0 for 35%)
-  netIncome: number // in billions
-  netIncomeGrowth: number | null // percentage, null for first year
-  netIncomeMargins: number // percentage (e.g., 17.0 for 17%)
-  eps: number // dollars per share
-  peLowEst: number
-  peHighEst: number
-  sharePriceLow: number
-  sharePriceHigh: number
-  valuationMethod: string // "P/E" or "P/S" - indicates which method was used
-  psLowEst?: number // Price-to-Sales low (if P/S used)
-  psHighEst?: number // Price-to-Sales high (if P/S used)
-  analystEpsEstimate?: number // Analyst consensus EPS estimate (if available)
-}
-
-// CAGR (Compound Annual Growth Rate) calculations
-export interface CagrMetrics {
-  revenue: number // percentage
-  sharePrice: number // percentage
-}
-
-// Projections for a single year with bear/base/bull scenarios
-export interface YearlyProjection {
-  year: number
-  bear: FinancialProjection
-  base: FinancialProjection
-  bull: FinancialProjection
-}
-
-// Complete projection results with baseline and forward projections
-export interface ProjectionResults {
-  baseline: FinancialProjection // Most recent complete year (actual data)
-  projections: YearlyProjection[] // Future years with bear/base/bull scenarios
-  cagr: ScenarioCagr // CAGR for each scenario
-}
-
-// Bundled response from `ibkr_generate_projection_results`. The
-// projection inputs and the underlying fundamentals come back in a
-// single call so the UI doesn't need to parallel-fetch fundamentals
-// (which used to double the daily AV quota burn).
-export interface ProjectionResultsWithFundamentals {
-  fundamentals: FundamentalData
+import type {
+  ProjectionAssumptions,
+  ProjectionResults,
+  ProjectionUpdate,
+  ScenarioCagr,
+} from "./bindings"
+
+// Wire types generated from the Rust backend (`pnpm bindings`).
+export type {
+  AnalystEstimate,
+  AnalystEstimates,
+  CagrMetrics,
+  CurrentMetrics,
+  DataQualityWarning,
+  DiscountRate,
+  DiscountedValuation,
+  ExpectedValue,
+  FinancialProjection,
+  FundamentalData,
+  FxConversion,
+  GrowthAnalysis,
+  HistoricalCagr,
+  HistoricalFinancial,
+  OverriddenField,
+  PresentValue,
+  ProjectedSegment,
+  ProjectionAssumptions,
+  ProjectionResults,
+  ProjectionResultsWithFundamentals,
+  ProjectionTrace,
+  ProjectionUpdate,
+  Quote,
+  RevenueSegment,
+  RiskFreeSource,
+  ScenarioCagr,
+  ScenarioProjections,
+  ScenarioProjectionsWithFundamentals,
+  ScenarioTrace,
+  SegmentGrowth,
+  ShortInterest,
+  TraceStep,
+  TradingSession,
+  YearGrowth,
+  YearTrace,
+  YearlyProjection,
+} from "./bindings"
+
+// Stored projection run from `get_projection_history`, oldest first
+export interface ProjectionHistoryEntry {
+  id: number
+  symbol: string
+  generatedAt: number // unix seconds
+  assumptions: ProjectionAssumptions
   results: ProjectionResults
+  diff: ProjectionSnapshotDiff | null // vs the previous entry
 }
 
-// Complete scenario projections (Bear/Base/Bull) - DEPRECATED, use ProjectionResults
-export interface ScenarioProjections {
-  bear: FinancialProjection[]
-  base: FinancialProjection[]
-  bull: FinancialProjection[]
-  cagr: ScenarioCagr
+export interface ProjectionsUpdatedPayload {
+  updates: ProjectionUpdate[]
 }
 
-// Bundled equivalent of ScenarioProjections for the deprecated
-// `ibkr_generate_projections` Tauri command.
-export interface ScenarioProjectionsWithFundamentals {
-  fundamentals: FundamentalData
-  projections: ScenarioProjections
+export interface ProjectionSnapshotDiff {
+  assumptions: FieldChange[]
+  baseline: FieldChange[] // a `year` change means a new fiscal year rolled in
+  targets: TargetChange[] // final-year price targets that moved
 }
 
-export interface ScenarioCagr {
-  bear: CagrMetrics
-  base: CagrMetrics
-  bull: CagrMetrics
+export interface FieldChange {
+  field: string
+  before: number
+  after: number
 }
 
-// Historical financial data point
-export interface HistoricalFinancial {
-  year: number
-  revenue: number
-  netIncome: number
-  eps: number
+export interface TargetChange {
+  scenario: "bear" | "base" | "bull"
+  before: ScenarioTarget
+  after: ScenarioTarget
 }
 
-// Analyst estimate for a specific metric
-export interface AnalystEstimate {
+export interface ScenarioTarget {
   year: number
-  estimate: number
-}
-
-// Complete fundamental data for a security
-export interface FundamentalData {
-  symbol: string
-  historical: HistoricalFinancial[]
-  analystEstimates?: AnalystEstimates
-  currentMetrics: CurrentMetrics
+  sharePriceLow: number
+  sharePriceHigh: number
 }
 
-export interface AnalystEstimates {
-  revenue: AnalystEstimate[]
-  eps: AnalystEstimate[]
+// Response of `analyze_portfolio`: every long stock position projected
+// with the same assumptions, share-price CAGR weighted by market value.
+export interface PortfolioAnalysis {
+  account: string
+  positions: PositionAnalysis[] // largest market value first
+  expectedCagr: WeightedCagr | null // null when nothing could be projected
+  totalValue: number
+  analyzedValue: number
+  coverage: number // analyzedValue / totalValue
+  skipped: { symbol: string; reason: string }[] // options, shorts, ...
 }
 
-export interface CurrentMetrics {
-  price?: number
-  peRatio: number
-  sharesOutstanding: number // in millions
-  name?: string
-  exchange?: string
-  marketCap?: string
-  dividendYield?: number
+export interface PositionAnalysis {
+  symbol: string
+  quantity: number
+  marketValue: number
+  weight: number // 0..1 of analyzedValue; 0 for failed rows
+  cagr?: ScenarioCagr
+  error?: string
 }
 
-// Assumptions for generating projections
-export interface ProjectionAssumptions {
-  years: number // number of years to project (default 5)
-  bearRevenueGrowth: number // percentage (e.g., 20.0 for 20%)
-  baseRevenueGrowth: number // percentage
-  bullRevenueGrowth: number // percentage
-  bearMarginChange: number // percentage points per year (can be negative)
-  baseMarginChange: number // percentage points per year
-  bullMarginChange: number // percentage points per year
-  peLow: number // PE multiple low estimate (used when EPS > 0)
-  peHigh: number // PE multiple high estimate (used when EPS > 0)
-  psLow: number // Price-to-Sales low estimate (used when EPS < 0)
-  psHigh: number // Price-to-Sales high estimate (used when EPS < 0)
-  sharesGrowth: number // annual change in shares (negative for buybacks)
+export interface WeightedCagr {
+  bear: number
+  base: number
+  bull: number
 }
 
 export const defaultProjectionAssumptions: ProjectionAssumptions = {
@@ -134,15 +123,10 @@ export const defaultProjectionAssumptions: ProjectionAssumptions = {
   psLow: 3.0, // Conservative P/S for unprofitable companies
   psHigh: 8.0, // Optimistic P/S for high-growth companies
   sharesGrowth: 0.0,
+  bearProbability: 25.0,
+  baseProbability: 50.0,
+  bullProbability: 25.0,
 }
 
 export type ScenarioType = "bear" | "base" | "bull"
 
-export interface Quote {
-  symbol: string
-  lastPrice?: number
-  prevClose?: number
-  volume?: number
-  /** Unix epoch seconds when the snapshot completed. */
-  timestamp: number
-}
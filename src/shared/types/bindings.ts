@@ -0,0 +1,1771 @@
+// Generated by `pnpm bindings` from the Rust types (src-tauri/src/bindings). Do not edit.
+
+/** An IBKR account id with its alias from `config.accounts`. */
+export interface AccountInfo {
+  alias: string | null
+  id: string
+}
+
+export interface AccountSummary {
+  account: string
+  currency: string
+  tag: string
+  value: string
+}
+
+export type AdaptivePriority = "Urgent" | "Normal" | "Patient"
+
+/**
+ * Persisted alert row mirroring the `alerts` table. `payload` is the
+ * event-specific JSON body; the frontend reads `payload.symbol` to wire
+ * row clicks to the analysis tab.
+ */
+export interface Alert {
+  /**
+   * Phase 6 — alert-dive enrichment marker. `None` means the per-alert
+   * deep-dive agent hasn't reached this row yet; `Some(_)` means the
+   * dive completed (with or without writing a note — see
+   * `research_note_id`).
+   */
+  enriched_at?: string
+  fired_at: string
+  id: number
+  kind: AlertKind
+  payload: unknown
+  /**
+   * Phase 6 — research note authored by the alert-dive agent for this
+   * alert. `None` when not yet enriched, or when enrichment was
+   * skipped (e.g. budget exhausted).
+   */
+  research_note_id?: number
+  seen: boolean
+  setup_id: number
+}
+
+/**
+ * Phase 21 — kinds of `alerts` rows the tracker pipeline records. The
+ * four kinds map 1:1 to the events the frontend AlertFeed surfaces:
+ * detector hit (`Detected`), state-machine invalidation (`Invalidated`),
+ * target hit on a completed setup (`TargetHit`), and a thesis update
+ * from the LLM pipeline (`ThesisChanged`). Storage encodes each as a
+ * snake_case string in the `alerts.kind` column.
+ */
+export type AlertKind = "detected" | "invalidated" | "target_hit" | "thesis_changed"
+
+export interface AlgoParams {
+  end_time: string | null
+  /** VWAP only: cap on the share of market volume, `0.1` = 10%. */
+  max_participation: number | null
+  /** Adaptive only; IBKR's default is `Normal`. */
+  priority: AdaptivePriority | null
+  /** Window to work the order in; IBKR defaults to now → close. */
+  start_time: string | null
+  strategy: AlgoStrategy
+}
+
+export type AlgoStrategy = "Vwap" | "Twap" | "Adaptive"
+
+/** Analyst estimate for a specific metric */
+export interface AnalystEstimate {
+  estimate: number
+  year: number
+}
+
+export interface AnalystEstimates {
+  eps: AnalystEstimate[]
+  revenue: AnalystEstimate[]
+}
+
+export type AppEvent = {
+  data: {
+    connected: boolean
+    message: string
+  }
+  type: "ConnectionStatusChanged"
+} | {
+  data: {
+    error: string
+  }
+  type: "ConnectionError"
+} | {
+  data: {
+    latency_ms: number
+    p50_ms: number
+  }
+  type: "ConnectionDegraded"
+} | {
+  data: {
+    account_id: string
+    data: unknown
+  }
+  type: "AccountUpdate"
+} | {
+  data: {
+    accounts: string[]
+  }
+  type: "AccountsListChanged"
+} | {
+  data: {
+    account: string
+    daily_pnl: number
+    realized_pnl: number | null
+    unrealized_pnl: number | null
+  }
+  type: "DailyPnLUpdate"
+} | {
+  data: {
+    data: unknown
+    symbol: string
+  }
+  type: "MarketDataUpdate"
+} | {
+  data: {
+    symbol: string
+  }
+  type: "MarketDataSubscribed"
+} | {
+  data: {
+    symbol: string
+  }
+  type: "MarketDataUnsubscribed"
+} | {
+  data: {
+    tier: DataTier
+  }
+  type: "DataTierDetected"
+} | {
+  data: {
+    order_id: number
+    symbol: string
+  }
+  type: "OrderPlaced"
+} | {
+  data: {
+    filled_qty: number
+    order_id: number
+  }
+  type: "OrderFilled"
+} | {
+  data: {
+    order_id: number
+  }
+  type: "OrderCancelled"
+} | {
+  data: {
+    error: string
+    order_id: number | null
+  }
+  type: "OrderError"
+} | {
+  data: {
+    parent_order_id: number
+    qty: number
+    setup_id: number
+    symbol: string
+  }
+  type: "BracketPlaced"
+} | {
+  data: {
+    parent_order_id: number
+    setup_id: number
+    status: BracketStatus
+  }
+  type: "BracketStatusChanged"
+} | {
+  data: {
+    position: number
+    symbol: string
+  }
+  type: "PositionUpdate"
+} | {
+  type: "PositionsRefreshed"
+} | {
+  data: {
+    results: ScannerData[]
+  }
+  type: "ScannerUpdate"
+} | {
+  data: {
+    setup: Setup
+    thesis: string | null
+  }
+  type: "SetupDetected"
+} | {
+  data: {
+    setup_id: number
+    sizing: Sizing
+    symbol: string
+  }
+  type: "SetupSized"
+} | {
+  data: {
+    reason: string
+    setup_id: number
+    symbol: string
+  }
+  type: "SetupInvalidated"
+} | {
+  data: {
+    kind: string
+    reason: string
+    setup_id: number
+    strategy: string
+    symbol: string
+  }
+  type: "SetupSkipped"
+} | {
+  data: {
+    from: TrackerStatus
+    symbol: string
+    to: TrackerStatus
+  }
+  type: "TickerStatusChanged"
+} | {
+  data: {
+    date: string
+    ranked_count: number
+  }
+  type: "MorningPackReady"
+} | {
+  data: {
+    alert_id: number | null
+    note_id: number
+    setup_id: number | null
+    symbol: string
+  }
+  type: "ResearchNoteWritten"
+} | {
+  data: {
+    date: string
+    idea_count: number
+  }
+  type: "AgentMorningPackWritten"
+} | {
+  data: {
+    account: string
+    date: string
+    formula_version: string
+    prompt_version: number
+  }
+  type: "TradeReviewWritten"
+} | {
+  data: {
+    account: string
+    date: string
+    generation_id: number
+    n_setups: number
+    n_skip: number
+  }
+  type: "PlaybookWritten"
+} | {
+  data: {
+    alert_id: number
+    decision: string
+    note_id: number | null
+  }
+  type: "AlertDecisionRecorded"
+} | {
+  data: {
+    alert_id: number
+    research_note_id: number | null
+  }
+  type: "AlertEnriched"
+} | {
+  data: {
+    alert_id: number
+    reason: string
+  }
+  type: "AlertDiveSkipped"
+} | {
+  data: {
+    as_of_date: string
+    source: string
+    symbol: string
+  }
+  type: "FundamentalsManualWritten"
+} | {
+  data: {
+    outcome: TickerPrimingOutcome
+    symbol: string
+  }
+  type: "TickerPrimingDone"
+} | {
+  data: {
+    account: string
+    nlv_cents: number
+    open_position_count: number
+    snapshot_id: number
+    total_dollar_risk_cents: number
+  }
+  type: "PortfolioRiskChanged"
+} | {
+  data: {
+    regime: Regime
+    snapshot_id: number
+    /** One of `daily_close` | `intraday` | `force_recompute`. */
+    source: string
+  }
+  type: "RegimeChanged"
+} | {
+  data: {
+    account: string
+    auto_reset_at: string
+    cumulative_r: number
+    episode_id: number
+    trigger_kind: string
+  }
+  type: "TiltActivated"
+} | {
+  data: {
+    account: string
+    episode_id: number
+    /** One of `auto` | `manual_override`. */
+    release_kind: string
+  }
+  type: "TiltReleased"
+} | {
+  data: {
+    job: JobInfo
+  }
+  type: "JobProgress"
+} | {
+  data: {
+    id: string
+    ok: boolean
+    summary: string
+  }
+  type: "ScheduledJobFinished"
+} | {
+  data: {
+    bear_value: number
+    bull_value: number
+    price: number
+    symbol: string
+    zone: FairValueZone
+  }
+  type: "FairValueCrossed"
+} | {
+  data: {
+    buy_below: number
+    price: number
+    scenario: string
+    sell_above: number
+    symbol: string
+    zone: BandZone
+  }
+  type: "MarginOfSafetyEntered"
+} | {
+  data: {
+    account: string
+    contract: string
+    level: PlanLevel
+    level_price: number
+    price: number
+    thesis: string | null
+  }
+  type: "PositionLevelHit"
+} | {
+  data: {
+    account: string
+    action: string
+    contract: string
+    exit_ticket: boolean
+    price: number
+    result: string
+    stop_price: number
+  }
+  type: "StopBreached"
+} | {
+  data: {
+    updates: ProjectionUpdate[]
+  }
+  type: "ProjectionsUpdated"
+} | {
+  data: {
+    account: string
+    /** Projected cash (or buying power) after pending buys; negative. */
+    amount: number
+    currency: string
+    kind: CashWarningKind
+    message: string
+  }
+  type: "CashWarning"
+} | {
+  data: {
+    account: string
+    cushion: number
+    excess_liquidity: number
+    threshold_pct: number
+  }
+  type: "MarginCushionLow"
+} | {
+  data: {
+    account: string
+    cash_pct: number
+    idle_cash: number
+    suggestions: string[]
+    threshold_pct: number
+  }
+  type: "IdleCash"
+} | {
+  data: {
+    action: CorporateAction
+    adjusted: TableAdjustment[]
+    source: string
+  }
+  type: "CorporateActionApplied"
+} | {
+  data: {
+    account: string
+    dte: number
+    ex_dividend_date: string | null
+    expiry: string
+    local_symbol: string
+    moneyness_pct: number
+    risk: AssignmentRisk
+    symbol: string
+  }
+  type: "OptionAssignmentRisk"
+} | {
+  data: {
+    order_id: number
+    price: number
+    quantity: number
+    side: string
+    symbol: string
+  }
+  type: "SimOrderFilled"
+} | {
+  data: {
+    action: string
+    detail: string | null
+    name: string
+    outcome: string
+    rule_id: number
+    symbol: string
+  }
+  type: "RuleTriggered"
+} | {
+  data: {
+    account: string
+    /** Unix seconds. */
+    asOf: number
+    delta: number
+    gamma: number
+    positions: PositionGreeks[]
+    theta: number
+    /** Local symbols of option positions without model greeks. */
+    unpriced: string[]
+    vega: number
+  }
+  type: "PortfolioGreeksUpdate"
+} | {
+  data: {
+    endpoint: string
+    remaining: number
+  }
+  type: "RateLimitWarning"
+} | {
+  data: {
+    error: string
+  }
+  type: "SystemError"
+}
+
+export type AssignmentRisk = "early_exercise" | "at_expiry"
+
+export type BandZone = "hold" | "buy" | "sell"
+
+export type BarSize = "Sec1" | "Sec5" | "Sec15" | "Sec30" | "Min1" | "Min2" | "Min3" | "Min5" | "Min15" | "Min20" | "Min30" | "Hour1" | "Day1"
+
+/**
+ * IBKR ids returned by `place_bracket`. Order of `target_order_ids`
+ * matches the input `target_rungs` 1:1 so the OrderTicket service
+ * can join back to its `TargetSpec` ladder.
+ */
+export interface BracketReceipt {
+  parent_order_id: number
+  stop_order_id: number
+  target_order_ids: number[]
+}
+
+/**
+ * Phase 3 — request shape for `IbkrClient::place_bracket`. Wraps the
+ * parent + stop + per-target rungs the OrderTicket service hands to
+ * the IBKR adapter. `entry` is always a LIMIT (the trader picks a
+ * price; setups never fire MKT in the bracket path); `stop` is a
+ * STOP order; `targets` are LIMITs whose qty sums to `qty`.
+ */
+export interface BracketRequest {
+  /**
+   * Entry side. Long brackets buy → sell; short brackets sell →
+   * buy. The stop + targets always use the *opposite* action.
+   */
+  entry_action: OrderAction
+  entry_limit_price: number
+  qty: number
+  stop_price: number
+  symbol: string
+  /**
+   * One LIMIT per rung. `(price, qty)`. Sum of qty must equal the
+   * parent qty; the placer trusts this and doesn't re-check.
+   */
+  target_rungs: ([number, number])[]
+}
+
+/**
+ * Lifecycle of a placed bracket. Updated by the post-fill reconciler
+ * (P3 only writes `Open`; partial / stopped / canceled / filled flips
+ * happen via `order_ticket_cancel_bracket` and the future fill-status
+ * stream). Stored as `as_str()` in `bracket_groups.last_status`.
+ */
+export type BracketStatus = "open" | "partial" | "filled" | "stopped" | "canceled"
+
+/**
+ * Breadth axis. Computed from "% of the SP500 universe trading above
+ * its 50-day MA". Defaults to `Mixed` when the breadth proxy can't
+ * be computed (< 80% fresh-bar coverage of the universe).
+ */
+export type BreadthAxis = "healthy" | "mixed" | "narrow"
+
+/** CAGR (Compound Annual Growth Rate) calculations */
+export interface CagrMetrics {
+  revenue: number
+  sharePrice: number
+}
+
+export type CashWarningKind = "negative_cash" | "margin_borrow" | "buying_power_exceeded"
+
+export type ComboKind = "vertical" | "calendar" | "diagonal"
+
+/**
+ * One option leg of a combo. `action` is the leg's side when the
+ * combo is bought; selling the combo reverses every leg.
+ */
+export interface ComboLeg {
+  action: OrderAction
+  option: OptionContract
+  ratio: number
+}
+
+/**
+ * Two-leg option spread on one underlying, sent to IBKR as a `BAG`
+ * contract so both legs fill together.
+ */
+export interface ComboOrderRequest {
+  /**
+   * `Buy` pays `price` (net debit) for the legs as given; `Sell`
+   * collects it (net credit) with every leg reversed.
+   */
+  action: OrderAction
+  legs: ComboLeg[]
+  /** `Market` or `Limit`. */
+  order_type: OrderType
+  /** Net price per spread, per share of underlying. */
+  price: number | null
+  /** Number of spreads. */
+  quantity: number
+  /** Underlying; every leg must be on it. */
+  symbol: string
+}
+
+/**
+ * What a validated command rejects with. Serialized with a `kind` tag:
+ * `{ kind: "invalid", fields }` or `{ kind: "failed", message }`.
+ */
+export type CommandError = {
+  fields: FieldError[]
+  kind: "invalid"
+} | {
+  kind: "failed"
+  message: string
+}
+
+export interface ConnectionConfig {
+  client_id: number
+  host: string
+  market_data_type: MarketDataType
+  port: number
+}
+
+export interface ConnectionStatus {
+  client_id: number
+  connected: boolean
+  server_time: string | null
+}
+
+export interface ContractDetails {
+  contract_id: number
+  currency: string
+  exchange: string
+  local_symbol: string
+  min_tick: number
+  multiplier: string
+  price_magnifier: number
+  primary_exchange: string
+  sec_type: SecurityType
+  symbol: string
+  trading_class: string
+}
+
+/**
+ * Explicit venue for a stock contract. Unset fields keep the defaults
+ * (`SMART` routing, `USD`, no primary exchange), which are wrong for
+ * dual-listed and non-US symbols.
+ */
+export interface ContractRoute {
+  currency: string | null
+  exchange: string | null
+  primary_exchange: string | null
+}
+
+/**
+ * Half-Kelly-ish per-trade risk by conviction grade. A continuous
+ * `conviction_signal: f64` from the detector maps to one of these
+ * via [`ConvictionGrade::from_signal`]; the LLM thesis (P17) can
+ * later override on a per-setup basis but P1 sizes from the
+ * detector signal alone.
+ */
+export type ConvictionGrade = "A" | "B" | "C"
+
+/** Tagged by `kind`; dates are the first session after the action. */
+export type CorporateAction = {
+  effective_date: string
+  kind: "split"
+  ratio: number
+  symbol: string
+} | {
+  effective_date: string
+  from: string
+  kind: "symbol_change"
+  to: string
+} | {
+  cost_fraction: number
+  effective_date: string
+  kind: "spin_off"
+  new_symbol: string
+  ratio: number
+  symbol: string
+}
+
+/**
+ * Cross-sectional correlation axis. 20-day rolling avg pairwise
+ * correlation across the SP500 universe; bucketed Low under 0.5 and
+ * High at-or-above 0.5. Mid-band collapsed to `Mixed` so the bucket
+ * count stays at three, consistent with the other axes.
+ */
+export type CorrAxis = "low" | "mixed" | "high"
+
+export interface CurrentMetrics {
+  dividendYield?: number
+  exchange?: string
+  marketCap?: string
+  name?: string
+  peRatio: number
+  /**
+   * Optional because Alpha Vantage's OVERVIEW endpoint does not
+   * return a current price. Live price comes from the separate
+   * `Quote` path. Kept on the type so existing callers (e.g.
+   * projections, mock fixtures) can pass an explicit value when
+   * they have one.
+   */
+  price?: number
+  sharesOutstanding: number
+  /**
+   * Latest exchange-reported short interest, laid over by
+   * `services::short_interest`; never part of a provider payload.
+   */
+  shortInterest?: ShortInterest
+}
+
+export type DataQualityCode = "revenue_jump" | "negative_shares_outstanding" | "missing_years"
+
+/**
+ * `Error` makes projections refuse the record; `Warning` is shown
+ * alongside the numbers.
+ */
+export type DataQualitySeverity = "warning" | "error"
+
+/** One anomaly in a [`FundamentalData`] record. */
+export interface DataQualityWarning {
+  code: DataQualityCode
+  message: string
+  severity: DataQualitySeverity
+  /** Fiscal year the anomaly points at, when it is year-specific. */
+  year?: number
+}
+
+/**
+ * Empirically detected market-data capability for the active IBKR
+ * connection. Probed at connect time by `IbkrClient::probe_data_tier`
+ * and surfaced through `IbkrState.data_tier` + `AppEvent::DataTierDetected`.
+ *
+ * Distinct from `MarketDataType` (the *configured* mode the client
+ * requests): a paper account configured as `Live` may still only get
+ * `Delayed` ticks, and that fact only shows up in the data stream.
+ */
+export type DataTier = "unknown" | "delayed" | "real_time"
+
+export type Direction = "long" | "short"
+
+export interface DiscountRate {
+  /** Date of the Treasury reading when `source` is `treasury10y`. */
+  asOf: string | null
+  /** `risk_free_pct + equity_risk_premium_pct`. */
+  discountRatePct: number
+  equityRiskPremiumPct: number
+  riskFreePct: number
+  source: RiskFreeSource
+}
+
+export interface DiscountedValuation {
+  currentPrice: number | null
+  discountRate: DiscountRate
+  years: PresentValue[]
+}
+
+export type ExecutionSide = "bought" | "sold"
+
+/**
+ * Scenario outcomes weighted by `ProjectionAssumptions`'s
+ * `*_probability`.
+ */
+export interface ExpectedValue {
+  /** Σ probability × scenario CAGR. */
+  cagr: CagrMetrics
+  /** Σ probability × midpoint of the scenario's price range. */
+  targetPrice: number
+  /** Final projected year the target is for. */
+  year: number
+}
+
+export type FairValueZone = "below_bear" | "within" | "above_bull"
+
+export interface FieldError {
+  field: string
+  message: string
+}
+
+/** Financial projection for a single year */
+export interface FinancialProjection {
+  analystEpsEstimate?: number
+  eps: number
+  netIncome: number
+  netIncomeGrowth: number | null
+  netIncomeMargins: number
+  peHighEst: number
+  peLowEst: number
+  psHighEst?: number
+  psLowEst?: number
+  revenue: number
+  revenueGrowth: number
+  /**
+   * Segment revenues `revenue` rolled up from, when the projection
+   * ran by segment.
+   */
+  segments?: ProjectedSegment[]
+  sharePriceHigh: number
+  sharePriceLow: number
+  valuationMethod: string
+  year: number
+}
+
+/** Complete fundamental data for a security */
+export interface FundamentalData {
+  analystEstimates: AnalystEstimates | null
+  /**
+   * ISO 4217 code of every monetary figure in the record; `None`
+   * means USD (pre-currency records, manual rows that omit it).
+   */
+  currency?: string
+  currentMetrics: CurrentMetrics
+  /**
+   * Anomalies found by `services::fundamentals_quality::validate`.
+   * Empty (and omitted) when the inputs look sane or were never
+   * validated.
+   */
+  dataQuality?: DataQualityWarning[]
+  /**
+   * Set when `services::fx_service::convert` moved the record out of
+   * its reporting currency.
+   */
+  fxConversion?: FxConversion
+  /**
+   * Growth decomposition of `historical`, filled by
+   * `services::fundamentals_growth::annotate`.
+   */
+  growth?: GrowthAnalysis
+  historical: HistoricalFinancial[]
+  /**
+   * Fields replaced by an operator override (see
+   * `services::fundamentals_overrides`). Empty when the record is
+   * exactly what the provider returned.
+   */
+  overrides?: OverriddenField[]
+  /**
+   * Revenue by reported business segment, from a manual record or
+   * an override. Empty when none was entered.
+   */
+  segments?: RevenueSegment[]
+  symbol: string
+}
+
+export interface FxConversion {
+  /** Reporting currency the provider returned. */
+  from: string
+  /** Units of `FundamentalData::currency` per unit of `from`. */
+  rate: number
+}
+
+/**
+ * Year-over-year growth, margin trend and trailing CAGRs derived from
+ * the historical series. All figures are percentages (35.0 = 35%);
+ * `None` where the math is undefined (no prior year, a non-positive
+ * base, a gap in the series).
+ */
+export interface GrowthAnalysis {
+  /** Trailing CAGRs ending at the latest year, one per window. */
+  cagr: HistoricalCagr[]
+  /** One row per historical year, ascending. */
+  years: YearGrowth[]
+}
+
+export interface HistoricalBar {
+  close: number
+  count: number
+  high: number
+  low: number
+  open: number
+  time: string
+  volume: number
+  wap: number
+}
+
+export interface HistoricalCagr {
+  eps: number | null
+  netIncome: number | null
+  revenue: number | null
+  /** Window length in years (3 = latest year vs. three years before). */
+  years: number
+}
+
+export interface HistoricalDataRequest {
+  bar_size: BarSize
+  duration: string
+  end_date_time: string
+  symbol: string
+  use_rth: boolean
+  what_to_show: WhatToShow
+}
+
+/** Historical financial data point */
+export interface HistoricalFinancial {
+  eps: number
+  netIncome: number
+  revenue: number
+  /**
+   * Cumulative split factor `eps` was divided by to put it on today's
+   * share basis; `None` when the figure is as-reported.
+   */
+  splitAdjustment?: number
+  year: number
+}
+
+export interface IbkrExecution {
+  /**
+   * IBKR account number that booked the fill. Carried through from
+   * `ExecutionData.execution.account_number` so multi-account users
+   * can attribute fills correctly.
+   */
+  account: string
+  avg_price: number
+  /**
+   * Commission charged for this fill, as reported by IBKR's
+   * `CommissionReport`. `None` ↔ "not (yet) reported"; a literal `0.0`
+   * is real (free trade).
+   */
+  commission: number | null
+  /**
+   * The currency the commission is reported in. May differ from
+   * `currency` for some non-US instruments; identical to it for US
+   * equities and options in practice.
+   */
+  commission_currency: string | null
+  /**
+   * IBKR `secType` code: `STK`, `OPT`, `FUT`, etc. Mirrors the
+   * `Position.contract_type` field used by `get_positions`.
+   */
+  contract_type: string
+  /** The contract's currency (e.g. `"USD"`). */
+  currency: string | null
+  exec_id: string
+  exec_time: string
+  /**
+   * Option expiry as a `NaiveDate`, parsed from the SDK's
+   * `last_trade_date_or_contract_month`. `None` for non-options or
+   * when the SDK only reports `YYYYMM` (no day).
+   */
+  expiry: string | null
+  /**
+   * Option contract multiplier (typically `"100"` for equity options);
+   * `None` for non-options.
+   */
+  multiplier: string | null
+  order_id: number
+  qty: number
+  /**
+   * Realized P&L for the fill (closing legs only), as reported by
+   * IBKR. `None` for opening legs or when the report has not arrived.
+   */
+  realized_pnl: number | null
+  /** Option right normalised to `"C"` or `"P"`; `None` for non-options. */
+  right: string | null
+  side: ExecutionSide
+  /** Option strike price; `None` for non-options. */
+  strike: number | null
+  symbol: string
+}
+
+export interface JobInfo {
+  done: number
+  error?: string
+  finishedAt?: number
+  id: number
+  /** What the job does, e.g. `"portfolio_analysis"`. */
+  kind: string
+  /**
+   * Worker output, kept for completed and cancelled (partial) jobs.
+   * Omitted from `JobProgress` events.
+   */
+  result?: unknown
+  startedAt: number
+  status: JobStatus
+  /** Label of the step in progress (ticker, sheet name). */
+  step?: string
+  total: number
+}
+
+export type JobStatus = "running" | "completed" | "failed" | "cancelled"
+
+export interface MarketDataSnapshot {
+  ask_price: number | null
+  ask_size: number | null
+  bid_price: number | null
+  bid_size: number | null
+  close: number | null
+  high: number | null
+  last_price: number | null
+  /**
+   * Last trade of the regular session (generic tick 318). Outside
+   * regular hours `last_price` is an extended-hours trade; this is
+   * the price alerts and P&L should compare against.
+   */
+  last_rth_price: number | null
+  last_size: number | null
+  low: number | null
+  open: number | null
+  /**
+   * Trading session of the snapshot's venue when it completed;
+   * `None` for a venue without a known calendar.
+   */
+  session: TradingSession | null
+  symbol: string
+  timestamp: number
+  volume: number | null
+}
+
+/**
+ * IBKR market data type. Mirrors `ibapi::market_data::MarketDataType`.
+ *
+ * Defaults to `DelayedFrozen` so accounts without real-time subscriptions still
+ * receive ticks during RTH (delayed) and the last frozen quote outside RTH
+ * (weekends, after-hours) instead of silent 5s snapshot timeouts.
+ */
+export type MarketDataType = "live" | "frozen" | "delayed" | "delayed_frozen"
+
+/**
+ * Phase 7 — request shape for `IbkrClient::modify_stop_price`. Re-
+ * submits an existing stop child with a new `aux_price`. The
+ * `BracketReviser` builds these from a `BracketGroupRecord` plus
+ * the freshly-computed chandelier stop.
+ */
+export interface ModifyStopRequest {
+  /**
+   * Action of the *stop child*, not the parent: short brackets
+   * have a stop with action=BUY; long brackets stop with
+   * action=SELL.
+   */
+  action: OrderAction
+  new_stop_price: number
+  /**
+   * OCA group the stop child belongs to. Mirrors the format from
+   * `place_bracket` (`"br-{parent_id}"`); included so the modify
+   * keeps the OCA semantics (target fill auto-cancels stop).
+   */
+  oca_group: string
+  parent_id: number
+  qty: number
+  stop_order_id: number
+  symbol: string
+}
+
+export interface NewsItem {
+  overall_sentiment_label: string | null
+  overall_sentiment_score: number | null
+  source: string
+  summary: string
+  ticker_sentiment: TickerSentiment[]
+  time_published: string
+  title: string
+  url: string
+}
+
+export type NewsTone = "bullish" | "bearish" | "neutral"
+
+/**
+ * LLM-derived per-symbol news classification. Persisted in
+ * `news_cache.news_verdict_json` and consumed by the EP detector to
+ * disambiguate sentiment polarity.
+ */
+export interface NewsVerdict {
+  ep_worthy: boolean
+  parabolic_risk: boolean
+  summary: string
+  tone: NewsTone
+}
+
+/**
+ * A working order from IBKR's `reqAllOpenOrders`, so it includes
+ * orders entered in TWS or by other API clients. `remaining` is the
+ * unfilled quantity.
+ */
+export interface OpenOrder {
+  account: string
+  action: OrderAction
+  auxPrice: number | null
+  currency: string
+  limitPrice: number | null
+  /** Contract multiplier; 1 for stocks. */
+  multiplier: number
+  orderId: number
+  orderType: string
+  remaining: number
+  secType: string
+  status: string
+  symbol: string
+}
+
+/** Listed expirations and strikes for an underlying (SMART). */
+export interface OptionChain {
+  /** `YYYYMMDD`, ascending. */
+  expirations: string[]
+  multiplier: string
+  /** Ascending. */
+  strikes: number[]
+  symbol: string
+}
+
+/** One listed option, enough to resolve the contract on SMART. */
+export interface OptionContract {
+  /** `None` is USD. */
+  currency: string | null
+  /** `YYYYMMDD`. */
+  expiry: string
+  /** `None` lets IBKR pick (100 for standard equity options). */
+  multiplier: string | null
+  right: OptionRight
+  strike: number
+  symbol: string
+}
+
+/**
+ * TWS model greeks for one option contract (tick type
+ * `ModelOption` / `DelayedModelOption`). Per contract, per unit of
+ * underlying: multiply by quantity × multiplier for the position.
+ */
+export interface OptionGreeks {
+  delta: number | null
+  gamma: number | null
+  impliedVolatility: number | null
+  optionPrice: number | null
+  /** Per calendar day. */
+  theta: number | null
+  underlyingPrice: number | null
+  /** Per 1 vol point. */
+  vega: number | null
+}
+
+export type OptionRight = "C" | "P"
+
+export type OrderAction = "Buy" | "Sell"
+
+/**
+ * One entry of the order audit trail: what the adapter sent and what
+ * IBKR said back. Published on the sink wired by
+ * `IbkrClient::set_order_sink`; `services::order_audit` persists it.
+ */
+export type OrderAuditEvent = SentOrder | {
+  error: string
+  kind: "rejected"
+  order: SentOrder
+} | {
+  avg_fill_price: number
+  filled: number
+  kind: "status"
+  order_id: number
+  remaining: number
+  status: string
+  symbol: string
+} | {
+  exec_id: string
+  kind: "fill"
+  order_id: number
+  price: number
+  shares: number
+  /** `BOT` / `SLD`, as IBKR reports it. */
+  side: string
+  symbol: string
+  /** IBKR's execution timestamp string. */
+  time: string
+} | {
+  code: number
+  kind: "notice"
+  message: string
+  order_id: number
+  symbol: string
+}
+
+export interface OrderRequest {
+  action: OrderAction
+  /**
+   * Work the order through an IBKR algo instead of sending it to the
+   * book in one piece.
+   */
+  algo: AlgoParams | null
+  /**
+   * Trade this option on `symbol` instead of the stock. Quantity is
+   * in contracts; `route` is ignored.
+   */
+  option: OptionContract | null
+  order_type: OrderType
+  price: number | null
+  quantity: number
+  /**
+   * Exchange / primary exchange / currency, typically copied from the
+   * symbol's `ContractDetails`. `None` is SMART / USD.
+   */
+  route: ContractRoute | null
+  /**
+   * Hold the order until this time. Sent to IBKR straight away as a
+   * Good-After-Time order, so IBKR (not this app) holds it and it
+   * survives restarts on either side.
+   */
+  start_at: StartAt | null
+  symbol: string
+}
+
+export type OrderType = "Market" | "Limit" | "Stop" | "StopLimit" | "Midprice"
+
+/** One field in a [`FundamentalData`] record that an override replaced. */
+export interface OverriddenField {
+  /** `revenue` | `net_income` | `eps` | `shares_outstanding` | `price`. */
+  field: string
+  /** What the provider said, if it said anything. */
+  providerValue: number | null
+  value: number
+  /** Fiscal year for the per-year fields; `None` for current metrics. */
+  year?: number
+}
+
+export type PlanLevel = "target" | "stop"
+
+export interface Position {
+  account: string
+  average_cost: number
+  contract_type: string
+  currency: string
+  exchange: string
+  /**
+   * Option / future expiry. `YYYYMMDD` (last trading day) or `YYYYMM`
+   * (contract month). `None` for stocks. Sourced from
+   * `Contract::last_trade_date_or_contract_month`.
+   */
+  expiry?: string
+  local_symbol: string
+  market_price: number
+  market_value: number
+  /**
+   * Contract multiplier (e.g. `"100"` for standard equity options).
+   * `None` when IBKR didn't report one (typical for stocks).
+   */
+  multiplier?: string
+  position: number
+  realized_pnl: number
+  /**
+   * Option right — `"C"` (call) or `"P"` (put). `None` for
+   * non-options. Pass-through from IBKR (sometimes `CALL` / `PUT`).
+   */
+  right?: string
+  /** Option strike price. `None` for non-option instruments. */
+  strike?: number
+  symbol: string
+  unrealized_pnl: number
+}
+
+export interface PositionGreeks {
+  delta: number
+  gamma: number
+  localSymbol: string
+  /** Per contract, as TWS reports them. */
+  model: OptionGreeks
+  multiplier: number
+  quantity: number
+  symbol: string
+  theta: number
+  vega: number
+}
+
+/** One projected year's targets in today's dollars. */
+export interface PresentValue {
+  baseHigh: number
+  baseLow: number
+  bearLow: number
+  bullHigh: number
+  /**
+   * Annual return, percent, from the current price to the base-case
+   * midpoint. `None` without a price or a positive target.
+   */
+  impliedRatePct: number | null
+  year: number
+  yearsOut: number
+}
+
+/** One segment's revenue in a projected year. */
+export interface ProjectedSegment {
+  name: string
+  revenue: number
+  /** Percent over the segment's prior year. */
+  revenueGrowth: number
+}
+
+/** Assumptions for generating projections */
+export interface ProjectionAssumptions {
+  baseMarginChange: number
+  baseProbability: number
+  baseRevenueGrowth: number
+  bearMarginChange: number
+  /**
+   * Scenario probabilities in percent; each non-negative, together
+   * summing to 100.
+   */
+  bearProbability: number
+  bearRevenueGrowth: number
+  bullMarginChange: number
+  bullProbability: number
+  bullRevenueGrowth: number
+  peHigh: number
+  peLow: number
+  psHigh: number
+  psLow: number
+  /**
+   * Per-segment revenue growth. When set and the baseline year has
+   * segment revenue, revenue is projected segment by segment and
+   * summed; see `services::projection_service::segments`.
+   */
+  segmentGrowth?: SegmentGrowth[]
+  sharesGrowth: number
+  years: number
+}
+
+/** Complete projection results with baseline and forward projections */
+export interface ProjectionResults {
+  baseline: FinancialProjection
+  cagr: ScenarioCagr
+  /**
+   * Probability-weighted outcome; `None` with no projected years (and
+   * on snapshots stored before scenario weights existed).
+   */
+  expected?: ExpectedValue
+  projections: YearlyProjection[]
+}
+
+/**
+ * Bundled response for `ibkr_generate_projection_results`. Returning
+ * fundamentals + projection results in a single Tauri call lets the UI
+ * hook collapse to one request instead of fetching fundamentals twice
+ * (once for display, once internally for the projection inputs).
+ */
+export interface ProjectionResultsWithFundamentals {
+  fundamentals: FundamentalData
+  results: ProjectionResults
+  /** Projection template the assumptions came from, if any. */
+  template?: string
+  /** Per-year calculation trace, when asked for with `include_trace`. */
+  trace?: ProjectionTrace
+  /** Targets discounted to today; see `services::valuation`. */
+  valuation?: DiscountedValuation
+}
+
+export interface ProjectionTrace {
+  /** Bear, base, bull. */
+  scenarios: ScenarioTrace[]
+}
+
+/** One re-run ticker. */
+export interface ProjectionUpdate {
+  baselineYear: number
+  changePct: number | null
+  material: boolean
+  previousBaselineYear: number
+  /** Base-case midpoint of the final projected year. */
+  previousTarget: number | null
+  /** `projection_snapshots.id` of the new snapshot. */
+  snapshotId: number | null
+  symbol: string
+  target: number | null
+}
+
+/**
+ * A live, never-cached, UI-shaped quote. Sourced from
+ * `MarketDataSnapshot` via `QuoteService`. Distinct from
+ * `MarketDataSnapshot` because the UI only needs four fields and
+ * because future quote sources need not match the snapshot shape.
+ */
+export interface Quote {
+  /**
+   * Last traded price (regular or delayed, depending on TWS data
+   * permissions). `None` if no last tick was received before the
+   * snapshot end.
+   */
+  lastPrice: number | null
+  /**
+   * Last regular-session trade; differs from `last_price` in pre-
+   * and post-market.
+   */
+  lastRthPrice: number | null
+  /**
+   * Previous session's close. Used by the frontend to compute
+   * change and change-percent.
+   */
+  prevClose: number | null
+  /**
+   * Trading session when the snapshot completed; `None` for venues
+   * without a known calendar.
+   */
+  session: TradingSession | null
+  symbol: string
+  /** Unix epoch seconds when the snapshot completed. */
+  timestamp: number
+  /** Cumulative session volume. */
+  volume: number | null
+}
+
+/**
+ * One classification of the four-axis regime. Persisted to
+ * `regime_snapshots.regime_json` as the raw read; the gate-time
+ * "stable" view applies the 3-day persistence rule on top.
+ */
+export interface Regime {
+  breadth: BreadthAxis
+  corr: CorrAxis
+  trend: TrendAxis
+  vol: VolAxis
+}
+
+/** One reported business segment's revenue history. */
+export interface RevenueSegment {
+  name: string
+  revenue: SegmentRevenue[]
+}
+
+export type RiskFreeSource = "manual" | "treasury10y" | "default"
+
+export interface ScannerData {
+  contract: ContractDetails
+  leg: string
+  rank: number
+}
+
+export interface ScannerSubscription {
+  above_price: number | null
+  above_volume: number | null
+  below_price: number | null
+  /**
+   * Optional IBKR `industryLike` filter. When `Some`, the scan is
+   * constrained to issuers whose `industry` matches the value (e.g.
+   * `"Semiconductors"`). Threaded through to IBKR as a
+   * `scannerSubscriptionFilterOptions` `TagValue`.
+   */
+  industry_filter?: string
+  instrument: string
+  location_code: string
+  market_cap_above: number | null
+  market_cap_below: number | null
+  number_of_rows: number
+  scan_code: string
+}
+
+export interface ScenarioCagr {
+  base: CagrMetrics
+  bear: CagrMetrics
+  bull: CagrMetrics
+}
+
+/** Complete scenario projections (Bear/Base/Bull) - DEPRECATED, use ProjectionResults */
+export interface ScenarioProjections {
+  base: FinancialProjection[]
+  bear: FinancialProjection[]
+  bull: FinancialProjection[]
+  cagr: ScenarioCagr
+}
+
+/** Same idea for the deprecated `ibkr_generate_projections` command. */
+export interface ScenarioProjectionsWithFundamentals {
+  fundamentals: FundamentalData
+  projections: ScenarioProjections
+}
+
+export interface ScenarioTrace {
+  inputs: TraceStep[]
+  /** `bear`, `base` or `bull`. */
+  scenario: string
+  years: YearTrace[]
+}
+
+export type SecurityType = "Stock" | "Option" | "Future" | "Forex" | "Combo" | "Warrant" | "Bond" | "Commodity" | "News" | "Fund"
+
+/**
+ * Revenue growth for one segment, in percent per year like the
+ * scenario-wide `*_revenue_growth`.
+ */
+export interface SegmentGrowth {
+  baseRevenueGrowth: number
+  bearRevenueGrowth: number
+  bullRevenueGrowth: number
+  /** Matches `RevenueSegment::name`, ignoring case. */
+  segment: string
+}
+
+export interface SegmentRevenue {
+  /** Billions, like `HistoricalFinancial::revenue`. */
+  revenue: number
+  year: number
+}
+
+/**
+ * The order fields the adapter actually handed to ibapi, as recorded
+ * in the audit trail. Mirrors the subset of `ibapi::orders::Order`
+ * our placement paths set.
+ */
+export interface SentOrder {
+  /** `BUY` / `SELL`. */
+  action: string
+  algo_params: ([string, string])[]
+  /** IBKR algo name (`Vwap`, `Twap`, ...) and its tag/value params. */
+  algo_strategy: string | null
+  aux_price: number | null
+  /** IBKR `goodAfterTime` as sent; `None` for an immediate order. */
+  good_after_time: string | null
+  limit_price: number | null
+  order_id: number
+  /** IBKR order-type code (`MKT`, `LMT`, `STP`, ...). */
+  order_type: string
+  /** `0` for a standalone order. */
+  parent_id: number
+  quantity: number
+  symbol: string
+  transmit: boolean
+}
+
+/**
+ * Persisted strategy setup row, mirroring the `setups` table. The
+ * `direction` and `targets` types are owned by the strategies module
+ * so the persistence layer and the detector framework agree on a
+ * single representation; Phase 10 introduces this shared shape.
+ */
+export interface Setup {
+  archived_at: string | null
+  detected_at: string
+  direction: Direction
+  /**
+   * Phase 8 — concentration-gate `warn` annotation. Short tag like
+   * `"sector_80pct"`. `None` when the setup passed cleanly. `block`
+   * outcomes land as skipped rows (not warnings), so a value here
+   * always means the trader can proceed without an explicit override
+   * — the SetupCard renders an inline banner instead of a modal.
+   */
+  gate_warning?: string
+  id: number
+  invalidated_at: string | null
+  invalidation_reason: string | null
+  /**
+   * Phase 10 — `param_vintages.vintage_id` of the locked param
+   * vintage that was active when this setup fired. `None` for
+   * pre-P10 rows AND for runs where the detector has no active
+   * vintage (e.g. fresh install before backfill). The eval panel
+   * joins on this column to attribute realized R back to the
+   * vintage that produced the setup.
+   */
+  param_vintage_id?: string
+  raw_signals: unknown
+  /**
+   * Quant-decisions Phase 1 — risk-engine sizing pinned at
+   * detection. `None` for pre-P1 rows (migration default) and for
+   * rows the engine refused to size before persistence; `Some` (
+   * possibly with `skipped_reason`) once the engine touched the row.
+   * Surfaced to the UI so each setup card shows qty / dollar-risk /
+   * R-per-share without a separate query.
+   */
+  sizing?: Sizing
+  /**
+   * Phase 5 — JSON describing the blackout window the setup tripped
+   * (`{ kind, start, end, reason, source, confidence }`). Always
+   * `Some` when `skipped_reason` is `Some`; `None` otherwise. Stored
+   * as `serde_json::Value` so the UI can read it without re-parsing.
+   */
+  skip_window_json?: unknown
+  /**
+   * Phase 5 — non-NULL when the runner gated this setup before sizing
+   * (e.g. earnings or FOMC blackout). The row is persisted so the
+   * trader can review skipped hits and override per-setup; the
+   * risk-engine and state-machine paths are skipped for these rows.
+   */
+  skipped_reason?: SkipReason
+  status: SetupStatus
+  stop_price: number
+  strategy: string
+  symbol: string
+  targets: TargetLevel[]
+  thesis: string | null
+  /**
+   * Phase 17 — full structured thesis JSON (markdown + conviction +
+   * invalidation_levels + risk_notes). Markdown also stays in `thesis`
+   * for legacy callers.
+   */
+  thesis_json: unknown
+  trigger_price: number
+}
+
+/**
+ * Lifecycle of a persisted strategy setup. Phase 10 only writes
+ * `Active` rows; `Invalidated` and `Completed` are reserved for the
+ * status state machine in Phase 12 and the LLM decay-watcher in
+ * Phase 18.
+ */
+export type SetupStatus = "active" | "invalidated" | "completed"
+
+/**
+ * Short position as of one FINRA settlement date (reported twice a
+ * month, published about a week later).
+ */
+export interface ShortInterest {
+  avgDailyVolume?: number
+  /** Shares short over average daily volume. */
+  daysToCover?: number
+  /** Percent of shares outstanding (no float figure is available). */
+  percentOfShares?: number
+  previousSharesShort?: number
+  /** `YYYY-MM-DD`. */
+  settlementDate: string
+  sharesShort: number
+}
+
+/**
+ * Order-size rules for one contract, from IBKR's contract details
+ * (`minSize` / `sizeIncrement`). A fractional-eligible US stock
+ * reports an increment below 1; everything else trades whole shares.
+ */
+export interface SizeRules {
+  minSize: number
+  sizeIncrement: number
+}
+
+/**
+ * Output of `compute_sizing`. Lands on the `setups` row via
+ * `TrackerService::update_setup_sizing`. Cent-encoded fields keep
+ * the SQLite schema integer-only.
+ */
+export interface Sizing {
+  /**
+   * True when `max_position_pct`, or the liquidity cap, clipped the
+   * qty below what the dollar-risk math would have produced. Useful diagnostic for
+   * "why did sizing pick fewer shares than I expected".
+   */
+  cap_applied: boolean
+  conviction_grade: ConvictionGrade
+  /**
+   * Multiplier applied beyond the per-grade `risk_pct`, in basis
+   * points. 10000 = 1.0×. Capped at `conviction_multiplier_cap *
+   * 10000` so the field can't carry an unvetted scale.
+   */
+  conviction_multiplier_bps: number
+  dollar_risk_cents: number
+  equity_at_decision_cents: number
+  /**
+   * Whole-share quantity. Zero only when `skipped_reason` is
+   * `Some(_)` — a non-skipped sizing always emits at least one
+   * share.
+   */
+  qty: number
+  r_per_share_cents: number
+  /** `Some(_)` when the engine refused to size; `qty` is then 0. */
+  skipped_reason: SizingSkippedReason | null
+  version: number
+}
+
+/**
+ * Why the engine refused to size this setup. Persisted on the
+ * row's `sizing_skipped_reason` so the UI can render an explicit
+ * "skipped: below_min_risk" badge instead of a phantom zero.
+ */
+export type SizingSkippedReason = "zero_r" | "below_min_risk" | "stale_snapshot" | "tilt_paused" | "invalid_price" | "illiquid"
+
+/**
+ * Phase 5 — short tag identifying *why* a setup was skipped (rather
+ * than fired). Persisted on the `setups` row as `skipped_reason` and
+ * surfaced to the UI so the trader can review skipped detector hits in
+ * the SkippedSetupsPanel. Distinct from
+ * [`crate::services::risk_engine::SizingSkippedReason`], which tracks
+ * risk-engine sizing failures on a fired setup.
+ */
+export type SkipReason = "earnings_blackout" | "fomc_blackout" | "concentration_blocked" | "off_regime"
+
+export type StartAt = {
+  kind: "market_open"
+} | {
+  at: string
+  kind: "time"
+}
+
+/** `breakout`, `episodic_pivot`, `parabolic_short` or a custom label. */
+export type StrategyTag = string
+
+/**
+ * Rows one action changed in one table. `workspaces.watchlist` counts
+ * the workspaces whose watchlist was renamed.
+ */
+export interface TableAdjustment {
+  rows: number
+  table: string
+}
+
+export interface TargetLevel {
+  label: string
+  price: number
+}
+
+/**
+ * Outcome payload of `AppEvent::TickerPrimingDone`. Granular per-step
+ * status lets the UI decide which panel to refresh without re-querying
+ * every read command.
+ */
+export interface TickerPrimingOutcome {
+  fundamentals: TickerPrimingStepStatus
+  news: TickerPrimingStepStatus
+  primed_at: string
+  projection: TickerPrimingStepStatus
+}
+
+/**
+ * Per-step status emitted by `TickerPrimerService::prime`. The chain
+ * runs fundamentals → projection → news; each step records one of these
+ * so the workspace event listener can decide which panels to refresh.
+ *
+ * `Skipped` is reserved for the idempotent fast-path (a re-prime within
+ * 24h short-circuits with every step set to `Skipped`); `NoData` means
+ * the upstream was healthy but had nothing for this symbol.
+ */
+export type TickerPrimingStepStatus = {
+  kind: "ok"
+} | {
+  kind: "no_data"
+} | {
+  detail: string
+  kind: "err"
+} | {
+  kind: "skipped"
+}
+
+export interface TickerSentiment {
+  relevance_score: number
+  ticker: string
+  ticker_sentiment_label: string
+  ticker_sentiment_score: number
+}
+
+/** One computed (or input) figure. */
+export interface TraceStep {
+  /** How the value was derived, with the operands filled in. */
+  formula: string
+  label: string
+  value: number
+}
+
+export interface TrackedTicker {
+  added_at: string
+  archived_at: string | null
+  cool_down_until: string | null
+  in_play_until: string | null
+  last_checked_at: string | null
+  /**
+   * Phase 1 of ticker-intake: unix-epoch stamp of the last successful
+   * `TickerPrimerService::prime` run. `None` means "never primed". The
+   * primer's 24h idempotency window reads this column to short-circuit
+   * repeat calls; `archive_ticker` clears it so a re-prime fires on
+   * unarchive. Cleared rows are eligible for the agent ticker-intake
+   * loop only after a fresh prime stamps the column again.
+   */
+  last_primed_at: string | null
+  notes: string | null
+  source: TrackerSource
+  source_meta: unknown
+  status: TrackerStatus
+  symbol: string
+  tags: StrategyTag[]
+}
+
+export type TrackerSource = "scanner" | "manual" | "news" | "auto_scanner" | "agent"
+
+export type TrackerStatus = "watching" | "in_play" | "setup_active" | "cool_down"
+
+/** Which part of the trading day an instant falls in. */
+export type TradingSession = "pre" | "regular" | "post" | "closed"
+
+/**
+ * SPY trend axis. Computed from SPY's relationship to its 50-day and
+ * 200-day moving averages plus the 50-DMA's slope.
+ */
+export type TrendAxis = "up" | "sideways" | "down"
+
+/**
+ * Volatility axis. Computed from VIX level (or fallback) bucketed
+ * against the master-committed thresholds. Falls back to `Normal` if
+ * the VIX series is missing.
+ */
+export type VolAxis = "low" | "normal" | "high"
+
+export type WhatToShow = "Trades" | "Midpoint" | "Bid" | "Ask" | "BidAsk" | "HistoricalVolatility" | "OptionImpliedVolatility"
+
+export interface YearGrowth {
+  epsGrowth: number | null
+  /** Change in `net_margin` from the prior year, in percentage points. */
+  marginChange: number | null
+  netIncomeGrowth: number | null
+  /** Net income over revenue. */
+  netMargin: number | null
+  revenueGrowth: number | null
+  year: number
+}
+
+export interface YearTrace {
+  steps: TraceStep[]
+  year: number
+}
+
+/** Projections for a single year with bear/base/bull scenarios */
+export interface YearlyProjection {
+  base: FinancialProjection
+  bear: FinancialProjection
+  bull: FinancialProjection
+  year: number
+}
@@ -286,6 +286,8 @@ export const quote: Quote = {
   lastPrice: 512.4,
   prevClose: 504.9,
   volume: 18_523_400,
+  lastRthPrice: null,
+  session: null,
   timestamp: Math.floor(Date.now() / 1000),
 }
 
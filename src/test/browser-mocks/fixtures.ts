@@ -24,7 +24,7 @@ type Fixture = (args: Record<string, unknown>) => unknown
 export const fixtures: Record<string, Fixture> = {
   // Connection
   ibkr_get_connection_status: () => status(),
-  ibkr_get_accounts: () => [ACCT],
+  ibkr_get_accounts: () => [{ id: ACCT, alias: null }],
   ibkr_get_data_tier: (): DataTier => "delayed",
   ibkr_connect: () => undefined,
   ibkr_disconnect: () => undefined,